@@ -3,8 +3,9 @@ use alloc::vec::Vec;
 use vm_core::{StackOutputs, stack::MIN_STACK_DEPTH};
 
 use super::super::{
-    Assertion, CLK_COL_IDX, DECODER_TRACE_OFFSET, EvaluationFrame, FMP_COL_IDX, Felt, FieldElement,
-    ONE, STACK_AUX_TRACE_OFFSET, STACK_TRACE_OFFSET, TransitionConstraintDegree, ZERO,
+    Assertion, CLK_COL_IDX, CTX_COL_IDX, DECODER_TRACE_OFFSET, EvaluationFrame, FMP_COL_IDX, Felt,
+    FieldElement, ONE, STACK_AUX_TRACE_OFFSET, STACK_TRACE_OFFSET, TransitionConstraintDegree,
+    ZERO,
 };
 use crate::{
     decoder::{IS_CALL_FLAG_COL_IDX, IS_SYSCALL_FLAG_COL_IDX, USER_OP_HELPERS_OFFSET},
@@ -283,6 +284,9 @@ trait EvaluationFrameExt<E: FieldElement> {
     /// Gets the current element of the fmp register in the trace.
     fn fmp(&self) -> E;
 
+    /// Gets the current element of the ctx register in the trace.
+    fn ctx(&self) -> E;
+
     /// Gets the next element of the fmp register in the trace.
     fn fmp_next(&self) -> E;
 
@@ -354,6 +358,11 @@ impl<E: FieldElement> EvaluationFrameExt<E> for &EvaluationFrame<E> {
         self.next()[FMP_COL_IDX]
     }
 
+    #[inline(always)]
+    fn ctx(&self) -> E {
+        self.current()[CTX_COL_IDX]
+    }
+
     #[inline(always)]
     fn user_op_helper(&self, index: usize) -> E {
         self.current()[DECODER_TRACE_OFFSET + USER_OP_HELPERS_OFFSET + index]
@@ -83,6 +83,10 @@ pub struct OpFlags<E: FieldElement> {
     overflow: E,
     top_binary: E,
     u32_rc_op: E,
+    u64_op: E,
+    u64add: E,
+    u64sub: E,
+    u32rotl: E,
 }
 
 #[allow(dead_code)]
@@ -228,6 +232,22 @@ impl<E: FieldElement> OpFlags<E> {
         // possible degree 6 operations flags.
         degree6_op_flags.iter_mut().for_each(|v| *v *= degree_6_flag);
 
+        // --- computation of the u64 operation flags ---------------------------------------------
+
+        // u64 operations and U32ROTL share the prefix `1000` with the u32 arithmetic operations,
+        // but have the least significant bit set. U64ADD, U64SUB and U32ROTL are distinguished
+        // from U32ADD, U32SUB and U32MUL only by this bit, and thus the flags of these u32
+        // operations are updated to exclude it. All of these operations shift the stack to the
+        // left by one element.
+        let f1000_odd = f1000 * frame.op_bit(0);
+        let u64_op = f1000_odd * not_2;
+        let u64add = degree6_op_flags[0] * frame.op_bit(0);
+        let u64sub = degree6_op_flags[1] * frame.op_bit(0);
+        let u32rotl = degree6_op_flags[2] * frame.op_bit(0);
+        degree6_op_flags[0] -= u64add;
+        degree6_op_flags[1] -= u64sub;
+        degree6_op_flags[2] -= u32rotl;
+
         // --- computation of the degree 5 operation flags ----------------------------------------
 
         // the degree 5 flag uses the first degree reduction column.
@@ -299,7 +319,8 @@ impl<E: FieldElement> OpFlags<E> {
             + degree4_op_flags[4] * binary_not(frame.is_loop_end()); // END
 
         no_shift_flags[1] = no_shift_flags[0] + no_change_1_flag;
-        no_shift_flags[2] = no_shift_flags[1] + degree7_op_flags[8] + f1000; // SWAP
+        // SWAP; u32 arithmetic operations
+        no_shift_flags[2] = no_shift_flags[1] + degree7_op_flags[8] + f1000 - f1000_odd;
         no_shift_flags[3] = no_shift_flags[2] + mov2_flag;
         no_shift_flags[4] = no_shift_flags[3]
             + mov3_flag
@@ -352,10 +373,11 @@ impl<E: FieldElement> OpFlags<E> {
             + degree5_op_flags[8] // DYN
             + degree5_op_flags[12]; // DYNCALL
 
-        left_shift_flags[2] = left_shift_flags[1] + left_change_1_flag;
+        left_shift_flags[2] = left_shift_flags[1] + left_change_1_flag + u32rotl;
         left_shift_flags[3] =
             left_shift_flags[2] + add3_madd_flag + degree7_op_flags[42] - degree7_op_flags[11];
-        left_shift_flags[4] = left_shift_flags[3] - degree7_op_flags[13];
+        // u64 operations
+        left_shift_flags[4] = left_shift_flags[3] - degree7_op_flags[13] + u64_op;
         left_shift_flags[5] = left_shift_flags[4] + degree7_op_flags[44] - degree7_op_flags[17];
         left_shift_flags[6] = left_shift_flags[5] - degree7_op_flags[19];
         left_shift_flags[7] = left_shift_flags[6] - degree7_op_flags[21];
@@ -378,7 +400,8 @@ impl<E: FieldElement> OpFlags<E> {
             + degree7_op_flags[22]
             + degree7_op_flags[26];
 
-        right_shift_flags[0] = f011 + degree5_op_flags[11] + movupn_flag; // degree 5: PUSH
+        // degree 5: PUSH; CTX
+        right_shift_flags[0] = f011 + degree5_op_flags[11] + degree5_op_flags[13] + movupn_flag;
 
         right_shift_flags[1] = right_shift_flags[0] + degree6_op_flags[4]; // degree 6: U32SPLIT
 
@@ -398,13 +421,15 @@ impl<E: FieldElement> OpFlags<E> {
         right_shift_flags[15] = right_shift_flags[8];
 
         // Flag if the stack has been shifted to the right.
-        let right_shift = f011 + degree5_op_flags[11] + degree6_op_flags[4]; // PUSH; U32SPLIT
+        // PUSH; CTX; U32SPLIT
+        let right_shift = f011 + degree5_op_flags[11] + degree5_op_flags[13] + degree6_op_flags[4];
 
         // Flag if the stack has been shifted to the left. Note that `DYNCALL` is not included in
         // this flag even if it shifts the stack to the left. See `Opflags::left_shift()` for more
         // information.
         let left_shift = f010
             + add3_madd_flag
+            + f1000_odd // U64ADD; U64SUB; U32ROTL
             + split_loop_flag
             + degree4_op_flags[5]
             + shift_left_on_end
@@ -445,6 +470,10 @@ impl<E: FieldElement> OpFlags<E> {
             overflow,
             top_binary,
             u32_rc_op,
+            u64_op,
+            u64add,
+            u64sub,
+            u32rotl,
         }
     }
 
@@ -820,23 +849,41 @@ impl<E: FieldElement> OpFlags<E> {
     // ------ Degree 6 u32 operations  ------------------------------------------------------------
 
     /// Operation Flag of U32ADD operation.
+    ///
+    /// Unlike the flags of other u32 operations, this flag has degree 7, as it is distinguished
+    /// from the flag of U64ADD by the least significant bit of the opcode.
     #[inline(always)]
     pub fn u32add(&self) -> E {
         self.degree6_op_flags[get_op_index(Operation::U32add.op_code())]
     }
 
     /// Operation Flag of U32SUB operation.
+    ///
+    /// Unlike the flags of other u32 operations, this flag has degree 7, as it is distinguished
+    /// from the flag of U64SUB by the least significant bit of the opcode.
     #[inline(always)]
     pub fn u32sub(&self) -> E {
         self.degree6_op_flags[get_op_index(Operation::U32sub.op_code())]
     }
 
     /// Operation Flag of U32MUL operation.
+    ///
+    /// Unlike the flags of other u32 operations, this flag has degree 7, as it is distinguished
+    /// from the flag of U32ROTL by the least significant bit of the opcode.
     #[inline(always)]
     pub fn u32mul(&self) -> E {
         self.degree6_op_flags[get_op_index(Operation::U32mul.op_code())]
     }
 
+    /// Operation Flag of U32ROTL operation.
+    ///
+    /// This flag has degree 7, as U32ROTL is distinguished from U32MUL by the least significant
+    /// bit of the opcode.
+    #[inline(always)]
+    pub fn u32rotl(&self) -> E {
+        self.u32rotl
+    }
+
     /// Operation Flag of U32DIV operation.
     #[inline(always)]
     pub fn u32div(&self) -> E {
@@ -867,6 +914,19 @@ impl<E: FieldElement> OpFlags<E> {
         self.degree6_op_flags[get_op_index(Operation::U32madd.op_code())]
     }
 
+    // ------ Degree 7 u64 operations  ------------------------------------------------------------
+
+    /// Operation Flag of U64ADD operation.
+    #[inline(always)]
+    pub fn u64add(&self) -> E {
+        self.u64add
+    }
+
+    /// Operation Flag of U64SUB operation.
+    #[inline(always)]
+    pub fn u64sub(&self) -> E {
+        self.u64sub
+    }
     // ------ Degree 6 non u32 operations  --------------------------------------------------------
 
     /// Operation Flag of HPERM operation.
@@ -937,6 +997,12 @@ impl<E: FieldElement> OpFlags<E> {
         self.degree5_op_flags[get_op_index(Operation::Dyncall.op_code())]
     }
 
+    /// Operation Flag of CTX operation.
+    #[inline(always)]
+    pub fn ctx(&self) -> E {
+        self.degree5_op_flags[get_op_index(Operation::Ctx.op_code())]
+    }
+
     /// Operation Flag of END operation.
     #[inline(always)]
     pub fn end(&self) -> E {
@@ -1020,6 +1086,13 @@ impl<E: FieldElement> OpFlags<E> {
         self.u32_rc_op
     }
 
+    /// Returns true when the stack operation is a u64 operation.
+    /// Degree: 6
+    #[inline(always)]
+    pub fn u64_op(&self) -> E {
+        self.u64_op
+    }
+
     /// Returns the flag if the stack overflow table contains values or not.
     /// Degree: 2
     #[inline(always)]
@@ -66,6 +66,7 @@ fn degree_6_op_flags() {
 
         // Asserts operation flag of degree 6 operation being executed to ONE.
         assert_eq!(op_flags.degree6_op_flags[idx_in_degree6_flags], ONE);
+        assert_eq!(op_flags.u64_op(), ZERO);
 
         // Assert operation flags not present in the trace to ZERO as the operation being executed
         // is of degree 6.
@@ -347,6 +348,37 @@ fn composite_flags() {
     assert_eq!(op_flags.control_flow(), ZERO);
     assert_eq!(op_flags.top_binary(), ONE);
 
+    let op = Operation::U32rotl;
+    // frame initialised with an op operation.
+    let frame = generate_evaluation_frame(op.op_code().into());
+
+    // All the operation flags are generated for the given frame.
+    let op_flags = OpFlags::new(&frame);
+
+    assert_eq!(op_flags.left_shift_at(1), ZERO);
+    for i in 2..16 {
+        assert_eq!(op_flags.left_shift_at(i), ONE);
+    }
+
+    for i in 0..16 {
+        assert_eq!(op_flags.no_shift_at(i), ZERO);
+    }
+
+    for i in 0..15 {
+        assert_eq!(op_flags.right_shift_at(i), ZERO);
+    }
+
+    assert_eq!(op_flags.right_shift(), ZERO);
+    assert_eq!(op_flags.left_shift(), ONE);
+    assert_eq!(op_flags.control_flow(), ZERO);
+    assert_eq!(op_flags.top_binary(), ZERO);
+    assert_eq!(op_flags.u32_rc_op(), ONE);
+    assert_eq!(op_flags.u64_op(), ZERO);
+
+    // U32ROTL is distinguished from U32MUL, which shares its upper opcode bits.
+    assert_eq!(op_flags.u32mul(), ZERO);
+    assert_eq!(op_flags.u32rotl(), ONE);
+
     // ------ Left shift 3 ---------------------------------------------------------------------
 
     let op = Operation::U32add3;
@@ -375,6 +407,46 @@ fn composite_flags() {
     assert_eq!(op_flags.control_flow(), ZERO);
     assert_eq!(op_flags.top_binary(), ZERO);
 
+    // ------ Left shift 4 ---------------------------------------------------------------------
+
+    let op_left_shift_4 = [Operation::U64add, Operation::U64sub];
+    for op in op_left_shift_4 {
+        // frame initialised with an op operation.
+        let frame = generate_evaluation_frame(op.op_code().into());
+
+        // All the operation flags are generated for the given frame.
+        let op_flags = OpFlags::new(&frame);
+
+        for i in 1..4 {
+            assert_eq!(op_flags.left_shift_at(i), ZERO);
+        }
+        for i in 4..16 {
+            assert_eq!(op_flags.left_shift_at(i), ONE);
+        }
+
+        for i in 0..16 {
+            assert_eq!(op_flags.no_shift_at(i), ZERO);
+        }
+
+        for i in 0..15 {
+            assert_eq!(op_flags.right_shift_at(i), ZERO);
+        }
+
+        assert_eq!(op_flags.right_shift(), ZERO);
+        assert_eq!(op_flags.left_shift(), ONE);
+        assert_eq!(op_flags.control_flow(), ZERO);
+        assert_eq!(op_flags.top_binary(), ZERO);
+        assert_eq!(op_flags.u32_rc_op(), ONE);
+        assert_eq!(op_flags.u64_op(), ONE);
+
+        // u64 operations are distinguished from u32 operations sharing their upper opcode bits.
+        assert_eq!(op_flags.u32add(), ZERO);
+        assert_eq!(op_flags.u32sub(), ZERO);
+        assert_eq!(op_flags.u64add(), (op == Operation::U64add).into());
+        assert_eq!(op_flags.u64sub(), (op == Operation::U64sub).into());
+        assert_eq!(op_flags.u32rotl(), ZERO);
+    }
+
     // ------ Left shift 5 ---------------------------------------------------------------------
 
     let op = Operation::MLoadW;
@@ -407,7 +479,7 @@ fn composite_flags() {
 
     // ------ Right shift 0 ---------------------------------------------------------------------
 
-    let op_no_change_0 = [Operation::MovUp2, Operation::Dup1, Operation::Push(ONE)];
+    let op_no_change_0 = [Operation::MovUp2, Operation::Dup1, Operation::Push(ONE), Operation::Ctx];
     for op in op_no_change_0 {
         // frame initialised with an op operation.
         let frame = generate_evaluation_frame(op.op_code().into());
@@ -10,7 +10,7 @@ pub mod tests;
 // ================================================================================================
 
 /// The number of unique transition constraints in the system operations.
-pub const NUM_CONSTRAINTS: usize = 3;
+pub const NUM_CONSTRAINTS: usize = 4;
 
 /// The degrees of constraints in the individual constraints of the system ops.
 pub const CONSTRAINT_DEGREES: [usize; NUM_CONSTRAINTS] = [
@@ -19,6 +19,7 @@ pub const CONSTRAINT_DEGREES: [usize; NUM_CONSTRAINTS] = [
     8, // constraint for ASSERT operation.
     8, // constraint for FMPADD operation.
     8, // constraint for FMPUPDATE operation.
+    6, // constraint for CTX operation, which is a degree 5 operation.
 ];
 
 // SYSTEM OPERATIONS TRANSITION CONSTRAINTS
@@ -54,6 +55,9 @@ pub fn enforce_constraints<E: FieldElement>(
     // enforces fmpupdate operation constraints.
     index += enforce_fmpupdate_constraints(frame, &mut result[index..], op_flag.fmpupdate());
 
+    // enforces ctx operation constraints.
+    index += enforce_ctx_constraints(frame, &mut result[index..], op_flag.ctx());
+
     index
 }
 
@@ -108,6 +112,23 @@ pub fn enforce_fmpupdate_constraints<E: FieldElement>(
     1
 }
 
+/// Enforces constraints of the CTX operation.
+///
+/// The CTX operation pushes the id of the current execution context to the stack. Therefore, the
+/// following constraints are enforced:
+/// - The first element in the next frame should be equal to the value of the ctx register in the
+///   current frame. s0' - ctx = 0.
+pub fn enforce_ctx_constraints<E: FieldElement>(
+    frame: &EvaluationFrame<E>,
+    result: &mut [E],
+    op_flag: E,
+) -> usize {
+    // Enforces the first element in the next frame is equal to the current context id.
+    result[0] = op_flag * are_equal(frame.stack_item_next(0), frame.ctx());
+
+    1
+}
+
 /// Enforces constraints of the CLK operation.
 ///
 /// The CLK operation pushes the current cycle number to the stack. Therefore, the following
@@ -2,7 +2,7 @@ use proptest::prelude::*;
 use vm_core::{Felt, ONE, Operation, ZERO};
 
 use super::{
-    super::{CLK_COL_IDX, FMP_COL_IDX, STACK_TRACE_OFFSET},
+    super::{CLK_COL_IDX, CTX_COL_IDX, FMP_COL_IDX, STACK_TRACE_OFFSET},
     EvaluationFrame, NUM_CONSTRAINTS, enforce_constraints,
 };
 use crate::stack::op_flags::{OpFlags, generate_evaluation_frame};
@@ -41,6 +41,16 @@ proptest! {
         let result = get_constraint_evaluation(frame);
         assert_eq!(expected, result);
     }
+
+    // -------------------------------- CTX test --------------------------------------------------
+
+    #[test]
+    fn test_ctx_operation(a in any::<u32>()) {
+        let expected = [ZERO; NUM_CONSTRAINTS];
+        let frame = get_ctx_test_frame(a);
+        let result = get_constraint_evaluation(frame);
+        assert_eq!(expected, result);
+    }
 }
 
 // UNIT TEST
@@ -122,3 +132,16 @@ pub fn get_clk_test_frame(a: u64) -> EvaluationFrame<Felt> {
 
     frame
 }
+
+/// Generates the correct current and next rows for the CTX operation and inputs and
+/// returns an EvaluationFrame for testing.
+pub fn get_ctx_test_frame(a: u32) -> EvaluationFrame<Felt> {
+    // frame initialised with a ctx operation using it's unique opcode.
+    let mut frame = generate_evaluation_frame(Operation::Ctx.op_code() as usize);
+
+    // Set the output. The top element in the next frame should be the current context id.
+    frame.current_mut()[CTX_COL_IDX] = Felt::from(a);
+    frame.next_mut()[STACK_TRACE_OFFSET] = frame.current()[CTX_COL_IDX];
+
+    frame
+}
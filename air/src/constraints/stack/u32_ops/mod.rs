@@ -13,7 +13,7 @@ pub mod tests;
 // ================================================================================================
 
 /// The number of unique transition constraints in stack manipulation operations.
-pub const NUM_CONSTRAINTS: usize = 13;
+pub const NUM_CONSTRAINTS: usize = 23;
 
 // The co-efficient of the most significant 16-bit limb in the helper register during aggregation.
 pub const TWO_48: Felt = Felt::new(2u64.pow(48));
@@ -33,14 +33,21 @@ pub const CONSTRAINT_DEGREES: [usize; NUM_CONSTRAINTS] = [
     // Given it is a degree 6 operation, 6 is added to all the individual constraints
     // degree.
     9, // constraint for element validity check
-    7, 7, // 2 constraints in the agg of lower and upper limbs
+    8, 7, // 2 constraints in the agg of lower and upper limbs
     7, // constraint for U32SPLIT operation
-    7, // constraint for U32ADD  operation
+    8, // constraint for U32ADD  operation
     7, // constraint for U32ADD3 operation
-    7, 8, // 2 constraints for U32SUB operation
-    8, // constraint for U32MUL operation
+    8, 9, // 2 constraints for U32SUB operation
+    9, // constraint for U32MUL operation
     8, // constraint for U32MADD operation
     8, 7, 7, // constraint for U32DIV operation
+    // The flag of U32ROTL has degree 7.
+    9, 8, // 2 constraints for U32ROTL operation
+    // The flag of all u64 operations has degree 6, while the flags of U64ADD and U64SUB have
+    // degree 7.
+    7, 7, 8, 8, // 4 constraints in the agg of limbs of u64 operations
+    8, 8, // 2 constraints for U64ADD operation
+    8, 8, // 2 constraints for U64SUB operation
 ];
 
 // U32 OPERATIONS TRANSITION CONSTRAINTS
@@ -95,6 +102,18 @@ pub fn enforce_constraints<E: FieldElement<BaseField = Felt>>(
     // Enforce constaints of the U32DIV operations.
     index += enforce_u32div_constraints(frame, &mut result[index..], op_flag.u32div(), &limbs);
 
+    // Enforce constaints of the U32ROTL operations.
+    index += enforce_u32rotl_constraints(frame, &mut result[index..], op_flag.u32rotl(), &limbs);
+
+    // Enforce general constraints of the u64 operations.
+    index += enforce_u64_limbs_agg(frame, &mut result[index..], op_flag.u64_op(), &limbs);
+
+    // Enforce constaints of the U64ADD operations.
+    index += enforce_u64add_constraints(frame, &mut result[index..], op_flag.u64add(), &limbs);
+
+    // Enforce constaints of the U64SUB operations.
+    index += enforce_u64sub_constraints(frame, &mut result[index..], op_flag.u64sub(), &limbs);
+
     index
 }
 
@@ -266,11 +285,102 @@ pub fn enforce_u32div_constraints<E: FieldElement<BaseField = Felt>>(
     3
 }
 
+/// Enforces constraints of the U32ROTL operation.
+///
+/// The U32ROTL operation rotates the bits of the second element in the current trace to the left
+/// by `b` bits, where the first element is `2^b`. Therefore, the following constraints are
+/// enforced:
+/// - The aggregation of all the limbs in the helper registers is equal to the product of the top
+///   two elements in the stack.
+/// - The first element in the next trace is equal to the sum of the aggregations of the lower and
+///   the upper limbs, i.e., the bits shifted out of the lower 32 bits of the product are wrapped
+///   around.
+pub fn enforce_u32rotl_constraints<E: FieldElement<BaseField = Felt>>(
+    frame: &EvaluationFrame<E>,
+    result: &mut [E],
+    op_flag: E,
+    limbs: &LimbCompositions<E>,
+) -> usize {
+    let a = frame.stack_item(0);
+    let b = frame.stack_item(1);
+    let c = frame.stack_item_next(0);
+
+    // Enforces the aggregation of all the limbs in the helper registers is the product of
+    // a and b.
+    result[0] = op_flag * are_equal(a * b, limbs.v64());
+
+    // Enforces that c is the sum of the lower and upper limbs of the product.
+    result[1] = op_flag * are_equal(c, limbs.v_lo() + limbs.v_hi());
+
+    2
+}
+
+/// Enforces constraints of the U64ADD operation. The U64ADD operation adds two 64-bit values at
+/// the top of the stack, each represented by its upper and lower 32-bit limbs. Therefore, the
+/// following constraints are enforced:
+/// - The sum of the lower limbs is equal to the lower limb of the result, plus the carry from the
+///   fifth helper register times 2^32.
+/// - The sum of the upper limbs and the carry is equal to the upper limb of the result, plus the
+///   overflow flag (the first element in the next row) times 2^32.
+pub fn enforce_u64add_constraints<E: FieldElement<BaseField = Felt>>(
+    frame: &EvaluationFrame<E>,
+    result: &mut [E],
+    op_flag: E,
+    limbs: &LimbCompositions<E>,
+) -> usize {
+    let b_hi = frame.stack_item(0);
+    let b_lo = frame.stack_item(1);
+    let a_hi = frame.stack_item(2);
+    let a_lo = frame.stack_item(3);
+    let carry = frame.user_op_helper(4);
+    let overflow = frame.stack_item_next(0);
+
+    // Enforces that the lower limb of the result and the carry are computed correctly.
+    result[0] = op_flag * are_equal(a_lo + b_lo, limbs.v_lo() + E::from(TWO_32) * carry);
+
+    // Enforces that the upper limb of the result and the overflow flag are computed correctly.
+    result[1] = op_flag * are_equal(a_hi + b_hi + carry, limbs.v_hi() + E::from(TWO_32) * overflow);
+
+    2
+}
+
+/// Enforces constraints of the U64SUB operation. The U64SUB operation subtracts the 64-bit value
+/// at the top of the stack from the 64-bit value below it, where each value is represented by its
+/// upper and lower 32-bit limbs. Therefore, the following constraints are enforced:
+/// - The lower limb of the minuend plus the borrow from the fifth helper register times 2^32 is
+///   equal to the sum of the lower limbs of the subtrahend and of the result.
+/// - The upper limb of the minuend plus the underflow flag (the first element in the next row)
+///   times 2^32 is equal to the sum of the upper limbs of the subtrahend and of the result, plus
+///   the borrow.
+pub fn enforce_u64sub_constraints<E: FieldElement<BaseField = Felt>>(
+    frame: &EvaluationFrame<E>,
+    result: &mut [E],
+    op_flag: E,
+    limbs: &LimbCompositions<E>,
+) -> usize {
+    let b_hi = frame.stack_item(0);
+    let b_lo = frame.stack_item(1);
+    let a_hi = frame.stack_item(2);
+    let a_lo = frame.stack_item(3);
+    let borrow = frame.user_op_helper(4);
+    let underflow = frame.stack_item_next(0);
+
+    // Enforces that the lower limb of the result and the borrow are computed correctly.
+    result[0] = op_flag * are_equal(a_lo + E::from(TWO_32) * borrow, b_lo + limbs.v_lo());
+
+    // Enforces that the upper limb of the result and the underflow flag are computed correctly.
+    result[1] =
+        op_flag * are_equal(a_hi + E::from(TWO_32) * underflow, b_hi + borrow + limbs.v_hi());
+
+    2
+}
+
 // GENERAL U32 OPERATION CONSTRAINTS
 // ===============================================================================================================
 
 /// The constraint checks if the top four element in the trace on aggregating forms a valid field
-/// element. no not. This constraint is applicable in `U32SPLIT`, `U32MADD` and `U32MUL`.
+/// element. no not. This constraint is applicable in `U32SPLIT`, `U32MADD`, `U32MUL` and
+/// `U32ROTL`.
 pub fn enforce_check_element_validity<E: FieldElement<BaseField = Felt>>(
     frame: &EvaluationFrame<E>,
     result: &mut [E],
@@ -279,8 +389,9 @@ pub fn enforce_check_element_validity<E: FieldElement<BaseField = Felt>>(
 ) -> usize {
     let m = frame.user_op_helper(4);
 
-    // composite flag for u32split, u32madd and u32mul.
-    let u32_split_mul_madd = op_flag.u32mul() + op_flag.u32split() + op_flag.u32madd();
+    // composite flag for u32split, u32madd, u32mul and u32rotl.
+    let u32_split_mul_madd =
+        op_flag.u32mul() + op_flag.u32rotl() + op_flag.u32split() + op_flag.u32madd();
 
     let v_hi_comp = E::ONE - m * (E::from(TWO_32) - E::ONE - limbs.v_hi());
 
@@ -302,8 +413,13 @@ pub fn enforce_limbs_agg<E: FieldElement<BaseField = Felt>>(
     op_flag: &OpFlags<E>,
     limbs: &LimbCompositions<E>,
 ) -> usize {
-    // flag of u32 arithmetic operation excluding the `U32DIV` operation.
-    let u32op_ex_div_assert2 = op_flag.u32_rc_op() - op_flag.u32div() - op_flag.u32assert2();
+    // flag of u32 arithmetic operation excluding the `U32DIV`, `U32ROTL` operations and the u64
+    // operations.
+    let u32op_ex_div_assert2 = op_flag.u32_rc_op()
+        - op_flag.u32div()
+        - op_flag.u32assert2()
+        - op_flag.u32rotl()
+        - op_flag.u64_op();
 
     let u32op_ex_div_assert2_sub = u32op_ex_div_assert2 - op_flag.u32sub();
 
@@ -318,6 +434,39 @@ pub fn enforce_limbs_agg<E: FieldElement<BaseField = Felt>>(
     2
 }
 
+/// Enforces constraints common to the u64 operations, which push the upper and lower 32-bit limbs
+/// of their result onto the stack below a binary flag. Therefore, the following constraints are
+/// enforced:
+/// - The aggregation of the two lower 16-bit limbs is equal to the third stack element in the next
+///   row.
+/// - The aggregation of the two upper 16-bit limbs is equal to the second stack element in the
+///   next row.
+/// - The carry (or borrow) between the lower and upper limbs, which is stored in the fifth helper
+///   register, is binary.
+/// - The first stack element in the next row is binary.
+pub fn enforce_u64_limbs_agg<E: FieldElement<BaseField = Felt>>(
+    frame: &EvaluationFrame<E>,
+    result: &mut [E],
+    op_flag: E,
+    limbs: &LimbCompositions<E>,
+) -> usize {
+    // Enforces that aggregation of the two lower 16-bits limbs is equal to the third stack element
+    // in the next row.
+    result[0] = op_flag * are_equal(frame.stack_item_next(2), limbs.v_lo());
+
+    // Enforces that aggregation of the two upper 16-bits limbs is equal to the second stack
+    // element in the next row.
+    result[1] = op_flag * are_equal(frame.stack_item_next(1), limbs.v_hi());
+
+    // Enforces that the carry between the limbs is binary.
+    result[2] = op_flag * is_binary(frame.user_op_helper(4));
+
+    // Enforces that the first stack element in the next row is binary.
+    result[3] = op_flag * is_binary(frame.stack_item_next(0));
+
+    4
+}
+
 // U32 HELPERS
 // ================================================================================================
 
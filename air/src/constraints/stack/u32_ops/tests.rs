@@ -88,6 +88,63 @@ proptest! {
         }
 
     }
+
+    // -------------------------------- U32ROTL test -------------------------------------------------
+
+    #[test]
+    fn test_u32rotl_operation(a in any::<u32>(), b in 0..=32u32) {
+        let expected = [ZERO; NUM_CONSTRAINTS];
+        let frame = get_u32rotl_test_frame(a, b);
+        let result = get_constraint_evaluation(frame);
+        assert_eq!(expected, result);
+    }
+
+    // -------------------------------- U64ADD test --------------------------------------------------
+
+    #[test]
+    fn test_u64add_operation(a in any::<u64>(), b in any::<u64>()) {
+        let expected = [ZERO; NUM_CONSTRAINTS];
+        let frame = get_u64add_test_frame(a, b);
+        let result = get_constraint_evaluation(frame);
+        assert_eq!(expected, result);
+    }
+
+    // -------------------------------- U64SUB test --------------------------------------------------
+
+    #[test]
+    fn test_u64sub_operation(a in any::<u64>(), b in any::<u64>()) {
+        let expected = [ZERO; NUM_CONSTRAINTS];
+        let frame = get_u64sub_test_frame(a, b);
+        let result = get_constraint_evaluation(frame);
+        assert_eq!(expected, result);
+    }
+}
+
+// UNIT TESTS
+// ================================================================================================
+
+#[test]
+fn test_u64add_operation_invalid_carry() {
+    // the lower limbs overflow, and thus the carry must be propagated to the upper limbs.
+    let a = u32::MAX as u64;
+    let b = 1;
+    let mut frame = get_u64add_test_frame(a, b);
+    assert_eq!([ZERO; NUM_CONSTRAINTS], get_constraint_evaluation(frame.clone()));
+
+    // dropping the carry from the upper limb of the result must violate the constraints.
+    frame.current_mut()[DECODER_TRACE_OFFSET + USER_OP_HELPERS_OFFSET + 2] = ZERO;
+    frame.next_mut()[STACK_TRACE_OFFSET + 1] = ZERO;
+    assert_ne!([ZERO; NUM_CONSTRAINTS], get_constraint_evaluation(frame));
+}
+
+#[test]
+fn test_u32rotl_operation_invalid_result() {
+    let mut frame = get_u32rotl_test_frame(0x8000_0001, 1);
+    assert_eq!([ZERO; NUM_CONSTRAINTS], get_constraint_evaluation(frame.clone()));
+
+    // dropping the bit shifted out of the lower limb must violate the constraints.
+    frame.next_mut()[STACK_TRACE_OFFSET] = Felt::new(2);
+    assert_ne!([ZERO; NUM_CONSTRAINTS], get_constraint_evaluation(frame));
 }
 
 // TEST HELPERS
@@ -312,6 +369,95 @@ pub fn get_u32div_test_frame(a: u32, b: u32) -> EvaluationFrame<Felt> {
     frame
 }
 
+/// Generates the correct current and next rows for the U32ROTL operation rotating `a` to the left
+/// by `b` bits and returns an EvaluationFrame for testing.
+pub fn get_u32rotl_test_frame(a: u32, b: u32) -> EvaluationFrame<Felt> {
+    // frame initialised with a u32rotl operation using it's unique opcode.
+    let mut frame = generate_evaluation_frame(Operation::U32rotl.op_code() as usize);
+
+    // product of the value and the power of two splitted into two 32-bit limbs.
+    let pow2 = Felt::new(1 << b);
+    let (hi, lo) = split_element(Felt::new(a.into()) * pow2);
+
+    // Set the output. The first element in the next frame should be the rotated value.
+    frame.current_mut()[STACK_TRACE_OFFSET] = pow2;
+    frame.current_mut()[STACK_TRACE_OFFSET + 1] = Felt::new(a.into());
+    frame.next_mut()[STACK_TRACE_OFFSET] = Felt::new(a.rotate_left(b).into());
+
+    let (t1, t0) = split_u32_into_u16(lo.as_int());
+    let (t3, t2) = split_u32_into_u16(hi.as_int());
+    let m = (Felt::from(u32::MAX) - hi).inv();
+
+    // set the helper registers in the decoder.
+    frame.current_mut()[DECODER_TRACE_OFFSET + USER_OP_HELPERS_OFFSET] = Felt::new(t0 as u64);
+    frame.current_mut()[DECODER_TRACE_OFFSET + USER_OP_HELPERS_OFFSET + 1] = Felt::new(t1 as u64);
+    frame.current_mut()[DECODER_TRACE_OFFSET + USER_OP_HELPERS_OFFSET + 2] = Felt::new(t2 as u64);
+    frame.current_mut()[DECODER_TRACE_OFFSET + USER_OP_HELPERS_OFFSET + 3] = Felt::new(t3 as u64);
+    frame.current_mut()[DECODER_TRACE_OFFSET + USER_OP_HELPERS_OFFSET + 4] = m;
+
+    frame
+}
+
+/// Generates the correct current and next rows for the U64ADD operation and inputs and
+/// returns an EvaluationFrame for testing.
+pub fn get_u64add_test_frame(a: u64, b: u64) -> EvaluationFrame<Felt> {
+    // frame initialised with a u64add operation using it's unique opcode.
+    let mut frame = generate_evaluation_frame(Operation::U64add.op_code() as usize);
+
+    let (result, over) = a.overflowing_add(b);
+    let carry = ((a as u32) as u64 + (b as u32) as u64) >> 32;
+
+    set_u64_test_frame(&mut frame, a, b, result, over, carry);
+    frame
+}
+
+/// Generates the correct current and next rows for the U64SUB operation and inputs and
+/// returns an EvaluationFrame for testing.
+pub fn get_u64sub_test_frame(a: u64, b: u64) -> EvaluationFrame<Felt> {
+    // frame initialised with a u64sub operation using it's unique opcode.
+    let mut frame = generate_evaluation_frame(Operation::U64sub.op_code() as usize);
+
+    let (result, under) = a.overflowing_sub(b);
+    let borrow = ((a as u32) < (b as u32)) as u64;
+
+    set_u64_test_frame(&mut frame, a, b, result, under, borrow);
+    frame
+}
+
+/// Sets the inputs and the outputs of a u64 operation on values `a` and `b` in the provided frame,
+/// as well as the helper registers in the decoder.
+fn set_u64_test_frame(
+    frame: &mut EvaluationFrame<Felt>,
+    a: u64,
+    b: u64,
+    result: u64,
+    flag: bool,
+    carry: u64,
+) {
+    let (a_hi, a_lo) = split_element(Felt::new(a));
+    let (b_hi, b_lo) = split_element(Felt::new(b));
+    let (c_hi, c_lo) = split_element(Felt::new(result));
+
+    // Set the inputs, i.e., [b_hi, b_lo, a_hi, a_lo], and the outputs, i.e., [flag, c_hi, c_lo].
+    frame.current_mut()[STACK_TRACE_OFFSET] = b_hi;
+    frame.current_mut()[STACK_TRACE_OFFSET + 1] = b_lo;
+    frame.current_mut()[STACK_TRACE_OFFSET + 2] = a_hi;
+    frame.current_mut()[STACK_TRACE_OFFSET + 3] = a_lo;
+    frame.next_mut()[STACK_TRACE_OFFSET] = Felt::new(flag.into());
+    frame.next_mut()[STACK_TRACE_OFFSET + 1] = c_hi;
+    frame.next_mut()[STACK_TRACE_OFFSET + 2] = c_lo;
+
+    let (t1, t0) = split_element_u32_into_u16(c_lo);
+    let (t3, t2) = split_element_u32_into_u16(c_hi);
+
+    // set the helper registers in the decoder.
+    frame.current_mut()[DECODER_TRACE_OFFSET + USER_OP_HELPERS_OFFSET] = t0;
+    frame.current_mut()[DECODER_TRACE_OFFSET + USER_OP_HELPERS_OFFSET + 1] = t1;
+    frame.current_mut()[DECODER_TRACE_OFFSET + USER_OP_HELPERS_OFFSET + 2] = t2;
+    frame.current_mut()[DECODER_TRACE_OFFSET + USER_OP_HELPERS_OFFSET + 3] = t3;
+    frame.current_mut()[DECODER_TRACE_OFFSET + USER_OP_HELPERS_OFFSET + 4] = Felt::new(carry);
+}
+
 /// Splits an element into two field elements containing 32-bit integer values
 pub fn split_element(value: Felt) -> (Felt, Felt) {
     let value = value.as_int();
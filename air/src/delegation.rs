@@ -0,0 +1,188 @@
+use alloc::vec::Vec;
+
+use vm_core::{
+    ProgramInfo, StackInputs, StackOutputs, Word,
+    crypto::hash::{Rpo256, RpoDigest},
+    utils::{
+        ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable, ToElements,
+    },
+};
+
+use super::ExecutionProof;
+
+// DELEGATED CLAIM
+// ================================================================================================
+
+/// A claim that a program was executed against the specified stack inputs, and produced the
+/// specified stack outputs.
+///
+/// Claims allow a program to consume the results of a delegated computation without verifying a
+/// proof of the computation inside the VM. Instead, the program commits to the claims it consumes,
+/// and the verifier checks each of these claims against an accompanying proof (see
+/// [ProofEnvelope]).
+///
+/// A program identifies a claim by its digest, computed as:
+///
+/// `hash(PROGRAM, hash(INPUTS, OUTPUTS))`
+///
+/// where `PROGRAM` is the commitment to the program of the claim (see
+/// [DelegatedClaim::program_commitment]), and `INPUTS` and `OUTPUTS` are the sequential hashes of
+/// the 16 stack inputs and outputs respectively, with the top of the stack first. The claims
+/// consumed by a program are committed to by folding their digests into an accumulator which
+/// starts as the empty word (see [claims_commitment]). The `std::sys` module of the standard
+/// library provides procedures for computing claim digests and accumulating claims.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DelegatedClaim {
+    program_info: ProgramInfo,
+    stack_inputs: StackInputs,
+    stack_outputs: StackOutputs,
+}
+
+impl DelegatedClaim {
+    /// Creates a new claim that the specified program produces `stack_outputs` when executed
+    /// against `stack_inputs`.
+    pub fn new(
+        program_info: ProgramInfo,
+        stack_inputs: StackInputs,
+        stack_outputs: StackOutputs,
+    ) -> Self {
+        Self {
+            program_info,
+            stack_inputs,
+            stack_outputs,
+        }
+    }
+
+    /// Returns the information about the program of this claim.
+    pub fn program_info(&self) -> &ProgramInfo {
+        &self.program_info
+    }
+
+    /// Returns the stack inputs of this claim.
+    pub fn stack_inputs(&self) -> &StackInputs {
+        &self.stack_inputs
+    }
+
+    /// Returns the stack outputs of this claim.
+    pub fn stack_outputs(&self) -> &StackOutputs {
+        &self.stack_outputs
+    }
+
+    /// Returns the commitment to the program of this claim, computed as the sequential hash of
+    /// the program hash and the hashes of the kernel procedures.
+    pub fn program_commitment(&self) -> RpoDigest {
+        Rpo256::hash_elements(&self.program_info.to_elements())
+    }
+
+    /// Returns the digest by which programs identify this claim.
+    pub fn digest(&self) -> RpoDigest {
+        let inputs = Rpo256::hash_elements(self.stack_inputs.as_slice());
+        let outputs = Rpo256::hash_elements(self.stack_outputs.as_slice());
+        Rpo256::merge(&[self.program_commitment(), Rpo256::merge(&[inputs, outputs])])
+    }
+}
+
+impl Serializable for DelegatedClaim {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.program_info.write_into(target);
+        self.stack_inputs.write_into(target);
+        self.stack_outputs.write_into(target);
+    }
+}
+
+impl Deserializable for DelegatedClaim {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let program_info = source.read()?;
+        let stack_inputs = source.read()?;
+        let stack_outputs = source.read()?;
+        Ok(Self {
+            program_info,
+            stack_inputs,
+            stack_outputs,
+        })
+    }
+}
+
+/// Returns the commitment to the provided claims, in the order in which they were consumed.
+///
+/// The commitment starts as the empty word, and the digest of each claim is folded into it as
+/// `COMMITMENT' = hash(COMMITMENT, CLAIM)`.
+pub fn claims_commitment<'a>(claims: impl IntoIterator<Item = &'a DelegatedClaim>) -> Word {
+    claims
+        .into_iter()
+        .fold(RpoDigest::default(), |commitment, claim| {
+            Rpo256::merge(&[commitment, claim.digest()])
+        })
+        .into()
+}
+
+// PROOF ENVELOPE
+// ================================================================================================
+
+/// A proof of correct execution of a program, together with proofs of the claims consumed by the
+/// program.
+///
+/// The program is expected to leave the commitment to the claims it consumed (see
+/// [claims_commitment]) in the word at the top of its output stack. The delegated proofs must be
+/// provided in the order in which the program consumed the claims.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofEnvelope {
+    proof: ExecutionProof,
+    delegated_proofs: Vec<(DelegatedClaim, ExecutionProof)>,
+}
+
+impl ProofEnvelope {
+    /// Creates a new envelope for the provided proof, without any delegated proofs.
+    pub fn new(proof: ExecutionProof) -> Self {
+        Self { proof, delegated_proofs: Vec::new() }
+    }
+
+    /// Returns this envelope with the provided proof of a claim consumed by the program appended
+    /// to its delegated proofs.
+    pub fn with_delegated_proof(mut self, claim: DelegatedClaim, proof: ExecutionProof) -> Self {
+        self.delegated_proofs.push((claim, proof));
+        self
+    }
+
+    /// Returns the proof of the execution of the program which consumed the claims.
+    pub fn proof(&self) -> &ExecutionProof {
+        &self.proof
+    }
+
+    /// Returns the claims consumed by the program together with their proofs.
+    pub fn delegated_proofs(&self) -> &[(DelegatedClaim, ExecutionProof)] {
+        &self.delegated_proofs
+    }
+
+    /// Returns the commitment to the claims in this envelope.
+    pub fn claims_commitment(&self) -> Word {
+        claims_commitment(self.delegated_proofs.iter().map(|(claim, _)| claim))
+    }
+
+    /// Returns the components of this envelope.
+    pub fn into_parts(self) -> (ExecutionProof, Vec<(DelegatedClaim, ExecutionProof)>) {
+        (self.proof, self.delegated_proofs)
+    }
+}
+
+impl Serializable for ProofEnvelope {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.proof.write_into(target);
+        target.write_usize(self.delegated_proofs.len());
+        for (claim, proof) in self.delegated_proofs.iter() {
+            claim.write_into(target);
+            proof.write_into(target);
+        }
+    }
+}
+
+impl Deserializable for ProofEnvelope {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let proof = source.read()?;
+        let num_delegated_proofs = source.read_usize()?;
+        let delegated_proofs = (0..num_delegated_proofs)
+            .map(|_| Ok((source.read()?, source.read()?)))
+            .collect::<Result<_, DeserializationError>>()?;
+        Ok(Self { proof, delegated_proofs })
+    }
+}
@@ -6,16 +6,19 @@ extern crate alloc;
 #[cfg(feature = "std")]
 extern crate std;
 
-use alloc::vec::Vec;
+use alloc::{sync::Arc, vec::Vec};
+use core::fmt;
 
 use vm_core::{
-    ExtensionOf, ONE, ProgramInfo, StackInputs, StackOutputs, ZERO,
+    ExecutionEnvironment, ExtensionOf, ONE, ProgramInfo, StackInputs, StackOutputs, ZERO,
+    crypto::hash::RpoDigest,
     utils::{ByteReader, ByteWriter, Deserializable, Serializable},
 };
 use winter_air::{
     Air, AirContext, Assertion, EvaluationFrame, ProofOptions as WinterProofOptions, TraceInfo,
     TransitionConstraintDegree,
 };
+use winter_prover::crypto::{RandomCoin, RandomCoinError};
 use winter_prover::matrix::ColMatrix;
 
 mod constraints;
@@ -26,16 +29,24 @@ pub mod trace;
 pub use trace::rows::RowIndex;
 use trace::*;
 
+mod delegation;
 mod errors;
 mod options;
 mod proof;
+mod randomizer;
+mod report;
+mod security;
 
 mod utils;
 // RE-EXPORTS
 // ================================================================================================
+pub use delegation::{DelegatedClaim, ProofEnvelope, claims_commitment};
 pub use errors::ExecutionOptionsError;
-pub use options::{ExecutionOptions, ProvingOptions};
+pub use options::{CancellationToken, ExecutionOptions, OpDispatch, ProvingOptions};
 pub use proof::{ExecutionProof, HashFunction};
+pub use randomizer::TraceRandomizer;
+pub use report::{ComponentConstraints, ConstraintReport};
+pub use security::{SecurityEstimate, SecurityPreset};
 use utils::TransitionConstraintRange;
 pub use vm_core::{
     Felt, FieldElement, StarkField,
@@ -52,6 +63,7 @@ pub struct ProcessorAir {
     stack_inputs: StackInputs,
     stack_outputs: StackOutputs,
     constraint_ranges: TransitionConstraintRange,
+    trace_randomizer: Option<Arc<dyn TraceRandomizer>>,
 }
 
 impl ProcessorAir {
@@ -59,6 +71,12 @@ impl ProcessorAir {
     pub fn last_step(&self) -> usize {
         self.trace_length() - self.context().num_transition_exemptions()
     }
+
+    /// Returns a report of the number and degrees of the constraints of the AIR, broken down by
+    /// the component of the VM they apply to.
+    pub fn constraint_report() -> ConstraintReport {
+        ConstraintReport::new()
+    }
 }
 
 impl Air for ProcessorAir {
@@ -117,7 +135,46 @@ impl Air for ProcessorAir {
             stack_inputs: pub_inputs.stack_inputs,
             stack_outputs: pub_inputs.stack_outputs,
             constraint_ranges,
+            trace_randomizer: pub_inputs.trace_randomizer,
+        }
+    }
+
+    // AUXILIARY TRACE RANDOMNESS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the random elements used to build the auxiliary trace.
+    ///
+    /// The elements are drawn from the provided public coin, and then passed through the
+    /// [TraceRandomizer] of the public inputs, if there is one.
+    ///
+    /// # Panics
+    /// Panics if the randomizer does not return as many elements as it was given.
+    fn get_aux_rand_elements<E, R>(
+        &self,
+        public_coin: &mut R,
+    ) -> Result<AuxRandElements<E>, RandomCoinError>
+    where
+        E: FieldElement<BaseField = Felt>,
+        R: RandomCoin<BaseField = Felt>,
+    {
+        let num_elements = self.trace_info().get_num_aux_segment_rand_elements();
+        let mut rand_elements = Vec::with_capacity(num_elements);
+        for _ in 0..num_elements {
+            rand_elements.push(public_coin.draw()?);
+        }
+
+        if let Some(randomizer) = &self.trace_randomizer {
+            let coin_elements = E::slice_as_base_elements(&rand_elements);
+            let derived_elements = randomizer.aux_rand_elements(coin_elements);
+            assert_eq!(
+                derived_elements.len(),
+                coin_elements.len(),
+                "the trace randomizer must return as many elements as it is given"
+            );
+            rand_elements = E::slice_from_base_elements(&derived_elements).to_vec();
         }
+
+        Ok(AuxRandElements::new(rand_elements))
     }
 
     // PERIODIC COLUMNS
@@ -250,14 +307,21 @@ impl Air for ProcessorAir {
 // PUBLIC INPUTS
 // ================================================================================================
 
-#[derive(Debug)]
+/// The public inputs of a proof of execution.
+///
+/// The public inputs may also carry a [TraceRandomizer], which is not part of the public inputs
+/// proper: it is neither committed to by the proof nor serialized.
 pub struct PublicInputs {
     program_info: ProgramInfo,
     stack_inputs: StackInputs,
     stack_outputs: StackOutputs,
+    environment: ExecutionEnvironment,
+    initial_memory: RpoDigest,
+    trace_randomizer: Option<Arc<dyn TraceRandomizer>>,
 }
 
 impl PublicInputs {
+    /// Returns public inputs for an execution in the default environment.
     pub fn new(
         program_info: ProgramInfo,
         stack_inputs: StackInputs,
@@ -267,15 +331,79 @@ impl PublicInputs {
             program_info,
             stack_inputs,
             stack_outputs,
+            environment: ExecutionEnvironment::default(),
+            initial_memory: RpoDigest::default(),
+            trace_randomizer: None,
         }
     }
+
+    /// Returns these public inputs with the environment of the execution set to the specified
+    /// one.
+    pub fn with_environment(mut self, environment: ExecutionEnvironment) -> Self {
+        self.environment = environment;
+        self
+    }
+
+    /// Returns these public inputs with the commitment to the memory of the VM before the execution
+    /// started set to the specified one.
+    ///
+    /// The commitment of memory initialized to zero, which is the default, is four ZERO elements.
+    pub fn with_initial_memory(mut self, commitment: RpoDigest) -> Self {
+        self.initial_memory = commitment;
+        self
+    }
+
+    /// Returns these public inputs with the random elements used to build the auxiliary trace
+    /// derived by the specified [TraceRandomizer].
+    pub fn with_trace_randomizer(mut self, randomizer: Arc<dyn TraceRandomizer>) -> Self {
+        self.trace_randomizer = Some(randomizer);
+        self
+    }
+
+    /// Returns the information about the program whose execution is attested to.
+    pub fn program_info(&self) -> &ProgramInfo {
+        &self.program_info
+    }
+
+    /// Returns the environment of the execution which is attested to.
+    pub fn environment(&self) -> &ExecutionEnvironment {
+        &self.environment
+    }
+
+    /// Returns the commitment to the memory of the VM before the execution started.
+    pub fn initial_memory(&self) -> &RpoDigest {
+        &self.initial_memory
+    }
+}
+
+impl fmt::Debug for PublicInputs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PublicInputs")
+            .field("program_info", &self.program_info)
+            .field("stack_inputs", &self.stack_inputs)
+            .field("stack_outputs", &self.stack_outputs)
+            .field("environment", &self.environment)
+            .field("initial_memory", &self.initial_memory)
+            .field("trace_randomizer", &self.trace_randomizer.is_some())
+            .finish()
+    }
 }
 
 impl vm_core::ToElements<Felt> for PublicInputs {
+    /// Returns the elements of the public inputs, i.e., the program hash and the kernel procedure
+    /// hashes, the input and output stacks, the commitment to the environment, the version of the
+    /// instruction set targeted by the program (see [vm_core::IsaVersion::to_word]), and the
+    /// commitment to the initial memory.
+    ///
+    /// As the version is part of the public inputs, a proof only verifies against the version of
+    /// the instruction set it was generated for.
     fn to_elements(&self) -> Vec<Felt> {
         let mut result = self.program_info.to_elements();
         result.append(&mut self.stack_inputs.to_vec());
         result.append(&mut self.stack_outputs.to_vec());
+        result.extend_from_slice(self.environment.commitment().as_elements());
+        result.extend_from_slice(&self.program_info.isa_version().to_word());
+        result.extend_from_slice(self.initial_memory.as_elements());
         result
     }
 }
@@ -288,6 +416,8 @@ impl Serializable for PublicInputs {
         self.program_info.write_into(target);
         self.stack_inputs.write_into(target);
         self.stack_outputs.write_into(target);
+        self.environment.write_into(target);
+        self.initial_memory.write_into(target);
     }
 }
 
@@ -296,11 +426,16 @@ impl Deserializable for PublicInputs {
         let program_info = ProgramInfo::read_from(source)?;
         let stack_inputs = StackInputs::read_from(source)?;
         let stack_outputs = StackOutputs::read_from(source)?;
+        let environment = ExecutionEnvironment::read_from(source)?;
+        let initial_memory = RpoDigest::read_from(source)?;
 
         Ok(PublicInputs {
             program_info,
             stack_inputs,
             stack_outputs,
+            environment,
+            initial_memory,
+            trace_randomizer: None,
         })
     }
 }
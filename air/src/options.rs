@@ -1,7 +1,12 @@
+use alloc::{string::ToString, sync::Arc};
+use core::sync::atomic::{AtomicBool, Ordering};
+
+use vm_core::utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
 use winter_air::BatchingMethod;
 
 use super::{
-    ExecutionOptionsError, FieldExtension, HashFunction, WinterProofOptions, trace::MIN_TRACE_LEN,
+    ExecutionOptionsError, FieldExtension, HashFunction, SecurityEstimate, SecurityPreset,
+    WinterProofOptions, trace::MIN_TRACE_LEN,
 };
 
 // PROVING OPTIONS
@@ -13,6 +18,8 @@ pub struct ProvingOptions {
     exec_options: ExecutionOptions,
     proof_options: WinterProofOptions,
     hash_fn: HashFunction,
+    memory_limit: Option<usize>,
+    self_check: bool,
 }
 
 impl ProvingOptions {
@@ -31,6 +38,18 @@ impl ProvingOptions {
         BatchingMethod::Algebraic,
     );
 
+    /// Standard proof parameters for 100-bit conjectured security in non-recursive context.
+    pub const REGULAR_100_BITS: WinterProofOptions = WinterProofOptions::new(
+        29,
+        8,
+        16,
+        FieldExtension::Quadratic,
+        8,
+        255,
+        BatchingMethod::Algebraic,
+        BatchingMethod::Algebraic,
+    );
+
     /// Standard proof parameters for 128-bit conjectured security in non-recursive context.
     pub const REGULAR_128_BITS: WinterProofOptions = WinterProofOptions::new(
         27,
@@ -44,13 +63,31 @@ impl ProvingOptions {
     );
 
     /// Standard proof parameters for 96-bit conjectured security in recursive context.
+    ///
+    /// These are the parameters expected by the recursive STARK verifier of the standard library,
+    /// which folds the FRI layers until the remainder polynomial has 64 or 128 coefficients, i.e.,
+    /// a degree of up to 127, as for [ProvingOptions::RECURSIVE_100_BITS]. The degree of the
+    /// remainder polynomial only trades the number of FRI layers for the size of the remainder, and
+    /// does not affect the security of proofs.
     pub const RECURSIVE_96_BITS: WinterProofOptions = WinterProofOptions::new(
         27,
         8,
         16,
         FieldExtension::Quadratic,
         4,
-        7,
+        127,
+        BatchingMethod::Algebraic,
+        BatchingMethod::Algebraic,
+    );
+
+    /// Standard proof parameters for 100-bit conjectured security in recursive context.
+    pub const RECURSIVE_100_BITS: WinterProofOptions = WinterProofOptions::new(
+        29,
+        8,
+        16,
+        FieldExtension::Quadratic,
+        4,
+        127,
         BatchingMethod::Algebraic,
         BatchingMethod::Algebraic,
     );
@@ -67,6 +104,45 @@ impl ProvingOptions {
         BatchingMethod::Algebraic,
     );
 
+    /// Standard proof parameters for 96-bit proven security.
+    ///
+    /// Proven security requires a cubic field extension, which the recursive STARK verifier of the
+    /// standard library does not support, so the same parameters are used in both contexts.
+    pub const PROVEN_96_BITS: WinterProofOptions = WinterProofOptions::new(
+        58,
+        8,
+        16,
+        FieldExtension::Cubic,
+        8,
+        255,
+        BatchingMethod::Algebraic,
+        BatchingMethod::Algebraic,
+    );
+
+    /// Standard proof parameters for 100-bit proven security.
+    pub const PROVEN_100_BITS: WinterProofOptions = WinterProofOptions::new(
+        64,
+        8,
+        16,
+        FieldExtension::Cubic,
+        8,
+        255,
+        BatchingMethod::Algebraic,
+        BatchingMethod::Algebraic,
+    );
+
+    /// Standard proof parameters for 128-bit proven security.
+    pub const PROVEN_128_BITS: WinterProofOptions = WinterProofOptions::new(
+        118,
+        16,
+        21,
+        FieldExtension::Cubic,
+        8,
+        255,
+        BatchingMethod::Algebraic,
+        BatchingMethod::Algebraic,
+    );
+
     // CONSTRUCTORS
     // --------------------------------------------------------------------------------------------
 
@@ -91,7 +167,51 @@ impl ProvingOptions {
             BatchingMethod::Algebraic,
         );
         let exec_options = ExecutionOptions::default();
-        Self { exec_options, proof_options, hash_fn }
+        Self {
+            exec_options,
+            proof_options,
+            hash_fn,
+            memory_limit: None,
+            self_check: false,
+        }
+    }
+
+    /// Creates a new preset instance of [ProvingOptions] targeting the specified security level.
+    ///
+    /// If `recursive` flag is set to true, proofs will be generated using an arithmetization-
+    /// friendly hash function (RPO). Such proofs are well-suited for recursive proof verification,
+    /// but may take significantly longer to generate. Otherwise, proofs are generated using BLAKE3,
+    /// with a 256-bit output for security levels above 96 bits.
+    ///
+    /// The estimated security of the resulting options can be checked with
+    /// [ProvingOptions::estimate_security].
+    pub fn with_security_preset(preset: SecurityPreset, recursive: bool) -> Self {
+        let proof_options = match (preset, recursive) {
+            (SecurityPreset::Conjectured96, false) => Self::REGULAR_96_BITS,
+            (SecurityPreset::Conjectured96, true) => Self::RECURSIVE_96_BITS,
+            (SecurityPreset::Conjectured100, false) => Self::REGULAR_100_BITS,
+            (SecurityPreset::Conjectured100, true) => Self::RECURSIVE_100_BITS,
+            (SecurityPreset::Conjectured128, false) => Self::REGULAR_128_BITS,
+            (SecurityPreset::Conjectured128, true) => Self::RECURSIVE_128_BITS,
+            (SecurityPreset::Proven96, _) => Self::PROVEN_96_BITS,
+            (SecurityPreset::Proven100, _) => Self::PROVEN_100_BITS,
+            (SecurityPreset::Proven128, _) => Self::PROVEN_128_BITS,
+        };
+        let hash_fn = if recursive {
+            HashFunction::Rpo256
+        } else if preset.bits() > 96 {
+            HashFunction::Blake3_256
+        } else {
+            HashFunction::Blake3_192
+        };
+
+        Self {
+            exec_options: ExecutionOptions::default(),
+            proof_options,
+            hash_fn,
+            memory_limit: None,
+            self_check: false,
+        }
     }
 
     /// Creates a new preset instance of [ProvingOptions] targeting 96-bit security level.
@@ -100,19 +220,7 @@ impl ProvingOptions {
     /// friendly hash function (RPO). Such proofs are well-suited for recursive proof verification,
     /// but may take significantly longer to generate.
     pub fn with_96_bit_security(recursive: bool) -> Self {
-        if recursive {
-            Self {
-                exec_options: ExecutionOptions::default(),
-                proof_options: Self::RECURSIVE_96_BITS,
-                hash_fn: HashFunction::Rpo256,
-            }
-        } else {
-            Self {
-                exec_options: ExecutionOptions::default(),
-                proof_options: Self::REGULAR_96_BITS,
-                hash_fn: HashFunction::Blake3_192,
-            }
-        }
+        Self::with_security_preset(SecurityPreset::Conjectured96, recursive)
     }
 
     /// Creates a new preset instance of [ProvingOptions] targeting 96-bit security level,
@@ -122,6 +230,8 @@ impl ProvingOptions {
             exec_options: ExecutionOptions::default(),
             proof_options: Self::RECURSIVE_96_BITS,
             hash_fn: HashFunction::Rpx256,
+            memory_limit: None,
+            self_check: false,
         }
     }
 
@@ -131,19 +241,7 @@ impl ProvingOptions {
     /// friendly hash function (RPO). Such proofs are well-suited for recursive proof verification,
     /// but may take significantly longer to generate.
     pub fn with_128_bit_security(recursive: bool) -> Self {
-        if recursive {
-            Self {
-                exec_options: ExecutionOptions::default(),
-                proof_options: Self::RECURSIVE_128_BITS,
-                hash_fn: HashFunction::Rpo256,
-            }
-        } else {
-            Self {
-                exec_options: ExecutionOptions::default(),
-                proof_options: Self::REGULAR_128_BITS,
-                hash_fn: HashFunction::Blake3_256,
-            }
-        }
+        Self::with_security_preset(SecurityPreset::Conjectured128, recursive)
     }
 
     /// Creates a new preset instance of [ProvingOptions] targeting 128-bit security level,
@@ -153,9 +251,25 @@ impl ProvingOptions {
             exec_options: ExecutionOptions::default(),
             proof_options: Self::RECURSIVE_128_BITS,
             hash_fn: HashFunction::Rpx256,
+            memory_limit: None,
+            self_check: false,
         }
     }
 
+    /// Sets the hash function used to commit to the execution trace and to generate the proof.
+    ///
+    /// The verifier reads the hash function from the proof, so proofs generated with any of the
+    /// supported hash functions and any of the presets can be verified without additional
+    /// configuration. The security level of such proofs is capped by the collision resistance of
+    /// the hash function (e.g., 96 bits for BLAKE3-192). However, only
+    /// proofs generated with RPO-256 and the parameters of [ProvingOptions::RECURSIVE_96_BITS] or
+    /// [ProvingOptions::RECURSIVE_100_BITS] can be verified recursively by the STARK verifier of
+    /// the standard library.
+    pub fn with_hash_fn(mut self, hash_fn: HashFunction) -> Self {
+        self.hash_fn = hash_fn;
+        self
+    }
+
     /// Sets [ExecutionOptions] for this [ProvingOptions].
     ///
     /// This sets the maximum number of cycles a program is allowed to execute as well as
@@ -165,6 +279,35 @@ impl ProvingOptions {
         self
     }
 
+    /// Sets the maximum amount of memory, in bytes, which the low-degree extension of the
+    /// execution trace is allowed to occupy.
+    ///
+    /// When the extended trace would not fit in `memory_limit` bytes, the prover builds and
+    /// commits to it one coset of the trace domain at a time, and spills the extended columns to a
+    /// temporary file instead of keeping them in memory. Rows of the extended trace are then read
+    /// back from this file during constraint evaluation and query generation, which makes proving
+    /// slower, but reduces its peak memory usage by close to the blowup factor times the size of
+    /// the execution trace.
+    ///
+    /// The temporary file is created in the directory returned by `std::env::temp_dir`, and the
+    /// limit has no effect when the prover is built without the `std` feature.
+    pub fn with_memory_limit(mut self, memory_limit: usize) -> Self {
+        self.memory_limit = Some(memory_limit);
+        self
+    }
+
+    /// Enables the verification of every proof by the prover, before the proof is returned.
+    ///
+    /// When enabled, the prover checks each proof it generates against the public inputs of the
+    /// proven execution, and returns an error describing the failure instead of a proof which would
+    /// be rejected by a verifier. This roughly adds the cost of verification to the cost of proving,
+    /// and is intended for catching prover bugs and misconfigurations early, e.g., in testing or
+    /// before publishing proofs.
+    pub fn with_self_check(mut self) -> Self {
+        self.self_check = true;
+        self
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -177,6 +320,25 @@ impl ProvingOptions {
     pub const fn execution_options(&self) -> &ExecutionOptions {
         &self.exec_options
     }
+
+    /// Returns the maximum amount of memory, in bytes, which the low-degree extension of the
+    /// execution trace is allowed to occupy, if there is one.
+    pub const fn memory_limit(&self) -> Option<usize> {
+        self.memory_limit
+    }
+
+    /// Returns true if the prover verifies every proof it generates before returning it.
+    pub const fn self_check(&self) -> bool {
+        self.self_check
+    }
+
+    /// Returns the estimated security of proofs of an execution trace with `trace_len` rows
+    /// generated with these options.
+    ///
+    /// Only the proven security levels of the estimate depend on the trace length.
+    pub fn estimate_security(&self, trace_len: usize) -> SecurityEstimate {
+        SecurityEstimate::new(&self.proof_options, self.hash_fn, trace_len)
+    }
 }
 
 impl Default for ProvingOptions {
@@ -191,6 +353,76 @@ impl From<ProvingOptions> for WinterProofOptions {
     }
 }
 
+// SERIALIZATION
+// ================================================================================================
+
+/// [ProvingOptions] are serialized with the parameters which determine the generated proofs and
+/// the resources available to the proven execution, so that they can be sent to a remote prover.
+///
+/// The options of [ExecutionOptions] which only affect local diagnostics (i.e., tracing,
+/// debugging, profiling, coverage and event recording), the cancellation token and the operation
+/// dispatch strategy are not serialized, and are set to their defaults on deserialization.
+impl Serializable for ProvingOptions {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.proof_options.write_into(target);
+        self.hash_fn.write_into(target);
+        target.write_u32(self.exec_options.max_cycles);
+        target.write_u32(self.exec_options.expected_cycles);
+        target.write_u32(self.exec_options.max_memory_rows);
+        target.write_u32(self.exec_options.max_nesting_depth);
+        target.write_bool(self.exec_options.enable_trace_generation);
+        self.memory_limit.write_into(target);
+        target.write_bool(self.self_check);
+    }
+}
+
+impl Deserializable for ProvingOptions {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let proof_options = WinterProofOptions::read_from(source)?;
+        let hash_fn = HashFunction::read_from(source)?;
+        let max_cycles = source.read_u32()?;
+        let expected_cycles = source.read_u32()?;
+        let max_memory_rows = source.read_u32()?;
+        let max_nesting_depth = source.read_u32()?;
+        let enable_trace_generation = source.read_bool()?;
+        let memory_limit = Option::<usize>::read_from(source)?;
+        let self_check = source.read_bool()?;
+
+        let mut exec_options =
+            ExecutionOptions::new(Some(max_cycles), expected_cycles, false, false)
+                .map_err(|err| DeserializationError::InvalidValue(err.to_string()))?
+                .with_max_memory_rows(max_memory_rows)
+                .with_max_nesting_depth(max_nesting_depth);
+        if !enable_trace_generation {
+            exec_options = exec_options.without_trace_generation();
+        }
+
+        Ok(Self {
+            exec_options,
+            proof_options,
+            hash_fn,
+            memory_limit,
+            self_check,
+        })
+    }
+}
+
+/// Proving options are serialized in the binary format of [Serializable], as a hex string in
+/// human-readable formats and as bytes otherwise.
+#[cfg(feature = "serde")]
+impl serde::Serialize for ProvingOptions {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        vm_core::utils::serde_bytes::serialize(self, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for ProvingOptions {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        vm_core::utils::serde_bytes::deserialize(deserializer)
+    }
+}
+
 // EXECUTION OPTIONS
 // ================================================================================================
 
@@ -198,12 +430,35 @@ impl From<ProvingOptions> for WinterProofOptions {
 ///
 /// - `max_cycles` specifies the maximum number of cycles a program is allowed to execute.
 /// - `expected_cycles` specifies the number of cycles a program is expected to execute.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// - `max_memory_rows` specifies the maximum number of rows of the memory chiplet trace, i.e., the
+///   maximum number of memory accesses a program is allowed to perform.
+/// - `max_nesting_depth` specifies the maximum number of MAST nodes which can be executed at the
+///   same time, i.e., the maximum depth to which the nodes executed by a program can be nested.
+/// - `enable_trace_generation` specifies whether the VM builds the execution trace of the program,
+///   which is required for proving it.
+/// - `enable_profiling` specifies whether the VM records the number of cycles spent in each
+///   procedure of the program.
+/// - `enable_coverage` specifies whether the VM records which basic blocks and branches of the
+///   program were executed.
+/// - `enable_event_timeline` specifies whether the VM records the events emitted by the program,
+///   and `event_stack_snapshots` whether the top of the stack is recorded with every event.
+/// - `cancel_token` specifies a [CancellationToken] which can be used to abort the execution.
+/// - `op_dispatch` specifies the [OpDispatch] strategy used by the VM to execute operations.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ExecutionOptions {
     max_cycles: u32,
     expected_cycles: u32,
+    max_memory_rows: u32,
+    max_nesting_depth: u32,
     enable_tracing: bool,
     enable_debugging: bool,
+    enable_trace_generation: bool,
+    enable_profiling: bool,
+    enable_coverage: bool,
+    enable_event_timeline: bool,
+    event_stack_snapshots: bool,
+    cancel_token: Option<CancellationToken>,
+    op_dispatch: OpDispatch,
 }
 
 impl Default for ExecutionOptions {
@@ -211,13 +466,30 @@ impl Default for ExecutionOptions {
         ExecutionOptions {
             max_cycles: u32::MAX,
             expected_cycles: MIN_TRACE_LEN as u32,
+            max_memory_rows: u32::MAX,
+            max_nesting_depth: Self::DEFAULT_MAX_NESTING_DEPTH,
             enable_tracing: false,
             enable_debugging: false,
+            enable_trace_generation: true,
+            enable_profiling: false,
+            enable_coverage: false,
+            enable_event_timeline: false,
+            event_stack_snapshots: false,
+            cancel_token: None,
+            op_dispatch: OpDispatch::default(),
         }
     }
 }
 
 impl ExecutionOptions {
+    /// The default maximum nesting depth of MAST nodes.
+    ///
+    /// The VM executes nested nodes recursively, so this is chosen low enough for executions of
+    /// optimized builds to fail with an error, rather than by overflowing the stack of the
+    /// executing thread, even on threads with small (i.e., 2 MiB) stacks. Unoptimized builds use
+    /// much larger stack frames, and may need to execute programs on threads with larger stacks.
+    pub const DEFAULT_MAX_NESTING_DEPTH: u32 = 1024;
+
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
 
@@ -248,11 +520,44 @@ impl ExecutionOptions {
         Ok(ExecutionOptions {
             max_cycles,
             expected_cycles,
+            max_memory_rows: u32::MAX,
+            max_nesting_depth: Self::DEFAULT_MAX_NESTING_DEPTH,
             enable_tracing,
             enable_debugging,
+            enable_trace_generation: true,
+            enable_profiling: false,
+            enable_coverage: false,
+            enable_event_timeline: false,
+            event_stack_snapshots: false,
+            cancel_token: None,
+            op_dispatch: OpDispatch::default(),
         })
     }
 
+    /// Sets the maximum number of rows of the memory chiplet trace.
+    ///
+    /// Every memory access performed by a program adds a row to the memory chiplet trace, so this
+    /// bounds the size of the trace, and thus the memory required to prove the program, regardless
+    /// of the number of cycles it executes. Execution fails once the limit is exceeded.
+    pub fn with_max_memory_rows(mut self, max_memory_rows: u32) -> Self {
+        self.max_memory_rows = max_memory_rows;
+        self
+    }
+
+    /// Sets the maximum nesting depth of the MAST nodes executed by a program.
+    ///
+    /// This counts all nodes which are being executed at the same time, including the nodes of
+    /// procedures which are called (or executed via `dyn` and `dyncall`). Programs which are
+    /// statically known to exceed the limit are rejected before execution starts; otherwise,
+    /// execution fails once the limit is exceeded.
+    ///
+    /// Since nested nodes are executed recursively, raising the limit above the default may
+    /// require executing programs on a thread with a larger stack.
+    pub fn with_max_nesting_depth(mut self, max_nesting_depth: u32) -> Self {
+        self.max_nesting_depth = max_nesting_depth;
+        self
+    }
+
     /// Enables execution of the `trace` instructions.
     pub fn with_tracing(mut self) -> Self {
         self.enable_tracing = true;
@@ -270,6 +575,72 @@ impl ExecutionOptions {
         self
     }
 
+    /// Disables generation of the execution trace.
+    ///
+    /// In this mode the VM maintains only the state needed to execute the program correctly, and
+    /// skips building the system, decoder and stack trace columns, as well as the traces of the
+    /// hash and bitwise chiplets, whose rows are only counted. This makes execution faster
+    /// and reduces memory usage, but the resulting execution cannot be proven. This is useful
+    /// when a program only needs to be run to obtain its outputs, e.g. to pre-validate it.
+    pub fn without_trace_generation(mut self) -> Self {
+        self.enable_trace_generation = false;
+        self
+    }
+
+    /// Enables profiling of program execution.
+    ///
+    /// When profiling is enabled, the VM records the number of cycles spent in each procedure of
+    /// the program, which is made available via `ExecutionTrace::profile()`. Procedures are named
+    /// after their AsmOp decorators, so the program should be assembled in debug mode.
+    pub fn with_profiling(mut self) -> Self {
+        self.enable_profiling = true;
+        self
+    }
+
+    /// Enables coverage recording of program execution.
+    ///
+    /// When coverage recording is enabled, the VM records which basic blocks and which branches of
+    /// split and loop nodes were executed, which is made available via
+    /// `ExecutionTrace::coverage()`.
+    pub fn with_coverage(mut self) -> Self {
+        self.enable_coverage = true;
+        self
+    }
+
+    /// Enables recording of the events emitted by the program.
+    ///
+    /// When the event timeline is enabled, the VM records every event emitted via the `emit`
+    /// instruction together with the clock cycle, the execution context and the procedures it was
+    /// emitted in, which is made available via `ExecutionTrace::event_timeline()`.
+    pub fn with_event_timeline(mut self) -> Self {
+        self.enable_event_timeline = true;
+        self
+    }
+
+    /// Enables recording of the events emitted by the program, as with
+    /// [ExecutionOptions::with_event_timeline], and of the top 16 elements of the stack at the time
+    /// each event is emitted.
+    pub fn with_event_stack_snapshots(mut self) -> Self {
+        self.enable_event_timeline = true;
+        self.event_stack_snapshots = true;
+        self
+    }
+
+    /// Sets the [CancellationToken] used to abort the execution.
+    ///
+    /// The VM checks the token at every cycle, and stops executing the program once the token is
+    /// cancelled, e.g., by a supervising thread.
+    pub fn with_cancel_token(mut self, cancel_token: CancellationToken) -> Self {
+        self.cancel_token = Some(cancel_token);
+        self
+    }
+
+    /// Sets the [OpDispatch] strategy used to execute the operations of a program.
+    pub fn with_op_dispatch(mut self, op_dispatch: OpDispatch) -> Self {
+        self.op_dispatch = op_dispatch;
+        self
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -287,6 +658,16 @@ impl ExecutionOptions {
         self.expected_cycles
     }
 
+    /// Returns the maximum number of rows of the memory chiplet trace.
+    pub fn max_memory_rows(&self) -> u32 {
+        self.max_memory_rows
+    }
+
+    /// Returns the maximum nesting depth of the MAST nodes executed by a program.
+    pub fn max_nesting_depth(&self) -> u32 {
+        self.max_nesting_depth
+    }
+
     /// Returns a flag indicating whether the VM should execute `trace` instructions.
     pub fn enable_tracing(&self) -> bool {
         self.enable_tracing
@@ -296,4 +677,137 @@ impl ExecutionOptions {
     pub fn enable_debugging(&self) -> bool {
         self.enable_debugging
     }
+
+    /// Returns a flag indicating whether the VM should generate the execution trace of a program.
+    pub fn enable_trace_generation(&self) -> bool {
+        self.enable_trace_generation
+    }
+
+    /// Returns a flag indicating whether the VM should profile the execution of a program.
+    pub fn enable_profiling(&self) -> bool {
+        self.enable_profiling
+    }
+
+    /// Returns a flag indicating whether the VM should record the coverage of a program.
+    pub fn enable_coverage(&self) -> bool {
+        self.enable_coverage
+    }
+
+    /// Returns a flag indicating whether the VM should record the events emitted by a program.
+    pub fn enable_event_timeline(&self) -> bool {
+        self.enable_event_timeline
+    }
+
+    /// Returns a flag indicating whether the VM should record the top of the stack with every
+    /// event emitted by a program.
+    pub fn event_stack_snapshots(&self) -> bool {
+        self.event_stack_snapshots
+    }
+
+    /// Returns the [CancellationToken] used to abort the execution, if any.
+    pub fn cancel_token(&self) -> Option<&CancellationToken> {
+        self.cancel_token.as_ref()
+    }
+
+    /// Returns the [OpDispatch] strategy used to execute the operations of a program.
+    pub fn op_dispatch(&self) -> OpDispatch {
+        self.op_dispatch
+    }
+}
+
+// OPERATION DISPATCH
+// ================================================================================================
+
+/// The strategy used by the VM to select the handler of each executed operation.
+///
+/// Both strategies execute operations identically, and differ only in their performance
+/// characteristics, which depend on the target and on the mix of operations executed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OpDispatch {
+    /// Handlers are looked up in a table of function pointers indexed by opcode.
+    #[default]
+    Table,
+    /// Handlers are selected by matching on the operation.
+    Match,
+}
+
+// CANCELLATION TOKEN
+// ================================================================================================
+
+/// A token used to cooperatively cancel the execution of a program.
+///
+/// Clones of a token share their state, so a token can be passed to the VM via
+/// [ExecutionOptions::with_cancel_token], and cancelled from another thread via a clone.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// Returns a new token which has not been cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels this token, and all of its clones.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns true if this token was cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+impl PartialEq for CancellationToken {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for CancellationToken {}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn proving_options_serialization() {
+        let exec_options = ExecutionOptions::new(Some(1 << 20), 1 << 12, true, true)
+            .unwrap()
+            .with_max_memory_rows(1 << 16)
+            .with_max_nesting_depth(64);
+        let options = ProvingOptions::with_128_bit_security(true)
+            .with_execution_options(exec_options)
+            .with_memory_limit(1 << 30)
+            .with_self_check();
+
+        let deserialized = ProvingOptions::read_from_bytes(&options.to_bytes()).unwrap();
+        assert_eq!(deserialized.proof_options, options.proof_options);
+        assert_eq!(deserialized.hash_fn(), HashFunction::Rpo256);
+        assert_eq!(deserialized.memory_limit(), Some(1 << 30));
+        assert!(deserialized.self_check());
+
+        // limits are preserved, while local diagnostics are reset
+        let exec_options = deserialized.execution_options();
+        assert_eq!(exec_options.max_cycles(), 1 << 20);
+        assert_eq!(exec_options.expected_cycles(), 1 << 12);
+        assert_eq!(exec_options.max_memory_rows(), 1 << 16);
+        assert_eq!(exec_options.max_nesting_depth(), 64);
+        assert!(!exec_options.enable_tracing());
+        assert!(!exec_options.enable_debugging());
+
+        let default = ProvingOptions::default();
+        assert_eq!(ProvingOptions::read_from_bytes(&default.to_bytes()).unwrap(), default);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn proving_options_serde_json() {
+        let options = ProvingOptions::with_96_bit_security(true).with_memory_limit(1 << 30);
+        let json = serde_json::to_string(&options).unwrap();
+        assert_eq!(serde_json::from_str::<ProvingOptions>(&json).unwrap(), options);
+    }
 }
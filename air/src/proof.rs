@@ -1,11 +1,37 @@
-use alloc::vec::Vec;
+use alloc::{string::String, vec::Vec};
+use core::{fmt, str::FromStr};
 
 use vm_core::{
-    crypto::hash::{Blake3_192, Blake3_256, Hasher, Rpo256, Rpx256},
-    utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable},
+    crypto::hash::{Blake3_192, Blake3_256, Hasher, Keccak256, Rpo256, Rpx256},
+    utils::{
+        ByteReader, ByteWriter, Deserializable, DeserializationError, FormatVersions, Serializable,
+        SliceReader, UnsupportedVersion,
+    },
 };
 use winter_air::proof::Proof;
 
+// CONSTANTS
+// ================================================================================================
+
+/// Magic string for detecting that a file is a serialized [ExecutionProof].
+const MAGIC: &[u8; 5] = b"MPRF\0";
+
+/// The format version of serialized [ExecutionProof]s.
+///
+/// Version `[0, 0, 0]` refers to the format used before format versions were introduced, in which
+/// proofs start directly with their hash function, and have no header.
+const VERSION: [u8; 3] = [0, 0, 1];
+
+/// The format versions which can be deserialized.
+const SUPPORTED_VERSIONS: FormatVersions = FormatVersions::new([0, 0, 0], VERSION);
+
+/// The format versions of the proofs which can be verified.
+///
+/// Proofs serialized by previous releases remain readable, but were generated against other
+/// constraints and public inputs (e.g., without the version of the instruction set targeted by the
+/// program), and thus cannot be verified by this release.
+const VERIFIABLE_VERSIONS: FormatVersions = FormatVersions::new(VERSION, VERSION);
+
 // EXECUTION PROOF
 // ================================================================================================
 
@@ -13,10 +39,14 @@ use winter_air::proof::Proof;
 ///
 /// The proof encodes the proof itself as well as STARK protocol parameters used to generate the
 /// proof. However, the proof does not contain public inputs needed to verify the proof.
+///
+/// The proof also records the version of the format it was deserialized from, which identifies
+/// the proofs generated by previous releases (see [ExecutionProof::check_version]).
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ExecutionProof {
     pub proof: Proof,
     pub hash_fn: HashFunction,
+    version: [u8; 3],
 }
 
 impl ExecutionProof {
@@ -26,7 +56,7 @@ impl ExecutionProof {
     /// Creates a new instance of [ExecutionProof] from the specified STARK proof and hash
     /// function.
     pub const fn new(proof: Proof, hash_fn: HashFunction) -> Self {
-        Self { proof, hash_fn }
+        Self { proof, hash_fn, version: VERSION }
     }
 
     // PUBLIC ACCESSORS
@@ -49,30 +79,71 @@ impl ExecutionProof {
             HashFunction::Blake3_256 => self.proof.conjectured_security::<Blake3_256>(),
             HashFunction::Rpo256 => self.proof.conjectured_security::<Rpo256>(),
             HashFunction::Rpx256 => self.proof.conjectured_security::<Rpx256>(),
+            HashFunction::Keccak256 => self.proof.conjectured_security::<Keccak256>(),
         };
         conjectured_security.bits()
     }
 
+    /// Returns the version of the format this proof was deserialized from, i.e., the current
+    /// version for the proofs generated by this release, and `[0, 0, 0]` for the proofs generated
+    /// by releases which predate format versions.
+    pub const fn version(&self) -> [u8; 3] {
+        self.version
+    }
+
+    /// Returns an error if this proof cannot be verified by this release, i.e., if it was
+    /// generated by a previous release, against other constraints and public inputs.
+    pub fn check_version(&self) -> Result<(), UnsupportedVersion> {
+        VERIFIABLE_VERSIONS.check("verifiable execution proof", self.version)
+    }
+
     // SERIALIZATION / DESERIALIZATION
     // --------------------------------------------------------------------------------------------
 
     /// Serializes this proof into a vector of bytes.
+    ///
+    /// The serialization format is as follows:
+    /// - `MAGIC`
+    /// - the format version of the proof (see [ExecutionProof::version])
+    /// - the hash function used to generate the proof (`u8`)
+    /// - the STARK proof (`Proof`)
     pub fn to_bytes(&self) -> Vec<u8> {
-        let mut bytes = self.proof.to_bytes();
-        assert!(!bytes.is_empty(), "invalid STARK proof");
-        // TODO: ideally we should write hash function into the proof first to avoid reallocations
-        bytes.insert(0, self.hash_fn as u8);
+        let mut bytes = Vec::new();
+        self.write_into(&mut bytes);
         bytes
     }
 
     /// Reads the source bytes, parsing a new proof instance.
+    ///
+    /// Proofs serialized by previous releases, which start directly with the hash function and
+    /// have no header, are also supported, but cannot be verified (see
+    /// [ExecutionProof::check_version]).
     pub fn from_bytes(source: &[u8]) -> Result<Self, DeserializationError> {
         if source.len() < 2 {
             return Err(DeserializationError::UnexpectedEOF);
         }
-        let hash_fn = HashFunction::try_from(source[0])?;
-        let proof = Proof::from_bytes(&source[1..])?;
-        Ok(Self::new(proof, hash_fn))
+
+        // proofs serialized before format versions were introduced start with the hash function,
+        // which never matches the first byte of the magic string
+        if source[0] != MAGIC[0] {
+            let hash_fn = HashFunction::try_from(source[0])?;
+            let proof = Proof::from_bytes(&source[1..])?;
+            return Ok(Self { proof, hash_fn, version: [0, 0, 0] });
+        }
+
+        let mut reader = SliceReader::new(source);
+        let magic: [u8; 5] = reader.read_array()?;
+        if magic != *MAGIC {
+            return Err(DeserializationError::InvalidValue(format!(
+                "invalid magic bytes. Expected '{MAGIC:?}', got '{magic:?}'"
+            )));
+        }
+        let version = SUPPORTED_VERSIONS.read_version("execution proof", &mut reader)?;
+        let hash_fn = HashFunction::read_from(&mut reader)?;
+
+        let header_len = MAGIC.len() + VERSION.len() + 1;
+        let proof = Proof::from_bytes(&source[header_len..])?;
+        Ok(Self { proof, hash_fn, version })
     }
 
     // DESTRUCTOR
@@ -99,6 +170,8 @@ pub enum HashFunction {
     Rpo256 = 0x02,
     /// RPX hash function with 256-bit output.
     Rpx256 = 0x03,
+    /// Keccak hash function with 256-bit output.
+    Keccak256 = 0x04,
 }
 
 impl Default for HashFunction {
@@ -115,6 +188,48 @@ impl HashFunction {
             HashFunction::Blake3_256 => Blake3_256::COLLISION_RESISTANCE,
             HashFunction::Rpo256 => Rpo256::COLLISION_RESISTANCE,
             HashFunction::Rpx256 => Rpx256::COLLISION_RESISTANCE,
+            HashFunction::Keccak256 => Keccak256::COLLISION_RESISTANCE,
+        }
+    }
+
+    /// Returns true if this hash function is arithmetization-friendly, i.e., if proofs generated
+    /// with it are cheap to verify inside Miden VM.
+    ///
+    /// Proofs generated with other hash functions are faster to generate, and cheaper to verify
+    /// natively or, for Keccak, on chains which provide a Keccak precompile.
+    pub const fn is_arithmetization_friendly(&self) -> bool {
+        matches!(self, HashFunction::Rpo256 | HashFunction::Rpx256)
+    }
+}
+
+impl fmt::Display for HashFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HashFunction::Blake3_192 => f.write_str("blake3-192"),
+            HashFunction::Blake3_256 => f.write_str("blake3-256"),
+            HashFunction::Rpo256 => f.write_str("rpo256"),
+            HashFunction::Rpx256 => f.write_str("rpx256"),
+            HashFunction::Keccak256 => f.write_str("keccak256"),
+        }
+    }
+}
+
+impl FromStr for HashFunction {
+    type Err = String;
+
+    /// Parses a hash function from its name, as displayed by [HashFunction]'s `Display`
+    /// implementation.
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name {
+            "blake3-192" => Ok(Self::Blake3_192),
+            "blake3-256" => Ok(Self::Blake3_256),
+            "rpo256" => Ok(Self::Rpo256),
+            "rpx256" => Ok(Self::Rpx256),
+            "keccak256" => Ok(Self::Keccak256),
+            _ => Err(format!(
+                "unknown hash function '{name}', expected one of blake3-192, blake3-256, rpo256, \
+                 rpx256 or keccak256"
+            )),
         }
     }
 }
@@ -128,6 +243,7 @@ impl TryFrom<u8> for HashFunction {
             0x01 => Ok(Self::Blake3_256),
             0x02 => Ok(Self::Rpo256),
             0x03 => Ok(Self::Rpx256),
+            0x04 => Ok(Self::Keccak256),
             _ => Err(DeserializationError::InvalidValue(format!(
                 "the hash function representation {repr} is not valid!"
             ))),
@@ -152,16 +268,118 @@ impl Deserializable for HashFunction {
 
 impl Serializable for ExecutionProof {
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
-        self.proof.write_into(target);
+        target.write_bytes(MAGIC);
+        target.write_bytes(&self.version);
         self.hash_fn.write_into(target);
+        self.proof.write_into(target);
     }
 }
 
 impl Deserializable for ExecutionProof {
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
-        let proof = Proof::read_from(source)?;
+        // proofs serialized before format versions were introduced start with the STARK proof, and
+        // thus with the width of the main trace, which never matches the first byte of the magic
+        // string
+        if source.peek_u8()? != MAGIC[0] {
+            let proof = Proof::read_from(source)?;
+            let hash_fn = HashFunction::read_from(source)?;
+            return Ok(ExecutionProof { proof, hash_fn, version: [0, 0, 0] });
+        }
+
+        let magic: [u8; 5] = source.read_array()?;
+        if magic != *MAGIC {
+            return Err(DeserializationError::InvalidValue(format!(
+                "invalid magic bytes. Expected '{MAGIC:?}', got '{magic:?}'"
+            )));
+        }
+        let version = SUPPORTED_VERSIONS.read_version("execution proof", source)?;
         let hash_fn = HashFunction::read_from(source)?;
+        let proof = Proof::read_from(source)?;
+
+        Ok(ExecutionProof { proof, hash_fn, version })
+    }
+}
+
+/// Execution proofs are serialized in the format of [ExecutionProof::to_bytes], as a hex string in
+/// human-readable formats and as bytes otherwise.
+#[cfg(feature = "serde")]
+impl serde::Serialize for ExecutionProof {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        vm_core::utils::serde_bytes::serialize_bytes(&self.to_bytes(), serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for ExecutionProof {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bytes = vm_core::utils::serde_bytes::deserialize_bytes(deserializer)?;
+        Self::from_bytes(&bytes).map_err(serde::de::Error::custom)
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use vm_core::utils::Deserializable;
+    use winter_air::proof::Proof;
+
+    use super::{ExecutionProof, HashFunction};
+
+    #[test]
+    fn execution_proof_serde_legacy_format() {
+        let proof = ExecutionProof::new(Proof::new_dummy(), HashFunction::Rpo256);
+
+        // proofs serialized before format versions were introduced have no header, and can be
+        // read but not verified
+        let mut bytes = proof.proof.to_bytes();
+        bytes.insert(0, proof.hash_fn as u8);
+        let legacy = ExecutionProof::from_bytes(&bytes).unwrap();
+        assert_eq!((legacy.proof.clone(), legacy.hash_fn), (proof.proof.clone(), proof.hash_fn));
+        assert_eq!(legacy.version(), [0, 0, 0]);
+        assert_eq!(
+            legacy.check_version().unwrap_err().to_string(),
+            "unsupported verifiable execution proof format version 0.0.0; supported versions are \
+             0.0.1 to 0.0.1"
+        );
+
+        let mut bytes = proof.proof.to_bytes();
+        bytes.push(proof.hash_fn as u8);
+        assert_eq!(ExecutionProof::read_from_bytes(&bytes).unwrap(), legacy);
+
+        assert_eq!(ExecutionProof::from_bytes(&proof.to_bytes()).unwrap(), proof);
+        assert_eq!(ExecutionProof::read_from_bytes(&proof.to_bytes()).unwrap(), proof);
+        assert!(proof.check_version().is_ok());
+    }
+
+    #[test]
+    fn execution_proof_serde_unsupported_format_version() {
+        let proof = ExecutionProof::new(Proof::new_dummy(), HashFunction::Blake3_192);
+        let mut bytes = proof.to_bytes();
+        bytes[5..8].copy_from_slice(&[1, 0, 0]);
+
+        let err = ExecutionProof::from_bytes(&bytes).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unsupported execution proof format version 1.0.0; supported versions are 0.0.0 to 0.0.1"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn execution_proof_serde_json() {
+        let proof = ExecutionProof::new(Proof::new_dummy(), HashFunction::Keccak256);
+
+        let json = serde_json::to_string(&proof).unwrap();
+        assert!(json.starts_with("\"0x"));
+        assert_eq!(serde_json::from_str::<ExecutionProof>(&json).unwrap(), proof);
 
-        Ok(ExecutionProof { proof, hash_fn })
+        // byte arrays are accepted as well, as produced by binary formats
+        let bytes = serde_json::to_string(&proof.to_bytes()).unwrap();
+        assert_eq!(serde_json::from_str::<ExecutionProof>(&bytes).unwrap(), proof);
+        assert!(serde_json::from_str::<ExecutionProof>("\"0x0\"").is_err());
     }
 }
@@ -0,0 +1,39 @@
+use alloc::vec::Vec;
+
+use vm_core::Felt;
+
+// TRACE RANDOMIZER
+// ================================================================================================
+
+/// A source of the random elements used to build the auxiliary trace.
+///
+/// By default, these elements are drawn from the public coin of the proof right after it is
+/// reseeded with the commitment to the main trace. A randomizer derives them instead from the
+/// elements drawn from the coin, which allows protocols embedding Miden proofs to bind them to an
+/// external transcript, e.g., by hashing the elements drawn from the coin together with the state
+/// of the transcript.
+///
+/// The prover and the verifier must use the same randomizer (see `prove_with_randomizer` and
+/// `verify_with_randomizer`). The soundness of the proof requires the returned elements to be
+/// unpredictable before the main trace is committed to: they must thus depend on all the elements
+/// drawn from the coin, which themselves depend on the commitment.
+///
+/// Proofs generated with a randomizer cannot be verified by the recursive STARK verifier of the
+/// standard library, which always draws these elements from the public coin.
+pub trait TraceRandomizer: Send + Sync {
+    /// Returns the random elements used to build the auxiliary trace, given the elements drawn for
+    /// this purpose from the public coin.
+    ///
+    /// Both are given as the base field elements of the extension field elements used by the
+    /// proof, so the returned vector must have the same length as `coin_elements`.
+    fn aux_rand_elements(&self, coin_elements: &[Felt]) -> Vec<Felt>;
+}
+
+impl<F> TraceRandomizer for F
+where
+    F: Fn(&[Felt]) -> Vec<Felt> + Send + Sync,
+{
+    fn aux_rand_elements(&self, coin_elements: &[Felt]) -> Vec<Felt> {
+        self(coin_elements)
+    }
+}
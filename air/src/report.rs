@@ -0,0 +1,302 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use winter_air::TransitionConstraintDegree;
+
+use super::{
+    constraints::{chiplets, range, stack},
+    trace::{
+        CHIPLETS_AUX_TRACE_WIDTH, CHIPLETS_WIDTH, DECODER_AUX_TRACE_WIDTH, DECODER_TRACE_WIDTH,
+        HASHER_AUX_TRACE_WIDTH, RANGE_CHECK_AUX_TRACE_WIDTH, RANGE_CHECK_TRACE_WIDTH,
+        STACK_AUX_TRACE_WIDTH, STACK_TRACE_WIDTH, SYS_TRACE_WIDTH,
+    },
+};
+
+// CONSTANTS
+// ================================================================================================
+
+/// The trace length at which the degrees of constraints involving periodic columns are computed.
+const REFERENCE_TRACE_LEN: usize = 1 << 20;
+
+// CONSTRAINT REPORT
+// ================================================================================================
+
+/// A summary of the constraints of the [ProcessorAir](crate::ProcessorAir), broken down by the
+/// component of the VM they apply to.
+///
+/// The report describes the AIR of the current build, and can be used to reason about the cost of
+/// verifying a proof, e.g., since the number of constraints determines the number of random
+/// coefficients drawn by the verifier, and the maximum constraint degree determines the minimum
+/// blowup factor of the proof.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConstraintReport {
+    components: Vec<ComponentConstraints>,
+}
+
+impl ConstraintReport {
+    /// Returns a report of the constraints of the [ProcessorAir](crate::ProcessorAir).
+    pub fn new() -> Self {
+        let system = ComponentConstraints::new("system", SYS_TRACE_WIDTH, 0)
+            .with_constraints(&[TransitionConstraintDegree::new(1)], &[])
+            .with_assertions(2, 0);
+
+        // the decoder constraints are not yet enforced by the AIR, but its auxiliary columns are
+        // part of the trace
+        let decoder =
+            ComponentConstraints::new("decoder", DECODER_TRACE_WIDTH, DECODER_AUX_TRACE_WIDTH);
+
+        let stack = ComponentConstraints::new("stack", STACK_TRACE_WIDTH, STACK_AUX_TRACE_WIDTH)
+            .with_constraints(&stack::get_transition_constraint_degrees(), &[])
+            .with_assertions(stack::NUM_ASSERTIONS, stack::NUM_AUX_ASSERTIONS);
+
+        let range = ComponentConstraints::new(
+            "range",
+            RANGE_CHECK_TRACE_WIDTH,
+            RANGE_CHECK_AUX_TRACE_WIDTH,
+        )
+        .with_constraints(
+            &range::get_transition_constraint_degrees(),
+            &range::get_aux_transition_constraint_degrees(),
+        )
+        .with_assertions(range::NUM_ASSERTIONS, range::NUM_AUX_ASSERTIONS);
+
+        let chiplets = ComponentConstraints::new(
+            "chiplets",
+            CHIPLETS_WIDTH,
+            HASHER_AUX_TRACE_WIDTH + CHIPLETS_AUX_TRACE_WIDTH,
+        )
+        .with_constraints(&chiplets::get_transition_constraint_degrees(), &[]);
+
+        Self {
+            components: vec![system, decoder, stack, range, chiplets],
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the constraints of each component of the VM, in the order in which the columns of
+    /// the components appear in the execution trace.
+    pub fn components(&self) -> &[ComponentConstraints] {
+        &self.components
+    }
+
+    /// Returns the total number of columns in the main segment of the execution trace.
+    pub fn num_main_columns(&self) -> usize {
+        self.components.iter().map(|c| c.num_main_columns).sum()
+    }
+
+    /// Returns the total number of columns in the auxiliary segment of the execution trace.
+    pub fn num_aux_columns(&self) -> usize {
+        self.components.iter().map(|c| c.num_aux_columns).sum()
+    }
+
+    /// Returns the total number of transition constraints over the main trace segment.
+    pub fn num_main_constraints(&self) -> usize {
+        self.components.iter().map(|c| c.num_main_constraints).sum()
+    }
+
+    /// Returns the total number of transition constraints over the auxiliary trace segment.
+    pub fn num_aux_constraints(&self) -> usize {
+        self.components.iter().map(|c| c.num_aux_constraints).sum()
+    }
+
+    /// Returns the total number of boundary constraints over the main trace segment.
+    pub fn num_main_assertions(&self) -> usize {
+        self.components.iter().map(|c| c.num_main_assertions).sum()
+    }
+
+    /// Returns the total number of boundary constraints over the auxiliary trace segment.
+    pub fn num_aux_assertions(&self) -> usize {
+        self.components.iter().map(|c| c.num_aux_assertions).sum()
+    }
+
+    /// Returns the maximum degree of all transition constraints.
+    pub fn max_degree(&self) -> usize {
+        self.components
+            .iter()
+            .map(|c| c.max_main_degree.max(c.max_aux_degree))
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Returns the minimum blowup factor which proofs must use to accommodate the degree of the
+    /// transition constraints.
+    pub fn min_blowup_factor(&self) -> usize {
+        self.components.iter().map(|c| c.min_blowup_factor).max().unwrap_or(0)
+    }
+}
+
+impl Default for ConstraintReport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for ConstraintReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:<10} {:>8} {:>8} {:>12} {:>10} {:>12} {:>10}",
+            "component", "columns", "aux cols", "constraints", "max degree", "assertions", "blowup"
+        )?;
+        for c in self.components.iter() {
+            writeln!(
+                f,
+                "{:<10} {:>8} {:>8} {:>12} {:>10} {:>12} {:>10}",
+                c.name,
+                c.num_main_columns,
+                c.num_aux_columns,
+                format!("{} + {}", c.num_main_constraints, c.num_aux_constraints),
+                format!("{} / {}", c.max_main_degree, c.max_aux_degree),
+                format!("{} + {}", c.num_main_assertions, c.num_aux_assertions),
+                c.min_blowup_factor,
+            )?;
+        }
+        write!(
+            f,
+            "{:<10} {:>8} {:>8} {:>12} {:>10} {:>12} {:>10}",
+            "total",
+            self.num_main_columns(),
+            self.num_aux_columns(),
+            format!("{} + {}", self.num_main_constraints(), self.num_aux_constraints()),
+            self.max_degree(),
+            format!("{} + {}", self.num_main_assertions(), self.num_aux_assertions()),
+            self.min_blowup_factor(),
+        )
+    }
+}
+
+// COMPONENT CONSTRAINTS
+// ================================================================================================
+
+/// A summary of the constraints applied to the columns of a single component of the VM.
+///
+/// Counts and degrees are given separately for the main and the auxiliary segments of the trace.
+/// The degree of a constraint is its degree in the trace columns, with each periodic column it
+/// involves adding one to the degree.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ComponentConstraints {
+    /// The name of the component, e.g. "stack".
+    pub name: &'static str,
+    /// The number of columns of the component in the main trace segment.
+    pub num_main_columns: usize,
+    /// The number of columns of the component in the auxiliary trace segment.
+    pub num_aux_columns: usize,
+    /// The number of transition constraints over the main trace segment.
+    pub num_main_constraints: usize,
+    /// The number of transition constraints over the auxiliary trace segment.
+    pub num_aux_constraints: usize,
+    /// The maximum degree of the transition constraints over the main trace segment.
+    pub max_main_degree: usize,
+    /// The maximum degree of the transition constraints over the auxiliary trace segment.
+    pub max_aux_degree: usize,
+    /// The number of boundary constraints over the main trace segment.
+    pub num_main_assertions: usize,
+    /// The number of boundary constraints over the auxiliary trace segment.
+    pub num_aux_assertions: usize,
+    /// The minimum blowup factor needed to evaluate the transition constraints.
+    pub min_blowup_factor: usize,
+}
+
+impl ComponentConstraints {
+    fn new(name: &'static str, num_main_columns: usize, num_aux_columns: usize) -> Self {
+        Self {
+            name,
+            num_main_columns,
+            num_aux_columns,
+            num_main_constraints: 0,
+            num_aux_constraints: 0,
+            max_main_degree: 0,
+            max_aux_degree: 0,
+            num_main_assertions: 0,
+            num_aux_assertions: 0,
+            min_blowup_factor: 0,
+        }
+    }
+
+    fn with_constraints(
+        mut self,
+        main_degrees: &[TransitionConstraintDegree],
+        aux_degrees: &[TransitionConstraintDegree],
+    ) -> Self {
+        self.num_main_constraints = main_degrees.len();
+        self.num_aux_constraints = aux_degrees.len();
+        self.max_main_degree = main_degrees.iter().map(constraint_degree).max().unwrap_or(0);
+        self.max_aux_degree = aux_degrees.iter().map(constraint_degree).max().unwrap_or(0);
+        self.min_blowup_factor = main_degrees
+            .iter()
+            .chain(aux_degrees)
+            .map(TransitionConstraintDegree::min_blowup_factor)
+            .max()
+            .unwrap_or(0);
+        self
+    }
+
+    fn with_assertions(mut self, num_main_assertions: usize, num_aux_assertions: usize) -> Self {
+        self.num_main_assertions = num_main_assertions;
+        self.num_aux_assertions = num_aux_assertions;
+        self
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the degree of a transition constraint in terms of the trace columns.
+///
+/// [TransitionConstraintDegree] does not expose its degree directly, so it is recovered from the
+/// degree of the constraint polynomial over a trace of [REFERENCE_TRACE_LEN] rows. Each periodic
+/// column contributes a fraction of the trace length to the degree, which is rounded up to one.
+fn constraint_degree(degree: &TransitionConstraintDegree) -> usize {
+    degree
+        .get_evaluation_degree(REFERENCE_TRACE_LEN)
+        .div_ceil(REFERENCE_TRACE_LEN - 1)
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use vm_core::{StackInputs, StackOutputs};
+    use winter_air::{Air, BatchingMethod, FieldExtension, ProofOptions, TraceInfo};
+
+    use super::ConstraintReport;
+    use crate::{ProcessorAir, PublicInputs, trace::AUX_TRACE_RAND_ELEMENTS};
+
+    #[test]
+    fn report_matches_air() {
+        let report = ConstraintReport::new();
+
+        let trace_info = TraceInfo::new_multi_segment(
+            report.num_main_columns(),
+            report.num_aux_columns(),
+            AUX_TRACE_RAND_ELEMENTS,
+            1024,
+            vec![],
+        );
+        let pub_inputs =
+            PublicInputs::new(Default::default(), StackInputs::default(), StackOutputs::default());
+        let options = ProofOptions::new(
+            27,
+            8,
+            16,
+            FieldExtension::Quadratic,
+            8,
+            255,
+            BatchingMethod::Linear,
+            BatchingMethod::Linear,
+        );
+        let air = ProcessorAir::new(trace_info, pub_inputs, options);
+        let context = air.context();
+
+        assert_eq!(report.num_main_constraints(), context.num_main_transition_constraints());
+        assert_eq!(report.num_aux_constraints(), context.num_aux_transition_constraints());
+        assert_eq!(
+            report.num_main_assertions() + report.num_aux_assertions(),
+            context.num_assertions()
+        );
+        assert_eq!(report.min_blowup_factor(), context.ce_domain_size() / context.trace_len());
+    }
+}
@@ -0,0 +1,499 @@
+use core::{cmp, fmt, str::FromStr};
+
+use winter_air::{BatchingMethod, ProofOptions as WinterProofOptions};
+
+use super::{ConstraintReport, Felt, HashFunction, StarkField, trace::MIN_TRACE_LEN};
+
+// CONSTANTS
+// ================================================================================================
+
+/// The number of bits of query security above which grinding contributes to conjectured security.
+const GRINDING_CONTRIBUTION_FLOOR: u32 = 80;
+
+/// The maximum proximity parameter considered when estimating proven security in the list-decoding
+/// regime.
+const MAX_PROXIMITY_PARAMETER: usize = 1000;
+
+/// The number of points at which the trace polynomials are opened by the DEEP composition
+/// polynomial.
+const NUM_OPENINGS: f64 = 2.0;
+
+// SECURITY PRESET
+// ================================================================================================
+
+/// A security level which [ProvingOptions](crate::ProvingOptions) can be configured to target.
+///
+/// Conjectured security levels are estimated under Conjecture 1 of
+/// [ethSTARK](https://eprint.iacr.org/2021/582), while proven security levels are estimated using
+/// Theorems 2 and 3 of [eprint 2024/1553](https://eprint.iacr.org/2024/1553). Proofs targeting a
+/// proven security level need roughly three times as many queries, and are therefore much larger
+/// and slower to verify.
+///
+/// Proven security also degrades slowly as the execution trace grows, and proven presets are
+/// guaranteed to reach their security level for traces of up to [Self::MAX_PROVEN_TRACE_LEN]
+/// rows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SecurityPreset {
+    /// 96 bits of conjectured security.
+    Conjectured96,
+    /// 100 bits of conjectured security.
+    Conjectured100,
+    /// 128 bits of conjectured security.
+    Conjectured128,
+    /// 96 bits of proven security.
+    Proven96,
+    /// 100 bits of proven security.
+    Proven100,
+    /// 128 bits of proven security.
+    Proven128,
+}
+
+impl SecurityPreset {
+    /// The length of the longest execution trace for which proven presets reach their security
+    /// level.
+    pub const MAX_PROVEN_TRACE_LEN: usize = 1 << 30;
+
+    /// Returns the number of bits of security targeted by this preset.
+    pub const fn bits(&self) -> u32 {
+        match self {
+            Self::Conjectured96 | Self::Proven96 => 96,
+            Self::Conjectured100 | Self::Proven100 => 100,
+            Self::Conjectured128 | Self::Proven128 => 128,
+        }
+    }
+
+    /// Returns true if this preset targets a proven, rather than a conjectured, security level.
+    pub const fn is_proven(&self) -> bool {
+        matches!(self, Self::Proven96 | Self::Proven100 | Self::Proven128)
+    }
+}
+
+impl fmt::Display for SecurityPreset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_proven() {
+            write!(f, "{}bits-proven", self.bits())
+        } else {
+            write!(f, "{}bits", self.bits())
+        }
+    }
+}
+
+impl FromStr for SecurityPreset {
+    type Err = alloc::string::String;
+
+    /// Parses a preset from its name, e.g., `96bits` or `128bits-proven`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "96bits" => Ok(Self::Conjectured96),
+            "100bits" => Ok(Self::Conjectured100),
+            "128bits" => Ok(Self::Conjectured128),
+            "96bits-proven" => Ok(Self::Proven96),
+            "100bits-proven" => Ok(Self::Proven100),
+            "128bits-proven" => Ok(Self::Proven128),
+            other => Err(format!("{other} is not a valid security preset")),
+        }
+    }
+}
+
+// SECURITY ESTIMATE
+// ================================================================================================
+
+/// The estimated security, in bits, of proofs of execution generated with a given set of
+/// [ProvingOptions](crate::ProvingOptions).
+///
+/// Conjectured security depends only on the proof parameters and on the hash function, while
+/// proven security also depends on the length of the execution trace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SecurityEstimate {
+    conjectured: u32,
+    unique_decoding: u32,
+    list_decoding: u32,
+}
+
+impl SecurityEstimate {
+    /// Estimates the security of proofs of an execution trace with `trace_len` rows, generated with
+    /// the specified proof parameters and hash function.
+    ///
+    /// The trace length is rounded up to the next power of two, and to at least the minimum trace
+    /// length.
+    pub(crate) fn new(
+        options: &WinterProofOptions,
+        hash_fn: HashFunction,
+        trace_len: usize,
+    ) -> Self {
+        let trace_len = trace_len.next_power_of_two().max(MIN_TRACE_LEN);
+        let collision_resistance = hash_fn.collision_resistance();
+
+        // the constraints and committed polynomials are counted as done by the prover; the blowup
+        // factor is used as an upper bound on the number of constraint composition columns
+        let report = ConstraintReport::new();
+        let num_constraints = report.num_main_constraints()
+            + report.num_aux_constraints()
+            + report.num_main_assertions()
+            + report.num_aux_assertions();
+        let num_committed_polys =
+            report.num_main_columns() + report.num_aux_columns() + options.blowup_factor();
+        let params = ProvenParams {
+            options,
+            trace_len,
+            num_constraints,
+            num_committed_polys,
+        };
+
+        let list_decoding = (3..compute_upper_m(trace_len))
+            .map(|m| params.list_decoding_bits(m))
+            .max()
+            .expect("the proximity parameter range is not empty");
+
+        Self {
+            conjectured: conjectured_bits(options).min(collision_resistance),
+            unique_decoding: params.unique_decoding_bits().min(collision_resistance),
+            list_decoding: list_decoding.min(collision_resistance),
+        }
+    }
+
+    /// Returns the estimated conjectured security level (in bits).
+    pub const fn conjectured_bits(&self) -> u32 {
+        self.conjectured
+    }
+
+    /// Returns the estimated proven security level (in bits), i.e., the greater of the proven
+    /// security levels in the unique-decoding and list-decoding regimes.
+    pub const fn proven_bits(&self) -> u32 {
+        if self.unique_decoding > self.list_decoding {
+            self.unique_decoding
+        } else {
+            self.list_decoding
+        }
+    }
+
+    /// Returns the estimated proven security level (in bits) in the unique-decoding regime.
+    pub const fn unique_decoding_bits(&self) -> u32 {
+        self.unique_decoding
+    }
+
+    /// Returns the estimated proven security level (in bits) in the list-decoding regime.
+    pub const fn list_decoding_bits(&self) -> u32 {
+        self.list_decoding
+    }
+
+    /// Returns true if the estimated security reaches the level targeted by the specified preset.
+    pub const fn meets(&self, preset: SecurityPreset) -> bool {
+        if preset.is_proven() {
+            self.proven_bits() >= preset.bits()
+        } else {
+            self.conjectured >= preset.bits()
+        }
+    }
+}
+
+impl fmt::Display for SecurityEstimate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} bits conjectured, {} bits proven (unique decoding: {}, list decoding: {})",
+            self.conjectured,
+            self.proven_bits(),
+            self.unique_decoding,
+            self.list_decoding
+        )
+    }
+}
+
+// CONJECTURED SECURITY
+// ================================================================================================
+
+/// Returns the conjectured security level (in bits) of the protocol using Eq. (19) in
+/// [ethSTARK](https://eprint.iacr.org/2021/582), without accounting for the hash function.
+fn conjectured_bits(options: &WinterProofOptions) -> u32 {
+    let field_security = Felt::MODULUS_BITS * options.field_extension().degree();
+
+    let mut query_security = options.blowup_factor().ilog2() * options.num_queries() as u32;
+    // grinding only contributes to the security of proofs which are already reasonably secure
+    if query_security >= GRINDING_CONTRIBUTION_FLOOR {
+        query_security += options.grinding_factor();
+    }
+
+    cmp::min(field_security, query_security) - 1
+}
+
+// PROVEN SECURITY
+// ================================================================================================
+
+/// The parameters of the protocol which determine its proven security level.
+struct ProvenParams<'a> {
+    options: &'a WinterProofOptions,
+    trace_len: usize,
+    num_constraints: usize,
+    num_committed_polys: usize,
+}
+
+impl ProvenParams<'_> {
+    /// Returns the proven security level (in bits) of the protocol in the list-decoding regime,
+    /// for the proximity parameter `m`, using Theorem 2 in
+    /// [eprint 2024/1553](https://eprint.iacr.org/2024/1553).
+    fn list_decoding_bits(&self, m: usize) -> u32 {
+        let extension_field_bits = self.extension_field_bits();
+        let m = m as f64;
+        let rho = 1.0 / self.options.blowup_factor() as f64;
+        let alpha = (1.0 + 0.5 / m) * libm::sqrt(rho);
+        let max_degree = self.options.blowup_factor() as f64 + 1.0;
+        let lde_domain_size = self.lde_domain_size();
+        let trace_len = self.trace_len as f64;
+
+        // list size
+        let l = m / (rho - (2.0 * m / lde_domain_size));
+
+        // ALI soundness error
+        let ali =
+            extension_field_bits
+                - libm::log2(l)
+                - libm::log2(self.batching_factor(
+                    self.options.constraint_batching_method(),
+                    self.num_constraints,
+                ));
+
+        // DEEP soundness error, using the blowup factor as an upper bound on the constraint degree
+        let deep = extension_field_bits
+            - libm::log2(
+                l * l * (max_degree * (trace_len + NUM_OPENINGS - 1.0) + (trace_len - 1.0)),
+            );
+
+        // FRI commit-phase soundness error, of which only the dominant term is considered
+        let fri_commit = extension_field_bits
+            - libm::log2(
+                (libm::pow(m + 0.5, 7.0) / (3.0 * libm::pow(rho, 1.5)))
+                    * libm::pow(lde_domain_size, 2.0)
+                    * self.batching_factor(
+                        self.options.deep_poly_batching_method(),
+                        self.num_committed_polys,
+                    ),
+            );
+
+        // FRI query-phase soundness error
+        let fri_query = self.query_phase_bits(alpha);
+
+        round_by_round_bits(&[ali, deep, fri_commit, fri_query])
+    }
+
+    /// Returns the proven security level (in bits) of the protocol in the unique-decoding regime,
+    /// using Theorem 3 in [eprint 2024/1553](https://eprint.iacr.org/2024/1553).
+    fn unique_decoding_bits(&self) -> u32 {
+        let extension_field_bits = self.extension_field_bits();
+        let lde_domain_size = self.lde_domain_size();
+        let trace_len = self.trace_len as f64;
+        let rho_plus = (trace_len + NUM_OPENINGS) / lde_domain_size;
+        let alpha = (1.0 + rho_plus) * 0.5;
+        let max_degree = self.options.blowup_factor() as f64 + 1.0;
+
+        // ALI soundness error
+        let ali =
+            extension_field_bits
+                - libm::log2(self.batching_factor(
+                    self.options.constraint_batching_method(),
+                    self.num_constraints,
+                ));
+
+        // DEEP soundness error, using the blowup factor as an upper bound on the constraint degree
+        let deep = extension_field_bits
+            - libm::log2(max_degree * (trace_len + NUM_OPENINGS - 1.0) + (trace_len - 1.0));
+
+        // FRI commit-phase soundness error of the first round
+        let fri_commit = extension_field_bits
+            - libm::log2(
+                lde_domain_size
+                    * self.batching_factor(
+                        self.options.deep_poly_batching_method(),
+                        self.num_committed_polys,
+                    ),
+            );
+
+        // FRI commit-phase soundness error of the folding rounds
+        let fri_options = self.options.to_fri_options();
+        let fri_folding = if fri_options.num_fri_layers(lde_domain_size as usize) > 0 {
+            let folding_factor = fri_options.folding_factor() as f64;
+            extension_field_bits - libm::log2((folding_factor - 1.0) * (lde_domain_size + 1.0))
+        } else {
+            f64::INFINITY
+        };
+
+        // FRI query-phase soundness error
+        let fri_query = self.query_phase_bits(alpha);
+
+        round_by_round_bits(&[ali, deep, fri_commit, fri_folding, fri_query])
+    }
+
+    fn extension_field_bits(&self) -> f64 {
+        (Felt::MODULUS_BITS * self.options.field_extension().degree()) as f64
+    }
+
+    fn lde_domain_size(&self) -> f64 {
+        (self.trace_len * self.options.blowup_factor()) as f64
+    }
+
+    /// Returns the soundness loss factor of batching `num_items` items with the specified method.
+    ///
+    /// Only linear batching is free of loss; every other method (algebraic batching, as well as
+    /// Horner batching in later versions of winter-air) batches with the powers of a single
+    /// random element, and loses a factor of `num_items - 1`.
+    fn batching_factor(&self, method: BatchingMethod, num_items: usize) -> f64 {
+        match method {
+            BatchingMethod::Linear => 1.0,
+            _ => num_items as f64 - 1.0,
+        }
+    }
+
+    /// Returns the security (in bits) of the FRI query phase, in which each query passes with
+    /// probability at most `alpha`.
+    fn query_phase_bits(&self, alpha: f64) -> f64 {
+        self.options.grinding_factor() as f64
+            - libm::log2(libm::pow(alpha, self.options.num_queries() as f64))
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the round-by-round security level (in bits) given the security of each round.
+fn round_by_round_bits(rounds: &[f64]) -> u32 {
+    rounds.iter().copied().fold(f64::INFINITY, f64::min) as u32
+}
+
+/// Returns the upper bound of the proximity parameters considered for a trace of `trace_len` rows,
+/// i.e., the largest parameter for which the proof of Theorem 2 in
+/// [eprint 2024/1553](https://eprint.iacr.org/2024/1553) holds, capped at
+/// [MAX_PROXIMITY_PARAMETER].
+fn compute_upper_m(trace_len: usize) -> usize {
+    let h = trace_len as f64;
+    let m_max = libm::ceil(1.0 / (2.0 * (libm::sqrt((h + 2.0) / h) - 1.0)));
+    cmp::min(m_max as usize, MAX_PROXIMITY_PARAMETER)
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::{SecurityEstimate, SecurityPreset};
+    use crate::{ProvingOptions, trace::MIN_TRACE_LEN};
+
+    const PRESETS: [SecurityPreset; 6] = [
+        SecurityPreset::Conjectured96,
+        SecurityPreset::Conjectured100,
+        SecurityPreset::Conjectured128,
+        SecurityPreset::Proven96,
+        SecurityPreset::Proven100,
+        SecurityPreset::Proven128,
+    ];
+
+    #[test]
+    fn presets_meet_their_security_level() {
+        let trace_lens = (MIN_TRACE_LEN.ilog2()..=SecurityPreset::MAX_PROVEN_TRACE_LEN.ilog2())
+            .step_by(4)
+            .chain([SecurityPreset::MAX_PROVEN_TRACE_LEN.ilog2()])
+            .map(|log_len| 1 << log_len);
+
+        for trace_len in trace_lens {
+            for preset in PRESETS {
+                for recursive in [false, true] {
+                    let options = ProvingOptions::with_security_preset(preset, recursive);
+                    let estimate = options.estimate_security(trace_len);
+                    assert!(
+                        estimate.meets(preset),
+                        "{preset} not met at {trace_len} rows: {estimate}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn conjectured_security_matches_presets() {
+        let estimate =
+            |options: ProvingOptions| options.estimate_security(1 << 20).conjectured_bits();
+
+        assert_eq!(estimate(ProvingOptions::with_96_bit_security(false)), 96);
+        assert_eq!(estimate(ProvingOptions::with_96_bit_security(true)), 96);
+        assert_eq!(estimate(ProvingOptions::with_128_bit_security(false)), 128);
+        assert_eq!(estimate(ProvingOptions::with_128_bit_security_rpx()), 128);
+
+        // security is capped by the collision resistance of the hash function
+        let options = ProvingOptions::new(
+            29,
+            8,
+            16,
+            crate::FieldExtension::Quadratic,
+            8,
+            255,
+            crate::HashFunction::Blake3_192,
+        );
+        assert_eq!(estimate(options), 96);
+    }
+
+    #[test]
+    fn remainder_degree_does_not_affect_security() {
+        let options = |remainder_max_degree| {
+            ProvingOptions::new(
+                27,
+                8,
+                16,
+                crate::FieldExtension::Quadratic,
+                4,
+                remainder_max_degree,
+                crate::HashFunction::Rpo256,
+            )
+        };
+
+        for trace_len in [1 << 10, 1 << 20] {
+            assert_eq!(
+                options(7).estimate_security(trace_len),
+                options(127).estimate_security(trace_len)
+            );
+        }
+    }
+
+    #[test]
+    fn proven_security_decreases_with_trace_length() {
+        let options = ProvingOptions::with_security_preset(SecurityPreset::Proven100, false);
+        let short = options.estimate_security(1 << 10);
+        let long = options.estimate_security(1 << 30);
+
+        assert_eq!(short.conjectured_bits(), long.conjectured_bits());
+        assert!(short.proven_bits() > long.proven_bits());
+
+        // the trace length is rounded up to the next power of two
+        assert_eq!(options.estimate_security(1000), short);
+        assert_eq!(options.estimate_security(1), options.estimate_security(MIN_TRACE_LEN));
+    }
+
+    #[test]
+    fn conjectured_presets_are_not_proven() {
+        let estimate = ProvingOptions::with_96_bit_security(false).estimate_security(1 << 20);
+        assert!(estimate.meets(SecurityPreset::Conjectured96));
+        assert!(!estimate.meets(SecurityPreset::Conjectured100));
+        assert!(!estimate.meets(SecurityPreset::Proven96));
+    }
+
+    #[test]
+    fn preset_names_round_trip() {
+        for preset in PRESETS {
+            assert_eq!(preset.to_string().parse::<SecurityPreset>(), Ok(preset));
+        }
+        assert!("64bits".parse::<SecurityPreset>().is_err());
+    }
+
+    #[test]
+    fn estimate_display() {
+        let estimate = SecurityEstimate {
+            conjectured: 96,
+            unique_decoding: 38,
+            list_decoding: 53,
+        };
+        assert_eq!(
+            estimate.to_string(),
+            "96 bits conjectured, 53 bits proven (unique decoding: 38, list decoding: 53)"
+        );
+    }
+}
@@ -236,6 +236,8 @@ impl MainTrace {
         ([b6, b5, b4] == [ZERO, ONE, ZERO])||
         // U32ADD3 or U32MADD
         ([b6, b5, b4, b3, b2] == [ONE, ZERO, ZERO, ONE, ONE]) ||
+        // U64ADD, U64SUB or U32ROTL
+        ([b6, b5, b4, b3, b0] == [ONE, ZERO, ZERO, ZERO, ONE]) ||
         // SPLIT or LOOP block
         ([e0, b3, b2, b1] == [ONE, ZERO, ONE, ZERO]) ||
         // REPEAT
@@ -262,7 +264,9 @@ impl MainTrace {
         // u32SPLIT 100_1000
         ([b6, b5, b4, b3, b2, b1, b0] == [ONE, ZERO, ZERO, ONE, ZERO, ZERO, ZERO]) ||
         // PUSH i.e., 101_1011
-        ([b6, b5, b4, b3, b2, b1, b0] == [ONE, ZERO, ONE, ONE, ZERO, ONE, ONE])
+        ([b6, b5, b4, b3, b2, b1, b0] == [ONE, ZERO, ONE, ONE, ZERO, ONE, ONE]) ||
+        // CTX i.e., 101_1101
+        ([b6, b5, b4, b3, b2, b1, b0] == [ONE, ZERO, ONE, ONE, ONE, ZERO, ONE])
     }
 
     // STACK COLUMNS
@@ -0,0 +1,287 @@
+use vm_core::{Felt, Operation::*};
+
+use super::{
+    BasicBlockBuilder,
+    field_ops::append_pow2_op,
+    u32_ops::{compute_lt, compute_max_and_min},
+};
+
+// Signed 32-bit integers are represented by their two's complement, i.e., a value a in the range
+// [-2^31, 2^31) is represented by the u32 value a mod 2^32, and its sign is the most significant
+// bit of this value.
+
+/// The most significant bit of a 32-bit value, i.e., its sign bit in two's complement.
+const SIGN_BIT: u32 = 1 << 31;
+
+// ARITHMETIC OPERATIONS
+// ================================================================================================
+
+/// Translates i32neg assembly instruction to VM operations.
+///
+/// The value is subtracted from zero, and the underflow flag is dropped.
+///
+/// This takes 4 VM cycles.
+pub fn i32neg(block_builder: &mut BasicBlockBuilder) {
+    append_neg(block_builder);
+}
+
+/// Translates i32abs assembly instruction to VM operations.
+///
+/// The absolute value of -2^31 does not fit into 32 bits, and thus wraps around to -2^31.
+///
+/// This takes 11 VM cycles.
+pub fn i32abs(block_builder: &mut BasicBlockBuilder) {
+    // [a, ...] -> [-a, a, ...]
+    block_builder.push_op(Dup0);
+    append_neg(block_builder);
+
+    // [-a, a, ...] -> [s, -a, a, ...]
+    block_builder.push_op(Dup1);
+    append_sign(block_builder);
+
+    // keep -a if a is negative, and a otherwise
+    block_builder.push_ops([CSwap, Drop]);
+}
+
+/// Translates i32div assembly instruction to VM operations.
+///
+/// The quotient is rounded towards zero, and the quotient of -2^31 by -1 wraps around to -2^31.
+///
+/// This takes 43 VM cycles.
+pub fn i32div(block_builder: &mut BasicBlockBuilder) {
+    append_abs_divmod(block_builder);
+
+    // [r, q, sa, sb, ...] -> [sa != sb, q, ...]
+    #[rustfmt::skip]
+    block_builder.push_ops([
+        Drop,
+        MovUp2, MovUp2, Eq, Not,
+    ]);
+    append_conditional_neg(block_builder);
+}
+
+/// Translates i32mod assembly instruction to VM operations.
+///
+/// The remainder has the sign of the dividend, i.e., a = b * (a / b) + (a mod b), where the
+/// quotient is rounded towards zero.
+///
+/// This takes 43 VM cycles.
+pub fn i32mod(block_builder: &mut BasicBlockBuilder) {
+    append_abs_divmod(block_builder);
+
+    // [r, q, sa, sb, ...] -> [sa, r, ...]
+    #[rustfmt::skip]
+    block_builder.push_ops([
+        Swap, Drop,
+        MovUp2, Drop,
+        Swap,
+    ]);
+    append_conditional_neg(block_builder);
+}
+
+/// Translates i32divmod assembly instruction to VM operations.
+///
+/// The quotient and the remainder are computed as for the i32div and i32mod instructions.
+///
+/// This takes 54 VM cycles.
+pub fn i32divmod(block_builder: &mut BasicBlockBuilder) {
+    append_abs_divmod(block_builder);
+
+    // [r, q, sa, sb, ...] -> [r', q, sa, sb, ...], where r' has the sign of a
+    block_builder.push_op(Dup2);
+    append_conditional_neg(block_builder);
+
+    // [r', q, sa, sb, ...] -> [sa != sb, q, r', ...]
+    #[rustfmt::skip]
+    block_builder.push_ops([
+        Swap,
+        MovUp3, MovUp3, Eq, Not,
+    ]);
+    append_conditional_neg(block_builder);
+
+    // put the remainder on top of the stack as for the u32divmod instruction
+    block_builder.push_op(Swap);
+}
+
+// BITWISE OPERATIONS
+// ================================================================================================
+
+/// Translates i32shr assembly instruction to VM operations.
+///
+/// The shift is arithmetic, i.e., the value is shifted in its sign bit. This is computed as a
+/// logical shift of the value whose bits are flipped if it is negative, as a >> b = !(!a >> b) for
+/// negative a.
+///
+/// This takes 30 VM cycles.
+pub fn i32shr(block_builder: &mut BasicBlockBuilder) {
+    // [b, a, ...] -> [m, a, b, ...], where m = 2^32 - 1 if a is negative, and 0 otherwise
+    block_builder.push_ops([Swap, Dup0]);
+    append_sign(block_builder);
+    block_builder.push_ops([Push(Felt::from(u32::MAX)), Mul]);
+
+    // [m, a, b, ...] -> [2^b, a XOR m, m, ...]
+    #[rustfmt::skip]
+    block_builder.push_ops([
+        Dup0, MovDn3,
+        U32xor,
+        Swap,
+    ]);
+    append_pow2_op(block_builder);
+
+    // [2^b, a XOR m, m, ...] -> [((a XOR m) >> b) XOR m, ...]
+    block_builder.push_ops([U32div, Drop, U32xor]);
+}
+
+// COMPARISON OPERATIONS
+// ================================================================================================
+
+/// Translates i32lt assembly instruction to VM operations.
+///
+/// Flipping the sign bits of both values maps [-2^31, 2^31) onto [0, 2^32) while preserving the
+/// order, and thus the values are compared as u32 values after their sign bits are flipped.
+///
+/// This takes 11 VM cycles.
+pub fn i32lt(block_builder: &mut BasicBlockBuilder) {
+    append_flip_sign_bits(block_builder);
+    block_builder.push_op(Swap);
+    compute_lt(block_builder);
+}
+
+/// Translates i32lte assembly instruction to VM operations.
+///
+/// This takes 11 VM cycles.
+pub fn i32lte(block_builder: &mut BasicBlockBuilder) {
+    append_flip_sign_bits(block_builder);
+    compute_lt(block_builder);
+
+    // Flip the final results to get the lte results.
+    block_builder.push_op(Not);
+}
+
+/// Translates i32gt assembly instruction to VM operations.
+///
+/// This takes 10 VM cycles.
+pub fn i32gt(block_builder: &mut BasicBlockBuilder) {
+    append_flip_sign_bits(block_builder);
+    compute_lt(block_builder);
+}
+
+/// Translates i32gte assembly instruction to VM operations.
+///
+/// This takes 12 VM cycles.
+pub fn i32gte(block_builder: &mut BasicBlockBuilder) {
+    append_flip_sign_bits(block_builder);
+    block_builder.push_op(Swap);
+    compute_lt(block_builder);
+
+    // Flip the final results to get the gte results.
+    block_builder.push_op(Not);
+}
+
+/// Translates i32min assembly instruction to VM operations.
+///
+/// This takes 18 VM cycles.
+pub fn i32min(block_builder: &mut BasicBlockBuilder) {
+    append_flip_sign_bits(block_builder);
+    compute_max_and_min(block_builder);
+
+    // Drop the max and keep the min
+    block_builder.push_op(Drop);
+    append_flip_sign_bit(block_builder);
+}
+
+/// Translates i32max assembly instruction to VM operations.
+///
+/// This takes 19 VM cycles.
+pub fn i32max(block_builder: &mut BasicBlockBuilder) {
+    append_flip_sign_bits(block_builder);
+    compute_max_and_min(block_builder);
+
+    // Drop the min and keep the max
+    block_builder.push_ops([Swap, Drop]);
+    append_flip_sign_bit(block_builder);
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Appends the operations negating the value at the top of the stack, i.e., computing
+/// (2^32 - a) mod 2^32.
+///
+/// VM cycles: 4
+fn append_neg(block_builder: &mut BasicBlockBuilder) {
+    block_builder.push_ops([Pad, Swap, U32sub, Drop]);
+}
+
+/// Appends the operations replacing the value at the top of the stack with its sign bit, i.e., 1
+/// if the value is negative and 0 otherwise.
+///
+/// VM cycles: 3
+fn append_sign(block_builder: &mut BasicBlockBuilder) {
+    block_builder.push_ops([Push(Felt::from(SIGN_BIT)), U32div, Drop]);
+}
+
+/// Appends the operations mutating the stack from `[s, a, ...]` into `[c, ...]`, where c is -a if
+/// s is 1, and a if s is 0.
+///
+/// VM cycles: 9
+fn append_conditional_neg(block_builder: &mut BasicBlockBuilder) {
+    // [s, a, ...] -> [-a, a, s, ...]
+    block_builder.push_ops([Swap, Dup0]);
+    append_neg(block_builder);
+
+    // [-a, a, s, ...] -> [c, ...]
+    block_builder.push_ops([MovUp2, CSwap, Drop]);
+}
+
+/// Appends the operations mutating the stack from `[a, ...]` into `[|a|, s, ...]`, where s is
+/// the sign bit of a.
+///
+/// VM cycles: 13
+fn append_abs_with_sign(block_builder: &mut BasicBlockBuilder) {
+    // [a, ...] -> [-a, a, ...]
+    block_builder.push_op(Dup0);
+    append_neg(block_builder);
+
+    // [-a, a, ...] -> [s, -a, a, s, ...]
+    block_builder.push_op(Dup1);
+    append_sign(block_builder);
+    block_builder.push_ops([Dup0, MovDn3]);
+
+    // [s, -a, a, s, ...] -> [|a|, s, ...]
+    block_builder.push_ops([CSwap, Drop]);
+}
+
+/// Appends the operations mutating the stack from `[b, a, ...]` into `[r, q, sa, sb, ...]`, where
+/// q and r are the quotient and the remainder of the division of |a| by |b|, and sa and sb are the
+/// sign bits of a and b respectively. Fails if b is 0.
+///
+/// VM cycles: 29
+fn append_abs_divmod(block_builder: &mut BasicBlockBuilder) {
+    // [b, a, ...] -> [|b|, sb, a, ...]
+    append_abs_with_sign(block_builder);
+
+    // [|b|, sb, a, ...] -> [|a|, sa, |b|, sb, ...]
+    block_builder.push_op(MovUp2);
+    append_abs_with_sign(block_builder);
+
+    // [|a|, sa, |b|, sb, ...] -> [r, q, sa, sb, ...]
+    block_builder.push_ops([MovUp2, U32div]);
+}
+
+/// Appends the operations flipping the sign bit of the value at the top of the stack.
+///
+/// VM cycles: 3
+fn append_flip_sign_bit(block_builder: &mut BasicBlockBuilder) {
+    block_builder.push_ops([Push(Felt::from(SIGN_BIT)), U32add, Drop]);
+}
+
+/// Appends the operations mutating the stack from `[b, a, ...]` into `[a', b', ...]`, where a' and
+/// b' are a and b with their sign bits flipped.
+///
+/// VM cycles: 7
+fn append_flip_sign_bits(block_builder: &mut BasicBlockBuilder) {
+    append_flip_sign_bit(block_builder);
+    block_builder.push_op(Swap);
+    append_flip_sign_bit(block_builder);
+}
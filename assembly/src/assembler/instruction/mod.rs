@@ -1,3 +1,4 @@
+use alloc::string::ToString;
 use core::ops::RangeBounds;
 
 use miette::miette;
@@ -11,6 +12,7 @@ mod crypto_ops;
 mod env_ops;
 mod ext2_ops;
 mod field_ops;
+mod i32_ops;
 mod mem_ops;
 mod procedures;
 mod u32_ops;
@@ -25,17 +27,18 @@ impl Assembler {
         block_builder: &mut BasicBlockBuilder,
         proc_ctx: &mut ProcedureContext,
     ) -> Result<Option<MastNodeId>, AssemblyError> {
-        // if the assembler is in debug mode, start tracking the instruction about to be executed;
-        // this will allow us to map the instruction to the sequence of operations which were
-        // executed as a part of this instruction.
-        if self.in_debug_mode() {
+        // if the assembler emits AsmOp decorators, start tracking the instruction about to be
+        // executed; this will allow us to map the instruction to the sequence of operations which
+        // were executed as a part of this instruction.
+        let track_instructions = self.decorators().asm_ops;
+        if track_instructions {
             block_builder.track_instruction(instruction, proc_ctx)?;
         }
 
         let result = self.compile_instruction_impl(instruction, block_builder, proc_ctx)?;
 
         // compute and update the cycle count of the instruction which just finished executing
-        if self.in_debug_mode() {
+        if track_instructions {
             block_builder.set_instruction_cycle_count();
         }
 
@@ -185,10 +188,14 @@ impl Assembler {
             Instruction::U32ShlImm(v) => u32_ops::u32shl(block_builder, Some(v.expect_value()))?,
             Instruction::U32Shr => u32_ops::u32shr(block_builder, None)?,
             Instruction::U32ShrImm(v) => u32_ops::u32shr(block_builder, Some(v.expect_value()))?,
-            Instruction::U32Rotl => u32_ops::u32rotl(block_builder, None)?,
-            Instruction::U32RotlImm(v) => u32_ops::u32rotl(block_builder, Some(v.expect_value()))?,
-            Instruction::U32Rotr => u32_ops::u32rotr(block_builder, None)?,
-            Instruction::U32RotrImm(v) => u32_ops::u32rotr(block_builder, Some(v.expect_value()))?,
+            Instruction::U32Rotl => u32_ops::u32rotl(block_builder, None, self.isa_version())?,
+            Instruction::U32RotlImm(v) => {
+                u32_ops::u32rotl(block_builder, Some(v.expect_value()), self.isa_version())?
+            },
+            Instruction::U32Rotr => u32_ops::u32rotr(block_builder, None, self.isa_version())?,
+            Instruction::U32RotrImm(v) => {
+                u32_ops::u32rotr(block_builder, Some(v.expect_value()), self.isa_version())?
+            },
             Instruction::U32Popcnt => u32_ops::u32popcnt(block_builder),
             Instruction::U32Clz => u32_ops::u32clz(block_builder),
             Instruction::U32Ctz => u32_ops::u32ctz(block_builder),
@@ -201,6 +208,30 @@ impl Assembler {
             Instruction::U32Min => u32_ops::u32min(block_builder),
             Instruction::U32Max => u32_ops::u32max(block_builder),
 
+            // ----- u64 operations ---------------------------------------------------------------
+            Instruction::U64OverflowingAdd => {
+                self.ensure_supported_operation(&U64add, instruction, proc_ctx)?;
+                block_builder.push_op(U64add)
+            },
+            Instruction::U64OverflowingSub => {
+                self.ensure_supported_operation(&U64sub, instruction, proc_ctx)?;
+                block_builder.push_op(U64sub)
+            },
+
+            // ----- i32 operations ---------------------------------------------------------------
+            Instruction::I32Neg => i32_ops::i32neg(block_builder),
+            Instruction::I32Abs => i32_ops::i32abs(block_builder),
+            Instruction::I32Div => i32_ops::i32div(block_builder),
+            Instruction::I32Mod => i32_ops::i32mod(block_builder),
+            Instruction::I32DivMod => i32_ops::i32divmod(block_builder),
+            Instruction::I32Lt => i32_ops::i32lt(block_builder),
+            Instruction::I32Lte => i32_ops::i32lte(block_builder),
+            Instruction::I32Gt => i32_ops::i32gt(block_builder),
+            Instruction::I32Gte => i32_ops::i32gte(block_builder),
+            Instruction::I32Min => i32_ops::i32min(block_builder),
+            Instruction::I32Max => i32_ops::i32max(block_builder),
+            Instruction::I32Shr => i32_ops::i32shr(block_builder),
+
             // ----- stack manipulation -----------------------------------------------------------
             Instruction::Drop => block_builder.push_op(Drop),
             Instruction::DropW => block_builder.push_ops([Drop; 4]),
@@ -308,10 +339,21 @@ impl Assembler {
             Instruction::Sdepth => block_builder.push_op(SDepth),
             Instruction::Caller => env_ops::caller(block_builder, proc_ctx, instruction.span())?,
             Instruction::Clk => block_builder.push_op(Clk),
+            Instruction::Ctx => {
+                self.ensure_supported_operation(&Ctx, instruction, proc_ctx)?;
+                block_builder.push_op(Ctx)
+            },
             Instruction::AdvPipe => block_builder.push_op(Pipe),
             Instruction::AdvPush(n) => adv_ops::adv_push(block_builder, n.expect_value())?,
             Instruction::AdvLoadW => block_builder.push_op(AdvPopW),
 
+            Instruction::MemInit(_) => {
+                return Err(AssemblyError::UnanalyzedOperation {
+                    span: instruction.span(),
+                    source_file: proc_ctx.source_manager().get(instruction.span().source_id()).ok(),
+                    kind: "reference to a data constant",
+                });
+            },
             Instruction::MemStream => block_builder.push_op(MStream),
             Instruction::Locaddr(v) => env_ops::locaddr(block_builder, v.expect_value(), proc_ctx)?,
             Instruction::MemLoad => mem_ops::mem_read(block_builder, proc_ctx, None, false, true)?,
@@ -398,15 +440,18 @@ impl Assembler {
             Instruction::HornerExt => block_builder.push_op(HornerExt),
 
             // ----- exec/call instructions -------------------------------------------------------
-            Instruction::Exec(callee) => {
-                return self
-                    .invoke(
-                        InvokeKind::Exec,
-                        callee,
-                        proc_ctx,
-                        block_builder.mast_forest_builder_mut(),
-                    )
-                    .map(Into::into);
+            Instruction::Exec(callee) => match self.resolve_intrinsic(callee, proc_ctx)? {
+                Some(intrinsic) => block_builder.push_ops(intrinsic.ops().iter().copied()),
+                None => {
+                    return self
+                        .invoke(
+                            InvokeKind::Exec,
+                            callee,
+                            proc_ctx,
+                            block_builder.mast_forest_builder_mut(),
+                        )
+                        .map(Into::into);
+                },
             },
             Instruction::Call(callee) => {
                 return self
@@ -436,12 +481,14 @@ impl Assembler {
             Instruction::Breakpoint => {
                 if self.in_debug_mode() {
                     block_builder.push_op(Noop);
-                    block_builder.track_instruction(instruction, proc_ctx)?;
+                    if self.decorators().asm_ops {
+                        block_builder.track_instruction(instruction, proc_ctx)?;
+                    }
                 }
             },
 
             Instruction::Debug(options) => {
-                if self.in_debug_mode() {
+                if self.decorators().debug {
                     block_builder.push_decorator(Decorator::Debug(
                         options.clone().try_into().expect("unresolved constant"),
                     ))?;
@@ -455,12 +502,34 @@ impl Assembler {
 
             // ----- trace instruction ------------------------------------------------------------
             Instruction::Trace(trace_id) => {
-                block_builder.push_decorator(Decorator::Trace(trace_id.expect_value()))?;
+                if self.decorators().trace {
+                    block_builder.push_decorator(Decorator::Trace(trace_id.expect_value()))?;
+                }
             },
         }
 
         Ok(None)
     }
+
+    /// Returns an error if `op`, which implements `instruction`, is not a part of the version of
+    /// the instruction set targeted by the assembler.
+    fn ensure_supported_operation(
+        &self,
+        op: &Operation,
+        instruction: &Span<Instruction>,
+        proc_ctx: &ProcedureContext,
+    ) -> Result<(), AssemblyError> {
+        if self.isa_version().supports_operation(op) {
+            return Ok(());
+        }
+
+        Err(AssemblyError::UnsupportedInstruction {
+            span: instruction.span(),
+            source_file: proc_ctx.source_manager().get(instruction.span().source_id()).ok(),
+            instruction: instruction.to_string(),
+            isa_version: self.isa_version(),
+        })
+    }
 }
 
 // HELPER FUNCTIONS
@@ -3,9 +3,14 @@ use vm_core::mast::MastNodeId;
 
 use super::{Assembler, BasicBlockBuilder, Operation};
 use crate::{
-    AssemblyError, RpoDigest,
-    assembler::{ProcedureContext, mast_forest_builder::MastForestBuilder},
-    ast::{InvocationTarget, InvokeKind},
+    AssemblyError, RpoDigest, Spanned,
+    assembler::{
+        CallerInfo, ProcedureContext, ResolvedTarget,
+        intrinsics::{Intrinsic, find_intrinsic},
+        mast_forest_builder::MastForestBuilder,
+        module_graph::WrappedModule,
+    },
+    ast::{InvocationTarget, InvokeKind, QualifiedProcedureName},
 };
 
 /// Procedure Invocation
@@ -32,6 +37,42 @@ impl Assembler {
         }
     }
 
+    /// Returns the intrinsic implemented by the procedure invoked via `exec` by `callee`, if the
+    /// procedure is an intrinsic which can be lowered for the targeted version of the instruction
+    /// set.
+    ///
+    /// Intrinsics are matched on the fully-qualified path of the procedure the invocation resolves
+    /// to, and only procedures provided by a library are considered: procedures compiled from
+    /// source as part of the assembled code are always invoked, even if their path is the one of an
+    /// intrinsic, so that user code cannot be replaced by the operations of an intrinsic.
+    ///
+    /// Returns `None` for all other procedures, including the ones which are only known by their
+    /// MAST root, as their paths cannot be determined.
+    pub(super) fn resolve_intrinsic(
+        &self,
+        callee: &InvocationTarget,
+        proc_ctx: &ProcedureContext,
+    ) -> Result<Option<&'static Intrinsic>, AssemblyError> {
+        let caller = CallerInfo {
+            span: callee.span(),
+            module: proc_ctx.id().module,
+            kind: InvokeKind::Exec,
+        };
+        let gid = match self.module_graph.resolve_target(&caller, callee)? {
+            ResolvedTarget::Phantom(_) => return Ok(None),
+            ResolvedTarget::Exact { gid } | ResolvedTarget::Resolved { gid, .. } => gid,
+        };
+        if !matches!(self.module_graph[gid.module], WrappedModule::Info(_)) {
+            return Ok(None);
+        }
+
+        let name = QualifiedProcedureName::new(
+            self.module_graph[gid.module].path().clone(),
+            self.module_graph.get_procedure_unsafe(gid).name().clone(),
+        );
+        Ok(find_intrinsic(&name).filter(|intrinsic| intrinsic.is_supported_by(self.isa_version())))
+    }
+
     /// Creates a new DYN block for the dynamic code execution and return.
     pub(super) fn dynexec(
         &self,
@@ -65,6 +106,7 @@ impl Assembler {
                 proc_ctx,
                 block_builder.mast_forest_builder_mut(),
             )?;
+            block_builder.mast_forest_builder_mut().mark_referenced(proc_body_id);
             // Note: it's ok to `unwrap()` here since `proc_body_id` was returned from
             // `mast_forest_builder`
             block_builder
@@ -0,0 +1,80 @@
+use alloc::string::ToString;
+
+use vm_core::{IsaVersion, Operation};
+
+use crate::ast::QualifiedProcedureName;
+
+// INTRINSICS
+// ================================================================================================
+
+/// A procedure of the standard library which the assembler lowers to a sequence of operations,
+/// rather than to an invocation of the procedure, when `exec`-ing it.
+///
+/// Intrinsics allow the standard library to benefit from operations added in newer versions of
+/// the instruction set without breaking programs which target older versions: the MASM
+/// implementation of an intrinsic only uses operations available in all versions, and calls to it
+/// are lowered only when the targeted version supports all of the operations of the intrinsic.
+#[derive(Debug)]
+pub struct Intrinsic {
+    /// The fully-qualified path of the procedure.
+    path: &'static str,
+    /// The operations the invocations of the procedure are lowered to.
+    ops: &'static [Operation],
+}
+
+impl Intrinsic {
+    /// Returns the operations the invocations of this intrinsic are lowered to.
+    pub fn ops(&self) -> &'static [Operation] {
+        self.ops
+    }
+
+    /// Returns true if all operations of this intrinsic are part of `isa_version`.
+    pub fn is_supported_by(&self, isa_version: IsaVersion) -> bool {
+        self.ops.iter().all(|op| isa_version.supports_operation(op))
+    }
+}
+
+/// Returns the intrinsic implemented by the procedure with the specified fully-qualified path, if
+/// any.
+pub fn find_intrinsic(path: &QualifiedProcedureName) -> Option<&'static Intrinsic> {
+    let path = path.to_string();
+    INTRINSICS.iter().find(|intrinsic| intrinsic.path == path)
+}
+
+// INTRINSIC DEFINITIONS
+// ================================================================================================
+
+const INTRINSICS: &[Intrinsic] = &[
+    // ----- std::math::u64 -----------------------------------------------------------------------
+    Intrinsic {
+        path: "std::math::u64::overflowing_add",
+        ops: &[Operation::U64add],
+    },
+    Intrinsic {
+        path: "std::math::u64::wrapping_add",
+        ops: &[Operation::U64add, Operation::Drop],
+    },
+    Intrinsic {
+        path: "std::math::u64::overflowing_sub",
+        ops: &[Operation::U64sub],
+    },
+    Intrinsic {
+        path: "std::math::u64::wrapping_sub",
+        ops: &[Operation::U64sub, Operation::Drop],
+    },
+    Intrinsic {
+        path: "std::math::u64::lt",
+        ops: &[Operation::U64sub, Operation::MovDn2, Operation::Drop, Operation::Drop],
+    },
+    Intrinsic {
+        path: "std::math::u64::gt",
+        ops: &[
+            Operation::MovUp3,
+            Operation::MovUp3,
+            Operation::U64sub,
+            Operation::MovDn2,
+            Operation::Drop,
+            Operation::Drop,
+        ],
+    },
+];
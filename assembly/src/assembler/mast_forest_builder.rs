@@ -24,6 +24,10 @@ use crate::{AssemblyError, Library};
 /// Constant that decides how many operation batches disqualify a procedure from inlining.
 const PROCEDURE_INLINING_THRESHOLD: usize = 32;
 
+/// Nesting depth above which the JOIN nodes of a sequence of nodes are flattened before the
+/// sequence is joined into a tree (see [`MastForestBuilder::join_nodes`]).
+const REBALANCING_DEPTH_THRESHOLD: u32 = 32;
+
 // MAST FOREST BUILDER
 // ================================================================================================
 
@@ -56,12 +60,18 @@ pub struct MastForestBuilder {
     /// The reverse mapping of `node_id_by_fingerprint`. This map caches the fingerprints of all
     /// nodes (for performance reasons).
     hash_by_node_id: BTreeMap<MastNodeId, MastNodeFingerprint>,
+    /// The nesting depth of all nodes, i.e., the number of nodes on the longest path from a node
+    /// to a leaf of the MAST.
+    depth_by_node_id: BTreeMap<MastNodeId, u32>,
     /// A map of decorator fingerprints to their corresponding positions in the MAST forest.
     decorator_id_by_fingerprint: BTreeMap<DecoratorFingerprint, DecoratorId>,
     /// A set of IDs for basic blocks which have been merged into a bigger basic blocks. This is
     /// used as a candidate set of nodes that may be eliminated if the are not referenced by any
     /// other node in the forest and are not a root of any procedure.
     merged_basic_block_ids: BTreeSet<MastNodeId>,
+    /// The IDs of the procedures referenced by `procref` instructions, which may be invoked
+    /// dynamically, and hence must not be pruned from the MAST forest.
+    referenced_procedure_ids: BTreeSet<MastNodeId>,
     /// A MastForest that contains vendored libraries, it's used to find precompiled procedures and
     /// copy their subtrees instead of inserting external nodes.
     vendored_mast: Arc<MastForest>,
@@ -103,6 +113,27 @@ impl MastForestBuilder {
 
         (self.mast_forest, id_remappings)
     }
+
+    /// Removes the nodes which are not reachable from the specified roots, nor from the procedures
+    /// referenced via `procref`, and returns the resulting MAST forest.
+    ///
+    /// The roots are specified by their IDs in this builder, and are updated to their IDs in the
+    /// returned MAST forest.
+    pub fn build_reachable(mut self, roots: &mut [MastNodeId]) -> MastForest {
+        let referenced_procedure_ids = core::mem::take(&mut self.referenced_procedure_ids);
+        let (mut mast_forest, id_remappings) = self.build();
+        let remap = |node_id: &MastNodeId| *id_remappings.get(node_id).unwrap_or(node_id);
+
+        let reachable_roots: Vec<_> =
+            roots.iter().chain(referenced_procedure_ids.iter()).map(remap).collect();
+        let reachable_id_remappings = mast_forest.remove_unreachable_nodes(&reachable_roots);
+        for root in roots.iter_mut() {
+            let node_id = remap(root);
+            *root = *reachable_id_remappings.get(&node_id).unwrap_or(&node_id);
+        }
+
+        mast_forest
+    }
 }
 
 /// Takes the set of MAST node ids (all basic blocks) that were merged as part of the assembly
@@ -241,15 +272,26 @@ impl MastForestBuilder {
 
         Ok(())
     }
+
+    /// Marks the procedure with the specified body as referenced by a `procref` instruction, so
+    /// that it is retained when unreachable nodes are removed from the MAST forest.
+    pub fn mark_referenced(&mut self, proc_body_id: MastNodeId) {
+        self.referenced_procedure_ids.insert(proc_body_id);
+    }
 }
 
 // ------------------------------------------------------------------------------------------------
 /// Joining nodes
 impl MastForestBuilder {
     /// Builds a tree of `JOIN` operations to combine the provided MAST node IDs.
+    ///
+    /// The tree is balanced, so its depth grows logarithmically with the number of nodes. Nodes
+    /// which are themselves deeply nested JOIN nodes are rebalanced together with the rest of the
+    /// sequence (see [`Self::flatten_deep_joins`]).
     pub fn join_nodes(&mut self, node_ids: Vec<MastNodeId>) -> Result<MastNodeId, AssemblyError> {
         debug_assert!(!node_ids.is_empty(), "cannot combine empty MAST node id list");
 
+        let node_ids = self.flatten_deep_joins(node_ids);
         let mut node_ids = self.merge_contiguous_basic_blocks(node_ids)?;
 
         // build a binary tree of blocks joining them using JOIN blocks
@@ -275,6 +317,54 @@ impl MastForestBuilder {
         Ok(node_ids.remove(0))
     }
 
+    /// If any of the JOIN nodes in the provided list is nested deeper than
+    /// [`REBALANCING_DEPTH_THRESHOLD`], replaces all JOIN nodes in the list with their children,
+    /// recursively, so that the list can be joined into a balanced tree again.
+    ///
+    /// Executing a JOIN node is equivalent to executing its children in sequence, so this does not
+    /// change the semantics of the list. However, it keeps the depth of the MAST bounded when long
+    /// sequences of nodes are built up over several procedures, e.g., by procedures which `exec`
+    /// each other in a chain: without rebalancing, each procedure in the chain would add a level of
+    /// nesting. Since the rebalanced tree is only as deep as needed to join all the children, it
+    /// can grow by many levels before it needs to be rebalanced again.
+    ///
+    /// JOIN nodes with decorators are kept as they are, so that the decorators are not lost. Each
+    /// JOIN node is replaced by its children at most once: further occurrences of the same node,
+    /// e.g., in a DAG in which a procedure executes another one twice, are kept as they are, as
+    /// flattening them again would grow the list exponentially with the depth of the DAG.
+    fn flatten_deep_joins(&self, node_ids: Vec<MastNodeId>) -> Vec<MastNodeId> {
+        let undecorated_join = |node_id: MastNodeId| match &self.mast_forest[node_id] {
+            MastNode::Join(join)
+                if join.before_enter().is_empty() && join.after_exit().is_empty() =>
+            {
+                Some(join)
+            },
+            _ => None,
+        };
+        let is_deep = |node_id: &MastNodeId| {
+            self.depth_by_node_id[node_id] > REBALANCING_DEPTH_THRESHOLD
+                && undecorated_join(*node_id).is_some()
+        };
+        if !node_ids.iter().any(is_deep) {
+            return node_ids;
+        }
+
+        let mut flattened = Vec::with_capacity(node_ids.len());
+        let mut flattened_joins = BTreeSet::new();
+        let mut worklist: Vec<MastNodeId> = node_ids.into_iter().rev().collect();
+        while let Some(node_id) = worklist.pop() {
+            match undecorated_join(node_id) {
+                Some(join) if flattened_joins.insert(node_id) => {
+                    worklist.push(join.second());
+                    worklist.push(join.first());
+                },
+                _ => flattened.push(node_id),
+            }
+        }
+
+        flattened
+    }
+
     /// Returns a list of [`MastNodeId`]s built from merging the contiguous basic blocks
     /// found in the provided list of [`MastNodeId`]s.
     fn merge_contiguous_basic_blocks(
@@ -397,11 +487,17 @@ impl MastForestBuilder {
             // node already exists in the forest; return previously assigned id
             Ok(*node_id)
         } else {
+            let mut children = Vec::new();
+            node.append_children_to(&mut children);
+            let depth =
+                children.iter().map(|child| self.depth_by_node_id[child]).max().unwrap_or(0);
+
             let new_node_id = self.mast_forest.add_node(node).map_err(|source| {
                 AssemblyError::forest_error("assembler failed to add new node", source)
             })?;
             self.node_id_by_fingerprint.insert(node_fingerprint, new_node_id);
             self.hash_by_node_id.insert(new_node_id, node_fingerprint);
+            self.depth_by_node_id.insert(new_node_id, depth + 1);
 
             Ok(new_node_id)
         }
@@ -1,29 +1,44 @@
-use alloc::{collections::BTreeMap, sync::Arc, vec::Vec};
+use alloc::{
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet},
+    string::ToString,
+    sync::Arc,
+    vec::Vec,
+};
 
 use basic_block_builder::BasicBlockOrDecorators;
 use mast_forest_builder::MastForestBuilder;
 use module_graph::{ProcedureWrapper, WrappedModule};
 use vm_core::{
-    DecoratorList, Felt, Kernel, Operation, Program, WORD_SIZE,
+    DecoratorKinds, DecoratorList, Felt, IsaVersion, Kernel, Operation, Program, WORD_SIZE,
     crypto::hash::RpoDigest,
     debuginfo::SourceSpan,
-    mast::{DecoratorId, MastNodeId},
+    mast::{DecoratorId, MastForest, MastNodeId, SourceMap},
 };
 
 use crate::{
-    AssemblyError, Compile, CompileOptions, LibraryNamespace, LibraryPath, SourceManager, Spanned,
-    ast::{self, Export, InvocationTarget, InvokeKind, ModuleKind, QualifiedProcedureName},
+    AssemblyError, Compile, CompileOptions, LibraryNamespace, LibraryPath, ResourceLimits,
+    SourceManager, Spanned,
+    ast::{
+        self, AttributeSet, Export, InvocationTarget, InvokeKind, ModuleKind,
+        QualifiedProcedureName,
+    },
     diagnostics::Report,
-    library::{KernelLibrary, Library},
-    sema::SemanticAnalysisError,
+    library::{KernelLibrary, Library, ModuleInfo, ProcedureInfo},
+    sema::{
+        AnalysisContext, DEPRECATED_ATTRIBUTE, STACK_EFFECT_ATTRIBUTE, SemanticAnalysisError,
+        StackEffect, VerifyStackEffect, deprecation_reason,
+    },
 };
 
 mod basic_block_builder;
 mod id;
 mod instruction;
+mod intrinsics;
 mod mast_forest_builder;
 mod module_graph;
 mod procedure;
+mod stack_depth;
 
 #[cfg(test)]
 mod tests;
@@ -34,10 +49,12 @@ mod mast_forest_merger_tests;
 use self::{
     basic_block_builder::BasicBlockBuilder,
     module_graph::{CallerInfo, ModuleGraph, ResolvedTarget},
+    stack_depth::StackDepthAnalysis,
 };
 pub use self::{
     id::{GlobalProcedureIndex, ModuleIndex},
     procedure::{Procedure, ProcedureContext},
+    stack_depth::{StackDepth, StackDepthReport},
 };
 
 // ASSEMBLER
@@ -64,15 +81,29 @@ pub use self::{
 #[derive(Clone)]
 pub struct Assembler {
     /// The source manager to use for compilation and source location information
-    source_manager: Arc<dyn SourceManager>,
+    source_manager: Arc<dyn SourceManager + Send + Sync>,
     /// The global [ModuleGraph] for this assembler.
     module_graph: ModuleGraph,
     /// Whether to treat warning diagnostics as errors
     warnings_as_errors: bool,
+    /// The limits on the resources which may be consumed when parsing and analyzing a module
+    limits: ResourceLimits,
+    /// The features enabled when conditionally compiling modules from source
+    features: BTreeSet<Arc<str>>,
     /// Whether the assembler enables extra debugging information.
     in_debug_mode: bool,
+    /// The kinds of decorators emitted by the assembler, if set explicitly rather than derived
+    /// from the debug mode.
+    decorators: Option<DecoratorKinds>,
     /// Collects libraries that can be used during assembly to vendor procedures.
     vendored_libraries: BTreeMap<RpoDigest, Library>,
+    /// The MAST forests of all libraries added to the assembler, used to analyze their procedures.
+    library_forests: Vec<Arc<MastForest>>,
+    /// The version of the instruction set targeted by the assembled code.
+    isa_version: IsaVersion,
+    /// Whether nodes which are unreachable from the entrypoint of a program, or from the exports
+    /// of a library, are removed from its MAST forest.
+    pruning: bool,
 }
 
 impl Default for Assembler {
@@ -83,8 +114,14 @@ impl Default for Assembler {
             source_manager,
             module_graph,
             warnings_as_errors: false,
+            limits: ResourceLimits::UNLIMITED,
+            features: BTreeSet::new(),
             in_debug_mode: false,
+            decorators: None,
             vendored_libraries: BTreeMap::new(),
+            library_forests: Vec::new(),
+            isa_version: IsaVersion::CURRENT,
+            pruning: false,
         }
     }
 }
@@ -93,19 +130,28 @@ impl Default for Assembler {
 /// Constructors
 impl Assembler {
     /// Start building an [Assembler]
-    pub fn new(source_manager: Arc<dyn SourceManager>) -> Self {
+    pub fn new(source_manager: Arc<dyn SourceManager + Send + Sync>) -> Self {
         let module_graph = ModuleGraph::new(source_manager.clone());
         Self {
             source_manager,
             module_graph,
             warnings_as_errors: false,
+            limits: ResourceLimits::UNLIMITED,
+            features: BTreeSet::new(),
             in_debug_mode: false,
+            decorators: None,
             vendored_libraries: BTreeMap::new(),
+            library_forests: Vec::new(),
+            isa_version: IsaVersion::CURRENT,
+            pruning: false,
         }
     }
 
     /// Start building an [`Assembler`] with a kernel defined by the provided [KernelLibrary].
-    pub fn with_kernel(source_manager: Arc<dyn SourceManager>, kernel_lib: KernelLibrary) -> Self {
+    pub fn with_kernel(
+        source_manager: Arc<dyn SourceManager + Send + Sync>,
+        kernel_lib: KernelLibrary,
+    ) -> Self {
         let (kernel, kernel_module, _) = kernel_lib.into_parts();
         let module_graph = ModuleGraph::with_kernel(source_manager.clone(), kernel, kernel_module);
         Self {
@@ -123,17 +169,88 @@ impl Assembler {
         self
     }
 
+    /// Sets the limits on the resources which may be consumed when parsing and analyzing modules
+    /// from source.
+    ///
+    /// This is intended for use when assembling untrusted source code. Note that these limits do
+    /// not apply to modules which were parsed before being provided to the assembler.
+    pub fn with_resource_limits(mut self, limits: ResourceLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Enables the given features when conditionally compiling modules from source.
+    ///
+    /// Procedures and operations annotated with `@cfg(feature = "name")` are only assembled if
+    /// `name` is one of the enabled features. Note that, like resource limits, features do not
+    /// apply to modules which were parsed before being provided to the assembler.
+    pub fn with_features<I, S>(mut self, features: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<Arc<str>>,
+    {
+        self.features.extend(features.into_iter().map(Into::into));
+        self
+    }
+
     /// Puts the assembler into the debug mode.
     pub fn with_debug_mode(mut self, yes: bool) -> Self {
         self.in_debug_mode = yes;
         self
     }
 
+    /// Sets the kinds of decorators emitted by the assembler, overriding the ones implied by the
+    /// debug mode (see [Assembler::decorators]).
+    ///
+    /// Decorators of other kinds are also removed from the vendored libraries included in the
+    /// assembled code. Since decorators don't contribute to the MAST roots of procedures, the
+    /// selection doesn't affect the hashes of assembled programs and libraries, which allows
+    /// release and development artifacts to be built from the same source. Note however that
+    /// `breakpoint` instructions are only assembled in debug mode, and do affect the MAST roots.
+    pub fn with_decorators(mut self, kinds: DecoratorKinds) -> Self {
+        self.decorators = Some(kinds);
+        self
+    }
+
+    /// Sets the version of the instruction set targeted by the assembler.
+    ///
+    /// Invocations of the standard library procedures which can be lowered to operations of the
+    /// targeted version are replaced by these operations, and instructions which are not part of
+    /// the targeted version are rejected. Programs assembled by this assembler record the targeted
+    /// version. Defaults to [IsaVersion::CURRENT].
+    pub fn with_isa_version(mut self, isa_version: IsaVersion) -> Self {
+        self.isa_version = isa_version;
+        self
+    }
+
+    /// Sets whether the MAST nodes which are not reachable from the entrypoint of an assembled
+    /// program, or from the exported procedures of an assembled library, are removed from its MAST
+    /// forest, along with their decorators.
+    ///
+    /// Procedures referenced by `procref` instructions are retained, but procedures whose MAST
+    /// roots are otherwise provided to `dynexec` or `dyncall` instructions are not, and hence must
+    /// be available from another library at runtime. Defaults to `false`.
+    pub fn with_pruning(mut self, yes: bool) -> Self {
+        self.pruning = yes;
+        self
+    }
+
     /// Sets the debug mode flag of the assembler
     pub fn set_debug_mode(&mut self, yes: bool) {
         self.in_debug_mode = yes;
     }
 
+    /// Sets the kinds of decorators emitted by the assembler (see [Assembler::with_decorators]).
+    pub fn set_decorators(&mut self, kinds: DecoratorKinds) {
+        self.decorators = Some(kinds);
+    }
+
+    /// Sets the limits on the resources which may be consumed when parsing and analyzing modules
+    /// from source.
+    pub fn set_resource_limits(&mut self, limits: ResourceLimits) {
+        self.limits = limits;
+    }
+
     /// Adds `module` to the module graph of the assembler.
     ///
     /// The given module must be a library module, or an error will be returned.
@@ -163,7 +280,12 @@ impl Assembler {
     /// The given module must be a library module, or an error will be returned.
     #[inline]
     pub fn add_module(&mut self, module: impl Compile) -> Result<ModuleIndex, Report> {
-        self.add_module_with_options(module, CompileOptions::for_library())
+        let options = CompileOptions {
+            limits: self.limits,
+            features: self.features.clone(),
+            ..CompileOptions::for_library()
+        };
+        self.add_module_with_options(module, options)
     }
 
     /// Adds `module` to the module graph of the assembler, using the provided options.
@@ -246,6 +368,7 @@ impl Assembler {
         self.module_graph
             .add_compiled_modules(library.as_ref().module_infos())
             .map_err(Report::from)?;
+        self.library_forests.push(library.as_ref().mast_forest().clone());
         Ok(())
     }
 
@@ -257,6 +380,34 @@ impl Assembler {
         Ok(self)
     }
 
+    /// Adds the interface definition `interface` (e.g. the contents of a `.masi` file) to provide
+    /// a module for the compilation.
+    ///
+    /// The interface declares the procedures of the module by name and MAST root only (see
+    /// [ModuleInfo::from_interface]), and, as with [Self::add_library], all calls to these
+    /// procedures will be compiled down to a [`vm_core::mast::ExternalNode`]. The library which
+    /// implements the interface is expected to be loaded in the processor at execution time, and
+    /// can be checked against the interface via [Library::check_interface].
+    pub fn add_interface(&mut self, interface: impl Compile) -> Result<(), Report> {
+        let options = CompileOptions {
+            limits: self.limits,
+            features: self.features.clone(),
+            ..CompileOptions::for_library()
+        };
+        let module = interface.compile_with_options(&self.source_manager, options)?;
+        let module_info = ModuleInfo::from_interface(&module)?;
+        self.module_graph.add_compiled_modules([module_info]).map_err(Report::from)?;
+        Ok(())
+    }
+
+    /// Adds the interface definition `interface` to provide a module for the compilation.
+    ///
+    /// See [`Self::add_interface`] for more detailed information.
+    pub fn with_interface(mut self, interface: impl Compile) -> Result<Self, Report> {
+        self.add_interface(interface)?;
+        Ok(self)
+    }
+
     /// Adds a compiled library from which procedures will be vendored into the assembled code.
     ///
     /// Vendoring in this context means that when a procedure from this library is invoked from the
@@ -287,11 +438,45 @@ impl Assembler {
         self.warnings_as_errors
     }
 
+    /// Returns the limits on the resources which may be consumed when parsing and analyzing
+    /// modules from source.
+    pub fn resource_limits(&self) -> ResourceLimits {
+        self.limits
+    }
+
+    /// Returns the features enabled when conditionally compiling modules from source.
+    pub fn features(&self) -> impl Iterator<Item = &str> + '_ {
+        self.features.iter().map(|feature| feature.as_ref())
+    }
+
     /// Returns true if this assembler was instantiated in debug mode.
     pub fn in_debug_mode(&self) -> bool {
         self.in_debug_mode
     }
 
+    /// Returns the kinds of decorators emitted by this assembler.
+    ///
+    /// Unless set explicitly via [Assembler::with_decorators], all kinds of decorators are emitted
+    /// in debug mode, and only [Decorator::Trace](vm_core::Decorator::Trace) decorators otherwise.
+    pub fn decorators(&self) -> DecoratorKinds {
+        match self.decorators {
+            Some(kinds) => kinds,
+            None if self.in_debug_mode => DecoratorKinds::ALL,
+            None => DecoratorKinds::TRACE_ONLY,
+        }
+    }
+
+    /// Returns true if this assembler removes the MAST nodes which are unreachable from assembled
+    /// programs and libraries (see [Assembler::with_pruning]).
+    pub fn prunes_unreachable_nodes(&self) -> bool {
+        self.pruning
+    }
+
+    /// Returns the version of the instruction set targeted by this assembler.
+    pub fn isa_version(&self) -> IsaVersion {
+        self.isa_version
+    }
+
     /// Returns a reference to the kernel for this assembler.
     ///
     /// If the assembler was instantiated without a kernel, the internal kernel will be empty.
@@ -300,7 +485,7 @@ impl Assembler {
     }
 
     /// Returns a link to the source manager used by this assembler.
-    pub fn source_manager(&self) -> Arc<dyn SourceManager> {
+    pub fn source_manager(&self) -> Arc<dyn SourceManager + Send + Sync> {
         self.source_manager.clone()
     }
 
@@ -311,50 +496,273 @@ impl Assembler {
     }
 }
 
+// ------------------------------------------------------------------------------------------------
+/// Analysis
+impl Assembler {
+    /// Parses and semantically analyzes `module` using the provided `options`, without lowering
+    /// it to MAST.
+    ///
+    /// The resulting [ast::Module] is the intermediate artifact of assembly: it can be inspected,
+    /// transformed by custom passes, or serialized and handed to an [Assembler] in another
+    /// process. Analyzed modules are accepted anywhere an [impl Compile](Compile) is expected,
+    /// e.g. [Assembler::assemble_library], and are lowered without being analyzed again.
+    pub fn analyze_module(
+        &self,
+        module: impl Compile,
+        options: CompileOptions,
+    ) -> Result<Box<ast::Module>, Report> {
+        module.compile_with_options(&self.source_manager, options)
+    }
+
+    /// Checks that `modules` form a valid library, without lowering them to MAST.
+    ///
+    /// The modules are parsed, semantically analyzed, and the references between them and the
+    /// modules known to this assembler are resolved, so every error which
+    /// [Assembler::assemble_library] would report is reported here too, except for those raised
+    /// during code generation (e.g. exceeded resource limits). This assembler is left unmodified.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if parsing, semantic analysis or name resolution of the modules fails.
+    pub fn check_library(
+        &self,
+        modules: impl IntoIterator<Item = impl Compile>,
+    ) -> Result<(), Report> {
+        let options = CompileOptions {
+            kind: ModuleKind::Library,
+            warnings_as_errors: self.warnings_as_errors,
+            path: None,
+            limits: self.limits,
+            features: self.features.clone(),
+        };
+        self.clone().add_modules_with_options(modules, options)?;
+
+        Ok(())
+    }
+
+    /// Checks that all `.masm` files under `dir` form a valid library in the namespace
+    /// `namespace`, without lowering them to MAST.
+    ///
+    /// The modules are named as described in [Assembler::add_modules_from_dir], and are checked
+    /// as described in [Assembler::check_library].
+    #[cfg(feature = "std")]
+    pub fn check_dir(
+        &self,
+        namespace: crate::LibraryNamespace,
+        dir: &std::path::Path,
+    ) -> Result<(), Report> {
+        let modules = crate::parser::read_modules_from_dir(namespace, dir, &self.source_manager)?;
+        self.check_library(modules)
+    }
+
+    /// Checks that `source` is a valid program, without lowering it to MAST.
+    ///
+    /// This is the counterpart of [Assembler::check_library] for executable modules: in addition
+    /// to the checks done there, the program is required to have an entrypoint.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if parsing, semantic analysis or name resolution of the program fails,
+    /// or if the program doesn't have an entrypoint.
+    pub fn check_program(&self, source: impl Compile) -> Result<(), Report> {
+        let options = CompileOptions {
+            kind: ModuleKind::Executable,
+            warnings_as_errors: self.warnings_as_errors,
+            path: Some(LibraryPath::from(LibraryNamespace::Exec)),
+            limits: self.limits,
+            features: self.features.clone(),
+        };
+
+        let program = source.compile_with_options(&self.source_manager, options)?;
+        assert!(program.is_executable());
+        if !program.procedures().any(|p| p.is_main()) {
+            return Err(SemanticAnalysisError::MissingEntrypoint.into());
+        }
+
+        let mut module_graph = self.module_graph.clone();
+        module_graph.add_ast_module(program)?;
+
+        Ok(())
+    }
+
+    /// Statically analyzes the stack depth of the procedures of the program `source`, and of the
+    /// procedures of the modules added to this assembler in source form.
+    ///
+    /// The procedures of the libraries added with [Assembler::add_library] are analyzed from
+    /// their MAST, while the invocations of procedures which are only known by their MAST root,
+    /// e.g. those of an interface added with [Assembler::add_interface], cannot be analyzed, see
+    /// [StackDepth] for details. This assembler is left unmodified.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if parsing, semantic analysis or name resolution of the program fails.
+    pub fn analyze_stack_depth(&self, source: impl Compile) -> Result<StackDepthReport, Report> {
+        let options = CompileOptions {
+            kind: ModuleKind::Executable,
+            warnings_as_errors: self.warnings_as_errors,
+            path: Some(LibraryPath::from(LibraryNamespace::Exec)),
+            limits: self.limits,
+            features: self.features.clone(),
+        };
+
+        let program = source.compile_with_options(&self.source_manager, options)?;
+        let mut module_graph = self.module_graph.clone();
+        module_graph.add_ast_module(program)?;
+
+        Ok(StackDepthAnalysis::new(&module_graph, &self.library_forests).analyze())
+    }
+
+    /// Returns the completions valid at byte `offset` of `source`, using the symbols of `module`
+    /// and the modules known to this assembler.
+    ///
+    /// Here `source` is the current text of `module`, e.g. the contents of an editor buffer, which
+    /// is expected to be incomplete at the time completions are requested, while `module` is the
+    /// most recent successfully analyzed version of it. Procedures of imported modules can only be
+    /// offered if those modules were previously added to this assembler.
+    pub fn completions(
+        &self,
+        module: &ast::Module,
+        source: &str,
+        offset: usize,
+    ) -> Vec<crate::completion::Completion> {
+        use crate::completion::{self, Completion, CompletionContext, CompletionKind};
+
+        let context = CompletionContext::at(source, offset);
+        completion::complete(module, context, |path| match self.module_graph.find_module(path) {
+            Some(WrappedModule::Ast(module)) => completion::exported_procedures(&module),
+            Some(WrappedModule::Info(module)) => module
+                .procedures()
+                .map(|(_, procedure)| Completion {
+                    label: procedure.name.to_string(),
+                    kind: CompletionKind::Procedure,
+                    detail: Some(format!("{path}::{}", procedure.name)),
+                    docs: None,
+                })
+                .collect(),
+            None => Vec::new(),
+        })
+    }
+}
+
 // ------------------------------------------------------------------------------------------------
 /// Compilation/Assembly
 impl Assembler {
     /// Shared code used by both [Assembler::assemble_library()] and [Assembler::assemble_kernel()].
+    ///
+    /// The modules are split into groups which do not depend on one another, and each group is
+    /// compiled into its own MAST forest. When the `concurrent` feature is enabled, the groups are
+    /// compiled in parallel. The resulting forests are then merged in group order, so the output
+    /// is the same regardless of whether, or how, the groups were compiled in parallel.
     fn assemble_common(
         mut self,
         modules: impl IntoIterator<Item = impl Compile>,
         options: CompileOptions,
     ) -> Result<Library, Report> {
-        let mut mast_forest_builder = MastForestBuilder::new(self.vendored_libraries.values())?;
-
         let ast_module_indices = self.add_modules_with_options(modules, options)?;
+        let groups = self.module_graph.independent_module_groups(&ast_module_indices);
+        let attributes = self.exported_procedure_attributes(&ast_module_indices);
+
+        let compile_group =
+            |modules: Vec<ModuleIndex>| self.clone().compile_library_modules(&modules);
+        #[cfg(feature = "concurrent")]
+        let compiled = {
+            use rayon::prelude::*;
 
-        let mut exports = {
-            let mut exports = BTreeMap::new();
+            groups.into_par_iter().map(compile_group).collect::<Vec<_>>()
+        };
+        #[cfg(not(feature = "concurrent"))]
+        let compiled = groups.into_iter().map(compile_group).collect::<Vec<_>>();
+        // Collect the results in group order, so that the error reported is deterministic
+        let mut compiled = compiled.into_iter().collect::<Result<Vec<_>, Report>>()?;
+
+        if compiled.len() == 1 {
+            let (mast_forest, exports) = compiled.pop().unwrap();
+            let library = Library::new(mast_forest.into(), exports)?;
+            return Ok(library.with_procedure_attributes(attributes));
+        }
 
-            for module_idx in ast_module_indices {
-                // Note: it is safe to use `unwrap_ast()` here, since all of the modules contained
-                // in `ast_module_indices` are in AST form by definition.
-                let ast_module = self.module_graph[module_idx].unwrap_ast().clone();
+        let (mast_forest, root_map) = MastForest::merge(compiled.iter().map(|(forest, _)| forest))
+            .map_err(|err| {
+                AssemblyError::forest_error("failed to merge the MAST forests of a library", err)
+            })?;
+        let exports = compiled
+            .into_iter()
+            .enumerate()
+            .flat_map(|(forest_idx, (_, exports))| {
+                let root_map = &root_map;
+                exports.into_iter().map(move |(fqn, node_id)| {
+                    let node_id = root_map
+                        .map_root(forest_idx, &node_id)
+                        .expect("exported procedure is not a root of its MAST forest");
+                    (fqn, node_id)
+                })
+            })
+            .collect();
 
-                for (proc_idx, fqn) in ast_module.exported_procedures() {
-                    let gid = module_idx + proc_idx;
-                    self.compile_subgraph(gid, &mut mast_forest_builder)?;
+        let library = Library::new(mast_forest.into(), exports)?;
+        Ok(library.with_procedure_attributes(attributes))
+    }
 
-                    let proc_root_node_id = mast_forest_builder
-                        .get_procedure(gid)
-                        .expect("compilation succeeded but root not found in cache")
-                        .body_node_id();
-                    exports.insert(fqn, proc_root_node_id);
+    /// Returns the attributes attached to the exported procedures of the specified AST modules.
+    fn exported_procedure_attributes(
+        &self,
+        modules: &[ModuleIndex],
+    ) -> Vec<(QualifiedProcedureName, AttributeSet)> {
+        let mut attributes = Vec::new();
+        for &module_idx in modules {
+            let ast_module = self.module_graph[module_idx].unwrap_ast();
+            for (proc_idx, fqn) in ast_module.exported_procedures() {
+                if let Some(attrs) = ast_module[proc_idx].attributes() {
+                    attributes.push((fqn, attrs.clone()));
                 }
             }
+        }
+        attributes
+    }
 
-            exports
-        };
+    /// Compiles the exported procedures of the specified AST modules into a new [MastForest],
+    /// returning it along with the node IDs of the exports.
+    fn compile_library_modules(
+        mut self,
+        modules: &[ModuleIndex],
+    ) -> Result<(MastForest, BTreeMap<QualifiedProcedureName, MastNodeId>), Report> {
+        let mut mast_forest_builder = MastForestBuilder::new(self.vendored_libraries.values())?;
+
+        let mut exports = BTreeMap::new();
+        for &module_idx in modules {
+            // Note: it is safe to use `unwrap_ast()` here, since all of the modules contained
+            // in `modules` are in AST form by definition.
+            let ast_module = self.module_graph[module_idx].unwrap_ast().clone();
+
+            for (proc_idx, fqn) in ast_module.exported_procedures() {
+                let gid = module_idx + proc_idx;
+                self.compile_subgraph(gid, &mut mast_forest_builder)?;
+
+                let proc_root_node_id = mast_forest_builder
+                    .get_procedure(gid)
+                    .expect("compilation succeeded but root not found in cache")
+                    .body_node_id();
+                exports.insert(fqn, proc_root_node_id);
+            }
+        }
+
+        if self.pruning {
+            let mut roots: Vec<_> = exports.values().copied().collect();
+            let mut mast_forest = mast_forest_builder.build_reachable(&mut roots);
+            exports.values_mut().zip(roots).for_each(|(node_id, root)| *node_id = root);
+            self.retain_selected_decorators(&mut mast_forest);
+            return Ok((mast_forest, exports));
+        }
 
-        let (mast_forest, id_remappings) = mast_forest_builder.build();
+        let (mut mast_forest, id_remappings) = mast_forest_builder.build();
         for (_proc_name, node_id) in exports.iter_mut() {
             if let Some(&new_node_id) = id_remappings.get(node_id) {
                 *node_id = new_node_id;
             }
         }
+        self.retain_selected_decorators(&mut mast_forest);
 
-        Ok(Library::new(mast_forest.into(), exports)?)
+        Ok((mast_forest, exports))
     }
 
     /// Assembles a set of modules into a [Library].
@@ -370,6 +778,8 @@ impl Assembler {
             kind: ModuleKind::Library,
             warnings_as_errors: self.warnings_as_errors,
             path: None,
+            limits: self.limits,
+            features: self.features.clone(),
         };
         self.assemble_common(modules, options)
     }
@@ -384,6 +794,8 @@ impl Assembler {
             kind: ModuleKind::Kernel,
             warnings_as_errors: self.warnings_as_errors,
             path: Some(LibraryPath::from(LibraryNamespace::Kernel)),
+            limits: self.limits,
+            features: self.features.clone(),
         };
         let library = self.assemble_common([module], options)?;
         Ok(library.try_into()?)
@@ -392,6 +804,9 @@ impl Assembler {
     /// Compiles the provided module into a [`Program`]. The resulting program can be executed on
     /// Miden VM.
     ///
+    /// The static analysis of the program's MAST forest (see [`Program::static_analysis`]) is
+    /// attached to the returned program.
+    ///
     /// # Errors
     ///
     /// Returns an error if parsing or compilation of the specified program fails, or if the source
@@ -401,6 +816,8 @@ impl Assembler {
             kind: ModuleKind::Executable,
             warnings_as_errors: self.warnings_as_errors,
             path: Some(LibraryPath::from(LibraryNamespace::Exec)),
+            limits: self.limits,
+            features: self.features.clone(),
         };
 
         let program = source.compile_with_options(&self.source_manager, options)?;
@@ -427,14 +844,73 @@ impl Assembler {
             .body_node_id();
 
         // in case the node IDs changed, update the entrypoint ID to the new value
-        let (mast_forest, id_remappings) = mast_forest_builder.build();
-        let entry_node_id = *id_remappings.get(&entry_node_id).unwrap_or(&entry_node_id);
+        let (mut mast_forest, entry_node_id) = if self.pruning {
+            let mut roots = [entry_node_id];
+            let mast_forest = mast_forest_builder.build_reachable(&mut roots);
+            (mast_forest, roots[0])
+        } else {
+            let (mast_forest, id_remappings) = mast_forest_builder.build();
+            (mast_forest, *id_remappings.get(&entry_node_id).unwrap_or(&entry_node_id))
+        };
+        self.retain_selected_decorators(&mut mast_forest);
 
         Ok(Program::with_kernel(
             mast_forest.into(),
             entry_node_id,
             self.module_graph.kernel().clone(),
-        ))
+        )
+        .with_isa_version(self.isa_version)
+        .with_static_analysis())
+    }
+
+    /// Compiles the provided module into a [`Program`] without decorators, along with the
+    /// [`SourceMap`] of the program.
+    ///
+    /// The program is assembled with all kinds of decorators, and the source map is extracted from
+    /// the locations of its AsmOp decorators before all of its decorators are removed. The nodes
+    /// which only differed by their decorators are then deduplicated. Since decorators don't
+    /// contribute to the MAST roots of procedures, the program has the same hash
+    /// as if it were assembled without the source map, but the execution of its `debug` and
+    /// `trace` instructions is skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if parsing or compilation of the specified program fails, or if the source
+    /// doesn't have an entrypoint.
+    pub fn assemble_program_with_source_map(
+        self,
+        source: impl Compile,
+    ) -> Result<(Program, SourceMap), Report> {
+        let source_manager = self.source_manager.clone();
+        let program = self.with_decorators(DecoratorKinds::ALL).assemble_program(source)?;
+
+        let mut mast_forest = (**program.mast_forest()).clone();
+        let source_map = SourceMap::from_forest(&mast_forest, source_manager.as_ref());
+        mast_forest.strip_decorators();
+
+        // blocks which only differed by their decorators are identical now, so the stripped forest
+        // is deduplicated by merging it on its own
+        let (mast_forest, node_map) = MastForest::merge([&mast_forest]).map_err(|err| {
+            AssemblyError::forest_error("failed to deduplicate the MAST forest of a program", err)
+        })?;
+        let entrypoint = node_map
+            .map_node(0, &program.entrypoint())
+            .expect("merged forest must contain the entrypoint");
+        let source_map = source_map.remap(|node_id| node_map.map_node(0, &node_id));
+
+        let stripped_program =
+            Program::with_kernel(mast_forest.into(), entrypoint, program.kernel().clone())
+                .with_isa_version(program.isa_version())
+                .with_static_analysis();
+        Ok((stripped_program, source_map))
+    }
+
+    /// Removes the decorators which are not selected by an explicitly set [DecoratorKinds] from
+    /// `mast_forest`, i.e., the ones originating from vendored libraries.
+    fn retain_selected_decorators(&self, mast_forest: &mut MastForest) {
+        if let Some(kinds) = self.decorators {
+            mast_forest.retain_decorators(kinds);
+        }
     }
 
     /// Compile the uncompiled procedure in the module graph which are members of the subgraph
@@ -493,6 +969,7 @@ impl Assembler {
             let export = &module[procedure_gid.index];
             match export {
                 Export::Procedure(proc) => {
+                    self.verify_stack_effect(procedure_gid, proc)?;
                     let num_locals = proc.num_locals();
                     let name = QualifiedProcedureName {
                         span: proc.span(),
@@ -558,6 +1035,51 @@ impl Assembler {
         Ok(())
     }
 
+    /// Verifies that `procedure` respects the stack effect it declares, if any, now that the
+    /// procedures of other modules it invokes can be resolved.
+    ///
+    /// Semantic analysis only knows the stack effects declared by the procedures of the module
+    /// being analyzed, so it stops verifying a procedure at its first invocation of a procedure of
+    /// another module. Here, the effects declared by such procedures are used as well, including
+    /// the ones of the procedures of libraries, which keep their `@stack_effect` attribute.
+    fn verify_stack_effect(
+        &self,
+        gid: GlobalProcedureIndex,
+        procedure: &ast::Procedure,
+    ) -> Result<(), Report> {
+        if !procedure.has_attribute(STACK_EFFECT_ATTRIBUTE) {
+            return Ok(());
+        }
+        // Errors can only be reported along with the source of the procedure
+        let Ok(source_file) = self.source_manager.get(procedure.span().source_id()) else {
+            return Ok(());
+        };
+
+        let external = |target: &InvocationTarget| {
+            let caller = CallerInfo {
+                span: target.span(),
+                module: gid.module,
+                kind: InvokeKind::Exec,
+            };
+            let callee =
+                self.module_graph.resolve_target(&caller, target).ok()?.into_global_id()?;
+            match self.module_graph.get_procedure_unsafe(callee) {
+                ProcedureWrapper::Ast(Export::Procedure(procedure)) => StackEffect::of(procedure),
+                ProcedureWrapper::Ast(Export::Alias(_)) => None,
+                ProcedureWrapper::Info(info) => info
+                    .attributes
+                    .get(STACK_EFFECT_ATTRIBUTE)
+                    .and_then(StackEffect::from_attribute),
+            }
+        };
+        let mut analyzer = AnalysisContext::new(source_file);
+        analyzer.set_warnings_as_errors(self.warnings_as_errors);
+        VerifyStackEffect::new(&mut analyzer, &BTreeMap::new())
+            .with_external_effects(&external)
+            .verify_procedure(procedure);
+        analyzer.into_result().map_err(Report::new)
+    }
+
     /// Compiles a single Miden Assembly procedure to its MAST representation.
     fn compile_procedure(
         &self,
@@ -656,6 +1178,7 @@ impl Assembler {
                 },
 
                 Op::Repeat { count, body, .. } => {
+                    let count = count.expect_value();
                     if let Some(basic_block_id) = block_builder.make_basic_block()? {
                         body_node_ids.push(basic_block_id);
                     }
@@ -677,11 +1200,11 @@ impl Assembler {
                             .ensure_node(first_repeat_node)?;
 
                         body_node_ids.push(first_repeat_node_id);
-                        for _ in 0..(*count - 1) {
+                        for _ in 0..(count - 1) {
                             body_node_ids.push(repeat_node_id);
                         }
                     } else {
-                        for _ in 0..*count {
+                        for _ in 0..count {
                             body_node_ids.push(repeat_node_id);
                         }
                     }
@@ -709,6 +1232,26 @@ impl Assembler {
 
                     body_node_ids.push(loop_node_id);
                 },
+
+                Op::Expand(invocation) => {
+                    return Err(AssemblyError::UnanalyzedOperation {
+                        span: invocation.span(),
+                        source_file: proc_ctx
+                            .source_manager()
+                            .get(invocation.span().source_id())
+                            .ok(),
+                        kind: "macro invocation",
+                    }
+                    .into());
+                },
+                Op::Cfg { span, .. } => {
+                    return Err(AssemblyError::UnanalyzedOperation {
+                        span: *span,
+                        source_file: proc_ctx.source_manager().get(span.source_id()).ok(),
+                        kind: "conditionally compiled block",
+                    }
+                    .into());
+                },
             }
         }
 
@@ -779,12 +1322,15 @@ impl Assembler {
                     // We didn't find the procedure in our current MAST forest. We still need to
                     // check if it exists in one of a library dependency.
                     None => match self.module_graph.get_procedure_unsafe(gid) {
-                        ProcedureWrapper::Info(p) => self.ensure_valid_procedure_mast_root(
-                            kind,
-                            target.span(),
-                            p.digest,
-                            mast_forest_builder,
-                        ),
+                        ProcedureWrapper::Info(p) => {
+                            self.check_deprecated(gid, p, target.span(), proc_ctx)?;
+                            self.ensure_valid_procedure_mast_root(
+                                kind,
+                                target.span(),
+                                p.digest,
+                                mast_forest_builder,
+                            )
+                        },
                         ProcedureWrapper::Ast(_) => panic!(
                             "AST procedure {gid:?} exits in the module graph but not in the MastForestBuilder"
                         ),
@@ -794,6 +1340,47 @@ impl Assembler {
         }
     }
 
+    /// Warns about the invocation of `callee`, a procedure of a library, if it is marked
+    /// `@deprecated` and the invoking procedure is not deprecated itself.
+    ///
+    /// The warning is returned as an error if warnings are treated as errors.
+    fn check_deprecated(
+        &self,
+        gid: GlobalProcedureIndex,
+        callee: &ProcedureInfo,
+        span: SourceSpan,
+        proc_ctx: &ProcedureContext,
+    ) -> Result<(), AssemblyError> {
+        let Some(attribute) = callee.attributes.get(DEPRECATED_ATTRIBUTE) else {
+            return Ok(());
+        };
+        let caller_is_deprecated = match self.module_graph.get_procedure_unsafe(proc_ctx.id()) {
+            ProcedureWrapper::Ast(caller) => {
+                caller.attributes().is_some_and(|attrs| attrs.has(DEPRECATED_ATTRIBUTE))
+            },
+            ProcedureWrapper::Info(_) => false,
+        };
+        if caller_is_deprecated {
+            return Ok(());
+        }
+
+        let warning = AssemblyError::DeprecatedProcedure {
+            span,
+            source_file: proc_ctx.source_manager().get(span.source_id()).ok(),
+            callee: QualifiedProcedureName::new(
+                self.module_graph[gid.module].path().clone(),
+                callee.name.clone(),
+            ),
+            reason: deprecation_reason(attribute),
+        };
+        if self.warnings_as_errors {
+            return Err(warning);
+        }
+        #[cfg(feature = "std")]
+        std::eprintln!("{}", Report::from(warning));
+        Ok(())
+    }
+
     /// Verifies the validity of the MAST root as a procedure root hash, and adds it to the forest.
     ///
     /// If the root is present in the vendored MAST, its subtree is copied. Otherwise an
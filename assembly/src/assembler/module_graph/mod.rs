@@ -163,14 +163,14 @@ pub struct ModuleGraph {
     procedures_by_mast_root: BTreeMap<RpoDigest, SmallVec<[GlobalProcedureIndex; 1]>>,
     kernel_index: Option<ModuleIndex>,
     kernel: Kernel,
-    source_manager: Arc<dyn SourceManager>,
+    source_manager: Arc<dyn SourceManager + Send + Sync>,
 }
 
 // ------------------------------------------------------------------------------------------------
 /// Constructors
 impl ModuleGraph {
     /// Instantiate a new [ModuleGraph], using the provided [SourceManager] to resolve source info.
-    pub fn new(source_manager: Arc<dyn SourceManager>) -> Self {
+    pub fn new(source_manager: Arc<dyn SourceManager + Send + Sync>) -> Self {
         Self {
             modules: Default::default(),
             pending: Default::default(),
@@ -265,7 +265,7 @@ impl ModuleGraph {
     ///
     /// TODO: consider passing `KerneLibrary` into this constructor as a parameter instead.
     pub(super) fn with_kernel(
-        source_manager: Arc<dyn SourceManager>,
+        source_manager: Arc<dyn SourceManager + Send + Sync>,
         kernel: Kernel,
         kernel_module: ModuleInfo,
     ) -> Self {
@@ -515,6 +515,63 @@ impl ModuleGraph {
         self.callgraph.toposort_caller(caller)
     }
 
+    /// Partitions `modules` into groups which do not depend on one another, either directly, or
+    /// transitively via other modules in the graph, so that each group can be compiled on its own.
+    ///
+    /// Groups are ordered by their first module, and the modules of each group retain their order
+    /// in `modules`, so the result only depends on the contents of the graph.
+    pub fn independent_module_groups(&self, modules: &[ModuleIndex]) -> Vec<Vec<ModuleIndex>> {
+        fn find(parents: &mut [usize], mut index: usize) -> usize {
+            while parents[index] != index {
+                parents[index] = parents[parents[index]];
+                index = parents[index];
+            }
+            index
+        }
+
+        // Union every AST module with the AST modules its procedures depend on
+        let mut parents = (0..self.modules.len()).collect::<Vec<_>>();
+        for (module_index, module) in self.modules.iter().enumerate() {
+            let WrappedModule::Ast(module) = module else {
+                continue;
+            };
+            for index in 0..module.procedures().len() {
+                let gid = GlobalProcedureIndex {
+                    module: ModuleIndex::new(module_index),
+                    index: ProcedureIndex::new(index),
+                };
+                for callee in self.callgraph.out_edges(gid) {
+                    if !matches!(self[callee.module], WrappedModule::Ast(_)) {
+                        continue;
+                    }
+                    let a = find(&mut parents, module_index);
+                    let b = find(&mut parents, callee.module.as_usize());
+                    parents[a.max(b)] = a.min(b);
+                }
+            }
+        }
+
+        let mut group_by_root = BTreeMap::new();
+        let mut groups: Vec<Vec<ModuleIndex>> = Vec::new();
+        for &module_index in modules {
+            let root = find(&mut parents, module_index.as_usize());
+            let group = *group_by_root.entry(root).or_insert_with(|| {
+                groups.push(Vec::new());
+                groups.len() - 1
+            });
+            groups[group].push(module_index);
+        }
+        groups
+    }
+
+    /// Returns an iterator over the modules in this graph for which the AST is available.
+    pub fn ast_modules(&self) -> impl Iterator<Item = (ModuleIndex, &Arc<Module>)> + '_ {
+        self.modules.iter().enumerate().filter_map(|(index, module)| match module {
+            WrappedModule::Ast(module) => Some((ModuleIndex::new(index), module)),
+            WrappedModule::Info(_) => None,
+        })
+    }
+
     /// Fetch a [WrapperProcedure] by [GlobalProcedureIndex].
     ///
     /// # Panics
@@ -540,6 +597,21 @@ impl ModuleGraph {
         self.procedures_by_mast_root.get(procedure_digest).map(|indices| indices[0])
     }
 
+    /// Returns the index of the procedure of the specified module which corresponds to the provided
+    /// procedure digest, or an arbitrary procedure with this digest if the module has none.
+    ///
+    /// This is used to resolve the procedures of compiled modules, which are known by their digest,
+    /// so that a procedure whose digest is shared with procedures of other modules (e.g., because
+    /// they re-export it) is resolved to the procedure of the module it was referenced through.
+    pub fn get_procedure_index_by_digest_in_module(
+        &self,
+        procedure_digest: &RpoDigest,
+        module: ModuleIndex,
+    ) -> Option<GlobalProcedureIndex> {
+        let indices = self.procedures_by_mast_root.get(procedure_digest)?;
+        indices.iter().find(|gid| gid.module == module).or(indices.first()).copied()
+    }
+
     /// Resolves `target` from the perspective of `caller`.
     pub fn resolve_target(
         &self,
@@ -128,6 +128,9 @@ impl<'a> NameResolver<'a> {
                 }
             },
             InvocationTarget::ProcedureName(callee) => self.resolve(caller, callee),
+            InvocationTarget::Instance { .. } => {
+                unreachable!("generic procedures are instantiated during semantic analysis")
+            },
             InvocationTarget::ProcedurePath { name, module: imported_module } => {
                 match self.resolve_import(caller, imported_module) {
                     Some(imported_module) => {
@@ -214,7 +217,7 @@ impl<'a> NameResolver<'a> {
                 })
             },
             Some(ResolvedProcedure::MastRoot(ref digest)) => {
-                match self.graph.get_procedure_index_by_digest(digest) {
+                match self.graph.get_procedure_index_by_digest_in_module(digest, caller.module) {
                     Some(gid) => Ok(ResolvedTarget::Exact { gid }),
                     None => Ok(ResolvedTarget::Phantom(*digest)),
                 }
@@ -355,7 +358,9 @@ impl<'a> NameResolver<'a> {
                     current_callee = Cow::Owned(fqn);
                 },
                 Some(ResolvedProcedure::MastRoot(ref digest)) => {
-                    if let Some(id) = self.graph.get_procedure_index_by_digest(digest) {
+                    if let Some(id) =
+                        self.graph.get_procedure_index_by_digest_in_module(digest, module_index)
+                    {
                         break Ok(id);
                     }
                     // This is a phantom procedure - we know its root, but do not have its
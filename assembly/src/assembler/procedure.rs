@@ -14,7 +14,7 @@ use crate::{
 
 /// Information about a procedure currently being compiled.
 pub struct ProcedureContext {
-    source_manager: Arc<dyn SourceManager>,
+    source_manager: Arc<dyn SourceManager + Send + Sync>,
     gid: GlobalProcedureIndex,
     span: SourceSpan,
     name: QualifiedProcedureName,
@@ -31,7 +31,7 @@ impl ProcedureContext {
         name: QualifiedProcedureName,
         visibility: Visibility,
         is_kernel: bool,
-        source_manager: Arc<dyn SourceManager>,
+        source_manager: Arc<dyn SourceManager + Send + Sync>,
     ) -> Self {
         Self {
             source_manager,
@@ -0,0 +1,367 @@
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    string::ToString,
+    sync::Arc,
+};
+use core::fmt;
+
+use vm_core::{
+    crypto::hash::RpoDigest,
+    mast::{MastForest, MastNode, MastNodeId},
+    stack::MIN_STACK_DEPTH,
+};
+
+use super::{
+    GlobalProcedureIndex, ModuleIndex,
+    module_graph::{CallerInfo, ModuleGraph, ProcedureWrapper},
+};
+use crate::{
+    Spanned,
+    ast::*,
+    sema::{StackEffect, StackSummary as Summary},
+};
+
+// STACK DEPTH
+// ================================================================================================
+
+/// The stack depth of a procedure, as determined by statically analyzing its body.
+///
+/// All depths are counted from the top of the stack at the time the procedure is invoked, so a
+/// procedure which requires `N` elements accesses the top `N` elements of the stack of its caller.
+///
+/// The procedures of the libraries added to the assembler are analyzed from the operations of
+/// their MAST. The analysis of a procedure stops at the first operation whose effect on the stack
+/// cannot be determined statically, e.g. an invocation of a procedure which is only known by its
+/// MAST root, a `syscall`, a dynamic invocation, or a conditional whose branches leave a different
+/// number of elements on the stack. In that case, [StackDepth::outputs] is `None`, and the other
+/// depths only account for the operations which precede it.
+///
+/// A procedure invoked with `call` is executed in a new context, in which only the top 16
+/// elements of the stack of its caller are available, so it requires at most 16 elements from its
+/// caller, and leaves the depth of the stack of its caller unchanged.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct StackDepth {
+    required: usize,
+    max: usize,
+    outputs: Option<usize>,
+}
+
+impl StackDepth {
+    /// Returns the minimum number of elements which must be on the stack when the procedure is
+    /// invoked.
+    pub fn required(&self) -> usize {
+        self.required
+    }
+
+    /// Returns the maximum number of elements on the stack during the execution of the
+    /// procedure, when it is invoked with exactly [StackDepth::required] elements.
+    pub fn max(&self) -> usize {
+        self.max
+    }
+
+    /// Returns the number of elements left on the stack when the procedure returns, when it is
+    /// invoked with exactly [StackDepth::required] elements, or `None` if it cannot be
+    /// determined statically.
+    pub fn outputs(&self) -> Option<usize> {
+        self.outputs
+    }
+
+    /// Returns true if the procedure may access elements beyond the top 16 elements of the stack
+    /// it is invoked with.
+    ///
+    /// Only the top 16 elements are accessible to a procedure invoked with `call`, and to
+    /// instructions such as `dup` and `movup` in general, so such a procedure may underflow, or
+    /// read elements which its caller didn't intend to pass to it.
+    pub fn may_underflow(&self) -> bool {
+        self.required > MIN_STACK_DEPTH
+    }
+}
+
+// STACK DEPTH REPORT
+// ================================================================================================
+
+/// The stack depth of each procedure known to an [Assembler](super::Assembler) in source form, as
+/// returned by [Assembler::analyze_stack_depth](super::Assembler::analyze_stack_depth).
+///
+/// The [fmt::Display] implementation of this type renders a human-readable table of the depths,
+/// and flags the procedures which may underflow.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StackDepthReport {
+    procedures: BTreeMap<QualifiedProcedureName, StackDepth>,
+}
+
+impl StackDepthReport {
+    /// Returns the stack depth of the procedure `name`, if it was analyzed.
+    pub fn get(&self, name: &QualifiedProcedureName) -> Option<&StackDepth> {
+        self.procedures.get(name)
+    }
+
+    /// Returns an iterator over the analyzed procedures and their stack depths, ordered by name.
+    pub fn iter(&self) -> impl Iterator<Item = (&QualifiedProcedureName, &StackDepth)> {
+        self.procedures.iter()
+    }
+
+    /// Returns an iterator over the names of the procedures which may underflow, see
+    /// [StackDepth::may_underflow].
+    pub fn underflows(&self) -> impl Iterator<Item = &QualifiedProcedureName> {
+        self.iter().filter(|(_, depth)| depth.may_underflow()).map(|(name, _)| name)
+    }
+}
+
+impl fmt::Display for StackDepthReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self
+            .procedures
+            .keys()
+            .map(|name| name.to_string().len())
+            .max()
+            .unwrap_or(0)
+            .max("Procedure".len());
+        writeln!(f, "{:width$}  {:>8}  {:>8}  {:>8}", "Procedure", "Required", "Max", "Outputs")?;
+        for (name, depth) in self.procedures.iter() {
+            let outputs = match depth.outputs {
+                Some(outputs) => outputs.to_string(),
+                None => "?".to_string(),
+            };
+            write!(
+                f,
+                "{:width$}  {:>8}  {:>8}  {:>8}",
+                name.to_string(),
+                depth.required,
+                depth.max,
+                outputs
+            )?;
+            if depth.may_underflow() {
+                write!(f, "  (may underflow)")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+// STACK DEPTH ANALYSIS
+// ================================================================================================
+
+impl From<Summary> for StackDepth {
+    fn from(summary: Summary) -> Self {
+        Self {
+            required: summary.required,
+            max: summary.required + summary.peak,
+            outputs: summary.delta.map(|delta| (summary.required as isize + delta) as usize),
+        }
+    }
+}
+
+/// Computes the [StackDepth] of the AST procedures of a [ModuleGraph].
+///
+/// Procedures are summarized from the effect of each instruction on the stack, and the summaries
+/// of the procedures they invoke with `exec` or `call`, which are resolved through the graph. The
+/// procedures of compiled modules are summarized from the operations of their MAST, which is
+/// looked up by MAST root in the forests of the libraries they belong to.
+pub(super) struct StackDepthAnalysis<'a> {
+    graph: &'a ModuleGraph,
+    /// The MAST forests of the libraries providing the compiled modules of the graph
+    libraries: &'a [Arc<MastForest>],
+    summaries: BTreeMap<GlobalProcedureIndex, Summary>,
+    /// The summaries of the MAST nodes of the libraries, by library and node
+    node_summaries: BTreeMap<(usize, MastNodeId), Summary>,
+    /// The procedures being summarized, used to stop at recursive invocations
+    visiting: BTreeSet<GlobalProcedureIndex>,
+}
+
+impl<'a> StackDepthAnalysis<'a> {
+    pub fn new(graph: &'a ModuleGraph, libraries: &'a [Arc<MastForest>]) -> Self {
+        Self {
+            graph,
+            libraries,
+            summaries: BTreeMap::new(),
+            node_summaries: BTreeMap::new(),
+            visiting: BTreeSet::new(),
+        }
+    }
+
+    /// Returns the report of the stack depth of every AST procedure in the graph.
+    pub fn analyze(mut self) -> StackDepthReport {
+        let mut procedures = BTreeMap::new();
+        for (module_index, module) in self.graph.ast_modules() {
+            for (index, export) in module.procedures().enumerate() {
+                if !matches!(export, Export::Procedure(_)) {
+                    continue;
+                }
+                let summary = self.summarize(module_index + ProcedureIndex::new(index));
+                let name =
+                    QualifiedProcedureName::new(module.path().clone(), export.name().clone());
+                procedures.insert(name, summary.into());
+            }
+        }
+        StackDepthReport { procedures }
+    }
+
+    fn summarize(&mut self, gid: GlobalProcedureIndex) -> Summary {
+        if let Some(summary) = self.summaries.get(&gid) {
+            return *summary;
+        }
+        let graph = self.graph;
+        let procedure = match graph.get_procedure_unsafe(gid) {
+            ProcedureWrapper::Ast(Export::Procedure(procedure)) => procedure,
+            ProcedureWrapper::Ast(Export::Alias(_)) => return Summary::UNKNOWN,
+            ProcedureWrapper::Info(info) => {
+                let summary = self.summarize_mast_root(info.digest);
+                self.summaries.insert(gid, summary);
+                return summary;
+            },
+        };
+        if !self.visiting.insert(gid) {
+            return Summary::UNKNOWN;
+        }
+        let summary = self.summarize_block(gid.module, procedure.body());
+        self.visiting.remove(&gid);
+        self.summaries.insert(gid, summary);
+        summary
+    }
+
+    fn summarize_block(&mut self, module: ModuleIndex, block: &Block) -> Summary {
+        let mut summary = Summary::EMPTY;
+        for op in block.iter() {
+            if summary.delta.is_none() {
+                break;
+            }
+            summary = summary.then(self.summarize_op(module, op));
+        }
+        summary
+    }
+
+    fn summarize_op(&mut self, module: ModuleIndex, op: &Op) -> Summary {
+        let condition = Summary::of_effect(StackEffect { inputs: 1, outputs: 0 });
+        match op {
+            Op::Inst(inst) => match &**inst {
+                Instruction::Exec(target) => {
+                    self.summarize_invoke(module, InvokeKind::Exec, target)
+                },
+                Instruction::Call(target) => {
+                    self.summarize_invoke(module, InvokeKind::Call, target)
+                },
+                inst => StackEffect::of_instruction(inst)
+                    .map(Summary::of_effect)
+                    .unwrap_or(Summary::UNKNOWN),
+            },
+            Op::If { then_blk, else_blk, .. } => {
+                let then_blk = self.summarize_block(module, then_blk);
+                let else_blk = self.summarize_block(module, else_blk);
+                condition.then(then_blk.or(else_blk))
+            },
+            Op::While { body, .. } => {
+                // The body must push the condition of the next iteration
+                let body = self.summarize_block(module, body);
+                let iteration = Summary {
+                    delta: body.delta.filter(|delta| *delta == 1).map(|_| 0),
+                    ..body
+                };
+                condition.then(iteration)
+            },
+            Op::Repeat { count, body, .. } => {
+                let count = count.expect_value() as usize;
+                self.summarize_block(module, body).repeat(count)
+            },
+            // These can only be left in modules which were constructed without being analyzed,
+            // which the assembler rejects
+            Op::Expand(_) | Op::Cfg { .. } => Summary::UNKNOWN,
+        }
+    }
+
+    fn summarize_invoke(
+        &mut self,
+        module: ModuleIndex,
+        kind: InvokeKind,
+        target: &InvocationTarget,
+    ) -> Summary {
+        let caller = CallerInfo { span: target.span(), module, kind };
+        let summary = match self
+            .graph
+            .resolve_target(&caller, target)
+            .ok()
+            .and_then(|t| t.into_global_id())
+        {
+            Some(gid) => self.summarize(gid),
+            None => Summary::UNKNOWN,
+        };
+        match kind {
+            InvokeKind::Call => Self::called(summary),
+            _ => summary,
+        }
+    }
+
+    /// Returns the summary of invoking a procedure summarized by `summary` with `call`.
+    ///
+    /// The callee only has access to the top 16 elements of the stack, and must return with 16
+    /// elements on its own stack, so the depth of the stack of the caller is left unchanged.
+    fn called(summary: Summary) -> Summary {
+        Summary {
+            required: summary.required.min(MIN_STACK_DEPTH),
+            peak: 0,
+            delta: summary.delta.map(|_| 0),
+        }
+    }
+
+    /// Returns the summary of the procedure with MAST root `digest` in the libraries, if found.
+    fn summarize_mast_root(&mut self, digest: RpoDigest) -> Summary {
+        let library = self.libraries.iter().enumerate().find_map(|(library, forest)| {
+            forest.find_procedure_root(digest).map(|node_id| (library, node_id))
+        });
+        match library {
+            Some((library, node_id)) => self.summarize_node(library, node_id),
+            None => Summary::UNKNOWN,
+        }
+    }
+
+    fn summarize_node(&mut self, library: usize, node_id: MastNodeId) -> Summary {
+        if let Some(summary) = self.node_summaries.get(&(library, node_id)) {
+            return *summary;
+        }
+        let forest = self.libraries[library].clone();
+        let condition = Summary::of_effect(StackEffect { inputs: 1, outputs: 0 });
+        let summary = match &forest[node_id] {
+            MastNode::Block(block) => {
+                let mut summary = Summary::EMPTY;
+                for op in block.operations() {
+                    let metadata = op.metadata();
+                    summary = summary.then(Summary::of_effect(StackEffect {
+                        inputs: metadata.inputs() as usize,
+                        outputs: metadata.outputs() as usize,
+                    }));
+                }
+                summary
+            },
+            MastNode::Join(join) => {
+                let first = self.summarize_node(library, join.first());
+                if first.delta.is_none() {
+                    first
+                } else {
+                    first.then(self.summarize_node(library, join.second()))
+                }
+            },
+            MastNode::Split(split) => {
+                let on_true = self.summarize_node(library, split.on_true());
+                let on_false = self.summarize_node(library, split.on_false());
+                condition.then(on_true.or(on_false))
+            },
+            MastNode::Loop(loop_node) => {
+                // The body must push the condition of the next iteration
+                let body = self.summarize_node(library, loop_node.body());
+                let iteration = Summary {
+                    delta: body.delta.filter(|delta| *delta == 1).map(|_| 0),
+                    ..body
+                };
+                condition.then(iteration)
+            },
+            MastNode::Call(call) if !call.is_syscall() => {
+                Self::called(self.summarize_node(library, call.callee()))
+            },
+            MastNode::External(external) => self.summarize_mast_root(external.digest()),
+            MastNode::Call(_) | MastNode::Dyn(_) => Summary::UNKNOWN,
+        };
+        self.node_summaries.insert((library, node_id), summary);
+        summary
+    }
+}
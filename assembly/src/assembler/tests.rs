@@ -1,15 +1,16 @@
-use alloc::vec::Vec;
+use alloc::{boxed::Box, string::ToString, vec, vec::Vec};
 
 use pretty_assertions::assert_eq;
 use vm_core::{
-    Program, assert_matches,
+    Decorator, DecoratorKinds, Program, assert_matches,
     crypto::hash::RpoDigest,
     mast::{MastForest, MastNode},
 };
 
 use super::{Assembler, Operation};
 use crate::{
-    assembler::mast_forest_builder::MastForestBuilder, diagnostics::Report, testing::TestContext,
+    CompileOptions, SourceSpan, Span, assembler::mast_forest_builder::MastForestBuilder, ast::*,
+    diagnostics::Report, testing::TestContext,
 };
 
 // TESTS
@@ -319,6 +320,65 @@ fn explicit_fully_qualified_procedure_references() -> Result<(), Report> {
     Ok(())
 }
 
+#[test]
+fn library_of_independent_modules() -> Result<(), Report> {
+    const A: &str = r#"
+        use.foo::b
+
+        export.a
+            exec.b::b mul
+        end"#;
+    const B: &str = r#"
+        export.b
+            add
+        end"#;
+    const C: &str = r#"
+        export.c
+            push.1 add
+        end"#;
+    const D: &str = r#"
+        export.d
+            add
+        end"#;
+
+    let context = TestContext::new();
+    let parse_modules = || -> Result<Vec<_>, Report> {
+        [("foo::a", A), ("foo::b", B), ("foo::c", C), ("foo::d", D)]
+            .into_iter()
+            .map(|(path, source)| context.parse_module_with_path(path.parse().unwrap(), source))
+            .collect()
+    };
+
+    // `foo::a` depends on `foo::b`, while `foo::c` and `foo::d` are independent of everything
+    let mut assembler = Assembler::new(context.source_manager());
+    let indices =
+        assembler.add_modules_with_options(parse_modules()?, CompileOptions::for_library())?;
+    assert_eq!(
+        assembler.module_graph.independent_module_groups(&indices),
+        vec![vec![indices[0], indices[1]], vec![indices[2]], vec![indices[3]]]
+    );
+
+    // The libraries assembled from the same modules are identical, and identical procedures of
+    // independent modules share the same node in the merged forest
+    let library = context.assemble_library(parse_modules()?)?;
+    assert_eq!(library, context.assemble_library(parse_modules()?)?);
+    assert_eq!(library.num_exports(), 4);
+    let export = |name: &str| library.get_export_node_id(&name.parse().unwrap());
+    assert_eq!(export("foo::b::b"), export("foo::d::d"));
+    assert_ne!(export("foo::a::a"), export("foo::c::c"));
+
+    let assembler = Assembler::new(context.source_manager()).with_library(&library)?;
+    let program = r#"
+    use.foo::a
+    use.foo::c
+
+    begin
+        exec.a::a exec.c::c
+    end"#;
+    assert_matches!(assembler.assemble_program(program), Ok(_));
+    Ok(())
+}
+
 #[test]
 fn re_exports() -> Result<(), Report> {
     const BAR_NAME: &str = "foo::bar";
@@ -357,3 +417,210 @@ fn re_exports() -> Result<(), Report> {
     assert_matches!(assembler.assemble_program(program), Ok(_));
     Ok(())
 }
+
+#[test]
+fn pruning_removes_unreachable_nodes() -> Result<(), Report> {
+    let context = TestContext::new();
+    let program_source = r#"
+    proc.foo
+        add mul
+    end
+
+    begin
+        exec.foo push.1 exec.foo
+    end"#;
+
+    // the body of `foo` is merged into the body of the entrypoint, and is only retained as a
+    // procedure root
+    let program = Assembler::new(context.source_manager()).assemble_program(program_source)?;
+    assert_eq!(program.mast_forest().num_nodes(), 2);
+
+    let pruned_program = Assembler::new(context.source_manager())
+        .with_pruning(true)
+        .assemble_program(program_source)?;
+    assert_eq!(pruned_program.mast_forest().num_nodes(), 1);
+    assert_eq!(pruned_program.mast_forest().procedure_roots(), &[pruned_program.entrypoint()]);
+    assert_eq!(pruned_program.hash(), program.hash());
+    Ok(())
+}
+
+#[test]
+fn pruning_retains_referenced_procedures() -> Result<(), Report> {
+    let context = TestContext::new();
+    let program_source = r#"
+    proc.foo
+        add
+    end
+
+    begin
+        procref.foo dropw
+    end"#;
+
+    let program = Assembler::new(context.source_manager())
+        .with_pruning(true)
+        .assemble_program(program_source)?;
+    let foo_digest = MastNode::new_basic_block(vec![Operation::Add], None).unwrap().digest();
+    assert!(program.mast_forest().find_procedure_root(foo_digest).is_some());
+    Ok(())
+}
+
+#[test]
+fn source_map_of_stripped_program() -> Result<(), Report> {
+    let context = TestContext::new();
+    let program_source = "begin
+    push.1
+    trace.3
+    if.true
+        mul
+    end
+end";
+
+    let (program, source_map) = Assembler::new(context.source_manager())
+        .assemble_program_with_source_map(program_source)?;
+    let expected = Assembler::new(context.source_manager()).assemble_program(program_source)?;
+    assert_eq!(program.hash(), expected.hash());
+    assert!(program.mast_forest().decorators().is_empty());
+
+    // `push.1` starts the block preceding the split, and `mul` the block of its true branch
+    let forest = program.mast_forest();
+    let lines = |node_id| source_map.get_exact(node_id, 0).map(|location| location.line);
+    let MastNode::Join(join) = &forest[program.entrypoint()] else {
+        panic!("expected the entrypoint to be a join node");
+    };
+    let MastNode::Split(split) = &forest[join.second()] else {
+        panic!("expected a split node");
+    };
+    assert_eq!(lines(join.first()), Some(2));
+    assert_eq!(lines(split.on_true()), Some(5));
+    assert_eq!(source_map.get_exact(split.on_true(), 0).unwrap().column, 9);
+    Ok(())
+}
+
+#[test]
+fn source_map_of_deduplicated_program() -> Result<(), Report> {
+    let context = TestContext::new();
+    let program_source = "begin
+    if.true
+        mul
+    else
+        mul
+    end
+end";
+
+    // the branches only differ by the locations of their decorators, so they are merged into a
+    // single block once the decorators are stripped
+    let (program, source_map) = Assembler::new(context.source_manager())
+        .assemble_program_with_source_map(program_source)?;
+    let expected = Assembler::new(context.source_manager()).assemble_program(program_source)?;
+    assert_eq!(program.hash(), expected.hash());
+    assert_eq!(program.mast_forest().num_nodes(), expected.mast_forest().num_nodes());
+
+    let forest = program.mast_forest();
+    let MastNode::Split(split) = &forest[program.entrypoint()] else {
+        panic!("expected the entrypoint to be a split node");
+    };
+    assert_eq!(split.on_true(), split.on_false());
+    assert_eq!(source_map.get_exact(split.on_true(), 0).unwrap().line, 3);
+    assert_eq!(source_map.len(), 1);
+    Ok(())
+}
+
+#[test]
+fn decorators_of_selected_kinds() -> Result<(), Report> {
+    let context = TestContext::new();
+    let program_source = "begin
+    push.1
+    trace.3
+    debug.stack
+    mul
+end";
+    let assemble = |assembler: Assembler| assembler.assemble_program(program_source);
+    let kinds = |program: &Program| {
+        let decorators = program.mast_forest().decorators();
+        [
+            decorators.iter().any(|decorator| matches!(decorator, Decorator::AsmOp(_))),
+            decorators.iter().any(|decorator| matches!(decorator, Decorator::Debug(_))),
+            decorators.iter().any(|decorator| matches!(decorator, Decorator::Trace(_))),
+        ]
+    };
+
+    // by default, the emitted decorators depend on the debug mode
+    let release = assemble(Assembler::new(context.source_manager()))?;
+    assert_eq!(kinds(&release), [false, false, true]);
+    let dev = assemble(Assembler::new(context.source_manager()).with_debug_mode(true))?;
+    assert_eq!(kinds(&dev), [true, true, true]);
+
+    let selected = DecoratorKinds {
+        asm_ops: true,
+        debug: false,
+        trace: false,
+    };
+    let program = assemble(
+        Assembler::new(context.source_manager())
+            .with_debug_mode(true)
+            .with_decorators(selected),
+    )?;
+    assert_eq!(kinds(&program), [true, false, false]);
+    let program =
+        assemble(Assembler::new(context.source_manager()).with_decorators(DecoratorKinds::NONE))?;
+    assert!(program.mast_forest().decorators().is_empty());
+
+    // the selection doesn't affect the hash of the program
+    assert_eq!(program.hash(), release.hash());
+    assert_eq!(dev.hash(), release.hash());
+    Ok(())
+}
+
+/// Returns a library module exporting a procedure `foo` whose body is `op`, which is not
+/// semantically analyzed.
+fn unanalyzed_module(op: Op) -> Box<Module> {
+    let path = "test::unanalyzed".parse().unwrap();
+    let mut module = Module::new(ModuleKind::Library, path);
+    let body = Block::new(SourceSpan::default(), vec![op]);
+    let name = ProcedureName::new("foo").unwrap();
+    let procedure = Procedure::new(SourceSpan::default(), Visibility::Public, name, 0, body);
+    module.define_procedure(Export::Procedure(procedure)).unwrap();
+    Box::new(module)
+}
+
+#[test]
+fn unanalyzed_macro_invocation() {
+    let invocation =
+        MacroInvocation::new(SourceSpan::default(), Ident::new("go").unwrap(), Vec::new());
+    let module = unanalyzed_module(Op::Expand(invocation));
+    let err = Assembler::default().assemble_library([module]).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "unexpected macro invocation: modules must be semantically analyzed before they are \
+         assembled"
+    );
+}
+
+#[test]
+fn unanalyzed_conditional_block() {
+    let attribute = Attribute::from(Ident::new("cfg").unwrap());
+    let body = Block::new(SourceSpan::default(), vec![Op::Inst(Span::unknown(Instruction::Add))]);
+    let module = unanalyzed_module(Op::Cfg {
+        span: SourceSpan::default(),
+        attribute,
+        body,
+    });
+    let err = Assembler::default().assemble_library([module]).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "unexpected conditionally compiled block: modules must be semantically analyzed before \
+         they are assembled"
+    );
+}
+
+#[test]
+fn unanalyzed_data_constant() {
+    let mem_init = Instruction::MemInit(Ident::new("DATA").unwrap());
+    let module = unanalyzed_module(Op::Inst(Span::unknown(mem_init)));
+    let err = Assembler::default().assemble_library([module]).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "unexpected reference to a data constant: modules must be semantically analyzed before \
+         they are assembled"
+    );
+}
@@ -1,5 +1,7 @@
 use alloc::{string::String, sync::Arc};
 
+use vm_core::utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+
 use crate::{Felt, SourceSpan, Span, Spanned, ast::Ident, parser::HexEncodedValue, prettier};
 
 /// Represents a metadata expression of an [crate::ast::Attribute]
@@ -84,3 +86,58 @@ impl Spanned for MetaExpr {
         }
     }
 }
+
+impl Serializable for MetaExpr {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        match self {
+            Self::Ident(id) => {
+                target.write_u8(0);
+                id.write_into(target);
+            },
+            Self::Int(value) => match value.inner() {
+                HexEncodedValue::U8(value) => {
+                    target.write_u8(1);
+                    target.write_u8(*value);
+                },
+                HexEncodedValue::U16(value) => {
+                    target.write_u8(2);
+                    target.write_u16(*value);
+                },
+                HexEncodedValue::U32(value) => {
+                    target.write_u8(3);
+                    target.write_u32(*value);
+                },
+                HexEncodedValue::Felt(value) => {
+                    target.write_u8(4);
+                    value.write_into(target);
+                },
+                HexEncodedValue::Word(value) => {
+                    target.write_u8(5);
+                    value.write_into(target);
+                },
+            },
+            Self::String(id) => {
+                target.write_u8(6);
+                id.write_into(target);
+            },
+        }
+    }
+}
+
+impl Deserializable for MetaExpr {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let int = |value| Ok(Self::Int(Span::unknown(value)));
+        match source.read_u8()? {
+            0 => Ok(Self::Ident(source.read()?)),
+            1 => int(HexEncodedValue::U8(source.read_u8()?)),
+            2 => int(HexEncodedValue::U16(source.read_u16()?)),
+            3 => int(HexEncodedValue::U32(source.read_u32()?)),
+            4 => int(HexEncodedValue::Felt(source.read()?)),
+            5 => int(HexEncodedValue::Word(source.read()?)),
+            6 => Ok(Self::String(source.read()?)),
+            tag => Err(DeserializationError::InvalidValue(format!(
+                "invalid attribute metadata expression tag '{tag}'"
+            ))),
+        }
+    }
+}
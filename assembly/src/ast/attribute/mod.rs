@@ -1,8 +1,11 @@
 mod meta;
 mod set;
 
+use alloc::{collections::BTreeMap, vec::Vec};
 use core::fmt;
 
+use vm_core::utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+
 pub use self::{
     meta::{BorrowedMeta, Meta, MetaExpr, MetaItem, MetaKeyValue, MetaList},
     set::{AttributeSet, AttributeSetEntry},
@@ -244,3 +247,58 @@ impl From<MetaKeyValue> for Attribute {
         Self::KeyValue(value)
     }
 }
+
+/// Attributes are serialized without their source locations.
+impl Serializable for Attribute {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        match self {
+            Self::Marker(id) => {
+                target.write_u8(0);
+                id.write_into(target);
+            },
+            Self::List(list) => {
+                target.write_u8(1);
+                list.name.write_into(target);
+                target.write_usize(list.items.len());
+                target.write_many(&list.items);
+            },
+            Self::KeyValue(kv) => {
+                target.write_u8(2);
+                kv.name.write_into(target);
+                target.write_usize(kv.items.len());
+                for (key, value) in kv.items.iter() {
+                    key.write_into(target);
+                    value.write_into(target);
+                }
+            },
+        }
+    }
+}
+
+impl Deserializable for Attribute {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let tag = source.read_u8()?;
+        let name: Ident = source.read()?;
+        match tag {
+            0 => Ok(Self::Marker(name)),
+            1 => {
+                let len = source.read_usize()?;
+                let items: Vec<MetaExpr> = source.read_many(len)?;
+                Ok(Self::List(MetaList::new(name, items)))
+            },
+            2 => {
+                let len = source.read_usize()?;
+                let mut items = BTreeMap::new();
+                for _ in 0..len {
+                    let key: Ident = source.read()?;
+                    let value: MetaExpr = source.read()?;
+                    items.insert(key, value);
+                }
+                Ok(Self::KeyValue(MetaKeyValue::new(name, items)))
+            },
+            tag => {
+                Err(DeserializationError::InvalidValue(format!("invalid attribute tag '{tag}'")))
+            },
+        }
+    }
+}
@@ -1,6 +1,8 @@
 use alloc::vec::Vec;
 use core::fmt;
 
+use vm_core::utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+
 use super::*;
 use crate::ast::Ident;
 
@@ -175,6 +177,21 @@ impl Extend<Attribute> for AttributeSet {
     }
 }
 
+impl Serializable for AttributeSet {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_usize(self.attrs.len());
+        target.write_many(&self.attrs);
+    }
+}
+
+impl Deserializable for AttributeSet {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let len = source.read_usize()?;
+        let attrs: Vec<Attribute> = source.read_many(len)?;
+        Ok(Self::new(attrs))
+    }
+}
+
 /// Represents an entry under a specific key in a [AttributeSet]
 pub enum AttributeSetEntry<'a> {
     /// The entry is currently occupied with a value
@@ -27,6 +27,11 @@ impl Block {
         self.body.push(op);
     }
 
+    /// Removes all of the ops of this block, returning them.
+    pub(crate) fn take_ops(&mut self) -> Vec<Op> {
+        core::mem::take(&mut self.body)
+    }
+
     /// Returns the number of ops in this block.
     ///
     /// NOTE: The count does not include nested ops,
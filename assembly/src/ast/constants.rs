@@ -1,7 +1,7 @@
-use alloc::{boxed::Box, string::String};
+use alloc::{boxed::Box, string::String, sync::Arc, vec::Vec};
 use core::fmt;
 
-use vm_core::FieldElement;
+use vm_core::StarkField;
 
 use super::DocString;
 use crate::{Felt, SourceSpan, Span, Spanned, ast::Ident, parser::ParsingError};
@@ -10,6 +10,7 @@ use crate::{Felt, SourceSpan, Span, Spanned, ast::Ident, parser::ParsingError};
 // ================================================================================================
 
 /// Represents a constant definition in Miden Assembly syntax, i.e. `const.FOO = 1 + 1`.
+#[derive(Clone)]
 pub struct Constant {
     /// The source span of the definition.
     pub span: SourceSpan,
@@ -79,6 +80,7 @@ impl Spanned for Constant {
 // ================================================================================================
 
 /// Represents a constant expression or value in Miden Assembly syntax.
+#[derive(Clone)]
 pub enum ConstantExpr {
     /// A literal integer value.
     Literal(Span<Felt>),
@@ -116,74 +118,31 @@ impl ConstantExpr {
         match self {
             Self::Literal(_) | Self::Var(_) => Ok(self),
             Self::BinaryOp { span, op, lhs, rhs } => {
-                if rhs.is_literal() {
-                    let rhs = Self::into_inner(rhs).try_fold()?;
-                    match rhs {
-                        Self::Literal(rhs) => {
-                            let lhs = Self::into_inner(lhs).try_fold()?;
-                            match lhs {
-                                Self::Literal(lhs) => {
-                                    let lhs = lhs.into_inner();
-                                    let rhs = rhs.into_inner();
-                                    let is_division =
-                                        matches!(op, ConstantOp::Div | ConstantOp::IntDiv);
-                                    let is_division_by_zero = is_division && rhs == Felt::ZERO;
-                                    if is_division_by_zero {
-                                        return Err(ParsingError::DivisionByZero { span });
-                                    }
-                                    match op {
-                                        ConstantOp::Add => {
-                                            Ok(Self::Literal(Span::new(span, lhs + rhs)))
-                                        },
-                                        ConstantOp::Sub => {
-                                            Ok(Self::Literal(Span::new(span, lhs - rhs)))
-                                        },
-                                        ConstantOp::Mul => {
-                                            Ok(Self::Literal(Span::new(span, lhs * rhs)))
-                                        },
-                                        ConstantOp::Div => {
-                                            Ok(Self::Literal(Span::new(span, lhs / rhs)))
-                                        },
-                                        ConstantOp::IntDiv => Ok(Self::Literal(Span::new(
-                                            span,
-                                            Felt::new(lhs.as_int() / rhs.as_int()),
-                                        ))),
-                                    }
-                                },
-                                lhs => Ok(Self::BinaryOp {
-                                    span,
-                                    op,
-                                    lhs: Box::new(lhs),
-                                    rhs: Box::new(Self::Literal(rhs)),
-                                }),
-                            }
-                        },
-                        rhs => {
-                            let lhs = Self::into_inner(lhs).try_fold()?;
-                            Ok(Self::BinaryOp {
-                                span,
-                                op,
-                                lhs: Box::new(lhs),
-                                rhs: Box::new(rhs),
-                            })
-                        },
-                    }
-                } else {
-                    let lhs = Self::into_inner(lhs).try_fold()?;
-                    Ok(Self::BinaryOp { span, op, lhs: Box::new(lhs), rhs })
+                let lhs = Self::into_inner(lhs).try_fold()?;
+                let rhs = Self::into_inner(rhs).try_fold()?;
+                match (lhs, rhs) {
+                    (Self::Literal(lhs), Self::Literal(rhs)) => {
+                        match op.apply(lhs.into_inner(), rhs.into_inner()) {
+                            Ok(value) => Ok(Self::Literal(Span::new(span, value))),
+                            Err(ConstantEvalError::DivisionByZero) => {
+                                Err(ParsingError::DivisionByZero { span })
+                            },
+                            Err(ConstantEvalError::Overflow) => {
+                                Err(ParsingError::ConstantOverflow { span })
+                            },
+                        }
+                    },
+                    (lhs, rhs) => Ok(Self::BinaryOp {
+                        span,
+                        op,
+                        lhs: Box::new(lhs),
+                        rhs: Box::new(rhs),
+                    }),
                 }
             },
         }
     }
 
-    fn is_literal(&self) -> bool {
-        match self {
-            Self::Literal(_) => true,
-            Self::Var(_) => false,
-            Self::BinaryOp { lhs, rhs, .. } => lhs.is_literal() && rhs.is_literal(),
-        }
-    }
-
     #[inline(always)]
     #[allow(clippy::boxed_local)]
     fn into_inner(self: Box<Self>) -> Self {
@@ -248,7 +207,13 @@ impl Spanned for ConstantExpr {
 // CONSTANT OPERATION
 // ================================================================================================
 
-/// Represents the set of binary arithmetic operators supported in Miden Assembly syntax.
+/// Represents the set of binary operators supported in constant expressions in Miden Assembly
+/// syntax.
+///
+/// The arithmetic operators `+`, `-`, `*` and `/` operate on field elements, while the other
+/// operators operate on the integer representation of their operands, and fail if the result is
+/// not a valid field element. Comparison operators evaluate to `1` if the comparison holds, and to
+/// `0` otherwise.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum ConstantOp {
     Add,
@@ -256,6 +221,27 @@ pub enum ConstantOp {
     Mul,
     Div,
     IntDiv,
+    Mod,
+    Shl,
+    Shr,
+    And,
+    Or,
+    Xor,
+    Eq,
+    Neq,
+    Lt,
+    Lte,
+    Gt,
+    Gte,
+}
+
+/// The reasons for which a constant operation can fail to be evaluated.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum ConstantEvalError {
+    /// The right-hand operand of a division or modulo is zero.
+    DivisionByZero,
+    /// The result is not a valid field element.
+    Overflow,
 }
 
 impl ConstantOp {
@@ -266,8 +252,55 @@ impl ConstantOp {
             Self::Mul => "Mul",
             Self::Div => "Div",
             Self::IntDiv => "IntDiv",
+            Self::Mod => "Mod",
+            Self::Shl => "Shl",
+            Self::Shr => "Shr",
+            Self::And => "And",
+            Self::Or => "Or",
+            Self::Xor => "Xor",
+            Self::Eq => "Eq",
+            Self::Neq => "Neq",
+            Self::Lt => "Lt",
+            Self::Lte => "Lte",
+            Self::Gt => "Gt",
+            Self::Gte => "Gte",
         }
     }
+
+    /// Applies this operator to `lhs` and `rhs`.
+    pub(crate) fn apply(self, lhs: Felt, rhs: Felt) -> Result<Felt, ConstantEvalError> {
+        let (x, y) = (lhs.as_int(), rhs.as_int());
+        let result = match self {
+            Self::Add => return Ok(lhs + rhs),
+            Self::Sub => return Ok(lhs - rhs),
+            Self::Mul => return Ok(lhs * rhs),
+            Self::Div | Self::IntDiv | Self::Mod if y == 0 => {
+                return Err(ConstantEvalError::DivisionByZero);
+            },
+            Self::Div => return Ok(lhs / rhs),
+            Self::IntDiv => x / y,
+            Self::Mod => x % y,
+            Self::Shl => match u32::try_from(y).ok().and_then(|y| x.checked_shl(y)) {
+                Some(shifted) if shifted >> y == x => shifted,
+                _ if x == 0 => 0,
+                _ => return Err(ConstantEvalError::Overflow),
+            },
+            Self::Shr => u32::try_from(y).ok().and_then(|y| x.checked_shr(y)).unwrap_or(0),
+            Self::And => x & y,
+            Self::Or => x | y,
+            Self::Xor => x ^ y,
+            Self::Eq => (x == y) as u64,
+            Self::Neq => (x != y) as u64,
+            Self::Lt => (x < y) as u64,
+            Self::Lte => (x <= y) as u64,
+            Self::Gt => (x > y) as u64,
+            Self::Gte => (x >= y) as u64,
+        };
+        if result >= Felt::MODULUS {
+            return Err(ConstantEvalError::Overflow);
+        }
+        Ok(Felt::new(result))
+    }
 }
 
 impl fmt::Display for ConstantOp {
@@ -278,6 +311,163 @@ impl fmt::Display for ConstantOp {
             Self::Mul => f.write_str("*"),
             Self::Div => f.write_str("/"),
             Self::IntDiv => f.write_str("//"),
+            Self::Mod => f.write_str("%"),
+            Self::Shl => f.write_str("<<"),
+            Self::Shr => f.write_str(">>"),
+            Self::And => f.write_str("&"),
+            Self::Or => f.write_str("|"),
+            Self::Xor => f.write_str("^"),
+            Self::Eq => f.write_str("=="),
+            Self::Neq => f.write_str("!="),
+            Self::Lt => f.write_str("<"),
+            Self::Lte => f.write_str("<="),
+            Self::Gt => f.write_str(">"),
+            Self::Gte => f.write_str(">="),
+        }
+    }
+}
+
+// DATA CONSTANT
+// ================================================================================================
+
+/// Represents a data constant definition in Miden Assembly syntax, i.e. `const.FOO="foo"` or
+/// `const.FOO=[1, 2, BAR]`.
+///
+/// Unlike a [Constant], which denotes a single field element, a data constant denotes a sequence
+/// of field elements, which can be pushed on the stack, or written to memory, as a whole.
+#[derive(Clone)]
+pub struct DataConstant {
+    /// The source span of the definition.
+    pub span: SourceSpan,
+    /// The documentation string attached to this definition.
+    pub docs: Option<DocString>,
+    /// The name of the constant.
+    pub name: Ident,
+    /// The data associated with the constant.
+    pub value: DataValue,
+}
+
+impl DataConstant {
+    /// Creates a new [DataConstant] from the given source span, name, and value.
+    pub fn new(span: SourceSpan, name: Ident, value: DataValue) -> Self {
+        Self { span, docs: None, name, value }
+    }
+
+    /// Adds documentation to this constant declaration.
+    pub fn with_docs(mut self, docs: Option<Span<String>>) -> Self {
+        self.docs = docs.map(DocString::new);
+        self
+    }
+}
+
+impl fmt::Debug for DataConstant {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("DataConstant")
+            .field("docs", &self.docs)
+            .field("name", &self.name)
+            .field("value", &self.value)
+            .finish()
+    }
+}
+
+impl crate::prettier::PrettyPrint for DataConstant {
+    fn render(&self) -> crate::prettier::Document {
+        use crate::prettier::*;
+
+        let mut doc = self
+            .docs
+            .as_ref()
+            .map(|docstring| docstring.render())
+            .unwrap_or(Document::Empty);
+
+        doc += flatten(const_text("const") + const_text(".") + display(&self.name));
+        doc += const_text("=");
+
+        doc + self.value.render()
+    }
+}
+
+impl Eq for DataConstant {}
+
+impl PartialEq for DataConstant {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.value == other.value
+    }
+}
+
+impl Spanned for DataConstant {
+    fn span(&self) -> SourceSpan {
+        self.span
+    }
+}
+
+// DATA VALUE
+// ================================================================================================
+
+/// Represents the value of a data constant in Miden Assembly syntax.
+#[derive(Clone, PartialEq, Eq)]
+pub enum DataValue {
+    /// A quoted string, whose UTF-8 bytes are packed into field elements, four bytes per element,
+    /// in little-endian order.
+    String(Span<Arc<str>>),
+    /// A list of constant expressions, one per field element.
+    Elements(Span<Vec<ConstantExpr>>),
+}
+
+impl DataValue {
+    /// Returns the field elements denoted by this value.
+    ///
+    /// This is used in places where we expect the elements to have been folded to literals,
+    /// otherwise a bug occurred.
+    #[track_caller]
+    pub fn expect_elements(&self) -> Vec<Felt> {
+        match self {
+            Self::String(value) => value
+                .as_bytes()
+                .chunks(4)
+                .map(|chunk| {
+                    let mut bytes = [0u8; 4];
+                    bytes[..chunk.len()].copy_from_slice(chunk);
+                    Felt::from(u32::from_le_bytes(bytes))
+                })
+                .collect(),
+            Self::Elements(elements) => elements.iter().map(ConstantExpr::expect_literal).collect(),
+        }
+    }
+}
+
+impl fmt::Debug for DataValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::String(value) => f.debug_tuple("String").field(&value.inner()).finish(),
+            Self::Elements(elements) => f.debug_list().entries(elements.iter()).finish(),
+        }
+    }
+}
+
+impl crate::prettier::PrettyPrint for DataValue {
+    fn render(&self) -> crate::prettier::Document {
+        use crate::prettier::*;
+
+        match self {
+            Self::String(value) => const_text("\"") + display(value.inner()) + const_text("\""),
+            Self::Elements(elements) => {
+                let elements = elements
+                    .iter()
+                    .map(|element| element.render())
+                    .reduce(|acc, element| acc + const_text(", ") + element)
+                    .unwrap_or(Document::Empty);
+                const_text("[") + elements + const_text("]")
+            },
+        }
+    }
+}
+
+impl Spanned for DataValue {
+    fn span(&self) -> SourceSpan {
+        match self {
+            Self::String(value) => value.span(),
+            Self::Elements(elements) => elements.span(),
         }
     }
 }
@@ -1,6 +1,6 @@
 use alloc::string::String;
 
-use super::{Block, Constant, Export, Import};
+use super::{Block, Constant, DataConstant, Export, Import, Macro};
 use crate::{SourceSpan, Span, Spanned};
 
 /// This type represents the top-level forms of a Miden Assembly module
@@ -14,10 +14,14 @@ pub enum Form {
     Import(Import),
     /// A constant definition, possibly unresolved
     Constant(Constant),
+    /// A data constant definition, possibly unresolved
+    DataConstant(DataConstant),
     /// An executable block, represents a program entrypoint
     Begin(Block),
     /// A procedure
     Procedure(Export),
+    /// A macro definition
+    Macro(Macro),
 }
 
 impl From<Span<String>> for Form {
@@ -38,6 +42,12 @@ impl From<Constant> for Form {
     }
 }
 
+impl From<DataConstant> for Form {
+    fn from(constant: DataConstant) -> Self {
+        Self::DataConstant(constant)
+    }
+}
+
 impl From<Block> for Form {
     fn from(block: Block) -> Self {
         Self::Begin(block)
@@ -50,13 +60,22 @@ impl From<Export> for Form {
     }
 }
 
+impl From<Macro> for Form {
+    fn from(definition: Macro) -> Self {
+        Self::Macro(definition)
+    }
+}
+
 impl Spanned for Form {
     fn span(&self) -> SourceSpan {
         match self {
             Self::ModuleDoc(spanned) | Self::Doc(spanned) => spanned.span(),
-            Self::Import(Import { span, .. }) | Self::Constant(Constant { span, .. }) => *span,
+            Self::Import(Import { span, .. })
+            | Self::Constant(Constant { span, .. })
+            | Self::DataConstant(DataConstant { span, .. }) => *span,
             Self::Begin(spanned) => spanned.span(),
             Self::Procedure(spanned) => spanned.span(),
+            Self::Macro(spanned) => spanned.span(),
         }
     }
 }
@@ -1,10 +1,15 @@
-use alloc::{string::ToString, sync::Arc};
+use alloc::{
+    string::{String, ToString},
+    sync::Arc,
+};
 use core::{
     fmt,
     hash::{Hash, Hasher},
     str::FromStr,
 };
 
+use vm_core::utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+
 use crate::{SourceSpan, Span, Spanned};
 
 /// Represents the types of errors that can occur when parsing/validating an [Ident]
@@ -199,6 +204,21 @@ impl FromStr for Ident {
     }
 }
 
+impl Serializable for Ident {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.as_str().write_into(target)
+    }
+}
+
+impl Deserializable for Ident {
+    /// Reads an identifier without validating it, as identifiers are also used to represent
+    /// arbitrary strings, e.g. the quoted strings of attributes.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let name: String = source.read()?;
+        Ok(Self::from_raw_parts(Span::unknown(Arc::from(name.into_boxed_str()))))
+    }
+}
+
 #[cfg(feature = "testing")]
 pub(crate) mod testing {
     use alloc::string::String;
@@ -115,6 +115,25 @@ impl<T: PartialEq> PartialEq for Immediate<T> {
     }
 }
 
+impl<T: Ord> PartialOrd for Immediate<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Ord> Ord for Immediate<T> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        use core::cmp::Ordering;
+
+        match (self, other) {
+            (Self::Value(l), Self::Value(r)) => l.cmp(r),
+            (Self::Value(_), Self::Constant(_)) => Ordering::Less,
+            (Self::Constant(_), Self::Value(_)) => Ordering::Greater,
+            (Self::Constant(l), Self::Constant(r)) => l.cmp(r),
+        }
+    }
+}
+
 impl<T: PartialEq> PartialEq<T> for Immediate<T> {
     fn eq(&self, other: &T) -> bool {
         match self {
@@ -7,7 +7,7 @@ use alloc::vec::Vec;
 pub use self::{advice::SystemEventNode, debug::DebugOptions};
 use crate::{
     Felt, Word,
-    ast::{InvocationTarget, immediate::*},
+    ast::{Ident, InvocationTarget, immediate::*},
 };
 
 // INSTRUCTION
@@ -123,6 +123,24 @@ pub enum Instruction {
     U32Min,
     U32Max,
 
+    // ----- u64 operations ----------------------------------------------------------------------
+    U64OverflowingAdd,
+    U64OverflowingSub,
+
+    // ----- i32 operations ----------------------------------------------------------------------
+    I32Neg,
+    I32Abs,
+    I32Div,
+    I32Mod,
+    I32DivMod,
+    I32Lt,
+    I32Lte,
+    I32Gt,
+    I32Gte,
+    I32Min,
+    I32Max,
+    I32Shr,
+
     // ----- stack manipulation ------------------------------------------------------------------
     Drop,
     DropW,
@@ -218,6 +236,7 @@ pub enum Instruction {
     Sdepth,
     Caller,
     Clk,
+    Ctx,
 
     MemLoad,
     MemLoadImm(ImmU32),
@@ -233,6 +252,8 @@ pub enum Instruction {
     MemStoreWImm(ImmU32),
     LocStoreW(ImmU16),
 
+    MemInit(Ident),
+
     MemStream,
     AdvPipe,
 
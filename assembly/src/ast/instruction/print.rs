@@ -128,6 +128,22 @@ impl PrettyPrint for Instruction {
             Self::U32Gte => const_text("u32gte"),
             Self::U32Min => const_text("u32min"),
             Self::U32Max => const_text("u32max"),
+            Self::U64OverflowingAdd => const_text("u64overflowing_add"),
+            Self::U64OverflowingSub => const_text("u64overflowing_sub"),
+
+            // ----- i32 operations ---------------------------------------------------------------
+            Self::I32Neg => const_text("i32neg"),
+            Self::I32Abs => const_text("i32abs"),
+            Self::I32Div => const_text("i32div"),
+            Self::I32Mod => const_text("i32mod"),
+            Self::I32DivMod => const_text("i32divmod"),
+            Self::I32Lt => const_text("i32lt"),
+            Self::I32Lte => const_text("i32lte"),
+            Self::I32Gt => const_text("i32gt"),
+            Self::I32Gte => const_text("i32gte"),
+            Self::I32Min => const_text("i32min"),
+            Self::I32Max => const_text("i32max"),
+            Self::I32Shr => const_text("i32shr"),
 
             // ----- stack manipulation -----------------------------------------------------------
             Self::Drop => const_text("drop"),
@@ -227,6 +243,7 @@ impl PrettyPrint for Instruction {
             Self::Sdepth => const_text("sdepth"),
             Self::Caller => const_text("caller"),
             Self::Clk => const_text("clk"),
+            Self::Ctx => const_text("ctx"),
 
             Self::MemLoad => const_text("mem_load"),
             Self::MemLoadImm(value) => inst_with_imm("mem_load", value),
@@ -242,6 +259,10 @@ impl PrettyPrint for Instruction {
             Self::MemStoreWImm(value) => inst_with_imm("mem_storew", value),
             Self::LocStoreW(value) => inst_with_imm("loc_storew", value),
 
+            Self::MemInit(name) => {
+                flatten(const_text("mem_init") + const_text(".") + display(name))
+            },
+
             Self::MemStream => const_text("mem_stream"),
             Self::AdvPipe => const_text("adv_pipe"),
 
@@ -273,57 +294,29 @@ impl PrettyPrint for Instruction {
                     + const_text(".")
                     + text(format!("{:#x}", DisplayHex(root.as_bytes().as_slice()))),
             ),
-            Self::Exec(InvocationTarget::ProcedureName(name)) => {
-                flatten(const_text("exec") + const_text(".") + text(name))
-            },
-            Self::Exec(InvocationTarget::ProcedurePath { name, module }) => {
-                const_text("exec") + const_text(".") + text(format!("{}::{}", module, name))
-            },
-            Self::Exec(InvocationTarget::AbsoluteProcedurePath { name, path }) => {
-                const_text("exec") + const_text(".") + text(format!("::{}::{}", path, name))
-            },
+            Self::Exec(target) => flatten(const_text("exec") + const_text(".") + target.render()),
             Self::Call(InvocationTarget::MastRoot(root)) => {
                 const_text("call")
                     + const_text(".")
                     + text(format!("{:#x}", DisplayHex(root.as_bytes().as_slice())))
             },
-            Self::Call(InvocationTarget::ProcedureName(name)) => {
-                flatten(const_text("call") + const_text(".") + text(name))
-            },
-            Self::Call(InvocationTarget::ProcedurePath { name, module }) => {
-                const_text("call") + const_text(".") + text(format!("{}::{}", module, name))
-            },
-            Self::Call(InvocationTarget::AbsoluteProcedurePath { name, path }) => {
-                const_text("call") + const_text(".") + text(format!("::{}::{}", path, name))
-            },
+            Self::Call(target) => flatten(const_text("call") + const_text(".") + target.render()),
             Self::SysCall(InvocationTarget::MastRoot(root)) => {
                 const_text("syscall")
                     + const_text(".")
                     + text(format!("{:#x}", DisplayHex(root.as_bytes().as_slice())))
             },
-            Self::SysCall(InvocationTarget::ProcedureName(name)) => {
-                flatten(const_text("syscall") + const_text(".") + text(format!("{}", name)))
-            },
-            Self::SysCall(InvocationTarget::ProcedurePath { name, module }) => {
-                const_text("syscall") + const_text(".") + text(format!("{}::{}", module, name))
-            },
-            Self::SysCall(InvocationTarget::AbsoluteProcedurePath { name, path }) => {
-                const_text("syscall") + const_text(".") + text(format!("::{}::{}", path, name))
+            Self::SysCall(target) => {
+                flatten(const_text("syscall") + const_text(".") + target.render())
             },
             Self::DynExec => const_text("dynexec"),
             Self::DynCall => const_text("dyncall"),
             Self::ProcRef(InvocationTarget::MastRoot(_)) => {
                 panic!("invalid procref instruction: expected name not MAST root")
             },
-            Self::ProcRef(InvocationTarget::ProcedureName(name)) => {
-                flatten(const_text("procref") + const_text(".") + text(name))
+            Self::ProcRef(target) => {
+                flatten(const_text("procref") + const_text(".") + target.render())
             },
-            Self::ProcRef(InvocationTarget::ProcedurePath { name, module }) => flatten(
-                const_text("procref") + const_text(".") + text(format!("{}::{}", module, name)),
-            ),
-            Self::ProcRef(InvocationTarget::AbsoluteProcedurePath { name, path }) => flatten(
-                const_text("procref") + const_text(".") + text(format!("::{}::{}", path, name)),
-            ),
 
             // ----- debug decorators -------------------------------------------------------------
             Self::Breakpoint => const_text("breakpoint"),
@@ -1,8 +1,9 @@
+use alloc::vec::Vec;
 use core::fmt;
 
 use crate::{
     LibraryPath, RpoDigest, SourceSpan, Span, Spanned,
-    ast::{Ident, ProcedureName},
+    ast::{Ident, ImmU32, ProcedureName},
 };
 
 // INVOKE
@@ -66,6 +67,12 @@ pub enum InvocationTarget {
     /// A fully-resolved procedure path, which refers to a specific externally-defined procedure
     /// with its full path.
     AbsoluteProcedurePath { name: ProcedureName, path: LibraryPath },
+    /// An instance of a locally-defined generic procedure, e.g. `hash_n<4>`.
+    ///
+    /// Instances are replaced by references to the procedure monomorphized for the given
+    /// arguments during semantic analysis, hence are never present in an analyzed
+    /// [Module](super::Module).
+    Instance { name: ProcedureName, args: Vec<ImmU32> },
 }
 
 impl Spanned for InvocationTarget {
@@ -73,9 +80,9 @@ impl Spanned for InvocationTarget {
         match self {
             Self::MastRoot(spanned) => spanned.span(),
             Self::ProcedureName(spanned) => spanned.span(),
-            Self::ProcedurePath { name, .. } | Self::AbsoluteProcedurePath { name, .. } => {
-                name.span()
-            },
+            Self::ProcedurePath { name, .. }
+            | Self::AbsoluteProcedurePath { name, .. }
+            | Self::Instance { name, .. } => name.span(),
         }
     }
 }
@@ -87,11 +94,22 @@ impl crate::prettier::PrettyPrint for InvocationTarget {
         use crate::prettier::*;
 
         match self {
-            Self::MastRoot(digest) => display(DisplayHex(digest.as_bytes().as_slice())),
-            Self::ProcedureName(name) => display(name),
-            Self::ProcedurePath { name, module } => display(format_args!("{}::{}", module, name)),
+            Self::MastRoot(digest) => {
+                display(format_args!("{:#x}", DisplayHex(digest.as_bytes().as_slice())))
+            },
+            Self::ProcedureName(name) => name.render(),
+            Self::ProcedurePath { name, module } => {
+                display(module) + const_text("::") + name.render()
+            },
             Self::AbsoluteProcedurePath { name, path } => {
-                display(format_args!("::{}::{}", path, name))
+                display(format_args!("::{}::", path)) + name.render()
+            },
+            Self::Instance { name, args } => {
+                let args = args
+                    .iter()
+                    .map(|arg| arg.render())
+                    .reduce(|acc, arg| acc + const_text(", ") + arg);
+                name.render() + const_text("<") + args.unwrap_or(Document::Empty) + const_text(">")
             },
         }
     }
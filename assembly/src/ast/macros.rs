@@ -0,0 +1,162 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use super::{Block, Ident, ImmFelt};
+use crate::{SourceSpan, Spanned};
+
+// MACRO DEFINITION
+// ================================================================================================
+
+/// Represents a macro definition in Miden Assembly syntax, i.e. `macro.name(PARAM, ..) .. end`.
+///
+/// Macros are local to the module which defines them, and are expanded in place of each of their
+/// invocations (see [MacroInvocation]) before semantic analysis. Within the body of a macro, its
+/// parameters can be referenced wherever a constant can be, and shadow the constants of the
+/// module; they are substituted with the arguments of each invocation upon expansion.
+#[derive(Clone)]
+pub struct Macro {
+    span: SourceSpan,
+    name: Ident,
+    params: Vec<Ident>,
+    body: Block,
+}
+
+impl Macro {
+    /// Creates a new [Macro] named `name`, with the given parameters and body.
+    pub fn new(span: SourceSpan, name: Ident, params: Vec<Ident>, body: Block) -> Self {
+        Self { span, name, params, body }
+    }
+
+    /// Returns the name of this macro.
+    pub fn name(&self) -> &Ident {
+        &self.name
+    }
+
+    /// Returns the names of the parameters of this macro.
+    pub fn params(&self) -> &[Ident] {
+        &self.params
+    }
+
+    /// Returns the body of this macro, before expansion.
+    pub fn body(&self) -> &Block {
+        &self.body
+    }
+
+    /// Returns a mutable reference to the body of this macro.
+    pub fn body_mut(&mut self) -> &mut Block {
+        &mut self.body
+    }
+}
+
+impl fmt::Debug for Macro {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Macro")
+            .field("name", &self.name)
+            .field("params", &self.params)
+            .field("body", &self.body)
+            .finish()
+    }
+}
+
+impl crate::prettier::PrettyPrint for Macro {
+    fn render(&self) -> crate::prettier::Document {
+        use crate::prettier::*;
+
+        let mut doc = display(format_args!("macro.{}", &self.name));
+        if !self.params.is_empty() {
+            let params = self
+                .params
+                .iter()
+                .map(display)
+                .reduce(|acc, param| acc + const_text(", ") + param);
+            doc += const_text("(") + params.unwrap() + const_text(")");
+        }
+        doc + self.body.render() + const_text("end")
+    }
+}
+
+impl Spanned for Macro {
+    fn span(&self) -> SourceSpan {
+        self.span
+    }
+}
+
+impl Eq for Macro {}
+
+impl PartialEq for Macro {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.params == other.params && self.body == other.body
+    }
+}
+
+// MACRO INVOCATION
+// ================================================================================================
+
+/// Represents an invocation of a [Macro] in Miden Assembly syntax, i.e. `name!(ARG, ..)`.
+///
+/// Each argument is either a literal value, or the name of a constant of the invoking module.
+#[derive(Clone)]
+pub struct MacroInvocation {
+    span: SourceSpan,
+    name: Ident,
+    args: Vec<ImmFelt>,
+}
+
+impl MacroInvocation {
+    /// Creates a new invocation of the macro named `name`, with the given arguments.
+    pub fn new(span: SourceSpan, name: Ident, args: Vec<ImmFelt>) -> Self {
+        Self { span, name, args }
+    }
+
+    /// Returns the name of the invoked macro.
+    pub fn name(&self) -> &Ident {
+        &self.name
+    }
+
+    /// Returns the arguments of this invocation.
+    pub fn args(&self) -> &[ImmFelt] {
+        &self.args
+    }
+
+    /// Returns a mutable reference to the arguments of this invocation.
+    pub fn args_mut(&mut self) -> &mut [ImmFelt] {
+        &mut self.args
+    }
+}
+
+impl fmt::Debug for MacroInvocation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MacroInvocation")
+            .field("name", &self.name)
+            .field("args", &self.args)
+            .finish()
+    }
+}
+
+impl crate::prettier::PrettyPrint for MacroInvocation {
+    fn render(&self) -> crate::prettier::Document {
+        use crate::prettier::*;
+
+        let mut doc = display(format_args!("{}!", &self.name));
+        if !self.args.is_empty() {
+            let args =
+                self.args.iter().map(display).reduce(|acc, arg| acc + const_text(", ") + arg);
+            doc += const_text("(") + args.unwrap() + const_text(")");
+        }
+        doc
+    }
+}
+
+impl Spanned for MacroInvocation {
+    fn span(&self) -> SourceSpan {
+        self.span
+    }
+}
+
+impl Eq for MacroInvocation {}
+
+impl PartialEq for MacroInvocation {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.args == other.args
+    }
+}
@@ -10,6 +10,7 @@ mod immediate;
 mod imports;
 mod instruction;
 mod invocation_target;
+mod macros;
 mod module;
 mod op;
 mod procedure;
@@ -23,7 +24,7 @@ pub use self::{
         MetaKeyValue, MetaList,
     },
     block::Block,
-    constants::{Constant, ConstantExpr, ConstantOp},
+    constants::{Constant, ConstantExpr, ConstantOp, DataConstant, DataValue},
     docstring::DocString,
     form::Form,
     ident::{CaseKindError, Ident, IdentError},
@@ -31,12 +32,15 @@ pub use self::{
     imports::Import,
     instruction::{DebugOptions, Instruction, SystemEventNode, advice::SignatureKind},
     invocation_target::{InvocationTarget, Invoke, InvokeKind},
+    macros::{Macro, MacroInvocation},
     module::{Module, ModuleKind},
     op::Op,
     procedure::*,
     visit::{Visit, VisitMut},
 };
 
+pub(crate) use self::constants::ConstantEvalError;
+
 pub(crate) type SmallOpsVec = smallvec::SmallVec<[Op; 1]>;
 
 /// Maximum stack index at which a full word can start.
@@ -1,9 +1,14 @@
-use alloc::{boxed::Box, string::String, sync::Arc, vec::Vec};
+use alloc::{
+    boxed::Box,
+    string::{String, ToString},
+    sync::Arc,
+    vec::Vec,
+};
 use core::fmt;
 
 use super::{
-    DocString, Export, Import, LocalNameResolver, ProcedureIndex, ProcedureName,
-    QualifiedProcedureName, ResolvedProcedure,
+    Constant, DataConstant, DocString, Export, Import, LocalNameResolver, ProcedureIndex,
+    ProcedureName, QualifiedProcedureName, ResolvedProcedure,
 };
 use crate::{
     ByteReader, ByteWriter, Deserializable, DeserializationError, LibraryNamespace, LibraryPath,
@@ -119,6 +124,16 @@ pub struct Module {
     kind: ModuleKind,
     /// The imports defined in the module body.
     pub(crate) imports: Vec<Import>,
+    /// The constants defined in the module body.
+    ///
+    /// Constants are folded to their values during semantic analysis, and all references to them
+    /// are replaced with the value of the constant, so these are retained purely as metadata.
+    pub(crate) constants: Vec<Constant>,
+    /// The data constants defined in the module body.
+    ///
+    /// Like constants, these are retained purely as metadata, as all references to them are
+    /// lowered to primitive instructions during semantic analysis.
+    pub(crate) data_constants: Vec<DataConstant>,
     /// The procedures (defined or re-exported) in the module body.
     ///
     /// NOTE: Despite the name, the procedures in this set are not necessarily exported, the
@@ -149,6 +164,8 @@ impl Module {
             path,
             kind,
             imports: Default::default(),
+            constants: Default::default(),
+            data_constants: Default::default(),
             procedures: Default::default(),
         }
     }
@@ -296,6 +313,21 @@ impl Module {
         self.index_of(|p| p.is_main()).is_some()
     }
 
+    /// Get an iterator over the constants defined in this module.
+    ///
+    /// The value of each constant is the literal it was evaluated to during semantic analysis.
+    pub fn constants(&self) -> core::slice::Iter<'_, Constant> {
+        self.constants.iter()
+    }
+
+    /// Get an iterator over the data constants defined in this module.
+    ///
+    /// The elements of each data constant are the literals they were evaluated to during semantic
+    /// analysis.
+    pub fn data_constants(&self) -> core::slice::Iter<'_, DataConstant> {
+        self.data_constants.iter()
+    }
+
     /// Get an iterator over the procedures defined in this module.
     ///
     /// The entity returned is an [Export], which abstracts over locally-defined procedures and
@@ -467,6 +499,51 @@ impl PartialEq for Module {
     }
 }
 
+/// Serialization of analyzed modules
+///
+/// A [Module] is serialized as its kind, its fully-qualified path, and its pretty-printed Miden
+/// Assembly source. Because the module has already gone through semantic analysis, the printed
+/// form has all constants folded and all imports resolved, so re-analyzing it on deserialization
+/// is cheap, and is done only to re-establish the invariants expected by the assembler.
+///
+/// NOTE: Source locations are not preserved across serialization, the spans of a deserialized
+/// module refer to the printed source, not the original one.
+impl Serializable for Module {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.kind.write_into(target);
+        self.path.write_into(target);
+        // A documentation comment on the first line is always parsed as the module docs, so if
+        // this module has none, make sure the docs of its first item are not mistaken for them
+        let mut source = String::new();
+        if self.docs.is_none() {
+            source.push('\n');
+        }
+        source.push_str(&self.to_string());
+        target.write_usize(source.len());
+        target.write_bytes(source.as_bytes());
+    }
+}
+
+impl Deserializable for Module {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        use crate::{DefaultSourceManager, SourceManager};
+
+        let kind = ModuleKind::read_from(source)?;
+        let path = LibraryPath::read_from(source)?;
+        let len = source.read_usize()?;
+        let bytes = source.read_slice(len)?;
+        let text = core::str::from_utf8(bytes)
+            .map_err(|err| DeserializationError::InvalidValue(err.to_string()))?;
+
+        let source_manager = DefaultSourceManager::default();
+        let name = Arc::<str>::from(path.path().into_owned().into_boxed_str());
+        let source_file = source_manager.load(&name, text.to_string());
+        Module::parse(path, kind, source_file)
+            .map(|module| *module)
+            .map_err(|err| DeserializationError::InvalidValue(err.to_string()))
+    }
+}
+
 /// Debug representation of this module
 impl fmt::Debug for Module {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -475,6 +552,8 @@ impl fmt::Debug for Module {
             .field("path", &self.path)
             .field("kind", &self.kind)
             .field("imports", &self.imports)
+            .field("constants", &self.constants)
+            .field("data_constants", &self.data_constants)
             .field("procedures", &self.procedures)
             .finish()
     }
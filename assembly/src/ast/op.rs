@@ -1,6 +1,6 @@
 use core::fmt;
 
-use super::{Block, Instruction};
+use super::{Attribute, Block, ImmU32, Instruction, MacroInvocation};
 use crate::{SourceSpan, Span, Spanned};
 
 /// Represents the Miden Assembly instruction set syntax
@@ -25,14 +25,30 @@ pub enum Op {
     /// Represents a counter-controlled loop.
     ///
     /// NOTE: The iteration count must be known at compile-time, so this is _not_ used for general
-    /// `for`-style loops where the iteration count is dynamic.
+    /// `for`-style loops where the iteration count is dynamic. It may however refer to a constant,
+    /// or to a parameter of a generic procedure.
     Repeat {
         span: SourceSpan,
-        count: u32,
+        count: ImmU32,
         body: Block,
     } = 2,
     /// A primitive operation, e.g. `add`
     Inst(Span<Instruction>) = 3,
+    /// An invocation of a macro, e.g. `shuffle!(2)`
+    ///
+    /// Invocations are replaced by the body of the invoked macro during semantic analysis, and
+    /// hence are never present in an analyzed [Module](super::Module).
+    Expand(MacroInvocation) = 4,
+    /// An operation which is conditionally compiled, e.g. `@cfg(feature = "debug") exec.check`
+    ///
+    /// The predicate given by `attribute` is evaluated during semantic analysis, which replaces
+    /// this with the contents of `body` if it holds, or removes it otherwise. Hence, these are
+    /// never present in an analyzed [Module](super::Module).
+    Cfg {
+        span: SourceSpan,
+        attribute: Attribute,
+        body: Block,
+    } = 5,
 }
 
 impl crate::prettier::PrettyPrint for Op {
@@ -48,6 +64,11 @@ impl crate::prettier::PrettyPrint for Op {
                 display(format!("repeat.{count}")) + body.render() + text("end")
             },
             Self::Inst(inst) => inst.render(),
+            Self::Expand(invocation) => invocation.render(),
+            Self::Cfg { attribute, body, .. } => body
+                .iter()
+                .map(PrettyPrint::render)
+                .fold(attribute.render(), |acc, doc| acc + nl() + doc),
         }
     }
 }
@@ -63,6 +84,10 @@ impl fmt::Debug for Op {
                 f.debug_struct("Repeat").field("count", count).field("body", body).finish()
             },
             Self::Inst(inst) => fmt::Debug::fmt(&**inst, f),
+            Self::Expand(invocation) => fmt::Debug::fmt(invocation, f),
+            Self::Cfg { attribute, body, .. } => {
+                f.debug_struct("Cfg").field("attribute", attribute).field("body", body).finish()
+            },
         }
     }
 }
@@ -82,6 +107,11 @@ impl PartialEq for Op {
                 Self::Repeat { count: rcount, body: rbody, .. },
             ) => lcount == rcount && lbody == rbody,
             (Self::Inst(l), Self::Inst(r)) => l == r,
+            (Self::Expand(l), Self::Expand(r)) => l == r,
+            (
+                Self::Cfg { attribute: lattr, body: lbody, .. },
+                Self::Cfg { attribute: rattr, body: rbody, .. },
+            ) => lattr == rattr && lbody == rbody,
             _ => false,
         }
     }
@@ -90,8 +120,12 @@ impl PartialEq for Op {
 impl Spanned for Op {
     fn span(&self) -> SourceSpan {
         match self {
-            Self::If { span, .. } | Self::While { span, .. } | Self::Repeat { span, .. } => *span,
+            Self::If { span, .. }
+            | Self::While { span, .. }
+            | Self::Repeat { span, .. }
+            | Self::Cfg { span, .. } => *span,
             Self::Inst(spanned) => spanned.span(),
+            Self::Expand(invocation) => invocation.span(),
         }
     }
 }
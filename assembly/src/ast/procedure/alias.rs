@@ -166,7 +166,9 @@ impl TryFrom<InvocationTarget> for AliasTarget {
             InvocationTarget::AbsoluteProcedurePath { name, path: module } => {
                 Ok(Self::AbsoluteProcedurePath(QualifiedProcedureName { span, module, name }))
             },
-            target @ InvocationTarget::ProcedureName(_) => Err(target),
+            target @ (InvocationTarget::ProcedureName(_) | InvocationTarget::Instance { .. }) => {
+                Err(target)
+            },
         }
     }
 }
@@ -210,7 +212,9 @@ impl crate::prettier::PrettyPrint for AliasTarget {
         use crate::prettier::*;
 
         match self {
-            Self::MastRoot(digest) => display(DisplayHex(digest.as_bytes().as_slice())),
+            Self::MastRoot(digest) => {
+                display(format_args!("{:#x}", DisplayHex(digest.as_bytes().as_slice())))
+            },
             Self::ProcedurePath(fqn) => display(fqn),
             Self::AbsoluteProcedurePath(fqn) => display(format_args!("::{}", fqn)),
         }
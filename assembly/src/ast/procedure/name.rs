@@ -225,6 +225,15 @@ impl ProcedureName {
     pub fn as_str(&self) -> &str {
         self.as_ref()
     }
+
+    /// Returns true if this name must be quoted in Miden Assembly source, i.e. if it is not a
+    /// valid bare identifier.
+    pub fn requires_quoting(&self) -> bool {
+        let mut chars = self.0.as_str().chars();
+        let is_bare_char = |c: char| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_';
+        !chars.next().is_some_and(|c| c.is_ascii_lowercase() || c == '_')
+            || !chars.all(is_bare_char)
+    }
 }
 
 impl Eq for ProcedureName {}
@@ -300,6 +309,18 @@ impl PartialEq<Ident> for ProcedureName {
     }
 }
 
+impl crate::prettier::PrettyPrint for ProcedureName {
+    fn render(&self) -> vm_core::prettier::Document {
+        use crate::prettier::*;
+
+        if self.requires_quoting() {
+            text(format!("\"{}\"", self.as_str()))
+        } else {
+            display(self)
+        }
+    }
+}
+
 impl fmt::Display for ProcedureName {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.write_str(&self.0)
@@ -1,10 +1,10 @@
-use alloc::{collections::BTreeSet, string::String};
+use alloc::{collections::BTreeSet, string::String, vec::Vec};
 use core::fmt;
 
 use super::ProcedureName;
 use crate::{
     SourceSpan, Span, Spanned,
-    ast::{Attribute, AttributeSet, Block, DocString, Invoke},
+    ast::{Attribute, AttributeSet, Block, DocString, Ident, Invoke},
 };
 
 // PROCEDURE VISIBILITY
@@ -59,6 +59,8 @@ pub struct Procedure {
     attrs: AttributeSet,
     /// The local name of this procedure
     name: ProcedureName,
+    /// The compile-time parameters of this procedure, if it is generic, e.g. `N` in `hash_n<N>`
+    params: Vec<Ident>,
     /// The visibility of this procedure (i.e. whether it is exported or not)
     visibility: Visibility,
     /// The number of locals to allocate for this procedure
@@ -85,6 +87,7 @@ impl Procedure {
             docs: None,
             attrs: Default::default(),
             name,
+            params: Vec::new(),
             visibility,
             num_locals,
             invoked: Default::default(),
@@ -98,6 +101,12 @@ impl Procedure {
         self
     }
 
+    /// Makes this procedure generic over the given compile-time parameters
+    pub fn with_params(mut self, params: Vec<Ident>) -> Self {
+        self.params = params;
+        self
+    }
+
     /// Adds attributes to this procedure definition
     pub fn with_attributes<I>(mut self, attrs: I) -> Self
     where
@@ -146,6 +155,18 @@ impl Procedure {
         self.docs.as_ref().map(|docstring| docstring.as_spanned_str())
     }
 
+    /// Get the compile-time parameters of this procedure, which are empty unless it is generic
+    #[inline]
+    pub fn params(&self) -> &[Ident] {
+        &self.params
+    }
+
+    /// Returns true if this procedure is generic, i.e. if it has compile-time parameters
+    #[inline]
+    pub fn is_generic(&self) -> bool {
+        !self.params.is_empty()
+    }
+
     /// Get the attributes attached to this procedure
     #[inline]
     pub fn attributes(&self) -> &AttributeSet {
@@ -255,13 +276,19 @@ impl crate::prettier::PrettyPrint for Procedure {
                 .iter()
                 .map(|attr| attr.render())
                 .reduce(|acc, attr| acc + nl() + attr)
-                .unwrap_or(Document::Empty);
+                .unwrap_or(Document::Empty)
+                + nl();
         }
 
         if self.is_entrypoint() {
             doc += const_text("begin");
         } else {
-            doc += display(self.visibility) + const_text(".") + display(&self.name);
+            doc += display(self.visibility) + const_text(".") + self.name.render();
+            if self.is_generic() {
+                let params =
+                    self.params.iter().map(text).reduce(|acc, p| acc + const_text(", ") + p);
+                doc += const_text("<") + params.unwrap_or(Document::Empty) + const_text(">");
+            }
             if self.num_locals > 0 {
                 doc += const_text(".") + display(self.num_locals);
             }
@@ -277,6 +304,7 @@ impl fmt::Debug for Procedure {
             .field("docs", &self.docs)
             .field("attrs", &self.attrs)
             .field("name", &self.name)
+            .field("params", &self.params)
             .field("visibility", &self.visibility)
             .field("num_locals", &self.num_locals)
             .field("body", &self.body)
@@ -290,6 +318,7 @@ impl Eq for Procedure {}
 impl PartialEq for Procedure {
     fn eq(&self, other: &Self) -> bool {
         self.name == other.name
+            && self.params == other.params
             && self.visibility == other.visibility
             && self.num_locals == other.num_locals
             && self.body == other.body
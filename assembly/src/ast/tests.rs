@@ -744,7 +744,7 @@ fn test_use_in_proc_body() {
         "  :          `-- found a use here",
         "5 |     end",
         "  `----",
-        r#" help: expected primitive opcode (e.g. "add"), or "end", or control flow opcode (e.g. "if.true")"#
+        r#" help: expected "@", or primitive opcode (e.g. "add"), or "end", or control flow opcode (e.g. "if.true")"#
     );
 }
 
@@ -761,7 +761,7 @@ fn test_unterminated_proc() {
         "  :                  ^^|^^",
         "  :                    `-- found a begin here",
         "  `----",
-        r#" help: expected ".", or primitive opcode (e.g. "add"), or "end", or control flow opcode (e.g. "if.true")"#
+        r#" help: expected ".", or "@", or primitive opcode (e.g. "add"), or "end", or control flow opcode (e.g. "if.true")"#
     );
 }
 
@@ -778,7 +778,7 @@ fn test_unterminated_if() {
         "  :                                ^^|^^",
         "  :                                  `-- found a begin here",
         "  `----",
-        r#" help: expected primitive opcode (e.g. "add"), or "else", or "end", or control flow opcode (e.g. "if.true")"#
+        r#" help: expected "@", or primitive opcode (e.g. "add"), or "else", or "end", or control flow opcode (e.g. "if.true")"#
     );
 }
 
@@ -1041,7 +1041,7 @@ fn test_ast_parsing_module_docs_fail() {
         "4 |         loc_load.0",
         "5 |     end",
         "  `----",
-        r#" help: expected primitive opcode (e.g. "add"), or control flow opcode (e.g. "if.true")"#
+        r#" help: expected "@", or primitive opcode (e.g. "add"), or control flow opcode (e.g. "if.true")"#
     );
 }
 
@@ -1067,7 +1067,7 @@ fn assert_parsing_line_unmatched_begin() {
         "4 |         add",
         "5 |         mul",
         "  `----",
-        r#"help: expected ".", or primitive opcode (e.g. "add"), or "end", or control flow opcode (e.g. "if.true")"#
+        r#"help: expected ".", or "@", or primitive opcode (e.g. "add"), or "end", or control flow opcode (e.g. "if.true")"#
     );
 }
 
@@ -1091,7 +1091,7 @@ fn assert_parsing_line_extra_param() {
         "  :                `-- found a . here",
         "3 |         end",
         "  `----",
-        r#" help: expected primitive opcode (e.g. "add"), or "end", or control flow opcode (e.g. "if.true")"#
+        r#" help: expected "@", or primitive opcode (e.g. "add"), or "end", or control flow opcode (e.g. "if.true")"#
     );
 }
 
@@ -1143,7 +1143,7 @@ fn assert_parsing_line_invalid_op() {
         "   :                      `-- found a identifier here",
         "29 |         end",
         "   `----",
-        r#" help: expected ".", or primitive opcode (e.g. "add"), or "end", or control flow opcode (e.g. "if.true")"#
+        r#" help: expected ".", or "@", or primitive opcode (e.g. "add"), or "end", or control flow opcode (e.g. "if.true")"#
     );
 }
 
@@ -1168,7 +1168,7 @@ fn assert_parsing_line_unexpected_token() {
         "  :     ^|^",
         "  :      `-- found a mul here",
         "  `----",
-        r#" help: expected "@", or "begin", or "const", or "export", or "proc", or "use", or end of file, or doc comment"#
+        r#" help: expected "@", or "begin", or "const", or "export", or "macro", or "proc", or "use", or end of file, or doc comment"#
     );
 }
 
@@ -98,6 +98,9 @@ pub trait Visit<T = ()> {
     fn visit_inst(&mut self, inst: &Span<Instruction>) -> ControlFlow<T> {
         visit_inst(self, inst)
     }
+    fn visit_macro_invocation(&mut self, invocation: &MacroInvocation) -> ControlFlow<T> {
+        visit_macro_invocation(self, invocation)
+    }
     fn visit_system_event(&mut self, sys_event: Span<&SystemEventNode>) -> ControlFlow<T> {
         visit_system_event(self, sys_event)
     }
@@ -167,6 +170,9 @@ where
     fn visit_inst(&mut self, inst: &Span<Instruction>) -> ControlFlow<T> {
         (**self).visit_inst(inst)
     }
+    fn visit_macro_invocation(&mut self, invocation: &MacroInvocation) -> ControlFlow<T> {
+        (**self).visit_macro_invocation(invocation)
+    }
     fn visit_system_event(&mut self, sys_event: Span<&SystemEventNode>) -> ControlFlow<T> {
         (**self).visit_system_event(sys_event)
     }
@@ -274,11 +280,27 @@ where
             visitor.visit_block(then_blk)?;
             visitor.visit_block(else_blk)
         },
-        Op::While { body, .. } | Op::Repeat { body, .. } => visitor.visit_block(body),
+        Op::While { body, .. } => visitor.visit_block(body),
+        Op::Repeat { count, body, .. } => {
+            visitor.visit_immediate_u32(count)?;
+            visitor.visit_block(body)
+        },
         Op::Inst(inst) => visitor.visit_inst(inst),
+        Op::Expand(invocation) => visitor.visit_macro_invocation(invocation),
+        Op::Cfg { body, .. } => visitor.visit_block(body),
     }
 }
 
+pub fn visit_macro_invocation<V, T>(visitor: &mut V, invocation: &MacroInvocation) -> ControlFlow<T>
+where
+    V: ?Sized + Visit<T>,
+{
+    for arg in invocation.args() {
+        visitor.visit_immediate_felt(arg)?;
+    }
+    ControlFlow::Continue(())
+}
+
 pub fn visit_inst<V, T>(visitor: &mut V, inst: &Span<Instruction>) -> ControlFlow<T>
 where
     V: ?Sized + Visit<T>,
@@ -331,20 +353,22 @@ where
         | U32WrappingSub | U32OverflowingSub | U32WrappingMul | U32OverflowingMul
         | U32OverflowingMadd | U32WrappingMadd | U32Div | U32Mod | U32DivMod | U32And | U32Or
         | U32Xor | U32Not | U32Shr | U32Shl | U32Rotr | U32Rotl | U32Popcnt | U32Clz | U32Ctz
-        | U32Clo | U32Cto | U32Lt | U32Lte | U32Gt | U32Gte | U32Min | U32Max | Drop | DropW
-        | PadW | Dup0 | Dup1 | Dup2 | Dup3 | Dup4 | Dup5 | Dup6 | Dup7 | Dup8 | Dup9 | Dup10
-        | Dup11 | Dup12 | Dup13 | Dup14 | Dup15 | DupW0 | DupW1 | DupW2 | DupW3 | Swap1 | Swap2
-        | Swap3 | Swap4 | Swap5 | Swap6 | Swap7 | Swap8 | Swap9 | Swap10 | Swap11 | Swap12
-        | Swap13 | Swap14 | Swap15 | SwapW1 | SwapW2 | SwapW3 | SwapDw | MovUp2 | MovUp3
-        | MovUp4 | MovUp5 | MovUp6 | MovUp7 | MovUp8 | MovUp9 | MovUp10 | MovUp11 | MovUp12
-        | MovUp13 | MovUp14 | MovUp15 | MovUpW2 | MovUpW3 | MovDn2 | MovDn3 | MovDn4 | MovDn5
-        | MovDn6 | MovDn7 | MovDn8 | MovDn9 | MovDn10 | MovDn11 | MovDn12 | MovDn13 | MovDn14
-        | MovDn15 | MovDnW2 | MovDnW3 | CSwap | CSwapW | CDrop | CDropW | PushU8(_)
-        | PushU16(_) | PushU32(_) | PushFelt(_) | PushWord(_) | PushU8List(_) | PushU16List(_)
-        | PushU32List(_) | PushFeltList(_) | Sdepth | Caller | Clk | MemLoad | MemLoadW
-        | MemStore | MemStoreW | MemStream | AdvPipe | AdvLoadW | Hash | HMerge | HPerm
-        | MTreeGet | MTreeSet | MTreeMerge | MTreeVerify | FriExt2Fold4 | DynExec | DynCall
-        | Breakpoint | HornerBase | HornerExt => ControlFlow::Continue(()),
+        | U32Clo | U32Cto | U32Lt | U32Lte | U32Gt | U32Gte | U32Min | U32Max
+        | U64OverflowingAdd | U64OverflowingSub | I32Neg | I32Abs | I32Div | I32Mod | I32DivMod
+        | I32Lt | I32Lte | I32Gt | I32Gte | I32Min | I32Max | I32Shr | Drop | DropW | PadW
+        | Dup0 | Dup1 | Dup2 | Dup3 | Dup4 | Dup5 | Dup6 | Dup7 | Dup8 | Dup9 | Dup10 | Dup11
+        | Dup12 | Dup13 | Dup14 | Dup15 | DupW0 | DupW1 | DupW2 | DupW3 | Swap1 | Swap2 | Swap3
+        | Swap4 | Swap5 | Swap6 | Swap7 | Swap8 | Swap9 | Swap10 | Swap11 | Swap12 | Swap13
+        | Swap14 | Swap15 | SwapW1 | SwapW2 | SwapW3 | SwapDw | MovUp2 | MovUp3 | MovUp4
+        | MovUp5 | MovUp6 | MovUp7 | MovUp8 | MovUp9 | MovUp10 | MovUp11 | MovUp12 | MovUp13
+        | MovUp14 | MovUp15 | MovUpW2 | MovUpW3 | MovDn2 | MovDn3 | MovDn4 | MovDn5 | MovDn6
+        | MovDn7 | MovDn8 | MovDn9 | MovDn10 | MovDn11 | MovDn12 | MovDn13 | MovDn14 | MovDn15
+        | MovDnW2 | MovDnW3 | CSwap | CSwapW | CDrop | CDropW | PushU8(_) | PushU16(_)
+        | PushU32(_) | PushFelt(_) | PushWord(_) | PushU8List(_) | PushU16List(_)
+        | PushU32List(_) | PushFeltList(_) | Sdepth | Caller | Clk | Ctx | MemLoad | MemLoadW
+        | MemInit(_) | MemStore | MemStoreW | MemStream | AdvPipe | AdvLoadW | Hash | HMerge
+        | HPerm | MTreeGet | MTreeSet | MTreeMerge | MTreeVerify | FriExt2Fold4 | DynExec
+        | DynCall | Breakpoint | HornerBase | HornerExt => ControlFlow::Continue(()),
     }
 }
 
@@ -409,10 +433,15 @@ where
 }
 
 #[inline(always)]
-pub fn visit_invoke_target<V, T>(_visitor: &mut V, _target: &InvocationTarget) -> ControlFlow<T>
+pub fn visit_invoke_target<V, T>(visitor: &mut V, target: &InvocationTarget) -> ControlFlow<T>
 where
     V: ?Sized + Visit<T>,
 {
+    if let InvocationTarget::Instance { args, .. } = target {
+        for arg in args {
+            visitor.visit_immediate_u32(arg)?;
+        }
+    }
     ControlFlow::Continue(())
 }
 
@@ -512,6 +541,9 @@ pub trait VisitMut<T = ()> {
     fn visit_mut_inst(&mut self, inst: &mut Span<Instruction>) -> ControlFlow<T> {
         visit_mut_inst(self, inst)
     }
+    fn visit_mut_macro_invocation(&mut self, invocation: &mut MacroInvocation) -> ControlFlow<T> {
+        visit_mut_macro_invocation(self, invocation)
+    }
     fn visit_mut_system_event(&mut self, sys_event: Span<&mut SystemEventNode>) -> ControlFlow<T> {
         visit_mut_system_event(self, sys_event)
     }
@@ -581,6 +613,9 @@ where
     fn visit_mut_inst(&mut self, inst: &mut Span<Instruction>) -> ControlFlow<T> {
         (**self).visit_mut_inst(inst)
     }
+    fn visit_mut_macro_invocation(&mut self, invocation: &mut MacroInvocation) -> ControlFlow<T> {
+        (**self).visit_mut_macro_invocation(invocation)
+    }
     fn visit_mut_system_event(&mut self, sys_event: Span<&mut SystemEventNode>) -> ControlFlow<T> {
         (**self).visit_mut_system_event(sys_event)
     }
@@ -691,11 +726,30 @@ where
             visitor.visit_mut_block(then_blk)?;
             visitor.visit_mut_block(else_blk)
         },
-        Op::While { body, .. } | Op::Repeat { body, .. } => visitor.visit_mut_block(body),
+        Op::While { body, .. } => visitor.visit_mut_block(body),
+        Op::Repeat { count, body, .. } => {
+            visitor.visit_mut_immediate_u32(count)?;
+            visitor.visit_mut_block(body)
+        },
         Op::Inst(inst) => visitor.visit_mut_inst(inst),
+        Op::Expand(invocation) => visitor.visit_mut_macro_invocation(invocation),
+        Op::Cfg { body, .. } => visitor.visit_mut_block(body),
     }
 }
 
+pub fn visit_mut_macro_invocation<V, T>(
+    visitor: &mut V,
+    invocation: &mut MacroInvocation,
+) -> ControlFlow<T>
+where
+    V: ?Sized + VisitMut<T>,
+{
+    for arg in invocation.args_mut() {
+        visitor.visit_mut_immediate_felt(arg)?;
+    }
+    ControlFlow::Continue(())
+}
+
 pub fn visit_mut_inst<V, T>(visitor: &mut V, inst: &mut Span<Instruction>) -> ControlFlow<T>
 where
     V: ?Sized + VisitMut<T>,
@@ -748,20 +802,22 @@ where
         | U32WrappingSub | U32OverflowingSub | U32WrappingMul | U32OverflowingMul
         | U32OverflowingMadd | U32WrappingMadd | U32Div | U32Mod | U32DivMod | U32And | U32Or
         | U32Xor | U32Not | U32Shr | U32Shl | U32Rotr | U32Rotl | U32Popcnt | U32Clz | U32Ctz
-        | U32Clo | U32Cto | U32Lt | U32Lte | U32Gt | U32Gte | U32Min | U32Max | Drop | DropW
-        | PadW | Dup0 | Dup1 | Dup2 | Dup3 | Dup4 | Dup5 | Dup6 | Dup7 | Dup8 | Dup9 | Dup10
-        | Dup11 | Dup12 | Dup13 | Dup14 | Dup15 | DupW0 | DupW1 | DupW2 | DupW3 | Swap1 | Swap2
-        | Swap3 | Swap4 | Swap5 | Swap6 | Swap7 | Swap8 | Swap9 | Swap10 | Swap11 | Swap12
-        | Swap13 | Swap14 | Swap15 | SwapW1 | SwapW2 | SwapW3 | SwapDw | MovUp2 | MovUp3
-        | MovUp4 | MovUp5 | MovUp6 | MovUp7 | MovUp8 | MovUp9 | MovUp10 | MovUp11 | MovUp12
-        | MovUp13 | MovUp14 | MovUp15 | MovUpW2 | MovUpW3 | MovDn2 | MovDn3 | MovDn4 | MovDn5
-        | MovDn6 | MovDn7 | MovDn8 | MovDn9 | MovDn10 | MovDn11 | MovDn12 | MovDn13 | MovDn14
-        | MovDn15 | MovDnW2 | MovDnW3 | CSwap | CSwapW | CDrop | CDropW | PushU8(_)
-        | PushU16(_) | PushU32(_) | PushFelt(_) | PushWord(_) | PushU8List(_) | PushU16List(_)
-        | PushU32List(_) | PushFeltList(_) | Sdepth | Caller | Clk | MemLoad | MemLoadW
-        | MemStore | MemStoreW | MemStream | AdvPipe | AdvLoadW | Hash | HMerge | HPerm
-        | MTreeGet | MTreeSet | MTreeMerge | MTreeVerify | FriExt2Fold4 | DynExec | DynCall
-        | Breakpoint | HornerBase | HornerExt => ControlFlow::Continue(()),
+        | U32Clo | U32Cto | U32Lt | U32Lte | U32Gt | U32Gte | U32Min | U32Max
+        | U64OverflowingAdd | U64OverflowingSub | I32Neg | I32Abs | I32Div | I32Mod | I32DivMod
+        | I32Lt | I32Lte | I32Gt | I32Gte | I32Min | I32Max | I32Shr | Drop | DropW | PadW
+        | Dup0 | Dup1 | Dup2 | Dup3 | Dup4 | Dup5 | Dup6 | Dup7 | Dup8 | Dup9 | Dup10 | Dup11
+        | Dup12 | Dup13 | Dup14 | Dup15 | DupW0 | DupW1 | DupW2 | DupW3 | Swap1 | Swap2 | Swap3
+        | Swap4 | Swap5 | Swap6 | Swap7 | Swap8 | Swap9 | Swap10 | Swap11 | Swap12 | Swap13
+        | Swap14 | Swap15 | SwapW1 | SwapW2 | SwapW3 | SwapDw | MovUp2 | MovUp3 | MovUp4
+        | MovUp5 | MovUp6 | MovUp7 | MovUp8 | MovUp9 | MovUp10 | MovUp11 | MovUp12 | MovUp13
+        | MovUp14 | MovUp15 | MovUpW2 | MovUpW3 | MovDn2 | MovDn3 | MovDn4 | MovDn5 | MovDn6
+        | MovDn7 | MovDn8 | MovDn9 | MovDn10 | MovDn11 | MovDn12 | MovDn13 | MovDn14 | MovDn15
+        | MovDnW2 | MovDnW3 | CSwap | CSwapW | CDrop | CDropW | PushU8(_) | PushU16(_)
+        | PushU32(_) | PushFelt(_) | PushWord(_) | PushU8List(_) | PushU16List(_)
+        | PushU32List(_) | PushFeltList(_) | Sdepth | Caller | Clk | Ctx | MemLoad | MemLoadW
+        | MemInit(_) | MemStore | MemStoreW | MemStream | AdvPipe | AdvLoadW | Hash | HMerge
+        | HPerm | MTreeGet | MTreeSet | MTreeMerge | MTreeVerify | FriExt2Fold4 | DynExec
+        | DynCall | Breakpoint | HornerBase | HornerExt => ControlFlow::Continue(()),
     }
 }
 
@@ -833,12 +889,17 @@ where
 
 #[inline(always)]
 pub fn visit_mut_invoke_target<V, T>(
-    _visitor: &mut V,
-    _target: &mut InvocationTarget,
+    visitor: &mut V,
+    target: &mut InvocationTarget,
 ) -> ControlFlow<T>
 where
     V: ?Sized + VisitMut<T>,
 {
+    if let InvocationTarget::Instance { args, .. } = target {
+        for arg in args {
+            visitor.visit_mut_immediate_u32(arg)?;
+        }
+    }
     ControlFlow::Continue(())
 }
 
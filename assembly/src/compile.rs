@@ -1,6 +1,7 @@
 use alloc::{
     borrow::Cow,
     boxed::Box,
+    collections::BTreeSet,
     string::{String, ToString},
     sync::Arc,
     vec::Vec,
@@ -9,6 +10,7 @@ use alloc::{
 use miette::miette;
 
 use crate::{
+    ResourceLimits,
     ast::{Module, ModuleKind},
     diagnostics::{
         IntoDiagnostic, NamedSource, Report, SourceCode, SourceContent, SourceFile, SourceManager,
@@ -37,6 +39,15 @@ pub struct Options {
     /// then the path will consist of just a namespace; using the value of `namespace` if provided,
     /// or deriving one from `kind`.
     pub path: Option<LibraryPath>,
+    /// The limits on the resources which may be consumed when parsing and analyzing the module.
+    ///
+    /// By default, no limits are enforced.
+    pub limits: ResourceLimits,
+    /// The features enabled when conditionally compiling the module.
+    ///
+    /// Procedures and operations annotated with `@cfg(feature = "name")` are only compiled if
+    /// `name` is in this set, which is empty by default.
+    pub features: BTreeSet<Arc<str>>,
 }
 
 impl Default for Options {
@@ -45,6 +56,8 @@ impl Default for Options {
             kind: ModuleKind::Executable,
             warnings_as_errors: false,
             path: None,
+            limits: ResourceLimits::UNLIMITED,
+            features: BTreeSet::new(),
         }
     }
 }
@@ -193,6 +206,8 @@ impl Compile for Arc<SourceFile> {
         };
         let mut parser = Module::parser(options.kind);
         parser.set_warnings_as_errors(options.warnings_as_errors);
+        parser.set_resource_limits(options.limits);
+        parser.set_features(options.features);
         parser.parse(path, source_file)
     }
 }
@@ -245,6 +260,8 @@ impl Compile for Box<str> {
         let name = Arc::<str>::from(path.path().into_owned().into_boxed_str());
         let mut parser = Module::parser(options.kind);
         parser.set_warnings_as_errors(options.warnings_as_errors);
+        parser.set_resource_limits(options.limits);
+        parser.set_features(options.features);
         let content = SourceContent::new(name.clone(), self);
         let source_file = source_manager.load_from_raw_parts(name, content);
         parser.parse(path, source_file)
@@ -342,6 +359,8 @@ where
         let source_file = source_manager.load_from_raw_parts(name, content);
         let mut parser = Module::parser(options.kind);
         parser.set_warnings_as_errors(options.warnings_as_errors);
+        parser.set_resource_limits(options.limits);
+        parser.set_features(options.features);
         parser.parse(path, source_file)
     }
 }
@@ -401,6 +420,8 @@ impl Compile for &std::path::Path {
             .into_diagnostic()
             .wrap_err("source manager is unable to load file")?;
         let mut parser = Module::parser(options.kind);
+        parser.set_resource_limits(options.limits);
+        parser.set_features(options.features);
         parser.parse(path, source_file)
     }
 }
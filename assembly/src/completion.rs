@@ -0,0 +1,298 @@
+//! Context-sensitive completion data for editor integrations.
+//!
+//! Completions are computed from the assembler's own knowledge of a module: the instruction set
+//! recognized by the parser, the constants and procedures defined in the module, and the
+//! procedures exported by the modules it imports. See [crate::Assembler::completions] for the
+//! entrypoint to this API.
+
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
+
+use crate::{
+    ast::{ConstantExpr, Module, Visibility},
+    parser::Token,
+    prettier::PrettyPrint,
+};
+
+// COMPLETION
+// ================================================================================================
+
+/// The kind of item a [Completion] refers to.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CompletionKind {
+    /// A keyword which is only valid at the top level of a module, e.g. `proc` or `use`.
+    Keyword,
+    /// An instruction or control flow opcode, e.g. `add` or `if`.
+    Instruction,
+    /// A constant defined in the current module.
+    Constant,
+    /// The local name of an imported module.
+    Module,
+    /// A procedure defined in the current module, or exported from an imported module.
+    Procedure,
+}
+
+impl fmt::Display for CompletionKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Keyword => f.write_str("keyword"),
+            Self::Instruction => f.write_str("instruction"),
+            Self::Constant => f.write_str("constant"),
+            Self::Module => f.write_str("module"),
+            Self::Procedure => f.write_str("procedure"),
+        }
+    }
+}
+
+/// A single completion candidate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Completion {
+    /// The text to insert in place of the partially-typed word at the cursor.
+    pub label: String,
+    /// The kind of item being completed.
+    pub kind: CompletionKind,
+    /// A short, single-line description of the item, e.g. the value of a constant, or the
+    /// fully-qualified path of a procedure.
+    pub detail: Option<String>,
+    /// The documentation attached to the item, if any.
+    pub docs: Option<String>,
+}
+
+impl Completion {
+    fn new(label: impl ToString, kind: CompletionKind) -> Self {
+        Self {
+            label: label.to_string(),
+            kind,
+            detail: None,
+            docs: None,
+        }
+    }
+
+    fn with_detail(mut self, detail: impl ToString) -> Self {
+        self.detail = Some(detail.to_string());
+        self
+    }
+
+    fn with_docs(mut self, docs: Option<&str>) -> Self {
+        self.docs = docs.map(|docs| docs.to_string());
+        self
+    }
+}
+
+// COMPLETION CONTEXT
+// ================================================================================================
+
+/// The syntactic position of the cursor, as far as completion is concerned.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum CompletionContext<'a> {
+    /// The cursor is at the top level of the module, outside of any procedure body.
+    TopLevel { prefix: &'a str },
+    /// The cursor is at the start of an instruction in a procedure body.
+    Instruction { prefix: &'a str },
+    /// The cursor is in the immediate of an `adv` instruction, i.e. `adv.<cursor>`.
+    AdviceInjector { prefix: &'a str },
+    /// The cursor is at the target of an invocation, i.e. `exec.<cursor>`, and `module` is set
+    /// when the target is qualified with the name of an imported module.
+    InvocationTarget { module: Option<&'a str>, prefix: &'a str },
+    /// The cursor is in the immediate of some other instruction, i.e. `push.<cursor>`.
+    Immediate { prefix: &'a str },
+}
+
+impl<'a> CompletionContext<'a> {
+    /// Determines the completion context of the cursor at byte `offset` in `source`.
+    ///
+    /// This is based purely on the text preceding the cursor, as the source is expected to be
+    /// incomplete, and thus not necessarily valid Miden Assembly, at the time completions are
+    /// requested.
+    pub fn at(source: &'a str, offset: usize) -> Self {
+        let mut offset = offset.min(source.len());
+        while !source.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        let before = &source[..offset];
+        let word_start = before
+            .rfind(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | ':' | '$')))
+            .map(|pos| pos + 1)
+            .unwrap_or(0);
+        let word = &before[word_start..];
+
+        let Some((opcode, rest)) = word.split_once('.') else {
+            return if is_in_procedure_body(&before[..word_start]) {
+                Self::Instruction { prefix: word }
+            } else {
+                Self::TopLevel { prefix: word }
+            };
+        };
+
+        match opcode {
+            "exec" | "call" | "syscall" | "procref" => match rest.rsplit_once("::") {
+                Some((module, prefix)) => Self::InvocationTarget { module: Some(module), prefix },
+                None => Self::InvocationTarget { module: None, prefix: rest },
+            },
+            "adv" => Self::AdviceInjector { prefix: rest },
+            _ => {
+                let prefix = rest.rsplit_once('.').map(|(_, prefix)| prefix).unwrap_or(rest);
+                Self::Immediate { prefix }
+            },
+        }
+    }
+}
+
+/// Returns true if the end of `source` is inside of a procedure body.
+///
+/// This tracks the nesting of blocks by counting block-opening keywords against `end`, skipping
+/// over comments.
+fn is_in_procedure_body(source: &str) -> bool {
+    let mut depth = 0usize;
+    for line in source.lines() {
+        let code = line.split_once('#').map(|(code, _)| code).unwrap_or(line);
+        for word in code.split_whitespace() {
+            let (opcode, rest) = word.split_once('.').unwrap_or((word, ""));
+            match opcode {
+                // Re-exports are not followed by a body
+                "export" if rest.contains("::") => (),
+                "proc" | "export" | "begin" | "if" | "while" | "repeat" => depth += 1,
+                "end" => depth = depth.saturating_sub(1),
+                _ => (),
+            }
+        }
+    }
+    depth > 0
+}
+
+// COMPLETION PROVIDERS
+// ================================================================================================
+
+/// Keywords which may appear at the top level of a module.
+const TOP_LEVEL_KEYWORDS: &[&str] = &["begin", "const", "export", "proc", "use"];
+
+/// Control flow opcodes, which are not instructions as far as the parser is concerned.
+const CONTROL_FLOW_KEYWORDS: &[&str] = &["if.true", "if.false", "while.true", "repeat"];
+
+/// Returns true if `keyword` is only valid in the immediate of an `adv` instruction.
+fn is_advice_injector(keyword: &str) -> bool {
+    keyword.starts_with("push_") || keyword.starts_with("insert_")
+}
+
+/// Computes the set of completions valid in `context`, using `module` for symbol information,
+/// and `exports_of` to look up the procedures exported by an imported module.
+pub(crate) fn complete<F>(
+    module: &Module,
+    context: CompletionContext<'_>,
+    exports_of: F,
+) -> Vec<Completion>
+where
+    F: Fn(&crate::LibraryPath) -> Vec<Completion>,
+{
+    let mut completions = Vec::new();
+    let prefix = match context {
+        CompletionContext::TopLevel { prefix } => {
+            completions.extend(
+                TOP_LEVEL_KEYWORDS.iter().map(|kw| Completion::new(kw, CompletionKind::Keyword)),
+            );
+            prefix
+        },
+        CompletionContext::Instruction { prefix } => {
+            completions.extend(
+                Token::instruction_keywords()
+                    .filter(|kw| !is_advice_injector(kw))
+                    .chain(CONTROL_FLOW_KEYWORDS.iter().copied())
+                    .map(|kw| Completion::new(kw, CompletionKind::Instruction)),
+            );
+            prefix
+        },
+        CompletionContext::AdviceInjector { prefix } => {
+            completions.extend(
+                Token::instruction_keywords()
+                    .filter(|kw| is_advice_injector(kw))
+                    .map(|kw| Completion::new(kw, CompletionKind::Instruction)),
+            );
+            prefix
+        },
+        CompletionContext::Immediate { prefix } => {
+            completions.extend(module.constants().map(|constant| {
+                // constants are only folded during semantic analysis, so the value of a constant
+                // in a freshly parsed module may still be an expression
+                let detail = match &constant.value {
+                    ConstantExpr::Literal(value) => value.to_string(),
+                    expr => expr.to_pretty_string(),
+                };
+                Completion::new(&constant.name, CompletionKind::Constant)
+                    .with_detail(detail)
+                    .with_docs(constant.docs.as_ref().map(|docs| docs.as_str()))
+            }));
+            prefix
+        },
+        CompletionContext::InvocationTarget { module: None, prefix } => {
+            completions.extend(module.procedures().filter(|p| !p.is_main()).map(|procedure| {
+                Completion::new(procedure.name(), CompletionKind::Procedure)
+                    .with_detail(format!("{}::{}", module.path(), procedure.name()))
+                    .with_docs(procedure.docs())
+            }));
+            completions.extend(module.imports().map(|import| {
+                Completion::new(&import.name, CompletionKind::Module).with_detail(&import.path)
+            }));
+            prefix
+        },
+        CompletionContext::InvocationTarget { module: Some(name), prefix } => {
+            if let Some(import) = module.imports().find(|import| import.name.as_str() == name) {
+                completions.extend(exports_of(&import.path));
+            }
+            prefix
+        },
+    };
+
+    // Deduplicate and sort by label, so that the output is deterministic
+    let completions = completions
+        .into_iter()
+        .filter(|completion| completion.label.starts_with(prefix))
+        .map(|completion| ((completion.label.clone(), completion.kind), completion))
+        .collect::<BTreeMap<_, _>>();
+    completions.into_values().collect()
+}
+
+/// Returns completions for the procedures exported from `module`.
+pub(crate) fn exported_procedures(module: &Module) -> Vec<Completion> {
+    module
+        .procedures()
+        .filter(|procedure| !matches!(procedure.visibility(), Visibility::Private))
+        .map(|procedure| {
+            Completion::new(procedure.name(), CompletionKind::Procedure)
+                .with_detail(format!("{}::{}", module.path(), procedure.name()))
+                .with_docs(procedure.docs())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CompletionContext;
+
+    #[test]
+    fn completion_context_detection() {
+        let source = "use.std::math::u64\n\nproc.foo\n    push.1 exec.u64::wrap";
+        assert_eq!(
+            CompletionContext::at(source, source.len()),
+            CompletionContext::InvocationTarget { module: Some("u64"), prefix: "wrap" }
+        );
+        assert_eq!(
+            CompletionContext::at(source, source.len() - "exec.u64::wrap".len() - 1),
+            CompletionContext::Immediate { prefix: "1" }
+        );
+        assert_eq!(
+            CompletionContext::at(source, source.len() - "exec.u64::wrap".len()),
+            CompletionContext::Instruction { prefix: "" }
+        );
+        assert_eq!(CompletionContext::at(source, 2), CompletionContext::TopLevel { prefix: "us" });
+
+        let source = "proc.foo\n    add\nend\n\nexport.foo::bar\n# proc.baz\npr";
+        assert_eq!(
+            CompletionContext::at(source, source.len()),
+            CompletionContext::TopLevel { prefix: "pr" }
+        );
+    }
+}
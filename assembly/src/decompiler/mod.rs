@@ -0,0 +1,441 @@
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::Write;
+
+use vm_core::{
+    Operation, Program,
+    crypto::hash::RpoDigest,
+    mast::{MastForest, MastNode, MastNodeId},
+};
+
+use crate::{
+    Library, Span,
+    ast::{Instruction, InvocationTarget, ProcedureName, QualifiedProcedureName},
+};
+
+#[cfg(test)]
+mod tests;
+
+/// The number of spaces by which nested blocks are indented.
+const INDENT: usize = 4;
+
+// DECOMPILER
+// ================================================================================================
+
+/// Reconstructs Miden Assembly source code from compiled MAST.
+///
+/// The reconstructed source contains:
+/// - A procedure for each procedure root of the MAST forest, named after the ID of its root node
+///   (e.g., `proc_3`). Procedure roots are invoked by name wherever they appear in the MAST.
+/// - A local procedure for each control flow node which is shared by several nodes of the forest,
+///   executed wherever it appears in the MAST, so that the reconstructed source grows linearly
+///   with the size of the forest, rather than with the number of paths through it.
+/// - The control flow of each procedure, i.e., `if.true`, `while.true`, `call`, `syscall`,
+///   `dynexec` and `dyncall` instructions.
+/// - The operations of each basic block, rendered as the instruction which assembles to exactly
+///   that operation where one exists, and as a comment otherwise.
+///
+/// Procedures exported by the libraries provided via [Decompiler::with_library] are recognized by
+/// their MAST roots, and invoked by their fully-qualified names instead. Other procedures which
+/// are not part of the MAST forest are invoked by their MAST roots.
+///
+/// Information which is lost during assembly, e.g., the names of local procedures, constants,
+/// comments and the instructions which were lowered to several operations, cannot be recovered.
+/// If none of the operations of the MAST had to be rendered as comments, assembling the
+/// reconstructed source with the same libraries results in an equivalent MAST forest, albeit not
+/// necessarily one with the same digests.
+#[derive(Debug, Clone, Default)]
+pub struct Decompiler {
+    /// The names of the known procedures, indexed by their MAST roots.
+    procedure_names: BTreeMap<RpoDigest, QualifiedProcedureName>,
+}
+
+impl Decompiler {
+    /// Returns a new decompiler which doesn't know about any library.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the exports of the specified library to the procedures which are recognized by their
+    /// MAST roots, and invoked by their fully-qualified names.
+    ///
+    /// If several procedures have the same MAST root, the name which was added first is used.
+    pub fn with_library(mut self, library: impl AsRef<Library>) -> Self {
+        let library = library.as_ref();
+        for name in library.exports() {
+            let digest = library.mast_forest()[library.get_export_node_id(name)].digest();
+            self.procedure_names.entry(digest).or_insert_with(|| name.clone());
+        }
+        self
+    }
+
+    /// Returns the Miden Assembly source of an executable module reconstructed from the specified
+    /// program.
+    pub fn decompile_program(&self, program: &Program) -> String {
+        let forest = program.mast_forest();
+        let mut writer = SourceWriter::new(self, forest);
+        writeln!(writer.source, "# decompiled from the MAST of program {}", program.hash())
+            .unwrap();
+        writer.write_procedures(Some(program.entrypoint()));
+        writer.write_line("begin");
+        writer.write_procedure_body(program.entrypoint());
+        writer.write_line("end");
+        writer.source
+    }
+
+    /// Returns the Miden Assembly source of a library module reconstructed from the specified MAST
+    /// forest, in which all procedure roots are exported.
+    pub fn decompile_forest(&self, forest: &MastForest) -> String {
+        let mut writer = SourceWriter::new(self, forest);
+        writer.write_line("# decompiled from a MAST forest");
+        writer.write_procedures(None);
+        writer.source
+    }
+
+    /// Returns the Miden Assembly source of the body of the procedure with the specified root,
+    /// without indentation.
+    ///
+    /// As the procedures of the forest are not defined alongside the body, the procedures it
+    /// invokes are referred to by their MAST roots, unless they are known. The control flow nodes
+    /// shared within the body are defined as local procedures preceding it.
+    pub fn decompile_procedure(&self, forest: &MastForest, root: MastNodeId) -> String {
+        let mut writer = SourceWriter::new(self, forest);
+        writer.local_procedure_names = false;
+        for node_id in writer.shared_nodes_of(root) {
+            writer.write_procedure("proc", node_id);
+            writer.write_line("");
+        }
+        writer.current_root = Some(root);
+        writer.write_node(root);
+        writer.source
+    }
+
+    /// Returns the name of the known procedure with the specified MAST root, if any.
+    fn procedure_name(&self, digest: &RpoDigest) -> Option<&QualifiedProcedureName> {
+        self.procedure_names.get(digest)
+    }
+}
+
+// SOURCE WRITER
+// ================================================================================================
+
+/// Writes the Miden Assembly source reconstructed from a MAST forest.
+struct SourceWriter<'a> {
+    decompiler: &'a Decompiler,
+    forest: &'a MastForest,
+    source: String,
+    indent: usize,
+    /// The root of the procedure being written.
+    current_root: Option<MastNodeId>,
+    /// The control flow nodes which are children of several nodes, and hence are written as
+    /// local procedures.
+    shared_nodes: BTreeSet<MastNodeId>,
+    /// Whether the procedures of the forest are invoked by their local names, rather than by
+    /// their MAST roots.
+    local_procedure_names: bool,
+}
+
+impl<'a> SourceWriter<'a> {
+    fn new(decompiler: &'a Decompiler, forest: &'a MastForest) -> Self {
+        Self {
+            decompiler,
+            forest,
+            source: String::new(),
+            indent: 0,
+            current_root: None,
+            shared_nodes: shared_nodes(forest),
+            local_procedure_names: true,
+        }
+    }
+
+    /// Writes a procedure for each procedure root of the forest which is neither the entrypoint
+    /// of a program, nor a known procedure, and for each shared node.
+    ///
+    /// Procedures are written in the order of their root IDs, so that every procedure is defined
+    /// before the procedures invoking it. If there is no entrypoint, the procedures of the
+    /// procedure roots are exported.
+    fn write_procedures(&mut self, entrypoint: Option<MastNodeId>) {
+        let mut roots = self.forest.procedure_roots().to_vec();
+        roots.extend(self.shared_nodes.iter().copied());
+        roots.sort();
+        roots.dedup();
+        for root in roots {
+            let digest = self.forest[root].digest();
+            if entrypoint == Some(root) || self.decompiler.procedure_name(&digest).is_some() {
+                continue;
+            }
+
+            let is_exported = entrypoint.is_none() && self.forest.is_procedure_root(root);
+            self.write_line("");
+            self.write_procedure(if is_exported { "export" } else { "proc" }, root);
+        }
+        self.write_line("");
+    }
+
+    /// Writes the procedure with the specified root, defined with the specified keyword.
+    fn write_procedure(&mut self, keyword: &str, root: MastNodeId) {
+        self.write_line(&format!("# MAST root: {}", self.forest[root].digest()));
+        self.write_line(&format!("{keyword}.{}", local_procedure_name(root)));
+        self.write_procedure_body(root);
+        self.write_line("end");
+    }
+
+    /// Returns the shared nodes of the procedure with the specified root, i.e., excluding the ones
+    /// of the procedures it invokes, in the order of their IDs.
+    fn shared_nodes_of(&self, root: MastNodeId) -> BTreeSet<MastNodeId> {
+        let mut shared_nodes = BTreeSet::new();
+        let mut visited = BTreeSet::new();
+        let mut worklist = vec![root];
+        while let Some(node_id) = worklist.pop() {
+            if !visited.insert(node_id)
+                || (node_id != root && self.forest.is_procedure_root(node_id))
+            {
+                continue;
+            }
+            if node_id != root && self.shared_nodes.contains(&node_id) {
+                shared_nodes.insert(node_id);
+            }
+            if !matches!(self.forest[node_id], MastNode::Call(_)) {
+                self.forest[node_id].append_children_to(&mut worklist);
+            }
+        }
+        shared_nodes
+    }
+
+    /// Writes the body of the procedure with the specified root.
+    fn write_procedure_body(&mut self, root: MastNodeId) {
+        self.current_root = Some(root);
+        self.indent += INDENT;
+        self.write_node(root);
+        self.indent -= INDENT;
+    }
+
+    fn write_node(&mut self, node_id: MastNodeId) {
+        // procedures other than the one being written are invoked rather than inlined
+        if self.current_root != Some(node_id)
+            && (self.forest.is_procedure_root(node_id) || self.shared_nodes.contains(&node_id))
+        {
+            let target = self.invocation_target(node_id);
+            self.write_instruction(Instruction::Exec(target));
+            return;
+        }
+
+        match &self.forest[node_id] {
+            MastNode::Block(block) => {
+                for op in block.operations() {
+                    match op_to_instruction(op) {
+                        Some(instruction) => self.write_instruction(instruction),
+                        None => self.write_line(&format!("# {op}")),
+                    }
+                }
+            },
+            MastNode::Join(join) => {
+                self.write_node(join.first());
+                self.write_node(join.second());
+            },
+            MastNode::Split(split) => {
+                self.write_line("if.true");
+                self.write_nested(split.on_true());
+                self.write_line("else");
+                self.write_nested(split.on_false());
+                self.write_line("end");
+            },
+            MastNode::Loop(loop_node) => {
+                self.write_line("while.true");
+                self.write_nested(loop_node.body());
+                self.write_line("end");
+            },
+            MastNode::Call(call) => {
+                let target = self.invocation_target(call.callee());
+                if call.is_syscall() {
+                    self.write_instruction(Instruction::SysCall(target));
+                } else {
+                    self.write_instruction(Instruction::Call(target));
+                }
+            },
+            MastNode::Dyn(dyn_node) if dyn_node.is_dyncall() => {
+                self.write_instruction(Instruction::DynCall)
+            },
+            MastNode::Dyn(_) => self.write_instruction(Instruction::DynExec),
+            MastNode::External(external) => {
+                let target = self.external_target(external.digest());
+                self.write_instruction(Instruction::Exec(target));
+            },
+        }
+    }
+
+    /// Writes the specified node as a nested block.
+    fn write_nested(&mut self, node_id: MastNodeId) {
+        self.indent += INDENT;
+        self.write_node(node_id);
+        self.indent -= INDENT;
+    }
+
+    /// Returns the target of an invocation of the specified node.
+    fn invocation_target(&self, node_id: MastNodeId) -> InvocationTarget {
+        let node = &self.forest[node_id];
+        if node.is_external() || self.decompiler.procedure_name(&node.digest()).is_some() {
+            self.external_target(node.digest())
+        } else if (self.local_procedure_names && self.forest.is_procedure_root(node_id))
+            || self.shared_nodes.contains(&node_id)
+        {
+            let name = ProcedureName::new(local_procedure_name(node_id))
+                .expect("local procedure names are valid identifiers");
+            InvocationTarget::ProcedureName(name)
+        } else {
+            InvocationTarget::MastRoot(Span::unknown(node.digest()))
+        }
+    }
+
+    /// Returns the target of an invocation of the procedure with the specified MAST root, which is
+    /// not defined in the forest.
+    fn external_target(&self, digest: RpoDigest) -> InvocationTarget {
+        match self.decompiler.procedure_name(&digest) {
+            Some(name) => InvocationTarget::AbsoluteProcedurePath {
+                name: name.name.clone(),
+                path: name.module.clone(),
+            },
+            None => InvocationTarget::MastRoot(Span::unknown(digest)),
+        }
+    }
+
+    fn write_instruction(&mut self, instruction: Instruction) {
+        self.write_line(&instruction.to_string());
+    }
+
+    fn write_line(&mut self, line: &str) {
+        if !line.is_empty() {
+            self.source.extend(core::iter::repeat_n(' ', self.indent));
+            self.source.push_str(line);
+        }
+        self.source.push('\n');
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the name of the procedure with the specified root in the reconstructed source.
+fn local_procedure_name(root: MastNodeId) -> String {
+    format!("proc_{}", root.as_u32())
+}
+
+/// Returns the control flow nodes of the specified forest which are children of several nodes,
+/// excluding procedure roots.
+fn shared_nodes(forest: &MastForest) -> BTreeSet<MastNodeId> {
+    let mut num_parents = vec![0usize; forest.num_nodes() as usize];
+    let mut children = Vec::new();
+    for node in forest.nodes() {
+        children.clear();
+        node.append_children_to(&mut children);
+        for child in children.iter() {
+            num_parents[child.as_usize()] += 1;
+        }
+    }
+
+    forest
+        .nodes()
+        .iter()
+        .enumerate()
+        .filter(|(index, node)| {
+            num_parents[*index] > 1
+                && matches!(node, MastNode::Join(_) | MastNode::Split(_) | MastNode::Loop(_))
+        })
+        .map(|(index, _)| MastNodeId::from_usize_safe(index, forest).expect("node exists"))
+        .filter(|&node_id| !forest.is_procedure_root(node_id))
+        .collect()
+}
+
+/// Returns the instruction which assembles to exactly the specified operation, if there is one.
+fn op_to_instruction(op: &Operation) -> Option<Instruction> {
+    let instruction = match *op {
+        Operation::Noop => Instruction::Nop,
+        Operation::Assert(0) => Instruction::Assert,
+        Operation::Assert(err_code) => Instruction::AssertWithError(err_code.into()),
+        Operation::SDepth => Instruction::Sdepth,
+        Operation::Caller => Instruction::Caller,
+        Operation::Clk => Instruction::Clk,
+        Operation::Ctx => Instruction::Ctx,
+        Operation::Emit(event_id) => Instruction::Emit(event_id.into()),
+
+        Operation::Add => Instruction::Add,
+        Operation::Neg => Instruction::Neg,
+        Operation::Mul => Instruction::Mul,
+        Operation::Inv => Instruction::Inv,
+        Operation::Incr => Instruction::Incr,
+        Operation::And => Instruction::And,
+        Operation::Or => Instruction::Or,
+        Operation::Not => Instruction::Not,
+        Operation::Eq => Instruction::Eq,
+
+        Operation::U32split => Instruction::U32Split,
+        Operation::U32add => Instruction::U32OverflowingAdd,
+        Operation::U32add3 => Instruction::U32OverflowingAdd3,
+        Operation::U32sub => Instruction::U32OverflowingSub,
+        Operation::U32mul => Instruction::U32OverflowingMul,
+        Operation::U32madd => Instruction::U32OverflowingMadd,
+        Operation::U32and => Instruction::U32And,
+        Operation::U32xor => Instruction::U32Xor,
+        Operation::U32assert2(0) => Instruction::U32Assert2,
+        Operation::U32assert2(err_code) => Instruction::U32Assert2WithError(err_code.into()),
+        Operation::U64add => Instruction::U64OverflowingAdd,
+        Operation::U64sub => Instruction::U64OverflowingSub,
+
+        Operation::Pad => Instruction::Push(vm_core::ZERO.into()),
+        Operation::Drop => Instruction::Drop,
+        Operation::Dup0 => Instruction::Dup0,
+        Operation::Dup1 => Instruction::Dup1,
+        Operation::Dup2 => Instruction::Dup2,
+        Operation::Dup3 => Instruction::Dup3,
+        Operation::Dup4 => Instruction::Dup4,
+        Operation::Dup5 => Instruction::Dup5,
+        Operation::Dup6 => Instruction::Dup6,
+        Operation::Dup7 => Instruction::Dup7,
+        Operation::Dup9 => Instruction::Dup9,
+        Operation::Dup11 => Instruction::Dup11,
+        Operation::Dup13 => Instruction::Dup13,
+        Operation::Dup15 => Instruction::Dup15,
+        Operation::Swap => Instruction::Swap1,
+        Operation::SwapW => Instruction::SwapW1,
+        Operation::SwapW2 => Instruction::SwapW2,
+        Operation::SwapW3 => Instruction::SwapW3,
+        Operation::SwapDW => Instruction::SwapDw,
+        Operation::MovUp2 => Instruction::MovUp2,
+        Operation::MovUp3 => Instruction::MovUp3,
+        Operation::MovUp4 => Instruction::MovUp4,
+        Operation::MovUp5 => Instruction::MovUp5,
+        Operation::MovUp6 => Instruction::MovUp6,
+        Operation::MovUp7 => Instruction::MovUp7,
+        Operation::MovUp8 => Instruction::MovUp8,
+        Operation::MovDn2 => Instruction::MovDn2,
+        Operation::MovDn3 => Instruction::MovDn3,
+        Operation::MovDn4 => Instruction::MovDn4,
+        Operation::MovDn5 => Instruction::MovDn5,
+        Operation::MovDn6 => Instruction::MovDn6,
+        Operation::MovDn7 => Instruction::MovDn7,
+        Operation::MovDn8 => Instruction::MovDn8,
+        Operation::CSwap => Instruction::CSwap,
+        Operation::CSwapW => Instruction::CSwapW,
+
+        Operation::Push(value) => Instruction::Push(value.into()),
+        Operation::AdvPop => Instruction::AdvPush(1.into()),
+        Operation::AdvPopW => Instruction::AdvLoadW,
+        Operation::MLoadW => Instruction::MemLoadW,
+        Operation::MStoreW => Instruction::MemStoreW,
+        Operation::MLoad => Instruction::MemLoad,
+        Operation::MStream => Instruction::MemStream,
+        Operation::Pipe => Instruction::AdvPipe,
+
+        Operation::HPerm => Instruction::HPerm,
+        Operation::MpVerify(0) => Instruction::MTreeVerify,
+        Operation::MpVerify(err_code) => Instruction::MTreeVerifyWithError(err_code.into()),
+        Operation::FriE2F4 => Instruction::FriExt2Fold4,
+        Operation::HornerBase => Instruction::HornerBase,
+        Operation::HornerExt => Instruction::HornerExt,
+        _ => return None,
+    };
+    Some(instruction)
+}
@@ -0,0 +1,212 @@
+use alloc::{format, string::ToString, vec::Vec};
+
+use vm_core::{
+    Felt, Operation, Program, ZERO,
+    mast::{MastForest, MastNode},
+};
+
+use super::{Decompiler, op_to_instruction};
+use crate::{Assembler, Library, LibraryPath, ModuleParser, ast::ModuleKind, testing::TestContext};
+
+fn assemble_library(context: &TestContext, path: &str, source: &str) -> Library {
+    let module = ModuleParser::new(ModuleKind::Library)
+        .parse_str(LibraryPath::new(path).unwrap(), source, &context.source_manager())
+        .unwrap();
+    Assembler::new(context.source_manager()).assemble_library([module]).unwrap()
+}
+
+#[test]
+fn decompiled_program_reassembles_to_same_program() {
+    let context = TestContext::default();
+    let library = assemble_library(
+        &context,
+        "test::math",
+        "export.double
+            dup add
+        end",
+    );
+
+    let source = "
+        use.test::math
+
+        proc.square
+            dup mul
+        end
+
+        proc.sum_squares
+            exec.square swap exec.square add
+        end
+
+        begin
+            push.3 push.4
+            call.sum_squares
+            exec.math::double
+            dup eq.50
+            if.true
+                push.1 add
+            else
+                while.true
+                    push.0
+                end
+            end
+            u32assert2.err=7
+            dynexec
+        end";
+    let program = Assembler::new(context.source_manager())
+        .with_library(&library)
+        .unwrap()
+        .assemble_program(source)
+        .unwrap();
+
+    let decompiled = Decompiler::new().with_library(&library).decompile_program(&program);
+    assert!(decompiled.contains("exec.::test::math::double"), "{decompiled}");
+    assert!(decompiled.contains("call.proc_"), "{decompiled}");
+    assert!(decompiled.contains("u32assert2.err=7"), "{decompiled}");
+    assert!(!decompiled.contains("\n    #"), "{decompiled}");
+
+    let reassembled = Assembler::new(context.source_manager())
+        .with_library(&library)
+        .unwrap()
+        .assemble_program(decompiled.as_str())
+        .unwrap();
+    assert_eq!(reassembled.hash(), program.hash(), "{decompiled}");
+}
+
+#[test]
+fn unknown_procedures_are_invoked_by_mast_root() {
+    let context = TestContext::default();
+    let library = assemble_library(
+        &context,
+        "test::lib",
+        "export.foo
+            push.1 drop
+        end
+
+        export.bar
+            call.foo
+        end",
+    );
+
+    let decompiled = Decompiler::new().decompile_forest(library.mast_forest());
+    assert_eq!(decompiled.matches("export.proc_").count(), 2, "{decompiled}");
+
+    let foo = library.mast_forest()[library.get_export_node_id(library.exports().next().unwrap())]
+        .digest();
+    let program = Assembler::new(context.source_manager())
+        .with_library(&library)
+        .unwrap()
+        .assemble_program(format!("begin call.{foo} end").as_str())
+        .unwrap();
+    let decompiled = Decompiler::new().decompile_program(&program);
+    assert!(decompiled.contains(&format!("call.{foo}")), "{decompiled}");
+}
+
+#[test]
+fn shared_nodes_are_written_once() {
+    // each join executes the previous one twice, i.e., the loop is executed 2^12 times
+    let mut forest = MastForest::new();
+    let body = forest.add_block(vec![Operation::Drop], None).unwrap();
+    let mut node = forest.add_loop(body).unwrap();
+    for _ in 0..12 {
+        node = forest.add_join(node, node).unwrap();
+    }
+    forest.make_root(node);
+    let program = Program::new(forest.into(), node);
+
+    let decompiled = Decompiler::new().decompile_program(&program);
+    assert_eq!(decompiled.matches("while.true").count(), 1, "{decompiled}");
+    assert_eq!(decompiled.matches("\nproc.proc_").count(), 12, "{decompiled}");
+
+    let context = TestContext::default();
+    let reassembled = Assembler::new(context.source_manager())
+        .assemble_program(decompiled.as_str())
+        .unwrap();
+    assert_eq!(reassembled.hash(), program.hash(), "{decompiled}");
+
+    // the shared nodes of a single procedure are defined along with its body
+    let body = Decompiler::new().decompile_procedure(program.mast_forest(), node);
+    assert_eq!(body.matches("while.true").count(), 1, "{body}");
+    assert!(body.ends_with("exec.proc_12\nexec.proc_12\n"), "{body}");
+}
+
+/// Checks that each operation which is rendered as an instruction is exactly the operation this
+/// instruction assembles to.
+#[test]
+fn operations_are_rendered_as_equivalent_instructions() {
+    let context = TestContext::default();
+    let operations = [
+        Operation::Noop,
+        Operation::Assert(0),
+        Operation::Assert(42),
+        Operation::SDepth,
+        Operation::Clk,
+        Operation::Emit(7),
+        Operation::Add,
+        Operation::Neg,
+        Operation::Mul,
+        Operation::Inv,
+        Operation::Incr,
+        Operation::And,
+        Operation::Or,
+        Operation::Not,
+        Operation::Eq,
+        Operation::U32split,
+        Operation::U32add,
+        Operation::U32add3,
+        Operation::U32sub,
+        Operation::U32mul,
+        Operation::U32madd,
+        Operation::U32and,
+        Operation::U32xor,
+        Operation::U32assert2(0),
+        Operation::U32assert2(3),
+        Operation::Pad,
+        Operation::Drop,
+        Operation::Dup0,
+        Operation::Dup7,
+        Operation::Dup15,
+        Operation::Swap,
+        Operation::SwapW,
+        Operation::SwapW2,
+        Operation::SwapW3,
+        Operation::SwapDW,
+        Operation::MovUp2,
+        Operation::MovUp8,
+        Operation::MovDn2,
+        Operation::MovDn8,
+        Operation::CSwap,
+        Operation::CSwapW,
+        Operation::Push(Felt::new(5)),
+        Operation::AdvPop,
+        Operation::AdvPopW,
+        Operation::MLoadW,
+        Operation::MStoreW,
+        Operation::MLoad,
+        Operation::MStream,
+        Operation::Pipe,
+        Operation::HPerm,
+        Operation::MpVerify(0),
+        Operation::MpVerify(9),
+        Operation::FriE2F4,
+        Operation::HornerBase,
+        Operation::HornerExt,
+    ];
+
+    for op in operations {
+        let instruction = op_to_instruction(&op).unwrap_or_else(|| panic!("{op} is not rendered"));
+        let source = format!("begin {instruction} end");
+        let program = Assembler::new(context.source_manager())
+            .assemble_program(source.as_str())
+            .unwrap();
+        let MastNode::Block(block) = &program.mast_forest()[program.entrypoint()] else {
+            panic!("`{source}` is not assembled to a basic block");
+        };
+        let ops = block.operations().copied().filter(|op| !matches!(op, Operation::Noop));
+        let expected = [op].into_iter().filter(|op| !matches!(op, Operation::Noop));
+        assert_eq!(ops.collect::<Vec<_>>(), expected.collect::<Vec<_>>(), "{source}");
+    }
+
+    // operations without an equivalent instruction are rendered as comments
+    assert!(op_to_instruction(&Operation::FmpUpdate).is_none());
+    assert_eq!(op_to_instruction(&Operation::Push(ZERO)).unwrap().to_string(), "push.0");
+}
@@ -0,0 +1,436 @@
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
+
+use vm_core::{
+    Program,
+    crypto::hash::RpoDigest,
+    mast::{MastForest, MastNodeId},
+};
+
+use crate::{Decompiler, Library};
+
+#[cfg(test)]
+mod tests;
+
+/// The name under which the entrypoint of a program is compared.
+const ENTRYPOINT_NAME: &str = "begin";
+
+/// The number of unchanged lines shown around the changed lines of a procedure body.
+const CONTEXT_LINES: usize = 2;
+
+// MAST DIFF
+// ================================================================================================
+
+/// The differences between the procedures of two compiled programs or libraries.
+///
+/// Procedures are matched by name: the exports of libraries by their fully-qualified names, and
+/// the entrypoints of programs as `begin`. A named procedure whose MAST root differs between the
+/// two is reported as changed, along with a line diff of its bodies, as reconstructed by the
+/// [Decompiler]. Procedure roots without a name, i.e., the local procedures of a program or the
+/// procedures of a library which are not exported, are matched by MAST root. Of those which only
+/// exist in one of the two, a removed and an added procedure whose decompiled bodies share at least
+/// half of their lines are reported as changed, along with the diff of their bodies, and the
+/// others as removed or added.
+///
+/// The [fmt::Display] implementation of this type renders a human-readable report of the
+/// differences.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MastDiff {
+    added: Vec<ProcedureSummary>,
+    removed: Vec<ProcedureSummary>,
+    changed: Vec<ProcedureChange>,
+}
+
+impl MastDiff {
+    /// Returns the differences between the procedures of the specified programs.
+    ///
+    /// The decompiler is used to render the bodies of the changed procedures, and hence
+    /// determines which of the invoked procedures are referred to by name.
+    pub fn programs(old: &Program, new: &Program, decompiler: &Decompiler) -> Self {
+        let old_procedures = ProcedureSet::new(
+            old.mast_forest(),
+            [(String::from(ENTRYPOINT_NAME), old.entrypoint())],
+        );
+        let new_procedures = ProcedureSet::new(
+            new.mast_forest(),
+            [(String::from(ENTRYPOINT_NAME), new.entrypoint())],
+        );
+        Self::new(&old_procedures, &new_procedures, decompiler)
+    }
+
+    /// Returns the differences between the procedures of the specified libraries.
+    ///
+    /// The decompiler is used to render the bodies of the changed procedures, and hence
+    /// determines which of the invoked procedures are referred to by name.
+    pub fn libraries(
+        old: impl AsRef<Library>,
+        new: impl AsRef<Library>,
+        decompiler: &Decompiler,
+    ) -> Self {
+        let exports = |library: &Library| {
+            library
+                .exports()
+                .map(|name| (name.to_string(), library.get_export_node_id(name)))
+                .collect::<Vec<_>>()
+        };
+        let (old, new) = (old.as_ref(), new.as_ref());
+        let old_procedures = ProcedureSet::new(old.mast_forest(), exports(old));
+        let new_procedures = ProcedureSet::new(new.mast_forest(), exports(new));
+        Self::new(&old_procedures, &new_procedures, decompiler)
+    }
+
+    fn new(old: &ProcedureSet, new: &ProcedureSet, decompiler: &Decompiler) -> Self {
+        let mut diff = Self::default();
+
+        for (name, &old_root) in old.named.iter() {
+            let old_digest = old.forest[old_root].digest();
+            match new.named.get(name) {
+                None => diff.removed.push(ProcedureSummary::new(Some(name), old_digest)),
+                Some(&new_root) => {
+                    let new_digest = new.forest[new_root].digest();
+                    if old_digest != new_digest {
+                        let old_body = decompiler.decompile_procedure(old.forest, old_root);
+                        let new_body = decompiler.decompile_procedure(new.forest, new_root);
+                        diff.changed.push(ProcedureChange {
+                            name: Some(name.clone()),
+                            old_digest,
+                            new_digest,
+                            body_diff: diff_lines(&old_body, &new_body),
+                        });
+                    }
+                },
+            }
+        }
+        for (name, &new_root) in new.named.iter() {
+            if !old.named.contains_key(name) {
+                let digest = new.forest[new_root].digest();
+                diff.added.push(ProcedureSummary::new(Some(name), digest));
+            }
+        }
+
+        // unnamed procedures are paired with the most similar procedure of the other side
+        let old_unnamed = old.unnamed_roots();
+        let new_unnamed = new.unnamed_roots();
+        let decompile = |procedures: &ProcedureSet,
+                         unnamed: &BTreeMap<RpoDigest, MastNodeId>,
+                         other: &BTreeMap<RpoDigest, MastNodeId>| {
+            unnamed
+                .iter()
+                .filter(|(digest, _)| !other.contains_key(digest))
+                .map(|(&digest, &root)| {
+                    (digest, decompiler.decompile_procedure(procedures.forest, root))
+                })
+                .collect::<Vec<_>>()
+        };
+        let old_only = decompile(old, &old_unnamed, &new_unnamed);
+        let mut new_only = decompile(new, &new_unnamed, &old_unnamed)
+            .into_iter()
+            .map(Some)
+            .collect::<Vec<_>>();
+        for (old_digest, old_body) in old_only {
+            let best_match = new_only
+                .iter()
+                .enumerate()
+                .filter_map(|(index, new)| {
+                    let (_, new_body) = new.as_ref()?;
+                    let body_diff = diff_lines(&old_body, new_body);
+                    let unchanged = body_diff
+                        .iter()
+                        .filter(|line| matches!(line, DiffLine::Unchanged(_)))
+                        .count();
+                    let len = old_body.lines().count().max(new_body.lines().count());
+                    (unchanged * 2 >= len && unchanged > 0).then_some((unchanged, index, body_diff))
+                })
+                .max_by_key(|(unchanged, ..)| *unchanged);
+            match best_match {
+                Some((_, index, body_diff)) => {
+                    let (new_digest, _) = new_only[index].take().expect("procedure is unmatched");
+                    diff.changed.push(ProcedureChange {
+                        name: None,
+                        old_digest,
+                        new_digest,
+                        body_diff,
+                    });
+                },
+                None => diff.removed.push(ProcedureSummary::new(None, old_digest)),
+            }
+        }
+        for (new_digest, _) in new_only.into_iter().flatten() {
+            diff.added.push(ProcedureSummary::new(None, new_digest));
+        }
+
+        diff
+    }
+
+    /// Returns true if the procedures of the two programs or libraries are identical.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    /// Returns the procedures which only exist in the new program or library.
+    pub fn added(&self) -> &[ProcedureSummary] {
+        &self.added
+    }
+
+    /// Returns the procedures which only exist in the old program or library.
+    pub fn removed(&self) -> &[ProcedureSummary] {
+        &self.removed
+    }
+
+    /// Returns the procedures whose MAST roots differ between the two programs or libraries.
+    pub fn changed(&self) -> &[ProcedureChange] {
+        &self.changed
+    }
+}
+
+impl fmt::Display for MastDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "no differences");
+        }
+
+        for procedure in self.removed.iter() {
+            writeln!(f, "- {procedure}")?;
+        }
+        for procedure in self.added.iter() {
+            writeln!(f, "+ {procedure}")?;
+        }
+        for change in self.changed.iter() {
+            write!(f, "{change}")?;
+        }
+        Ok(())
+    }
+}
+
+// PROCEDURE SUMMARY
+// ================================================================================================
+
+/// A procedure which only exists in one of two compared programs or libraries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcedureSummary {
+    name: Option<String>,
+    digest: RpoDigest,
+}
+
+impl ProcedureSummary {
+    fn new(name: Option<&String>, digest: RpoDigest) -> Self {
+        Self { name: name.cloned(), digest }
+    }
+
+    /// Returns the name of the procedure, or None if it is neither exported nor an entrypoint.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns the MAST root of the procedure.
+    pub fn digest(&self) -> RpoDigest {
+        self.digest
+    }
+}
+
+impl fmt::Display for ProcedureSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.name {
+            Some(name) => write!(f, "{name} ({})", self.digest),
+            None => write!(f, "{}", self.digest),
+        }
+    }
+}
+
+// PROCEDURE CHANGE
+// ================================================================================================
+
+/// A procedure whose MAST root differs between two compared programs or libraries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcedureChange {
+    name: Option<String>,
+    old_digest: RpoDigest,
+    new_digest: RpoDigest,
+    body_diff: Vec<DiffLine>,
+}
+
+impl ProcedureChange {
+    /// Returns the name of the procedure, or None if it is neither exported nor an entrypoint.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns the MAST root of the procedure in the old program or library.
+    pub fn old_digest(&self) -> RpoDigest {
+        self.old_digest
+    }
+
+    /// Returns the MAST root of the procedure in the new program or library.
+    pub fn new_digest(&self) -> RpoDigest {
+        self.new_digest
+    }
+
+    /// Returns the diff of the decompiled bodies of the procedure, line by line.
+    pub fn body_diff(&self) -> &[DiffLine] {
+        &self.body_diff
+    }
+}
+
+impl fmt::Display for ProcedureChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.name {
+            Some(name) => writeln!(f, "~ {name} ({} -> {})", self.old_digest, self.new_digest)?,
+            None => writeln!(f, "~ {} -> {}", self.old_digest, self.new_digest)?,
+        }
+
+        // only the changed lines are rendered, along with a few lines of context around them
+        let is_changed = |index: usize| {
+            self.body_diff
+                .get(index)
+                .is_some_and(|line| !matches!(line, DiffLine::Unchanged(_)))
+        };
+        let mut skipped = false;
+        for (index, line) in self.body_diff.iter().enumerate() {
+            let start = index.saturating_sub(CONTEXT_LINES);
+            if !(start..=index + CONTEXT_LINES).any(is_changed) {
+                skipped = true;
+                continue;
+            }
+            if skipped {
+                writeln!(f, "    ...")?;
+                skipped = false;
+            }
+            writeln!(f, "    {line}")?;
+        }
+        if skipped {
+            writeln!(f, "    ...")?;
+        }
+        Ok(())
+    }
+}
+
+/// A line of the diff of two procedure bodies.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffLine {
+    /// A line which exists in both bodies.
+    Unchanged(String),
+    /// A line which only exists in the old body.
+    Removed(String),
+    /// A line which only exists in the new body.
+    Added(String),
+}
+
+impl fmt::Display for DiffLine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unchanged(line) => write!(f, "  {line}"),
+            Self::Removed(line) => write!(f, "- {line}"),
+            Self::Added(line) => write!(f, "+ {line}"),
+        }
+    }
+}
+
+// PROCEDURE SET
+// ================================================================================================
+
+/// The procedures of a MAST forest which are compared.
+struct ProcedureSet<'a> {
+    forest: &'a MastForest,
+    /// The named procedures, indexed by name.
+    named: BTreeMap<String, MastNodeId>,
+}
+
+impl<'a> ProcedureSet<'a> {
+    fn new(forest: &'a MastForest, named: impl IntoIterator<Item = (String, MastNodeId)>) -> Self {
+        Self {
+            forest,
+            named: named.into_iter().collect(),
+        }
+    }
+
+    /// Returns the roots of the procedures which aren't named, indexed by MAST root.
+    fn unnamed_roots(&self) -> BTreeMap<RpoDigest, MastNodeId> {
+        self.forest
+            .procedure_roots()
+            .iter()
+            .filter(|root| !self.named.values().any(|named_root| named_root == *root))
+            .map(|&root| (self.forest[root].digest(), root))
+            .collect()
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the diff of the lines of the specified texts.
+///
+/// The diff is a shortest edit script computed with Myers' algorithm, which takes O((N + M) * D)
+/// time and O(D^2) space for texts of N and M lines which differ by D lines.
+fn diff_lines(old: &str, new: &str) -> Vec<DiffLine> {
+    let old = old.lines().collect::<Vec<_>>();
+    let new = new.lines().collect::<Vec<_>>();
+    let (old_len, new_len) = (old.len() as isize, new.len() as isize);
+    let max_edits = old_len + new_len;
+
+    // frontier[max_edits + k] is the furthest x reached on the diagonal k = x - y, where x and y
+    // are the numbers of lines of the old and new texts consumed so far; after d edits, the
+    // frontier of the diagonals -d..=d is recorded for the reconstruction of the edit script
+    let mut frontier = vec![0isize; 2 * max_edits as usize + 2];
+    let mut trace = Vec::new();
+    for edits in 0..=max_edits {
+        let mut done = false;
+        for k in (-edits..=edits).step_by(2) {
+            let index = (max_edits + k) as usize;
+            let mut x = if k == -edits || (k != edits && frontier[index - 1] < frontier[index + 1])
+            {
+                frontier[index + 1]
+            } else {
+                frontier[index - 1] + 1
+            };
+            let mut y = x - k;
+            while x < old_len && y < new_len && old[x as usize] == new[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            frontier[index] = x;
+            done |= x >= old_len && y >= new_len;
+        }
+        trace.push(frontier[(max_edits - edits) as usize..=(max_edits + edits) as usize].to_vec());
+        if done {
+            break;
+        }
+    }
+
+    // the edit script is reconstructed backwards from the end of both texts
+    let mut diff = Vec::with_capacity(old.len().max(new.len()));
+    let (mut x, mut y) = (old_len, new_len);
+    for (edits, _) in trace.iter().enumerate().rev() {
+        let edits = edits as isize;
+        let k = x - y;
+        let (prev_x, prev_y) = if edits == 0 {
+            (0, 0)
+        } else {
+            let prev = |k: isize| trace[edits as usize - 1][(k + edits - 1) as usize];
+            let prev_k = if k == -edits || (k != edits && prev(k - 1) < prev(k + 1)) {
+                k + 1
+            } else {
+                k - 1
+            };
+            (prev(prev_k), prev(prev_k) - prev_k)
+        };
+        while x > prev_x && y > prev_y {
+            diff.push(DiffLine::Unchanged(old[x as usize - 1].to_string()));
+            x -= 1;
+            y -= 1;
+        }
+        if edits > 0 {
+            if x == prev_x {
+                diff.push(DiffLine::Added(new[y as usize - 1].to_string()));
+            } else {
+                diff.push(DiffLine::Removed(old[x as usize - 1].to_string()));
+            }
+            (x, y) = (prev_x, prev_y);
+        }
+    }
+    diff.reverse();
+    diff
+}
@@ -0,0 +1,165 @@
+use alloc::{string::ToString, vec::Vec};
+
+use super::{DiffLine, MastDiff, diff_lines};
+use crate::{
+    Assembler, Decompiler, Library, LibraryPath, ModuleParser, ast::ModuleKind,
+    testing::TestContext,
+};
+
+fn assemble_library(context: &TestContext, source: &str) -> Library {
+    let module = ModuleParser::new(ModuleKind::Library)
+        .parse_str(LibraryPath::new("test::lib").unwrap(), source, &context.source_manager())
+        .unwrap();
+    Assembler::new(context.source_manager()).assemble_library([module]).unwrap()
+}
+
+#[test]
+fn diff_libraries() {
+    let context = TestContext::default();
+    let old = assemble_library(
+        &context,
+        "export.foo
+            push.3 add mul
+        end
+
+        export.bar
+            mul
+        end
+
+        export.baz
+            drop
+        end",
+    );
+    let new = assemble_library(
+        &context,
+        "export.foo
+            push.3 add push.5 mul
+        end
+
+        export.bar
+            mul
+        end
+
+        export.qux
+            swap
+        end",
+    );
+
+    let diff = MastDiff::libraries(&old, &new, &Decompiler::new());
+    let names = |procedures: &[super::ProcedureSummary]| {
+        procedures
+            .iter()
+            .map(|procedure| procedure.name().unwrap().to_string())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(names(diff.removed()), ["test::lib::baz"]);
+    assert_eq!(names(diff.added()), ["test::lib::qux"]);
+    assert_eq!(diff.changed().len(), 1);
+
+    let change = &diff.changed()[0];
+    assert_eq!(change.name(), Some("test::lib::foo"));
+    assert_ne!(change.old_digest(), change.new_digest());
+    assert_eq!(
+        change.body_diff(),
+        [
+            DiffLine::Unchanged("push.3".into()),
+            DiffLine::Unchanged("add".into()),
+            DiffLine::Added("push.5".into()),
+            DiffLine::Unchanged("mul".into()),
+        ]
+    );
+
+    let report = diff.to_string();
+    assert!(report.contains("- test::lib::baz"), "{report}");
+    assert!(report.contains("+ test::lib::qux"), "{report}");
+    assert!(report.contains("    + push.5"), "{report}");
+
+    assert!(MastDiff::libraries(&old, &old, &Decompiler::new()).is_empty());
+}
+
+#[test]
+fn diff_programs() {
+    let context = TestContext::default();
+    let assemble =
+        |source: &str| Assembler::new(context.source_manager()).assemble_program(source).unwrap();
+    let old = assemble(
+        "proc.foo
+            push.10 add drop
+        end
+        begin
+            call.foo
+            push.2
+        end",
+    );
+    let new = assemble(
+        "proc.foo
+            push.10 mul drop
+        end
+        begin
+            call.foo
+            push.2
+        end",
+    );
+
+    let diff = MastDiff::programs(&old, &new, &Decompiler::new());
+    assert!(diff.removed().is_empty());
+    assert!(diff.added().is_empty());
+    assert_eq!(diff.changed().len(), 2);
+
+    // the entrypoint changed because the procedure it calls did
+    let change = &diff.changed()[0];
+    assert_eq!(change.name(), Some("begin"));
+    let local = &diff.changed()[1];
+    let callee = |digest| DiffLine::Removed(alloc::format!("call.{digest}"));
+    assert!(change.body_diff().contains(&callee(local.old_digest())));
+
+    // the local procedure is matched with its new version, and its body diffed op by op
+    assert_eq!(local.name(), None);
+    assert!(local.body_diff().contains(&DiffLine::Removed("add".into())));
+    assert!(local.body_diff().contains(&DiffLine::Added("mul".into())));
+    assert!(local.body_diff().contains(&DiffLine::Unchanged("drop".into())));
+}
+
+#[test]
+fn diff_lines_of_texts() {
+    assert_eq!(
+        diff_lines("a\nb\nc\nd", "a\nc\nx\nd"),
+        [
+            DiffLine::Unchanged("a".into()),
+            DiffLine::Removed("b".into()),
+            DiffLine::Unchanged("c".into()),
+            DiffLine::Added("x".into()),
+            DiffLine::Unchanged("d".into()),
+        ]
+    );
+    assert_eq!(
+        diff_lines("a\nb", "c"),
+        [
+            DiffLine::Removed("a".into()),
+            DiffLine::Removed("b".into()),
+            DiffLine::Added("c".into())
+        ]
+    );
+    assert!(diff_lines("", "").is_empty());
+}
+
+#[test]
+fn diff_lines_of_long_texts() {
+    // texts with few differences are diffed without the quadratic cost of a full LCS table
+    let old = (0..200_000).map(|i| alloc::format!("{i}\n")).collect::<alloc::string::String>();
+    let new = old.replacen("1000\n", "x\n", 1).replacen("150000\n", "", 1);
+    let diff = diff_lines(&old, &new);
+    let changed = diff
+        .iter()
+        .filter(|line| !matches!(line, DiffLine::Unchanged(_)))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        changed,
+        [
+            &DiffLine::Removed("1000".into()),
+            &DiffLine::Added("x".into()),
+            &DiffLine::Removed("150000".into())
+        ]
+    );
+    assert_eq!(diff.len(), 200_001);
+}
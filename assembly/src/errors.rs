@@ -1,6 +1,6 @@
 use alloc::{string::String, sync::Arc, vec::Vec};
 
-use vm_core::mast::MastForestError;
+use vm_core::{IsaVersion, mast::MastForestError};
 
 use crate::{
     LibraryNamespace, LibraryPath, SourceSpan,
@@ -82,6 +82,40 @@ pub enum AssemblyError {
         #[source_code]
         source_file: Option<Arc<SourceFile>>,
     },
+    #[error(
+        "invalid instruction: '{instruction}' is not supported by version {isa_version} of the instruction set"
+    )]
+    #[diagnostic(help("the instruction set version targeted by the assembler can be changed"))]
+    UnsupportedInstruction {
+        #[label]
+        span: SourceSpan,
+        #[source_code]
+        source_file: Option<Arc<SourceFile>>,
+        instruction: String,
+        isa_version: IsaVersion,
+    },
+    #[error("unexpected {kind}: modules must be semantically analyzed before they are assembled")]
+    #[diagnostic(help(
+        "modules parsed from source are analyzed automatically, while modules constructed otherwise can be analyzed by parsing their textual form"
+    ))]
+    UnanalyzedOperation {
+        #[label]
+        span: SourceSpan,
+        #[source_code]
+        source_file: Option<Arc<SourceFile>>,
+        kind: &'static str,
+    },
+    #[error("use of deprecated procedure '{callee}'")]
+    #[diagnostic(severity(Warning))]
+    DeprecatedProcedure {
+        #[label]
+        span: SourceSpan,
+        #[source_code]
+        source_file: Option<Arc<SourceFile>>,
+        callee: QualifiedProcedureName,
+        #[help]
+        reason: Option<String>,
+    },
 
     #[error("invalid procedure: body must contain at least one instruction if it has decorators")]
     #[diagnostic()]
@@ -19,9 +19,13 @@ use vm_core::{
 mod assembler;
 pub mod ast;
 mod compile;
+pub mod completion;
+mod decompiler;
 pub mod diagnostics;
+mod diff;
 mod errors;
 mod library;
+mod limits;
 mod parser;
 mod sema;
 #[cfg(any(test, feature = "testing"))]
@@ -34,19 +38,23 @@ mod tests;
 /// Merkelized abstract syntax tree (MAST) components defining Miden VM programs.
 pub use vm_core::mast;
 pub use vm_core::utils;
+pub use vm_core::{DecoratorKinds, IsaVersion};
 
 pub use self::{
-    assembler::Assembler,
+    assembler::{Assembler, StackDepth, StackDepthReport},
     compile::{Compile, Options as CompileOptions},
+    decompiler::Decompiler,
     diagnostics::{
         DefaultSourceManager, Report, SourceFile, SourceId, SourceManager, SourceSpan, Span,
         Spanned,
     },
+    diff::{DiffLine, MastDiff, ProcedureChange, ProcedureSummary},
     errors::AssemblyError,
     library::{
         KernelLibrary, Library, LibraryError, LibraryNamespace, LibraryPath, LibraryPathComponent,
         PathError, Version, VersionError,
     },
+    limits::ResourceLimits,
     parser::ModuleParser,
 };
 
@@ -1,6 +1,10 @@
 use vm_core::errors::KernelError;
 
-use crate::{ast::QualifiedProcedureName, diagnostics::Diagnostic};
+use crate::{
+    LibraryPath, RpoDigest,
+    ast::{ProcedureName, QualifiedProcedureName},
+    diagnostics::Diagnostic,
+};
 
 #[derive(Debug, thiserror::Error, Diagnostic)]
 pub enum LibraryError {
@@ -17,4 +21,21 @@ pub enum LibraryError {
     KernelConversion(KernelError),
     #[error("invalid export: no procedure root for {procedure_path} procedure")]
     NoProcedureRootForExport { procedure_path: QualifiedProcedureName },
+    #[error(
+        "invalid interface definition for {path}: procedure '{name}' is not declared by digest"
+    )]
+    #[diagnostic(help(
+        "interface definitions may only declare procedures by MAST root, e.g. `export.DIGEST->foo`"
+    ))]
+    InvalidInterfaceProcedure { path: LibraryPath, name: ProcedureName },
+    #[error("library does not implement {procedure_path} declared by its interface")]
+    MissingInterfaceProcedure { procedure_path: QualifiedProcedureName },
+    #[error(
+        "library implementation of {procedure_path} does not match its interface: expected digest {expected}, got {actual}"
+    )]
+    InterfaceDigestMismatch {
+        procedure_path: QualifiedProcedureName,
+        expected: RpoDigest,
+        actual: RpoDigest,
+    },
 }
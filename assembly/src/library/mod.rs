@@ -12,7 +12,7 @@ use vm_core::{
     utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable},
 };
 
-use crate::ast::QualifiedProcedureName;
+use crate::ast::{AttributeSet, QualifiedProcedureName};
 
 mod error;
 mod module;
@@ -56,6 +56,10 @@ pub struct Library {
     exports: BTreeMap<QualifiedProcedureName, MastNodeId>,
     /// The MAST forest underlying this library.
     mast_forest: Arc<MastForest>,
+    /// The attributes attached to the exported procedures in their source, e.g. `@inline`.
+    ///
+    /// Procedures without any attribute have no entry in this map.
+    attributes: BTreeMap<QualifiedProcedureName, AttributeSet>,
 }
 
 impl AsRef<Library> for Library {
@@ -89,7 +93,12 @@ impl Library {
 
         let digest = compute_content_hash(&exports, &mast_forest);
 
-        Ok(Self { digest, exports, mast_forest })
+        Ok(Self {
+            digest,
+            exports,
+            mast_forest,
+            attributes: BTreeMap::new(),
+        })
     }
 
     /// Produces a new library with the existing [`MastForest`] and where all key/values in the  
@@ -102,6 +111,24 @@ impl Library {
             ..self
         }
     }
+
+    /// Produces a new library where the provided attributes are attached to the corresponding
+    /// exported procedures, replacing any attributes previously attached to them.
+    ///
+    /// Attributes of procedures which are not exported by this library, and empty sets of
+    /// attributes, are ignored.
+    pub fn with_procedure_attributes(
+        mut self,
+        attributes: impl IntoIterator<Item = (QualifiedProcedureName, AttributeSet)>,
+    ) -> Self {
+        for (proc_name, attrs) in attributes {
+            if attrs.is_empty() || !self.exports.contains_key(&proc_name) {
+                continue;
+            }
+            self.attributes.insert(proc_name, attrs);
+        }
+        self
+    }
 }
 
 // ------------------------------------------------------------------------------------------------
@@ -142,6 +169,15 @@ impl Library {
     pub fn mast_forest(&self) -> &Arc<MastForest> {
         &self.mast_forest
     }
+
+    /// Returns the attributes attached to the specified exported procedure in its source, or
+    /// `None` if it has none.
+    pub fn procedure_attributes(
+        &self,
+        proc_name: &QualifiedProcedureName,
+    ) -> Option<&AttributeSet> {
+        self.attributes.get(proc_name)
+    }
 }
 
 /// Conversions
@@ -151,29 +187,58 @@ impl Library {
         let mut modules_by_path: BTreeMap<LibraryPath, ModuleInfo> = BTreeMap::new();
 
         for (proc_name, &proc_root_node_id) in self.exports.iter() {
+            let proc_digest = self.mast_forest[proc_root_node_id].digest();
+            let attributes = self.attributes.get(proc_name).cloned().unwrap_or_default();
             modules_by_path
                 .entry(proc_name.module.clone())
-                .and_modify(|compiled_module| {
-                    let proc_digest = self.mast_forest[proc_root_node_id].digest();
-                    compiled_module.add_procedure(proc_name.name.clone(), proc_digest);
-                })
-                .or_insert_with(|| {
-                    let mut module_info = ModuleInfo::new(proc_name.module.clone());
+                .or_insert_with(|| ModuleInfo::new(proc_name.module.clone()))
+                .add_procedure_with_attributes(proc_name.name.clone(), proc_digest, attributes);
+        }
 
-                    let proc_digest = self.mast_forest[proc_root_node_id].digest();
-                    module_info.add_procedure(proc_name.name.clone(), proc_digest);
+        modules_by_path.into_values()
+    }
+}
 
-                    module_info
+/// Interface checks
+impl Library {
+    /// Checks that this library implements the procedures declared by `interface`.
+    ///
+    /// This is the check to perform when binding a library to an interface definition which code
+    /// was compiled against (see [ModuleInfo::from_interface]): every procedure declared by the
+    /// interface must be exported by this library under the same path and with the same MAST root.
+    ///
+    /// # Errors
+    /// Returns an error if a procedure declared by the interface is not exported by this library,
+    /// or if its MAST root differs from the one declared by the interface.
+    pub fn check_interface(&self, interface: &ModuleInfo) -> Result<(), LibraryError> {
+        for (_, proc) in interface.procedures() {
+            let procedure_path =
+                QualifiedProcedureName::new(interface.path().clone(), proc.name.clone());
+            let node_id = self.exports.get(&procedure_path).ok_or_else(|| {
+                LibraryError::MissingInterfaceProcedure { procedure_path: procedure_path.clone() }
+            })?;
+            let actual = self.mast_forest[*node_id].digest();
+            if actual != proc.digest {
+                return Err(LibraryError::InterfaceDigestMismatch {
+                    procedure_path,
+                    expected: proc.digest,
+                    actual,
                 });
+            }
         }
 
-        modules_by_path.into_values()
+        Ok(())
     }
 }
 
 impl Serializable for Library {
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
-        let Self { digest: _, exports, mast_forest } = self;
+        let Self {
+            digest: _,
+            exports,
+            mast_forest,
+            attributes,
+        } = self;
 
         mast_forest.write_into(target);
 
@@ -183,31 +248,100 @@ impl Serializable for Library {
             proc_name.name.write_into(target);
             target.write_u32(proc_node_id.as_u32());
         }
+
+        target.write_usize(attributes.len());
+        for (proc_name, attrs) in attributes {
+            proc_name.write_into(target);
+            attrs.write_into(target);
+        }
     }
 }
 
+/// Libraries serialized by previous releases don't hold the attributes of their procedures, so
+/// attributes are only read if `source` has bytes left after the exports. This keeps such
+/// libraries readable when they are serialized on their own, e.g. in `.masl` files, while
+/// formats which serialize other data after a library need to use
+/// [Library::read_from_with_options] instead.
 impl Deserializable for Library {
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
-        let mast_forest = Arc::new(MastForest::read_from(source)?);
+        let (mast_forest, exports) = read_exports(source)?;
+        let attributes = if source.has_more_bytes() {
+            read_attributes(source, &exports)?
+        } else {
+            BTreeMap::new()
+        };
+        Ok(Self::from_parts(mast_forest, exports, attributes))
+    }
+}
 
-        let num_exports = source.read_usize()?;
-        if num_exports == 0 {
-            return Err(DeserializationError::InvalidValue(String::from("No exported procedures")));
+impl Library {
+    /// Reads a library from `source`, along with the attributes of its procedures if
+    /// `with_attributes` is true.
+    ///
+    /// Libraries serialized by releases prior to 0.14 don't hold any attributes, and must be read
+    /// with `with_attributes` set to false.
+    pub fn read_from_with_options<R: ByteReader>(
+        source: &mut R,
+        with_attributes: bool,
+    ) -> Result<Self, DeserializationError> {
+        let (mast_forest, exports) = read_exports(source)?;
+        let attributes = if with_attributes {
+            read_attributes(source, &exports)?
+        } else {
+            BTreeMap::new()
         };
-        let mut exports = BTreeMap::new();
-        for _ in 0..num_exports {
-            let proc_module = source.read()?;
-            let proc_name = source.read()?;
-            let proc_name = QualifiedProcedureName::new(proc_module, proc_name);
-            let proc_node_id = MastNodeId::from_u32_safe(source.read_u32()?, &mast_forest)?;
-
-            exports.insert(proc_name, proc_node_id);
-        }
+        Ok(Self::from_parts(mast_forest, exports, attributes))
+    }
 
+    fn from_parts(
+        mast_forest: Arc<MastForest>,
+        exports: BTreeMap<QualifiedProcedureName, MastNodeId>,
+        attributes: BTreeMap<QualifiedProcedureName, AttributeSet>,
+    ) -> Self {
         let digest = compute_content_hash(&exports, &mast_forest);
+        Self { digest, exports, mast_forest, attributes }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn read_exports<R: ByteReader>(
+    source: &mut R,
+) -> Result<(Arc<MastForest>, BTreeMap<QualifiedProcedureName, MastNodeId>), DeserializationError> {
+    let mast_forest = Arc::new(MastForest::read_from(source)?);
+
+    let num_exports = source.read_usize()?;
+    if num_exports == 0 {
+        return Err(DeserializationError::InvalidValue(String::from("No exported procedures")));
+    };
+    let mut exports = BTreeMap::new();
+    for _ in 0..num_exports {
+        let proc_module = source.read()?;
+        let proc_name = source.read()?;
+        let proc_name = QualifiedProcedureName::new(proc_module, proc_name);
+        let proc_node_id = MastNodeId::from_u32_safe(source.read_u32()?, &mast_forest)?;
+
+        exports.insert(proc_name, proc_node_id);
+    }
+
+    Ok((mast_forest, exports))
+}
 
-        Ok(Self { digest, exports, mast_forest })
+fn read_attributes<R: ByteReader>(
+    source: &mut R,
+    exports: &BTreeMap<QualifiedProcedureName, MastNodeId>,
+) -> Result<BTreeMap<QualifiedProcedureName, AttributeSet>, DeserializationError> {
+    let num_attributes = source.read_usize()?;
+    let mut attributes = BTreeMap::new();
+    for _ in 0..num_attributes {
+        let proc_name: QualifiedProcedureName = source.read()?;
+        if !exports.contains_key(&proc_name) {
+            return Err(DeserializationError::InvalidValue(format!(
+                "attributes of procedure '{proc_name}' which is not exported"
+            )));
+        }
+        attributes.insert(proc_name, source.read()?);
     }
+    Ok(attributes)
 }
 
 fn compute_content_hash(
@@ -1,14 +1,20 @@
 use alloc::vec::Vec;
 
-use super::LibraryPath;
+use super::{LibraryError, LibraryPath};
 use crate::{
     RpoDigest,
-    ast::{ProcedureIndex, ProcedureName},
+    ast::{
+        AliasTarget, AttributeSet, Export, Module, ModuleKind, ProcedureAlias, ProcedureIndex,
+        ProcedureName,
+    },
+    diagnostics::Span,
 };
 
 // MODULE INFO
 // ================================================================================================
 
+/// Describes the procedures exported by a module, i.e., their names and MAST roots, without their
+/// bodies.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ModuleInfo {
     path: LibraryPath,
@@ -23,7 +29,17 @@ impl ModuleInfo {
 
     /// Adds a procedure to the module.
     pub fn add_procedure(&mut self, name: ProcedureName, digest: RpoDigest) {
-        self.procedures.push(ProcedureInfo { name, digest });
+        self.add_procedure_with_attributes(name, digest, AttributeSet::default());
+    }
+
+    /// Adds a procedure to the module, along with the attributes attached to it in its source.
+    pub fn add_procedure_with_attributes(
+        &mut self,
+        name: ProcedureName,
+        digest: RpoDigest,
+        attributes: AttributeSet,
+    ) {
+        self.procedures.push(ProcedureInfo { name, digest, attributes });
     }
 
     /// Returns the module's library path.
@@ -67,9 +83,71 @@ impl ModuleInfo {
     }
 }
 
-/// Stores the name and digest of a procedure.
+/// Interface definitions
+impl ModuleInfo {
+    /// File extension for an interface definition file.
+    pub const INTERFACE_EXTENSION: &'static str = "masi";
+
+    /// Returns the [ModuleInfo] declared by the interface definition `module`.
+    ///
+    /// An interface definition (conventionally a `.masi` file) is a library module which declares
+    /// the procedures of another module by name and MAST root only, e.g.:
+    ///
+    /// ```text
+    /// #! Pops two elements off the stack and pushes their sum.
+    /// export.0x<digest of the procedure>->add
+    /// ```
+    ///
+    /// Modules compiled against an interface (see [crate::Assembler::add_interface]) invoke its
+    /// procedures by MAST root, so the library implementing the interface needs to be provided
+    /// only when the program is executed. [super::Library::check_interface] can be used to check
+    /// that a library implements an interface.
+    ///
+    /// # Errors
+    /// Returns an error if the module defines a procedure, or re-exports a procedure by path.
+    pub fn from_interface(module: &Module) -> Result<Self, LibraryError> {
+        let mut info = Self::new(module.path().clone());
+        for export in module.procedures() {
+            let digest = match export {
+                Export::Alias(alias) => match alias.target() {
+                    AliasTarget::MastRoot(digest) => Some(digest.into_inner()),
+                    _ => None,
+                },
+                Export::Procedure(_) => None,
+            };
+            let digest = digest.ok_or_else(|| LibraryError::InvalidInterfaceProcedure {
+                path: module.path().clone(),
+                name: export.name().clone(),
+            })?;
+            info.add_procedure(export.name().clone(), digest);
+        }
+
+        Ok(info)
+    }
+
+    /// Returns the interface definition of this module, i.e., a module which declares each
+    /// procedure of this module by its MAST root.
+    ///
+    /// The returned module can be written out as a `.masi` file via its [core::fmt::Display]
+    /// implementation.
+    pub fn to_interface(&self) -> Module {
+        let mut module = Module::new(ModuleKind::Library, self.path.clone());
+        for proc in self.procedures.iter() {
+            let target = AliasTarget::MastRoot(Span::unknown(proc.digest));
+            let alias = ProcedureAlias::new(proc.name.clone(), target);
+            module
+                .define_procedure(Export::Alias(alias))
+                .expect("procedure names of a module must be unique");
+        }
+
+        module
+    }
+}
+
+/// Stores the name, digest and attributes of a procedure.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ProcedureInfo {
     pub name: ProcedureName,
     pub digest: RpoDigest,
+    pub attributes: AttributeSet,
 }
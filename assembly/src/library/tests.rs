@@ -1,8 +1,12 @@
+use alloc::string::ToString;
 use core::str::FromStr;
 
 use super::*;
 use crate::{
-    Assembler, Deserializable, ast::ProcedureName, diagnostics::Report, parse_module,
+    Assembler, Deserializable,
+    ast::{Attribute, Ident, MetaExpr, ProcedureName},
+    diagnostics::Report,
+    parse_module,
     testing::TestContext,
 };
 
@@ -221,3 +225,133 @@ fn get_module_by_path() -> Result<(), Report> {
 
     Ok(())
 }
+
+#[test]
+fn library_interface() -> Result<(), Report> {
+    let context = TestContext::new();
+    let math = r#"
+        export.add3
+            add add
+        end
+        export.mul3
+            mul mul
+        end
+    "#;
+    let math = parse_module!(&context, "test::math", math);
+    let library = Assembler::new(context.source_manager()).assemble_library([math])?;
+
+    // render the interface of the library module, and parse it back
+    let math_info = library.module_infos().next().unwrap();
+    let interface = math_info.to_interface().to_string();
+    let interface_ast = parse_module!(&context, "test::math", interface);
+    assert_eq!(ModuleInfo::from_interface(&interface_ast)?, math_info);
+
+    // code compiled against the interface is the same as code compiled against the library
+    let program = r#"
+        use.test::math
+        begin
+            exec.math::add3
+            exec.math::mul3
+        end
+    "#;
+    let with_interface = Assembler::new(context.source_manager())
+        .with_interface(interface_ast)?
+        .assemble_program(program)?;
+    let with_library = Assembler::new(context.source_manager())
+        .with_library(&library)?
+        .assemble_program(program)?;
+    assert_eq!(with_interface.hash(), with_library.hash());
+
+    library.check_interface(&math_info)?;
+
+    // a library with a different implementation of the interface is rejected
+    let other_math = r#"
+        export.add3
+            add add
+        end
+        export.mul3
+            mul add
+        end
+    "#;
+    let other_math = parse_module!(&context, "test::math", other_math);
+    let other_library = Assembler::new(context.source_manager()).assemble_library([other_math])?;
+    assert!(matches!(
+        other_library.check_interface(&math_info),
+        Err(LibraryError::InterfaceDigestMismatch { .. })
+    ));
+
+    // interfaces cannot define procedure bodies
+    let invalid = r#"
+        export.add3
+            add add
+        end
+    "#;
+    let invalid = parse_module!(&context, "test::math", invalid);
+    assert!(matches!(
+        ModuleInfo::from_interface(&invalid),
+        Err(LibraryError::InvalidInterfaceProcedure { .. })
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn library_procedure_attributes() -> Result<(), Report> {
+    let context = TestContext::new();
+    let foo = r#"
+        @deprecated("use `bar` instead")
+        @audit(status = "reviewed", round = 2)
+        export.foo
+            add
+        end
+
+        @inline
+        @no_syscall
+        export.bar
+            exec.baz
+        end
+
+        @inline
+        proc.baz
+            mul
+        end
+
+        export.qux
+            sub
+        end
+    "#;
+    let foo = parse_module!(&context, "test::foo", foo);
+    let library = Assembler::new(context.source_manager()).assemble_library([foo])?;
+
+    let foo = QualifiedProcedureName::from_str("test::foo::foo").unwrap();
+    let attrs = library.procedure_attributes(&foo).expect("missing attributes of foo");
+    assert_eq!(attrs.len(), 2);
+    let Some(Attribute::List(deprecated)) = attrs.get("deprecated") else {
+        panic!("expected a list attribute");
+    };
+    assert!(
+        matches!(deprecated.as_slice(), [MetaExpr::String(reason)] if reason.as_str() == "use `bar` instead")
+    );
+    let Some(Attribute::KeyValue(audit)) = attrs.get("audit") else {
+        panic!("expected a key-value attribute");
+    };
+    let status = audit.get(&Ident::new("status").unwrap());
+    assert!(matches!(status, Some(MetaExpr::String(status)) if status.as_str() == "reviewed"));
+
+    let bar = QualifiedProcedureName::from_str("test::foo::bar").unwrap();
+    let attrs = library.procedure_attributes(&bar).expect("missing attributes of bar");
+    assert!(attrs.has("inline") && attrs.has("no_syscall"));
+
+    // procedures without attributes, or which are not exported, have no attributes
+    let qux = QualifiedProcedureName::from_str("test::foo::qux").unwrap();
+    let baz = QualifiedProcedureName::from_str("test::foo::baz").unwrap();
+    assert!(library.procedure_attributes(&qux).is_none());
+    assert!(library.procedure_attributes(&baz).is_none());
+
+    // attributes are preserved by serialization
+    let deserialized = Library::read_from_bytes(&library.to_bytes()).unwrap();
+    assert_eq!(library, deserialized);
+    assert_eq!(deserialized.procedure_attributes(&foo), library.procedure_attributes(&foo));
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+// RESOURCE LIMITS
+// ================================================================================================
+
+/// Limits on the resources which may be consumed while parsing and analyzing a single module.
+///
+/// These are intended for services which assemble untrusted source code, so that adversarial
+/// inputs fail with an error, rather than consuming an unbounded amount of CPU time or memory. By
+/// default, no limits are enforced.
+///
+/// Limits are checked as early as possible: the nesting depth is checked while the source is
+/// being tokenized, before any syntax tree is built for the nested blocks, and the number of
+/// forms and procedures is checked before semantic analysis of the module begins.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ResourceLimits {
+    /// The maximum number of top-level forms in a module, i.e. doc comments, imports, constants,
+    /// procedures and entrypoints.
+    pub max_forms: usize,
+    /// The maximum depth to which control flow blocks (i.e. `if`, `while` and `repeat`) can be
+    /// nested within a procedure body.
+    pub max_nesting_depth: usize,
+    /// The maximum number of steps which can be taken to evaluate all of the constant expressions
+    /// of a module, where each step evaluates a single term of an expression.
+    pub max_constant_eval_steps: usize,
+    /// The maximum number of procedures, including re-exports and the entrypoint, in a module.
+    pub max_procedures: usize,
+}
+
+impl ResourceLimits {
+    /// A set of limits which places no bounds on the resources consumed.
+    pub const UNLIMITED: Self = Self {
+        max_forms: usize::MAX,
+        max_nesting_depth: usize::MAX,
+        max_constant_eval_steps: usize::MAX,
+        max_procedures: usize::MAX,
+    };
+}
+
+impl Default for ResourceLimits {
+    fn default() -> Self {
+        Self::UNLIMITED
+    }
+}
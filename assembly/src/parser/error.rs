@@ -168,6 +168,12 @@ pub enum ParsingError {
         #[label]
         span: SourceSpan,
     },
+    #[error("invalid constant expression: value is larger than expected range")]
+    #[diagnostic(help("the value of a constant must be a valid field element"))]
+    ConstantOverflow {
+        #[label]
+        span: SourceSpan,
+    },
     #[error("doc comment is too large")]
     #[diagnostic(help("make sure it is less than u16::MAX bytes in length"))]
     DocsTooLarge {
@@ -206,6 +212,16 @@ pub enum ParsingError {
         span: SourceSpan,
         message: String,
     },
+    #[error(
+        "invalid procedure instance: only procedures of the current module can be instantiated"
+    )]
+    #[diagnostic(help(
+        "generic procedures cannot be exported, invoke a procedure which instantiates it instead"
+    ))]
+    InvalidInstance {
+        #[label]
+        span: SourceSpan,
+    },
     #[error("invalid immediate: value must be in the range {}..{} (exclusive)", range.start, range.end)]
     ImmediateOutOfRange {
         #[label]
@@ -256,6 +272,13 @@ pub enum ParsingError {
         #[label("previously defined here")]
         prev: SourceSpan,
     },
+    #[error("control flow blocks are nested too deeply")]
+    #[diagnostic(help("blocks can be nested at most {limit} levels deep"))]
+    NestingTooDeep {
+        #[label("this block exceeds the limit")]
+        span: SourceSpan,
+        limit: usize,
+    },
     #[error("conflicting key-value attributes for procedure definition")]
     #[diagnostic()]
     AttributeKeyValueConflict {
@@ -369,6 +392,7 @@ fn simplify_expected_tokens(expected: Vec<String>) -> Vec<String> {
     use super::Token;
     let mut has_instruction = false;
     let mut has_ctrl = false;
+    let mut has_const_op = false;
     expected
         .into_iter()
         .filter_map(|t| {
@@ -380,6 +404,7 @@ fn simplify_expected_tokens(expected: Vec<String>) -> Vec<String> {
                 "hex_value" => return Some("hex-encoded literal".to_string()),
                 "bin_value" => return Some("bin-encoded literal".to_string()),
                 "uint" => return Some("integer literal".to_string()),
+                "macro_ident" => return Some("macro invocation".to_string()),
                 "EOF" => return Some("end of file".to_string()),
                 other => other[1..].strip_suffix('"').and_then(|t| Token::parse(t).ok()),
             };
@@ -392,6 +417,32 @@ fn simplify_expected_tokens(expected: Vec<String>) -> Vec<String> {
                         None
                     }
                 },
+                Some(
+                    Token::Plus
+                    | Token::Minus
+                    | Token::Star
+                    | Token::Slash
+                    | Token::SlashSlash
+                    | Token::Percent
+                    | Token::LangleLangle
+                    | Token::RangleRangle
+                    | Token::Ampersand
+                    | Token::Pipe
+                    | Token::Caret
+                    | Token::EqualEqual
+                    | Token::BangEqual
+                    | Token::Langle
+                    | Token::LangleEqual
+                    | Token::Rangle
+                    | Token::RangleEqual,
+                ) => {
+                    if !has_const_op {
+                        has_const_op = true;
+                        Some("constant operator (e.g. \"+\")".to_string())
+                    } else {
+                        None
+                    }
+                },
                 Some(tok) if tok.is_instruction() => {
                     if !has_instruction {
                         has_instruction = true;
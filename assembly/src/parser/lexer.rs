@@ -275,14 +275,20 @@ impl<'input> Lexer<'input> {
 
         match self.read() {
             '@' => pop!(self, Token::At),
-            '!' => pop!(self, Token::Bang),
+            '!' => match self.peek() {
+                '=' => pop2!(self, Token::BangEqual),
+                _ => pop!(self, Token::Bang),
+            },
             ':' => match self.peek() {
                 ':' => pop2!(self, Token::ColonColon),
                 _ => Err(ParsingError::InvalidToken { span: self.span() }),
             },
             '.' => pop!(self, Token::Dot),
             ',' => pop!(self, Token::Comma),
-            '=' => pop!(self, Token::Equal),
+            '=' => match self.peek() {
+                '=' => pop2!(self, Token::EqualEqual),
+                _ => pop!(self, Token::Equal),
+            },
             '(' => pop!(self, Token::Lparen),
             '[' => pop!(self, Token::Lbracket),
             ')' => pop!(self, Token::Rparen),
@@ -297,6 +303,20 @@ impl<'input> Lexer<'input> {
                 _ => pop!(self, Token::Slash),
             },
             '*' => pop!(self, Token::Star),
+            '%' => pop!(self, Token::Percent),
+            '&' => pop!(self, Token::Ampersand),
+            '|' => pop!(self, Token::Pipe),
+            '^' => pop!(self, Token::Caret),
+            '<' => match self.peek() {
+                '<' => pop2!(self, Token::LangleLangle),
+                '=' => pop2!(self, Token::LangleEqual),
+                _ => pop!(self, Token::Langle),
+            },
+            '>' => match self.peek() {
+                '>' => pop2!(self, Token::RangleRangle),
+                '=' => pop2!(self, Token::RangleEqual),
+                _ => pop!(self, Token::Rangle),
+            },
             '"' => self.lex_quoted_identifier_or_string(),
             '0' => match self.peek() {
                 'x' => {
@@ -405,6 +425,12 @@ impl<'input> Lexer<'input> {
         }
 
         let name = self.slice();
+        if self.read() == '!' {
+            // An identifier immediately followed by `!` is the name of an invoked macro, which may
+            // also be the name of an instruction
+            self.skip();
+            return Ok(Token::MacroIdent(name));
+        }
         match name {
             "exp" => {
                 // Special handling for the `exp.uXX` tokenization
@@ -28,8 +28,8 @@ pub use self::{
     token::{BinEncodedValue, DocumentationType, HexEncodedValue, Token},
 };
 use crate::{
-    LibraryPath, SourceManager, ast,
-    diagnostics::{Report, SourceFile, SourceSpan, Span, Spanned},
+    LibraryPath, ResourceLimits, SourceManager, ast,
+    diagnostics::{Report, SourceFile, SourceId, SourceSpan, Span, Spanned},
     sema,
 };
 
@@ -69,6 +69,10 @@ pub struct ModuleParser {
     interned: BTreeSet<Arc<str>>,
     /// When true, all warning diagnostics are promoted to error severity
     warnings_as_errors: bool,
+    /// The limits on the resources consumed while parsing and analyzing a module
+    limits: ResourceLimits,
+    /// The features enabled for conditional compilation, i.e. `@cfg(feature = "name")`
+    features: BTreeSet<Arc<str>>,
 }
 
 impl ModuleParser {
@@ -78,6 +82,8 @@ impl ModuleParser {
             kind,
             interned: Default::default(),
             warnings_as_errors: false,
+            limits: ResourceLimits::UNLIMITED,
+            features: Default::default(),
         }
     }
 
@@ -86,15 +92,35 @@ impl ModuleParser {
         self.warnings_as_errors = yes;
     }
 
+    /// Configure the limits on the resources this parser may consume when parsing a module.
+    pub fn set_resource_limits(&mut self, limits: ResourceLimits) {
+        self.limits = limits;
+    }
+
+    /// Configure the features which are enabled when conditionally compiling a module.
+    pub fn set_features(&mut self, features: BTreeSet<Arc<str>>) {
+        self.features = features;
+    }
+
     /// Parse a [ast::Module] from `source`, and give it the provided `path`.
     pub fn parse(
         &mut self,
         path: LibraryPath,
         source: Arc<SourceFile>,
     ) -> Result<Box<ast::Module>, Report> {
-        let forms = parse_forms_internal(source.clone(), &mut self.interned)
-            .map_err(|err| Report::new(err).with_source_code(source.clone()))?;
-        sema::analyze(source, self.kind, path, forms, self.warnings_as_errors).map_err(Report::new)
+        let forms =
+            parse_forms_internal(source.clone(), &mut self.interned, self.limits.max_nesting_depth)
+                .map_err(|err| Report::new(err).with_source_code(source.clone()))?;
+        sema::analyze(
+            source,
+            self.kind,
+            path,
+            forms,
+            self.warnings_as_errors,
+            &self.limits,
+            &self.features,
+        )
+        .map_err(Report::new)
     }
 
     /// Parse a [ast::Module], `name`, from `path`.
@@ -143,25 +169,68 @@ impl ModuleParser {
 #[cfg(any(test, feature = "testing"))]
 pub fn parse_forms(source: Arc<SourceFile>) -> Result<Vec<ast::Form>, ParsingError> {
     let mut interned = BTreeSet::default();
-    parse_forms_internal(source, &mut interned)
+    parse_forms_internal(source, &mut interned, usize::MAX)
 }
 
 /// Parse `source` as a set of [ast::Form]s
 ///
 /// Aside from catching syntax errors, this does little validation of the resulting forms, that is
 /// handled by semantic analysis, which the caller is expected to perform next.
+///
+/// Control flow blocks nested deeper than `max_nesting_depth` are rejected.
 fn parse_forms_internal(
     source: Arc<SourceFile>,
     interned: &mut BTreeSet<Arc<str>>,
+    max_nesting_depth: usize,
 ) -> Result<Vec<ast::Form>, ParsingError> {
     let source_id = source.id();
     let scanner = Scanner::new(source.as_str());
-    let lexer = Lexer::new(source_id, scanner);
+    let lexer = DepthLimitedLexer {
+        lexer: Lexer::new(source_id, scanner),
+        source_id,
+        depth: 0,
+        max_depth: max_nesting_depth,
+    };
     grammar::FormsParser::new()
         .parse(&source, interned, core::marker::PhantomData, lexer)
         .map_err(|err| ParsingError::from_parse_error(source_id, err))
 }
 
+/// A [Lexer] which tracks the nesting depth of control flow blocks in the token stream, and fails
+/// when it exceeds `max_depth`.
+///
+/// Doing this on the token stream, rather than on the parsed forms, ensures that we never build
+/// a syntax tree deep enough to exhaust the stack when it is later visited (or dropped).
+struct DepthLimitedLexer<'input> {
+    lexer: Lexer<'input>,
+    source_id: SourceId,
+    depth: usize,
+    max_depth: usize,
+}
+
+impl<'input> Iterator for DepthLimitedLexer<'input> {
+    type Item = lexer::Lexed<'input>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let lexed = self.lexer.next()?;
+        match lexed {
+            Ok((start, Token::If | Token::While | Token::Repeat, end)) => {
+                self.depth += 1;
+                if self.depth > self.max_depth {
+                    return Some(Err(ParsingError::NestingTooDeep {
+                        span: SourceSpan::new(self.source_id, start..end),
+                        limit: self.max_depth,
+                    }));
+                }
+            },
+            // The `end` of a procedure body or entrypoint is seen at depth zero
+            Ok((_, Token::End, _)) => self.depth = self.depth.saturating_sub(1),
+            _ => (),
+        }
+        Some(lexed)
+    }
+}
+
 // DIRECTORY PARSER
 // ================================================================================================
 
@@ -387,6 +456,22 @@ end
         assert_matches!(lexer.next(), Some(Ok(Token::Eof)));
     }
 
+    // This test checks that an identifier immediately followed by `!` is tokenized as a macro
+    // invocation, while a `!` preceded by whitespace is not
+    #[test]
+    fn lex_macro_invocation() {
+        let source_id = SourceId::default();
+        let scanner = Scanner::new("begin swap_all!(1) dup !");
+        let mut lexer = Lexer::new(source_id, scanner).map(|result| result.map(|(_, t, _)| t));
+        assert_matches!(lexer.next(), Some(Ok(Token::Begin)));
+        assert_matches!(lexer.next(), Some(Ok(Token::MacroIdent("swap_all"))));
+        assert_matches!(lexer.next(), Some(Ok(Token::Lparen)));
+        assert_matches!(lexer.next(), Some(Ok(Token::Int(1))));
+        assert_matches!(lexer.next(), Some(Ok(Token::Rparen)));
+        assert_matches!(lexer.next(), Some(Ok(Token::Dup)));
+        assert_matches!(lexer.next(), Some(Ok(Token::Bang)));
+    }
+
     #[test]
     fn lex_emit() {
         let source_id = SourceId::default();
@@ -165,6 +165,7 @@ pub enum Token<'input> {
     Cdropw,
     Clk,
     Const,
+    Ctx,
     Cswap,
     Cswapw,
     Debug,
@@ -200,6 +201,18 @@ pub enum Token<'input> {
     HornerExt,
     Hperm,
     Hmerge,
+    I32Abs,
+    I32Div,
+    I32Divmod,
+    I32Gt,
+    I32Gte,
+    I32Lt,
+    I32Lte,
+    I32Max,
+    I32Min,
+    I32Mod,
+    I32Neg,
+    I32Shr,
     If,
     ILog2,
     Inv,
@@ -212,7 +225,9 @@ pub enum Token<'input> {
     LocStorew,
     Lt,
     Lte,
+    Macro,
     Mem,
+    MemInit,
     MemLoad,
     MemLoadw,
     MemStore,
@@ -288,6 +303,8 @@ pub enum Token<'input> {
     U32WrappingMul,
     U32WrappingSub,
     U32Xor,
+    U64OverflowingAdd,
+    U64OverflowingSub,
     While,
     Xor,
     At,
@@ -306,6 +323,18 @@ pub enum Token<'input> {
     Rparen,
     Rbracket,
     Rstab,
+    Ampersand,
+    BangEqual,
+    Caret,
+    EqualEqual,
+    Langle,
+    LangleEqual,
+    LangleLangle,
+    Percent,
+    Pipe,
+    Rangle,
+    RangleEqual,
+    RangleRangle,
     DocComment(DocumentationType),
     HexValue(HexEncodedValue),
     BinValue(BinEncodedValue),
@@ -314,6 +343,8 @@ pub enum Token<'input> {
     ConstantIdent(&'input str),
     QuotedIdent(&'input str),
     QuotedString(&'input str),
+    /// The name of an invoked macro, i.e. an identifier immediately followed by `!`
+    MacroIdent(&'input str),
     Comment,
     Eof,
 }
@@ -350,6 +381,7 @@ impl fmt::Display for Token<'_> {
             Token::Cdrop => write!(f, "cdrop"),
             Token::Cdropw => write!(f, "cdropw"),
             Token::Clk => write!(f, "clk"),
+            Token::Ctx => write!(f, "ctx"),
             Token::Const => write!(f, "const"),
             Token::Cswap => write!(f, "cswap"),
             Token::Cswapw => write!(f, "cswapw"),
@@ -384,6 +416,18 @@ impl fmt::Display for Token<'_> {
             Token::Hash => write!(f, "hash"),
             Token::Hperm => write!(f, "hperm"),
             Token::Hmerge => write!(f, "hmerge"),
+            Token::I32Abs => write!(f, "i32abs"),
+            Token::I32Div => write!(f, "i32div"),
+            Token::I32Divmod => write!(f, "i32divmod"),
+            Token::I32Gt => write!(f, "i32gt"),
+            Token::I32Gte => write!(f, "i32gte"),
+            Token::I32Lt => write!(f, "i32lt"),
+            Token::I32Lte => write!(f, "i32lte"),
+            Token::I32Max => write!(f, "i32max"),
+            Token::I32Min => write!(f, "i32min"),
+            Token::I32Mod => write!(f, "i32mod"),
+            Token::I32Neg => write!(f, "i32neg"),
+            Token::I32Shr => write!(f, "i32shr"),
             Token::If => write!(f, "if"),
             Token::ILog2 => write!(f, "ilog2"),
             Token::Inv => write!(f, "inv"),
@@ -396,7 +440,9 @@ impl fmt::Display for Token<'_> {
             Token::LocStorew => write!(f, "loc_storew"),
             Token::Lt => write!(f, "lt"),
             Token::Lte => write!(f, "lte"),
+            Token::Macro => write!(f, "macro"),
             Token::Mem => write!(f, "mem"),
+            Token::MemInit => write!(f, "mem_init"),
             Token::MemLoad => write!(f, "mem_load"),
             Token::MemLoadw => write!(f, "mem_loadw"),
             Token::MemStore => write!(f, "mem_store"),
@@ -474,6 +520,8 @@ impl fmt::Display for Token<'_> {
             Token::U32WrappingMul => write!(f, "u32wrapping_mul"),
             Token::U32WrappingSub => write!(f, "u32wrapping_sub"),
             Token::U32Xor => write!(f, "u32xor"),
+            Token::U64OverflowingAdd => write!(f, "u64overflowing_add"),
+            Token::U64OverflowingSub => write!(f, "u64overflowing_sub"),
             Token::While => write!(f, "while"),
             Token::Xor => write!(f, "xor"),
             Token::At => write!(f, "@"),
@@ -492,6 +540,18 @@ impl fmt::Display for Token<'_> {
             Token::Rparen => write!(f, ")"),
             Token::Rbracket => write!(f, "]"),
             Token::Rstab => write!(f, "->"),
+            Token::Ampersand => write!(f, "&"),
+            Token::BangEqual => write!(f, "!="),
+            Token::Caret => write!(f, "^"),
+            Token::EqualEqual => write!(f, "=="),
+            Token::Langle => write!(f, "<"),
+            Token::LangleEqual => write!(f, "<="),
+            Token::LangleLangle => write!(f, "<<"),
+            Token::Percent => write!(f, "%"),
+            Token::Pipe => write!(f, "|"),
+            Token::Rangle => write!(f, ">"),
+            Token::RangleEqual => write!(f, ">="),
+            Token::RangleRangle => write!(f, ">>"),
             Token::DocComment(DocumentationType::Module(_)) => f.write_str("module doc"),
             Token::DocComment(DocumentationType::Form(_)) => f.write_str("doc comment"),
             Token::HexValue(_) => f.write_str("hex-encoded value"),
@@ -501,6 +561,7 @@ impl fmt::Display for Token<'_> {
             Token::ConstantIdent(_) => f.write_str("constant identifier"),
             Token::QuotedIdent(_) => f.write_str("quoted identifier"),
             Token::QuotedString(_) => f.write_str("quoted string"),
+            Token::MacroIdent(_) => f.write_str("macro invocation"),
             Token::Comment => f.write_str("comment"),
             Token::Eof => write!(f, "end of file"),
         }
@@ -543,6 +604,7 @@ impl<'input> Token<'input> {
                 | Token::Cdrop
                 | Token::Cdropw
                 | Token::Clk
+                | Token::Ctx
                 | Token::Cswap
                 | Token::Cswapw
                 | Token::Debug
@@ -573,6 +635,18 @@ impl<'input> Token<'input> {
                 | Token::Hmerge
                 | Token::HornerBase
                 | Token::HornerExt
+                | Token::I32Abs
+                | Token::I32Div
+                | Token::I32Divmod
+                | Token::I32Gt
+                | Token::I32Gte
+                | Token::I32Lt
+                | Token::I32Lte
+                | Token::I32Max
+                | Token::I32Min
+                | Token::I32Mod
+                | Token::I32Neg
+                | Token::I32Shr
                 | Token::ILog2
                 | Token::Inv
                 | Token::IsOdd
@@ -585,6 +659,7 @@ impl<'input> Token<'input> {
                 | Token::Lt
                 | Token::Lte
                 | Token::Mem
+                | Token::MemInit
                 | Token::MemLoad
                 | Token::MemLoadw
                 | Token::MemStore
@@ -656,6 +731,8 @@ impl<'input> Token<'input> {
                 | Token::U32WrappingMul
                 | Token::U32WrappingSub
                 | Token::U32Xor
+                | Token::U64OverflowingAdd
+                | Token::U64OverflowingSub
                 | Token::Xor
         )
     }
@@ -691,6 +768,7 @@ impl<'input> Token<'input> {
         ("cdropw", Token::Cdropw),
         ("clk", Token::Clk),
         ("const", Token::Const),
+        ("ctx", Token::Ctx),
         ("cswap", Token::Cswap),
         ("cswapw", Token::Cswapw),
         ("debug", Token::Debug),
@@ -724,6 +802,18 @@ impl<'input> Token<'input> {
         ("hash", Token::Hash),
         ("hperm", Token::Hperm),
         ("hmerge", Token::Hmerge),
+        ("i32abs", Token::I32Abs),
+        ("i32div", Token::I32Div),
+        ("i32divmod", Token::I32Divmod),
+        ("i32gt", Token::I32Gt),
+        ("i32gte", Token::I32Gte),
+        ("i32lt", Token::I32Lt),
+        ("i32lte", Token::I32Lte),
+        ("i32max", Token::I32Max),
+        ("i32min", Token::I32Min),
+        ("i32mod", Token::I32Mod),
+        ("i32neg", Token::I32Neg),
+        ("i32shr", Token::I32Shr),
         ("if", Token::If),
         ("ilog2", Token::ILog2),
         ("inv", Token::Inv),
@@ -736,7 +826,9 @@ impl<'input> Token<'input> {
         ("loc_storew", Token::LocStorew),
         ("lt", Token::Lt),
         ("lte", Token::Lte),
+        ("macro", Token::Macro),
         ("mem", Token::Mem),
+        ("mem_init", Token::MemInit),
         ("mem_load", Token::MemLoad),
         ("mem_loadw", Token::MemLoadw),
         ("mem_store", Token::MemStore),
@@ -814,10 +906,22 @@ impl<'input> Token<'input> {
         ("u32wrapping_mul", Token::U32WrappingMul),
         ("u32wrapping_sub", Token::U32WrappingSub),
         ("u32xor", Token::U32Xor),
+        ("u64overflowing_add", Token::U64OverflowingAdd),
+        ("u64overflowing_sub", Token::U64OverflowingSub),
         ("while", Token::While),
         ("xor", Token::Xor),
     ];
 
+    /// Returns an iterator over the keywords which name an instruction.
+    ///
+    /// This is used to provide completions for instructions, see [crate::completion].
+    pub fn instruction_keywords() -> impl Iterator<Item = &'static str> {
+        Self::KEYWORDS
+            .iter()
+            .filter(|(_, token)| token.is_instruction())
+            .map(|(kw, _)| *kw)
+    }
+
     /// Constructs a DFA capable of recognizing Miden Assembly keywords.
     ///
     /// Constructing the state machine is expensive, so it should not be done in hot code. Instead,
@@ -901,6 +1005,18 @@ impl<'input> Token<'input> {
                     ")" => Ok(Token::Rparen),
                     "]" => Ok(Token::Rbracket),
                     "->" => Ok(Token::Rstab),
+                    "&" => Ok(Token::Ampersand),
+                    "!=" => Ok(Token::BangEqual),
+                    "^" => Ok(Token::Caret),
+                    "==" => Ok(Token::EqualEqual),
+                    "<" => Ok(Token::Langle),
+                    "<=" => Ok(Token::LangleEqual),
+                    "<<" => Ok(Token::LangleLangle),
+                    "%" => Ok(Token::Percent),
+                    "|" => Ok(Token::Pipe),
+                    ">" => Ok(Token::Rangle),
+                    ">=" => Ok(Token::RangleEqual),
+                    ">>" => Ok(Token::RangleRangle),
                     "end of file" => Ok(Token::Eof),
                     "module doc" => Ok(Token::DocComment(DocumentationType::Module(String::new()))),
                     "doc comment" => Ok(Token::DocComment(DocumentationType::Form(String::new()))),
@@ -912,6 +1028,7 @@ impl<'input> Token<'input> {
                     "constant identifier" => Ok(Token::ConstantIdent("")),
                     "quoted identifier" => Ok(Token::QuotedIdent("")),
                     "quoted string" => Ok(Token::QuotedString("")),
+                    "macro invocation" => Ok(Token::MacroIdent("")),
                     _ => Err(()),
                 }
             },
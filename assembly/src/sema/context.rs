@@ -1,12 +1,13 @@
 use alloc::{
+    boxed::Box,
     collections::{BTreeMap, BTreeSet},
     sync::Arc,
     vec::Vec,
 };
 
-use super::{SemanticAnalysisError, SyntaxError};
+use super::{MacroExpansionError, SemanticAnalysisError, SyntaxError, errors::LimitKind};
 use crate::{
-    Felt, SourceFile, Span, Spanned,
+    Felt, SourceFile, SourceSpan, Span, Spanned,
     ast::*,
     diagnostics::{Diagnostic, Severity},
 };
@@ -15,20 +16,34 @@ use crate::{
 pub struct AnalysisContext {
     /// A map of constants to the value of that constant
     constants: BTreeMap<Ident, Constant>,
+    /// A map of data constants to their definition, with all elements evaluated
+    data: BTreeMap<Ident, DataConstant>,
     procedures: BTreeSet<ProcedureName>,
     errors: Vec<SemanticAnalysisError>,
     source_file: Arc<SourceFile>,
     warnings_as_errors: bool,
+    /// The number of steps which remain to evaluate constant expressions
+    const_eval_steps: usize,
+    /// The macro invocations being expanded, innermost last, along with the spans of the bodies
+    /// of the invoked macros
+    expanding: Vec<(SourceSpan, SourceSpan)>,
+    /// All macro invocations expanded so far, along with the spans of the bodies of the invoked
+    /// macros
+    expanded: Vec<(SourceSpan, SourceSpan)>,
 }
 
 impl AnalysisContext {
     pub fn new(source_file: Arc<SourceFile>) -> Self {
         Self {
             constants: Default::default(),
+            data: Default::default(),
             procedures: Default::default(),
             errors: Default::default(),
             source_file,
             warnings_as_errors: false,
+            const_eval_steps: usize::MAX,
+            expanding: Default::default(),
+            expanded: Default::default(),
         }
     }
 
@@ -36,6 +51,11 @@ impl AnalysisContext {
         self.warnings_as_errors = yes;
     }
 
+    /// Set the maximum number of steps which can be taken to evaluate constant expressions.
+    pub fn set_max_constant_eval_steps(&mut self, steps: usize) {
+        self.const_eval_steps = steps;
+    }
+
     #[inline(always)]
     pub fn warnings_as_errors(&self) -> bool {
         self.warnings_as_errors
@@ -51,11 +71,9 @@ impl AnalysisContext {
     pub fn define_constant(&mut self, mut constant: Constant) -> Result<(), SyntaxError> {
         // Handle symbol conflicts before eval to make sure we can catch self-referential
         // expressions.
-        if let Some(value) = self.constants.get(&constant.name) {
-            self.errors.push(SemanticAnalysisError::SymbolConflict {
-                span: constant.span(),
-                prev_span: value.span(),
-            });
+        if let Some(prev_span) = self.get_definition_span(&constant.name) {
+            self.errors
+                .push(SemanticAnalysisError::SymbolConflict { span: constant.span(), prev_span });
             return Ok(());
         }
 
@@ -76,38 +94,160 @@ impl AnalysisContext {
         }
     }
 
-    fn const_eval(&self, value: &ConstantExpr) -> Result<Felt, SemanticAnalysisError> {
+    /// Define a new data constant `name`, bound to `value`
+    ///
+    /// Returns `Err` if the symbol is already defined
+    pub fn define_data_constant(&mut self, mut constant: DataConstant) -> Result<(), SyntaxError> {
+        if let Some(prev_span) = self.get_definition_span(&constant.name) {
+            self.errors
+                .push(SemanticAnalysisError::SymbolConflict { span: constant.span(), prev_span });
+            return Ok(());
+        }
+
+        if let DataValue::Elements(elements) = &mut constant.value {
+            for element in elements.iter_mut() {
+                match self.const_eval(element) {
+                    Ok(value) => {
+                        *element = ConstantExpr::Literal(Span::new(element.span(), value));
+                    },
+                    Err(err) => {
+                        self.errors.push(err);
+                        let errors = core::mem::take(&mut self.errors);
+                        return Err(SyntaxError {
+                            source_file: self.source_file.clone(),
+                            errors,
+                        });
+                    },
+                }
+            }
+        }
+        if constant.value.expect_elements().is_empty() {
+            self.errors
+                .push(SemanticAnalysisError::EmptyDataConstant { span: constant.value.span() });
+            return Ok(());
+        }
+
+        self.data.insert(constant.name.clone(), constant);
+        Ok(())
+    }
+
+    fn get_definition_span(&self, name: &Ident) -> Option<SourceSpan> {
+        self.constants
+            .get(name)
+            .map(Spanned::span)
+            .or_else(|| self.data.get(name).map(Spanned::span))
+    }
+
+    fn const_eval(&mut self, value: &ConstantExpr) -> Result<Felt, SemanticAnalysisError> {
+        self.const_eval_steps =
+            self.const_eval_steps
+                .checked_sub(1)
+                .ok_or(SemanticAnalysisError::LimitExceeded {
+                    span: value.span(),
+                    kind: LimitKind::ConstantEvalSteps,
+                })?;
         match value {
             ConstantExpr::Literal(value) => Ok(value.into_inner()),
             ConstantExpr::Var(name) => self.get_constant(name),
-            ConstantExpr::BinaryOp { op, lhs, rhs, .. } => {
+            ConstantExpr::BinaryOp { span, op, lhs, rhs } => {
                 let rhs = self.const_eval(rhs)?;
                 let lhs = self.const_eval(lhs)?;
-                match op {
-                    ConstantOp::Add => Ok(lhs + rhs),
-                    ConstantOp::Sub => Ok(lhs - rhs),
-                    ConstantOp::Mul => Ok(lhs * rhs),
-                    ConstantOp::Div => Ok(lhs / rhs),
-                    ConstantOp::IntDiv => Ok(Felt::new(lhs.as_int() / rhs.as_int())),
-                }
+                op.apply(lhs, rhs).map_err(|error| match error {
+                    ConstantEvalError::DivisionByZero => {
+                        SemanticAnalysisError::DivisionByZero { span: *span }
+                    },
+                    ConstantEvalError::Overflow => {
+                        SemanticAnalysisError::ConstantOverflow { span: *span }
+                    },
+                })
             },
         }
     }
 
+    /// Get an iterator over the constants defined so far, with their values fully evaluated
+    pub fn constants(&self) -> impl Iterator<Item = &Constant> {
+        self.constants.values()
+    }
+
+    /// Get an iterator over the data constants defined so far, with their elements fully evaluated
+    pub fn data_constants(&self) -> impl Iterator<Item = &DataConstant> {
+        self.data.values()
+    }
+
     /// Get the constant value bound to `name`
     ///
-    /// Returns `Err` if the symbol is undefined
+    /// Returns `Err` if the symbol is undefined, or bound to a data constant
     pub fn get_constant(&self, name: &Ident) -> Result<Felt, SemanticAnalysisError> {
         let span = name.span();
         if let Some(expr) = self.constants.get(name) {
             Ok(expr.value.expect_literal())
+        } else if self.data.contains_key(name) {
+            Err(SemanticAnalysisError::UnexpectedDataConstant { span })
+        } else {
+            Err(SemanticAnalysisError::SymbolUndefined { span })
+        }
+    }
+
+    /// Get the elements of the data constant bound to `name`
+    ///
+    /// Returns `Err` if the symbol is undefined, or bound to a constant value
+    pub fn get_data_constant(&self, name: &Ident) -> Result<Vec<Felt>, SemanticAnalysisError> {
+        let span = name.span();
+        if let Some(constant) = self.data.get(name) {
+            Ok(constant.value.expect_elements())
+        } else if self.constants.contains_key(name) {
+            Err(SemanticAnalysisError::ExpectedDataConstant { span })
         } else {
             Err(SemanticAnalysisError::SymbolUndefined { span })
         }
     }
 
     pub fn error(&mut self, diagnostic: SemanticAnalysisError) {
-        self.errors.push(diagnostic);
+        let invocations = self.invocations_expanding(&diagnostic);
+        if invocations.is_empty() {
+            self.errors.push(diagnostic);
+        } else {
+            self.errors.push(SemanticAnalysisError::InMacroExpansion(MacroExpansionError {
+                error: Box::new(diagnostic),
+                invocations,
+            }));
+        }
+    }
+
+    /// Marks the start of the expansion of a macro whose body spans `body`, invoked at `invocation`.
+    ///
+    /// Diagnostics raised by the operations expanded from the body are labeled with the invocation.
+    pub fn enter_macro_expansion(&mut self, body: SourceSpan, invocation: SourceSpan) {
+        self.expanding.push((body, invocation));
+        self.expanded.push((body, invocation));
+    }
+
+    /// Marks the end of the innermost macro expansion.
+    pub fn exit_macro_expansion(&mut self) {
+        self.expanding.pop();
+    }
+
+    /// Returns the spans of the invocations which expanded the operation `diagnostic` points to.
+    ///
+    /// During expansion, this is the innermost invocation of the macro whose body contains the
+    /// operation, and afterwards, every invocation of that macro.
+    fn invocations_expanding(&self, diagnostic: &SemanticAnalysisError) -> Vec<SourceSpan> {
+        let Some(offset) = diagnostic.labels().and_then(|mut labels| labels.next()) else {
+            return Vec::new();
+        };
+        let offset = offset.offset() as u32;
+        let contains = |body: &SourceSpan| body.into_range().contains(&offset);
+        if let Some((_, invocation)) = self.expanding.iter().rev().find(|(body, _)| contains(body))
+        {
+            return Vec::from([*invocation]);
+        }
+        let mut invocations = Vec::new();
+        for (_, invocation) in self.expanded.iter().filter(|(body, _)| contains(body)) {
+            if !invocations.contains(invocation) {
+                invocations.push(*invocation);
+            }
+        }
+        invocations
     }
 
     pub fn has_errors(&self) -> bool {
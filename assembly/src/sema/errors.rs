@@ -1,7 +1,10 @@
-use alloc::{sync::Arc, vec::Vec};
+use alloc::{boxed::Box, string::String, sync::Arc, vec::Vec};
 use core::fmt;
 
-use crate::{SourceFile, SourceSpan, diagnostics::Diagnostic};
+use crate::{
+    SourceFile, SourceSpan,
+    diagnostics::{Diagnostic, LabeledSpan, Severity, SourceCode},
+};
 
 /// The high-level error type for all semantic analysis errors.
 ///
@@ -80,6 +83,26 @@ pub enum SemanticAnalysisError {
         #[label]
         span: SourceSpan,
     },
+    #[error("invalid constant reference: expected a value, but found a data constant")]
+    #[diagnostic(help("data constants can only be used with `push` and `mem_init`"))]
+    UnexpectedDataConstant {
+        #[label]
+        span: SourceSpan,
+    },
+    #[error("invalid constant reference: expected a data constant")]
+    #[diagnostic(help(
+        "`mem_init` requires a constant defined as a string, or as a list of elements"
+    ))]
+    ExpectedDataConstant {
+        #[label]
+        span: SourceSpan,
+    },
+    #[error("invalid data constant: value cannot be empty")]
+    #[diagnostic()]
+    EmptyDataConstant {
+        #[label]
+        span: SourceSpan,
+    },
     #[error("unused import")]
     #[diagnostic(severity(Warning), help("this import is never used and can be safely removed"))]
     UnusedImport {
@@ -151,6 +174,163 @@ pub enum SemanticAnalysisError {
         #[label]
         span: SourceSpan,
     },
+    #[error("symbol undefined: no such macro found in this module")]
+    #[diagnostic(help("macros must be defined in the module which invokes them"))]
+    UndefinedMacro {
+        #[label]
+        span: SourceSpan,
+    },
+    #[error("invalid macro invocation: expected {expected} arguments, but got {found}")]
+    #[diagnostic()]
+    MacroArityMismatch {
+        #[label("invoked here")]
+        span: SourceSpan,
+        #[label("macro defined here")]
+        definition: SourceSpan,
+        expected: usize,
+        found: usize,
+    },
+    #[error("invalid recursive macro invocation")]
+    #[diagnostic(help("macros are expanded in place, so they cannot invoke themselves"))]
+    RecursiveMacro {
+        #[label("caused by this invocation")]
+        span: SourceSpan,
+        #[label("while expanding this macro")]
+        definition: SourceSpan,
+    },
+    #[error("invalid procedure instance: expected {expected} arguments, but got {found}")]
+    #[diagnostic()]
+    GenericArityMismatch {
+        #[label("invoked here")]
+        span: SourceSpan,
+        #[label("procedure defined here")]
+        definition: SourceSpan,
+        expected: usize,
+        found: usize,
+    },
+    #[error("invalid generic procedure: generic procedures cannot be exported")]
+    #[diagnostic(help(
+        "export a procedure which invokes an instance of it instead, e.g. `export.foo_4 exec.foo<4> end`"
+    ))]
+    GenericProcedureExport {
+        #[label]
+        span: SourceSpan,
+    },
+    #[error("unused generic procedure")]
+    #[diagnostic(
+        severity(Warning),
+        help("this procedure is never instantiated and can be safely removed")
+    )]
+    UnusedGenericProcedure {
+        #[label]
+        span: SourceSpan,
+    },
+    #[error("unused macro")]
+    #[diagnostic(severity(Warning), help("this macro is never invoked and can be safely removed"))]
+    UnusedMacro {
+        #[label]
+        span: SourceSpan,
+    },
+    #[error("invalid conditional compilation predicate")]
+    #[diagnostic(help(
+        "expected `@cfg(feature = \"name\")`, which is the only annotation allowed on instructions"
+    ))]
+    InvalidCfgPredicate {
+        #[label]
+        span: SourceSpan,
+    },
+    #[error("invalid attribute: `@{name}` is malformed")]
+    #[diagnostic(help("expected {expected}"))]
+    InvalidAttribute {
+        #[label]
+        span: SourceSpan,
+        name: &'static str,
+        expected: &'static str,
+    },
+    #[error("invalid syscall: this procedure is marked `@no_syscall`")]
+    #[diagnostic()]
+    SyscallInNoSyscallProcedure {
+        #[label]
+        span: SourceSpan,
+        #[label("the procedure is marked here")]
+        attribute: SourceSpan,
+    },
+    #[error("use of deprecated procedure")]
+    #[diagnostic(severity(Warning))]
+    DeprecatedProcedure {
+        #[label]
+        span: SourceSpan,
+        #[label("deprecated here")]
+        attribute: SourceSpan,
+        #[help]
+        reason: Option<String>,
+    },
+    #[error("stack underflow: expected {required} elements on the stack, but found {found}")]
+    #[diagnostic(help(
+        "the procedure only has access to the inputs given by its stack effect, and to the \
+         elements it pushed itself"
+    ))]
+    StackUnderflow {
+        #[label]
+        span: SourceSpan,
+        #[label("stack effect declared here")]
+        declared: SourceSpan,
+        required: usize,
+        found: usize,
+    },
+    #[error("stack effect mismatch: expected {expected} outputs, but found {found}")]
+    #[diagnostic()]
+    StackEffectMismatch {
+        #[label]
+        span: SourceSpan,
+        #[label("stack effect declared here")]
+        declared: SourceSpan,
+        expected: usize,
+        found: usize,
+    },
+    #[error(
+        "stack effect mismatch: the branches of this conditional leave {then_depth} and \
+         {else_depth} elements on the stack"
+    )]
+    #[diagnostic(help("both branches of a conditional must leave the same number of elements"))]
+    UnbalancedBranches {
+        #[label]
+        span: SourceSpan,
+        then_depth: usize,
+        else_depth: usize,
+    },
+    #[error(
+        "stack effect mismatch: the body of this loop leaves {found} elements on the stack, \
+         instead of {expected}"
+    )]
+    #[diagnostic(help(
+        "the body of a loop must leave the stack as it found it, plus the condition of the next \
+         iteration"
+    ))]
+    UnbalancedLoop {
+        #[label]
+        span: SourceSpan,
+        expected: usize,
+        found: usize,
+    },
+    #[error("invalid constant expression: division by zero")]
+    #[diagnostic()]
+    DivisionByZero {
+        #[label]
+        span: SourceSpan,
+    },
+    #[error("invalid constant expression: value is larger than expected range")]
+    #[diagnostic(help("the value of a constant must be a valid field element"))]
+    ConstantOverflow {
+        #[label]
+        span: SourceSpan,
+    },
+    #[error("invalid repeat count: the body must be repeated at least once")]
+    #[diagnostic()]
+    InvalidRepeatCount {
+        #[label]
+        span: SourceSpan,
+    },
     #[error("invalid immediate: value is larger than expected range")]
     #[diagnostic()]
     ImmediateOverflow {
@@ -187,11 +367,79 @@ pub enum SemanticAnalysisError {
         #[label]
         span: SourceSpan,
     },
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    InMacroExpansion(MacroExpansionError),
+}
+
+// MACRO EXPANSION ERROR
+// ================================================================================================
+
+/// A diagnostic raised by operations expanded from the body of a macro.
+///
+/// Expanded operations retain the spans of their definitions in the body of the macro, so this
+/// type extends the diagnostic with labels pointing to the invocations which expanded them.
+#[derive(Debug)]
+pub struct MacroExpansionError {
+    pub error: Box<SemanticAnalysisError>,
+    pub invocations: Vec<SourceSpan>,
+}
+
+impl Diagnostic for MacroExpansionError {
+    fn code<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        self.error.code()
+    }
+    fn severity(&self) -> Option<Severity> {
+        self.error.severity()
+    }
+    fn help<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        self.error.help()
+    }
+    fn url<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        self.error.url()
+    }
+    fn source_code(&self) -> Option<&dyn SourceCode> {
+        self.error.source_code()
+    }
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        let invocations = self.invocations.iter().map(|span| {
+            LabeledSpan::new_with_span(Some("expanded from this invocation".into()), *span)
+        });
+        Some(Box::new(self.error.labels().into_iter().flatten().chain(invocations)))
+    }
+    fn related<'a>(&'a self) -> Option<Box<dyn Iterator<Item = &'a dyn Diagnostic> + 'a>> {
+        self.error.related()
+    }
+    fn diagnostic_source(&self) -> Option<&dyn Diagnostic> {
+        self.error.diagnostic_source()
+    }
+}
+
+impl fmt::Display for MacroExpansionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.error, f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for MacroExpansionError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        std::error::Error::source(&*self.error)
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl miette::StdError for MacroExpansionError {
+    fn source(&self) -> Option<&(dyn miette::StdError + 'static)> {
+        miette::StdError::source(&*self.error)
+    }
 }
 
 /// Represents a system limit that was exceeded
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum LimitKind {
+    /// The total number of top-level forms in a module
+    Forms,
     /// The total number of procedures in a module
     Procedures,
     /// The total number of procedure locals
@@ -204,16 +452,26 @@ pub enum LimitKind {
     CalledImports,
     /// The total number of instructions in a procedure.
     Instructions,
+    /// The total number of steps taken to evaluate the constants of a module
+    ConstantEvalSteps,
+    /// The depth to which control flow blocks are nested, once macros are expanded
+    NestingDepth,
+    /// The total number of operations produced by expanding the macros of a module
+    MacroExpansion,
 }
 
 impl fmt::Display for LimitKind {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
+            Self::Forms => f.write_str("too many items in module"),
             Self::Procedures => f.write_str("too many procedures in module"),
             Self::Locals => f.write_str("too many procedure locals"),
             Self::Imports => f.write_str("too many imported procedures"),
             Self::CalledImports => f.write_str("too many calls to imported procedures"),
             Self::Instructions => f.write_str("too many instructions in block"),
+            Self::ConstantEvalSteps => f.write_str("constant expressions are too complex"),
+            Self::NestingDepth => f.write_str("control flow blocks are nested too deeply"),
+            Self::MacroExpansion => f.write_str("macro expansion produces too many operations"),
         }
     }
 }
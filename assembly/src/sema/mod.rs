@@ -4,26 +4,39 @@ mod passes;
 
 use alloc::{
     boxed::Box,
-    collections::{BTreeSet, VecDeque},
+    collections::{BTreeMap, BTreeSet, VecDeque},
     sync::Arc,
     vec::Vec,
 };
 
-use self::passes::{ConstEvalVisitor, VerifyInvokeTargets};
+pub(crate) use self::passes::{
+    DEPRECATED_ATTRIBUTE, STACK_EFFECT_ATTRIBUTE, StackEffect, StackSummary, VerifyStackEffect,
+    deprecation_reason,
+};
 pub use self::{
     context::AnalysisContext,
-    errors::{SemanticAnalysisError, SyntaxError},
+    errors::{MacroExpansionError, SemanticAnalysisError, SyntaxError},
+};
+use self::{
+    errors::LimitKind,
+    passes::{
+        CfgResolver, ConstEvalVisitor, DataLowering, MacroExpander, Monomorphizer,
+        VerifyAttributes, VerifyInvokeTargets,
+    },
 };
-use crate::{LibraryPath, Spanned, ast::*, diagnostics::SourceFile};
+use crate::{LibraryPath, ResourceLimits, Spanned, ast::*, diagnostics::SourceFile};
 
 /// Constructs and validates a [Module], given the forms constituting the module body.
 ///
 /// As part of this process, the following is also done:
 ///
+/// * Items annotated with `@cfg` are removed if their predicate doesn't hold for `features`
+/// * Macro invocations are expanded
 /// * Documentation comments are attached to items they decorate
 /// * Import table is constructed
 /// * Symbol resolution is performed:
 ///   * Constants referenced by name are replaced with the value of that constant.
+///   * Data constants referenced by name are lowered to primitive instructions.
 ///   * Calls to imported procedures are resolved concretely
 /// * Semantic analysis is performed on the module to validate it
 pub fn analyze(
@@ -32,9 +45,24 @@ pub fn analyze(
     path: LibraryPath,
     forms: Vec<Form>,
     warnings_as_errors: bool,
+    limits: &ResourceLimits,
+    features: &BTreeSet<Arc<str>>,
 ) -> Result<Box<Module>, SyntaxError> {
     let mut analyzer = AnalysisContext::new(source.clone());
     analyzer.set_warnings_as_errors(warnings_as_errors);
+    analyzer.set_max_constant_eval_steps(limits.max_constant_eval_steps);
+
+    check_limits(&forms, limits, &mut analyzer);
+    analyzer.has_failed()?;
+
+    // Resolve conditional compilation first, so that the items which are not enabled are never
+    // analyzed, and macros are only expanded where they are enabled
+    let mut forms = resolve_cfg(forms, features, &mut analyzer);
+    analyzer.has_failed()?;
+
+    // Expand macros next, so that the rest of the analysis only sees their expansions
+    expand_macros(&mut forms, limits, &mut analyzer);
+    analyzer.has_failed()?;
 
     let mut module = Box::new(Module::new(kind, path).with_span(source.source_span()));
 
@@ -54,6 +82,9 @@ pub fn analyze(
             Form::Constant(constant) => {
                 analyzer.define_constant(constant.with_docs(docs.take()))?;
             },
+            Form::DataConstant(constant) => {
+                analyzer.define_data_constant(constant.with_docs(docs.take()))?;
+            },
             Form::Import(import) => {
                 if let Some(docs) = docs.take() {
                     analyzer.error(SemanticAnalysisError::ImportDocstring { span: docs.span() });
@@ -96,6 +127,10 @@ pub fn analyze(
                 docs.take();
                 analyzer.error(SemanticAnalysisError::UnexpectedEntrypoint { span: body.span() });
             },
+            Form::Macro(_) => {
+                // Macros are not part of the module once expanded, and neither are their docs
+                docs.take();
+            },
         }
     }
 
@@ -109,6 +144,15 @@ pub fn analyze(
 
     analyzer.has_failed()?;
 
+    // Retain the evaluated constants, so that tooling can still make use of them once all
+    // references have been replaced by their values
+    module.constants = analyzer.constants().cloned().collect();
+    module.data_constants = analyzer.data_constants().cloned().collect();
+
+    // Instantiate generic procedures, so that the procedure checks only see concrete procedures
+    Monomorphizer::new(&mut analyzer).monomorphize(&mut module);
+    analyzer.has_failed()?;
+
     // Run procedure checks
     visit_procedures(&mut module, &mut analyzer)?;
 
@@ -122,6 +166,80 @@ pub fn analyze(
     analyzer.into_result().map(move |_| module)
 }
 
+/// Check that the number of forms and procedures in a module do not exceed `limits`, before any
+/// further work is done to analyze them.
+fn check_limits(forms: &[Form], limits: &ResourceLimits, analyzer: &mut AnalysisContext) {
+    if let Some(form) = forms.get(limits.max_forms) {
+        analyzer.error(SemanticAnalysisError::LimitExceeded {
+            span: form.span(),
+            kind: LimitKind::Forms,
+        });
+    }
+    if let Some(form) = forms
+        .iter()
+        .filter(|form| matches!(form, Form::Procedure(_) | Form::Begin(_)))
+        .nth(limits.max_procedures)
+    {
+        analyzer.error(SemanticAnalysisError::LimitExceeded {
+            span: form.span(),
+            kind: LimitKind::Procedures,
+        });
+    }
+}
+
+/// Remove the procedures and operations of `forms` which are not enabled by `features`, along with
+/// the documentation of the removed procedures.
+fn resolve_cfg(
+    forms: Vec<Form>,
+    features: &BTreeSet<Arc<str>>,
+    analyzer: &mut AnalysisContext,
+) -> Vec<Form> {
+    let mut resolver = CfgResolver::new(analyzer, features);
+    let mut resolved = Vec::with_capacity(forms.len());
+    for mut form in forms {
+        let enabled = match &mut form {
+            Form::Procedure(Export::Procedure(procedure)) => resolver.resolve_procedure(procedure),
+            Form::Begin(body) => {
+                resolver.resolve_block(body);
+                true
+            },
+            Form::Macro(definition) => {
+                resolver.resolve_block(definition.body_mut());
+                true
+            },
+            _ => true,
+        };
+        if enabled {
+            resolved.push(form);
+        } else if let Some(Form::Doc(_)) = resolved.last() {
+            resolved.pop();
+        }
+    }
+    resolved
+}
+
+/// Expand the invocations of the macros defined in `forms`, in the bodies of its procedures and
+/// entrypoint.
+fn expand_macros(forms: &mut [Form], limits: &ResourceLimits, analyzer: &mut AnalysisContext) {
+    let mut expander = MacroExpander::new(analyzer, limits.max_nesting_depth);
+    for form in forms.iter() {
+        if let Form::Macro(definition) = form {
+            expander.define(definition.clone());
+        }
+    }
+
+    for form in forms.iter_mut() {
+        match form {
+            Form::Begin(body) => expander.expand_block(body),
+            Form::Procedure(Export::Procedure(procedure)) => {
+                expander.expand_block(procedure.body_mut())
+            },
+            _ => (),
+        }
+    }
+    expander.check_unused();
+}
+
 /// Visit all of the procedures of the current analysis context,
 /// and apply various transformation and analysis passes.
 ///
@@ -134,6 +252,20 @@ fn visit_procedures(
 ) -> Result<(), SyntaxError> {
     let is_kernel = module.is_kernel();
     let locals = BTreeSet::from_iter(module.procedures().map(|p| p.name().clone()));
+    let deprecated = BTreeMap::from_iter(module.procedures().filter_map(|p| {
+        match p {
+            Export::Procedure(procedure) => procedure
+                .get_attribute("deprecated")
+                .map(|attr| (procedure.name().clone(), attr.clone())),
+            Export::Alias(_) => None,
+        }
+    }));
+    let stack_effects = BTreeMap::from_iter(module.procedures().filter_map(|p| match p {
+        Export::Procedure(procedure) => {
+            StackEffect::of(procedure).map(|effect| (procedure.name().clone(), effect))
+        },
+        Export::Alias(_) => None,
+    }));
     let mut procedures = VecDeque::from(core::mem::take(&mut module.procedures));
     while let Some(procedure) = procedures.pop_front() {
         match procedure {
@@ -143,12 +275,23 @@ fn visit_procedures(
                     procedure.set_visibility(Visibility::Syscall);
                 }
 
-                // Evaluate all named immediates to their concrete values
+                // Lower references to data constants, before evaluating the remaining named
+                // immediates to their concrete values
+                DataLowering::new(analyzer).lower_block(procedure.body_mut());
                 {
                     let mut visitor = ConstEvalVisitor::new(analyzer);
                     let _ = visitor.visit_mut_procedure(&mut procedure);
                 }
 
+                // Validate the attributes known to the assembler, and enforce them
+                {
+                    let mut visitor = VerifyAttributes::new(analyzer, &deprecated);
+                    let _ = visitor.visit_procedure(&procedure);
+                }
+
+                // Verify that the procedure respects its declared stack effect, if any
+                VerifyStackEffect::new(analyzer, &stack_effects).verify_procedure(&procedure);
+
                 // Next, verify invoke targets:
                 //
                 // * Kernel procedures cannot use `syscall` or `call`
@@ -46,6 +46,16 @@ impl ConstEvalVisitor<'_> {
 }
 
 impl VisitMut for ConstEvalVisitor<'_> {
+    fn visit_mut_op(&mut self, op: &mut Op) -> ControlFlow<()> {
+        if let Op::Repeat { count, .. } = op {
+            self.eval_const(count)?;
+            if *count == 0 {
+                self.analyzer
+                    .error(SemanticAnalysisError::InvalidRepeatCount { span: count.span() });
+            }
+        }
+        visit::visit_mut_op(self, op)
+    }
     fn visit_mut_immediate_u8(&mut self, imm: &mut Immediate<u8>) -> ControlFlow<()> {
         self.eval_const(imm)
     }
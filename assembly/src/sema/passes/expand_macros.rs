@@ -0,0 +1,281 @@
+use alloc::{collections::BTreeMap, vec::Vec};
+use core::ops::ControlFlow;
+
+use crate::{
+    Felt, SourceSpan, Span, Spanned,
+    ast::*,
+    sema::{AnalysisContext, SemanticAnalysisError, errors::LimitKind},
+};
+
+/// The maximum number of operations which can be produced by expanding the macros of a module.
+///
+/// This bounds the work done to expand macros, which would otherwise grow exponentially with the
+/// nesting of invocations.
+const MAX_EXPANDED_OPS: usize = 1 << 20;
+
+/// Expands the invocations of the macros defined in a module.
+///
+/// Each invocation is replaced by the body of the invoked macro, in which references to the
+/// parameters of the macro are substituted with the arguments of the invocation, before the
+/// invocations within the body are expanded in turn. As a result, expansion is hygienic: the body
+/// of a macro can only refer to the names in scope at the invocation through its arguments, and
+/// the parameters of a macro are only in scope within its own body.
+///
+/// The expanded operations retain the spans of their definitions, so that any error they raise
+/// points into the body of the macro, and is labeled with the invocation which expanded it, while
+/// substituted arguments retain the spans of the invocation.
+pub struct MacroExpander<'analyzer> {
+    analyzer: &'analyzer mut AnalysisContext,
+    macros: BTreeMap<Ident, Macro>,
+    /// The names of the macros which have been invoked at least once.
+    used: BTreeMap<Ident, bool>,
+    /// The names of the macros currently being expanded, used to detect recursive invocations.
+    expanding: Vec<Ident>,
+    max_nesting_depth: usize,
+    num_expanded_ops: usize,
+}
+
+impl<'analyzer> MacroExpander<'analyzer> {
+    pub fn new(analyzer: &'analyzer mut AnalysisContext, max_nesting_depth: usize) -> Self {
+        Self {
+            analyzer,
+            macros: BTreeMap::new(),
+            used: BTreeMap::new(),
+            expanding: Vec::new(),
+            max_nesting_depth,
+            num_expanded_ops: 0,
+        }
+    }
+
+    /// Defines a macro which can be invoked by the blocks expanded afterwards.
+    pub fn define(&mut self, definition: Macro) {
+        let mut params = BTreeMap::<&Ident, &Ident>::new();
+        for param in definition.params() {
+            if let Some(prev) = params.insert(param, param) {
+                self.analyzer.error(SemanticAnalysisError::SymbolConflict {
+                    span: param.span(),
+                    prev_span: prev.span(),
+                });
+            }
+        }
+
+        if let Some(prev) = self.macros.get(definition.name()) {
+            self.analyzer.error(SemanticAnalysisError::SymbolConflict {
+                span: definition.name().span(),
+                prev_span: prev.name().span(),
+            });
+            return;
+        }
+        self.used.insert(definition.name().clone(), false);
+        self.macros.insert(definition.name().clone(), definition);
+    }
+
+    /// Expands all of the macro invocations in `block`, including those of nested blocks.
+    pub fn expand_block(&mut self, block: &mut Block) {
+        self.expand_block_at_depth(block, 0);
+    }
+
+    /// Emits a warning for each macro which was defined, but never invoked.
+    pub fn check_unused(&mut self) {
+        for (name, used) in self.used.iter() {
+            if !used {
+                self.analyzer.error(SemanticAnalysisError::UnusedMacro { span: name.span() });
+            }
+        }
+    }
+
+    fn expand_block_at_depth(&mut self, block: &mut Block, depth: usize) {
+        let ops = block.take_ops();
+        let mut expanded = Vec::with_capacity(ops.len());
+        for op in ops {
+            if self.expand_op(op, depth, &mut expanded).is_break() {
+                break;
+            }
+        }
+
+        if expanded.len() > u16::MAX as usize {
+            self.analyzer.error(SemanticAnalysisError::LimitExceeded {
+                span: block.span(),
+                kind: LimitKind::Instructions,
+            });
+            expanded.truncate(u16::MAX as usize);
+        }
+        for op in expanded {
+            block.push(op);
+        }
+    }
+
+    /// Appends the expansion of `op` to `expanded`, or breaks if expansion must stop because a
+    /// limit was exceeded.
+    fn expand_op(&mut self, mut op: Op, depth: usize, expanded: &mut Vec<Op>) -> ControlFlow<()> {
+        let span = op.span();
+        if !self.expanding.is_empty() {
+            self.num_expanded_ops += 1;
+            if self.num_expanded_ops > MAX_EXPANDED_OPS {
+                self.analyzer.error(SemanticAnalysisError::LimitExceeded {
+                    span,
+                    kind: LimitKind::MacroExpansion,
+                });
+                return ControlFlow::Break(());
+            }
+        }
+
+        match &mut op {
+            Op::Expand(invocation) => return self.expand_invocation(invocation, depth, expanded),
+            Op::If { then_blk, else_blk, .. } => {
+                self.check_nesting_depth(span, depth + 1)?;
+                self.expand_block_at_depth(then_blk, depth + 1);
+                self.expand_block_at_depth(else_blk, depth + 1);
+            },
+            Op::While { body, .. } | Op::Repeat { body, .. } => {
+                self.check_nesting_depth(span, depth + 1)?;
+                self.expand_block_at_depth(body, depth + 1);
+            },
+            Op::Inst(_) => (),
+            Op::Cfg { .. } => {
+                unreachable!("conditional compilation is resolved before macros are expanded")
+            },
+        }
+        expanded.push(op);
+        ControlFlow::Continue(())
+    }
+
+    fn expand_invocation(
+        &mut self,
+        invocation: &MacroInvocation,
+        depth: usize,
+        expanded: &mut Vec<Op>,
+    ) -> ControlFlow<()> {
+        let span = invocation.span();
+        let Some(definition) = self.macros.get(invocation.name()) else {
+            self.analyzer.error(SemanticAnalysisError::UndefinedMacro { span });
+            return ControlFlow::Continue(());
+        };
+        self.used.insert(invocation.name().clone(), true);
+        if definition.params().len() != invocation.args().len() {
+            self.analyzer.error(SemanticAnalysisError::MacroArityMismatch {
+                span,
+                definition: definition.span(),
+                expected: definition.params().len(),
+                found: invocation.args().len(),
+            });
+            return ControlFlow::Continue(());
+        }
+        if self.expanding.contains(invocation.name()) {
+            self.analyzer.error(SemanticAnalysisError::RecursiveMacro {
+                span,
+                definition: definition.span(),
+            });
+            return ControlFlow::Continue(());
+        }
+
+        // Substitute the arguments before expanding the invocations in the body, so that they
+        // can only be referred to by the body of the invoked macro
+        let mut body = definition.body().clone();
+        self.analyzer.enter_macro_expansion(body.span(), span);
+        let mut substitution =
+            SubstituteParams::new(self.analyzer, definition.params(), invocation.args());
+        let _ = substitution.visit_mut_block(&mut body);
+
+        self.expanding.push(invocation.name().clone());
+        let result = body
+            .take_ops()
+            .into_iter()
+            .try_for_each(|op| self.expand_op(op, depth, expanded));
+        self.expanding.pop();
+        self.analyzer.exit_macro_expansion();
+        result
+    }
+
+    fn check_nesting_depth(&mut self, span: SourceSpan, depth: usize) -> ControlFlow<()> {
+        if depth > self.max_nesting_depth {
+            self.analyzer.error(SemanticAnalysisError::LimitExceeded {
+                span,
+                kind: LimitKind::NestingDepth,
+            });
+            return ControlFlow::Break(());
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+/// This visitor substitutes the references to the parameters of a macro, or of a generic
+/// procedure, in its body with the arguments of an invocation.
+pub(super) struct SubstituteParams<'a> {
+    analyzer: &'a mut AnalysisContext,
+    params: &'a [Ident],
+    args: &'a [ImmFelt],
+}
+
+impl<'a> SubstituteParams<'a> {
+    pub(super) fn new(
+        analyzer: &'a mut AnalysisContext,
+        params: &'a [Ident],
+        args: &'a [ImmFelt],
+    ) -> Self {
+        Self { analyzer, params, args }
+    }
+}
+
+impl SubstituteParams<'_> {
+    fn substitute<T>(&mut self, imm: &mut Immediate<T>) -> ControlFlow<()>
+    where
+        T: TryFrom<u64>,
+    {
+        let Immediate::Constant(name) = imm else {
+            return ControlFlow::Continue(());
+        };
+        let Some(index) = self.params.iter().position(|param| param == name) else {
+            return ControlFlow::Continue(());
+        };
+        match &self.args[index] {
+            Immediate::Constant(name) => *imm = Immediate::Constant(name.clone()),
+            Immediate::Value(value) => match T::try_from(value.as_int()) {
+                Ok(converted) => *imm = Immediate::Value(Span::new(value.span(), converted)),
+                Err(_) => {
+                    self.analyzer
+                        .error(SemanticAnalysisError::ImmediateOverflow { span: value.span() });
+                },
+            },
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+impl VisitMut for SubstituteParams<'_> {
+    fn visit_mut_inst(&mut self, inst: &mut Span<Instruction>) -> ControlFlow<()> {
+        if let Instruction::MemInit(name) = &mut **inst {
+            if let Some(index) = self.params.iter().position(|param| param == name) {
+                match &self.args[index] {
+                    Immediate::Constant(arg) => *name = arg.clone(),
+                    Immediate::Value(value) => {
+                        self.analyzer.error(SemanticAnalysisError::ExpectedDataConstant {
+                            span: value.span(),
+                        });
+                    },
+                }
+            }
+        }
+        visit::visit_mut_inst(self, inst)
+    }
+    fn visit_mut_immediate_u8(&mut self, imm: &mut Immediate<u8>) -> ControlFlow<()> {
+        self.substitute(imm)
+    }
+    fn visit_mut_immediate_u16(&mut self, imm: &mut Immediate<u16>) -> ControlFlow<()> {
+        self.substitute(imm)
+    }
+    fn visit_mut_immediate_u32(&mut self, imm: &mut Immediate<u32>) -> ControlFlow<()> {
+        self.substitute(imm)
+    }
+    fn visit_mut_immediate_error_code(&mut self, imm: &mut Immediate<u32>) -> ControlFlow<()> {
+        self.substitute(imm)
+    }
+    fn visit_mut_immediate_felt(&mut self, imm: &mut Immediate<Felt>) -> ControlFlow<()> {
+        if let Immediate::Constant(name) = imm {
+            if let Some(index) = self.params.iter().position(|param| param == name) {
+                *imm = self.args[index].clone();
+            }
+        }
+        ControlFlow::Continue(())
+    }
+}
@@ -0,0 +1,125 @@
+use alloc::vec::Vec;
+
+use crate::{Felt, SourceSpan, Span, ast::*, sema::AnalysisContext};
+
+/// The maximum number of values which can be pushed by a single `push` instruction.
+const MAX_PUSH_VALUES: usize = 16;
+
+/// Lowers the references to data constants in a block to primitive instructions.
+///
+/// * `push.DATA` is lowered to `push` instructions which push the elements of `DATA` in order, so
+///   that its last element ends up on top of the stack.
+/// * `mem_init.DATA` is lowered to instructions which write the elements of `DATA` to consecutive
+///   memory addresses, starting at the address on top of the stack, which is consumed. Elements
+///   are written a word at a time, so that address must be word-aligned if `DATA` has at least
+///   four elements.
+///
+/// The lowered instructions retain the span of the instruction they are lowered from, so that any
+/// error they raise points to that instruction.
+///
+/// Data constants are deliberately not lowered to advice map entries loaded with `adv.push_mapval`
+/// and `adv_pipe`, even though that would take fewer cycles for large constants: the advice map is
+/// not committed to by the MAST root of a program, so the loaded elements would need to be hashed
+/// and checked against their key in the VM, as `std::mem::pipe_preimage_to_memory` does. Inline
+/// `push` instructions are part of the MAST root instead, which keeps the data trusted without
+/// such a check, and without the assembler depending on procedures of the standard library.
+pub struct DataLowering<'analyzer> {
+    analyzer: &'analyzer mut AnalysisContext,
+}
+
+impl<'analyzer> DataLowering<'analyzer> {
+    pub fn new(analyzer: &'analyzer mut AnalysisContext) -> Self {
+        Self { analyzer }
+    }
+
+    /// Lowers all of the references to data constants in `block`, including those of nested
+    /// blocks.
+    pub fn lower_block(&mut self, block: &mut Block) {
+        for mut op in block.take_ops() {
+            match &mut op {
+                Op::Inst(inst) => {
+                    if let Some(lowered) = self.lower_inst(inst) {
+                        let span = inst.span();
+                        for inst in lowered {
+                            block.push(Op::Inst(Span::new(span, inst)));
+                        }
+                        continue;
+                    }
+                },
+                Op::If { then_blk, else_blk, .. } => {
+                    self.lower_block(then_blk);
+                    self.lower_block(else_blk);
+                },
+                Op::While { body, .. } | Op::Repeat { body, .. } => self.lower_block(body),
+                Op::Expand(_) => {
+                    unreachable!("macros are expanded before data constants are lowered")
+                },
+                Op::Cfg { .. } => {
+                    unreachable!(
+                        "conditional compilation is resolved before data constants are lowered"
+                    )
+                },
+            }
+            block.push(op);
+        }
+    }
+
+    /// Returns the instructions `inst` is lowered to, or `None` if it doesn't refer to a data
+    /// constant.
+    fn lower_inst(&mut self, inst: &Span<Instruction>) -> Option<Vec<Instruction>> {
+        match &**inst {
+            Instruction::Push(Immediate::Constant(name)) => {
+                // References to other constants are evaluated along with all other immediates
+                let elements = self.analyzer.get_data_constant(name).ok()?;
+                Some(lower_push(&elements))
+            },
+            Instruction::MemInit(name) => match self.analyzer.get_data_constant(name) {
+                Ok(elements) => Some(lower_mem_init(&elements, inst.span())),
+                Err(error) => {
+                    self.analyzer.error(error);
+                    None
+                },
+            },
+            _ => None,
+        }
+    }
+}
+
+fn lower_push(elements: &[Felt]) -> Vec<Instruction> {
+    elements
+        .chunks(MAX_PUSH_VALUES)
+        .map(|values| match values {
+            [value] => Instruction::PushFelt(*value),
+            values => Instruction::PushFeltList(values.to_vec()),
+        })
+        .collect()
+}
+
+fn lower_mem_init(elements: &[Felt], span: SourceSpan) -> Vec<Instruction> {
+    let add_offset = |lowered: &mut Vec<Instruction>, offset: usize| {
+        if offset > 0 {
+            let offset = Felt::new(offset as u64);
+            lowered.push(Instruction::AddImm(Immediate::Value(Span::new(span, offset))));
+        }
+    };
+
+    // The address is kept at the bottom of the values being written, and copied for each write
+    let mut lowered = Vec::new();
+    let mut words = elements.chunks_exact(4);
+    for (index, word) in words.by_ref().enumerate() {
+        lowered.push(Instruction::PushFeltList(word.to_vec()));
+        lowered.push(Instruction::Dup4);
+        add_offset(&mut lowered, index * 4);
+        lowered.push(Instruction::MemStoreW);
+        lowered.push(Instruction::DropW);
+    }
+    let offset = elements.len() - words.remainder().len();
+    for (index, element) in words.remainder().iter().enumerate() {
+        lowered.push(Instruction::PushFelt(*element));
+        lowered.push(Instruction::Dup1);
+        add_offset(&mut lowered, offset + index);
+        lowered.push(Instruction::MemStore);
+    }
+    lowered.push(Instruction::Drop);
+    lowered
+}
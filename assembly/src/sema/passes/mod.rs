@@ -1,4 +1,19 @@
 mod const_eval;
+mod expand_macros;
+mod lower_data;
+mod monomorphize;
+mod resolve_cfg;
+mod verify_attributes;
 mod verify_invoke;
+mod verify_stack_effect;
 
-pub use self::{const_eval::ConstEvalVisitor, verify_invoke::VerifyInvokeTargets};
+pub use self::{
+    const_eval::ConstEvalVisitor,
+    expand_macros::MacroExpander,
+    lower_data::DataLowering,
+    monomorphize::Monomorphizer,
+    resolve_cfg::CfgResolver,
+    verify_attributes::{DEPRECATED_ATTRIBUTE, VerifyAttributes, deprecation_reason},
+    verify_invoke::VerifyInvokeTargets,
+    verify_stack_effect::{STACK_EFFECT_ATTRIBUTE, StackEffect, StackSummary, VerifyStackEffect},
+};
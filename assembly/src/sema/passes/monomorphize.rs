@@ -0,0 +1,212 @@
+use alloc::{
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    format,
+    string::String,
+    sync::Arc,
+    vec::Vec,
+};
+use core::ops::ControlFlow;
+
+use super::expand_macros::SubstituteParams;
+use crate::{
+    Felt, SourceSpan, Span, Spanned,
+    ast::*,
+    sema::{AnalysisContext, SemanticAnalysisError},
+};
+
+/// Instantiates the generic procedures of a module for each of the arguments they are invoked
+/// with.
+///
+/// The generic procedures are removed from the module, and each distinct instance, e.g.
+/// `exec.hash_n<4>`, is replaced with a private procedure named after it, e.g. `"hash_n<4>"`, in
+/// which references to the parameters of the generic procedure are substituted with the values of
+/// the arguments. Invocations of an instance are then rewritten to invoke that procedure. The
+/// bodies of the instances are visited in turn, so generic procedures can invoke other instances,
+/// including with their own parameters as arguments.
+///
+/// The instantiated procedures retain the span of their generic definition, so that any error
+/// they raise points into the body of the generic procedure. A warning is raised for each generic
+/// procedure which is never instantiated.
+pub struct Monomorphizer<'a> {
+    analyzer: &'a mut AnalysisContext,
+    generics: BTreeMap<ProcedureName, Procedure>,
+    /// The names of the concrete procedures of the module, and the spans of their definitions
+    concrete: BTreeMap<ProcedureName, SourceSpan>,
+    /// The names of the generic procedures which are invoked at least once
+    instantiated: BTreeSet<ProcedureName>,
+    /// The names of the instances which have been created so far
+    instances: BTreeSet<ProcedureName>,
+    /// The instances which have been created, but whose body has not been visited yet
+    pending: VecDeque<Procedure>,
+}
+
+impl<'a> Monomorphizer<'a> {
+    pub fn new(analyzer: &'a mut AnalysisContext) -> Self {
+        Self {
+            analyzer,
+            generics: BTreeMap::new(),
+            concrete: BTreeMap::new(),
+            instantiated: BTreeSet::new(),
+            instances: BTreeSet::new(),
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Replaces the generic procedures of `module` with the instances invoked by its procedures.
+    pub fn monomorphize(&mut self, module: &mut Module) {
+        let mut procedures = Vec::with_capacity(module.procedures.len());
+        for export in core::mem::take(&mut module.procedures) {
+            match export {
+                Export::Procedure(procedure) if procedure.is_generic() => self.define(procedure),
+                export => {
+                    self.concrete.insert(export.name().clone(), export.span());
+                    procedures.push(export);
+                },
+            }
+        }
+
+        for export in procedures.iter_mut() {
+            if let Export::Procedure(procedure) = export {
+                let _ = self.visit_mut_procedure(procedure);
+            }
+        }
+        while let Some(mut instance) = self.pending.pop_front() {
+            let _ = self.visit_mut_procedure(&mut instance);
+            procedures.push(Export::Procedure(instance));
+        }
+
+        for (name, generic) in self.generics.iter() {
+            if !self.instantiated.contains(name) {
+                self.analyzer.error(SemanticAnalysisError::UnusedGenericProcedure {
+                    span: generic.name().span(),
+                });
+            }
+        }
+
+        module.procedures = procedures;
+    }
+
+    fn define(&mut self, procedure: Procedure) {
+        if procedure.visibility().is_exported() {
+            self.analyzer
+                .error(SemanticAnalysisError::GenericProcedureExport { span: procedure.span() });
+        }
+        let mut params = BTreeMap::<&Ident, &Ident>::new();
+        for param in procedure.params() {
+            if let Some(prev) = params.insert(param, param) {
+                self.analyzer.error(SemanticAnalysisError::SymbolConflict {
+                    span: param.span(),
+                    prev_span: prev.span(),
+                });
+            }
+        }
+        self.generics.insert(procedure.name().clone(), procedure);
+    }
+
+    /// Returns the name of the procedure instantiated for `args`, instantiating it if needed, or
+    /// `None` if the instance is invalid.
+    fn instantiate(&mut self, name: &ProcedureName, args: &[ImmU32]) -> Option<ProcedureName> {
+        let span = name.span();
+        let Some(generic) = self.generics.get(name) else {
+            if let Some(&definition) = self.concrete.get(name) {
+                self.analyzer.error(SemanticAnalysisError::GenericArityMismatch {
+                    span,
+                    definition,
+                    expected: 0,
+                    found: args.len(),
+                });
+            } else {
+                self.analyzer.error(SemanticAnalysisError::SymbolUndefined { span });
+            }
+            return None;
+        };
+        self.instantiated.insert(generic.name().clone());
+        if generic.params().len() != args.len() {
+            self.analyzer.error(SemanticAnalysisError::GenericArityMismatch {
+                span,
+                definition: generic.name().span(),
+                expected: generic.params().len(),
+                found: args.len(),
+            });
+            return None;
+        }
+
+        let mut values = Vec::with_capacity(args.len());
+        for arg in args {
+            match arg {
+                Immediate::Value(value) => values.push(*value),
+                Immediate::Constant(constant) => match self.analyzer.get_constant(constant) {
+                    Ok(value) => match u32::try_from(value.as_int()) {
+                        Ok(value) => values.push(Span::new(constant.span(), value)),
+                        Err(_) => self.analyzer.error(SemanticAnalysisError::ImmediateOverflow {
+                            span: constant.span(),
+                        }),
+                    },
+                    Err(error) => self.analyzer.error(error),
+                },
+            }
+        }
+        if values.len() != args.len() {
+            return None;
+        }
+
+        let instance_name = values.iter().map(|value| format!("{value}")).collect::<Vec<_>>();
+        let instance_name = format!("{name}<{}>", instance_name.join(", "));
+        let instance_name = ProcedureName::from_raw_parts(Ident::from_raw_parts(Span::new(
+            generic.name().span(),
+            Arc::from(String::into_boxed_str(instance_name)),
+        )));
+        if self.instances.contains(&instance_name) {
+            return Some(instance_name.with_span(span));
+        }
+        if let Some(&prev_span) = self.concrete.get(&instance_name) {
+            // A procedure of the module is named after the instance
+            self.analyzer.error(SemanticAnalysisError::SymbolConflict { span, prev_span });
+            return None;
+        }
+
+        let args = values
+            .iter()
+            .map(|value| Immediate::Value(value.map(Felt::from)))
+            .collect::<Vec<_>>();
+        let mut body = generic.body().clone();
+        let _ = SubstituteParams::new(self.analyzer, generic.params(), &args)
+            .visit_mut_block(&mut body);
+        let instance = Procedure::new(
+            generic.span(),
+            Visibility::Private,
+            instance_name.clone(),
+            generic.num_locals(),
+            body,
+        )
+        .with_attributes(generic.attributes().iter().cloned());
+
+        self.instances.insert(instance_name.clone());
+        self.pending.push_back(instance);
+        Some(instance_name.with_span(span))
+    }
+}
+
+impl VisitMut for Monomorphizer<'_> {
+    fn visit_mut_invoke_target(&mut self, target: &mut InvocationTarget) -> ControlFlow<()> {
+        match target {
+            InvocationTarget::ProcedureName(name) => {
+                if let Some(generic) = self.generics.get(name) {
+                    self.analyzer.error(SemanticAnalysisError::GenericArityMismatch {
+                        span: name.span(),
+                        definition: generic.name().span(),
+                        expected: generic.params().len(),
+                        found: 0,
+                    });
+                }
+            },
+            InvocationTarget::Instance { name, args } => {
+                if let Some(instance) = self.instantiate(name, args) {
+                    *target = InvocationTarget::ProcedureName(instance);
+                }
+            },
+            _ => (),
+        }
+        ControlFlow::Continue(())
+    }
+}
@@ -0,0 +1,100 @@
+use alloc::{collections::BTreeSet, sync::Arc, vec::Vec};
+
+use crate::{
+    Span, Spanned,
+    ast::*,
+    sema::{AnalysisContext, SemanticAnalysisError},
+};
+
+/// The name of the attribute used to conditionally compile procedures and operations.
+const CFG_ATTRIBUTE: &str = "cfg";
+
+/// Resolves the conditional compilation of the items of a module, given the set of enabled
+/// features.
+///
+/// A procedure annotated with `@cfg(feature = "name")` is only defined if the feature `name` is
+/// enabled, and likewise for an annotated operation. Once resolved, the `@cfg` attributes of the
+/// remaining items are removed, and any block left empty is replaced with a `nop`.
+pub struct CfgResolver<'a> {
+    analyzer: &'a mut AnalysisContext,
+    features: &'a BTreeSet<Arc<str>>,
+}
+
+impl<'a> CfgResolver<'a> {
+    pub fn new(analyzer: &'a mut AnalysisContext, features: &'a BTreeSet<Arc<str>>) -> Self {
+        Self { analyzer, features }
+    }
+
+    /// Resolves the conditional compilation of `procedure` and of its body, returning false if
+    /// the procedure must be removed from the module.
+    pub fn resolve_procedure(&mut self, procedure: &mut Procedure) -> bool {
+        if let Some(attribute) = procedure.attributes_mut().remove(CFG_ATTRIBUTE) {
+            if !self.is_enabled(&attribute) {
+                return false;
+            }
+        }
+        self.resolve_block(procedure.body_mut());
+        true
+    }
+
+    /// Removes the operations of `block` which are not enabled, including in nested blocks.
+    pub fn resolve_block(&mut self, block: &mut Block) {
+        let mut resolved = Vec::new();
+        for op in block.take_ops() {
+            self.resolve_op(op, &mut resolved);
+        }
+
+        if resolved.is_empty() {
+            resolved.push(Op::Inst(Span::new(block.span(), Instruction::Nop)));
+        }
+        for op in resolved {
+            block.push(op);
+        }
+    }
+
+    fn resolve_op(&mut self, mut op: Op, resolved: &mut Vec<Op>) {
+        match &mut op {
+            Op::Cfg { attribute, body, .. } => {
+                if self.is_enabled(attribute) {
+                    for op in body.take_ops() {
+                        self.resolve_op(op, resolved);
+                    }
+                }
+                return;
+            },
+            Op::If { then_blk, else_blk, .. } => {
+                self.resolve_block(then_blk);
+                self.resolve_block(else_blk);
+            },
+            Op::While { body, .. } | Op::Repeat { body, .. } => self.resolve_block(body),
+            Op::Inst(_) | Op::Expand(_) => (),
+        }
+        resolved.push(op);
+    }
+
+    /// Evaluates the predicate of a `@cfg` attribute, i.e. `@cfg(feature = "name")`.
+    fn is_enabled(&mut self, attribute: &Attribute) -> bool {
+        let feature = match attribute {
+            Attribute::KeyValue(kv) if kv.name() == CFG_ATTRIBUTE && kv.items.len() == 1 => {
+                match kv.iter().next() {
+                    Some((key, MetaExpr::String(feature) | MetaExpr::Ident(feature)))
+                        if key.as_str() == "feature" =>
+                    {
+                        Some(feature)
+                    },
+                    _ => None,
+                }
+            },
+            _ => None,
+        };
+
+        match feature {
+            Some(feature) => self.features.contains(feature.as_str()),
+            None => {
+                self.analyzer
+                    .error(SemanticAnalysisError::InvalidCfgPredicate { span: attribute.span() });
+                false
+            },
+        }
+    }
+}
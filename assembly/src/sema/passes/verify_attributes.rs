@@ -0,0 +1,131 @@
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+};
+use core::ops::ControlFlow;
+
+use super::{STACK_EFFECT_ATTRIBUTE, StackEffect};
+use crate::{
+    SourceSpan, Spanned,
+    ast::*,
+    sema::{AnalysisContext, SemanticAnalysisError},
+};
+
+/// The name of the attribute used to request that a procedure be inlined at its call sites.
+const INLINE_ATTRIBUTE: &str = "inline";
+/// The name of the attribute used to mark a procedure as deprecated.
+pub const DEPRECATED_ATTRIBUTE: &str = "deprecated";
+/// The name of the attribute used to forbid the use of `syscall` in a procedure.
+const NO_SYSCALL_ATTRIBUTE: &str = "no_syscall";
+
+/// This visitor validates the attributes known to the assembler, which are attached to a
+/// procedure, and enforces them where possible within the current module:
+///
+/// * `@inline`, `@inline(always)` or `@inline(never)`
+/// * `@deprecated` or `@deprecated("reason")`, which raises a warning when the procedure is
+///   invoked from another procedure of the same module, unless that one is deprecated too.
+///   Invocations of deprecated procedures of libraries are reported by the assembler.
+/// * `@no_syscall`, which rejects any `syscall` in the body of the procedure.
+/// * `@stack_effect(inputs = N, outputs = M)`, which is verified by
+///   [VerifyStackEffect](super::VerifyStackEffect).
+///
+/// Any other attribute is left as is, and passed along as metadata of the procedure.
+pub struct VerifyAttributes<'a> {
+    analyzer: &'a mut AnalysisContext,
+    /// The `@deprecated` attributes of the procedures of the current module
+    deprecated: &'a BTreeMap<ProcedureName, Attribute>,
+    /// The span of the `@no_syscall` attribute of the current procedure, if present
+    no_syscall: Option<SourceSpan>,
+    /// Whether the current procedure is deprecated
+    is_deprecated: bool,
+}
+
+impl<'a> VerifyAttributes<'a> {
+    pub fn new(
+        analyzer: &'a mut AnalysisContext,
+        deprecated: &'a BTreeMap<ProcedureName, Attribute>,
+    ) -> Self {
+        Self {
+            analyzer,
+            deprecated,
+            no_syscall: None,
+            is_deprecated: false,
+        }
+    }
+
+    fn verify_attribute(&mut self, attribute: &Attribute) {
+        let (name, expected) = match attribute.name() {
+            INLINE_ATTRIBUTE => ("inline", "`@inline`, `@inline(always)` or `@inline(never)`"),
+            DEPRECATED_ATTRIBUTE => ("deprecated", "`@deprecated` or `@deprecated(\"reason\")`"),
+            NO_SYSCALL_ATTRIBUTE => ("no_syscall", "`@no_syscall`, which takes no arguments"),
+            STACK_EFFECT_ATTRIBUTE => (
+                "stack_effect",
+                "`@stack_effect(inputs = N, outputs = M)`, where N and M are u32",
+            ),
+            _ => return,
+        };
+        let is_valid = match attribute {
+            Attribute::Marker(_) => name != "stack_effect",
+            Attribute::List(list) => match (name, list.as_slice()) {
+                ("inline", [MetaExpr::Ident(id)]) => matches!(id.as_str(), "always" | "never"),
+                ("deprecated", [MetaExpr::String(_)]) => true,
+                _ => false,
+            },
+            Attribute::KeyValue(_) => {
+                name == "stack_effect" && StackEffect::from_attribute(attribute).is_some()
+            },
+        };
+        if !is_valid {
+            self.analyzer.error(SemanticAnalysisError::InvalidAttribute {
+                span: attribute.span(),
+                name,
+                expected,
+            });
+        }
+    }
+}
+
+impl Visit for VerifyAttributes<'_> {
+    fn visit_procedure(&mut self, procedure: &Procedure) -> ControlFlow<()> {
+        for attribute in procedure.attributes().iter() {
+            self.verify_attribute(attribute);
+        }
+        self.no_syscall = procedure.get_attribute(NO_SYSCALL_ATTRIBUTE).map(|attr| attr.span());
+        self.is_deprecated = procedure.has_attribute(DEPRECATED_ATTRIBUTE);
+        visit::visit_procedure(self, procedure)
+    }
+
+    fn visit_syscall(&mut self, target: &InvocationTarget) -> ControlFlow<()> {
+        if let Some(attribute) = self.no_syscall {
+            self.analyzer.error(SemanticAnalysisError::SyscallInNoSyscallProcedure {
+                span: target.span(),
+                attribute,
+            });
+        }
+        visit::visit_syscall(self, target)
+    }
+
+    fn visit_invoke_target(&mut self, target: &InvocationTarget) -> ControlFlow<()> {
+        if let InvocationTarget::ProcedureName(name) = target {
+            if let Some(attribute) = self.deprecated.get(name).filter(|_| !self.is_deprecated) {
+                self.analyzer.error(SemanticAnalysisError::DeprecatedProcedure {
+                    span: target.span(),
+                    attribute: attribute.span(),
+                    reason: deprecation_reason(attribute),
+                });
+            }
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+/// Returns the reason given by a `@deprecated("reason")` attribute, if any.
+pub fn deprecation_reason(attribute: &Attribute) -> Option<String> {
+    match attribute {
+        Attribute::List(list) => match list.as_slice() {
+            [MetaExpr::String(reason)] => Some(reason.as_str().to_string()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
@@ -134,6 +134,11 @@ impl VisitMut for VerifyInvokeTargets<'_> {
         let span = target.span();
         match target {
             InvocationTarget::MastRoot(_) => (),
+            InvocationTarget::Instance { .. } => {
+                unreachable!(
+                    "generic procedures are instantiated before invoke targets are verified"
+                )
+            },
             InvocationTarget::AbsoluteProcedurePath { name, path } => {
                 if self.module.path() == path && &self.current_procedure == name {
                     self.analyzer.error(SemanticAnalysisError::SelfRecursive { span });
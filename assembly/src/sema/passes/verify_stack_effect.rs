@@ -0,0 +1,486 @@
+use alloc::collections::BTreeMap;
+
+use crate::{
+    SourceSpan, Spanned,
+    ast::*,
+    parser::HexEncodedValue,
+    sema::{AnalysisContext, SemanticAnalysisError},
+};
+
+/// The name of the attribute used to declare the stack effect of a procedure.
+pub const STACK_EFFECT_ATTRIBUTE: &str = "stack_effect";
+
+/// The effect of a procedure or instruction on the operand stack: it consumes `inputs` elements
+/// from the top of the stack, and leaves `outputs` elements in their place.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct StackEffect {
+    pub inputs: usize,
+    pub outputs: usize,
+}
+
+impl StackEffect {
+    const fn new(inputs: usize, outputs: usize) -> Self {
+        Self { inputs, outputs }
+    }
+
+    /// Returns the stack effect declared by `procedure`, if it has a well-formed
+    /// `@stack_effect(inputs = N, outputs = M)` attribute.
+    pub fn of(procedure: &Procedure) -> Option<Self> {
+        procedure.get_attribute(STACK_EFFECT_ATTRIBUTE).and_then(Self::from_attribute)
+    }
+
+    /// Parses the stack effect declared by `attribute`, or returns `None` if it is malformed.
+    pub fn from_attribute(attribute: &Attribute) -> Option<Self> {
+        let Attribute::KeyValue(kv) = attribute else {
+            return None;
+        };
+        let mut inputs = None;
+        let mut outputs = None;
+        for (key, value) in kv.iter() {
+            let count = match value {
+                MetaExpr::Int(value) => match value.inner() {
+                    HexEncodedValue::U8(value) => *value as usize,
+                    HexEncodedValue::U16(value) => *value as usize,
+                    HexEncodedValue::U32(value) => *value as usize,
+                    _ => return None,
+                },
+                _ => return None,
+            };
+            match key.as_str() {
+                "inputs" => inputs = Some(count),
+                "outputs" => outputs = Some(count),
+                _ => return None,
+            }
+        }
+        Some(Self::new(inputs?, outputs?))
+    }
+
+    /// Returns the effect of `inst` on the stack, or `None` if it cannot be determined from the
+    /// instruction alone, i.e. for invocations of other procedures.
+    pub fn of_instruction(inst: &Instruction) -> Option<Self> {
+        use Instruction::*;
+
+        let (inputs, outputs) = match inst {
+            Nop | Breakpoint | Debug(_) | Emit(_) | Trace(_) | SysEvent(_) => (0, 0),
+
+            // ----- field operations ----------------------------------------------------------
+            Assert | AssertWithError(_) | Assertz | AssertzWithError(_) => (1, 0),
+            AssertEq | AssertEqWithError(_) => (2, 0),
+            AssertEqw | AssertEqwWithError(_) => (8, 0),
+            Add | Sub | Mul | Div | Exp | ExpBitLength(_) | And | Or | Xor | Eq | Neq | Lt
+            | Lte | Gt | Gte => (2, 1),
+            AddImm(_) | SubImm(_) | MulImm(_) | DivImm(_) | ExpImm(_) | EqImm(_) | NeqImm(_)
+            | Neg | ILog2 | Inv | Incr | Pow2 | Not | IsOdd => (1, 1),
+            Eqw => (8, 9),
+
+            // ----- ext2 operations -----------------------------------------------------------
+            Ext2Add | Ext2Sub | Ext2Mul | Ext2Div => (4, 2),
+            Ext2Neg | Ext2Inv => (2, 2),
+
+            // ----- u32 operations ------------------------------------------------------------
+            U32Test => (1, 2),
+            U32TestW => (4, 5),
+            U32Assert | U32AssertWithError(_) | U32Cast => (1, 1),
+            U32Assert2 | U32Assert2WithError(_) => (2, 2),
+            U32AssertW | U32AssertWWithError(_) => (4, 4),
+            U32Split => (1, 2),
+            U32WrappingAdd | U32WrappingSub | U32WrappingMul | U32Div | U32Mod | U32And | U32Or
+            | U32Xor | U32Shr | U32Shl | U32Rotr | U32Rotl | U32Lt | U32Lte | U32Gt | U32Gte
+            | U32Min | U32Max => (2, 1),
+            U32WrappingAddImm(_) | U32WrappingSubImm(_) | U32WrappingMulImm(_) | U32DivImm(_)
+            | U32ModImm(_) | U32ShrImm(_) | U32ShlImm(_) | U32RotrImm(_) | U32RotlImm(_)
+            | U32Not | U32Popcnt | U32Ctz | U32Clz | U32Clo | U32Cto => (1, 1),
+            U32OverflowingAdd | U32OverflowingSub | U32OverflowingMul | U32DivMod => (2, 2),
+            U32OverflowingAddImm(_)
+            | U32OverflowingSubImm(_)
+            | U32OverflowingMulImm(_)
+            | U32DivModImm(_) => (1, 2),
+            U32OverflowingAdd3 | U32OverflowingMadd => (3, 2),
+            U32WrappingAdd3 | U32WrappingMadd => (3, 1),
+
+            // ----- u64 operations ------------------------------------------------------------
+            U64OverflowingAdd | U64OverflowingSub => (4, 3),
+
+            // ----- i32 operations ------------------------------------------------------------
+            I32Neg | I32Abs => (1, 1),
+            I32Div | I32Mod | I32Lt | I32Lte | I32Gt | I32Gte | I32Min | I32Max | I32Shr => (2, 1),
+            I32DivMod => (2, 2),
+
+            // ----- stack manipulation --------------------------------------------------------
+            Drop => (1, 0),
+            DropW => (4, 0),
+            PadW => (0, 4),
+            Dup0 => (1, 2),
+            Dup1 => (2, 3),
+            Dup2 => (3, 4),
+            Dup3 => (4, 5),
+            Dup4 => (5, 6),
+            Dup5 => (6, 7),
+            Dup6 => (7, 8),
+            Dup7 => (8, 9),
+            Dup8 => (9, 10),
+            Dup9 => (10, 11),
+            Dup10 => (11, 12),
+            Dup11 => (12, 13),
+            Dup12 => (13, 14),
+            Dup13 => (14, 15),
+            Dup14 => (15, 16),
+            Dup15 => (16, 17),
+            DupW0 => (4, 8),
+            DupW1 => (8, 12),
+            DupW2 => (12, 16),
+            DupW3 => (16, 20),
+            Swap1 => (2, 2),
+            Swap2 | MovUp2 | MovDn2 => (3, 3),
+            Swap3 | MovUp3 | MovDn3 => (4, 4),
+            Swap4 | MovUp4 | MovDn4 => (5, 5),
+            Swap5 | MovUp5 | MovDn5 => (6, 6),
+            Swap6 | MovUp6 | MovDn6 => (7, 7),
+            Swap7 | MovUp7 | MovDn7 => (8, 8),
+            Swap8 | MovUp8 | MovDn8 => (9, 9),
+            Swap9 | MovUp9 | MovDn9 => (10, 10),
+            Swap10 | MovUp10 | MovDn10 => (11, 11),
+            Swap11 | MovUp11 | MovDn11 => (12, 12),
+            Swap12 | MovUp12 | MovDn12 => (13, 13),
+            Swap13 | MovUp13 | MovDn13 => (14, 14),
+            Swap14 | MovUp14 | MovDn14 => (15, 15),
+            Swap15 | MovUp15 | MovDn15 => (16, 16),
+            SwapW1 => (8, 8),
+            SwapW2 | MovUpW2 | MovDnW2 => (12, 12),
+            SwapW3 | MovUpW3 | MovDnW3 | SwapDw => (16, 16),
+            CSwap => (3, 2),
+            CSwapW => (9, 8),
+            CDrop => (3, 1),
+            CDropW => (9, 4),
+
+            // ----- input / output operations -------------------------------------------------
+            Push(_) | PushU8(_) | PushU16(_) | PushU32(_) | PushFelt(_) => (0, 1),
+            PushWord(_) => (0, 4),
+            PushU8List(values) => (0, values.len()),
+            PushU16List(values) => (0, values.len()),
+            PushU32List(values) => (0, values.len()),
+            PushFeltList(values) => (0, values.len()),
+            Locaddr(_) | Sdepth | Clk | Ctx => (0, 1),
+            Caller => (4, 4),
+            MemLoad => (1, 1),
+            MemLoadImm(_) | LocLoad(_) => (0, 1),
+            MemLoadW | MemStoreW => (5, 4),
+            MemLoadWImm(_) | LocLoadW(_) | MemStoreWImm(_) | LocStoreW(_) => (4, 4),
+            MemStore => (2, 0),
+            MemStoreImm(_) | LocStore(_) => (1, 0),
+            MemStream | AdvPipe => (13, 13),
+            AdvPush(Immediate::Value(count)) => (0, *count.inner() as usize),
+            AdvLoadW => (4, 4),
+            // Immediates are evaluated and data constants lowered before stack effects are
+            // verified, so these are only present if an error was raised
+            AdvPush(Immediate::Constant(_)) | MemInit(_) => return None,
+
+            // ----- cryptographic operations --------------------------------------------------
+            Hash => (4, 4),
+            HMerge | MTreeMerge => (8, 4),
+            HPerm => (12, 12),
+            MTreeGet => (6, 8),
+            MTreeSet => (10, 8),
+            MTreeVerify | MTreeVerifyWithError(_) => (10, 10),
+
+            // ----- STARK proof verification --------------------------------------------------
+            FriExt2Fold4 | HornerBase | HornerExt => (16, 16),
+
+            // ----- exec / call ---------------------------------------------------------------
+            ProcRef(_) => (0, 4),
+            Exec(_) | Call(_) | SysCall(_) | DynExec | DynCall => return None,
+        };
+        Some(Self::new(inputs, outputs))
+    }
+}
+
+/// The effect of a sequence of operations on the stack, relative to its depth before them.
+#[derive(Debug, Copy, Clone)]
+pub struct StackSummary {
+    /// The minimum depth required by the operations
+    pub required: usize,
+    /// The maximum increase of the depth during the operations
+    pub peak: usize,
+    /// The change of the depth after the operations, or `None` if it cannot be determined
+    pub delta: Option<isize>,
+}
+
+impl StackSummary {
+    pub const EMPTY: Self = Self { required: 0, peak: 0, delta: Some(0) };
+    pub const UNKNOWN: Self = Self { required: 0, peak: 0, delta: None };
+
+    pub fn of_effect(effect: StackEffect) -> Self {
+        Self {
+            required: effect.inputs,
+            peak: effect.outputs.saturating_sub(effect.inputs),
+            delta: Some(effect.outputs as isize - effect.inputs as isize),
+        }
+    }
+
+    /// Returns the summary of `self` followed by `next`.
+    pub fn then(self, next: Self) -> Self {
+        let Some(delta) = self.delta else {
+            return self;
+        };
+        Self {
+            required: self.required.max((next.required as isize - delta).max(0) as usize),
+            peak: self.peak.max((delta + next.peak as isize).max(0) as usize),
+            delta: next.delta.map(|next_delta| delta + next_delta),
+        }
+    }
+
+    /// Returns the summary of executing either `self` or `other`.
+    pub fn or(self, other: Self) -> Self {
+        Self {
+            required: self.required.max(other.required),
+            peak: self.peak.max(other.peak),
+            delta: self.delta.filter(|delta| other.delta == Some(*delta)),
+        }
+    }
+
+    /// Returns the summary of executing `self` `count` times in a row.
+    pub fn repeat(self, count: usize) -> Self {
+        let Some(delta) = self.delta else {
+            return self;
+        };
+        let growth = delta.unsigned_abs().saturating_mul(count - 1);
+        let (required, peak) = if delta >= 0 {
+            // Each iteration starts with more elements than the previous one
+            (self.required, self.peak.saturating_add(growth))
+        } else {
+            // Each iteration starts with fewer elements than the previous one
+            (self.required.saturating_add(growth), self.peak)
+        };
+        Self {
+            required,
+            peak,
+            delta: Some(delta * count as isize),
+        }
+    }
+}
+
+/// Resolves the declared stack effect of the procedure invoked by an invocation target, if any.
+pub type ExternalEffects<'a> = &'a dyn Fn(&InvocationTarget) -> Option<StackEffect>;
+
+/// This verifies that the body of a procedure respects the stack effect it declares with
+/// `@stack_effect(inputs = N, outputs = M)`.
+///
+/// The depth of the stack is tracked along every execution path, starting from the declared
+/// number of inputs, and the following are rejected:
+///
+/// * An instruction which accesses more elements than are available, i.e. which would consume
+///   elements beyond the declared inputs of the procedure.
+/// * A conditional whose branches leave a different number of elements on the stack.
+/// * A loop whose body does not leave the stack as it found it, plus the next condition.
+/// * A procedure which doesn't leave the declared number of outputs on the stack.
+///
+/// The analysis of a path stops at the first operation whose effect cannot be determined
+/// statically, i.e. an invocation of a procedure which doesn't declare its stack effect, a
+/// `syscall`, or a dynamic invocation. During semantic analysis, the effects of the procedures of
+/// other modules are unknown, so the assembler verifies procedures again once they are linked,
+/// with the effects declared by the procedures they invoke (see
+/// [VerifyStackEffect::with_external_effects]).
+pub struct VerifyStackEffect<'a> {
+    analyzer: &'a mut AnalysisContext,
+    /// The declared stack effects of the procedures of the current module
+    effects: &'a BTreeMap<ProcedureName, StackEffect>,
+    /// Resolves the declared stack effects of the procedures invoked by other means than by the
+    /// name of a procedure of the current module
+    external: Option<ExternalEffects<'a>>,
+    /// The span of the `@stack_effect` attribute of the current procedure
+    declared: SourceSpan,
+}
+
+impl<'a> VerifyStackEffect<'a> {
+    pub fn new(
+        analyzer: &'a mut AnalysisContext,
+        effects: &'a BTreeMap<ProcedureName, StackEffect>,
+    ) -> Self {
+        Self {
+            analyzer,
+            effects,
+            external: None,
+            declared: SourceSpan::UNKNOWN,
+        }
+    }
+
+    /// Uses `external` to determine the effects of the procedures which are not found among the
+    /// procedures of the current module, e.g. the procedures of other modules.
+    pub fn with_external_effects(mut self, external: ExternalEffects<'a>) -> Self {
+        self.external = Some(external);
+        self
+    }
+
+    /// Verifies `procedure` against its declared stack effect, if it has one.
+    pub fn verify_procedure(&mut self, procedure: &Procedure) {
+        let Some(attribute) = procedure.get_attribute(STACK_EFFECT_ATTRIBUTE) else {
+            return;
+        };
+        // Malformed declarations are reported when verifying attributes
+        let Some(effect) = StackEffect::from_attribute(attribute) else {
+            return;
+        };
+        self.declared = attribute.span();
+
+        let Some(depth) = self.verify_block(procedure.body(), effect.inputs) else {
+            return;
+        };
+        if depth != effect.outputs {
+            self.analyzer.error(SemanticAnalysisError::StackEffectMismatch {
+                span: procedure.name().span(),
+                declared: self.declared,
+                expected: effect.outputs,
+                found: depth,
+            });
+        }
+    }
+
+    /// Returns the depth of the stack after executing `block` with `depth` elements on it, or
+    /// `None` if it cannot be determined, or an error was raised.
+    fn verify_block(&mut self, block: &Block, mut depth: usize) -> Option<usize> {
+        for op in block.iter() {
+            depth = self.verify_op(op, depth)?;
+        }
+        Some(depth)
+    }
+
+    fn verify_op(&mut self, op: &Op, depth: usize) -> Option<usize> {
+        match op {
+            Op::Inst(inst) => {
+                let effect = self.instruction_effect(inst)?;
+                self.apply(inst.span(), effect, depth)
+            },
+            Op::If { span, then_blk, else_blk } => {
+                let depth = self.apply(*span, StackEffect::new(1, 0), depth)?;
+                let then_depth = self.verify_block(then_blk, depth);
+                let else_depth = self.verify_block(else_blk, depth);
+                match (then_depth?, else_depth?) {
+                    (then_depth, else_depth) if then_depth == else_depth => Some(then_depth),
+                    (then_depth, else_depth) => {
+                        self.analyzer.error(SemanticAnalysisError::UnbalancedBranches {
+                            span: *span,
+                            then_depth,
+                            else_depth,
+                        });
+                        None
+                    },
+                }
+            },
+            Op::While { span, body } => {
+                let depth = self.apply(*span, StackEffect::new(1, 0), depth)?;
+                let found = self.verify_block(body, depth)?;
+                if found != depth + 1 {
+                    self.analyzer.error(SemanticAnalysisError::UnbalancedLoop {
+                        span: *span,
+                        expected: depth + 1,
+                        found,
+                    });
+                    return None;
+                }
+                Some(depth)
+            },
+            Op::Repeat { span, count, body } => {
+                let Immediate::Value(count) = count else {
+                    return None;
+                };
+                let count = *count.inner() as usize;
+                if count == 0 {
+                    // This is reported when evaluating the count
+                    return None;
+                }
+                // Verify the first iteration, which raises any error in the body itself, then
+                // check that the later iterations, which start with a different depth, have
+                // enough elements available
+                let after = self.verify_block(body, depth)?;
+                if after >= depth {
+                    // Later iterations only have more elements available
+                    return Some(depth.saturating_add((after - depth).saturating_mul(count)));
+                }
+                let consumed = depth - after;
+                let required = self.summarize_block(body).repeat(count).required;
+                if required > depth {
+                    self.analyzer.error(SemanticAnalysisError::StackUnderflow {
+                        span: *span,
+                        declared: self.declared,
+                        required,
+                        found: depth,
+                    });
+                    return None;
+                }
+                Some(depth - count * consumed)
+            },
+            // These are rejected by the assembler if they are left in a module, which can only
+            // happen if it was constructed without being analyzed
+            Op::Expand(_) | Op::Cfg { .. } => None,
+        }
+    }
+
+    /// Applies `effect` to a stack with `depth` elements, and returns the resulting depth, or
+    /// raises an error if it needs more elements than are available.
+    fn apply(&mut self, span: SourceSpan, effect: StackEffect, depth: usize) -> Option<usize> {
+        if effect.inputs > depth {
+            self.analyzer.error(SemanticAnalysisError::StackUnderflow {
+                span,
+                declared: self.declared,
+                required: effect.inputs,
+                found: depth,
+            });
+            return None;
+        }
+        Some(depth - effect.inputs + effect.outputs)
+    }
+
+    /// Returns the effect of `inst` on the stack, or `None` if it cannot be determined.
+    fn instruction_effect(&self, inst: &Instruction) -> Option<StackEffect> {
+        match inst {
+            Instruction::Exec(target) | Instruction::Call(target) => {
+                let local = match target {
+                    InvocationTarget::ProcedureName(name) => self.effects.get(name).copied(),
+                    _ => None,
+                };
+                local.or_else(|| self.external.and_then(|external| external(target)))
+            },
+            inst => StackEffect::of_instruction(inst),
+        }
+    }
+
+    /// Returns the summary of the effect of `block` on the stack, without raising any error.
+    fn summarize_block(&self, block: &Block) -> StackSummary {
+        let mut summary = StackSummary::EMPTY;
+        for op in block.iter() {
+            if summary.delta.is_none() {
+                break;
+            }
+            summary = summary.then(self.summarize_op(op));
+        }
+        summary
+    }
+
+    fn summarize_op(&self, op: &Op) -> StackSummary {
+        let condition = StackSummary::of_effect(StackEffect::new(1, 0));
+        match op {
+            Op::Inst(inst) => self
+                .instruction_effect(inst)
+                .map(StackSummary::of_effect)
+                .unwrap_or(StackSummary::UNKNOWN),
+            Op::If { then_blk, else_blk, .. } => {
+                let then_blk = self.summarize_block(then_blk);
+                let else_blk = self.summarize_block(else_blk);
+                condition.then(then_blk.or(else_blk))
+            },
+            Op::While { body, .. } => {
+                // The body must push the condition of the next iteration
+                let body = self.summarize_block(body);
+                let iteration = StackSummary {
+                    delta: body.delta.filter(|delta| *delta == 1).map(|_| 0),
+                    ..body
+                };
+                condition.then(iteration)
+            },
+            Op::Repeat { count: Immediate::Value(count), body, .. } if *count.inner() > 0 => {
+                self.summarize_block(body).repeat(*count.inner() as usize)
+            },
+            _ => StackSummary::UNKNOWN,
+        }
+    }
+}
@@ -6,7 +6,7 @@ use vm_core::Program;
 #[cfg(feature = "std")]
 use crate::diagnostics::reporting::set_panic_hook;
 use crate::{
-    Compile, CompileOptions, LibraryPath, RpoDigest,
+    Compile, CompileOptions, LibraryPath, ResourceLimits, RpoDigest,
     assembler::Assembler,
     ast::{Form, Module, ModuleKind},
     diagnostics::{
@@ -185,7 +185,7 @@ macro_rules! parse_module {
 ///
 /// Some of the assertion macros defined above require a [TestContext], so be aware of that.
 pub struct TestContext {
-    source_manager: Arc<dyn SourceManager>,
+    source_manager: Arc<dyn SourceManager + Send + Sync>,
     assembler: Assembler,
 }
 
@@ -222,8 +222,18 @@ impl TestContext {
         self
     }
 
+    pub fn with_resource_limits(mut self, limits: ResourceLimits) -> Self {
+        self.assembler.set_resource_limits(limits);
+        self
+    }
+
+    pub fn with_features(mut self, features: &[&str]) -> Self {
+        self.assembler = self.assembler.with_features(features.iter().copied());
+        self
+    }
+
     #[inline(always)]
-    pub fn source_manager(&self) -> Arc<dyn SourceManager> {
+    pub fn source_manager(&self) -> Arc<dyn SourceManager + Send + Sync> {
         self.source_manager.clone()
     }
 
@@ -247,6 +257,7 @@ impl TestContext {
             self.source_manager.as_ref(),
             CompileOptions {
                 warnings_as_errors: self.assembler.warnings_as_errors(),
+                limits: self.assembler.resource_limits(),
                 ..Default::default()
             },
         )
@@ -263,6 +274,7 @@ impl TestContext {
             self.source_manager.as_ref(),
             CompileOptions {
                 warnings_as_errors: self.assembler.warnings_as_errors(),
+                limits: self.assembler.resource_limits(),
                 ..CompileOptions::for_kernel()
             },
         )
@@ -278,6 +290,7 @@ impl TestContext {
             self.source_manager.as_ref(),
             CompileOptions {
                 warnings_as_errors: self.assembler.warnings_as_errors(),
+                limits: self.assembler.resource_limits(),
                 ..CompileOptions::for_library()
             },
         )
@@ -294,6 +307,7 @@ impl TestContext {
             self.source_manager.as_ref(),
             CompileOptions {
                 warnings_as_errors: self.assembler.warnings_as_errors(),
+                limits: self.assembler.resource_limits(),
                 ..CompileOptions::new(ModuleKind::Library, path).unwrap()
             },
         )
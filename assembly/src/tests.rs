@@ -1,12 +1,17 @@
-use alloc::{string::ToString, vec::Vec};
+use alloc::{
+    boxed::Box,
+    string::{String, ToString},
+    vec::Vec,
+};
 
 use vm_core::{
-    Program,
+    IsaVersion, Program,
     mast::{MastNode, MastNodeId},
 };
 
 use crate::{
-    Assembler, Deserializable, LibraryPath, ModuleParser, Serializable, assert_diagnostic_lines,
+    Assembler, Deserializable, LibraryPath, ModuleParser, ResourceLimits, Serializable,
+    assert_diagnostic_lines,
     ast::{Module, ModuleKind},
     diagnostics::{IntoDiagnostic, Report},
     regex, source_file,
@@ -99,8 +104,8 @@ fn empty_if() -> TestResult {
         "  :               ^|^",
         "  :                `-- found a end here",
         "  `----",
-        " help: expected primitive opcode (e.g. \"add\"), or \"else\", or control flow",
-        "       opcode (e.g. \"if.true\")"
+        " help: expected \"@\", or primitive opcode (e.g. \"add\"), or \"else\", or control",
+        "       flow opcode (e.g. \"if.true\"), or macro invocation"
     );
     Ok(())
 }
@@ -658,6 +663,100 @@ fn constant_err_div_by_zero() -> TestResult {
     Ok(())
 }
 
+#[test]
+fn constant_bitwise_and_comparison_expressions() -> TestResult {
+    let context = TestContext::default();
+    let source = source_file!(
+        &context,
+        "const.BASE=0x0100
+    const.STRIDE=1<<3
+    const.OFFSET=BASE+STRIDE*4|1
+    const.MASKED=OFFSET&0xF0^0x30
+    const.REM=OFFSET%STRIDE+17//STRIDE
+    const.FLAGS=(BASE>>8)+(OFFSET>BASE)+(STRIDE==8)+(REM<=2)+(1!=1)
+    begin \
+    push.OFFSET push.MASKED push.REM push.FLAGS \
+    end \
+    "
+    );
+    let expected = "\
+begin
+    basic_block push(289) push(16) push(3) push(3) end
+end";
+    let program = context.assemble(source)?;
+    assert_str_eq!(format!("{program}"), expected);
+    Ok(())
+}
+
+#[test]
+fn constant_err_overflow() -> TestResult {
+    let context = TestContext::default();
+    let source = source_file!(
+        &context,
+        "const.TEST_CONSTANT=1<<64 \
+    begin \
+    push.TEST_CONSTANT \
+    end"
+    );
+    assert_assembler_diagnostic!(
+        context,
+        source,
+        "invalid constant expression: value is larger than expected range",
+        regex!(r#",-\[test[\d]+:1:21\]"#),
+        "1 | const.TEST_CONSTANT=1<<64 begin push.TEST_CONSTANT end",
+        "  :                     ^^^^^",
+        "  `----",
+        "help: the value of a constant must be a valid field element"
+    );
+
+    let source = source_file!(
+        &context,
+        "const.SHIFT=64 \
+    const.TEST_CONSTANT=1<<SHIFT \
+    begin \
+    push.TEST_CONSTANT \
+    end"
+    );
+    assert_assembler_diagnostic!(
+        context,
+        source,
+        "syntax error",
+        "help: see emitted diagnostics for details",
+        "invalid constant expression: value is larger than expected range",
+        regex!(r#",-\[test[\d]+:1:36\]"#),
+        "1 | const.SHIFT=64 const.TEST_CONSTANT=1<<SHIFT begin push.TEST_CONSTANT end",
+        "  :                                    ^^^^^^^^",
+        "  `----",
+        "help: the value of a constant must be a valid field element"
+    );
+    Ok(())
+}
+
+#[test]
+fn constant_err_derived_div_by_zero() -> TestResult {
+    let context = TestContext::default();
+    let source = source_file!(
+        &context,
+        "const.ZERO=0 \
+    const.TEST_CONSTANT=5%ZERO \
+    begin \
+    push.TEST_CONSTANT \
+    end"
+    );
+    assert_assembler_diagnostic!(
+        context,
+        source,
+        "syntax error",
+        "help: see emitted diagnostics for details",
+        "invalid constant expression: division by zero",
+        regex!(r#",-\[test[\d]+:1:34\]"#),
+        "1 | const.ZERO=0 const.TEST_CONSTANT=5%ZERO begin push.TEST_CONSTANT end",
+        "  :                                  ^^^^^^",
+        "  `----"
+    );
+    Ok(())
+}
+
 #[test]
 fn constants_must_be_uppercase() -> TestResult {
     let context = TestContext::default();
@@ -731,8 +830,8 @@ fn constant_must_be_valid_felt() -> TestResult {
         "  :                    ^^^|^^^",
         "  :                       `-- found a constant identifier here",
         "  `----",
-        " help: expected \"*\", or \"+\", or \"-\", or \"/\", or \"//\", or \"@\", or \"begin\", or \"const\", \
-or \"export\", or \"proc\", or \"use\", or end of file, or doc comment"
+        " help: expected constant operator (e.g. \"+\"), or \"@\", or \"begin\", or \"const\", \
+or \"export\", or \"macro\", or \"proc\", or \"use\", or end of file, or doc comment"
     );
     Ok(())
 }
@@ -783,7 +882,7 @@ fn constants_defined_in_global_scope() -> TestResult {
         "  :             `-- found a const here",
         "3 |     push.CONSTANT end",
         "  `----",
-        r#" help: expected primitive opcode (e.g. "add"), or control flow opcode (e.g. "if.true")"#
+        r#" help: expected "@", or primitive opcode (e.g. "add"), or control flow opcode (e.g. "if.true")"#
     );
     Ok(())
 }
@@ -814,6 +913,96 @@ fn constant_not_found() -> TestResult {
     Ok(())
 }
 
+#[test]
+fn data_constants() -> TestResult {
+    let context = TestContext::default();
+    let source = source_file!(
+        &context,
+        "const.BASE=2
+    const.DATA=[1, BASE, BASE+1, 4, 5]
+    const.NAME=\"miden\"
+    macro.init(DST)
+        push.8
+        mem_init.DST
+    end
+    begin
+        init!(DATA)
+        push.NAME
+    end"
+    );
+    let program = context.assemble(source)?;
+
+    let expected = source_file!(
+        &context,
+        "begin
+        push.8
+        push.1.2.3.4 dup.4 mem_storew dropw
+        push.5 dup.1 add.4 mem_store
+        drop
+        push.1701079405.110
+    end"
+    );
+    let expected = context.assemble(expected)?;
+    assert_eq!(program.hash(), expected.hash());
+    Ok(())
+}
+
+#[test]
+fn data_constant_err_invalid_reference() -> TestResult {
+    let context = TestContext::default();
+    let source = source_file!(
+        &context,
+        "const.DATA=[1, 2] \
+    const.VALUE=3 \
+    begin \
+    add.DATA mem_init.VALUE \
+    end"
+    );
+    assert_assembler_diagnostic!(
+        context,
+        source,
+        "syntax error",
+        "help: see emitted diagnostics for details",
+        "invalid constant reference: expected a data constant",
+        regex!(r#",-\[test[\d]+:1:57\]"#),
+        "1 | const.DATA=[1, 2] const.VALUE=3 begin add.DATA mem_init.VALUE end",
+        "  :                                                         ^^^^^",
+        "  `----",
+        "help: `mem_init` requires a constant defined as a string, or as a list of elements",
+        "invalid constant reference: expected a value, but found a data constant",
+        regex!(r#",-\[test[\d]+:1:43\]"#),
+        "1 | const.DATA=[1, 2] const.VALUE=3 begin add.DATA mem_init.VALUE end",
+        "  :                                           ^^^^",
+        "  `----",
+        "help: data constants can only be used with `push` and `mem_init`"
+    );
+    Ok(())
+}
+
+#[test]
+fn data_constant_err_empty() -> TestResult {
+    let context = TestContext::default();
+    let source = source_file!(
+        &context,
+        "const.DATA=\"\" \
+    begin \
+    push.DATA \
+    end"
+    );
+    assert_assembler_diagnostic!(
+        context,
+        source,
+        "syntax error",
+        "help: see emitted diagnostics for details",
+        "invalid data constant: value cannot be empty",
+        regex!(r#",-\[test[\d]+:1:12\]"#),
+        "1 | const.DATA=\"\" begin push.DATA end",
+        "  :            ^^",
+        "  `----"
+    );
+    Ok(())
+}
+
 #[test]
 fn mem_operations_with_constants() -> TestResult {
     let context = TestContext::default();
@@ -999,151 +1188,586 @@ fn const_conversion_failed_to_u32() -> TestResult {
     Ok(())
 }
 
-// DECORATORS
+// MACROS
 // ================================================================================================
 
 #[test]
-fn decorators_basic_block() -> TestResult {
+fn macro_expansion() -> TestResult {
     let context = TestContext::default();
     let source = source_file!(
         &context,
         "\
+    const.SHIFT=3
+
+    macro.rot3
+        movup.2 movup.2
+    end
+
+    macro.scale(FACTOR, DIVISOR)
+        mul.FACTOR u32div.DIVISOR
+    end
+
+    macro.scale_and_rot(FACTOR)
+        scale!(FACTOR, SHIFT) rot3!
+    end
+
     begin
-        trace.0
-        add
-        trace.1
-        mul
-        trace.2
+        scale_and_rot!(5)
+        if.true
+            scale!(2, 8)
+        end
     end"
     );
-    let expected = "\
-begin
-    basic_block trace(0) add trace(1) mul trace(2) end
-end";
-    let program = context.assemble(source)?;
-    assert_str_eq!(expected, format!("{program}"));
-    Ok(())
-}
-
-#[test]
-fn decorators_repeat_one_basic_block() -> TestResult {
-    let context = TestContext::default();
-    let source = source_file!(
+    let expected_source = source_file!(
         &context,
         "\
     begin
-        trace.0
-        repeat.2 add end
-        trace.1
-        repeat.2 mul end
-        trace.2
+        mul.5 u32div.3 movup.2 movup.2
+        if.true
+            mul.2 u32div.8
+        end
     end"
     );
-    let expected = "\
-begin
-    basic_block trace(0) add add trace(1) mul mul trace(2) end
-end";
     let program = context.assemble(source)?;
-    assert_str_eq!(expected, format!("{program}"));
+    let expected = context.assemble(expected_source)?;
+    assert_str_eq!(format!("{program}"), format!("{expected}"));
     Ok(())
 }
 
 #[test]
-fn decorators_repeat_split() -> TestResult {
+fn macro_params_shadow_constants() -> TestResult {
     let context = TestContext::default();
     let source = source_file!(
         &context,
         "\
+    const.N=3
+
+    macro.push_both(N)
+        push.N push.M
+    end
+
+    const.M=4
+
     begin
-        trace.0
-        repeat.2
-            if.true
-                trace.1 push.42 trace.2
-            else
-                trace.3 push.22 trace.3
-            end
-            trace.4
-        end
-        trace.5
+        push_both!(N)
+        push_both!(7)
+        push.N
     end"
     );
     let expected = "\
 begin
-    join
-        trace(0)
-        if.true
-            basic_block trace(1) push(42) trace(2) end
-        else
-            basic_block trace(3) push(22) trace(3) end
-        end
-        trace(4)
-        if.true
-            basic_block trace(1) push(42) trace(2) end
-        else
-            basic_block trace(3) push(22) trace(3) end
-        end
-        trace(4)
-    end
-    trace(5)
+    basic_block push(3) push(4) push(7) push(4) push(3) end
 end";
     let program = context.assemble(source)?;
-    assert_str_eq!(expected, format!("{program}"));
+    assert_str_eq!(format!("{program}"), expected);
     Ok(())
 }
 
 #[test]
-fn decorators_call() -> TestResult {
+fn macro_err_arity_mismatch() -> TestResult {
     let context = TestContext::default();
-    let source = source_file!(
-        &context,
-        "\
-    begin
-        trace.0 trace.1
-        call.0xdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeef
-        trace.2
-    end"
+    let source =
+        source_file!(&context, "macro.double(X) push.X push.X end begin double!(1, 2) end");
+    assert_assembler_diagnostic!(
+        context,
+        source,
+        "syntax error",
+        "help: see emitted diagnostics for details",
+        "invalid macro invocation: expected 1 arguments, but got 2",
+        regex!(r#",-\[test[\d]+:1:1\]"#),
+        "1 | macro.double(X) push.X push.X end begin double!(1, 2) end",
+        "  : ^^^^^^^^^^^^^^^^|^^^^^^^^^^^^^^^^       ^^^^^^|^^^^^^",
+        "  :                 |                             `-- invoked here",
+        "  :                 `-- macro defined here",
+        "  `----"
     );
-    let expected = "\
-begin
-    trace(0) trace(1)
-    call.0xdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeef
-    trace(2)
-end";
-    let program = context.assemble(source)?;
-    assert_str_eq!(expected, format!("{program}"));
     Ok(())
 }
 
 #[test]
-fn decorators_dyn() -> TestResult {
-    // single line
+fn macro_err_recursive_invocation() -> TestResult {
     let context = TestContext::default();
-    let source = source_file!(
-        &context,
-        "\
-    begin
-        trace.0
-        dynexec
-        trace.1
-    end"
+    let source = source_file!(&context, "macro.a\n  b!\nend\nmacro.b\n  a!\nend\nbegin a! end");
+    assert_assembler_diagnostic!(
+        context,
+        source,
+        "syntax error",
+        "help: see emitted diagnostics for details",
+        "invalid recursive macro invocation",
+        regex!(r#",-\[test[\d]+:1:1\]"#),
+        "1 | ,-> macro.a",
+        "2 | |     b!",
+        "  : |     ^|",
+        "  : |      `-- expanded from this invocation",
+        "3 | |-> end",
+        "  : `---- while expanding this macro",
+        "4 |     macro.b",
+        "5 |       a!",
+        "  :       ^|",
+        "  :        `-- caused by this invocation",
+        "6 |     end",
+        "  `----",
+        " help: macros are expanded in place, so they cannot invoke themselves"
     );
-    let expected = "\
-begin
-    trace(0) dyn trace(1)
-end";
-    let program = context.assemble(source)?;
-    assert_str_eq!(expected, format!("{program}"));
+    Ok(())
+}
 
-    // multi line
+#[test]
+fn macro_err_in_expansion() -> TestResult {
     let context = TestContext::default();
-    let source = source_file!(
-        &context,
-        "\
-    begin
-        trace.0 trace.1 trace.2 trace.3 trace.4
-        dynexec
-        trace.5 trace.6 trace.7 trace.8 trace.9
-    end"
+    let source = source_file!(&context, "macro.go exec.missing end begin go! end");
+    assert_assembler_diagnostic!(
+        context,
+        source,
+        "syntax error",
+        "help: see emitted diagnostics for details",
+        "symbol undefined: no such name found in scope",
+        regex!(r#",-\[test[\d]+:1:15\]"#),
+        "1 | macro.go exec.missing end begin go! end",
+        "  :               ^^^^^^^           ^|^",
+        "  :                  |               `-- expanded from this invocation",
+        "  `----",
+        " help: are you missing an import?"
+    );
+    Ok(())
+}
+
+#[test]
+fn macro_err_argument_out_of_range() -> TestResult {
+    let context = TestContext::default();
+    let source =
+        source_file!(&context, "macro.divide(D) u32div.D end begin divide!(4294967296) end");
+    assert_assembler_diagnostic!(
+        context,
+        source,
+        "syntax error",
+        "help: see emitted diagnostics for details",
+        "invalid immediate: value is larger than expected range",
+        regex!(r#",-\[test[\d]+:1:44\]"#),
+        "1 | macro.divide(D) u32div.D end begin divide!(4294967296) end",
+        "  :                                            ^^^^^^^^^^",
+        "  `----"
+    );
+    Ok(())
+}
+
+#[test]
+fn macro_err_undefined() -> TestResult {
+    let context = TestContext::default();
+    let source = source_file!(&context, "begin swap_all! end");
+    assert_assembler_diagnostic!(
+        context,
+        source,
+        "syntax error",
+        "help: see emitted diagnostics for details",
+        "symbol undefined: no such macro found in this module",
+        regex!(r#",-\[test[\d]+:1:7\]"#),
+        "1 | begin swap_all! end",
+        "  :       ^^^^^^^^^",
+        "  `----",
+        " help: macros must be defined in the module which invokes them"
+    );
+    Ok(())
+}
+
+// GENERIC PROCEDURES
+// ================================================================================================
+
+#[test]
+fn generic_procedures() -> TestResult {
+    let context = TestContext::default();
+    let source = source_file!(
+        &context,
+        "\
+    const.WIDTH=2
+
+    proc.sum_n<N>
+        repeat.N add end
+    end
+
+    proc.push_n<N, VALUE>
+        repeat.N push.VALUE end
+    end
+
+    proc.sum_of<N, VALUE>
+        exec.push_n<N, VALUE>
+        exec.sum_n<N>
+    end
+
+    begin
+        push.1
+        exec.sum_of<3, 5>
+        exec.sum_n<WIDTH>
+        exec.sum_of<3, 5>
+    end"
+    );
+    let expected_source = source_file!(
+        &context,
+        "\
+    proc.sum_3 repeat.3 add end end
+    proc.sum_2 repeat.2 add end end
+    proc.push_3_5 repeat.3 push.5 end end
+    proc.sum_of_3_5 exec.push_3_5 exec.sum_3 end
+
+    begin
+        push.1
+        exec.sum_of_3_5
+        exec.sum_2
+        exec.sum_of_3_5
+    end"
+    );
+    let program = context.assemble(source)?;
+    let expected = context.assemble(expected_source)?;
+    assert_str_eq!(format!("{program}"), format!("{expected}"));
+    Ok(())
+}
+
+#[test]
+fn generic_procedure_instances() -> TestResult {
+    let context = TestContext::default();
+    let source = "\
+    proc.sum_n<N>
+        repeat.N add end
+    end
+
+    export.sum_4
+        exec.sum_n<4>
+    end
+
+    export.sum_4_twice
+        exec.sum_n<4> exec.sum_n<4>
+    end";
+    let module = parse_module!(&context, "test::generic", source);
+
+    // generic procedures are replaced by a single procedure per instance
+    let names = module.procedures().map(|p| p.name().as_str()).collect::<Vec<_>>();
+    assert_eq!(names, ["sum_4", "sum_4_twice", "sum_n<4>"]);
+
+    // instances are quoted when printed, so that the printed module can be parsed back
+    let printed = format!("{module}");
+    assert!(printed.contains("proc.\"sum_n<4>\""));
+    assert!(printed.contains("exec.\"sum_n<4>\""));
+    let reparsed = parse_module!(&context, "test::generic", printed);
+    assert_eq!(module, reparsed);
+    Ok(())
+}
+
+#[test]
+fn generic_procedure_err_arity_mismatch() -> TestResult {
+    let context = TestContext::default();
+    let source =
+        source_file!(&context, "proc.sum_n<N> repeat.N add end end begin exec.sum_n<1, 2> end");
+    assert_assembler_diagnostic!(
+        context,
+        source,
+        "syntax error",
+        "help: see emitted diagnostics for details",
+        "invalid procedure instance: expected 1 arguments, but got 2",
+        regex!(r#",-\[test[\d]+:1:6\]"#),
+        "1 | proc.sum_n<N> repeat.N add end end begin exec.sum_n<1, 2> end",
+        "  :      ^^|^^                                    ^^|^^",
+        "  :        |                                        `-- invoked here",
+        "  :        `-- procedure defined here",
+        "  `----"
+    );
+
+    let source = source_file!(&context, "proc.sum_n<N> repeat.N add end end begin exec.sum_n end");
+    assert_assembler_diagnostic!(
+        context,
+        source,
+        "syntax error",
+        "help: see emitted diagnostics for details",
+        "invalid procedure instance: expected 1 arguments, but got 0"
+    );
+    Ok(())
+}
+
+#[test]
+fn generic_procedure_err_export() -> TestResult {
+    let context = TestContext::default();
+    let source = source_file!(&context, "export.sum_n<N> repeat.N add end end");
+    let error = context
+        .parse_module(source)
+        .expect_err("expected generic export to be rejected");
+    assert_diagnostic_lines!(
+        error,
+        "syntax error",
+        "help: see emitted diagnostics for details",
+        "invalid generic procedure: generic procedures cannot be exported",
+        regex!(r#",-\[test[\d]+:1:1\]"#),
+        "1 | export.sum_n<N> repeat.N add end end",
+        "  : ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^",
+        "  `----"
+    );
+    Ok(())
+}
+
+#[test]
+fn generic_procedure_err_qualified_instance() -> TestResult {
+    let context = TestContext::default();
+    let source = source_file!(&context, "use.std::math::u64 begin exec.u64::sum_n<4> end");
+    assert_assembler_diagnostic!(
+        context,
+        source,
+        "invalid procedure instance: only procedures of the current module can be instantiated",
+        regex!(r#",-\[test[\d]+:1:31\]"#),
+        "1 | use.std::math::u64 begin exec.u64::sum_n<4> end",
+        "  :                               ^^^^^^^^^^^^^",
+        "  `----",
+        " help: generic procedures cannot be exported, invoke a procedure which instantiates it instead"
+    );
+    Ok(())
+}
+
+#[test]
+fn generic_procedure_warn_unused() -> TestResult {
+    let context = TestContext::default();
+    let source = source_file!(&context, "proc.sum_n<N> repeat.N add end end begin push.1 end");
+    assert_assembler_diagnostic!(
+        context,
+        source,
+        "syntax error",
+        "help: see emitted diagnostics for details",
+        "unused generic procedure",
+        regex!(r#",-\[test[\d]+:1:6\]"#),
+        "1 | proc.sum_n<N> repeat.N add end end begin push.1 end",
+        "  :      ^^^^^",
+        "  `----",
+        " help: this procedure is never instantiated and can be safely removed"
+    );
+    Ok(())
+}
+
+#[test]
+fn repeat_count_constant() -> TestResult {
+    let context = TestContext::default();
+    let source = source_file!(&context, "const.N=1+2 begin repeat.N add end end");
+    let program = context.assemble(source)?;
+    let expected = "\
+begin
+    basic_block add add add end
+end";
+    assert_str_eq!(format!("{program}"), expected);
+
+    let source = source_file!(&context, "const.N=0 begin repeat.N add end end");
+    assert_assembler_diagnostic!(
+        context,
+        source,
+        "syntax error",
+        "help: see emitted diagnostics for details",
+        "invalid repeat count: the body must be repeated at least once",
+        regex!(r#",-\[test[\d]+:1:24\]"#),
+        "1 | const.N=0 begin repeat.N add end end",
+        "  :                        ^",
+        "  `----"
+    );
+    Ok(())
+}
+
+// CONDITIONAL COMPILATION
+// ================================================================================================
+
+#[test]
+fn cfg_procedures_and_ops() -> TestResult {
+    let source = "\
+    @cfg(feature = \"testnet\")
+    proc.network_id
+        push.2
+    end
+
+    #! Pushes the identifier of the network onto the stack
+    @cfg(feature = \"mainnet\")
+    proc.network_id
+        push.3
+    end
+
+    begin
+        exec.network_id
+        @cfg(feature = \"debug\")
+        dup
+        if.true
+            @cfg(feature = \"debug\") assert
+        end
+    end";
+
+    let context = TestContext::default().with_features(&["testnet"]);
+    let program = context.assemble(source_file!(&context, source))?;
+    let expected = context.assemble(source_file!(&context, "begin push.2 if.true nop end end"))?;
+    assert_str_eq!(format!("{program}"), format!("{expected}"));
+
+    let context = TestContext::default().with_features(&["mainnet", "debug"]);
+    let program = context.assemble(source_file!(&context, source))?;
+    let expected =
+        context.assemble(source_file!(&context, "begin push.3 dup if.true assert end end"))?;
+    assert_str_eq!(format!("{program}"), format!("{expected}"));
+    Ok(())
+}
+
+#[test]
+fn cfg_err_invalid_predicate() -> TestResult {
+    let context = TestContext::default();
+    let source = source_file!(&context, "begin @inline push.1 end");
+    assert_assembler_diagnostic!(
+        context,
+        source,
+        "syntax error",
+        "help: see emitted diagnostics for details",
+        "invalid conditional compilation predicate",
+        regex!(r#",-\[test[\d]+:1:7\]"#),
+        "1 | begin @inline push.1 end",
+        "  :       ^^^^^^^",
+        "  `----",
+        " help: expected `@cfg(feature = \"name\")`, which is the only annotation allowed on",
+        "instructions"
+    );
+    Ok(())
+}
+
+// DECORATORS
+// ================================================================================================
+
+#[test]
+fn decorators_basic_block() -> TestResult {
+    let context = TestContext::default();
+    let source = source_file!(
+        &context,
+        "\
+    begin
+        trace.0
+        add
+        trace.1
+        mul
+        trace.2
+    end"
+    );
+    let expected = "\
+begin
+    basic_block trace(0) add trace(1) mul trace(2) end
+end";
+    let program = context.assemble(source)?;
+    assert_str_eq!(expected, format!("{program}"));
+    Ok(())
+}
+
+#[test]
+fn decorators_repeat_one_basic_block() -> TestResult {
+    let context = TestContext::default();
+    let source = source_file!(
+        &context,
+        "\
+    begin
+        trace.0
+        repeat.2 add end
+        trace.1
+        repeat.2 mul end
+        trace.2
+    end"
+    );
+    let expected = "\
+begin
+    basic_block trace(0) add add trace(1) mul mul trace(2) end
+end";
+    let program = context.assemble(source)?;
+    assert_str_eq!(expected, format!("{program}"));
+    Ok(())
+}
+
+#[test]
+fn decorators_repeat_split() -> TestResult {
+    let context = TestContext::default();
+    let source = source_file!(
+        &context,
+        "\
+    begin
+        trace.0
+        repeat.2
+            if.true
+                trace.1 push.42 trace.2
+            else
+                trace.3 push.22 trace.3
+            end
+            trace.4
+        end
+        trace.5
+    end"
+    );
+    let expected = "\
+begin
+    join
+        trace(0)
+        if.true
+            basic_block trace(1) push(42) trace(2) end
+        else
+            basic_block trace(3) push(22) trace(3) end
+        end
+        trace(4)
+        if.true
+            basic_block trace(1) push(42) trace(2) end
+        else
+            basic_block trace(3) push(22) trace(3) end
+        end
+        trace(4)
+    end
+    trace(5)
+end";
+    let program = context.assemble(source)?;
+    assert_str_eq!(expected, format!("{program}"));
+    Ok(())
+}
+
+#[test]
+fn decorators_call() -> TestResult {
+    let context = TestContext::default();
+    let source = source_file!(
+        &context,
+        "\
+    begin
+        trace.0 trace.1
+        call.0xdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeef
+        trace.2
+    end"
+    );
+    let expected = "\
+begin
+    trace(0) trace(1)
+    call.0xdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeef
+    trace(2)
+end";
+    let program = context.assemble(source)?;
+    assert_str_eq!(expected, format!("{program}"));
+    Ok(())
+}
+
+#[test]
+fn decorators_dyn() -> TestResult {
+    // single line
+    let context = TestContext::default();
+    let source = source_file!(
+        &context,
+        "\
+    begin
+        trace.0
+        dynexec
+        trace.1
+    end"
+    );
+    let expected = "\
+begin
+    trace(0) dyn trace(1)
+end";
+    let program = context.assemble(source)?;
+    assert_str_eq!(expected, format!("{program}"));
+
+    // multi line
+    let context = TestContext::default();
+    let source = source_file!(
+        &context,
+        "\
+    begin
+        trace.0 trace.1 trace.2 trace.3 trace.4
+        dynexec
+        trace.5 trace.6 trace.7 trace.8 trace.9
+    end"
     );
     let expected = "\
 begin
@@ -1681,6 +2305,56 @@ end";
     Ok(())
 }
 
+#[test]
+fn exec_chains_are_rebalanced() -> TestResult {
+    // each procedure executes the previous one, followed by a control flow block, so that the
+    // procedures are not inlined into basic blocks
+    let chain_source = |num_procs: usize| {
+        let mut source = String::from("proc.p0 if.true push.1 else push.2 end end\n");
+        for i in 1..num_procs {
+            source.push_str(&format!(
+                "proc.p{i} exec.p{} if.true push.1 else push.2 end end\n",
+                i - 1
+            ));
+        }
+        source.push_str(&format!("begin exec.p{} end", num_procs - 1));
+        source
+    };
+
+    // short chains are left as they are, and each procedure adds a level of nesting
+    let context = TestContext::default();
+    let program = context.assemble(source_file!(&context, chain_source(16)))?;
+    assert_eq!(program.entrypoint_analysis().unwrap().depth(), 17);
+
+    // long chains are rebalanced, so that the depth grows logarithmically with their length
+    let context = TestContext::default();
+    let program = context.assemble(source_file!(&context, chain_source(500)))?;
+    let depth = program.entrypoint_analysis().unwrap().depth();
+    assert!(depth <= 32, "depth {depth} is too large");
+
+    Ok(())
+}
+
+#[test]
+fn shared_exec_dags_are_rebalanced_without_expansion() -> TestResult {
+    // each procedure executes the previous one twice, so that the MAST of the program is a DAG
+    // with 2^40 paths, which must not be expanded when deep JOIN nodes are flattened
+    let mut source = String::from("proc.p0 push.1 if.true add.1 end end\n");
+    for i in 1..40 {
+        source.push_str(&format!("proc.p{i} exec.p{0} exec.p{0} end\n", i - 1));
+    }
+    source.push_str("begin exec.p39 end");
+
+    let context = TestContext::default();
+    let program = context.assemble(source_file!(&context, source))?;
+    // each rebalanced procedure joins the flattened procedures it executes anew, so the number of
+    // nodes grows quadratically with the depth of the DAG, rather than exponentially
+    let num_nodes = program.mast_forest().num_nodes();
+    assert!(num_nodes < 5000, "{num_nodes} nodes are too many");
+
+    Ok(())
+}
+
 // PROGRAMS WITH PROCEDURES
 // ================================================================================================
 
@@ -1825,56 +2499,546 @@ begin
         push(18446744069414584317)
         fmpupdate
     end
-end";
-    assert_str_eq!(format!("{program}"), expected);
+end";
+    assert_str_eq!(format!("{program}"), expected);
+    Ok(())
+}
+
+#[test]
+fn program_with_proc_locals_fail() -> TestResult {
+    let context = TestContext::default();
+    let source = source_file!(
+        &context,
+        "\
+        proc.foo \
+            loc_store.0 \
+            add \
+            loc_load.0 \
+            mul \
+        end \
+        begin \
+            push.4 push.3 push.2 \
+            exec.foo \
+        end"
+    );
+    assert_assembler_diagnostic!(
+        context,
+        source,
+        "number of procedure locals was not set (or set to 0), but local values were used"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn program_with_exported_procedure() -> TestResult {
+    let context = TestContext::default();
+    let source = source_file!(
+        &context,
+        "export.foo push.3 push.7 mul end begin push.2 push.3 add exec.foo end"
+    );
+
+    assert_assembler_diagnostic!(
+        context,
+        source,
+        "syntax error",
+        "help: see emitted diagnostics for details",
+        "invalid program: procedure exports are not allowed",
+        regex!(r#",-\[test[\d]+:1:1\]"#),
+        "1 | export.foo push.3 push.7 mul end begin push.2 push.3 add exec.foo end",
+        "  : ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^",
+        "  `----"
+    );
+    Ok(())
+}
+
+#[test]
+fn program_with_procedure_attributes() -> TestResult {
+    let context = TestContext::default();
+    let source = source_file!(
+        &context,
+        "\
+    @inline proc.foo push.3 push.7 mul end \
+    @inline(never) @no_syscall @audit(status = \"reviewed\", round = 2) proc.bar add end \
+    @deprecated(\"use `bar` instead\") proc.baz exec.foo end \
+    begin push.2 push.3 exec.foo exec.bar end"
+    );
+    let program = context.assemble(source)?;
+
+    let expected = "\
+begin
+    basic_block push(2) push(3) push(3) push(7) mul add end
+end";
+    assert_str_eq!(format!("{program}"), expected);
+    Ok(())
+}
+
+#[test]
+fn procedure_attribute_err_invalid() -> TestResult {
+    let context = TestContext::default();
+    let source = source_file!(&context, "@inline(sometimes) proc.foo add end begin exec.foo end");
+    assert_assembler_diagnostic!(
+        context,
+        source,
+        "syntax error",
+        "help: see emitted diagnostics for details",
+        "invalid attribute: `@inline` is malformed",
+        regex!(r#",-\[test[\d]+:1:1\]"#),
+        "1 | @inline(sometimes) proc.foo add end begin exec.foo end",
+        "  : ^^^^^^^^^^^^^^^^^^",
+        "  `----",
+        " help: expected `@inline`, `@inline(always)` or `@inline(never)`"
+    );
+
+    let source = source_file!(&context, "@no_syscall(1) proc.foo add end begin exec.foo end");
+    assert_assembler_diagnostic!(
+        context,
+        source,
+        "syntax error",
+        "help: see emitted diagnostics for details",
+        "invalid attribute: `@no_syscall` is malformed",
+        regex!(r#",-\[test[\d]+:1:1\]"#),
+        "1 | @no_syscall(1) proc.foo add end begin exec.foo end",
+        "  : ^^^^^^^^^^^^^^",
+        "  `----",
+        " help: expected `@no_syscall`, which takes no arguments"
+    );
+    Ok(())
+}
+
+#[test]
+fn procedure_attribute_err_syscall_in_no_syscall_procedure() -> TestResult {
+    let context = TestContext::default();
+    let source = source_file!(
+        &context,
+        "proc.foo add end @no_syscall proc.bar syscall.foo end begin nop end"
+    );
+    assert_assembler_diagnostic!(
+        context,
+        source,
+        "syntax error",
+        "help: see emitted diagnostics for details",
+        "invalid syscall: this procedure is marked `@no_syscall`",
+        regex!(r#",-\[test[\d]+:1:18\]"#),
+        "1 | proc.foo add end @no_syscall proc.bar syscall.foo end begin nop end",
+        "  :                  ^^^^^|^^^^^                  ^^^",
+        "  :                       `-- the procedure is marked here",
+        "  `----"
+    );
+    Ok(())
+}
+
+#[test]
+fn procedure_attribute_warn_deprecated() -> TestResult {
+    let context = TestContext::default();
+    let source =
+        source_file!(&context, "@deprecated(\"use bar\") proc.foo add end begin exec.foo end");
+    assert_assembler_diagnostic!(
+        context,
+        source,
+        "syntax error",
+        "help: see emitted diagnostics for details",
+        "use of deprecated procedure",
+        regex!(r#",-\[test[\d]+:1:1\]"#),
+        "1 | @deprecated(\"use bar\") proc.foo add end begin exec.foo end",
+        "  : ^^^^^^^^^^^|^^^^^^^^^^                             ^^^",
+        "  :            `-- deprecated here",
+        "  `----",
+        " help: use bar"
+    );
+
+    // procedures which are deprecated themselves can use deprecated procedures
+    let source = source_file!(
+        &context,
+        "@deprecated proc.foo add end @deprecated proc.bar exec.foo end begin push.1 end"
+    );
+    context.assemble(source)?;
+    Ok(())
+}
+
+#[test]
+fn procedure_attribute_warn_deprecated_library() -> TestResult {
+    let context = TestContext::default();
+    let foo = r#"
+        @deprecated("use bar")
+        export.foo
+            add
+        end
+
+        export.bar
+            mul
+        end
+    "#;
+    let foo = parse_module!(&context, "test::foo", foo);
+    let library = Assembler::new(context.source_manager()).assemble_library([foo])?;
+    let assembler = Assembler::new(context.source_manager())
+        .with_warnings_as_errors(true)
+        .with_library(&library)?;
+
+    let source = source_file!(
+        &context,
+        "\
+    use.test::foo
+    begin
+        exec.foo::foo
+    end"
+    );
+    let report = assembler.clone().assemble_program(source).expect_err("expected a warning");
+    assert_diagnostic_lines!(
+        report,
+        "use of deprecated procedure 'test::foo::foo'",
+        regex!(r#",-\[test[\d]+:3:19\]"#),
+        "2 |     begin",
+        "3 |         exec.foo::foo",
+        "  :                   ^^^",
+        "4 |     end",
+        "  `----",
+        " help: use bar"
+    );
+
+    // procedures which are deprecated themselves can use deprecated procedures
+    let baz = r#"
+        use.test::foo
+
+        @deprecated
+        export.baz
+            exec.foo::foo
+        end
+    "#;
+    let baz = parse_module!(&context, "test::baz", baz);
+    assembler.assemble_library([baz])?;
+    Ok(())
+}
+
+// STACK EFFECTS
+// ================================================================================================
+
+#[test]
+fn procedure_stack_effects() -> TestResult {
+    let context = TestContext::default();
+    let source = source_file!(
+        &context,
+        "\
+    @stack_effect(inputs = 2, outputs = 1)
+    proc.max
+        dup.1 dup.1 lt
+        if.true swap drop else drop end
+    end
+
+    @stack_effect(inputs = 5, outputs = 1)
+    proc.max_of_5
+        repeat.4 exec.max end
+    end
+
+    @stack_effect(inputs = 1, outputs = 1)
+    proc.countdown
+        dup neq.0
+        while.true sub.1 dup neq.0 end
+    end
+
+    @stack_effect(inputs = 1, outputs = 0)
+    proc.consume
+        exec.unknown
+    end
+
+    proc.unknown
+        drop drop
+    end
+
+    begin
+        push.1.2.3.4.5 exec.max_of_5 exec.countdown exec.consume
+    end"
+    );
+    context.assemble(source)?;
+    Ok(())
+}
+
+#[test]
+fn procedure_stack_effect_err_underflow() -> TestResult {
+    let context = TestContext::default();
+    let source = source_file!(&context, "@stack_effect(inputs = 1, outputs = 1) proc.foo add end");
+    let error = context.parse_module(source).expect_err("expected stack underflow");
+    assert_diagnostic_lines!(
+        error,
+        "syntax error",
+        "help: see emitted diagnostics for details",
+        "stack underflow: expected 2 elements on the stack, but found 1",
+        regex!(r#",-\[test[\d]+:1:1\]"#),
+        "1 | @stack_effect(inputs = 1, outputs = 1) proc.foo add end",
+        "  : ^^^^^^^^^^^^^^^^^^^|^^^^^^^^^^^^^^^^^^          ^^^",
+        "  :                    `-- stack effect declared here",
+        "  `----"
+    );
+
+    // the last iteration of a loop consumes more elements than are available
+    let source = source_file!(
+        &context,
+        "@stack_effect(inputs = 2, outputs = 0) proc.foo repeat.3 drop end end begin nop end"
+    );
+    assert_assembler_diagnostic!(
+        context,
+        source,
+        "syntax error",
+        "help: see emitted diagnostics for details",
+        "stack underflow: expected 3 elements on the stack, but found 2",
+        regex!(r#",-\[test[\d]+:1:1\]"#),
+        "1 | @stack_effect(inputs = 2, outputs = 0) proc.foo repeat.3 drop end end begin nop end",
+        "  : ^^^^^^^^^^^^^^^^^^^|^^^^^^^^^^^^^^^^^^          ^^^^^^^^^^^^^^^^^",
+        "  :                    `-- stack effect declared here",
+        "  `----"
+    );
+
+    // errors in the body of a loop are only reported once
+    let source = source_file!(
+        &context,
+        "@stack_effect(inputs = 2, outputs = 0) proc.foo repeat.2 drop drop drop end end"
+    );
+    let error = context.parse_module(source).expect_err("expected stack underflow");
+    let rendered = format!("{error:?}");
+    assert_eq!(rendered.matches("stack underflow").count(), 1, "{rendered}");
+    Ok(())
+}
+
+#[test]
+fn procedure_stack_effect_err_mismatch() -> TestResult {
+    let context = TestContext::default();
+    let source = source_file!(&context, "@stack_effect(inputs = 1, outputs = 0) proc.foo dup end");
+    let error = context.parse_module(source).expect_err("expected stack effect mismatch");
+    assert_diagnostic_lines!(
+        error,
+        "syntax error",
+        "help: see emitted diagnostics for details",
+        "stack effect mismatch: expected 0 outputs, but found 2",
+        regex!(r#",-\[test[\d]+:1:1\]"#),
+        "1 | @stack_effect(inputs = 1, outputs = 0) proc.foo dup end",
+        "  : ^^^^^^^^^^^^^^^^^^^|^^^^^^^^^^^^^^^^^^      ^^^",
+        "  :                    `-- stack effect declared here",
+        "  `----"
+    );
+    Ok(())
+}
+
+#[test]
+fn procedure_stack_effect_err_external() -> TestResult {
+    let context = TestContext::default();
+    let math = r#"
+        @stack_effect(inputs = 2, outputs = 1)
+        export.add2
+            add
+        end
+    "#;
+    let math = parse_module!(&context, "test::math", math);
+    let library = Assembler::new(context.source_manager()).assemble_library([math])?;
+    let assembler = Assembler::new(context.source_manager()).with_library(&library)?;
+
+    // the declared effects of the procedures of libraries are used to verify their callers
+    let source = source_file!(
+        &context,
+        "\
+    use.test::math
+    @stack_effect(inputs = 1, outputs = 1)
+    proc.foo
+        exec.math::add2
+    end
+    begin
+        push.1 push.2 exec.foo
+    end"
+    );
+    let report = assembler.clone().assemble_program(source).expect_err("expected an error");
+    assert_diagnostic_lines!(
+        report,
+        "syntax error",
+        "help: see emitted diagnostics for details",
+        "stack underflow: expected 2 elements on the stack, but found",
+        regex!(r#",-\[test[\d]+:2:5\]"#),
+        "1 | use.test::math",
+        "2 |     @stack_effect(inputs = 1, outputs = 1)",
+        "  :     ^^^^^^^^^^^^^^^^^^^|^^^^^^^^^^^^^^^^^^",
+        "  :                        `-- stack effect declared here",
+        "3 |     proc.foo",
+        "4 |         exec.math::add2",
+        "  :         ^^^^^^^^^^^^^^^",
+        "5 |     end",
+        "  `----"
+    );
+
+    let source = source_file!(
+        &context,
+        "\
+    use.test::math
+    @stack_effect(inputs = 2, outputs = 1)
+    proc.foo
+        exec.math::add2
+    end
+    begin
+        push.1 push.2 exec.foo
+    end"
+    );
+    assembler.assemble_program(source)?;
     Ok(())
 }
 
 #[test]
-fn program_with_proc_locals_fail() -> TestResult {
+fn procedure_stack_effect_err_unbalanced() -> TestResult {
     let context = TestContext::default();
     let source = source_file!(
         &context,
-        "\
-        proc.foo \
-            loc_store.0 \
-            add \
-            loc_load.0 \
-            mul \
-        end \
-        begin \
-            push.4 push.3 push.2 \
-            exec.foo \
-        end"
+        "@stack_effect(inputs = 2, outputs = 1) proc.foo if.true dup end end begin nop end"
     );
     assert_assembler_diagnostic!(
         context,
         source,
-        "number of procedure locals was not set (or set to 0), but local values were used"
+        "syntax error",
+        "help: see emitted diagnostics for details",
+        "stack effect mismatch: the branches of this conditional leave 2 and 1 elements on the stack"
     );
 
-    Ok(())
-}
-
-#[test]
-fn program_with_exported_procedure() -> TestResult {
-    let context = TestContext::default();
     let source = source_file!(
         &context,
-        "export.foo push.3 push.7 mul end begin push.2 push.3 add exec.foo end"
+        "@stack_effect(inputs = 1, outputs = 0) proc.foo while.true push.1 push.0 end end begin nop end"
+    );
+    assert_assembler_diagnostic!(
+        context,
+        source,
+        "syntax error",
+        "help: see emitted diagnostics for details",
+        "stack effect mismatch: the body of this loop leaves 2 elements on the stack, instead of 1"
     );
 
+    let source =
+        source_file!(&context, "@stack_effect(inputs = 1) proc.foo drop end begin nop end");
     assert_assembler_diagnostic!(
         context,
         source,
         "syntax error",
         "help: see emitted diagnostics for details",
-        "invalid program: procedure exports are not allowed",
-        regex!(r#",-\[test[\d]+:1:1\]"#),
-        "1 | export.foo push.3 push.7 mul end begin push.2 push.3 add exec.foo end",
-        "  : ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^",
-        "  `----"
+        "invalid attribute: `@stack_effect` is malformed"
+    );
+    Ok(())
+}
+
+// STACK DEPTH ANALYSIS
+// ================================================================================================
+
+#[test]
+fn stack_depth_analysis() -> TestResult {
+    use crate::{
+        LibraryNamespace,
+        ast::{ProcedureName, QualifiedProcedureName},
+    };
+
+    let context = TestContext::default();
+    let math = context.parse_module_with_path(
+        "test::math".parse().unwrap(),
+        source_file!(&context, "export.sum3 add add end"),
+    )?;
+    let assembler = Assembler::new(context.source_manager()).with_module(math)?;
+    let source = source_file!(
+        &context,
+        "\
+    use.test::math
+
+    proc.push_pair
+        push.1.2
+    end
+
+    proc.drop_many
+        repeat.17 drop end
+    end
+
+    proc.unbalanced
+        if.true push.1 else push.1.2 end
+    end
+
+    begin
+        exec.push_pair exec.math::sum3 exec.drop_many exec.unbalanced
+    end"
+    );
+    let report = assembler.analyze_stack_depth(source)?;
+
+    let depth = |module: &str, name: &str| {
+        let module = match module {
+            "$exec" => LibraryPath::from(LibraryNamespace::Exec),
+            module => module.parse().unwrap(),
+        };
+        let name = match name {
+            "main" => ProcedureName::main(),
+            name => ProcedureName::new(name).unwrap(),
+        };
+        let depth = report.get(&QualifiedProcedureName::new(module, name)).unwrap();
+        (depth.required(), depth.max(), depth.outputs(), depth.may_underflow())
+    };
+    assert_eq!(depth("test::math", "sum3"), (3, 3, Some(1), false));
+    assert_eq!(depth("$exec", "push_pair"), (0, 2, Some(2), false));
+    assert_eq!(depth("$exec", "drop_many"), (17, 17, Some(0), true));
+    assert_eq!(depth("$exec", "unbalanced"), (1, 2, None, false));
+    // `drop_many` and `unbalanced` consume 18 elements, after the 2 elements pushed by
+    // `push_pair` are summed into the element below them
+    assert_eq!(depth("$exec", "main"), (18, 20, None, true));
+
+    let underflows = report.underflows().map(|name| name.to_string()).collect::<Vec<_>>();
+    assert_eq!(underflows, ["#exec::#main", "#exec::drop_many"]);
+    let expected = "\
+Procedure          Required       Max   Outputs
+#exec::#main             18        20         ?  (may underflow)
+#exec::drop_many         17        17         0  (may underflow)
+#exec::push_pair          0         2         2
+#exec::unbalanced         1         2         ?
+test::math::sum3          3         3         1
+";
+    assert_str_eq!(format!("{report}"), expected);
+    Ok(())
+}
+
+#[test]
+fn stack_depth_analysis_libraries() -> TestResult {
+    use crate::{
+        LibraryNamespace,
+        ast::{ProcedureName, QualifiedProcedureName},
+    };
+
+    let context = TestContext::default();
+    let u64 = r#"
+        export.wrapping_add
+            swap movup.3 u32overflowing_add movup.3 movup.3 u32wrapping_add3
+        end
+    "#;
+    let u64 = parse_module!(&context, "test::u64", u64);
+    let library = Assembler::new(context.source_manager()).assemble_library([u64])?;
+    let assembler = Assembler::new(context.source_manager()).with_library(&library)?;
+    let source = source_file!(
+        &context,
+        "\
+    use.test::u64
+
+    proc.drop_many
+        repeat.19 drop end
+    end
+
+    proc.call_many
+        call.drop_many
+    end
+
+    begin
+        push.1.2.3.4 exec.u64::wrapping_add exec.drop_many
+    end"
     );
+    let report = assembler.analyze_stack_depth(source)?;
+
+    let depth = |name: &str| {
+        let name = match name {
+            "main" => ProcedureName::main(),
+            name => ProcedureName::new(name).unwrap(),
+        };
+        let path = LibraryPath::from(LibraryNamespace::Exec);
+        let depth = report.get(&QualifiedProcedureName::new(path, name)).unwrap();
+        (depth.required(), depth.max(), depth.outputs(), depth.may_underflow())
+    };
+    // a procedure invoked with `call` only has access to the top 16 elements of the stack
+    assert_eq!(depth("call_many"), (16, 16, Some(16), false));
+    // the procedure of the library is analyzed from its MAST, so the analysis of `main` goes on
+    // with the 2 elements left by `wrapping_add`, before `drop_many` consumes 19 elements
+    assert_eq!(depth("main"), (17, 21, Some(0), true));
     Ok(())
 }
 
@@ -2328,7 +3492,7 @@ end";
         "  :                                      `-- found a -> here",
         "3 |",
         "  `----",
-        r#" help: expected "@", or "begin", or "const", or "export", or "proc", or "use", or end of file, or doc comment"#
+        r#" help: expected "@", or "begin", or "const", or "export", or "macro", or "proc", or "use", or end of file, or doc comment"#
     );
 
     // --- duplicate module import --------------------------------------------
@@ -2535,7 +3699,7 @@ fn invalid_empty_program() {
         "unexpected end of file",
         regex!(r#",-\[test[\d]+:1:1\]"#),
         "`----",
-        r#" help: expected "@", or "begin", or "const", or "export", or "proc", or "use", or doc comment"#
+        r#" help: expected "@", or "begin", or "const", or "export", or "macro", or "proc", or "use", or doc comment"#
     );
 
     assert_assembler_diagnostic!(
@@ -2544,7 +3708,7 @@ fn invalid_empty_program() {
         "unexpected end of file",
         regex!(r#",-\[test[\d]+:1:1\]"#),
         "  `----",
-        r#" help: expected "@", or "begin", or "const", or "export", or "proc", or "use", or doc comment"#
+        r#" help: expected "@", or "begin", or "const", or "export", or "macro", or "proc", or "use", or doc comment"#
     );
 }
 
@@ -2560,7 +3724,7 @@ fn invalid_program_unrecognized_token() {
         "  : ^^|^",
         "  :   `-- found a identifier here",
         "  `----",
-        r#" help: expected "@", or "begin", or "const", or "export", or "proc", or "use", or doc comment"#
+        r#" help: expected "@", or "begin", or "const", or "export", or "macro", or "proc", or "use", or doc comment"#
     );
 }
 
@@ -2574,7 +3738,7 @@ fn invalid_program_unmatched_begin() {
         regex!(r#",-\[test[\d]+:1:10\]"#),
         "1 | begin add",
         "  `----",
-        r#" help: expected ".", or primitive opcode (e.g. "add"), or "end", or control flow opcode (e.g. "if.true")"#
+        r#" help: expected ".", or "@", or primitive opcode (e.g. "add"), or "end", or control flow opcode (e.g. "if.true")"#
     );
 }
 
@@ -2590,7 +3754,7 @@ fn invalid_program_invalid_top_level_token() {
         "  :               ^|^",
         "  :                `-- found a mul here",
         "  `----",
-        r#" help: expected "@", or "begin", or "const", or "export", or "proc", or "use", or end of file, or doc comment"#
+        r#" help: expected "@", or "begin", or "const", or "export", or "macro", or "proc", or "use", or end of file, or doc comment"#
     );
 }
 
@@ -2607,7 +3771,7 @@ fn invalid_proc_missing_end_unexpected_begin() {
         "  :                  ^^|^^",
         "  :                    `-- found a begin here",
         "  `----",
-        r#" help: expected ".", or primitive opcode (e.g. "add"), or "end", or control flow opcode (e.g. "if.true")"#
+        r#" help: expected ".", or "@", or primitive opcode (e.g. "add"), or "end", or control flow opcode (e.g. "if.true")"#
     );
 }
 
@@ -2624,7 +3788,7 @@ fn invalid_proc_missing_end_unexpected_proc() {
         "  :                  ^^|^",
         "  :                    `-- found a proc here",
         "  `----",
-        r#" help: expected ".", or primitive opcode (e.g. "add"), or "end", or control flow opcode (e.g. "if.true")"#
+        r#" help: expected ".", or "@", or primitive opcode (e.g. "add"), or "end", or control flow opcode (e.g. "if.true")"#
     );
 }
 
@@ -2695,7 +3859,7 @@ fn invalid_if_missing_end_no_else() {
         regex!(r#",-\[test[\d]+:1:29\]"#),
         "1 | begin push.1 add if.true mul",
         "  `----",
-        r#" help: expected ".", or primitive opcode (e.g. "add"), or "else", or "end", or control flow opcode (e.g. "if.true")"#
+        r#" help: expected ".", or "@", or primitive opcode (e.g. "add"), or "else", or "end", or control flow opcode (e.g. "if.true")"#
     );
 }
 
@@ -2712,7 +3876,7 @@ fn invalid_else_with_no_if() {
         "  :                  ^^|^",
         "  :                    `-- found a else here",
         "  `----",
-        r#" help: expected primitive opcode (e.g. "add"), or "end", or control flow opcode (e.g. "if.true")"#
+        r#" help: expected "@", or primitive opcode (e.g. "add"), or "end", or control flow opcode (e.g. "if.true")"#
     );
 
     let source = source_file!(&context, "begin push.1 while.true add else mul end end");
@@ -2760,7 +3924,7 @@ fn invalid_if_else_no_matching_end() {
         regex!(r#",-\[test[\d]+:1:38\]"#),
         "1 | begin push.1 add if.true mul else add",
         "  `----",
-        r#" help: expected ".", or primitive opcode (e.g. "add"), or "end", or control flow opcode (e.g. "if.true")"#
+        r#" help: expected ".", or "@", or primitive opcode (e.g. "add"), or "end", or control flow opcode (e.g. "if.true")"#
     );
 }
 
@@ -2777,7 +3941,7 @@ fn invalid_repeat() -> TestResult {
         regex!(r#",-\[test[\d]+:1:31\]"#),
         "1 | begin push.1 add repeat.10 mul",
         "  `----",
-        r#" help: expected ".", or primitive opcode (e.g. "add"), or "end", or control flow opcode (e.g. "if.true")"#
+        r#" help: expected ".", or "@", or primitive opcode (e.g. "add"), or "end", or control flow opcode (e.g. "if.true")"#
     );
 
     // invalid iter count
@@ -2791,7 +3955,7 @@ fn invalid_repeat() -> TestResult {
         "  :                           ^|",
         "  :                            `-- found a identifier here",
         "  `----",
-        r#" help: expected primitive opcode (e.g. "add"), or control flow opcode (e.g. "if.true")"#
+        r#" help: expected "@", or primitive opcode (e.g. "add"), or control flow opcode (e.g. "if.true")"#
     );
 
     Ok(())
@@ -2835,7 +3999,7 @@ fn invalid_while() -> TestResult {
         regex!(r#",-\[test[\d]+:1:32\]"#),
         "1 | begin push.1 add while.true mul",
         "  `----",
-        r#" help: expected ".", or primitive opcode (e.g. "add"), or "end", or control flow opcode (e.g. "if.true")"#
+        r#" help: expected ".", or "@", or primitive opcode (e.g. "add"), or "end", or control flow opcode (e.g. "if.true")"#
     );
     Ok(())
 }
@@ -2974,6 +4138,424 @@ fn test_reexported_proc_with_same_name_as_local_proc_diff_locals() {
     let _program = assembler.assemble_program(program_source).unwrap();
 }
 
+// ANALYZED MODULES
+// ================================================================================================
+
+#[test]
+fn test_analyzed_module_serde() -> TestResult {
+    let context = TestContext::default();
+    let source = source_file!(
+        &context,
+        "\
+const.FOO=3
+
+#! Adds FOO to the top of the stack
+export.bar
+    push.FOO
+    add
+end
+
+proc.baz
+    exec.bar
+    mul
+end
+"
+    );
+    let assembler = Assembler::new(context.source_manager());
+    let options = crate::CompileOptions::new(ModuleKind::Library, "test::foo").unwrap();
+    let analyzed = assembler.analyze_module(source, options)?;
+
+    let bytes = analyzed.to_bytes();
+    let deserialized = Module::read_from_bytes(&bytes).into_diagnostic()?;
+    assert_eq!(analyzed.path(), deserialized.path());
+    assert_eq!(analyzed.kind(), deserialized.kind());
+    assert_str_eq!(analyzed.to_string(), deserialized.to_string());
+
+    // lowering the deserialized module yields the same library as lowering the original
+    let expected = Assembler::new(context.source_manager()).assemble_library([analyzed])?;
+    let actual = Assembler::default().assemble_library([Box::new(deserialized)])?;
+    assert_eq!(expected.digest(), actual.digest());
+
+    Ok(())
+}
+
+#[test]
+fn test_completions() -> TestResult {
+    use crate::completion::CompletionKind;
+
+    let context = TestContext::default();
+    let lib = "
+#! Increments the top of the stack
+export.incr
+    add.1
+end
+
+proc.helper
+    nop
+end
+";
+    let mut assembler = Assembler::new(context.source_manager());
+    assembler.add_module_with_options(
+        lib,
+        crate::CompileOptions::new(ModuleKind::Library, "test::lib").unwrap(),
+    )?;
+
+    let source = "\
+use.test::lib
+
+#! The answer
+const.ANSWER=42
+
+begin
+    push.ANSWER
+    exec.lib::incr
+end
+";
+    let module = assembler.analyze_module(source, crate::CompileOptions::default())?;
+
+    let completions_at = |text: &str| {
+        let offset = source.find(text).unwrap() + text.len();
+        assembler.completions(&module, source, offset)
+    };
+
+    let imported = completions_at("exec.lib::");
+    assert_eq!(imported.len(), 1);
+    assert_eq!(imported[0].label, "incr");
+    assert_eq!(imported[0].kind, CompletionKind::Procedure);
+    assert_eq!(imported[0].detail.as_deref(), Some("test::lib::incr"));
+    assert_eq!(imported[0].docs.as_deref(), Some("Increments the top of the stack\n"));
+
+    let constants = completions_at("push.AN");
+    assert_eq!(constants.len(), 1);
+    assert_eq!(constants[0].label, "ANSWER");
+    assert_eq!(constants[0].detail.as_deref(), Some("42"));
+    assert_eq!(constants[0].docs.as_deref(), Some("The answer\n"));
+
+    // the value of a constant is only guaranteed to be a literal once the module is analyzed
+    use crate::{
+        Felt, SourceSpan, Span,
+        ast::{Constant, ConstantExpr, ConstantOp, Ident},
+    };
+    let mut module = module.clone();
+    module.constants.push(Constant::new(
+        SourceSpan::default(),
+        Ident::new("DOUBLE").unwrap(),
+        ConstantExpr::BinaryOp {
+            span: SourceSpan::default(),
+            op: ConstantOp::Mul,
+            lhs: Box::new(ConstantExpr::Var(Ident::new("ANSWER").unwrap())),
+            rhs: Box::new(ConstantExpr::Literal(Span::unknown(Felt::new(2)))),
+        },
+    ));
+    let offset = source.find("push.").unwrap() + "push.".len();
+    let constants = assembler.completions(&module, source, offset);
+    assert_eq!(constants.len(), 2);
+    assert_eq!(constants[1].label, "DOUBLE");
+    assert_eq!(constants[1].detail.as_deref(), Some("ANSWER*2"));
+
+    let instructions = completions_at("    pu");
+    assert!(instructions.iter().all(|c| c.kind == CompletionKind::Instruction));
+    assert!(instructions.iter().any(|c| c.label == "push"));
+    assert!(!instructions.iter().any(|c| c.label == "push_mapval"));
+
+    let modules = completions_at("exec.");
+    assert!(modules.iter().any(|c| c.label == "lib" && c.kind == CompletionKind::Module));
+
+    Ok(())
+}
+
+#[test]
+fn test_check_only() -> TestResult {
+    let context = TestContext::default();
+    let foo = "export.foo push.1 end";
+    let bar = "use.lib::foo export.bar exec.foo::foo end";
+    let baz = "use.lib::foo export.baz exec.foo::missing end";
+    let assembler = Assembler::new(context.source_manager());
+
+    // references between the checked modules are resolved
+    assembler.check_library([
+        parse_module!(&context, "lib::foo", foo),
+        parse_module!(&context, "lib::bar", bar),
+    ])?;
+    let error = assembler
+        .check_library([
+            parse_module!(&context, "lib::foo", foo),
+            parse_module!(&context, "lib::baz", baz),
+        ])
+        .expect_err("expected the undefined procedure to be reported");
+    assert!(
+        format!("{error:?}").contains("undefined procedure"),
+        "unexpected error: {error:?}"
+    );
+
+    // the checked modules are not added to the assembler
+    assert!(assembler.module_graph().find_module(&"lib::foo".parse().unwrap()).is_none());
+
+    // programs are checked against the modules known to the assembler
+    let program = "use.lib::foo begin exec.foo::foo end";
+    assert!(assembler.check_program(program).is_err());
+    let assembler = assembler.with_module(parse_module!(&context, "lib::foo", foo))?;
+    assembler.check_program(program)?;
+
+    let error = assembler
+        .check_program("use.lib::foo proc.bar exec.foo::foo end")
+        .expect_err("expected the missing entrypoint to be reported");
+    assert!(format!("{error:?}").contains("entrypoint"), "unexpected error: {error:?}");
+
+    Ok(())
+}
+
+// INTRINSICS
+// ================================================================================================
+
+#[test]
+fn intrinsics_lowering() -> TestResult {
+    let context = TestContext::default();
+    let u64 = r#"
+        export.overflowing_add
+            swap movup.3 u32overflowing_add movup.3 movup.3 u32overflowing_add3
+        end
+
+        export.lt
+            movup.3 movup.2 u32overflowing_sub movdn.3 drop u32overflowing_sub swap eq.0 movup.2 and or
+        end
+
+        export.eqz
+            eq.0 swap eq.0 and
+        end
+    "#;
+    let u64 = parse_module!(&context, "std::math::u64", u64);
+    let std = Assembler::new(context.source_manager())
+        .with_isa_version(IsaVersion::V1_0)
+        .assemble_library([u64])?;
+
+    let source = "\
+    use.std::math::u64
+    begin
+        exec.u64::overflowing_add
+        exec.u64::lt
+        exec.u64::eqz
+    end";
+
+    // invocations of intrinsics are lowered when the targeted ISA version supports them
+    let program = Assembler::new(context.source_manager())
+        .with_library(&std)?
+        .assemble_program(source_file!(&context, source))?;
+    let expected = "\
+begin
+    join
+        basic_block u64add u64sub movdn2 drop drop end
+        external.0x";
+    assert!(format!("{program}").starts_with(expected), "{program}");
+    assert_eq!(program.isa_version(), IsaVersion::CURRENT);
+
+    // otherwise, the procedures implementing the intrinsics are invoked
+    let program = Assembler::new(context.source_manager())
+        .with_isa_version(IsaVersion::V1_0)
+        .with_library(&std)?
+        .assemble_program(source_file!(&context, source))?;
+    assert!(!format!("{program}").contains("u64"), "{program}");
+    assert_eq!(program.isa_version(), IsaVersion::V1_0);
+
+    // procedures which share the name of an intrinsic, but not its path, are invoked
+    let source = "\
+    proc.overflowing_add
+        add
+    end
+
+    begin
+        exec.overflowing_add
+    end";
+    let program = Assembler::new(context.source_manager())
+        .with_library(&std)?
+        .assemble_program(source_file!(&context, source))?;
+    assert!(!format!("{program}").contains("u64"), "{program}");
+
+    // procedures compiled from source are invoked even if they share the path of an intrinsic
+    let fake_u64 = parse_module!(&context, "std::math::u64", "export.lt add end");
+    let program = Assembler::new(context.source_manager())
+        .with_module(fake_u64)?
+        .assemble_program(source_file!(&context, "use.std::math::u64 begin exec.u64::lt end"))?;
+    assert!(!format!("{program}").contains("u64"), "{program}");
+
+    Ok(())
+}
+
+#[test]
+fn intrinsics_unsupported_instruction() -> TestResult {
+    let context = TestContext::default();
+    let source = source_file!(&context, "begin u64overflowing_add end");
+
+    let err = Assembler::new(context.source_manager())
+        .with_isa_version(IsaVersion::V1_0)
+        .assemble_program(source)
+        .expect_err("expected diagnostic to be raised, but compilation succeeded");
+    assert_diagnostic_lines!(
+        err,
+        "invalid instruction: 'u64overflowing_add' is not supported by version 1.0 of the instruction set",
+        regex!(r#",-\[test[\d]+:1:7\]"#),
+        "1 | begin u64overflowing_add end",
+        "  :       ^^^^^^^^^^^^^^^^^^",
+        "  `----",
+        " help: the instruction set version targeted by the assembler can be changed"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn u32_rotations_lowering() -> TestResult {
+    let context = TestContext::default();
+    let source = "begin u32rotl.8 u32rotr.8 end";
+
+    // rotations use the U32ROTL operation when the targeted ISA version supports it
+    let program = Assembler::new(context.source_manager())
+        .assemble_program(source_file!(&context, source))?;
+    let expected = "\
+begin
+    basic_block push(256) u32rotl push(16777216) u32rotl end
+end";
+    assert_str_eq!(format!("{program}"), expected);
+
+    // otherwise, they are computed via multiplication
+    let program = Assembler::new(context.source_manager())
+        .with_isa_version(IsaVersion::V1_1)
+        .assemble_program(source_file!(&context, source))?;
+    let expected = "\
+begin
+    basic_block push(256) u32mul add push(16777216) u32mul add end
+end";
+    assert_str_eq!(format!("{program}"), expected);
+    assert_eq!(program.isa_version(), IsaVersion::V1_1);
+
+    Ok(())
+}
+
+#[test]
+fn ctx_instruction() -> TestResult {
+    let context = TestContext::default();
+    let source = "begin ctx sdepth end";
+
+    let program = Assembler::new(context.source_manager())
+        .assemble_program(source_file!(&context, source))?;
+    let expected = "\
+begin
+    basic_block ctx sdepth end
+end";
+    assert_str_eq!(format!("{program}"), expected);
+
+    // the CTX operation is not available in earlier versions of the instruction set
+    let err = Assembler::new(context.source_manager())
+        .with_isa_version(IsaVersion::V1_2)
+        .assemble_program(source_file!(&context, source))
+        .expect_err("expected diagnostic to be raised, but compilation succeeded");
+    assert_diagnostic_lines!(
+        err,
+        "invalid instruction: 'ctx' is not supported by version 1.2 of the instruction set",
+        regex!(r#",-\[test[\d]+:1:7\]"#),
+        "1 | begin ctx sdepth end",
+        "  :       ^^^",
+        "  `----",
+        " help: the instruction set version targeted by the assembler can be changed"
+    );
+
+    Ok(())
+}
+
+// RESOURCE LIMITS
+// ================================================================================================
+
+#[test]
+fn resource_limits_nesting_depth() {
+    let source = "begin if.true repeat.2 while.true push.0 end end else push.1 end end";
+    let context = TestContext::default().with_resource_limits(ResourceLimits {
+        max_nesting_depth: 3,
+        ..ResourceLimits::UNLIMITED
+    });
+    assert!(context.assemble(source_file!(&context, source)).is_ok());
+
+    let context = TestContext::default().with_resource_limits(ResourceLimits {
+        max_nesting_depth: 2,
+        ..ResourceLimits::UNLIMITED
+    });
+    assert_assembler_diagnostic!(
+        context,
+        source_file!(&context, source),
+        "control flow blocks are nested too deeply",
+        regex!(r#",-\[test[\d]+:1:24\]"#),
+        "1 | begin if.true repeat.2 while.true push.0 end end else push.1 end end",
+        "  :                        ^^|^^",
+        "  :                          `-- this block exceeds the limit",
+        "  `----",
+        " help: blocks can be nested at most 2 levels deep"
+    );
+}
+
+#[test]
+fn resource_limits_forms_and_procedures() {
+    let source = "\
+const.A=1
+proc.foo push.A end
+proc.bar push.2 end
+begin exec.foo exec.bar end";
+    let context = TestContext::default().with_resource_limits(ResourceLimits {
+        max_forms: 4,
+        max_procedures: 3,
+        ..ResourceLimits::UNLIMITED
+    });
+    assert!(context.assemble(source_file!(&context, source)).is_ok());
+
+    let context = TestContext::default().with_resource_limits(ResourceLimits {
+        max_forms: 3,
+        ..ResourceLimits::UNLIMITED
+    });
+    assert_assembler_diagnostic!(
+        context,
+        source_file!(&context, source),
+        "syntax error",
+        "help: see emitted diagnostics for details",
+        "invalid module: too many items in module"
+    );
+
+    let context = TestContext::default().with_resource_limits(ResourceLimits {
+        max_procedures: 2,
+        ..ResourceLimits::UNLIMITED
+    });
+    assert_assembler_diagnostic!(
+        context,
+        source_file!(&context, source),
+        "syntax error",
+        "help: see emitted diagnostics for details",
+        "invalid module: too many procedures in module"
+    );
+}
+
+#[test]
+fn resource_limits_constant_eval_steps() {
+    // Evaluating `A` takes one step, and `B` takes three
+    let source = "\
+const.A=2
+const.B=A*3
+begin push.B end";
+    let context = TestContext::default().with_resource_limits(ResourceLimits {
+        max_constant_eval_steps: 4,
+        ..ResourceLimits::UNLIMITED
+    });
+    assert!(context.assemble(source_file!(&context, source)).is_ok());
+
+    let context = TestContext::default().with_resource_limits(ResourceLimits {
+        max_constant_eval_steps: 3,
+        ..ResourceLimits::UNLIMITED
+    });
+    assert_assembler_diagnostic!(
+        context,
+        source_file!(&context, source),
+        "syntax error",
+        "help: see emitted diagnostics for details",
+        "invalid module: constant expressions are too complex"
+    );
+}
+
 // PROGRAM SERIALIZATION AND DESERIALIZATION
 // ================================================================================================
 #[test]
@@ -0,0 +1,99 @@
+/*
+ * C bindings for assembling, executing, proving and verifying Miden VM programs.
+ *
+ * See README.md of the miden-capi crate for the conventions of this API: all functions which can
+ * fail return a MidenStatus, handles are opaque and must be released via the matching *_free
+ * function, and the message of the last error on the calling thread is available via
+ * miden_last_error_message().
+ */
+#ifndef MIDEN_H
+#define MIDEN_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* The number of bytes in the hash of a program. */
+#define MIDEN_PROGRAM_HASH_LEN 32
+
+/* The number of values returned from the stack by miden_execute() and miden_prove(). */
+#define MIDEN_STACK_OUTPUTS_LEN 16
+
+/* The status codes returned by the functions of this API; their values are stable. */
+typedef enum MidenStatus {
+    MIDEN_STATUS_OK = 0,
+    MIDEN_STATUS_NULL_POINTER = 1,
+    MIDEN_STATUS_INVALID_ARGUMENT = 2,
+    MIDEN_STATUS_ASSEMBLY_FAILED = 3,
+    MIDEN_STATUS_EXECUTION_FAILED = 4,
+    MIDEN_STATUS_PROVING_FAILED = 5,
+    MIDEN_STATUS_INVALID_PROOF = 6,
+    MIDEN_STATUS_VERIFICATION_FAILED = 7,
+    MIDEN_STATUS_INTERNAL_ERROR = 255,
+} MidenStatus;
+
+/* An opaque handle to an assembled program. */
+typedef struct MidenProgram MidenProgram;
+
+/* An opaque handle to a proof of program execution. */
+typedef struct MidenProof MidenProof;
+
+/* Returns the message of the last error on the calling thread, or NULL if no call failed yet. */
+const char *miden_last_error_message(void);
+
+/* ASSEMBLER */
+
+MidenStatus miden_assemble(const char *source, MidenProgram **program);
+
+MidenStatus miden_program_hash(const MidenProgram *program, uint8_t *hash);
+
+void miden_program_free(MidenProgram *program);
+
+/* PROCESSOR */
+
+MidenStatus miden_execute(const MidenProgram *program,
+                          const uint64_t *stack_inputs,
+                          size_t stack_inputs_len,
+                          const uint64_t *advice_stack,
+                          size_t advice_stack_len,
+                          uint64_t *stack_outputs);
+
+/* PROVER */
+
+MidenStatus miden_prove(const MidenProgram *program,
+                        const uint64_t *stack_inputs,
+                        size_t stack_inputs_len,
+                        const uint64_t *advice_stack,
+                        size_t advice_stack_len,
+                        uint32_t security_bits,
+                        uint64_t *stack_outputs,
+                        MidenProof **proof);
+
+uint32_t miden_proof_security_level(const MidenProof *proof);
+
+MidenStatus miden_proof_serialize(const MidenProof *proof, uint8_t **bytes, size_t *bytes_len);
+
+MidenStatus miden_proof_deserialize(const uint8_t *bytes, size_t bytes_len, MidenProof **proof);
+
+void miden_proof_free(MidenProof *proof);
+
+void miden_bytes_free(uint8_t *bytes, size_t bytes_len);
+
+/* VERIFIER */
+
+MidenStatus miden_verify(const uint8_t *program_hash,
+                         const uint64_t *stack_inputs,
+                         size_t stack_inputs_len,
+                         const uint64_t *stack_outputs,
+                         size_t stack_outputs_len,
+                         const MidenProof *proof,
+                         uint32_t *security_level);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* MIDEN_H */
@@ -0,0 +1,670 @@
+use std::{
+    cell::RefCell,
+    ffi::{CStr, CString, c_char},
+    panic::{self, AssertUnwindSafe},
+    ptr, slice,
+};
+
+use miden::{
+    AdviceInputs, Assembler, DefaultHost, Digest, ExecutionError, ExecutionProof, InputError,
+    Kernel, MemAdviceProvider, Program, ProgramInfo, ProvingOptions, StackInputs, StackOutputs,
+    VerificationError,
+    diagnostics::{Report, reporting::PrintDiagnostic},
+    execute_without_trace,
+};
+use stdlib::StdLibrary;
+
+// CONSTANTS
+// ================================================================================================
+
+/// The number of bytes in the hash of a program.
+pub const MIDEN_PROGRAM_HASH_LEN: usize = 32;
+
+/// The number of values returned from the stack by [miden_execute] and [miden_prove], and
+/// expected by [miden_verify].
+pub const MIDEN_STACK_OUTPUTS_LEN: usize = 16;
+
+// STATUS CODES
+// ================================================================================================
+
+/// The status code returned by the functions of this crate.
+///
+/// The values of the status codes are stable, and new codes are only ever added. On any code other
+/// than [MidenStatus::Ok], the message of the error is available via [miden_last_error_message].
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MidenStatus {
+    /// The call succeeded.
+    Ok = 0,
+    /// A required pointer argument was null.
+    NullPointer = 1,
+    /// An argument was malformed, e.g., a string was not valid UTF-8, or a value was not a valid
+    /// field element.
+    InvalidArgument = 2,
+    /// The program failed to assemble.
+    AssemblyFailed = 3,
+    /// The program failed to execute.
+    ExecutionFailed = 4,
+    /// The program executed, but the proof of its execution could not be generated.
+    ProvingFailed = 5,
+    /// The proof could not be deserialized.
+    InvalidProof = 6,
+    /// The proof is not a valid proof of the execution of the program against the specified inputs
+    /// and outputs.
+    VerificationFailed = 7,
+    /// An unexpected internal error occurred; this indicates a bug in the VM.
+    InternalError = 255,
+}
+
+/// Returns the message of the error returned by the last call on the current thread which failed,
+/// or null if no call failed yet.
+///
+/// The message is a NUL-terminated UTF-8 string owned by this library, which remains valid until
+/// the next call which fails on the same thread.
+#[unsafe(no_mangle)]
+pub extern "C" fn miden_last_error_message() -> *const c_char {
+    LAST_ERROR.with_borrow(|message| message.as_ref().map_or(ptr::null(), |msg| msg.as_ptr()))
+}
+
+// ASSEMBLER
+// ================================================================================================
+
+/// An opaque handle to an assembled program.
+pub struct MidenProgram(Program);
+
+/// Assembles the program with the specified source, linking it against the standard library, and
+/// writes a handle to the program to `program`.
+///
+/// The handle must be released via [miden_program_free].
+///
+/// # Safety
+/// `source` must point to a NUL-terminated string, and `program` must be valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn miden_assemble(
+    source: *const c_char,
+    program: *mut *mut MidenProgram,
+) -> MidenStatus {
+    ffi_call(|| {
+        let source = unsafe { c_str(source, "source") }?;
+        non_null(program, "program")?;
+
+        let mut assembler = Assembler::default();
+        assembler.add_library(StdLibrary::default()).map_err(Error::assembly)?;
+        let assembled = assembler.assemble_program(source).map_err(Error::assembly)?;
+
+        unsafe { program.write(Box::into_raw(Box::new(MidenProgram(assembled)))) };
+        Ok(())
+    })
+}
+
+/// Writes the [MIDEN_PROGRAM_HASH_LEN] bytes of the hash of the specified program to `hash`.
+///
+/// # Safety
+/// `program` must be a handle returned by [miden_assemble] which was not yet released, and `hash`
+/// must be valid for writes of [MIDEN_PROGRAM_HASH_LEN] bytes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn miden_program_hash(
+    program: *const MidenProgram,
+    hash: *mut u8,
+) -> MidenStatus {
+    ffi_call(|| {
+        let program = unsafe { handle(program, "program") }?;
+        non_null(hash, "hash")?;
+
+        let bytes = program.0.hash().as_bytes();
+        unsafe { ptr::copy_nonoverlapping(bytes.as_ptr(), hash, MIDEN_PROGRAM_HASH_LEN) };
+        Ok(())
+    })
+}
+
+/// Releases a handle returned by [miden_assemble]; null handles are ignored.
+///
+/// # Safety
+/// `program` must be null, or a handle returned by [miden_assemble] which was not yet released.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn miden_program_free(program: *mut MidenProgram) {
+    if !program.is_null() {
+        drop(unsafe { Box::from_raw(program) });
+    }
+}
+
+// PROCESSOR
+// ================================================================================================
+
+/// Executes the specified program, and writes the [MIDEN_STACK_OUTPUTS_LEN] values remaining on the
+/// stack to `stack_outputs`, starting with the value at the top of the stack.
+///
+/// The stack inputs are expected to be ordered as if they would be pushed onto the stack one by
+/// one, and the values of the advice stack as if they would be popped off the advice stack one by
+/// one. Either array may be null if its length is 0.
+///
+/// # Safety
+/// `program` must be a handle returned by [miden_assemble] which was not yet released, the input
+/// arrays must be valid for reads of their specified lengths, and `stack_outputs` must be valid for
+/// writes of [MIDEN_STACK_OUTPUTS_LEN] values.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn miden_execute(
+    program: *const MidenProgram,
+    stack_inputs: *const u64,
+    stack_inputs_len: usize,
+    advice_stack: *const u64,
+    advice_stack_len: usize,
+    stack_outputs: *mut u64,
+) -> MidenStatus {
+    ffi_call(|| {
+        let program = unsafe { handle(program, "program") }?;
+        let stack_inputs = unsafe { values(stack_inputs, stack_inputs_len, "stack_inputs") }?;
+        let advice_stack = unsafe { values(advice_stack, advice_stack_len, "advice_stack") }?;
+        non_null(stack_outputs, "stack_outputs")?;
+
+        let (stack_inputs, mut host) = prepare_execution(stack_inputs, advice_stack)?;
+        let outputs =
+            execute_without_trace(&program.0, stack_inputs, &mut host, Default::default())?;
+
+        unsafe { write_stack_outputs(&outputs, stack_outputs) };
+        Ok(())
+    })
+}
+
+// PROVER
+// ================================================================================================
+
+/// An opaque handle to a proof of program execution.
+pub struct MidenProof(ExecutionProof);
+
+/// Executes the specified program and proves its execution, targeting the specified number of
+/// bits of security, which must be either 96 or 128.
+///
+/// The values remaining on the stack are written to `stack_outputs` as by [miden_execute], and a
+/// handle to the proof to `proof`. The handle must be released via [miden_proof_free].
+///
+/// # Safety
+/// The arguments must satisfy the requirements of [miden_execute], and `proof` must be valid for
+/// writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn miden_prove(
+    program: *const MidenProgram,
+    stack_inputs: *const u64,
+    stack_inputs_len: usize,
+    advice_stack: *const u64,
+    advice_stack_len: usize,
+    security_bits: u32,
+    stack_outputs: *mut u64,
+    proof: *mut *mut MidenProof,
+) -> MidenStatus {
+    ffi_call(|| {
+        let program = unsafe { handle(program, "program") }?;
+        let stack_inputs = unsafe { values(stack_inputs, stack_inputs_len, "stack_inputs") }?;
+        let advice_stack = unsafe { values(advice_stack, advice_stack_len, "advice_stack") }?;
+        non_null(stack_outputs, "stack_outputs")?;
+        non_null(proof, "proof")?;
+
+        let options = match security_bits {
+            96 => ProvingOptions::with_96_bit_security(false),
+            128 => ProvingOptions::with_128_bit_security(false),
+            _ => {
+                return Err(Error::InvalidArgument {
+                    name: "security_bits",
+                    reason: format!("expected 96 or 128, but was {security_bits}"),
+                });
+            },
+        };
+        let (stack_inputs, mut host) = prepare_execution(stack_inputs, advice_stack)?;
+        let (outputs, execution_proof) =
+            miden::prove(&program.0, stack_inputs, &mut host, options)?;
+
+        unsafe {
+            write_stack_outputs(&outputs, stack_outputs);
+            proof.write(Box::into_raw(Box::new(MidenProof(execution_proof))));
+        }
+        Ok(())
+    })
+}
+
+/// Returns the number of bits of security of the specified proof, or 0 if the handle is null.
+///
+/// # Safety
+/// `proof` must be null, or a handle which was not yet released.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn miden_proof_security_level(proof: *const MidenProof) -> u32 {
+    unsafe { proof.as_ref() }.map_or(0, |proof| proof.0.security_level())
+}
+
+/// Serializes the specified proof, and writes a pointer to the serialized bytes to `bytes` and
+/// their number to `bytes_len`.
+///
+/// The bytes must be released via [miden_bytes_free].
+///
+/// # Safety
+/// `proof` must be a handle which was not yet released, and `bytes` and `bytes_len` must be valid
+/// for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn miden_proof_serialize(
+    proof: *const MidenProof,
+    bytes: *mut *mut u8,
+    bytes_len: *mut usize,
+) -> MidenStatus {
+    ffi_call(|| {
+        let proof = unsafe { handle(proof, "proof") }?;
+        non_null(bytes, "bytes")?;
+        non_null(bytes_len, "bytes_len")?;
+
+        let serialized = proof.0.to_bytes().into_boxed_slice();
+        unsafe {
+            bytes_len.write(serialized.len());
+            bytes.write(Box::into_raw(serialized).cast());
+        }
+        Ok(())
+    })
+}
+
+/// Deserializes a proof serialized via [miden_proof_serialize], and writes a handle to the proof
+/// to `proof`.
+///
+/// The handle must be released via [miden_proof_free].
+///
+/// # Safety
+/// `bytes` must be valid for reads of `bytes_len` bytes, and `proof` must be valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn miden_proof_deserialize(
+    bytes: *const u8,
+    bytes_len: usize,
+    proof: *mut *mut MidenProof,
+) -> MidenStatus {
+    ffi_call(|| {
+        let bytes = unsafe { values(bytes, bytes_len, "bytes") }?;
+        non_null(proof, "proof")?;
+
+        let execution_proof = ExecutionProof::from_bytes(bytes)
+            .map_err(|err| Error::InvalidProof(err.to_string()))?;
+
+        unsafe { proof.write(Box::into_raw(Box::new(MidenProof(execution_proof)))) };
+        Ok(())
+    })
+}
+
+/// Releases a handle returned by [miden_prove] or [miden_proof_deserialize]; null handles are
+/// ignored.
+///
+/// # Safety
+/// `proof` must be null, or a handle which was not yet released.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn miden_proof_free(proof: *mut MidenProof) {
+    if !proof.is_null() {
+        drop(unsafe { Box::from_raw(proof) });
+    }
+}
+
+/// Releases bytes returned by [miden_proof_serialize]; null pointers are ignored.
+///
+/// # Safety
+/// `bytes` must be null, or a pointer returned by [miden_proof_serialize] which was not yet
+/// released, and `bytes_len` the number of bytes returned with it.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn miden_bytes_free(bytes: *mut u8, bytes_len: usize) {
+    if !bytes.is_null() {
+        drop(unsafe { Box::from_raw(ptr::slice_from_raw_parts_mut(bytes, bytes_len)) });
+    }
+}
+
+// VERIFIER
+// ================================================================================================
+
+/// Verifies that the program with the specified hash was executed correctly against the specified
+/// inputs and outputs, and writes the security level of the proof to `security_level`, unless it
+/// is null.
+///
+/// The stack inputs are ordered as for [miden_execute], and the stack outputs as returned by
+/// [miden_execute], starting with the value at the top of the stack; values omitted at the end
+/// of the stack outputs are assumed to be 0. The program is expected to have been executed
+/// without a kernel.
+///
+/// # Safety
+/// `program_hash` must be valid for reads of [MIDEN_PROGRAM_HASH_LEN] bytes, the stack arrays must
+/// be valid for reads of their specified lengths, `proof` must be a handle which was not yet
+/// released, and `security_level` must be null or valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn miden_verify(
+    program_hash: *const u8,
+    stack_inputs: *const u64,
+    stack_inputs_len: usize,
+    stack_outputs: *const u64,
+    stack_outputs_len: usize,
+    proof: *const MidenProof,
+    security_level: *mut u32,
+) -> MidenStatus {
+    ffi_call(|| {
+        let program_hash = unsafe { values(program_hash, MIDEN_PROGRAM_HASH_LEN, "program_hash") }?;
+        let stack_inputs = unsafe { values(stack_inputs, stack_inputs_len, "stack_inputs") }?;
+        let stack_outputs = unsafe { values(stack_outputs, stack_outputs_len, "stack_outputs") }?;
+        let proof = unsafe { handle(proof, "proof") }?;
+
+        let program_hash = Digest::try_from(
+            <[u8; MIDEN_PROGRAM_HASH_LEN]>::try_from(program_hash).expect("length was checked"),
+        )
+        .map_err(|err| Error::InvalidArgument {
+            name: "program_hash",
+            reason: err.to_string(),
+        })?;
+        let stack_inputs = StackInputs::try_from_ints(stack_inputs.iter().copied())
+            .map_err(|err| Error::invalid_argument("stack_inputs", err))?;
+        let stack_outputs = StackOutputs::try_from_ints(stack_outputs.iter().copied())
+            .map_err(|err| Error::invalid_argument("stack_outputs", err))?;
+
+        let program_info = ProgramInfo::new(program_hash, Kernel::default());
+        let level = miden::verify(program_info, stack_inputs, stack_outputs, proof.0.clone())?;
+
+        if let Some(security_level) = unsafe { security_level.as_mut() } {
+            *security_level = level;
+        }
+        Ok(())
+    })
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+thread_local! {
+    /// The message of the error returned by the last call which failed on this thread.
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// Runs the body of an exported function, and converts its result, or a panic, into a status code,
+/// recording the message of the error for [miden_last_error_message].
+///
+/// Panics must not unwind across the C ABI, so they are caught here.
+fn ffi_call(body: impl FnOnce() -> Result<(), Error>) -> MidenStatus {
+    let (status, message) = match panic::catch_unwind(AssertUnwindSafe(body)) {
+        Ok(Ok(())) => return MidenStatus::Ok,
+        Ok(Err(err)) => (err.status(), err.to_string()),
+        Err(payload) => {
+            let reason = payload
+                .downcast_ref::<&str>()
+                .map(|reason| reason.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "unknown panic".to_string());
+            (MidenStatus::InternalError, format!("internal error: {reason}"))
+        },
+    };
+
+    let message = CString::new(message.replace('\0', " ")).expect("NUL bytes were replaced");
+    LAST_ERROR.set(Some(message));
+    status
+}
+
+/// Returns an error if the specified output pointer is null.
+fn non_null<T>(ptr: *mut T, name: &'static str) -> Result<(), Error> {
+    if ptr.is_null() {
+        Err(Error::NullPointer(name))
+    } else {
+        Ok(())
+    }
+}
+
+/// Returns a reference to the value behind the specified handle.
+///
+/// # Safety
+/// `ptr` must be null, or valid for reads for the lifetime of the returned reference.
+unsafe fn handle<'a, T>(ptr: *const T, name: &'static str) -> Result<&'a T, Error> {
+    unsafe { ptr.as_ref() }.ok_or(Error::NullPointer(name))
+}
+
+/// Returns the array with the specified pointer and length; the pointer may be null if the length
+/// is 0.
+///
+/// # Safety
+/// `ptr` must be null, or valid for reads of `len` values for the lifetime of the returned slice.
+unsafe fn values<'a, T>(ptr: *const T, len: usize, name: &'static str) -> Result<&'a [T], Error> {
+    if len == 0 {
+        Ok(&[])
+    } else if ptr.is_null() {
+        Err(Error::NullPointer(name))
+    } else {
+        Ok(unsafe { slice::from_raw_parts(ptr, len) })
+    }
+}
+
+/// Returns the string behind the specified pointer.
+///
+/// # Safety
+/// `ptr` must be null, or point to a NUL-terminated string which is valid for reads for the
+/// lifetime of the returned string.
+unsafe fn c_str<'a>(ptr: *const c_char, name: &'static str) -> Result<&'a str, Error> {
+    if ptr.is_null() {
+        return Err(Error::NullPointer(name));
+    }
+    unsafe { CStr::from_ptr(ptr) }
+        .to_str()
+        .map_err(|err| Error::invalid_argument(name, err))
+}
+
+/// Returns the stack inputs and the host to execute a program with, given the inputs passed to
+/// [miden_execute] or [miden_prove].
+fn prepare_execution(
+    stack_inputs: &[u64],
+    advice_stack: &[u64],
+) -> Result<(StackInputs, DefaultHost<MemAdviceProvider>), Error> {
+    let stack_inputs = StackInputs::try_from_ints(stack_inputs.iter().copied())
+        .map_err(|err| Error::invalid_argument("stack_inputs", err))?;
+    let advice_inputs = AdviceInputs::default()
+        .with_stack_values(advice_stack.iter().copied())
+        .map_err(|err: InputError| Error::invalid_argument("advice_stack", err))?;
+
+    let mut host = DefaultHost::new(MemAdviceProvider::from(advice_inputs));
+    host.load_mast_forest(StdLibrary::default().mast_forest().clone())?;
+    Ok((stack_inputs, host))
+}
+
+/// Writes the [MIDEN_STACK_OUTPUTS_LEN] values of the specified stack outputs to `dst`.
+///
+/// # Safety
+/// `dst` must be valid for writes of [MIDEN_STACK_OUTPUTS_LEN] values.
+unsafe fn write_stack_outputs(stack_outputs: &StackOutputs, dst: *mut u64) {
+    for (i, value) in stack_outputs.iter().take(MIDEN_STACK_OUTPUTS_LEN).enumerate() {
+        unsafe { dst.add(i).write(value.as_int()) };
+    }
+}
+
+// ERRORS
+// ================================================================================================
+
+/// An error returned by an exported function, which is reported to the caller as a [MidenStatus]
+/// and the message of the error.
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error("`{0}` must not be null")]
+    NullPointer(&'static str),
+    #[error("invalid `{name}`: {reason}")]
+    InvalidArgument { name: &'static str, reason: String },
+    /// The program failed to assemble; this contains the diagnostics of the assembler, rendered as
+    /// text.
+    #[error("{0}")]
+    Assembly(String),
+    #[error(transparent)]
+    Execution(#[from] ExecutionError),
+    #[error("failed to deserialize the proof: {0}")]
+    InvalidProof(String),
+    #[error(transparent)]
+    Verification(#[from] VerificationError),
+}
+
+impl Error {
+    fn assembly(report: Report) -> Self {
+        Self::Assembly(PrintDiagnostic::narrated(report).to_string())
+    }
+
+    fn invalid_argument(name: &'static str, reason: impl ToString) -> Self {
+        Self::InvalidArgument { name, reason: reason.to_string() }
+    }
+
+    fn status(&self) -> MidenStatus {
+        match self {
+            Self::NullPointer(_) => MidenStatus::NullPointer,
+            Self::InvalidArgument { .. } => MidenStatus::InvalidArgument,
+            Self::Assembly(_) => MidenStatus::AssemblyFailed,
+            Self::Execution(
+                ExecutionError::ProverError(_) | ExecutionError::ProofSelfCheckFailed { .. },
+            ) => MidenStatus::ProvingFailed,
+            Self::Execution(_) => MidenStatus::ExecutionFailed,
+            Self::InvalidProof(_) => MidenStatus::InvalidProof,
+            Self::Verification(_) => MidenStatus::VerificationFailed,
+        }
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn last_error() -> String {
+        let message = miden_last_error_message();
+        assert!(!message.is_null());
+        unsafe { CStr::from_ptr(message) }.to_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn prove_and_verify_via_c_api() {
+        let source = c"use.std::math::u64 begin adv_push.1 add push.0.3 exec.u64::wrapping_add end";
+        let mut program = ptr::null_mut();
+        assert_eq!(unsafe { miden_assemble(source.as_ptr(), &mut program) }, MidenStatus::Ok);
+        let mut hash = [0u8; MIDEN_PROGRAM_HASH_LEN];
+        assert_eq!(unsafe { miden_program_hash(program, hash.as_mut_ptr()) }, MidenStatus::Ok);
+
+        let stack_inputs = [0, 5];
+        let advice_stack = [2];
+        let mut outputs = [0u64; MIDEN_STACK_OUTPUTS_LEN];
+        let status = unsafe {
+            miden_execute(
+                program,
+                stack_inputs.as_ptr(),
+                2,
+                advice_stack.as_ptr(),
+                1,
+                outputs.as_mut_ptr(),
+            )
+        };
+        assert_eq!(status, MidenStatus::Ok);
+        assert_eq!(outputs[..2], [10, 0]);
+
+        let mut proven_outputs = [0u64; MIDEN_STACK_OUTPUTS_LEN];
+        let mut proof = ptr::null_mut();
+        let status = unsafe {
+            miden_prove(
+                program,
+                stack_inputs.as_ptr(),
+                2,
+                advice_stack.as_ptr(),
+                1,
+                96,
+                proven_outputs.as_mut_ptr(),
+                &mut proof,
+            )
+        };
+        assert_eq!(status, MidenStatus::Ok);
+        assert_eq!(proven_outputs, outputs);
+
+        // the proof survives a serialization round trip
+        let (mut bytes, mut bytes_len) = (ptr::null_mut(), 0);
+        assert_eq!(
+            unsafe { miden_proof_serialize(proof, &mut bytes, &mut bytes_len) },
+            MidenStatus::Ok
+        );
+        let mut deserialized = ptr::null_mut();
+        assert_eq!(
+            unsafe { miden_proof_deserialize(bytes, bytes_len, &mut deserialized) },
+            MidenStatus::Ok
+        );
+
+        let mut security_level = 0;
+        let status = unsafe {
+            miden_verify(
+                hash.as_ptr(),
+                stack_inputs.as_ptr(),
+                2,
+                outputs.as_ptr(),
+                outputs.len(),
+                deserialized,
+                &mut security_level,
+            )
+        };
+        assert_eq!(status, MidenStatus::Ok);
+        assert_eq!(security_level, unsafe { miden_proof_security_level(proof) });
+
+        // a proof does not verify against different outputs
+        outputs[0] += 1;
+        let status = unsafe {
+            miden_verify(
+                hash.as_ptr(),
+                stack_inputs.as_ptr(),
+                2,
+                outputs.as_ptr(),
+                16,
+                proof,
+                ptr::null_mut(),
+            )
+        };
+        assert_eq!(status, MidenStatus::VerificationFailed);
+
+        let mut truncated = ptr::null_mut();
+        assert_eq!(
+            unsafe { miden_proof_deserialize(bytes.add(1), bytes_len - 1, &mut truncated) },
+            MidenStatus::InvalidProof
+        );
+        assert!(truncated.is_null());
+
+        unsafe {
+            miden_bytes_free(bytes, bytes_len);
+            miden_proof_free(deserialized);
+            miden_proof_free(proof);
+            miden_program_free(program);
+        }
+    }
+
+    #[test]
+    fn errors_are_reported_via_status_codes() {
+        let mut program = ptr::null_mut();
+        assert_eq!(unsafe { miden_assemble(ptr::null(), &mut program) }, MidenStatus::NullPointer);
+        assert_eq!(last_error(), "`source` must not be null");
+
+        let status = unsafe { miden_assemble(c"begin push.1 ad end".as_ptr(), &mut program) };
+        assert_eq!(status, MidenStatus::AssemblyFailed);
+        assert!(program.is_null());
+
+        assert_eq!(
+            unsafe { miden_assemble(c"begin adv_push.1 add end".as_ptr(), &mut program) },
+            MidenStatus::Ok
+        );
+        let mut outputs = [0u64; MIDEN_STACK_OUTPUTS_LEN];
+        let status =
+            unsafe { miden_execute(program, ptr::null(), 0, ptr::null(), 0, outputs.as_mut_ptr()) };
+        assert_eq!(status, MidenStatus::ExecutionFailed);
+
+        let invalid_inputs = [u64::MAX];
+        let status = unsafe {
+            miden_execute(program, invalid_inputs.as_ptr(), 1, ptr::null(), 0, outputs.as_mut_ptr())
+        };
+        assert_eq!(status, MidenStatus::InvalidArgument);
+        assert!(last_error().starts_with("invalid `stack_inputs`"));
+
+        let mut proof = ptr::null_mut();
+        let status = unsafe {
+            miden_prove(
+                program,
+                ptr::null(),
+                0,
+                ptr::null(),
+                0,
+                100,
+                outputs.as_mut_ptr(),
+                &mut proof,
+            )
+        };
+        assert_eq!(status, MidenStatus::InvalidArgument);
+        assert!(proof.is_null());
+
+        unsafe { miden_program_free(program) };
+    }
+}
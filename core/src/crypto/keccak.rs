@@ -0,0 +1,118 @@
+use core::ops::Deref;
+
+use miden_crypto::hash::{Digest, ElementHasher, Hasher};
+use sha3::Digest as _;
+
+use crate::{
+    Felt, FieldElement,
+    utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable},
+};
+
+#[cfg(test)]
+mod tests;
+
+// KECCAK DIGEST
+// ================================================================================================
+
+/// 32-byte output of the Keccak-256 hash function.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct KeccakDigest([u8; 32]);
+
+impl KeccakDigest {
+    /// Returns the bytes of the specified digests, concatenated in order.
+    fn digests_as_bytes(digests: &[KeccakDigest]) -> impl Iterator<Item = &[u8]> {
+        digests.iter().map(|digest| digest.0.as_slice())
+    }
+}
+
+impl Deref for KeccakDigest {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<[u8; 32]> for KeccakDigest {
+    fn from(value: [u8; 32]) -> Self {
+        Self(value)
+    }
+}
+
+impl From<KeccakDigest> for [u8; 32] {
+    fn from(value: KeccakDigest) -> Self {
+        value.0
+    }
+}
+
+impl Digest for KeccakDigest {
+    fn as_bytes(&self) -> [u8; 32] {
+        self.0
+    }
+}
+
+impl Serializable for KeccakDigest {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_bytes(&self.0);
+    }
+}
+
+impl Deserializable for KeccakDigest {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        source.read_array().map(Self)
+    }
+}
+
+// KECCAK-256
+// ================================================================================================
+
+/// Keccak-256 hasher, as used by Ethereum.
+///
+/// Field elements are hashed as the concatenation of the little-endian bytes of their canonical
+/// representations, which makes the commitments of proofs generated with this hasher cheap to
+/// check in environments with a native Keccak-256 precompile.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Keccak256;
+
+impl Hasher for Keccak256 {
+    /// Keccak-256 collision resistance is 128-bits for 32-bytes output.
+    const COLLISION_RESISTANCE: u32 = 128;
+
+    type Digest = KeccakDigest;
+
+    fn hash(bytes: &[u8]) -> Self::Digest {
+        KeccakDigest(sha3::Keccak256::digest(bytes).into())
+    }
+
+    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        Self::merge_many(values)
+    }
+
+    fn merge_many(values: &[Self::Digest]) -> Self::Digest {
+        let mut hasher = sha3::Keccak256::new();
+        KeccakDigest::digests_as_bytes(values).for_each(|bytes| hasher.update(bytes));
+        KeccakDigest(hasher.finalize().into())
+    }
+
+    fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
+        let mut hasher = sha3::Keccak256::new();
+        hasher.update(seed.0);
+        hasher.update(value.to_le_bytes());
+        KeccakDigest(hasher.finalize().into())
+    }
+}
+
+impl ElementHasher for Keccak256 {
+    type BaseField = Felt;
+
+    fn hash_elements<E>(elements: &[E]) -> Self::Digest
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        let mut hasher = sha3::Keccak256::new();
+        for element in E::slice_as_base_elements(elements) {
+            hasher.update(element.as_int().to_le_bytes());
+        }
+        KeccakDigest(hasher.finalize().into())
+    }
+}
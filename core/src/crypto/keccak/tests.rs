@@ -0,0 +1,51 @@
+use super::*;
+use crate::{QuadExtension, utils::SliceReader};
+
+#[test]
+fn keccak256_matches_known_digest() {
+    let digest = Keccak256::hash(b"");
+    let expected = [
+        0xc5, 0xd2, 0x46, 0x01, 0x86, 0xf7, 0x23, 0x3c, 0x92, 0x7e, 0x7d, 0xb2, 0xdc, 0xc7, 0x03,
+        0xc0, 0xe5, 0x00, 0xb6, 0x53, 0xca, 0x82, 0x27, 0x3b, 0x7b, 0xfa, 0xd8, 0x04, 0x5d, 0x85,
+        0xa4, 0x70,
+    ];
+    assert_eq!(digest.as_bytes(), expected);
+}
+
+#[test]
+fn keccak256_merge_hashes_concatenated_digests() {
+    let a = Keccak256::hash(b"a");
+    let b = Keccak256::hash(b"b");
+
+    let mut bytes = [0; 64];
+    bytes[..32].copy_from_slice(&a);
+    bytes[32..].copy_from_slice(&b);
+    assert_eq!(Keccak256::merge(&[a, b]), Keccak256::hash(&bytes));
+    assert_eq!(Keccak256::merge_many(&[a, b]), Keccak256::hash(&bytes));
+
+    let mut bytes = [0; 40];
+    bytes[..32].copy_from_slice(&a);
+    bytes[32..].copy_from_slice(&7_u64.to_le_bytes());
+    assert_eq!(Keccak256::merge_with_int(a, 7), Keccak256::hash(&bytes));
+}
+
+#[test]
+fn keccak256_hashes_canonical_element_bytes() {
+    let elements = [Felt::new(1), Felt::new(u64::MAX), Felt::new(42)];
+    let bytes: alloc::vec::Vec<u8> =
+        elements.iter().flat_map(|element| element.as_int().to_le_bytes()).collect();
+    assert_eq!(Keccak256::hash_elements(&elements), Keccak256::hash(&bytes));
+
+    // extension field elements are hashed as their base field components
+    let extension = [QuadExtension::new(elements[0], elements[1])];
+    assert_eq!(Keccak256::hash_elements(&extension), Keccak256::hash_elements(&elements[..2]));
+}
+
+#[test]
+fn keccak_digest_serialization() {
+    let digest = Keccak256::hash(b"miden");
+    let bytes = digest.to_bytes();
+    assert_eq!(bytes.len(), 32);
+    let mut reader = SliceReader::new(&bytes);
+    assert_eq!(KeccakDigest::read_from(&mut reader).unwrap(), digest);
+}
@@ -0,0 +1,111 @@
+use alloc::vec::Vec;
+
+use miden_crypto::{
+    Felt, WORD_SIZE, Word, ZERO,
+    hash::rpo::{Rpo256, RpoDigest},
+};
+
+use crate::utils::{
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable, ToElements,
+};
+
+// EXECUTION ENVIRONMENT
+// ================================================================================================
+
+/// A read-only block describing the context in which a program is executed, e.g., the block of a
+/// chain in which a transaction is included.
+///
+/// The environment is provided to the VM by the host, and programs read it via the environment
+/// procedures of the `std::sys` module of the standard library. The environment is also part of
+/// the public inputs of a proof (via [ExecutionEnvironment::commitment]), so a proof of execution
+/// is checked against the environment the verifier expects.
+///
+/// The environment is made up of the following values:
+/// - the number of the block in which the program is executed,
+/// - the timestamp of that block,
+/// - a seed, which programs can use to derive deterministic pseudo-random values,
+/// - the ID of the caller of the program (e.g., an account ID).
+///
+/// The default environment has all values set to zero.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ExecutionEnvironment {
+    block_num: u32,
+    timestamp: u32,
+    seed: Word,
+    caller_id: Word,
+}
+
+impl ExecutionEnvironment {
+    /// The number of field elements the environment is encoded with.
+    pub const NUM_ELEMENTS: usize = 3 * WORD_SIZE;
+
+    /// Returns a new [ExecutionEnvironment] instantiated with the specified values.
+    pub fn new(block_num: u32, timestamp: u32, seed: Word, caller_id: Word) -> Self {
+        Self { block_num, timestamp, seed, caller_id }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of the block in which the program is executed.
+    pub fn block_num(&self) -> u32 {
+        self.block_num
+    }
+
+    /// Returns the timestamp of the block in which the program is executed.
+    pub fn timestamp(&self) -> u32 {
+        self.timestamp
+    }
+
+    /// Returns the seed of the execution.
+    pub fn seed(&self) -> Word {
+        self.seed
+    }
+
+    /// Returns the ID of the caller of the program.
+    pub fn caller_id(&self) -> Word {
+        self.caller_id
+    }
+
+    /// Returns the commitment to this environment, i.e., the hash of its elements (see
+    /// [ToElements]).
+    pub fn commitment(&self) -> RpoDigest {
+        Rpo256::hash_elements(&self.to_elements())
+    }
+}
+
+impl ToElements for ExecutionEnvironment {
+    /// Returns the elements encoding this environment, i.e., three words laid out as
+    /// `[block_num, timestamp, 0, 0, SEED, CALLER_ID]`.
+    fn to_elements(&self) -> Vec<Felt> {
+        let mut result = Vec::with_capacity(Self::NUM_ELEMENTS);
+        result.extend_from_slice(&[
+            Felt::from(self.block_num),
+            Felt::from(self.timestamp),
+            ZERO,
+            ZERO,
+        ]);
+        result.extend_from_slice(&self.seed);
+        result.extend_from_slice(&self.caller_id);
+        result
+    }
+}
+
+impl Serializable for ExecutionEnvironment {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u32(self.block_num);
+        target.write_u32(self.timestamp);
+        self.seed.write_into(target);
+        self.caller_id.write_into(target);
+    }
+}
+
+impl Deserializable for ExecutionEnvironment {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let block_num = source.read_u32()?;
+        let timestamp = source.read_u32()?;
+        let seed = source.read()?;
+        let caller_id = source.read()?;
+        Ok(Self { block_num, timestamp, seed, caller_id })
+    }
+}
@@ -0,0 +1,132 @@
+use core::fmt;
+
+use crate::{
+    Felt, Operation, Word, ZERO,
+    utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable},
+};
+
+// ISA VERSION
+// ================================================================================================
+
+/// A version of the instruction set of Miden VM.
+///
+/// Every [Program](crate::Program) records the version of the instruction set it was assembled
+/// for, and the processor and the verifier refuse programs targeting a version they don't
+/// support, rather than mis-executing them.
+///
+/// Versions follow the usual compatibility rules: minor versions only add operations, while major
+/// versions may change or remove existing ones. Thus, a VM supports the programs targeting any
+/// version with the same major version and a minor version which is not greater than its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct IsaVersion {
+    major: u8,
+    minor: u8,
+}
+
+impl IsaVersion {
+    /// The initial version of the instruction set.
+    pub const V1_0: Self = Self::new(1, 0);
+
+    /// The version of the instruction set which adds the u64 operations `U64ADD` and `U64SUB`.
+    pub const V1_1: Self = Self::new(1, 1);
+
+    /// The version of the instruction set which adds the `U32ROTL` operation.
+    pub const V1_2: Self = Self::new(1, 2);
+
+    /// The version of the instruction set which adds the `CTX` operation.
+    pub const V1_3: Self = Self::new(1, 3);
+
+    /// The version of the instruction set implemented by this crate.
+    pub const CURRENT: Self = Self::V1_3;
+
+    /// Returns a new [IsaVersion] instantiated with the specified major and minor versions.
+    pub const fn new(major: u8, minor: u8) -> Self {
+        Self { major, minor }
+    }
+
+    /// Returns the major version.
+    pub const fn major(&self) -> u8 {
+        self.major
+    }
+
+    /// Returns the minor version.
+    pub const fn minor(&self) -> u8 {
+        self.minor
+    }
+
+    /// Returns the word encoding this version in the public inputs of proofs, i.e.,
+    /// `[major, minor, 0, 0]`.
+    pub fn to_word(&self) -> Word {
+        [Felt::from(self.major), Felt::from(self.minor), ZERO, ZERO]
+    }
+
+    /// Returns true if a VM implementing this version can execute programs targeting `version`.
+    pub const fn supports(&self, version: IsaVersion) -> bool {
+        self.major == version.major && self.minor >= version.minor
+    }
+
+    /// Returns true if the specified operation is part of this version of the instruction set.
+    pub fn supports_operation(&self, op: &Operation) -> bool {
+        self.supports(op.isa_version())
+    }
+}
+
+impl Default for IsaVersion {
+    fn default() -> Self {
+        Self::CURRENT
+    }
+}
+
+impl fmt::Display for IsaVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+impl Serializable for IsaVersion {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8(self.major);
+        target.write_u8(self.minor);
+    }
+}
+
+impl Deserializable for IsaVersion {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let major = source.read_u8()?;
+        let minor = source.read_u8()?;
+        Ok(Self::new(major, minor))
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::IsaVersion;
+    use crate::Operation;
+
+    #[test]
+    fn isa_version_compatibility() {
+        let current = IsaVersion::new(1, 2);
+
+        assert!(current.supports(IsaVersion::new(1, 0)));
+        assert!(current.supports(IsaVersion::new(1, 2)));
+        assert!(!current.supports(IsaVersion::new(1, 3)));
+        assert!(!current.supports(IsaVersion::new(0, 9)));
+        assert!(!current.supports(IsaVersion::new(2, 0)));
+
+        assert!(IsaVersion::CURRENT.supports_operation(&Operation::Add));
+        assert!(!IsaVersion::new(2, 0).supports_operation(&Operation::Add));
+
+        assert!(IsaVersion::CURRENT.supports_operation(&Operation::U64add));
+        assert!(!IsaVersion::V1_0.supports_operation(&Operation::U64sub));
+
+        assert!(IsaVersion::CURRENT.supports_operation(&Operation::U32rotl));
+        assert!(!IsaVersion::V1_1.supports_operation(&Operation::U32rotl));
+
+        assert!(IsaVersion::CURRENT.supports_operation(&Operation::Ctx));
+        assert!(!IsaVersion::V1_2.supports_operation(&Operation::Ctx));
+    }
+}
@@ -15,6 +15,8 @@ use crate::{
 /// The internally-stored list always has a consistent order, regardless of the order of procedure
 /// list used to instantiate a kernel.
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "Vec<RpoDigest>", into = "Vec<RpoDigest>"))]
 pub struct Kernel(Vec<RpoDigest>);
 
 impl Kernel {
@@ -39,6 +41,25 @@ impl Kernel {
         }
     }
 
+    /// Returns a new [Kernel] containing the procedures of all of the specified kernels.
+    ///
+    /// Procedures contained in more than one of the kernels are included only once, so kernels
+    /// which share common procedures can be composed.
+    ///
+    /// # Errors
+    /// Returns an error if the resulting kernel contains more than
+    /// [Kernel::MAX_NUM_PROCEDURES] procedures.
+    pub fn merge<'a>(kernels: impl IntoIterator<Item = &'a Kernel>) -> Result<Self, KernelError> {
+        let mut hashes = kernels
+            .into_iter()
+            .flat_map(|kernel| kernel.proc_hashes().iter().copied())
+            .collect::<Vec<_>>();
+        hashes.sort_by_key(|v| v.as_bytes());
+        hashes.dedup();
+
+        Self::new(&hashes)
+    }
+
     /// Returns true if this kernel does not contain any procedures.
     pub fn is_empty(&self) -> bool {
         self.0.is_empty()
@@ -55,6 +76,20 @@ impl Kernel {
     }
 }
 
+impl TryFrom<Vec<RpoDigest>> for Kernel {
+    type Error = KernelError;
+
+    fn try_from(proc_hashes: Vec<RpoDigest>) -> Result<Self, Self::Error> {
+        Self::new(&proc_hashes)
+    }
+}
+
+impl From<Kernel> for Vec<RpoDigest> {
+    fn from(kernel: Kernel) -> Self {
+        kernel.0
+    }
+}
+
 // this is required by AIR as public inputs will be serialized with the proof
 impl Serializable for Kernel {
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
@@ -71,3 +106,36 @@ impl Deserializable for Kernel {
         Ok(Self(kernel))
     }
 }
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use miden_crypto::{Felt, hash::rpo::RpoDigest};
+
+    use super::{Kernel, KernelError};
+
+    fn digest(value: u64) -> RpoDigest {
+        RpoDigest::new([Felt::new(value); 4])
+    }
+
+    #[test]
+    fn merge_kernels() {
+        let a = Kernel::new(&[digest(1), digest(2)]).unwrap();
+        let b = Kernel::new(&[digest(3), digest(2)]).unwrap();
+
+        // procedures shared by both kernels are included once
+        let merged = Kernel::merge([&a, &b]).unwrap();
+        assert_eq!(merged, Kernel::new(&[digest(1), digest(2), digest(3)]).unwrap());
+        assert!(a.proc_hashes().iter().chain(b.proc_hashes()).all(|&h| merged.contains_proc(h)));
+
+        let hashes = (0..Kernel::MAX_NUM_PROCEDURES as u64).map(digest).collect::<Vec<_>>();
+        let full = Kernel::new(&hashes).unwrap();
+        assert_eq!(Kernel::merge([&full, &a]).unwrap(), full);
+        let c = Kernel::new(&[digest(Kernel::MAX_NUM_PROCEDURES as u64)]).unwrap();
+        assert!(matches!(Kernel::merge([&full, &c]), Err(KernelError::TooManyProcedures(_, _))));
+    }
+}
@@ -56,10 +56,18 @@ pub mod errors;
 mod program;
 pub use program::{Program, ProgramInfo};
 
+mod environment;
+pub use environment::ExecutionEnvironment;
+
+mod isa;
+pub use isa::IsaVersion;
+
 mod kernel;
 pub use kernel::Kernel;
 pub use miden_crypto::{EMPTY_WORD, ONE, WORD_SIZE, Word, ZERO};
 pub mod crypto {
+    mod keccak;
+
     pub mod merkle {
         pub use miden_crypto::merkle::{
             DefaultMerkleStore, EmptySubtreeRoots, InnerNodeInfo, LeafIndex, MerkleError,
@@ -75,6 +83,8 @@ pub mod crypto {
             rpo::{Rpo256, RpoDigest},
             rpx::{Rpx256, RpxDigest},
         };
+
+        pub use super::keccak::{Keccak256, KeccakDigest};
     }
 
     pub mod random {
@@ -117,8 +127,8 @@ pub mod prettier {
 
 mod operations;
 pub use operations::{
-    AssemblyOp, DebugOptions, Decorator, DecoratorIterator, DecoratorList, Operation,
-    SignatureKind, opcode_constants::*,
+    AssemblyOp, Chiplet, DebugOptions, Decorator, DecoratorIterator, DecoratorKinds, DecoratorList,
+    Operation, OperationMetadata, SignatureKind, opcode_constants::*,
 };
 
 pub mod stack;
@@ -0,0 +1,319 @@
+use alloc::vec::Vec;
+use core::{fmt, ops::Index};
+
+use super::{BasicBlockNode, MastForest, MastNode, MastNodeId, OpBatch};
+use crate::Operation;
+
+// MAST FOREST ANALYSIS
+// ================================================================================================
+
+/// Static analysis of all nodes in a [MastForest].
+///
+/// The analysis is computed once from the structure of the forest and can then be queried at
+/// runtime without traversing the MAST again, e.g., to estimate the cost of executing a procedure
+/// before scheduling it. For each node, the analysis contains:
+/// - bounds on the number of VM cycles needed to execute the node,
+/// - bounds on the number of hasher chiplet permutations performed while executing the node,
+/// - the net change in the depth of the operand stack caused by executing the node, if it can be
+///   determined statically,
+/// - the nesting depth of the node, i.e., the number of nodes on the longest path from the node to
+///   a leaf of the MAST.
+///
+/// Bounds which depend on runtime values (e.g., the number of iterations of a loop, the depth of
+/// a Merkle path or the callee of a dynamic call) have no upper bound. Nodes referring to
+/// procedures outside of the forest (i.e., external nodes) are assumed to have no lower bound
+/// either. Similarly, the nesting depth does not include the depth of procedures which are
+/// resolved at runtime.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MastForestAnalysis {
+    nodes: Vec<NodeAnalysis>,
+}
+
+impl MastForestAnalysis {
+    /// Computes the static analysis of all nodes in the provided [MastForest].
+    pub fn new(mast_forest: &MastForest) -> Self {
+        let mut nodes: Vec<NodeAnalysis> = Vec::with_capacity(mast_forest.nodes().len());
+
+        for node in mast_forest.nodes() {
+            // nodes are added to a forest after their children, so all children have already
+            // been analyzed; a child which hasn't (which is only possible in forests which were
+            // not built via the MastForest API) is conservatively treated as unknown
+            let child =
+                |id: MastNodeId| nodes.get(id.as_usize()).copied().unwrap_or(NodeAnalysis::UNKNOWN);
+
+            let analysis = match node {
+                MastNode::Block(block) => NodeAnalysis::basic_block(block),
+                MastNode::Join(join) => {
+                    let (first, second) = (child(join.first()), child(join.second()));
+                    NodeAnalysis {
+                        cycles: first.cycles + second.cycles + CostBounds::exact(2),
+                        hasher_permutations: first.hasher_permutations
+                            + second.hasher_permutations
+                            + CostBounds::exact(1),
+                        stack_effect: first
+                            .stack_effect
+                            .zip(second.stack_effect)
+                            .map(|(first, second)| first + second),
+                        depth: first.depth.max(second.depth) + 1,
+                    }
+                },
+                MastNode::Split(split) => {
+                    let (on_true, on_false) = (child(split.on_true()), child(split.on_false()));
+                    let stack_effect = match (on_true.stack_effect, on_false.stack_effect) {
+                        (Some(on_true), Some(on_false)) if on_true == on_false => Some(on_true - 1),
+                        _ => None,
+                    };
+                    NodeAnalysis {
+                        cycles: on_true.cycles.either(on_false.cycles) + CostBounds::exact(2),
+                        hasher_permutations: on_true
+                            .hasher_permutations
+                            .either(on_false.hasher_permutations)
+                            + CostBounds::exact(1),
+                        stack_effect,
+                        depth: on_true.depth.max(on_false.depth) + 1,
+                    }
+                },
+                MastNode::Loop(loop_node) => {
+                    // the condition is dropped when entering the loop and after every iteration,
+                    // so the stack effect is independent of the number of iterations only if the
+                    // body pushes exactly one element (the next condition)
+                    let body = child(loop_node.body());
+                    NodeAnalysis {
+                        cycles: CostBounds::at_least(2),
+                        hasher_permutations: CostBounds::at_least(1),
+                        stack_effect: body.stack_effect.filter(|&effect| effect == 1).map(|_| -1),
+                        depth: body.depth + 1,
+                    }
+                },
+                MastNode::Call(call) => {
+                    // the callee is executed in a new context, and the depth of the stack of the
+                    // caller is restored when the call returns
+                    let callee = child(call.callee());
+                    NodeAnalysis {
+                        cycles: callee.cycles + CostBounds::exact(2),
+                        hasher_permutations: callee.hasher_permutations + CostBounds::exact(1),
+                        stack_effect: Some(0),
+                        depth: callee.depth + 1,
+                    }
+                },
+                MastNode::Dyn(dyn_node) => NodeAnalysis {
+                    cycles: CostBounds::at_least(2),
+                    hasher_permutations: CostBounds::at_least(1),
+                    // a dynamic call drops the memory address of the callee hash and then
+                    // executes the callee in a new context
+                    stack_effect: dyn_node.is_dyncall().then_some(-1),
+                    depth: 1,
+                },
+                MastNode::External(_) => NodeAnalysis::UNKNOWN,
+            };
+            nodes.push(analysis);
+        }
+
+        Self { nodes }
+    }
+
+    /// Returns the analysis of the node with the specified ID, or `None` if the node is not in
+    /// the analyzed forest.
+    pub fn get(&self, node_id: MastNodeId) -> Option<&NodeAnalysis> {
+        self.nodes.get(node_id.as_usize())
+    }
+
+    /// Returns the number of analyzed nodes.
+    pub fn num_nodes(&self) -> usize {
+        self.nodes.len()
+    }
+}
+
+impl Index<MastNodeId> for MastForestAnalysis {
+    type Output = NodeAnalysis;
+
+    fn index(&self, node_id: MastNodeId) -> &Self::Output {
+        &self.nodes[node_id.as_usize()]
+    }
+}
+
+// NODE ANALYSIS
+// ================================================================================================
+
+/// Static analysis of a single MAST node, including all of its descendants.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NodeAnalysis {
+    cycles: CostBounds,
+    hasher_permutations: CostBounds,
+    stack_effect: Option<i32>,
+    depth: u32,
+}
+
+impl NodeAnalysis {
+    /// The analysis of a node about which nothing is known statically.
+    const UNKNOWN: Self = Self {
+        cycles: CostBounds::at_least(0),
+        hasher_permutations: CostBounds::at_least(0),
+        stack_effect: None,
+        depth: 1,
+    };
+
+    /// Returns the bounds on the number of VM cycles needed to execute the node.
+    pub fn cycles(&self) -> CostBounds {
+        self.cycles
+    }
+
+    /// Returns the bounds on the number of permutations performed by the hasher chiplet while
+    /// executing the node. Each permutation occupies 8 rows of the hasher chiplet trace.
+    pub fn hasher_permutations(&self) -> CostBounds {
+        self.hasher_permutations
+    }
+
+    /// Returns the net change in the depth of the operand stack caused by executing the node, or
+    /// `None` if the change depends on runtime values.
+    pub fn stack_effect(&self) -> Option<i32> {
+        self.stack_effect
+    }
+
+    /// Returns the nesting depth of the node, i.e., the number of nodes on the longest path from
+    /// the node to a leaf of the MAST; the depth of a basic block is 1.
+    ///
+    /// This bounds the number of nodes which are being executed at the same time while the node
+    /// is executed, except for procedures which are resolved at runtime (i.e., via external or
+    /// dynamic nodes).
+    pub fn depth(&self) -> u32 {
+        self.depth
+    }
+
+    /// Computes the analysis of a basic block.
+    ///
+    /// Executing a basic block takes one cycle for the SPAN and END operations, one cycle for
+    /// every RESPAN operation between consecutive batches, and the cycles needed to execute each
+    /// batch. The hasher performs one permutation per batch.
+    fn basic_block(block: &BasicBlockNode) -> Self {
+        let num_batches = block.num_op_batches() as u64;
+        let batch_cycles: u64 = block.op_batches().iter().map(batch_cycles).sum();
+
+        let mut hasher_permutations = CostBounds::exact(num_batches);
+        let mut stack_effect = 0;
+        for op in block.operations() {
+            hasher_permutations = hasher_permutations + op_hasher_permutations(op);
+            stack_effect += op.metadata().stack_effect();
+        }
+
+        Self {
+            cycles: CostBounds::exact(batch_cycles + num_batches + 1),
+            hasher_permutations,
+            stack_effect: Some(stack_effect),
+            depth: 1,
+        }
+    }
+}
+
+// COST BOUNDS
+// ================================================================================================
+
+/// Lower and (optional) upper bounds on a cost, such as the number of cycles needed to execute
+/// a MAST node.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CostBounds {
+    min: u64,
+    max: Option<u64>,
+}
+
+impl CostBounds {
+    /// Returns bounds for a cost which is known exactly.
+    pub const fn exact(cost: u64) -> Self {
+        Self { min: cost, max: Some(cost) }
+    }
+
+    /// Returns bounds for a cost which is at least `min`, and has no upper bound.
+    pub const fn at_least(min: u64) -> Self {
+        Self { min, max: None }
+    }
+
+    /// Returns the lower bound on the cost.
+    pub const fn min(&self) -> u64 {
+        self.min
+    }
+
+    /// Returns the upper bound on the cost, or `None` if the cost is not bounded statically.
+    pub const fn max(&self) -> Option<u64> {
+        self.max
+    }
+
+    /// Returns the cost if it is known exactly.
+    pub fn exact_value(&self) -> Option<u64> {
+        self.max.filter(|&max| max == self.min)
+    }
+
+    /// Returns the bounds on the cost of executing either this or the `other` alternative.
+    fn either(self, other: Self) -> Self {
+        Self {
+            min: self.min.min(other.min),
+            max: self.max.zip(other.max).map(|(a, b)| a.max(b)),
+        }
+    }
+}
+
+impl core::ops::Add for CostBounds {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            min: self.min.saturating_add(other.min),
+            max: self.max.zip(other.max).and_then(|(a, b)| a.checked_add(b)),
+        }
+    }
+}
+
+impl fmt::Display for CostBounds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.max {
+            Some(max) if max == self.min => write!(f, "{max}"),
+            Some(max) => write!(f, "{}..={max}", self.min),
+            None => write!(f, "{}..", self.min),
+        }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the number of cycles needed to execute the operations of a batch.
+///
+/// This includes the NOOPs executed by the VM after operations with immediate values which end an
+/// operation group, and the NOOPs executed to pad the number of groups to a power of two.
+fn batch_cycles(batch: &OpBatch) -> u64 {
+    let op_counts = batch.op_counts();
+    let mut num_cycles = batch.ops().len() as u64;
+    let mut op_idx = 0;
+    let mut group_idx = 0;
+    let mut next_group_idx = 1;
+
+    for op in batch.ops() {
+        let has_imm = op.imm_value().is_some();
+        if has_imm {
+            next_group_idx += 1;
+        }
+
+        if op_idx == op_counts[group_idx] - 1 {
+            if has_imm {
+                num_cycles += 1;
+            }
+            group_idx = next_group_idx;
+            next_group_idx += 1;
+            op_idx = 0;
+        } else {
+            op_idx += 1;
+        }
+    }
+
+    let num_batch_groups = batch.num_groups().next_power_of_two();
+    num_cycles + num_batch_groups.saturating_sub(group_idx) as u64
+}
+
+/// Returns the bounds on the number of hasher permutations performed by an operation.
+fn op_hasher_permutations(op: &Operation) -> CostBounds {
+    match op {
+        Operation::HPerm => CostBounds::exact(1),
+        // the number of permutations depends on the depth of the Merkle path
+        Operation::MpVerify(_) | Operation::MrUpdate => CostBounds::at_least(0),
+        _ => CostBounds::exact(0),
+    }
+}
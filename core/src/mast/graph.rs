@@ -0,0 +1,289 @@
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    string::String,
+    vec::Vec,
+};
+use core::fmt;
+
+use super::{MastForest, MastNode, MastNodeId};
+use crate::{Decorator, crypto::hash::RpoDigest};
+
+/// The number of hex digits of the node digests included in the labels of the nodes.
+const DIGEST_PREFIX_LEN: usize = 16;
+
+// GRAPH FORMAT
+// ================================================================================================
+
+/// The format of a graph describing the structure of a [MastForest].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphFormat {
+    /// The DOT language of Graphviz.
+    Dot,
+    /// The flowchart syntax of Mermaid.
+    Mermaid,
+}
+
+// MAST GRAPH
+// ================================================================================================
+
+/// A graph of the nodes of a [MastForest], which can be rendered by external tools.
+///
+/// Each node of the forest is labeled with its kind, its ID and a prefix of its digest (the full
+/// digest is available as the tooltip of the node in the DOT format). Procedure roots are
+/// highlighted and, if the forest was assembled in debug mode, labeled with the name of their
+/// procedure. The name is taken from the AsmOp decorators of the procedure, excluding the ones of
+/// the procedures it executes, whose instructions are inlined into it. The procedures of a forest without decorators can be named after the
+/// procedures of the same code assembled in debug mode, see [MastGraph::with_names_of].
+///
+/// An edge goes from each node to each of its children, in order. The edges of split nodes are
+/// labeled with the branch they lead to.
+///
+/// The graph is produced by the [fmt::Display] implementation of this type, see
+/// [MastForest::to_graph].
+pub struct MastGraph<'a> {
+    forest: &'a MastForest,
+    format: GraphFormat,
+    /// The names of the procedures, indexed by MAST root.
+    names: BTreeMap<RpoDigest, String>,
+}
+
+impl<'a> MastGraph<'a> {
+    /// Returns a new graph of the specified MAST forest, rendered in the specified format.
+    pub fn new(forest: &'a MastForest, format: GraphFormat) -> Self {
+        Self { forest, format, names: BTreeMap::new() }.with_names_of(forest)
+    }
+
+    /// Labels the procedure roots of the graph with the names of the procedures of `forest` which
+    /// have the same MAST roots.
+    ///
+    /// This allows labeling the graph of a forest without decorators, using the same code
+    /// assembled in debug mode, since decorators don't contribute to MAST roots.
+    pub fn with_names_of(mut self, forest: &MastForest) -> Self {
+        for (root, name) in procedure_names(forest) {
+            self.names.entry(forest[root].digest()).or_insert_with(|| String::from(name));
+        }
+        self
+    }
+
+    /// Returns the lines of the label of the specified node.
+    fn node_label(&self, node_id: MastNodeId) -> Vec<String> {
+        let node = &self.forest[node_id];
+        let mut label = Vec::with_capacity(3);
+        if self.forest.is_procedure_root(node_id) {
+            if let Some(name) = self.names.get(&node.digest()) {
+                label.push(name.clone());
+            }
+        }
+
+        let kind = match node {
+            MastNode::Block(block) => format!("Block ({} ops)", block.num_operations()),
+            MastNode::Join(_) => String::from("Join"),
+            MastNode::Split(_) => String::from("Split"),
+            MastNode::Loop(_) => String::from("Loop"),
+            MastNode::Call(call) if call.is_syscall() => String::from("SysCall"),
+            MastNode::Call(_) => String::from("Call"),
+            MastNode::Dyn(dyn_node) if dyn_node.is_dyncall() => String::from("DynCall"),
+            MastNode::Dyn(_) => String::from("Dyn"),
+            MastNode::External(_) => String::from("External"),
+        };
+        label.push(format!("{kind} #{}", node_id.as_u32()));
+
+        let digest = node.digest().to_hex();
+        label.push(format!("{}...", &digest[..2 + DIGEST_PREFIX_LEN]));
+        label
+    }
+
+    /// Returns the children of the specified node, along with the labels of the edges to them.
+    fn node_edges(&self, node_id: MastNodeId) -> Vec<(MastNodeId, Option<&'static str>)> {
+        match &self.forest[node_id] {
+            MastNode::Split(split) => {
+                vec![(split.on_true(), Some("true")), (split.on_false(), Some("false"))]
+            },
+            node => {
+                let mut children = Vec::new();
+                node.append_children_to(&mut children);
+                children.into_iter().map(|child| (child, None)).collect()
+            },
+        }
+    }
+
+    fn fmt_dot(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "digraph mast {{")?;
+        writeln!(f, "    node [shape=box, fontname=\"monospace\"];")?;
+        for node_id in self.node_ids() {
+            let label =
+                self.node_label(node_id).iter().map(|line| escape_dot(line)).collect::<Vec<_>>();
+            write!(
+                f,
+                "    n{} [label=\"{}\", tooltip=\"{}\"",
+                node_id.as_u32(),
+                label.join("\\n"),
+                self.forest[node_id].digest().to_hex()
+            )?;
+            if self.forest.is_procedure_root(node_id) {
+                write!(f, ", style=bold")?;
+            }
+            writeln!(f, "];")?;
+        }
+        for node_id in self.node_ids() {
+            for (child, label) in self.node_edges(node_id) {
+                write!(f, "    n{} -> n{}", node_id.as_u32(), child.as_u32())?;
+                match label {
+                    Some(label) => writeln!(f, " [label=\"{label}\"];")?,
+                    None => writeln!(f, ";")?,
+                }
+            }
+        }
+        writeln!(f, "}}")
+    }
+
+    fn fmt_mermaid(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "flowchart TD")?;
+        writeln!(f, "    classDef root stroke-width:3px;")?;
+        for node_id in self.node_ids() {
+            let label = self
+                .node_label(node_id)
+                .iter()
+                .map(|line| escape_mermaid(line))
+                .collect::<Vec<_>>();
+            write!(f, "    n{}[\"{}\"]", node_id.as_u32(), label.join("<br/>"))?;
+            if self.forest.is_procedure_root(node_id) {
+                write!(f, ":::root")?;
+            }
+            writeln!(f)?;
+        }
+        for node_id in self.node_ids() {
+            for (child, label) in self.node_edges(node_id) {
+                match label {
+                    Some(label) => {
+                        writeln!(f, "    n{} -->|{label}| n{}", node_id.as_u32(), child.as_u32())?
+                    },
+                    None => writeln!(f, "    n{} --> n{}", node_id.as_u32(), child.as_u32())?,
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns an iterator over the IDs of all nodes of the forest.
+    fn node_ids(&self) -> impl Iterator<Item = MastNodeId> {
+        (0..self.forest.num_nodes()).map(MastNodeId)
+    }
+}
+
+impl fmt::Display for MastGraph<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.format {
+            GraphFormat::Dot => self.fmt_dot(f),
+            GraphFormat::Mermaid => self.fmt_mermaid(f),
+        }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the names of the procedures of the specified forest, indexed by root.
+///
+/// The name of a procedure is the context of the AsmOp decorators executed by it. Since executed
+/// procedures are inlined into their callers along with their decorators, the contexts of the
+/// procedures which were already named are discarded, starting with the procedures which don't
+/// execute any others. A procedure which is still ambiguous, e.g., because it executes a
+/// procedure which is not a root of the forest, is named after the first remaining context.
+fn procedure_names(forest: &MastForest) -> BTreeMap<MastNodeId, &str> {
+    let contexts = forest
+        .procedure_roots()
+        .iter()
+        .map(|&root| (root, procedure_contexts(forest, root)))
+        .filter(|(_, contexts)| !contexts.is_empty())
+        .collect::<Vec<_>>();
+
+    let mut names = BTreeMap::new();
+    let mut known = BTreeSet::new();
+    loop {
+        let num_names = names.len();
+        for (root, contexts) in contexts.iter() {
+            if names.contains_key(root) {
+                continue;
+            }
+            let mut unknown = contexts.iter().filter(|context| !known.contains(*context));
+            if let (Some(&name), None) = (unknown.next(), unknown.next()) {
+                names.insert(*root, name);
+                known.insert(name);
+            }
+        }
+        if names.len() == num_names {
+            break;
+        }
+    }
+
+    for (root, contexts) in contexts {
+        let name = contexts
+            .iter()
+            .find(|context| !known.contains(*context))
+            .unwrap_or(&contexts[0]);
+        names.entry(root).or_insert(name);
+    }
+    names
+}
+
+/// Returns the distinct contexts of the AsmOp decorators executed by the procedure with the
+/// specified root, in order, excluding the procedures it invokes by their roots.
+fn procedure_contexts(forest: &MastForest, root: MastNodeId) -> Vec<&str> {
+    let mut contexts = Vec::new();
+    let mut visited = BTreeSet::new();
+    let mut worklist = vec![root];
+    while let Some(node_id) = worklist.pop() {
+        if !visited.insert(node_id) {
+            continue;
+        }
+
+        let node = &forest[node_id];
+        let block_decorators =
+            node.get_basic_block().into_iter().flat_map(|block| block.decorators().iter());
+        let decorators = node.before_enter().iter().chain(block_decorators.map(|(_, id)| id));
+        for &decorator_id in decorators {
+            if let Decorator::AsmOp(asmop) = &forest[decorator_id] {
+                if !contexts.contains(&asmop.context_name()) {
+                    contexts.push(asmop.context_name());
+                }
+            }
+        }
+
+        // invoked procedures are named on their own, and hence are not visited
+        if !matches!(node, MastNode::Call(_)) {
+            let mut children = Vec::new();
+            node.append_children_to(&mut children);
+            worklist.extend(
+                children.into_iter().rev().filter(|&child| !forest.is_procedure_root(child)),
+            );
+        }
+    }
+    contexts
+}
+
+/// Escapes the characters of `value` which cannot appear in a DOT string.
+fn escape_dot(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '"' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Escapes the characters of `value` which cannot appear in a Mermaid label.
+fn escape_mermaid(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("#quot;"),
+            '<' => escaped.push_str("#lt;"),
+            '>' => escaped.push_str("#gt;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -303,22 +303,20 @@ impl MastForestMerger {
             },
             // Other nodes are simply copied.
             MastNode::Block(basic_block_node) => {
-                MastNode::new_basic_block(
-                    basic_block_node.operations().copied().collect(),
-                    // Operation Indices of decorators stay the same while decorator IDs need to be
-                    // mapped.
-                    Some(
-                        basic_block_node
-                            .decorators()
-                            .iter()
-                            .map(|(idx, decorator_id)| match map_decorator_id(decorator_id) {
-                                Ok(mapped_decorator) => Ok((*idx, mapped_decorator)),
-                                Err(err) => Err(err),
-                            })
-                            .collect::<Result<Vec<_>, _>>()?,
-                    ),
-                )
-                .expect("previously valid BasicBlockNode should still be valid")
+                // Operation Indices of decorators stay the same while decorator IDs need to be
+                // mapped. The operations are unchanged, so we reuse the batches and digest of the
+                // existing node rather than hashing them again.
+                let decorators = basic_block_node
+                    .decorators()
+                    .iter()
+                    .map(|(idx, decorator_id)| match map_decorator_id(decorator_id) {
+                        Ok(mapped_decorator) => Ok((*idx, mapped_decorator)),
+                        Err(err) => Err(err),
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                let mut basic_block_node = basic_block_node.clone();
+                basic_block_node.set_decorators(decorators);
+                MastNode::Block(basic_block_node)
             },
             MastNode::Dyn(_) => MastNode::new_dyn(),
             MastNode::External(external_node) => MastNode::new_external(external_node.digest()),
@@ -346,13 +344,14 @@ impl MastForestMerger {
 // MAST FOREST ROOT MAP
 // ================================================================================================
 
-/// A mapping for the new location of the roots of a [`MastForest`] after a merge.
+/// A mapping for the new location of the nodes of a [`MastForest`] after a merge.
 ///
 /// It maps the roots ([`MastNodeId`]s) of a forest to their new [`MastNodeId`] in the merged
-/// forest. See [`MastForest::merge`] for more details.
+/// forest, as well as all other nodes of the forest. See [`MastForest::merge`] for more details.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct MastForestRootMap {
     root_maps: Vec<BTreeMap<MastNodeId, MastNodeId>>,
+    node_maps: Vec<MastForestNodeIdMap>,
 }
 
 impl MastForestRootMap {
@@ -369,7 +368,7 @@ impl MastForestRootMap {
             }
         }
 
-        Self { root_maps }
+        Self { root_maps, node_maps: id_map }
     }
 
     /// Maps the given root to its new location in the merged forest, if such a mapping exists.
@@ -378,6 +377,15 @@ impl MastForestRootMap {
     pub fn map_root(&self, forest_index: usize, root: &MastNodeId) -> Option<MastNodeId> {
         self.root_maps.get(forest_index).and_then(|map| map.get(root)).copied()
     }
+
+    /// Maps the given node to its new location in the merged forest, if such a mapping exists.
+    ///
+    /// It is guaranteed that every node of the map's corresponding forest is contained in the map.
+    /// Nodes which were deduplicated, or external nodes which were replaced by the node they
+    /// reference, are mapped to the node they were merged with.
+    pub fn map_node(&self, forest_index: usize, node_id: &MastNodeId) -> Option<MastNodeId> {
+        self.node_maps.get(forest_index).and_then(|map| map.get(node_id)).copied()
+    }
 }
 
 // DECORATOR ID MAP
@@ -162,6 +162,51 @@ fn mast_forest_merge_duplicate() {
     assert_child_id_lt_parent_id(&merged).unwrap();
 }
 
+/// Tests that the subtrees shared by two forests only exist once in the merged forest, and that
+/// every node of both forests is mapped to its location in the merged forest.
+///
+/// [Block(foo), Block(bar), Join(foo, bar)]
+/// +
+/// [External(foo), Block(bar), Join(External(foo), bar), Block(qux)]
+/// =
+/// [Block(foo), Block(bar), Join(foo, bar), Block(qux)]
+#[test]
+fn mast_forest_merge_with_maps_all_nodes() {
+    let mut forest_a = MastForest::new();
+    let id_foo_a = forest_a.add_node(block_foo()).unwrap();
+    let id_bar_a = forest_a.add_node(block_bar()).unwrap();
+    let id_join_a = forest_a.add_join(id_foo_a, id_bar_a).unwrap();
+    forest_a.make_root(id_join_a);
+
+    let mut forest_b = MastForest::new();
+    let id_external_b = forest_b.add_external(block_foo().digest()).unwrap();
+    let id_bar_b = forest_b.add_node(block_bar()).unwrap();
+    let id_join_b = forest_b.add_join(id_external_b, id_bar_b).unwrap();
+    let id_qux_b = forest_b.add_node(block_qux()).unwrap();
+    forest_b.make_root(id_join_b);
+    forest_b.make_root(id_qux_b);
+
+    let (merged, node_maps) = forest_a.merge_with(&forest_b).unwrap();
+
+    assert_eq!(merged.nodes().len(), 4);
+    assert_eq!(merged.num_procedures(), 2);
+    for digest in [block_foo().digest(), block_bar().digest(), block_qux().digest()] {
+        assert_contains_node_once(&merged, digest).unwrap();
+    }
+
+    for (id_a, id_b) in [(id_foo_a, id_external_b), (id_bar_a, id_bar_b), (id_join_a, id_join_b)] {
+        let mapped_a = node_maps.map_node(0, &id_a).unwrap();
+        assert_eq!(merged[mapped_a].digest(), forest_a[id_a].digest());
+        assert_eq!(node_maps.map_node(1, &id_b), Some(mapped_a));
+    }
+    let mapped_qux = node_maps.map_node(1, &id_qux_b).unwrap();
+    assert_eq!(merged[mapped_qux], block_qux());
+    assert_eq!(node_maps.map_root(1, &id_qux_b), Some(mapped_qux));
+    assert_eq!(node_maps.map_node(2, &id_qux_b), None);
+
+    assert_child_id_lt_parent_id(&merged).unwrap();
+}
+
 /// Tests that External(foo) is replaced by Block(foo) whether it is in forest A or B, and the
 /// duplicate Call is removed.
 ///
@@ -9,6 +9,15 @@ use core::{
 
 use miden_crypto::hash::rpo::RpoDigest;
 
+mod analysis;
+pub use analysis::{CostBounds, MastForestAnalysis, NodeAnalysis};
+
+mod graph;
+pub use graph::{GraphFormat, MastGraph};
+
+mod source_map;
+pub use source_map::SourceMap;
+
 mod node;
 pub use node::{
     BasicBlockNode, CallNode, DynNode, ExternalNode, JoinNode, LoopNode, MastNode, OP_BATCH_SIZE,
@@ -16,9 +25,12 @@ pub use node::{
 };
 use winter_utils::{ByteWriter, DeserializationError, Serializable};
 
-use crate::{AdviceMap, Decorator, DecoratorList, Operation};
+use crate::{
+    AdviceMap, Decorator, DecoratorKinds, DecoratorList, Operation, utils::FormatVersions,
+};
 
 mod serialization;
+pub(crate) use serialization::MAGIC as MAST_MAGIC;
 
 mod merger;
 pub(crate) use merger::MastForestMerger;
@@ -64,6 +76,26 @@ impl MastForest {
     }
 }
 
+// ------------------------------------------------------------------------------------------------
+/// Serialization
+impl MastForest {
+    /// The versions of the binary format of MAST forests which can be deserialized.
+    ///
+    /// Checking the [format version](Self::format_version) of a serialized MAST forest against
+    /// these versions yields an [`UnsupportedVersion`](crate::utils::UnsupportedVersion) error if
+    /// the forest cannot be read.
+    pub const SUPPORTED_FORMAT_VERSIONS: FormatVersions = serialization::SUPPORTED_VERSIONS;
+
+    /// Returns the version of the binary format of the MAST forest serialized in `bytes`, without
+    /// deserializing the forest.
+    ///
+    /// # Errors
+    /// Returns an error if `bytes` do not start with the header of a serialized MAST forest.
+    pub fn format_version(bytes: &[u8]) -> Result<[u8; 3], DeserializationError> {
+        serialization::read_format_version(bytes)
+    }
+}
+
 // ------------------------------------------------------------------------------------------------
 /// State mutators
 impl MastForest {
@@ -198,6 +230,88 @@ impl MastForest {
         id_remappings
     }
 
+    /// Removes all nodes which are not reachable from the specified roots, along with the
+    /// decorators which are no longer referenced by any node.
+    ///
+    /// Procedure roots which are not reachable from `roots` are no longer procedure roots of the
+    /// forest, while the advice map is left unchanged. Note that procedures which are only invoked
+    /// dynamically (i.e., via `dynexec` or `dyncall`) are not reachable from the nodes which invoke
+    /// them, and hence must be included in `roots` to be retained.
+    ///
+    /// It also returns the map from old node IDs to new node IDs. Any [`MastNodeId`] used in
+    /// reference to the old [`MastForest`] should be remapped using this map.
+    ///
+    /// # Panics
+    /// - if any of the `roots` doesn't belong to this MAST forest.
+    pub fn remove_unreachable_nodes(&mut self, roots: &[MastNodeId]) -> Remapping {
+        let mut reachable = BTreeSet::new();
+        let mut worklist = roots.to_vec();
+        while let Some(node_id) = worklist.pop() {
+            if reachable.insert(node_id) {
+                self[node_id].append_children_to(&mut worklist);
+            }
+        }
+
+        let nodes_to_remove = (0..self.nodes.len() as u32)
+            .map(MastNodeId)
+            .filter(|node_id| !reachable.contains(node_id))
+            .collect();
+        self.roots.retain(|root| reachable.contains(root));
+        let id_remappings = self.remove_nodes(&nodes_to_remove);
+        self.remove_unused_decorators();
+
+        id_remappings
+    }
+
+    /// Removes all decorators from the forest.
+    ///
+    /// The nodes of the forest, and hence their IDs, are preserved, so that a [SourceMap]
+    /// extracted from the forest beforehand applies to the stripped forest as well.
+    pub fn strip_decorators(&mut self) {
+        for node in self.nodes.iter_mut() {
+            match node {
+                MastNode::Block(block) => block.set_decorators(Vec::new()),
+                node => {
+                    node.set_before_enter(Vec::new());
+                    node.set_after_exit(Vec::new());
+                },
+            }
+        }
+        self.decorators.clear();
+    }
+
+    /// Removes the decorators whose kinds are not selected by `kinds` from the forest.
+    ///
+    /// As with [MastForest::strip_decorators], the nodes of the forest, and hence their IDs and
+    /// digests, are preserved. The IDs of the retained decorators may change.
+    pub fn retain_decorators(&mut self, kinds: DecoratorKinds) {
+        if kinds == DecoratorKinds::NONE {
+            return self.strip_decorators();
+        }
+
+        let decorators = &self.decorators;
+        let retain =
+            |decorator_id: &DecoratorId| kinds.contains(&decorators[decorator_id.as_usize()]);
+        for node in self.nodes.iter_mut() {
+            match node {
+                MastNode::Block(block) => {
+                    let block_decorators =
+                        block.decorators().iter().filter(|(_, id)| retain(id)).copied().collect();
+                    block.set_decorators(block_decorators);
+                },
+                node => {
+                    let before_enter =
+                        node.before_enter().iter().filter(|id| retain(id)).copied().collect();
+                    let after_exit =
+                        node.after_exit().iter().filter(|id| retain(id)).copied().collect();
+                    node.set_before_enter(before_enter);
+                    node.set_after_exit(after_exit);
+                },
+            }
+        }
+        self.remove_unused_decorators();
+    }
+
     pub fn set_before_enter(&mut self, node_id: MastNodeId, decorator_ids: Vec<DecoratorId>) {
         self[node_id].set_before_enter(decorator_ids)
     }
@@ -261,6 +375,20 @@ impl MastForest {
         MastForestMerger::merge(forests)
     }
 
+    /// Merges this forest with `other`, and returns the merged forest along with the new locations
+    /// of the nodes of both forests.
+    ///
+    /// This is equivalent to `MastForest::merge([self, other])`: in the returned
+    /// [`MastForestRootMap`], the nodes of this forest are at index 0 and the nodes of `other` are
+    /// at index 1. Nodes and subtrees present in both forests only exist once in the merged forest.
+    /// See [`MastForest::merge`] for more details.
+    pub fn merge_with(
+        &self,
+        other: &MastForest,
+    ) -> Result<(MastForest, MastForestRootMap), MastForestError> {
+        MastForestMerger::merge([self, other])
+    }
+
     /// Adds a basic block node to the forest, and returns the [`MastNodeId`] associated with it.
     ///
     /// It is assumed that the decorators have not already been added to the MAST forest. If they
@@ -293,7 +421,9 @@ impl MastForest {
         // Add each node to the new MAST forest, making sure to rewrite any outdated internal
         // `MastNodeId`s
         for live_node in nodes_to_add {
-            match &live_node {
+            let before_enter = live_node.before_enter().to_vec();
+            let after_exit = live_node.after_exit().to_vec();
+            let node_id = match &live_node {
                 MastNode::Join(join_node) => {
                     let first_child =
                         id_remappings.get(&join_node.first()).copied().unwrap_or(join_node.first());
@@ -302,7 +432,7 @@ impl MastForest {
                         .copied()
                         .unwrap_or(join_node.second());
 
-                    self.add_join(first_child, second_child).unwrap();
+                    self.add_join(first_child, second_child).unwrap()
                 },
                 MastNode::Split(split_node) => {
                     let on_true_child = id_remappings
@@ -314,13 +444,13 @@ impl MastForest {
                         .copied()
                         .unwrap_or(split_node.on_false());
 
-                    self.add_split(on_true_child, on_false_child).unwrap();
+                    self.add_split(on_true_child, on_false_child).unwrap()
                 },
                 MastNode::Loop(loop_node) => {
                     let body_id =
                         id_remappings.get(&loop_node.body()).copied().unwrap_or(loop_node.body());
 
-                    self.add_loop(body_id).unwrap();
+                    self.add_loop(body_id).unwrap()
                 },
                 MastNode::Call(call_node) => {
                     let callee_id = id_remappings
@@ -329,14 +459,63 @@ impl MastForest {
                         .unwrap_or(call_node.callee());
 
                     if call_node.is_syscall() {
-                        self.add_syscall(callee_id).unwrap();
+                        self.add_syscall(callee_id).unwrap()
                     } else {
-                        self.add_call(callee_id).unwrap();
+                        self.add_call(callee_id).unwrap()
                     }
                 },
                 MastNode::Block(_) | MastNode::Dyn(_) | MastNode::External(_) => {
-                    self.add_node(live_node).unwrap();
+                    self.add_node(live_node).unwrap()
                 },
+            };
+
+            // the decorators of basic blocks are part of the node itself
+            if !self[node_id].is_basic_block() {
+                self.set_before_enter(node_id, before_enter);
+                self.set_after_exit(node_id, after_exit);
+            }
+        }
+    }
+
+    /// Removes the decorators which are not referenced by any node, and remaps the references to
+    /// the remaining decorators.
+    fn remove_unused_decorators(&mut self) {
+        let mut used = vec![false; self.decorators.len()];
+        for node in self.nodes.iter() {
+            let decorator_ids = node.before_enter().iter().chain(node.after_exit());
+            let block_decorator_ids = match node {
+                MastNode::Block(block) => block.decorators().as_slice(),
+                _ => &[],
+            };
+            for decorator_id in decorator_ids.chain(block_decorator_ids.iter().map(|(_, id)| id)) {
+                used[decorator_id.as_usize()] = true;
+            }
+        }
+        if used.iter().all(|&used| used) {
+            return;
+        }
+
+        let mut id_remappings = vec![None; used.len()];
+        for (old_decorator, decorator) in mem::take(&mut self.decorators).into_iter().enumerate() {
+            if used[old_decorator] {
+                id_remappings[old_decorator] = Some(DecoratorId(self.decorators.len() as u32));
+                self.decorators.push(decorator);
+            }
+        }
+
+        let remap = |decorator_id: &DecoratorId| {
+            id_remappings[decorator_id.as_usize()].expect("referenced decorators are retained")
+        };
+        for node in self.nodes.iter_mut() {
+            if let MastNode::Block(block) = node {
+                let decorators =
+                    block.decorators().iter().map(|(op_idx, id)| (*op_idx, remap(id))).collect();
+                block.set_decorators(decorators);
+            } else {
+                let before_enter = node.before_enter().iter().map(remap).collect();
+                let after_exit = node.after_exit().iter().map(remap).collect();
+                node.set_before_enter(before_enter);
+                node.set_after_exit(after_exit);
             }
         }
     }
@@ -471,6 +650,12 @@ impl MastForest {
     pub fn advice_map_mut(&mut self) -> &mut AdviceMap {
         &mut self.advice_map
     }
+
+    /// Returns a graph of the nodes of this forest in the specified format, which can be rendered
+    /// with Graphviz or Mermaid. See [`MastGraph`] for details.
+    pub fn to_graph(&self, format: GraphFormat) -> MastGraph<'_> {
+        MastGraph::new(self, format)
+    }
 }
 
 impl Index<MastNodeId> for MastForest {
@@ -34,15 +34,33 @@
 //! (before enter and after exit decorators section)
 //! - before enter decorators (`Vec<(MastNodeId, Vec<DecoratorId>)>`)
 //! - after exit decorators (`Vec<(MastNodeId, Vec<DecoratorId>)>`)
+//!
+//! (extensions section, since version 0.1.0)
+//! - extensions (`Vec<(u8, Vec<u8>)>`), i.e., tagged sections of optional data
+//!
+//! # Compatibility
+//!
+//! The version is made of a major, a minor and a patch number:
+//! - Patch versions only add new kinds of extensions, which deserializers of older patch versions
+//!   skip. Thus, forests serialized by newer patch versions of the current version can be read,
+//!   albeit without the data of their unknown extensions.
+//! - Other changes to the format increment the minor (or, before version 1.0.0, the major)
+//!   version. Deserializers keep supporting the versions written by at least the previous release
+//!   of the VM, and reject newer versions with an error naming the supported versions.
+//!
+//! Version 0.0.0 is the format used before extensions were introduced, which has no extensions
+//! section.
 
 use alloc::vec::Vec;
 
 use decorator::{DecoratorDataBuilder, DecoratorInfo};
 use string_table::StringTable;
-use winter_utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+use winter_utils::{
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable, SliceReader,
+};
 
 use super::{DecoratorId, MastForest, MastNode, MastNodeId};
-use crate::AdviceMap;
+use crate::{AdviceMap, utils::FormatVersions};
 
 mod decorator;
 
@@ -78,14 +96,25 @@ type StringIndex = usize;
 // ================================================================================================
 
 /// Magic string for detecting that a file is binary-encoded MAST.
-const MAGIC: &[u8; 5] = b"MAST\0";
+pub(crate) const MAGIC: &[u8; 5] = b"MAST\0";
 
 /// The format version.
 ///
-/// If future modifications are made to this format, the version should be incremented by 1. A
-/// version of `[255, 255, 255]` is reserved for future extensions that require extending the
-/// version field itself, but should be considered invalid for now.
-const VERSION: [u8; 3] = [0, 0, 0];
+/// If future modifications are made to this format, the version should be incremented as
+/// described in the [module documentation](self). A version of `[255, 255, 255]` is reserved for
+/// future extensions that require extending the version field itself, but should be considered
+/// invalid for now.
+const VERSION: [u8; 3] = [0, 1, 0];
+
+/// The first version with an extensions section.
+const EXTENSIONS_VERSION: [u8; 3] = [0, 1, 0];
+
+/// The format versions which can be deserialized.
+///
+/// When the format is modified, the deserializer should keep supporting the versions written by
+/// at least the previous release of the VM, so that existing programs and libraries remain usable.
+pub(super) const SUPPORTED_VERSIONS: FormatVersions =
+    FormatVersions::new([0, 0, 0], VERSION).with_newer_patches();
 
 // MAST FOREST SERIALIZATION/DESERIALIZATION
 // ================================================================================================
@@ -173,13 +202,16 @@ impl Serializable for MastForest {
         // Write "before enter" and "after exit" decorators
         before_enter_decorators.write_into(target);
         after_exit_decorators.write_into(target);
+
+        // no extensions are defined by the current version
+        target.write_usize(0);
     }
 }
 
 impl Deserializable for MastForest {
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
         read_and_validate_magic(source)?;
-        read_and_validate_version(source)?;
+        let version = read_and_validate_version(source)?;
 
         // Reading sections metadata
         let node_count = source.read_usize()?;
@@ -273,6 +305,15 @@ impl Deserializable for MastForest {
             mast_forest.set_after_exit(node_id, decorator_ids);
         }
 
+        // extensions are skipped, as none of them is known to this version of the deserializer
+        if version >= EXTENSIONS_VERSION {
+            let num_extensions = source.read_usize()?;
+            for _ in 0..num_extensions {
+                let _tag = source.read_u8()?;
+                let _data: Vec<u8> = source.read()?;
+            }
+        }
+
         Ok(mast_forest)
     }
 }
@@ -288,16 +329,21 @@ fn read_and_validate_magic<R: ByteReader>(source: &mut R) -> Result<[u8; 5], Des
     Ok(magic)
 }
 
+/// Returns the format version of the MAST forest serialized in `bytes`, without deserializing
+/// the forest.
+///
+/// # Errors
+/// Returns an error if `bytes` do not start with the header of a serialized MAST forest.
+pub(super) fn read_format_version(bytes: &[u8]) -> Result<[u8; 3], DeserializationError> {
+    let mut source = SliceReader::new(bytes);
+    read_and_validate_magic(&mut source)?;
+    source.read_array()
+}
+
 fn read_and_validate_version<R: ByteReader>(
     source: &mut R,
 ) -> Result<[u8; 3], DeserializationError> {
-    let version: [u8; 3] = source.read_array()?;
-    if version != VERSION {
-        return Err(DeserializationError::InvalidValue(format!(
-            "Unsupported version. Got '{version:?}', but only '{VERSION:?}' is supported",
-        )));
-    }
-    Ok(version)
+    SUPPORTED_VERSIONS.read_version("MAST forest", source)
 }
 
 fn read_block_decorators<R: ByteReader>(
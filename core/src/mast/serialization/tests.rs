@@ -19,6 +19,7 @@ fn confirm_operation_and_decorator_structure() {
         Operation::SDepth => (),
         Operation::Caller => (),
         Operation::Clk => (),
+        Operation::Ctx => (),
         Operation::Join => (),
         Operation::Split => (),
         Operation::Loop => (),
@@ -50,9 +51,12 @@ fn confirm_operation_and_decorator_structure() {
         Operation::U32sub => (),
         Operation::U32mul => (),
         Operation::U32madd => (),
+        Operation::U32rotl => (),
         Operation::U32div => (),
         Operation::U32and => (),
         Operation::U32xor => (),
+        Operation::U64add => (),
+        Operation::U64sub => (),
         Operation::Pad => (),
         Operation::Drop => (),
         Operation::Dup0 => (),
@@ -132,6 +136,7 @@ fn serialize_deserialize_all_nodes() {
             Operation::SDepth,
             Operation::Caller,
             Operation::Clk,
+            Operation::Ctx,
             Operation::Join,
             Operation::Split,
             Operation::Loop,
@@ -162,9 +167,12 @@ fn serialize_deserialize_all_nodes() {
             Operation::U32sub,
             Operation::U32mul,
             Operation::U32madd,
+            Operation::U32rotl,
             Operation::U32div,
             Operation::U32and,
             Operation::U32xor,
+            Operation::U64add,
+            Operation::U64sub,
             Operation::Pad,
             Operation::Drop,
             Operation::Dup0,
@@ -405,3 +413,77 @@ fn mast_forest_serialize_deserialize_advice_map() {
     let parsed = MastForest::read_from_bytes(&forest.to_bytes()).unwrap();
     assert_eq!(forest.advice_map, parsed.advice_map);
 }
+
+// FORMAT VERSION TESTS
+// ================================================================================================
+
+/// Returns a MAST forest with a procedure and decorators, serialized in the current format.
+fn serialized_forest() -> (MastForest, Vec<u8>) {
+    let mut forest = MastForest::new();
+    let deco = forest.add_decorator(Decorator::Trace(0)).unwrap();
+    let block = forest.add_block(vec![Operation::U32add], Some(vec![(0, deco)])).unwrap();
+    let call = forest.add_call(block).unwrap();
+    forest[call].set_after_exit(vec![deco]);
+    forest.make_root(call);
+
+    let bytes = forest.to_bytes();
+    (forest, bytes)
+}
+
+/// Removes the extensions section of the current format, which is empty, from `bytes`.
+fn remove_extensions(bytes: &mut Vec<u8>) {
+    let mut extensions = Vec::new();
+    extensions.write_usize(0);
+    assert!(bytes.ends_with(&extensions));
+    bytes.truncate(bytes.len() - extensions.len());
+}
+
+#[test]
+fn mast_forest_format_version() {
+    let (_, bytes) = serialized_forest();
+    assert_eq!(MastForest::format_version(&bytes).unwrap(), VERSION);
+    assert!(MastForest::SUPPORTED_FORMAT_VERSIONS.check("MAST forest", VERSION).is_ok());
+    assert!(MastForest::format_version(b"PROG\0\0\0\0").is_err());
+}
+
+#[test]
+fn mast_forest_deserialize_previous_version() {
+    let (forest, mut bytes) = serialized_forest();
+
+    // version 0.0.0 has no extensions section, which ends the forest
+    bytes[5..8].copy_from_slice(&[0, 0, 0]);
+    remove_extensions(&mut bytes);
+    assert_eq!(MastForest::read_from_bytes(&bytes).unwrap(), forest);
+}
+
+#[test]
+fn mast_forest_deserialize_newer_patch_version() {
+    let (forest, mut bytes) = serialized_forest();
+
+    // newer patch versions may add extensions, which are skipped
+    bytes[5..8].copy_from_slice(&[VERSION[0], VERSION[1], VERSION[2] + 1]);
+    remove_extensions(&mut bytes);
+    bytes.write_usize(1);
+    bytes.write_u8(7);
+    alloc::vec![1_u8, 2, 3].write_into(&mut bytes);
+    assert_eq!(MastForest::read_from_bytes(&bytes).unwrap(), forest);
+}
+
+#[test]
+fn mast_forest_deserialize_unsupported_version() {
+    let (_, mut bytes) = serialized_forest();
+    let version = [VERSION[0], VERSION[1] + 1, 0];
+    bytes[5..8].copy_from_slice(&version);
+
+    let err = MastForest::read_from_bytes(&bytes).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "unsupported MAST forest format version 0.2.0; supported versions are 0.0.0 to 0.1.x"
+    );
+
+    let err = MastForest::SUPPORTED_FORMAT_VERSIONS
+        .check("MAST forest", MastForest::format_version(&bytes).unwrap())
+        .unwrap_err();
+    assert_eq!(err.version, version);
+    assert_eq!(err.supported, SUPPORTED_VERSIONS);
+}
@@ -0,0 +1,240 @@
+use alloc::{collections::BTreeMap, string::String, sync::Arc, vec::Vec};
+
+use super::{MastForest, MastNode, MastNodeId};
+use crate::{
+    Decorator,
+    debuginfo::{FileLineCol, SourceManager},
+    utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable},
+};
+
+/// Magic string identifying serialized source maps.
+const MAGIC: &[u8; 5] = b"SMAP\0";
+
+/// The version of the binary format of source maps.
+const VERSION: u8 = 0;
+
+// SOURCE MAP
+// ================================================================================================
+
+/// Maps the operations of the basic blocks of a [MastForest] to the source code they were
+/// assembled from.
+///
+/// A source map provides the same source attribution as the locations of AsmOp decorators, but is
+/// kept separately from the forest, so that production artifacts can be stripped of decorators
+/// (see [MastForest::strip_decorators]) while their source maps are kept alongside them. Since
+/// stripping decorators preserves the IDs of the nodes, a source map extracted from a forest
+/// applies to the stripped forest as well. If the stripped forest is deduplicated afterwards, the
+/// source map has to be remapped accordingly (see [SourceMap::remap]).
+///
+/// Each entry maps the index of the first operation of an instruction within a basic block to
+/// the file, line and column of the instruction. An operation without an entry of its own is
+/// attributed to the closest preceding entry of the same block.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SourceMap {
+    /// The paths of the source files, indexed by the file indices of the entries.
+    files: Vec<Arc<str>>,
+    /// The index of each file path in `files`.
+    file_indices: BTreeMap<Arc<str>, u32>,
+    /// The entries, indexed by node ID and operation index.
+    entries: BTreeMap<(MastNodeId, u32), SourceMapEntry>,
+}
+
+/// The location of an instruction in a [SourceMap].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct SourceMapEntry {
+    file: u32,
+    line: u32,
+    column: u32,
+}
+
+impl SourceMap {
+    /// Returns a new empty source map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the source map of the specified forest, built from the locations of its AsmOp
+    /// decorators, which are only present if the forest was assembled in debug mode.
+    ///
+    /// The locations are resolved to lines and columns with the specified source manager.
+    /// Locations in files which are not managed by it are skipped.
+    pub fn from_forest(forest: &MastForest, source_manager: &dyn SourceManager) -> Self {
+        let mut source_map = Self::new();
+        for (node_idx, node) in forest.nodes().iter().enumerate() {
+            let MastNode::Block(block) = node else {
+                continue;
+            };
+            for &(op_idx, decorator_id) in block.decorators() {
+                let Decorator::AsmOp(asmop) = &forest[decorator_id] else {
+                    continue;
+                };
+                let location = asmop
+                    .location()
+                    .and_then(|location| source_manager.location_to_span(location.clone()))
+                    .and_then(|span| source_manager.file_line_col(span).ok());
+                if let Some(location) = location {
+                    source_map.insert(MastNodeId(node_idx as u32), op_idx, location);
+                }
+            }
+        }
+        source_map
+    }
+
+    /// Maps the operation at `op_idx` in the basic block with the specified ID, and the operations
+    /// following it, to the specified location.
+    pub fn insert(&mut self, node_id: MastNodeId, op_idx: usize, location: FileLineCol) {
+        let file = match self.file_indices.get(&location.path) {
+            Some(&file) => file,
+            None => {
+                let file = self.files.len() as u32;
+                self.files.push(location.path.clone());
+                self.file_indices.insert(location.path.clone(), file);
+                file
+            },
+        };
+        let entry = SourceMapEntry {
+            file,
+            line: location.line,
+            column: location.column,
+        };
+        self.entries.insert((node_id, op_idx as u32), entry);
+    }
+
+    /// Returns the location of the operation at `op_idx` in the basic block with the specified ID,
+    /// if it is mapped.
+    pub fn get(&self, node_id: MastNodeId, op_idx: usize) -> Option<FileLineCol> {
+        let (_, entry) = self.entries.range((node_id, 0)..=(node_id, op_idx as u32)).next_back()?;
+        Some(self.to_file_line_col(entry))
+    }
+
+    /// Returns the location of the instruction starting at `op_idx` in the basic block with the
+    /// specified ID, i.e., only if the operation has an entry of its own.
+    pub fn get_exact(&self, node_id: MastNodeId, op_idx: usize) -> Option<FileLineCol> {
+        self.entries
+            .get(&(node_id, op_idx as u32))
+            .map(|entry| self.to_file_line_col(entry))
+    }
+
+    /// Returns this source map with the IDs of its nodes remapped by `map_node`, e.g., to the
+    /// locations of the nodes in a forest deduplicated with [MastForest::merge].
+    ///
+    /// The entries of nodes for which `map_node` returns `None` are dropped. If several nodes are
+    /// mapped to the same node, only the entries of the first of them are kept.
+    pub fn remap(&self, map_node: impl Fn(MastNodeId) -> Option<MastNodeId>) -> Self {
+        let mut sources = BTreeMap::new();
+        let mut entries = BTreeMap::new();
+        for (&(node_id, op_idx), entry) in self.entries.iter() {
+            let Some(new_id) = map_node(node_id) else {
+                continue;
+            };
+            if *sources.entry(new_id).or_insert(node_id) == node_id {
+                entries.insert((new_id, op_idx), *entry);
+            }
+        }
+        Self {
+            files: self.files.clone(),
+            file_indices: self.file_indices.clone(),
+            entries,
+        }
+    }
+
+    /// Returns the number of entries in this source map.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if this source map has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn to_file_line_col(&self, entry: &SourceMapEntry) -> FileLineCol {
+        FileLineCol::new(self.files[entry.file as usize].clone(), entry.line, entry.column)
+    }
+}
+
+// SERIALIZATION
+// ================================================================================================
+
+/// Source maps are serialized as a table of file paths, followed by the entries in order. The IDs
+/// of the nodes are delta-encoded, which keeps the entries of a basic block down to a few bytes
+/// each.
+impl Serializable for SourceMap {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_bytes(MAGIC);
+        target.write_u8(VERSION);
+
+        target.write_usize(self.files.len());
+        for path in self.files.iter() {
+            path.as_ref().write_into(target);
+        }
+
+        target.write_usize(self.entries.len());
+        let mut prev_node = 0;
+        for (&(node_id, op_idx), entry) in self.entries.iter() {
+            target.write_usize((node_id.as_u32() - prev_node) as usize);
+            target.write_usize(op_idx as usize);
+            target.write_usize(entry.file as usize);
+            target.write_usize(entry.line as usize);
+            target.write_usize(entry.column as usize);
+            prev_node = node_id.as_u32();
+        }
+    }
+}
+
+impl Deserializable for SourceMap {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let magic: [u8; 5] = source.read_array()?;
+        if &magic != MAGIC {
+            return Err(DeserializationError::InvalidValue(String::from(
+                "invalid magic bytes: expected a source map",
+            )));
+        }
+        let version = source.read_u8()?;
+        if version != VERSION {
+            return Err(DeserializationError::InvalidValue(format!(
+                "unsupported source map version {version}"
+            )));
+        }
+
+        let mut source_map = Self::new();
+        let num_files = source.read_usize()?;
+        for file in 0..num_files {
+            let path: Arc<str> = String::read_from(source)?.into();
+            if source_map.file_indices.insert(path.clone(), file as u32).is_some() {
+                return Err(DeserializationError::InvalidValue(format!(
+                    "duplicate source file {path}"
+                )));
+            }
+            source_map.files.push(path);
+        }
+
+        let num_entries = source.read_usize()?;
+        let mut node = 0u32;
+        for _ in 0..num_entries {
+            let read_u32 = |source: &mut R| {
+                u32::try_from(source.read_usize()?).map_err(|_| {
+                    DeserializationError::InvalidValue(String::from("value does not fit in u32"))
+                })
+            };
+            node = node.checked_add(read_u32(source)?).ok_or_else(|| {
+                DeserializationError::InvalidValue(String::from("node id overflow"))
+            })?;
+            let op_idx = read_u32(source)?;
+            let entry = SourceMapEntry {
+                file: read_u32(source)?,
+                line: read_u32(source)?,
+                column: read_u32(source)?,
+            };
+            if entry.file as usize >= num_files {
+                return Err(DeserializationError::InvalidValue(format!(
+                    "invalid source file index {}",
+                    entry.file
+                )));
+            }
+            source_map.entries.insert((MastNodeId(node), op_idx), entry);
+        }
+
+        Ok(source_map)
+    }
+}
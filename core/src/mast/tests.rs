@@ -1,11 +1,16 @@
-use alloc::vec::Vec;
+use alloc::{string::ToString, sync::Arc, vec::Vec};
 
 use miden_crypto::{Felt, hash::rpo::RpoDigest};
 use proptest::prelude::*;
 use rand_utils::prng_array;
 use winter_utils::{Deserializable, Serializable};
 
-use crate::{Kernel, ProgramInfo, Word, chiplets::hasher, mast::DynNode};
+use crate::{
+    AssemblyOp, DebugOptions, Decorator, DecoratorKinds, Kernel, Operation, ProgramInfo, Word,
+    chiplets::hasher,
+    debuginfo::FileLineCol,
+    mast::{DynNode, GraphFormat, MastForest, MastGraph, MastNode, MastNodeId, SourceMap},
+};
 
 #[test]
 fn dyn_hash_is_correct() {
@@ -14,6 +19,197 @@ fn dyn_hash_is_correct() {
     assert_eq!(expected_constant, DynNode::new_dyn().digest());
 }
 
+#[test]
+fn remove_unreachable_nodes_and_decorators() {
+    let mut forest = MastForest::new();
+    let unused_decorator = forest.add_decorator(Decorator::Trace(1)).unwrap();
+    let foo_decorator = forest.add_decorator(Decorator::Trace(2)).unwrap();
+    let call_decorator = forest.add_decorator(Decorator::Trace(3)).unwrap();
+
+    let foo = forest.add_block(vec![Operation::Add], Some(vec![(0, foo_decorator)])).unwrap();
+    let bar = forest
+        .add_block(vec![Operation::Mul], Some(vec![(0, unused_decorator)]))
+        .unwrap();
+    let qux = forest.add_block(vec![Operation::Eq], None).unwrap();
+    let call = forest.add_call(qux).unwrap();
+    forest.set_before_enter(call, vec![call_decorator]);
+    let join = forest.add_join(foo, call).unwrap();
+    forest.make_root(bar);
+    forest.make_root(qux);
+    forest.make_root(join);
+    let bar_digest = forest[bar].digest();
+    let join_digest = forest[join].digest();
+
+    let id_remappings = forest.remove_unreachable_nodes(&[join]);
+
+    // `bar` is removed along with its decorator, and is no longer a procedure root
+    assert_eq!(forest.num_nodes(), 4);
+    assert!(forest.nodes().iter().all(|node| node.digest() != bar_digest));
+    assert!(!id_remappings.contains_key(&bar));
+    assert_eq!(forest.decorators(), &[Decorator::Trace(2), Decorator::Trace(3)]);
+
+    let join = id_remappings[&join];
+    let qux = id_remappings[&qux];
+    assert_eq!(forest[join].digest(), join_digest);
+    assert_eq!(forest.procedure_roots(), &[qux, join]);
+
+    let MastNode::Block(foo) = &forest[id_remappings[&foo]] else {
+        panic!("expected a block")
+    };
+    assert_eq!(forest[foo.decorators()[0].1], Decorator::Trace(2));
+    assert_eq!(forest[forest[id_remappings[&call]].before_enter()[0]], Decorator::Trace(3));
+}
+
+#[test]
+fn mast_forest_graph() {
+    let mut forest = MastForest::new();
+    let asmop = AssemblyOp::new(None, "#exec::\"main\"".into(), 1, "add".into(), false);
+    let asmop = forest.add_decorator(Decorator::AsmOp(asmop)).unwrap();
+    let add = forest.add_block(vec![Operation::Add], Some(vec![(0, asmop)])).unwrap();
+    let mul = forest.add_block(vec![Operation::Mul, Operation::Drop], None).unwrap();
+    let split = forest.add_split(add, mul).unwrap();
+    forest.make_root(split);
+    let digest_prefix = &forest[split].digest().to_hex()[..18];
+
+    let dot = forest.to_graph(GraphFormat::Dot).to_string();
+    assert!(dot.starts_with("digraph mast {\n"));
+    assert!(dot.contains(&format!(
+        "    n2 [label=\"#exec::\\\"main\\\"\\nSplit #2\\n{digest_prefix}...\", tooltip=\"{}\", \
+         style=bold];\n",
+        forest[split].digest().to_hex()
+    )));
+    assert!(dot.contains("    n1 [label=\"Block (2 ops) #1\\n"));
+    assert!(dot.contains("    n2 -> n0 [label=\"true\"];\n    n2 -> n1 [label=\"false\"];\n}\n"));
+
+    let mermaid = forest.to_graph(GraphFormat::Mermaid).to_string();
+    assert!(mermaid.starts_with("flowchart TD\n"));
+    assert!(mermaid.contains(&format!(
+        "    n2[\"#exec::#quot;main#quot;<br/>Split #2<br/>{digest_prefix}...\"]:::root\n"
+    )));
+    assert!(mermaid.ends_with("    n2 -->|true| n0\n    n2 -->|false| n1\n"));
+}
+
+#[test]
+fn mast_forest_graph_procedure_names() {
+    let mut forest = MastForest::new();
+    let asmop = |forest: &mut MastForest, name: &str| {
+        let asmop = AssemblyOp::new(None, name.into(), 1, "add".into(), false);
+        forest.add_decorator(Decorator::AsmOp(asmop)).unwrap()
+    };
+    let foo_asmop = asmop(&mut forest, "foo");
+    let foo = forest.add_block(vec![Operation::Add], Some(vec![(0, foo_asmop)])).unwrap();
+    forest.make_root(foo);
+    let bar_asmop = asmop(&mut forest, "bar");
+    let baz_asmop = asmop(&mut forest, "baz");
+    let bar_block = forest.add_block(vec![Operation::Mul], Some(vec![(0, bar_asmop)])).unwrap();
+    let bar = forest.add_join(foo, bar_block).unwrap();
+    forest.make_root(bar);
+
+    // the procedure starting with an executed procedure is named after its own instructions
+    let dot = forest.to_graph(GraphFormat::Dot).to_string();
+    assert!(dot.contains("    n0 [label=\"foo\\nBlock (1 ops) #0\\n"), "{dot}");
+    assert!(dot.contains("    n2 [label=\"bar\\nJoin #2\\n"), "{dot}");
+
+    // the instructions of an executed procedure may be merged into the block of its caller
+    let baz = forest
+        .add_block(
+            vec![Operation::Add, Operation::Drop],
+            Some(vec![(0, foo_asmop), (1, baz_asmop)]),
+        )
+        .unwrap();
+    forest.make_root(baz);
+    let dot = forest.to_graph(GraphFormat::Dot).to_string();
+    assert!(dot.contains("    n3 [label=\"baz\\nBlock (2 ops) #3\\n"), "{dot}");
+
+    // the procedures of a stripped forest are named after the ones of the original forest
+    let mut stripped = forest.clone();
+    stripped.strip_decorators();
+    let dot = stripped.to_graph(GraphFormat::Dot).to_string();
+    assert!(dot.contains("    n2 [label=\"Join #2\\n"), "{dot}");
+    let dot = MastGraph::new(&stripped, GraphFormat::Dot).with_names_of(&forest).to_string();
+    assert!(dot.contains("    n2 [label=\"bar\\nJoin #2\\n"), "{dot}");
+}
+
+#[test]
+fn strip_decorators_preserves_nodes() {
+    let mut forest = MastForest::new();
+    let trace = forest.add_decorator(Decorator::Trace(1)).unwrap();
+    let add = forest.add_block(vec![Operation::Add], Some(vec![(0, trace)])).unwrap();
+    let call = forest.add_call(add).unwrap();
+    forest.set_before_enter(call, vec![trace]);
+    forest.set_after_exit(call, vec![trace]);
+    forest.make_root(call);
+    let digests = forest.nodes().iter().map(MastNode::digest).collect::<Vec<_>>();
+
+    forest.strip_decorators();
+
+    assert!(forest.decorators().is_empty());
+    assert_eq!(forest.nodes().iter().map(MastNode::digest).collect::<Vec<_>>(), digests);
+    assert!(forest[add].get_basic_block().unwrap().decorators().is_empty());
+    assert!(forest[call].before_enter().is_empty());
+    assert!(forest[call].after_exit().is_empty());
+}
+
+#[test]
+fn retain_decorators_of_selected_kinds() {
+    let mut forest = MastForest::new();
+    let trace = forest.add_decorator(Decorator::Trace(1)).unwrap();
+    let debug = forest.add_decorator(Decorator::Debug(DebugOptions::StackAll)).unwrap();
+    let add = forest
+        .add_block(vec![Operation::Add, Operation::Mul], Some(vec![(0, debug), (1, trace)]))
+        .unwrap();
+    let call = forest.add_call(add).unwrap();
+    forest.set_before_enter(call, vec![debug, trace]);
+    forest.make_root(call);
+    let digests = forest.nodes().iter().map(MastNode::digest).collect::<Vec<_>>();
+
+    forest.retain_decorators(DecoratorKinds::TRACE_ONLY);
+
+    // the retained decorator is remapped, since the decorator preceding it was removed
+    assert_eq!(forest.decorators(), [Decorator::Trace(1)]);
+    assert_eq!(forest.nodes().iter().map(MastNode::digest).collect::<Vec<_>>(), digests);
+    let trace = forest.decorators().len() - 1;
+    let block = forest[add].get_basic_block().unwrap();
+    assert_eq!(
+        block
+            .decorators()
+            .iter()
+            .map(|(op_idx, id)| (*op_idx, id.as_usize()))
+            .collect::<Vec<_>>(),
+        [(1, trace)]
+    );
+    assert_eq!(forest[call].before_enter().len(), 1);
+
+    forest.retain_decorators(DecoratorKinds::NONE);
+    assert!(forest.decorators().is_empty());
+    assert!(forest[call].before_enter().is_empty());
+}
+
+#[test]
+fn source_map_lookup_and_serialization() {
+    let file: Arc<str> = "lib.masm".into();
+    let other_file: Arc<str> = "other.masm".into();
+    let block = MastNodeId::from_u32_safe(1, &forest_with_nodes(3)).unwrap();
+    let other_block = MastNodeId::from_u32_safe(2, &forest_with_nodes(3)).unwrap();
+
+    let mut source_map = SourceMap::new();
+    source_map.insert(block, 0, FileLineCol::new(file.clone(), 3, 5));
+    source_map.insert(block, 4, FileLineCol::new(file.clone(), 4, 9));
+    source_map.insert(other_block, 2, FileLineCol::new(other_file.clone(), 1, 1));
+    assert_eq!(source_map.len(), 3);
+
+    // operations without an entry are attributed to the preceding entry of the same block
+    assert_eq!(source_map.get(block, 2), Some(FileLineCol::new(file.clone(), 3, 5)));
+    assert_eq!(source_map.get(block, 7), Some(FileLineCol::new(file.clone(), 4, 9)));
+    assert_eq!(source_map.get_exact(block, 2), None);
+    assert_eq!(source_map.get(other_block, 1), None);
+    assert_eq!(source_map.get(other_block, 2), Some(FileLineCol::new(other_file, 1, 1)));
+
+    let bytes = source_map.to_bytes();
+    assert_eq!(SourceMap::read_from_bytes(&bytes).unwrap(), source_map);
+    assert!(SourceMap::read_from_bytes(&bytes[1..]).is_err());
+}
+
 proptest! {
     #[test]
     fn arbitrary_program_info_serialization_works(
@@ -38,6 +234,14 @@ proptest! {
 // HELPER FUNCTIONS
 // --------------------------------------------------------------------------------------------
 
+fn forest_with_nodes(num_nodes: usize) -> MastForest {
+    let mut forest = MastForest::new();
+    for i in 0..num_nodes {
+        forest.add_block(vec![Operation::Push(Felt::new(i as u64))], None).unwrap();
+    }
+    forest
+}
+
 fn digest_from_seed(seed: [u8; 32]) -> RpoDigest {
     let mut digest = Word::default();
     digest.iter_mut().enumerate().for_each(|(i, d)| {
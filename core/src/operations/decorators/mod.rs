@@ -75,6 +75,52 @@ impl fmt::Display for Decorator {
     }
 }
 
+// DECORATOR KINDS
+// ================================================================================================
+
+/// A selection of the kinds of [Decorator]s, used to control which decorators are emitted by the
+/// assembler, or retained in a MAST forest.
+///
+/// Decorators do not contribute to the digests of MAST nodes, and hence the selection does not
+/// affect the MAST roots of the assembled code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecoratorKinds {
+    /// Whether [Decorator::AsmOp] decorators are selected.
+    pub asm_ops: bool,
+    /// Whether [Decorator::Debug] decorators are selected.
+    pub debug: bool,
+    /// Whether [Decorator::Trace] decorators are selected.
+    pub trace: bool,
+}
+
+impl DecoratorKinds {
+    /// A selection of all kinds of decorators.
+    pub const ALL: Self = Self { asm_ops: true, debug: true, trace: true };
+
+    /// A selection of no decorators.
+    pub const NONE: Self = Self {
+        asm_ops: false,
+        debug: false,
+        trace: false,
+    };
+
+    /// A selection of [Decorator::Trace] decorators only.
+    pub const TRACE_ONLY: Self = Self {
+        asm_ops: false,
+        debug: false,
+        trace: true,
+    };
+
+    /// Returns true if the kind of the specified decorator is selected.
+    pub fn contains(&self, decorator: &Decorator) -> bool {
+        match decorator {
+            Decorator::AsmOp(_) => self.asm_ops,
+            Decorator::Debug(_) => self.debug,
+            Decorator::Trace(_) => self.trace,
+        }
+    }
+}
+
 /// Vector consisting of a tuple of operation index (within a span block) and decorator at that
 /// index
 pub type DecoratorList = Vec<(usize, DecoratorId)>;
@@ -0,0 +1,277 @@
+use core::fmt;
+
+use super::Operation;
+
+// CHIPLET
+// ================================================================================================
+
+/// A chiplet of the VM with which an operation can interact via the chiplets bus.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Chiplet {
+    /// The hasher chiplet, which computes hashes of code blocks and RPO permutations, and
+    /// verifies and updates Merkle paths.
+    Hasher,
+    /// The bitwise chiplet, which computes bitwise AND and XOR of u32 values.
+    Bitwise,
+    /// The memory chiplet, which handles reads from and writes to memory.
+    Memory,
+    /// The kernel ROM chiplet, which contains the roots of the procedures of the kernel.
+    KernelRom,
+}
+
+impl fmt::Display for Chiplet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Hasher => write!(f, "hasher"),
+            Self::Bitwise => write!(f, "bitwise"),
+            Self::Memory => write!(f, "memory"),
+            Self::KernelRom => write!(f, "kernel_rom"),
+        }
+    }
+}
+
+// OPERATION METADATA
+// ================================================================================================
+
+/// Describes the effect of executing an [Operation] on the operand stack, and the chiplets
+/// involved in its execution.
+///
+/// The inputs of an operation are the items at the top of the stack which the operation may read,
+/// overwrite or remove, and its outputs are the items at the top of the stack which the operation
+/// may write. Items below the inputs are not affected by the operation, other than being shifted
+/// when the number of inputs and outputs differ. For example, `DUP2` reads the 3rd item of the
+/// stack and pushes a copy of it onto the stack, and thus has 3 inputs and 4 outputs.
+///
+/// For control flow operations, the metadata describes the operation itself rather than the code
+/// block it begins or ends. For example, `CALL` has no inputs or outputs, but it can modify the
+/// overflow table since the overflow table of the caller is hidden from the callee.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct OperationMetadata {
+    inputs: u8,
+    outputs: u8,
+    shifts_overflow: bool,
+    chiplets: &'static [Chiplet],
+}
+
+impl OperationMetadata {
+    /// Returns the metadata of an operation which reads `inputs` items from the top of the stack
+    /// and replaces them with `outputs` items.
+    const fn new(inputs: u8, outputs: u8) -> Self {
+        Self {
+            inputs,
+            outputs,
+            shifts_overflow: inputs != outputs,
+            chiplets: &[],
+        }
+    }
+
+    /// Marks the operation as able to modify the overflow table, even if it has as many inputs as
+    /// outputs.
+    const fn shifting_overflow(mut self) -> Self {
+        self.shifts_overflow = true;
+        self
+    }
+
+    /// Sets the chiplets involved in the execution of the operation.
+    const fn with_chiplets(mut self, chiplets: &'static [Chiplet]) -> Self {
+        self.chiplets = chiplets;
+        self
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of items at the top of the stack consumed by the operation.
+    pub const fn inputs(&self) -> u8 {
+        self.inputs
+    }
+
+    /// Returns the number of items at the top of the stack produced by the operation.
+    pub const fn outputs(&self) -> u8 {
+        self.outputs
+    }
+
+    /// Returns the net change in the depth of the stack caused by executing the operation.
+    pub const fn stack_effect(&self) -> i32 {
+        self.outputs as i32 - self.inputs as i32
+    }
+
+    /// Returns true if executing the operation can move items into or out of the overflow table.
+    ///
+    /// This is the case for all operations which change the depth of the stack, as well as for
+    /// operations which start or end the execution of a procedure in a new context.
+    pub const fn shifts_overflow(&self) -> bool {
+        self.shifts_overflow
+    }
+
+    /// Returns the chiplets involved in the execution of the operation.
+    pub const fn chiplets(&self) -> &'static [Chiplet] {
+        self.chiplets
+    }
+
+    /// Returns true if the specified chiplet is involved in the execution of the operation.
+    pub fn uses_chiplet(&self, chiplet: Chiplet) -> bool {
+        self.chiplets.contains(&chiplet)
+    }
+}
+
+// OPERATION METADATA TABLE
+// ================================================================================================
+
+const HASHER: &[Chiplet] = &[Chiplet::Hasher];
+const BITWISE: &[Chiplet] = &[Chiplet::Bitwise];
+const MEMORY: &[Chiplet] = &[Chiplet::Memory];
+const HASHER_MEMORY: &[Chiplet] = &[Chiplet::Hasher, Chiplet::Memory];
+const HASHER_KERNEL_ROM: &[Chiplet] = &[Chiplet::Hasher, Chiplet::KernelRom];
+
+impl Operation {
+    /// Returns the metadata describing the effect of this operation on the stack and the chiplets
+    /// involved in its execution.
+    pub const fn metadata(&self) -> OperationMetadata {
+        type M = OperationMetadata;
+
+        match self {
+            // ----- system operations ------------------------------------------------------------
+            Self::Noop | Self::Emit(_) => M::new(0, 0),
+            Self::Assert(_) | Self::FmpUpdate => M::new(1, 0),
+            Self::FmpAdd => M::new(1, 1),
+            Self::SDepth | Self::Clk | Self::Ctx => M::new(0, 1),
+            Self::Caller => M::new(4, 4),
+
+            // ----- flow control operations ------------------------------------------------------
+            Self::Join | Self::Span | Self::Respan => M::new(0, 0).with_chiplets(HASHER),
+            // SPLIT and LOOP drop the condition from the stack
+            Self::Split | Self::Loop => M::new(1, 0).with_chiplets(HASHER),
+            Self::Repeat => M::new(1, 0),
+            // END drops the condition from the stack when exiting a loop, and restores the
+            // overflow table of the caller when ending a call
+            Self::End => M::new(0, 0).shifting_overflow().with_chiplets(HASHER),
+            Self::Call => M::new(0, 0).shifting_overflow().with_chiplets(HASHER),
+            Self::SysCall => M::new(0, 0).shifting_overflow().with_chiplets(HASHER_KERNEL_ROM),
+            // DYN and DYNCALL read the hash of the callee from memory, and drop its address
+            Self::Dyn | Self::Dyncall => M::new(1, 0).with_chiplets(HASHER_MEMORY),
+            Self::Halt => M::new(0, 0),
+
+            // ----- field operations -------------------------------------------------------------
+            Self::Add | Self::Mul | Self::And | Self::Or | Self::Eq => M::new(2, 1),
+            Self::Neg | Self::Inv | Self::Incr | Self::Not | Self::Eqz => M::new(1, 1),
+            Self::Expacc | Self::Ext2Mul => M::new(4, 4),
+
+            // ----- u32 operations ---------------------------------------------------------------
+            Self::U32split => M::new(1, 2),
+            Self::U32add | Self::U32sub | Self::U32mul | Self::U32div | Self::U32assert2(_) => {
+                M::new(2, 2)
+            },
+            Self::U32add3 | Self::U32madd => M::new(3, 2),
+            Self::U32rotl => M::new(2, 1),
+            Self::U32and | Self::U32xor => M::new(2, 1).with_chiplets(BITWISE),
+
+            // ----- u64 operations ---------------------------------------------------------------
+            Self::U64add | Self::U64sub => M::new(4, 3),
+
+            // ----- stack manipulation -----------------------------------------------------------
+            Self::Pad => M::new(0, 1),
+            Self::Drop => M::new(1, 0),
+            Self::Dup0 => M::new(1, 2),
+            Self::Dup1 => M::new(2, 3),
+            Self::Dup2 => M::new(3, 4),
+            Self::Dup3 => M::new(4, 5),
+            Self::Dup4 => M::new(5, 6),
+            Self::Dup5 => M::new(6, 7),
+            Self::Dup6 => M::new(7, 8),
+            Self::Dup7 => M::new(8, 9),
+            Self::Dup9 => M::new(10, 11),
+            Self::Dup11 => M::new(12, 13),
+            Self::Dup13 => M::new(14, 15),
+            Self::Dup15 => M::new(16, 17),
+            Self::Swap => M::new(2, 2),
+            Self::SwapW => M::new(8, 8),
+            Self::SwapW2 => M::new(12, 12),
+            Self::SwapW3 | Self::SwapDW => M::new(16, 16),
+            Self::MovUp2 | Self::MovDn2 => M::new(3, 3),
+            Self::MovUp3 | Self::MovDn3 => M::new(4, 4),
+            Self::MovUp4 | Self::MovDn4 => M::new(5, 5),
+            Self::MovUp5 | Self::MovDn5 => M::new(6, 6),
+            Self::MovUp6 | Self::MovDn6 => M::new(7, 7),
+            Self::MovUp7 | Self::MovDn7 => M::new(8, 8),
+            Self::MovUp8 | Self::MovDn8 => M::new(9, 9),
+            Self::CSwap => M::new(3, 2),
+            Self::CSwapW => M::new(9, 8),
+
+            // ----- input / output operations ----------------------------------------------------
+            Self::Push(_) | Self::AdvPop => M::new(0, 1),
+            Self::AdvPopW => M::new(4, 4),
+            Self::MLoad => M::new(1, 1).with_chiplets(MEMORY),
+            Self::MStore => M::new(2, 1).with_chiplets(MEMORY),
+            Self::MLoadW | Self::MStoreW => M::new(5, 4).with_chiplets(MEMORY),
+            Self::MStream | Self::Pipe => M::new(13, 13).with_chiplets(MEMORY),
+
+            // ----- cryptographic operations -----------------------------------------------------
+            Self::HPerm => M::new(12, 12).with_chiplets(HASHER),
+            Self::MpVerify(_) => M::new(10, 10).with_chiplets(HASHER),
+            Self::MrUpdate => M::new(14, 14).with_chiplets(HASHER),
+            Self::FriE2F4 => M::new(16, 15),
+            // the evaluation point is read from memory
+            Self::HornerBase | Self::HornerExt => M::new(16, 16).with_chiplets(MEMORY),
+        }
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use winter_utils::Deserializable;
+
+    use super::{super::opcode_constants::*, *};
+
+    /// Returns all operations, with immediate values set to zero.
+    fn all_operations() -> Vec<Operation> {
+        (0..=u8::MAX)
+            .filter_map(|op_code| {
+                let num_imm_bytes = match op_code {
+                    OPCODE_PUSH => 8,
+                    OPCODE_ASSERT | OPCODE_MPVERIFY | OPCODE_U32ASSERT2 | OPCODE_EMIT => 4,
+                    _ => 0,
+                };
+                let mut bytes = [0; 9];
+                bytes[0] = op_code;
+                Operation::read_from_bytes(&bytes[..num_imm_bytes + 1]).ok()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn stack_effect_matches_opcode() {
+        for op in all_operations() {
+            let metadata = op.metadata();
+            if metadata.stack_effect() != 0 {
+                assert!(metadata.shifts_overflow(), "{op} changes the depth of the stack");
+            }
+
+            // the opcodes of operations below 64 determine the direction of the stack shift
+            let expected_shift = match op.op_code() >> 4 {
+                0b000 | 0b001 => Some(0),
+                0b010 => Some(-1),
+                0b011 => Some(1),
+                _ => None,
+            };
+            if let Some(expected_shift) = expected_shift {
+                assert_eq!(metadata.stack_effect().signum(), expected_shift, "{op}");
+                assert_eq!(metadata.shifts_overflow(), expected_shift != 0, "{op}");
+            }
+        }
+    }
+
+    #[test]
+    fn chiplets() {
+        assert!(Operation::HPerm.metadata().uses_chiplet(Chiplet::Hasher));
+        assert!(Operation::U32xor.metadata().uses_chiplet(Chiplet::Bitwise));
+        assert!(Operation::SysCall.metadata().uses_chiplet(Chiplet::KernelRom));
+        assert!(!Operation::Call.metadata().uses_chiplet(Chiplet::KernelRom));
+        assert!(Operation::Add.metadata().chiplets().is_empty());
+    }
+}
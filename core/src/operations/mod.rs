@@ -1,10 +1,13 @@
 use core::fmt;
 
-use super::Felt;
+use super::{Felt, IsaVersion};
 mod decorators;
 pub use decorators::{
-    AssemblyOp, DebugOptions, Decorator, DecoratorIterator, DecoratorList, SignatureKind,
+    AssemblyOp, DebugOptions, Decorator, DecoratorIterator, DecoratorKinds, DecoratorList,
+    SignatureKind,
 };
+mod metadata;
+pub use metadata::{Chiplet, OperationMetadata};
 // OPERATIONS OP CODES
 // ================================================================================================
 use opcode_constants::*;
@@ -15,7 +18,8 @@ use winter_utils::{ByteReader, ByteWriter, Deserializable, DeserializationError,
 /// - 010xxxx operations shift the stack the left; constraint degree can be up to 2.
 /// - 011xxxx operations shift the stack to the right; constraint degree can be up to 2.
 /// - 100xxx-: operations consume 4 range checks; constraint degree can be up to 3. These are used
-///   to encode most u32 operations.
+///   to encode most u32 operations. The least significant bit is set only for the u64 operations
+///   (10000x1) and U32ROTL (1000101), which shift the stack to the left.
 /// - 101xxx-: operations where constraint degree can be up to 3. These include control flow
 ///   operations and some other operations requiring high degree constraints.
 /// - 11xxx--: operations where constraint degree can be up to 5. These include control flow
@@ -90,8 +94,11 @@ pub(super) mod opcode_constants {
     pub const OPCODE_CLK: u8        = 0b0011_1111;
 
     pub const OPCODE_U32ADD: u8     = 0b0100_0000;
+    pub const OPCODE_U64ADD: u8     = 0b0100_0001;
     pub const OPCODE_U32SUB: u8     = 0b0100_0010;
+    pub const OPCODE_U64SUB: u8     = 0b0100_0011;
     pub const OPCODE_U32MUL: u8     = 0b0100_0100;
+    pub const OPCODE_U32ROTL: u8    = 0b0100_0101;
     pub const OPCODE_U32DIV: u8     = 0b0100_0110;
     pub const OPCODE_U32SPLIT: u8   = 0b0100_1000;
     pub const OPCODE_U32ASSERT2: u8 = 0b0100_1010;
@@ -111,6 +118,7 @@ pub(super) mod opcode_constants {
     pub const OPCODE_EMIT: u8       = 0b0101_1010;
     pub const OPCODE_PUSH: u8       = 0b0101_1011;
     pub const OPCODE_DYNCALL: u8    = 0b0101_1100;
+    pub const OPCODE_CTX: u8        = 0b0101_1101;
 
     pub const OPCODE_MRUPDATE: u8   = 0b0110_0000;
     pub const OPCODE_HORNERBASE: u8 = 0b0110_0100;
@@ -158,6 +166,11 @@ pub enum Operation {
     /// instruction.
     Clk = OPCODE_CLK,
 
+    /// Pushes the id of the current execution context onto the stack. The id of the root context
+    /// is 0, and the ids of the contexts created by CALL and DYNCALL are unique within an
+    /// execution. Kernel procedures invoked via SYSCALL execute in the root context.
+    Ctx = OPCODE_CTX,
+
     /// Emits an event id (`u32` value) to the host.
     ///
     /// We interpret the event id as follows:
@@ -311,6 +324,17 @@ pub enum Operation {
     /// undefined.
     U32mul = OPCODE_U32MUL,
 
+    /// Pops two elements off the stack, where the first element is a power of two `2^b` with
+    /// `0 <= b <= 32`, and rotates the bits of the second element to the left by `b` bits. Then
+    /// pushes the result onto the stack.
+    ///
+    /// That is, the stack transitions from `[2^b, a, ...]` to `[c, ...]`, where `c` is `a` rotated
+    /// to the left by `b` bits (equivalently, rotated to the right by `32 - b` bits).
+    ///
+    /// If `a` is greater than or equal to 2^32, or the first element is not a power of two between
+    /// 1 and 2^32, the result of this operation is undefined.
+    U32rotl = OPCODE_U32ROTL,
+
     /// Pops two elements off the stack and multiplies them. Then pops the third element off the
     /// stack, and adds it to the result. Finally, splits the result into upper and lower 32-bit
     /// values, and pushes them onto the stack.
@@ -338,6 +362,30 @@ pub enum Operation {
     /// If either of the elements is greater than or equal to 2^32, execution fails.
     U32xor = OPCODE_U32XOR,
 
+    // ----- u64 operations ----------------------------------------------------------------------
+    /// Pops two 64-bit values, each represented by its upper and lower 32-bit limbs, off the
+    /// stack and adds them. Then pushes the lower and upper 32-bit limbs of the sum, together
+    /// with a flag indicating whether the addition overflowed, onto the stack.
+    ///
+    /// That is, the stack transitions from `[b_hi, b_lo, a_hi, a_lo, ...]` to
+    /// `[overflow, c_hi, c_lo, ...]`, where `c = (a + b) mod 2^64`.
+    ///
+    /// If any of the limbs is greater than or equal to 2^32, the result of this operation is
+    /// undefined.
+    U64add = OPCODE_U64ADD,
+
+    /// Pops two 64-bit values, each represented by its upper and lower 32-bit limbs, off the
+    /// stack and subtracts the first value from the second. Then pushes the lower and upper 32-bit
+    /// limbs of the difference, together with a flag indicating whether the subtraction
+    /// underflowed, onto the stack.
+    ///
+    /// That is, the stack transitions from `[b_hi, b_lo, a_hi, a_lo, ...]` to
+    /// `[underflow, c_hi, c_lo, ...]`, where `c = (a - b) mod 2^64`.
+    ///
+    /// If any of the limbs is greater than or equal to 2^32, the result of this operation is
+    /// undefined.
+    U64sub = OPCODE_U64SUB,
+
     // ----- stack manipulation ------------------------------------------------------------------
     /// Pushes 0 onto the stack.
     Pad = OPCODE_PAD,
@@ -638,6 +686,16 @@ impl Operation {
                 | Self::SysCall
         )
     }
+
+    /// Returns the earliest version of the instruction set which includes this operation.
+    pub fn isa_version(&self) -> IsaVersion {
+        match self {
+            Self::U64add | Self::U64sub => IsaVersion::V1_1,
+            Self::U32rotl => IsaVersion::V1_2,
+            Self::Ctx => IsaVersion::V1_3,
+            _ => IsaVersion::V1_0,
+        }
+    }
 }
 
 impl crate::prettier::PrettyPrint for Operation {
@@ -660,6 +718,7 @@ impl fmt::Display for Operation {
             Self::Caller => write!(f, "caller"),
 
             Self::Clk => write!(f, "clk"),
+            Self::Ctx => write!(f, "ctx"),
 
             // ----- flow control operations ------------------------------------------------------
             Self::Join => write!(f, "join"),
@@ -702,11 +761,16 @@ impl fmt::Display for Operation {
             Self::U32sub => write!(f, "u32sub"),
             Self::U32mul => write!(f, "u32mul"),
             Self::U32madd => write!(f, "u32madd"),
+            Self::U32rotl => write!(f, "u32rotl"),
             Self::U32div => write!(f, "u32div"),
 
             Self::U32and => write!(f, "u32and"),
             Self::U32xor => write!(f, "u32xor"),
 
+            // ----- u64 operations ---------------------------------------------------------------
+            Self::U64add => write!(f, "u64add"),
+            Self::U64sub => write!(f, "u64sub"),
+
             // ----- stack manipulation -----------------------------------------------------------
             Self::Drop => write!(f, "drop"),
             Self::Pad => write!(f, "pad"),
@@ -800,6 +864,7 @@ impl Serializable for Operation {
             | Operation::SDepth
             | Operation::Caller
             | Operation::Clk
+            | Operation::Ctx
             | Operation::Join
             | Operation::Split
             | Operation::Loop
@@ -830,9 +895,12 @@ impl Serializable for Operation {
             | Operation::U32sub
             | Operation::U32mul
             | Operation::U32madd
+            | Operation::U32rotl
             | Operation::U32div
             | Operation::U32and
             | Operation::U32xor
+            | Operation::U64add
+            | Operation::U64sub
             | Operation::Pad
             | Operation::Drop
             | Operation::Dup0
@@ -972,6 +1040,10 @@ impl Deserializable for Operation {
             },
             OPCODE_U32ADD3 => Self::U32add3,
             OPCODE_U32MADD => Self::U32madd,
+            OPCODE_U32ROTL => Self::U32rotl,
+
+            OPCODE_U64ADD => Self::U64add,
+            OPCODE_U64SUB => Self::U64sub,
 
             OPCODE_HPERM => Self::HPerm,
             OPCODE_MPVERIFY => {
@@ -987,6 +1059,7 @@ impl Deserializable for Operation {
             OPCODE_JOIN => Self::Join,
             OPCODE_DYN => Self::Dyn,
             OPCODE_DYNCALL => Self::Dyncall,
+            OPCODE_CTX => Self::Ctx,
             OPCODE_HORNERBASE => Self::HornerBase,
             OPCODE_HORNEREXT => Self::HornerExt,
 
@@ -6,9 +6,9 @@ use winter_utils::{ByteReader, ByteWriter, Deserializable, DeserializationError,
 
 use super::Kernel;
 use crate::{
-    AdviceMap,
-    mast::{MastForest, MastNode, MastNodeId},
-    utils::ToElements,
+    AdviceMap, IsaVersion,
+    mast::{MAST_MAGIC, MastForest, MastForestAnalysis, MastNode, MastNodeId, NodeAnalysis},
+    utils::{FormatVersions, ToElements},
 };
 
 // PROGRAM
@@ -19,12 +19,21 @@ use crate::{
 /// A program consists of a MAST forest, an entrypoint defining the MAST node at which the program
 /// execution begins, and a definition of the kernel against which the program must be executed
 /// (the kernel can be an empty kernel).
-#[derive(Clone, Debug, PartialEq, Eq)]
+///
+/// A program also records the version of the instruction set it was assembled for, so that it is
+/// rejected by VMs which don't support this version.
+///
+/// A program can additionally carry a precomputed static analysis of its MAST forest (see
+/// [`Program::with_static_analysis`]). The analysis is derived from the MAST forest, and is thus
+/// neither serialized nor taken into account when comparing programs.
+#[derive(Clone, Debug, Eq)]
 pub struct Program {
     mast_forest: Arc<MastForest>,
     /// The "entrypoint" is the node where execution of the program begins.
     entrypoint: MastNodeId,
     kernel: Kernel,
+    isa_version: IsaVersion,
+    static_analysis: Option<Arc<MastForestAnalysis>>,
 }
 
 /// Constructors
@@ -52,7 +61,29 @@ impl Program {
         assert!(mast_forest.get_node_by_id(entrypoint).is_some(), "invalid entrypoint");
         assert!(mast_forest.is_procedure_root(entrypoint), "entrypoint not a procedure");
 
-        Self { mast_forest, entrypoint, kernel }
+        Self {
+            mast_forest,
+            entrypoint,
+            kernel,
+            isa_version: IsaVersion::CURRENT,
+            static_analysis: None,
+        }
+    }
+
+    /// Returns this program, with the version of the instruction set it targets set to
+    /// `isa_version`.
+    pub fn with_isa_version(mut self, isa_version: IsaVersion) -> Self {
+        self.isa_version = isa_version;
+        self
+    }
+
+    /// Returns this program with the static analysis of its [`MastForest`] attached, computing
+    /// the analysis if it is not attached yet.
+    pub fn with_static_analysis(mut self) -> Self {
+        if self.static_analysis.is_none() {
+            self.static_analysis = Some(Arc::new(MastForestAnalysis::new(&self.mast_forest)));
+        }
+        self
     }
 
     /// Produces a new program with the existing [`MastForest`] and where all key/values in the
@@ -92,6 +123,11 @@ impl Program {
         &self.kernel
     }
 
+    /// Returns the version of the instruction set this program was assembled for.
+    pub fn isa_version(&self) -> IsaVersion {
+        self.isa_version
+    }
+
     /// Returns the [`MastNode`] associated with the provided [`MastNodeId`] if valid, or else
     /// `None`.
     ///
@@ -111,6 +147,25 @@ impl Program {
     pub fn num_procedures(&self) -> u32 {
         self.mast_forest.num_procedures()
     }
+
+    /// Returns the static analysis of the program's [`MastForest`], if one is attached.
+    pub fn static_analysis(&self) -> Option<&MastForestAnalysis> {
+        self.static_analysis.as_deref()
+    }
+
+    /// Returns the static analysis of the program's entrypoint, if an analysis is attached.
+    pub fn entrypoint_analysis(&self) -> Option<&NodeAnalysis> {
+        self.static_analysis().map(|analysis| &analysis[self.entrypoint])
+    }
+}
+
+impl PartialEq for Program {
+    fn eq(&self, other: &Self) -> bool {
+        self.mast_forest == other.mast_forest
+            && self.entrypoint == other.entrypoint
+            && self.kernel == other.kernel
+            && self.isa_version == other.isa_version
+    }
 }
 
 // ------------------------------------------------------------------------------------------------
@@ -149,8 +204,31 @@ impl Program {
     }
 }
 
+/// Magic string for detecting that a file is a serialized [Program].
+const MAGIC: &[u8; 5] = b"PROG\0";
+
+/// The format version.
+///
+/// The serialization format of a [Program] is as follows:
+/// - `MAGIC`
+/// - `VERSION`
+/// - the version of the instruction set targeted by the program (`IsaVersion`)
+/// - the MAST forest of the program (`MastForest`)
+/// - the kernel of the program (`Kernel`)
+/// - the entrypoint of the program (`u32`)
+///
+/// Version `[0, 0, 0]` refers to the format used before ISA versions were introduced, in which
+/// programs start directly with their MAST forest, and have neither a header nor an ISA version.
+const VERSION: [u8; 3] = [0, 0, 1];
+
+/// The format versions which can be deserialized.
+const SUPPORTED_VERSIONS: FormatVersions = FormatVersions::new([0, 0, 0], VERSION);
+
 impl Serializable for Program {
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_bytes(MAGIC);
+        target.write_bytes(&VERSION);
+        self.isa_version.write_into(target);
         self.mast_forest.write_into(target);
         self.kernel.write_into(target);
         target.write_u32(self.entrypoint.as_u32());
@@ -159,6 +237,25 @@ impl Serializable for Program {
 
 impl Deserializable for Program {
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let version = if source.peek_u8()? == MAST_MAGIC[0] {
+            [0, 0, 0]
+        } else {
+            let magic: [u8; 5] = source.read_array()?;
+            if magic != *MAGIC {
+                return Err(DeserializationError::InvalidValue(format!(
+                    "invalid magic bytes. Expected '{MAGIC:?}', got '{magic:?}'"
+                )));
+            }
+            SUPPORTED_VERSIONS.read_version("program", source)?
+        };
+
+        // programs serialized before ISA versions were introduced target the initial version of
+        // the instruction set
+        let isa_version = if version == [0, 0, 0] {
+            IsaVersion::V1_0
+        } else {
+            source.read()?
+        };
         let mast_forest = Arc::new(source.read()?);
         let kernel = source.read()?;
         let entrypoint = MastNodeId::from_u32_safe(source.read_u32()?, &mast_forest)?;
@@ -169,7 +266,10 @@ impl Deserializable for Program {
             )));
         }
 
-        Ok(Self::with_kernel(mast_forest, entrypoint, kernel))
+        Ok(Self {
+            isa_version,
+            ..Self::with_kernel(mast_forest, entrypoint, kernel)
+        })
     }
 }
 
@@ -203,16 +303,32 @@ impl fmt::Display for Program {
 /// security guarantees provided by the kernel. We also allow the user to easily prove the
 /// membership of a given kernel procedure for a given proof, without compromising its
 /// zero-knowledge properties.
+///
+/// The program information also records the version of the instruction set the program was
+/// assembled for, so that verifiers can reject proofs of programs targeting an unsupported version.
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct ProgramInfo {
     program_hash: RpoDigest,
     kernel: Kernel,
+    isa_version: IsaVersion,
 }
 
 impl ProgramInfo {
-    /// Creates a new instance of a program info.
+    /// Creates a new instance of a program info, for a program targeting the current version of
+    /// the instruction set.
     pub const fn new(program_hash: RpoDigest, kernel: Kernel) -> Self {
-        Self { program_hash, kernel }
+        Self {
+            program_hash,
+            kernel,
+            isa_version: IsaVersion::CURRENT,
+        }
+    }
+
+    /// Returns this program info, with the version of the instruction set set to `isa_version`.
+    pub const fn with_isa_version(mut self, isa_version: IsaVersion) -> Self {
+        self.isa_version = isa_version;
+        self
     }
 
     /// Returns the program hash computed from its code block root.
@@ -229,14 +345,20 @@ impl ProgramInfo {
     pub fn kernel_procedures(&self) -> &[RpoDigest] {
         self.kernel.proc_hashes()
     }
+
+    /// Returns the version of the instruction set the program was assembled for.
+    pub const fn isa_version(&self) -> IsaVersion {
+        self.isa_version
+    }
 }
 
 impl From<Program> for ProgramInfo {
     fn from(program: Program) -> Self {
         let program_hash = program.hash();
         let kernel = program.kernel().clone();
+        let isa_version = program.isa_version();
 
-        Self { program_hash, kernel }
+        Self { program_hash, kernel, isa_version }
     }
 }
 
@@ -245,6 +367,7 @@ impl From<Program> for ProgramInfo {
 
 impl Serializable for ProgramInfo {
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.isa_version.write_into(target);
         self.program_hash.write_into(target);
         self.kernel.write_into(target);
     }
@@ -252,9 +375,10 @@ impl Serializable for ProgramInfo {
 
 impl Deserializable for ProgramInfo {
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let isa_version = source.read()?;
         let program_hash = source.read()?;
         let kernel = source.read()?;
-        Ok(Self { program_hash, kernel })
+        Ok(Self { program_hash, kernel, isa_version })
     }
 }
 
@@ -276,3 +400,74 @@ impl ToElements for ProgramInfo {
         result
     }
 }
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::ToString, sync::Arc, vec};
+
+    use winter_utils::{ByteWriter, Deserializable, Serializable};
+
+    use super::{IsaVersion, Kernel, MastForest, Program};
+    use crate::Operation;
+
+    fn build_program() -> Program {
+        let mut mast_forest = MastForest::new();
+        let entrypoint = mast_forest.add_block(vec![Operation::Add], None).unwrap();
+        mast_forest.make_root(entrypoint);
+        Program::new(Arc::new(mast_forest), entrypoint)
+    }
+
+    #[test]
+    fn program_serde_legacy_format() {
+        let program = build_program();
+
+        // programs serialized before ISA versions were introduced have no header
+        let mut bytes = vec![];
+        program.mast_forest().write_into(&mut bytes);
+        Kernel::default().write_into(&mut bytes);
+        bytes.write_u32(program.entrypoint().as_u32());
+
+        let deserialized = Program::read_from_bytes(&bytes).unwrap();
+        assert_eq!(deserialized.mast_forest(), program.mast_forest());
+        assert_eq!(deserialized.entrypoint(), program.entrypoint());
+        assert_eq!(deserialized.kernel(), program.kernel());
+        assert_eq!(deserialized.isa_version(), IsaVersion::V1_0);
+
+        // programs serialized in the current format round-trip
+        assert_eq!(Program::read_from_bytes(&program.to_bytes()).unwrap(), program);
+    }
+
+    #[test]
+    fn program_serde_unsupported_format_version() {
+        let mut bytes = build_program().to_bytes();
+        bytes[5..8].copy_from_slice(&[0, 1, 0]);
+
+        let err = Program::read_from_bytes(&bytes).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unsupported program format version 0.1.0; supported versions are 0.0.0 to 0.0.1"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn program_info_serde_json() {
+        use super::ProgramInfo;
+
+        let program = build_program();
+        let kernel = Kernel::new(&[program.hash()]).unwrap();
+        let info = ProgramInfo::new(program.hash(), kernel);
+
+        let json = serde_json::to_string(&info).unwrap();
+        assert!(json.contains(&program.hash().to_hex()));
+        assert_eq!(serde_json::from_str::<ProgramInfo>(&json).unwrap(), info);
+
+        // kernels with duplicated procedures are rejected
+        let hash = program.hash().to_hex();
+        let json = json.replace(&format!("[\"{hash}\"]"), &format!("[\"{hash}\",\"{hash}\"]"));
+        assert!(serde_json::from_str::<ProgramInfo>(&json).is_err());
+    }
+}
@@ -13,7 +13,9 @@ use crate::utils::{ByteReader, Deserializable, DeserializationError};
 ///
 /// The values in the struct are stored in the "stack order" - i.e., the last input is at the top
 /// of the stack (in position 0).
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct StackInputs {
     elements: [Felt; MIN_STACK_DEPTH],
 }
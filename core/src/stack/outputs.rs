@@ -17,6 +17,8 @@ use crate::utils::{ByteReader, Deserializable, DeserializationError, range};
 /// Thus, the value at the top of the stack is expected to be in the first position, and the order
 /// of the rest of the output elements will also match the order on the stack.
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct StackOutputs {
     elements: [Felt; MIN_STACK_DEPTH],
 }
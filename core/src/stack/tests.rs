@@ -128,3 +128,25 @@ fn test_outputs_empty() {
 
     assert_eq!(*inputs, *result);
 }
+
+// SERDE TESTS
+// ================================================================================================
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_stack_serde_json() {
+    let inputs = StackInputs::try_from_ints([1, 2, 3]).unwrap();
+    let json = serde_json::to_string(&inputs).unwrap();
+    assert_eq!(json, "[3,2,1,0,0,0,0,0,0,0,0,0,0,0,0,0]");
+    assert_eq!(serde_json::from_str::<StackInputs>(&json).unwrap(), inputs);
+
+    let outputs = StackOutputs::try_from_ints([1, 2, 3]).unwrap();
+    let json = serde_json::to_string(&outputs).unwrap();
+    assert_eq!(json, "[1,2,3,0,0,0,0,0,0,0,0,0,0,0,0,0]");
+    assert_eq!(serde_json::from_str::<StackOutputs>(&json).unwrap(), outputs);
+
+    // stacks of other depths and values outside of the field are rejected
+    assert!(serde_json::from_str::<StackInputs>("[1,2,3]").is_err());
+    let json = format!("[{},0,0,0,0,0,0,0,0,0,0,0,0,0,0,0]", u64::MAX);
+    assert!(serde_json::from_str::<StackOutputs>(&json).is_err());
+}
@@ -24,19 +24,30 @@ mod constants {
     pub const EVENT_ILOG2: u32                        = 2297972669;
     pub const EVENT_MEM_TO_MAP: u32                   = 2389394361;
     pub const EVENT_HDWORD_TO_MAP: u32                = 2391452729;
+    pub const EVENT_ENV_TO_STACK: u32                 = 2472184824;
+    pub const EVENT_MEM_PROTECT: u32                  = 2545618245;
+    pub const EVENT_NTT: u32                          = 2704591861;
+    pub const EVENT_INTT: u32                         = 2736143517;
     pub const EVENT_HDWORD_TO_MAP_WITH_DOMAIN: u32    = 2822590340;
     pub const EVENT_HPERM_TO_MAP: u32                 = 3297060969;
     pub const EVENT_FALCON_DIV: u32                   = 3419226155;
+    pub const EVENT_SECP256K1_BASE_INV: u32           = 3716254071;
+    pub const EVENT_SECP256K1_SCALAR_INV: u32         = 3962741930;
 }
 
 /// Defines a set of actions which can be initiated from the VM to inject new data into the advice
-/// provider.
+/// provider, or to configure the VM itself.
 ///
 /// These actions can affect all 3 components of the advice provider: Merkle store, advice stack,
 /// and advice map.
 ///
-/// All actions, except for `MerkleNodeMerge`, `Ext2Inv` and `UpdateMerkleNode` can be invoked
-/// directly from Miden assembly via dedicated instructions.
+/// All actions, except for `MerkleNodeMerge`, `Ext2Inv`, `UpdateMerkleNode`, `Ntt`, `Intt`,
+/// `MemProtect`, `EnvToStack`, `Secp256k1BaseInv` and `Secp256k1ScalarInv` can be invoked directly
+/// from Miden assembly via dedicated instructions. `MemProtect` is invoked via the
+/// `std::mem::protect` procedure of the standard library, `EnvToStack` via the environment
+/// procedures of the `std::sys` module, `Ntt` and `Intt` via the procedures of the
+/// `std::math::ntt` module, and the secp256k1 events via the `inv` procedures of the
+/// `std::math::secp256k1` modules.
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum SystemEvent {
     // MERKLE STORE EVENTS
@@ -173,6 +184,50 @@ pub enum SystemEvent {
     ///   coefficients are located at the top of the advice stack.
     Ext2Intt,
 
+    /// Evaluates a polynomial over the base field on the multiplicative subgroup of size `n`, and
+    /// pushes the evaluations onto the advice stack.
+    ///
+    /// The evaluation is performed using the NTT algorithm.
+    ///
+    /// Inputs:
+    ///   Operand stack: [n, coeffs_ptr, ...]
+    ///   Advice stack: [...]
+    ///
+    /// Outputs:
+    ///   Operand stack: [n, coeffs_ptr, ...]
+    ///   Advice stack: [evaluations...]
+    ///
+    /// - `n` is the number of coefficients of the polynomial. Must be a power of 2 greater than or
+    ///   equal to 4.
+    /// - `coeffs_ptr` is the word-aligned memory address of the first coefficient, with
+    ///   coefficients stored in order of increasing degree.
+    /// - `evaluations` are the evaluations of the polynomial at `ω^0, ..., ω^(n-1)`, where `ω` is
+    ///   the primitive `n`-th root of unity of the field, such that the evaluation at `ω^0` is
+    ///   located at the top of the advice stack.
+    Ntt,
+
+    /// Interpolates the evaluations of a polynomial over the base field on the multiplicative
+    /// subgroup of size `n` into a polynomial in coefficient form, and pushes the coefficients onto
+    /// the advice stack.
+    ///
+    /// The interpolation is performed using the iNTT algorithm; this is the inverse of
+    /// [SystemEvent::Ntt].
+    ///
+    /// Inputs:
+    ///   Operand stack: [n, evals_ptr, ...]
+    ///   Advice stack: [...]
+    ///
+    /// Outputs:
+    ///   Operand stack: [n, evals_ptr, ...]
+    ///   Advice stack: [coefficients...]
+    ///
+    /// - `n` is the number of evaluations. Must be a power of 2 greater than or equal to 4.
+    /// - `evals_ptr` is the word-aligned memory address of the evaluation at `ω^0`, with the
+    ///   evaluation at `ω^i` stored at `evals_ptr + i`.
+    /// - `coefficients` are the coefficients of the interpolated polynomial such that the lowest
+    ///   degree coefficient is located at the top of the advice stack.
+    Intt,
+
     /// Pushes onto the advice stack the value associated with the specified key in a Sparse
     /// Merkle Tree defined by the specified root.
     ///
@@ -242,6 +297,29 @@ pub enum SystemEvent {
     ///   Advice stack: [ilog2(n), ...]
     ILog2,
 
+    /// Pushes the multiplicative inverse of an element of the secp256k1 base field onto the advice
+    /// stack.
+    ///
+    /// Inputs:
+    ///   Operand stack: [a0, a1, a2, a3, a4, a5, a6, a7, ...]
+    ///   Advice stack: [...]
+    ///
+    /// Outputs:
+    ///   Operand stack: [a0, a1, a2, a3, a4, a5, a6, a7, ...]
+    ///   Advice stack: [b7, b6, b5, b4, b3, b2, b1, b0, ...]
+    ///
+    /// Where a = (a0, ..., a7) and b = (b0, ..., b7) are 256-bit integers in Montgomery form
+    /// represented by 32-bit limbs (least significant limb first), such that b = a^-1 (mod p).
+    /// If a = 0 (mod p), b is 0.
+    Secp256k1BaseInv,
+
+    /// Pushes the multiplicative inverse of an element of the secp256k1 scalar field onto the
+    /// advice stack.
+    ///
+    /// Inputs and outputs are the same as for `Secp256k1BaseInv`, except that the inverse is
+    /// computed modulo the order of the secp256k1 group.
+    Secp256k1ScalarInv,
+
     // ADVICE MAP SYSTEM EVENTS
     // --------------------------------------------------------------------------------------------
     /// Reads words from memory at the specified range and inserts them into the advice map under
@@ -300,6 +378,39 @@ pub enum SystemEvent {
     /// Where KEY is computed by extracting the digest elements from hperm([C, A, B]). For example,
     /// if C is [0, d, 0, 0], KEY will be set as hash(A || B, d).
     HpermToMap,
+
+    // MEMORY SYSTEM EVENTS
+    // --------------------------------------------------------------------------------------------
+    /// Marks the memory addresses in the range [start_addr, end_addr) of the current execution
+    /// context as read-only. Any subsequent write to these addresses fails the execution.
+    ///
+    /// This is enforced by the processor only (i.e., it is a debugging aid), and is not part of
+    /// the proven statement.
+    ///
+    /// Inputs:
+    ///   Operand stack: [start_addr, end_addr, ...]
+    ///
+    /// Outputs:
+    ///   Operand stack: [start_addr, end_addr, ...]
+    MemProtect,
+
+    // ENVIRONMENT SYSTEM EVENTS
+    // --------------------------------------------------------------------------------------------
+    /// Pushes the environment of the execution onto the advice stack, so that it can be read via
+    /// three `adv_loadw` instructions.
+    ///
+    /// The environment is the one the host provided when the execution started, and thus the one
+    /// committed to in the public inputs of the execution, even if the host reports a different
+    /// environment later on.
+    ///
+    /// Inputs:
+    ///   Advice stack: [...]
+    ///
+    /// Outputs:
+    ///   Advice stack: [CALLER_ID, SEED, HEADER, ...]
+    ///
+    /// Where HEADER is [block_num, timestamp, 0, 0].
+    EnvToStack,
 }
 
 impl SystemEvent {
@@ -313,16 +424,22 @@ impl SystemEvent {
             SystemEvent::FalconDiv => EVENT_FALCON_DIV,
             SystemEvent::Ext2Inv => EVENT_EXT2_INV,
             SystemEvent::Ext2Intt => EVENT_EXT2_INTT,
+            SystemEvent::Ntt => EVENT_NTT,
+            SystemEvent::Intt => EVENT_INTT,
             SystemEvent::SmtPeek => EVENT_SMT_PEEK,
             SystemEvent::U32Clz => EVENT_U32_CLZ,
             SystemEvent::U32Ctz => EVENT_U32_CTZ,
             SystemEvent::U32Clo => EVENT_U32_CLO,
             SystemEvent::U32Cto => EVENT_U32_CTO,
             SystemEvent::ILog2 => EVENT_ILOG2,
+            SystemEvent::Secp256k1BaseInv => EVENT_SECP256K1_BASE_INV,
+            SystemEvent::Secp256k1ScalarInv => EVENT_SECP256K1_SCALAR_INV,
             SystemEvent::MemToMap => EVENT_MEM_TO_MAP,
             SystemEvent::HdwordToMap => EVENT_HDWORD_TO_MAP,
             SystemEvent::HdwordToMapWithDomain => EVENT_HDWORD_TO_MAP_WITH_DOMAIN,
             SystemEvent::HpermToMap => EVENT_HPERM_TO_MAP,
+            SystemEvent::MemProtect => EVENT_MEM_PROTECT,
+            SystemEvent::EnvToStack => EVENT_ENV_TO_STACK,
         }
     }
 
@@ -338,16 +455,22 @@ impl SystemEvent {
             EVENT_FALCON_DIV => Some(SystemEvent::FalconDiv),
             EVENT_EXT2_INV => Some(SystemEvent::Ext2Inv),
             EVENT_EXT2_INTT => Some(SystemEvent::Ext2Intt),
+            EVENT_NTT => Some(SystemEvent::Ntt),
+            EVENT_INTT => Some(SystemEvent::Intt),
             EVENT_SMT_PEEK => Some(SystemEvent::SmtPeek),
             EVENT_U32_CLZ => Some(SystemEvent::U32Clz),
             EVENT_U32_CTZ => Some(SystemEvent::U32Ctz),
             EVENT_U32_CLO => Some(SystemEvent::U32Clo),
             EVENT_U32_CTO => Some(SystemEvent::U32Cto),
             EVENT_ILOG2 => Some(SystemEvent::ILog2),
+            EVENT_SECP256K1_BASE_INV => Some(SystemEvent::Secp256k1BaseInv),
+            EVENT_SECP256K1_SCALAR_INV => Some(SystemEvent::Secp256k1ScalarInv),
             EVENT_MEM_TO_MAP => Some(SystemEvent::MemToMap),
             EVENT_HDWORD_TO_MAP => Some(SystemEvent::HdwordToMap),
             EVENT_HDWORD_TO_MAP_WITH_DOMAIN => Some(SystemEvent::HdwordToMapWithDomain),
             EVENT_HPERM_TO_MAP => Some(SystemEvent::HpermToMap),
+            EVENT_MEM_PROTECT => Some(SystemEvent::MemProtect),
+            EVENT_ENV_TO_STACK => Some(SystemEvent::EnvToStack),
             _ => None,
         }
     }
@@ -370,16 +493,22 @@ impl fmt::Display for SystemEvent {
             Self::FalconDiv => write!(f, "falcon_div"),
             Self::Ext2Inv => write!(f, "ext2_inv"),
             Self::Ext2Intt => write!(f, "ext2_intt"),
+            Self::Ntt => write!(f, "ntt"),
+            Self::Intt => write!(f, "intt"),
             Self::SmtPeek => write!(f, "smt_peek"),
             Self::U32Clz => write!(f, "u32clz"),
             Self::U32Ctz => write!(f, "u32ctz"),
             Self::U32Clo => write!(f, "u32clo"),
             Self::U32Cto => write!(f, "u32cto"),
             Self::ILog2 => write!(f, "ilog2"),
+            Self::Secp256k1BaseInv => write!(f, "secp256k1_base_inv"),
+            Self::Secp256k1ScalarInv => write!(f, "secp256k1_scalar_inv"),
             Self::MemToMap => write!(f, "mem_to_map"),
             Self::HdwordToMap => write!(f, "hdword_to_map"),
             Self::HdwordToMapWithDomain => write!(f, "hdword_to_map_with_domain"),
             Self::HpermToMap => write!(f, "hperm_to_map"),
+            Self::MemProtect => write!(f, "mem_protect"),
+            Self::EnvToStack => write!(f, "env_to_stack"),
         }
     }
 }
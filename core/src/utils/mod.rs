@@ -1,8 +1,10 @@
+#[cfg(feature = "serde")]
+pub mod serde_bytes;
 pub mod sync;
 
-use alloc::vec::Vec;
+use alloc::{string::ToString, vec::Vec};
 use core::{
-    fmt::Debug,
+    fmt::{self, Debug},
     ops::{Bound, Range},
 };
 
@@ -101,6 +103,114 @@ where
     }
 }
 
+// FORMAT VERSIONS
+// ================================================================================================
+
+/// The range of versions of a serialization format which can be read by a deserializer.
+///
+/// Format versions consist of three bytes, and are ordered lexicographically. A version of
+/// `[255, 255, 255]` is reserved for future extensions of the version field itself, and is never
+/// supported.
+///
+/// Formats which only add optional data in patch versions, which older deserializers can skip, can
+/// be made forward-compatible with [FormatVersions::with_newer_patches], in which case newer patch
+/// versions of the current version are supported as well.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FormatVersions {
+    /// The oldest supported version.
+    pub oldest: [u8; 3],
+    /// The current version, i.e., the newest version which can be written.
+    pub current: [u8; 3],
+    /// Whether newer patch versions of the current version are supported.
+    pub newer_patches: bool,
+}
+
+impl FormatVersions {
+    /// Returns a new [FormatVersions] supporting all versions from `oldest` to `current`.
+    pub const fn new(oldest: [u8; 3], current: [u8; 3]) -> Self {
+        Self { oldest, current, newer_patches: false }
+    }
+
+    /// Returns these [FormatVersions], supporting newer patch versions of the current version as
+    /// well.
+    pub const fn with_newer_patches(mut self) -> Self {
+        self.newer_patches = true;
+        self
+    }
+
+    /// Returns true if the specified version is supported.
+    pub fn contains(&self, version: [u8; 3]) -> bool {
+        if version == [u8::MAX; 3] || version < self.oldest {
+            return false;
+        }
+        version <= self.current || (self.newer_patches && version[..2] == self.current[..2])
+    }
+
+    /// Returns an error if the specified version is not supported. `format` names the
+    /// serialization format in the error message, e.g. "MAST forest".
+    pub fn check(&self, format: &'static str, version: [u8; 3]) -> Result<(), UnsupportedVersion> {
+        if self.contains(version) {
+            Ok(())
+        } else {
+            Err(UnsupportedVersion { format, version, supported: *self })
+        }
+    }
+
+    /// Reads a version from `source`, and returns it if it is supported.
+    ///
+    /// # Errors
+    /// Returns an error listing the supported versions if the version is not supported. `format`
+    /// names the serialization format in the error message, e.g. "MAST forest".
+    pub fn read_version<R: ByteReader>(
+        &self,
+        format: &'static str,
+        source: &mut R,
+    ) -> Result<[u8; 3], DeserializationError> {
+        let version: [u8; 3] = source.read_array()?;
+        self.check(format, version)?;
+        Ok(version)
+    }
+}
+
+impl fmt::Display for FormatVersions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [oldest_major, oldest_minor, oldest_patch] = self.oldest;
+        let [current_major, current_minor, current_patch] = self.current;
+        write!(
+            f,
+            "{oldest_major}.{oldest_minor}.{oldest_patch} to {current_major}.{current_minor}."
+        )?;
+        if self.newer_patches {
+            f.write_str("x")
+        } else {
+            write!(f, "{current_patch}")
+        }
+    }
+}
+
+/// An error returned when a serialized value uses a version of its format which cannot be read.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error(
+    "unsupported {format} format version {}.{}.{}; supported versions are {supported}",
+    .version[0],
+    .version[1],
+    .version[2]
+)]
+pub struct UnsupportedVersion {
+    /// The name of the serialization format, e.g. "MAST forest".
+    pub format: &'static str,
+    /// The version of the serialized value.
+    pub version: [u8; 3],
+    /// The versions which can be read.
+    pub supported: FormatVersions,
+}
+
+impl From<UnsupportedVersion> for DeserializationError {
+    fn from(error: UnsupportedVersion) -> Self {
+        DeserializationError::InvalidValue(error.to_string())
+    }
+}
+
 // ARRAY CONSTRUCTORS
 // ================================================================================================
 
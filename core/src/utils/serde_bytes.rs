@@ -0,0 +1,126 @@
+//! Serde support for types which define their own binary serialization.
+//!
+//! [serialize()] and [deserialize()] serialize a value with its [Serializable] implementation: as a
+//! hex string in human-readable formats (e.g., JSON), and as a byte string in binary formats (e.g.,
+//! CBOR). They can be used with `#[serde(with = "miden_core::utils::serde_bytes")]`, or to
+//! implement `serde::Serialize` and `serde::Deserialize` for such types. [serialize_bytes()] and
+//! [deserialize_bytes()] do the same for types with other binary encodings.
+
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+
+use serde::{
+    Deserializer, Serializer,
+    de::{self, SeqAccess, Visitor},
+};
+
+use super::{Deserializable, Serializable};
+
+/// Serializes `value` as a hex string in human-readable formats, and as bytes otherwise.
+pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Serializable,
+    S: Serializer,
+{
+    serialize_bytes(&value.to_bytes(), serializer)
+}
+
+/// Deserializes a value serialized with [serialize()].
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: Deserializable,
+    D: Deserializer<'de>,
+{
+    let bytes = deserialize_bytes(deserializer)?;
+    T::read_from_bytes(&bytes).map_err(de::Error::custom)
+}
+
+/// Serializes `bytes` as a hex string in human-readable formats, and as bytes otherwise.
+pub fn serialize_bytes<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    if serializer.is_human_readable() {
+        serializer.serialize_str(&to_hex(bytes))
+    } else {
+        serializer.serialize_bytes(bytes)
+    }
+}
+
+/// Deserializes bytes serialized with [serialize_bytes()].
+///
+/// Sequences of bytes are accepted in human-readable formats as well.
+pub fn deserialize_bytes<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_any(BytesVisitor)
+    } else {
+        deserializer.deserialize_byte_buf(BytesVisitor)
+    }
+}
+
+// VISITOR
+// ================================================================================================
+
+/// Reads bytes from a hex string, or from a sequence of bytes.
+struct BytesVisitor;
+
+impl<'de> Visitor<'de> for BytesVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a hex string or a sequence of bytes")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Vec<u8>, E> {
+        from_hex(value).map_err(E::custom)
+    }
+
+    fn visit_bytes<E: de::Error>(self, value: &[u8]) -> Result<Vec<u8>, E> {
+        Ok(value.to_vec())
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, value: Vec<u8>) -> Result<Vec<u8>, E> {
+        Ok(value)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Vec<u8>, A::Error> {
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(byte) = seq.next_element()? {
+            bytes.push(byte);
+        }
+        Ok(bytes)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the lowercase hex encoding of `bytes`, prefixed with `0x`.
+fn to_hex(bytes: &[u8]) -> String {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+    let mut hex = String::with_capacity(2 + 2 * bytes.len());
+    hex.push_str("0x");
+    for byte in bytes {
+        hex.push(DIGITS[(byte >> 4) as usize] as char);
+        hex.push(DIGITS[(byte & 0xf) as usize] as char);
+    }
+    hex
+}
+
+/// Decodes a hex string, optionally prefixed with `0x`.
+fn from_hex(hex: &str) -> Result<Vec<u8>, &'static str> {
+    let hex = hex.strip_prefix("0x").unwrap_or(hex).as_bytes();
+    if hex.len() % 2 != 0 {
+        return Err("hex string has an odd number of digits");
+    }
+
+    fn digit(c: u8) -> Result<u8, &'static str> {
+        match c {
+            b'0'..=b'9' => Ok(c - b'0'),
+            b'a'..=b'f' => Ok(c - b'a' + 10),
+            b'A'..=b'F' => Ok(c - b'A' + 10),
+            _ => Err("hex string contains a non-hex digit"),
+        }
+    }
+
+    hex.chunks_exact(2)
+        .map(|pair| Ok((digit(pair[0])? << 4) | digit(pair[1])?))
+        .collect()
+}
@@ -0,0 +1,22 @@
+//! Parses arbitrary source text as Miden Assembly modules of every kind.
+//!
+//! The parser must reject malformed input with a diagnostic; it must never panic, regardless of
+//! the module kind the source is parsed as.
+#![no_main]
+
+use std::sync::Arc;
+
+use assembly::{DefaultSourceManager, LibraryPath, ModuleParser, ast::ModuleKind};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(source) = core::str::from_utf8(data) else {
+        return;
+    };
+
+    let source_manager = Arc::new(DefaultSourceManager::default());
+    for kind in [ModuleKind::Executable, ModuleKind::Library, ModuleKind::Kernel] {
+        let path = LibraryPath::new("fuzz::module").unwrap();
+        let _ = ModuleParser::new(kind).parse_str(path, source, source_manager.as_ref());
+    }
+});
@@ -0,0 +1,22 @@
+//! Deserializes arbitrary bytes as a [MastForest].
+//!
+//! Deserialization must reject malformed input with an error, and any forest it accepts must
+//! survive a serialization round trip unchanged.
+#![no_main]
+
+use assembly::{
+    mast::MastForest,
+    utils::{Deserializable, Serializable},
+};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(forest) = MastForest::read_from_bytes(data) else {
+        return;
+    };
+
+    let bytes = forest.to_bytes();
+    let deserialized =
+        MastForest::read_from_bytes(&bytes).expect("failed to deserialize a serialized forest");
+    assert_eq!(forest, deserialized);
+});
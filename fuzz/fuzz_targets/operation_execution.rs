@@ -0,0 +1,66 @@
+//! Executes a program consisting of a single basic block of arbitrary operations.
+//!
+//! The input is decoded as a sequence of serialized operations, from which control flow
+//! operations are dropped (these are only valid when emitted by the decoder). The processor must
+//! reject invalid executions with an error; it must never panic.
+#![no_main]
+
+use std::sync::Arc;
+
+use libfuzzer_sys::fuzz_target;
+use processor::{
+    DefaultHost, ExecutionOptions, MastForest, Operation, Program, StackInputs, execute,
+    utils::{Deserializable, SliceReader},
+};
+
+/// The maximum number of operations decoded from a single input.
+const MAX_OPERATIONS: usize = 1024;
+
+/// The maximum number of cycles a single input may execute for.
+const MAX_CYCLES: u32 = 1 << 14;
+
+fuzz_target!(|data: &[u8]| {
+    let mut reader = SliceReader::new(data);
+    let mut operations = Vec::new();
+    while operations.len() < MAX_OPERATIONS {
+        let Ok(op) = Operation::read_from(&mut reader) else {
+            break;
+        };
+        if !is_control_flow(&op) {
+            operations.push(op);
+        }
+    }
+    if operations.is_empty() {
+        return;
+    }
+
+    let mut mast_forest = MastForest::new();
+    let Ok(entrypoint) = mast_forest.add_block(operations, None) else {
+        return;
+    };
+    mast_forest.make_root(entrypoint);
+    let program = Program::new(Arc::new(mast_forest), entrypoint);
+
+    let options = ExecutionOptions::new(Some(MAX_CYCLES), MAX_CYCLES, false, false).unwrap();
+    let _ = execute(&program, StackInputs::default(), &mut DefaultHost::default(), options);
+});
+
+/// Returns true if the operation may only be emitted by the decoder when entering or leaving a
+/// node, and is therefore invalid inside a basic block.
+fn is_control_flow(op: &Operation) -> bool {
+    matches!(
+        op,
+        Operation::Join
+            | Operation::Split
+            | Operation::Loop
+            | Operation::Call
+            | Operation::Dyn
+            | Operation::Dyncall
+            | Operation::SysCall
+            | Operation::Span
+            | Operation::End
+            | Operation::Repeat
+            | Operation::Respan
+            | Operation::Halt
+    )
+}
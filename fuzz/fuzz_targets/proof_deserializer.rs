@@ -0,0 +1,19 @@
+//! Deserializes arbitrary bytes as an [ExecutionProof].
+//!
+//! Deserialization must reject malformed input with an error, and any proof it accepts must
+//! survive a serialization round trip unchanged.
+#![no_main]
+
+use air::ExecutionProof;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(proof) = ExecutionProof::from_bytes(data) else {
+        return;
+    };
+
+    let bytes = proof.to_bytes();
+    let deserialized =
+        ExecutionProof::from_bytes(&bytes).expect("failed to deserialize a serialized proof");
+    assert_eq!(proof, deserialized);
+});
@@ -0,0 +1,265 @@
+//! A miniature token-transfer rollup built on top of Miden VM.
+//!
+//! The state of the rollup is a Sparse Merkle Tree mapping account ids to balances. The rules of
+//! the rollup are defined by a kernel exposing a single `transfer` procedure, and every transfer
+//! is a program which invokes this procedure via a syscall. Transfers are executed and proven one
+//! by one against the current state root, and are then aggregated into a batch by a program which
+//! verifies their proofs recursively. A single proof of the batch thus attests to the transition
+//! of the rollup from its initial state root to its final one, provided that the recursive
+//! verifier checks the constraints of the VM, which `std::crypto::stark::verify` does not do yet:
+//! this example must not be used as is to aggregate untrusted proofs.
+//!
+//! The example can be run as follows:
+//!
+//! ```text
+//! cargo run --release -p miden-vm --example rollup
+//! ```
+
+use std::{sync::Arc, time::Instant};
+
+use miden_vm::{
+    AdviceInputs, Assembler, DefaultHost, ExecutionProof, MemAdviceProvider, Program, ProgramInfo,
+    ProvingOptions, StackInputs, StackOutputs, Word, ZERO,
+    assembly::{DefaultSourceManager, KernelLibrary},
+    crypto::{MerkleStore, RpoDigest, Smt},
+    math::Felt,
+    prove,
+    recursive::{VerifierData, generate_verifier_data},
+    verify,
+};
+use stdlib::StdLibrary;
+
+const KERNEL_SOURCE: &str = include_str!("kernel.masm");
+const BATCH_SOURCE: &str = include_str!("batch.masm");
+const TRANSFER_SOURCE: &str = "begin syscall.transfer end";
+
+fn main() {
+    let mut rollup = Rollup::new(&[(1, 1000), (2, 500), (3, 0)]);
+    let initial_root = rollup.state_root();
+    println!("initial state root: {}", initial_root);
+
+    let transfers = [
+        Transfer { from: 1, to: 3, amount: 250 },
+        Transfer { from: 2, to: 1, amount: 500 },
+        Transfer { from: 3, to: 2, amount: 100 },
+    ];
+
+    let mut proofs = Vec::with_capacity(transfers.len());
+    for transfer in transfers {
+        let now = Instant::now();
+        let proof = rollup.apply_transfer(transfer).expect("failed to apply transfer");
+        println!(
+            "transferred {} tokens from account {} to account {} in {} ms",
+            transfer.amount,
+            transfer.from,
+            transfer.to,
+            now.elapsed().as_millis()
+        );
+        proofs.push(proof);
+    }
+
+    // the kernel rejects transfers exceeding the balance of the sender
+    let overdraft = Transfer { from: 3, to: 1, amount: 1000 };
+    match rollup.apply_transfer(overdraft) {
+        Ok(_) => panic!("the kernel accepted an overdraft"),
+        Err(err) => println!("rejected transfer of {} tokens: {}", overdraft.amount, err),
+    }
+
+    for id in 1..=3 {
+        println!("balance of account {}: {}", id, rollup.balance(id));
+    }
+
+    let now = Instant::now();
+    let final_root = rollup.prove_batch(initial_root, proofs);
+    assert_eq!(final_root, rollup.state_root());
+    println!(
+        "proved batch transitioning the state to root {} in {} ms",
+        final_root,
+        now.elapsed().as_millis()
+    );
+}
+
+// ROLLUP
+// ================================================================================================
+
+/// A transfer of tokens between two accounts.
+#[derive(Debug, Clone, Copy)]
+struct Transfer {
+    from: u64,
+    to: u64,
+    amount: u32,
+}
+
+/// The operator of the rollup, which maintains its state and proves its transitions.
+struct Rollup {
+    kernel: KernelLibrary,
+    transfer_program: Program,
+    batch_program: Program,
+    state: Smt,
+}
+
+impl Rollup {
+    /// Returns a new rollup whose accounts hold the specified balances.
+    fn new(balances: &[(u64, u32)]) -> Self {
+        let source_manager = Arc::new(DefaultSourceManager::default());
+
+        let kernel = Assembler::new(source_manager.clone())
+            .with_library(StdLibrary::default())
+            .expect("failed to load the standard library")
+            .assemble_kernel(KERNEL_SOURCE)
+            .expect("failed to assemble the kernel");
+        let transfer_program = Assembler::with_kernel(source_manager.clone(), kernel.clone())
+            .assemble_program(TRANSFER_SOURCE)
+            .expect("failed to assemble the transfer program");
+        let batch_program = Assembler::new(source_manager)
+            .with_library(StdLibrary::default())
+            .expect("failed to load the standard library")
+            .assemble_program(BATCH_SOURCE)
+            .expect("failed to assemble the batch program");
+
+        let state = Smt::with_entries(
+            balances.iter().map(|&(id, balance)| (account_key(id), account_value(balance))),
+        )
+        .expect("duplicate accounts");
+
+        Self {
+            kernel,
+            transfer_program,
+            batch_program,
+            state,
+        }
+    }
+
+    /// Returns the root of the state tree.
+    fn state_root(&self) -> RpoDigest {
+        self.state.root()
+    }
+
+    /// Returns the balance of the specified account.
+    fn balance(&self, id: u64) -> u32 {
+        self.state.get_value(&account_key(id))[0].as_int() as u32
+    }
+
+    /// Executes and proves the specified transfer against the current state, and then updates the
+    /// state accordingly.
+    ///
+    /// Returns the data needed to verify the proof of the transfer inside the VM.
+    fn apply_transfer(&mut self, transfer: Transfer) -> Result<VerifierData, String> {
+        let root: Word = self.state_root().into();
+        let stack_inputs = StackInputs::try_from_ints([
+            transfer.amount as u64,
+            transfer.to,
+            transfer.from,
+            root[0].as_int(),
+            root[1].as_int(),
+            root[2].as_int(),
+            root[3].as_int(),
+        ])
+        .unwrap();
+
+        // proofs of transfers are verified recursively, and thus must be generated using RPO-256
+        let options = ProvingOptions::with_96_bit_security(true);
+        let mut host = self.build_host(advice_inputs_for(&self.state));
+        let (stack_outputs, proof) =
+            prove(&self.transfer_program, stack_inputs.clone(), &mut host, options)
+                .map_err(|err| err.to_string())?;
+
+        // update the state natively, and make sure that it matches the one computed by the VM
+        let sender_balance = self.balance(transfer.from) - transfer.amount;
+        self.state.insert(account_key(transfer.from), account_value(sender_balance));
+        let receiver_balance = self.balance(transfer.to) + transfer.amount;
+        self.state.insert(account_key(transfer.to), account_value(receiver_balance));
+        assert_eq!(stack_outputs.get_stack_word(0), Some(self.state_root().into()));
+
+        let program_info = ProgramInfo::from(self.transfer_program.clone());
+        verify_natively(program_info.clone(), stack_inputs.clone(), &stack_outputs, &proof);
+
+        generate_verifier_data(proof, program_info, stack_inputs, stack_outputs)
+            .map_err(|err| err.to_string())
+    }
+
+    /// Proves a batch of transfers by verifying their proofs recursively, and returns the state
+    /// root resulting from applying the transfers to the specified initial state root.
+    fn prove_batch(&self, initial_root: RpoDigest, proofs: Vec<VerifierData>) -> RpoDigest {
+        // the data of all proofs is provided to the batch program via the advice provider
+        let num_transfers = proofs.len() as u64;
+        let mut advice_stack = Vec::new();
+        let mut store = MerkleStore::default();
+        let mut advice_map = Vec::new();
+        for proof in proofs {
+            advice_stack.extend(proof.initial_stack);
+            advice_stack.extend(proof.advice_stack);
+            store.extend(proof.store.inner_nodes());
+            advice_map.extend(proof.advice_map);
+        }
+        let advice_inputs = AdviceInputs::default()
+            .with_stack_values(advice_stack)
+            .unwrap()
+            .with_merkle_store(store)
+            .with_map(advice_map);
+
+        // the batch is bound to the transfer program, its kernel and the initial state root
+        let kernel_proc_digest = self.transfer_program.kernel().proc_hashes()[0];
+        let mut stack_inputs = Vec::new();
+        stack_inputs.extend(word_to_ints(kernel_proc_digest.into()));
+        stack_inputs.extend(word_to_ints(self.transfer_program.hash().into()));
+        stack_inputs.extend(word_to_ints(initial_root.into()));
+        stack_inputs.push(num_transfers);
+        let stack_inputs = StackInputs::try_from_ints(stack_inputs).unwrap();
+
+        let mut host = self.build_host(advice_inputs);
+        let (stack_outputs, proof) =
+            prove(&self.batch_program, stack_inputs.clone(), &mut host, ProvingOptions::default())
+                .expect("failed to prove the batch");
+
+        let program_info = ProgramInfo::from(self.batch_program.clone());
+        verify_natively(program_info, stack_inputs, &stack_outputs, &proof);
+
+        stack_outputs.get_stack_word(0).expect("missing final state root").into()
+    }
+
+    /// Returns a host loaded with the procedures of the standard library and of the kernel.
+    fn build_host(&self, advice_inputs: AdviceInputs) -> DefaultHost<MemAdviceProvider> {
+        let mut host = DefaultHost::new(MemAdviceProvider::from(advice_inputs));
+        host.load_mast_forest(StdLibrary::default().mast_forest().clone()).unwrap();
+        host.load_mast_forest(self.kernel.mast_forest().clone()).unwrap();
+        host
+    }
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Returns the key under which the balance of the specified account is stored. Each account lives
+/// in its own leaf, as leaves are indexed by the most significant element of the key.
+fn account_key(id: u64) -> RpoDigest {
+    RpoDigest::new([ZERO, ZERO, ZERO, Felt::new(id)])
+}
+
+/// Returns the value holding the specified balance.
+fn account_value(balance: u32) -> Word {
+    [Felt::from(balance), ZERO, ZERO, ZERO]
+}
+
+/// Returns the advice inputs needed to read and update the specified state tree inside the VM.
+fn advice_inputs_for(state: &Smt) -> AdviceInputs {
+    let leaves = state.leaves().map(|(_, leaf)| (leaf.hash(), leaf.to_elements()));
+    AdviceInputs::default()
+        .with_merkle_store(MerkleStore::from(state))
+        .with_map(leaves)
+}
+
+/// Verifies the specified proof natively, panicking if it is invalid.
+fn verify_natively(
+    program_info: ProgramInfo,
+    stack_inputs: StackInputs,
+    stack_outputs: &StackOutputs,
+    proof: &ExecutionProof,
+) {
+    verify(program_info, stack_inputs, stack_outputs.clone(), proof.clone())
+        .expect("failed to verify the proof");
+}
+
+fn word_to_ints(word: Word) -> [u64; 4] {
+    word.map(|element| element.as_int())
+}
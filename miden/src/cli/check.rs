@@ -0,0 +1,60 @@
+use std::path::PathBuf;
+
+use assembly::{Assembler, LibraryNamespace, diagnostics::Report};
+use clap::Parser;
+use stdlib::StdLibrary;
+
+use super::data::{Libraries, TrustOptions};
+
+#[derive(Debug, Clone, Parser)]
+#[clap(
+    about = "Check a miden program or a directory of library modules for errors without compiling it"
+)]
+pub struct CheckCmd {
+    /// Path to a .masm program file, or to a directory containing the `.masm` files of a library
+    #[clap(value_parser)]
+    path: PathBuf,
+    /// Defines the top-level namespace of the library, e.g. `mylib`, otherwise the directory name
+    /// is used. Ignored when checking a program.
+    #[clap(short, long)]
+    namespace: Option<String>,
+    /// Paths to .masl library files or .masp library packages
+    #[clap(short = 'l', long = "libraries", value_parser)]
+    library_paths: Vec<PathBuf>,
+    #[clap(flatten)]
+    trust: TrustOptions,
+    /// Treat warnings as errors
+    #[clap(short = 'W', long = "warnings-as-errors", action)]
+    warnings_as_errors: bool,
+}
+
+impl CheckCmd {
+    pub fn execute(&self) -> Result<(), Report> {
+        let mut assembler = Assembler::default().with_warnings_as_errors(self.warnings_as_errors);
+        assembler.add_library(StdLibrary::default())?;
+        for library in Libraries::new(&self.library_paths, &self.trust.policy())?.libraries {
+            assembler.add_library(library)?;
+        }
+
+        if self.path.is_dir() {
+            let namespace = match &self.namespace {
+                Some(namespace) => namespace.clone(),
+                None => self
+                    .path
+                    .file_name()
+                    .ok_or("`path` cannot end with `..`.")
+                    .map_err(Report::msg)?
+                    .to_string_lossy()
+                    .into_owned(),
+            };
+            let namespace = namespace.parse::<LibraryNamespace>()?;
+            assembler.check_dir(namespace, &self.path)?;
+        } else {
+            assembler.check_program(self.path.as_path())?;
+        }
+
+        println!("No errors found in {}", self.path.display());
+
+        Ok(())
+    }
+}
@@ -2,8 +2,12 @@ use std::path::PathBuf;
 
 use assembly::diagnostics::{IntoDiagnostic, Report, WrapErr};
 use clap::Parser;
+use vm_core::{
+    mast::{GraphFormat, MastGraph},
+    utils::Serializable,
+};
 
-use super::data::{Debug, Libraries, ProgramFile};
+use super::data::{Debug, Libraries, ProgramFile, TrustOptions};
 
 #[derive(Debug, Clone, Parser)]
 #[clap(about = "Compile a miden program")]
@@ -11,12 +15,26 @@ pub struct CompileCmd {
     /// Path to .masm assembly file
     #[clap(short = 'a', long = "assembly", value_parser)]
     assembly_file: PathBuf,
-    /// Paths to .masl library files
+    /// Paths to .masl library files or .masp library packages
     #[clap(short = 'l', long = "libraries", value_parser)]
     library_paths: Vec<PathBuf>,
+    #[clap(flatten)]
+    trust: TrustOptions,
     /// Path to output file
     #[clap(short = 'o', long = "output", value_parser)]
     output_file: Option<PathBuf>,
+    /// Path to a file to which the MAST of the program is written as a graph; the graph is written
+    /// in the Mermaid format if the file has the `.mmd` extension, and in the DOT format otherwise
+    #[clap(long = "graph", value_parser)]
+    graph_file: Option<PathBuf>,
+    /// Path to a file to which the source map of the program is written; the compiled program is
+    /// stripped of its decorators, with the source map providing the locations of its instructions
+    #[clap(long = "source-map", value_parser)]
+    source_map_file: Option<PathBuf>,
+    /// Features enabled when compiling the program, i.e. which make the procedures and
+    /// instructions annotated with `@cfg(feature = "<name>")` part of the program
+    #[clap(long = "features", value_delimiter = ',')]
+    features: Vec<String>,
 }
 
 impl CompileCmd {
@@ -26,18 +44,44 @@ impl CompileCmd {
         println!("============================================================");
 
         // load the program from file and parse it
-        let program = ProgramFile::read(&self.assembly_file)?;
+        let program = ProgramFile::read_with_features(&self.assembly_file, &self.features)?;
 
         // load libraries from files
-        let libraries = Libraries::new(&self.library_paths)?;
+        let libraries = Libraries::new(&self.library_paths, &self.trust.policy())?;
 
-        // compile the program
-        let compiled_program = program.compile(Debug::Off, &libraries.libraries)?;
+        // compile the program, along with its source map if requested
+        let compiled_program = match &self.source_map_file {
+            Some(source_map_path) => {
+                let (compiled_program, source_map) =
+                    program.compile_with_source_map(&libraries.libraries)?;
+                std::fs::write(source_map_path, source_map.to_bytes())
+                    .into_diagnostic()
+                    .wrap_err("Failed to write the source map of the program")?;
+                compiled_program
+            },
+            None => program.compile(Debug::Off, &libraries.libraries)?,
+        };
 
         // report program hash to user
         let program_hash: [u8; 32] = compiled_program.hash().into();
         println!("program hash is {}", hex::encode(program_hash));
 
+        // write the graph of the compiled program's MAST, whose procedures are named after the ones
+        // of the program compiled in debug mode
+        if let Some(graph_path) = &self.graph_file {
+            let format = match graph_path.extension().and_then(|ext| ext.to_str()) {
+                Some("mmd") => GraphFormat::Mermaid,
+                _ => GraphFormat::Dot,
+            };
+            let debug_program = program.compile(Debug::On, &libraries.libraries)?;
+            let graph = MastGraph::new(compiled_program.mast_forest(), format)
+                .with_names_of(debug_program.mast_forest())
+                .to_string();
+            std::fs::write(graph_path, graph)
+                .into_diagnostic()
+                .wrap_err("Failed to write the graph of the program")?;
+        }
+
         // write the compiled program into the specified path if one is provided; if the path is
         // not provided, writes the file into the same directory as the source file, but with
         // `.masb` extension.
@@ -6,15 +6,18 @@ use std::{
 };
 
 use assembly::{
-    Assembler, Library, LibraryNamespace,
+    Assembler, Library, LibraryNamespace, StackDepthReport,
     ast::{Module, ModuleKind},
-    diagnostics::{Report, WrapErr},
+    diagnostics::{IntoDiagnostic, Report, WrapErr},
 };
+use ed25519_dalek::{Signature, VerifyingKey};
 use miden_vm::{Digest, ExecutionProof, Program, StackOutputs, utils::SliceReader};
+use package::{PUBLIC_KEY_LEN, PublicKey, SIGNATURE_LEN, SignatureVerifier, TrustPolicy};
 use prover::utils::Deserializable;
 use serde_derive::{Deserialize, Serialize};
 use stdlib::StdLibrary;
 use tracing::instrument;
+use vm_core::mast::SourceMap;
 
 // HELPERS
 // ================================================================================================
@@ -108,27 +111,30 @@ impl OutputFile {
 
 pub struct ProgramFile {
     ast: Box<Module>,
-    source_manager: Arc<dyn assembly::SourceManager>,
+    source_manager: Arc<dyn assembly::SourceManager + Send + Sync>,
 }
 
 /// Helper methods to interact with masm program file.
 impl ProgramFile {
-    /// Reads the masm file at the specified path and parses it into a [ProgramFile].
-    pub fn read(path: impl AsRef<Path>) -> Result<Self, Report> {
+    /// Reads the masm file at the specified path and parses it into a [ProgramFile], enabling the
+    /// specified features for conditional compilation.
+    pub fn read_with_features(path: impl AsRef<Path>, features: &[String]) -> Result<Self, Report> {
         let source_manager = Arc::new(assembly::DefaultSourceManager::default());
-        Self::read_with(path, source_manager)
+        Self::read_with(path, features, source_manager)
     }
 
     /// Reads the masm file at the specified path and parses it into a [ProgramFile], using the
     /// provided [assembly::SourceManager] implementation.
-    #[instrument(name = "read_program_file", skip(source_manager), fields(path = %path.as_ref().display()))]
+    #[instrument(name = "read_program_file", skip(features, source_manager), fields(path = %path.as_ref().display()))]
     pub fn read_with(
         path: impl AsRef<Path>,
-        source_manager: Arc<dyn assembly::SourceManager>,
+        features: &[String],
+        source_manager: Arc<dyn assembly::SourceManager + Send + Sync>,
     ) -> Result<Self, Report> {
         // parse the program into an AST
         let path = path.as_ref();
         let mut parser = Module::parser(ModuleKind::Executable);
+        parser.set_features(features.iter().map(|feature| feature.as_str().into()).collect());
         let ast = parser
             .parse_file(LibraryNamespace::Exec.into(), path, &source_manager)
             .wrap_err_with(|| format!("Failed to parse program file `{}`", path.display()))?;
@@ -143,19 +149,51 @@ impl ProgramFile {
         I: IntoIterator<Item = &'a Library>,
     {
         // compile program
-        let mut assembler =
-            Assembler::new(self.source_manager.clone()).with_debug_mode(debug.is_on());
+        let assembler = self.assembler(libraries)?.with_debug_mode(debug.is_on());
+        let program: Program = assembler
+            .assemble_program(self.ast.as_ref())
+            .wrap_err("Failed to compile program")?;
+
+        Ok(program)
+    }
+
+    /// Compiles this program without decorators, and returns it along with its source map.
+    #[instrument(name = "compile_program_with_source_map", skip_all)]
+    pub fn compile_with_source_map<'a, I>(
+        &self,
+        libraries: I,
+    ) -> Result<(Program, SourceMap), Report>
+    where
+        I: IntoIterator<Item = &'a Library>,
+    {
+        self.assembler(libraries)?
+            .assemble_program_with_source_map(self.ast.as_ref())
+            .wrap_err("Failed to compile program")
+    }
+
+    /// Statically analyzes the stack depth of the procedures of this program.
+    pub fn analyze_stack_depth<'a, I>(&self, libraries: I) -> Result<StackDepthReport, Report>
+    where
+        I: IntoIterator<Item = &'a Library>,
+    {
+        self.assembler(libraries)?
+            .analyze_stack_depth(self.ast.as_ref())
+            .wrap_err("Failed to analyze program")
+    }
+
+    /// Returns an assembler with the standard library and the specified libraries loaded.
+    fn assembler<'a, I>(&self, libraries: I) -> Result<Assembler, Report>
+    where
+        I: IntoIterator<Item = &'a Library>,
+    {
+        let mut assembler = Assembler::new(self.source_manager.clone());
         assembler.add_library(StdLibrary::default()).wrap_err("Failed to load stdlib")?;
 
         for library in libraries {
             assembler.add_library(library).wrap_err("Failed to load libraries")?;
         }
 
-        let program: Program = assembler
-            .assemble_program(self.ast.as_ref())
-            .wrap_err("Failed to compile program")?;
-
-        Ok(program)
+        Ok(assembler)
     }
 }
 
@@ -250,9 +288,10 @@ pub struct Libraries {
 }
 
 impl Libraries {
-    /// Creates a new instance of [Libraries] from a list of library paths.
+    /// Creates a new instance of [Libraries] from a list of paths to `.masl` library files or
+    /// `.masp` library packages, all of which must satisfy the specified trust policy.
     #[instrument(name = "read_library_files", skip_all)]
-    pub fn new<P, I>(paths: I) -> Result<Self, Report>
+    pub fn new<P, I>(paths: I, policy: &TrustPolicy) -> Result<Self, Report>
     where
         P: AsRef<Path>,
         I: IntoIterator<Item = P>,
@@ -260,9 +299,25 @@ impl Libraries {
         let mut libraries = Vec::new();
 
         for path in paths {
-            // TODO(plafer): How to create a `Report` from an error that doesn't derive
-            // `Diagnostic`?
-            let library = Library::deserialize_from_file(path).unwrap();
+            let path = path.as_ref();
+            let library = if path.extension().is_some_and(|ext| ext == "masp") {
+                let bytes = fs::read(path).into_diagnostic().wrap_err_with(|| {
+                    format!("Failed to read library package {}", path.display())
+                })?;
+                let library =
+                    policy.load_library(&bytes, &Ed25519Verifier).wrap_err_with(|| {
+                        format!("Failed to load library package {}", path.display())
+                    })?;
+                Arc::unwrap_or_clone(library)
+            } else {
+                let library = Library::deserialize_from_file(path)
+                    .into_diagnostic()
+                    .wrap_err_with(|| format!("Failed to read library {}", path.display()))?;
+                policy
+                    .check_library(&library)
+                    .wrap_err_with(|| format!("Failed to load library {}", path.display()))?;
+                library
+            };
             libraries.push(library);
         }
 
@@ -270,6 +325,64 @@ impl Libraries {
     }
 }
 
+// TRUST POLICY
+// ================================================================================================
+
+/// Options defining the [TrustPolicy] which the libraries loaded by a command must satisfy.
+#[derive(Debug, Clone, Default, clap::Args)]
+pub struct TrustOptions {
+    /// Requires the libraries providing modules in a namespace to be packages signed by the
+    /// specified ed25519 key, given as `<namespace>=<hex-encoded public key>`; can be repeated to
+    /// trust several keys or namespaces
+    #[clap(long = "trust", value_name = "NAMESPACE=PUBLIC_KEY", value_parser = parse_owner)]
+    owners: Vec<(LibraryNamespace, PublicKey)>,
+    /// Requires all libraries to be signed packages
+    #[clap(long = "require-signed", action)]
+    require_signed: bool,
+}
+
+impl TrustOptions {
+    /// Returns the trust policy defined by these options.
+    pub fn policy(&self) -> TrustPolicy {
+        let policy = self.owners.iter().fold(TrustPolicy::new(), |policy, (namespace, key)| {
+            policy.with_owner(namespace.clone(), *key)
+        });
+        if self.require_signed {
+            policy.with_required_signatures()
+        } else {
+            policy
+        }
+    }
+}
+
+/// Parses an owner of a namespace, given as `<namespace>=<hex-encoded public key>`.
+fn parse_owner(owner: &str) -> Result<(LibraryNamespace, PublicKey), String> {
+    let (namespace, key) = owner
+        .split_once('=')
+        .ok_or_else(|| format!("expected `<namespace>=<public key>`, got `{owner}`"))?;
+    let namespace = namespace.parse::<LibraryNamespace>().map_err(|err| err.to_string())?;
+    let key = hex::decode(key.strip_prefix("0x").unwrap_or(key))
+        .map_err(|err| format!("invalid public key: {err}"))?;
+    let key = PublicKey::try_from(key.as_slice())
+        .map_err(|_| format!("public key must be {PUBLIC_KEY_LEN} bytes long"))?;
+    Ok((namespace, key))
+}
+
+/// Verifies the ed25519 signatures of packages.
+pub struct Ed25519Verifier;
+
+impl SignatureVerifier for Ed25519Verifier {
+    fn verify(
+        &self,
+        public_key: &PublicKey,
+        message: &[u8],
+        signature: &[u8; SIGNATURE_LEN],
+    ) -> bool {
+        VerifyingKey::from_bytes(public_key)
+            .is_ok_and(|key| key.verify_strict(message, &Signature::from_bytes(signature)).is_ok())
+    }
+}
+
 // TESTS
 // ================================================================================================
 #[cfg(test)]
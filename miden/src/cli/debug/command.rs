@@ -1,5 +1,10 @@
+use miden_vm::{
+    Breakpoint,
+    math::{Felt, StarkField},
+};
+
 /// debug commands supported by the debugger
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DebugCommand {
     Continue,
     Next(usize),
@@ -11,6 +16,9 @@ pub enum DebugCommand {
     PrintMem,
     PrintMemAddress(u64),
     Clock,
+    AddBreakpoint(Breakpoint),
+    RemoveBreakpoint(usize),
+    ListBreakpoints,
     Quit,
     Help,
 }
@@ -39,6 +47,7 @@ impl DebugCommand {
             "r" | "rewind" => Self::Rewind,
             "p" | "print" => Self::parse_print(tokens.by_ref())?,
             "l" | "clock" => Self::Clock,
+            "bp" | "breakpoint" => Self::parse_breakpoint(tokens.by_ref())?,
             "h" | "?" | "help" => Self::Help,
             "q" | "quit" => Self::Quit,
             _ => {
@@ -94,6 +103,66 @@ impl DebugCommand {
         Ok(Self::Back(num_cycles))
     }
 
+    /// parse breakpoint command - `bp [clk <c>|stack <i> <v>|mem <a> [<b>]|delete <n>]`
+    fn parse_breakpoint<'a, I>(mut tokens: I) -> Result<Self, String>
+    where
+        I: Iterator<Item = &'a str>,
+    {
+        let command = match tokens.next() {
+            Some(c) => c,
+            None => return Ok(Self::ListBreakpoints),
+        };
+
+        let parse_arg = |name: &str, arg: Option<&str>| -> Result<u64, String> {
+            let arg =
+                arg.ok_or_else(|| format!("malformed `breakpoint` command - missing {name}"))?;
+            arg.parse::<u64>().map_err(|err| {
+                format!("malformed `breakpoint` command - failed to parse {name}: `{arg}` {err}")
+            })
+        };
+        let parse_addr = |name: &str, arg: Option<&str>| -> Result<u32, String> {
+            u32::try_from(parse_arg(name, arg)?)
+                .map_err(|_| format!("malformed `breakpoint` command - {name} out of range"))
+        };
+
+        match command {
+            "clk" | "clock" => {
+                let clk = u32::try_from(parse_arg("clock cycle", tokens.next())?)
+                    .map_err(|_| "malformed `breakpoint` command - clock cycle out of range")?;
+                Ok(Self::AddBreakpoint(Breakpoint::Cycle(clk.into())))
+            },
+            "s" | "stack" => {
+                let index = parse_arg("stack index", tokens.next())? as usize;
+                let value = parse_arg("stack value", tokens.next())?;
+                if value >= Felt::MODULUS {
+                    return Err(format!(
+                        "malformed `breakpoint` command - stack value `{value}` is not a field element"
+                    ));
+                }
+                Ok(Self::AddBreakpoint(Breakpoint::StackItem { index, value: Felt::new(value) }))
+            },
+            "m" | "mem" => {
+                let start = parse_addr("start address", tokens.next())?;
+                let end = match tokens.next() {
+                    Some(arg) => parse_addr("end address", Some(arg))?,
+                    None => start,
+                };
+                if end < start {
+                    return Err(format!(
+                        "malformed `breakpoint` command - end address `{end}` is smaller than start address `{start}`"
+                    ));
+                }
+                Ok(Self::AddBreakpoint(Breakpoint::MemoryWrite { start, end }))
+            },
+            "d" | "delete" => {
+                Ok(Self::RemoveBreakpoint(parse_arg("breakpoint number", tokens.next())? as usize))
+            },
+            _ => {
+                Err(format!("malformed `breakpoint` command - unexpected subcommand: `{command}`"))
+            },
+        }
+    }
+
     /// parse print command - `p [m|s] [addr]`
     fn parse_print<'a, I>(mut tokens: I) -> Result<Self, String>
     where
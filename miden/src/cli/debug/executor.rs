@@ -1,6 +1,8 @@
 use std::sync::Arc;
 
-use miden_vm::{DefaultHost, MemAdviceProvider, Program, StackInputs, VmState, VmStateIterator};
+use miden_vm::{
+    Breakpoint, DefaultHost, MemAdviceProvider, Program, StackInputs, VmState, VmStateIterator,
+};
 
 use super::DebugCommand;
 use crate::utils::print_mem_address;
@@ -11,7 +13,7 @@ pub struct DebugExecutor {
     vm_state: VmState,
     // TODO(pauls): Use this to render source-level diagnostics when program errors are encountered
     #[allow(unused)]
-    source_manager: Arc<dyn assembly::SourceManager>,
+    source_manager: Arc<dyn assembly::SourceManager + Send + Sync>,
 }
 
 impl DebugExecutor {
@@ -25,10 +27,11 @@ impl DebugExecutor {
         program: Program,
         stack_inputs: StackInputs,
         advice_provider: MemAdviceProvider,
-        source_manager: Arc<dyn assembly::SourceManager>,
+        source_manager: Arc<dyn assembly::SourceManager + Send + Sync>,
     ) -> Result<Self, String> {
         let mut vm_state_iter =
             processor::execute_iter(&program, stack_inputs, &mut DefaultHost::new(advice_provider));
+        vm_state_iter.add_breakpoint(Breakpoint::Instruction);
         let vm_state = vm_state_iter
             .next()
             .ok_or(
@@ -47,11 +50,16 @@ impl DebugExecutor {
     pub fn execute(&mut self, command: DebugCommand) -> bool {
         match command {
             DebugCommand::Continue => {
-                while let Some(new_vm_state) = self.next_vm_state() {
-                    self.vm_state = new_vm_state;
-                    if self.should_break() {
-                        break;
-                    }
+                match self.vm_state_iter.resume() {
+                    Some(Ok((new_vm_state, breakpoint))) => {
+                        self.vm_state = new_vm_state;
+                        match breakpoint {
+                            Some(index) => self.print_breakpoint(index),
+                            None => println!("Program execution complete."),
+                        }
+                    },
+                    Some(Err(err)) => println!("Execution error: {err:?}"),
+                    None => println!("Program execution complete."),
                 }
                 self.print_vm_state();
             },
@@ -59,8 +67,9 @@ impl DebugExecutor {
                 for _cycle in 0..cycles {
                     match self.next_vm_state() {
                         Some(next_vm_state) => {
-                            self.vm_state = next_vm_state;
-                            if self.should_break() {
+                            let prev_vm_state =
+                                core::mem::replace(&mut self.vm_state, next_vm_state);
+                            if self.break_from(&prev_vm_state) {
                                 break;
                             }
                         },
@@ -79,8 +88,9 @@ impl DebugExecutor {
                 for _cycle in 0..cycles {
                     match self.vm_state_iter.back() {
                         Some(new_vm_state) => {
-                            self.vm_state = new_vm_state;
-                            if self.should_break() {
+                            let prev_vm_state =
+                                core::mem::replace(&mut self.vm_state, new_vm_state);
+                            if self.break_from(&prev_vm_state) {
                                 break;
                             }
                         },
@@ -95,6 +105,17 @@ impl DebugExecutor {
             DebugCommand::PrintMem => self.print_memory(),
             DebugCommand::PrintMemAddress(address) => self.print_memory_entry(address),
             DebugCommand::Clock => println!("{}", self.vm_state.clk),
+            DebugCommand::AddBreakpoint(breakpoint) => {
+                let index = self.vm_state_iter.add_breakpoint(breakpoint);
+                println!("Breakpoint {index}: {breakpoint}");
+            },
+            DebugCommand::RemoveBreakpoint(index) => {
+                match self.vm_state_iter.remove_breakpoint(index) {
+                    Some(breakpoint) => println!("Deleted breakpoint {index}: {breakpoint}"),
+                    None => println!("breakpoint '{index}' not found"),
+                }
+            },
+            DebugCommand::ListBreakpoints => self.print_breakpoints(),
             DebugCommand::Help => Self::print_help(),
             DebugCommand::Quit => return false,
         }
@@ -151,6 +172,27 @@ impl DebugExecutor {
         }
     }
 
+    /// Prints all registered breakpoints.
+    pub fn print_breakpoints(&self) {
+        for (index, breakpoint) in self.vm_state_iter.breakpoints().iter().enumerate() {
+            println!("Breakpoint {index}: {breakpoint}");
+        }
+    }
+
+    /// Prints the breakpoint which paused execution, together with the memory writes which fired
+    /// it in the case of a memory breakpoint.
+    fn print_breakpoint(&self, index: usize) {
+        let breakpoint = self.vm_state_iter.breakpoints()[index];
+        println!("Hit breakpoint {index}: {breakpoint}");
+        if let Breakpoint::MemoryWrite { start, end } = breakpoint {
+            for (address, mem) in self.vm_state_iter.memory_writes(&self.vm_state) {
+                if (u64::from(start)..=u64::from(end)).contains(&address) {
+                    print_mem_address(address, mem)
+                }
+            }
+        }
+    }
+
     /// Prints all memory entries.
     pub fn print_memory(&self) {
         for &(address, mem) in self.vm_state.memory.iter() {
@@ -191,6 +233,15 @@ impl DebugExecutor {
             print stack        displays the complete state of the stack\n\
             print stack <i>    displays the stack element at index `i`\n\
             clock              displays the current clock cycle\n\
+            breakpoint         lists the breakpoints\n\
+            breakpoint clk <c> breaks at clock cycle `c`\n\
+            breakpoint stack <i> <v>\n\
+            \x20                  breaks when the stack element at index `i` becomes `v`\n\
+            breakpoint mem <a> <b>\n\
+            \x20                  breaks when memory in the range `a..=b` is written to;\n\
+            \x20                  `b` defaults to `a`\n\
+            breakpoint delete <n>\n\
+            \x20                  deletes the breakpoint numbered `n`\n\
             quit               quits the debugger\n\
             help               displays this message\n\
             \n\
@@ -203,6 +254,7 @@ impl DebugExecutor {
             m -> mem\n\
             s -> stack\n\
             l -> clock\n\
+            bp -> breakpoint\n\
             q -> quit\n\
             h -> help\n\
             ? -> help";
@@ -210,8 +262,15 @@ impl DebugExecutor {
         println!("{}", message);
     }
 
-    /// Returns `true` if the current state should break.
-    fn should_break(&self) -> bool {
-        self.vm_state.asmop.as_ref().map(|asm| asm.should_break()).unwrap_or(false)
+    /// Returns `true` if a breakpoint fired when stepping from `prev_vm_state` to the current
+    /// state, printing the breakpoint if so.
+    fn break_from(&self, prev_vm_state: &VmState) -> bool {
+        match self.vm_state_iter.fired_breakpoint(Some(prev_vm_state), &self.vm_state) {
+            Some(index) => {
+                self.print_breakpoint(index);
+                true
+            },
+            None => false,
+        }
     }
 }
@@ -5,7 +5,7 @@ use clap::Parser;
 use miden_vm::internal::InputFile;
 use rustyline::{Config, DefaultEditor, EditMode, error::ReadlineError};
 
-use super::data::Libraries;
+use super::data::{Libraries, TrustOptions};
 
 mod command;
 use command::DebugCommand;
@@ -30,9 +30,16 @@ pub struct DebugCmd {
     #[clap(long = "vi", long = "vim_edit_mode")]
     vim_edit_mode: Option<String>,
 
-    /// Paths to .masl library files
+    /// Paths to .masl library files or .masp library packages
     #[clap(short = 'l', long = "libraries", value_parser)]
     library_paths: Vec<PathBuf>,
+    #[clap(flatten)]
+    trust: TrustOptions,
+
+    /// Features enabled when compiling an assembly file, i.e. which make the procedures and
+    /// instructions annotated with `@cfg(feature = "<name>")` part of the program
+    #[clap(long = "features", value_delimiter = ',')]
+    features: Vec<String>,
 }
 
 impl DebugCmd {
@@ -42,7 +49,7 @@ impl DebugCmd {
         println!("============================================================");
 
         // load libraries from files
-        let libraries = Libraries::new(&self.library_paths)?;
+        let libraries = Libraries::new(&self.library_paths, &self.trust.policy())?;
 
         // Determine file type based on extension.
         let ext = self
@@ -55,7 +62,7 @@ impl DebugCmd {
         // Use a single match expression to load the program.
         let program = match ext.as_str() {
             "masp" => get_masp_program(&self.program_file)?,
-            "masm" => get_masm_program(&self.program_file, &libraries)?,
+            "masm" => get_masm_program(&self.program_file, &libraries, &self.features)?,
             _ => return Err(Report::msg("The provided file must have a .masm or .masp extension")),
         };
         let program_hash: [u8; 32] = program.hash().into();
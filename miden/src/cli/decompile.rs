@@ -0,0 +1,54 @@
+use std::{fs, path::PathBuf};
+
+use assembly::{
+    Decompiler,
+    diagnostics::{IntoDiagnostic, Report, WrapErr},
+};
+use clap::Parser;
+use stdlib::StdLibrary;
+
+use super::{
+    data::{Libraries, TrustOptions},
+    utils::get_compiled_program,
+};
+
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Reconstruct the assembly source of a compiled miden program")]
+pub struct DecompileCmd {
+    /// Path to a compiled .masb program file, or to a .masp package file
+    #[clap(short = 'p', long = "program", value_parser)]
+    program_file: PathBuf,
+    /// Paths to .masl library files or .masp library packages whose procedures are invoked by name
+    #[clap(short = 'l', long = "libraries", value_parser)]
+    library_paths: Vec<PathBuf>,
+    #[clap(flatten)]
+    trust: TrustOptions,
+    /// Path to output file; the source is printed to stdout if not provided
+    #[clap(short = 'o', long = "output", value_parser)]
+    output_file: Option<PathBuf>,
+}
+
+impl DecompileCmd {
+    pub fn execute(&self) -> Result<(), Report> {
+        let program = get_compiled_program(&self.program_file)?;
+
+        // procedures of the standard library and of the provided libraries are named
+        let libraries = Libraries::new(&self.library_paths, &self.trust.policy())?;
+        let decompiler = libraries
+            .libraries
+            .iter()
+            .fold(Decompiler::new(), |decompiler, library| decompiler.with_library(library))
+            .with_library(StdLibrary::default());
+        let source = decompiler.decompile_program(&program);
+
+        match &self.output_file {
+            Some(path) => fs::write(path, source)
+                .into_diagnostic()
+                .wrap_err("Failed to write the decompiled source"),
+            None => {
+                print!("{source}");
+                Ok(())
+            },
+        }
+    }
+}
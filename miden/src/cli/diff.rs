@@ -0,0 +1,67 @@
+use std::path::{Path, PathBuf};
+
+use assembly::{
+    Decompiler, Library, MastDiff,
+    diagnostics::{IntoDiagnostic, Report, WrapErr},
+};
+use clap::Parser;
+use stdlib::StdLibrary;
+
+use super::{
+    data::{Libraries, TrustOptions},
+    utils::get_compiled_program,
+};
+
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Report the procedures which differ between two compiled programs or libraries")]
+pub struct DiffCmd {
+    /// Path to the old .masb program, .masp package or .masl library file
+    old_file: PathBuf,
+    /// Path to the new .masb program, .masp package or .masl library file
+    new_file: PathBuf,
+    /// Paths to .masl library files or .masp library packages whose procedures are invoked by name in the body diffs
+    #[clap(short = 'l', long = "libraries", value_parser)]
+    library_paths: Vec<PathBuf>,
+    #[clap(flatten)]
+    trust: TrustOptions,
+}
+
+impl DiffCmd {
+    pub fn execute(&self) -> Result<(), Report> {
+        // procedures of the standard library and of the provided libraries are named
+        let libraries = Libraries::new(&self.library_paths, &self.trust.policy())?;
+        let decompiler = libraries
+            .libraries
+            .iter()
+            .fold(Decompiler::new(), |decompiler, library| decompiler.with_library(library))
+            .with_library(StdLibrary::default());
+
+        let diff = match (is_library(&self.old_file), is_library(&self.new_file)) {
+            (true, true) => {
+                let old = read_library(&self.old_file)?;
+                let new = read_library(&self.new_file)?;
+                MastDiff::libraries(old, new, &decompiler)
+            },
+            (false, false) => {
+                let old = get_compiled_program(&self.old_file)?;
+                let new = get_compiled_program(&self.new_file)?;
+                MastDiff::programs(&old, &new, &decompiler)
+            },
+            _ => return Err(Report::msg("Cannot compare a program with a library")),
+        };
+
+        print!("{diff}");
+        Ok(())
+    }
+}
+
+/// Returns true if the file at the specified path is a library, i.e., has the `.masl` extension.
+fn is_library(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == Library::LIBRARY_EXTENSION)
+}
+
+fn read_library(path: &Path) -> Result<Library, Report> {
+    Library::deserialize_from_file(path)
+        .into_diagnostic()
+        .wrap_err_with(|| format!("Failed to read library file `{}`", path.display()))
+}
@@ -1,7 +1,10 @@
 mod bundle;
+mod check;
 mod compile;
 pub mod data;
 mod debug;
+mod decompile;
+mod diff;
 mod prove;
 mod repl;
 mod run;
@@ -9,8 +12,11 @@ pub mod utils;
 mod verify;
 
 pub use bundle::BundleCmd;
+pub use check::CheckCmd;
 pub use compile::CompileCmd;
 pub use debug::DebugCmd;
+pub use decompile::DecompileCmd;
+pub use diff::DiffCmd;
 pub use prove::ProveCmd;
 pub use repl::ReplCmd;
 pub use run::RunCmd;
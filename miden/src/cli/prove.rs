@@ -1,14 +1,16 @@
 use std::{path::PathBuf, time::Instant};
 
 use assembly::diagnostics::{IntoDiagnostic, Report, WrapErr};
-use clap::Parser;
-use miden_vm::{ProvingOptions, internal::InputFile};
-use processor::{DefaultHost, ExecutionOptions, ExecutionOptionsError, Program};
+use clap::{CommandFactory, Parser, error::ErrorKind};
+use miden_vm::{
+    ConstraintReport, HashFunction, ProvingOptions, SecurityPreset, internal::InputFile,
+};
+use processor::{DefaultHost, ExecutionOptions, Program};
 use stdlib::StdLibrary;
 use tracing::instrument;
 
 use super::{
-    data::{Libraries, OutputFile, ProofFile},
+    data::{Libraries, OutputFile, ProofFile, TrustOptions},
     utils::{get_masm_program, get_masp_program},
 };
 
@@ -19,22 +21,44 @@ pub struct ProveCmd {
     #[clap(value_parser)]
     program_file: PathBuf,
 
+    /// Print a report of the AIR constraints checked by the proof before proving the program
+    #[clap(long = "constraints")]
+    constraints: bool,
+
     /// Number of cycles the program is expected to consume
     #[clap(short = 'e', long = "exp-cycles", default_value = "64")]
     expected_cycles: u32,
 
+    /// Hash function used to generate the proof: blake3-192, blake3-256, rpo256, rpx256, or
+    /// keccak256. Defaults to the hash function of the security level, or to rpo256 for
+    /// recursive proofs
+    #[clap(long = "hash-fn", conflicts_with("rpx"))]
+    hash_fn: Option<HashFunction>,
+
     /// Path to input file
     #[clap(short = 'i', long = "input", value_parser)]
     input_file: Option<PathBuf>,
 
-    /// Paths to .masl library files
+    /// Paths to .masl library files or .masp library packages
     #[clap(short = 'l', long = "libraries", value_parser)]
     library_paths: Vec<PathBuf>,
+    #[clap(flatten)]
+    trust: TrustOptions,
+
+    /// Features enabled when compiling an assembly file, i.e. which make the procedures and
+    /// instructions annotated with `@cfg(feature = "<name>")` part of the program
+    #[clap(long = "features", value_delimiter = ',')]
+    features: Vec<String>,
 
     /// Maximum number of cycles a program is allowed to consume
     #[clap(short = 'm', long = "max-cycles", default_value = "4294967295")]
     max_cycles: u32,
 
+    /// Maximum amount of memory, in MiB, the extended execution trace may occupy before it is
+    /// spilled to disk
+    #[clap(long = "memory-limit")]
+    memory_limit: Option<usize>,
+
     /// Number of outputs
     #[clap(short = 'n', long = "num-outputs", default_value = "16")]
     num_outputs: usize,
@@ -55,9 +79,14 @@ pub struct ProveCmd {
     #[clap(long = "rpx", conflicts_with("recursive"))]
     rpx: bool,
 
-    /// Security level for execution proofs generated by the VM
+    /// Security level for execution proofs generated by the VM: 96bits, 100bits, or 128bits, with
+    /// an optional -proven suffix to target proven rather than conjectured security
     #[clap(short = 's', long = "security", default_value = "96bits")]
-    security: String,
+    security: SecurityPreset,
+
+    /// Verify the proof right after it is generated, and fail if it is rejected
+    #[clap(long = "self-check")]
+    self_check: bool,
 
     /// Enable tracing to monitor execution of the VM
     #[clap(short = 't', long = "trace")]
@@ -65,27 +94,58 @@ pub struct ProveCmd {
 }
 
 impl ProveCmd {
-    pub fn get_proof_options(&self) -> Result<ProvingOptions, ExecutionOptionsError> {
+    /// Returns an error if the arguments conflict in a way which cannot be expressed via the
+    /// attributes of the arguments, i.e., if `--rpx` is combined with a security preset which has
+    /// no RPX variant.
+    pub fn validate(&self) -> Result<(), clap::Error> {
+        if self.rpx {
+            self.rpx_options()?;
+        }
+        Ok(())
+    }
+
+    pub fn get_proof_options(&self) -> Result<ProvingOptions, Report> {
         let exec_options =
-            ExecutionOptions::new(Some(self.max_cycles), self.expected_cycles, self.trace, false)?;
-        Ok(match self.security.as_str() {
-            "96bits" => {
-                if self.rpx {
-                    ProvingOptions::with_96_bit_security_rpx()
-                } else {
-                    ProvingOptions::with_96_bit_security(self.recursive)
-                }
-            },
-            "128bits" => {
-                if self.rpx {
-                    ProvingOptions::with_128_bit_security_rpx()
-                } else {
-                    ProvingOptions::with_128_bit_security(self.recursive)
-                }
-            },
-            other => panic!("{} is not a valid security setting", other),
+            ExecutionOptions::new(Some(self.max_cycles), self.expected_cycles, self.trace, false)
+                .map_err(|err| Report::msg(format!("{err}")))?;
+        let options = if self.rpx {
+            self.rpx_options().map_err(|err| Report::msg(err.to_string()))?
+        } else {
+            let options = ProvingOptions::with_security_preset(self.security, self.recursive);
+            match self.hash_fn {
+                Some(hash_fn) => options.with_hash_fn(hash_fn),
+                None => options,
+            }
+        }
+        .with_execution_options(exec_options);
+
+        let options = match self.memory_limit {
+            // a limit exceeding the address space is no limit at all
+            Some(memory_limit) => options.with_memory_limit(memory_limit.saturating_mul(1 << 20)),
+            None => options,
+        };
+
+        Ok(if self.self_check {
+            options.with_self_check()
+        } else {
+            options
+        })
+    }
+
+    /// Returns the proving options using RPX at the specified security level, or a conflict error
+    /// if the security preset has no RPX variant.
+    fn rpx_options(&self) -> Result<ProvingOptions, clap::Error> {
+        match self.security {
+            SecurityPreset::Conjectured96 => Ok(ProvingOptions::with_96_bit_security_rpx()),
+            SecurityPreset::Conjectured128 => Ok(ProvingOptions::with_128_bit_security_rpx()),
+            other => Err(Self::command().error(
+                ErrorKind::ArgumentConflict,
+                format!(
+                    "the argument '--rpx' cannot be used with '--security {other}', as RPX only \
+                    supports the 96bits and 128bits presets"
+                ),
+            )),
         }
-        .with_execution_options(exec_options))
     }
     pub fn execute(&self) -> Result<(), Report> {
         println!("===============================================================================");
@@ -106,6 +166,13 @@ impl ProveCmd {
             _ => return Err(Report::msg("File must have a .masm or .masp extension")),
         };
 
+        if self.constraints {
+            println!("{}", ConstraintReport::new());
+            println!(
+                "-------------------------------------------------------------------------------"
+            );
+        }
+
         let program_hash: [u8; 32] = program.hash().into();
         println!("Proving program with hash {}...", hex::encode(program_hash));
         let now = Instant::now();
@@ -115,16 +182,19 @@ impl ProveCmd {
         let mut host = DefaultHost::new(input_data.parse_advice_provider().map_err(Report::msg)?);
         host.load_mast_forest(StdLibrary::default().mast_forest().clone()).unwrap();
 
-        let proving_options =
-            self.get_proof_options().map_err(|err| Report::msg(format!("{err}")))?;
+        let proving_options = self.get_proof_options()?;
 
         // execute program and generate proof
         let (stack_outputs, proof) =
-            prover::prove(&program, stack_inputs, &mut host, proving_options)
+            prover::prove(&program, stack_inputs, &mut host, proving_options.clone())
                 .into_diagnostic()
                 .wrap_err("Failed to prove program")?;
 
         println!("Program proved in {} ms", now.elapsed().as_millis());
+        println!(
+            "Estimated security: {}",
+            proving_options.estimate_security(proof.stark_proof().trace_info().length())
+        );
 
         // write proof to file
         ProofFile::write(proof, &self.proof_file, &self.program_file).map_err(Report::msg)?;
@@ -161,8 +231,8 @@ fn load_masp_data(params: &ProveCmd) -> Result<(Program, InputFile), Report> {
 
 #[instrument(skip_all)]
 fn load_masm_data(params: &ProveCmd) -> Result<(Program, InputFile), Report> {
-    let libraries = Libraries::new(&params.library_paths)?;
-    let program = get_masm_program(&params.program_file, &libraries)?;
+    let libraries = Libraries::new(&params.library_paths, &params.trust.policy())?;
+    let program = get_masm_program(&params.program_file, &libraries, &params.features)?;
     let input_data = InputFile::read(&params.input_file, &params.program_file)?;
     Ok((program, input_data))
 }
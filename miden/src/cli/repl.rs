@@ -3,14 +3,17 @@ use std::path::PathBuf;
 use assembly::diagnostics::Report;
 use clap::Parser;
 
+use super::data::{Libraries, TrustOptions};
 use crate::repl::start_repl;
 
 #[derive(Debug, Clone, Parser)]
 #[clap(about = "Initiates the Miden REPL tool")]
 pub struct ReplCmd {
-    /// Paths to .masl library files
+    /// Paths to .masl library files or .masp library packages
     #[clap(short = 'l', long = "libraries", value_parser)]
     library_paths: Vec<PathBuf>,
+    #[clap(flatten)]
+    trust: TrustOptions,
 
     /// Usage of standard library
     #[clap(short = 's', long = "stdlib")]
@@ -19,8 +22,10 @@ pub struct ReplCmd {
 
 impl ReplCmd {
     pub fn execute(&self) -> Result<(), Report> {
+        let libraries = Libraries::new(&self.library_paths, &self.trust.policy())?;
+
         // initiates repl tool.
-        start_repl(&self.library_paths, self.use_stdlib);
+        start_repl(libraries.libraries, self.use_stdlib);
         Ok(())
     }
 }
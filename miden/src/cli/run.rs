@@ -3,12 +3,17 @@ use std::{path::PathBuf, time::Instant};
 use assembly::diagnostics::{IntoDiagnostic, Report, WrapErr};
 use clap::Parser;
 use miden_vm::internal::InputFile;
-use processor::{DefaultHost, ExecutionOptions, ExecutionTrace};
+use processor::{
+    DefaultHost, EventTimeline, ExecutionOptions, ExecutionRecord, ExecutionTrace, Host,
+    MemoryState, Process, Program, StackInputs, StackOutputs,
+    utils::{Deserializable, Serializable},
+};
+use serde_derive::Serialize;
 use stdlib::StdLibrary;
 use tracing::instrument;
 
 use super::{
-    data::{Libraries, OutputFile},
+    data::{Libraries, OutputFile, TrustOptions},
     utils::{get_masm_program, get_masp_program},
 };
 
@@ -27,9 +32,11 @@ pub struct RunCmd {
     #[clap(short = 'i', long = "input", value_parser)]
     input_file: Option<PathBuf>,
 
-    /// Paths to .masl library files (only used for assembly files)
+    /// Paths to .masl library files or .masp library packages (only used for assembly files)
     #[clap(short = 'l', long = "libraries", value_parser)]
     library_paths: Vec<PathBuf>,
+    #[clap(flatten)]
+    trust: TrustOptions,
 
     /// Maximum number of cycles a program is allowed to consume
     #[clap(short = 'm', long = "max-cycles", default_value = "4294967295")]
@@ -50,6 +57,39 @@ pub struct RunCmd {
     /// Enable debug instructions
     #[clap(short = 'd', long = "debug")]
     debug: bool,
+
+    /// Features enabled when compiling an assembly file, i.e. which make the procedures and
+    /// instructions annotated with `@cfg(feature = "<name>")` part of the program
+    #[clap(long = "features", value_delimiter = ',')]
+    features: Vec<String>,
+
+    /// Path to which the execution profile is written, in the folded stack format of flamegraphs
+    #[clap(short = 'p', long = "profile", value_parser)]
+    profile_file: Option<PathBuf>,
+
+    /// Path to a binary file loaded into memory of the root context starting at address 0, with
+    /// 4 bytes per element
+    #[clap(long = "memory-file", value_parser)]
+    memory_file: Option<PathBuf>,
+
+    /// Path to which the events emitted by the program are written as a JSON report, including
+    /// the cycle, context and procedures of every event, and a commitment to the report
+    #[clap(long = "events", value_parser, conflicts_with_all = ["record_file", "compare_file"])]
+    events_file: Option<PathBuf>,
+
+    /// Record the top 16 elements of the stack with every event written via `--events`
+    #[clap(long = "events-stack", requires = "events_file")]
+    events_stack: bool,
+
+    /// Path to which a cycle-by-cycle record of the execution is written, for later comparison
+    /// against an execution by another version of the VM
+    #[clap(long = "record", value_parser, conflicts_with_all = ["memory_file", "compare_file"])]
+    record_file: Option<PathBuf>,
+
+    /// Path to a record written with `--record`, against which the execution is compared; fails
+    /// at the first cycle where the state of the VM differs from the record
+    #[clap(long = "compare", value_parser, conflicts_with = "memory_file")]
+    compare_file: Option<PathBuf>,
 }
 
 impl RunCmd {
@@ -69,7 +109,7 @@ impl RunCmd {
         let now = Instant::now();
 
         // use a single match expression based on file extension
-        let (trace, program_hash) = match ext.as_str() {
+        let (output, program_hash) = match ext.as_str() {
             "masp" => run_masp_program(self)?,
             "masm" => run_masm_program(self)?,
             _ => return Err(Report::msg("The provided file must have a .masm or .masp extension")),
@@ -81,39 +121,61 @@ impl RunCmd {
             now.elapsed().as_millis()
         );
 
-        if let Some(output_path) = &self.output_file {
-            // write outputs to file if one was specified
-            OutputFile::write(trace.stack_outputs(), output_path).map_err(Report::msg)?;
-        } else {
-            // write the stack outputs to the terminal
-            println!("Output: {:?}", trace.stack_outputs().stack_truncated(self.num_outputs));
+        let trace = match output {
+            RunOutput::Trace(trace) => trace,
+            RunOutput::StackOutputs(stack_outputs) => {
+                // the execution trace was not generated, and thus only the outputs are available
+                return self.write_outputs(&stack_outputs);
+            },
+        };
+
+        if let Some(profile_path) = &self.profile_file {
+            let profile = trace.profile().expect("profiling was enabled");
+            std::fs::write(profile_path, profile.to_folded())
+                .into_diagnostic()
+                .wrap_err("Failed to write execution profile")?;
+            println!("Wrote the execution profile to {}", profile_path.display());
         }
 
+        if let Some(events_path) = &self.events_file {
+            let timeline = trace.event_timeline().expect("the event timeline was enabled");
+            let file = std::fs::File::create(events_path)
+                .into_diagnostic()
+                .wrap_err("Failed to create event timeline file")?;
+            serde_json::to_writer_pretty(file, &EventReport::new(timeline))
+                .into_diagnostic()
+                .wrap_err("Failed to write event timeline")?;
+            println!("Wrote {} events to {}", timeline.events().len(), events_path.display());
+        }
+
+        self.write_outputs(trace.stack_outputs())?;
+
         // calculate the percentage of padded rows
-        let padding_percentage = (trace.trace_len_summary().padded_trace_len()
-            - trace.trace_len_summary().trace_len())
-            * 100
-            / trace.trace_len_summary().padded_trace_len();
+        let stats = trace.trace_stats();
+        let padding_percentage =
+            (stats.padded_trace_len() - stats.trace_len()) * 100 / stats.padded_trace_len();
         // print the required cycles for each component
         println!(
             "VM cycles: {} extended to {} steps ({}% padding).
 ├── Stack rows: {}
+│   └── Overflow table peak size: {}
 ├── Range checker rows: {}
 └── Chiplets rows: {}
     ├── Hash chiplet rows: {}
     ├── Bitwise chiplet rows: {}
     ├── Memory chiplet rows: {}
     └── Kernel ROM rows: {}",
-            trace.trace_len_summary().trace_len(),
-            trace.trace_len_summary().padded_trace_len(),
+            stats.trace_len(),
+            stats.padded_trace_len(),
             padding_percentage,
-            trace.trace_len_summary().main_trace_len(),
-            trace.trace_len_summary().range_trace_len(),
-            trace.trace_len_summary().chiplets_trace_len().trace_len(),
-            trace.trace_len_summary().chiplets_trace_len().hash_chiplet_len(),
-            trace.trace_len_summary().chiplets_trace_len().bitwise_chiplet_len(),
-            trace.trace_len_summary().chiplets_trace_len().memory_chiplet_len(),
-            trace.trace_len_summary().chiplets_trace_len().kernel_rom_len(),
+            stats.main_trace_rows(),
+            stats.overflow_table_peak_len(),
+            stats.range_checker_rows(),
+            stats.chiplets_rows(),
+            stats.hasher_rows(),
+            stats.bitwise_rows(),
+            stats.memory_rows(),
+            stats.kernel_rom_rows(),
         );
 
         Ok(())
@@ -123,8 +185,173 @@ impl RunCmd {
 // HELPER FUNCTIONS
 // ================================================================================================
 
+/// The result of running a program, which includes the execution trace unless the initial memory
+/// was provided.
+enum RunOutput {
+    Trace(Box<ExecutionTrace>),
+    StackOutputs(StackOutputs),
+}
+
+impl RunCmd {
+    fn write_outputs(&self, stack_outputs: &StackOutputs) -> Result<(), Report> {
+        if let Some(output_path) = &self.output_file {
+            // write outputs to file if one was specified
+            OutputFile::write(stack_outputs, output_path).map_err(Report::msg)
+        } else {
+            // write the stack outputs to the terminal
+            println!("Output: {:?}", stack_outputs.stack_truncated(self.num_outputs));
+            Ok(())
+        }
+    }
+
+    fn execution_options(&self) -> Result<ExecutionOptions, Report> {
+        let options = ExecutionOptions::new(
+            Some(self.max_cycles),
+            self.expected_cycles,
+            self.trace,
+            self.debug,
+        )
+        .into_diagnostic()?;
+        let options = if self.profile_file.is_some() {
+            options.with_profiling()
+        } else {
+            options
+        };
+        if self.events_stack {
+            Ok(options.with_event_stack_snapshots())
+        } else if self.events_file.is_some() {
+            Ok(options.with_event_timeline())
+        } else {
+            Ok(options)
+        }
+    }
+}
+
+/// The JSON representation of an [EventTimeline], with digests encoded as hex strings.
+#[derive(Serialize)]
+struct EventReport {
+    program_hash: String,
+    commitment: String,
+    events: Vec<EventReportEntry>,
+}
+
+#[derive(Serialize)]
+struct EventReportEntry {
+    event_id: u32,
+    is_system_event: bool,
+    clk: u32,
+    ctx: u32,
+    procedures: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stack: Option<Vec<u64>>,
+}
+
+impl EventReport {
+    fn new(timeline: &EventTimeline) -> Self {
+        let events = timeline
+            .events()
+            .iter()
+            .map(|event| EventReportEntry {
+                event_id: event.event_id(),
+                is_system_event: event.is_system_event(),
+                clk: event.clk(),
+                ctx: event.ctx().into(),
+                procedures: event.procedures().iter().map(ToString::to_string).collect(),
+                stack: event
+                    .stack()
+                    .map(|stack| stack.iter().map(|element| element.as_int()).collect()),
+            })
+            .collect();
+
+        Self {
+            program_hash: timeline.program_hash().to_string(),
+            commitment: timeline.commitment().to_string(),
+            events,
+        }
+    }
+}
+
+/// Executes the program, starting from the initial memory provided by `params`, if any.
+fn execute_program(
+    params: &RunCmd,
+    program: &Program,
+    stack_inputs: StackInputs,
+    host: &mut impl Host,
+) -> Result<RunOutput, Report> {
+    let execution_options = params.execution_options()?;
+
+    if params.record_file.is_some() || params.compare_file.is_some() {
+        return record_program(params, program, stack_inputs, host, execution_options);
+    }
+
+    let Some(memory_path) = &params.memory_file else {
+        let trace = processor::execute(program, stack_inputs, host, execution_options)
+            .into_diagnostic()
+            .wrap_err("Failed to generate execution trace")?;
+        return Ok(RunOutput::Trace(Box::new(trace)));
+    };
+
+    let bytes = std::fs::read(memory_path)
+        .into_diagnostic()
+        .wrap_err_with(|| format!("Failed to read memory file `{}`", memory_path.display()))?;
+    let memory = MemoryState::from_bytes(0, &bytes).into_diagnostic()?;
+
+    let mut process = Process::new(program.kernel().clone(), stack_inputs, execution_options)
+        .with_initial_memory(memory)
+        .into_diagnostic()?;
+    let stack_outputs = process
+        .execute(program, host)
+        .into_diagnostic()
+        .wrap_err("Failed to generate execution trace")?;
+    let trace = ExecutionTrace::new(process, stack_outputs);
+    Ok(RunOutput::Trace(Box::new(trace)))
+}
+
+/// Executes the program while recording the state of the VM at every cycle, and then writes the
+/// record to, or compares it against, the file specified by `params`.
+fn record_program(
+    params: &RunCmd,
+    program: &Program,
+    stack_inputs: StackInputs,
+    host: &mut impl Host,
+    execution_options: ExecutionOptions,
+) -> Result<RunOutput, Report> {
+    let record = ExecutionRecord::record(program, stack_inputs, host, execution_options)
+        .into_diagnostic()
+        .wrap_err("Failed to record execution")?;
+
+    if let Some(record_path) = &params.record_file {
+        std::fs::write(record_path, record.to_bytes())
+            .into_diagnostic()
+            .wrap_err_with(|| {
+                format!("Failed to write execution record `{}`", record_path.display())
+            })?;
+    }
+
+    if let Some(compare_path) = &params.compare_file {
+        let bytes = std::fs::read(compare_path).into_diagnostic().wrap_err_with(|| {
+            format!("Failed to read execution record `{}`", compare_path.display())
+        })?;
+        let expected = ExecutionRecord::read_from_bytes(&bytes)
+            .map_err(|err| Report::msg(format!("Failed to deserialize execution record: {err}")))?;
+        if let Some(divergence) = expected.first_divergence(&record) {
+            return Err(Report::msg(format!(
+                "Execution diverges from `{}` at {divergence}",
+                compare_path.display()
+            )));
+        }
+        println!("Execution matches the record in {}", compare_path.display());
+    }
+
+    let stack_outputs = record
+        .outcome()
+        .cloned()
+        .map_err(|err| Report::msg(format!("Failed to execute program: {err}")))?;
+    Ok(RunOutput::StackOutputs(stack_outputs))
+}
+
 #[instrument(name = "run_program", skip_all)]
-fn run_masp_program(params: &RunCmd) -> Result<(ExecutionTrace, [u8; 32]), Report> {
+fn run_masp_program(params: &RunCmd) -> Result<(RunOutput, [u8; 32]), Report> {
     let program = get_masp_program(&params.program_file)?;
 
     // use simplified input data reading
@@ -133,26 +360,16 @@ fn run_masp_program(params: &RunCmd) -> Result<(ExecutionTrace, [u8; 32]), Repor
     let stack_inputs = input_data.parse_stack_inputs().map_err(Report::msg)?;
     let mut host = DefaultHost::new(input_data.parse_advice_provider().map_err(Report::msg)?);
 
-    let execution_options = ExecutionOptions::new(
-        Some(params.max_cycles),
-        params.expected_cycles,
-        params.trace,
-        params.debug,
-    )
-    .into_diagnostic()?;
-
     let program_hash: [u8; 32] = program.hash().into();
 
     // execute program and generate outputs
-    let trace = processor::execute(&program, stack_inputs, &mut host, execution_options)
-        .into_diagnostic()
-        .wrap_err("Failed to generate execution trace")?;
+    let output = execute_program(params, &program, stack_inputs, &mut host)?;
 
-    Ok((trace, program_hash))
+    Ok((output, program_hash))
 }
 
 #[instrument(name = "run_program", skip_all)]
-fn run_masm_program(params: &RunCmd) -> Result<(ExecutionTrace, [u8; 32]), Report> {
+fn run_masm_program(params: &RunCmd) -> Result<(RunOutput, [u8; 32]), Report> {
     for lib in &params.library_paths {
         if !lib.is_file() {
             let name = lib.display();
@@ -161,20 +378,12 @@ fn run_masm_program(params: &RunCmd) -> Result<(ExecutionTrace, [u8; 32]), Repor
     }
 
     // load libraries from files
-    let libraries = Libraries::new(&params.library_paths)?;
+    let libraries = Libraries::new(&params.library_paths, &params.trust.policy())?;
 
     // load program from file and compile
-    let program = get_masm_program(&params.program_file, &libraries)?;
+    let program = get_masm_program(&params.program_file, &libraries, &params.features)?;
     let input_data = InputFile::read(&params.input_file, &params.program_file)?;
 
-    let execution_options = ExecutionOptions::new(
-        Some(params.max_cycles),
-        params.expected_cycles,
-        params.trace,
-        params.debug,
-    )
-    .into_diagnostic()?;
-
     // fetch the stack and program inputs from the arguments
     let stack_inputs = input_data.parse_stack_inputs().map_err(Report::msg)?;
     let mut host = DefaultHost::new(input_data.parse_advice_provider().map_err(Report::msg)?);
@@ -185,9 +394,7 @@ fn run_masm_program(params: &RunCmd) -> Result<(ExecutionTrace, [u8; 32]), Repor
 
     let program_hash: [u8; 32] = program.hash().into();
 
-    let trace = processor::execute(&program, stack_inputs, &mut host, execution_options)
-        .into_diagnostic()
-        .wrap_err("Failed to generate execution trace")?;
+    let output = execute_program(params, &program, stack_inputs, &mut host)?;
 
-    Ok((trace, program_hash))
+    Ok((output, program_hash))
 }
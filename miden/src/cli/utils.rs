@@ -22,8 +22,26 @@ pub fn get_masp_program(path: &Path) -> Result<vm_core::Program, Report> {
     Ok(program)
 }
 
-/// Returns a `Program` type from a `.masm` assembly file.
-pub fn get_masm_program(path: &Path, libraries: &Libraries) -> Result<vm_core::Program, Report> {
-    let program = ProgramFile::read(path)?.compile(Debug::On, &libraries.libraries)?;
+/// Returns a `Program` type from a `.masp` package file, or from a compiled `.masb` program file
+/// for any other extension.
+pub fn get_compiled_program(path: &Path) -> Result<vm_core::Program, Report> {
+    if path.extension().is_some_and(|ext| ext == "masp") {
+        return get_masp_program(path);
+    }
+    let bytes = fs::read(path).into_diagnostic().wrap_err("Failed to read program file")?;
+    vm_core::Program::read_from_bytes(&bytes)
+        .into_diagnostic()
+        .wrap_err("Failed to deserialize program")
+}
+
+/// Returns a `Program` type from a `.masm` assembly file, compiled with the specified features
+/// enabled.
+pub fn get_masm_program(
+    path: &Path,
+    libraries: &Libraries,
+    features: &[String],
+) -> Result<vm_core::Program, Report> {
+    let program = ProgramFile::read_with_features(path, features)?
+        .compile(Debug::On, &libraries.libraries)?;
     Ok(program)
 }
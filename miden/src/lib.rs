@@ -9,16 +9,47 @@ pub use assembly::{
     ast::{Module, ModuleKind},
     diagnostics,
 };
+#[cfg(feature = "std")]
+pub use processor::FileMerkleNodeStorage;
 pub use processor::{
-    AdviceInputs, AdviceProvider, AsmOpInfo, DefaultHost, ExecutionError, ExecutionTrace, Host,
-    Kernel, MemAdviceProvider, Operation, Program, ProgramInfo, StackInputs, VmState,
-    VmStateIterator, ZERO, crypto, execute, execute_iter, utils,
+    AdviceInputs, AdviceInputsBuilder, AdviceInputsError, AdviceProvider, AdviceTape,
+    AdviceTapeEntry, AdviceTapeRecorder, AdviceTapeReplayer, AsmOpInfo, BranchCoverage, Breakpoint,
+    CallFrame, CallStack, CancellationToken, CoverageReport, DefaultHost, Divergence,
+    DivergenceKind, EVENT_RANDOM_WORD, EVENT_STORAGE_SET, EmittedEvent, EventHandler,
+    EventTimeline, ExecutionEnvironment, ExecutionError, ExecutionOptions, ExecutionRecord,
+    ExecutionTrace, ForeignCallError, ForeignValue, ForestCoverage, Host, InstructionCoverage,
+    IsaVersion, Kernel, KernelError, MemAdviceProvider, MemoryState, MemoryWordDiff,
+    MerkleMultiProof, MerkleNodeStorage, OpDispatch, Operation, PersistentAdviceProvider,
+    ProfileNode, ProfileReport, Program, ProgramInfo, RandomSource, RecordedStep,
+    SandboxAdviceProvider, SandboxPolicy, StackInputs, StorageHost, TraceComponent, TraceStats,
+    VmState, VmStateIterator, ZERO, crypto, execute, execute_iter, execute_without_trace,
+    foreign_function_id, storage_map_key, storage_slot_key, utils,
 };
+#[cfg(feature = "rpc-advice")]
+pub use processor::{AdviceRpcClient, RpcAdviceProvider, RpcNodeError};
+#[cfg(feature = "std")]
 pub use prover::{
-    Digest, ExecutionProof, FieldExtension, HashFunction, InputError, Proof, ProvingOptions,
-    StackOutputs, Word, math, prove,
+    CoinOperation, GrindingChallenge, MAX_GRINDER_NONCE, NonceGrinder, ProgressListener,
+    ProvingJob, ProvingPhase, prove_batch, prove_with_grinder, prove_with_progress,
+    prove_with_randomizer,
 };
-pub use verifier::{VerificationError, verify};
+pub use prover::{
+    ComponentConstraints, ConstraintReport, DelegatedClaim, Digest, ExecutionProof, FieldExtension,
+    HashFunction, InputError, Proof, ProofEnvelope, ProvingOptions, SecurityEstimate,
+    SecurityPreset, StackOutputs, TraceRandomizer, Word, claims_commitment, math, prove,
+    prove_trace,
+};
+#[cfg(feature = "prove-async")]
+pub use prover::{ProvingResult, ProvingTask, prove_async};
+pub use verifier::{
+    VerificationError, VerificationFailure, VerificationRequest, Verifier, recursive, verify,
+    verify_batch, verify_envelope, verify_with_environment, verify_with_randomizer,
+};
+
+#[cfg(feature = "std")]
+mod pipeline;
+#[cfg(feature = "std")]
+pub use pipeline::{MidenPipeline, PipelineError, PipelineOutput, PipelineTimings};
 
 // (private) exports
 // ================================================================================================
@@ -26,7 +26,10 @@ pub enum Actions {
     Analyze(tools::Analyze),
     Compile(cli::CompileCmd),
     Bundle(cli::BundleCmd),
+    Check(cli::CheckCmd),
     Debug(cli::DebugCmd),
+    Decompile(cli::DecompileCmd),
+    Diff(cli::DiffCmd),
     Prove(cli::ProveCmd),
     Run(cli::RunCmd),
     Verify(cli::VerifyCmd),
@@ -36,12 +39,23 @@ pub enum Actions {
 
 /// CLI entry point
 impl Cli {
+    /// Returns an error if the arguments of the action conflict, see [cli::ProveCmd::validate].
+    pub fn validate(&self) -> Result<(), clap::Error> {
+        match &self.action {
+            Actions::Prove(prove) => prove.validate(),
+            _ => Ok(()),
+        }
+    }
+
     pub fn execute(&self) -> Result<(), Report> {
         match &self.action {
             Actions::Analyze(analyze) => analyze.execute(),
             Actions::Compile(compile) => compile.execute(),
             Actions::Bundle(compile) => compile.execute(),
+            Actions::Check(check) => check.execute(),
             Actions::Debug(debug) => debug.execute(),
+            Actions::Decompile(decompile) => decompile.execute(),
+            Actions::Diff(diff) => diff.execute(),
             Actions::Prove(prove) => prove.execute(),
             Actions::Run(run) => run.execute(),
             Actions::Verify(verify) => verify.execute(),
@@ -55,6 +69,9 @@ impl Cli {
 pub fn main() -> Result<(), Report> {
     // read command-line args
     let cli = Cli::parse();
+    if let Err(err) = cli.validate() {
+        err.exit();
+    }
 
     initialize_diagnostics();
 
@@ -0,0 +1,309 @@
+use core::{fmt, time::Duration};
+use std::{collections::BTreeMap, sync::Arc, time::Instant};
+
+use assembly::{Assembler, Library, diagnostics::Report, mast::MastForest};
+use processor::{
+    AdviceInputs, DefaultHost, ExecutionError, MemAdviceProvider, Program, ProgramInfo,
+    StackInputs, execute,
+};
+use prover::{ExecutionProof, InputError, ProvingOptions, StackOutputs, prove_trace};
+use stdlib::StdLibrary;
+use verifier::{VerificationError, Verifier};
+
+// MIDEN PIPELINE
+// ================================================================================================
+
+/// A pipeline which assembles, executes, proves and verifies Miden programs.
+///
+/// A [MidenPipeline] is configured once with the libraries available to programs, the options of
+/// the proofs, and the [Verifier] which checks them, and is then used to run any number of
+/// programs with [MidenPipeline::run] or [MidenPipeline::run_program]. Each run goes through the
+/// following stages, and stops at the first one which fails:
+/// 1. The program is assembled from its source, unless the same source was assembled before.
+/// 2. The stack inputs are validated.
+/// 3. The program is executed, generating its execution trace, so that programs which fail to
+///    execute are rejected before incurring the cost of proving them.
+/// 4. The execution trace of the program is proven, without executing the program again.
+/// 5. The proof is verified, unless verification was disabled.
+///
+/// The standard library is always available to programs run by the pipeline. Every run executes
+/// the program in a new host, in which the libraries of the pipeline are loaded.
+#[derive(Clone)]
+pub struct MidenPipeline {
+    assembler: Assembler,
+    libraries: Vec<Arc<MastForest>>,
+    proving_options: ProvingOptions,
+    verifier: Option<Verifier>,
+    programs: BTreeMap<[u8; 32], Program>,
+}
+
+impl MidenPipeline {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new pipeline which links programs against the standard library, proves them with
+    /// the default [ProvingOptions], and verifies the proofs with a [Verifier] which accepts any
+    /// valid proof.
+    pub fn new() -> Self {
+        let stdlib = StdLibrary::default();
+        let assembler = Assembler::default()
+            .with_library(&stdlib)
+            .expect("failed to link the standard library");
+
+        Self {
+            assembler,
+            libraries: vec![stdlib.mast_forest().clone()],
+            proving_options: ProvingOptions::default(),
+            verifier: Some(Verifier::new()),
+            programs: BTreeMap::new(),
+        }
+    }
+
+    /// Makes the procedures of the specified library available to the programs run by this
+    /// pipeline.
+    ///
+    /// # Errors
+    /// Returns an error if the library conflicts with a library which was already added.
+    pub fn with_library(mut self, library: impl AsRef<Library>) -> Result<Self, PipelineError> {
+        let library = library.as_ref();
+        self.assembler.add_library(library).map_err(PipelineError::Assembly)?;
+        self.libraries.push(library.mast_forest().clone());
+        // programs assembled before the library was added may resolve procedures differently
+        self.programs.clear();
+        Ok(self)
+    }
+
+    /// Sets the options with which programs are executed and proven.
+    pub fn with_proving_options(mut self, proving_options: ProvingOptions) -> Self {
+        self.proving_options = proving_options;
+        self
+    }
+
+    /// Sets the [Verifier] which checks the proofs generated by this pipeline.
+    pub fn with_verifier(mut self, verifier: Verifier) -> Self {
+        self.verifier = Some(verifier);
+        self
+    }
+
+    /// Disables the verification of the proofs generated by this pipeline.
+    pub fn without_verification(mut self) -> Self {
+        self.verifier = None;
+        self
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the options with which programs are executed and proven.
+    pub fn proving_options(&self) -> &ProvingOptions {
+        &self.proving_options
+    }
+
+    /// Returns the [Verifier] which checks the proofs generated by this pipeline, if verification
+    /// is enabled.
+    pub fn verifier(&self) -> Option<&Verifier> {
+        self.verifier.as_ref()
+    }
+
+    // PIPELINE
+    // --------------------------------------------------------------------------------------------
+
+    /// Assembles the program with the specified source, or returns the program assembled from the
+    /// same source by a previous call.
+    ///
+    /// # Errors
+    /// Returns an error if the program fails to assemble.
+    pub fn assemble(&mut self, source: &str) -> Result<Program, PipelineError> {
+        let key = *blake3::hash(source.as_bytes()).as_bytes();
+        if let Some(program) = self.programs.get(&key) {
+            return Ok(program.clone());
+        }
+
+        let program = self
+            .assembler
+            .clone()
+            .assemble_program(source)
+            .map_err(PipelineError::Assembly)?;
+        self.programs.insert(key, program.clone());
+        Ok(program)
+    }
+
+    /// Assembles the program with the specified source, and executes, proves and verifies it
+    /// against the specified inputs.
+    ///
+    /// The stack inputs are expected to be ordered as if they would be pushed onto the stack one
+    /// by one.
+    ///
+    /// # Errors
+    /// Returns an error if any stage of the pipeline fails, as described in [PipelineError].
+    pub fn run(
+        &mut self,
+        source: &str,
+        stack_inputs: &[u64],
+        advice_inputs: AdviceInputs,
+    ) -> Result<PipelineOutput, PipelineError> {
+        let now = Instant::now();
+        let program = self.assemble(source)?;
+        let assembly = now.elapsed();
+
+        let mut output = self.run_program(&program, stack_inputs, advice_inputs)?;
+        output.timings.assembly = assembly;
+        Ok(output)
+    }
+
+    /// Executes, proves and verifies the specified program against the specified inputs.
+    ///
+    /// The stack inputs are expected to be ordered as if they would be pushed onto the stack one
+    /// by one.
+    ///
+    /// # Errors
+    /// Returns an error if any stage of the pipeline fails, as described in [PipelineError].
+    pub fn run_program(
+        &self,
+        program: &Program,
+        stack_inputs: &[u64],
+        advice_inputs: AdviceInputs,
+    ) -> Result<PipelineOutput, PipelineError> {
+        let mut timings = PipelineTimings::default();
+        let stack_inputs = StackInputs::try_from_ints(stack_inputs.iter().copied())?;
+        let exec_options = self.proving_options.execution_options().clone();
+
+        let now = Instant::now();
+        let trace = execute(
+            program,
+            stack_inputs.clone(),
+            &mut self.new_host(advice_inputs)?,
+            exec_options,
+        )
+        .map_err(PipelineError::Execution)?;
+        timings.execution = now.elapsed();
+
+        let now = Instant::now();
+        let (stack_outputs, proof) =
+            prove_trace(trace, stack_inputs.clone(), self.proving_options.clone())
+                .map_err(PipelineError::Proving)?;
+        timings.proving = now.elapsed();
+
+        let program_info = ProgramInfo::from(program.clone());
+        let security_level = match &self.verifier {
+            Some(verifier) => {
+                let now = Instant::now();
+                let security_level = verifier.verify(
+                    program_info.clone(),
+                    stack_inputs.clone(),
+                    stack_outputs.clone(),
+                    proof.clone(),
+                )?;
+                timings.verification = now.elapsed();
+                security_level
+            },
+            None => proof.security_level(),
+        };
+
+        Ok(PipelineOutput {
+            program_info,
+            stack_inputs,
+            stack_outputs,
+            proof,
+            security_level,
+            timings,
+        })
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new host with the specified advice inputs, in which the libraries of this
+    /// pipeline are loaded.
+    fn new_host(
+        &self,
+        advice_inputs: AdviceInputs,
+    ) -> Result<DefaultHost<MemAdviceProvider>, PipelineError> {
+        let mut host = DefaultHost::new(MemAdviceProvider::from(advice_inputs));
+        for library in self.libraries.iter() {
+            host.load_mast_forest(library.clone()).map_err(PipelineError::Execution)?;
+        }
+        Ok(host)
+    }
+}
+
+impl Default for MidenPipeline {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// PIPELINE OUTPUT
+// ================================================================================================
+
+/// The result of a successful run of a [MidenPipeline].
+#[derive(Debug, Clone)]
+pub struct PipelineOutput {
+    /// Information about the program which was run.
+    pub program_info: ProgramInfo,
+    /// The stack inputs against which the program was executed.
+    pub stack_inputs: StackInputs,
+    /// The stack outputs of the program.
+    pub stack_outputs: StackOutputs,
+    /// The proof of the execution of the program.
+    pub proof: ExecutionProof,
+    /// The security level of the proof, in bits.
+    pub security_level: u32,
+    /// The time spent in each stage of the pipeline.
+    pub timings: PipelineTimings,
+}
+
+/// The time spent in each stage of a run of a [MidenPipeline].
+///
+/// The time of the stages which were skipped, i.e., the assembly of programs which were assembled
+/// by a previous run, or the verification of proofs when verification is disabled, is zero.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PipelineTimings {
+    /// The time spent assembling the program.
+    pub assembly: Duration,
+    /// The time spent executing the program and generating its execution trace.
+    pub execution: Duration,
+    /// The time spent proving the execution of the program.
+    pub proving: Duration,
+    /// The time spent verifying the proof.
+    pub verification: Duration,
+}
+
+impl PipelineTimings {
+    /// Returns the total time spent in all stages of the pipeline.
+    pub fn total(&self) -> Duration {
+        self.assembly + self.execution + self.proving + self.verification
+    }
+}
+
+impl fmt::Display for PipelineTimings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "assembly: {} ms, execution: {} ms, proving: {} ms, verification: {} ms, total: {} ms",
+            self.assembly.as_millis(),
+            self.execution.as_millis(),
+            self.proving.as_millis(),
+            self.verification.as_millis(),
+            self.total().as_millis()
+        )
+    }
+}
+
+// PIPELINE ERROR
+// ================================================================================================
+
+/// An error which occurred at one of the stages of a [MidenPipeline].
+#[derive(Debug, thiserror::Error)]
+pub enum PipelineError {
+    #[error("failed to assemble the program: {0}")]
+    Assembly(Report),
+    #[error("invalid stack inputs")]
+    InvalidInputs(#[from] InputError),
+    #[error("failed to execute the program")]
+    Execution(#[source] ExecutionError),
+    #[error("failed to prove the program")]
+    Proving(#[source] ExecutionError),
+    #[error("failed to verify the proof")]
+    Verification(#[from] VerificationError),
+}
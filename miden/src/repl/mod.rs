@@ -1,4 +1,4 @@
-use std::{collections::BTreeSet, path::PathBuf};
+use std::collections::BTreeSet;
 
 use assembly::{Assembler, Library};
 use miden_vm::{DefaultHost, StackInputs, math::Felt};
@@ -145,20 +145,13 @@ use crate::utils::print_mem_address;
 // ```
 
 /// Initiates the Miden Repl tool.
-pub fn start_repl(library_paths: &Vec<PathBuf>, use_stdlib: bool) {
+pub fn start_repl(libraries: Vec<Library>, use_stdlib: bool) {
     let mut program_lines: Vec<String> = Vec::new();
 
     // set of user imported modules
     let mut imported_modules: BTreeSet<String> = BTreeSet::new();
 
-    // load libraries from files
-    let mut provided_libraries = Vec::new();
-    for path in library_paths {
-        let library = Library::deserialize_from_file(path)
-            .map_err(|e| format!("Failed to read library: {e}"))
-            .unwrap();
-        provided_libraries.push(library);
-    }
+    let mut provided_libraries = libraries;
     if use_stdlib {
         provided_libraries.push(StdLibrary::default().into());
     }
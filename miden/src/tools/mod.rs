@@ -6,9 +6,9 @@ use clap::Parser;
 use miden_vm::{DefaultHost, Host, Operation, StackInputs, internal::InputFile};
 use processor::{AsmOpInfo, TraceLenSummary};
 use stdlib::StdLibrary;
-use vm_core::Program;
+use vm_core::{Program, stack::MIN_STACK_DEPTH};
 
-use super::cli::data::Libraries;
+use super::cli::data::{Libraries, ProgramFile, TrustOptions};
 use crate::cli::utils::{get_masm_program, get_masp_program};
 
 // CLI
@@ -26,16 +26,29 @@ pub struct Analyze {
     #[clap(short = 'i', long = "input", value_parser)]
     input_file: Option<PathBuf>,
 
-    /// Paths to .masl library files
+    /// Paths to .masl library files or .masp library packages
     #[clap(short = 'l', long = "libraries", value_parser)]
     library_paths: Vec<PathBuf>,
+    #[clap(flatten)]
+    trust: TrustOptions,
+
+    /// Features enabled when compiling an assembly file, i.e. which make the procedures and
+    /// instructions annotated with `@cfg(feature = "<name>")` part of the program
+    #[clap(long = "features", value_delimiter = ',')]
+    features: Vec<String>,
+
+    /// Report the stack depth of each procedure of an assembly file, as determined by static
+    /// analysis, instead of executing the program; the procedures of the standard library and of
+    /// the provided libraries are analyzed from their MAST
+    #[clap(long = "stack-depth")]
+    stack_depth: bool,
 }
 
 /// Implements CLI execution logic
 impl Analyze {
     pub fn execute(&self) -> Result<(), Report> {
         // load libraries from files
-        let libraries = Libraries::new(&self.library_paths)?;
+        let libraries = Libraries::new(&self.library_paths, &self.trust.policy())?;
 
         // Determine file type based on extension.
         let ext = self
@@ -45,10 +58,14 @@ impl Analyze {
             .unwrap_or("")
             .to_lowercase();
 
+        if self.stack_depth {
+            return self.analyze_stack_depth(&ext, &libraries);
+        }
+
         // Use a single match expression to load the program.
         let program = match ext.as_str() {
             "masp" => get_masp_program(&self.program_file)?,
-            "masm" => get_masm_program(&self.program_file, &libraries)?,
+            "masm" => get_masm_program(&self.program_file, &libraries, &self.features)?,
             _ => return Err(Report::msg("The provided file must have a .masm or .masp extension")),
         };
         // let program_hash: [u8; 32] = program.hash().into();
@@ -86,6 +103,31 @@ impl Analyze {
 
         Ok(())
     }
+
+    /// Prints the stack depth of each procedure of the program, followed by a warning if any of
+    /// them may underflow.
+    fn analyze_stack_depth(&self, ext: &str, libraries: &Libraries) -> Result<(), Report> {
+        if ext != "masm" {
+            return Err(Report::msg("Stack depth analysis requires a .masm file"));
+        }
+        let report = ProgramFile::read_with_features(&self.program_file, &self.features)?
+            .analyze_stack_depth(&libraries.libraries)?;
+
+        println!("============================================================");
+        println!("Stack depth of the procedures of {}", self.program_file.display());
+        println!("{report}");
+
+        let underflows = report.underflows().map(|name| name.to_string()).collect::<Vec<_>>();
+        if !underflows.is_empty() {
+            println!(
+                "warning: the following procedures may access elements beyond the top {} \
+                 elements of the stack: {}",
+                MIN_STACK_DEPTH,
+                underflows.join(", ")
+            );
+        }
+        Ok(())
+    }
 }
 
 // EXECUTION DETAILS
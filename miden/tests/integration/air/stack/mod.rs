@@ -29,3 +29,31 @@ fn full_inputs() {
 
     build_op_test!(&asm_op, &pub_inputs).prove_and_verify(pub_inputs, false);
 }
+
+/// Test u64 operations, which shift the stack to the left, with a non-empty overflow table.
+#[test]
+fn u64_ops_with_overflow() {
+    let asm_op = "push.1.2.3.4 u64overflowing_add push.5 u64overflowing_sub drop drop drop";
+    let pub_inputs = vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16];
+
+    build_op_test!(&asm_op, &pub_inputs).prove_and_verify(pub_inputs, false);
+}
+
+/// Test the U32ROTL operation, which shifts the stack to the left, with a non-empty overflow
+/// table.
+#[test]
+fn u32rotl_with_overflow() {
+    let asm_op = "push.0x80000001 u32rotl.3 push.5 u32rotr push.0 u32rotr push.31 u32rotl drop";
+    let pub_inputs = vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16];
+
+    build_op_test!(&asm_op, &pub_inputs).prove_and_verify(pub_inputs, false);
+}
+
+/// Test the CTX operation, which shifts the stack to the right, with a non-empty overflow table.
+#[test]
+fn ctx_with_overflow() {
+    let asm_op = "ctx ctx add sdepth drop drop";
+    let pub_inputs = vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16];
+
+    build_op_test!(&asm_op, &pub_inputs).prove_and_verify(pub_inputs, false);
+}
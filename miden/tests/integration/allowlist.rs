@@ -0,0 +1,126 @@
+use assembly::Assembler;
+use miden_vm::{
+    DefaultHost, ExecutionProof, Kernel, ProgramInfo, ProvingOptions, StackInputs, StackOutputs,
+    VerificationError, VerificationRequest, Verifier, prove,
+};
+use vm_core::{Program, assert_matches, crypto::hash::Rpo256};
+
+// ALLOWLIST TESTS
+// ================================================================================================
+
+fn prove_program(source: &str) -> (Program, StackOutputs, ExecutionProof) {
+    let program = Assembler::default().assemble_program(source).unwrap();
+    let (stack_outputs, proof) = prove(
+        &program,
+        StackInputs::default(),
+        &mut DefaultHost::default(),
+        ProvingOptions::default(),
+    )
+    .unwrap();
+    (program, stack_outputs, proof)
+}
+
+#[test]
+fn verifier_program_allowlist() {
+    let (program, stack_outputs, proof) = prove_program("begin push.1 push.2 add swap drop end");
+    let (other_program, ..) = prove_program("begin push.1 push.2 mul swap drop end");
+    let program_info = ProgramInfo::from(program.clone());
+
+    // a verifier without restrictions accepts any valid proof
+    let security_level = Verifier::new()
+        .verify(
+            program_info.clone(),
+            StackInputs::default(),
+            stack_outputs.clone(),
+            proof.clone(),
+        )
+        .unwrap();
+    assert_eq!(security_level, proof.security_level());
+
+    let verifier = Verifier::new().with_allowed_programs([program.hash()]);
+    verifier
+        .verify(
+            program_info.clone(),
+            StackInputs::default(),
+            stack_outputs.clone(),
+            proof.clone(),
+        )
+        .unwrap();
+
+    let verifier = Verifier::new().with_allowed_programs([other_program.hash()]);
+    let result = verifier.verify(program_info, StackInputs::default(), stack_outputs, proof);
+    assert_matches!(
+        result,
+        Err(VerificationError::ProgramNotAllowed(hash)) if hash == program.hash()
+    );
+}
+
+#[test]
+fn verifier_kernel_allowlist() {
+    let [foo, bar] = [Rpo256::hash(b"foo"), Rpo256::hash(b"bar")];
+    let program_hash = Rpo256::hash(b"program");
+    let program_info = ProgramInfo::new(program_hash, Kernel::new(&[foo, bar]).unwrap());
+
+    Verifier::new()
+        .with_allowed_kernel_procedures([foo, bar])
+        .check(&program_info, 96)
+        .unwrap();
+
+    let result = Verifier::new().with_allowed_kernel_procedures([foo]).check(&program_info, 96);
+    assert_matches!(
+        result,
+        Err(VerificationError::KernelProcedureNotAllowed { program, procedure })
+            if program == program_hash && procedure == bar
+    );
+
+    // programs without a kernel are not restricted by the kernel allowlist
+    let program_info = ProgramInfo::new(program_hash, Kernel::default());
+    Verifier::new()
+        .with_allowed_kernel_procedures([foo])
+        .check(&program_info, 96)
+        .unwrap();
+}
+
+#[test]
+fn verifier_batch() {
+    let (program, stack_outputs, proof) = prove_program("begin push.1 push.2 add swap drop end");
+    let (other_program, other_outputs, other_proof) =
+        prove_program("begin push.1 push.2 mul swap drop end");
+    let request = VerificationRequest::new(
+        program.clone().into(),
+        StackInputs::default(),
+        stack_outputs.clone(),
+        proof.clone(),
+    );
+
+    let verifier = Verifier::new().with_allowed_programs([program.hash()]);
+    let results = verifier.verify_batch([
+        request.clone(),
+        VerificationRequest::new(
+            other_program.into(),
+            StackInputs::default(),
+            other_outputs,
+            other_proof,
+        ),
+        // allowed programs are still verified against the specified outputs
+        VerificationRequest::new(
+            program.into(),
+            StackInputs::default(),
+            StackOutputs::default(),
+            proof.clone(),
+        ),
+    ]);
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0].as_ref().unwrap(), &proof.security_level());
+    assert_matches!(results[1], Err(VerificationError::ProgramNotAllowed(_)));
+    assert_matches!(results[2], Err(VerificationError::ProgramVerificationError { .. }));
+
+    // the security level of the proofs is checked before verification
+    let verifier = Verifier::new().with_min_security_level(proof.security_level() + 1);
+    let results = verifier.verify_batch([request]);
+    assert_matches!(
+        results[0],
+        Err(VerificationError::InsufficientSecurityLevel { security_level, .. })
+            if security_level == proof.security_level()
+    );
+}
@@ -0,0 +1,121 @@
+use miden_vm::{
+    Assembler, DefaultHost, ExecutionError, ProgramInfo, ProvingJob, ProvingOptions, StackInputs,
+    StackOutputs, VerificationError, VerificationRequest, prove, prove_batch, verify_batch,
+};
+
+// BATCH PROVING TESTS
+// ================================================================================================
+
+#[test]
+fn prove_batch_matches_prove() {
+    let assembler = Assembler::default();
+    let programs = [
+        "begin push.3 push.5 u32xor swap drop end",
+        "begin repeat.8 hperm end end",
+        "begin add mul end",
+        // proven first with the `concurrent` feature, as its trace is the longest of the batch
+        "begin repeat.100 hperm end end",
+    ]
+    .map(|source| assembler.clone().assemble_program(source).unwrap());
+    let stack_inputs = StackInputs::try_from_ints([1, 2, 3, 4]).unwrap();
+    let options = ProvingOptions::with_96_bit_security(false);
+
+    let jobs = programs
+        .iter()
+        .map(|program| ProvingJob::new(program, stack_inputs.clone(), DefaultHost::default()))
+        .collect();
+    let results = prove_batch(jobs, options.clone());
+    assert_eq!(results.len(), programs.len());
+
+    // proofs are returned in the order of the jobs, and are identical to those of `prove`
+    for (program, result) in programs.iter().zip(results) {
+        let (stack_outputs, proof) = result.unwrap();
+        let (expected_outputs, expected_proof) =
+            prove(program, stack_inputs.clone(), &mut DefaultHost::default(), options.clone())
+                .unwrap();
+        assert_eq!(stack_outputs, expected_outputs);
+        assert!(proof.to_bytes() == expected_proof.to_bytes());
+
+        verifier::verify(
+            ProgramInfo::from(program.clone()),
+            stack_inputs.clone(),
+            stack_outputs,
+            proof,
+        )
+        .expect("proof must be valid");
+    }
+}
+
+#[test]
+fn prove_batch_with_failing_job() {
+    let assembler = Assembler::default();
+    let valid = assembler.clone().assemble_program("begin push.1 add end").unwrap();
+    let failing = assembler.assemble_program("begin push.0 assert end").unwrap();
+    let options = ProvingOptions::with_96_bit_security(false);
+
+    let jobs = vec![
+        ProvingJob::new(&valid, StackInputs::default(), DefaultHost::default()),
+        ProvingJob::new(&failing, StackInputs::default(), DefaultHost::default()),
+        ProvingJob::new(&valid, StackInputs::default(), DefaultHost::default()),
+    ];
+    let results = prove_batch(jobs, options);
+
+    // a failing job does not prevent the other jobs from being proven
+    assert!(results[0].is_ok());
+    assert!(matches!(results[1], Err(ExecutionError::FailedAssertion { .. })));
+    assert!(results[2].is_ok());
+}
+
+// BATCH VERIFICATION TESTS
+// ================================================================================================
+
+#[test]
+fn verify_batch_reports_each_proof() {
+    let assembler = Assembler::default();
+    let programs = [
+        "begin push.3 push.5 u32xor swap drop end",
+        "begin repeat.8 hperm end end",
+        "begin add mul end",
+        // proven first with the `concurrent` feature, as its trace is the longest of the batch
+        "begin repeat.100 hperm end end",
+    ]
+    .map(|source| assembler.clone().assemble_program(source).unwrap());
+    let stack_inputs = StackInputs::try_from_ints([1, 2, 3, 4]).unwrap();
+    let options = ProvingOptions::with_96_bit_security(false);
+
+    let mut requests = programs
+        .iter()
+        .map(|program| {
+            let (stack_outputs, proof) =
+                prove(program, stack_inputs.clone(), &mut DefaultHost::default(), options.clone())
+                    .unwrap();
+            VerificationRequest::new(
+                ProgramInfo::from(program.clone()),
+                stack_inputs.clone(),
+                stack_outputs,
+                proof,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    // claim wrong outputs for the second proof
+    let request = requests[1].clone();
+    requests[1] = VerificationRequest::new(
+        request.program_info().clone(),
+        stack_inputs.clone(),
+        StackOutputs::try_from_ints([42]).unwrap(),
+        request.proof().clone(),
+    );
+
+    // the results are in the order of the requests, and the invalid proof does not prevent the
+    // other proofs from being verified
+    let results = verify_batch(requests);
+    assert_eq!(results.len(), programs.len());
+    assert!(results[0].is_ok());
+    assert!(matches!(
+        &results[1],
+        Err(VerificationError::ProgramVerificationError { program, .. })
+            if *program == programs[1].hash()
+    ));
+    assert!(results[2].is_ok());
+}
@@ -58,6 +58,59 @@ fn cli_run_masp() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[test]
+fn cli_run_with_memory_file() -> Result<(), Box<dyn std::error::Error>> {
+    let memory_file = std::env::temp_dir().join("cli_run_with_memory_file.bin");
+    // elements at addresses 0 and 1 hold 40 and 2 respectively
+    fs::write(&memory_file, [40, 0, 0, 0, 2, 0, 0, 0])?;
+
+    let mut cmd = bin_under_test().command();
+    cmd.arg("run")
+        .arg("./tests/integration/cli/data/memory.masm")
+        .arg("-n")
+        .arg("1")
+        .arg("--memory-file")
+        .arg(memory_file.as_path());
+
+    let output = cmd.unwrap();
+    output.assert().stdout(predicate::str::contains("Output: [42]"));
+
+    fs::remove_file(&memory_file)?;
+    Ok(())
+}
+
+#[test]
+fn cli_run_record_and_compare() -> Result<(), Box<dyn std::error::Error>> {
+    let record_file = std::env::temp_dir().join("cli_run_record_and_compare.rec");
+
+    let mut cmd = bin_under_test().command();
+    cmd.arg("run")
+        .arg("./tests/integration/cli/data/memory.masm")
+        .arg("--record")
+        .arg(record_file.as_path());
+    cmd.assert().success();
+
+    let mut cmd = bin_under_test().command();
+    cmd.arg("run")
+        .arg("./tests/integration/cli/data/memory.masm")
+        .arg("--compare")
+        .arg(record_file.as_path());
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Execution matches the record"));
+
+    // a different program diverges from the record
+    let mut cmd = bin_under_test().command();
+    cmd.arg("run")
+        .arg("./masm-examples/fib/fib.masm")
+        .arg("--compare")
+        .arg(record_file.as_path());
+    cmd.assert().failure().stderr(predicate::str::contains("Execution diverges"));
+
+    fs::remove_file(&record_file)?;
+    Ok(())
+}
+
 #[test]
 fn cli_prove_masp() -> Result<(), Box<dyn std::error::Error>> {
     let mut cmd = bin_under_test().command();
@@ -77,6 +130,22 @@ fn cli_prove_masp() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[test]
+fn cli_prove_rpx_with_unsupported_security() {
+    let mut cmd = bin_under_test().command();
+
+    cmd.arg("prove")
+        .arg("./tests/integration/cli/data/masp/is_prime.masp")
+        .arg("--rpx")
+        .arg("-s")
+        .arg("100bits");
+
+    // the conflict is reported as a usage error before the program is loaded, rather than panicking
+    cmd.assert()
+        .code(2)
+        .stderr(predicate::str::contains("'--rpx' cannot be used with '--security 100bits'"));
+}
+
 use assembly::Library;
 use vm_core::Decorator;
 
@@ -152,6 +221,38 @@ fn cli_bundle_output() {
     fs::remove_file("test.masl").unwrap()
 }
 
+#[test]
+fn cli_check_lib() {
+    let mut cmd = bin_under_test().command();
+    cmd.arg("check").arg("./tests/integration/cli/data/lib");
+    cmd.assert().success().stdout(predicate::str::contains("No errors found"));
+}
+
+#[test]
+// A program which imports a library is only valid if the library is provided.
+fn cli_check_program() {
+    let mut cmd = bin_under_test().command();
+    cmd.arg("check").arg("./tests/integration/cli/data/main.masm");
+    cmd.assert().failure().stderr(predicate::str::contains("undefined module"));
+
+    let output_file = std::env::temp_dir().join("cli_check_program.masl");
+    let mut cmd = bin_under_test().command();
+    cmd.arg("bundle")
+        .arg("./tests/integration/cli/data/lib")
+        .arg("--output")
+        .arg(output_file.as_path());
+    cmd.assert().success();
+
+    let mut cmd = bin_under_test().command();
+    cmd.arg("check")
+        .arg("./tests/integration/cli/data/main.masm")
+        .arg("-l")
+        .arg(output_file.as_path());
+    cmd.assert().success().stdout(predicate::str::contains("No errors found"));
+
+    fs::remove_file(&output_file).unwrap()
+}
+
 #[test]
 // First compile a library to a .masl file, then run a program that uses it.
 fn cli_run_with_lib() -> Result<(), Box<dyn std::error::Error>> {
@@ -204,3 +305,100 @@ fn cli_debug_masp() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+/// Returns a predicate checking that the output contains `message`, regardless of how the error
+/// report wraps its lines.
+fn contains_words(message: &'static str) -> impl Predicate<str> {
+    predicate::function(move |output: &str| {
+        output.split_whitespace().collect::<Vec<_>>().join(" ").contains(message)
+    })
+}
+
+/// Signs packages with an ed25519 key.
+struct Ed25519Signer(ed25519_dalek::SigningKey);
+
+impl package::PackageSigner for Ed25519Signer {
+    fn public_key(&self) -> package::PublicKey {
+        self.0.verifying_key().to_bytes()
+    }
+
+    fn sign(&self, message: &[u8]) -> [u8; package::SIGNATURE_LEN] {
+        use ed25519_dalek::Signer;
+        self.0.sign(message).to_bytes()
+    }
+}
+
+#[test]
+// Libraries are only loaded if they satisfy the trust policy defined by `--trust` and
+// `--require-signed`.
+fn cli_run_with_trusted_lib() -> Result<(), Box<dyn std::error::Error>> {
+    use std::sync::Arc;
+
+    use package::{MastArtifact, Package, PackageManifest, PackageSigner};
+    use vm_core::utils::Serializable;
+
+    let library_file = std::env::temp_dir().join("cli_run_with_trusted_lib.masl");
+    let mut cmd = bin_under_test().command();
+    cmd.arg("bundle")
+        .arg("./tests/integration/cli/data/lib")
+        .arg("--output")
+        .arg(library_file.as_path());
+    cmd.assert().success();
+
+    let package = Package {
+        name: "lib".to_string(),
+        mast: MastArtifact::Library(Arc::new(Library::deserialize_from_file(&library_file)?)),
+        manifest: PackageManifest::default(),
+    };
+    let owner = Ed25519Signer(ed25519_dalek::SigningKey::from_bytes(&[1; 32]));
+    let other = Ed25519Signer(ed25519_dalek::SigningKey::from_bytes(&[2; 32]));
+    let owner_key: String = owner.public_key().iter().map(|byte| format!("{byte:02x}")).collect();
+    let trust = format!("lib={owner_key}");
+
+    let unsigned_file = std::env::temp_dir().join("cli_run_with_trusted_lib_unsigned.masp");
+    fs::write(&unsigned_file, package.to_bytes())?;
+    let signed_by_other_file = std::env::temp_dir().join("cli_run_with_trusted_lib_other.masp");
+    fs::write(&signed_by_other_file, package.sign(&other).to_bytes())?;
+    let signed_file = std::env::temp_dir().join("cli_run_with_trusted_lib_signed.masp");
+    fs::write(&signed_file, package.sign(&owner).to_bytes())?;
+
+    let run = |library: &Path, trust_args: &[&str]| {
+        let mut cmd = bin_under_test().command();
+        cmd.arg("run")
+            .arg("./tests/integration/cli/data/main.masm")
+            .arg("-l")
+            .arg(library)
+            .args(trust_args);
+        cmd.assert()
+    };
+
+    // without a policy, all libraries are loaded
+    run(&library_file, &[]).success();
+    run(&unsigned_file, &[]).success();
+
+    // signatures are required
+    run(&library_file, &["--require-signed"])
+        .failure()
+        .stderr(contains_words("not signed, but signatures are required"));
+    run(&unsigned_file, &["--require-signed"])
+        .failure()
+        .stderr(contains_words("not signed, but signatures are required"));
+    run(&signed_by_other_file, &["--require-signed"]).success();
+
+    // the `lib` namespace is owned
+    run(&library_file, &["--trust", &trust])
+        .failure()
+        .stderr(contains_words("is not signed by one of its owners"));
+    run(&unsigned_file, &["--trust", &trust])
+        .failure()
+        .stderr(contains_words("is not signed by one of its owners"));
+    run(&signed_by_other_file, &["--trust", &trust])
+        .failure()
+        .stderr(contains_words("which is not owned by its signer"));
+    run(&signed_file, &["--trust", &trust]).success();
+
+    for file in [library_file, unsigned_file, signed_by_other_file, signed_file] {
+        fs::remove_file(file)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,99 @@
+use assembly::Assembler;
+use miden_vm::{
+    DefaultHost, DelegatedClaim, ExecutionProof, ProgramInfo, ProofEnvelope, ProvingOptions,
+    StackOutputs, VerificationError, prove, verify_envelope,
+};
+use prover::{Digest, StackInputs};
+use stdlib::StdLibrary;
+use vm_core::{
+    Felt, Program, Word, assert_matches,
+    crypto::hash::Rpo256,
+    utils::{Deserializable, Serializable},
+};
+
+#[test]
+fn delegated_claims() {
+    // the delegated computation multiplies its input by 3
+    let inner_program = Assembler::default().assemble_program("begin push.3 mul end").unwrap();
+    let inner_inputs = StackInputs::try_from_ints([5]).unwrap();
+    let (inner_outputs, inner_proof) = prove_program(&inner_program, inner_inputs.clone());
+    assert_eq!(inner_outputs.get_stack_item(0), Some(Felt::new(15)));
+    let claim = DelegatedClaim::new(inner_program.clone().into(), inner_inputs, inner_outputs);
+
+    // the program consumes the claim and leaves the commitment to it at the top of the stack
+    let (program, stack_inputs) = build_consuming_program(&claim);
+    let (stack_outputs, proof) = prove_program(&program, stack_inputs.clone());
+    let program_info = ProgramInfo::from(program);
+
+    let envelope =
+        ProofEnvelope::new(proof.clone()).with_delegated_proof(claim.clone(), inner_proof);
+    assert_eq!(stack_outputs.get_stack_word(0), Some(envelope.claims_commitment()));
+
+    // envelopes round-trip through serialization
+    let envelope = ProofEnvelope::read_from_bytes(&envelope.to_bytes()).unwrap();
+    verify_envelope(program_info.clone(), stack_inputs.clone(), stack_outputs.clone(), envelope)
+        .unwrap();
+
+    // the program consumed a claim, so an envelope without delegated proofs is rejected
+    let result =
+        verify_envelope(program_info, stack_inputs, stack_outputs, ProofEnvelope::new(proof));
+    assert_matches!(result, Err(VerificationError::ClaimsCommitmentMismatch { .. }));
+}
+
+#[test]
+fn delegated_claims_false_claim() {
+    let inner_program = Assembler::default().assemble_program("begin push.3 mul end").unwrap();
+    let inner_inputs = StackInputs::try_from_ints([5]).unwrap();
+    let (_, inner_proof) = prove_program(&inner_program, inner_inputs.clone());
+
+    // the program consumes a claim with outputs the delegated computation did not produce
+    let false_outputs = StackOutputs::try_from_ints([16]).unwrap();
+    let claim = DelegatedClaim::new(inner_program.into(), inner_inputs, false_outputs);
+    let (program, stack_inputs) = build_consuming_program(&claim);
+    let (stack_outputs, proof) = prove_program(&program, stack_inputs.clone());
+
+    let envelope = ProofEnvelope::new(proof).with_delegated_proof(claim, inner_proof);
+    let result = verify_envelope(ProgramInfo::from(program), stack_inputs, stack_outputs, envelope);
+    assert_matches!(
+        result,
+        Err(VerificationError::DelegatedClaimNotProven(0, source))
+            if matches!(*source, VerificationError::ProgramVerificationError { .. })
+    );
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn prove_program(program: &Program, stack_inputs: StackInputs) -> (StackOutputs, ExecutionProof) {
+    let mut host = DefaultHost::default();
+    host.load_mast_forest(StdLibrary::default().mast_forest().clone()).unwrap();
+    prove(program, stack_inputs, &mut host, ProvingOptions::default()).unwrap()
+}
+
+/// Returns a program consuming the specified claim, along with its stack inputs.
+///
+/// The commitment to the program of the claim and the hashes of its inputs and outputs are
+/// provided to the program via the stack.
+fn build_consuming_program(claim: &DelegatedClaim) -> (Program, StackInputs) {
+    let source = "
+    use.std::sys
+
+    begin
+        exec.sys::claim_digest
+        padw swapw exec.sys::consume_claim
+    end";
+    let program = Assembler::default()
+        .with_library(StdLibrary::default())
+        .unwrap()
+        .assemble_program(source)
+        .unwrap();
+
+    let inputs = Rpo256::hash_elements(claim.stack_inputs().as_slice());
+    let outputs = Rpo256::hash_elements(claim.stack_outputs().as_slice());
+    let stack_inputs = [outputs, inputs, claim.program_commitment()]
+        .iter()
+        .flat_map(|word: &Digest| Word::from(*word))
+        .collect();
+
+    (program, StackInputs::new(stack_inputs).unwrap())
+}
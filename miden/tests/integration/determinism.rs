@@ -0,0 +1,88 @@
+use miden_vm::{
+    Assembler, DefaultHost, ExecutionProof, HashFunction, Program, ProgramInfo, ProvingOptions,
+    StackInputs, prove,
+};
+
+// DETERMINISM TESTS
+// ================================================================================================
+
+/// A program which exercises the hasher, bitwise and memory chiplets, so that all segments of the
+/// trace are non-trivial.
+const SOURCE: &str = "
+    begin
+        repeat.8
+            push.3 push.5 u32xor drop
+            dup mem_store.8 mem_load.8 drop
+            hperm
+        end
+    end";
+
+fn proving_options(hash_fn: HashFunction) -> ProvingOptions {
+    match hash_fn {
+        HashFunction::Blake3_192 => ProvingOptions::with_96_bit_security(false),
+        HashFunction::Rpo256 => ProvingOptions::with_96_bit_security(true),
+        HashFunction::Rpx256 => ProvingOptions::with_96_bit_security_rpx(),
+        HashFunction::Blake3_256 => ProvingOptions::with_128_bit_security(false),
+        HashFunction::Keccak256 => {
+            ProvingOptions::with_96_bit_security(false).with_hash_fn(HashFunction::Keccak256)
+        },
+    }
+}
+
+fn prove_program(program: &Program, options: ProvingOptions) -> ExecutionProof {
+    let stack_inputs = StackInputs::try_from_ints([1, 2, 3, 4, 5, 6, 7, 8]).unwrap();
+    let (stack_outputs, proof) =
+        prove(program, stack_inputs.clone(), &mut DefaultHost::default(), options).unwrap();
+    let program_info = ProgramInfo::from(program.clone());
+    verifier::verify(program_info, stack_inputs, stack_outputs, proof.clone())
+        .expect("proof must be valid");
+    proof
+}
+
+#[test]
+fn determinism_across_runs() {
+    let program = Assembler::default().assemble_program(SOURCE).unwrap();
+    for hash_fn in [
+        HashFunction::Blake3_192,
+        HashFunction::Rpo256,
+        HashFunction::Rpx256,
+        HashFunction::Keccak256,
+    ] {
+        let proof = prove_program(&program, proving_options(hash_fn)).to_bytes();
+        let proof_again = prove_program(&program, proving_options(hash_fn)).to_bytes();
+        assert!(proof == proof_again, "proofs generated with {hash_fn:?} differ");
+    }
+}
+
+#[test]
+#[cfg(feature = "concurrent")]
+fn determinism_across_thread_counts() {
+    let program = Assembler::default().assemble_program(SOURCE).unwrap();
+    for hash_fn in [HashFunction::Blake3_192, HashFunction::Rpo256] {
+        let proofs: Vec<Vec<u8>> = [1, 2, 4, 8]
+            .into_iter()
+            .map(|num_threads| {
+                let pool =
+                    rayon::ThreadPoolBuilder::new().num_threads(num_threads).build().unwrap();
+                pool.install(|| prove_program(&program, proving_options(hash_fn)).to_bytes())
+            })
+            .collect();
+        assert!(
+            proofs.windows(2).all(|pair| pair[0] == pair[1]),
+            "proofs generated with {hash_fn:?} differ across thread counts"
+        );
+    }
+}
+
+#[test]
+fn determinism_with_memory_limit() {
+    // a memory limit of zero forces the prover to spill the extended trace to disk, which must not
+    // change the proof
+    let program = Assembler::default().assemble_program(SOURCE).unwrap();
+    for hash_fn in [HashFunction::Blake3_192, HashFunction::Rpo256] {
+        let proof = prove_program(&program, proving_options(hash_fn)).to_bytes();
+        let spilled_proof =
+            prove_program(&program, proving_options(hash_fn).with_memory_limit(0)).to_bytes();
+        assert!(proof == spilled_proof, "proofs generated with {hash_fn:?} differ when spilled");
+    }
+}
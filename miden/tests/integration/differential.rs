@@ -0,0 +1,141 @@
+use miden_vm::{
+    Assembler, DefaultHost, ExecutionOptions, ExecutionRecord, Program, RecordedStep, StackInputs,
+    StackOutputs,
+};
+use processor_v0_13::{Operation as PrevOperation, VmState as PrevVmState};
+use vm_core::Felt;
+
+// DIFFERENTIAL EXECUTION TESTS
+// ================================================================================================
+
+/// Programs executed by both the processor and its previous release, with their stack inputs.
+const PROGRAMS: [(&str, &[u64]); 7] = [
+    // field operations and memory
+    ("begin push.2 add mem_store.8 mem_load.8 mul.5 swap drop end", &[1]),
+    // u32 operations
+    (
+        "begin push.7 push.3 u32wrapping_add push.5 u32divmod u32xor push.13 u32shl u32split add
+            swap drop
+        end",
+        &[],
+    ),
+    // word memory accesses, including writes which don't change the memory
+    (
+        "begin
+            push.1.2.3.4 mem_storew.100 dropw push.0 mem_store.104 push.0.0.0.0 mem_storew.108
+            dropw mem_loadw.100 push.2 mem_store.102 mem_load.102 padw mem_loadw.100 dropw drop
+        end",
+        &[],
+    ),
+    // procedure locals, and memory in a new execution context
+    (
+        "proc.foo.4 loc_store.0 loc_load.0 push.1 add end
+        proc.bar push.5 mem_store.0 mem_load.0 add end
+        begin exec.foo call.bar swap drop end",
+        &[3],
+    ),
+    // loops
+    ("begin push.0 push.1 repeat.20 swap dup.1 add end swap drop swap drop end", &[]),
+    ("begin dup neq.0 while.true push.1 sub dup neq.0 end drop end", &[5]),
+    // conditionals and hashing
+    (
+        "begin
+            if.true mul.2 else mul.3 end
+            push.1.2.3.4.5.6.7.8.9.10.11.12 hperm dropw dropw dropw
+        end",
+        &[7, 1],
+    ),
+];
+
+#[test]
+fn previous_release_differential_execution() {
+    for (source, inputs) in PROGRAMS {
+        let expected = record_previous_release(source, inputs);
+        assert!(expected.outcome().is_ok(), "{source}: {:?}", expected.outcome());
+
+        let program: Program = Assembler::default().assemble_program(source).unwrap();
+        let stack_inputs = StackInputs::try_from_ints(inputs.iter().copied()).unwrap();
+        let actual = ExecutionRecord::record(
+            &program,
+            stack_inputs,
+            &mut DefaultHost::default(),
+            ExecutionOptions::default(),
+        )
+        .unwrap();
+
+        if let Some(divergence) = expected.first_divergence(&actual) {
+            panic!("execution of `{source}` diverges from the previous release: {divergence}");
+        }
+    }
+}
+
+#[test]
+fn previous_release_differential_execution_divergence() {
+    // the records made by the previous release are not trivially equal to any other record
+    let expected = record_previous_release(PROGRAMS[0].0, PROGRAMS[0].1);
+    let program: Program = Assembler::default().assemble_program(PROGRAMS[0].0).unwrap();
+    let actual = ExecutionRecord::record(
+        &program,
+        StackInputs::try_from_ints([2]).unwrap(),
+        &mut DefaultHost::default(),
+        ExecutionOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(expected.first_divergence(&actual).unwrap().clk, 0);
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Executes `source` with the previous release of the assembler and the processor, and records the
+/// states of the VM at every clock cycle.
+fn record_previous_release(source: &str, inputs: &[u64]) -> ExecutionRecord {
+    let program = assembly_v0_13::Assembler::default().assemble_program(source).unwrap();
+    let stack_inputs = processor_v0_13::StackInputs::try_from_ints(inputs.iter().copied()).unwrap();
+    let mut host = processor_v0_13::DefaultHost::default();
+    let states = processor_v0_13::execute_iter(&program, stack_inputs, &mut host);
+
+    let mut steps = Vec::new();
+    let mut prev_state: Option<PrevVmState> = None;
+    for state in states {
+        let state = match state {
+            Ok(state) => state,
+            Err(err) => return ExecutionRecord::new(steps, Err(err.to_string())),
+        };
+        let memory_writes = match &prev_state {
+            Some(prev_state) => memory_writes(prev_state, &state),
+            None => Vec::new(),
+        };
+        steps.push(RecordedStep {
+            ctx: state.ctx.into(),
+            stack: state.stack.iter().map(|value| Felt::new(value.as_int())).collect(),
+            memory_writes,
+        });
+        prev_state = Some(state);
+    }
+
+    let final_stack = &steps.last().expect("no states recorded").stack;
+    let outputs = StackOutputs::new(final_stack[..16].to_vec()).unwrap();
+    ExecutionRecord::new(steps, Ok(outputs))
+}
+
+/// Returns the memory writes performed by the operation which led from `prev_state` to `state`.
+///
+/// The previous release only reports the state of memory at every cycle, which does not reveal
+/// the writes which don't change the memory. Thus, the written addresses are derived from the
+/// operation and the stack instead, and the written values are read from the state of memory.
+fn memory_writes(prev_state: &PrevVmState, state: &PrevVmState) -> Vec<(u64, Felt)> {
+    let (addr, num_elements) = match state.op {
+        Some(PrevOperation::MStore) => (prev_state.stack[0], 1),
+        Some(PrevOperation::MStoreW) => (prev_state.stack[0], 4),
+        Some(PrevOperation::Pipe) => (prev_state.stack[12], 8),
+        _ => return Vec::new(),
+    };
+    let addrs = addr.as_int()..addr.as_int() + num_elements;
+    state
+        .memory
+        .iter()
+        .filter(|(addr, _)| addrs.contains(addr))
+        .map(|(addr, value)| (*addr, Felt::new(value.as_int())))
+        .collect()
+}
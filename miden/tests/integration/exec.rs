@@ -1,8 +1,24 @@
 use assembly::Assembler;
-use miden_vm::DefaultHost;
-use processor::{ExecutionOptions, MastForest};
+use miden_vm::{
+    AdviceInputs, AdviceProvider, AdviceTape, AdviceTapeEntry, AdviceTapeRecorder,
+    AdviceTapeReplayer, BranchCoverage, CancellationToken, DefaultHost, DivergenceKind,
+    EventTimeline, ExecutionEnvironment, ExecutionRecord, FileMerkleNodeStorage, ForestCoverage,
+    IsaVersion, MemAdviceProvider, MemoryState, OpDispatch, PersistentAdviceProvider, ProgramInfo,
+    ProvingOptions, SandboxAdviceProvider, SandboxPolicy, TraceComponent, VerificationError,
+    VerificationRequest,
+};
+use processor::{
+    ContextId, ExecutionError, ExecutionOptions, ExecutionTrace, MastForest, MastNode, Process,
+    VmStateIterator,
+};
 use prover::{Digest, StackInputs};
-use vm_core::{ONE, Program, assert_matches};
+use test_utils::{TRUNCATE_STACK_PROC, build_test, crypto::init_merkle_store};
+use vm_core::{
+    EMPTY_WORD, Felt, Kernel, ONE, Operation, Program, Word, ZERO, assert_matches,
+    crypto::merkle::{LeafIndex, MerkleTree, SimpleSmt},
+    sys_events::EVENT_ENV_TO_STACK,
+    utils::{Deserializable, Serializable},
+};
 
 #[test]
 fn advice_map_loaded_before_execution() {
@@ -51,3 +67,1286 @@ fn advice_map_loaded_before_execution() {
     )
     .unwrap();
 }
+
+#[test]
+fn execute_without_trace_matches_traced_execution() {
+    let source = "\
+    proc.sum_to
+        push.0 swap
+        dup neq.0
+        while.true
+            dup movdn.2 add swap sub.1 dup neq.0
+        end
+        drop
+    end
+
+    begin
+        push.1.2.3.4.5.6.7.8.9.10.11.12.13.14.15.16 push.17.18
+        mem_storew.100 dropw
+        push.100 call.sum_to
+        padw mem_loadw.100
+        movupw.2 dropw
+        repeat.15 movup.5 drop end
+    end";
+    let program: Program = Assembler::default().assemble_program(source).unwrap();
+    let stack_inputs = StackInputs::try_from_ints([7, 8, 9]).unwrap();
+
+    let trace = processor::execute(
+        &program,
+        stack_inputs.clone(),
+        &mut DefaultHost::default(),
+        ExecutionOptions::default(),
+    )
+    .unwrap();
+    let outputs = processor::execute_without_trace(
+        &program,
+        stack_inputs.clone(),
+        &mut DefaultHost::default(),
+        ExecutionOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(trace.stack_outputs(), &outputs);
+
+    // requesting a trace with trace generation disabled is an error
+    let result = processor::execute(
+        &program,
+        stack_inputs,
+        &mut DefaultHost::default(),
+        ExecutionOptions::default().without_trace_generation(),
+    );
+    assert!(matches!(result, Err(prover::ExecutionError::TraceGenerationDisabled)));
+}
+
+#[test]
+fn estimate_trace_stats_matches_traced_execution() {
+    let estimate_and_execute = |source: &str| {
+        let program: Program = Assembler::default().assemble_program(source).unwrap();
+        let trace = processor::execute(
+            &program,
+            StackInputs::default(),
+            &mut DefaultHost::default(),
+            ExecutionOptions::default(),
+        )
+        .unwrap();
+        let stats = processor::estimate_trace_stats(
+            &program,
+            StackInputs::default(),
+            &mut DefaultHost::default(),
+            ExecutionOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(trace.trace_stats(), &stats);
+        assert_eq!(stats.padded_trace_len(), trace.get_trace_len());
+        let outputs = processor::execute_without_trace(
+            &program,
+            StackInputs::default(),
+            &mut DefaultHost::default(),
+            ExecutionOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(trace.stack_outputs(), &outputs);
+        stats
+    };
+
+    // stack manipulations, u32 arithmetic and memory accesses
+    let stats = estimate_and_execute(
+        "\
+    begin
+        repeat.8
+            push.3.7 u32and push.1000 u32wrapping_mul
+            mem_store.40 mem_load.40 push.0 u32lt
+            padw padw dropw dropw drop
+        end
+    end",
+    );
+    assert_eq!(stats.dominant_component(), TraceComponent::Main);
+    assert!(stats.bitwise_rows() > 0 && stats.memory_rows() > 0);
+    // the 8 items pushed by the two PADWs come on top of the result of the preceding U32LT
+    assert_eq!(stats.overflow_table_peak_len(), 9);
+
+    // each permutation takes 8 rows of the hash chiplet but a single cycle
+    let stats = estimate_and_execute("begin repeat.64 hperm end end");
+    assert_eq!(stats.dominant_component(), TraceComponent::Chiplets);
+    assert!(stats.hasher_rows() > stats.main_trace_rows());
+
+    // the hasher rows of blocks with the same hash are counted without recording them
+    let stats = estimate_and_execute(
+        "\
+    proc.f
+        push.6 u32xor push.5 u32and
+    end
+
+    begin
+        push.1 call.f call.f call.f drop
+    end",
+    );
+    assert!(stats.bitwise_rows() > 0);
+}
+
+#[test]
+fn execution_record_divergence() {
+    let record = |source: &str, inputs: &[u64]| {
+        let program: Program = Assembler::default().assemble_program(source).unwrap();
+        let stack_inputs = StackInputs::try_from_ints(inputs.iter().copied()).unwrap();
+        ExecutionRecord::record(
+            &program,
+            stack_inputs,
+            &mut DefaultHost::default(),
+            ExecutionOptions::default(),
+        )
+        .unwrap()
+    };
+    let source = "begin push.2 add mem_store.8 mem_load.8 mul.5 swap drop end";
+
+    // recording the same execution twice, and round-tripping a record, yields no divergence
+    let expected = record(source, &[1]);
+    assert_eq!(expected.first_divergence(&record(source, &[1])), None);
+    let deserialized = ExecutionRecord::read_from_bytes(&expected.to_bytes()).unwrap();
+    assert_eq!(deserialized, expected);
+    assert_eq!(expected.outcome().unwrap().get_stack_item(0), Some(Felt::new(15)));
+
+    // different inputs diverge at the first cycle
+    let divergence = expected.first_divergence(&record(source, &[2])).unwrap();
+    assert_eq!(divergence.clk, 0);
+    assert_matches!(divergence.kind, DivergenceKind::Stack { .. });
+
+    // a different memory address diverges once the address is pushed onto the stack, after the
+    // common prefix of the two executions
+    let other = record("begin push.2 add mem_store.12 mem_load.8 mul.5 swap drop end", &[1]);
+    let divergence = expected.first_divergence(&other).unwrap();
+    assert!(divergence.clk > 2);
+    assert_matches!(divergence.kind, DivergenceKind::Stack { .. });
+    assert!(divergence.to_string().starts_with(&format!("clk={}", divergence.clk)));
+
+    // an execution which runs for more cycles diverges after the end of the shorter one
+    let longer = record("begin push.2 add mem_store.8 mem_load.8 mul.5 swap drop nop end", &[1]);
+    let divergence = expected.first_divergence(&longer).unwrap();
+    assert_matches!(divergence.kind, DivergenceKind::NumCycles { expected, actual } if expected < actual);
+
+    // failing executions are recorded up to the failure
+    let failing = record("begin push.2 add assert end", &[1]);
+    assert!(failing.outcome().is_err());
+    assert!(failing.num_cycles() > 0);
+
+    // the execution options are applied to the recorded execution
+    let program: Program = Assembler::default()
+        .assemble_program("begin repeat.100 push.1 drop end end")
+        .unwrap();
+    let stack_inputs = StackInputs::default();
+    let options = ExecutionOptions::new(Some(64), 64, false, false).unwrap();
+    let limited = ExecutionRecord::record(
+        &program,
+        stack_inputs.clone(),
+        &mut DefaultHost::default(),
+        options,
+    )
+    .unwrap();
+    assert!(limited.outcome().unwrap_err().contains("cycle"));
+    assert_matches!(
+        ExecutionRecord::record(
+            &program,
+            stack_inputs,
+            &mut DefaultHost::default(),
+            ExecutionOptions::default().without_trace_generation(),
+        ),
+        Err(ExecutionError::TraceGenerationDisabled)
+    );
+}
+
+#[test]
+fn execution_profile() {
+    let source = "\
+    proc.bar
+        push.1 add
+    end
+
+    proc.foo
+        dup
+        if.true
+            call.bar
+        else
+            push.2 add
+        end
+    end
+
+    begin
+        push.1 exec.foo
+        push.0 exec.foo
+        call.bar
+        dropw dropw
+    end";
+    let program: Program =
+        Assembler::default().with_debug_mode(true).assemble_program(source).unwrap();
+
+    let trace = processor::execute(
+        &program,
+        StackInputs::default(),
+        &mut DefaultHost::default(),
+        ExecutionOptions::default().with_profiling(),
+    )
+    .unwrap();
+    let profile = trace.profile().expect("profiling was enabled");
+
+    // main -> foo (x2) -> bar (x1), and main -> bar (x1)
+    let main = profile.root();
+    assert_eq!(main.digest(), program.hash());
+    assert_eq!(main.num_calls(), 1);
+    let [foo, bar] = main.children() else {
+        panic!("expected two callees of main, got {:?}", main.children());
+    };
+    assert!(foo.name().ends_with("::foo"), "unexpected name {}", foo.name());
+    assert_eq!(foo.num_calls(), 2);
+    assert!(bar.name().ends_with("::bar"), "unexpected name {}", bar.name());
+    assert_eq!(bar.num_calls(), 1);
+    let [foo_bar] = foo.children() else {
+        panic!("expected one callee of foo, got {:?}", foo.children());
+    };
+    assert_eq!(foo_bar.name(), bar.name());
+    assert_eq!(foo_bar.num_calls(), 1);
+    assert!(foo_bar.children().is_empty() && bar.children().is_empty());
+
+    // cycles of the callees are included in the total of their callers
+    assert_eq!(bar.total_cycles(), foo_bar.total_cycles());
+    assert!(foo.total_cycles() > foo_bar.total_cycles());
+    assert_eq!(
+        profile.total_cycles(),
+        main.self_cycles() + foo.self_cycles() + bar.total_cycles() + foo_bar.total_cycles()
+    );
+
+    // each call stack is folded into a single line
+    let expected = format!(
+        "{main} {}\n{main};{foo} {}\n{main};{foo};{bar} {}\n{main};{bar} {}\n",
+        main.self_cycles(),
+        foo.self_cycles(),
+        foo_bar.total_cycles(),
+        bar.total_cycles(),
+        main = main.name(),
+        foo = foo.name(),
+        bar = bar.name(),
+    );
+    assert_eq!(profile.to_folded(), expected);
+
+    // profiling is disabled by default
+    let trace = processor::execute(
+        &program,
+        StackInputs::default(),
+        &mut DefaultHost::default(),
+        ExecutionOptions::default(),
+    )
+    .unwrap();
+    assert!(trace.profile().is_none());
+}
+
+#[test]
+fn execution_error_source_location() {
+    let source = "\
+begin
+    push.1 drop
+    push.4294967296
+    u32assert.err=7
+end";
+    let (program, source_map) =
+        Assembler::default().assemble_program_with_source_map(source).unwrap();
+    assert!(program.mast_forest().decorators().is_empty());
+
+    // the failing instruction is attributed to its source, even though the program has no
+    // decorators
+    let mut process =
+        Process::new(Kernel::default(), StackInputs::default(), ExecutionOptions::default())
+            .with_source_map(source_map.clone());
+    let err = process.execute(&program, &mut DefaultHost::default()).unwrap_err();
+    let location = err.source_location().expect("source location is attached");
+    assert_eq!((location.line, location.column), (4, 5));
+    assert!(err.to_string().ends_with(&format!("\n    at {location}")));
+    assert_matches!(err.without_source_location(), ExecutionError::NotU32Value(..));
+
+    // in debug mode, the location of each executed instruction is recorded
+    let mut process = Process::new(
+        Kernel::default(),
+        StackInputs::default(),
+        ExecutionOptions::default().with_debugging(),
+    )
+    .with_source_map(source_map);
+    let result = process.execute(&program, &mut DefaultHost::default());
+    let mut iter = VmStateIterator::new(process, result);
+    let mut lines = Vec::new();
+    while let Some(Ok(state)) = iter.next() {
+        if let Some(location) = iter.source_location(&state) {
+            if lines.last() != Some(&location.line) {
+                lines.push(location.line);
+            }
+        }
+    }
+    assert_eq!(lines, [2, 3, 4]);
+}
+
+#[test]
+fn execution_error_call_stack() {
+    let source = "\
+    proc.check
+        u32assert.err=7
+    end
+
+    proc.foo
+        add exec.check
+    end
+
+    begin
+        push.2 call.foo
+    end";
+    let program: Program =
+        Assembler::default().with_debug_mode(true).assemble_program(source).unwrap();
+    let stack_inputs = StackInputs::try_from_ints([u32::MAX as u64]).unwrap();
+
+    let Err(err) = processor::execute(
+        &program,
+        stack_inputs.clone(),
+        &mut DefaultHost::default(),
+        ExecutionOptions::default().with_debugging(),
+    ) else {
+        panic!("expected execution to fail");
+    };
+    let call_stack = err.call_stack().expect("call stack is captured in debug mode").clone();
+
+    // check is inlined into foo, which is called from main
+    let [check, foo, main] = call_stack.frames() else {
+        panic!("expected three frames, got {:?}", call_stack.frames());
+    };
+    assert!(check.name().ends_with("::check"), "unexpected name {}", check.name());
+    assert!(check.is_inlined());
+    assert_eq!(check.digest(), foo.digest());
+    assert_eq!(check.instruction(), Some("u32assert.err=7"));
+    assert!(check.location().is_some());
+    assert!(foo.name().ends_with("::foo"), "unexpected name {}", foo.name());
+    assert_eq!(main.name(), "#exec::#main");
+    assert_eq!(main.digest(), program.hash());
+
+    // the failing instruction is only known for the innermost frame
+    for frame in [foo, main] {
+        assert!(!frame.is_inlined());
+        assert!(frame.instruction().is_none() && frame.location().is_none());
+    }
+
+    // the call stack is rendered after the error
+    let rendered = err.to_string();
+    assert!(
+        rendered.contains(&format!("stack backtrace:\n   0: {}", check.name())),
+        "{rendered}"
+    );
+    assert_matches!(
+        err.without_call_stack(),
+        prover::ExecutionError::NotU32Value(_, err_code) if err_code == Felt::new(7)
+    );
+
+    // call stacks are not captured outside of debug mode
+    let Err(err) = processor::execute(
+        &program,
+        stack_inputs,
+        &mut DefaultHost::default(),
+        ExecutionOptions::default(),
+    ) else {
+        panic!("expected execution to fail");
+    };
+    assert_matches!(err, prover::ExecutionError::NotU32Value(..));
+}
+
+#[test]
+fn execution_coverage() {
+    let source = "\
+    proc.foo
+        if.true
+            push.1 add
+        else
+            push.2 mul
+        end
+    end
+
+    begin
+        exec.foo
+        dup neq.0
+        while.true
+            sub.1 dup neq.0
+        end
+    end";
+    let program: Program =
+        Assembler::default().with_debug_mode(true).assemble_program(source).unwrap();
+    let run = |inputs: [u64; 2]| {
+        let trace = processor::execute(
+            &program,
+            StackInputs::try_from_ints(inputs).unwrap(),
+            &mut DefaultHost::default(),
+            ExecutionOptions::default().with_coverage(),
+        )
+        .unwrap();
+        trace.coverage().expect("coverage was enabled").clone()
+    };
+
+    // the false branch of the split is executed, and the loop body is skipped
+    let mut coverage = run([0, 0]);
+    let forest = program.mast_forest();
+    let split = forest.nodes().iter().find(|node| matches!(node, MastNode::Split(_))).unwrap();
+    let lp = forest.nodes().iter().find(|node| matches!(node, MastNode::Loop(_))).unwrap();
+    assert_eq!(
+        coverage.branch_coverage(split.digest()),
+        BranchCoverage { on_true: false, on_false: true }
+    );
+    assert_eq!(
+        coverage.branch_coverage(lp.digest()),
+        BranchCoverage { on_true: false, on_false: true }
+    );
+
+    let report = coverage.forest_coverage(forest);
+    assert_eq!(report.num_branches, 4);
+    assert_eq!(report.num_executed_branches, 2);
+    assert!(report.num_executed_blocks < report.num_blocks);
+    let executed = |report: &ForestCoverage, instruction: &str| {
+        let matching = report.instructions.iter().filter(|i| i.instruction == instruction);
+        matching.map(|i| i.executed).collect::<Vec<_>>()
+    };
+    assert_eq!(executed(&report, "add"), [false]);
+    assert_eq!(executed(&report, "mul"), [true]);
+    assert_eq!(executed(&report, "sub.1"), [false]);
+
+    // merging the coverage of another run covers the remaining branches
+    coverage.merge(&run([5, 1]));
+    assert_eq!(
+        coverage.branch_coverage(split.digest()),
+        BranchCoverage { on_true: true, on_false: true }
+    );
+    assert_eq!(
+        coverage.branch_coverage(lp.digest()),
+        BranchCoverage { on_true: true, on_false: true }
+    );
+    let report = coverage.forest_coverage(forest);
+    assert_eq!(report.num_executed_branches, report.num_branches);
+    assert_eq!(report.num_executed_blocks, report.num_blocks);
+    assert!(report.instructions.iter().all(|i| i.executed));
+
+    // coverage is not recorded by default
+    let trace = processor::execute(
+        &program,
+        StackInputs::default(),
+        &mut DefaultHost::default(),
+        ExecutionOptions::default(),
+    )
+    .unwrap();
+    assert!(trace.coverage().is_none());
+}
+
+#[test]
+fn execution_event_timeline() {
+    let source = "\
+    proc.foo
+        push.7 emit.1 drop
+    end
+
+    begin
+        emit.2
+        call.foo
+        emit.3
+    end";
+    let program: Program = Assembler::default().assemble_program(source).unwrap();
+    let run = |options: ExecutionOptions| {
+        let trace = processor::execute(
+            &program,
+            StackInputs::try_from_ints([5]).unwrap(),
+            &mut DefaultHost::default(),
+            options,
+        )
+        .unwrap();
+        trace.event_timeline().cloned()
+    };
+
+    let timeline = run(ExecutionOptions::default().with_event_timeline()).unwrap();
+    assert_eq!(timeline.program_hash(), program.hash());
+    let events = timeline.events();
+    assert_eq!(events.iter().map(|event| event.event_id()).collect::<Vec<_>>(), [2, 1, 3]);
+    assert!(events.windows(2).all(|pair| pair[0].clk() < pair[1].clk()));
+    assert!(events.iter().all(|event| !event.is_system_event() && event.stack().is_none()));
+
+    // the event emitted by the called procedure is emitted in its own context, and within the
+    // procedure
+    let forest = program.mast_forest();
+    let mut roots = forest.procedure_roots().iter().map(|&root| forest[root].digest());
+    let foo = roots.find(|&digest| digest != program.hash()).unwrap();
+    assert_eq!(events[0].ctx(), ContextId::root());
+    assert_ne!(events[1].ctx(), ContextId::root());
+    assert_eq!(events[0].procedures(), [program.hash()]);
+    assert_eq!(events[1].procedures(), [program.hash(), foo]);
+    assert_eq!(timeline.events_with_id(1).count(), 1);
+
+    let deserialized = EventTimeline::read_from_bytes(&timeline.to_bytes()).unwrap();
+    assert_eq!(deserialized, timeline);
+    assert_eq!(deserialized.commitment(), timeline.commitment());
+
+    // stack snapshots contain the top of the stack, and are part of the commitment
+    let with_stack = run(ExecutionOptions::default().with_event_stack_snapshots()).unwrap();
+    let stack: Vec<u64> = with_stack.events()[1]
+        .stack()
+        .unwrap()
+        .iter()
+        .map(|element| element.as_int())
+        .collect();
+    assert_eq!(stack[..2], [7, 5]);
+    assert_eq!(with_stack.events()[0].stack().unwrap()[0], Felt::new(5));
+    assert_ne!(with_stack.commitment(), timeline.commitment());
+
+    // events are not recorded by default
+    assert!(run(ExecutionOptions::default()).is_none());
+}
+
+#[test]
+fn unsupported_isa_version() {
+    let source = "begin push.1 push.2 add drop end";
+    let program: Program = Assembler::default().assemble_program(source).unwrap();
+    assert_eq!(program.isa_version(), IsaVersion::CURRENT);
+
+    // the ISA version follows the magic and the format version of a serialized program; bump its
+    // major version
+    let mut bytes = program.to_bytes();
+    bytes[8] += 1;
+    let future_program = Program::read_from_bytes(&bytes).unwrap();
+    let future_version =
+        IsaVersion::new(IsaVersion::CURRENT.major() + 1, IsaVersion::CURRENT.minor());
+    assert_eq!(future_program.isa_version(), future_version);
+
+    let Err(err) = processor::execute(
+        &future_program,
+        StackInputs::default(),
+        &mut DefaultHost::default(),
+        ExecutionOptions::default(),
+    ) else {
+        panic!("expected execution to fail");
+    };
+    assert_matches!(
+        err,
+        prover::ExecutionError::UnsupportedIsaVersion { program, supported }
+            if program == future_version && supported == IsaVersion::CURRENT
+    );
+
+    // proofs of the program are rejected when the verifier is given a future ISA version
+    let (stack_outputs, proof) = miden_vm::prove(
+        &program,
+        StackInputs::default(),
+        &mut DefaultHost::default(),
+        ProvingOptions::default(),
+    )
+    .unwrap();
+    let program_info = ProgramInfo::from(program.clone()).with_isa_version(future_version);
+    assert_matches!(
+        miden_vm::verify(program_info, StackInputs::default(), stack_outputs, proof),
+        Err(VerificationError::UnsupportedIsaVersion { .. })
+    );
+
+    // the ISA version is part of the public inputs, so proofs only verify against the version
+    // they were generated for, even when the verifier supports both versions
+    let old_program = program.with_isa_version(IsaVersion::V1_0);
+    let (stack_outputs, proof) = miden_vm::prove(
+        &old_program,
+        StackInputs::default(),
+        &mut DefaultHost::default(),
+        ProvingOptions::default(),
+    )
+    .unwrap();
+    let program_info = ProgramInfo::from(old_program);
+    miden_vm::verify(
+        program_info.clone(),
+        StackInputs::default(),
+        stack_outputs.clone(),
+        proof.clone(),
+    )
+    .unwrap();
+    assert_matches!(
+        miden_vm::verify(
+            program_info.with_isa_version(IsaVersion::CURRENT),
+            StackInputs::default(),
+            stack_outputs,
+            proof
+        ),
+        Err(VerificationError::ProgramVerificationError { .. })
+    );
+}
+
+#[test]
+fn execution_environment_in_public_inputs() {
+    // reads the environment, and keeps the block number and the timestamp only
+    let source = format!(
+        "begin
+            emit.{EVENT_ENV_TO_STACK} padw adv_loadw padw adv_loadw padw adv_loadw drop drop swap
+            movdn.9 movdn.9 dropw dropw movup.2 drop movup.2 drop
+        end"
+    );
+    let program: Program = Assembler::default().assemble_program(source).unwrap();
+    let environment = ExecutionEnvironment::new(42, 1_700_000_000, [ONE; 4], [Felt::new(2); 4]);
+    let mut host = DefaultHost::default().with_environment(environment);
+
+    let (stack_outputs, proof) =
+        miden_vm::prove(&program, StackInputs::default(), &mut host, ProvingOptions::default())
+            .unwrap();
+    assert_eq!(&stack_outputs.stack_truncated(2), &[Felt::new(42), Felt::new(1_700_000_000)]);
+
+    // the proof verifies in the environment it was generated in only
+    let program_info = ProgramInfo::from(program);
+    miden_vm::verify_with_environment(
+        program_info.clone(),
+        StackInputs::default(),
+        stack_outputs.clone(),
+        environment,
+        proof.clone(),
+    )
+    .unwrap();
+    assert_matches!(
+        miden_vm::verify(program_info, StackInputs::default(), stack_outputs, proof),
+        Err(VerificationError::ProgramVerificationError { .. })
+    );
+}
+
+#[test]
+fn memory_limit_exceeded() {
+    let source = "begin repeat.10 push.1 mem_store.0 end end";
+    let program: Program = Assembler::default().assemble_program(source).unwrap();
+    let run = |max_memory_rows| {
+        processor::execute(
+            &program,
+            StackInputs::default(),
+            &mut DefaultHost::default(),
+            ExecutionOptions::default().with_max_memory_rows(max_memory_rows),
+        )
+    };
+
+    // each store adds a row to the memory chiplet trace
+    assert!(run(10).is_ok());
+
+    let Err(err) = run(9) else {
+        panic!("expected execution to fail");
+    };
+    assert_matches!(err, prover::ExecutionError::MemoryLimitExceeded(9));
+}
+
+#[test]
+fn execution_cancelled() {
+    // the program never terminates
+    let source = "begin push.1 while.true push.1 end end";
+    let program: Program = Assembler::default().assemble_program(source).unwrap();
+
+    let cancel_token = CancellationToken::new();
+    let handle = {
+        let cancel_token = cancel_token.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(std::time::Duration::from_millis(50));
+            cancel_token.cancel();
+        })
+    };
+
+    let Err(err) = processor::execute(
+        &program,
+        StackInputs::default(),
+        &mut DefaultHost::default(),
+        ExecutionOptions::default().with_cancel_token(cancel_token.clone()),
+    ) else {
+        panic!("expected execution to fail");
+    };
+    handle.join().unwrap();
+    assert_matches!(err, prover::ExecutionError::Cancelled);
+    assert!(cancel_token.is_cancelled());
+}
+
+#[test]
+fn sandboxed_execution() {
+    let source = "
+    begin
+        adv_push.2 drop drop
+        emit.1234
+        repeat.10 push.1 mem_store.0 end
+        repeat.100 push.1 drop end
+    end";
+    let program: Program = Assembler::default().assemble_program(source).unwrap();
+    let policy = SandboxPolicy::new(1 << 10)
+        .unwrap()
+        .with_max_memory_rows(10)
+        .with_max_advice_elements(2)
+        .with_allowed_events([1234])
+        .with_timeout(std::time::Duration::from_secs(60));
+    let run = |policy: &SandboxPolicy| {
+        let advice_inputs = AdviceInputs::default().with_stack_values([1, 2]).unwrap();
+        let provider = SandboxAdviceProvider::new(MemAdviceProvider::from(advice_inputs));
+        policy.execute(&program, StackInputs::default(), &mut DefaultHost::new(provider))
+    };
+
+    // the program stays within all limits of the policy
+    assert!(run(&policy).is_ok());
+
+    let Err(err) = run(&policy.clone().with_max_advice_elements(1)) else {
+        panic!("expected execution to fail");
+    };
+    assert_matches!(err, prover::ExecutionError::AdviceLimitExceeded(1));
+
+    let Err(err) = run(&SandboxPolicy::new(1 << 10).unwrap()) else {
+        panic!("expected execution to fail");
+    };
+    assert_matches!(err, prover::ExecutionError::EventNotAllowed(1234));
+
+    let Err(err) = run(&policy.clone().with_max_memory_rows(9)) else {
+        panic!("expected execution to fail");
+    };
+    assert_matches!(err, prover::ExecutionError::MemoryLimitExceeded(9));
+
+    let Err(err) = run(&SandboxPolicy::new(64).unwrap().with_allowed_events([1234])) else {
+        panic!("expected execution to fail");
+    };
+    assert_matches!(err, prover::ExecutionError::CycleLimitExceeded(64));
+
+    // the program never terminates, and is stopped once the timeout elapses
+    let source = "begin push.1 while.true push.1 end end";
+    let program: Program = Assembler::default().assemble_program(source).unwrap();
+    let timeout = std::time::Duration::from_millis(50);
+    let policy = SandboxPolicy::new(u32::MAX).unwrap().with_timeout(timeout);
+    let mut host = DefaultHost::new(SandboxAdviceProvider::new(MemAdviceProvider::default()));
+    let Err(err) = policy.execute(&program, StackInputs::default(), &mut host) else {
+        panic!("expected execution to fail");
+    };
+    assert_matches!(err, prover::ExecutionError::TimeLimitExceeded(elapsed) if elapsed == timeout);
+}
+
+#[test]
+fn op_dispatch_strategies_agree() {
+    let source = "
+    begin
+        push.3.7 u32assert2 u32divmod swap u32wrapping_add
+        push.5 mul add.1 neg inv dup.3 movdn.5 swapw
+        push.1 mem_store.4 mem_load.4 eq.1 assert
+        adv_push.2 hperm dropw
+    end";
+    let program: Program = Assembler::default().assemble_program(source).unwrap();
+    let run = |op_dispatch| {
+        processor::execute(
+            &program,
+            StackInputs::try_from_ints([1, 2, 3]).unwrap(),
+            &mut DefaultHost::new(MemAdviceProvider::from(
+                AdviceInputs::default().with_stack_values([11, 12]).unwrap(),
+            )),
+            ExecutionOptions::default().with_op_dispatch(op_dispatch),
+        )
+        .unwrap()
+    };
+
+    let table_trace = run(OpDispatch::Table);
+    let match_trace = run(OpDispatch::Match);
+    assert_eq!(table_trace.stack_outputs(), match_trace.stack_outputs());
+    assert_eq!(table_trace.trace_len_summary(), match_trace.trace_len_summary());
+}
+
+#[test]
+fn memory_persisted_across_executions() {
+    // increments the counter stored at address 0, and returns its new value
+    let source = "begin mem_load.0 add.1 dup mem_store.0 swap drop end";
+    let program: Program = Assembler::default().assemble_program(source).unwrap();
+    let run = |memory: MemoryState| {
+        let options = ExecutionOptions::default().without_trace_generation();
+        let mut process = Process::new(program.kernel().clone(), StackInputs::default(), options)
+            .with_initial_memory(memory)
+            .unwrap();
+        let stack_outputs = process.execute(&program, &mut DefaultHost::default()).unwrap();
+        (stack_outputs.get_stack_item(0).unwrap(), process.memory_state())
+    };
+
+    let mut memory = MemoryState::new();
+    for expected in 1..=3_u32 {
+        let (counter, final_memory) = run(memory);
+        assert_eq!(counter, Felt::from(expected));
+        memory = final_memory;
+    }
+    assert_eq!(
+        memory.get_word(ContextId::root(), 0),
+        Some([Felt::from(3_u32), ZERO, ZERO, ZERO])
+    );
+}
+
+#[test]
+fn initial_memory_in_public_inputs() {
+    // reads initialized words, one of which is overwritten first, while another word is
+    // initialized but never accessed
+    let source = "begin
+        push.5 mem_store.4 mem_load.4 mem_load.0 add padw mem_loadw.8 drop drop drop add
+        swap drop
+    end";
+    let program: Program = Assembler::default().assemble_program(source).unwrap();
+    let mut memory = MemoryState::new();
+    memory.set_word(ContextId::root(), 0, [Felt::new(40), ONE, ONE, ONE]).unwrap();
+    memory.set_word(ContextId::root(), 4, [Felt::new(7), ONE, ONE, ONE]).unwrap();
+    memory.set_word(ContextId::root(), 8, [Felt::new(2), ONE, ONE, ONE]).unwrap();
+    memory.set_word(ContextId::root(), 1024, [Felt::new(9); 4]).unwrap();
+
+    let mut process =
+        Process::new(program.kernel().clone(), StackInputs::default(), ExecutionOptions::default())
+            .with_initial_memory(memory.clone())
+            .unwrap();
+    let stack_outputs = process.execute(&program, &mut DefaultHost::default()).unwrap();
+    assert_eq!(stack_outputs.get_stack_item(0), Some(Felt::new(47)));
+    let trace = ExecutionTrace::new(process, stack_outputs);
+    assert_eq!(trace.initial_memory(), &memory);
+
+    let (stack_outputs, proof) =
+        miden_vm::prove_trace(trace, StackInputs::default(), ProvingOptions::default()).unwrap();
+
+    // the proof verifies against the memory the execution started from only
+    let request = |commitment| {
+        VerificationRequest::new(
+            ProgramInfo::from(program.clone()),
+            StackInputs::default(),
+            stack_outputs.clone(),
+            proof.clone(),
+        )
+        .with_initial_memory(commitment)
+    };
+    let mut other_memory = memory.clone();
+    other_memory.set_word(ContextId::root(), 1024, [Felt::new(8); 4]).unwrap();
+    let results = miden_vm::verify_batch([
+        request(memory.commitment()),
+        request(other_memory.commitment()),
+        request(MemoryState::new().commitment()),
+    ]);
+    assert!(results[0].is_ok(), "{:?}", results[0]);
+    assert_matches!(results[1], Err(VerificationError::ProgramVerificationError { .. }));
+    assert_matches!(results[2], Err(VerificationError::ProgramVerificationError { .. }));
+}
+
+#[test]
+fn read_only_memory() {
+    let mut memory = MemoryState::new();
+    memory.set_word(ContextId::root(), 0, [ONE, ONE, ONE, ONE]).unwrap();
+    let options = ExecutionOptions::default().without_trace_generation();
+    let new_process = |program: &Program| {
+        Process::new(program.kernel().clone(), StackInputs::default(), options.clone())
+            .with_initial_memory(memory.clone())
+            .unwrap()
+            .with_read_only_memory(ContextId::root(), 0..4)
+            .unwrap()
+    };
+
+    // read-only memory can be read
+    let program = Assembler::default()
+        .assemble_program("begin mem_load.3 push.1.4 mem_store swap drop end")
+        .unwrap();
+    let stack_outputs = new_process(&program).execute(&program, &mut DefaultHost::default());
+    assert_eq!(stack_outputs.unwrap().get_stack_item(0), Some(ONE));
+
+    // but not written to
+    let program = Assembler::default().assemble_program("begin push.0.3 mem_store end").unwrap();
+    let err = new_process(&program)
+        .execute(&program, &mut DefaultHost::default())
+        .unwrap_err();
+    assert_matches!(err, prover::ExecutionError::ReadOnlyMemoryWrite { addr: 3, .. });
+
+    // invalid ranges are rejected
+    let result = Process::new(Kernel::default(), StackInputs::default(), options.clone())
+        .with_read_only_memory(ContextId::root(), core::ops::Range { start: 4, end: 0 });
+    assert_matches!(
+        result.err(),
+        Some(prover::ExecutionError::InvalidMemoryRange { start_addr: 4, end_addr: 0 })
+    );
+}
+
+#[test]
+fn final_memory_state() {
+    let source = "
+    proc.store_local.4
+        push.5.6.7.8 loc_storew.0 dropw
+    end
+
+    begin
+        push.1.2.3.4 mem_storew.8 dropw
+        push.9 mem_store.13
+        call.store_local
+    end";
+
+    let test = build_test!(source);
+    let mut expected = MemoryState::new();
+    expected
+        .set_word(ContextId::root(), 8, [ONE, 2_u32.into(), 3_u32.into(), 4_u32.into()])
+        .unwrap();
+    expected
+        .set_word(ContextId::root(), 12, [ZERO, 9_u32.into(), ZERO, ZERO])
+        .unwrap();
+
+    // the local memory of the called procedure lives in a separate context
+    let trace = test.execute().unwrap();
+    let memory = trace.memory();
+    let contexts: Vec<ContextId> = memory.contexts().collect();
+    assert_eq!(contexts.len(), 2);
+    let local_words: Vec<_> = memory.words(contexts[1]).collect();
+    assert_eq!(local_words.len(), 1);
+    let (local_addr, local_word) = local_words[0];
+    assert_eq!(local_word, [5_u32.into(), 6_u32.into(), 7_u32.into(), 8_u32.into()]);
+    assert_eq!(memory.get_value(ContextId::root(), 13), Some(9_u32.into()));
+
+    expected.set_word(contexts[1], local_addr, local_word).unwrap();
+    test.expect_memory(&expected);
+}
+
+#[test]
+fn static_program_analysis() {
+    let source = "
+    proc.foo
+        push.1 push.2 add drop
+    end
+
+    begin
+        push.5 push.1
+        if.true hperm else hperm end
+        call.foo
+        repeat.100 push.1 drop end
+        drop
+    end";
+    let program: Program = Assembler::default().assemble_program(source).unwrap();
+    let analysis = *program.entrypoint_analysis().expect("analysis attached by the assembler");
+
+    let trace = processor::execute(
+        &program,
+        StackInputs::default(),
+        &mut DefaultHost::default(),
+        ExecutionOptions::default(),
+    )
+    .unwrap();
+    let trace_len = trace.trace_len_summary();
+
+    // the program has no loops, so its cost is known exactly
+    assert_eq!(analysis.cycles().exact_value(), Some(trace_len.main_trace_len() as u64));
+    assert_eq!(
+        analysis.hasher_permutations().exact_value(),
+        Some(trace_len.chiplets_trace_len().hash_chiplet_len() as u64 / 8)
+    );
+    assert_eq!(analysis.stack_effect(), Some(0));
+
+    // the analysis is not serialized, but doesn't affect program equality
+    let deserialized = Program::read_from_bytes(&program.to_bytes()).unwrap();
+    assert!(deserialized.static_analysis().is_none());
+    assert_eq!(deserialized, program);
+    assert_eq!(deserialized.with_static_analysis().entrypoint_analysis(), Some(&analysis));
+}
+
+#[test]
+fn static_program_analysis_of_loops() {
+    // the loop body pushes the next condition, so the stack effect is known
+    let source = "begin push.1 push.1 while.true push.0 end drop end";
+    let program: Program = Assembler::default().assemble_program(source).unwrap();
+    let analysis = program.entrypoint_analysis().unwrap();
+    assert_eq!(analysis.stack_effect(), Some(0));
+    assert_eq!(analysis.cycles().max(), None);
+    assert_eq!(analysis.hasher_permutations().max(), None);
+
+    let trace = processor::execute(
+        &program,
+        StackInputs::default(),
+        &mut DefaultHost::default(),
+        ExecutionOptions::default(),
+    )
+    .unwrap();
+    assert!(analysis.cycles().min() <= trace.trace_len_summary().main_trace_len() as u64);
+
+    // the number of elements pushed by the loop depends on the number of iterations
+    let source = "begin push.1 while.true push.0 push.0 end end";
+    let program: Program = Assembler::default().assemble_program(source).unwrap();
+    assert_eq!(program.entrypoint_analysis().unwrap().stack_effect(), None);
+}
+
+#[test]
+fn cycle_limit_exceeded_statically() {
+    let source = "begin repeat.100 push.1 drop end end";
+    let program: Program = Assembler::default().assemble_program(source).unwrap();
+    let min_cycles = program.entrypoint_analysis().unwrap().cycles().min() as u32;
+    assert!(min_cycles > 64);
+
+    let options = ExecutionOptions::new(Some(64), 64, false, false).unwrap();
+    let mut process = Process::new(program.kernel().clone(), StackInputs::default(), options);
+    let result = process.execute(&program, &mut DefaultHost::default());
+    assert_matches!(result, Err(prover::ExecutionError::CycleLimitExceeded(64)));
+
+    // the program is rejected before any of its operations is executed
+    assert_eq!(process.system.clk(), 0);
+}
+
+/// Returns a program whose entrypoint is a chain of JOIN nodes of the specified nesting depth.
+fn build_join_chain(depth: u32) -> Program {
+    let mut forest = MastForest::new();
+    let block = forest.add_block(vec![Operation::Push(ONE), Operation::Drop], None).unwrap();
+    let mut root = block;
+    for _ in 1..depth {
+        root = forest.add_join(root, block).unwrap();
+    }
+    forest.make_root(root);
+    Program::new(forest.into(), root)
+}
+
+#[test]
+fn nesting_depth_limit() {
+    // nodes are executed recursively, and the frames of unoptimized builds are much larger than
+    // those of optimized ones, so the test is run on a thread with a large stack
+    let test = || {
+        let max_depth = ExecutionOptions::DEFAULT_MAX_NESTING_DEPTH;
+
+        // a program nested up to the default limit is executed
+        let program = build_join_chain(max_depth);
+        let mut process =
+            Process::new(Kernel::default(), StackInputs::default(), Default::default());
+        assert!(process.execute(&program, &mut DefaultHost::default()).is_ok());
+
+        // a program nested deeper fails gracefully once the limit is exceeded
+        let program = build_join_chain(10 * max_depth);
+        let mut process =
+            Process::new(Kernel::default(), StackInputs::default(), Default::default());
+        let result = process.execute(&program, &mut DefaultHost::default());
+        assert_matches!(result, Err(prover::ExecutionError::NestingDepthExceeded(depth)) if depth == max_depth);
+        assert!(process.system.clk() > 0);
+
+        // with a static analysis attached, the program is rejected before it is executed
+        let program = program.with_static_analysis();
+        assert_eq!(program.entrypoint_analysis().unwrap().depth(), 10 * max_depth);
+        let mut process =
+            Process::new(Kernel::default(), StackInputs::default(), Default::default());
+        let result = process.execute(&program, &mut DefaultHost::default());
+        assert_matches!(result, Err(prover::ExecutionError::NestingDepthExceeded(_)));
+        assert_eq!(process.system.clk(), 0);
+    };
+    std::thread::Builder::new()
+        .stack_size(64 << 20)
+        .spawn(test)
+        .unwrap()
+        .join()
+        .unwrap();
+
+    // the limit is configurable
+    let options = ExecutionOptions::default().with_max_nesting_depth(8);
+    let program = build_join_chain(9);
+    let mut process = Process::new(Kernel::default(), StackInputs::default(), options);
+    let result = process.execute(&program, &mut DefaultHost::default());
+    assert_matches!(result, Err(prover::ExecutionError::NestingDepthExceeded(8)));
+}
+
+#[test]
+fn long_exec_chain() {
+    // each procedure increments the top of the stack and executes the previous one; the chain is
+    // much longer than the default nesting depth limit, but is rebalanced by the assembler
+    let num_procs = 1100;
+    let mut source = String::from("proc.p0 push.1 if.true add.1 end end\n");
+    for i in 1..num_procs {
+        source.push_str(&format!("proc.p{i} exec.p{} push.1 if.true add.1 end end\n", i - 1));
+    }
+    source.push_str(&format!("begin exec.p{} end", num_procs - 1));
+
+    let test = build_test!(&source, &[0]);
+    test.expect_stack(&[num_procs]);
+}
+
+#[test]
+fn advice_tape_replay() {
+    let source = format!(
+        "
+    {TRUNCATE_STACK_PROC}
+
+    begin
+        mtree_get
+        adv_push.1
+        push.1.1.1.1 adv.push_mapval dropw
+        adv_push.2
+        exec.truncate_stack
+    end"
+    );
+    let program: Program = Assembler::default().assemble_program(source).unwrap();
+
+    let (leaves, store) = init_merkle_store(&[1, 2, 3, 4, 5, 6, 7, 8]);
+    let root = MerkleTree::new(leaves.clone()).unwrap().root();
+    let advice_inputs = AdviceInputs::default()
+        .with_stack_values([9, 10, 11])
+        .unwrap()
+        .with_map([(Digest::new([ONE; 4]), vec![Felt::new(12), Felt::new(13)])])
+        .with_merkle_store(store);
+    let stack_inputs = |index: u64| {
+        let mut inputs: Vec<u64> = root.iter().map(|element| element.as_int()).collect();
+        inputs.extend([index, 3]);
+        StackInputs::try_from_ints(inputs).unwrap()
+    };
+
+    // record the advice read during an execution
+    let mut host =
+        DefaultHost::new(AdviceTapeRecorder::new(MemAdviceProvider::from(advice_inputs)));
+    let trace =
+        processor::execute(&program, stack_inputs(5), &mut host, ExecutionOptions::default())
+            .unwrap();
+    let (tape, _) = host.into_inner().finalize();
+
+    // `mtree_get` reads the node from the Merkle store, and then verifies it against its path,
+    // after which the program pops the values from the advice inputs
+    assert_eq!(
+        tape.entries().first(),
+        Some(&AdviceTapeEntry::TreeNode {
+            root: root.into(),
+            depth: Felt::new(3),
+            index: Felt::new(5),
+            node: leaves[5],
+        })
+    );
+    assert_eq!(
+        &tape.entries()[tape.len() - 3..],
+        &[
+            AdviceTapeEntry::StackElement(Felt::new(9)),
+            AdviceTapeEntry::StackElement(Felt::new(12)),
+            AdviceTapeEntry::StackElement(Felt::new(13)),
+        ]
+    );
+
+    // the tape survives serialization
+    let tape = AdviceTape::read_from_bytes(&tape.to_bytes()).unwrap();
+
+    // replaying the tape reproduces the execution without any advice inputs
+    let mut host = DefaultHost::new(AdviceTapeReplayer::new(tape.clone()));
+    let replayed_trace =
+        processor::execute(&program, stack_inputs(5), &mut host, ExecutionOptions::default())
+            .unwrap();
+    assert_eq!(replayed_trace.stack_outputs(), trace.stack_outputs());
+    assert_eq!(replayed_trace.program_hash(), trace.program_hash());
+    assert_eq!(host.advice_provider().num_remaining_entries(), 0);
+
+    // an execution requesting different advice diverges from the tape
+    let mut host = DefaultHost::new(AdviceTapeReplayer::new(tape));
+    let result =
+        processor::execute(&program, stack_inputs(6), &mut host, ExecutionOptions::default());
+    assert!(matches!(
+        result,
+        Err(prover::ExecutionError::AdviceTapeMismatch { position: 0, .. })
+    ));
+}
+
+#[test]
+fn persistent_merkle_storage() {
+    let source = format!(
+        "
+    {TRUNCATE_STACK_PROC}
+
+    begin
+        mtree_set
+        exec.truncate_stack
+    end"
+    );
+    let program: Program = Assembler::default().assemble_program(source).unwrap();
+
+    // the tree is large enough for the storage to grow while it is being populated
+    let (leaves, store) = init_merkle_store(&(0..1024).collect::<Vec<_>>());
+    let root = MerkleTree::new(leaves.clone()).unwrap().root();
+    let new_value = [Felt::new(42), ZERO, ZERO, ONE];
+
+    let path = std::env::temp_dir().join("persistent_merkle_storage.bin");
+    let _ = std::fs::remove_file(&path);
+
+    // the provider answers Merkle store queries in the same way as the in-memory provider
+    let storage = FileMerkleNodeStorage::open(&path).unwrap();
+    let advice_inputs = AdviceInputs::default().with_merkle_store(store);
+    let provider = PersistentAdviceProvider::new(advice_inputs.clone(), storage).unwrap();
+    let mem_provider = MemAdviceProvider::from(advice_inputs);
+    for index in [0, 5, 1023] {
+        let (depth, index) = (Felt::new(10), Felt::new(index));
+        assert_eq!(
+            provider.get_merkle_path(root.into(), &depth, &index).unwrap(),
+            mem_provider.get_merkle_path(root.into(), &depth, &index).unwrap()
+        );
+        assert_eq!(
+            provider.get_leaf_depth(root.into(), &Felt::new(20), &index).unwrap(),
+            mem_provider.get_leaf_depth(root.into(), &Felt::new(20), &index).unwrap()
+        );
+    }
+    assert!(provider.get_tree_node(Digest::default().into(), &ONE, &ZERO).is_err());
+
+    // update a leaf of the tree, and persist the storage
+    let mut inputs: Vec<u64> = new_value.iter().map(|element| element.as_int()).collect();
+    inputs.extend(root.iter().map(|element| element.as_int()));
+    inputs.extend([5, 10]);
+    let mut host = DefaultHost::new(provider);
+    let stack_outputs = processor::execute(
+        &program,
+        StackInputs::try_from_ints(inputs).unwrap(),
+        &mut host,
+        ExecutionOptions::default(),
+    )
+    .unwrap()
+    .stack_outputs()
+    .clone();
+    assert_eq!(stack_outputs.get_stack_word(0), Some(leaves[5]));
+    let new_root = stack_outputs.get_stack_word(4).unwrap();
+
+    let mut storage = host.into_inner().into_storage();
+    storage.flush().unwrap();
+    drop(storage);
+
+    // the updated tree is available after the storage is reopened
+    let storage = FileMerkleNodeStorage::open(&path).unwrap();
+    let provider = PersistentAdviceProvider::new(AdviceInputs::default(), storage).unwrap();
+    assert_eq!(
+        provider.get_tree_node(new_root, &Felt::new(10), &Felt::new(5)).unwrap(),
+        new_value
+    );
+    assert_eq!(
+        provider.get_tree_node(root.into(), &Felt::new(10), &Felt::new(5)).unwrap(),
+        leaves[5]
+    );
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn sparse_merkle_tree_of_depth_64() {
+    let source = format!(
+        "
+    {TRUNCATE_STACK_PROC}
+
+    begin
+        mtree_set
+        exec.truncate_stack
+    end"
+    );
+    let program: Program = Assembler::default().assemble_program(source).unwrap();
+
+    let leaves = [
+        (0, [ONE, ZERO, ZERO, ZERO]),
+        (1 << 40, [Felt::new(2), ZERO, ZERO, ZERO]),
+        (1 << 63, [Felt::new(3), ZERO, ZERO, ZERO]),
+    ];
+    let mut smt = SimpleSmt::<64>::with_leaves(leaves).unwrap();
+
+    // only the nodes on the paths from the leaves to the root are added to the store
+    let mut advice_inputs = AdviceInputs::default();
+    let num_empty_nodes = advice_inputs.merkle_store().num_internal_nodes();
+    let root = advice_inputs.extend_sparse_merkle_tree(64, leaves).unwrap();
+    assert_eq!(root, smt.root());
+    assert!(advice_inputs.merkle_store().num_internal_nodes() <= num_empty_nodes + 3 * 64);
+
+    let mut provider = MemAdviceProvider::default();
+    assert_eq!(provider.insert_sparse_merkle_tree(64, leaves).unwrap(), Word::from(root));
+    assert_eq!(
+        provider
+            .get_tree_node(root.into(), &Felt::new(64), &Felt::new(1 << 40))
+            .unwrap(),
+        leaves[1].1
+    );
+    assert!(provider.insert_sparse_merkle_tree(65, leaves).is_err());
+    assert!(provider.insert_sparse_merkle_tree(8, leaves).is_err());
+
+    // insert a leaf at an empty index; the old value of the leaf proves that it was empty
+    let new_value = [Felt::new(42), ZERO, ZERO, ONE];
+    let mut inputs: Vec<u64> = new_value.iter().map(|element| element.as_int()).collect();
+    inputs.extend(root.iter().map(|element| element.as_int()));
+    inputs.extend([7, 64]);
+    let mut host = DefaultHost::new(MemAdviceProvider::from(advice_inputs));
+    let stack_outputs = processor::execute(
+        &program,
+        StackInputs::try_from_ints(inputs).unwrap(),
+        &mut host,
+        ExecutionOptions::default(),
+    )
+    .unwrap()
+    .stack_outputs()
+    .clone();
+
+    smt.insert(LeafIndex::new(7).unwrap(), new_value);
+    assert_eq!(stack_outputs.get_stack_word(0), Some(EMPTY_WORD));
+    assert_eq!(stack_outputs.get_stack_word(4), Some(smt.root().into()));
+}
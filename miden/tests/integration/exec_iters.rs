@@ -1,9 +1,87 @@
-use processor::{AsmOpInfo, ContextId, RowIndex, VmState};
+use processor::{AsmOpInfo, Breakpoint, ContextId, RowIndex, VmState};
 use test_utils::{Felt, ONE, ToElements, assert_eq, build_debug_test};
 use vm_core::{AssemblyOp, Operation, debuginfo::Location};
 
 // EXEC ITER TESTS
 // =================================================================
+
+#[test]
+fn test_exec_iter_breakpoints() {
+    let source = "begin push.0 repeat.5 push.1 add end drop end";
+    let test = build_debug_test!(source);
+
+    let mut iter = test.execute_iter();
+    let three = Breakpoint::StackItem { index: 0, value: Felt::new(3) };
+    let five = Breakpoint::StackItem { index: 0, value: Felt::new(5) };
+    assert_eq!(iter.add_breakpoint(three), 0);
+    assert_eq!(iter.add_breakpoint(five), 1);
+
+    // a stack breakpoint fires once when the top of the stack becomes its value, even though the
+    // value stays on top of the stack for several cycles
+    let (state, breakpoint) = iter.resume().unwrap().unwrap();
+    assert_eq!(breakpoint, Some(0));
+    assert_eq!(state.stack[0], Felt::new(3));
+    assert_eq!(state.asmop.unwrap().op(), "add");
+
+    let (state, breakpoint) = iter.resume().unwrap().unwrap();
+    assert_eq!(breakpoint, Some(1));
+    assert_eq!(state.stack[0], Felt::new(5));
+
+    // once no breakpoint fires, the iterator runs to the end of the execution
+    assert_eq!(iter.remove_breakpoint(1), Some(five));
+    assert_eq!(iter.remove_breakpoint(1), None);
+    let (state, breakpoint) = iter.resume().unwrap().unwrap();
+    assert_eq!(breakpoint, None);
+    assert_eq!(state.stack[0], Felt::new(0));
+    assert!(iter.resume().is_none());
+
+    let mut iter = test.execute_iter();
+    iter.add_breakpoint(Breakpoint::Cycle(RowIndex::from(4)));
+    let (state, breakpoint) = iter.resume().unwrap().unwrap();
+    assert_eq!(breakpoint, Some(0));
+    assert_eq!(state.clk, RowIndex::from(4));
+}
+
+#[test]
+fn test_exec_iter_memory_breakpoints() {
+    let source = "
+        begin
+            push.1 mem_store.2
+            push.5.6.7.8 mem_storew.8 dropw
+            push.3 mem_store.2
+            mem_load.9 drop
+        end";
+    let test = build_debug_test!(source);
+
+    let mut iter = test.execute_iter();
+    iter.add_breakpoint(Breakpoint::MemoryWrite { start: 2, end: 2 });
+
+    // an element write fires a breakpoint on its address
+    let (state, breakpoint) = iter.resume().unwrap().unwrap();
+    assert_eq!(breakpoint, Some(0));
+    assert_eq!(state.op, Some(Operation::MStore));
+    assert_eq!(iter.memory_writes(&state), vec![(2, ONE)]);
+
+    // the breakpoint fires again on the next write to the address, but not on writes to other
+    // addresses in between
+    let (state, breakpoint) = iter.resume().unwrap().unwrap();
+    assert_eq!(breakpoint, Some(0));
+    assert_eq!(iter.memory_writes(&state), vec![(2, Felt::new(3))]);
+
+    // a word write fires a breakpoint on any of its addresses, while reads do not
+    let mut iter = test.execute_iter();
+    iter.add_breakpoint(Breakpoint::MemoryWrite { start: 9, end: 100 });
+    let (state, breakpoint) = iter.resume().unwrap().unwrap();
+    assert_eq!(breakpoint, Some(0));
+    assert_eq!(state.op, Some(Operation::MStoreW));
+    assert_eq!(
+        iter.memory_writes(&state),
+        vec![(8, Felt::new(5)), (9, Felt::new(6)), (10, Felt::new(7)), (11, Felt::new(8))]
+    );
+    let (_, breakpoint) = iter.resume().unwrap().unwrap();
+    assert_eq!(breakpoint, None);
+}
+
 /// TODO: Reenable (and fix) after we stabilized the assembler
 /// Note: expect the memory values to be very wrong.
 #[test]
@@ -270,6 +270,61 @@ fn simple_syscall_2() {
     test.prove_and_verify(vec![2, 2, 3, 2, 1], false);
 }
 
+#[test]
+fn syscalls_into_multiple_kernels() {
+    use processor::{DefaultHost, ExecutionOptions, ExecutionTrace, Kernel, Process, ProgramInfo};
+    use vm_core::Program;
+
+    let source_manager = Arc::new(assembly::DefaultSourceManager::default());
+    let kernel_a = Assembler::new(source_manager.clone())
+        .assemble_kernel("export.foo add end")
+        .unwrap();
+    let kernel_b = Assembler::new(source_manager.clone())
+        .assemble_kernel("export.bar mul end")
+        .unwrap();
+    let [bar] = kernel_b.kernel().proc_hashes() else {
+        panic!("expected a single procedure in kernel B");
+    };
+
+    // the program is assembled against kernel A, and calls into kernel B via the procedure root
+    let program_source = format!("begin syscall.foo syscall.{bar} end");
+    let program = Assembler::with_kernel(source_manager, kernel_a.clone())
+        .assemble_program(program_source)
+        .unwrap();
+    let kernels = [kernel_a.kernel().clone(), kernel_b.kernel().clone()];
+
+    let mut host = DefaultHost::default();
+    host.load_mast_forest(kernel_a.mast_forest().clone()).unwrap();
+    host.load_mast_forest(kernel_b.mast_forest().clone()).unwrap();
+
+    // executing against kernel A only fails, since `bar` is not a valid syscall target
+    let stack_inputs = StackInputs::try_from_ints([2, 3, 4]).unwrap();
+    let mut process =
+        Process::new(program.kernel().clone(), stack_inputs.clone(), ExecutionOptions::default());
+    let error = process.execute(&program, &mut host).unwrap_err();
+    assert!(matches!(error, ExecutionError::SyscallTargetNotInKernel(target) if target == *bar));
+
+    let mut process =
+        Process::new_with_kernels(&kernels, stack_inputs.clone(), ExecutionOptions::default())
+            .unwrap();
+    let stack_outputs = process.execute(&program, &mut host).unwrap();
+    // (4 + 3) * 2
+    assert_eq!(stack_outputs.get_stack_item(0), Some(14u32.into()));
+    assert_eq!(process.kernel(), &Kernel::merge(&kernels).unwrap());
+    let trace = ExecutionTrace::new(process, stack_outputs);
+    assert_eq!(trace.program_info().kernel(), &Kernel::merge(&kernels).unwrap());
+
+    // proofs of programs running with the merged kernel are verified against the merged kernel
+    let program = Program::with_kernel(
+        program.mast_forest().clone(),
+        program.entrypoint(),
+        Kernel::merge(&kernels).unwrap(),
+    );
+    let (stack_outputs, proof) =
+        prover::prove(&program, stack_inputs.clone(), &mut host, Default::default()).unwrap();
+    verifier::verify(ProgramInfo::from(program), stack_inputs, stack_outputs, proof).unwrap();
+}
+
 // DYNAMIC CODE EXECUTION
 // ================================================================================================
 
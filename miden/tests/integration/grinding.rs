@@ -0,0 +1,148 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+};
+#[cfg(feature = "concurrent")]
+use std::{sync::mpsc, thread, time::Duration};
+
+use miden_vm::{
+    Assembler, DefaultHost, ExecutionProof, GrindingChallenge, HashFunction, NonceGrinder,
+    ProgramInfo, ProvingOptions, StackInputs, prove, prove_with_grinder,
+    utils::{Deserializable, Serializable},
+};
+
+// GRINDING TESTS
+// ================================================================================================
+
+const SOURCE: &str = "begin repeat.8 push.3 push.5 u32xor drop hperm end end";
+
+/// Proves the test program with the specified grinder (or without a grinder), verifies the proof,
+/// and returns it.
+fn prove_program(hash_fn: HashFunction, grinder: Option<Arc<dyn NonceGrinder>>) -> ExecutionProof {
+    let program = Assembler::default().assemble_program(SOURCE).unwrap();
+    let options = match hash_fn {
+        HashFunction::Blake3_192 => ProvingOptions::with_96_bit_security(false),
+        HashFunction::Rpo256 => ProvingOptions::with_96_bit_security(true),
+        HashFunction::Rpx256 => ProvingOptions::with_96_bit_security_rpx(),
+        HashFunction::Blake3_256 => ProvingOptions::with_128_bit_security(false),
+        HashFunction::Keccak256 => {
+            ProvingOptions::with_96_bit_security(false).with_hash_fn(HashFunction::Keccak256)
+        },
+    };
+    let stack_inputs = StackInputs::default();
+    let mut host = DefaultHost::default();
+
+    let (stack_outputs, proof) = match grinder {
+        Some(grinder) => {
+            prove_with_grinder(&program, stack_inputs.clone(), &mut host, options, grinder)
+        },
+        None => prove(&program, stack_inputs.clone(), &mut host, options),
+    }
+    .unwrap();
+
+    verifier::verify(ProgramInfo::from(program), stack_inputs, stack_outputs, proof.clone())
+        .expect("proof must be valid");
+    proof
+}
+
+#[test]
+fn offloaded_grinding() {
+    for hash_fn in [
+        HashFunction::Blake3_192,
+        HashFunction::Rpo256,
+        HashFunction::Rpx256,
+        HashFunction::Keccak256,
+    ] {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let grinder_calls = calls.clone();
+
+        // the grinder reconstructs the challenge from its serialized form, as a remote service
+        // would, and returns the smallest valid nonce
+        let grinder = move |challenge: &GrindingChallenge| {
+            grinder_calls.fetch_add(1, Ordering::Relaxed);
+            let challenge = GrindingChallenge::read_from_bytes(&challenge.to_bytes()).unwrap();
+            assert_eq!(challenge.hash_fn(), hash_fn);
+            let nonce = challenge.find_nonce(1..u64::MAX)?;
+            assert!(challenge.is_valid_nonce(nonce));
+            assert!(!challenge.is_valid_nonce(0));
+            Some(nonce)
+        };
+
+        let proof = prove_program(hash_fn, Some(Arc::new(grinder)));
+        assert_eq!(calls.load(Ordering::Relaxed), 1);
+        assert!(
+            proof.to_bytes() == prove_program(hash_fn, None).to_bytes(),
+            "proofs generated with {hash_fn:?} differ"
+        );
+    }
+}
+
+#[test]
+fn invalid_grinder_nonce() {
+    // the prover searches for the nonce itself when the grinder fails or returns an invalid nonce
+    let expected = prove_program(HashFunction::Rpo256, None).to_bytes();
+    let grinders: [Arc<dyn NonceGrinder>; 3] = [
+        Arc::new(|_: &GrindingChallenge| None),
+        Arc::new(|_: &GrindingChallenge| Some(0)),
+        Arc::new(|challenge: &GrindingChallenge| {
+            (1..u64::MAX).find(|&nonce| !challenge.is_valid_nonce(nonce))
+        }),
+    ];
+    for grinder in grinders {
+        assert!(prove_program(HashFunction::Rpo256, Some(grinder)).to_bytes() == expected);
+    }
+}
+
+#[test]
+#[cfg(feature = "concurrent")]
+fn concurrent_grinding() {
+    use miden_vm::FieldExtension;
+
+    const GRINDING_FACTOR: u32 = 18;
+
+    // the grinder returns a valid nonce other than the smallest one, so that the prover must
+    // accept a nonce which the threads searching in parallel are not the first to reach
+    let grinder = |challenge: &GrindingChallenge| {
+        let smallest = challenge.find_nonce(1..u64::MAX)?;
+        challenge.find_nonce(smallest + 1..u64::MAX)
+    };
+    let expected_nonce = Arc::new(AtomicUsize::new(0));
+    let grinder_nonce = expected_nonce.clone();
+    let grinder = move |challenge: &GrindingChallenge| {
+        let nonce = grinder(challenge)?;
+        grinder_nonce.store(nonce as usize, Ordering::Relaxed);
+        Some(nonce)
+    };
+
+    // proving runs on a separate thread, so that a deadlock of the nonce search fails the test
+    // rather than hanging it; the options are not accepted by the verifier, so the proof is
+    // checked by the prover instead
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let program = Assembler::default().assemble_program(SOURCE).unwrap();
+        let options = ProvingOptions::new(
+            27,
+            8,
+            GRINDING_FACTOR,
+            FieldExtension::Quadratic,
+            4,
+            127,
+            HashFunction::Rpo256,
+        )
+        .with_self_check();
+        let mut host = DefaultHost::default();
+        let (_, proof) = prove_with_grinder(
+            &program,
+            StackInputs::default(),
+            &mut host,
+            options,
+            Arc::new(grinder),
+        )
+        .unwrap();
+        sender.send(proof).unwrap();
+    });
+
+    let proof = receiver.recv_timeout(Duration::from_secs(300)).expect("proving timed out");
+    let (_, proof) = proof.into_parts();
+    assert_eq!(proof.pow_nonce, expected_nonce.load(Ordering::Relaxed) as u64);
+}
@@ -3,11 +3,23 @@ extern crate alloc;
 use test_utils::{build_op_test, build_test};
 
 mod air;
+mod allowlist;
+mod batch;
 mod cli;
+mod delegation;
+mod determinism;
+mod differential;
 mod exec;
 mod exec_iters;
 mod flow_control;
+mod grinding;
 mod operations;
+mod pipeline;
+mod progress;
+mod randomizer;
+mod security;
+mod self_check;
+mod verification;
 
 // TESTS
 // ================================================================================================
@@ -1,6 +1,10 @@
 use assembly::Assembler;
-use processor::{ExecutionOptions, Program};
+use processor::{
+    AdviceProvider, AdviceSource, DefaultHost, ExecutionError, ExecutionOptions, ForeignCallError,
+    MemAdviceProvider, ProcessState, Program,
+};
 use prover::StackInputs;
+use vm_core::{Felt, Word, sys_events::EVENT_U64_DIV};
 
 use super::TestHost;
 
@@ -26,6 +30,147 @@ fn test_event_handling() {
     assert_eq!(host.event_handler, expected);
 }
 
+#[test]
+fn test_registered_event_handlers() {
+    const EVENT_DOUBLE: u32 = 7;
+    const EVENT_UNHANDLED: u32 = 8;
+
+    let source = "\
+    begin
+        push.21
+        emit.7
+        emit.8
+        drop adv_push.1 swap drop
+    end";
+
+    // the handler pushes twice the value on top of the stack onto the advice stack
+    let program: Program = Assembler::default().assemble_program(source).unwrap();
+    let mut host = DefaultHost::default();
+    host.register_event_handler(
+        EVENT_DOUBLE,
+        |process: ProcessState, advice: &mut MemAdviceProvider| {
+            let value = process.get_stack_item(0);
+            advice.push_stack(AdviceSource::Value(value + value))
+        },
+    )
+    .unwrap();
+
+    let trace = processor::execute(
+        &program,
+        StackInputs::default(),
+        &mut host,
+        ExecutionOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(trace.stack_outputs().get_stack_item(0), Some(Felt::new(42)));
+
+    // once the handler is removed, the advice stack is empty when the program reads from it
+    assert!(host.unregister_event_handler(EVENT_DOUBLE));
+    assert!(!host.unregister_event_handler(EVENT_UNHANDLED));
+    let result = processor::execute(
+        &program,
+        StackInputs::default(),
+        &mut host,
+        ExecutionOptions::default(),
+    );
+    assert!(matches!(result, Err(ExecutionError::AdviceStackReadFailed(_))));
+}
+
+#[test]
+fn test_event_handler_registration_errors() {
+    let handler = |_: ProcessState, _: &mut MemAdviceProvider| Ok(());
+    let mut host = DefaultHost::default();
+
+    let result = host.register_event_handler(EVENT_U64_DIV, handler);
+    assert!(matches!(result, Err(ExecutionError::ReservedEventId(EVENT_U64_DIV))));
+
+    host.register_event_handler(1, handler).unwrap();
+    let result = host.register_event_handler(1, handler);
+    assert!(matches!(result, Err(ExecutionError::EventHandlerAlreadyRegistered(1))));
+}
+
+#[test]
+fn test_foreign_calls() {
+    let mut host = DefaultHost::default();
+    let shift_id = host
+        .register_foreign_function("shift", |(shift, value): (u32, u64)| {
+            value
+                .checked_shl(shift)
+                .filter(|_| shift <= value.leading_zeros())
+                .ok_or_else(|| ForeignCallError::failed("shift overflow"))
+        })
+        .unwrap();
+    let is_zero_id = host
+        .register_foreign_function("is_zero", |value: Felt| Ok(value == Felt::new(0)))
+        .unwrap();
+
+    // the function name determines the event ID, so it cannot be registered twice
+    let result = host.register_foreign_function("shift", |value: u32| Ok(value));
+    assert!(
+        matches!(result, Err(ExecutionError::EventHandlerAlreadyRegistered(id)) if id == shift_id)
+    );
+
+    // the arguments must fit at the top of the stack
+    let result = host
+        .register_foreign_function("too_many_args", |_: (Word, Word, Word, (Word, Felt))| Ok(()));
+    assert!(matches!(
+        result,
+        Err(ExecutionError::ForeignCallTooManyArguments { num_elements: 17, .. })
+    ));
+
+    // shift(4, 2^32 + 3) = 2^36 + 48, is_zero(16) = false
+    let source = format!(
+        "
+    begin
+        push.3.1.4
+        emit.{shift_id} adv_push.2
+        movup.2 drop movup.2 drop movup.2 drop
+        dup emit.{is_zero_id} adv_push.1 swap drop
+        movup.3 drop movup.3 drop movup.3 drop
+    end"
+    );
+    let program = Assembler::default().assemble_program(source).unwrap();
+    let stack_inputs = StackInputs::default();
+    let trace =
+        processor::execute(&program, stack_inputs, &mut host, ExecutionOptions::default()).unwrap();
+    let outputs = trace.stack_outputs();
+    assert_eq!(outputs.get_stack_item(0), Some(Felt::new(0)));
+    assert_eq!(outputs.get_stack_item(1), Some(Felt::new(16)));
+    assert_eq!(outputs.get_stack_item(2), Some(Felt::new(48)));
+
+    // arguments which are not valid encodings of the argument types are rejected
+    let source =
+        format!("begin push.3.1.{} emit.{shift_id} adv_push.2 end", u64::from(u32::MAX) + 1);
+    let program = Assembler::default().assemble_program(source).unwrap();
+    let result = processor::execute(
+        &program,
+        StackInputs::default(),
+        &mut host,
+        ExecutionOptions::default(),
+    );
+    assert!(matches!(
+        result,
+        Err(ExecutionError::ForeignCallFailed {
+            source: ForeignCallError::InvalidArgument { expected: "u32", .. },
+            ..
+        })
+    ));
+
+    // errors returned by the function fail the execution
+    let source = format!("begin push.3.1.40 emit.{shift_id} adv_push.2 end");
+    let program = Assembler::default().assemble_program(source).unwrap();
+    let result = processor::execute(
+        &program,
+        StackInputs::default(),
+        &mut host,
+        ExecutionOptions::default(),
+    );
+    assert!(matches!(
+        result,
+        Err(ExecutionError::ForeignCallFailed { source: ForeignCallError::Failed(_), .. })
+    ));
+}
+
 #[test]
 fn test_trace_handling() {
     let source = "\
@@ -252,7 +252,7 @@ fn neg_fail() {
         "   :          |",
         "   :          `-- found a . here",
         "   `----",
-        r#" help: expected primitive opcode (e.g. "add"), or "end", or control flow opcode (e.g. "if.true")"#
+        r#" help: expected "@", or primitive opcode (e.g. "add"), or "end", or control flow opcode (e.g. "if.true"), or macro invocation"#
     );
 }
 
@@ -295,7 +295,7 @@ fn inv_fail() {
         "   :          |",
         "   :          `-- found a . here",
         "   `----",
-        r#" help: expected primitive opcode (e.g. "add"), or "end", or control flow opcode (e.g. "if.true")"#
+        r#" help: expected "@", or primitive opcode (e.g. "add"), or "end", or control flow opcode (e.g. "if.true"), or macro invocation"#
     );
 }
 
@@ -372,7 +372,7 @@ fn exp_bits_length_fail() {
         "12 | begin exp.u65 exec.truncate_stack end",
         "   :            ^^",
         "   `----",
-        r#" help: expected primitive opcode (e.g. "add"), or "end", or control flow opcode (e.g. "if.true")"#
+        r#" help: expected "@", or primitive opcode (e.g. "add"), or "end", or control flow opcode (e.g. "if.true"), or macro invocation"#
     );
 }
 
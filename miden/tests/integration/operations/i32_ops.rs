@@ -0,0 +1,144 @@
+use processor::ExecutionError;
+use test_utils::{build_op_test, expect_exec_error_matches, proptest::prelude::*};
+
+// I32 OPERATIONS TESTS - MANUAL
+// ================================================================================================
+
+#[test]
+fn i32neg() {
+    let asm_op = "i32neg";
+
+    for a in [0, 1, -1, 7, i32::MAX, i32::MIN] {
+        let test = build_op_test!(asm_op, &[to_u64(a)]);
+        test.expect_stack(&[to_u64(a.wrapping_neg())]);
+    }
+}
+
+#[test]
+fn i32abs() {
+    let asm_op = "i32abs";
+
+    // the absolute value of i32::MIN wraps around to i32::MIN
+    for a in [0, 1, -1, -7, i32::MAX, i32::MIN] {
+        let test = build_op_test!(asm_op, &[to_u64(a)]);
+        test.expect_stack(&[to_u64(a.wrapping_abs())]);
+    }
+}
+
+#[test]
+fn i32div() {
+    let asm_op = "i32div";
+
+    // the quotient is rounded towards zero, and i32::MIN / -1 wraps around to i32::MIN
+    for (a, b) in [(7, 2), (-7, 2), (7, -2), (-7, -2), (0, -3), (i32::MIN, -1), (i32::MIN, 1)] {
+        let test = build_op_test!(asm_op, &[to_u64(a), to_u64(b)]);
+        test.expect_stack(&[to_u64(a.wrapping_div(b))]);
+    }
+}
+
+#[test]
+fn i32mod() {
+    let asm_op = "i32mod";
+
+    // the remainder has the sign of the dividend
+    for (a, b) in [(7, 2), (-7, 2), (7, -2), (-7, -2), (0, -3), (i32::MIN, -1), (i32::MIN, 3)] {
+        let test = build_op_test!(asm_op, &[to_u64(a), to_u64(b)]);
+        test.expect_stack(&[to_u64(a.wrapping_rem(b))]);
+    }
+}
+
+#[test]
+fn i32divmod() {
+    let asm_op = "i32divmod";
+
+    for (a, b) in [(7, 2), (-7, 2), (7, -2), (-7, -2), (0, -3), (i32::MIN, -1), (i32::MIN, 3)] {
+        let test = build_op_test!(asm_op, &[to_u64(a), to_u64(b)]);
+        test.expect_stack(&[to_u64(a.wrapping_rem(b)), to_u64(a.wrapping_div(b))]);
+    }
+}
+
+#[test]
+fn i32div_fail() {
+    // should fail if b == 0
+    for asm_op in ["i32div", "i32mod", "i32divmod"] {
+        let test = build_op_test!(asm_op, &[to_u64(-5), 0]);
+        expect_exec_error_matches!(test, ExecutionError::DivideByZero(_));
+    }
+}
+
+#[test]
+fn i32shr() {
+    let asm_op = "i32shr";
+
+    // negative values are shifted in their sign bit
+    for (a, b) in [(12, 2), (-12, 2), (-1, 31), (i32::MIN, 31), (i32::MAX, 30), (-5, 0)] {
+        let test = build_op_test!(asm_op, &[to_u64(a), b as u64]);
+        test.expect_stack(&[to_u64(a >> b)]);
+    }
+}
+
+#[test]
+fn i32_comparisons() {
+    let values = [i32::MIN, -2, -1, 0, 1, 2, i32::MAX];
+
+    for a in values {
+        for b in values {
+            let inputs = [to_u64(a), to_u64(b)];
+            build_op_test!("i32lt", &inputs).expect_stack(&[(a < b) as u64]);
+            build_op_test!("i32lte", &inputs).expect_stack(&[(a <= b) as u64]);
+            build_op_test!("i32gt", &inputs).expect_stack(&[(a > b) as u64]);
+            build_op_test!("i32gte", &inputs).expect_stack(&[(a >= b) as u64]);
+            build_op_test!("i32min", &inputs).expect_stack(&[to_u64(a.min(b))]);
+            build_op_test!("i32max", &inputs).expect_stack(&[to_u64(a.max(b))]);
+        }
+    }
+}
+
+// I32 OPERATIONS TESTS - RANDOMIZED
+// ================================================================================================
+
+proptest! {
+    #[test]
+    fn i32abs_proptest(a in any::<i32>()) {
+        let test = build_op_test!("i32abs", &[to_u64(a)]);
+        test.prop_expect_stack(&[to_u64(a.wrapping_abs())])?;
+    }
+
+    #[test]
+    fn i32divmod_proptest(a in any::<i32>(), b in any::<i32>().prop_filter("b != 0", |b| *b != 0)) {
+        let test = build_op_test!("i32divmod", &[to_u64(a), to_u64(b)]);
+        test.prop_expect_stack(&[to_u64(a.wrapping_rem(b)), to_u64(a.wrapping_div(b))])?;
+    }
+
+    #[test]
+    fn i32shr_proptest(a in any::<i32>(), b in 0_u32..32) {
+        let test = build_op_test!("i32shr", &[to_u64(a), b as u64]);
+        test.prop_expect_stack(&[to_u64(a >> b)])?;
+    }
+
+    #[test]
+    fn i32lt_proptest(a in any::<i32>(), b in any::<i32>()) {
+        let test = build_op_test!("i32lt", &[to_u64(a), to_u64(b)]);
+        test.prop_expect_stack(&[(a < b) as u64])?;
+    }
+
+    #[test]
+    fn i32min_proptest(a in any::<i32>(), b in any::<i32>()) {
+        let test = build_op_test!("i32min", &[to_u64(a), to_u64(b)]);
+        test.prop_expect_stack(&[to_u64(a.min(b))])?;
+    }
+
+    #[test]
+    fn i32max_proptest(a in any::<i32>(), b in any::<i32>()) {
+        let test = build_op_test!("i32max", &[to_u64(a), to_u64(b)]);
+        test.prop_expect_stack(&[to_u64(a.max(b))])?;
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the two's complement representation of `a` as a u32 value.
+fn to_u64(a: i32) -> u64 {
+    a as u32 as u64
+}
@@ -223,3 +223,42 @@ fn clk() {
     let test = build_test!(source, &[]);
     test.expect_stack(&[3, 4, 5]);
 }
+
+// CTX INSTRUCTION
+// ================================================================================================
+
+#[test]
+fn ctx() {
+    let test = build_op_test!("ctx");
+    test.expect_stack(&[0]);
+
+    let kernel_source = "
+        export.foo
+            ctx movup.15 drop
+        end
+    ";
+
+    let program_source = "
+        proc.bar
+            # a procedure invoked via call is executed in a new context
+            ctx neq.0 movup.15 drop
+
+            # kernel procedures are executed in the root context
+            syscall.foo
+        end
+
+        begin
+            ctx
+            call.bar
+            movup.3 drop
+        end";
+
+    let mut test = Test::new(&format!("test{}", line!()), program_source, false);
+    test.kernel_source = Some(
+        test.source_manager
+            .load(&format!("kernel{}", line!()), kernel_source.to_string()),
+    );
+    test.expect_stack(&[0, 1, 0]);
+
+    test.prove_and_verify(vec![], false);
+}
@@ -86,6 +86,36 @@ fn mem_storew() {
     test.expect_stack_and_memory(&[4, 3, 2, 1, 0], addr, &[1, 2, 3, 4]);
 }
 
+// INITIALIZING MEMORY FROM DATA CONSTANTS (MEM_INIT)
+// ================================================================================================
+
+#[test]
+fn mem_init() {
+    // --- elements which span a partial word -----------------------------------------------------
+    let source = "
+        const.DATA=[1, 2, 3, 4, 5, 3+3]
+        begin
+            push.8
+            mem_init.DATA
+        end";
+
+    let test = build_test!(source, &[7]);
+    test.expect_stack_and_memory(&[7], 8, &[1, 2, 3, 4, 5, 6, 0, 0]);
+
+    // --- strings are packed four bytes per element ----------------------------------------------
+    let source = "
+        const.NAME=\"miden\"
+        begin
+            push.4
+            mem_init.NAME
+            push.NAME
+            movup.2 drop movup.2 drop
+        end";
+
+    let test = build_test!(source, &[]);
+    test.expect_stack_and_memory(&[110, 1701079405], 4, &[1701079405, 110, 0, 0]);
+}
+
 // STREAMING ELEMENTS FROM MEMORY (MSTREAM)
 // ================================================================================================
 
@@ -3,7 +3,9 @@ mod decorators;
 mod ext2_ops;
 mod field_ops;
 mod fri_ops;
+mod i32_ops;
 mod io_ops;
 mod stack_ops;
 mod sys_ops;
 mod u32_ops;
+mod vm_ops;
@@ -536,6 +536,77 @@ fn u32divmod_fail() {
     );
 }
 
+#[test]
+fn u64overflowing_add() {
+    let asm_op = "u64overflowing_add";
+
+    // --- no carry between the limbs -------------------------------------------------------------
+    let test = build_op_test!(asm_op, &[1, 2, 3, 4]);
+    test.expect_stack(&[0, 6, 4]);
+
+    // --- carry from the lower limbs -------------------------------------------------------------
+    let test = build_op_test!(asm_op, &[u32::MAX as u64, 0, 1, 0]);
+    test.expect_stack(&[0, 1, 0]);
+
+    // --- (a + b) = 2^64 -------------------------------------------------------------------------
+    let test = build_op_test!(asm_op, &[u32::MAX as u64, u32::MAX as u64, 1, 0]);
+    test.expect_stack(&[1, 0, 0]);
+
+    // --- random u64 values ----------------------------------------------------------------------
+    let a = rand_value::<u64>();
+    let b = rand_value::<u64>();
+    let (c, overflow) = a.overflowing_add(b);
+    let stack = u64_stack_inputs(a, b);
+    let test = build_op_test!(asm_op, &stack);
+    test.expect_stack(&[overflow as u64, c >> 32, c as u32 as u64]);
+    test.prove_and_verify(stack.to_vec(), false);
+
+    // --- test that the rest of the stack isn't affected -----------------------------------------
+    let e = rand_value::<u64>();
+    let test = build_op_test!(asm_op, &[&[e], stack.as_slice()].concat());
+    test.expect_stack(&[overflow as u64, c >> 32, c as u32 as u64, e]);
+}
+
+#[test]
+fn u64overflowing_sub() {
+    let asm_op = "u64overflowing_sub";
+
+    // --- no borrow between the limbs ------------------------------------------------------------
+    let test = build_op_test!(asm_op, &[4, 3, 2, 1]);
+    test.expect_stack(&[0, 2, 2]);
+
+    // --- borrow from the higher limbs -----------------------------------------------------------
+    let test = build_op_test!(asm_op, &[0, 1, 1, 0]);
+    test.expect_stack(&[0, 0, u32::MAX as u64]);
+
+    // --- a < b ----------------------------------------------------------------------------------
+    let test = build_op_test!(asm_op, &[1, 0, 2, 0]);
+    test.expect_stack(&[1, u32::MAX as u64, u32::MAX as u64]);
+
+    // --- random u64 values ----------------------------------------------------------------------
+    let a = rand_value::<u64>();
+    let b = rand_value::<u64>();
+    let (c, underflow) = a.overflowing_sub(b);
+    let stack = u64_stack_inputs(a, b);
+    let test = build_op_test!(asm_op, &stack);
+    test.expect_stack(&[underflow as u64, c >> 32, c as u32 as u64]);
+    test.prove_and_verify(stack.to_vec(), false);
+
+    // --- test that the rest of the stack isn't affected -----------------------------------------
+    let e = rand_value::<u64>();
+    let test = build_op_test!(asm_op, &[&[e], stack.as_slice()].concat());
+    test.expect_stack(&[underflow as u64, c >> 32, c as u32 as u64, e]);
+}
+
+#[test]
+fn u64overflowing_add_fail() {
+    let asm_op = "u64overflowing_add";
+
+    // should fail if any of the limbs is not a u32 value.
+    let test = build_op_test!(asm_op, &[1, 2, 3, U32_BOUND]);
+    expect_exec_error_matches!(test, ExecutionError::NotU32Value(..));
+}
+
 // U32 OPERATIONS TESTS - RANDOMIZED - ARITHMETIC OPERATIONS
 // ================================================================================================
 proptest! {
@@ -660,3 +731,12 @@ proptest! {
         test.prop_expect_stack(&[e, d])?;
     }
 }
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the stack inputs [a_lo, a_hi, b_lo, b_hi], which place the limbs of `b` at the top of
+/// the stack, followed by the limbs of `a`.
+fn u64_stack_inputs(a: u64, b: u64) -> [u64; 4] {
+    [a as u32 as u64, a >> 32, b as u32 as u64, b >> 32]
+}
@@ -0,0 +1,278 @@
+use processor::ExecutionError;
+use test_utils::{
+    Felt, FieldElement, Operation, StarkField, U32_BOUND, build_expected_perm,
+    build_operation_test, expect_exec_error_matches, felt_slice_to_ints,
+};
+
+// OPERATION-LEVEL TESTS
+// ================================================================================================
+//
+// These tests execute VM operations directly, without going through the assembler, so that the
+// semantics of each operation are tested independently of the instructions it is used to
+// implement.
+
+// SYSTEM OPERATIONS
+// ------------------------------------------------------------------------------------------------
+
+#[test]
+fn noop() {
+    build_operation_test!([Noop], &[1, 2]).expect_stack(&[2, 1]);
+}
+
+#[test]
+fn assert() {
+    build_operation_test!([Assert(0)], &[2, 1]).expect_stack(&[2]);
+
+    let test = build_operation_test!([Assert(7)], &[2]);
+    expect_exec_error_matches!(test, ExecutionError::FailedAssertion { err_code: 7, .. });
+}
+
+#[test]
+fn fmp() {
+    const FMP_MIN: u64 = 2_u64.pow(30);
+
+    build_operation_test!([FmpAdd], &[5]).expect_stack(&[FMP_MIN + 5]);
+    build_operation_test!([FmpUpdate, Push(Felt::new(5)), FmpAdd], &[3])
+        .expect_stack(&[FMP_MIN + 8]);
+
+    let test = build_operation_test!([FmpUpdate], &[Felt::MODULUS - 1]);
+    expect_exec_error_matches!(test, ExecutionError::InvalidFmpValue(..));
+}
+
+#[test]
+fn sdepth_and_clk() {
+    build_operation_test!([SDepth], &[1, 2]).expect_stack(&[16, 2, 1]);
+
+    // the first operation of the basic block is executed at clock cycle 1, after the SPAN
+    build_operation_test!([Noop, Noop, Clk]).expect_stack(&[3]);
+}
+
+#[test]
+fn caller() {
+    let test = build_operation_test!([Caller, Drop, Drop, Drop, Drop]);
+    expect_exec_error_matches!(test, ExecutionError::CallerNotInSyscall);
+}
+
+// FIELD OPERATIONS
+// ------------------------------------------------------------------------------------------------
+
+#[test]
+fn field_arithmetic() {
+    let a = Felt::MODULUS - 3;
+
+    build_operation_test!([Add], &[a, 5, 1]).expect_stack(&[6, a]);
+    build_operation_test!([Add], &[a, 5]).expect_stack(&[2]);
+    build_operation_test!([Neg], &[3]).expect_stack(&[a]);
+    build_operation_test!([Mul], &[a, 2]).expect_stack(&[Felt::MODULUS - 6]);
+    build_operation_test!([Incr], &[a]).expect_stack(&[a + 1]);
+
+    let inv_two = Felt::new(2).inv().as_int();
+    build_operation_test!([Inv], &[2]).expect_stack(&[inv_two]);
+
+    let test = build_operation_test!([Inv], &[0]);
+    expect_exec_error_matches!(test, ExecutionError::DivideByZero(_));
+}
+
+#[test]
+fn field_boolean() {
+    build_operation_test!([And], &[1, 1]).expect_stack(&[1]);
+    build_operation_test!([And], &[0, 1]).expect_stack(&[0]);
+    build_operation_test!([Or], &[0, 1]).expect_stack(&[1]);
+    build_operation_test!([Or], &[0, 0]).expect_stack(&[0]);
+    build_operation_test!([Not], &[0]).expect_stack(&[1]);
+    build_operation_test!([Not], &[1]).expect_stack(&[0]);
+
+    for op in [Operation::Or, Operation::And] {
+        let test = build_operation_test!([op], &[2, 1]);
+        expect_exec_error_matches!(test, ExecutionError::NotBinaryValue(value) if value == Felt::new(2));
+    }
+    let test = build_operation_test!([Not], &[2]);
+    expect_exec_error_matches!(test, ExecutionError::NotBinaryValue(_));
+}
+
+#[test]
+fn field_comparison() {
+    build_operation_test!([Eq], &[7, 7]).expect_stack(&[1]);
+    build_operation_test!([Eq], &[7, 8]).expect_stack(&[0]);
+    build_operation_test!([Eqz], &[0]).expect_stack(&[1]);
+    build_operation_test!([Eqz], &[8]).expect_stack(&[0]);
+}
+
+// U32 OPERATIONS
+// ------------------------------------------------------------------------------------------------
+
+#[test]
+fn u32split() {
+    build_operation_test!([U32split], &[3 * U32_BOUND + 5]).expect_stack(&[3, 5]);
+}
+
+#[test]
+fn u32_arithmetic() {
+    let max = u32::MAX as u64;
+
+    // results are pushed as (hi, lo), with the high 32 bits on top of the stack
+    build_operation_test!([U32add], &[max, 2]).expect_stack(&[1, 1]);
+    build_operation_test!([U32add3], &[max, max, 2]).expect_stack(&[2, 0]);
+    build_operation_test!([U32mul], &[max, 2]).expect_stack(&[1, max - 1]);
+    build_operation_test!([U32madd], &[3, max, 2]).expect_stack(&[2, 1]);
+
+    // subtraction pushes the borrow on top of the difference, and division the remainder on top
+    // of the quotient
+    build_operation_test!([U32sub], &[2, 5]).expect_stack(&[1, max - 2]);
+    build_operation_test!([U32div], &[17, 5]).expect_stack(&[2, 3]);
+
+    let test = build_operation_test!([U32div], &[17, 0]);
+    expect_exec_error_matches!(test, ExecutionError::DivideByZero(_));
+}
+
+#[test]
+fn u32_bitwise() {
+    build_operation_test!([U32and], &[0b1100, 0b1010]).expect_stack(&[0b1000]);
+    build_operation_test!([U32xor], &[0b1100, 0b1010]).expect_stack(&[0b0110]);
+}
+
+#[test]
+fn u32_operand_checks() {
+    build_operation_test!([U32assert2(0)], &[1, 2]).expect_stack(&[2, 1]);
+
+    let test = build_operation_test!([U32assert2(9)], &[U32_BOUND, 2]);
+    expect_exec_error_matches!(test, ExecutionError::NotU32Value(value, err_code)
+        if value == Felt::new(U32_BOUND) && err_code == Felt::new(9));
+
+    for op in [
+        Operation::U32add,
+        Operation::U32sub,
+        Operation::U32mul,
+        Operation::U32div,
+        Operation::U32and,
+        Operation::U32xor,
+    ] {
+        let test = build_operation_test!([op], &[U32_BOUND, 2]);
+        expect_exec_error_matches!(test, ExecutionError::NotU32Value(..));
+    }
+}
+
+// STACK OPERATIONS
+// ------------------------------------------------------------------------------------------------
+
+#[test]
+fn stack_growth() {
+    build_operation_test!([Pad, Drop], &[1]).expect_stack(&[1]);
+
+    // operations which grow the stack push items into the overflow table, which are compared too
+    let stack_inputs = [16, 15, 14, 13, 12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1];
+    build_operation_test!([Pad], &stack_inputs)
+        .expect_stack(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
+    build_operation_test!([Dup2], &stack_inputs)
+        .expect_stack(&[3, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
+    build_operation_test!([Dup15], &stack_inputs)
+        .expect_stack(&[16, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
+}
+
+#[test]
+fn stack_permutations() {
+    build_operation_test!([Swap], &[1, 2]).expect_stack(&[1, 2]);
+    build_operation_test!([SwapW], &[1, 2, 3, 4, 5, 6, 7, 8])
+        .expect_stack(&[4, 3, 2, 1, 8, 7, 6, 5]);
+    build_operation_test!([SwapDW], &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16])
+        .expect_stack(&[8, 7, 6, 5, 4, 3, 2, 1, 16, 15, 14, 13, 12, 11, 10, 9]);
+    build_operation_test!([MovUp3], &[1, 2, 3, 4]).expect_stack(&[1, 4, 3, 2]);
+    build_operation_test!([MovDn3], &[1, 2, 3, 4]).expect_stack(&[3, 2, 1, 4]);
+}
+
+#[test]
+fn conditional_swaps() {
+    build_operation_test!([CSwap], &[1, 2, 1]).expect_stack(&[1, 2]);
+    build_operation_test!([CSwap], &[1, 2, 0]).expect_stack(&[2, 1]);
+    build_operation_test!([CSwapW], &[1, 2, 3, 4, 5, 6, 7, 8, 1])
+        .expect_stack(&[4, 3, 2, 1, 8, 7, 6, 5]);
+
+    let test = build_operation_test!([CSwap], &[1, 2, 2]);
+    expect_exec_error_matches!(test, ExecutionError::NotBinaryValue(_));
+}
+
+// INPUT / OUTPUT OPERATIONS
+// ------------------------------------------------------------------------------------------------
+
+#[test]
+fn advice_pops() {
+    build_operation_test!([Push(Felt::new(5))]).expect_stack(&[5]);
+    build_operation_test!([AdvPop], &[], &[9]).expect_stack(&[9]);
+    build_operation_test!([AdvPopW], &[0, 0, 0, 0], &[1, 2, 3, 4]).expect_stack(&[4, 3, 2, 1]);
+
+    let test = build_operation_test!([AdvPop]);
+    expect_exec_error_matches!(test, ExecutionError::AdviceStackReadFailed(_));
+}
+
+#[test]
+fn memory_element_access() {
+    build_operation_test!([MStore], &[7, 100]).expect_stack_and_memory(&[7], 100, &[7]);
+    build_operation_test!([MStore, Drop, Push(Felt::new(100)), MLoad], &[7, 100])
+        .expect_stack(&[7]);
+
+    let test = build_operation_test!([MLoad], &[U32_BOUND]);
+    expect_exec_error_matches!(test, ExecutionError::MemoryAddressOutOfBounds(addr) if addr == U32_BOUND);
+}
+
+#[test]
+fn memory_word_access() {
+    build_operation_test!([MStoreW], &[1, 2, 3, 4, 100]).expect_stack_and_memory(
+        &[4, 3, 2, 1],
+        100,
+        &[1, 2, 3, 4],
+    );
+    build_operation_test!([MStoreW, Push(Felt::new(100)), MLoadW], &[0, 0, 0, 0, 5, 6, 7, 8, 100])
+        .expect_stack(&[8, 7, 6, 5, 0, 0, 0, 0]);
+
+    let test = build_operation_test!([MStoreW], &[1, 2, 3, 4, 101]);
+    expect_exec_error_matches!(test, ExecutionError::MemoryUnalignedWordAccess { addr: 101, .. });
+}
+
+#[test]
+fn pipe() {
+    // the destination address is at stack position 12, and is incremented by two words
+    let stack_inputs = [100, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+    build_operation_test!([Pipe], &stack_inputs, &[1, 2, 3, 4, 5, 6, 7, 8])
+        .expect_stack_and_memory(
+            &[8, 7, 6, 5, 4, 3, 2, 1, 0, 0, 0, 0, 108],
+            100,
+            &[1, 2, 3, 4, 5, 6, 7, 8],
+        );
+}
+
+// CRYPTOGRAPHIC OPERATIONS
+// ------------------------------------------------------------------------------------------------
+
+#[test]
+fn hperm() {
+    let values: Vec<u64> = (1..=12).collect();
+    let expected = build_expected_perm(&values);
+    build_operation_test!([HPerm], &values).expect_stack(&felt_slice_to_ints(&expected));
+}
+
+// TRACE VALIDITY
+// ------------------------------------------------------------------------------------------------
+
+#[test]
+fn operation_sequence_trace_is_valid() {
+    let test = build_operation_test!(
+        [
+            U32split,
+            U32add,
+            Add,
+            Push(Felt::new(100)),
+            MStore,
+            MovDn2,
+            AdvPopW,
+            SwapW,
+            MStoreW,
+            Drop,
+            Drop,
+            Drop,
+            Drop,
+        ],
+        &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 3 * U32_BOUND + 5],
+        &[1, 2, 3, 4]
+    );
+    test.prove_and_verify();
+}
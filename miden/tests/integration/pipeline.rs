@@ -0,0 +1,140 @@
+use std::sync::Arc;
+
+use assembly::{Assembler, Library, LibraryPath, ast::ModuleKind};
+use miden_vm::{
+    AdviceInputs, DefaultHost, ExecutionError, InputError, MidenPipeline, Module, PipelineError,
+    ProvingOptions, StackInputs, VerificationError, Verifier, execute, prove, prove_trace, verify,
+};
+
+// PIPELINE TESTS
+// ================================================================================================
+
+const SOURCE: &str = "
+    use.std::math::u64
+
+    begin
+        adv_push.1 add
+        push.7.0.5.0 exec.u64::wrapping_add drop add
+    end";
+
+#[test]
+fn pipeline_proves_and_verifies_program() {
+    let mut pipeline = MidenPipeline::new();
+    let advice_inputs = AdviceInputs::default().with_stack_values([10]).unwrap();
+
+    let output = pipeline.run(SOURCE, &[1, 2], advice_inputs.clone()).unwrap();
+    assert_eq!(output.stack_outputs.first().unwrap().as_int(), 2 + 10 + 12);
+    assert_eq!(output.stack_inputs, StackInputs::try_from_ints([1, 2]).unwrap());
+    assert_eq!(output.security_level, output.proof.security_level());
+    assert!(output.timings.proving > output.timings.execution);
+    assert_eq!(output.timings.total(), {
+        let timings = output.timings;
+        timings.assembly + timings.execution + timings.proving + timings.verification
+    });
+
+    // the proof verifies outside of the pipeline
+    verify(
+        output.program_info.clone(),
+        output.stack_inputs.clone(),
+        output.stack_outputs.clone(),
+        output.proof.clone(),
+    )
+    .unwrap();
+
+    // the program is assembled only once
+    let program = pipeline.assemble(SOURCE).unwrap();
+    assert_eq!(program.hash(), *output.program_info.program_hash());
+    assert!(Arc::ptr_eq(
+        program.mast_forest(),
+        pipeline.assemble(SOURCE).unwrap().mast_forest()
+    ));
+
+    let output = pipeline.run_program(&program, &[3, 4], advice_inputs).unwrap();
+    assert_eq!(output.stack_outputs.first().unwrap().as_int(), 4 + 10 + 12);
+    assert_eq!(output.timings.assembly, Default::default());
+}
+
+#[test]
+fn prove_trace_matches_prove() {
+    let program = Assembler::default()
+        .assemble_program("begin repeat.4 push.3 mul end end")
+        .unwrap();
+    let stack_inputs = StackInputs::try_from_ints([5]).unwrap();
+    let options = ProvingOptions::with_96_bit_security(false);
+
+    // proving the trace of an execution is the same as executing the program again to prove it
+    let trace = execute(
+        &program,
+        stack_inputs.clone(),
+        &mut DefaultHost::default(),
+        options.execution_options().clone(),
+    )
+    .unwrap();
+    let (stack_outputs, proof) = prove_trace(trace, stack_inputs.clone(), options.clone()).unwrap();
+    let (expected_outputs, expected_proof) =
+        prove(&program, stack_inputs, &mut DefaultHost::default(), options).unwrap();
+    assert_eq!(stack_outputs, expected_outputs);
+    assert!(proof.to_bytes() == expected_proof.to_bytes());
+}
+
+#[test]
+fn pipeline_without_verification() {
+    let mut pipeline = MidenPipeline::new().without_verification();
+    assert!(pipeline.verifier().is_none());
+
+    let output = pipeline.run("begin push.1 add end", &[1], AdviceInputs::default()).unwrap();
+    assert_eq!(output.stack_outputs.first().unwrap().as_int(), 2);
+    assert_eq!(output.timings.verification, Default::default());
+}
+
+#[test]
+fn pipeline_with_library() {
+    let module = Module::parser(ModuleKind::Library)
+        .parse_str(
+            LibraryPath::new("app::math").unwrap(),
+            "export.triple dup dup add add end",
+            &Arc::new(assembly::DefaultSourceManager::default()),
+        )
+        .unwrap();
+    let library: Library = Assembler::default().assemble_library([module]).unwrap();
+
+    let source = "use.app::math begin exec.math::triple end";
+    let mut pipeline = MidenPipeline::new();
+    assert!(matches!(
+        pipeline.run(source, &[7], AdviceInputs::default()),
+        Err(PipelineError::Assembly(_))
+    ));
+
+    let mut pipeline = pipeline.with_library(&library).unwrap();
+    let output = pipeline.run(source, &[7], AdviceInputs::default()).unwrap();
+    assert_eq!(output.stack_outputs.first().unwrap().as_int(), 21);
+}
+
+#[test]
+fn pipeline_stops_at_failing_stage() {
+    let mut pipeline =
+        MidenPipeline::new().with_proving_options(ProvingOptions::with_96_bit_security(false));
+
+    // invalid stack inputs are rejected before the program is executed
+    assert!(matches!(
+        pipeline.run("begin push.1 add end", &[u64::MAX], AdviceInputs::default()),
+        Err(PipelineError::InvalidInputs(InputError::NotFieldElement(..)))
+    ));
+
+    // programs which fail to execute are rejected before being proven
+    assert!(matches!(
+        pipeline.run(
+            "begin push.1 assert.err=7 push.0 assert.err=9 end",
+            &[],
+            AdviceInputs::default()
+        ),
+        Err(PipelineError::Execution(ExecutionError::FailedAssertion { err_code: 9, .. }))
+    ));
+
+    // proofs are checked against the policy of the verifier
+    let mut pipeline = pipeline.with_verifier(Verifier::new().with_min_security_level(100));
+    assert!(matches!(
+        pipeline.run("begin push.1 add end", &[1], AdviceInputs::default()),
+        Err(PipelineError::Verification(VerificationError::InsufficientSecurityLevel { .. }))
+    ));
+}
@@ -0,0 +1,147 @@
+use std::sync::{Arc, Mutex, mpsc};
+
+use miden_vm::{
+    Assembler, DefaultHost, ProgramInfo, ProvingOptions, ProvingPhase, StackInputs, prove,
+    prove_with_progress,
+};
+
+// PROGRESS TESTS
+// ================================================================================================
+
+const SOURCE: &str = "begin repeat.8 push.3 push.5 u32xor drop hperm end end";
+
+#[test]
+fn progress_phases() {
+    let program = Assembler::default().assemble_program(SOURCE).unwrap();
+    let options = ProvingOptions::with_96_bit_security(true);
+    let stack_inputs = StackInputs::default();
+
+    let phases = Arc::new(Mutex::new(Vec::new()));
+    let listener_phases = phases.clone();
+    let listener = move |phase: ProvingPhase| listener_phases.lock().unwrap().push(phase);
+
+    let (stack_outputs, proof) = prove_with_progress(
+        &program,
+        stack_inputs.clone(),
+        &mut DefaultHost::default(),
+        options.clone(),
+        Arc::new(listener),
+    )
+    .unwrap();
+    assert_eq!(*phases.lock().unwrap(), ProvingPhase::ALL);
+
+    // reporting progress does not affect the proof
+    let (_, expected) =
+        prove(&program, stack_inputs.clone(), &mut DefaultHost::default(), options).unwrap();
+    assert!(proof.to_bytes() == expected.to_bytes());
+
+    verifier::verify(ProgramInfo::from(program), stack_inputs, stack_outputs, proof)
+        .expect("proof must be valid");
+}
+
+#[test]
+fn progress_phases_over_channel() {
+    let program = Assembler::default().assemble_program(SOURCE).unwrap();
+    let (sender, receiver) = mpsc::channel();
+
+    prove_with_progress(
+        &program,
+        StackInputs::default(),
+        &mut DefaultHost::default(),
+        ProvingOptions::with_96_bit_security(false),
+        Arc::new(sender),
+    )
+    .unwrap();
+    assert_eq!(receiver.iter().collect::<Vec<_>>(), ProvingPhase::ALL);
+}
+
+#[cfg(feature = "prove-async")]
+mod prove_async {
+    use std::{
+        pin::pin,
+        sync::{Arc, mpsc},
+        task::{Context, Poll, Wake, Waker},
+        thread::{self, Thread},
+    };
+
+    use miden_vm::{
+        Assembler, DefaultHost, ProgramInfo, ProvingOptions, ProvingPhase, ProvingTask,
+        StackInputs, prove, prove_async,
+    };
+
+    use super::SOURCE;
+
+    /// Wakes a thread blocked in [block_on].
+    struct ThreadWaker(Thread);
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    /// Polls the specified task on the current thread, parking the thread until it is woken.
+    fn block_on(task: ProvingTask) -> <ProvingTask as Future>::Output {
+        let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+        let mut cx = Context::from_waker(&waker);
+        let mut task = pin!(task);
+        loop {
+            match task.as_mut().poll(&mut cx) {
+                Poll::Ready(output) => return output,
+                Poll::Pending => thread::park(),
+            }
+        }
+    }
+
+    #[test]
+    fn prove_async_future() {
+        let program = Assembler::default().assemble_program(SOURCE).unwrap();
+        let options = ProvingOptions::with_96_bit_security(true);
+        let stack_inputs = StackInputs::default();
+        let (sender, receiver) = mpsc::channel();
+
+        let task = prove_async(
+            program.clone(),
+            stack_inputs.clone(),
+            DefaultHost::default(),
+            options.clone(),
+            sender,
+        );
+        let (stack_outputs, proof) = block_on(task).unwrap();
+        assert_eq!(receiver.iter().collect::<Vec<_>>(), ProvingPhase::ALL);
+
+        let (_, expected) =
+            prove(&program, stack_inputs.clone(), &mut DefaultHost::default(), options).unwrap();
+        assert!(proof.to_bytes() == expected.to_bytes());
+
+        verifier::verify(ProgramInfo::from(program), stack_inputs, stack_outputs, proof)
+            .expect("proof must be valid");
+    }
+
+    #[test]
+    fn prove_async_wait() {
+        let program = Assembler::default()
+            .assemble_program("begin push.1 push.2 add swap drop end")
+            .unwrap();
+        let task = prove_async(
+            program,
+            StackInputs::default(),
+            DefaultHost::default(),
+            ProvingOptions::with_96_bit_security(false),
+            |_| {},
+        );
+        let (stack_outputs, _) = task.wait().unwrap();
+        assert_eq!(stack_outputs.get_stack_item(0).unwrap().as_int(), 3);
+
+        // execution errors are returned by the task
+        let program = Assembler::default().assemble_program("begin push.0 assert end").unwrap();
+        let task = prove_async(
+            program,
+            StackInputs::default(),
+            DefaultHost::default(),
+            ProvingOptions::with_96_bit_security(false),
+            |_| {},
+        );
+        assert!(task.wait().is_err());
+    }
+}
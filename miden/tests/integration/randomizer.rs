@@ -0,0 +1,117 @@
+use std::sync::Arc;
+
+use miden_vm::{
+    Assembler, DefaultHost, ExecutionEnvironment, ExecutionProof, ProgramInfo, ProvingOptions,
+    StackInputs, StackOutputs, TraceRandomizer,
+    crypto::Rpo256,
+    math::Felt,
+    prove, prove_with_randomizer, verify_with_environment, verify_with_randomizer,
+};
+
+// TRACE RANDOMIZER TESTS
+// ================================================================================================
+
+const SOURCE: &str = "begin repeat.8 push.3 push.5 u32xor drop hperm end end";
+
+/// Returns a randomizer binding the elements drawn from the public coin to the state of an
+/// external transcript, by hashing them together.
+fn transcript_randomizer(transcript: u64) -> Arc<dyn TraceRandomizer> {
+    Arc::new(move |coin_elements: &[Felt]| {
+        (0..coin_elements.len() as u64)
+            .map(|i| {
+                let mut elements = coin_elements.to_vec();
+                elements.extend([Felt::new(transcript), Felt::new(i)]);
+                Rpo256::hash_elements(&elements).as_elements()[0]
+            })
+            .collect::<Vec<_>>()
+    })
+}
+
+/// Returns a non-default environment, so that the proofs are bound to it.
+fn environment() -> ExecutionEnvironment {
+    ExecutionEnvironment::new(42, 1_700_000_000, [Felt::new(1); 4], [Felt::new(2); 4])
+}
+
+fn prove_program(
+    options: ProvingOptions,
+    randomizer: Option<Arc<dyn TraceRandomizer>>,
+) -> (ProgramInfo, StackOutputs, ExecutionProof) {
+    let program = Assembler::default().assemble_program(SOURCE).unwrap();
+    let mut host = DefaultHost::default().with_environment(environment());
+    let (stack_outputs, proof) = match randomizer {
+        Some(randomizer) => {
+            prove_with_randomizer(&program, StackInputs::default(), &mut host, options, randomizer)
+        },
+        None => prove(&program, StackInputs::default(), &mut host, options),
+    }
+    .unwrap();
+    (ProgramInfo::from(program), stack_outputs, proof)
+}
+
+#[test]
+fn proof_with_randomizer() {
+    for options in [
+        ProvingOptions::with_96_bit_security(false),
+        ProvingOptions::with_128_bit_security(false),
+    ] {
+        let (program_info, stack_outputs, proof) =
+            prove_program(options, Some(transcript_randomizer(42)));
+
+        verify_with_randomizer(
+            program_info.clone(),
+            StackInputs::default(),
+            stack_outputs.clone(),
+            environment(),
+            proof.clone(),
+            transcript_randomizer(42),
+        )
+        .expect("proof must be valid with the same randomizer");
+
+        // the proof is bound to the external transcript
+        assert!(
+            verify_with_randomizer(
+                program_info.clone(),
+                StackInputs::default(),
+                stack_outputs.clone(),
+                environment(),
+                proof.clone(),
+                transcript_randomizer(43),
+            )
+            .is_err()
+        );
+        assert!(
+            verify_with_environment(
+                program_info.clone(),
+                StackInputs::default(),
+                stack_outputs.clone(),
+                environment(),
+                proof.clone(),
+            )
+            .is_err()
+        );
+
+        // the proof is bound to the environment it was generated in
+        assert!(
+            verify_with_randomizer(
+                program_info,
+                StackInputs::default(),
+                stack_outputs,
+                ExecutionEnvironment::default(),
+                proof,
+                transcript_randomizer(42),
+            )
+            .is_err()
+        );
+    }
+}
+
+#[test]
+fn identity_randomizer_matches_default() {
+    let options = ProvingOptions::with_96_bit_security(false);
+    let identity: Arc<dyn TraceRandomizer> =
+        Arc::new(|coin_elements: &[Felt]| coin_elements.to_vec());
+
+    let (_, _, proof) = prove_program(options.clone(), Some(identity));
+    let (_, _, expected) = prove_program(options, None);
+    assert!(proof.to_bytes() == expected.to_bytes());
+}
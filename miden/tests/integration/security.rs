@@ -0,0 +1,92 @@
+use miden_vm::{
+    Assembler, DefaultHost, HashFunction, ProgramInfo, ProvingOptions, SecurityPreset, StackInputs,
+    prove, verify,
+};
+
+// SECURITY PRESET TESTS
+// ================================================================================================
+
+const SOURCE: &str = "begin repeat.8 push.3 push.5 u32xor drop hperm end end";
+
+#[test]
+fn proofs_with_security_presets() {
+    let program = Assembler::default().assemble_program(SOURCE).unwrap();
+
+    // the 128-bit presets are left out, as grinding their proofs is too slow in debug builds
+    for preset in [
+        SecurityPreset::Conjectured100,
+        SecurityPreset::Proven96,
+        SecurityPreset::Proven100,
+    ] {
+        for recursive in [false, true] {
+            let options = ProvingOptions::with_security_preset(preset, recursive);
+            let (stack_outputs, proof) = prove(
+                &program,
+                StackInputs::default(),
+                &mut DefaultHost::default(),
+                options.clone(),
+            )
+            .unwrap();
+
+            let expected_hash_fn = match (recursive, preset.bits()) {
+                (true, _) => HashFunction::Rpo256,
+                (false, 96) => HashFunction::Blake3_192,
+                (false, _) => HashFunction::Blake3_256,
+            };
+            assert_eq!(proof.hash_fn(), expected_hash_fn);
+
+            // the estimate agrees with the security level of the proof, and meets the preset
+            let estimate = options.estimate_security(proof.stark_proof().trace_info().length());
+            assert_eq!(estimate.conjectured_bits(), proof.security_level());
+            assert!(estimate.meets(preset), "{preset} not met: {estimate}");
+
+            let security_level = verify(
+                ProgramInfo::from(program.clone()),
+                StackInputs::default(),
+                stack_outputs,
+                proof.clone(),
+            )
+            .unwrap_or_else(|err| panic!("{preset} proof must be valid: {err}"));
+            assert_eq!(security_level, proof.security_level());
+        }
+    }
+}
+
+#[test]
+fn proofs_with_any_hash_function() {
+    let program = Assembler::default().assemble_program(SOURCE).unwrap();
+
+    for hash_fn in [
+        HashFunction::Blake3_192,
+        HashFunction::Blake3_256,
+        HashFunction::Rpo256,
+        HashFunction::Rpx256,
+        HashFunction::Keccak256,
+    ] {
+        for recursive in [false, true] {
+            let options =
+                ProvingOptions::with_security_preset(SecurityPreset::Conjectured100, recursive)
+                    .with_hash_fn(hash_fn);
+            let (stack_outputs, proof) =
+                prove(&program, StackInputs::default(), &mut DefaultHost::default(), options)
+                    .unwrap();
+            assert_eq!(proof.hash_fn(), hash_fn);
+
+            let security_level = verify(
+                ProgramInfo::from(program.clone()),
+                StackInputs::default(),
+                stack_outputs,
+                proof.clone(),
+            )
+            .unwrap_or_else(|err| {
+                panic!("{hash_fn:?} proof (recursive: {recursive}) must be valid: {err}")
+            });
+            assert_eq!(security_level, proof.security_level());
+
+            // the security level is capped by the collision resistance of the hash function
+            if hash_fn == HashFunction::Blake3_192 {
+                assert!(security_level <= 96);
+            }
+        }
+    }
+}
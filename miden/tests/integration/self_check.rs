@@ -0,0 +1,86 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicU64, Ordering},
+};
+
+use miden_vm::{
+    Assembler, DefaultHost, ExecutionError, FieldExtension, HashFunction, ProgramInfo,
+    ProvingOptions, StackInputs, math::Felt, prove, prove_with_randomizer, verify,
+};
+
+// SELF-CHECK TESTS
+// ================================================================================================
+
+const SOURCE: &str = "begin repeat.8 push.3 push.5 u32xor drop hperm end end";
+
+#[test]
+fn self_checked_proofs_are_valid() {
+    let program = Assembler::default().assemble_program(SOURCE).unwrap();
+
+    for options in [
+        ProvingOptions::with_96_bit_security(false),
+        ProvingOptions::with_96_bit_security(true),
+        ProvingOptions::with_96_bit_security_rpx(),
+    ] {
+        let (stack_outputs, proof) = prove(
+            &program,
+            StackInputs::default(),
+            &mut DefaultHost::default(),
+            options.clone().with_self_check(),
+        )
+        .unwrap();
+
+        // the self-check does not change the proof
+        let (_, unchecked_proof) =
+            prove(&program, StackInputs::default(), &mut DefaultHost::default(), options).unwrap();
+        assert_eq!(proof.to_bytes(), unchecked_proof.to_bytes());
+
+        verify(ProgramInfo::from(program.clone()), StackInputs::default(), stack_outputs, proof)
+            .unwrap();
+    }
+}
+
+#[test]
+fn self_check_accepts_custom_options() {
+    let program = Assembler::default().assemble_program(SOURCE).unwrap();
+
+    // the verifier only accepts proofs generated with one of the predefined sets of options, but
+    // the self-check accepts any options
+    let options =
+        ProvingOptions::new(20, 16, 0, FieldExtension::Quadratic, 4, 31, HashFunction::Blake3_256)
+            .with_self_check();
+    let (stack_outputs, proof) =
+        prove(&program, StackInputs::default(), &mut DefaultHost::default(), options).unwrap();
+    assert!(
+        verify(ProgramInfo::from(program), StackInputs::default(), stack_outputs, proof).is_err()
+    );
+}
+
+#[test]
+fn self_check_rejects_invalid_proof() {
+    let program = Assembler::default().assemble_program(SOURCE).unwrap();
+
+    // a randomizer which returns different elements on every call makes the verifier derive
+    // different auxiliary trace randomness than the prover, so the proof cannot be valid
+    let calls = Arc::new(AtomicU64::new(0));
+    let randomizer = Arc::new(move |coin_elements: &[Felt]| {
+        let call = calls.fetch_add(1, Ordering::Relaxed);
+        coin_elements
+            .iter()
+            .map(|element| *element + Felt::new(call))
+            .collect::<Vec<_>>()
+    });
+
+    let err = prove_with_randomizer(
+        &program,
+        StackInputs::default(),
+        &mut DefaultHost::default(),
+        ProvingOptions::with_96_bit_security(false).with_self_check(),
+        randomizer,
+    )
+    .unwrap_err();
+    assert!(
+        matches!(err, ExecutionError::ProofSelfCheckFailed { program: root, .. } if root == program.hash()),
+        "unexpected error: {err}"
+    );
+}
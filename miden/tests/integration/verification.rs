@@ -0,0 +1,128 @@
+use miden_vm::{
+    Assembler, DefaultHost, ExecutionProof, HashFunction, Program, ProgramInfo, ProvingOptions,
+    StackInputs, StackOutputs, VerificationError, VerificationFailure, prove,
+    utils::Deserializable, verify,
+};
+
+// VERIFICATION FAILURE TESTS
+// ================================================================================================
+
+const SOURCE: &str = "begin repeat.8 push.3 push.5 u32xor drop hperm end end";
+
+fn prove_program(stack_inputs: &[u64]) -> (ProgramInfo, StackInputs, StackOutputs, ExecutionProof) {
+    let program = Assembler::default().assemble_program(SOURCE).unwrap();
+    let stack_inputs = StackInputs::try_from_ints(stack_inputs.iter().copied()).unwrap();
+    let (stack_outputs, proof) = prove(
+        &program,
+        stack_inputs.clone(),
+        &mut DefaultHost::default(),
+        ProvingOptions::with_96_bit_security(false),
+    )
+    .unwrap();
+    (ProgramInfo::from(program), stack_inputs, stack_outputs, proof)
+}
+
+fn verification_failure(
+    program_info: ProgramInfo,
+    stack_inputs: StackInputs,
+    stack_outputs: StackOutputs,
+    proof: ExecutionProof,
+) -> VerificationFailure {
+    let expected_program = *program_info.program_hash();
+    match verify(program_info, stack_inputs, stack_outputs, proof) {
+        Err(VerificationError::ProgramVerificationError { program, failure, .. }) => {
+            assert_eq!(program, expected_program);
+            failure
+        },
+        result => panic!("expected the proof to fail verification, but got {result:?}"),
+    }
+}
+
+#[test]
+fn verification_failures() {
+    let (program_info, stack_inputs, stack_outputs, proof) = prove_program(&[1, 2]);
+    let (_, _, _, other_proof) = prove_program(&[3, 4]);
+    let (hash_fn, stark_proof) = proof.clone().into_parts();
+    let (_, other_stark_proof) = other_proof.into_parts();
+
+    // stack outputs other than those of the proven execution
+    let failure = verification_failure(
+        program_info.clone(),
+        stack_inputs.clone(),
+        StackOutputs::try_from_ints([42]).unwrap(),
+        proof,
+    );
+    assert_eq!(failure, VerificationFailure::OodConstraintEvaluation);
+
+    // proof-of-work nonce which does not meet the grinding factor
+    let mut tampered = stark_proof.clone();
+    tampered.pow_nonce += 1;
+    let failure = verification_failure(
+        program_info.clone(),
+        stack_inputs.clone(),
+        stack_outputs.clone(),
+        ExecutionProof::new(tampered, hash_fn),
+    );
+    assert_eq!(failure, VerificationFailure::ProofOfWork);
+
+    // FRI proof of another execution
+    let mut tampered = stark_proof.clone();
+    tampered.fri_proof = other_stark_proof.fri_proof.clone();
+    let failure = verification_failure(
+        program_info.clone(),
+        stack_inputs.clone(),
+        stack_outputs.clone(),
+        ExecutionProof::new(tampered, hash_fn),
+    );
+    // the trace of the program is short enough for the FRI proof to consist of the remainder only
+    assert!(matches!(failure, VerificationFailure::FriRemainder(_)), "{failure:?}");
+
+    // constraint queries of another execution
+    let mut tampered = stark_proof.clone();
+    tampered.constraint_queries = other_stark_proof.constraint_queries.clone();
+    let failure = verification_failure(
+        program_info.clone(),
+        stack_inputs.clone(),
+        stack_outputs.clone(),
+        ExecutionProof::new(tampered, hash_fn),
+    );
+    assert_eq!(failure, VerificationFailure::ConstraintQueryMerklePath);
+
+    // trace queries of another execution
+    let mut tampered = stark_proof.clone();
+    tampered.trace_queries = other_stark_proof.trace_queries.clone();
+    let failure = verification_failure(
+        program_info.clone(),
+        stack_inputs.clone(),
+        stack_outputs.clone(),
+        ExecutionProof::new(tampered, hash_fn),
+    );
+    assert_eq!(failure, VerificationFailure::TraceQueryMerklePath);
+}
+
+// PREVIOUS RELEASES
+// ================================================================================================
+
+#[test]
+// A program and a proof of its execution serialized by Miden VM 0.13.2 can be read, but the proof
+// was generated against other constraints and public inputs, and is rejected as such.
+fn previous_release_proof() {
+    let program = Program::read_from_bytes(include_bytes!("data/v0_13/add.masb")).unwrap();
+    let proof = ExecutionProof::from_bytes(include_bytes!("data/v0_13/add.proof")).unwrap();
+    assert_eq!(proof.version(), [0, 0, 0]);
+    assert_eq!(proof.hash_fn(), HashFunction::Blake3_192);
+
+    // the program is the same as the one assembled by this release
+    let source = include_str!("data/v0_13/add.masm");
+    assert_eq!(Assembler::default().assemble_program(source).unwrap().hash(), program.hash());
+
+    let program_info = ProgramInfo::from(program);
+    let expected_program = *program_info.program_hash();
+    let stack_outputs = StackOutputs::try_from_ints([7]).unwrap();
+    match verify(program_info, StackInputs::default(), stack_outputs, proof) {
+        Err(VerificationError::UnsupportedProofVersion { program, .. }) => {
+            assert_eq!(program, expected_program)
+        },
+        result => panic!("expected the proof to be rejected, but got {result:?}"),
+    }
+}
@@ -12,7 +12,7 @@ mod package;
 extern crate std;
 
 pub use assembly::{
-    Library, LibraryPath,
+    Library, LibraryNamespace, LibraryPath,
     ast::{ProcedureName, QualifiedProcedureName},
 };
 pub use vm_core::{Program, chiplets::hasher::Digest, mast::MastForest};
@@ -25,5 +25,9 @@ pub use self::{
             ResolvedDependency,
         },
     },
-    package::{MastArtifact, Package, PackageExport, PackageManifest},
+    package::{
+        AssemblerPackageExt, MastArtifact, PUBLIC_KEY_LEN, Package, PackageCipher, PackageExport,
+        PackageManifest, PackageSignature, PackageSigner, PublicKey, SIGNATURE_LEN, SealedPackage,
+        SignatureVerifier, SignedPackage, TrustPolicy,
+    },
 };
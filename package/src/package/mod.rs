@@ -6,7 +6,17 @@ use vm_core::{Program, mast::MastForest, utils::DisplayHex};
 
 use crate::{Dependency, Digest};
 
+mod sealed;
 mod serialization;
+mod signed;
+
+pub use self::{
+    sealed::{PackageCipher, SealedPackage},
+    signed::{
+        AssemblerPackageExt, PUBLIC_KEY_LEN, PackageSignature, PackageSigner, PublicKey,
+        SIGNATURE_LEN, SignatureVerifier, SignedPackage, TrustPolicy,
+    },
+};
 
 // MAST ARTIFACT
 // ================================================================================================
@@ -0,0 +1,93 @@
+use alloc::{format, string::String, vec::Vec};
+
+use assembly::Report;
+use vm_core::utils::{Deserializable, Serializable};
+
+use super::{MastArtifact, Package, PackageManifest};
+use crate::Digest;
+
+// PACKAGE CIPHER
+// ================================================================================================
+
+/// A cipher used to encrypt the MAST artifact of a [SealedPackage], and to decrypt it before it is
+/// loaded for execution.
+///
+/// This crate does not provide any implementations of this trait: the choice of cipher and the
+/// management of keys is left to the distributor of the package, and to the host which executes
+/// it. Implementations are expected to authenticate the ciphertext, so that a package sealed with
+/// a different key, or tampered with, fails to unseal.
+pub trait PackageCipher {
+    /// Encrypts the serialized MAST artifact `plaintext`.
+    fn encrypt(&self, plaintext: &[u8]) -> Vec<u8>;
+
+    /// Decrypts `ciphertext`, as produced by [PackageCipher::encrypt], into the serialized MAST
+    /// artifact.
+    fn decrypt(&self, ciphertext: &[u8]) -> Result<Vec<u8>, Report>;
+}
+
+// SEALED PACKAGE
+// ================================================================================================
+
+/// A [Package] whose MAST artifact is encrypted at rest, for distributing proprietary programs
+/// and libraries to operators.
+///
+/// Only the digest of the MAST artifact (i.e. the MAST root of a program), the package name, and
+/// its manifest are stored in the clear, so that the package can be identified and its
+/// dependencies resolved without decrypting it. The structure of the MAST forest is only revealed
+/// to a host which holds the [PackageCipher] used to seal it.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SealedPackage {
+    /// Name of the package
+    pub name: String,
+    /// The digest of the MAST artifact before it was encrypted, i.e. the MAST root of the program
+    /// or library.
+    ///
+    /// This is intentionally not a digest of the ciphertext: the MAST root of a program is public
+    /// anyway, as it is part of the public inputs of its execution proofs, and it is what hosts and
+    /// verifiers use to identify the package. It is checked against the decrypted artifact when
+    /// the package is unsealed.
+    pub digest: Digest,
+    /// The package manifest, containing the set of exported procedures and their signatures,
+    /// if known.
+    pub manifest: PackageManifest,
+    /// The serialized MAST artifact of the package, encrypted with a [PackageCipher]
+    pub ciphertext: Vec<u8>,
+}
+
+impl Package {
+    /// Seals this package by encrypting its MAST artifact with `cipher`.
+    pub fn seal(&self, cipher: &dyn PackageCipher) -> SealedPackage {
+        SealedPackage {
+            name: self.name.clone(),
+            digest: self.digest(),
+            manifest: self.manifest.clone(),
+            ciphertext: cipher.encrypt(&self.mast.to_bytes()),
+        }
+    }
+}
+
+impl SealedPackage {
+    /// Decrypts the MAST artifact of this package with `cipher`, returning the unsealed [Package].
+    ///
+    /// Returns an error if the artifact cannot be decrypted or deserialized, or if its digest does
+    /// not match the digest the package was sealed with.
+    pub fn unseal(&self, cipher: &dyn PackageCipher) -> Result<Package, Report> {
+        let plaintext = cipher.decrypt(&self.ciphertext)?;
+        let mast = MastArtifact::read_from_bytes(&plaintext).map_err(|err| {
+            Report::msg(format!("failed to deserialize sealed MAST artifact: {err}"))
+        })?;
+        if mast.digest() != self.digest {
+            return Err(Report::msg(format!(
+                "sealed MAST artifact has digest {}, but the package was sealed with digest {}",
+                mast.digest(),
+                self.digest
+            )));
+        }
+
+        Ok(Package {
+            name: self.name.clone(),
+            mast,
+            manifest: self.manifest.clone(),
+        })
+    }
+}
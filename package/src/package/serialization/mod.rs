@@ -17,7 +17,7 @@
 //!   - If `Program`:
 //!     - `program` (`Program`)
 //!   - If `Library`:
-//!     - `library` (`Library`)
+//!     - `library` (`Library`), which holds the attributes of its procedures since version 0.0.1
 //!
 //! (Package Manifest)
 //! - `manifest` (`PackageManifest`)
@@ -33,16 +33,53 @@
 //!     - `dependency` (`Dependency`)
 //!       - `name` (`String`)
 //!       - `digest` (`Digest`)
+//!
+//! The serialization format of `SealedPackage` is as follows:
+//!
+//! (Metadata)
+//! - `MAGIC_SEALED_PACKAGE`
+//! - `VERSION`
+//!
+//! (Package Name)
+//! - `name` (`String`)
+//!
+//! (MAST Artifact Digest)
+//! - `digest` (`Digest`)
+//!
+//! (Package Manifest)
+//! - `manifest` (`PackageManifest`)
+//!
+//! (Encrypted MAST Artifact)
+//! - `ciphertext_len` (`usize`)
+//! - `ciphertext` (`[u8; ciphertext_len]`), the encrypted serialization of the `MastArtifact`
+//!
+//! The serialization format of `SignedPackage` is as follows:
+//!
+//! (Metadata)
+//! - `MAGIC_SIGNED_PACKAGE`
+//! - `VERSION`
+//!
+//! (Package)
+//! - `package` (`Package`)
+//!
+//! (Signature)
+//! - `public_key` (`[u8; 32]`), the ed25519 public key of the signer
+//! - `signature` (`[u8; 64]`), the ed25519 signature of the signing digest of the package
 
 use alloc::{collections::BTreeSet, format, string::String, sync::Arc, vec::Vec};
 
 use assembly::{Library, ast::QualifiedProcedureName};
 use vm_core::{
     Program,
-    utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable},
+    utils::{
+        ByteReader, ByteWriter, Deserializable, DeserializationError, FormatVersions, Serializable,
+    },
 };
 
-use super::{Dependency, MastArtifact, Package, PackageExport, PackageManifest};
+use super::{
+    Dependency, MastArtifact, Package, PackageExport, PackageManifest, PackageSignature,
+    SealedPackage, SignedPackage,
+};
 use crate::Digest;
 
 #[cfg(test)]
@@ -54,6 +91,12 @@ mod tests;
 /// Magic string for detecting that a file is serialized [`Package`]
 const MAGIC_PACKAGE: &[u8; 5] = b"MASP\0";
 
+/// Magic string for detecting that a file is serialized [`SealedPackage`]
+const MAGIC_SEALED_PACKAGE: &[u8; 5] = b"MASE\0";
+
+/// Magic string for detecting that a file is serialized [`SignedPackage`]
+const MAGIC_SIGNED_PACKAGE: &[u8; 5] = b"MASG\0";
+
 /// Magic string indicating a Program artifact.
 const MAGIC_PROGRAM: &[u8; 4] = b"PRG\0";
 
@@ -63,7 +106,16 @@ const MAGIC_LIBRARY: &[u8; 4] = b"LIB\0";
 /// The format version.
 ///
 /// If future modifications are made to this format, the version should be incremented by 1.
-const VERSION: [u8; 3] = [0, 0, 0];
+const VERSION: [u8; 3] = [0, 0, 1];
+
+/// The first format version in which libraries hold the attributes of their procedures.
+const LIBRARY_ATTRIBUTES_VERSION: [u8; 3] = [0, 0, 1];
+
+/// The format versions which can be deserialized.
+///
+/// Programs embedded in packages carry their own format version, so packages written by previous
+/// releases remain readable even if the format of programs changes.
+const SUPPORTED_VERSIONS: FormatVersions = FormatVersions::new([0, 0, 0], VERSION);
 
 // PACKAGE SERIALIZATION/DESERIALIZATION
 // ================================================================================================
@@ -95,18 +147,13 @@ impl Deserializable for Package {
             )));
         }
 
-        let version: [u8; 3] = source.read_array()?;
-        if version != VERSION {
-            return Err(DeserializationError::InvalidValue(format!(
-                "unsupported version. Got '{version:?}', but only '{VERSION:?}' is supported"
-            )));
-        }
+        let version = SUPPORTED_VERSIONS.read_version("package", source)?;
 
         // Read package name
         let name = String::read_from(source)?;
 
         // Read MAST artifact
-        let mast = MastArtifact::read_from(source)?;
+        let mast = MastArtifact::read_with_version(source, version)?;
 
         // Read manifest
         let manifest = PackageManifest::read_from(source)?;
@@ -115,6 +162,98 @@ impl Deserializable for Package {
     }
 }
 
+// SEALED PACKAGE SERIALIZATION/DESERIALIZATION
+// ================================================================================================
+
+impl Serializable for SealedPackage {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        // Write magic & version
+        target.write_bytes(MAGIC_SEALED_PACKAGE);
+        target.write_bytes(&VERSION);
+
+        self.name.write_into(target);
+        self.digest.write_into(target);
+        self.manifest.write_into(target);
+
+        // Write the encrypted MAST artifact
+        target.write_usize(self.ciphertext.len());
+        target.write_bytes(&self.ciphertext);
+    }
+}
+
+impl Deserializable for SealedPackage {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        // Read and validate magic & version
+        let magic: [u8; 5] = source.read_array()?;
+        if magic != *MAGIC_SEALED_PACKAGE {
+            return Err(DeserializationError::InvalidValue(format!(
+                "invalid magic bytes. Expected '{MAGIC_SEALED_PACKAGE:?}', got '{magic:?}'"
+            )));
+        }
+
+        SUPPORTED_VERSIONS.read_version("sealed package", source)?;
+
+        let name = String::read_from(source)?;
+        let digest = Digest::read_from(source)?;
+        let manifest = PackageManifest::read_from(source)?;
+
+        // Read the encrypted MAST artifact
+        let ciphertext_len = source.read_usize()?;
+        let ciphertext = source.read_vec(ciphertext_len)?;
+
+        Ok(Self { name, digest, manifest, ciphertext })
+    }
+}
+
+// SIGNED PACKAGE SERIALIZATION/DESERIALIZATION
+// ================================================================================================
+
+impl SignedPackage {
+    /// Returns true if `bytes` start with the magic bytes of a serialized [SignedPackage].
+    pub fn is_signed_package(bytes: &[u8]) -> bool {
+        bytes.starts_with(MAGIC_SIGNED_PACKAGE)
+    }
+}
+
+impl Serializable for SignedPackage {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        // Write magic & version
+        target.write_bytes(MAGIC_SIGNED_PACKAGE);
+        target.write_bytes(&VERSION);
+
+        self.package.write_into(target);
+
+        // Write the signature
+        target.write_bytes(&self.signature.public_key);
+        target.write_bytes(&self.signature.signature);
+    }
+}
+
+impl Deserializable for SignedPackage {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        // Read and validate magic & version
+        let magic: [u8; 5] = source.read_array()?;
+        if magic != *MAGIC_SIGNED_PACKAGE {
+            return Err(DeserializationError::InvalidValue(format!(
+                "invalid magic bytes. Expected '{MAGIC_SIGNED_PACKAGE:?}', got '{magic:?}'"
+            )));
+        }
+
+        SUPPORTED_VERSIONS.read_version("signed package", source)?;
+
+        let package = Package::read_from(source)?;
+
+        // Read the signature
+        let public_key = source.read_array()?;
+        let signature = source.read_array()?;
+
+        Ok(Self {
+            package,
+            signature: PackageSignature { public_key, signature },
+        })
+    }
+}
+
 // MAST ARTIFACT SERIALIZATION/DESERIALIZATION
 // ================================================================================================
 
@@ -135,12 +274,25 @@ impl Serializable for MastArtifact {
 
 impl Deserializable for MastArtifact {
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Self::read_with_version(source, VERSION)
+    }
+}
+
+impl MastArtifact {
+    /// Reads a MAST artifact serialized with the specified version of the package format.
+    fn read_with_version<R: ByteReader>(
+        source: &mut R,
+        version: [u8; 3],
+    ) -> Result<Self, DeserializationError> {
         let tag: [u8; 4] = source.read_array()?;
 
         if &tag == MAGIC_PROGRAM {
             Program::read_from(source).map(Arc::new).map(MastArtifact::Executable)
         } else if &tag == MAGIC_LIBRARY {
-            Library::read_from(source).map(Arc::new).map(MastArtifact::Library)
+            let with_attributes = version >= LIBRARY_ATTRIBUTES_VERSION;
+            Library::read_from_with_options(source, with_attributes)
+                .map(Arc::new)
+                .map(MastArtifact::Library)
         } else {
             Err(DeserializationError::InvalidValue(format!(
                 "invalid MAST artifact tag: {:?}",
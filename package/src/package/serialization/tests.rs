@@ -1,4 +1,4 @@
-use std::format;
+use std::{format, vec};
 
 use proptest::{
     prelude::*,
@@ -22,3 +22,254 @@ fn package_serialization_roundtrip() {
         })
         .unwrap();
 }
+
+#[test]
+fn package_without_library_attributes() {
+    TestRunner::new(Config::with_cases(8))
+        .run(&any::<Package>(), move |package| {
+            prop_assume!(package.is_library());
+
+            // packages of version 0.0.0 hold libraries which end with their exports, rather than
+            // with the attributes of their procedures, of which the example libraries have none
+            let library = package.mast.clone().unwrap_library().to_bytes();
+            let mut bytes = Vec::new();
+            bytes.extend_from_slice(MAGIC_PACKAGE);
+            bytes.extend_from_slice(&[0, 0, 0]);
+            package.name.write_into(&mut bytes);
+            bytes.extend_from_slice(MAGIC_LIBRARY);
+            bytes.extend_from_slice(&library[..library.len() - 1]);
+            package.manifest.write_into(&mut bytes);
+
+            prop_assert_eq!(Package::read_from_bytes(&bytes).unwrap(), package);
+            Ok(())
+        })
+        .unwrap();
+}
+
+/// A toy cipher which XORs the plaintext with a single-byte key, and prefixes the ciphertext with
+/// the key so that decrypting with the wrong key fails.
+struct XorCipher(u8);
+
+impl crate::PackageCipher for XorCipher {
+    fn encrypt(&self, plaintext: &[u8]) -> Vec<u8> {
+        core::iter::once(self.0)
+            .chain(plaintext.iter().map(|byte| byte ^ self.0))
+            .collect()
+    }
+
+    fn decrypt(&self, ciphertext: &[u8]) -> Result<Vec<u8>, assembly::Report> {
+        match ciphertext.split_first() {
+            Some((&key, ciphertext)) if key == self.0 => {
+                Ok(ciphertext.iter().map(|byte| byte ^ self.0).collect())
+            },
+            _ => Err(assembly::Report::msg("wrong key")),
+        }
+    }
+}
+
+#[test]
+fn sealed_package_serialization_roundtrip() {
+    let cases = 32;
+    TestRunner::new(Config::with_cases(cases))
+        .run(&any::<Package>(), move |package| {
+            let sealed = package.seal(&XorCipher(0x5a));
+            prop_assert_eq!(sealed.digest, package.digest());
+            prop_assert_ne!(&sealed.ciphertext, &package.mast.to_bytes());
+
+            let bytes = sealed.to_bytes();
+            let deserialized = SealedPackage::read_from_bytes(&bytes).unwrap();
+            prop_assert_eq!(&sealed, &deserialized);
+            prop_assert_eq!(deserialized.unseal(&XorCipher(0x5a)).unwrap(), package);
+            prop_assert!(deserialized.unseal(&XorCipher(0xa5)).is_err());
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn sealed_package_digest_mismatch() {
+    TestRunner::new(Config::with_cases(8))
+        .run(&any::<Package>(), move |package| {
+            let mut sealed = package.seal(&XorCipher(1));
+            sealed.digest = Digest::default();
+
+            let err = format!("{}", sealed.unseal(&XorCipher(1)).unwrap_err());
+            prop_assert!(err.contains("but the package was sealed with digest"), "{}", err);
+            Ok(())
+        })
+        .unwrap();
+}
+
+/// A toy signer whose signature of a message is the hash of the message and its public key, which
+/// is derived from a single-byte secret key.
+struct ToySigner(u8);
+
+impl crate::PackageSigner for ToySigner {
+    fn public_key(&self) -> crate::PublicKey {
+        [self.0; crate::PUBLIC_KEY_LEN]
+    }
+
+    fn sign(&self, message: &[u8]) -> [u8; crate::SIGNATURE_LEN] {
+        toy_signature(&self.public_key(), message)
+    }
+}
+
+struct ToyVerifier;
+
+impl crate::SignatureVerifier for ToyVerifier {
+    fn verify(
+        &self,
+        public_key: &crate::PublicKey,
+        message: &[u8],
+        signature: &[u8; crate::SIGNATURE_LEN],
+    ) -> bool {
+        toy_signature(public_key, message) == *signature
+    }
+}
+
+fn toy_signature(public_key: &crate::PublicKey, message: &[u8]) -> [u8; crate::SIGNATURE_LEN] {
+    let mut signature = [0; crate::SIGNATURE_LEN];
+    let digest = vm_core::crypto::hash::Rpo256::hash(&[message, public_key].concat());
+    signature[..32].copy_from_slice(&digest.as_bytes());
+    signature[32..].copy_from_slice(public_key);
+    signature
+}
+
+#[test]
+fn signed_package_serialization_roundtrip() {
+    TestRunner::new(Config::with_cases(32))
+        .run(&any::<Package>(), move |package| {
+            let signed = package.sign(&ToySigner(7));
+            prop_assert!(SignedPackage::is_signed_package(&signed.to_bytes()));
+            prop_assert!(!SignedPackage::is_signed_package(&package.to_bytes()));
+
+            let deserialized = SignedPackage::read_from_bytes(&signed.to_bytes()).unwrap();
+            prop_assert_eq!(&signed, &deserialized);
+            prop_assert!(deserialized.verify_signature(&ToyVerifier).is_ok());
+
+            // the signature covers the name and the manifest of the package
+            let mut tampered = deserialized.clone();
+            tampered.package.name.push('!');
+            prop_assert!(tampered.verify_signature(&ToyVerifier).is_err());
+
+            let mut tampered = deserialized.clone();
+            tampered.package.manifest.dependencies.clear();
+            tampered.package.manifest.exports.clear();
+            prop_assert_eq!(
+                tampered.verify_signature(&ToyVerifier).is_ok(),
+                package.manifest == tampered.package.manifest
+            );
+
+            // the signature also covers the parts of the MAST artifact which do not contribute to
+            // its digest, such as the advice map of a program
+            if let MastArtifact::Executable(program) = &package.mast {
+                let mut forest = (**program.mast_forest()).clone();
+                forest.advice_map_mut().insert(Digest::default(), vec![vm_core::Felt::new(1)]);
+                let program = Program::with_kernel(
+                    Arc::new(forest),
+                    program.entrypoint(),
+                    program.kernel().clone(),
+                );
+                let mut tampered = deserialized;
+                tampered.package.mast = MastArtifact::Executable(Arc::new(program));
+                prop_assert_eq!(tampered.package.digest(), package.digest());
+                prop_assert!(tampered.verify_signature(&ToyVerifier).is_err());
+            }
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn trust_policy_namespace_ownership() {
+    let owner = ToySigner(1);
+    let other = ToySigner(2);
+
+    TestRunner::new(Config::with_cases(32))
+        .run(&any::<Package>(), move |package| {
+            let namespaces = package.namespaces();
+            let policy = namespaces.iter().fold(crate::TrustPolicy::new(), |policy, namespace| {
+                policy.with_owner(namespace.clone(), crate::PackageSigner::public_key(&owner))
+            });
+
+            // packages signed by the owner of their namespaces are accepted
+            let signed = package.sign(&owner);
+            prop_assert!(policy.check_signed(&signed, &ToyVerifier).is_ok());
+            let loaded = policy.load(&signed.to_bytes(), &ToyVerifier).unwrap();
+            prop_assert_eq!(&loaded, &package);
+
+            // packages signed by another key, or not signed, are rejected if they provide modules
+            // in an owned namespace
+            let signed_by_other = package.sign(&other);
+            prop_assert_eq!(
+                policy.check_signed(&signed_by_other, &ToyVerifier).is_ok(),
+                namespaces.is_empty()
+            );
+            prop_assert_eq!(
+                policy.load(&package.to_bytes(), &ToyVerifier).is_ok(),
+                namespaces.is_empty()
+            );
+
+            // packages with invalid signatures are rejected
+            let mut forged = signed;
+            forged.signature.public_key = crate::PackageSigner::public_key(&other);
+            prop_assert!(policy.check_signed(&forged, &ToyVerifier).is_err());
+
+            // unsigned packages are rejected if signatures are required
+            let policy = policy.with_required_signatures();
+            prop_assert!(policy.check(&package, None, &ToyVerifier).is_err());
+
+            // only library packages can be loaded as libraries
+            prop_assert_eq!(
+                policy.load_library(&package.sign(&owner).to_bytes(), &ToyVerifier).is_ok(),
+                package.is_library()
+            );
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn trust_policy_assembler_integration() {
+    use assembly::Assembler;
+
+    use crate::AssemblerPackageExt;
+
+    let owner = ToySigner(1);
+    let other = ToySigner(2);
+
+    TestRunner::new(Config::with_cases(8))
+        .run(&any::<Package>(), move |package| {
+            prop_assume!(package.is_library());
+            let policy = package.namespaces().into_iter().fold(
+                crate::TrustPolicy::new(),
+                |policy, namespace| {
+                    policy.with_owner(namespace, crate::PackageSigner::public_key(&owner))
+                },
+            );
+
+            // packages which are not signed by the owner of their namespaces are not added to the
+            // assembler
+            let mut assembler = Assembler::default();
+            prop_assert!(
+                assembler.add_package(&package.to_bytes(), &policy, &ToyVerifier).is_err()
+            );
+            let signed_by_other = package.sign(&other).to_bytes();
+            prop_assert!(assembler.add_package(&signed_by_other, &policy, &ToyVerifier).is_err());
+            let signed = package.sign(&owner).to_bytes();
+            prop_assert!(assembler.add_package(&signed, &policy, &ToyVerifier).is_ok());
+
+            // libraries which are not packaged are not signed
+            let library = package.mast.clone().unwrap_library();
+            prop_assert!(policy.check_library(&library).is_err());
+            prop_assert!(crate::TrustPolicy::new().check_library(&library).is_ok());
+            prop_assert!(
+                crate::TrustPolicy::new()
+                    .with_required_signatures()
+                    .check_library(&library)
+                    .is_err()
+            );
+            Ok(())
+        })
+        .unwrap();
+}
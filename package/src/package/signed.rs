@@ -0,0 +1,361 @@
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    format,
+    sync::Arc,
+};
+
+use assembly::{Assembler, Library, LibraryNamespace, Report};
+use vm_core::{
+    crypto::hash::Rpo256,
+    utils::{Deserializable, DisplayHex, Serializable},
+};
+
+use super::{MastArtifact, Package};
+use crate::Digest;
+
+// CONSTANTS
+// ================================================================================================
+
+/// The length in bytes of an ed25519 public key.
+pub const PUBLIC_KEY_LEN: usize = 32;
+
+/// The length in bytes of an ed25519 signature.
+pub const SIGNATURE_LEN: usize = 64;
+
+/// An ed25519 public key, in its standard 32-byte encoding.
+pub type PublicKey = [u8; PUBLIC_KEY_LEN];
+
+// SIGNER AND VERIFIER
+// ================================================================================================
+
+/// A key used to sign packages with ed25519.
+///
+/// Like [crate::PackageCipher], this crate does not provide any implementations of this trait, so
+/// that the choice of ed25519 implementation and the management of keys is left to the publisher
+/// of the package.
+pub trait PackageSigner {
+    /// Returns the public key of this signer.
+    fn public_key(&self) -> PublicKey;
+
+    /// Signs `message` with ed25519.
+    fn sign(&self, message: &[u8]) -> [u8; SIGNATURE_LEN];
+}
+
+/// An ed25519 implementation used to verify the signatures of packages when they are loaded.
+pub trait SignatureVerifier {
+    /// Returns true if `signature` is a valid ed25519 signature of `message` under `public_key`.
+    fn verify(
+        &self,
+        public_key: &PublicKey,
+        message: &[u8],
+        signature: &[u8; SIGNATURE_LEN],
+    ) -> bool;
+}
+
+// SIGNED PACKAGE
+// ================================================================================================
+
+/// The signature of a package, along with the public key of its signer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PackageSignature {
+    /// The public key of the signer of the package
+    pub public_key: PublicKey,
+    /// The ed25519 signature of the signing digest of the package
+    pub signature: [u8; SIGNATURE_LEN],
+}
+
+/// A [Package] signed by its publisher, so that its provenance can be checked against a
+/// [TrustPolicy] before it is loaded.
+///
+/// The signature covers the [Package::signing_digest], which commits to the whole serialized
+/// package.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SignedPackage {
+    /// The signed package
+    pub package: Package,
+    /// The signature of the package
+    pub signature: PackageSignature,
+}
+
+impl Package {
+    /// Returns the digest signed by the publisher of this package, i.e., the hash of the
+    /// serialized package.
+    ///
+    /// This commits to the whole MAST artifact, rather than to its digest only, so that the parts
+    /// of the artifact which do not contribute to its digest (e.g., its advice map and decorators)
+    /// cannot be altered without invalidating the signature.
+    pub fn signing_digest(&self) -> Digest {
+        Rpo256::hash(&self.to_bytes())
+    }
+
+    /// Returns the namespaces of the modules provided by this package.
+    ///
+    /// For a library, these are the namespaces of the modules of the library; for a program, these
+    /// are the namespaces of the procedures it exports, if any.
+    pub fn namespaces(&self) -> BTreeSet<LibraryNamespace> {
+        match &self.mast {
+            MastArtifact::Library(library) => {
+                library.module_infos().map(|info| info.path().namespace().clone()).collect()
+            },
+            MastArtifact::Executable(_) => self
+                .manifest
+                .exports
+                .iter()
+                .map(|export| export.name.module.namespace().clone())
+                .collect(),
+        }
+    }
+
+    /// Signs this package with `signer`.
+    pub fn sign(&self, signer: &dyn PackageSigner) -> SignedPackage {
+        let signature = signer.sign(&self.signing_digest().as_bytes());
+        SignedPackage {
+            package: self.clone(),
+            signature: PackageSignature {
+                public_key: signer.public_key(),
+                signature,
+            },
+        }
+    }
+}
+
+impl SignedPackage {
+    /// Returns an error if the signature of this package is not a valid signature of its signing
+    /// digest under the public key it carries.
+    pub fn verify_signature(&self, verifier: &dyn SignatureVerifier) -> Result<(), Report> {
+        verify_signature(&self.package, &self.signature, verifier)
+    }
+}
+
+// TRUST POLICY
+// ================================================================================================
+
+/// The provenance requirements of the packages loaded by a build pipeline.
+///
+/// A policy assigns owners to library namespaces: a package providing modules in a namespace with
+/// owners must be signed by one of them, so that, e.g., only the maintainers of `std` can publish
+/// packages containing `std` modules. Namespaces without owners are unrestricted, unless the policy
+/// requires all packages to be signed, in which case any valid signature is accepted for them.
+///
+/// The policy is enforced by [TrustPolicy::check] and by [TrustPolicy::load], which deserializes
+/// packages from the bytes of either a `.masp` file or a signed package. Library packages are
+/// added to an assembler under a policy via [AssemblerPackageExt::add_package], and libraries
+/// which are not packaged (e.g., `.masl` files) are checked with [TrustPolicy::check_library].
+/// The `miden` CLI enforces the policy configured by its `--trust` and `--require-signed` options
+/// on the libraries it loads.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TrustPolicy {
+    owners: BTreeMap<LibraryNamespace, BTreeSet<PublicKey>>,
+    require_signatures: bool,
+}
+
+impl TrustPolicy {
+    /// Returns a new policy which accepts all packages.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the owner with the specified public key to `namespace`.
+    ///
+    /// A namespace can have several owners, any of which can sign packages providing its modules.
+    pub fn with_owner(mut self, namespace: LibraryNamespace, public_key: PublicKey) -> Self {
+        self.owners.entry(namespace).or_default().insert(public_key);
+        self
+    }
+
+    /// Rejects the packages which are not signed.
+    pub fn with_required_signatures(mut self) -> Self {
+        self.require_signatures = true;
+        self
+    }
+
+    /// Returns the public keys of the owners of `namespace`, if it has any.
+    pub fn owners(&self, namespace: &LibraryNamespace) -> Option<&BTreeSet<PublicKey>> {
+        self.owners.get(namespace)
+    }
+
+    /// Returns true if this policy rejects the packages which are not signed.
+    pub fn requires_signatures(&self) -> bool {
+        self.require_signatures
+    }
+
+    /// Checks that `package`, with the specified signature, satisfies this policy.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The signature is not valid for the package.
+    /// - The package is not signed, and either this policy requires signatures or the package
+    ///   provides modules in an owned namespace.
+    /// - The package provides modules in a namespace which is not owned by its signer.
+    pub fn check(
+        &self,
+        package: &Package,
+        signature: Option<&PackageSignature>,
+        verifier: &dyn SignatureVerifier,
+    ) -> Result<(), Report> {
+        let signer = match signature {
+            Some(signature) => {
+                verify_signature(package, signature, verifier)?;
+                Some(&signature.public_key)
+            },
+            None => None,
+        };
+
+        self.check_provenance(&format!("package '{}'", package.name), package.namespaces(), signer)
+    }
+
+    /// Checks that `library`, which is not part of a package and thus not signed (e.g., a library
+    /// loaded from a `.masl` file), satisfies this policy.
+    ///
+    /// # Errors
+    /// Returns an error if this policy requires signatures, or if the library provides modules in
+    /// an owned namespace.
+    pub fn check_library(&self, library: &Library) -> Result<(), Report> {
+        let namespaces =
+            library.module_infos().map(|info| info.path().namespace().clone()).collect();
+        self.check_provenance("library", namespaces, None)
+    }
+
+    /// Checks that `package` satisfies this policy. See [TrustPolicy::check].
+    pub fn check_signed(
+        &self,
+        package: &SignedPackage,
+        verifier: &dyn SignatureVerifier,
+    ) -> Result<(), Report> {
+        self.check(&package.package, Some(&package.signature), verifier)
+    }
+
+    /// Deserializes a package, signed or not, from `bytes`, and checks that it satisfies this
+    /// policy.
+    ///
+    /// # Errors
+    /// Returns an error if `bytes` do not encode a package, or if the package does not satisfy this
+    /// policy (see [TrustPolicy::check]).
+    pub fn load(&self, bytes: &[u8], verifier: &dyn SignatureVerifier) -> Result<Package, Report> {
+        if SignedPackage::is_signed_package(bytes) {
+            let signed = SignedPackage::read_from_bytes(bytes).map_err(|err| {
+                Report::msg(format!("failed to deserialize signed package: {err}"))
+            })?;
+            self.check_signed(&signed, verifier)?;
+            Ok(signed.package)
+        } else {
+            let package = Package::read_from_bytes(bytes)
+                .map_err(|err| Report::msg(format!("failed to deserialize package: {err}")))?;
+            self.check(&package, None, verifier)?;
+            Ok(package)
+        }
+    }
+
+    /// Deserializes a library package, signed or not, from `bytes`, checks that it satisfies this
+    /// policy, and returns its library, e.g., for adding it to an assembler.
+    ///
+    /// # Errors
+    /// Returns an error if the package cannot be loaded (see [TrustPolicy::load]), or if it does
+    /// not contain a library.
+    pub fn load_library(
+        &self,
+        bytes: &[u8],
+        verifier: &dyn SignatureVerifier,
+    ) -> Result<Arc<Library>, Report> {
+        let package = self.load(bytes, verifier)?;
+        match package.mast {
+            MastArtifact::Library(library) => Ok(library),
+            MastArtifact::Executable(_) => Err(Report::msg(format!(
+                "expected package '{}' to contain a library, but got an executable",
+                package.name
+            ))),
+        }
+    }
+
+    // HELPERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Checks that the item described by `name`, which provides modules in the specified
+    /// namespaces and was signed by `signer` (if it is signed), satisfies this policy.
+    fn check_provenance(
+        &self,
+        name: &str,
+        namespaces: BTreeSet<LibraryNamespace>,
+        signer: Option<&PublicKey>,
+    ) -> Result<(), Report> {
+        if signer.is_none() && self.require_signatures {
+            return Err(Report::msg(format!("{name} is not signed, but signatures are required")));
+        }
+
+        for namespace in namespaces {
+            let Some(owners) = self.owners.get(&namespace) else {
+                continue;
+            };
+            match signer {
+                Some(signer) if owners.contains(signer) => (),
+                Some(signer) => {
+                    return Err(Report::msg(format!(
+                        "{name} provides modules in namespace '{namespace}', which is not owned by \
+                         its signer {}",
+                        DisplayHex::new(signer)
+                    )));
+                },
+                None => {
+                    return Err(Report::msg(format!(
+                        "{name} provides modules in namespace '{namespace}', but is not signed by \
+                         one of its owners"
+                    )));
+                },
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// ASSEMBLER INTEGRATION
+// ================================================================================================
+
+/// Adds library packages to an [Assembler] under a [TrustPolicy].
+pub trait AssemblerPackageExt {
+    /// Deserializes a library package, signed or not, from `bytes`, checks that it satisfies
+    /// `policy`, and adds its library to this assembler.
+    ///
+    /// # Errors
+    /// Returns an error if the package cannot be loaded as a library under `policy` (see
+    /// [TrustPolicy::load_library]), or if the library cannot be added to the assembler.
+    fn add_package(
+        &mut self,
+        bytes: &[u8],
+        policy: &TrustPolicy,
+        verifier: &dyn SignatureVerifier,
+    ) -> Result<(), Report>;
+}
+
+impl AssemblerPackageExt for Assembler {
+    fn add_package(
+        &mut self,
+        bytes: &[u8],
+        policy: &TrustPolicy,
+        verifier: &dyn SignatureVerifier,
+    ) -> Result<(), Report> {
+        let library = policy.load_library(bytes, verifier)?;
+        self.add_library(library)
+    }
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Returns an error if `signature` is not a valid signature of the signing digest of `package`.
+fn verify_signature(
+    package: &Package,
+    signature: &PackageSignature,
+    verifier: &dyn SignatureVerifier,
+) -> Result<(), Report> {
+    let PackageSignature { public_key, signature } = signature;
+    if verifier.verify(public_key, &package.signing_digest().as_bytes(), signature) {
+        Ok(())
+    } else {
+        Err(Report::msg(format!(
+            "invalid signature of package '{}' by key {}",
+            package.name,
+            DisplayHex::new(public_key)
+        )))
+    }
+}
@@ -0,0 +1,53 @@
+use std::time::Duration;
+
+use assembly::Assembler;
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use miden_processor::{DefaultHost, ExecutionOptions, StackInputs, execute};
+
+/// A loop applying the RPO permutation, dominated by the hasher chiplet.
+const HPERM_SOURCE: &str = "
+begin
+    padw padw padw
+    repeat.2000
+        hperm
+    end
+    dropw dropw dropw
+end";
+
+/// A loop growing and shrinking the stack, which requires inverting the stack depth for many
+/// rows when building the stack trace.
+const STACK_DEPTH_SOURCE: &str = "
+begin
+    repeat.2000
+        push.1.2.3.4.5.6.7.8.9.10.11.12.13.14.15.16
+        dropw dropw dropw dropw
+    end
+end";
+
+/// Benchmarks the execution of programs dominated by field arithmetic which has SIMD
+/// implementations; run with and without the `simd` feature to compare the implementations.
+fn field_arithmetic(c: &mut Criterion) {
+    let mut group = c.benchmark_group("field_arithmetic");
+    group.measurement_time(Duration::from_secs(10));
+
+    for (name, source) in [("hperm", HPERM_SOURCE), ("stack_depth", STACK_DEPTH_SOURCE)] {
+        let program = Assembler::default().assemble_program(source).unwrap();
+
+        group.bench_function(BenchmarkId::new("execute", name), |bench| {
+            bench.iter(|| {
+                execute(
+                    &program,
+                    StackInputs::default(),
+                    &mut DefaultHost::default(),
+                    ExecutionOptions::default(),
+                )
+                .unwrap()
+            });
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(field_arithmetic_group, field_arithmetic);
+criterion_main!(field_arithmetic_group);
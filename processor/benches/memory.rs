@@ -0,0 +1,89 @@
+use std::time::Duration;
+
+use assembly::Assembler;
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use miden_processor::{DefaultHost, ExecutionOptions, StackInputs, execute};
+
+/// Builds a linked list whose nodes are scattered across the whole address space, by storing at
+/// each node the address of the next node computed with a linear congruential generator, and then
+/// follows the pointers from the head of the list.
+const POINTER_CHASING_SOURCE: &str = "
+begin
+    push.4
+    repeat.5000
+        dup push.1664525 u32wrapping_mul push.1013904223 u32wrapping_add push.4294967292 u32and
+        dup movup.2 mem_store
+    end
+    drop
+
+    push.4
+    repeat.5000
+        mem_load
+    end
+    drop
+end";
+
+/// Writes and reads back scattered addresses in the memory of many short-lived contexts.
+const SCATTERED_CONTEXTS_SOURCE: &str = "
+proc.scatter
+    push.4
+    repeat.50
+        dup push.1664525 u32wrapping_mul push.1013904223 u32wrapping_add push.4294967292 u32and
+        dup movup.2 mem_store
+    end
+    mem_load drop
+end
+
+begin
+    repeat.100
+        call.scatter
+    end
+end";
+
+/// Writes and reads back a contiguous region of memory.
+const SEQUENTIAL_SOURCE: &str = "
+begin
+    push.0
+    repeat.5000
+        dup dup mem_store add.1
+    end
+    drop
+
+    push.0
+    repeat.5000
+        dup mem_load drop add.1
+    end
+    drop
+end";
+
+/// Benchmarks the execution of programs dominated by memory accesses with different access
+/// patterns.
+fn memory(c: &mut Criterion) {
+    let mut group = c.benchmark_group("memory");
+    group.measurement_time(Duration::from_secs(10));
+
+    for (name, source) in [
+        ("pointer_chasing", POINTER_CHASING_SOURCE),
+        ("scattered_contexts", SCATTERED_CONTEXTS_SOURCE),
+        ("sequential", SEQUENTIAL_SOURCE),
+    ] {
+        let program = Assembler::default().assemble_program(source).unwrap();
+
+        group.bench_function(BenchmarkId::new("execute", name), |bench| {
+            bench.iter(|| {
+                execute(
+                    &program,
+                    StackInputs::default(),
+                    &mut DefaultHost::default(),
+                    ExecutionOptions::default(),
+                )
+                .unwrap()
+            });
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(memory_group, memory);
+criterion_main!(memory_group);
@@ -0,0 +1,66 @@
+use std::time::Duration;
+
+use assembly::Assembler;
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use miden_processor::{
+    DefaultHost, ExecutionOptions, OpDispatch, StackInputs, execute, execute_without_trace,
+};
+
+/// A loop executing a mix of field, u32, stack manipulation and memory operations.
+const SOURCE: &str = "
+begin
+    push.1.2.3.4
+    repeat.1000
+        dup.3 dup.3 add dup.2 mul u32split drop
+        push.7 u32divmod add
+        dup mem_store.0 mem_load.0 eq add
+        swap movup.3 movdn.2 neg neg dup.1 inv mul add.1 sub.1
+        swapw swapw dup.2 drop
+    end
+    dropw
+end";
+
+/// Benchmarks the execution of a program using each operation dispatch strategy, both with and
+/// without generating the execution trace.
+fn op_dispatch(c: &mut Criterion) {
+    let mut group = c.benchmark_group("op_dispatch");
+    group.measurement_time(Duration::from_secs(10));
+
+    let program = Assembler::default().assemble_program(SOURCE).unwrap();
+
+    for op_dispatch in [OpDispatch::Table, OpDispatch::Match] {
+        let options = ExecutionOptions::default().with_op_dispatch(op_dispatch);
+
+        group.bench_function(BenchmarkId::new("execute", format!("{op_dispatch:?}")), |bench| {
+            bench.iter(|| {
+                execute(
+                    &program,
+                    StackInputs::default(),
+                    &mut DefaultHost::default(),
+                    options.clone(),
+                )
+                .unwrap()
+            });
+        });
+
+        group.bench_function(
+            BenchmarkId::new("execute_without_trace", format!("{op_dispatch:?}")),
+            |bench| {
+                bench.iter(|| {
+                    execute_without_trace(
+                        &program,
+                        StackInputs::default(),
+                        &mut DefaultHost::default(),
+                        options.clone(),
+                    )
+                    .unwrap()
+                });
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(op_dispatch_group, op_dispatch);
+criterion_main!(op_dispatch_group);
@@ -0,0 +1,209 @@
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
+
+use vm_core::{
+    AssemblyOp, Decorator,
+    crypto::hash::RpoDigest,
+    debuginfo::Location,
+    mast::{DecoratorId, MastForest},
+};
+
+// CALL STACK
+// ================================================================================================
+
+/// The stack of procedures which were being executed when a program failed.
+///
+/// The frames are ordered from the innermost procedure, i.e., the one executing the operation
+/// which failed, to the entrypoint of the program. A call stack is attached to an
+/// [ExecutionError](crate::ExecutionError) when the program is executed in debug mode.
+///
+/// Procedures invoked via `exec` may be inlined into their caller by the assembler. The procedure
+/// containing the failing instruction is reported as an inlined frame, but other inlined
+/// procedures are not part of the call stack.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CallStack {
+    frames: Vec<CallFrame>,
+}
+
+impl CallStack {
+    /// Returns the frames of the call stack, starting from the innermost procedure.
+    pub fn frames(&self) -> &[CallFrame] {
+        &self.frames
+    }
+
+    /// Returns true if the call stack has no frames.
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+}
+
+impl fmt::Display for CallStack {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "stack backtrace:")?;
+        for (i, frame) in self.frames.iter().enumerate() {
+            write!(f, "\n{i:>4}: {}", frame.name())?;
+            if let Some(location) = frame.location() {
+                let range = location.range();
+                write!(
+                    f,
+                    "\n             at {}:{}..{}",
+                    location.path,
+                    range.start.to_u32(),
+                    range.end.to_u32()
+                )?;
+            }
+            if let Some(instruction) = frame.instruction() {
+                write!(f, " ({instruction})")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A procedure on a [CallStack].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallFrame {
+    name: Option<String>,
+    digest: RpoDigest,
+    is_inlined: bool,
+    location: Option<Location>,
+    instruction: Option<String>,
+}
+
+impl CallFrame {
+    /// Returns the name of the procedure.
+    ///
+    /// The name is taken from the AsmOp decorators of the procedure, which are only present when
+    /// the program was assembled in debug mode. Otherwise, this is the hex-encoded MAST root of the
+    /// procedure.
+    pub fn name(&self) -> String {
+        self.name.clone().unwrap_or_else(|| self.digest.to_string())
+    }
+
+    /// Returns the MAST root of the procedure, or of the procedure it was inlined into.
+    pub fn digest(&self) -> RpoDigest {
+        self.digest
+    }
+
+    /// Returns true if the procedure was inlined into the procedure of the next frame.
+    pub fn is_inlined(&self) -> bool {
+        self.is_inlined
+    }
+
+    /// Returns the source location of the instruction which failed.
+    ///
+    /// This is only available for the innermost frame of a call stack, and only when the program
+    /// was assembled in debug mode.
+    pub fn location(&self) -> Option<&Location> {
+        self.location.as_ref()
+    }
+
+    /// Returns the instruction which failed, see [CallFrame::location].
+    pub fn instruction(&self) -> Option<&str> {
+        self.instruction.as_deref()
+    }
+}
+
+// CALL STACK TRACKER
+// ================================================================================================
+
+/// Tracks the procedures being executed, so that a [CallStack] can be built if execution fails.
+///
+/// A frame is entered whenever the processor starts executing a procedure root of a MAST forest,
+/// and exited once the procedure finishes executing. If the procedure fails instead, the frame is
+/// unwound into a [CallFrame], resolving the AsmOp decorators executed in the frame against the
+/// MAST forest of the procedure: the first one names the procedure, and in the innermost frame,
+/// the last one identifies the failing instruction.
+///
+/// Instructions which don't contribute any operations (e.g., `exec` and `call`) have no AsmOp
+/// decorators, so the call sites of the outer frames are not known.
+#[derive(Debug, Default)]
+pub struct CallStackTracker {
+    /// The frames currently being executed.
+    frames: Vec<Frame>,
+    /// The frames unwound due to an execution error, starting from the innermost one.
+    unwound: Vec<CallFrame>,
+}
+
+#[derive(Debug)]
+struct Frame {
+    digest: RpoDigest,
+    first_asmop: Option<DecoratorId>,
+    last_asmop: Option<DecoratorId>,
+}
+
+impl CallStackTracker {
+    /// Enters a frame for the procedure with the specified MAST root.
+    pub fn enter(&mut self, digest: RpoDigest) {
+        self.frames.push(Frame {
+            digest,
+            first_asmop: None,
+            last_asmop: None,
+        });
+    }
+
+    /// Exits the current frame.
+    pub fn exit(&mut self) {
+        self.frames.pop().expect("no frame to exit");
+    }
+
+    /// Exits the current frame due to an execution error, recording it in the call stack. The
+    /// frame must be a procedure root of the specified MAST forest.
+    pub fn unwind(&mut self, program: &MastForest) {
+        let frame = self.frames.pop().expect("no frame to unwind");
+        let name = get_asmop(program, frame.first_asmop).map(|asmop| asmop.context_name().into());
+
+        if self.unwound.is_empty() {
+            if let Some(asmop) = get_asmop(program, frame.last_asmop) {
+                let is_inlined = name.as_deref() != Some(asmop.context_name());
+                self.unwound.push(CallFrame {
+                    name: Some(asmop.context_name().into()),
+                    digest: frame.digest,
+                    is_inlined,
+                    location: asmop.location().cloned(),
+                    instruction: Some(asmop.op().into()),
+                });
+                if !is_inlined {
+                    return;
+                }
+            }
+        }
+
+        self.unwound.push(CallFrame {
+            name,
+            digest: frame.digest,
+            is_inlined: false,
+            location: None,
+            instruction: None,
+        });
+    }
+
+    /// Records the id of an AsmOp decorator executed in the current frame.
+    pub fn record_asmop(&mut self, decorator_id: DecoratorId) {
+        if let Some(frame) = self.frames.last_mut() {
+            frame.first_asmop.get_or_insert(decorator_id);
+            frame.last_asmop = Some(decorator_id);
+        }
+    }
+
+    /// Returns the call stack of the frames unwound so far.
+    pub fn take_call_stack(&mut self) -> CallStack {
+        CallStack {
+            frames: core::mem::take(&mut self.unwound),
+        }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the AsmOp decorator with the specified id in the MAST forest, if any.
+fn get_asmop(program: &MastForest, decorator_id: Option<DecoratorId>) -> Option<&AssemblyOp> {
+    match decorator_id.and_then(|id| program.get_decorator_by_id(id)) {
+        Some(Decorator::AsmOp(asmop)) => Some(asmop),
+        _ => None,
+    }
+}
@@ -30,7 +30,7 @@ use vm_core::{
     OPCODE_SPAN, OPCODE_SPLIT, OPCODE_SYSCALL, OPCODE_U32AND, OPCODE_U32XOR, ZERO,
 };
 
-use super::{Felt, FieldElement};
+use super::{Felt, FieldElement, MemoryState};
 use crate::{
     debug::{BusDebugger, BusMessage},
     trace::AuxColumnBuilder,
@@ -45,10 +45,49 @@ mod memory;
 // ================================================================================================
 
 /// Describes how to construct the execution trace of the chiplets bus auxiliary trace column.
-#[derive(Default)]
-pub struct BusColumnBuilder {}
+pub struct BusColumnBuilder<'a> {
+    /// The state of memory before execution started, whose words are written to the memory
+    /// chiplet at clock cycle 0.
+    initial_memory: &'a MemoryState,
+}
+
+impl<'a> BusColumnBuilder<'a> {
+    /// Returns a new builder of the chiplets bus column of an execution which started from the
+    /// specified state of memory.
+    pub fn new(initial_memory: &'a MemoryState) -> Self {
+        Self { initial_memory }
+    }
+}
+
+impl<E: FieldElement<BaseField = Felt>> AuxColumnBuilder<E> for BusColumnBuilder<'_> {
+    /// Constructs the requests for the writes of the initial memory to the memory chiplet.
+    ///
+    /// These writes are not requested by any operation of the execution, but are implied by the
+    /// public inputs of the proof, which commit to the initial memory.
+    fn init_requests(
+        &self,
+        _main_trace: &MainTrace,
+        alphas: &[E],
+        _debugger: &mut BusDebugger<E>,
+    ) -> E {
+        let mut requests = E::ONE;
+        for (ctx, addr, word) in self.initial_memory.iter() {
+            let message = MemoryWordMessage {
+                op_label: Felt::from(MEMORY_WRITE_WORD_LABEL),
+                ctx: ctx.into(),
+                addr: addr.into(),
+                clk: ZERO,
+                word,
+                source: "initial memory",
+            };
+            requests *= message.value(alphas);
+
+            #[cfg(any(test, feature = "bus-debugger"))]
+            _debugger.add_request(alloc::boxed::Box::new(message), alphas);
+        }
+        requests
+    }
 
-impl<E: FieldElement<BaseField = Felt>> AuxColumnBuilder<E> for BusColumnBuilder {
     /// Constructs the requests made by the VM-components to the chiplets at `row`.
     fn get_requests_at(
         &self,
@@ -3,7 +3,7 @@ use alloc::vec::Vec;
 use miden_air::trace::main_trace::MainTrace;
 use vm_core::Kernel;
 
-use super::{super::trace::AuxColumnBuilder, Felt, FieldElement};
+use super::{super::trace::AuxColumnBuilder, Felt, FieldElement, MemoryState};
 
 mod bus;
 pub use bus::BusColumnBuilder;
@@ -14,14 +14,15 @@ pub use virtual_table::ChipletsVTableColBuilder;
 /// Constructs the execution trace for chiplets-related auxiliary columns (used in multiset checks).
 pub struct AuxTraceBuilder {
     kernel: Kernel,
+    initial_memory: MemoryState,
 }
 
 impl AuxTraceBuilder {
     // CONSTRUCTORS
     // --------------------------------------------------------------------------------------------
 
-    pub fn new(kernel: Kernel) -> Self {
-        Self { kernel }
+    pub fn new(kernel: Kernel, initial_memory: MemoryState) -> Self {
+        Self { kernel, initial_memory }
     }
 
     // COLUMN TRACE CONSTRUCTOR
@@ -36,7 +37,7 @@ impl AuxTraceBuilder {
         rand_elements: &[E],
     ) -> Vec<Vec<E>> {
         let v_table_col_builder = ChipletsVTableColBuilder::new(self.kernel.clone());
-        let bus_col_builder = BusColumnBuilder::default();
+        let bus_col_builder = BusColumnBuilder::new(&self.initial_memory);
         let t_chip = v_table_col_builder.build_aux_column(main_trace, rand_elements);
         let b_chip = bus_col_builder.build_aux_column(main_trace, rand_elements);
 
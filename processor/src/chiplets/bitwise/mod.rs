@@ -1,8 +1,8 @@
 use alloc::vec::Vec;
 
 use miden_air::trace::chiplets::bitwise::{
-    A_COL_IDX, A_COL_RANGE, B_COL_IDX, B_COL_RANGE, BITWISE_AND, BITWISE_XOR, OUTPUT_COL_IDX,
-    PREV_OUTPUT_COL_IDX, TRACE_WIDTH,
+    A_COL_IDX, A_COL_RANGE, B_COL_IDX, B_COL_RANGE, BITWISE_AND, BITWISE_XOR, OP_CYCLE_LEN,
+    OUTPUT_COL_IDX, PREV_OUTPUT_COL_IDX, TRACE_WIDTH,
 };
 
 use super::{ExecutionError, Felt, TraceFragment, ZERO, utils::get_trace_len};
@@ -48,9 +48,13 @@ const INIT_TRACE_CAPACITY: usize = 128;
 ///   significant 4-bit limbs of the input values. With every subsequent row, the next most
 ///   significant 4-bit limb of the result is appended to it. Thus, by the 8th row, column `z`
 ///   contains the full result of the bitwise operation.
+///
+/// When trace generation is disabled, the rows of the trace are only counted, and not recorded.
 #[derive(Debug)]
 pub struct Bitwise {
     trace: [Vec<Felt>; TRACE_WIDTH],
+    /// The number of rows of the trace when trace generation is disabled, or None otherwise.
+    untraced_len: Option<usize>,
 }
 
 impl Bitwise {
@@ -63,7 +67,16 @@ impl Bitwise {
             .collect::<Vec<_>>()
             .try_into()
             .expect("failed to convert vector to array");
-        Self { trace }
+        Self { trace, untraced_len: None }
+    }
+
+    /// Returns a new [Bitwise] which computes the same results as one created via
+    /// [Bitwise::new], but only counts the rows of its execution trace instead of recording them.
+    pub fn new_without_trace() -> Self {
+        Self {
+            trace: Default::default(),
+            untraced_len: Some(0),
+        }
     }
 
     // PUBLIC ACCESSORS
@@ -72,7 +85,7 @@ impl Bitwise {
     /// Returns length of execution trace required to describe bitwise operations executed on the
     /// VM.
     pub fn trace_len(&self) -> usize {
-        get_trace_len(&self.trace)
+        self.untraced_len.unwrap_or_else(|| get_trace_len(&self.trace))
     }
 
     // TRACE MUTATORS
@@ -86,6 +99,10 @@ impl Bitwise {
     pub fn u32and(&mut self, a: Felt, b: Felt) -> Result<Felt, ExecutionError> {
         let a = assert_u32(a)?.as_int();
         let b = assert_u32(b)?.as_int();
+        if let Some(len) = self.untraced_len.as_mut() {
+            *len += OP_CYCLE_LEN;
+            return Ok(Felt::new(a & b));
+        }
         let mut result = 0u64;
 
         // append 8 rows to the trace, each row computing bitwise AND in 4 bit limbs starting with
@@ -121,6 +138,10 @@ impl Bitwise {
     pub fn u32xor(&mut self, a: Felt, b: Felt) -> Result<Felt, ExecutionError> {
         let a = assert_u32(a)?.as_int();
         let b = assert_u32(b)?.as_int();
+        if let Some(len) = self.untraced_len.as_mut() {
+            *len += OP_CYCLE_LEN;
+            return Ok(Felt::new(a ^ b));
+        }
         let mut result = 0u64;
 
         // append 8 rows to the trace, each row computing bitwise XOR in 4 bit limbs starting with
@@ -153,6 +174,7 @@ impl Bitwise {
 
     /// Fills the provided trace fragment with trace data from this bitwise helper instance.
     pub fn fill_trace(self, trace: &mut TraceFragment) {
+        assert!(self.untraced_len.is_none(), "trace generation was disabled");
         // make sure fragment dimensions are consistent with the dimensions of this trace
         debug_assert_eq!(self.trace_len(), trace.len(), "inconsistent trace lengths");
         debug_assert_eq!(TRACE_WIDTH, trace.width(), "inconsistent trace widths");
@@ -61,6 +61,19 @@ pub struct Hasher {
 }
 
 impl Hasher {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new [Hasher] which performs the same computations as one created via
+    /// [Hasher::default], but only counts the rows of its execution trace instead of recording
+    /// them.
+    pub fn new_without_trace() -> Self {
+        Self {
+            trace: HasherTrace::new_disabled(),
+            ..Self::default()
+        }
+    }
+
     // STATE ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -104,6 +117,10 @@ impl Hasher {
         if let Some((start_row, end_row)) = self.get_memoized_trace(expected_hash) {
             // copy the trace of a block with same hash instead of building it again.
             self.trace.copy_trace(&mut state, *start_row..*end_row);
+            if !self.trace.is_enabled() {
+                // the state is not recorded, but the copied block has the same hash
+                return (addr, expected_hash.into());
+            }
         } else {
             // perform the hash.
             self.trace.append_permutation(&mut state, LINEAR_HASH, RETURN_HASH);
@@ -183,6 +200,10 @@ impl Hasher {
             self.insert_to_memoized_trace_map(addr, expected_hash);
         } else {
             self.trace.copy_trace(&mut state, start_row..end_row);
+            if !self.trace.is_enabled() {
+                // the state is not recorded, but the copied block has the same hash
+                return (addr, expected_hash.into());
+            }
         }
 
         let result = get_digest(&state);
@@ -2,9 +2,12 @@ use alloc::vec::Vec;
 use core::ops::Range;
 
 use miden_air::trace::chiplets::hasher::NUM_ROUNDS;
+#[cfg(not(feature = "simd"))]
 use vm_core::chiplets::hasher::apply_round;
 
 use super::{Felt, HasherState, STATE_WIDTH, Selectors, TRACE_WIDTH, TraceFragment, ZERO};
+#[cfg(feature = "simd")]
+use crate::simd::apply_round;
 
 // HASHER TRACE
 // ================================================================================================
@@ -15,20 +18,37 @@ use super::{Felt, HasherState, STATE_WIDTH, Selectors, TRACE_WIDTH, TraceFragmen
 /// - 3 selector columns.
 /// - 12 columns describing hasher state.
 /// - 1 node index column used for Merkle path related computations.
+///
+/// When trace generation is disabled, rows are only counted, and not recorded.
 #[derive(Debug, Default)]
 pub struct HasherTrace {
     selectors: [Vec<Felt>; 3],
     hasher_state: [Vec<Felt>; STATE_WIDTH],
     node_index: Vec<Felt>,
+    /// The number of rows of the trace when trace generation is disabled, or None otherwise.
+    untraced_len: Option<usize>,
 }
 
 impl HasherTrace {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a blank [HasherTrace] which only counts the rows appended to it.
+    pub fn new_disabled() -> Self {
+        Self { untraced_len: Some(0), ..Self::default() }
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
     /// Returns current length of this execution trace.
     pub fn trace_len(&self) -> usize {
-        self.selectors[0].len()
+        self.untraced_len.unwrap_or(self.selectors[0].len())
+    }
+
+    /// Returns true if rows appended to this trace are recorded.
+    pub fn is_enabled(&self) -> bool {
+        self.untraced_len.is_none()
     }
 
     /// Returns the next row address. The address is equal to the current trace length + 1.
@@ -96,6 +116,10 @@ impl HasherTrace {
 
     /// Appends a new row to the execution trace based on the supplied parameters.
     fn append_row(&mut self, selectors: Selectors, state: &HasherState, index: Felt) {
+        if let Some(num_rows) = self.untraced_len.as_mut() {
+            *num_rows += 1;
+            return;
+        }
         for (trace_col, selector_val) in self.selectors.iter_mut().zip(selectors) {
             trace_col.push(selector_val);
         }
@@ -107,7 +131,14 @@ impl HasherTrace {
 
     /// Copies section of trace from the given range of start and end rows at the end of the trace.
     /// The hasher state of the last row is copied to the provided state input.
+    ///
+    /// When trace generation is disabled, the rows are only counted, and `state` is left
+    /// unchanged.
     pub fn copy_trace(&mut self, state: &mut [Felt; STATE_WIDTH], range: Range<usize>) {
+        if let Some(num_rows) = self.untraced_len.as_mut() {
+            *num_rows += range.len();
+            return;
+        }
         for selector in self.selectors.iter_mut() {
             selector.extend_from_within(range.clone());
         }
@@ -129,6 +160,7 @@ impl HasherTrace {
 
     /// Fills the provided trace fragment with trace data from this hasher trace instance.
     pub fn fill_trace(self, trace: &mut TraceFragment) {
+        assert!(self.is_enabled(), "trace generation was disabled");
         // make sure fragment dimensions are consistent with the dimensions of this trace
         debug_assert_eq!(self.trace_len(), trace.len(), "inconsistent trace lengths");
         debug_assert_eq!(TRACE_WIDTH, trace.width(), "inconsistent trace widths");
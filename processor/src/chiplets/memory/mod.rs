@@ -1,4 +1,8 @@
-use alloc::{collections::BTreeMap, vec::Vec};
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    vec::Vec,
+};
+use core::{fmt, ops::Range};
 
 use miden_air::{
     RowIndex,
@@ -9,7 +13,10 @@ use miden_air::{
         MEMORY_WRITE, V_COL_RANGE, WORD_COL_IDX,
     },
 };
-use vm_core::{WORD_SIZE, ZERO};
+use vm_core::{
+    WORD_SIZE, ZERO,
+    crypto::hash::{Rpo256, RpoDigest},
+};
 
 use super::{
     EMPTY_WORD, Felt, FieldElement, ONE, RangeChecker, TraceFragment, Word,
@@ -44,8 +51,13 @@ const INIT_MEM_VALUE: Word = EMPTY_WORD;
 /// memory address, and we can read and write elements to/from memory either individually or in
 /// groups of four.
 ///
-/// Memory for a given address is always initialized to zero. That is, reading from an address
-/// before writing to it will return ZERO.
+/// Memory for a given address is initialized to zero. That is, reading from an address before
+/// writing to it will return ZERO. Memory can instead be initialized with an arbitrary
+/// [MemoryState], e.g., the final memory state of a previous execution. As the memory chiplet
+/// constrains every word to be zero until it is first written to, each initialized word is then
+/// written at clock cycle 0 in the execution trace. These writes are requested from the chiplet
+/// on behalf of the verifier rather than by the stack, which is why the initial memory is part of
+/// the public inputs (see [MemoryState::commitment]).
 ///
 /// ## Execution trace
 /// The layout of the memory access trace is shown below.
@@ -86,14 +98,31 @@ const INIT_MEM_VALUE: Word = EMPTY_WORD;
 ///   as in the next row.
 ///
 /// For the first row of the trace, values in `d0`, `d1`, and `d_inv` are set to zeros.
+///
+/// ## Read-only memory
+/// Address ranges of a context can be marked as read-only, e.g., once lookup tables have been
+/// loaded into them. Writing to a read-only address fails with
+/// [ExecutionError::ReadOnlyMemoryWrite]. This is a debugging aid only: it is enforced by the
+/// processor, but not reflected in the constraints of the memory chiplet, so proofs do not attest
+/// that read-only addresses were not written to.
 #[derive(Debug, Default)]
 pub struct Memory {
-    /// Memory segment traces sorted by their execution context ID.
-    trace: BTreeMap<ContextId, MemorySegmentTrace>,
+    /// Memory segment traces, in the order of the first access to each segment.
+    segments: Vec<MemorySegmentTrace>,
+
+    /// Indexes into `segments`, keyed by execution context ID.
+    contexts: BTreeMap<ContextId, usize>,
+
+    /// The execution context ID and the index into `segments` of the most recently accessed
+    /// segment.
+    last_segment: Option<(ContextId, usize)>,
 
     /// Total number of entries in the trace (across all contexts); tracked separately so that we
     /// don't have to sum up lengths of all address trace vectors for all contexts all the time.
     num_trace_rows: usize,
+
+    /// Read-only address ranges of each execution context.
+    read_only: BTreeMap<ContextId, Vec<Range<u64>>>,
 }
 
 impl Memory {
@@ -112,7 +141,7 @@ impl Memory {
     /// Unlike read() which modifies the memory access trace, this method returns the value at the
     /// specified address (if one exists) without altering the memory access trace.
     pub fn get_value(&self, ctx: ContextId, addr: u32) -> Option<Felt> {
-        match self.trace.get(&ctx) {
+        match self.segment(ctx) {
             Some(segment) => segment.get_value(addr),
             None => None,
         }
@@ -124,7 +153,7 @@ impl Memory {
     /// # Errors
     /// - Returns an error if `addr` is not word aligned.
     pub fn get_word(&self, ctx: ContextId, addr: u32) -> Result<Option<Word>, ExecutionError> {
-        match self.trace.get(&ctx) {
+        match self.segment(ctx) {
             Some(segment) => segment
                 .get_word(addr)
                 .map_err(|_| ExecutionError::MemoryUnalignedWordAccessNoClk { addr, ctx }),
@@ -134,25 +163,101 @@ impl Memory {
 
     /// Returns the entire memory state for the specified execution context at the specified cycle.
     /// The state is returned as a vector of (address, value) tuples, and includes addresses which
-    /// have been accessed at least once.
+    /// have been initialized or accessed at least once.
     pub fn get_state_at(&self, ctx: ContextId, clk: RowIndex) -> Vec<(u64, Felt)> {
         if clk == 0 {
             return vec![];
         }
 
-        match self.trace.get(&ctx) {
+        match self.segment(ctx) {
             Some(segment) => segment.get_state_at(clk),
             None => vec![],
         }
     }
 
+    /// Returns the elements written in the specified execution context at the specified cycle.
+    /// The writes are returned as a vector of (address, value) tuples sorted by address.
+    ///
+    /// This includes the writes of the `MStore`, `MStoreW` and `Pipe` operations, and can be used
+    /// to watch for writes to specific addresses during execution.
+    pub fn get_writes_at(&self, ctx: ContextId, clk: RowIndex) -> Vec<(u64, Felt)> {
+        match self.segment(ctx) {
+            Some(segment) => segment.get_writes_at(clk),
+            None => vec![],
+        }
+    }
+
+    /// Returns the state of memory before execution started, across all execution contexts.
+    pub fn initial_state(&self) -> MemoryState {
+        let mut state = MemoryState::default();
+        for (ctx, segment) in self.segments() {
+            let words = segment.initial_words();
+            if !words.is_empty() {
+                state.0.insert(ctx, words.clone());
+            }
+        }
+        state
+    }
+
+    /// Returns the state of memory as of the last memory access, across all execution contexts.
+    ///
+    /// Words holding four ZERO elements are omitted from the returned state.
+    pub fn state(&self) -> MemoryState {
+        let mut state = MemoryState::default();
+        for (ctx, segment) in self.segments() {
+            let words: BTreeMap<u32, Word> =
+                segment.words().into_iter().filter(|(_, word)| *word != EMPTY_WORD).collect();
+            if !words.is_empty() {
+                state.0.insert(ctx, words);
+            }
+        }
+        state
+    }
+
     // STATE MUTATORS
     // --------------------------------------------------------------------------------------------
 
+    /// Sets the state of memory before execution starts.
+    ///
+    /// Memory which is not part of the specified state is initialized to ZERO. Each word of the
+    /// state takes up a row of the execution trace, in which the word is written at clock cycle 0.
+    pub fn set_initial_state(&mut self, state: MemoryState) {
+        debug_assert_eq!(self.num_trace_rows, 0, "memory already accessed");
+        for (ctx, words) in state.0 {
+            self.num_trace_rows += words.len();
+            self.segment_mut(ctx).set_initial_words(words);
+        }
+    }
+
+    /// Marks the addresses in the range [start_addr, end_addr) of the specified context as
+    /// read-only.
+    ///
+    /// # Errors
+    /// - Returns an error if `start_addr` is greater than `end_addr`.
+    /// - Returns an error if `end_addr` is greater than 2^32.
+    pub fn protect(
+        &mut self,
+        ctx: ContextId,
+        start_addr: u64,
+        end_addr: u64,
+    ) -> Result<(), ExecutionError> {
+        if start_addr > end_addr {
+            return Err(ExecutionError::InvalidMemoryRange { start_addr, end_addr });
+        }
+        if end_addr > u32::MAX as u64 + 1 {
+            return Err(ExecutionError::MemoryAddressOutOfBounds(end_addr));
+        }
+
+        if start_addr < end_addr {
+            self.read_only.entry(ctx).or_default().push(start_addr..end_addr);
+        }
+        Ok(())
+    }
+
     /// Returns the field element located in memory at the specified context/address.
     ///
-    /// If the specified address hasn't been previously written to, ZERO is returned. This
-    /// effectively implies that memory is initialized to ZERO.
+    /// If the specified address hasn't been previously written to, its initial value is returned.
+    /// Unless memory was initialized via [Memory::set_initial_state], this is ZERO.
     ///
     /// # Errors
     /// - Returns an error if the address is equal or greater than 2^32.
@@ -168,13 +273,13 @@ impl Memory {
             .try_into()
             .map_err(|_| ExecutionError::MemoryAddressOutOfBounds(addr.as_int()))?;
         self.num_trace_rows += 1;
-        self.trace.entry(ctx).or_default().read(ctx, addr, Felt::from(clk))
+        self.segment_mut(ctx).read(ctx, addr, Felt::from(clk))
     }
 
     /// Returns a word located in memory at the specified context/address.
     ///
-    /// If the specified address hasn't been previously written to, four ZERO elements are
-    /// returned. This effectively implies that memory is initialized to ZERO.
+    /// If the specified address hasn't been previously written to, its initial value is returned.
+    /// Unless memory was initialized via [Memory::set_initial_state], this is four ZERO elements.
     ///
     /// # Errors
     /// - Returns an error if the address is equal or greater than 2^32.
@@ -199,7 +304,7 @@ impl Memory {
         }
 
         self.num_trace_rows += 1;
-        self.trace.entry(ctx).or_default().read_word(ctx, addr, Felt::from(clk))
+        self.segment_mut(ctx).read_word(ctx, addr, Felt::from(clk))
     }
 
     /// Writes the provided field element at the specified context/address.
@@ -218,8 +323,10 @@ impl Memory {
             .as_int()
             .try_into()
             .map_err(|_| ExecutionError::MemoryAddressOutOfBounds(addr.as_int()))?;
+        self.check_writable(ctx, addr, 1, clk)?;
+
         self.num_trace_rows += 1;
-        self.trace.entry(ctx).or_default().write(ctx, addr, Felt::from(clk), value)
+        self.segment_mut(ctx).write(ctx, addr, Felt::from(clk), value)
     }
 
     /// Writes the provided word at the specified context/address.
@@ -247,8 +354,10 @@ impl Memory {
             });
         }
 
+        self.check_writable(ctx, addr, WORD_SIZE as u32, clk)?;
+
         self.num_trace_rows += 1;
-        self.trace.entry(ctx).or_default().write_word(ctx, addr, Felt::from(clk), value)
+        self.segment_mut(ctx).write_word(ctx, addr, Felt::from(clk), value)
     }
 
     // EXECUTION TRACE GENERATION
@@ -261,18 +370,19 @@ impl Memory {
         // trace; we also adjust the clock cycle so that delta value for the first row would end
         // up being ZERO. if the trace is empty, return without any further processing.
         let (mut prev_ctx, mut prev_addr, mut prev_clk) = match self.get_first_row_info() {
-            Some((ctx, addr, clk)) => (ctx, addr, clk.as_int() - 1),
+            Some((ctx, addr, clk)) => (ctx, addr, clk.as_int().wrapping_sub(1)),
             None => return,
         };
 
         // op range check index
         let mut row = memory_start_row;
 
-        for (&ctx, segment) in self.trace.iter() {
-            for (&addr, addr_trace) in segment.inner().iter() {
+        for (ctx, segment) in self.segments() {
+            for (addr, init_access, addr_trace) in segment.trace_rows() {
                 // when we start a new address, we set the previous value to all zeros. the effect
-                // of this is that memory is always initialized to zero.
-                for memory_access in addr_trace {
+                // of this is that memory is always initialized to zero, unless the word is written
+                // to at clock cycle 0 with its initial value.
+                for memory_access in init_access.iter().chain(addr_trace) {
                     let clk = memory_access.clk().as_int();
 
                     // compute delta as difference between context IDs, addresses, or clock cycles
@@ -281,7 +391,7 @@ impl Memory {
                     } else if prev_addr != addr {
                         u64::from(addr - prev_addr)
                     } else {
-                        clk - prev_clk
+                        clk.wrapping_sub(prev_clk)
                     };
 
                     let (delta_hi, delta_lo) = split_u32_into_u16(delta);
@@ -313,13 +423,14 @@ impl Memory {
         // into the trace. we expect the trace to be 15 columns wide.
         let mut row: RowIndex = 0.into();
 
-        for (ctx, segment) in self.trace {
+        for (ctx, segment) in self.segments() {
             let ctx = Felt::from(ctx);
-            for (addr, addr_trace) in segment.into_inner() {
+            for (addr, init_access, addr_trace) in segment.trace_rows() {
                 // when we start a new address, we set the previous value to all zeros. the effect
-                // of this is that memory is always initialized to zero.
+                // of this is that memory is always initialized to zero, unless the word is written
+                // to at clock cycle 0 with its initial value.
                 let felt_addr = Felt::from(addr);
-                for memory_access in addr_trace {
+                for memory_access in init_access.iter().chain(addr_trace) {
                     let clk = memory_access.clk();
                     let value = memory_access.word();
 
@@ -387,17 +498,78 @@ impl Memory {
     // HELPER METHODS
     // --------------------------------------------------------------------------------------------
 
-    /// Returns the context, address, and clock cycle of the first trace row, or None if the trace
-    /// is empty.
-    fn get_first_row_info(&self) -> Option<(ContextId, u32, Felt)> {
-        let (ctx, segment) = match self.trace.iter().next() {
-            Some((&ctx, segment)) => (ctx, segment),
-            None => return None,
+    /// Returns an error if any of the `len` addresses starting at `addr` in the specified context
+    /// is read-only.
+    fn check_writable(
+        &self,
+        ctx: ContextId,
+        addr: u32,
+        len: u32,
+        clk: RowIndex,
+    ) -> Result<(), ExecutionError> {
+        let Some(ranges) = self.read_only.get(&ctx) else {
+            return Ok(());
+        };
+
+        let start = addr as u64;
+        let end = start + len as u64;
+        for range in ranges {
+            if range.start < end && start < range.end {
+                let addr = start.max(range.start) as u32;
+                return Err(ExecutionError::ReadOnlyMemoryWrite {
+                    ctx,
+                    addr,
+                    clk: Felt::from(clk),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns an iterator over the memory segments and their execution context IDs, sorted by
+    /// context ID.
+    fn segments(&self) -> impl Iterator<Item = (ContextId, &MemorySegmentTrace)> {
+        self.contexts
+            .iter()
+            .map(|(&ctx, &segment_idx)| (ctx, &self.segments[segment_idx]))
+    }
+
+    /// Returns the memory segment of the specified execution context, or None if the segment
+    /// hasn't been accessed nor initialized.
+    fn segment(&self, ctx: ContextId) -> Option<&MemorySegmentTrace> {
+        let segment_idx = match self.last_segment {
+            Some((last_ctx, segment_idx)) if last_ctx == ctx => segment_idx,
+            _ => *self.contexts.get(&ctx)?,
         };
+        Some(&self.segments[segment_idx])
+    }
 
-        let (&addr, addr_trace) = segment.inner().iter().next().expect("empty memory segment");
+    /// Returns the memory segment of the specified execution context, creating it if it hasn't
+    /// been accessed nor initialized.
+    fn segment_mut(&mut self, ctx: ContextId) -> &mut MemorySegmentTrace {
+        let segment_idx = match self.last_segment {
+            Some((last_ctx, segment_idx)) if last_ctx == ctx => segment_idx,
+            _ => {
+                let segment_idx = *self.contexts.entry(ctx).or_insert_with(|| {
+                    self.segments.push(MemorySegmentTrace::default());
+                    self.segments.len() - 1
+                });
+                self.last_segment = Some((ctx, segment_idx));
+                segment_idx
+            },
+        };
+        &mut self.segments[segment_idx]
+    }
 
-        Some((ctx, addr, addr_trace[0].clk()))
+    /// Returns the context, address, and clock cycle of the first trace row, or None if the trace
+    /// is empty.
+    fn get_first_row_info(&self) -> Option<(ContextId, u32, Felt)> {
+        self.segments().find_map(|(ctx, segment)| {
+            segment.trace_rows().next().map(|(addr, init_access, addr_trace)| {
+                let first_access = init_access.as_ref().unwrap_or(&addr_trace[0]);
+                (ctx, addr, first_access.clk())
+            })
+        })
     }
 
     // TEST HELPERS
@@ -406,6 +578,219 @@ impl Memory {
     /// Returns the number of words that were accessed at least once across all contexts.
     #[cfg(test)]
     pub fn num_accessed_words(&self) -> usize {
-        self.trace.iter().fold(0, |acc, (_, s)| acc + s.num_accessed_words())
+        self.segments().fold(0, |acc, (_, s)| acc + s.num_accessed_words())
+    }
+}
+
+// MEMORY STATE
+// ================================================================================================
+
+/// The contents of memory across all execution contexts, keyed by context ID and by the address
+/// of the first element of each word.
+///
+/// The final memory state of an execution can be obtained via [crate::Process::memory_state], and
+/// used as the initial memory of a subsequent execution via
+/// [crate::Process::with_initial_memory]. Words which are not part of the state hold four ZERO
+/// elements.
+///
+/// Note that the IDs of execution contexts other than the root context depend on the clock cycle
+/// at which they were created, and thus usually differ between executions.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MemoryState(BTreeMap<ContextId, BTreeMap<u32, Word>>);
+
+impl MemoryState {
+    /// Returns a new empty [MemoryState].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a [MemoryState] in which the memory of the root context holds the specified
+    /// elements at consecutive addresses, starting at the specified address.
+    ///
+    /// This allows initializing large datasets in memory without loading them through the advice
+    /// provider. The elements are padded with ZEROs to a multiple of the word size.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The start address is not aligned to a word boundary.
+    /// - Any of the elements would be located beyond the end of the address space.
+    pub fn from_elements(start_addr: u32, elements: &[Felt]) -> Result<Self, ExecutionError> {
+        let end_addr = start_addr as u64 + elements.len() as u64;
+        if end_addr > u32::MAX as u64 + 1 {
+            return Err(ExecutionError::MemoryAddressOutOfBounds(end_addr - 1));
+        }
+
+        let mut state = Self::new();
+        for (i, chunk) in elements.chunks(WORD_SIZE).enumerate() {
+            let mut word = EMPTY_WORD;
+            word[..chunk.len()].copy_from_slice(chunk);
+            let addr = start_addr + (i * WORD_SIZE) as u32;
+            state.set_word(ContextId::root(), addr, word)?;
+        }
+        Ok(state)
+    }
+
+    /// Returns a [MemoryState] in which the memory of the root context holds the specified bytes,
+    /// starting at the specified address.
+    ///
+    /// Each element holds 4 bytes, encoded as a little-endian u32 value; the last element is
+    /// padded with zero bytes.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The start address is not aligned to a word boundary.
+    /// - Any of the elements would be located beyond the end of the address space.
+    pub fn from_bytes(start_addr: u32, bytes: &[u8]) -> Result<Self, ExecutionError> {
+        let elements: Vec<Felt> = bytes
+            .chunks(4)
+            .map(|chunk| {
+                let mut buf = [0_u8; 4];
+                buf[..chunk.len()].copy_from_slice(chunk);
+                Felt::from(u32::from_le_bytes(buf))
+            })
+            .collect();
+        Self::from_elements(start_addr, &elements)
+    }
+
+    /// Returns true if no word of memory holds a value other than four ZERO elements.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the commitment to this state, which is part of the public inputs of a proof of an
+    /// execution starting from this state.
+    ///
+    /// The commitment is the hash of the words of the state sorted by context ID and then by
+    /// address, each word being preceded by the word `[ctx, addr, 0, 0]`. The commitment to an
+    /// empty state is four ZERO elements.
+    pub fn commitment(&self) -> RpoDigest {
+        if self.is_empty() {
+            return RpoDigest::default();
+        }
+
+        let mut elements = Vec::new();
+        for (ctx, addr, word) in self.iter() {
+            elements.extend_from_slice(&[Felt::from(ctx), Felt::from(addr), ZERO, ZERO]);
+            elements.extend_from_slice(&word);
+        }
+        Rpo256::hash_elements(&elements)
+    }
+
+    /// Returns the IDs of the execution contexts whose memory holds at least one word.
+    pub fn contexts(&self) -> impl Iterator<Item = ContextId> + '_ {
+        self.0.keys().copied()
+    }
+
+    /// Returns the word located in memory of the specified context starting at the specified
+    /// address, or None if the word is not part of this state.
+    pub fn get_word(&self, ctx: ContextId, addr: u32) -> Option<Word> {
+        self.0.get(&ctx).and_then(|words| words.get(&addr)).copied()
+    }
+
+    /// Returns the element located in memory of the specified context at the specified address, or
+    /// None if the word containing the element is not part of this state.
+    pub fn get_value(&self, ctx: ContextId, addr: u32) -> Option<Felt> {
+        let idx = addr % WORD_SIZE as u32;
+        self.get_word(ctx, addr - idx).map(|word| word[idx as usize])
+    }
+
+    /// Returns an iterator over the (address, word) pairs located in memory of the specified
+    /// context, sorted by address.
+    pub fn words(&self, ctx: ContextId) -> impl Iterator<Item = (u32, Word)> + '_ {
+        self.0
+            .get(&ctx)
+            .into_iter()
+            .flat_map(|words| words.iter().map(|(&a, &w)| (a, w)))
+    }
+
+    /// Returns an iterator over the (address, element) pairs located in memory of the specified
+    /// context, sorted by address.
+    ///
+    /// All four elements of each word of the state are included, even if some of them are ZERO.
+    pub fn elements(&self, ctx: ContextId) -> impl Iterator<Item = (u32, Felt)> + '_ {
+        self.words(ctx).flat_map(|(addr, word)| {
+            word.into_iter().enumerate().map(move |(i, v)| (addr + i as u32, v))
+        })
+    }
+
+    /// Returns an iterator over the (context ID, address, word) triples of this state, sorted by
+    /// context ID and then by address.
+    pub fn iter(&self) -> impl Iterator<Item = (ContextId, u32, Word)> + '_ {
+        self.0
+            .iter()
+            .flat_map(|(&ctx, words)| words.iter().map(move |(&addr, &word)| (ctx, addr, word)))
+    }
+
+    /// Returns the words whose values differ between this state and the provided state, sorted by
+    /// context ID and then by address.
+    ///
+    /// Words which are not part of a state are considered to hold four ZERO elements.
+    pub fn diff(&self, other: &Self) -> Vec<MemoryWordDiff> {
+        let keys: BTreeSet<(ContextId, u32)> =
+            self.iter().chain(other.iter()).map(|(ctx, addr, _)| (ctx, addr)).collect();
+
+        keys.into_iter()
+            .filter_map(|(ctx, addr)| {
+                let old = self.get_word(ctx, addr).unwrap_or(EMPTY_WORD);
+                let new = other.get_word(ctx, addr).unwrap_or(EMPTY_WORD);
+                (old != new).then_some(MemoryWordDiff { ctx, addr, old, new })
+            })
+            .collect()
+    }
+
+    /// Sets the word located in memory of the specified context starting at the specified address.
+    ///
+    /// # Errors
+    /// Returns an error if the address is not aligned to a word boundary.
+    pub fn set_word(
+        &mut self,
+        ctx: ContextId,
+        addr: u32,
+        word: Word,
+    ) -> Result<(), ExecutionError> {
+        if addr % WORD_SIZE as u32 != 0 {
+            return Err(ExecutionError::MemoryUnalignedWordAccessNoClk { addr, ctx });
+        }
+
+        let words = self.0.entry(ctx).or_default();
+        if word == EMPTY_WORD {
+            words.remove(&addr);
+            if words.is_empty() {
+                self.0.remove(&ctx);
+            }
+        } else {
+            words.insert(addr, word);
+        }
+        Ok(())
+    }
+
+    /// Removes the memory of the specified execution context from this state, and returns it.
+    pub fn remove_context(&mut self, ctx: ContextId) -> BTreeMap<u32, Word> {
+        self.0.remove(&ctx).unwrap_or_default()
+    }
+}
+
+/// A word whose value differs between two memory states, as returned by [MemoryState::diff].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryWordDiff {
+    /// ID of the execution context whose memory holds the word.
+    pub ctx: ContextId,
+    /// Address of the first element of the word.
+    pub addr: u32,
+    /// Value of the word in the first state.
+    pub old: Word,
+    /// Value of the word in the second state.
+    pub new: Word,
+}
+
+impl fmt::Display for MemoryWordDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [o0, o1, o2, o3] = self.old.map(|v| v.as_int());
+        let [n0, n1, n2, n3] = self.new.map(|v| v.as_int());
+        write!(
+            f,
+            "ctx {}, addr {}: [{o0}, {o1}, {o2}, {o3}] -> [{n0}, {n1}, {n2}, {n3}]",
+            self.ctx, self.addr
+        )
     }
 }
@@ -1,14 +1,20 @@
-use alloc::{
-    collections::{BTreeMap, btree_map::Entry},
-    vec::Vec,
-};
+use alloc::{collections::BTreeMap, vec::Vec};
 
 use miden_air::RowIndex;
-use vm_core::WORD_SIZE;
+use vm_core::{WORD_SIZE, ZERO};
 
 use super::{Felt, INIT_MEM_VALUE, Word};
 use crate::{ContextId, ExecutionError};
 
+// CONSTANTS
+// ================================================================================================
+
+/// Number of words in a memory page.
+const WORDS_PER_PAGE: usize = 16;
+
+/// Base 2 logarithm of the number of elements in a memory page.
+const LOG_PAGE_SIZE: u32 = (WORDS_PER_PAGE * WORD_SIZE).ilog2();
+
 // MEMORY SEGMENT TRACE
 // ================================================================================================
 
@@ -17,31 +23,48 @@ use crate::{ContextId, ExecutionError};
 /// A memory segment is an isolated address space accessible from a specific execution context.
 /// Within each segment, the memory is word-addressable. That is, four field elements are located
 /// at each memory address, and we can read and write elements to/from memory in groups of four.
+///
+/// Words of the segment which were not initialized before execution hold four ZERO elements until
+/// they are first written to.
+///
+/// Accesses are tracked in fixed-size pages of consecutive words, allocated when any of their
+/// words is first accessed, so that address spaces which are both huge and sparse can be tracked
+/// compactly. Pages are looked up by their number in an ordered map, except for the most recently
+/// accessed page which is cached; this makes accesses to nearby addresses cheap, while iterating
+/// over the accessed words in ascending order of addresses remains straightforward.
 #[derive(Debug, Default)]
-pub struct MemorySegmentTrace(BTreeMap<u32, Vec<MemorySegmentAccess>>);
+pub struct MemorySegmentTrace {
+    /// Pages which were accessed at least once, in the order of the first access to each page.
+    pages: Vec<Page>,
+    /// Indexes into `pages`, keyed by page number.
+    page_table: BTreeMap<u32, usize>,
+    /// The page number and the index into `pages` of the most recently accessed page.
+    last_page: Option<(u32, usize)>,
+    /// Values of the words of the segment before execution started.
+    init: BTreeMap<u32, Word>,
+}
+
+/// Accesses to each word of a memory page, sorted by clock cycle; words which were never accessed
+/// have no accesses.
+type Page = [Vec<MemorySegmentAccess>; WORDS_PER_PAGE];
 
 impl MemorySegmentTrace {
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
     /// Returns the element located at the specified address, or None if the address hasn't been
-    /// accessed previously.
+    /// accessed previously nor initialized.
     ///
     /// Unlike read() which modifies the memory access trace, this method returns the value at the
     /// specified address (if one exists) without altering the memory access trace.
     pub fn get_value(&self, addr: u32) -> Option<Felt> {
         let (word_addr, addr_idx_in_word) = addr_to_word_addr_and_idx(addr);
 
-        match self.0.get(&word_addr) {
-            Some(addr_trace) => {
-                addr_trace.last().map(|access| access.word()[addr_idx_in_word as usize])
-            },
-            None => None,
-        }
+        self.current_word(word_addr).map(|word| word[addr_idx_in_word as usize])
     }
 
     /// Returns the word located in memory starting at the specified address, which must be word
-    /// aligned.
+    /// aligned, or None if the word hasn't been accessed previously nor initialized.
     ///
     /// # Errors
     /// - Returns an error if `addr` is not word aligned.
@@ -52,10 +75,7 @@ impl MemorySegmentTrace {
 
         let (word_addr, _) = addr_to_word_addr_and_idx(addr);
 
-        match self.0.get(&word_addr) {
-            Some(addr_trace) => Ok(addr_trace.last().map(|access| access.word())),
-            None => Ok(None),
-        }
+        Ok(self.current_word(word_addr))
     }
 
     /// Returns the entire memory state at the beginning of the specified cycle.
@@ -71,46 +91,105 @@ impl MemorySegmentTrace {
         // the previous cycle is the same as memory state the the beginning of the current cycle.
         let search_clk: u64 = (clk - 1).into();
 
-        for (&addr, addr_trace) in self.0.iter() {
+        let mut words: BTreeMap<u32, Word> = self.init.clone();
+        for (addr, addr_trace) in self.accesses() {
             match addr_trace.binary_search_by(|access| access.clk().as_int().cmp(&search_clk)) {
                 Ok(i) => {
-                    let word_addr = addr_trace[i].word();
-                    let addr: u64 = addr.into();
-                    result.extend([
-                        (addr, word_addr[0]),
-                        (addr + 1, word_addr[1]),
-                        (addr + 2, word_addr[2]),
-                        (addr + 3, word_addr[3]),
-                    ]);
+                    words.insert(addr, addr_trace[i].word());
                 },
                 Err(i) => {
                     // Binary search finds the index of the data with the specified clock cycle.
                     // Decrement the index to get the trace from the previously accessed clock
                     // cycle to insert into the results.
                     if i > 0 {
-                        let word_addr = addr_trace[i - 1].word();
-                        let addr: u64 = addr.into();
-                        result.extend([
-                            (addr, word_addr[0]),
-                            (addr + 1, word_addr[1]),
-                            (addr + 2, word_addr[2]),
-                            (addr + 3, word_addr[3]),
-                        ]);
+                        words.insert(addr, addr_trace[i - 1].word());
                     }
                 },
             }
         }
 
+        for (addr, word) in words {
+            let addr: u64 = addr.into();
+            result.extend([
+                (addr, word[0]),
+                (addr + 1, word[1]),
+                (addr + 2, word[2]),
+                (addr + 3, word[3]),
+            ]);
+        }
+
         result
     }
 
+    /// Returns the elements written at the specified cycle, as a vector of (address, value) tuples
+    /// sorted by address.
+    ///
+    /// A word write contributes all four elements of the word, while an element write contributes
+    /// only the element which was written.
+    pub fn get_writes_at(&self, clk: RowIndex) -> Vec<(u64, Felt)> {
+        let clk: u64 = clk.into();
+        let mut result: Vec<(u64, Felt)> = Vec::new();
+
+        for (addr, addr_trace) in self.accesses() {
+            let Ok(i) = addr_trace.binary_search_by(|access| access.clk().as_int().cmp(&clk))
+            else {
+                continue;
+            };
+            let access = &addr_trace[i];
+            if access.operation() != MemoryOperation::Write {
+                continue;
+            }
+
+            let word = access.word();
+            let addr: u64 = addr.into();
+            match access.access_type() {
+                MemoryAccessType::Element { addr_idx_in_word } => {
+                    let idx = addr_idx_in_word as usize;
+                    result.push((addr + idx as u64, word[idx]));
+                },
+                MemoryAccessType::Word => result.extend([
+                    (addr, word[0]),
+                    (addr + 1, word[1]),
+                    (addr + 2, word[2]),
+                    (addr + 3, word[3]),
+                ]),
+            }
+        }
+
+        result
+    }
+
+    /// Returns the values of all words of the segment which were either initialized or accessed,
+    /// as of the last memory access.
+    pub fn words(&self) -> BTreeMap<u32, Word> {
+        let mut words = self.init.clone();
+        for (addr, addr_trace) in self.accesses() {
+            words.insert(addr, addr_trace.last().expect("empty address trace").word());
+        }
+        words
+    }
+
+    /// Returns the values of the words of the segment before execution started, keyed by the
+    /// address of the first element of each word.
+    pub fn initial_words(&self) -> &BTreeMap<u32, Word> {
+        &self.init
+    }
+
     // STATE MUTATORS
     // --------------------------------------------------------------------------------------------
 
+    /// Sets the values of the specified words before execution starts, keyed by the address of the
+    /// first element of each word.
+    pub fn set_initial_words(&mut self, words: BTreeMap<u32, Word>) {
+        debug_assert!(self.pages.is_empty(), "memory segment already accessed");
+        self.init = words;
+    }
+
     /// Returns the element located at the specified address. The memory access is assumed to happen
     /// at the provided clock cycle.
     ///
-    /// If the element at the specified address hasn't been previously written to, ZERO is returned.
+    /// If the element at the specified address hasn't been previously written to, its initial value
+    /// is returned.
     ///
     /// # Errors
     /// - Returns an error if the same address is accessed more than once in the same clock cycle.
@@ -130,8 +209,8 @@ impl MemorySegmentTrace {
     /// Returns a word located in memory starting at the specified address, which must be word
     /// aligned. The memory access is assumed to happen at the provided clock cycle.
     ///
-    /// If the word starting at the specified address hasn't been previously written to, four ZERO
-    /// elements are returned. This effectively implies that memory is initialized to ZERO.
+    /// If the word starting at the specified address hasn't been previously written to, its initial
+    /// value is returned.
     ///
     /// # Preconditions
     /// - Assumes that the address is word aligned.
@@ -166,12 +245,14 @@ impl MemorySegmentTrace {
     ) -> Result<(), ExecutionError> {
         let (word_addr, addr_idx_in_word) = addr_to_word_addr_and_idx(addr);
 
-        match self.0.entry(word_addr) {
+        let (page_idx, word_idx) = self.get_or_insert_page(word_addr);
+        let addr_trace = &mut self.pages[page_idx][word_idx];
+        match addr_trace.last() {
             // If this is the first access to the ctx/word pair, then all values in the word are
-            // initialized to 0, except for when the address being written to.
-            Entry::Vacant(vacant_entry) => {
+            // set to their initial values, except for when the address being written to.
+            None => {
                 let word = {
-                    let mut word = Word::default();
+                    let mut word = self.init.get(&word_addr).copied().unwrap_or(INIT_MEM_VALUE);
                     word[addr_idx_in_word as usize] = value;
                     word
                 };
@@ -182,21 +263,20 @@ impl MemorySegmentTrace {
                     MemoryAccessType::Element { addr_idx_in_word },
                     word,
                 );
-                vacant_entry.insert(vec![access]);
+                addr_trace.push(access);
                 Ok(())
             },
             // If the ctx/word pair has been accessed before, then the values in the word are the
             // same as the previous access, except for when the address being written to.
-            Entry::Occupied(mut occupied_entry) => {
-                let addr_trace = occupied_entry.get_mut();
-                if addr_trace.last().expect("empty address trace").clk() == clk {
+            Some(last_access) => {
+                if last_access.clk() == clk {
                     // The same address is accessed more than once in the same clock cycle. This is
                     // an error, since this access is a write, and the only valid accesses are
                     // reads when in the same clock cycle.
                     Err(ExecutionError::IllegalMemoryAccess { ctx, addr, clk })
                 } else {
                     let word = {
-                        let mut last_word = addr_trace.last().expect("empty address trace").word();
+                        let mut last_word = last_access.word();
                         last_word[addr_idx_in_word as usize] = value;
 
                         last_word
@@ -238,24 +318,19 @@ impl MemorySegmentTrace {
 
         let access =
             MemorySegmentAccess::new(clk, MemoryOperation::Write, MemoryAccessType::Word, word);
-        match self.0.entry(word_addr) {
+        let (page_idx, word_idx) = self.get_or_insert_page(word_addr);
+        let addr_trace = &mut self.pages[page_idx][word_idx];
+        match addr_trace.last() {
             // All values in the word are set to the word being written.
-            Entry::Vacant(vacant_entry) => {
-                vacant_entry.insert(vec![access]);
-                Ok(())
+            Some(last_access) if last_access.clk() == clk => {
+                // The same address is accessed more than once in the same clock cycle. This is an
+                // error, since this access is a write, and the only valid accesses are reads when
+                // in the same clock cycle.
+                Err(ExecutionError::IllegalMemoryAccess { ctx, addr, clk })
             },
-            // All values in the word are set to the word being written.
-            Entry::Occupied(mut occupied_entry) => {
-                let addr_trace = occupied_entry.get_mut();
-                if addr_trace.last().expect("empty address trace").clk() == clk {
-                    // The same address is accessed more than once in the same clock cycle. This is
-                    // an error, since this access is a write, and the only valid accesses are
-                    // reads when in the same clock cycle.
-                    Err(ExecutionError::IllegalMemoryAccess { ctx, addr, clk })
-                } else {
-                    addr_trace.push(access);
-                    Ok(())
-                }
+            _ => {
+                addr_trace.push(access);
+                Ok(())
             },
         }
     }
@@ -263,14 +338,55 @@ impl MemorySegmentTrace {
     // INNER VALUE ACCESSORS
     // --------------------------------------------------------------------------------------------
 
-    /// Returns a reference to the map underlying this memory segment trace.
-    pub(super) fn inner(&self) -> &BTreeMap<u32, Vec<MemorySegmentAccess>> {
-        &self.0
+    /// Returns an iterator over the addresses of the words which were accessed at least once and
+    /// the accesses to each of them, sorted by address.
+    pub(super) fn accesses(&self) -> impl Iterator<Item = (u32, &[MemorySegmentAccess])> {
+        self.page_table.iter().flat_map(move |(&page_num, &page_idx)| {
+            self.pages[page_idx]
+                .iter()
+                .enumerate()
+                .filter(|(_, addr_trace)| !addr_trace.is_empty())
+                .map(move |(word_idx, addr_trace)| {
+                    let word_addr = (page_num << LOG_PAGE_SIZE) + (word_idx * WORD_SIZE) as u32;
+                    (word_addr, addr_trace.as_slice())
+                })
+        })
     }
 
-    /// Returns a map underlying this memory segment trace while consuming self.
-    pub(super) fn into_inner(self) -> BTreeMap<u32, Vec<MemorySegmentAccess>> {
-        self.0
+    /// Returns an iterator over the words described by the execution trace of the segment, sorted
+    /// by address, together with the accesses to each of them.
+    ///
+    /// The words are those which were either initialized or accessed. The initial value of an
+    /// initialized word is written to it at clock cycle 0, before it is accessed; this write is
+    /// returned separately from the accesses, as it is not part of the execution.
+    pub(super) fn trace_rows(
+        &self,
+    ) -> impl Iterator<Item = (u32, Option<MemorySegmentAccess>, &[MemorySegmentAccess])> {
+        let mut accesses = self.accesses().peekable();
+        let mut init = self.init.iter().peekable();
+        core::iter::from_fn(move || {
+            let next_access_addr = accesses.peek().map(|(addr, _)| *addr);
+            let next_init_addr = init.peek().map(|(addr, _)| **addr);
+            let addr = match (next_access_addr, next_init_addr) {
+                (Some(access_addr), Some(init_addr)) => access_addr.min(init_addr),
+                (Some(addr), None) | (None, Some(addr)) => addr,
+                (None, None) => return None,
+            };
+
+            let init_access =
+                init.next_if(|(init_addr, _)| **init_addr == addr).map(|(_, word)| {
+                    MemorySegmentAccess::new(
+                        ZERO,
+                        MemoryOperation::Write,
+                        MemoryAccessType::Word,
+                        *word,
+                    )
+                });
+            let addr_trace = accesses
+                .next_if(|&(access_addr, _)| access_addr == addr)
+                .map_or(&[][..], |(_, addr_trace)| addr_trace);
+            Some((addr, init_access, addr_trace))
+        })
     }
 
     // HELPER FUNCTIONS
@@ -290,31 +406,28 @@ impl MemorySegmentTrace {
         clk: Felt,
         access_type: MemoryAccessType,
     ) -> Result<Word, ExecutionError> {
-        match self.0.entry(word_addr) {
-            Entry::Vacant(vacant_entry) => {
-                // If this is the first access to the ctx/word pair, then all values in the word
-                // are initialized to 0.
-                let access = MemorySegmentAccess::new(
-                    clk,
-                    MemoryOperation::Read,
-                    access_type,
-                    INIT_MEM_VALUE,
-                );
-                vacant_entry.insert(vec![access]);
-                Ok(INIT_MEM_VALUE)
+        let (page_idx, word_idx) = self.get_or_insert_page(word_addr);
+        let addr_trace = &mut self.pages[page_idx][word_idx];
+        match addr_trace.last() {
+            None => {
+                // If this is the first access to the ctx/word pair, then the values in the word are
+                // their initial values.
+                let word = self.init.get(&word_addr).copied().unwrap_or(INIT_MEM_VALUE);
+                let access =
+                    MemorySegmentAccess::new(clk, MemoryOperation::Read, access_type, word);
+                addr_trace.push(access);
+                Ok(word)
             },
-            Entry::Occupied(mut occupied_entry) => {
+            Some(last_access) => {
                 // If the ctx/word pair has been accessed before, then the values in the word are
                 // the same as the previous access.
-                let addr_trace = occupied_entry.get_mut();
-                let last_access = addr_trace.last().expect("empty address trace");
                 if last_access.clk() == clk && last_access.operation() == MemoryOperation::Write {
                     // The same address is accessed more than once in the same clock cycle. This is
                     // an error, since the previous access was a write, and the only valid accesses
                     // are reads when in the same clock cycle.
                     Err(ExecutionError::IllegalMemoryAccess { ctx, addr: word_addr, clk })
                 } else {
-                    let last_word = addr_trace.last().expect("empty address trace").word();
+                    let last_word = last_access.word();
                     let access = MemorySegmentAccess::new(
                         clk,
                         MemoryOperation::Read,
@@ -329,10 +442,53 @@ impl MemorySegmentTrace {
         }
     }
 
+    /// Returns the value of the word at the specified address as of its last access, or its
+    /// initial value if the word hasn't been accessed previously, or None if it was neither
+    /// accessed nor initialized.
+    fn current_word(&self, word_addr: u32) -> Option<Word> {
+        match self.addr_trace(word_addr).last() {
+            Some(access) => Some(access.word()),
+            None => self.init.get(&word_addr).copied(),
+        }
+    }
+
+    /// Returns the accesses to the word at the specified address; this is empty if the word
+    /// hasn't been accessed previously, even if other words of its page were.
+    fn addr_trace(&self, word_addr: u32) -> &[MemorySegmentAccess] {
+        let (page_num, word_idx) = addr_to_page_num_and_word_idx(word_addr);
+        let page_idx = match self.last_page {
+            Some((last_page_num, page_idx)) if last_page_num == page_num => Some(page_idx),
+            _ => self.page_table.get(&page_num).copied(),
+        };
+        match page_idx {
+            Some(page_idx) => &self.pages[page_idx][word_idx],
+            None => &[],
+        }
+    }
+
+    /// Returns the index into `pages` of the page containing the word at the specified address,
+    /// and the index of the word in that page. The page is allocated if it hasn't been accessed
+    /// previously.
+    fn get_or_insert_page(&mut self, word_addr: u32) -> (usize, usize) {
+        let (page_num, word_idx) = addr_to_page_num_and_word_idx(word_addr);
+        if let Some((last_page_num, page_idx)) = self.last_page {
+            if last_page_num == page_num {
+                return (page_idx, word_idx);
+            }
+        }
+
+        let page_idx = *self.page_table.entry(page_num).or_insert_with(|| {
+            self.pages.push(Page::default());
+            self.pages.len() - 1
+        });
+        self.last_page = Some((page_num, page_idx));
+        (page_idx, word_idx)
+    }
+
     /// Returns the number of words that were accessed at least once.
     #[cfg(test)]
     pub fn num_accessed_words(&self) -> usize {
-        self.0.len()
+        self.accesses().count()
     }
 }
 
@@ -400,3 +556,11 @@ pub fn addr_to_word_addr_and_idx(addr: u32) -> (u32, u8) {
     let idx = addr % WORD_SIZE as u32;
     (addr - idx, idx as u8)
 }
+
+/// Splits a word address into the number of the page containing the word, and the index of the
+/// word within that page.
+fn addr_to_page_num_and_word_idx(word_addr: u32) -> (u32, usize) {
+    let page_num = word_addr >> LOG_PAGE_SIZE;
+    let word_idx = (word_addr as usize / WORD_SIZE) % WORDS_PER_PAGE;
+    (page_num, word_idx)
+}
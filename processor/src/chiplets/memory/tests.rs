@@ -1,4 +1,4 @@
-use alloc::vec::Vec;
+use alloc::{string::ToString, vec::Vec};
 
 use miden_air::{
     RowIndex,
@@ -13,7 +13,8 @@ use vm_core::{WORD_SIZE, Word, assert_matches};
 use super::{
     super::ZERO,
     CLK_COL_IDX, CTX_COL_IDX, D_INV_COL_IDX, D0_COL_IDX, D1_COL_IDX, EMPTY_WORD, Felt,
-    FieldElement, Memory, ONE, TraceFragment, V_COL_RANGE, WORD_COL_IDX,
+    FieldElement, Memory, MemoryState, MemoryWordDiff, ONE, TraceFragment, V_COL_RANGE,
+    WORD_COL_IDX,
     segment::{MemoryAccessType, MemoryOperation},
 };
 use crate::{ContextId, ExecutionError};
@@ -412,6 +413,59 @@ fn mem_write_read() {
     verify_memory_access(&trace, 8, memory_access, prev_row);
 }
 
+#[test]
+fn mem_sparse_addresses() {
+    let mut mem = Memory::default();
+    let ctx3 = ContextId::from(3);
+    let ctx7 = ContextId::from(7);
+
+    // write to addresses scattered across the address space, on both sides of page boundaries and
+    // in multiple contexts, in no particular order
+    let writes: [(ContextId, u32); 8] = [
+        (ctx7, 64),
+        (ContextId::root(), u32::MAX),
+        (ctx3, 1 << 20),
+        (ContextId::root(), 63),
+        (ctx7, 60),
+        (ContextId::root(), 0),
+        (ctx3, 123_456_789),
+        (ctx7, 64 + 3),
+    ];
+    for (clk, &(ctx, addr)) in writes.iter().enumerate() {
+        let clk = clk as u32 + 1;
+        mem.write(ctx, addr.into(), clk.into(), Felt::from(clk)).unwrap();
+    }
+    assert_eq!(7, mem.num_accessed_words());
+    assert_eq!(8, mem.trace_len());
+
+    for (clk, &(ctx, addr)) in writes.iter().enumerate() {
+        assert_eq!(Some(Felt::from(clk as u32 + 1)), mem.get_value(ctx, addr));
+    }
+    assert_eq!(Some(ZERO), mem.get_value(ctx7, 65));
+    assert_eq!(None, mem.get_value(ctx7, 68));
+    assert_eq!(None, mem.get_value(ctx3, 0));
+
+    // the trace is sorted by context, then by word address, and then by clock cycle
+    let trace = build_trace(mem, 8);
+    let rows: Vec<(Felt, Felt, Felt)> = (0..8)
+        .map(|row| (trace[CTX_COL_IDX][row], trace[WORD_COL_IDX][row], trace[CLK_COL_IDX][row]))
+        .collect();
+    let expected: Vec<(Felt, Felt, Felt)> = [
+        (0, 0, 6),
+        (0, 60, 4),
+        (0, u32::MAX - 3, 2),
+        (3, 1 << 20, 3),
+        (3, 123_456_788, 7),
+        (7, 60, 5),
+        (7, 64, 1),
+        (7, 64, 8),
+    ]
+    .into_iter()
+    .map(|(ctx, addr, clk)| (Felt::from(ctx as u32), Felt::from(addr), Felt::from(clk as u32)))
+    .collect();
+    assert_eq!(expected, rows);
+}
+
 #[test]
 fn mem_get_state_at() {
     let mut mem = Memory::default();
@@ -455,6 +509,223 @@ fn mem_get_state_at() {
     assert_eq!(mem.get_state_at(3.into(), clk), vec![]);
 }
 
+#[test]
+fn mem_get_writes_at() {
+    let mut mem = Memory::default();
+    let ctx = ContextId::root();
+
+    // Write a word at clk = 1, and read it back together with writing an element at clk = 2.
+    let word1234 = [ONE, 2_u32.into(), 3_u32.into(), 4_u32.into()];
+    mem.write_word(ctx, 40_u32.into(), 1.into(), word1234).unwrap();
+    mem.read_word(ctx, 40_u32.into(), 2.into()).unwrap();
+    mem.write(ctx, 9_u32.into(), 2.into(), 42_u32.into()).unwrap();
+
+    assert_eq!(mem.get_writes_at(ctx, 0.into()), vec![]);
+    assert_eq!(
+        mem.get_writes_at(ctx, 1.into()),
+        vec![(40, word1234[0]), (41, word1234[1]), (42, word1234[2]), (43, word1234[3])]
+    );
+
+    // reads are not reported, and only the written element of a word is
+    assert_eq!(mem.get_writes_at(ctx, 2.into()), vec![(9, 42_u32.into())]);
+    assert_eq!(mem.get_writes_at(3.into(), 2.into()), vec![]);
+}
+
+#[test]
+fn mem_initial_state() {
+    let ctx = ContextId::root();
+    let word1234 = [ONE, 2_u32.into(), 3_u32.into(), 4_u32.into()];
+    let word5678 = [5_u32.into(), 6_u32.into(), 7_u32.into(), 8_u32.into()];
+
+    let mut state = MemoryState::new();
+    state.set_word(ctx, 40, word1234).unwrap();
+    state.set_word(ctx, 44, word5678).unwrap();
+    state.set_word(ctx, 48, word1234).unwrap();
+    state.set_word(3.into(), 8, word5678).unwrap();
+    assert_matches!(
+        state.set_word(ctx, 41, word1234),
+        Err(ExecutionError::MemoryUnalignedWordAccessNoClk { addr: 41, .. })
+    );
+
+    let mut mem = Memory::default();
+    mem.set_initial_state(state.clone());
+    assert_eq!(mem.state(), state);
+    assert_eq!(mem.get_word(ctx, 40).unwrap(), Some(word1234));
+    assert_eq!(mem.get_value(3.into(), 9), Some(word5678[1]));
+
+    // initialized words are returned by reads which precede any write
+    assert_eq!(mem.read_word(ctx, 40_u32.into(), 1.into()).unwrap(), word1234);
+    assert_eq!(mem.read(3.into(), 10_u32.into(), 1.into()).unwrap(), word5678[2]);
+
+    // element writes preserve the initial values of the other elements of the word
+    mem.write(ctx, 45_u32.into(), 2.into(), 42_u32.into()).unwrap();
+    assert_eq!(
+        mem.get_word(ctx, 44).unwrap(),
+        Some([word5678[0], 42_u32.into(), word5678[2], word5678[3]])
+    );
+
+    // words which were never accessed keep their initial values, even if other words of the same
+    // page were accessed
+    assert_eq!(mem.get_word(ctx, 48).unwrap(), Some(word1234));
+    assert_eq!(mem.get_value(ctx, 49), Some(word1234[1]));
+    assert_eq!(mem.get_value(ctx, 52), None);
+
+    // words set to zero are omitted from the final state
+    mem.write_word(ctx, 40_u32.into(), 3.into(), EMPTY_WORD).unwrap();
+    mem.write(ctx, 100_u32.into(), 4.into(), ONE).unwrap();
+
+    let mut expected = MemoryState::new();
+    expected
+        .set_word(ctx, 44, [word5678[0], 42_u32.into(), word5678[2], word5678[3]])
+        .unwrap();
+    expected.set_word(ctx, 48, word1234).unwrap();
+    expected.set_word(ctx, 100, [ONE, ZERO, ZERO, ZERO]).unwrap();
+    expected.set_word(3.into(), 8, word5678).unwrap();
+    assert_eq!(mem.state(), expected);
+    assert_eq!(expected.contexts().collect::<Vec<_>>(), vec![ctx, 3.into()]);
+    assert_eq!(mem.initial_state(), state);
+
+    // every initialized word is written at clock cycle 0 in the trace, before it is accessed
+    assert_eq!(mem.trace_len(), 9);
+    let trace = build_trace(mem, 9);
+    for (row, ctx, addr, word) in [
+        (0, ctx, 40_u32, word1234),
+        (3, ctx, 44, word5678),
+        (5, ctx, 48, word1234),
+        (7, 3.into(), 8, word5678),
+    ] {
+        let row = read_trace_row(&trace, row);
+        assert_eq!(row[IS_READ_COL_IDX], MEMORY_WRITE);
+        assert_eq!(row[IS_WORD_ACCESS_COL_IDX], MEMORY_ACCESS_WORD);
+        assert_eq!(row[CTX_COL_IDX], Felt::from(ctx));
+        assert_eq!(row[WORD_COL_IDX], Felt::from(addr));
+        assert_eq!(row[CLK_COL_IDX], ZERO);
+        assert_eq!(row[V_COL_RANGE], word);
+    }
+    assert_eq!(read_trace_row(&trace, 1)[CLK_COL_IDX], ONE);
+    assert_eq!(read_trace_row(&trace, 6)[WORD_COL_IDX], Felt::from(100_u32));
+}
+
+#[test]
+fn mem_state_from_blob() {
+    let ctx = ContextId::root();
+
+    let elements: Vec<Felt> = (1..=6_u32).map(Felt::from).collect();
+    let state = MemoryState::from_elements(8, &elements).unwrap();
+    assert_eq!(state.contexts().collect::<Vec<_>>(), vec![ctx]);
+    assert_eq!(
+        state.get_word(ctx, 8),
+        Some([elements[0], elements[1], elements[2], elements[3]])
+    );
+    assert_eq!(state.get_word(ctx, 12), Some([elements[4], elements[5], ZERO, ZERO]));
+
+    let state = MemoryState::from_bytes(0, &[1, 0, 0, 0, 0, 1, 0, 0, 255]).unwrap();
+    assert_eq!(state.get_word(ctx, 0), Some([ONE, 256_u32.into(), 255_u32.into(), ZERO]));
+
+    assert_matches!(
+        MemoryState::from_elements(2, &elements),
+        Err(ExecutionError::MemoryUnalignedWordAccessNoClk { addr: 2, .. })
+    );
+    assert_matches!(
+        MemoryState::from_elements(u32::MAX - 3, &elements),
+        Err(ExecutionError::MemoryAddressOutOfBounds(_))
+    );
+}
+
+#[test]
+fn mem_state_queries_and_diff() {
+    let ctx = ContextId::root();
+    let ctx3 = ContextId::from(3);
+    let word1234 = [ONE, 2_u32.into(), 3_u32.into(), 4_u32.into()];
+    let word5678 = [5_u32.into(), 6_u32.into(), 7_u32.into(), 8_u32.into()];
+
+    let mut old = MemoryState::new();
+    old.set_word(ctx, 40, word1234).unwrap();
+    old.set_word(ctx, 44, word5678).unwrap();
+    old.set_word(ctx3, 8, word1234).unwrap();
+
+    assert_eq!(old.get_value(ctx, 42), Some(3_u32.into()));
+    assert_eq!(old.get_value(ctx, 48), None);
+    assert_eq!(
+        old.elements(ctx3).collect::<Vec<_>>(),
+        vec![(8, word1234[0]), (9, word1234[1]), (10, word1234[2]), (11, word1234[3])]
+    );
+    assert_eq!(
+        old.iter().collect::<Vec<_>>(),
+        vec![(ctx, 40, word1234), (ctx, 44, word5678), (ctx3, 8, word1234)]
+    );
+
+    // change one word, clear another one, and add a word in a new context
+    let mut new = old.clone();
+    new.set_word(ctx, 44, word1234).unwrap();
+    new.set_word(ctx3, 8, EMPTY_WORD).unwrap();
+    new.set_word(7.into(), 0, word5678).unwrap();
+
+    assert!(old.diff(&old).is_empty());
+    let diff = old.diff(&new);
+    assert_eq!(
+        diff,
+        vec![
+            MemoryWordDiff {
+                ctx,
+                addr: 44,
+                old: word5678,
+                new: word1234
+            },
+            MemoryWordDiff {
+                ctx: ctx3,
+                addr: 8,
+                old: word1234,
+                new: EMPTY_WORD
+            },
+            MemoryWordDiff {
+                ctx: 7.into(),
+                addr: 0,
+                old: EMPTY_WORD,
+                new: word5678
+            },
+        ]
+    );
+    assert_eq!(diff[0].to_string(), "ctx 0, addr 44: [5, 6, 7, 8] -> [1, 2, 3, 4]");
+}
+
+#[test]
+fn mem_read_only() {
+    let ctx = ContextId::root();
+    let mut mem = Memory::default();
+
+    mem.protect(ctx, 8, 13).unwrap();
+    mem.protect(ctx, 20, 20).unwrap();
+    assert_matches!(
+        mem.protect(ctx, 8, 4),
+        Err(ExecutionError::InvalidMemoryRange { start_addr: 8, end_addr: 4 })
+    );
+    assert_matches!(
+        mem.protect(ctx, 0, u32::MAX as u64 + 2),
+        Err(ExecutionError::MemoryAddressOutOfBounds(_))
+    );
+
+    // writes outside of the read-only range, or to other contexts, succeed
+    mem.write(ctx, 7_u32.into(), 1.into(), ONE).unwrap();
+    mem.write(ctx, 13_u32.into(), 2.into(), ONE).unwrap();
+    mem.write(ctx, 20_u32.into(), 3.into(), ONE).unwrap();
+    mem.write_word(3.into(), 8_u32.into(), 4.into(), [ONE; 4]).unwrap();
+
+    // writes to read-only addresses fail
+    assert_matches!(
+        mem.write(ctx, 12_u32.into(), 5.into(), ONE),
+        Err(ExecutionError::ReadOnlyMemoryWrite { addr: 12, .. })
+    );
+    assert_matches!(
+        mem.write_word(ctx, 12_u32.into(), 6.into(), [ONE; 4]),
+        Err(ExecutionError::ReadOnlyMemoryWrite { addr: 12, .. })
+    );
+
+    // reads of read-only addresses succeed
+    assert_eq!(mem.read(ctx, 8_u32.into(), 7.into()).unwrap(), ZERO);
+    assert_eq!(mem.trace_len(), 5);
+}
+
 // HELPER STRUCT & FUNCTIONS
 // ================================================================================================
 
@@ -21,6 +21,7 @@ pub(crate) use hasher::init_state_from_words;
 
 mod memory;
 use memory::Memory;
+pub use memory::{MemoryState, MemoryWordDiff};
 
 mod kernel_rom;
 use kernel_rom::KernelRom;
@@ -133,6 +134,19 @@ impl Chiplets {
         }
     }
 
+    /// Returns a new [Chiplets] component instantiated with the provided Kernel, whose hasher and
+    /// bitwise chiplets only count the rows of their execution traces instead of recording them.
+    ///
+    /// The lengths of the traces of all chiplets are the same as for [Chiplets::new], but the
+    /// execution trace of the chiplets cannot be built.
+    pub fn new_without_trace(kernel: Kernel) -> Self {
+        Self {
+            hasher: Hasher::new_without_trace(),
+            bitwise: Bitwise::new_without_trace(),
+            ..Self::new(kernel)
+        }
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -186,6 +200,7 @@ impl Chiplets {
         assert!(self.trace_len() + num_rand_rows <= trace_len, "target trace length too small");
 
         let kernel = self.kernel_rom.kernel().clone();
+        let initial_memory = self.memory.initial_state();
 
         // Allocate columns for the trace of the chiplets.
         let mut trace = (0..CHIPLETS_WIDTH)
@@ -197,7 +212,7 @@ impl Chiplets {
 
         ChipletsTrace {
             trace,
-            aux_builder: AuxTraceBuilder::new(kernel),
+            aux_builder: AuxTraceBuilder::new(kernel, initial_memory),
         }
     }
 
@@ -0,0 +1,163 @@
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    string::String,
+    vec::Vec,
+};
+
+use vm_core::{
+    Decorator,
+    crypto::hash::RpoDigest,
+    debuginfo::Location,
+    mast::{MastForest, MastNode},
+};
+
+// COVERAGE REPORT
+// ================================================================================================
+
+/// A record of the MAST basic blocks, and of the branches of split and loop nodes, which were
+/// executed by a program.
+///
+/// Nodes are identified by their MAST digest, so identical nodes are covered together, regardless
+/// of the procedure or the MAST forest they appear in. This makes it possible to measure the
+/// coverage of a library by merging the reports of several programs which use it, and mapping the
+/// result back to the source of the library via [CoverageReport::forest_coverage].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CoverageReport {
+    blocks: BTreeSet<RpoDigest>,
+    branches: BTreeMap<RpoDigest, BranchCoverage>,
+}
+
+impl CoverageReport {
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the digests of the basic blocks which were executed.
+    pub fn executed_blocks(&self) -> impl Iterator<Item = RpoDigest> + '_ {
+        self.blocks.iter().copied()
+    }
+
+    /// Returns true if the basic block with the specified digest was executed.
+    pub fn is_block_executed(&self, digest: RpoDigest) -> bool {
+        self.blocks.contains(&digest)
+    }
+
+    /// Returns the branches of the split or loop node with the specified digest which were
+    /// executed.
+    pub fn branch_coverage(&self, digest: RpoDigest) -> BranchCoverage {
+        self.branches.get(&digest).copied().unwrap_or_default()
+    }
+
+    // COMBINATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Adds the blocks and branches covered by `other` to this report.
+    pub fn merge(&mut self, other: &CoverageReport) {
+        self.blocks.extend(other.blocks.iter().copied());
+        for (&digest, branch) in other.branches.iter() {
+            let entry = self.branches.entry(digest).or_default();
+            entry.on_true |= branch.on_true;
+            entry.on_false |= branch.on_false;
+        }
+    }
+
+    /// Returns the coverage of the nodes of the specified MAST forest by this report.
+    ///
+    /// Instructions are mapped to source locations via the AsmOp decorators of the basic blocks
+    /// of the forest, which are only present when the forest was assembled in debug mode.
+    pub fn forest_coverage(&self, forest: &MastForest) -> ForestCoverage {
+        let mut coverage = ForestCoverage::default();
+        let mut instructions = BTreeMap::<(Location, String), bool>::new();
+        for node in forest.nodes() {
+            match node {
+                MastNode::Block(block) => {
+                    let executed = self.is_block_executed(block.digest());
+                    coverage.num_blocks += 1;
+                    coverage.num_executed_blocks += executed as usize;
+
+                    for &(_, decorator_id) in block.decorators() {
+                        if let Decorator::AsmOp(asmop) = &forest[decorator_id] {
+                            if let Some(location) = asmop.location() {
+                                let key = (location.clone(), asmop.op().into());
+                                *instructions.entry(key).or_default() |= executed;
+                            }
+                        }
+                    }
+                },
+                MastNode::Split(_) | MastNode::Loop(_) => {
+                    let branch = self.branch_coverage(node.digest());
+                    coverage.num_branches += 2;
+                    coverage.num_executed_branches +=
+                        branch.on_true as usize + branch.on_false as usize;
+                },
+                _ => (),
+            }
+        }
+        coverage.instructions = instructions
+            .into_iter()
+            .map(|((location, instruction), executed)| InstructionCoverage {
+                location,
+                instruction,
+                executed,
+            })
+            .collect();
+        coverage
+    }
+
+    // RECORDERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Records the execution of the basic block with the specified digest.
+    pub(crate) fn record_block(&mut self, digest: RpoDigest) {
+        self.blocks.insert(digest);
+    }
+
+    /// Records the execution of a branch of the split or loop node with the specified digest.
+    pub(crate) fn record_branch(&mut self, digest: RpoDigest, condition: bool) {
+        let branch = self.branches.entry(digest).or_default();
+        if condition {
+            branch.on_true = true;
+        } else {
+            branch.on_false = true;
+        }
+    }
+}
+
+/// The branches of a split or loop node which were executed.
+///
+/// For split nodes, these are the `true` and the `false` branches. For loop nodes, `on_true`
+/// indicates that the body of the loop was executed, and `on_false` that the loop was skipped
+/// without executing its body.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BranchCoverage {
+    pub on_true: bool,
+    pub on_false: bool,
+}
+
+// FOREST COVERAGE
+// ================================================================================================
+
+/// The coverage of the nodes of a MAST forest by a [CoverageReport].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ForestCoverage {
+    /// The number of basic blocks in the forest.
+    pub num_blocks: usize,
+    /// The number of basic blocks in the forest which were executed.
+    pub num_executed_blocks: usize,
+    /// The number of branches of the split and loop nodes in the forest, i.e., two per node.
+    pub num_branches: usize,
+    /// The number of branches of the split and loop nodes in the forest which were executed.
+    pub num_executed_branches: usize,
+    /// The instructions of the forest, ordered by their source location.
+    pub instructions: Vec<InstructionCoverage>,
+}
+
+/// An instruction of a MAST forest, and whether it was executed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstructionCoverage {
+    /// The source location of the instruction.
+    pub location: Location,
+    /// The instruction, e.g. `u32assert`.
+    pub instruction: String,
+    /// True if a basic block containing the instruction was executed.
+    pub executed: bool,
+}
@@ -6,7 +6,7 @@ use alloc::{
 use core::fmt;
 
 use miden_air::RowIndex;
-use vm_core::{AssemblyOp, FieldElement, Operation, StackOutputs};
+use vm_core::{AssemblyOp, FieldElement, Operation, StackOutputs, debuginfo::FileLineCol};
 
 use crate::{
     Chiplets, ChipletsLengths, Decoder, ExecutionError, Felt, Process, Stack, System,
@@ -46,10 +46,76 @@ impl fmt::Display for VmState {
     }
 }
 
+// BREAKPOINTS
+// ================================================================================================
+
+/// A condition under which stepping through the execution with [VmStateIterator::resume] pauses.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Breakpoint {
+    /// Pause at the `breakpoint` instructions of the program. These are only present in programs
+    /// assembled in debug mode.
+    Instruction,
+    /// Pause at the specified clock cycle.
+    Cycle(RowIndex),
+    /// Pause when the stack item at `index` (where `0` is the top of the stack) becomes `value`.
+    ///
+    /// The breakpoint fires on the step at which the item changes to `value`, rather than on
+    /// every step at which the item is equal to `value`.
+    StackItem { index: usize, value: Felt },
+    /// Pause when an operation writes to memory at an address in the range `start..=end` of the
+    /// current execution context. This is the case for the `MStore`, `MStoreW` and `Pipe`
+    /// operations.
+    ///
+    /// The breakpoint fires even if the value written is the same as the value already stored at
+    /// the address.
+    MemoryWrite { start: u32, end: u32 },
+}
+
+impl Breakpoint {
+    /// Returns true if this breakpoint fires when stepping from `prev` to `state`.
+    ///
+    /// `prev` is `None` if `state` is the first state stepped through. `writes` are the memory
+    /// writes performed by the operation which led to `state`, as returned by
+    /// [VmStateIterator::memory_writes].
+    pub fn fires(&self, prev: Option<&VmState>, state: &VmState, writes: &[(u64, Felt)]) -> bool {
+        match self {
+            Self::Instruction => state.asmop.as_ref().is_some_and(AsmOpInfo::should_break),
+            Self::Cycle(clk) => state.clk == *clk,
+            Self::StackItem { index, value } => {
+                let is_value = |state: &VmState| state.stack.get(*index) == Some(value);
+                is_value(state) && !prev.is_some_and(is_value)
+            },
+            Self::MemoryWrite { start, end } => {
+                let addrs = u64::from(*start)..=u64::from(*end);
+                writes.iter().any(|(addr, _)| addrs.contains(addr))
+            },
+        }
+    }
+}
+
+impl fmt::Display for Breakpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Instruction => f.write_str("breakpoint instruction"),
+            Self::Cycle(clk) => write!(f, "clk == {clk}"),
+            Self::StackItem { index, value } => write!(f, "stack[{index}] == {value}"),
+            Self::MemoryWrite { start, end } if start == end => write!(f, "write to mem[{start}]"),
+            Self::MemoryWrite { start, end } => write!(f, "write to mem[{start}..={end}]"),
+        }
+    }
+}
+
+// VM STATE ITERATOR
+// ================================================================================================
+
 /// Iterator that iterates through vm state at each step of the execution.
 ///
 /// This allows debugging or replaying ability to view various process state at each clock cycle. If
 /// the execution returned an error, it returns that error on the clock cycle it stopped.
+///
+/// In addition to stepping through the execution one cycle at a time, [Breakpoint]s can be
+/// registered with the iterator, and [VmStateIterator::resume] used to step forward until one of
+/// them fires.
 pub struct VmStateIterator {
     chiplets: Chiplets,
     decoder: Decoder,
@@ -60,6 +126,9 @@ pub struct VmStateIterator {
     asmop_idx: usize,
     forward: bool,
     trace_len_summary: TraceLenSummary,
+    breakpoints: Vec<Breakpoint>,
+    /// The clock cycle of the state most recently returned by the iterator, in either direction.
+    last_clk: Option<RowIndex>,
 }
 
 impl VmStateIterator {
@@ -77,6 +146,102 @@ impl VmStateIterator {
             asmop_idx: 0,
             forward: true,
             trace_len_summary,
+            breakpoints: Vec::new(),
+            last_clk: None,
+        }
+    }
+
+    // BREAKPOINTS
+    // --------------------------------------------------------------------------------------------
+
+    /// Registers `breakpoint` with this iterator, returning its index.
+    pub fn add_breakpoint(&mut self, breakpoint: Breakpoint) -> usize {
+        self.breakpoints.push(breakpoint);
+        self.breakpoints.len() - 1
+    }
+
+    /// Removes the breakpoint at `index`, shifting the indices of the breakpoints after it.
+    ///
+    /// Returns `None` if there is no breakpoint at `index`.
+    pub fn remove_breakpoint(&mut self, index: usize) -> Option<Breakpoint> {
+        (index < self.breakpoints.len()).then(|| self.breakpoints.remove(index))
+    }
+
+    /// Returns the breakpoints registered with this iterator.
+    pub fn breakpoints(&self) -> &[Breakpoint] {
+        &self.breakpoints
+    }
+
+    /// Returns the index of the first registered breakpoint which fires when stepping from `prev`
+    /// to `state`, if any.
+    pub fn fired_breakpoint(&self, prev: Option<&VmState>, state: &VmState) -> Option<usize> {
+        let writes = if self
+            .breakpoints
+            .iter()
+            .any(|breakpoint| matches!(breakpoint, Breakpoint::MemoryWrite { .. }))
+        {
+            self.memory_writes(state)
+        } else {
+            Vec::new()
+        };
+        self.breakpoints
+            .iter()
+            .position(|breakpoint| breakpoint.fires(prev, state, &writes))
+    }
+
+    /// Returns the memory writes performed by the operation which led to `state`, as a vector of
+    /// (address, value) tuples sorted by address.
+    ///
+    /// This allows watching for writes to specific addresses while stepping through the execution
+    /// in either direction, without diffing the memory of consecutive states.
+    pub fn memory_writes(&self, state: &VmState) -> Vec<(u64, Felt)> {
+        if state.clk == 0 {
+            return Vec::new();
+        }
+        // the operation which led to the state at `clk` was executed at cycle `clk - 1`
+        self.chiplets.memory.get_writes_at(state.ctx, state.clk - 1)
+    }
+
+    /// Returns the source location of the instruction executing the operation which led to
+    /// `state`, i.e., the location of the last instruction started at or before that operation.
+    ///
+    /// Source locations are only recorded if the process was executed in debug mode with the
+    /// source map of the program, see [Process::with_source_map](crate::Process::with_source_map).
+    pub fn source_location(&self, state: &VmState) -> Option<&FileLineCol> {
+        if state.clk == 0 {
+            return None;
+        }
+        // the operation which led to the state at `clk` was executed at cycle `clk - 1`
+        let clk = (state.clk - 1).as_usize();
+        let locations = self.decoder.debug_info().source_locations();
+        let num_started = locations.partition_point(|(start, _)| *start <= clk);
+        num_started.checked_sub(1).map(|idx| &locations[idx].1)
+    }
+
+    /// Steps forward through the execution until one of the registered breakpoints fires.
+    ///
+    /// Returns the state at which the iterator paused, together with the index of the breakpoint
+    /// which fired, or the final state of the execution and `None` if no breakpoint fired. If the
+    /// execution failed before any breakpoint fired, the error is returned instead.
+    ///
+    /// Returns `None` if there are no states left to step through.
+    pub fn resume(&mut self) -> Option<Result<(VmState, Option<usize>), ExecutionError>> {
+        // the state the iterator is paused at is only rebuilt when resuming, rather than being
+        // kept around on every step
+        let mut prev = self.last_clk.map(|clk| self.state_at(clk, None));
+        let mut stepped = false;
+        loop {
+            match self.next() {
+                Some(Ok(state)) => {
+                    if let Some(index) = self.fired_breakpoint(prev.as_ref(), &state) {
+                        return Some(Ok((state, Some(index))));
+                    }
+                    prev = Some(state);
+                    stepped = true;
+                },
+                Some(Err(err)) => return Some(Err(err)),
+                None => return prev.filter(|_| stepped).map(|state| Ok((state, None))),
+            }
         }
     }
 
@@ -146,32 +311,37 @@ impl VmStateIterator {
             self.forward = false;
         }
 
-        let ctx = self.system.get_ctx_at(self.clk);
-
-        let op = if self.clk == 0 {
-            None
-        } else {
-            Some(self.decoder.debug_info().operations()[self.clk - 1])
-        };
-
         let (asmop, is_start) = self.get_asmop();
         if is_start {
             self.asmop_idx -= 1;
         }
 
-        let result = Some(VmState {
-            clk: self.clk,
-            ctx,
-            op,
-            asmop,
-            fmp: self.system.get_fmp_at(self.clk),
-            stack: self.stack.get_state_at(self.clk),
-            memory: self.chiplets.memory.get_state_at(ctx, self.clk),
-        });
+        let state = self.state_at(self.clk, asmop);
+        self.last_clk = Some(self.clk);
 
         self.clk -= 1;
 
-        result
+        Some(state)
+    }
+
+    /// Returns the state at `clk`, with the provided asm op info.
+    fn state_at(&self, clk: RowIndex, asmop: Option<AsmOpInfo>) -> VmState {
+        let ctx = self.system.get_ctx_at(clk);
+        let op = if clk == 0 {
+            None
+        } else {
+            Some(self.decoder.debug_info().operations()[clk - 1])
+        };
+
+        VmState {
+            clk,
+            ctx,
+            op,
+            asmop,
+            fmp: self.system.get_fmp_at(clk),
+            stack: self.stack.get_state_at(clk),
+            memory: self.chiplets.memory.get_state_at(ctx, clk),
+        }
     }
 
     pub fn into_parts(self) -> (System, Decoder, Stack, Chiplets, Option<ExecutionError>) {
@@ -216,32 +386,17 @@ impl Iterator for VmStateIterator {
             self.forward = true;
         }
 
-        let ctx = self.system.get_ctx_at(self.clk);
-
-        let op = if self.clk == 0 {
-            None
-        } else {
-            Some(self.decoder.debug_info().operations()[self.clk - 1])
-        };
-
         let (asmop, is_start) = self.get_asmop();
         if is_start {
             self.asmop_idx += 1;
         }
 
-        let result = Some(Ok(VmState {
-            clk: self.clk,
-            ctx,
-            op,
-            asmop,
-            fmp: self.system.get_fmp_at(self.clk),
-            stack: self.stack.get_state_at(self.clk),
-            memory: self.chiplets.memory.get_state_at(ctx, self.clk),
-        }));
+        let state = self.state_at(self.clk, asmop);
+        self.last_clk = Some(self.clk);
 
         self.clk += 1;
 
-        result
+        Some(Ok(state))
     }
 }
 
@@ -12,6 +12,7 @@ use miden_air::{
 };
 use vm_core::{
     AssemblyOp,
+    debuginfo::FileLineCol,
     mast::{
         BasicBlockNode, CallNode, DynNode, JoinNode, LoopNode, MastForest, OP_BATCH_SIZE, SplitNode,
     },
@@ -569,6 +570,14 @@ impl Decoder {
         }
     }
 
+    /// Returns an empty instance of [Decoder] which does not record the execution trace.
+    pub fn new_without_trace(in_debug_mode: bool) -> Self {
+        Self {
+            trace: DecoderTrace::new_disabled(),
+            ..Self::new(in_debug_mode)
+        }
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -878,6 +887,11 @@ impl Decoder {
         self.debug_info.append_asmop(clk, asmop);
     }
 
+    /// Records the source location of the instruction starting at the specified clock cycle.
+    pub fn append_source_location(&mut self, clk: RowIndex, location: FileLineCol) {
+        self.debug_info.append_source_location(clk, location);
+    }
+
     // TEST METHODS
     // --------------------------------------------------------------------------------------------
 
@@ -958,6 +972,7 @@ pub struct DebugInfo {
     in_debug_mode: bool,
     operations: Vec<Operation>,
     assembly_ops: Vec<(usize, AssemblyOp)>,
+    source_locations: Vec<(usize, FileLineCol)>,
 }
 
 impl DebugInfo {
@@ -966,6 +981,7 @@ impl DebugInfo {
             in_debug_mode,
             operations: Vec::<Operation>::new(),
             assembly_ops: Vec::<(usize, AssemblyOp)>::new(),
+            source_locations: Vec::new(),
         }
     }
 
@@ -986,6 +1002,12 @@ impl DebugInfo {
         &self.assembly_ops
     }
 
+    /// Returns the source locations of the instructions executed in debug mode, along with the
+    /// clock cycles at which they started, in order.
+    pub fn source_locations(&self) -> &[(usize, FileLineCol)] {
+        &self.source_locations
+    }
+
     /// Adds an operation to the operations vector in debug mode.
     #[inline(always)]
     pub fn append_operation(&mut self, op: Operation) {
@@ -998,4 +1020,9 @@ impl DebugInfo {
     pub fn append_asmop(&mut self, clk: RowIndex, asmop: AssemblyOp) {
         self.assembly_ops.push((clk.into(), asmop));
     }
+
+    /// Appends the source location of the instruction starting at the specified clock cycle.
+    pub fn append_source_location(&mut self, clk: RowIndex, location: FileLineCol) {
+        self.source_locations.push((clk.into(), location));
+    }
 }
@@ -45,6 +45,8 @@ pub struct DecoderTrace {
     op_idx_trace: Vec<Felt>,
     op_batch_flag_trace: [Vec<Felt>; NUM_OP_BATCH_FLAGS],
     op_bit_extra_trace: [Vec<Felt>; NUM_OP_BITS_EXTRA_COLS],
+    /// When false, no rows are appended to the trace.
+    enabled: bool,
 }
 
 impl DecoderTrace {
@@ -61,6 +63,22 @@ impl DecoderTrace {
             op_idx_trace: Vec::with_capacity(MIN_TRACE_LEN),
             op_batch_flag_trace: new_array_vec(MIN_TRACE_LEN),
             op_bit_extra_trace: new_array_vec(MIN_TRACE_LEN),
+            enabled: true,
+        }
+    }
+
+    /// Initializes a blank [DecoderTrace] to which no rows are ever appended.
+    pub fn new_disabled() -> Self {
+        Self {
+            addr_trace: Vec::new(),
+            op_bits_trace: new_array_vec(0),
+            hasher_trace: new_array_vec(0),
+            group_count_trace: Vec::new(),
+            in_span_trace: Vec::new(),
+            op_idx_trace: Vec::new(),
+            op_batch_flag_trace: new_array_vec(0),
+            op_bit_extra_trace: new_array_vec(0),
+            enabled: false,
         }
     }
 
@@ -104,6 +122,9 @@ impl DecoderTrace {
     /// - Set operation index register to ZERO.
     /// - Set op_batch_flags to ZEROs.
     pub fn append_block_start(&mut self, parent_addr: Felt, op: Operation, h1: Word, h2: Word) {
+        if !self.enabled {
+            return;
+        }
         self.addr_trace.push(parent_addr);
         self.append_opcode(op);
 
@@ -148,6 +169,9 @@ impl DecoderTrace {
         is_call: Felt,
         is_syscall: Felt,
     ) {
+        if !self.enabled {
+            return;
+        }
         debug_assert!(is_loop_body.as_int() <= 1, "invalid is_loop_body");
         debug_assert!(is_loop.as_int() <= 1, "invalid is_loop");
         debug_assert!(is_call.as_int() <= 1, "invalid is_call");
@@ -191,6 +215,9 @@ impl DecoderTrace {
     /// - Set operation index register to ZERO.
     /// - Set op_batch_flags to ZEROs.
     pub fn append_loop_repeat(&mut self, loop_addr: Felt) {
+        if !self.enabled {
+            return;
+        }
         self.addr_trace.push(loop_addr);
         self.append_opcode(Operation::Repeat);
 
@@ -226,6 +253,9 @@ impl DecoderTrace {
         first_op_batch: &[Felt; OP_BATCH_SIZE],
         num_op_groups: Felt,
     ) {
+        if !self.enabled {
+            return;
+        }
         self.addr_trace.push(parent_addr);
         self.append_opcode(Operation::Span);
         for (i, &op_group) in first_op_batch.iter().enumerate() {
@@ -254,6 +284,9 @@ impl DecoderTrace {
     /// - Set operation index register to ZERO.
     /// - Set the op_batch_flags based on the current operation group count.
     pub fn append_respan(&mut self, op_batch: &[Felt; OP_BATCH_SIZE]) {
+        if !self.enabled {
+            return;
+        }
         self.addr_trace.push(self.last_addr());
         self.append_opcode(Operation::Respan);
         for (i, &op_group) in op_batch.iter().enumerate() {
@@ -295,6 +328,9 @@ impl DecoderTrace {
         group_ops_left: Felt,
         op_idx: Felt,
     ) {
+        if !self.enabled {
+            return;
+        }
         self.addr_trace.push(basic_block_addr);
         self.append_opcode(op);
 
@@ -329,6 +365,9 @@ impl DecoderTrace {
     /// - Set operation index register to ZERO.
     /// - Set op_batch_flags to ZEROs.
     pub fn append_span_end(&mut self, span_hash: Word, is_loop_body: Felt) {
+        if !self.enabled {
+            return;
+        }
         debug_assert!(is_loop_body.as_int() <= 1, "invalid loop body");
 
         self.addr_trace.push(self.last_addr());
@@ -372,6 +411,7 @@ impl DecoderTrace {
     ///   values from the last filled row. This is done so that the hash of the program is
     ///   propagated to the last row.
     pub fn into_vec(mut self, trace_len: usize, num_rand_rows: usize) -> Vec<Vec<Felt>> {
+        assert!(self.enabled, "trace generation was disabled");
         let own_len = self.trace_len();
         // make sure that only the duplicate rows will be overwritten with random values
         assert!(own_len + num_rand_rows <= trace_len, "target trace length too small");
@@ -498,6 +538,9 @@ impl DecoderTrace {
     /// The specified USER_OP_HELPERS in the `hasher_trace` are used as helper registers, since they
     /// are not required for hashing during execution of user operations.
     pub fn set_user_op_helpers(&mut self, values: &[Felt]) {
+        if !self.enabled {
+            return;
+        }
         assert!(values.len() <= USER_OP_HELPERS.len(), "too many values for helper columns");
 
         for (idx, value) in values.iter().enumerate() {
@@ -0,0 +1,264 @@
+use alloc::{
+    boxed::Box,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
+
+use vm_core::{
+    StackOutputs,
+    utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable},
+};
+
+use crate::{
+    ExecutionError, ExecutionOptions, Felt, Host, Process, Program, StackInputs, VmStateIterator,
+};
+
+// EXECUTION RECORD
+// ================================================================================================
+
+/// A cycle-by-cycle record of the execution of a program, which can be compared against the
+/// record of another execution of the same program and inputs.
+///
+/// Records are intended for checking that upgrading the VM does not change the behavior of
+/// existing programs: a record made by one version of the processor can be serialized, and then
+/// compared against a record made by another version via [ExecutionRecord::first_divergence].
+/// Records can also be built from the states of the VM reported by another version of the
+/// processor via [ExecutionRecord::new].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExecutionRecord {
+    steps: Vec<RecordedStep>,
+    outcome: Result<StackOutputs, String>,
+}
+
+impl ExecutionRecord {
+    /// Returns a new record of an execution which went through the specified states, one per
+    /// clock cycle, and ended with the specified outcome, i.e., with the outputs of the program or
+    /// the message of the error it failed with.
+    pub fn new(steps: Vec<RecordedStep>, outcome: Result<StackOutputs, String>) -> Self {
+        Self { steps, outcome }
+    }
+
+    /// Executes `program` against the provided inputs with the specified options, and records the
+    /// state of the VM at every clock cycle.
+    ///
+    /// The program is always executed in debug mode, as the record is built by replaying the
+    /// execution. Failing executions are recorded too: the record then contains the states of the
+    /// VM up to the failure (e.g., up to the cycle limit of `options`), and the message of the
+    /// error.
+    ///
+    /// # Errors
+    /// Returns an error if trace generation is disabled in `options`, as the states of the VM are
+    /// read from the execution trace.
+    pub fn record(
+        program: &Program,
+        stack_inputs: StackInputs,
+        host: &mut impl Host,
+        options: ExecutionOptions,
+    ) -> Result<Self, ExecutionError> {
+        if !options.enable_trace_generation() {
+            return Err(ExecutionError::TraceGenerationDisabled);
+        }
+
+        let mut process =
+            Process::new(program.kernel().clone(), stack_inputs, options.with_debugging());
+        let result = process.execute(program, host);
+        let outcome = result.as_ref().map(Clone::clone).map_err(ToString::to_string);
+
+        let mut states = VmStateIterator::new(process, result);
+        let mut steps = Vec::new();
+        while let Some(Ok(state)) = states.next() {
+            steps.push(RecordedStep {
+                ctx: state.ctx.into(),
+                stack: state.stack.clone(),
+                memory_writes: states.memory_writes(&state),
+            });
+        }
+
+        Ok(Self { steps, outcome })
+    }
+
+    /// Returns the number of clock cycles recorded.
+    pub fn num_cycles(&self) -> usize {
+        self.steps.len()
+    }
+
+    /// Returns the outputs of the recorded execution, or the message of the error it failed with.
+    pub fn outcome(&self) -> Result<&StackOutputs, &str> {
+        self.outcome.as_ref().map_err(String::as_str)
+    }
+
+    /// Returns the first difference between this record, which is treated as the expected one,
+    /// and `other`, or `None` if the records are the same.
+    ///
+    /// The states of the VM are compared cycle by cycle, so the returned divergence points at the
+    /// first operation whose effects differ between the two executions. If all common cycles
+    /// match, the lengths of the executions and then their outcomes are compared.
+    pub fn first_divergence(&self, other: &Self) -> Option<Divergence> {
+        for (clk, (expected, actual)) in self.steps.iter().zip(other.steps.iter()).enumerate() {
+            let kind = if expected.ctx != actual.ctx {
+                DivergenceKind::Context {
+                    expected: expected.ctx,
+                    actual: actual.ctx,
+                }
+            } else if expected.stack != actual.stack {
+                DivergenceKind::Stack {
+                    expected: expected.stack.clone(),
+                    actual: actual.stack.clone(),
+                }
+            } else if expected.memory_writes != actual.memory_writes {
+                DivergenceKind::MemoryWrites {
+                    expected: expected.memory_writes.clone(),
+                    actual: actual.memory_writes.clone(),
+                }
+            } else {
+                continue;
+            };
+            return Some(Divergence { clk: clk as u32, kind });
+        }
+
+        let clk = self.steps.len().min(other.steps.len()) as u32;
+        if self.steps.len() != other.steps.len() {
+            let kind = DivergenceKind::NumCycles {
+                expected: self.steps.len(),
+                actual: other.steps.len(),
+            };
+            return Some(Divergence { clk, kind });
+        }
+        if self.outcome != other.outcome {
+            let kind = DivergenceKind::Outcome {
+                expected: Box::new(self.outcome.clone()),
+                actual: Box::new(other.outcome.clone()),
+            };
+            return Some(Divergence { clk, kind });
+        }
+
+        None
+    }
+}
+
+impl Serializable for ExecutionRecord {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.steps.write_into(target);
+        match &self.outcome {
+            Ok(stack_outputs) => {
+                target.write_bool(true);
+                stack_outputs.write_into(target);
+            },
+            Err(message) => {
+                target.write_bool(false);
+                message.write_into(target);
+            },
+        }
+    }
+}
+
+impl Deserializable for ExecutionRecord {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let steps = Vec::<RecordedStep>::read_from(source)?;
+        let outcome = if source.read_bool()? {
+            Ok(StackOutputs::read_from(source)?)
+        } else {
+            Err(String::read_from(source)?)
+        };
+
+        Ok(Self { steps, outcome })
+    }
+}
+
+// RECORDED STEP
+// ================================================================================================
+
+/// The state of the VM at a single clock cycle, as recorded by [ExecutionRecord].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordedStep {
+    /// The execution context.
+    pub ctx: u32,
+    /// The full state of the stack, including the overflow table.
+    pub stack: Vec<Felt>,
+    /// The memory writes performed by the operation which led to this state, as (address, value)
+    /// tuples of the current execution context sorted by address.
+    pub memory_writes: Vec<(u64, Felt)>,
+}
+
+impl Serializable for RecordedStep {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u32(self.ctx);
+        self.stack.write_into(target);
+        target.write_usize(self.memory_writes.len());
+        for (addr, value) in self.memory_writes.iter() {
+            target.write_u64(*addr);
+            value.write_into(target);
+        }
+    }
+}
+
+impl Deserializable for RecordedStep {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let ctx = source.read_u32()?;
+        let stack = Vec::<Felt>::read_from(source)?;
+        let num_writes = source.read_usize()?;
+        let mut memory_writes = Vec::with_capacity(num_writes);
+        for _ in 0..num_writes {
+            memory_writes.push((source.read_u64()?, Felt::read_from(source)?));
+        }
+
+        Ok(Self { ctx, stack, memory_writes })
+    }
+}
+
+// DIVERGENCE
+// ================================================================================================
+
+/// The first difference between two [ExecutionRecord]s.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Divergence {
+    /// The clock cycle at which the records diverge.
+    pub clk: u32,
+    /// The state which differs at this clock cycle.
+    pub kind: DivergenceKind,
+}
+
+/// The state of the VM which differs between two [ExecutionRecord]s.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DivergenceKind {
+    /// The executions run in different execution contexts.
+    Context { expected: u32, actual: u32 },
+    /// The states of the stack differ.
+    Stack { expected: Vec<Felt>, actual: Vec<Felt> },
+    /// The operations write different values, or to different addresses, of memory.
+    MemoryWrites {
+        expected: Vec<(u64, Felt)>,
+        actual: Vec<(u64, Felt)>,
+    },
+    /// The executions run for a different number of cycles.
+    NumCycles { expected: usize, actual: usize },
+    /// The executions produce different outputs, or fail with different errors.
+    Outcome {
+        expected: Box<Result<StackOutputs, String>>,
+        actual: Box<Result<StackOutputs, String>>,
+    },
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let clk = self.clk;
+        match &self.kind {
+            DivergenceKind::Context { expected, actual } => {
+                write!(f, "clk={clk}: expected context {expected}, found context {actual}")
+            },
+            DivergenceKind::Stack { expected, actual } => {
+                write!(f, "clk={clk}: expected stack {expected:?}, found stack {actual:?}")
+            },
+            DivergenceKind::MemoryWrites { expected, actual } => {
+                write!(f, "clk={clk}: expected memory writes {expected:?}, found {actual:?}")
+            },
+            DivergenceKind::NumCycles { expected, actual } => {
+                write!(f, "clk={clk}: expected {expected} cycles, found {actual} cycles")
+            },
+            DivergenceKind::Outcome { expected, actual } => {
+                write!(f, "clk={clk}: expected outcome {expected:?}, found outcome {actual:?}")
+            },
+        }
+    }
+}
@@ -1,8 +1,10 @@
 use alloc::{boxed::Box, string::String};
-use core::error::Error;
+use core::{error::Error, time::Duration};
 
 use miden_air::RowIndex;
 use vm_core::{
+    IsaVersion,
+    debuginfo::FileLineCol,
     mast::{DecoratorId, MastNodeId},
     stack::MIN_STACK_DEPTH,
     utils::to_hex,
@@ -14,23 +16,38 @@ use super::{
     crypto::MerkleError,
     system::{FMP_MAX, FMP_MIN},
 };
-use crate::ContextId;
+use crate::{CallStack, ContextId, ForeignCallError};
 
 // EXECUTION ERROR
 // ================================================================================================
 
 #[derive(Debug, thiserror::Error)]
 pub enum ExecutionError {
+    #[error("exceeded the allowed number of elements read from the advice provider {0}")]
+    AdviceLimitExceeded(u64),
+    #[error(
+        "failed to fetch the value for key {} of the advice map",
+        to_hex(Felt::elements_as_bytes(key))
+    )]
+    AdviceMapFetchFailed {
+        key: Word,
+        #[source]
+        source: Box<dyn Error + Send + Sync + 'static>,
+    },
     #[error("value for key {} not present in the advice map", to_hex(Felt::elements_as_bytes(.0)))]
     AdviceMapKeyNotFound(Word),
     #[error("value for key {} already present in the advice map", to_hex(Felt::elements_as_bytes(.0)))]
     AdviceMapKeyAlreadyPresent(Word),
     #[error("advice stack read failed at step {0}")]
     AdviceStackReadFailed(RowIndex),
+    #[error("advice tape entry {position} does not record the requested {request}")]
+    AdviceTapeMismatch { position: usize, request: &'static str },
     #[error("illegal use of instruction {0} while inside a syscall")]
     CallInSyscall(&'static str),
     #[error("instruction `caller` used outside of kernel context")]
     CallerNotInSyscall,
+    #[error("execution was cancelled")]
+    Cancelled,
     #[error("external node with mast root {0} resolved to an external node")]
     CircularExternalNode(Digest),
     #[error("exceeded the allowed number of max cycles {0}")]
@@ -45,6 +62,10 @@ pub enum ExecutionError {
     DynamicNodeNotFound(Digest),
     #[error("error during processing of event in on_event handler")]
     EventError(#[source] Box<dyn Error + Send + Sync + 'static>),
+    #[error("event {0} is not allowed by the sandbox policy")]
+    EventNotAllowed(u32),
+    #[error("a handler for event {0} is already registered")]
+    EventHandlerAlreadyRegistered(u32),
     #[error("failed to execute Ext2Intt operation: {0}")]
     Ext2InttError(Ext2InttError),
     #[error("assertion failed at clock cycle {clk} with error code {err_code}{}",
@@ -58,6 +79,16 @@ pub enum ExecutionError {
         err_code: u32,
         err_msg: Option<String>,
     },
+    #[error("foreign call to function {function} failed")]
+    ForeignCallFailed {
+        function: String,
+        #[source]
+        source: ForeignCallError,
+    },
+    #[error(
+        "arguments of foreign function {function} take {num_elements} elements, but at most {MIN_STACK_DEPTH} elements fit at the top of the stack"
+    )]
+    ForeignCallTooManyArguments { function: String, num_elements: usize },
     #[error(
         "memory address {addr} in context {ctx} was read and written, or written twice, in the same clock cycle {clk}"
     )]
@@ -94,6 +125,8 @@ pub enum ExecutionError {
     MastNodeNotFoundInForest { node_id: MastNodeId },
     #[error("no MAST forest contains the procedure with root digest {root_digest}")]
     NoMastForestWithProcedure { root_digest: Digest },
+    #[error("exceeded the allowed number of memory chiplet rows {0}")]
+    MemoryLimitExceeded(u32),
     #[error("memory address cannot exceed 2^32 but was {0}")]
     MemoryAddressOutOfBounds(u64),
     #[error(
@@ -104,6 +137,8 @@ pub enum ExecutionError {
     // have access to the clock.
     #[error("word access at memory address {addr} in context {ctx} is unaligned")]
     MemoryUnalignedWordAccessNoClk { addr: u32, ctx: ContextId },
+    #[error("advice provider Merkle node storage could not be accessed")]
+    MerkleNodeStorageFailed(#[source] Box<dyn Error + Send + Sync + 'static>),
     #[error("merkle path verification failed for value {value} at index {index} in the Merkle tree with root {root} (error code: {err_code})", 
       value = to_hex(Felt::elements_as_bytes(value)),
       root = to_hex(root.as_bytes()),
@@ -120,16 +155,40 @@ pub enum ExecutionError {
     MerkleStoreMergeFailed(#[source] MerkleError),
     #[error("advice provider Merkle store backend update failed")]
     MerkleStoreUpdateFailed(#[source] MerkleError),
+    #[error("exceeded the allowed nesting depth of MAST nodes {0}")]
+    NestingDepthExceeded(u32),
     #[error("an operation expected a binary value, but received {0}")]
     NotBinaryValue(Felt),
+    #[error("an operation expected a power of two not greater than 2^32, but received {0}")]
+    NotPowerOfTwo(Felt),
     #[error("an operation expected a u32 value, but received {0} (error code: {1})")]
     NotU32Value(Felt, Felt),
+    #[error("failed to execute NTT operation: {0}")]
+    NttError(NttError),
     #[error("stack should have at most {MIN_STACK_DEPTH} elements at the end of program execution, but had {} elements", MIN_STACK_DEPTH + .0)]
     OutputStackOverflow(usize),
     #[error("a program has already been executed in this process")]
     ProgramAlreadyExecuted,
+    #[error("cannot build an execution trace when trace generation is disabled")]
+    TraceGenerationDisabled,
+    #[error("exceeded the allowed execution time of {0:?}")]
+    TimeLimitExceeded(Duration),
+    #[error("proof of the program with root {hex} was rejected by the self-check of the prover",
+      hex = to_hex(.program.as_bytes())
+    )]
+    ProofSelfCheckFailed {
+        program: Digest,
+        #[source]
+        source: Box<dyn Error + Send + Sync + 'static>,
+    },
     #[error("proof generation failed")]
     ProverError(#[source] ProverError),
+    #[error(
+        "memory address {addr} in context {ctx} is read-only, but was written to at clock cycle {clk}"
+    )]
+    ReadOnlyMemoryWrite { ctx: ContextId, addr: u32, clk: Felt },
+    #[error("event ID {0} is reserved for system events and cannot be handled by the host")]
+    ReservedEventId(u32),
     #[error("smt node {node_hex} not found", node_hex = to_hex(Felt::elements_as_bytes(.0)))]
     SmtNodeNotFound(Word),
     #[error("expected pre-image length of node {node_hex} to be a multiple of 8 but was {preimage_len}",
@@ -137,10 +196,81 @@ pub enum ExecutionError {
       preimage_len = .1
     )]
     SmtNodePreImageNotValid(Word, usize),
+    #[error(
+        "storage was updated to root {actual}, but the same update of the storage of the host results in root {expected}"
+    )]
+    StorageRootMismatch { expected: Digest, actual: Digest },
     #[error("syscall failed: procedure with root {hex} was not found in the kernel",
       hex = to_hex(.0.as_bytes())
     )]
     SyscallTargetNotInKernel(Digest),
+    #[error(
+        "program targets version {program} of the instruction set, but the VM supports version {supported}"
+    )]
+    UnsupportedIsaVersion {
+        program: IsaVersion,
+        supported: IsaVersion,
+    },
+    #[error("{error}\n{call_stack}")]
+    WithCallStack {
+        error: Box<ExecutionError>,
+        call_stack: CallStack,
+    },
+    #[error("{error}\n    at {location}")]
+    WithSourceLocation {
+        error: Box<ExecutionError>,
+        location: FileLineCol,
+    },
+}
+
+impl ExecutionError {
+    /// Returns the call stack at the point where execution failed, if one was captured.
+    ///
+    /// Call stacks are only captured when the program is executed in debug mode.
+    pub fn call_stack(&self) -> Option<&CallStack> {
+        match self {
+            Self::WithCallStack { call_stack, .. } => Some(call_stack),
+            _ => None,
+        }
+    }
+
+    /// Returns the source location of the instruction which failed, if it is known.
+    ///
+    /// Source locations are only known when the program is executed with its source map, see
+    /// [Process::with_source_map](crate::Process::with_source_map).
+    pub fn source_location(&self) -> Option<&FileLineCol> {
+        match self {
+            Self::WithSourceLocation { location, .. } => Some(location),
+            Self::WithCallStack { error, .. } => error.source_location(),
+            _ => None,
+        }
+    }
+
+    /// Returns this error without the source location attached to it, if any.
+    pub fn without_source_location(self) -> Self {
+        match self {
+            Self::WithSourceLocation { error, .. } => *error,
+            Self::WithCallStack { error, call_stack } => Self::WithCallStack {
+                error: Box::new(error.without_source_location()),
+                call_stack,
+            },
+            error => error,
+        }
+    }
+
+    /// Returns this error without the call stack attached to it, if any.
+    pub fn without_call_stack(self) -> Self {
+        match self {
+            Self::WithCallStack { error, .. } => *error,
+            error => error,
+        }
+    }
+}
+
+impl From<NttError> for ExecutionError {
+    fn from(value: NttError) -> Self {
+        Self::NttError(value)
+    }
 }
 
 impl From<Ext2InttError> for ExecutionError {
@@ -174,6 +304,47 @@ pub enum Ext2InttError {
     UninitializedMemoryAddress(u32),
 }
 
+// NTT ERROR
+// ================================================================================================
+
+#[derive(Debug, thiserror::Error)]
+pub enum NttError {
+    #[error("domain size must be a power of two, but was {0}")]
+    DomainSizeNotPowerOf2(u64),
+    #[error("domain size ({0} elements) is too small")]
+    DomainSizeTooSmall(u64),
+    #[error("address of the last input must be smaller than 2^32, but was {0}")]
+    InputEndAddressTooBig(u64),
+    #[error("address of the first input must be smaller than 2^32, but was {0}")]
+    InputStartAddressTooBig(u64),
+    #[error("address of the first input is not word aligned: {0}")]
+    InputStartNotWordAligned(u64),
+    #[error("uninitialized memory at address {0}")]
+    UninitializedMemoryAddress(u32),
+}
+
+// ADVICE INPUTS ERROR
+// ================================================================================================
+
+#[derive(Debug, thiserror::Error)]
+pub enum AdviceInputsError {
+    #[error("advice map key {} is already associated with different values", to_hex(.0.as_bytes()))]
+    ConflictingMapValues(Digest),
+    #[error(
+        "value {value} at index {index} of the advice map entry with key {} is not a valid field element",
+        to_hex(.key.as_bytes())
+    )]
+    InvalidMapValue { key: Digest, index: usize, value: u64 },
+    #[error("failed to build Merkle tree {tree} of the advice inputs")]
+    InvalidMerkleTree {
+        tree: usize,
+        #[source]
+        source: MerkleError,
+    },
+    #[error("value {value} at index {index} of the advice stack is not a valid field element")]
+    InvalidStackValue { index: usize, value: u64 },
+}
+
 #[cfg(test)]
 mod error_assertions {
     use super::*;
@@ -0,0 +1,219 @@
+use alloc::vec::Vec;
+
+use vm_core::{
+    Felt,
+    crypto::hash::{Rpo256, RpoDigest},
+    sys_events::SystemEvent,
+    utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable},
+};
+
+use crate::ContextId;
+
+// EVENT TIMELINE
+// ================================================================================================
+
+/// The events emitted by a program via the `emit` instruction, in the order in which they were
+/// emitted.
+///
+/// A timeline gives auditors and indexers access to the semantic event log of a program without
+/// instrumenting the host. Each event is recorded together with the clock cycle and the context it
+/// was emitted in, the procedures which were being executed, and optionally the state of the top
+/// of the stack. System events, which are handled by the VM itself, are recorded too.
+///
+/// The timeline is bound to the program which emitted the events via its hash, and can be
+/// attested to by signing its [EventTimeline::commitment].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventTimeline {
+    program_hash: RpoDigest,
+    events: Vec<EmittedEvent>,
+}
+
+impl EventTimeline {
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the hash of the program which emitted the events.
+    pub fn program_hash(&self) -> RpoDigest {
+        self.program_hash
+    }
+
+    /// Returns the emitted events, in the order in which they were emitted.
+    pub fn events(&self) -> &[EmittedEvent] {
+        &self.events
+    }
+
+    /// Returns the events with the specified id, in the order in which they were emitted.
+    pub fn events_with_id(&self, event_id: u32) -> impl Iterator<Item = &EmittedEvent> + '_ {
+        self.events.iter().filter(move |event| event.event_id == event_id)
+    }
+
+    /// Returns a commitment to this timeline, i.e., to the hash of the program and to all of the
+    /// recorded events, including their stack snapshots.
+    ///
+    /// The commitment is computed by hashing the elements of the timeline with RPO, so it can be
+    /// signed by whoever executed the program, and recomputed by anyone holding the timeline.
+    pub fn commitment(&self) -> RpoDigest {
+        let mut elements = Vec::from(self.program_hash.as_elements());
+        for event in self.events.iter() {
+            elements.extend([
+                Felt::from(event.event_id),
+                Felt::from(event.clk),
+                Felt::from(u32::from(event.ctx)),
+                Felt::from(event.procedures.len() as u32),
+            ]);
+            elements.extend(event.procedures.iter().flat_map(|digest| digest.as_elements()));
+            match &event.stack {
+                Some(stack) => {
+                    elements.push(Felt::from(stack.len() as u32));
+                    elements.extend_from_slice(stack);
+                },
+                None => elements.push(Felt::from(u32::MAX)),
+            }
+        }
+        Rpo256::hash_elements(&elements)
+    }
+}
+
+impl Serializable for EventTimeline {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.program_hash.write_into(target);
+        self.events.write_into(target);
+    }
+}
+
+impl Deserializable for EventTimeline {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let program_hash = RpoDigest::read_from(source)?;
+        let events = Vec::<EmittedEvent>::read_from(source)?;
+
+        Ok(Self { program_hash, events })
+    }
+}
+
+// EMITTED EVENT
+// ================================================================================================
+
+/// An event recorded in an [EventTimeline].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmittedEvent {
+    event_id: u32,
+    clk: u32,
+    ctx: ContextId,
+    procedures: Vec<RpoDigest>,
+    stack: Option<Vec<Felt>>,
+}
+
+impl EmittedEvent {
+    /// Returns the id of the event.
+    pub fn event_id(&self) -> u32 {
+        self.event_id
+    }
+
+    /// Returns true if the event is a system event, i.e., one which is handled by the VM rather
+    /// than forwarded to the host.
+    pub fn is_system_event(&self) -> bool {
+        SystemEvent::from_event_id(self.event_id).is_some()
+    }
+
+    /// Returns the clock cycle at which the event was emitted.
+    pub fn clk(&self) -> u32 {
+        self.clk
+    }
+
+    /// Returns the execution context in which the event was emitted.
+    pub fn ctx(&self) -> ContextId {
+        self.ctx
+    }
+
+    /// Returns the MAST roots of the procedures which were being executed when the event was
+    /// emitted, starting from the entrypoint of the program.
+    ///
+    /// Procedures invoked via `exec` may be inlined into their caller by the assembler, in which
+    /// case they are not part of the returned procedures.
+    pub fn procedures(&self) -> &[RpoDigest] {
+        &self.procedures
+    }
+
+    /// Returns the top 16 elements of the stack at the time the event was emitted, starting from
+    /// the top of the stack, if stack snapshots were enabled in the
+    /// [ExecutionOptions](crate::ExecutionOptions).
+    pub fn stack(&self) -> Option<&[Felt]> {
+        self.stack.as_deref()
+    }
+}
+
+impl Serializable for EmittedEvent {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u32(self.event_id);
+        target.write_u32(self.clk);
+        target.write_u32(self.ctx.into());
+        self.procedures.write_into(target);
+        self.stack.write_into(target);
+    }
+}
+
+impl Deserializable for EmittedEvent {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let event_id = source.read_u32()?;
+        let clk = source.read_u32()?;
+        let ctx = source.read_u32()?.into();
+        let procedures = Vec::<RpoDigest>::read_from(source)?;
+        let stack = Option::<Vec<Felt>>::read_from(source)?;
+
+        Ok(Self { event_id, clk, ctx, procedures, stack })
+    }
+}
+
+// EVENT RECORDER
+// ================================================================================================
+
+/// Records the events emitted during the execution of a program into an [EventTimeline].
+///
+/// The recorder keeps track of the procedures being executed: a procedure is entered whenever the
+/// processor starts executing a procedure root of a MAST forest, and exited once the procedure
+/// finishes executing.
+#[derive(Debug, Default)]
+pub struct EventRecorder {
+    stack_snapshots: bool,
+    procedures: Vec<RpoDigest>,
+    events: Vec<EmittedEvent>,
+}
+
+impl EventRecorder {
+    /// Returns a new recorder, which records the top of the stack with every event if
+    /// `stack_snapshots` is true.
+    pub fn new(stack_snapshots: bool) -> Self {
+        Self { stack_snapshots, ..Default::default() }
+    }
+
+    /// Returns true if the top of the stack should be recorded with every event.
+    pub fn stack_snapshots(&self) -> bool {
+        self.stack_snapshots
+    }
+
+    /// Enters the procedure with the specified MAST root.
+    pub fn enter(&mut self, digest: RpoDigest) {
+        self.procedures.push(digest);
+    }
+
+    /// Exits the procedure currently being executed.
+    pub fn exit(&mut self) {
+        self.procedures.pop().expect("no procedure to exit");
+    }
+
+    /// Records an event emitted at the specified clock cycle and context.
+    pub fn record(&mut self, event_id: u32, clk: u32, ctx: ContextId, stack: Option<Vec<Felt>>) {
+        self.events.push(EmittedEvent {
+            event_id,
+            clk,
+            ctx,
+            procedures: self.procedures.clone(),
+            stack,
+        });
+    }
+
+    /// Returns the timeline of the events recorded for the program with the specified hash.
+    pub fn into_timeline(self, program_hash: RpoDigest) -> EventTimeline {
+        EventTimeline { program_hash, events: self.events }
+    }
+}
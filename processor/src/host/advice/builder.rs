@@ -0,0 +1,322 @@
+use alloc::vec::Vec;
+
+use vm_core::crypto::{hash::Rpo256, merkle::MerkleTree};
+
+use super::{AdviceInputs, Felt, MerkleMultiProof, RpoDigest, Word};
+use crate::AdviceInputsError;
+
+// ADVICE INPUTS BUILDER
+// ================================================================================================
+
+/// A builder of [AdviceInputs] with typed helpers for the most common kinds of advice.
+///
+/// All inputs are validated as they are added to the builder. Since the methods of the builder
+/// can be chained, validation errors are deferred until [AdviceInputsBuilder::build] is invoked,
+/// which returns the first error encountered; all inputs added after an invalid input are
+/// ignored. Errors identify the offending input, e.g., by its position in the advice stack.
+///
+/// For example, the following advice inputs contain a word which is loaded by `adv_loadw`, a
+/// Merkle tree, and a list of values keyed by their hash, as expected by `adv.push_mapval`:
+/// ```
+/// # use miden_processor::{AdviceInputsBuilder, ONE, ZERO, crypto::NodeIndex};
+/// let builder = AdviceInputsBuilder::new()
+///     .with_word([ONE, ZERO, ZERO, ZERO])
+///     .with_merkle_tree([[ONE; 4], [ZERO; 4]])
+///     .with_hashed_map_values([1, 2, 3]);
+/// let root = builder.merkle_roots()[0];
+/// let advice_inputs = builder.build().unwrap();
+///
+/// assert_eq!(advice_inputs.stack(), [ONE, ZERO, ZERO, ZERO]);
+/// assert!(advice_inputs.merkle_store().get_node(root, NodeIndex::new(1, 0).unwrap()).is_ok());
+/// ```
+#[derive(Debug, Default)]
+pub struct AdviceInputsBuilder {
+    inputs: AdviceInputs,
+    merkle_roots: Vec<RpoDigest>,
+    error: Option<AdviceInputsError>,
+}
+
+impl AdviceInputsBuilder {
+    /// Returns a new builder without any advice inputs.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // ADVICE STACK
+    // --------------------------------------------------------------------------------------------
+
+    /// Appends the provided values to the advice stack.
+    ///
+    /// Values appended first are read first, i.e., they end up closer to the top of the stack.
+    ///
+    /// A value which is not a valid field element results in an
+    /// [AdviceInputsError::InvalidStackValue] error.
+    pub fn with_stack_values(self, values: impl IntoIterator<Item = u64>) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        match to_elements(values) {
+            Ok(elements) => self.with_stack(elements),
+            Err((index, value)) => {
+                let index = self.inputs.stack().len() + index;
+                self.fail(AdviceInputsError::InvalidStackValue { index, value })
+            },
+        }
+    }
+
+    /// Appends the provided elements to the advice stack.
+    ///
+    /// Elements appended first are read first, i.e., they end up closer to the top of the stack.
+    pub fn with_stack(mut self, elements: impl IntoIterator<Item = Felt>) -> Self {
+        if self.error.is_none() {
+            self.inputs.extend_stack(elements);
+        }
+        self
+    }
+
+    /// Appends the provided word to the advice stack, so that it is read as is by `adv_loadw`.
+    pub fn with_word(self, word: Word) -> Self {
+        self.with_stack(word)
+    }
+
+    /// Appends the provided words to the advice stack, so that they are read as is and in order
+    /// by `adv_loadw` or `adv_pipe`.
+    pub fn with_words(self, words: impl IntoIterator<Item = Word>) -> Self {
+        self.with_stack(words.into_iter().flatten())
+    }
+
+    // MERKLE STORE
+    // --------------------------------------------------------------------------------------------
+
+    /// Adds a Merkle tree with the provided leaves to the Merkle store.
+    ///
+    /// The root of the tree is appended to [AdviceInputsBuilder::merkle_roots]. Trees can be
+    /// nested by using the root of a tree as a leaf of another tree.
+    ///
+    /// A number of leaves which is not a power of two results in an
+    /// [AdviceInputsError::InvalidMerkleTree] error.
+    pub fn with_merkle_tree(mut self, leaves: impl IntoIterator<Item = Word>) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        let leaves: Vec<Word> = leaves.into_iter().collect();
+        match MerkleTree::new(leaves) {
+            Ok(tree) => {
+                self.inputs.extend_merkle_store(tree.inner_nodes());
+                self.merkle_roots.push(tree.root());
+                self
+            },
+            Err(source) => {
+                let tree = self.merkle_roots.len();
+                self.fail(AdviceInputsError::InvalidMerkleTree { tree, source })
+            },
+        }
+    }
+
+    /// Adds a sparse Merkle tree of the specified depth with the provided leaves to the Merkle
+    /// store, without materializing its empty subtrees (see
+    /// [AdviceInputs::extend_sparse_merkle_tree]).
+    ///
+    /// The root of the tree is appended to [AdviceInputsBuilder::merkle_roots].
+    ///
+    /// An invalid depth or leaf index results in an [AdviceInputsError::InvalidMerkleTree]
+    /// error.
+    pub fn with_sparse_merkle_tree(
+        mut self,
+        depth: u8,
+        leaves: impl IntoIterator<Item = (u64, Word)>,
+    ) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        match self.inputs.extend_sparse_merkle_tree(depth, leaves) {
+            Ok(root) => {
+                self.merkle_roots.push(root);
+                self
+            },
+            Err(source) => {
+                let tree = self.merkle_roots.len();
+                self.fail(AdviceInputsError::InvalidMerkleTree { tree, source })
+            },
+        }
+    }
+
+    /// Adds the nodes of the provided Merkle multi-proof to the Merkle store (see
+    /// [AdviceInputs::extend_merkle_multi_proof]).
+    ///
+    /// The root of the tree is appended to [AdviceInputsBuilder::merkle_roots].
+    pub fn with_merkle_multi_proof(mut self, proof: &MerkleMultiProof) -> Self {
+        if self.error.is_none() {
+            let root = self.inputs.extend_merkle_multi_proof(proof);
+            self.merkle_roots.push(root);
+        }
+        self
+    }
+
+    // ADVICE MAP
+    // --------------------------------------------------------------------------------------------
+
+    /// Inserts the provided values into the advice map under the specified key.
+    ///
+    /// A value which is not a valid field element results in an
+    /// [AdviceInputsError::InvalidMapValue] error, and a key which is already associated with
+    /// different values results in an [AdviceInputsError::ConflictingMapValues] error.
+    pub fn with_map_values(self, key: Word, values: impl IntoIterator<Item = u64>) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        let key = RpoDigest::from(key);
+        match to_elements(values) {
+            Ok(elements) => self.with_map_elements(key, elements),
+            Err((index, value)) => {
+                self.fail(AdviceInputsError::InvalidMapValue { key, index, value })
+            },
+        }
+    }
+
+    /// Inserts the provided values into the advice map under the key computed by hashing the
+    /// values (i.e., `hash_elements(values)`).
+    ///
+    /// This is the layout expected by procedures which load data via `adv.push_mapval` and then
+    /// verify it against its commitment.
+    ///
+    /// A value which is not a valid field element results in an
+    /// [AdviceInputsError::InvalidMapValue] error, keyed by the empty word since the key cannot be
+    /// computed.
+    pub fn with_hashed_map_values(self, values: impl IntoIterator<Item = u64>) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        match to_elements(values) {
+            Ok(elements) => {
+                let key = Rpo256::hash_elements(&elements);
+                self.with_map_elements(key, elements)
+            },
+            Err((index, value)) => self.fail(AdviceInputsError::InvalidMapValue {
+                key: RpoDigest::default(),
+                index,
+                value,
+            }),
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the roots of the Merkle trees added to this builder, in the order in which they
+    /// were added.
+    pub fn merkle_roots(&self) -> &[RpoDigest] {
+        &self.merkle_roots
+    }
+
+    /// Returns the advice inputs, or the first error encountered while adding them.
+    pub fn build(self) -> Result<AdviceInputs, AdviceInputsError> {
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(self.inputs),
+        }
+    }
+
+    // HELPERS
+    // --------------------------------------------------------------------------------------------
+
+    fn with_map_elements(mut self, key: RpoDigest, elements: Vec<Felt>) -> Self {
+        match self.inputs.mapped_values(&key) {
+            Some(existing) if existing != elements.as_slice() => {
+                self.fail(AdviceInputsError::ConflictingMapValues(key))
+            },
+            _ => {
+                self.inputs.extend_map([(key, elements)]);
+                self
+            },
+        }
+    }
+
+    fn fail(mut self, error: AdviceInputsError) -> Self {
+        self.error = Some(error);
+        self
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Converts the provided values into field elements, or returns the index and the value of the
+/// first value which is not a valid field element.
+fn to_elements(values: impl IntoIterator<Item = u64>) -> Result<Vec<Felt>, (usize, u64)> {
+    values
+        .into_iter()
+        .enumerate()
+        .map(|(index, value)| Felt::try_from(value).map_err(|_| (index, value)))
+        .collect()
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use vm_core::{ONE, ZERO, crypto::merkle::NodeIndex};
+
+    use super::*;
+
+    #[test]
+    fn advice_inputs_builder() {
+        let leaves = [[ONE; 4], [ZERO; 4]];
+        let builder = AdviceInputsBuilder::new()
+            .with_stack_values([1, 2])
+            .with_word([ONE, ZERO, ZERO, ONE])
+            .with_merkle_tree(leaves)
+            .with_hashed_map_values([3, 4]);
+        let root = builder.merkle_roots()[0];
+
+        // the root of a tree can be used as a leaf of another tree
+        let builder = builder.with_merkle_tree([root.into(), [ZERO; 4]]);
+        let outer_root = builder.merkle_roots()[1];
+        let inputs = builder.build().unwrap();
+
+        let expected_stack = [1, 2, 1, 0, 0, 1].map(Felt::new);
+        assert_eq!(inputs.stack(), expected_stack);
+        let store = inputs.merkle_store();
+        assert_eq!(store.get_node(root, NodeIndex::new(1, 0).unwrap()).unwrap(), leaves[0].into());
+        assert_eq!(store.get_node(outer_root, NodeIndex::new(1, 0).unwrap()).unwrap(), root);
+
+        let key = Rpo256::hash_elements(&[Felt::new(3), Felt::new(4)]);
+        assert_eq!(inputs.mapped_values(&key), Some([Felt::new(3), Felt::new(4)].as_slice()));
+    }
+
+    #[test]
+    fn advice_inputs_builder_errors() {
+        // errors point at the offending input, and later inputs are ignored
+        let result = AdviceInputsBuilder::new()
+            .with_stack_values([1, 2])
+            .with_stack_values([3, u64::MAX])
+            .with_merkle_tree([[ONE; 4]; 3])
+            .build();
+        assert!(matches!(
+            result,
+            Err(AdviceInputsError::InvalidStackValue { index: 3, value: u64::MAX })
+        ));
+
+        let result = AdviceInputsBuilder::new()
+            .with_merkle_tree([[ONE; 4]; 2])
+            .with_merkle_tree([[ONE; 4]; 3])
+            .build();
+        assert!(matches!(result, Err(AdviceInputsError::InvalidMerkleTree { tree: 1, .. })));
+
+        let key = [ONE; 4];
+        let result = AdviceInputsBuilder::new()
+            .with_map_values(key, [1, 2])
+            .with_map_values(key, [1, 2])
+            .with_map_values(key, [1, 3])
+            .build();
+        assert!(
+            matches!(result, Err(AdviceInputsError::ConflictingMapValues(k)) if k == key.into())
+        );
+    }
+}
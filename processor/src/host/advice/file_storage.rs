@@ -0,0 +1,245 @@
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, BufReader, Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+};
+
+use super::{Felt, MerkleNodeStorage, RpoDigest};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Identifies files containing Merkle node storage.
+const MAGIC: &[u8; 8] = b"MIDENMNS";
+
+/// Size of the header of the file, consisting of the magic bytes, the number of buckets and the
+/// number of nodes in the storage.
+const HEADER_SIZE: u64 = 24;
+
+/// Size of a bucket, consisting of the hash of a node followed by the hashes of its children.
+const BUCKET_SIZE: usize = 3 * 4 * 8;
+
+/// Number of buckets of a newly created storage.
+const INITIAL_CAPACITY: u64 = 1024;
+
+// FILE MERKLE NODE STORAGE
+// ================================================================================================
+
+/// A [MerkleNodeStorage] which keeps the nodes of Merkle trees in a file.
+///
+/// Nodes are stored in an open-addressing hash table keyed by the hash of the node, so that each
+/// lookup requires only a few reads from the file, regardless of the number of nodes in the
+/// storage. When the table becomes 3/4 full, it is rebuilt with twice as many buckets into a new
+/// file, which then replaces the original one.
+///
+/// Writes are not synced to disk until [FileMerkleNodeStorage::flush] is called.
+#[derive(Debug)]
+pub struct FileMerkleNodeStorage {
+    path: PathBuf,
+    file: File,
+    capacity: u64,
+    len: u64,
+}
+
+impl FileMerkleNodeStorage {
+    /// Opens the storage in the file at the specified path, creating the file if it does not
+    /// exist.
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be accessed, or if it does not contain Merkle node
+    /// storage.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)?;
+
+        if file.metadata()?.len() == 0 {
+            Self::init_file(&mut file, INITIAL_CAPACITY)?;
+            return Ok(Self {
+                path,
+                file,
+                capacity: INITIAL_CAPACITY,
+                len: 0,
+            });
+        }
+
+        let mut header = [0; HEADER_SIZE as usize];
+        file.read_exact(&mut header)?;
+        if &header[..8] != MAGIC {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "file does not contain Merkle node storage",
+            ));
+        }
+        let capacity = u64::from_le_bytes(header[8..16].try_into().unwrap());
+        let len = u64::from_le_bytes(header[16..24].try_into().unwrap());
+        if capacity == 0 || file.metadata()?.len() != HEADER_SIZE + capacity * BUCKET_SIZE as u64 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Merkle node storage file is corrupted",
+            ));
+        }
+
+        Ok(Self { path, file, capacity, len })
+    }
+
+    /// Returns the path of the file containing the storage.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the number of nodes in the storage.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Returns true if the storage does not contain any nodes.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Syncs all writes made to the storage to disk.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+        self.file.sync_data()
+    }
+
+    // HELPERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Writes the header and the empty buckets of a storage with the specified number of buckets
+    /// into the provided file.
+    fn init_file(file: &mut File, capacity: u64) -> io::Result<()> {
+        file.set_len(HEADER_SIZE + capacity * BUCKET_SIZE as u64)?;
+        Self::write_header(file, capacity, 0)
+    }
+
+    /// Writes the header of a storage with the specified number of buckets and nodes into the
+    /// provided file.
+    fn write_header(file: &mut File, capacity: u64, len: u64) -> io::Result<()> {
+        let mut header = [0; HEADER_SIZE as usize];
+        header[..8].copy_from_slice(MAGIC);
+        header[8..16].copy_from_slice(&capacity.to_le_bytes());
+        header[16..24].copy_from_slice(&len.to_le_bytes());
+
+        file.seek(SeekFrom::Start(0))?;
+        file.write_all(&header)
+    }
+
+    /// Returns the index of the bucket containing the specified node, or of the empty bucket into
+    /// which the node should be inserted if the node is not in the storage, together with the
+    /// content of the bucket.
+    fn find_bucket(
+        mut file: &File,
+        capacity: u64,
+        node: &RpoDigest,
+    ) -> io::Result<(u64, Option<[RpoDigest; 2]>)> {
+        let mut index = node[0].as_int() % capacity;
+        let mut bucket = [0; BUCKET_SIZE];
+        loop {
+            file.seek(SeekFrom::Start(bucket_offset(index)))?;
+            file.read_exact(&mut bucket)?;
+
+            let [bucket_node, left, right] = decode_bucket(&bucket);
+            if bucket_node == RpoDigest::default() {
+                return Ok((index, None));
+            }
+            if bucket_node == *node {
+                return Ok((index, Some([left, right])));
+            }
+
+            index = (index + 1) % capacity;
+        }
+    }
+
+    /// Rebuilds the storage with twice as many buckets.
+    ///
+    /// The new table is written into a temporary file next to the original one, which then
+    /// replaces the original file.
+    fn grow(&mut self) -> io::Result<()> {
+        let capacity = self.capacity * 2;
+        let mut tmp_path = self.path.clone().into_os_string();
+        tmp_path.push(".tmp");
+
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&tmp_path)?;
+        Self::init_file(&mut file, capacity)?;
+
+        self.file.seek(SeekFrom::Start(HEADER_SIZE))?;
+        let mut reader = BufReader::new(&self.file);
+        let mut bucket = [0; BUCKET_SIZE];
+        for _ in 0..self.capacity {
+            reader.read_exact(&mut bucket)?;
+            let [node, ..] = decode_bucket(&bucket);
+            if node == RpoDigest::default() {
+                continue;
+            }
+
+            let (index, _) = Self::find_bucket(&file, capacity, &node)?;
+            file.seek(SeekFrom::Start(bucket_offset(index)))?;
+            file.write_all(&bucket)?;
+        }
+        Self::write_header(&mut file, capacity, self.len)?;
+
+        fs::rename(&tmp_path, &self.path)?;
+        self.file = file;
+        self.capacity = capacity;
+        Ok(())
+    }
+}
+
+impl MerkleNodeStorage for FileMerkleNodeStorage {
+    type Error = io::Error;
+
+    fn get_children(&self, node: &RpoDigest) -> Result<Option<[RpoDigest; 2]>, Self::Error> {
+        Self::find_bucket(&self.file, self.capacity, node).map(|(_, children)| children)
+    }
+
+    fn insert(&mut self, node: RpoDigest, children: [RpoDigest; 2]) -> Result<(), Self::Error> {
+        if Self::find_bucket(&self.file, self.capacity, &node)?.1.is_some() {
+            return Ok(());
+        }
+        if (self.len + 1) * 4 > self.capacity * 3 {
+            self.grow()?;
+        }
+
+        let (index, _) = Self::find_bucket(&self.file, self.capacity, &node)?;
+        self.file.seek(SeekFrom::Start(bucket_offset(index)))?;
+        self.file.write_all(&encode_bucket([node, children[0], children[1]]))?;
+
+        self.len += 1;
+        Self::write_header(&mut self.file, self.capacity, self.len)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the offset of the bucket with the specified index in the file.
+fn bucket_offset(index: u64) -> u64 {
+    HEADER_SIZE + index * BUCKET_SIZE as u64
+}
+
+fn encode_bucket(digests: [RpoDigest; 3]) -> [u8; BUCKET_SIZE] {
+    let mut bucket = [0; BUCKET_SIZE];
+    let elements = digests.iter().flat_map(|digest| digest.as_elements());
+    for (chunk, element) in bucket.chunks_exact_mut(8).zip(elements) {
+        chunk.copy_from_slice(&element.as_int().to_le_bytes());
+    }
+    bucket
+}
+
+fn decode_bucket(bucket: &[u8; BUCKET_SIZE]) -> [RpoDigest; 3] {
+    let mut elements = bucket
+        .chunks_exact(8)
+        .map(|chunk| Felt::new(u64::from_le_bytes(chunk.try_into().unwrap())));
+    core::array::from_fn(|_| RpoDigest::new(core::array::from_fn(|_| elements.next().unwrap())))
+}
@@ -1,15 +1,18 @@
 use alloc::vec::Vec;
+use core::convert::Infallible;
 
 use vm_core::{
-    AdviceMap, Felt,
+    AdviceMap, Felt, Word,
     crypto::{
-        hash::RpoDigest,
-        merkle::{InnerNodeInfo, MerkleStore},
+        hash::{Rpo256, RpoDigest},
+        merkle::{InnerNodeInfo, MerkleError, MerkleStore},
     },
     errors::InputError,
     utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable},
 };
 
+use super::MerkleMultiProof;
+
 // ADVICE INPUTS
 // ================================================================================================
 
@@ -26,6 +29,7 @@ use vm_core::{
 ///    with Merkle trees.
 #[cfg(not(feature = "testing"))]
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct AdviceInputs {
     stack: Vec<Felt>,
     map: AdviceMap,
@@ -102,6 +106,52 @@ impl AdviceInputs {
         self.store.extend(iter);
     }
 
+    /// Adds a sparse Merkle tree of the specified depth with the provided leaves to the
+    /// [MerkleStore], and returns the root of the tree.
+    ///
+    /// Leaves are specified as pairs of leaf indexes and values; all other leaves of the tree are
+    /// empty. Only the nodes on the paths from the provided leaves to the root are added to the
+    /// store, since the store always contains the roots of empty subtrees. Thus, the memory needed
+    /// to store the tree depends only on the number of non-empty leaves, and not on its depth.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The depth is zero or greater than 64.
+    /// - The index of a leaf is not valid for the depth of the tree.
+    /// - Multiple leaves are provided for the same index.
+    pub fn extend_sparse_merkle_tree<I>(
+        &mut self,
+        depth: u8,
+        leaves: I,
+    ) -> Result<RpoDigest, MerkleError>
+    where
+        I: IntoIterator<Item = (u64, Word)>,
+    {
+        let leaves = super::sparse::sparse_leaves(depth, leaves)?;
+
+        let mut nodes = Vec::new();
+        let root = super::sparse::build_sparse_tree(depth, leaves, |left, right| {
+            let value = Rpo256::merge(&[left, right]);
+            nodes.push(InnerNodeInfo { value, left, right });
+            Ok::<_, Infallible>(value)
+        });
+        self.store.extend(nodes);
+
+        Ok(root.unwrap_or_else(|never| match never {}))
+    }
+
+    /// Adds the nodes of the provided Merkle multi-proof to the [MerkleStore], and returns the
+    /// root of the tree.
+    ///
+    /// Afterwards, each of the leaves proven by the multi-proof can be verified by the VM via
+    /// `mtree_verify` or read via `mtree_get`. Since nodes shared by the paths of several leaves
+    /// are included in the proof only once, this requires fewer advice inputs than adding the
+    /// Merkle path of each leaf separately.
+    pub fn extend_merkle_multi_proof(&mut self, proof: &MerkleMultiProof) -> RpoDigest {
+        self.store.extend(proof.inner_nodes());
+        proof.root()
+    }
+
     /// Extends the contents of this instance with the contents of the other instance.
     pub fn extend(&mut self, other: Self) {
         self.stack.extend(other.stack);
@@ -161,6 +211,7 @@ impl Deserializable for AdviceInputs {
 
 #[cfg(feature = "testing")]
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct AdviceInputs {
     pub stack: Vec<Felt>,
     pub map: AdviceMap,
@@ -197,4 +248,24 @@ mod tests {
 
         assert_eq!(advice1, advice2);
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_advice_inputs_serde_json() {
+        use vm_core::{
+            Felt,
+            crypto::{hash::RpoDigest, merkle::MerkleTree},
+        };
+
+        let tree = MerkleTree::new([[Felt::new(1); 4], [Felt::new(2); 4]]).unwrap();
+        let key = RpoDigest::from([Felt::new(3); 4]);
+        let advice = AdviceInputs::default()
+            .with_stack_values([1, 2, 3].iter().copied())
+            .unwrap()
+            .with_map([(key, vec![Felt::new(4), Felt::new(5)])])
+            .with_merkle_store(tree.inner_nodes().collect());
+
+        let json = serde_json::to_string(&advice).unwrap();
+        assert_eq!(serde_json::from_str::<AdviceInputs>(&json).unwrap(), advice);
+    }
 }
@@ -7,15 +7,39 @@ use vm_core::{
 
 use crate::{ExecutionError, ProcessState, Word};
 
+mod builder;
+pub use builder::AdviceInputsBuilder;
+
 mod inputs;
 pub use inputs::AdviceInputs;
 
+mod multiproof;
+pub use multiproof::MerkleMultiProof;
+
+mod persistent;
+pub use persistent::{MerkleNodeStorage, PersistentAdviceProvider};
+
+#[cfg(feature = "std")]
+mod file_storage;
+#[cfg(feature = "std")]
+pub use file_storage::FileMerkleNodeStorage;
+
 mod providers;
 pub use providers::{MemAdviceProvider, RecAdviceProvider};
 
+#[cfg(any(test, feature = "rpc-advice"))]
+mod rpc;
+#[cfg(any(test, feature = "rpc-advice"))]
+pub use rpc::{AdviceRpcClient, RpcAdviceProvider, RpcNodeError};
+
 mod source;
 pub use source::AdviceSource;
 
+mod sparse;
+
+mod tape;
+pub use tape::{AdviceTape, AdviceTapeEntry, AdviceTapeRecorder, AdviceTapeReplayer};
+
 // ADVICE PROVIDER
 // ================================================================================================
 
@@ -162,4 +186,72 @@ pub trait AdviceProvider: Sized {
     /// It is not checked whether a Merkle tree for either of the specified roots can be found in
     /// this advice provider.
     fn merge_roots(&mut self, lhs: Word, rhs: Word) -> Result<Word, ExecutionError>;
+
+    // PROVIDED METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Makes the value(s) associated with the specified key in the advice map available via
+    /// [AdviceProvider::get_mapped_values()], e.g., by fetching them from an external source.
+    ///
+    /// This is invoked by the VM before it reads values from the advice map directly. Advice
+    /// providers which keep the whole advice map in memory don't need to do anything.
+    ///
+    /// # Errors
+    /// Returns an error if the values could not be obtained. Keys which are not associated with
+    /// any values are not an error.
+    fn resolve_mapped_values(&mut self, _key: &RpoDigest) -> Result<(), ExecutionError> {
+        Ok(())
+    }
+
+    /// Adds a sparse Merkle tree of the specified depth with the provided leaves to the Merkle
+    /// store of this advice provider, and returns the root of the tree.
+    ///
+    /// Leaves are specified as pairs of leaf indexes and values; all other leaves of the tree are
+    /// empty. Only the nodes on the paths from the provided leaves to the root are added to the
+    /// store, since the roots of empty subtrees are always known to the advice provider. Thus,
+    /// trees of depth up to 64 can be accessed and updated via [AdviceProvider::get_tree_node],
+    /// [AdviceProvider::get_merkle_path] and [AdviceProvider::update_merkle_node] (and hence via
+    /// the `MPVERIFY` and `MRUPDATE` operations), including to prove that a leaf is empty.
+    ///
+    /// The nodes are added via [AdviceProvider::merge_roots].
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The depth is zero or greater than 64.
+    /// - The index of a leaf is not valid for the depth of the tree.
+    /// - Multiple leaves are provided for the same index.
+    /// - A node cannot be added to the Merkle store.
+    fn insert_sparse_merkle_tree(
+        &mut self,
+        depth: u8,
+        leaves: impl IntoIterator<Item = (u64, Word)>,
+    ) -> Result<Word, ExecutionError> {
+        let leaves = sparse::sparse_leaves(depth, leaves)
+            .map_err(ExecutionError::MerkleStoreUpdateFailed)?;
+        let root = sparse::build_sparse_tree(depth, leaves, |left, right| {
+            self.merge_roots(left.into(), right.into()).map(RpoDigest::from)
+        })?;
+
+        Ok(root.into())
+    }
+
+    /// Adds the nodes of the provided Merkle multi-proof to the Merkle store of this advice
+    /// provider, and returns the root of the tree.
+    ///
+    /// Afterwards, the leaves proven by the multi-proof can be accessed via
+    /// [AdviceProvider::get_tree_node] and [AdviceProvider::get_merkle_path] (and hence via the
+    /// `MPVERIFY` operation). The nodes are added via [AdviceProvider::merge_roots].
+    ///
+    /// # Errors
+    /// Returns an error if a node cannot be added to the Merkle store.
+    fn insert_merkle_multi_proof(
+        &mut self,
+        proof: &MerkleMultiProof,
+    ) -> Result<Word, ExecutionError> {
+        let root = proof.merge_nodes(|left, right| {
+            self.merge_roots(left.into(), right.into()).map(RpoDigest::from)
+        })?;
+
+        Ok(root.into())
+    }
 }
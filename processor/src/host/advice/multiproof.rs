@@ -0,0 +1,330 @@
+use alloc::{
+    collections::BTreeMap,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::convert::Infallible;
+
+use vm_core::{
+    crypto::{
+        hash::{Rpo256, RpoDigest},
+        merkle::{InnerNodeInfo, MerkleError, MerkleStore, MerkleTree, NodeIndex},
+    },
+    utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable},
+};
+
+// MERKLE MULTI-PROOF
+// ================================================================================================
+
+/// A compact proof that a set of leaves belongs to a single Merkle tree.
+///
+/// Instead of a full Merkle path for every leaf, a multi-proof contains only the nodes which
+/// cannot be computed from the leaves themselves, i.e., each node shared by the paths of several
+/// leaves is included once, and nodes which are ancestors of proven leaves are not included at
+/// all. For example, proving two sibling leaves of a tree of depth `d` requires `d - 1` nodes
+/// instead of `2 * d`.
+///
+/// Nodes are stored in the order in which they are consumed when computing the root: level by
+/// level starting from the leaves, and from left to right within a level.
+///
+/// The nodes of a multi-proof can be added to the Merkle store of the VM via
+/// [AdviceInputs::extend_merkle_multi_proof](super::AdviceInputs::extend_merkle_multi_proof) or
+/// [AdviceProvider::insert_merkle_multi_proof](super::AdviceProvider::insert_merkle_multi_proof),
+/// after which programs can verify the leaves via `mtree_verify` (e.g., using the
+/// `std::collections::merkle::verify_leaves` procedure).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MerkleMultiProof {
+    depth: u8,
+    leaves: BTreeMap<u64, RpoDigest>,
+    nodes: Vec<RpoDigest>,
+}
+
+impl MerkleMultiProof {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a multi-proof for the leaves of `tree` at the specified indexes.
+    ///
+    /// # Errors
+    /// Returns an error if an index is not valid for the depth of the tree.
+    ///
+    /// # Panics
+    /// Panics if no indexes are provided.
+    pub fn new(
+        tree: &MerkleTree,
+        indexes: impl IntoIterator<Item = u64>,
+    ) -> Result<Self, MerkleError> {
+        Self::build(tree.depth(), indexes, |index| tree.get_node(index))
+    }
+
+    /// Returns a multi-proof for the leaves at the specified indexes of the tree of the specified
+    /// depth with root `root`, whose nodes are read from `store`.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The depth is zero or greater than 64.
+    /// - An index is not valid for the depth of the tree.
+    /// - A node needed by the proof is not in the store.
+    ///
+    /// # Panics
+    /// Panics if no indexes are provided.
+    pub fn from_store(
+        store: &MerkleStore,
+        root: RpoDigest,
+        depth: u8,
+        indexes: impl IntoIterator<Item = u64>,
+    ) -> Result<Self, MerkleError> {
+        Self::build(depth, indexes, |index| store.get_node(root, index))
+    }
+
+    fn build(
+        depth: u8,
+        indexes: impl IntoIterator<Item = u64>,
+        mut get_node: impl FnMut(NodeIndex) -> Result<RpoDigest, MerkleError>,
+    ) -> Result<Self, MerkleError> {
+        if depth == 0 {
+            return Err(MerkleError::DepthTooSmall(depth));
+        }
+        if depth > 64 {
+            return Err(MerkleError::DepthTooBig(depth as u64));
+        }
+
+        let mut leaves = BTreeMap::new();
+        for index in indexes {
+            leaves.insert(index, get_node(NodeIndex::new(depth, index)?)?);
+        }
+        assert!(!leaves.is_empty(), "a multi-proof must prove at least one leaf");
+
+        let mut nodes = Vec::new();
+        merge_paths(
+            depth,
+            &leaves,
+            |index| {
+                let node = get_node(index)?;
+                nodes.push(node);
+                Ok(node)
+            },
+            |left, right| Ok(Rpo256::merge(&[left, right])),
+        )?;
+
+        Ok(Self { depth, leaves, nodes })
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the depth of the tree.
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    /// Returns the leaves proven by this multi-proof as pairs of leaf indexes and values, sorted by
+    /// index.
+    pub fn leaves(&self) -> impl Iterator<Item = (u64, RpoDigest)> + '_ {
+        self.leaves.iter().map(|(&index, &leaf)| (index, leaf))
+    }
+
+    /// Returns the nodes of this multi-proof, i.e., the nodes needed besides the leaves to compute
+    /// the root of the tree.
+    pub fn nodes(&self) -> &[RpoDigest] {
+        &self.nodes
+    }
+
+    /// Returns the root of the tree, computed from the leaves and the nodes of this multi-proof.
+    pub fn root(&self) -> RpoDigest {
+        let root =
+            self.merge_nodes(|left, right| Ok::<_, Infallible>(Rpo256::merge(&[left, right])));
+        root.unwrap_or_else(|never| match never {})
+    }
+
+    /// Returns the inner nodes of the tree which can be computed from this multi-proof, i.e., all
+    /// nodes on the paths from the proven leaves to the root.
+    pub fn inner_nodes(&self) -> Vec<InnerNodeInfo> {
+        let mut nodes = Vec::new();
+        let root = self.merge_nodes(|left, right| {
+            let value = Rpo256::merge(&[left, right]);
+            nodes.push(InnerNodeInfo { value, left, right });
+            Ok::<_, Infallible>(value)
+        });
+        root.unwrap_or_else(|never| match never {});
+        nodes
+    }
+
+    // HELPERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Computes the root of the tree, invoking `merge` with the children of every inner node on
+    /// the paths from the proven leaves to the root.
+    pub(super) fn merge_nodes<E>(
+        &self,
+        merge: impl FnMut(RpoDigest, RpoDigest) -> Result<RpoDigest, E>,
+    ) -> Result<RpoDigest, E> {
+        // the number of nodes is validated when the proof is built or deserialized
+        let mut nodes = self.nodes.iter();
+        merge_paths(
+            self.depth,
+            &self.leaves,
+            |_| Ok(*nodes.next().expect("multi-proof is missing a node")),
+            merge,
+        )
+    }
+}
+
+impl Serializable for MerkleMultiProof {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8(self.depth);
+        target.write_usize(self.leaves.len());
+        for (index, leaf) in self.leaves.iter() {
+            target.write_u64(*index);
+            leaf.write_into(target);
+        }
+        self.nodes.write_into(target);
+    }
+}
+
+impl Deserializable for MerkleMultiProof {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let depth = source.read_u8()?;
+        if depth == 0 || depth > 64 {
+            return Err(DeserializationError::InvalidValue(format!(
+                "invalid multi-proof depth {depth}"
+            )));
+        }
+
+        let num_leaves = source.read_usize()?;
+        if num_leaves == 0 {
+            return Err(DeserializationError::InvalidValue(String::from(
+                "multi-proof without leaves",
+            )));
+        }
+        let mut leaves = BTreeMap::new();
+        for _ in 0..num_leaves {
+            let index = source.read_u64()?;
+            NodeIndex::new(depth, index)
+                .map_err(|err| DeserializationError::InvalidValue(err.to_string()))?;
+            if leaves.insert(index, RpoDigest::read_from(source)?).is_some() {
+                return Err(DeserializationError::InvalidValue(format!(
+                    "duplicate multi-proof leaf index {index}"
+                )));
+            }
+        }
+        let nodes = Vec::<RpoDigest>::read_from(source)?;
+
+        // make sure that the proof contains exactly the nodes needed to compute the root
+        let mut num_nodes = 0;
+        merge_paths(
+            depth,
+            &leaves,
+            |_| {
+                num_nodes += 1;
+                Ok::<_, Infallible>(RpoDigest::default())
+            },
+            |_, _| Ok(RpoDigest::default()),
+        )
+        .unwrap_or_else(|never| match never {});
+        if num_nodes != nodes.len() {
+            return Err(DeserializationError::InvalidValue(format!(
+                "expected {num_nodes} multi-proof nodes, but found {}",
+                nodes.len()
+            )));
+        }
+
+        Ok(Self { depth, leaves, nodes })
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Computes the root of a tree of the specified depth from the provided leaves, and returns it.
+///
+/// The tree is built bottom up; at every level, `sibling` is invoked, from left to right, with
+/// the index of every node which is needed to compute the next level but cannot be computed from
+/// the leaves, and `merge` is invoked with the children of every computed node.
+fn merge_paths<E>(
+    depth: u8,
+    leaves: &BTreeMap<u64, RpoDigest>,
+    mut sibling: impl FnMut(NodeIndex) -> Result<RpoDigest, E>,
+    mut merge: impl FnMut(RpoDigest, RpoDigest) -> Result<RpoDigest, E>,
+) -> Result<RpoDigest, E> {
+    let mut nodes: Vec<(u64, RpoDigest)> =
+        leaves.iter().map(|(&index, &leaf)| (index, leaf)).collect();
+    for node_depth in (1..=depth).rev() {
+        // nodes are sorted by index, so siblings are always adjacent
+        let mut parents = Vec::with_capacity(nodes.len());
+        let mut nodes_iter = nodes.into_iter().peekable();
+        while let Some((index, node)) = nodes_iter.next() {
+            let sibling_index = NodeIndex::new_unchecked(node_depth, index ^ 1);
+            let (left, right) = if index & 1 == 1 {
+                (sibling(sibling_index)?, node)
+            } else if let Some((_, right)) = nodes_iter.next_if(|(next, _)| *next == index + 1) {
+                (node, right)
+            } else {
+                (node, sibling(sibling_index)?)
+            };
+            parents.push((index >> 1, merge(left, right)?));
+        }
+        nodes = parents;
+    }
+
+    Ok(nodes[0].1)
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use vm_core::{Felt, ONE, Word, ZERO};
+
+    use super::*;
+    use crate::{AdviceInputs, AdviceProvider, MemAdviceProvider};
+
+    #[test]
+    fn merkle_multi_proof() {
+        let leaves: Vec<_> = (0..16).map(|i| [Felt::new(i), ZERO, ZERO, ONE]).collect();
+        let tree = MerkleTree::new(leaves.clone()).unwrap();
+
+        // leaves 4 and 5 are siblings and share the rest of their paths, and the paths of all
+        // leaves share their top nodes, so the proof contains 5 nodes instead of 12
+        let proof = MerkleMultiProof::new(&tree, [5, 4, 11]).unwrap();
+        assert_eq!(proof.leaves().map(|(index, _)| index).collect::<Vec<_>>(), [4, 5, 11]);
+        assert_eq!(proof.nodes().len(), 5);
+        assert_eq!(proof.root(), tree.root());
+
+        let store = MerkleStore::from(&tree);
+        assert_eq!(
+            MerkleMultiProof::from_store(&store, tree.root(), 4, [4, 5, 11]).unwrap(),
+            proof
+        );
+        assert!(MerkleMultiProof::new(&tree, [16]).is_err());
+
+        // the proven leaves can be read from the advice inputs and the advice provider
+        let mut inputs = AdviceInputs::default();
+        assert_eq!(inputs.extend_merkle_multi_proof(&proof), tree.root());
+        let index = NodeIndex::new(4, 11).unwrap();
+        assert_eq!(inputs.merkle_store().get_node(tree.root(), index).unwrap(), leaves[11].into());
+
+        let mut provider = MemAdviceProvider::default();
+        let root = provider.insert_merkle_multi_proof(&proof).unwrap();
+        assert_eq!(root, Word::from(tree.root()));
+        assert_eq!(provider.get_tree_node(root, &Felt::new(4), &Felt::new(4)).unwrap(), leaves[4]);
+        assert!(provider.get_tree_node(root, &Felt::new(4), &Felt::new(6)).is_err());
+    }
+
+    #[test]
+    fn merkle_multi_proof_serialization() {
+        let leaves: Vec<_> = (0..8).map(|i| [Felt::new(i), ZERO, ZERO, ZERO]).collect();
+        let tree = MerkleTree::new(leaves).unwrap();
+        let proof = MerkleMultiProof::new(&tree, [0, 3, 7]).unwrap();
+
+        let bytes = proof.to_bytes();
+        assert_eq!(MerkleMultiProof::read_from_bytes(&bytes).unwrap(), proof);
+
+        // a proof with a missing node is rejected
+        let mut invalid = proof.clone();
+        invalid.nodes.pop();
+        assert!(MerkleMultiProof::read_from_bytes(&invalid.to_bytes()).is_err());
+    }
+}
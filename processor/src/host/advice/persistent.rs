@@ -0,0 +1,293 @@
+use alloc::{boxed::Box, collections::BTreeMap, vec::Vec};
+use core::{convert::Infallible, error::Error};
+
+use vm_core::crypto::{
+    hash::Rpo256,
+    merkle::{EmptySubtreeRoots, MerkleError, NodeIndex, StoreNode},
+};
+
+use super::{
+    AdviceInputs, AdviceProvider, AdviceSource, ExecutionError, Felt, MerklePath, RpoDigest, Word,
+    providers::BaseAdviceProvider,
+};
+use crate::ProcessState;
+
+// MERKLE NODE STORAGE
+// ================================================================================================
+
+/// Defines the storage of the nodes of the Merkle trees of a [PersistentAdviceProvider].
+///
+/// Nodes are content-addressed: each internal node is stored under its hash, and maps to the
+/// hashes of its children. Nodes which are not in the storage are considered to be leaves.
+/// Since nodes are never removed, storages can be shared between executions, and can hold trees
+/// which are too large to fit into memory (see `FileMerkleNodeStorage`).
+pub trait MerkleNodeStorage {
+    /// The error returned when the storage cannot be accessed.
+    type Error: Error + Send + Sync + 'static;
+
+    /// Returns the hashes of the left and right children of the node with the specified hash, or
+    /// `None` if the node is not in the storage.
+    fn get_children(&self, node: &RpoDigest) -> Result<Option<[RpoDigest; 2]>, Self::Error>;
+
+    /// Inserts the node with the specified hash and children into the storage.
+    ///
+    /// The hash of the node must be the hash of its children. Inserting a node which is already
+    /// in the storage has no effect.
+    fn insert(&mut self, node: RpoDigest, children: [RpoDigest; 2]) -> Result<(), Self::Error>;
+}
+
+impl MerkleNodeStorage for BTreeMap<RpoDigest, [RpoDigest; 2]> {
+    type Error = Infallible;
+
+    fn get_children(&self, node: &RpoDigest) -> Result<Option<[RpoDigest; 2]>, Self::Error> {
+        Ok(self.get(node).copied())
+    }
+
+    fn insert(&mut self, node: RpoDigest, children: [RpoDigest; 2]) -> Result<(), Self::Error> {
+        self.insert(node, children);
+        Ok(())
+    }
+}
+
+// PERSISTENT ADVICE PROVIDER
+// ================================================================================================
+
+/// An [AdviceProvider] which keeps the nodes of its Merkle trees in a pluggable
+/// [MerkleNodeStorage], rather than in memory.
+///
+/// This makes it possible to execute programs against Merkle trees which are too large to fit
+/// into memory, and to persist updates made to these trees across executions. The advice stack
+/// and the advice map are kept in memory.
+///
+/// Like the Merkle store of [crate::MemAdviceProvider], the storage is populated with the roots of
+/// all empty subtrees, so that empty trees of any depth can be accessed and updated without being
+/// added to the storage first.
+pub struct PersistentAdviceProvider<S> {
+    // the Merkle store of the base provider is not used; all Merkle tree operations are performed
+    // against the node storage
+    provider: BaseAdviceProvider<BTreeMap<RpoDigest, Vec<Felt>>, BTreeMap<RpoDigest, StoreNode>>,
+    storage: S,
+}
+
+impl<S: MerkleNodeStorage> PersistentAdviceProvider<S> {
+    /// Returns a new advice provider initialized with the provided inputs, which keeps the nodes
+    /// of its Merkle trees in the provided storage.
+    ///
+    /// The nodes of the Merkle store of the inputs are added to the storage.
+    ///
+    /// # Errors
+    /// Returns an error if the storage cannot be accessed.
+    pub fn new(inputs: AdviceInputs, mut storage: S) -> Result<Self, ExecutionError> {
+        let (stack, map, store) = inputs.into_parts();
+
+        let empty_roots = EmptySubtreeRoots::empty_hashes(u8::MAX);
+        let empty_nodes = empty_roots.windows(2).map(|pair| (pair[0], [pair[1], pair[1]])).rev();
+        let store_nodes = store.inner_nodes().map(|node| (node.value, [node.left, node.right]));
+        for (node, children) in empty_nodes.chain(store_nodes) {
+            storage.insert(node, children).map_err(storage_failed)?;
+        }
+
+        let provider = AdviceInputs::default().with_stack(stack).with_map(map).into();
+        Ok(Self { provider, storage })
+    }
+
+    /// Returns the storage of the nodes of the Merkle trees of this provider.
+    pub fn storage(&self) -> &S {
+        &self.storage
+    }
+
+    /// Consumes this provider and returns the storage of the nodes of its Merkle trees.
+    pub fn into_storage(self) -> S {
+        self.storage
+    }
+
+    // HELPERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the children of the specified node, or `None` if the node is not in the storage.
+    fn get_children(&self, node: &RpoDigest) -> Result<Option<[RpoDigest; 2]>, ExecutionError> {
+        self.storage.get_children(node).map_err(storage_failed)
+    }
+
+    /// Returns the node at the specified index of the tree with the specified root, together with
+    /// its path to the root, starting at the sibling of the node.
+    fn get_path(
+        &self,
+        root: RpoDigest,
+        index: NodeIndex,
+    ) -> Result<(RpoDigest, MerklePath), ExecutionError> {
+        if self.get_children(&root)?.is_none() {
+            return Err(ExecutionError::MerkleStoreLookupFailed(MerkleError::RootNotInStore(root)));
+        }
+
+        let mut node = root;
+        let mut path = Vec::with_capacity(index.depth().into());
+        for i in (0..index.depth()).rev() {
+            let [left, right] =
+                self.get_children(&node)?.ok_or(ExecutionError::MerkleStoreLookupFailed(
+                    MerkleError::NodeIndexNotFoundInStore(node, index),
+                ))?;
+
+            if (index.value() >> i) & 1 == 0 {
+                path.push(right);
+                node = left;
+            } else {
+                path.push(left);
+                node = right;
+            }
+        }
+
+        // the path is computed from the root to the node, so it must be reversed
+        path.reverse();
+        Ok((node, MerklePath::new(path)))
+    }
+}
+
+impl<S: MerkleNodeStorage> AdviceProvider for PersistentAdviceProvider<S> {
+    // ADVICE STACK
+    // --------------------------------------------------------------------------------------------
+
+    fn pop_stack(&mut self, process: ProcessState) -> Result<Felt, ExecutionError> {
+        self.provider.pop_stack(process)
+    }
+
+    fn pop_stack_word(&mut self, process: ProcessState) -> Result<Word, ExecutionError> {
+        self.provider.pop_stack_word(process)
+    }
+
+    fn pop_stack_dword(&mut self, process: ProcessState) -> Result<[Word; 2], ExecutionError> {
+        self.provider.pop_stack_dword(process)
+    }
+
+    fn push_stack(&mut self, source: AdviceSource) -> Result<(), ExecutionError> {
+        self.provider.push_stack(source)
+    }
+
+    // ADVICE MAP
+    // --------------------------------------------------------------------------------------------
+
+    fn get_mapped_values(&self, key: &RpoDigest) -> Option<&[Felt]> {
+        self.provider.get_mapped_values(key)
+    }
+
+    fn insert_into_map(&mut self, key: Word, values: Vec<Felt>) {
+        self.provider.insert_into_map(key, values)
+    }
+
+    // MERKLE STORE
+    // --------------------------------------------------------------------------------------------
+
+    fn get_tree_node(
+        &self,
+        root: Word,
+        depth: &Felt,
+        index: &Felt,
+    ) -> Result<Word, ExecutionError> {
+        let index = NodeIndex::from_elements(depth, index).map_err(|_| {
+            ExecutionError::InvalidMerkleTreeNodeIndex { depth: *depth, value: *index }
+        })?;
+        self.get_path(root.into(), index).map(|(node, _)| node.into())
+    }
+
+    fn get_merkle_path(
+        &self,
+        root: Word,
+        depth: &Felt,
+        index: &Felt,
+    ) -> Result<MerklePath, ExecutionError> {
+        let index = NodeIndex::from_elements(depth, index).map_err(|_| {
+            ExecutionError::InvalidMerkleTreeNodeIndex { depth: *depth, value: *index }
+        })?;
+        self.get_path(root.into(), index).map(|(_, path)| path)
+    }
+
+    fn get_leaf_depth(
+        &self,
+        root: Word,
+        tree_depth: &Felt,
+        index: &Felt,
+    ) -> Result<u8, ExecutionError> {
+        let tree_depth = u8::try_from(tree_depth.as_int())
+            .map_err(|_| ExecutionError::InvalidMerkleTreeDepth { depth: *tree_depth })?;
+        if tree_depth > 64 {
+            return Err(ExecutionError::MerkleStoreLookupFailed(MerkleError::DepthTooBig(
+                tree_depth as u64,
+            )));
+        }
+        let index = index.as_int();
+        NodeIndex::new(tree_depth, index).map_err(ExecutionError::MerkleStoreLookupFailed)?;
+
+        let root = RpoDigest::from(root);
+        if self.get_children(&root)?.is_none() {
+            return Err(ExecutionError::MerkleStoreLookupFailed(MerkleError::RootNotInStore(root)));
+        }
+
+        // traverse the tree from the root down to the first leaf or empty node; the bits of the
+        // index are reversed, so that the bit selecting the child at each depth is the lowest one
+        let empty_roots = EmptySubtreeRoots::empty_hashes(tree_depth);
+        let mut path = (index << (64 - tree_depth)).reverse_bits();
+        let mut node = root;
+        for depth in 0..=tree_depth {
+            if node == empty_roots[depth as usize] {
+                return Ok(depth);
+            }
+            let Some([left, right]) = self.get_children(&node)? else {
+                return Ok(depth);
+            };
+
+            node = if path & 1 == 0 { left } else { right };
+            path >>= 1;
+        }
+
+        Err(ExecutionError::MerkleStoreLookupFailed(MerkleError::DepthTooBig(
+            tree_depth as u64 + 1,
+        )))
+    }
+
+    fn update_merkle_node(
+        &mut self,
+        root: Word,
+        depth: &Felt,
+        index: &Felt,
+        value: Word,
+    ) -> Result<(MerklePath, Word), ExecutionError> {
+        let node_index = NodeIndex::from_elements(depth, index).map_err(|_| {
+            ExecutionError::InvalidMerkleTreeNodeIndex { depth: *depth, value: *index }
+        })?;
+        let (node, path) = self.get_path(root.into(), node_index)?;
+
+        // the tree is updated only if the value of the node changes
+        let value = RpoDigest::from(value);
+        if node == value {
+            return Ok((path, root));
+        }
+
+        let mut new_root = value;
+        for node in path
+            .inner_nodes(node_index.value(), value)
+            .map_err(ExecutionError::MerkleStoreUpdateFailed)?
+        {
+            self.storage
+                .insert(node.value, [node.left, node.right])
+                .map_err(storage_failed)?;
+            new_root = node.value;
+        }
+
+        Ok((path, new_root.into()))
+    }
+
+    fn merge_roots(&mut self, lhs: Word, rhs: Word) -> Result<Word, ExecutionError> {
+        let children = [RpoDigest::from(lhs), RpoDigest::from(rhs)];
+        let parent = Rpo256::merge(&children);
+        self.storage.insert(parent, children).map_err(storage_failed)?;
+
+        Ok(parent.into())
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn storage_failed(error: impl Error + Send + Sync + 'static) -> ExecutionError {
+    ExecutionError::MerkleNodeStorageFailed(Box::new(error))
+}
@@ -0,0 +1,432 @@
+use alloc::{boxed::Box, collections::BTreeMap, sync::Arc, vec::Vec};
+use core::{
+    cell::RefCell,
+    error::Error,
+    future::Future,
+    pin::pin,
+    task::{Context, Poll, Waker},
+};
+use std::{
+    task::Wake,
+    thread::{self, Thread},
+};
+
+use vm_core::crypto::hash::Rpo256;
+
+use super::{
+    AdviceInputs, AdviceProvider, AdviceSource, ExecutionError, Felt, MerkleNodeStorage,
+    MerklePath, PersistentAdviceProvider, RpoDigest, Word,
+};
+use crate::ProcessState;
+
+// ADVICE RPC CLIENT
+// ================================================================================================
+
+/// Defines the interface through which an [RpcAdviceProvider] fetches advice from a remote
+/// service, e.g., the service storing the state against which programs are executed.
+///
+/// The futures returned by the client are driven to completion on the thread executing the
+/// program. Thus, they must not depend on being polled by a specific async runtime; clients built
+/// on top of such a runtime should spawn their requests onto it, and return a future which
+/// receives the response (e.g., via a channel).
+pub trait AdviceRpcClient {
+    /// The error returned when a request fails.
+    type Error: Error + Send + Sync + 'static;
+
+    /// Returns the hashes of the left and right children of the Merkle tree node with the
+    /// specified hash, or `None` if the node is not known to the service (e.g., if it is a leaf).
+    ///
+    /// The returned children are not trusted: the provider checks that they hash to the requested
+    /// node before using them.
+    fn get_children(
+        &self,
+        node: RpoDigest,
+    ) -> impl Future<Output = Result<Option<[RpoDigest; 2]>, Self::Error>>;
+
+    /// Returns the values associated with the specified key in the advice map, or `None` if the
+    /// key is not known to the service.
+    fn get_mapped_values(
+        &self,
+        key: RpoDigest,
+    ) -> impl Future<Output = Result<Option<Vec<Felt>>, Self::Error>>;
+}
+
+// RPC ADVICE PROVIDER
+// ================================================================================================
+
+/// An [AdviceProvider] which fetches Merkle tree nodes and advice map entries lazily from a remote
+/// service through an [AdviceRpcClient].
+///
+/// Only the nodes and entries which are accessed by the executed programs are fetched, and all
+/// fetched data is cached locally, so that each node or entry is fetched at most once. Nodes and
+/// entries inserted by the programs, as well as the advice inputs the provider is created with,
+/// are kept locally and are never sent to the service.
+pub struct RpcAdviceProvider<C> {
+    provider: PersistentAdviceProvider<RpcNodeCache<C>>,
+}
+
+impl<C: AdviceRpcClient> RpcAdviceProvider<C> {
+    /// Returns a new advice provider initialized with the provided inputs, which fetches all
+    /// other advice through the provided client.
+    pub fn new(inputs: AdviceInputs, client: C) -> Self {
+        let cache = RpcNodeCache {
+            client,
+            nodes: RefCell::new(BTreeMap::new()),
+        };
+        let provider = PersistentAdviceProvider::new(inputs, cache)
+            .expect("inserting nodes into the cache cannot fail");
+        Self { provider }
+    }
+
+    /// Returns the client through which advice is fetched.
+    pub fn client(&self) -> &C {
+        &self.provider.storage().client
+    }
+
+    /// Returns the number of Merkle tree nodes cached by this provider, including the nodes which
+    /// were not fetched from the remote service.
+    pub fn num_cached_nodes(&self) -> usize {
+        self.provider.storage().nodes.borrow().len()
+    }
+
+    // HELPERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Fetches the values associated with the specified key in the advice map, unless they are
+    /// already available locally.
+    fn fetch_mapped_values(&mut self, key: &RpoDigest) -> Result<(), ExecutionError> {
+        if self.provider.get_mapped_values(key).is_some() {
+            return Ok(());
+        }
+
+        let values = block_on(self.client().get_mapped_values(*key)).map_err(|err| {
+            ExecutionError::AdviceMapFetchFailed {
+                key: (*key).into(),
+                source: Box::new(err),
+            }
+        })?;
+        if let Some(values) = values {
+            self.provider.insert_into_map((*key).into(), values);
+        }
+        Ok(())
+    }
+}
+
+impl<C: AdviceRpcClient> AdviceProvider for RpcAdviceProvider<C> {
+    // ADVICE STACK
+    // --------------------------------------------------------------------------------------------
+
+    fn pop_stack(&mut self, process: ProcessState) -> Result<Felt, ExecutionError> {
+        self.provider.pop_stack(process)
+    }
+
+    fn pop_stack_word(&mut self, process: ProcessState) -> Result<Word, ExecutionError> {
+        self.provider.pop_stack_word(process)
+    }
+
+    fn pop_stack_dword(&mut self, process: ProcessState) -> Result<[Word; 2], ExecutionError> {
+        self.provider.pop_stack_dword(process)
+    }
+
+    fn push_stack(&mut self, source: AdviceSource) -> Result<(), ExecutionError> {
+        if let AdviceSource::Map { key, .. } = source {
+            self.fetch_mapped_values(&key.into())?;
+        }
+        self.provider.push_stack(source)
+    }
+
+    // ADVICE MAP
+    // --------------------------------------------------------------------------------------------
+
+    fn get_mapped_values(&self, key: &RpoDigest) -> Option<&[Felt]> {
+        self.provider.get_mapped_values(key)
+    }
+
+    fn insert_into_map(&mut self, key: Word, values: Vec<Felt>) {
+        self.provider.insert_into_map(key, values)
+    }
+
+    fn resolve_mapped_values(&mut self, key: &RpoDigest) -> Result<(), ExecutionError> {
+        self.fetch_mapped_values(key)
+    }
+
+    // MERKLE STORE
+    // --------------------------------------------------------------------------------------------
+
+    fn get_tree_node(
+        &self,
+        root: Word,
+        depth: &Felt,
+        index: &Felt,
+    ) -> Result<Word, ExecutionError> {
+        self.provider.get_tree_node(root, depth, index)
+    }
+
+    fn get_merkle_path(
+        &self,
+        root: Word,
+        depth: &Felt,
+        index: &Felt,
+    ) -> Result<MerklePath, ExecutionError> {
+        self.provider.get_merkle_path(root, depth, index)
+    }
+
+    fn get_leaf_depth(
+        &self,
+        root: Word,
+        tree_depth: &Felt,
+        index: &Felt,
+    ) -> Result<u8, ExecutionError> {
+        self.provider.get_leaf_depth(root, tree_depth, index)
+    }
+
+    fn update_merkle_node(
+        &mut self,
+        root: Word,
+        depth: &Felt,
+        index: &Felt,
+        value: Word,
+    ) -> Result<(MerklePath, Word), ExecutionError> {
+        self.provider.update_merkle_node(root, depth, index, value)
+    }
+
+    fn merge_roots(&mut self, lhs: Word, rhs: Word) -> Result<Word, ExecutionError> {
+        self.provider.merge_roots(lhs, rhs)
+    }
+}
+
+// RPC NODE ERROR
+// ================================================================================================
+
+/// The error returned when a Merkle tree node cannot be fetched through an [AdviceRpcClient].
+#[derive(Debug, thiserror::Error)]
+pub enum RpcNodeError<E> {
+    /// The request to the remote service failed.
+    #[error("failed to fetch the children of a Merkle tree node")]
+    Client(#[source] E),
+    /// The remote service returned children which do not hash to the requested node.
+    #[error("the children returned for Merkle tree node {node} do not hash to the node")]
+    InvalidChildren { node: RpoDigest },
+}
+
+// RPC NODE CACHE
+// ================================================================================================
+
+/// A [MerkleNodeStorage] which fetches the nodes missing from its cache through an
+/// [AdviceRpcClient].
+///
+/// Nodes which are not known to the remote service are cached as well, so that leaves are looked
+/// up at most once. Fetched nodes are cached only if their children hash to them.
+struct RpcNodeCache<C> {
+    client: C,
+    nodes: RefCell<BTreeMap<RpoDigest, Option<[RpoDigest; 2]>>>,
+}
+
+impl<C: AdviceRpcClient> MerkleNodeStorage for RpcNodeCache<C> {
+    type Error = RpcNodeError<C::Error>;
+
+    fn get_children(&self, node: &RpoDigest) -> Result<Option<[RpoDigest; 2]>, Self::Error> {
+        let cached = self.nodes.borrow().get(node).copied();
+        if let Some(children) = cached {
+            return Ok(children);
+        }
+
+        let children = block_on(self.client.get_children(*node)).map_err(RpcNodeError::Client)?;
+        if let Some(children) = children {
+            if Rpo256::merge(&children) != *node {
+                return Err(RpcNodeError::InvalidChildren { node: *node });
+            }
+        }
+        self.nodes.borrow_mut().insert(*node, children);
+        Ok(children)
+    }
+
+    fn insert(&mut self, node: RpoDigest, children: [RpoDigest; 2]) -> Result<(), Self::Error> {
+        self.nodes.get_mut().insert(node, Some(children));
+        Ok(())
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Drives the provided future to completion on the current thread, parking the thread while the
+/// future is pending.
+fn block_on<F: Future>(future: F) -> F::Output {
+    struct ThreadWaker(Thread);
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut context = Context::from_waker(&waker);
+    let mut future = pin!(future);
+    loop {
+        match future.as_mut().poll(&mut context) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use alloc::{collections::BTreeMap, vec::Vec};
+    use core::{
+        cell::Cell,
+        convert::Infallible,
+        future::Future,
+        pin::Pin,
+        task::{Context, Poll},
+    };
+
+    use vm_core::crypto::{
+        hash::RpoDigest,
+        merkle::{MerkleStore, MerkleTree, NodeIndex},
+    };
+
+    use super::{AdviceRpcClient, RpcAdviceProvider, RpcNodeError};
+    use crate::{
+        AdviceInputs, AdviceProvider, AdviceSource, ExecutionError, Felt, ONE, Word, ZERO,
+    };
+
+    #[test]
+    fn rpc_advice_provider() {
+        let leaves: Vec<Word> = (0..8).map(|i| [Felt::new(i), ZERO, ZERO, ZERO]).collect();
+        let tree = MerkleTree::new(leaves.clone()).unwrap();
+        let store: MerkleStore = MerkleStore::from(&tree);
+        let key = [ONE; 4];
+        let client = TestClient {
+            nodes: store.inner_nodes().map(|node| (node.value, [node.left, node.right])).collect(),
+            map: BTreeMap::from([(key.into(), vec![Felt::new(9), Felt::new(10)])]),
+            num_requests: Cell::new(0),
+        };
+        let mut provider = RpcAdviceProvider::new(AdviceInputs::default(), client);
+
+        // the path to the node is fetched from the service, and matches the path in the tree
+        let (depth, index) = (Felt::new(3), Felt::new(5));
+        let root = tree.root().into();
+        assert_eq!(provider.get_tree_node(root, &depth, &index).unwrap(), leaves[5]);
+        assert_eq!(
+            provider.get_merkle_path(root, &depth, &index).unwrap(),
+            tree.get_path(NodeIndex::new(3, 5).unwrap()).unwrap()
+        );
+        assert_eq!(provider.client().num_requests.get(), 3);
+
+        // the nodes are fetched only once
+        assert_eq!(provider.get_tree_node(root, &depth, &Felt::new(4)).unwrap(), leaves[4]);
+        assert_eq!(provider.client().num_requests.get(), 3);
+
+        // updated trees are kept locally, and nodes of the original tree are fetched on demand
+        let (_, new_root) = provider.update_merkle_node(root, &depth, &index, [ONE; 4]).unwrap();
+        assert_eq!(provider.get_tree_node(new_root, &depth, &Felt::new(0)).unwrap(), leaves[0]);
+        assert_eq!(provider.client().num_requests.get(), 5);
+
+        // map entries are fetched when they are pushed onto the advice stack, or resolved
+        assert!(provider.get_mapped_values(&key.into()).is_none());
+        provider.push_stack(AdviceSource::Map { key, include_len: false }).unwrap();
+        assert_eq!(
+            provider.get_mapped_values(&key.into()),
+            Some([Felt::new(9), Felt::new(10)].as_slice())
+        );
+        provider.resolve_mapped_values(&key.into()).unwrap();
+        assert_eq!(provider.client().num_requests.get(), 6);
+
+        // keys which are not known to the service are not an error until the values are used
+        provider.resolve_mapped_values(&[ZERO; 4].into()).unwrap();
+        assert!(provider.get_mapped_values(&[ZERO; 4].into()).is_none());
+        assert!(
+            provider
+                .push_stack(AdviceSource::Map { key: [ZERO; 4], include_len: false })
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn rpc_advice_provider_rejects_invalid_children() {
+        let leaves: Vec<Word> = (0..8).map(|i| [Felt::new(i), ZERO, ZERO, ZERO]).collect();
+        let tree = MerkleTree::new(leaves).unwrap();
+        let store: MerkleStore = MerkleStore::from(&tree);
+        let root = tree.root();
+
+        // the service swaps the children of the root
+        let mut nodes: BTreeMap<RpoDigest, [RpoDigest; 2]> =
+            store.inner_nodes().map(|node| (node.value, [node.left, node.right])).collect();
+        nodes.get_mut(&root).unwrap().swap(0, 1);
+        let client = TestClient {
+            nodes,
+            map: BTreeMap::new(),
+            num_requests: Cell::new(0),
+        };
+        let provider = RpcAdviceProvider::new(AdviceInputs::default(), client);
+        let num_cached_nodes = provider.num_cached_nodes();
+
+        let err = provider.get_tree_node(root.into(), &Felt::new(3), &Felt::new(5)).unwrap_err();
+        let ExecutionError::MerkleNodeStorageFailed(source) = err else {
+            panic!("unexpected error: {err:?}");
+        };
+        assert!(matches!(
+            source.downcast_ref::<RpcNodeError<Infallible>>(),
+            Some(RpcNodeError::InvalidChildren { node }) if *node == root
+        ));
+
+        // the invalid node is not cached, and is fetched again on the next access
+        assert_eq!(provider.num_cached_nodes(), num_cached_nodes);
+        assert!(provider.get_tree_node(root.into(), &Felt::new(3), &Felt::new(5)).is_err());
+        assert_eq!(provider.client().num_requests.get(), 2);
+    }
+
+    // HELPERS
+    // --------------------------------------------------------------------------------------------
+
+    /// A client which serves requests from memory, after yielding to the executor once.
+    struct TestClient {
+        nodes: BTreeMap<RpoDigest, [RpoDigest; 2]>,
+        map: BTreeMap<RpoDigest, Vec<Felt>>,
+        num_requests: Cell<usize>,
+    }
+
+    impl AdviceRpcClient for TestClient {
+        type Error = Infallible;
+
+        async fn get_children(
+            &self,
+            node: RpoDigest,
+        ) -> Result<Option<[RpoDigest; 2]>, Self::Error> {
+            self.num_requests.set(self.num_requests.get() + 1);
+            YieldOnce(false).await;
+            Ok(self.nodes.get(&node).copied())
+        }
+
+        async fn get_mapped_values(
+            &self,
+            key: RpoDigest,
+        ) -> Result<Option<Vec<Felt>>, Self::Error> {
+            self.num_requests.set(self.num_requests.get() + 1);
+            YieldOnce(false).await;
+            Ok(self.map.get(&key).cloned())
+        }
+    }
+
+    /// A future which is pending when it is first polled.
+    struct YieldOnce(bool);
+
+    impl Future for YieldOnce {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            if self.0 {
+                return Poll::Ready(());
+            }
+            self.0 = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use alloc::{collections::BTreeMap, vec::Vec};
+
+use vm_core::crypto::merkle::{EmptySubtreeRoots, MerkleError, NodeIndex};
+
+use super::{RpoDigest, Word};
+
+// SPARSE MERKLE TREES
+// ================================================================================================
+
+/// Validates the leaves of a sparse Merkle tree of the specified depth, and returns them sorted by
+/// index.
+///
+/// Leaves set to the empty word are omitted, since they are implied by the tree being sparse.
+///
+/// # Errors
+/// Returns an error if:
+/// - The depth is zero or greater than 64.
+/// - The index of a leaf is not valid for the depth of the tree.
+/// - Multiple leaves are provided for the same index.
+pub(super) fn sparse_leaves(
+    depth: u8,
+    leaves: impl IntoIterator<Item = (u64, Word)>,
+) -> Result<BTreeMap<u64, RpoDigest>, MerkleError> {
+    if depth == 0 {
+        return Err(MerkleError::DepthTooSmall(depth));
+    }
+    if depth > 64 {
+        return Err(MerkleError::DepthTooBig(depth as u64));
+    }
+
+    let mut sparse_leaves = BTreeMap::new();
+    for (index, value) in leaves {
+        NodeIndex::new(depth, index)?;
+        if sparse_leaves.insert(index, RpoDigest::from(value)).is_some() {
+            return Err(MerkleError::DuplicateValuesForIndex(index));
+        }
+    }
+    let empty_leaf = *EmptySubtreeRoots::entry(depth, depth);
+    sparse_leaves.retain(|_, leaf| *leaf != empty_leaf);
+
+    Ok(sparse_leaves)
+}
+
+/// Computes the root of a sparse Merkle tree of the specified depth with the provided leaves.
+///
+/// The tree is built bottom up, and `merge` is invoked with the children of every internal node
+/// which has at least one non-empty descendant; subtrees without any leaves are represented by the
+/// roots of empty subtrees and are never materialized. Thus, building a tree with `n` leaves
+/// requires at most `n * depth` merges, regardless of the depth of the tree.
+///
+/// The leaves must have been validated via [sparse_leaves].
+pub(super) fn build_sparse_tree<E>(
+    depth: u8,
+    leaves: BTreeMap<u64, RpoDigest>,
+    mut merge: impl FnMut(RpoDigest, RpoDigest) -> Result<RpoDigest, E>,
+) -> Result<RpoDigest, E> {
+    let mut nodes: Vec<(u64, RpoDigest)> = leaves.into_iter().collect();
+    for node_depth in (1..=depth).rev() {
+        let empty_node = *EmptySubtreeRoots::entry(depth, node_depth);
+
+        // nodes are sorted by index, so siblings are always adjacent
+        let mut parents = Vec::with_capacity(nodes.len());
+        let mut nodes_iter = nodes.into_iter().peekable();
+        while let Some((index, node)) = nodes_iter.next() {
+            let (left, right) = if index & 1 == 1 {
+                (empty_node, node)
+            } else if let Some((_, sibling)) = nodes_iter.next_if(|(next, _)| *next == index + 1) {
+                (node, sibling)
+            } else {
+                (node, empty_node)
+            };
+            parents.push((index >> 1, merge(left, right)?));
+        }
+        nodes = parents;
+    }
+
+    match nodes.first() {
+        Some(&(_, root)) => Ok(root),
+        None => Ok(*EmptySubtreeRoots::entry(depth, 0)),
+    }
+}
@@ -0,0 +1,544 @@
+use alloc::{format, vec::Vec};
+use core::cell::{Cell, RefCell};
+
+use vm_core::utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+
+use super::{AdviceProvider, AdviceSource, ExecutionError, Felt, MerklePath, RpoDigest, Word};
+use crate::ProcessState;
+
+// ADVICE TAPE
+// ================================================================================================
+
+/// A journal of all values read from an advice provider during an execution, in the order in which
+/// they were read.
+///
+/// A tape is recorded by wrapping the advice provider of an execution into an
+/// [AdviceTapeRecorder], and can be replayed via an [AdviceTapeReplayer]. Since the values read
+/// from the advice provider are the only source of non-determinism of an execution, replaying the
+/// tape of an execution of a program reproduces the execution exactly, without access to the
+/// original advice provider (e.g., to external sources of advice used by the host).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AdviceTape {
+    entries: Vec<AdviceTapeEntry>,
+}
+
+impl AdviceTape {
+    /// Returns the entries of this tape, in the order in which they were recorded.
+    pub fn entries(&self) -> &[AdviceTapeEntry] {
+        &self.entries
+    }
+
+    /// Returns the number of entries in this tape.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if no values were recorded on this tape.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl Serializable for AdviceTape {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_usize(self.entries.len());
+        target.write_many(&self.entries);
+    }
+}
+
+impl Deserializable for AdviceTape {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let num_entries = source.read_usize()?;
+        let entries = source.read_many(num_entries)?;
+        Ok(Self { entries })
+    }
+}
+
+// ADVICE TAPE ENTRY
+// ================================================================================================
+
+/// A single read from an advice provider recorded on an [AdviceTape].
+///
+/// Entries of reads from the advice map and the Merkle store include the request, so that a
+/// replayed execution which diverges from the recorded one is detected at the first diverging
+/// request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AdviceTapeEntry {
+    /// An element popped from the advice stack.
+    StackElement(Felt),
+    /// A word popped from the advice stack.
+    StackWord(Word),
+    /// The values associated with a key in the advice map, if any.
+    MapValues {
+        key: RpoDigest,
+        values: Option<Vec<Felt>>,
+    },
+    /// A node of a Merkle tree.
+    TreeNode {
+        root: Word,
+        depth: Felt,
+        index: Felt,
+        node: Word,
+    },
+    /// A path to a node of a Merkle tree.
+    MerklePath {
+        root: Word,
+        depth: Felt,
+        index: Felt,
+        path: MerklePath,
+    },
+    /// The depth of a leaf of a Merkle tree.
+    LeafDepth {
+        root: Word,
+        tree_depth: Felt,
+        index: Felt,
+        depth: u8,
+    },
+    /// An update of a node of a Merkle tree, with the path to the node and the new root.
+    NodeUpdate {
+        root: Word,
+        depth: Felt,
+        index: Felt,
+        value: Word,
+        path: MerklePath,
+        new_root: Word,
+    },
+    /// A merge of two Merkle trees, with the root of the merged tree.
+    RootMerge { lhs: Word, rhs: Word, root: Word },
+}
+
+impl AdviceTapeEntry {
+    const STACK_ELEMENT: u8 = 0;
+    const STACK_WORD: u8 = 1;
+    const MAP_VALUES: u8 = 2;
+    const TREE_NODE: u8 = 3;
+    const MERKLE_PATH: u8 = 4;
+    const LEAF_DEPTH: u8 = 5;
+    const NODE_UPDATE: u8 = 6;
+    const ROOT_MERGE: u8 = 7;
+}
+
+impl Serializable for AdviceTapeEntry {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        match self {
+            Self::StackElement(element) => {
+                target.write_u8(Self::STACK_ELEMENT);
+                element.write_into(target);
+            },
+            Self::StackWord(word) => {
+                target.write_u8(Self::STACK_WORD);
+                word.write_into(target);
+            },
+            Self::MapValues { key, values } => {
+                target.write_u8(Self::MAP_VALUES);
+                key.write_into(target);
+                values.write_into(target);
+            },
+            Self::TreeNode { root, depth, index, node } => {
+                target.write_u8(Self::TREE_NODE);
+                root.write_into(target);
+                depth.write_into(target);
+                index.write_into(target);
+                node.write_into(target);
+            },
+            Self::MerklePath { root, depth, index, path } => {
+                target.write_u8(Self::MERKLE_PATH);
+                root.write_into(target);
+                depth.write_into(target);
+                index.write_into(target);
+                path.write_into(target);
+            },
+            Self::LeafDepth { root, tree_depth, index, depth } => {
+                target.write_u8(Self::LEAF_DEPTH);
+                root.write_into(target);
+                tree_depth.write_into(target);
+                index.write_into(target);
+                target.write_u8(*depth);
+            },
+            Self::NodeUpdate {
+                root,
+                depth,
+                index,
+                value,
+                path,
+                new_root,
+            } => {
+                target.write_u8(Self::NODE_UPDATE);
+                root.write_into(target);
+                depth.write_into(target);
+                index.write_into(target);
+                value.write_into(target);
+                path.write_into(target);
+                new_root.write_into(target);
+            },
+            Self::RootMerge { lhs, rhs, root } => {
+                target.write_u8(Self::ROOT_MERGE);
+                lhs.write_into(target);
+                rhs.write_into(target);
+                root.write_into(target);
+            },
+        }
+    }
+}
+
+impl Deserializable for AdviceTapeEntry {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        match source.read_u8()? {
+            Self::STACK_ELEMENT => Ok(Self::StackElement(source.read()?)),
+            Self::STACK_WORD => Ok(Self::StackWord(source.read()?)),
+            Self::MAP_VALUES => Ok(Self::MapValues {
+                key: source.read()?,
+                values: source.read()?,
+            }),
+            Self::TREE_NODE => Ok(Self::TreeNode {
+                root: source.read()?,
+                depth: source.read()?,
+                index: source.read()?,
+                node: source.read()?,
+            }),
+            Self::MERKLE_PATH => Ok(Self::MerklePath {
+                root: source.read()?,
+                depth: source.read()?,
+                index: source.read()?,
+                path: source.read()?,
+            }),
+            Self::LEAF_DEPTH => Ok(Self::LeafDepth {
+                root: source.read()?,
+                tree_depth: source.read()?,
+                index: source.read()?,
+                depth: source.read_u8()?,
+            }),
+            Self::NODE_UPDATE => Ok(Self::NodeUpdate {
+                root: source.read()?,
+                depth: source.read()?,
+                index: source.read()?,
+                value: source.read()?,
+                path: source.read()?,
+                new_root: source.read()?,
+            }),
+            Self::ROOT_MERGE => Ok(Self::RootMerge {
+                lhs: source.read()?,
+                rhs: source.read()?,
+                root: source.read()?,
+            }),
+            tag => Err(DeserializationError::InvalidValue(format!(
+                "invalid advice tape entry tag {tag}"
+            ))),
+        }
+    }
+}
+
+// ADVICE TAPE RECORDER
+// ================================================================================================
+
+/// An [AdviceProvider] which records all values read from the wrapped advice provider on an
+/// [AdviceTape].
+///
+/// Only successful reads are recorded. Writes to the advice provider (e.g., values pushed onto the
+/// advice stack) are not recorded, since they are reproduced by the replayed execution itself.
+#[derive(Debug, Clone, Default)]
+pub struct AdviceTapeRecorder<A> {
+    provider: A,
+    tape: RefCell<AdviceTape>,
+}
+
+impl<A: AdviceProvider> AdviceTapeRecorder<A> {
+    /// Returns a recorder which records the values read from the provided advice provider.
+    pub fn new(provider: A) -> Self {
+        Self { provider, tape: RefCell::default() }
+    }
+
+    /// Returns a reference to the wrapped advice provider.
+    pub fn provider(&self) -> &A {
+        &self.provider
+    }
+
+    /// Consumes the recorder and returns the recorded tape together with the wrapped advice
+    /// provider.
+    pub fn finalize(self) -> (AdviceTape, A) {
+        (self.tape.into_inner(), self.provider)
+    }
+
+    /// Appends the provided entry to the tape.
+    fn record(&self, entry: AdviceTapeEntry) {
+        self.tape.borrow_mut().entries.push(entry);
+    }
+}
+
+impl<A: AdviceProvider> AdviceProvider for AdviceTapeRecorder<A> {
+    fn pop_stack(&mut self, process: ProcessState) -> Result<Felt, ExecutionError> {
+        let element = self.provider.pop_stack(process)?;
+        self.record(AdviceTapeEntry::StackElement(element));
+        Ok(element)
+    }
+
+    fn pop_stack_word(&mut self, process: ProcessState) -> Result<Word, ExecutionError> {
+        let word = self.provider.pop_stack_word(process)?;
+        self.record(AdviceTapeEntry::StackWord(word));
+        Ok(word)
+    }
+
+    fn pop_stack_dword(&mut self, process: ProcessState) -> Result<[Word; 2], ExecutionError> {
+        let words = self.provider.pop_stack_dword(process)?;
+        self.record(AdviceTapeEntry::StackWord(words[0]));
+        self.record(AdviceTapeEntry::StackWord(words[1]));
+        Ok(words)
+    }
+
+    fn push_stack(&mut self, source: AdviceSource) -> Result<(), ExecutionError> {
+        self.provider.push_stack(source)
+    }
+
+    fn get_mapped_values(&self, key: &RpoDigest) -> Option<&[Felt]> {
+        let values = self.provider.get_mapped_values(key);
+        self.record(AdviceTapeEntry::MapValues {
+            key: *key,
+            values: values.map(|values| values.to_vec()),
+        });
+        values
+    }
+
+    fn insert_into_map(&mut self, key: Word, values: Vec<Felt>) {
+        self.provider.insert_into_map(key, values)
+    }
+
+    fn get_tree_node(
+        &self,
+        root: Word,
+        depth: &Felt,
+        index: &Felt,
+    ) -> Result<Word, ExecutionError> {
+        let node = self.provider.get_tree_node(root, depth, index)?;
+        self.record(AdviceTapeEntry::TreeNode { root, depth: *depth, index: *index, node });
+        Ok(node)
+    }
+
+    fn get_merkle_path(
+        &self,
+        root: Word,
+        depth: &Felt,
+        index: &Felt,
+    ) -> Result<MerklePath, ExecutionError> {
+        let path = self.provider.get_merkle_path(root, depth, index)?;
+        self.record(AdviceTapeEntry::MerklePath {
+            root,
+            depth: *depth,
+            index: *index,
+            path: path.clone(),
+        });
+        Ok(path)
+    }
+
+    fn get_leaf_depth(
+        &self,
+        root: Word,
+        tree_depth: &Felt,
+        index: &Felt,
+    ) -> Result<u8, ExecutionError> {
+        let depth = self.provider.get_leaf_depth(root, tree_depth, index)?;
+        self.record(AdviceTapeEntry::LeafDepth {
+            root,
+            tree_depth: *tree_depth,
+            index: *index,
+            depth,
+        });
+        Ok(depth)
+    }
+
+    fn update_merkle_node(
+        &mut self,
+        root: Word,
+        depth: &Felt,
+        index: &Felt,
+        value: Word,
+    ) -> Result<(MerklePath, Word), ExecutionError> {
+        let (path, new_root) = self.provider.update_merkle_node(root, depth, index, value)?;
+        self.record(AdviceTapeEntry::NodeUpdate {
+            root,
+            depth: *depth,
+            index: *index,
+            value,
+            path: path.clone(),
+            new_root,
+        });
+        Ok((path, new_root))
+    }
+
+    fn merge_roots(&mut self, lhs: Word, rhs: Word) -> Result<Word, ExecutionError> {
+        let root = self.provider.merge_roots(lhs, rhs)?;
+        self.record(AdviceTapeEntry::RootMerge { lhs, rhs, root });
+        Ok(root)
+    }
+
+    fn resolve_mapped_values(&mut self, key: &RpoDigest) -> Result<(), ExecutionError> {
+        self.provider.resolve_mapped_values(key)
+    }
+}
+
+// ADVICE TAPE REPLAYER
+// ================================================================================================
+
+/// An [AdviceProvider] which serves all reads from a previously recorded [AdviceTape].
+///
+/// The replayer doesn't hold an advice stack, map or Merkle store: each read returns the next
+/// entry of the tape, and writes are ignored. If a read doesn't match the next entry of the tape
+/// (e.g., because the replayed execution diverged from the recorded one, or because the recorded
+/// execution failed at this point), the read fails with
+/// [ExecutionError::AdviceTapeMismatch]. Reads from the advice map which don't match the tape
+/// return `None`.
+#[derive(Debug, Clone, Default)]
+pub struct AdviceTapeReplayer {
+    tape: AdviceTape,
+    position: Cell<usize>,
+}
+
+impl AdviceTapeReplayer {
+    /// Returns a replayer which serves reads from the provided tape, starting with its first
+    /// entry.
+    pub fn new(tape: AdviceTape) -> Self {
+        Self { tape, position: Cell::new(0) }
+    }
+
+    /// Returns the number of tape entries which were not yet replayed.
+    pub fn num_remaining_entries(&self) -> usize {
+        self.tape.len() - self.position.get()
+    }
+
+    /// Reads a value from the next entry of the tape via the provided function, and advances the
+    /// tape past the entry.
+    ///
+    /// # Errors
+    /// Returns an error naming the `request` if the tape is exhausted or the value cannot be read
+    /// from the next entry (i.e., `read` returns `None`).
+    fn next_entry<'a, T>(
+        &'a self,
+        request: &'static str,
+        read: impl FnOnce(&'a AdviceTapeEntry) -> Option<T>,
+    ) -> Result<T, ExecutionError> {
+        let position = self.position.get();
+        let value = self
+            .tape
+            .entries
+            .get(position)
+            .and_then(read)
+            .ok_or(ExecutionError::AdviceTapeMismatch { position, request })?;
+        self.position.set(position + 1);
+        Ok(value)
+    }
+
+    fn next_stack_word(&self) -> Result<Word, ExecutionError> {
+        self.next_entry("stack word", |entry| match entry {
+            AdviceTapeEntry::StackWord(word) => Some(*word),
+            _ => None,
+        })
+    }
+}
+
+impl AdviceProvider for AdviceTapeReplayer {
+    fn pop_stack(&mut self, _process: ProcessState) -> Result<Felt, ExecutionError> {
+        self.next_entry("stack element", |entry| match entry {
+            AdviceTapeEntry::StackElement(element) => Some(*element),
+            _ => None,
+        })
+    }
+
+    fn pop_stack_word(&mut self, _process: ProcessState) -> Result<Word, ExecutionError> {
+        self.next_stack_word()
+    }
+
+    fn pop_stack_dword(&mut self, _process: ProcessState) -> Result<[Word; 2], ExecutionError> {
+        Ok([self.next_stack_word()?, self.next_stack_word()?])
+    }
+
+    fn push_stack(&mut self, _source: AdviceSource) -> Result<(), ExecutionError> {
+        Ok(())
+    }
+
+    fn get_mapped_values(&self, key: &RpoDigest) -> Option<&[Felt]> {
+        self.next_entry("map lookup", |entry| match entry {
+            AdviceTapeEntry::MapValues { key: k, values } if k == key => Some(values.as_deref()),
+            _ => None,
+        })
+        .ok()
+        .flatten()
+    }
+
+    fn insert_into_map(&mut self, _key: Word, _values: Vec<Felt>) {}
+
+    fn get_tree_node(
+        &self,
+        root: Word,
+        depth: &Felt,
+        index: &Felt,
+    ) -> Result<Word, ExecutionError> {
+        self.next_entry("tree node", |entry| match entry {
+            AdviceTapeEntry::TreeNode { root: r, depth: d, index: i, node }
+                if (*r, d, i) == (root, depth, index) =>
+            {
+                Some(*node)
+            },
+            _ => None,
+        })
+    }
+
+    fn get_merkle_path(
+        &self,
+        root: Word,
+        depth: &Felt,
+        index: &Felt,
+    ) -> Result<MerklePath, ExecutionError> {
+        self.next_entry("Merkle path", |entry| match entry {
+            AdviceTapeEntry::MerklePath { root: r, depth: d, index: i, path }
+                if (*r, d, i) == (root, depth, index) =>
+            {
+                Some(path.clone())
+            },
+            _ => None,
+        })
+    }
+
+    fn get_leaf_depth(
+        &self,
+        root: Word,
+        tree_depth: &Felt,
+        index: &Felt,
+    ) -> Result<u8, ExecutionError> {
+        self.next_entry("leaf depth", |entry| match entry {
+            AdviceTapeEntry::LeafDepth { root: r, tree_depth: t, index: i, depth }
+                if (*r, t, i) == (root, tree_depth, index) =>
+            {
+                Some(*depth)
+            },
+            _ => None,
+        })
+    }
+
+    fn update_merkle_node(
+        &mut self,
+        root: Word,
+        depth: &Felt,
+        index: &Felt,
+        value: Word,
+    ) -> Result<(MerklePath, Word), ExecutionError> {
+        self.next_entry("node update", |entry| match entry {
+            AdviceTapeEntry::NodeUpdate {
+                root: r,
+                depth: d,
+                index: i,
+                value: v,
+                path,
+                new_root,
+            } if (*r, d, i, *v) == (root, depth, index, value) => Some((path.clone(), *new_root)),
+            _ => None,
+        })
+    }
+
+    fn merge_roots(&mut self, lhs: Word, rhs: Word) -> Result<Word, ExecutionError> {
+        self.next_entry("root merge", |entry| match entry {
+            AdviceTapeEntry::RootMerge { lhs: l, rhs: r, root } if (*l, *r) == (lhs, rhs) => {
+                Some(*root)
+            },
+            _ => None,
+        })
+    }
+}
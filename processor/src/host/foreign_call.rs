@@ -0,0 +1,256 @@
+use alloc::{boxed::Box, string::ToString, vec::Vec};
+use core::{error::Error, marker::PhantomData};
+
+use vm_core::{Felt, Word, crypto::hash::Rpo256, stack::MIN_STACK_DEPTH};
+
+use super::{
+    EventHandler, ExecutionError, ProcessState,
+    advice::{AdviceProvider, AdviceSource},
+};
+
+// FOREIGN CALLS
+// ================================================================================================
+//
+// A foreign call invokes a function implemented by the host, identified by its name. The calling
+// convention is built on top of events:
+// - The program puts the arguments of the function on the stack, with the first argument at the
+//   top, and emits the event with the ID derived from the name of the function (see
+//   `foreign_function_id()`). Emitting the event doesn't change the stack.
+// - The host decodes the arguments from the stack, invokes the function and pushes its results
+//   onto the advice stack.
+// - The program reads the results via `adv_push.N`, where `N` is the number of elements of the
+//   results, after which the results are on the stack with the first result at the top.
+
+/// Returns the ID of the event through which the foreign function with the specified name is
+/// called.
+///
+/// The ID consists of the lower 32 bits of the first element of the RPO hash of the name.
+pub fn foreign_function_id(name: &str) -> u32 {
+    Rpo256::hash(name.as_bytes())[0].as_int() as u32
+}
+
+// FOREIGN CALL HANDLER
+// ================================================================================================
+
+/// An event handler which calls a foreign function with the arguments on the stack and pushes its
+/// results onto the advice stack, according to the foreign call convention.
+pub(super) struct ForeignCallHandler<Args, Ret, F> {
+    name: Box<str>,
+    function: F,
+    _signature: PhantomData<fn(Args) -> Ret>,
+}
+
+impl<Args, Ret, F> ForeignCallHandler<Args, Ret, F>
+where
+    Args: ForeignValue,
+    Ret: ForeignValue,
+    F: Fn(Args) -> Result<Ret, ForeignCallError> + Send + Sync,
+{
+    /// Returns a handler for the foreign function with the specified name.
+    ///
+    /// # Errors
+    /// Returns an error if the arguments of the function don't fit into the top 16 elements of
+    /// the stack.
+    pub fn new(name: &str, function: F) -> Result<Self, ExecutionError> {
+        if Args::NUM_ELEMENTS > MIN_STACK_DEPTH {
+            return Err(ExecutionError::ForeignCallTooManyArguments {
+                function: name.to_string(),
+                num_elements: Args::NUM_ELEMENTS,
+            });
+        }
+
+        Ok(Self {
+            name: name.into(),
+            function,
+            _signature: PhantomData,
+        })
+    }
+}
+
+impl<A, Args, Ret, F> EventHandler<A> for ForeignCallHandler<Args, Ret, F>
+where
+    A: AdviceProvider,
+    Args: ForeignValue,
+    Ret: ForeignValue,
+    F: Fn(Args) -> Result<Ret, ForeignCallError> + Send + Sync,
+{
+    fn on_event(
+        &self,
+        process: ProcessState,
+        advice_provider: &mut A,
+    ) -> Result<(), ExecutionError> {
+        let args: Vec<Felt> = (0..Args::NUM_ELEMENTS).map(|i| process.get_stack_item(i)).collect();
+        let results = Args::decode(&args).and_then(&self.function).map_err(|source| {
+            ExecutionError::ForeignCallFailed { function: self.name.to_string(), source }
+        })?;
+
+        // the results are pushed in stack order, so that the first element is popped last and
+        // ends up at the top of the operand stack
+        let mut elements = Vec::with_capacity(Ret::NUM_ELEMENTS);
+        results.encode(&mut elements);
+        for element in elements {
+            advice_provider.push_stack(AdviceSource::Value(element))?;
+        }
+        Ok(())
+    }
+}
+
+// FOREIGN CALL ERROR
+// ================================================================================================
+
+/// An error which occurred while executing a foreign call.
+#[derive(Debug, thiserror::Error)]
+pub enum ForeignCallError {
+    #[error("argument element {value} is not a valid {expected}")]
+    InvalidArgument { value: Felt, expected: &'static str },
+    #[error("foreign function returned an error")]
+    Failed(#[source] Box<dyn Error + Send + Sync + 'static>),
+}
+
+impl ForeignCallError {
+    /// Returns an error for a foreign function which failed with the provided error.
+    pub fn failed(error: impl Into<Box<dyn Error + Send + Sync + 'static>>) -> Self {
+        Self::Failed(error.into())
+    }
+}
+
+// FOREIGN VALUE
+// ================================================================================================
+
+/// A value which can be passed to, or returned from, a foreign function.
+///
+/// Values are encoded as a fixed number of field elements, in the order in which they appear on
+/// the stack (i.e., the element at the top of the stack first). The encodings match the
+/// conventions of Miden assembly:
+/// - `bool` and `u32` values are encoded as a single element.
+/// - `u64` values are encoded as their high and low 32-bit limbs, with the high limb first.
+/// - Words are encoded as they are laid out on the stack, i.e., with their last element first.
+/// - Tuples are encoded as the concatenation of the encodings of their components, with the first
+///   component first.
+pub trait ForeignValue: Sized {
+    /// The number of field elements encoding a value.
+    const NUM_ELEMENTS: usize;
+
+    /// Decodes a value from exactly [ForeignValue::NUM_ELEMENTS] elements.
+    fn decode(elements: &[Felt]) -> Result<Self, ForeignCallError>;
+
+    /// Appends the [ForeignValue::NUM_ELEMENTS] elements encoding this value to `target`.
+    fn encode(&self, target: &mut Vec<Felt>);
+}
+
+impl ForeignValue for () {
+    const NUM_ELEMENTS: usize = 0;
+
+    fn decode(_elements: &[Felt]) -> Result<Self, ForeignCallError> {
+        Ok(())
+    }
+
+    fn encode(&self, _target: &mut Vec<Felt>) {}
+}
+
+impl ForeignValue for Felt {
+    const NUM_ELEMENTS: usize = 1;
+
+    fn decode(elements: &[Felt]) -> Result<Self, ForeignCallError> {
+        Ok(elements[0])
+    }
+
+    fn encode(&self, target: &mut Vec<Felt>) {
+        target.push(*self);
+    }
+}
+
+impl ForeignValue for bool {
+    const NUM_ELEMENTS: usize = 1;
+
+    fn decode(elements: &[Felt]) -> Result<Self, ForeignCallError> {
+        match elements[0].as_int() {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(ForeignCallError::InvalidArgument { value: elements[0], expected: "bool" }),
+        }
+    }
+
+    fn encode(&self, target: &mut Vec<Felt>) {
+        target.push(Felt::from(*self));
+    }
+}
+
+impl ForeignValue for u32 {
+    const NUM_ELEMENTS: usize = 1;
+
+    fn decode(elements: &[Felt]) -> Result<Self, ForeignCallError> {
+        decode_u32(elements[0])
+    }
+
+    fn encode(&self, target: &mut Vec<Felt>) {
+        target.push(Felt::from(*self));
+    }
+}
+
+impl ForeignValue for u64 {
+    const NUM_ELEMENTS: usize = 2;
+
+    fn decode(elements: &[Felt]) -> Result<Self, ForeignCallError> {
+        let hi = decode_u32(elements[0])? as u64;
+        let lo = decode_u32(elements[1])? as u64;
+        Ok((hi << 32) | lo)
+    }
+
+    fn encode(&self, target: &mut Vec<Felt>) {
+        target.push(Felt::from((*self >> 32) as u32));
+        target.push(Felt::from(*self as u32));
+    }
+}
+
+impl ForeignValue for Word {
+    const NUM_ELEMENTS: usize = 4;
+
+    fn decode(elements: &[Felt]) -> Result<Self, ForeignCallError> {
+        Ok([elements[3], elements[2], elements[1], elements[0]])
+    }
+
+    fn encode(&self, target: &mut Vec<Felt>) {
+        target.extend(self.iter().rev());
+    }
+}
+
+macro_rules! impl_foreign_value_for_tuple {
+    ($($name:ident),+) => {
+        impl<$($name: ForeignValue),+> ForeignValue for ($($name,)+) {
+            const NUM_ELEMENTS: usize = 0 $(+ $name::NUM_ELEMENTS)+;
+
+            #[allow(non_snake_case)]
+            fn decode(elements: &[Felt]) -> Result<Self, ForeignCallError> {
+                let mut offset = 0;
+                $(
+                    let $name = $name::decode(&elements[offset..offset + $name::NUM_ELEMENTS])?;
+                    offset += $name::NUM_ELEMENTS;
+                )+
+                let _ = offset;
+                Ok(($($name,)+))
+            }
+
+            #[allow(non_snake_case)]
+            fn encode(&self, target: &mut Vec<Felt>) {
+                let ($($name,)+) = self;
+                $($name.encode(target);)+
+            }
+        }
+    };
+}
+
+impl_foreign_value_for_tuple!(A);
+impl_foreign_value_for_tuple!(A, B);
+impl_foreign_value_for_tuple!(A, B, C);
+impl_foreign_value_for_tuple!(A, B, C, D);
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn decode_u32(element: Felt) -> Result<u32, ForeignCallError> {
+    element
+        .as_int()
+        .try_into()
+        .map_err(|_| ForeignCallError::InvalidArgument { value: element, expected: "u32" })
+}
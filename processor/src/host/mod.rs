@@ -1,6 +1,9 @@
-use alloc::sync::Arc;
+use alloc::{collections::BTreeMap, sync::Arc};
 
-use vm_core::{DebugOptions, crypto::hash::RpoDigest, mast::MastForest};
+use vm_core::{
+    DebugOptions, ExecutionEnvironment, crypto::hash::RpoDigest, mast::MastForest,
+    sys_events::SystemEvent,
+};
 
 use super::{ExecutionError, ProcessState};
 use crate::{KvMap, MemAdviceProvider};
@@ -11,9 +14,18 @@ use advice::AdviceProvider;
 #[cfg(feature = "std")]
 mod debug;
 
+mod foreign_call;
+pub use foreign_call::{ForeignCallError, ForeignValue, foreign_function_id};
+
 mod mast_forest_store;
 pub use mast_forest_store::{MastForestStore, MemMastForestStore};
 
+mod random;
+pub use random::{EVENT_RANDOM_WORD, RandomSource};
+
+mod storage;
+pub use storage::{EVENT_STORAGE_SET, StorageHost, storage_map_key, storage_slot_key};
+
 // HOST TRAIT
 // ================================================================================================
 
@@ -43,6 +55,16 @@ pub trait Host {
     // PROVIDED METHODS
     // --------------------------------------------------------------------------------------------
 
+    /// Returns the environment in which programs are executed, which programs read via the
+    /// `std::sys` module of the standard library, and which is committed to in the public inputs
+    /// of proofs.
+    ///
+    /// The default implementation returns the default environment, which has all values set to
+    /// zero.
+    fn environment(&self) -> ExecutionEnvironment {
+        ExecutionEnvironment::default()
+    }
+
     /// Handles the event emitted from the VM.
     fn on_event(&mut self, _process: ProcessState, _event_id: u32) -> Result<(), ExecutionError> {
         #[cfg(feature = "std")]
@@ -106,6 +128,10 @@ where
         H::get_mast_forest(self, node_digest)
     }
 
+    fn environment(&self) -> ExecutionEnvironment {
+        H::environment(self)
+    }
+
     fn on_debug(
         &mut self,
         process: ProcessState,
@@ -127,13 +153,52 @@ where
     }
 }
 
+// EVENT HANDLER
+// ================================================================================================
+
+/// Handles an event emitted from the VM via the `emit` instruction.
+///
+/// Event handlers are registered for specific event IDs with
+/// [DefaultHost::register_event_handler]. A handler can inspect the state of the VM at the time the
+/// event was emitted and mutate the advice provider of the host, e.g., to push the result of a
+/// computation performed by the host onto the advice stack.
+///
+/// This trait is implemented for closures taking the same arguments as [EventHandler::on_event].
+pub trait EventHandler<A>: Send + Sync {
+    /// Handles the event emitted from the VM.
+    fn on_event(
+        &self,
+        process: ProcessState,
+        advice_provider: &mut A,
+    ) -> Result<(), ExecutionError>;
+}
+
+impl<A, F> EventHandler<A> for F
+where
+    F: Fn(ProcessState, &mut A) -> Result<(), ExecutionError> + Send + Sync,
+{
+    fn on_event(
+        &self,
+        process: ProcessState,
+        advice_provider: &mut A,
+    ) -> Result<(), ExecutionError> {
+        self(process, advice_provider)
+    }
+}
+
 // DEFAULT HOST IMPLEMENTATION
 // ================================================================================================
 
 /// A default [Host] implementation that provides the essential functionality required by the VM.
+///
+/// Events emitted by programs are dispatched to the handlers registered for their IDs via
+/// [DefaultHost::register_event_handler]; events without a registered handler are ignored, other
+/// than being printed when the `std` feature is enabled.
 pub struct DefaultHost<A> {
     adv_provider: A,
     store: MemMastForestStore,
+    event_handlers: BTreeMap<u32, Arc<dyn EventHandler<A>>>,
+    environment: ExecutionEnvironment,
 }
 
 impl<A: Clone> Clone for DefaultHost<A> {
@@ -141,6 +206,8 @@ impl<A: Clone> Clone for DefaultHost<A> {
         Self {
             adv_provider: self.adv_provider.clone(),
             store: self.store.clone(),
+            event_handlers: self.event_handlers.clone(),
+            environment: self.environment,
         }
     }
 }
@@ -150,6 +217,8 @@ impl Default for DefaultHost<MemAdviceProvider> {
         Self {
             adv_provider: MemAdviceProvider::default(),
             store: MemMastForestStore::default(),
+            event_handlers: BTreeMap::new(),
+            environment: ExecutionEnvironment::default(),
         }
     }
 }
@@ -159,9 +228,78 @@ impl<A: AdviceProvider> DefaultHost<A> {
         Self {
             adv_provider,
             store: MemMastForestStore::default(),
+            event_handlers: BTreeMap::new(),
+            environment: ExecutionEnvironment::default(),
         }
     }
 
+    /// Returns this host with the environment in which programs are executed set to the
+    /// specified one.
+    pub fn with_environment(mut self, environment: ExecutionEnvironment) -> Self {
+        self.environment = environment;
+        self
+    }
+
+    /// Registers the provided handler for events with the specified ID.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The event ID is reserved for a system event, which is always handled by the VM itself.
+    /// - A handler is already registered for the event ID.
+    pub fn register_event_handler(
+        &mut self,
+        event_id: u32,
+        handler: impl EventHandler<A> + 'static,
+    ) -> Result<(), ExecutionError> {
+        if SystemEvent::from_event_id(event_id).is_some() {
+            return Err(ExecutionError::ReservedEventId(event_id));
+        }
+        if self.event_handlers.contains_key(&event_id) {
+            return Err(ExecutionError::EventHandlerAlreadyRegistered(event_id));
+        }
+
+        self.event_handlers.insert(event_id, Arc::new(handler));
+        Ok(())
+    }
+
+    /// Registers a foreign function with the specified name, and returns the ID of the event
+    /// through which programs call the function.
+    ///
+    /// To call the function, a program puts its arguments on the stack (with the first argument
+    /// at the top), emits the event with the returned ID, and reads the results from the advice
+    /// stack via `adv_push.N`, where `N` is the number of elements encoding the results (see
+    /// [ForeignValue]). After that, the results are on the stack with the first result at the
+    /// top. The arguments are left on the stack.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The arguments of the function are encoded by more than 16 elements.
+    /// - The event ID derived from the name (see [foreign_function_id]) is reserved for a system
+    ///   event, or a handler is already registered for it.
+    pub fn register_foreign_function<Args, Ret, F>(
+        &mut self,
+        name: &str,
+        function: F,
+    ) -> Result<u32, ExecutionError>
+    where
+        Args: ForeignValue + 'static,
+        Ret: ForeignValue + 'static,
+        F: Fn(Args) -> Result<Ret, ForeignCallError> + Send + Sync + 'static,
+    {
+        let event_id = foreign_function_id(name);
+        self.register_event_handler(
+            event_id,
+            foreign_call::ForeignCallHandler::new(name, function)?,
+        )?;
+        Ok(event_id)
+    }
+
+    /// Removes the handler registered for events with the specified ID, and returns true if a
+    /// handler was registered.
+    pub fn unregister_event_handler(&mut self, event_id: u32) -> bool {
+        self.event_handlers.remove(&event_id).is_some()
+    }
+
     pub fn load_mast_forest(&mut self, mast_forest: Arc<MastForest>) -> Result<(), ExecutionError> {
         // Load the MAST's advice data into the advice provider.
 
@@ -209,13 +347,21 @@ impl<A: AdviceProvider> Host for DefaultHost<A> {
         self.store.get(node_digest)
     }
 
-    fn on_event(&mut self, _process: ProcessState, _event_id: u32) -> Result<(), ExecutionError> {
+    fn environment(&self) -> ExecutionEnvironment {
+        self.environment
+    }
+
+    fn on_event(&mut self, process: ProcessState, event_id: u32) -> Result<(), ExecutionError> {
+        if let Some(handler) = self.event_handlers.get(&event_id) {
+            return handler.on_event(process, &mut self.adv_provider);
+        }
+
         #[cfg(feature = "std")]
         std::println!(
             "Event with id {} emitted at step {} in context {}",
-            _event_id,
-            _process.clk(),
-            _process.ctx()
+            event_id,
+            process.clk(),
+            process.ctx()
         );
         Ok(())
     }
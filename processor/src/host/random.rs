@@ -0,0 +1,112 @@
+use alloc::{sync::Arc, vec::Vec};
+use core::sync::atomic::{AtomicU64, Ordering};
+
+use vm_core::{
+    Felt, StackInputs, Word,
+    crypto::hash::{Rpo256, RpoDigest},
+};
+
+use super::{AdviceProvider, EventHandler, ExecutionError, ProcessState};
+use crate::{AdviceSource, ZERO};
+
+// CONSTANTS
+// ================================================================================================
+
+/// ID of the event emitted by the `std::random` module of the standard library to request the
+/// next pseudo-random word from the host.
+///
+/// The host is expected to push the word onto the advice stack, so that it can be read via
+/// `adv_loadw`. [RandomSource] handles this event when registered with a host.
+pub const EVENT_RANDOM_WORD: u32 = 2961382557;
+
+// RANDOM SOURCE
+// ================================================================================================
+
+/// A deterministic source of pseudo-random words, which programs query via the `std::random`
+/// module of the standard library.
+///
+/// The words are derived from a seed: the word with index `i` is `hash(SEED, [i, 0, 0, 0])`, and
+/// the source returns the words with indexes 0, 1, 2, ... in order. Thus, executions of a program
+/// with sources built from the same seed receive the same words, and the words received by a
+/// program can be recomputed (e.g., by an auditor) from the seed via [RandomSource::word], or by
+/// the program itself via `std::random::derive_word`.
+///
+/// The seed is either provided explicitly or derived from the public inputs of the program (see
+/// [RandomSource::from_public_inputs]). Note that the words are provided as advice, and thus the
+/// VM does not enforce that they were derived from the seed.
+///
+/// The source is registered with a host as the handler of [EVENT_RANDOM_WORD]:
+/// ```ignore
+/// let source = RandomSource::from_public_inputs(program.hash(), &stack_inputs);
+/// host.register_event_handler(EVENT_RANDOM_WORD, source.clone())?;
+/// ```
+/// Clones of a source share the index of the next word, so that a clone retained by the caller
+/// reports the number of words drawn by the program via [RandomSource::num_words].
+#[derive(Debug, Clone)]
+pub struct RandomSource {
+    seed: RpoDigest,
+    next_index: Arc<AtomicU64>,
+}
+
+impl RandomSource {
+    /// Returns a new source with the specified seed.
+    pub fn new(seed: Word) -> Self {
+        Self {
+            seed: seed.into(),
+            next_index: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Returns a new source with the seed derived from the public inputs of a program, i.e., its
+    /// hash and its stack inputs.
+    ///
+    /// The seed is computed as `hash(PROGRAM_HASH, hash_elements(stack_inputs))`.
+    pub fn from_public_inputs(program_hash: RpoDigest, stack_inputs: &StackInputs) -> Self {
+        let stack_inputs: Vec<Felt> = stack_inputs.iter().copied().collect();
+        let seed = Rpo256::merge(&[program_hash, Rpo256::hash_elements(&stack_inputs)]);
+        Self::new(seed.into())
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the seed of this source.
+    pub fn seed(&self) -> Word {
+        self.seed.into()
+    }
+
+    /// Returns the word with the specified index, i.e., `hash(SEED, [index, 0, 0, 0])`.
+    pub fn word(&self, index: u64) -> Word {
+        let index = RpoDigest::new([Felt::new(index), ZERO, ZERO, ZERO]);
+        Rpo256::merge(&[self.seed, index]).into()
+    }
+
+    /// Returns the number of words drawn from this source (and its clones) so far, which is also
+    /// the index of the next word.
+    pub fn num_words(&self) -> u64 {
+        self.next_index.load(Ordering::Relaxed)
+    }
+
+    // STATE MUTATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the next word of this source.
+    pub fn next_word(&self) -> Word {
+        self.word(self.next_index.fetch_add(1, Ordering::Relaxed))
+    }
+
+    /// Restarts this source (and its clones) from the word with index 0.
+    pub fn reset(&self) {
+        self.next_index.store(0, Ordering::Relaxed);
+    }
+}
+
+impl<A: AdviceProvider> EventHandler<A> for RandomSource {
+    fn on_event(
+        &self,
+        _process: ProcessState,
+        advice_provider: &mut A,
+    ) -> Result<(), ExecutionError> {
+        advice_provider.push_stack(AdviceSource::Word(self.next_word()))
+    }
+}
@@ -0,0 +1,233 @@
+use alloc::sync::Arc;
+
+use vm_core::{
+    DebugOptions, ExecutionEnvironment, Felt, Word,
+    crypto::{
+        hash::{Rpo256, RpoDigest},
+        merkle::{SMT_DEPTH, Smt},
+    },
+    mast::MastForest,
+};
+
+use super::{AdviceProvider, DefaultHost, ExecutionError, Host, ProcessState};
+use crate::{EMPTY_WORD, ZERO};
+
+// CONSTANTS
+// ================================================================================================
+
+/// ID of the event emitted by the `std::storage` module of the standard library after a storage
+/// slot or map entry is updated.
+///
+/// When the event is emitted, the stack contains `[OLD_VALUE, NEW_ROOT, VALUE, KEY, ...]`, where
+/// `KEY` is the key under which the entry is committed, `OLD_VALUE` and `VALUE` are its previous
+/// and new values, and `NEW_ROOT` is the root of the storage after the update.
+pub const EVENT_STORAGE_SET: u32 = 3533623937;
+
+// STORAGE KEYS
+// ================================================================================================
+
+/// Returns the key under which the storage slot with the specified index is committed, computed
+/// as `hash([index, 0, 0, 0], [0, 0, 0, 0])`.
+pub fn storage_slot_key(index: u32) -> RpoDigest {
+    let index = RpoDigest::new([Felt::from(index), ZERO, ZERO, ZERO]);
+    Rpo256::merge(&[index, EMPTY_WORD.into()])
+}
+
+/// Returns the key under which the entry with the specified key of the storage map with the
+/// specified index is committed, computed as `hash(SLOT_KEY, KEY)`, where `SLOT_KEY` is the key of
+/// the storage slot with the specified index.
+pub fn storage_map_key(index: u32, key: Word) -> RpoDigest {
+    Rpo256::merge(&[storage_slot_key(index), key.into()])
+}
+
+// STORAGE HOST
+// ================================================================================================
+
+/// A host which maintains the persistent storage accessed by programs via the `std::storage`
+/// module of the standard library.
+///
+/// The storage consists of word-sized slots and of maps from words to words, identified by their
+/// indexes. All slots and map entries are committed to by a single Sparse Merkle Tree, under the
+/// keys computed by [storage_slot_key] and [storage_map_key] respectively. Programs
+/// receive the root of the storage (see [StorageHost::root]) as an input, read and update the
+/// storage against it, and output its final root.
+///
+/// The host keeps the advice provider populated with the data required to access the storage, and
+/// tracks the updates made by programs, so that the storage can be read and updated between
+/// executions. All other interactions with the VM are delegated to the wrapped [DefaultHost].
+pub struct StorageHost<A> {
+    host: DefaultHost<A>,
+    storage: Smt,
+}
+
+impl<A: AdviceProvider> StorageHost<A> {
+    /// Returns a new host with empty storage, wrapping the provided host.
+    pub fn new(host: DefaultHost<A>) -> Self {
+        Self { host, storage: Smt::new() }
+    }
+
+    /// Returns a new host with the provided storage, wrapping the provided host.
+    ///
+    /// The data required to access the storage is loaded into the advice provider of the host.
+    ///
+    /// # Errors
+    /// Returns an error if the advice provider of the host does not contain the empty Sparse
+    /// Merkle Tree.
+    pub fn with_storage(host: DefaultHost<A>, storage: Smt) -> Result<Self, ExecutionError> {
+        let mut this = Self::new(host);
+        for &(key, value) in storage.entries() {
+            this.set_entry(key, value)?;
+        }
+        debug_assert_eq!(this.storage.root(), storage.root());
+        Ok(this)
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the root of the storage.
+    pub fn root(&self) -> RpoDigest {
+        self.storage.root()
+    }
+
+    /// Returns the Sparse Merkle Tree committing to the storage.
+    pub fn storage(&self) -> &Smt {
+        &self.storage
+    }
+
+    /// Returns the value of the storage slot with the specified index.
+    pub fn get_item(&self, index: u32) -> Word {
+        self.storage.get_value(&storage_slot_key(index))
+    }
+
+    /// Returns the value of the entry with the specified key of the storage map with the specified
+    /// index.
+    pub fn get_map_item(&self, index: u32, key: Word) -> Word {
+        self.storage.get_value(&storage_map_key(index, key))
+    }
+
+    /// Returns the wrapped host.
+    pub fn host(&self) -> &DefaultHost<A> {
+        &self.host
+    }
+
+    /// Returns a mutable reference to the wrapped host.
+    pub fn host_mut(&mut self) -> &mut DefaultHost<A> {
+        &mut self.host
+    }
+
+    /// Returns the wrapped host and the Sparse Merkle Tree committing to the storage.
+    pub fn into_parts(self) -> (DefaultHost<A>, Smt) {
+        (self.host, self.storage)
+    }
+
+    // STATE MUTATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Sets the value of the storage slot with the specified index, and returns its previous
+    /// value.
+    ///
+    /// # Errors
+    /// Returns an error if the advice provider of the host does not contain the storage.
+    pub fn set_item(&mut self, index: u32, value: Word) -> Result<Word, ExecutionError> {
+        self.set_entry(storage_slot_key(index), value)
+    }
+
+    /// Sets the value of the entry with the specified key of the storage map with the specified
+    /// index, and returns its previous value.
+    ///
+    /// # Errors
+    /// Returns an error if the advice provider of the host does not contain the storage.
+    pub fn set_map_item(
+        &mut self,
+        index: u32,
+        key: Word,
+        value: Word,
+    ) -> Result<Word, ExecutionError> {
+        self.set_entry(storage_map_key(index, key), value)
+    }
+
+    // HELPERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Sets the value of the entry with the specified key in the storage and in the advice
+    /// provider, and returns its previous value.
+    fn set_entry(&mut self, key: RpoDigest, value: Word) -> Result<Word, ExecutionError> {
+        let root = self.storage.root();
+        let old_value = self.storage.insert(key, value);
+        let leaf = self.storage.get_leaf(&key);
+
+        let advice_provider = self.host.advice_provider_mut();
+        if let Err(err) = advice_provider.update_merkle_node(
+            root.into(),
+            &Felt::from(SMT_DEPTH),
+            &Felt::new(leaf.index().value()),
+            leaf.hash().into(),
+        ) {
+            self.storage.insert(key, old_value);
+            return Err(err);
+        }
+        if !leaf.is_empty() {
+            advice_provider.insert_into_map(leaf.hash().into(), leaf.to_elements());
+        }
+
+        Ok(old_value)
+    }
+}
+
+impl<A: AdviceProvider> Host for StorageHost<A> {
+    type AdviceProvider = A;
+
+    fn advice_provider(&self) -> &Self::AdviceProvider {
+        self.host.advice_provider()
+    }
+
+    fn advice_provider_mut(&mut self) -> &mut Self::AdviceProvider {
+        self.host.advice_provider_mut()
+    }
+
+    fn get_mast_forest(&self, node_digest: &RpoDigest) -> Option<Arc<MastForest>> {
+        self.host.get_mast_forest(node_digest)
+    }
+
+    fn environment(&self) -> ExecutionEnvironment {
+        self.host.environment()
+    }
+
+    fn on_event(&mut self, process: ProcessState, event_id: u32) -> Result<(), ExecutionError> {
+        if event_id != EVENT_STORAGE_SET {
+            return self.host.on_event(process, event_id);
+        }
+
+        // the advice provider is updated by the program itself, so only the storage of the host
+        // needs to be updated; the update is rejected if it results in a root other than the one
+        // computed by the program, i.e., if the program updated a different storage
+        let new_root = RpoDigest::from(process.get_stack_word(1));
+        let value = process.get_stack_word(2);
+        let key = RpoDigest::from(process.get_stack_word(3));
+        let old_value = self.storage.insert(key, value);
+        if self.storage.root() != new_root {
+            let expected = self.storage.root();
+            self.storage.insert(key, old_value);
+            return Err(ExecutionError::StorageRootMismatch { expected, actual: new_root });
+        }
+
+        Ok(())
+    }
+
+    fn on_debug(
+        &mut self,
+        process: ProcessState,
+        options: &DebugOptions,
+    ) -> Result<(), ExecutionError> {
+        self.host.on_debug(process, options)
+    }
+
+    fn on_trace(&mut self, process: ProcessState, trace_id: u32) -> Result<(), ExecutionError> {
+        self.host.on_trace(process, trace_id)
+    }
+
+    fn on_assert_failed(&mut self, process: ProcessState, err_code: u32) -> ExecutionError {
+        self.host.on_assert_failed(process, err_code)
+    }
+}
@@ -0,0 +1,152 @@
+//! Structured [tracing] instrumentation of the processor, enabled by the `tracing-spans` feature.
+//!
+//! The processor emits the following spans and events, which can be consumed by any `tracing`
+//! subscriber:
+//!
+//! - A `mast_node` span (at DEBUG level) for every MAST node executed, with the `kind` and the
+//!   `digest` of the node, the clock cycle `clk` at which its execution starts, and the number of
+//!   `cycles` it took to execute. Since nodes are nested, these spans mirror the transitions of
+//!   the decoder between code blocks, and provide per-block timings.
+//! - A `build_execution_trace` span (at INFO level) around the construction of the execution trace
+//!   from the state of the processor.
+//! - An event (at TRACE level) for every operation executed, with the clock cycle `clk`, the
+//!   operation `op`, and the `chiplets` the operation interacts with. The decoder transitions
+//!   which occur within basic blocks and loops, i.e. `RESPAN` and `REPEAT`, are also emitted as
+//!   TRACE events.
+//! - An event (at INFO level) summarizing a successful execution, with the number of `cycles`
+//!   executed and the number of rows of the trace of each chiplet.
+//!
+//! All spans and events are emitted with the `miden_processor` target.
+
+use vm_core::mast::MastNode;
+
+use crate::{Process, trace::ChipletsLengths};
+
+/// Returns the name of the kind of the specified node, as recorded in `mast_node` spans.
+pub(crate) fn node_kind(node: &MastNode) -> &'static str {
+    match node {
+        MastNode::Block(_) => "span",
+        MastNode::Join(_) => "join",
+        MastNode::Split(_) => "split",
+        MastNode::Loop(_) => "loop",
+        MastNode::Call(node) if node.is_syscall() => "syscall",
+        MastNode::Call(_) => "call",
+        MastNode::Dyn(node) if node.is_dyncall() => "dyncall",
+        MastNode::Dyn(_) => "dyn",
+        MastNode::External(_) => "external",
+    }
+}
+
+/// Emits an event summarizing the execution of a program by the specified process.
+pub(crate) fn record_execution_summary(process: &Process) {
+    let chiplets = ChipletsLengths::new(&process.chiplets);
+    tracing::info!(
+        cycles = u32::from(process.system.clk()),
+        hasher_rows = chiplets.hash_chiplet_len(),
+        bitwise_rows = chiplets.bitwise_chiplet_len(),
+        memory_rows = chiplets.memory_chiplet_len(),
+        kernel_rom_rows = chiplets.kernel_rom_len(),
+        "executed program"
+    );
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use alloc::{
+        string::{String, ToString},
+        sync::Arc,
+        vec::Vec,
+    };
+    use core::fmt::Debug;
+    use std::sync::Mutex;
+
+    use assembly::Assembler;
+    use tracing::{
+        Event, Metadata,
+        field::{Field, Visit},
+        span::{Attributes, Id, Record},
+        subscriber::Interest,
+    };
+
+    use crate::{DefaultHost, ExecutionOptions, StackInputs};
+
+    /// A subscriber which records the names of the spans and the fields of the events emitted by
+    /// the processor.
+    #[derive(Default, Clone)]
+    struct RecordingSubscriber {
+        records: Arc<Mutex<Vec<String>>>,
+    }
+
+    struct FieldRecorder<'a>(&'a mut String);
+
+    impl Visit for FieldRecorder<'_> {
+        fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+            self.0.push_str(&format!(" {}={:?}", field.name(), value));
+        }
+    }
+
+    impl tracing::Subscriber for RecordingSubscriber {
+        fn register_callsite(&self, _: &'static Metadata<'static>) -> Interest {
+            Interest::always()
+        }
+
+        fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+            metadata.target().starts_with("miden_processor")
+        }
+
+        fn new_span(&self, span: &Attributes<'_>) -> Id {
+            let mut record = span.metadata().name().to_string();
+            span.record(&mut FieldRecorder(&mut record));
+            let mut records = self.records.lock().unwrap();
+            records.push(record);
+            Id::from_u64(records.len() as u64)
+        }
+
+        fn record(&self, _: &Id, _: &Record<'_>) {}
+
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            let mut record = String::new();
+            event.record(&mut FieldRecorder(&mut record));
+            self.records.lock().unwrap().push(record);
+        }
+
+        fn enter(&self, _: &Id) {}
+
+        fn exit(&self, _: &Id) {}
+    }
+
+    #[test]
+    fn spans_and_events() {
+        let source = "begin push.1 while.true push.3 push.5 u32and drop push.0 end end";
+        let program = Assembler::default().assemble_program(source).unwrap();
+
+        let subscriber = RecordingSubscriber::default();
+        tracing::subscriber::with_default(subscriber.clone(), || {
+            crate::execute(
+                &program,
+                StackInputs::default(),
+                &mut DefaultHost::default(),
+                ExecutionOptions::default(),
+            )
+            .unwrap();
+        });
+        let records = subscriber.records.lock().unwrap();
+
+        for kind in ["join", "loop", "span"] {
+            let span = format!("mast_node kind=\"{kind}\"");
+            assert!(records.iter().any(|record| record.starts_with(&span)), "no {kind} span");
+        }
+        assert!(records.iter().any(|record| record.starts_with("build_execution_trace")));
+        assert!(records.iter().any(|record| {
+            record.contains("op=u32and") && record.contains("chiplets=[Bitwise]")
+        }));
+        assert!(records.iter().any(|record| {
+            record.contains("message=executed program") && record.contains("bitwise_rows=8")
+        }));
+    }
+}
@@ -6,27 +6,32 @@ extern crate alloc;
 #[cfg(feature = "std")]
 extern crate std;
 
-use alloc::vec::Vec;
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use core::ops::Range;
 
 use miden_air::trace::{
     CHIPLETS_WIDTH, DECODER_TRACE_WIDTH, MIN_TRACE_LEN, RANGE_CHECK_TRACE_WIDTH, STACK_TRACE_WIDTH,
     SYS_TRACE_WIDTH,
 };
-pub use miden_air::{ExecutionOptions, ExecutionOptionsError, RowIndex};
+pub use miden_air::{
+    CancellationToken, ExecutionOptions, ExecutionOptionsError, OpDispatch, RowIndex,
+};
 pub use vm_core::{
-    AssemblyOp, EMPTY_WORD, Felt, Kernel, ONE, Operation, Program, ProgramInfo, QuadExtension,
-    StackInputs, StackOutputs, Word, ZERO,
+    AssemblyOp, EMPTY_WORD, ExecutionEnvironment, Felt, IsaVersion, Kernel, ONE, Operation,
+    Program, ProgramInfo, QuadExtension, StackInputs, StackOutputs, Word, ZERO,
     chiplets::hasher::Digest,
     crypto::merkle::SMT_DEPTH,
-    errors::InputError,
-    mast::{MastForest, MastNode, MastNodeId},
+    errors::{InputError, KernelError},
+    mast::{MastForest, MastNode, MastNodeId, SourceMap},
     sys_events::SystemEvent,
     utils::{DeserializationError, collections::KvMap},
 };
 use vm_core::{
     Decorator, DecoratorIterator, FieldElement,
+    debuginfo::FileLineCol,
     mast::{
-        BasicBlockNode, CallNode, DynNode, JoinNode, LoopNode, OP_GROUP_SIZE, OpBatch, SplitNode,
+        BasicBlockNode, CallNode, DecoratorId, DynNode, ExternalNode, JoinNode, LoopNode,
+        OP_GROUP_SIZE, OpBatch, SplitNode,
     },
 };
 pub use winter_prover::matrix::ColMatrix;
@@ -47,25 +52,65 @@ mod range;
 use range::RangeChecker;
 
 mod host;
+#[cfg(feature = "std")]
+pub use host::advice::FileMerkleNodeStorage;
+#[cfg(any(test, feature = "rpc-advice"))]
+pub use host::advice::{AdviceRpcClient, RpcAdviceProvider, RpcNodeError};
 pub use host::{
-    DefaultHost, Host, MastForestStore, MemMastForestStore,
-    advice::{AdviceInputs, AdviceProvider, AdviceSource, MemAdviceProvider, RecAdviceProvider},
+    DefaultHost, EVENT_RANDOM_WORD, EVENT_STORAGE_SET, EventHandler, ForeignCallError,
+    ForeignValue, Host, MastForestStore, MemMastForestStore, RandomSource, StorageHost,
+    advice::{
+        AdviceInputs, AdviceInputsBuilder, AdviceProvider, AdviceSource, AdviceTape,
+        AdviceTapeEntry, AdviceTapeRecorder, AdviceTapeReplayer, MemAdviceProvider,
+        MerkleMultiProof, MerkleNodeStorage, PersistentAdviceProvider, RecAdviceProvider,
+    },
+    foreign_function_id, storage_map_key, storage_slot_key,
 };
 
 mod chiplets;
 use chiplets::Chiplets;
+pub use chiplets::{MemoryState, MemoryWordDiff};
 
 mod trace;
 use trace::TraceFragment;
-pub use trace::{ChipletsLengths, ExecutionTrace, NUM_RAND_ROWS, TraceLenSummary};
+pub use trace::{
+    ChipletsLengths, ExecutionTrace, NUM_RAND_ROWS, TraceComponent, TraceLenSummary, TraceStats,
+};
+
+#[cfg(feature = "tracing-spans")]
+mod instrumentation;
 
 mod errors;
-pub use errors::{ExecutionError, Ext2InttError};
+pub use errors::{AdviceInputsError, ExecutionError, Ext2InttError, NttError};
 
 pub mod utils;
 
 mod debug;
-pub use debug::{AsmOpInfo, VmState, VmStateIterator};
+pub use debug::{AsmOpInfo, Breakpoint, VmState, VmStateIterator};
+
+mod differential;
+pub use differential::{Divergence, DivergenceKind, ExecutionRecord, RecordedStep};
+
+mod profiler;
+use profiler::Profiler;
+pub use profiler::{ProfileNode, ProfileReport};
+
+mod call_stack;
+use call_stack::CallStackTracker;
+pub use call_stack::{CallFrame, CallStack};
+
+mod coverage;
+pub use coverage::{BranchCoverage, CoverageReport, ForestCoverage, InstructionCoverage};
+
+mod events;
+use events::EventRecorder;
+pub use events::{EmittedEvent, EventTimeline};
+
+mod sandbox;
+pub use sandbox::{SandboxAdviceProvider, SandboxPolicy};
+
+#[cfg(feature = "simd")]
+mod simd;
 
 // RE-EXPORTS
 // ================================================================================================
@@ -78,11 +123,12 @@ pub mod math {
 pub mod crypto {
     pub use vm_core::crypto::{
         hash::{
-            Blake3_192, Blake3_256, ElementHasher, Hasher, Rpo256, RpoDigest, Rpx256, RpxDigest,
+            Blake3_192, Blake3_256, ElementHasher, Hasher, Keccak256, KeccakDigest, Rpo256,
+            RpoDigest, Rpx256, RpxDigest,
         },
         merkle::{
             MerkleError, MerklePath, MerkleStore, MerkleTree, NodeIndex, PartialMerkleTree,
-            SimpleSmt,
+            SimpleSmt, Smt,
         },
         random::{RandomCoin, RpoRandomCoin, RpxRandomCoin, WinterRandomCoin},
     };
@@ -129,13 +175,66 @@ pub fn execute(
     host: &mut impl Host,
     options: ExecutionOptions,
 ) -> Result<ExecutionTrace, ExecutionError> {
+    if !options.enable_trace_generation() {
+        return Err(ExecutionError::TraceGenerationDisabled);
+    }
     let mut process = Process::new(program.kernel().clone(), stack_inputs, options);
     let stack_outputs = process.execute(program, host)?;
+    #[cfg(feature = "tracing-spans")]
+    let _span = tracing::info_span!("build_execution_trace").entered();
     let trace = ExecutionTrace::new(process, stack_outputs);
     assert_eq!(&program.hash(), trace.program_hash(), "inconsistent program hash");
     Ok(trace)
 }
 
+/// Returns the outputs resulting from executing the provided program against the provided inputs,
+/// without generating the execution trace.
+///
+/// This is considerably faster than [execute], and is intended for cases where the program needs
+/// to be run but will never be proven. Trace generation is disabled regardless of `options`.
+#[tracing::instrument("execute_program_without_trace", skip_all)]
+pub fn execute_without_trace(
+    program: &Program,
+    stack_inputs: StackInputs,
+    host: &mut impl Host,
+    options: ExecutionOptions,
+) -> Result<StackOutputs, ExecutionError> {
+    let options = options.without_trace_generation();
+    let mut process = Process::new(program.kernel().clone(), stack_inputs, options);
+    process.execute(program, host)
+}
+
+/// Returns statistics about the execution trace which [execute] would generate for the provided
+/// program and inputs, without generating the trace.
+///
+/// The program is executed as with [execute_without_trace], while the rows required by the range
+/// checker and the chiplets are tallied. The returned statistics, including the padded trace
+/// length and the component which determines it, are the same as the ones reported by
+/// [ExecutionTrace::trace_stats], so this can be used to predict the cost of proving the execution
+/// before committing to it.
+#[tracing::instrument("estimate_trace_stats", skip_all)]
+pub fn estimate_trace_stats(
+    program: &Program,
+    stack_inputs: StackInputs,
+    host: &mut impl Host,
+    options: ExecutionOptions,
+) -> Result<TraceStats, ExecutionError> {
+    let options = options.without_trace_generation();
+    let mut process = Process::new(program.kernel().clone(), stack_inputs, options);
+    process.execute(program, host)?;
+
+    let overflow_table_peak_len = process.stack.overflow_table_peak_len();
+    let (system, _, _, mut range, chiplets) = process.into_parts();
+    chiplets.append_range_checks(&mut range);
+    let trace_len_summary = TraceLenSummary::new(
+        system.clk().into(),
+        range.get_number_range_checker_rows(),
+        ChipletsLengths::new(&chiplets),
+    );
+
+    Ok(TraceStats::new(trace_len_summary, overflow_table_peak_len))
+}
+
 /// Returns an iterator which allows callers to step through the execution and inspect VM state at
 /// each execution step.
 pub fn execute_iter(
@@ -175,7 +274,23 @@ pub struct Process {
     range: RangeChecker,
     chiplets: Chiplets,
     max_cycles: u32,
+    max_memory_rows: u32,
+    max_nesting_depth: u32,
+    nesting_depth: u32,
+    cancel_token: Option<CancellationToken>,
+    op_dispatch: OpDispatch,
     enable_tracing: bool,
+    profiler: Option<Profiler>,
+    call_stack: Option<CallStackTracker>,
+    source_map: Option<SourceMap>,
+    /// The MAST forest of the program being executed, to which the source map applies.
+    source_map_forest: Option<Arc<MastForest>>,
+    coverage: Option<CoverageReport>,
+    events: Option<EventRecorder>,
+    /// The environment of the execution, as provided by the host when the execution started.
+    environment: ExecutionEnvironment,
+    /// The version of the instruction set targeted by the program being executed.
+    isa_version: IsaVersion,
 }
 
 #[cfg(any(test, feature = "testing"))]
@@ -186,7 +301,20 @@ pub struct Process {
     pub range: RangeChecker,
     pub chiplets: Chiplets,
     pub max_cycles: u32,
+    pub max_memory_rows: u32,
+    pub max_nesting_depth: u32,
+    pub nesting_depth: u32,
+    pub cancel_token: Option<CancellationToken>,
+    pub op_dispatch: OpDispatch,
     pub enable_tracing: bool,
+    pub profiler: Option<Profiler>,
+    pub call_stack: Option<CallStackTracker>,
+    pub source_map: Option<SourceMap>,
+    pub source_map_forest: Option<Arc<MastForest>>,
+    pub coverage: Option<CoverageReport>,
+    pub events: Option<EventRecorder>,
+    pub environment: ExecutionEnvironment,
+    pub isa_version: IsaVersion,
 }
 
 impl Process {
@@ -201,6 +329,26 @@ impl Process {
         Self::initialize(kernel, stack_inputs, execution_options)
     }
 
+    /// Creates a new process with the provided inputs, whose kernel consists of the procedures of
+    /// all of the specified kernels.
+    ///
+    /// This allows a program to make syscalls into any of the kernels without assembling a single
+    /// kernel module exporting all of their procedures. Proofs of executions of such a process are
+    /// verified against the merged kernel (see [Kernel::merge]), which is the kernel reported by
+    /// [Process::kernel].
+    ///
+    /// # Errors
+    /// Returns an error if the kernels contain more than [Kernel::MAX_NUM_PROCEDURES] distinct
+    /// procedures in total.
+    pub fn new_with_kernels(
+        kernels: &[Kernel],
+        stack_inputs: StackInputs,
+        execution_options: ExecutionOptions,
+    ) -> Result<Self, KernelError> {
+        let kernel = Kernel::merge(kernels)?;
+        Ok(Self::initialize(kernel, stack_inputs, execution_options))
+    }
+
     /// Creates a new process with provided inputs and debug options enabled.
     pub fn new_debug(kernel: Kernel, stack_inputs: StackInputs) -> Self {
         Self::initialize(
@@ -210,16 +358,103 @@ impl Process {
         )
     }
 
+    /// Initializes the memory of this process with the specified state, e.g., the final memory
+    /// state of a previous execution obtained via [Process::memory_state].
+    ///
+    /// This allows multi-stage programs to share state across executions without passing it
+    /// through the advice provider. The execution can be proven as any other: the initial memory
+    /// is part of the public inputs of the proof, via [MemoryState::commitment], and thus the
+    /// proof must be verified against the same initial memory.
+    ///
+    /// # Errors
+    /// Returns an error if the process has already executed a program.
+    pub fn with_initial_memory(mut self, memory: MemoryState) -> Result<Self, ExecutionError> {
+        if self.system.clk() != 0 {
+            return Err(ExecutionError::ProgramAlreadyExecuted);
+        }
+
+        self.chiplets.memory.set_initial_state(memory);
+        Ok(self)
+    }
+
+    /// Marks the addresses in the specified range of the specified execution context as
+    /// read-only, e.g., to protect lookup tables loaded via [Process::with_initial_memory].
+    ///
+    /// Writing to a read-only address fails the execution with
+    /// [ExecutionError::ReadOnlyMemoryWrite]. Programs can also mark addresses as read-only during
+    /// execution, via the `std::mem::protect` procedure of the standard library.
+    ///
+    /// Read-only memory is a debugging aid: it is enforced by the processor only, and not by the
+    /// constraints of the VM, so a proof of the execution does not attest that read-only addresses
+    /// were not written to.
+    ///
+    /// # Errors
+    /// Returns an error if the start of the range is greater than its end.
+    pub fn with_read_only_memory(
+        mut self,
+        ctx: ContextId,
+        addrs: Range<u32>,
+    ) -> Result<Self, ExecutionError> {
+        self.chiplets.memory.protect(ctx, addrs.start as u64, addrs.end as u64)?;
+        Ok(self)
+    }
+
+    /// Attaches the source map of the program to be executed to this process.
+    ///
+    /// When an operation of the program fails, the location of its instruction is attached to the
+    /// error (see [ExecutionError::source_location]). In debug mode, the location of each executed
+    /// instruction is recorded as well, and can be queried via [VmStateIterator::source_location].
+    /// This allows attributing the execution of programs stripped of their decorators to their
+    /// source code, see [SourceMap].
+    ///
+    /// The source map only applies to the MAST forest of the program, and not to the forests of
+    /// the libraries it invokes via the host.
+    pub fn with_source_map(mut self, source_map: SourceMap) -> Self {
+        self.source_map = Some(source_map);
+        self
+    }
+
     fn initialize(kernel: Kernel, stack: StackInputs, execution_options: ExecutionOptions) -> Self {
         let in_debug_mode = execution_options.enable_debugging();
+        let (system, decoder, stack, chiplets) = if execution_options.enable_trace_generation() {
+            let expected_cycles = execution_options.expected_cycles() as usize;
+            (
+                System::new(expected_cycles),
+                Decoder::new(in_debug_mode),
+                Stack::new(&stack, expected_cycles, in_debug_mode),
+                Chiplets::new(kernel),
+            )
+        } else {
+            (
+                System::without_trace(),
+                Decoder::new_without_trace(in_debug_mode),
+                Stack::new_without_trace(&stack),
+                Chiplets::new_without_trace(kernel),
+            )
+        };
         Self {
-            system: System::new(execution_options.expected_cycles() as usize),
-            decoder: Decoder::new(in_debug_mode),
-            stack: Stack::new(&stack, execution_options.expected_cycles() as usize, in_debug_mode),
+            system,
+            decoder,
+            stack,
             range: RangeChecker::new(),
-            chiplets: Chiplets::new(kernel),
+            chiplets,
             max_cycles: execution_options.max_cycles(),
+            max_memory_rows: execution_options.max_memory_rows(),
+            max_nesting_depth: execution_options.max_nesting_depth(),
+            nesting_depth: 0,
+            cancel_token: execution_options.cancel_token().cloned(),
+            op_dispatch: execution_options.op_dispatch(),
             enable_tracing: execution_options.enable_tracing(),
+            profiler: execution_options.enable_profiling().then(Profiler::default),
+            call_stack: in_debug_mode.then(CallStackTracker::default),
+            source_map: None,
+            source_map_forest: None,
+            coverage: execution_options.enable_coverage().then(CoverageReport::default),
+            events: execution_options
+                .enable_event_timeline()
+                .then(|| EventRecorder::new(execution_options.event_stack_snapshots())),
+            environment: ExecutionEnvironment::default(),
+            isa_version: IsaVersion::CURRENT,
         }
     }
 
@@ -236,6 +471,29 @@ impl Process {
             return Err(ExecutionError::ProgramAlreadyExecuted);
         }
 
+        if !IsaVersion::CURRENT.supports(program.isa_version()) {
+            return Err(ExecutionError::UnsupportedIsaVersion {
+                program: program.isa_version(),
+                supported: IsaVersion::CURRENT,
+            });
+        }
+        self.isa_version = program.isa_version();
+
+        // reject programs which are statically known to exceed the cycle or nesting depth limits
+        // before executing any of their operations
+        if let Some(analysis) = program.entrypoint_analysis() {
+            if analysis.cycles().min() > self.max_cycles as u64 {
+                return Err(ExecutionError::CycleLimitExceeded(self.max_cycles));
+            }
+            if analysis.depth() > self.max_nesting_depth {
+                return Err(ExecutionError::NestingDepthExceeded(self.max_nesting_depth));
+            }
+        }
+
+        // the environment is committed to in the public inputs, so it is read once, before any
+        // operation is executed, and programs read this snapshot rather than querying the host
+        self.environment = host.environment();
+
         // Load the program's advice data into the advice provider
         for (digest, values) in program.mast_forest().advice_map().iter() {
             if let Some(stored_values) = host.advice_provider().get_mapped_values(digest) {
@@ -247,7 +505,17 @@ impl Process {
             }
         }
 
-        self.execute_mast_node(program.entrypoint(), &program.mast_forest().clone(), host)?;
+        if self.source_map.is_some() {
+            self.source_map_forest = Some(program.mast_forest().clone());
+        }
+        if let Err(err) =
+            self.execute_mast_node(program.entrypoint(), &program.mast_forest().clone(), host)
+        {
+            return Err(self.attach_call_stack(err));
+        }
+
+        #[cfg(feature = "tracing-spans")]
+        instrumentation::record_execution_summary(self);
 
         self.stack.build_stack_outputs()
     }
@@ -265,46 +533,120 @@ impl Process {
             .get_node_by_id(node_id)
             .ok_or(ExecutionError::MastNodeNotFoundInForest { node_id })?;
 
+        // nodes are executed recursively, so the nesting depth is bounded to make sure that deeply
+        // nested programs fail with an error, rather than by overflowing the stack
+        if self.nesting_depth == self.max_nesting_depth {
+            return Err(ExecutionError::NestingDepthExceeded(self.max_nesting_depth));
+        }
+        self.nesting_depth += 1;
+
+        #[cfg(feature = "tracing-spans")]
+        let (span, start_clk) = (
+            tracing::debug_span!(
+                "mast_node",
+                kind = instrumentation::node_kind(node),
+                digest = %node.digest(),
+                clk = u32::from(self.system.clk()),
+                cycles = tracing::field::Empty,
+            )
+            .entered(),
+            u32::from(self.system.clk()),
+        );
+
+        // enter a profiling frame if this node is the root of a procedure; external nodes are
+        // skipped, as the frame is entered when the root they refer to is executed
+        let is_proc_root =
+            (self.profiler.is_some() || self.call_stack.is_some() || self.events.is_some())
+                && !node.is_external()
+                && program.is_procedure_root(node_id);
+        if is_proc_root {
+            if let Some(profiler) = self.profiler.as_mut() {
+                profiler.enter(node.digest(), self.system.clk().into());
+            }
+        }
+
         for &decorator_id in node.before_enter() {
-            self.execute_decorator(&program[decorator_id], host)?;
-        }
-
-        match node {
-            MastNode::Block(node) => self.execute_basic_block_node(node, program, host)?,
-            MastNode::Join(node) => self.execute_join_node(node, program, host)?,
-            MastNode::Split(node) => self.execute_split_node(node, program, host)?,
-            MastNode::Loop(node) => self.execute_loop_node(node, program, host)?,
-            MastNode::Call(node) => self.execute_call_node(node, program, host)?,
-            MastNode::Dyn(node) => self.execute_dyn_node(node, program, host)?,
-            MastNode::External(external_node) => {
-                let node_digest = external_node.digest();
-                let mast_forest = host.get_mast_forest(&node_digest).ok_or(
-                    ExecutionError::NoMastForestWithProcedure { root_digest: node_digest },
-                )?;
+            self.execute_decorator(decorator_id, program, host)?;
+        }
 
-                // We limit the parts of the program that can be called externally to procedure
-                // roots, even though MAST doesn't have that restriction.
-                let root_id = mast_forest.find_procedure_root(node_digest).ok_or(
-                    ExecutionError::MalformedMastForestInHost { root_digest: node_digest },
-                )?;
+        // the call frame is entered after the decorators preceding the node are executed, since
+        // these may include the AsmOp decorator of the instruction invoking the procedure
+        if is_proc_root {
+            if let Some(call_stack) = self.call_stack.as_mut() {
+                call_stack.enter(node.digest());
+            }
+            if let Some(events) = self.events.as_mut() {
+                events.enter(node.digest());
+            }
+        }
 
-                // if the node that we got by looking up an external reference is also an External
-                // node, we are about to enter into an infinite loop - so, return an error
-                if mast_forest[root_id].is_external() {
-                    return Err(ExecutionError::CircularExternalNode(node_digest));
-                }
+        let result = match node {
+            MastNode::Block(node) => self.execute_basic_block_node(node_id, node, program, host),
+            MastNode::Join(node) => self.execute_join_node(node, program, host),
+            MastNode::Split(node) => self.execute_split_node(node, program, host),
+            MastNode::Loop(node) => self.execute_loop_node(node, program, host),
+            MastNode::Call(node) => self.execute_call_node(node, program, host),
+            MastNode::Dyn(node) => self.execute_dyn_node(node, program, host),
+            MastNode::External(node) => self.execute_external_node(node, host),
+        };
+        self.nesting_depth -= 1;
 
-                self.execute_mast_node(root_id, &mast_forest, host)?;
-            },
+        #[cfg(feature = "tracing-spans")]
+        span.record("cycles", u32::from(self.system.clk()) - start_clk);
+
+        if is_proc_root {
+            if let Some(call_stack) = self.call_stack.as_mut() {
+                match result {
+                    Ok(_) => call_stack.exit(),
+                    Err(_) => call_stack.unwind(program),
+                }
+            }
+            if let Some(events) = self.events.as_mut() {
+                events.exit();
+            }
         }
+        result?;
 
         for &decorator_id in node.after_exit() {
-            self.execute_decorator(&program[decorator_id], host)?;
+            self.execute_decorator(decorator_id, program, host)?;
+        }
+
+        if is_proc_root {
+            if let Some(profiler) = self.profiler.as_mut() {
+                profiler.exit(self.system.clk().into());
+            }
         }
 
         Ok(())
     }
 
+    /// Executes the procedure referenced by the specified [ExternalNode].
+    #[inline(always)]
+    fn execute_external_node(
+        &mut self,
+        external_node: &ExternalNode,
+        host: &mut impl Host,
+    ) -> Result<(), ExecutionError> {
+        let node_digest = external_node.digest();
+        let mast_forest = host
+            .get_mast_forest(&node_digest)
+            .ok_or(ExecutionError::NoMastForestWithProcedure { root_digest: node_digest })?;
+
+        // We limit the parts of the program that can be called externally to procedure
+        // roots, even though MAST doesn't have that restriction.
+        let root_id = mast_forest
+            .find_procedure_root(node_digest)
+            .ok_or(ExecutionError::MalformedMastForestInHost { root_digest: node_digest })?;
+
+        // if the node that we got by looking up an external reference is also an External
+        // node, we are about to enter into an infinite loop - so, return an error
+        if mast_forest[root_id].is_external() {
+            return Err(ExecutionError::CircularExternalNode(node_digest));
+        }
+
+        self.execute_mast_node(root_id, &mast_forest, host)
+    }
+
     /// Executes the specified [JoinNode].
     #[inline(always)]
     fn execute_join_node(
@@ -335,8 +677,10 @@ impl Process {
 
         // execute either the true or the false branch of the split block based on the condition
         if condition == ONE {
+            self.record_branch(node.digest(), true);
             self.execute_mast_node(node.on_true(), program, host)?;
         } else if condition == ZERO {
+            self.record_branch(node.digest(), false);
             self.execute_mast_node(node.on_false(), program, host)?;
         } else {
             return Err(ExecutionError::NotBinaryValue(condition));
@@ -359,12 +703,15 @@ impl Process {
         // if the top of the stack is ONE, execute the loop body; otherwise skip the loop body
         if condition == ONE {
             // execute the loop body at least once
+            self.record_branch(node.digest(), true);
             self.execute_mast_node(node.body(), program, host)?;
 
             // keep executing the loop body until the condition on the top of the stack is no
             // longer ONE; each iteration of the loop is preceded by executing REPEAT operation
             // which drops the condition from the stack
             while self.stack.peek() == ONE {
+                #[cfg(feature = "tracing-spans")]
+                tracing::trace!(clk = u32::from(self.system.clk()), "repeat");
                 self.decoder.repeat();
                 self.execute_op(Operation::Drop, host)?;
                 self.execute_mast_node(node.body(), program, host)?;
@@ -379,6 +726,7 @@ impl Process {
         } else if condition == ZERO {
             // end the LOOP block, but don't drop the condition from the stack because it was
             // already dropped when we started the LOOP block
+            self.record_branch(node.digest(), false);
             self.end_loop_node(node, false, host)
         } else {
             Err(ExecutionError::NotBinaryValue(condition))
@@ -462,20 +810,28 @@ impl Process {
     }
 
     /// Executes the specified [BasicBlockNode].
-    #[inline(always)]
+    ///
+    /// This is not inlined, so that the locals used to execute operations do not add to the size
+    /// of the frames of recursive [Process::execute_mast_node] calls.
+    #[inline(never)]
     fn execute_basic_block_node(
         &mut self,
+        node_id: MastNodeId,
         basic_block: &BasicBlockNode,
         program: &MastForest,
         host: &mut impl Host,
     ) -> Result<(), ExecutionError> {
         self.start_basic_block_node(basic_block, host)?;
+        if let Some(coverage) = self.coverage.as_mut() {
+            coverage.record_block(basic_block.digest());
+        }
 
         let mut op_offset = 0;
         let mut decorator_ids = basic_block.decorator_iter();
 
         // execute the first operation batch
         self.execute_op_batch(
+            node_id,
             &basic_block.op_batches()[0],
             &mut decorator_ids,
             op_offset,
@@ -488,9 +844,11 @@ impl Process {
         // preceded by a RESPAN operation; executing RESPAN operation does not change the state
         // of the stack
         for op_batch in basic_block.op_batches().iter().skip(1) {
+            #[cfg(feature = "tracing-spans")]
+            tracing::trace!(clk = u32::from(self.system.clk()), "respan");
             self.respan(op_batch);
             self.execute_op(Operation::Noop, host)?;
-            self.execute_op_batch(op_batch, &mut decorator_ids, op_offset, program, host)?;
+            self.execute_op_batch(node_id, op_batch, &mut decorator_ids, op_offset, program, host)?;
             op_offset += op_batch.ops().len();
         }
 
@@ -501,10 +859,7 @@ impl Process {
         // are executed after SPAN block is closed to make sure the VM clock cycle advances beyond
         // the last clock cycle of the SPAN block ops.
         for &decorator_id in decorator_ids {
-            let decorator = program
-                .get_decorator_by_id(decorator_id)
-                .ok_or(ExecutionError::DecoratorNotFoundInForest { decorator_id })?;
-            self.execute_decorator(decorator, host)?;
+            self.execute_decorator(decorator_id, program, host)?;
         }
 
         Ok(())
@@ -519,6 +874,7 @@ impl Process {
     #[inline(always)]
     fn execute_op_batch(
         &mut self,
+        node_id: MastNodeId,
         batch: &OpBatch,
         decorators: &mut DecoratorIterator,
         op_offset: usize,
@@ -538,15 +894,18 @@ impl Process {
         // execute operations in the batch one by one
         for (i, &op) in batch.ops().iter().enumerate() {
             while let Some(&decorator_id) = decorators.next_filtered(i + op_offset) {
-                let decorator = program
-                    .get_decorator_by_id(decorator_id)
-                    .ok_or(ExecutionError::DecoratorNotFoundInForest { decorator_id })?;
-                self.execute_decorator(decorator, host)?;
+                self.execute_decorator(decorator_id, program, host)?;
+            }
+
+            if self.source_map.is_some() && self.decoder.in_debug_mode() {
+                self.record_source_location(node_id, i + op_offset, program);
             }
 
             // decode and execute the operation
             self.decoder.execute_user_op(op, op_idx);
-            self.execute_op(op, host)?;
+            if let Err(err) = self.execute_op(op, host) {
+                return Err(self.attach_source_location(err, node_id, i + op_offset, program));
+            }
 
             // if the operation carries an immediate value, the value is stored at the next group
             // pointer; so, we advance the pointer to the following group
@@ -602,12 +961,16 @@ impl Process {
         Ok(())
     }
 
-    /// Executes the specified decorator
+    /// Executes the decorator with the specified id in the MAST forest.
     fn execute_decorator(
         &mut self,
-        decorator: &Decorator,
+        decorator_id: DecoratorId,
+        program: &MastForest,
         host: &mut impl Host,
     ) -> Result<(), ExecutionError> {
+        let decorator = program
+            .get_decorator_by_id(decorator_id)
+            .ok_or(ExecutionError::DecoratorNotFoundInForest { decorator_id })?;
         match decorator {
             Decorator::Debug(options) => {
                 if self.decoder.in_debug_mode() {
@@ -615,6 +978,12 @@ impl Process {
                 }
             },
             Decorator::AsmOp(assembly_op) => {
+                if let Some(profiler) = self.profiler.as_mut() {
+                    profiler.record_asmop(assembly_op.context_name());
+                }
+                if let Some(call_stack) = self.call_stack.as_mut() {
+                    call_stack.record_asmop(decorator_id);
+                }
                 if self.decoder.in_debug_mode() {
                     self.decoder.append_asmop(self.system.clk(), assembly_op.clone());
                 }
@@ -628,6 +997,61 @@ impl Process {
         Ok(())
     }
 
+    /// Records the execution of a branch of a split or loop node, if coverage is recorded.
+    fn record_branch(&mut self, digest: Digest, condition: bool) {
+        if let Some(coverage) = self.coverage.as_mut() {
+            coverage.record_branch(digest, condition);
+        }
+    }
+
+    /// Returns the source map of the program being executed if the specified MAST forest is the
+    /// forest of the program.
+    fn source_map_for(&self, forest: &MastForest) -> Option<&SourceMap> {
+        let program_forest = self.source_map_forest.as_deref()?;
+        self.source_map.as_ref().filter(|_| core::ptr::eq(program_forest, forest))
+    }
+
+    /// Records the source location of the instruction starting at the specified operation of a
+    /// basic block, if it is known, as the location of the current clock cycle.
+    fn record_source_location(&mut self, node_id: MastNodeId, op_idx: usize, forest: &MastForest) {
+        let location = self
+            .source_map_for(forest)
+            .and_then(|source_map| source_map.get_exact(node_id, op_idx));
+        if let Some(location) = location {
+            self.decoder.append_source_location(self.system.clk(), location);
+        }
+    }
+
+    /// Attaches the source location of the specified operation of a basic block to the error
+    /// raised by executing it, if the location is known.
+    fn attach_source_location(
+        &self,
+        error: ExecutionError,
+        node_id: MastNodeId,
+        op_idx: usize,
+        forest: &MastForest,
+    ) -> ExecutionError {
+        let location: Option<FileLineCol> = self
+            .source_map_for(forest)
+            .and_then(|source_map| source_map.get(node_id, op_idx));
+        match location {
+            Some(location) => {
+                ExecutionError::WithSourceLocation { error: Box::new(error), location }
+            },
+            None => error,
+        }
+    }
+
+    /// Attaches the call stack unwound by the specified error to it, if the call stack is tracked.
+    fn attach_call_stack(&mut self, error: ExecutionError) -> ExecutionError {
+        match self.call_stack.as_mut().map(CallStackTracker::take_call_stack) {
+            Some(call_stack) if !call_stack.is_empty() => {
+                ExecutionError::WithCallStack { error: Box::new(error), call_stack }
+            },
+            _ => error,
+        }
+    }
+
     // PUBLIC ACCESSORS
     // ================================================================================================
 
@@ -635,6 +1059,12 @@ impl Process {
         self.chiplets.kernel_rom.kernel()
     }
 
+    /// Returns the state of memory across all execution contexts, which can be used as the initial
+    /// memory of a subsequent execution via [Process::with_initial_memory].
+    pub fn memory_state(&self) -> MemoryState {
+        self.chiplets.memory.state()
+    }
+
     pub fn into_parts(self) -> (System, Decoder, Stack, RangeChecker, Chiplets) {
         (self.system, self.decoder, self.stack, self.range, self.chiplets)
     }
@@ -661,6 +1091,11 @@ impl ProcessState<'_> {
         self.system.ctx()
     }
 
+    /// Returns the kernel of the process, whose procedure roots are the valid syscall targets.
+    pub fn kernel(&self) -> &Kernel {
+        self.chiplets.kernel_rom.kernel()
+    }
+
     /// Returns the current value of the free memory pointer.
     pub fn fmp(&self) -> u64 {
         self.system.fmp().as_int()
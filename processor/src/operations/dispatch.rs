@@ -0,0 +1,194 @@
+use core::marker::PhantomData;
+
+use vm_core::{
+    OPCODE_ADD, OPCODE_ADVPOP, OPCODE_ADVPOPW, OPCODE_AND, OPCODE_ASSERT, OPCODE_CALLER,
+    OPCODE_CLK, OPCODE_CSWAP, OPCODE_CSWAPW, OPCODE_CTX, OPCODE_DROP, OPCODE_DUP0, OPCODE_DUP1,
+    OPCODE_DUP2, OPCODE_DUP3, OPCODE_DUP4, OPCODE_DUP5, OPCODE_DUP6, OPCODE_DUP7, OPCODE_DUP9,
+    OPCODE_DUP11, OPCODE_DUP13, OPCODE_DUP15, OPCODE_EMIT, OPCODE_EQ, OPCODE_EQZ, OPCODE_EXPACC,
+    OPCODE_EXT2MUL, OPCODE_FMPADD, OPCODE_FMPUPDATE, OPCODE_FRIE2F4, OPCODE_HORNERBASE,
+    OPCODE_HORNEREXT, OPCODE_HPERM, OPCODE_INCR, OPCODE_INV, OPCODE_MLOAD, OPCODE_MLOADW,
+    OPCODE_MOVDN2, OPCODE_MOVDN3, OPCODE_MOVDN4, OPCODE_MOVDN5, OPCODE_MOVDN6, OPCODE_MOVDN7,
+    OPCODE_MOVDN8, OPCODE_MOVUP2, OPCODE_MOVUP3, OPCODE_MOVUP4, OPCODE_MOVUP5, OPCODE_MOVUP6,
+    OPCODE_MOVUP7, OPCODE_MOVUP8, OPCODE_MPVERIFY, OPCODE_MRUPDATE, OPCODE_MSTORE, OPCODE_MSTOREW,
+    OPCODE_MSTREAM, OPCODE_MUL, OPCODE_NEG, OPCODE_NOOP, OPCODE_NOT, OPCODE_OR, OPCODE_PAD,
+    OPCODE_PIPE, OPCODE_PUSH, OPCODE_SDEPTH, OPCODE_SWAP, OPCODE_SWAPDW, OPCODE_SWAPW,
+    OPCODE_SWAPW2, OPCODE_SWAPW3, OPCODE_U32ADD, OPCODE_U32ADD3, OPCODE_U32AND, OPCODE_U32ASSERT2,
+    OPCODE_U32DIV, OPCODE_U32MADD, OPCODE_U32MUL, OPCODE_U32ROTL, OPCODE_U32SPLIT, OPCODE_U32SUB,
+    OPCODE_U32XOR, OPCODE_U64ADD, OPCODE_U64SUB,
+};
+
+use super::{ExecutionError, Host, Operation, Process};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of distinct opcodes, i.e., the number of entries in a dispatch table.
+const NUM_OPCODES: usize = 1 << Operation::OP_BITS;
+
+// DISPATCH TABLE
+// ================================================================================================
+
+/// A function executing a single operation against the specified process.
+type OpHandler<H> = fn(&mut Process, Operation, &mut H) -> Result<(), ExecutionError>;
+
+/// A table mapping each opcode to the handler of the corresponding operation.
+///
+/// Handlers of operations carrying an immediate value extract it from the operation passed to
+/// them; all other handlers ignore it. Opcodes of control flow operations, as well as unassigned
+/// opcodes, map to a handler which panics, as these operations are never executed directly.
+pub(super) struct OpTable<H>(PhantomData<H>);
+
+impl<H: Host> OpTable<H> {
+    #[rustfmt::skip]
+    pub(super) const HANDLERS: [OpHandler<H>; NUM_OPCODES] = {
+        let mut table = [control_flow_op::<H> as OpHandler<H>; NUM_OPCODES];
+
+        // ----- system operations ----------------------------------------------------------------
+        table[OPCODE_NOOP as usize] = |process, _, _| {
+            process.stack.copy_state(0);
+            Ok(())
+        };
+        table[OPCODE_ASSERT as usize] = |process, op, host| {
+            let Operation::Assert(err_code) = op else { unreachable!() };
+            process.op_assert(err_code, host)
+        };
+
+        table[OPCODE_FMPADD as usize] = |process, _, _| process.op_fmpadd();
+        table[OPCODE_FMPUPDATE as usize] = |process, _, _| process.op_fmpupdate();
+
+        table[OPCODE_SDEPTH as usize] = |process, _, _| process.op_sdepth();
+        table[OPCODE_CALLER as usize] = |process, _, _| process.op_caller();
+
+        table[OPCODE_CLK as usize] = |process, _, _| process.op_clk();
+        table[OPCODE_CTX as usize] = |process, _, _| process.op_ctx();
+        table[OPCODE_EMIT as usize] = |process, op, host| {
+            let Operation::Emit(event_id) = op else { unreachable!() };
+            process.op_emit(event_id, host)
+        };
+
+        // ----- field operations -----------------------------------------------------------------
+        table[OPCODE_ADD as usize] = |process, _, _| process.op_add();
+        table[OPCODE_NEG as usize] = |process, _, _| process.op_neg();
+        table[OPCODE_MUL as usize] = |process, _, _| process.op_mul();
+        table[OPCODE_INV as usize] = |process, _, _| process.op_inv();
+        table[OPCODE_INCR as usize] = |process, _, _| process.op_incr();
+
+        table[OPCODE_AND as usize] = |process, _, _| process.op_and();
+        table[OPCODE_OR as usize] = |process, _, _| process.op_or();
+        table[OPCODE_NOT as usize] = |process, _, _| process.op_not();
+
+        table[OPCODE_EQ as usize] = |process, _, _| process.op_eq();
+        table[OPCODE_EQZ as usize] = |process, _, _| process.op_eqz();
+
+        table[OPCODE_EXPACC as usize] = |process, _, _| process.op_expacc();
+
+        // ----- ext2 operations ------------------------------------------------------------------
+        table[OPCODE_EXT2MUL as usize] = |process, _, _| process.op_ext2mul();
+
+        // ----- u32 operations -------------------------------------------------------------------
+        table[OPCODE_U32SPLIT as usize] = |process, _, _| process.op_u32split();
+        table[OPCODE_U32ADD as usize] = |process, _, _| process.op_u32add();
+        table[OPCODE_U32ADD3 as usize] = |process, _, _| process.op_u32add3();
+        table[OPCODE_U32SUB as usize] = |process, _, _| process.op_u32sub();
+        table[OPCODE_U32MUL as usize] = |process, _, _| process.op_u32mul();
+        table[OPCODE_U32MADD as usize] = |process, _, _| process.op_u32madd();
+        table[OPCODE_U32DIV as usize] = |process, _, _| process.op_u32div();
+        table[OPCODE_U32ROTL as usize] = |process, _, _| process.op_u32rotl();
+
+        table[OPCODE_U32AND as usize] = |process, _, _| process.op_u32and();
+        table[OPCODE_U32XOR as usize] = |process, _, _| process.op_u32xor();
+        table[OPCODE_U32ASSERT2 as usize] = |process, op, _| {
+            let Operation::U32assert2(err_code) = op else { unreachable!() };
+            process.op_u32assert2(err_code)
+        };
+
+        // ----- u64 operations -------------------------------------------------------------------
+        table[OPCODE_U64ADD as usize] = |process, _, _| process.op_u64add();
+        table[OPCODE_U64SUB as usize] = |process, _, _| process.op_u64sub();
+
+        // ----- stack manipulation ---------------------------------------------------------------
+        table[OPCODE_PAD as usize] = |process, _, _| process.op_pad();
+        table[OPCODE_DROP as usize] = |process, _, _| process.op_drop();
+
+        table[OPCODE_DUP0 as usize] = |process, _, _| process.op_dup(0);
+        table[OPCODE_DUP1 as usize] = |process, _, _| process.op_dup(1);
+        table[OPCODE_DUP2 as usize] = |process, _, _| process.op_dup(2);
+        table[OPCODE_DUP3 as usize] = |process, _, _| process.op_dup(3);
+        table[OPCODE_DUP4 as usize] = |process, _, _| process.op_dup(4);
+        table[OPCODE_DUP5 as usize] = |process, _, _| process.op_dup(5);
+        table[OPCODE_DUP6 as usize] = |process, _, _| process.op_dup(6);
+        table[OPCODE_DUP7 as usize] = |process, _, _| process.op_dup(7);
+        table[OPCODE_DUP9 as usize] = |process, _, _| process.op_dup(9);
+        table[OPCODE_DUP11 as usize] = |process, _, _| process.op_dup(11);
+        table[OPCODE_DUP13 as usize] = |process, _, _| process.op_dup(13);
+        table[OPCODE_DUP15 as usize] = |process, _, _| process.op_dup(15);
+
+        table[OPCODE_SWAP as usize] = |process, _, _| process.op_swap();
+        table[OPCODE_SWAPW as usize] = |process, _, _| process.op_swapw();
+        table[OPCODE_SWAPW2 as usize] = |process, _, _| process.op_swapw2();
+        table[OPCODE_SWAPW3 as usize] = |process, _, _| process.op_swapw3();
+        table[OPCODE_SWAPDW as usize] = |process, _, _| process.op_swapdw();
+
+        table[OPCODE_MOVUP2 as usize] = |process, _, _| process.op_movup(2);
+        table[OPCODE_MOVUP3 as usize] = |process, _, _| process.op_movup(3);
+        table[OPCODE_MOVUP4 as usize] = |process, _, _| process.op_movup(4);
+        table[OPCODE_MOVUP5 as usize] = |process, _, _| process.op_movup(5);
+        table[OPCODE_MOVUP6 as usize] = |process, _, _| process.op_movup(6);
+        table[OPCODE_MOVUP7 as usize] = |process, _, _| process.op_movup(7);
+        table[OPCODE_MOVUP8 as usize] = |process, _, _| process.op_movup(8);
+
+        table[OPCODE_MOVDN2 as usize] = |process, _, _| process.op_movdn(2);
+        table[OPCODE_MOVDN3 as usize] = |process, _, _| process.op_movdn(3);
+        table[OPCODE_MOVDN4 as usize] = |process, _, _| process.op_movdn(4);
+        table[OPCODE_MOVDN5 as usize] = |process, _, _| process.op_movdn(5);
+        table[OPCODE_MOVDN6 as usize] = |process, _, _| process.op_movdn(6);
+        table[OPCODE_MOVDN7 as usize] = |process, _, _| process.op_movdn(7);
+        table[OPCODE_MOVDN8 as usize] = |process, _, _| process.op_movdn(8);
+
+        table[OPCODE_CSWAP as usize] = |process, _, _| process.op_cswap();
+        table[OPCODE_CSWAPW as usize] = |process, _, _| process.op_cswapw();
+
+        // ----- input / output -------------------------------------------------------------------
+        table[OPCODE_PUSH as usize] = |process, op, _| {
+            let Operation::Push(value) = op else { unreachable!() };
+            process.op_push(value)
+        };
+
+        table[OPCODE_ADVPOP as usize] = |process, _, host| process.op_advpop(host);
+        table[OPCODE_ADVPOPW as usize] = |process, _, host| process.op_advpopw(host);
+
+        table[OPCODE_MLOADW as usize] = |process, _, _| process.op_mloadw();
+        table[OPCODE_MSTOREW as usize] = |process, _, _| process.op_mstorew();
+
+        table[OPCODE_MLOAD as usize] = |process, _, _| process.op_mload();
+        table[OPCODE_MSTORE as usize] = |process, _, _| process.op_mstore();
+
+        table[OPCODE_MSTREAM as usize] = |process, _, _| process.op_mstream();
+        table[OPCODE_PIPE as usize] = |process, _, host| process.op_pipe(host);
+
+        // ----- cryptographic operations ---------------------------------------------------------
+        table[OPCODE_HPERM as usize] = |process, _, _| process.op_hperm();
+        table[OPCODE_MPVERIFY as usize] = |process, op, host| {
+            let Operation::MpVerify(err_code) = op else { unreachable!() };
+            process.op_mpverify(err_code, host)
+        };
+        table[OPCODE_MRUPDATE as usize] = |process, _, host| process.op_mrupdate(host);
+        table[OPCODE_FRIE2F4 as usize] = |process, _, _| process.op_fri_ext2fold4();
+        table[OPCODE_HORNERBASE as usize] = |process, _, _| process.op_horner_eval_base();
+        table[OPCODE_HORNEREXT as usize] = |process, _, _| process.op_horner_eval_ext();
+
+        table
+    };
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// The handler of control flow operations, which are never executed directly.
+fn control_flow_op<H: Host>(
+    _: &mut Process,
+    _: Operation,
+    _: &mut H,
+) -> Result<(), ExecutionError> {
+    unreachable!("control flow operation")
+}
@@ -1,8 +1,11 @@
 use vm_core::stack::MIN_STACK_DEPTH;
 
-use super::{ExecutionError, Felt, FieldElement, Host, Operation, Process};
+use super::{
+    CancellationToken, ExecutionError, Felt, FieldElement, Host, OpDispatch, Operation, Process,
+};
 
 mod crypto_ops;
+mod dispatch;
 mod ext2_ops;
 mod field_ops;
 mod fri_ops;
@@ -21,15 +24,40 @@ use super::Kernel;
 
 impl Process {
     /// Executes the specified operation.
-    pub(super) fn execute_op(
+    ///
+    /// The handler of the operation is selected according to the [OpDispatch] strategy of the
+    /// process.
+    pub(super) fn execute_op<H: Host>(
         &mut self,
         op: Operation,
-        host: &mut impl Host,
+        host: &mut H,
     ) -> Result<(), ExecutionError> {
         // make sure there is enough memory allocated to hold the execution trace
         self.ensure_trace_capacity();
 
+        #[cfg(feature = "tracing-spans")]
+        tracing::trace!(
+            clk = u32::from(self.system.clk()),
+            op = %op,
+            chiplets = ?op.metadata().chiplets(),
+        );
+
         // execute the operation
+        match self.op_dispatch {
+            OpDispatch::Table => {
+                let handler = dispatch::OpTable::<H>::HANDLERS[op.op_code() as usize];
+                handler(self, op, host)?
+            },
+            OpDispatch::Match => self.match_op(op, host)?,
+        }
+
+        self.advance_clock()?;
+
+        Ok(())
+    }
+
+    /// Executes the specified operation by matching on it to select its handler.
+    fn match_op(&mut self, op: Operation, host: &mut impl Host) -> Result<(), ExecutionError> {
         match op {
             // ----- system operations ------------------------------------------------------------
             Operation::Noop => self.stack.copy_state(0),
@@ -42,6 +70,7 @@ impl Process {
             Operation::Caller => self.op_caller()?,
 
             Operation::Clk => self.op_clk()?,
+            Operation::Ctx => self.op_ctx()?,
             Operation::Emit(event_id) => self.op_emit(event_id, host)?,
 
             // ----- flow control operations ------------------------------------------------------
@@ -86,11 +115,16 @@ impl Process {
             Operation::U32mul => self.op_u32mul()?,
             Operation::U32madd => self.op_u32madd()?,
             Operation::U32div => self.op_u32div()?,
+            Operation::U32rotl => self.op_u32rotl()?,
 
             Operation::U32and => self.op_u32and()?,
             Operation::U32xor => self.op_u32xor()?,
             Operation::U32assert2(err_code) => self.op_u32assert2(err_code)?,
 
+            // ----- u64 operations ---------------------------------------------------------------
+            Operation::U64add => self.op_u64add()?,
+            Operation::U64sub => self.op_u64sub()?,
+
             // ----- stack manipulation -----------------------------------------------------------
             Operation::Pad => self.op_pad()?,
             Operation::Drop => self.op_drop()?,
@@ -157,15 +191,23 @@ impl Process {
             Operation::HornerExt => self.op_horner_eval_ext()?,
         }
 
-        self.advance_clock()?;
-
         Ok(())
     }
 
     /// Increments the clock cycle for all components of the process.
+    ///
+    /// # Errors
+    /// Returns an error if the maximum number of cycles, or of rows of the memory chiplet trace,
+    /// was exceeded, or if the execution was cancelled.
     pub(super) fn advance_clock(&mut self) -> Result<(), ExecutionError> {
         self.system.advance_clock(self.max_cycles)?;
         self.stack.advance_clock();
+        if self.chiplets.memory.trace_len() > self.max_memory_rows as usize {
+            return Err(ExecutionError::MemoryLimitExceeded(self.max_memory_rows));
+        }
+        if self.cancel_token.as_ref().is_some_and(CancellationToken::is_cancelled) {
+            return Err(ExecutionError::Cancelled);
+        }
         Ok(())
     }
 
@@ -1,4 +1,4 @@
-use vm_core::{Felt, Operation, sys_events::SystemEvent};
+use vm_core::{Felt, Operation, stack::MIN_STACK_DEPTH, sys_events::SystemEvent};
 
 use super::{
     super::{
@@ -113,6 +113,17 @@ impl Process {
         Ok(())
     }
 
+    // EXECUTION CONTEXT
+    // --------------------------------------------------------------------------------------------
+
+    /// Pushes the id of the current execution context onto the stack.
+    pub(super) fn op_ctx(&mut self) -> Result<(), ExecutionError> {
+        let ctx = self.system.ctx();
+        self.stack.set(0, Felt::from(ctx));
+        self.stack.shift_right(0);
+        Ok(())
+    }
+
     // EVENTS
     // --------------------------------------------------------------------------------------------
 
@@ -124,6 +135,13 @@ impl Process {
         self.stack.copy_state(0);
         self.decoder.set_user_op_helpers(Operation::Emit(event_id), &[event_id.into()]);
 
+        if let Some(events) = self.events.as_mut() {
+            let stack = events
+                .stack_snapshots()
+                .then(|| (0..MIN_STACK_DEPTH).map(|pos| self.stack.get(pos)).collect());
+            events.record(event_id, self.system.clk().into(), self.system.ctx(), stack);
+        }
+
         // If it's a system event, handle it directly. Otherwise, forward it to the host.
         if let Some(system_event) = SystemEvent::from_event_id(event_id) {
             self.handle_system_event(system_event, host)
@@ -285,6 +303,24 @@ mod tests {
         assert_eq!(expected, process.stack.trace_state());
     }
 
+    #[test]
+    fn op_ctx() {
+        let mut host = DefaultHost::default();
+        let mut process = Process::new_dummy_with_empty_stack();
+
+        // the program starts executing in the root context.
+        process.execute_op(Operation::Ctx, &mut host).unwrap();
+        let expected = build_expected_stack(&[0]);
+        assert_eq!(expected, process.stack.trace_state());
+
+        // a call creates a new context whose id depends on the clock cycle of the call.
+        process.system.start_call_or_dyncall([ZERO; 4]);
+        process.execute_op(Operation::Ctx, &mut host).unwrap();
+        let expected = build_expected_stack(&[3, 0]);
+        assert_eq!(expected, process.stack.trace_state());
+        assert_eq!(MIN_STACK_DEPTH + 2, process.stack.depth());
+    }
+
     // HELPER FUNCTIONS
     // --------------------------------------------------------------------------------------------
 
@@ -1,18 +1,19 @@
 use alloc::vec::Vec;
 
 use vm_core::{
-    Felt, FieldElement, WORD_SIZE, Word, ZERO,
+    ExecutionEnvironment, Felt, FieldElement, WORD_SIZE, Word, ZERO,
     crypto::{
         hash::{Rpo256, RpoDigest},
         merkle::{EmptySubtreeRoots, SMT_DEPTH, Smt},
     },
     sys_events::SystemEvent,
+    utils::ToElements,
 };
 use winter_prover::math::fft;
 
 use crate::{
-    AdviceProvider, AdviceSource, ExecutionError, Ext2InttError, Host, Process, ProcessState,
-    QuadFelt,
+    AdviceProvider, AdviceSource, ExecutionError, Ext2InttError, Host, NttError, Process,
+    ProcessState, QuadFelt,
 };
 
 /// The offset of the domain value on the stack in the `hdword_to_map_with_domain` system event.
@@ -21,14 +22,32 @@ const HDWORD_TO_MAP_WITH_DOMAIN_DOMAIN_OFFSET: usize = 8;
 /// Falcon signature prime.
 const M: u64 = 12289;
 
+/// The prime defining the secp256k1 base field, as 64-bit limbs (least significant limb first).
+const SECP256K1_BASE_MODULUS: [u64; 4] =
+    [0xfffffffefffffc2f, 0xffffffffffffffff, 0xffffffffffffffff, 0xffffffffffffffff];
+
+/// The order of the secp256k1 group (i.e., the prime defining the scalar field), as 64-bit limbs
+/// (least significant limb first).
+const SECP256K1_SCALAR_MODULUS: [u64; 4] =
+    [0xbfd25e8cd0364141, 0xbaaedce6af48a03b, 0xfffffffffffffffe, 0xffffffffffffffff];
+
 impl Process {
     pub(super) fn handle_system_event(
-        &self,
+        &mut self,
         system_event: SystemEvent,
         host: &mut impl Host,
     ) -> Result<(), ExecutionError> {
+        // memory protection configures the memory chiplet rather than the advice provider, and thus
+        // requires mutable access to the process
+        if let SystemEvent::MemProtect = system_event {
+            let start_addr = self.stack.get(0).as_int();
+            let end_addr = self.stack.get(1).as_int();
+            return self.chiplets.memory.protect(self.system.ctx(), start_addr, end_addr);
+        }
+
+        let environment = self.environment;
         let advice_provider = host.advice_provider_mut();
-        let process_state: ProcessState = self.into();
+        let process_state: ProcessState = (&*self).into();
         match system_event {
             SystemEvent::MerkleNodeMerge => merge_merkle_nodes(advice_provider, process_state),
             SystemEvent::MerkleNodeToStack => {
@@ -44,12 +63,20 @@ impl Process {
             SystemEvent::FalconDiv => push_falcon_mod_result(advice_provider, process_state),
             SystemEvent::Ext2Inv => push_ext2_inv_result(advice_provider, process_state),
             SystemEvent::Ext2Intt => push_ext2_intt_result(advice_provider, process_state),
+            SystemEvent::Ntt => push_ntt_result(advice_provider, process_state),
+            SystemEvent::Intt => push_intt_result(advice_provider, process_state),
             SystemEvent::SmtPeek => push_smtpeek_result(advice_provider, process_state),
             SystemEvent::U32Clz => push_leading_zeros(advice_provider, process_state),
             SystemEvent::U32Ctz => push_trailing_zeros(advice_provider, process_state),
             SystemEvent::U32Clo => push_leading_ones(advice_provider, process_state),
             SystemEvent::U32Cto => push_trailing_ones(advice_provider, process_state),
             SystemEvent::ILog2 => push_ilog2(advice_provider, process_state),
+            SystemEvent::Secp256k1BaseInv => {
+                push_secp256k1_inv_result(advice_provider, process_state, &SECP256K1_BASE_MODULUS)
+            },
+            SystemEvent::Secp256k1ScalarInv => {
+                push_secp256k1_inv_result(advice_provider, process_state, &SECP256K1_SCALAR_MODULUS)
+            },
 
             SystemEvent::MemToMap => insert_mem_values_into_adv_map(advice_provider, process_state),
             SystemEvent::HdwordToMap => {
@@ -59,7 +86,10 @@ impl Process {
                 let domain = self.stack.get(HDWORD_TO_MAP_WITH_DOMAIN_DOMAIN_OFFSET);
                 insert_hdword_into_adv_map(advice_provider, process_state, domain)
             },
+            SystemEvent::MemProtect => unreachable!("handled above"),
             SystemEvent::HpermToMap => insert_hperm_into_adv_map(advice_provider, process_state),
+
+            SystemEvent::EnvToStack => push_environment(advice_provider, &environment),
         }
     }
 }
@@ -523,6 +553,145 @@ pub fn push_ext2_intt_result(
     Ok(())
 }
 
+/// Given the coefficients of a polynomial over the base field, evaluates the polynomial on the
+/// multiplicative subgroup of size `n` and pushes the evaluations onto the advice stack.
+///
+/// Inputs:
+///   Operand stack: [n, coeffs_ptr, ...]
+///   Advice stack: [...]
+///
+/// Outputs:
+///   Operand stack: [n, coeffs_ptr, ...]
+///   Advice stack: [evaluations...]
+///
+/// # Errors
+/// Returns an error if:
+/// - `n` is not a power of 2 greater than or equal to 4.
+/// - `coeffs_ptr` is not word-aligned, or the coefficients don't fit into memory.
+/// - Any of the coefficients is located in uninitialized memory.
+pub fn push_ntt_result(
+    advice_provider: &mut impl AdviceProvider,
+    process: ProcessState,
+) -> Result<(), ExecutionError> {
+    let mut poly = read_ntt_input(process)?;
+
+    let twiddles = fft::get_twiddles::<Felt>(poly.len());
+    fft::evaluate_poly(&mut poly, &twiddles);
+
+    for element in poly.iter().rev() {
+        advice_provider.push_stack(AdviceSource::Value(*element))?;
+    }
+
+    Ok(())
+}
+
+/// Given the evaluations of a polynomial over the base field on the multiplicative subgroup of
+/// size `n`, interpolates the polynomial and pushes its coefficients onto the advice stack.
+///
+/// Inputs:
+///   Operand stack: [n, evals_ptr, ...]
+///   Advice stack: [...]
+///
+/// Outputs:
+///   Operand stack: [n, evals_ptr, ...]
+///   Advice stack: [coefficients...]
+///
+/// # Errors
+/// Returns an error if:
+/// - `n` is not a power of 2 greater than or equal to 4.
+/// - `evals_ptr` is not word-aligned, or the evaluations don't fit into memory.
+/// - Any of the evaluations is located in uninitialized memory.
+pub fn push_intt_result(
+    advice_provider: &mut impl AdviceProvider,
+    process: ProcessState,
+) -> Result<(), ExecutionError> {
+    let mut poly = read_ntt_input(process)?;
+
+    let inv_twiddles = fft::get_inv_twiddles::<Felt>(poly.len());
+    fft::interpolate_poly(&mut poly, &inv_twiddles);
+
+    for element in poly.iter().rev() {
+        advice_provider.push_stack(AdviceSource::Value(*element))?;
+    }
+
+    Ok(())
+}
+
+/// Given an element of a secp256k1 field in Montgomery form on the top of the stack, computes its
+/// multiplicative inverse (also in Montgomery form) and pushes the result onto the advice stack.
+///
+/// Inputs:
+///   Operand stack: [a0, a1, a2, a3, a4, a5, a6, a7, ...]
+///   Advice stack: [...]
+///
+/// Outputs:
+///   Operand stack: [a0, a1, a2, a3, a4, a5, a6, a7, ...]
+///   Advice stack: [b7, b6, b5, b4, b3, b2, b1, b0, ...]
+///
+/// Where (a0, ..., a7) and (b0, ..., b7) are the 32-bit limbs of a and b respectively (with a0
+/// and b0 representing the 32 least significant bits), and b = a^-1 (mod `modulus`). If a is 0
+/// modulo `modulus`, b is 0.
+///
+/// # Errors
+/// Returns an error if any of the limbs of a is not a u32 value.
+pub fn push_secp256k1_inv_result(
+    advice_provider: &mut impl AdviceProvider,
+    process: ProcessState,
+    modulus: &[u64; 4],
+) -> Result<(), ExecutionError> {
+    let mut element = [0_u64; 4];
+    for i in 0..8 {
+        let limb = process.get_stack_item(i);
+        let limb: u32 =
+            limb.as_int().try_into().map_err(|_| ExecutionError::NotU32Value(limb, ZERO))?;
+        element[i / 2] |= (limb as u64) << (32 * (i % 2));
+    }
+
+    // for a in Montgomery form (i.e., a = x * R for R = 2^256), the inverse of x in Montgomery form
+    // is x^-1 * R = R^2 / a (mod p)
+    let element = u256::reduce(element, modulus);
+    let result = if element == [0; 4] {
+        [0; 4]
+    } else {
+        let r = u256::reduce(u256::neg(modulus), modulus);
+        let r2 = u256::mul_mod(&r, &r, modulus);
+        let exponent = u256::sub(modulus, &[2, 0, 0, 0]);
+        u256::mul_mod(&r2, &u256::pow_mod(&element, &exponent, modulus), modulus)
+    };
+
+    for limb in result.iter() {
+        advice_provider.push_stack(AdviceSource::Value(Felt::from(*limb as u32)))?;
+        advice_provider.push_stack(AdviceSource::Value(Felt::from((*limb >> 32) as u32)))?;
+    }
+
+    Ok(())
+}
+
+/// Pushes the environment of the execution onto the advice stack.
+///
+/// The environment is the one the host provided when the execution started, i.e., the
+/// environment committed to in the public inputs of the execution.
+///
+/// Inputs:
+///   Advice stack: [...]
+///
+/// Outputs:
+///   Advice stack: [CALLER_ID, SEED, HEADER, ...]
+///
+/// Where HEADER is [block_num, timestamp, 0, 0].
+pub fn push_environment(
+    advice_provider: &mut impl AdviceProvider,
+    environment: &ExecutionEnvironment,
+) -> Result<(), ExecutionError> {
+    let elements = environment.to_elements();
+    for word in elements.chunks_exact(WORD_SIZE) {
+        let word: Word = word.try_into().expect("environment is made up of whole words");
+        advice_provider.push_stack(AdviceSource::Word(word))?;
+    }
+
+    Ok(())
+}
+
 /// Pushes the number of the leading zeros of the top stack element onto the advice stack.
 ///
 /// Inputs:
@@ -656,6 +825,7 @@ pub fn push_smtpeek_result(
         // the specified key
         advice_provider.push_stack(AdviceSource::Word(Smt::EMPTY_VALUE))?;
     } else {
+        advice_provider.resolve_mapped_values(&node.into())?;
         let leaf_preimage = get_smt_leaf_preimage(advice_provider, node)?;
 
         for (key_in_leaf, value_in_leaf) in leaf_preimage {
@@ -748,3 +918,110 @@ fn get_smt_leaf_preimage<A: AdviceProvider>(
         })
         .collect())
 }
+
+/// Reads the `n` base field elements starting at the memory address `ptr` for the `Ntt` and `Intt`
+/// system events, where the operand stack is `[n, ptr, ...]`.
+fn read_ntt_input(process: ProcessState) -> Result<Vec<Felt>, ExecutionError> {
+    let size = process.get_stack_item(0).as_int();
+    let start_ptr = process.get_stack_item(1).as_int();
+
+    if size < WORD_SIZE as u64 {
+        return Err(NttError::DomainSizeTooSmall(size).into());
+    }
+    if !size.is_power_of_two() {
+        return Err(NttError::DomainSizeNotPowerOf2(size).into());
+    }
+    if start_ptr >= u32::MAX as u64 {
+        return Err(NttError::InputStartAddressTooBig(start_ptr).into());
+    }
+    if start_ptr % WORD_SIZE as u64 != 0 {
+        return Err(NttError::InputStartNotWordAligned(start_ptr).into());
+    }
+
+    let end_ptr = start_ptr + size;
+    if end_ptr > u32::MAX as u64 {
+        return Err(NttError::InputEndAddressTooBig(end_ptr).into());
+    }
+
+    let mut poly = Vec::with_capacity(size as usize);
+    for addr in ((start_ptr as u32)..(end_ptr as u32)).step_by(WORD_SIZE) {
+        let word = process
+            .get_mem_word(process.ctx(), addr)?
+            .ok_or(NttError::UninitializedMemoryAddress(addr))?;
+        poly.extend(word);
+    }
+
+    Ok(poly)
+}
+
+/// Modular arithmetic over 256-bit integers represented by 64-bit limbs (least significant limb
+/// first), for moduli greater than 2^255.
+mod u256 {
+    pub type U256 = [u64; 4];
+
+    /// Returns a - b (mod 2^256).
+    pub fn sub(a: &U256, b: &U256) -> U256 {
+        let mut result = [0; 4];
+        let mut borrow = false;
+        for i in 0..4 {
+            let (diff, borrow1) = a[i].overflowing_sub(b[i]);
+            let (diff, borrow2) = diff.overflowing_sub(borrow as u64);
+            result[i] = diff;
+            borrow = borrow1 || borrow2;
+        }
+        result
+    }
+
+    /// Returns -a (mod 2^256).
+    pub fn neg(a: &U256) -> U256 {
+        sub(&[0; 4], a)
+    }
+
+    /// Returns a (mod m) for any a < 2^256.
+    pub fn reduce(a: U256, m: &U256) -> U256 {
+        if is_less(&a, m) { a } else { sub(&a, m) }
+    }
+
+    /// Returns a * b (mod m) for a, b < m.
+    pub fn mul_mod(a: &U256, b: &U256, m: &U256) -> U256 {
+        let mut result = [0; 4];
+        for i in (0..256).rev() {
+            result = add_mod(&result, &result, m);
+            if (b[i / 64] >> (i % 64)) & 1 == 1 {
+                result = add_mod(&result, a, m);
+            }
+        }
+        result
+    }
+
+    /// Returns a^e (mod m) for a < m.
+    pub fn pow_mod(a: &U256, e: &U256, m: &U256) -> U256 {
+        let mut result = reduce([1, 0, 0, 0], m);
+        for i in (0..256).rev() {
+            result = mul_mod(&result, &result, m);
+            if (e[i / 64] >> (i % 64)) & 1 == 1 {
+                result = mul_mod(&result, a, m);
+            }
+        }
+        result
+    }
+
+    /// Returns a + b (mod m) for a, b < m.
+    fn add_mod(a: &U256, b: &U256, m: &U256) -> U256 {
+        let mut sum = [0; 4];
+        let mut carry = false;
+        for i in 0..4 {
+            let (s, carry1) = a[i].overflowing_add(b[i]);
+            let (s, carry2) = s.overflowing_add(carry as u64);
+            sum[i] = s;
+            carry = carry1 || carry2;
+        }
+        // since a + b < 2m, subtracting m once is enough to reduce the sum, and the subtraction
+        // also accounts for the carry bit (if any) as it is computed modulo 2^256
+        if carry || !is_less(&sum, m) { sub(&sum, m) } else { sum }
+    }
+
+    fn is_less(a: &U256, b: &U256) -> bool {
+        a.iter().rev().lt(b.iter().rev())
+    }
+}
@@ -199,6 +199,85 @@ impl Process {
         Ok(())
     }
 
+    /// Pops two elements off the stack, where the top element is a power of two 2^b, rotates the
+    /// bits of the second element to the left by b bits, and pushes the result back onto the stack.
+    ///
+    /// The rotation is computed by multiplying the value by 2^b, and adding the upper 32-bit limb
+    /// of the product to the lower one.
+    ///
+    /// # Errors
+    /// Returns an error if the top element is not a power of two between 1 and 2^32.
+    pub(super) fn op_u32rotl(&mut self) -> Result<(), ExecutionError> {
+        let b = self.stack.get(0);
+        let a = require_u32_operand!(self.stack, 1).as_int();
+
+        if !b.as_int().is_power_of_two() || b.as_int() > 1 << 32 {
+            return Err(ExecutionError::NotPowerOfTwo(b));
+        }
+
+        let (hi, lo) = split_element(Felt::new(a * b.as_int()));
+        self.add_range_checks(Operation::U32rotl, lo, hi, true);
+
+        self.stack.set(0, lo + hi);
+        self.stack.shift_left(2);
+        Ok(())
+    }
+
+    // U64 OPERATIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Pops two 64-bit values represented by their upper and lower 32-bit limbs off the stack,
+    /// adds them, and pushes the lower and upper 32-bit limbs of the sum, as well as a flag
+    /// indicating whether there was overflow, back onto the stack.
+    pub(super) fn op_u64add(&mut self) -> Result<(), ExecutionError> {
+        let b_hi = require_u32_operand!(self.stack, 0).as_int();
+        let b_lo = require_u32_operand!(self.stack, 1).as_int();
+        let a_hi = require_u32_operand!(self.stack, 2).as_int();
+        let a_lo = require_u32_operand!(self.stack, 3).as_int();
+
+        let lo = a_lo + b_lo;
+        let carry = lo >> 32;
+        let hi = a_hi + b_hi + carry;
+
+        let c_lo = Felt::new(lo & U32_MAX);
+        let c_hi = Felt::new(hi & U32_MAX);
+        // the carry from the lower limbs is saved as the fifth helper value.
+        self.add_range_checks_with_helper(Operation::U64add, c_lo, c_hi, Felt::new(carry));
+
+        self.stack.set(0, Felt::new(hi >> 32));
+        self.stack.set(1, c_hi);
+        self.stack.set(2, c_lo);
+        self.stack.shift_left(4);
+        Ok(())
+    }
+
+    /// Pops two 64-bit values represented by their upper and lower 32-bit limbs off the stack,
+    /// subtracts the top value from the second value, and pushes the lower and upper 32-bit limbs
+    /// of the difference, as well as a flag indicating whether there was underflow, back onto the
+    /// stack.
+    pub(super) fn op_u64sub(&mut self) -> Result<(), ExecutionError> {
+        let b_hi = require_u32_operand!(self.stack, 0).as_int();
+        let b_lo = require_u32_operand!(self.stack, 1).as_int();
+        let a_hi = require_u32_operand!(self.stack, 2).as_int();
+        let a_lo = require_u32_operand!(self.stack, 3).as_int();
+
+        let borrow = (a_lo < b_lo) as u64;
+        let c_lo = Felt::new(a_lo.wrapping_sub(b_lo) & U32_MAX);
+        let c_hi = Felt::new(a_hi.wrapping_sub(b_hi + borrow) & U32_MAX);
+        let underflow = (a_hi < b_hi + borrow) as u64;
+        // the borrow from the lower limbs is saved as the fifth helper value.
+        self.add_range_checks_with_helper(Operation::U64sub, c_lo, c_hi, Felt::new(borrow));
+
+        self.stack.set(0, Felt::new(underflow));
+        self.stack.set(1, c_hi);
+        self.stack.set(2, c_lo);
+        self.stack.shift_left(4);
+        Ok(())
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
     /// Adds 16-bit range checks to the RangeChecker for the high and low 16-bit limbs of two field
     /// elements which are assumed to have 32-bit integer values. This results in 4 range checks.
     ///
@@ -214,6 +293,18 @@ impl Process {
         hi: Felt,
         check_element_validity: bool,
     ) {
+        let m = if check_element_validity {
+            (Felt::from(u32::MAX) - hi).inv()
+        } else {
+            ZERO
+        };
+
+        self.add_range_checks_with_helper(op, lo, hi, m);
+    }
+
+    /// Adds 16-bit range checks for the limbs of `lo` and `hi`, and saves these limbs, followed
+    /// by the specified `helper` value, to the decoder's user operation helper columns.
+    fn add_range_checks_with_helper(&mut self, op: Operation, lo: Felt, hi: Felt, helper: Felt) {
         let (t1, t0) = split_u32_into_u16(lo.as_int());
         let (t3, t2) = split_u32_into_u16(hi.as_int());
 
@@ -221,13 +312,8 @@ impl Process {
         self.range.add_range_checks(self.system.clk(), &[t0, t1, t2, t3]);
 
         // save the range check lookups to the decoder's user operation helper columns.
-        let mut helper_values =
-            [Felt::from(t0), Felt::from(t1), Felt::from(t2), Felt::from(t3), ZERO];
-
-        if check_element_validity {
-            let m = (Felt::from(u32::MAX) - hi).inv();
-            helper_values[4] = m;
-        }
+        let helper_values =
+            [Felt::from(t0), Felt::from(t1), Felt::from(t2), Felt::from(t3), helper];
 
         self.decoder.set_user_op_helpers(op, &helper_values);
     }
@@ -244,7 +330,7 @@ mod tests {
 
     use super::{
         super::{Felt, Operation},
-        Process, split_u32_into_u16,
+        FieldElement, Process, split_u32_into_u16,
     };
     use crate::{DefaultHost, StackInputs, ZERO};
 
@@ -463,9 +549,119 @@ mod tests {
         assert!(process.execute_op(Operation::U32xor, &mut host).is_ok());
     }
 
+    #[test]
+    fn op_u32rotl() {
+        // --- test random values ---------------------------------------------
+        let mut host = DefaultHost::default();
+        let (a, b, c, _) = get_rand_values();
+        let shift = b % 33;
+        let stack = StackInputs::try_from_ints([c as u64, a as u64, 1 << shift]).unwrap();
+        let mut process = Process::new_dummy_with_decoder_helpers(stack);
+
+        process.execute_op(Operation::U32rotl, &mut host).unwrap();
+        let expected = build_expected(&[a.rotate_left(shift), c]);
+        assert_eq!(expected, process.stack.trace_state());
+
+        // --- test rotation of the most significant bit ----------------------
+        let stack = StackInputs::try_from_ints([0x8000_0001, 4]).unwrap();
+        let mut process = Process::new_dummy_with_decoder_helpers(stack);
+
+        process.execute_op(Operation::U32rotl, &mut host).unwrap();
+        let expected = build_expected(&[0b110]);
+        assert_eq!(expected, process.stack.trace_state());
+
+        // the product 0x8000_0001 * 4 is split into the limbs of its lower and upper halves,
+        // followed by the helper value of the element validity check.
+        let mut expected_helper_registers = build_expected_helper_registers(&[4, 0, 2, 0]);
+        expected_helper_registers[4] = (Felt::from(u32::MAX) - Felt::new(2)).inv();
+        assert_eq!(expected_helper_registers, process.decoder.get_user_op_helpers());
+
+        // --- test multiplier which is not a power of two --------------------
+        let stack = StackInputs::try_from_ints([a as u64, 3]).unwrap();
+        let mut process = Process::new_dummy_with_decoder_helpers(stack);
+        assert!(process.execute_op(Operation::U32rotl, &mut host).is_err());
+    }
+
+    // U64 OPERATIONS
+    // --------------------------------------------------------------------------------------------
+
+    #[test]
+    fn op_u64add() {
+        // --- test random values ---------------------------------------------
+        let mut host = DefaultHost::default();
+        let (a, b) = (rand_value::<u64>(), rand_value::<u64>());
+        let c = rand_value::<u64>() as u32;
+        let stack = build_u64_stack_inputs(a, b, c);
+        let mut process = Process::new_dummy_with_decoder_helpers(stack);
+        let (result, over) = a.overflowing_add(b);
+
+        process.execute_op(Operation::U64add, &mut host).unwrap();
+        let expected = build_expected(&[over as u32, (result >> 32) as u32, result as u32, c]);
+        assert_eq!(expected, process.stack.trace_state());
+
+        // --- test carry between the limbs -----------------------------------
+        let stack = build_u64_stack_inputs(u32::MAX as u64, 1, 0);
+        let mut process = Process::new_dummy_with_decoder_helpers(stack);
+
+        process.execute_op(Operation::U64add, &mut host).unwrap();
+        let expected = build_expected(&[0, 1, 0]);
+        assert_eq!(expected, process.stack.trace_state());
+
+        let expected_helper_registers = build_expected_helper_registers(&[0, 0, 1, 0, 1]);
+        assert_eq!(expected_helper_registers, process.decoder.get_user_op_helpers());
+
+        // --- test with minimum stack depth ----------------------------------
+        let mut process = Process::new_dummy_with_decoder_helpers_and_empty_stack();
+        assert!(process.execute_op(Operation::U64add, &mut host).is_ok());
+    }
+
+    #[test]
+    fn op_u64sub() {
+        // --- test random values ---------------------------------------------
+        let mut host = DefaultHost::default();
+        let (a, b) = (rand_value::<u64>(), rand_value::<u64>());
+        let c = rand_value::<u64>() as u32;
+        let stack = build_u64_stack_inputs(a, b, c);
+        let mut process = Process::new_dummy_with_decoder_helpers(stack);
+        let (result, under) = a.overflowing_sub(b);
+
+        process.execute_op(Operation::U64sub, &mut host).unwrap();
+        let expected = build_expected(&[under as u32, (result >> 32) as u32, result as u32, c]);
+        assert_eq!(expected, process.stack.trace_state());
+
+        // --- test borrow between the limbs ----------------------------------
+        let stack = build_u64_stack_inputs(1 << 32, 1, 0);
+        let mut process = Process::new_dummy_with_decoder_helpers(stack);
+
+        process.execute_op(Operation::U64sub, &mut host).unwrap();
+        let expected = build_expected(&[0, 0, u32::MAX]);
+        assert_eq!(expected, process.stack.trace_state());
+
+        let expected_helper_registers =
+            build_expected_helper_registers(&[u16::MAX as u32, u16::MAX as u32, 0, 0, 1]);
+        assert_eq!(expected_helper_registers, process.decoder.get_user_op_helpers());
+
+        // --- test underflow -------------------------------------------------
+        let stack = build_u64_stack_inputs(1, 2, 0);
+        let mut process = Process::new_dummy_with_decoder_helpers(stack);
+
+        process.execute_op(Operation::U64sub, &mut host).unwrap();
+        let expected = build_expected(&[1, u32::MAX, u32::MAX]);
+        assert_eq!(expected, process.stack.trace_state());
+    }
+
     // HELPER FUNCTIONS
     // --------------------------------------------------------------------------------------------
 
+    /// Returns stack inputs with the limbs of `b` at the top of the stack, followed by the limbs of
+    /// `a` and by `c`, i.e., [b_hi, b_lo, a_hi, a_lo, c].
+    fn build_u64_stack_inputs(a: u64, b: u64, c: u32) -> StackInputs {
+        let limbs = |value: u64| [value as u32 as u64, value >> 32];
+        let [a_lo, a_hi] = limbs(a);
+        let [b_lo, b_hi] = limbs(b);
+        StackInputs::try_from_ints([c as u64, a_lo, a_hi, b_lo, b_hi]).unwrap()
+    }
+
     fn get_rand_values() -> (u32, u32, u32, u32) {
         let a = rand_value::<u64>() as u32;
         let b = rand_value::<u64>() as u32;
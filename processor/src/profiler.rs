@@ -0,0 +1,216 @@
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
+
+use vm_core::crypto::hash::RpoDigest;
+
+// PROFILE REPORT
+// ================================================================================================
+
+/// A report of the number of cycles spent executing each procedure of a program.
+///
+/// The report is organized as a call tree, rooted at the entrypoint of the program. Each node of
+/// the tree corresponds to a procedure invoked from its parent, with all invocations of the same
+/// procedure from the same parent aggregated into a single node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProfileReport {
+    root: ProfileNode,
+}
+
+impl ProfileReport {
+    /// Returns the node of the call tree corresponding to the entrypoint of the program.
+    pub fn root(&self) -> &ProfileNode {
+        &self.root
+    }
+
+    /// Returns the total number of cycles spent executing the program.
+    pub fn total_cycles(&self) -> u64 {
+        self.root.total_cycles
+    }
+
+    /// Writes this report in the folded stack format consumed by `inferno` and `flamegraph.pl`.
+    ///
+    /// Each line consists of the semicolon-separated names of the procedures on a call stack,
+    /// starting from the entrypoint, followed by the number of cycles spent in the innermost
+    /// procedure of that stack, excluding its callees. Call stacks in which no cycles were spent
+    /// are omitted.
+    pub fn write_folded<W: fmt::Write>(&self, writer: &mut W) -> fmt::Result {
+        let mut stack = String::new();
+        self.root.write_folded(&mut stack, writer)
+    }
+
+    /// Returns this report in the folded stack format, see [ProfileReport::write_folded].
+    pub fn to_folded(&self) -> String {
+        let mut folded = String::new();
+        self.write_folded(&mut folded).expect("writing to a string is infallible");
+        folded
+    }
+}
+
+/// A node of the call tree of a [ProfileReport].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProfileNode {
+    name: String,
+    digest: RpoDigest,
+    num_calls: u32,
+    total_cycles: u64,
+    children: Vec<ProfileNode>,
+}
+
+impl ProfileNode {
+    /// Returns the name of the procedure.
+    ///
+    /// The name is taken from the AsmOp decorators of the procedure, which are only present when
+    /// the program was assembled in debug mode. Otherwise, this is the hex-encoded MAST root of the
+    /// procedure.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the MAST root of the procedure.
+    pub fn digest(&self) -> RpoDigest {
+        self.digest
+    }
+
+    /// Returns the number of times the procedure was invoked from its parent.
+    pub fn num_calls(&self) -> u32 {
+        self.num_calls
+    }
+
+    /// Returns the number of cycles spent in the procedure, including its callees.
+    pub fn total_cycles(&self) -> u64 {
+        self.total_cycles
+    }
+
+    /// Returns the number of cycles spent in the procedure, excluding its callees.
+    pub fn self_cycles(&self) -> u64 {
+        self.total_cycles - self.children.iter().map(|child| child.total_cycles).sum::<u64>()
+    }
+
+    /// Returns the procedures invoked from this procedure, in the order of their first invocation.
+    pub fn children(&self) -> &[ProfileNode] {
+        &self.children
+    }
+
+    /// Writes the folded stacks of this node and its callees, where `stack` holds the folded
+    /// stack of the caller.
+    fn write_folded<W: fmt::Write>(&self, stack: &mut String, writer: &mut W) -> fmt::Result {
+        let caller_len = stack.len();
+        if caller_len > 0 {
+            stack.push(';');
+        }
+        // semicolons delimit the frames of a stack, so they can't appear in the name of a frame
+        stack.extend(self.name.chars().map(|c| if c == ';' { ':' } else { c }));
+
+        let self_cycles = self.self_cycles();
+        if self_cycles > 0 {
+            writeln!(writer, "{stack} {self_cycles}")?;
+        }
+        for child in self.children.iter() {
+            child.write_folded(stack, writer)?;
+        }
+
+        stack.truncate(caller_len);
+        Ok(())
+    }
+}
+
+// PROFILER
+// ================================================================================================
+
+/// Records the number of cycles spent in each procedure during execution.
+///
+/// A frame is entered whenever the processor starts executing a procedure root of a MAST forest,
+/// and exited once the procedure finishes executing.
+#[derive(Debug, Default)]
+pub struct Profiler {
+    /// The nodes of the call tree, where the first node (if any) is the root.
+    nodes: Vec<Frame>,
+    /// The indices of the nodes of the frames currently being executed, with the clock cycle at
+    /// which each frame was entered.
+    stack: Vec<(usize, u32)>,
+}
+
+#[derive(Debug)]
+struct Frame {
+    name: Option<String>,
+    digest: RpoDigest,
+    num_calls: u32,
+    total_cycles: u64,
+    children: Vec<usize>,
+}
+
+impl Profiler {
+    /// Enters a frame for the procedure with the specified MAST root at cycle `clk`.
+    pub fn enter(&mut self, digest: RpoDigest, clk: u32) {
+        let existing = match self.stack.last() {
+            Some(&(parent, _)) => self.nodes[parent]
+                .children
+                .iter()
+                .copied()
+                .find(|&child| self.nodes[child].digest == digest),
+            None => (!self.nodes.is_empty()).then_some(0),
+        };
+        let index = existing.unwrap_or_else(|| {
+            let index = self.nodes.len();
+            self.nodes.push(Frame {
+                name: None,
+                digest,
+                num_calls: 0,
+                total_cycles: 0,
+                children: Vec::new(),
+            });
+            if let Some(&(parent, _)) = self.stack.last() {
+                self.nodes[parent].children.push(index);
+            }
+            index
+        });
+        self.nodes[index].num_calls += 1;
+        self.stack.push((index, clk));
+    }
+
+    /// Exits the current frame at cycle `clk`.
+    pub fn exit(&mut self, clk: u32) {
+        let (index, start) = self.stack.pop().expect("no frame to exit");
+        self.nodes[index].total_cycles += u64::from(clk - start);
+    }
+
+    /// Records the procedure name of an AsmOp decorator executed in the current frame.
+    ///
+    /// The first name recorded in a frame is used as the name of its procedure.
+    pub fn record_asmop(&mut self, context_name: &str) {
+        if let Some(&(index, _)) = self.stack.last() {
+            let frame = &mut self.nodes[index];
+            if frame.name.is_none() {
+                frame.name = Some(context_name.into());
+            }
+        }
+    }
+
+    /// Returns the report of the profiled execution, or `None` if no procedure was executed.
+    ///
+    /// Frames which have not been exited, e.g. due to an execution error, are closed at `clk`.
+    pub fn into_report(mut self, clk: u32) -> Option<ProfileReport> {
+        while !self.stack.is_empty() {
+            self.exit(clk);
+        }
+        if self.nodes.is_empty() {
+            return None;
+        }
+        let root = self.build_node(0);
+        Some(ProfileReport { root })
+    }
+
+    fn build_node(&self, index: usize) -> ProfileNode {
+        let frame = &self.nodes[index];
+        ProfileNode {
+            name: frame.name.clone().unwrap_or_else(|| frame.digest.to_string()),
+            digest: frame.digest,
+            num_calls: frame.num_calls,
+            total_cycles: frame.total_cycles,
+            children: frame.children.iter().map(|&child| self.build_node(child)).collect(),
+        }
+    }
+}
@@ -0,0 +1,409 @@
+use alloc::{collections::BTreeSet, sync::Arc, vec::Vec};
+use core::cell::Cell;
+#[cfg(feature = "std")]
+use std::{
+    sync::mpsc::{self, RecvTimeoutError},
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use miden_air::trace::MIN_TRACE_LEN;
+use vm_core::{
+    DebugOptions, ExecutionEnvironment,
+    crypto::{hash::RpoDigest, merkle::MerklePath},
+    mast::MastForest,
+};
+
+use crate::{
+    AdviceProvider, AdviceSource, CancellationToken, ExecutionError, ExecutionOptions,
+    ExecutionOptionsError, ExecutionTrace, Felt, Host, ProcessState, Program, StackInputs, Word,
+};
+
+// SANDBOX POLICY
+// ================================================================================================
+
+/// A set of resource limits applied to an execution of an untrusted program.
+///
+/// A policy bounds:
+/// - The number of cycles executed, the number of rows of the memory chiplet trace and the nesting
+///   depth of the executed MAST nodes (see [ExecutionOptions]).
+/// - The number of field elements which the program reads from the advice provider.
+/// - The events which the program may emit. Events other than system events are rejected unless
+///   they are explicitly allowed.
+/// - The wall-clock time of the execution (requires the `std` feature).
+///
+/// Programs are executed under a policy via [SandboxPolicy::execute]. An execution which exceeds
+/// any of the limits fails with a corresponding [ExecutionError].
+#[derive(Debug, Clone)]
+pub struct SandboxPolicy {
+    options: ExecutionOptions,
+    max_advice_elements: u64,
+    allowed_events: BTreeSet<u32>,
+    #[cfg(feature = "std")]
+    timeout: Option<Duration>,
+}
+
+impl SandboxPolicy {
+    /// Returns a new policy limiting executions to the specified number of cycles.
+    ///
+    /// All other resources are unlimited, and no events (other than system events) are allowed.
+    ///
+    /// # Errors
+    /// Returns an error if the maximum number of cycles is smaller than the minimum trace length.
+    pub fn new(max_cycles: u32) -> Result<Self, ExecutionOptionsError> {
+        let options = ExecutionOptions::new(Some(max_cycles), MIN_TRACE_LEN as u32, false, false)?;
+        Ok(Self {
+            options,
+            max_advice_elements: u64::MAX,
+            allowed_events: BTreeSet::new(),
+            #[cfg(feature = "std")]
+            timeout: None,
+        })
+    }
+
+    /// Sets the maximum number of rows of the memory chiplet trace.
+    pub fn with_max_memory_rows(mut self, max_memory_rows: u32) -> Self {
+        self.options = self.options.with_max_memory_rows(max_memory_rows);
+        self
+    }
+
+    /// Sets the maximum nesting depth of the MAST nodes executed by a program.
+    pub fn with_max_nesting_depth(mut self, max_nesting_depth: u32) -> Self {
+        self.options = self.options.with_max_nesting_depth(max_nesting_depth);
+        self
+    }
+
+    /// Sets the maximum number of field elements which a program may read from the advice
+    /// provider.
+    ///
+    /// See [SandboxAdviceProvider] for how the elements read from the advice provider are counted.
+    pub fn with_max_advice_elements(mut self, max_advice_elements: u64) -> Self {
+        self.max_advice_elements = max_advice_elements;
+        self
+    }
+
+    /// Allows programs to emit the specified events, in addition to the events allowed already.
+    pub fn with_allowed_events(mut self, event_ids: impl IntoIterator<Item = u32>) -> Self {
+        self.allowed_events.extend(event_ids);
+        self
+    }
+
+    /// Sets the maximum wall-clock time of an execution.
+    #[cfg(feature = "std")]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the options with which programs are executed under this policy.
+    pub fn execution_options(&self) -> &ExecutionOptions {
+        &self.options
+    }
+
+    /// Returns the maximum number of field elements which a program may read from the advice
+    /// provider.
+    pub fn max_advice_elements(&self) -> u64 {
+        self.max_advice_elements
+    }
+
+    /// Returns true if programs may emit the event with the specified ID.
+    pub fn is_event_allowed(&self, event_id: u32) -> bool {
+        self.allowed_events.contains(&event_id)
+    }
+
+    /// Returns the maximum wall-clock time of an execution, if any.
+    #[cfg(feature = "std")]
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    // EXECUTION
+    // --------------------------------------------------------------------------------------------
+
+    /// Executes the provided program against the provided inputs and host under this policy, and
+    /// returns the resulting execution trace.
+    ///
+    /// The advice quota is reset before the execution, so that each execution may read up to the
+    /// maximum number of elements from the advice provider of the host.
+    ///
+    /// # Errors
+    /// Returns an error if the execution fails, including if it exceeds any of the limits of this
+    /// policy.
+    pub fn execute<A, H>(
+        &self,
+        program: &Program,
+        stack_inputs: StackInputs,
+        host: &mut H,
+    ) -> Result<ExecutionTrace, ExecutionError>
+    where
+        A: AdviceProvider,
+        H: Host<AdviceProvider = SandboxAdviceProvider<A>>,
+    {
+        host.advice_provider_mut().reset(self.max_advice_elements);
+
+        let cancel_token = CancellationToken::new();
+        let options = self.options.clone().with_cancel_token(cancel_token.clone());
+        let mut host = SandboxHost {
+            host,
+            allowed_events: &self.allowed_events,
+        };
+
+        #[cfg(feature = "std")]
+        let timer = self.timeout.map(|timeout| (timeout, Timer::start(timeout, cancel_token)));
+
+        let result = crate::execute(program, stack_inputs, &mut host, options);
+
+        #[cfg(feature = "std")]
+        if let Some((timeout, timer)) = timer {
+            let timed_out = timer.stop();
+            if timed_out && matches!(result, Err(ExecutionError::Cancelled)) {
+                return Err(ExecutionError::TimeLimitExceeded(timeout));
+            }
+        }
+
+        result
+    }
+}
+
+// SANDBOX ADVICE PROVIDER
+// ================================================================================================
+
+/// An [AdviceProvider] which limits the number of field elements read from the wrapped provider.
+///
+/// Elements are counted as they are transferred to the VM: each element popped from the advice
+/// stack counts as one element, each Merkle tree node as four elements, each Merkle path as four
+/// elements per node, and each leaf depth as one element. Values of the advice map are counted
+/// when they are popped from the advice stack.
+///
+/// Once the limit is exceeded, all reads fail with [ExecutionError::AdviceLimitExceeded]. The
+/// limit is set by [SandboxPolicy::execute].
+#[derive(Debug)]
+pub struct SandboxAdviceProvider<A> {
+    provider: A,
+    max_elements: u64,
+    num_elements: Cell<u64>,
+}
+
+impl<A: AdviceProvider> SandboxAdviceProvider<A> {
+    /// Returns a new advice provider wrapping the provided provider, without any limit on the
+    /// number of elements read from it.
+    pub fn new(provider: A) -> Self {
+        Self {
+            provider,
+            max_elements: u64::MAX,
+            num_elements: Cell::new(0),
+        }
+    }
+
+    /// Returns the number of field elements read since the start of the last execution.
+    pub fn num_elements_read(&self) -> u64 {
+        self.num_elements.get()
+    }
+
+    /// Returns the wrapped provider.
+    pub fn inner(&self) -> &A {
+        &self.provider
+    }
+
+    /// Consumes this provider and returns the wrapped provider.
+    pub fn into_inner(self) -> A {
+        self.provider
+    }
+
+    // HELPERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Sets the maximum number of elements which can be read, and resets the number of elements
+    /// read.
+    fn reset(&mut self, max_elements: u64) {
+        self.max_elements = max_elements;
+        self.num_elements.set(0);
+    }
+
+    /// Records the read of the specified number of elements, and returns an error if the limit
+    /// is exceeded.
+    fn consume(&self, num_elements: u64) -> Result<(), ExecutionError> {
+        let num_elements = self.num_elements.get().saturating_add(num_elements);
+        self.num_elements.set(num_elements);
+        if num_elements > self.max_elements {
+            return Err(ExecutionError::AdviceLimitExceeded(self.max_elements));
+        }
+        Ok(())
+    }
+}
+
+impl<A: AdviceProvider> AdviceProvider for SandboxAdviceProvider<A> {
+    fn pop_stack(&mut self, process: ProcessState) -> Result<Felt, ExecutionError> {
+        let element = self.provider.pop_stack(process)?;
+        self.consume(1)?;
+        Ok(element)
+    }
+
+    fn pop_stack_word(&mut self, process: ProcessState) -> Result<Word, ExecutionError> {
+        let word = self.provider.pop_stack_word(process)?;
+        self.consume(4)?;
+        Ok(word)
+    }
+
+    fn pop_stack_dword(&mut self, process: ProcessState) -> Result<[Word; 2], ExecutionError> {
+        let words = self.provider.pop_stack_dword(process)?;
+        self.consume(8)?;
+        Ok(words)
+    }
+
+    fn push_stack(&mut self, source: AdviceSource) -> Result<(), ExecutionError> {
+        self.provider.push_stack(source)
+    }
+
+    fn get_mapped_values(&self, key: &RpoDigest) -> Option<&[Felt]> {
+        self.provider.get_mapped_values(key)
+    }
+
+    fn insert_into_map(&mut self, key: Word, values: Vec<Felt>) {
+        self.provider.insert_into_map(key, values)
+    }
+
+    fn get_tree_node(
+        &self,
+        root: Word,
+        depth: &Felt,
+        index: &Felt,
+    ) -> Result<Word, ExecutionError> {
+        let node = self.provider.get_tree_node(root, depth, index)?;
+        self.consume(4)?;
+        Ok(node)
+    }
+
+    fn get_merkle_path(
+        &self,
+        root: Word,
+        depth: &Felt,
+        index: &Felt,
+    ) -> Result<MerklePath, ExecutionError> {
+        let path = self.provider.get_merkle_path(root, depth, index)?;
+        self.consume(4 * path.len() as u64)?;
+        Ok(path)
+    }
+
+    fn get_leaf_depth(
+        &self,
+        root: Word,
+        tree_depth: &Felt,
+        index: &Felt,
+    ) -> Result<u8, ExecutionError> {
+        let depth = self.provider.get_leaf_depth(root, tree_depth, index)?;
+        self.consume(1)?;
+        Ok(depth)
+    }
+
+    fn update_merkle_node(
+        &mut self,
+        root: Word,
+        depth: &Felt,
+        index: &Felt,
+        value: Word,
+    ) -> Result<(MerklePath, Word), ExecutionError> {
+        let (path, new_root) = self.provider.update_merkle_node(root, depth, index, value)?;
+        self.consume(4 * path.len() as u64)?;
+        Ok((path, new_root))
+    }
+
+    fn merge_roots(&mut self, lhs: Word, rhs: Word) -> Result<Word, ExecutionError> {
+        self.provider.merge_roots(lhs, rhs)
+    }
+
+    fn resolve_mapped_values(&mut self, key: &RpoDigest) -> Result<(), ExecutionError> {
+        self.provider.resolve_mapped_values(key)
+    }
+}
+
+// SANDBOX HOST
+// ================================================================================================
+
+/// A host which rejects the events not allowed by a [SandboxPolicy], and delegates everything
+/// else to the wrapped host.
+struct SandboxHost<'a, H> {
+    host: &'a mut H,
+    allowed_events: &'a BTreeSet<u32>,
+}
+
+impl<H: Host> Host for SandboxHost<'_, H> {
+    type AdviceProvider = H::AdviceProvider;
+
+    fn advice_provider(&self) -> &Self::AdviceProvider {
+        self.host.advice_provider()
+    }
+
+    fn advice_provider_mut(&mut self) -> &mut Self::AdviceProvider {
+        self.host.advice_provider_mut()
+    }
+
+    fn get_mast_forest(&self, node_digest: &RpoDigest) -> Option<Arc<MastForest>> {
+        self.host.get_mast_forest(node_digest)
+    }
+
+    fn environment(&self) -> ExecutionEnvironment {
+        self.host.environment()
+    }
+
+    fn on_event(&mut self, process: ProcessState, event_id: u32) -> Result<(), ExecutionError> {
+        if !self.allowed_events.contains(&event_id) {
+            return Err(ExecutionError::EventNotAllowed(event_id));
+        }
+        self.host.on_event(process, event_id)
+    }
+
+    fn on_debug(
+        &mut self,
+        process: ProcessState,
+        options: &DebugOptions,
+    ) -> Result<(), ExecutionError> {
+        self.host.on_debug(process, options)
+    }
+
+    fn on_trace(&mut self, process: ProcessState, trace_id: u32) -> Result<(), ExecutionError> {
+        self.host.on_trace(process, trace_id)
+    }
+
+    fn on_assert_failed(&mut self, process: ProcessState, err_code: u32) -> ExecutionError {
+        self.host.on_assert_failed(process, err_code)
+    }
+}
+
+// TIMER
+// ================================================================================================
+
+/// A background thread which cancels an execution once its timeout elapses, unless it is stopped
+/// first.
+#[cfg(feature = "std")]
+struct Timer {
+    sender: mpsc::Sender<()>,
+    handle: JoinHandle<bool>,
+}
+
+#[cfg(feature = "std")]
+impl Timer {
+    /// Starts a timer which cancels the provided token once the specified timeout elapses.
+    fn start(timeout: Duration, cancel_token: CancellationToken) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let handle = thread::spawn(move || {
+            let timed_out =
+                matches!(receiver.recv_timeout(timeout), Err(RecvTimeoutError::Timeout));
+            if timed_out {
+                cancel_token.cancel();
+            }
+            timed_out
+        });
+        Self { sender, handle }
+    }
+
+    /// Stops the timer, and returns true if the timeout elapsed before the timer was stopped.
+    fn stop(self) -> bool {
+        // dropping the sender wakes up the timer thread
+        drop(self.sender);
+        self.handle.join().unwrap_or(false)
+    }
+}
@@ -0,0 +1,96 @@
+//! AVX2 implementation of the SIMD kernels, operating on vectors of 4 field elements.
+
+#![allow(unsafe_op_in_unsafe_fn)]
+
+use core::arch::x86_64::*;
+
+const LANES: usize = 4;
+
+type Packed = __m256i;
+
+/// Flips the sign bit of each lane, mapping unsigned comparisons to signed ones.
+const SIGN_BIT: i64 = i64::MIN;
+
+// PRIMITIVES
+// ================================================================================================
+
+#[inline]
+#[target_feature(enable = "avx2")]
+unsafe fn splat(value: u64) -> Packed {
+    _mm256_set1_epi64x(value as i64)
+}
+
+#[inline]
+#[target_feature(enable = "avx2")]
+unsafe fn load(src: &[u64]) -> Packed {
+    assert!(src.len() >= LANES);
+    _mm256_loadu_si256(src.as_ptr() as *const __m256i)
+}
+
+#[inline]
+#[target_feature(enable = "avx2")]
+unsafe fn store(value: Packed, dst: &mut [u64]) {
+    assert!(dst.len() >= LANES);
+    _mm256_storeu_si256(dst.as_mut_ptr() as *mut __m256i, value)
+}
+
+#[inline]
+#[target_feature(enable = "avx2")]
+unsafe fn add64(a: Packed, b: Packed) -> Packed {
+    _mm256_add_epi64(a, b)
+}
+
+#[inline]
+#[target_feature(enable = "avx2")]
+unsafe fn sub64(a: Packed, b: Packed) -> Packed {
+    _mm256_sub_epi64(a, b)
+}
+
+#[inline]
+#[target_feature(enable = "avx2")]
+unsafe fn and(a: Packed, b: Packed) -> Packed {
+    _mm256_and_si256(a, b)
+}
+
+#[inline]
+#[target_feature(enable = "avx2")]
+unsafe fn mul32(a: Packed, b: Packed) -> Packed {
+    _mm256_mul_epu32(a, b)
+}
+
+#[inline]
+#[target_feature(enable = "avx2")]
+unsafe fn shl32(a: Packed) -> Packed {
+    _mm256_slli_epi64::<32>(a)
+}
+
+#[inline]
+#[target_feature(enable = "avx2")]
+unsafe fn shr32(a: Packed) -> Packed {
+    _mm256_srli_epi64::<32>(a)
+}
+
+#[inline]
+#[target_feature(enable = "avx2")]
+unsafe fn lt(a: Packed, b: Packed) -> Packed {
+    // AVX2 only has signed 64-bit comparisons
+    let sign = _mm256_set1_epi64x(SIGN_BIT);
+    _mm256_cmpgt_epi64(_mm256_xor_si256(b, sign), _mm256_xor_si256(a, sign))
+}
+
+#[inline]
+#[target_feature(enable = "avx2")]
+unsafe fn eq(a: Packed, b: Packed) -> Packed {
+    _mm256_cmpeq_epi64(a, b)
+}
+
+#[inline]
+#[target_feature(enable = "avx2")]
+unsafe fn select(mask: Packed, a: Packed, b: Packed) -> Packed {
+    _mm256_blendv_epi8(b, a, mask)
+}
+
+// KERNELS
+// ================================================================================================
+
+field_kernels!("avx2");
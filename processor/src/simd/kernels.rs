@@ -0,0 +1,288 @@
+/// Implements the SIMD kernels on top of a set of packed 64-bit integer primitives.
+///
+/// The module invoking this macro must define a `Packed` vector type holding `LANES` 64-bit
+/// integers, and the following functions enabling the specified target feature:
+/// - `splat(u64)`, `load(&[u64])` and `store(Packed, &mut [u64])`, where `load` and `store` read
+///   and write the first `LANES` elements of the slice.
+/// - `add64`, `sub64`, `and`, `mul32`, `shl32` and `shr32`, where `add64` and `sub64` wrap around
+///   and `mul32` multiplies the low 32 bits of each lane into a 64-bit product.
+/// - `lt` and `eq`, which compare unsigned lanes into masks with all bits set for true lanes, and
+///   `select(mask, a, b)`, which selects the lanes of `a` for true lanes and of `b` otherwise.
+///
+/// Field elements are kept in Montgomery form, and all field operations follow the scalar
+/// implementations from `winter-math`, so that the kernels produce identical results.
+macro_rules! field_kernels {
+    ($feature:literal) => {
+        use alloc::vec::Vec;
+
+        use vm_core::{Felt, FieldElement, chiplets::hasher::STATE_WIDTH, crypto::hash::Rpo256};
+
+        use super::{NUM_ROUNDS, mds_columns, round_constants};
+
+        const NUM_VECTORS: usize = STATE_WIDTH / LANES;
+
+        /// The field modulus, 2^64 - 2^32 + 1.
+        const MODULUS: u64 = 0xffff_ffff_0000_0001;
+
+        /// Montgomery form of ONE.
+        const ONE: u64 = Felt::ONE.inner();
+
+        static MDS_COLUMNS: [[[u64; LANES]; STATE_WIDTH]; NUM_VECTORS] = mds_columns();
+        static ARK1: [[[u64; LANES]; NUM_VECTORS]; NUM_ROUNDS] = round_constants(&Rpo256::ARK1);
+        static ARK2: [[[u64; LANES]; NUM_VECTORS]; NUM_ROUNDS] = round_constants(&Rpo256::ARK2);
+
+        /// RPO state split into vectors of consecutive elements.
+        type State = [Packed; NUM_VECTORS];
+
+        // KERNELS
+        // ----------------------------------------------------------------------------------------
+
+        /// Applies a single round of the RPO permutation to the provided state.
+        ///
+        /// # Safety
+        /// The target feature must be supported by the current CPU.
+        #[target_feature(enable = $feature)]
+        pub unsafe fn apply_round(state: &mut [Felt; STATE_WIDTH], round: usize) {
+            let values = state.map(|value| value.inner());
+            let mut vectors = [splat(0); NUM_VECTORS];
+            for (i, vector) in vectors.iter_mut().enumerate() {
+                *vector = load(&values[i * LANES..]);
+            }
+
+            // first half of the round: MDS, constants, and S-box
+            vectors = apply_mds(&vectors);
+            add_constants(&mut vectors, &ARK1[round]);
+            vectors = apply_sbox(&vectors);
+
+            // second half of the round: MDS, constants, and inverse S-box
+            vectors = apply_mds(&vectors);
+            add_constants(&mut vectors, &ARK2[round]);
+            vectors = apply_inv_sbox(&vectors);
+
+            let mut values = [0; STATE_WIDTH];
+            for (i, vector) in vectors.iter().enumerate() {
+                store(*vector, &mut values[i * LANES..]);
+            }
+            *state = values.map(Felt::from_mont);
+        }
+
+        /// Writes the inverses of the provided values into `result`, mapping ZERO to ZERO.
+        ///
+        /// The values are split into `LANES` interleaved sequences, each inverted using
+        /// Montgomery batch inversion; a trailing partial vector is padded with ONE.
+        ///
+        /// # Safety
+        /// The target feature must be supported by the current CPU.
+        #[target_feature(enable = $feature)]
+        pub unsafe fn batch_inversion(values: &[Felt], result: &mut [Felt]) {
+            debug_assert_eq!(values.len(), result.len());
+            let num_vectors = values.len().div_ceil(LANES);
+            let zero = splat(0);
+            let one = splat(ONE);
+
+            // compute products of all vectors preceding each vector, treating ZERO as ONE
+            let mut prefixes = Vec::with_capacity(num_vectors);
+            let mut acc = one;
+            for i in 0..num_vectors {
+                prefixes.push(acc);
+                let vector = load_felts(values, i * LANES);
+                acc = mul(acc, select(eq(vector, zero), one, vector));
+            }
+
+            // invert the product of each sequence; since ZEROs are skipped, these are never ZERO
+            let mut totals = [0; LANES];
+            store(acc, &mut totals);
+            let totals = totals.map(|total| Felt::from_mont(total).inv().inner());
+            let mut acc_inv = load(&totals);
+
+            // walk back through the vectors, peeling off one vector from the inverse at a time
+            for (i, &prefix) in prefixes.iter().enumerate().rev() {
+                let vector = load_felts(values, i * LANES);
+                let is_zero = eq(vector, zero);
+                let inverse = mul(acc_inv, prefix);
+                acc_inv = mul(acc_inv, select(is_zero, one, vector));
+                store_felts(select(is_zero, zero, inverse), &mut result[i * LANES..]);
+            }
+        }
+
+        // RPO ROUND COMPONENTS
+        // ----------------------------------------------------------------------------------------
+
+        /// Multiplies the state by the MDS matrix.
+        ///
+        /// Since the entries of the matrix are small integers, the dot products are accumulated
+        /// as exact integers split into low and high 32-bit halves of the state elements, and
+        /// reduced only once per output element.
+        #[inline]
+        #[target_feature(enable = $feature)]
+        unsafe fn apply_mds(state: &State) -> State {
+            let mut values = [0; STATE_WIDTH];
+            for (i, vector) in state.iter().enumerate() {
+                store(*vector, &mut values[i * LANES..]);
+            }
+
+            let mut result = [splat(0); NUM_VECTORS];
+            for (vector, columns) in result.iter_mut().zip(MDS_COLUMNS.iter()) {
+                let mut lo = splat(0);
+                let mut hi = splat(0);
+                for (column, &value) in columns.iter().zip(values.iter()) {
+                    let column = load(column);
+                    lo = add64(lo, mul32(column, splat(value)));
+                    hi = add64(hi, mul32(column, splat(value >> 32)));
+                }
+                *vector = reduce_wide(hi, lo);
+            }
+            result
+        }
+
+        #[inline]
+        #[target_feature(enable = $feature)]
+        unsafe fn add_constants(state: &mut State, ark: &[[u64; LANES]; NUM_VECTORS]) {
+            for (vector, constants) in state.iter_mut().zip(ark.iter()) {
+                *vector = add(*vector, load(constants));
+            }
+        }
+
+        /// Computes x^7 for each element of the state.
+        #[inline]
+        #[target_feature(enable = $feature)]
+        unsafe fn apply_sbox(state: &State) -> State {
+            let x2 = mul_state(state, state);
+            let x4 = mul_state(&x2, &x2);
+            let x3 = mul_state(&x2, state);
+            mul_state(&x3, &x4)
+        }
+
+        /// Computes x^10540996611094048183 for each element of the state, using the same addition
+        /// chain as the scalar implementation.
+        #[inline]
+        #[target_feature(enable = $feature)]
+        unsafe fn apply_inv_sbox(state: &State) -> State {
+            let t1 = mul_state(state, state);
+            let t2 = mul_state(&t1, &t1);
+            let t3 = exp_acc::<3>(&t2, &t2);
+            let t4 = exp_acc::<6>(&t3, &t3);
+            let t5 = exp_acc::<12>(&t4, &t4);
+            let t6 = exp_acc::<6>(&t5, &t3);
+            let t7 = exp_acc::<31>(&t6, &t6);
+
+            let a = mul_state(&t7, &t7);
+            let a = mul_state(&a, &t6);
+            let a = mul_state(&a, &a);
+            let a = mul_state(&a, &a);
+            let b = mul_state(&t1, &t2);
+            let b = mul_state(&b, state);
+            mul_state(&a, &b)
+        }
+
+        /// Squares `base` M times and multiplies the result by `tail`.
+        #[inline]
+        #[target_feature(enable = $feature)]
+        unsafe fn exp_acc<const M: usize>(base: &State, tail: &State) -> State {
+            let mut result = *base;
+            for _ in 0..M {
+                result = mul_state(&result, &result);
+            }
+            mul_state(&result, tail)
+        }
+
+        #[inline]
+        #[target_feature(enable = $feature)]
+        unsafe fn mul_state(a: &State, b: &State) -> State {
+            let mut result = [splat(0); NUM_VECTORS];
+            for (i, vector) in result.iter_mut().enumerate() {
+                *vector = mul(a[i], b[i]);
+            }
+            result
+        }
+
+        // FIELD OPERATIONS
+        // ----------------------------------------------------------------------------------------
+
+        /// Adds two vectors of field elements.
+        #[inline]
+        #[target_feature(enable = $feature)]
+        unsafe fn add(a: Packed, b: Packed) -> Packed {
+            // a + b = a - (p - b), adding p back (i.e., subtracting 2^32 - 1) on underflow
+            let neg_b = sub64(splat(MODULUS), b);
+            let underflow = lt(a, neg_b);
+            sub64(sub64(a, neg_b), shr32(underflow))
+        }
+
+        /// Multiplies two vectors of field elements.
+        #[inline]
+        #[target_feature(enable = $feature)]
+        unsafe fn mul(a: Packed, b: Packed) -> Packed {
+            // compute the full 128-bit products from 32-bit partial products
+            let a_hi = shr32(a);
+            let b_hi = shr32(b);
+            let ll = mul32(a, b);
+            let lh = mul32(a, b_hi);
+            let hl = mul32(a_hi, b);
+            let hh = mul32(a_hi, b_hi);
+
+            let mid = add64(hl, shr32(ll));
+            let mid2 = add64(lh, and(mid, splat(u32::MAX as u64)));
+            let lo = add64(shl32(mid2), and(ll, splat(u32::MAX as u64)));
+            let hi = add64(add64(hh, shr32(mid)), shr32(mid2));
+
+            mont_reduce(hi, lo)
+        }
+
+        /// Performs Montgomery reduction of the 128-bit values `hi * 2^64 + lo`.
+        #[inline]
+        #[target_feature(enable = $feature)]
+        unsafe fn mont_reduce(hi: Packed, lo: Packed) -> Packed {
+            let a = add64(lo, shl32(lo));
+            let overflow = lt(a, lo);
+            // adding the all-ones mask subtracts 1 from overflowing lanes
+            let b = add64(sub64(a, shr32(a)), overflow);
+            let underflow = lt(hi, b);
+            sub64(sub64(hi, b), shr32(underflow))
+        }
+
+        /// Reduces the values `hi * 2^32 + lo` into canonical field elements, assuming both `hi`
+        /// and `lo` are smaller than 2^41.
+        #[inline]
+        #[target_feature(enable = $feature)]
+        unsafe fn reduce_wide(hi: Packed, lo: Packed) -> Packed {
+            // fold the bits of hi above 2^64 using 2^64 = 2^32 - 1 (mod p)
+            let low = add64(shl32(hi), lo);
+            let carry = lt(low, lo);
+            let high = add64(mul32(shr32(hi), splat(u32::MAX as u64)), shr32(carry));
+
+            let sum = add64(low, high);
+            let carry = lt(sum, high);
+            let sum = add64(sum, shr32(carry));
+
+            let in_range = lt(sum, splat(MODULUS));
+            select(in_range, sum, sub64(sum, splat(MODULUS)))
+        }
+
+        // HELPER FUNCTIONS
+        // ----------------------------------------------------------------------------------------
+
+        /// Loads `LANES` field elements starting at the specified offset, padding the vector with
+        /// ONE past the end of the slice.
+        #[inline]
+        #[target_feature(enable = $feature)]
+        unsafe fn load_felts(values: &[Felt], offset: usize) -> Packed {
+            let mut buf = [ONE; LANES];
+            for (dst, src) in buf.iter_mut().zip(values[offset..].iter()) {
+                *dst = src.inner();
+            }
+            load(&buf)
+        }
+
+        /// Stores up to `LANES` field elements at the start of the provided slice.
+        #[inline]
+        #[target_feature(enable = $feature)]
+        unsafe fn store_felts(vector: Packed, values: &mut [Felt]) {
+            let mut buf = [0; LANES];
+            store(vector, &mut buf);
+            for (dst, &src) in values.iter_mut().zip(buf.iter()) {
+                *dst = Felt::from_mont(src);
+            }
+        }
+    };
+}
@@ -0,0 +1,191 @@
+//! SIMD implementations of the field arithmetic which dominates execution time of cryptographic
+//! programs: the RPO permutation applied by the hasher chiplet, and batch inversions performed
+//! while building the execution trace.
+//!
+//! The implementation is selected at runtime based on the features of the CPU: AVX2 is used on
+//! x86_64 CPUs supporting it, NEON on aarch64 CPUs, and the scalar implementations otherwise. All
+//! implementations operate directly on the Montgomery representation of field elements, and thus
+//! produce results identical to the scalar ones.
+//!
+//! Horner evaluations performed by the `HORNERBASE` and `HORNEREXT` operations are left scalar:
+//! each evaluates a short chain of dependent extension field multiplications, which leaves no
+//! independent work to spread across vector lanes.
+
+use alloc::vec::Vec;
+
+use vm_core::{
+    Felt,
+    chiplets::hasher::{self, STATE_WIDTH},
+    crypto::hash::Rpo256,
+    utils::{math, uninit_vector},
+};
+use winter_utils::batch_iter_mut;
+#[cfg(feature = "concurrent")]
+use winter_utils::iterators::*;
+
+#[macro_use]
+mod kernels;
+
+#[cfg(target_arch = "x86_64")]
+mod avx2;
+
+#[cfg(target_arch = "aarch64")]
+mod neon;
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of rounds of the RPO permutation.
+const NUM_ROUNDS: usize = Rpo256::NUM_ROUNDS;
+
+/// The smallest number of elements inverted by a single thread in [batch_inversion].
+#[cfg(feature = "concurrent")]
+const MIN_BATCH_INVERSION_SIZE: usize = 1024;
+
+// BACKEND
+// ================================================================================================
+
+/// An instruction set used to perform field arithmetic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SimdBackend {
+    #[cfg(target_arch = "x86_64")]
+    Avx2,
+    #[cfg(target_arch = "aarch64")]
+    Neon,
+    Scalar,
+}
+
+impl SimdBackend {
+    /// Returns the most efficient backend supported by the current CPU.
+    ///
+    /// The features of the CPU are detected once and cached by the standard library, and thus
+    /// this is cheap enough to be invoked for every operation.
+    #[inline(always)]
+    pub fn detect() -> Self {
+        #[cfg(target_arch = "x86_64")]
+        if std::is_x86_feature_detected!("avx2") {
+            return Self::Avx2;
+        }
+
+        #[cfg(target_arch = "aarch64")]
+        if std::arch::is_aarch64_feature_detected!("neon") {
+            return Self::Neon;
+        }
+
+        Self::Scalar
+    }
+}
+
+// KERNELS
+// ================================================================================================
+
+/// Applies a single round of the RPO permutation to the provided state.
+#[inline(always)]
+pub fn apply_round(state: &mut [Felt; STATE_WIDTH], round: usize) {
+    apply_round_with(SimdBackend::detect(), state, round)
+}
+
+/// Returns a vector with the inverses of the provided values, computed using Montgomery batch
+/// inversion. Any ZERO in the provided values is mapped to ZERO.
+///
+/// When the `concurrent` feature is enabled, the values are split into batches inverted in
+/// separate threads.
+pub fn batch_inversion(values: &[Felt]) -> Vec<Felt> {
+    batch_inversion_with(SimdBackend::detect(), values)
+}
+
+/// Applies a single round of the RPO permutation to the provided state using the specified
+/// backend, which must be supported by the current CPU.
+#[inline(always)]
+fn apply_round_with(backend: SimdBackend, state: &mut [Felt; STATE_WIDTH], round: usize) {
+    match backend {
+        // SAFETY: the backend is supported by the current CPU
+        #[cfg(target_arch = "x86_64")]
+        SimdBackend::Avx2 => unsafe { avx2::apply_round(state, round) },
+        #[cfg(target_arch = "aarch64")]
+        SimdBackend::Neon => unsafe { neon::apply_round(state, round) },
+        _ => hasher::apply_round(state, round),
+    }
+}
+
+/// Returns a vector with the inverses of the provided values, computed using the specified
+/// backend, which must be supported by the current CPU.
+fn batch_inversion_with(backend: SimdBackend, values: &[Felt]) -> Vec<Felt> {
+    if backend == SimdBackend::Scalar {
+        return math::batch_inversion(values);
+    }
+
+    // SAFETY: every element of the vector is initialized below
+    let mut result: Vec<Felt> = unsafe { uninit_vector(values.len()) };
+    batch_iter_mut!(
+        &mut result,
+        MIN_BATCH_INVERSION_SIZE,
+        |batch: &mut [Felt], batch_offset: usize| {
+            let values = &values[batch_offset..batch_offset + batch.len()];
+            match backend {
+                // SAFETY: the backend is supported by the current CPU
+                #[cfg(target_arch = "x86_64")]
+                SimdBackend::Avx2 => unsafe { avx2::batch_inversion(values, batch) },
+                #[cfg(target_arch = "aarch64")]
+                SimdBackend::Neon => unsafe { neon::batch_inversion(values, batch) },
+                _ => batch.copy_from_slice(&math::batch_inversion(values)),
+            }
+        }
+    );
+    result
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the columns of the RPO MDS matrix as canonical integers, split into vectors of `LANES`
+/// consecutive rows.
+///
+/// All entries of the matrix are small integers, which allows multiplying them with field
+/// elements in Montgomery form without Montgomery reduction.
+const fn mds_columns<const LANES: usize, const NUM_VECTORS: usize>()
+-> [[[u64; LANES]; STATE_WIDTH]; NUM_VECTORS] {
+    let mut result = [[[0; LANES]; STATE_WIDTH]; NUM_VECTORS];
+    let mut vector = 0;
+    while vector < NUM_VECTORS {
+        let mut col = 0;
+        while col < STATE_WIDTH {
+            let mut lane = 0;
+            while lane < LANES {
+                let value = Rpo256::MDS[vector * LANES + lane][col].as_int();
+                // keeps the sum of products of a row with the state below 2^73
+                assert!(value < 1 << 8, "MDS matrix entry is too large");
+                result[vector][col][lane] = value;
+                lane += 1;
+            }
+            col += 1;
+        }
+        vector += 1;
+    }
+    result
+}
+
+/// Returns the provided round constants in Montgomery form, split into vectors of `LANES`
+/// consecutive state elements.
+const fn round_constants<const LANES: usize, const NUM_VECTORS: usize>(
+    ark: &[[Felt; STATE_WIDTH]; NUM_ROUNDS],
+) -> [[[u64; LANES]; NUM_VECTORS]; NUM_ROUNDS] {
+    let mut result = [[[0; LANES]; NUM_VECTORS]; NUM_ROUNDS];
+    let mut round = 0;
+    while round < NUM_ROUNDS {
+        let mut vector = 0;
+        while vector < NUM_VECTORS {
+            let mut lane = 0;
+            while lane < LANES {
+                result[round][vector][lane] = ark[round][vector * LANES + lane].inner();
+                lane += 1;
+            }
+            vector += 1;
+        }
+        round += 1;
+    }
+    result
+}
@@ -0,0 +1,91 @@
+//! NEON implementation of the SIMD kernels, operating on vectors of 2 field elements.
+
+#![allow(unsafe_op_in_unsafe_fn)]
+
+use core::arch::aarch64::*;
+
+const LANES: usize = 2;
+
+type Packed = uint64x2_t;
+
+// PRIMITIVES
+// ================================================================================================
+
+#[inline]
+#[target_feature(enable = "neon")]
+unsafe fn splat(value: u64) -> Packed {
+    vdupq_n_u64(value)
+}
+
+#[inline]
+#[target_feature(enable = "neon")]
+unsafe fn load(src: &[u64]) -> Packed {
+    assert!(src.len() >= LANES);
+    vld1q_u64(src.as_ptr())
+}
+
+#[inline]
+#[target_feature(enable = "neon")]
+unsafe fn store(value: Packed, dst: &mut [u64]) {
+    assert!(dst.len() >= LANES);
+    vst1q_u64(dst.as_mut_ptr(), value)
+}
+
+#[inline]
+#[target_feature(enable = "neon")]
+unsafe fn add64(a: Packed, b: Packed) -> Packed {
+    vaddq_u64(a, b)
+}
+
+#[inline]
+#[target_feature(enable = "neon")]
+unsafe fn sub64(a: Packed, b: Packed) -> Packed {
+    vsubq_u64(a, b)
+}
+
+#[inline]
+#[target_feature(enable = "neon")]
+unsafe fn and(a: Packed, b: Packed) -> Packed {
+    vandq_u64(a, b)
+}
+
+#[inline]
+#[target_feature(enable = "neon")]
+unsafe fn mul32(a: Packed, b: Packed) -> Packed {
+    vmull_u32(vmovn_u64(a), vmovn_u64(b))
+}
+
+#[inline]
+#[target_feature(enable = "neon")]
+unsafe fn shl32(a: Packed) -> Packed {
+    vshlq_n_u64::<32>(a)
+}
+
+#[inline]
+#[target_feature(enable = "neon")]
+unsafe fn shr32(a: Packed) -> Packed {
+    vshrq_n_u64::<32>(a)
+}
+
+#[inline]
+#[target_feature(enable = "neon")]
+unsafe fn lt(a: Packed, b: Packed) -> Packed {
+    vcltq_u64(a, b)
+}
+
+#[inline]
+#[target_feature(enable = "neon")]
+unsafe fn eq(a: Packed, b: Packed) -> Packed {
+    vceqq_u64(a, b)
+}
+
+#[inline]
+#[target_feature(enable = "neon")]
+unsafe fn select(mask: Packed, a: Packed, b: Packed) -> Packed {
+    vbslq_u64(mask, a, b)
+}
+
+// KERNELS
+// ================================================================================================
+
+field_kernels!("neon");
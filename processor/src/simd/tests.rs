@@ -0,0 +1,98 @@
+use alloc::vec::Vec;
+
+use test_utils::rand::{rand_array, rand_vector};
+use vm_core::{Felt, FieldElement, ONE, ZERO, chiplets::hasher, crypto::hash::Rpo256};
+
+use super::{NUM_ROUNDS, STATE_WIDTH, SimdBackend, apply_round_with, batch_inversion_with, math};
+
+// RPO ROUNDS
+// ================================================================================================
+
+#[test]
+fn apply_round_matches_scalar() {
+    let states = [
+        [ZERO; STATE_WIDTH],
+        [ONE; STATE_WIDTH],
+        [-ONE; STATE_WIDTH],
+        rand_array::<Felt, STATE_WIDTH>(),
+        rand_array::<Felt, STATE_WIDTH>(),
+    ];
+
+    for backend in backends() {
+        for init_state in states {
+            let mut expected = init_state;
+            let mut state = init_state;
+            for round in 0..NUM_ROUNDS {
+                hasher::apply_round(&mut expected, round);
+                apply_round_with(backend, &mut state, round);
+                assert_eq!(expected, state, "backend {backend:?}, round {round}");
+            }
+        }
+    }
+}
+
+#[test]
+fn permutation_matches_scalar() {
+    for backend in backends() {
+        let mut expected = rand_array::<Felt, STATE_WIDTH>();
+        let mut state = expected;
+
+        Rpo256::apply_permutation(&mut expected);
+        for round in 0..NUM_ROUNDS {
+            apply_round_with(backend, &mut state, round);
+        }
+        assert_eq!(expected, state, "backend {backend:?}");
+    }
+}
+
+// BATCH INVERSION
+// ================================================================================================
+
+#[test]
+fn batch_inversion_matches_scalar() {
+    for backend in backends() {
+        assert!(batch_inversion_with(backend, &[]).is_empty());
+
+        // lengths which are not multiples of the number of lanes exercise the padding
+        for len in [1, 2, 3, 5, 8, 17, 1029, 16387] {
+            let values = rand_vector::<Felt>(len);
+            let expected = math::batch_inversion(&values);
+            assert_eq!(expected, batch_inversion_with(backend, &values), "backend {backend:?}");
+        }
+    }
+}
+
+#[test]
+fn batch_inversion_with_zeros() {
+    let mut values = rand_vector::<Felt>(37);
+    for i in [0, 1, 4, 5, 6, 7, 20, 36] {
+        values[i] = ZERO;
+    }
+    values[10] = ONE;
+    values[11] = -ONE;
+
+    for backend in backends() {
+        let result = batch_inversion_with(backend, &values);
+        for (value, inverse) in values.iter().zip(result.iter()) {
+            assert_eq!(value.inv(), *inverse, "backend {backend:?}");
+        }
+    }
+
+    // a batch consisting only of zeros
+    for backend in backends() {
+        assert_eq!(vec![ZERO; 9], batch_inversion_with(backend, &[ZERO; 9]));
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns all backends supported by the current CPU.
+fn backends() -> Vec<SimdBackend> {
+    let mut backends = vec![SimdBackend::Scalar];
+    let detected = SimdBackend::detect();
+    if detected != SimdBackend::Scalar {
+        backends.push(detected);
+    }
+    backends
+}
@@ -85,6 +85,23 @@ impl Stack {
         }
     }
 
+    /// Returns a [Stack] initialized with the specified program inputs, which does not record the
+    /// execution trace of the stack.
+    ///
+    /// Only the state of the stack at the current clock cycle can be inspected on such a stack.
+    pub fn new_without_trace(inputs: &StackInputs) -> Self {
+        let overflow = OverflowTable::new(false);
+        let trace = StackTrace::new_without_trace(&**inputs, MIN_STACK_DEPTH, ZERO);
+
+        Self {
+            clk: RowIndex::from(0),
+            trace,
+            overflow,
+            active_depth: MIN_STACK_DEPTH,
+            full_depth: MIN_STACK_DEPTH,
+        }
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -93,6 +110,12 @@ impl Stack {
         self.active_depth
     }
 
+    /// Returns the largest number of items which were simultaneously stored in the overflow table
+    /// (across all execution contexts) during execution.
+    pub fn overflow_table_peak_len(&self) -> usize {
+        self.overflow.max_active_rows()
+    }
+
     /// Returns the current clock cycle of the execution trace.
     pub fn current_clk(&self) -> RowIndex {
         self.clk
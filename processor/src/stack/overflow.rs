@@ -29,6 +29,9 @@ pub struct OverflowTable {
     /// entering new execution context, this value is set to ZERO, and thus, will differ from the
     /// row address actually at the top of the table.
     last_row_addr: Felt,
+    /// The largest number of rows which were simultaneously present in the overflow table (across
+    /// all execution contexts) at any point during execution.
+    max_active_rows: usize,
 }
 
 impl OverflowTable {
@@ -42,6 +45,7 @@ impl OverflowTable {
             trace: BTreeMap::new(),
             trace_enabled: enable_trace,
             last_row_addr: ZERO,
+            max_active_rows: 0,
         }
     }
 
@@ -63,6 +67,7 @@ impl OverflowTable {
         let new_row = OverflowTableRow::new(clk, value, self.last_row_addr);
         self.all_rows.push(new_row);
         self.active_rows.push(row_idx as usize);
+        self.max_active_rows = self.max_active_rows.max(self.active_rows.len());
 
         // set the last row address to the address of the newly added row
         self.last_row_addr = clk;
@@ -154,6 +159,12 @@ impl OverflowTable {
         self.active_rows.len()
     }
 
+    /// Returns the largest number of overflowing stack elements (across all execution contexts)
+    /// at any clock cycle so far.
+    pub fn max_active_rows(&self) -> usize {
+        self.max_active_rows
+    }
+
     // HELPER METHODS
     // --------------------------------------------------------------------------------------------
 
@@ -391,6 +391,49 @@ fn generate_trace() {
     assert_eq!(read_helpers(&trace, 13), build_helpers(16, 0));
 }
 
+#[test]
+fn generate_trace_with_unchanged_helpers() {
+    let stack_inputs = StackInputs::try_from_ints([1, 2]).unwrap();
+    let mut stack = Stack::new(&stack_inputs, 4, false);
+
+    // clk = 0
+    stack.ensure_trace_capacity();
+    stack.copy_state(0);
+    stack.advance_clock();
+
+    // clk = 1
+    stack.ensure_trace_capacity();
+    stack.shift_right(0);
+    stack.advance_clock();
+
+    // clk = 2..=9; the trace grows while the helper columns are unchanged
+    for _ in 2..=9 {
+        stack.ensure_trace_capacity();
+        stack.copy_state(0);
+        stack.advance_clock();
+    }
+
+    // clk = 10
+    stack.ensure_trace_capacity();
+    stack.shift_left(1);
+    stack.advance_clock();
+
+    let trace = stack.into_trace(32, 1).trace;
+
+    for row in 0..=1 {
+        assert_eq!(read_stack_top(&trace, row), build_stack(&[2, 1]));
+        assert_eq!(read_helpers(&trace, row), build_helpers(16, 0));
+    }
+    for row in 2..=10 {
+        assert_eq!(read_stack_top(&trace, row), build_stack(&[0, 2, 1]));
+        assert_eq!(read_helpers(&trace, row), build_helpers(17, 1));
+    }
+    for row in 11..32 {
+        assert_eq!(read_stack_top(&trace, row), build_stack(&[2, 1]));
+        assert_eq!(read_helpers(&trace, row), build_helpers(16, 0));
+    }
+}
+
 // HELPERS
 // ================================================================================================
 
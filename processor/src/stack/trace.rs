@@ -1,12 +1,12 @@
 use alloc::vec::Vec;
 
-use miden_air::{
-    RowIndex,
-    trace::stack::{H0_COL_IDX, NUM_STACK_HELPER_COLS},
-};
+use miden_air::{RowIndex, trace::stack::NUM_STACK_HELPER_COLS};
 use vm_core::{FieldElement, stack::MIN_STACK_DEPTH};
 
 use super::{super::utils::get_trace_len, Felt, MAX_TOP_IDX, ONE, STACK_TRACE_WIDTH, ZERO};
+#[cfg(feature = "simd")]
+use crate::simd::batch_inversion;
+#[cfg(not(feature = "simd"))]
 use crate::utils::math::batch_inversion;
 
 // STACK TRACE
@@ -17,10 +17,18 @@ use crate::utils::math::batch_inversion;
 /// The trace consists of 19 columns grouped logically as follows:
 /// - 16 stack columns holding the top of the stack.
 /// - 3 columns for bookkeeping and helper values that manage left and right shifts.
+///
+/// The bookkeeping and helper columns change only when the stack depth or the overflow table
+/// changes, which most operations don't do. Thus, rather than writing these columns at every
+/// clock cycle, the changes of their values are appended to a journal during execution, and the
+/// columns are materialized from the journal only once the trace is finalized.
 #[derive(Debug)]
 pub struct StackTrace {
     stack: [Vec<Felt>; MIN_STACK_DEPTH],
-    helpers: [Vec<Felt>; NUM_STACK_HELPER_COLS],
+    helpers: Vec<HelpersEntry>,
+    /// A mask applied to clock cycles to get the index of the corresponding trace row. When the
+    /// full trace is recorded, this is `usize::MAX`; otherwise only the last two rows are kept.
+    row_mask: usize,
 }
 
 impl StackTrace {
@@ -40,7 +48,29 @@ impl StackTrace {
     ) -> Self {
         StackTrace {
             stack: init_stack_columns(init_trace_capacity, init_values),
-            helpers: init_helper_columns(init_trace_capacity, init_depth, init_overflow_addr),
+            helpers: vec![HelpersEntry {
+                clk: 0,
+                // TODO: change type of `init_depth` to `u32`
+                depth: Felt::try_from(init_depth as u64)
+                    .expect("value is greater than or equal to the field modulus"),
+                overflow_addr: init_overflow_addr,
+            }],
+            row_mask: usize::MAX,
+        }
+    }
+
+    /// Returns a [StackTrace] instantiated with the provided input values, which keeps only the
+    /// rows for the current and the next clock cycles.
+    ///
+    /// See [StackTrace::new] for details on how the input values are used.
+    pub fn new_without_trace(
+        init_values: &[Felt],
+        init_depth: usize,
+        init_overflow_addr: Felt,
+    ) -> Self {
+        StackTrace {
+            row_mask: 1,
+            ..Self::new(init_values, 2, init_depth, init_overflow_addr)
         }
     }
 
@@ -50,20 +80,20 @@ impl StackTrace {
     /// Returns a copy of the item at the top of the stack at the specified clock cycle.
     #[inline(always)]
     pub fn peek_at(&self, clk: RowIndex) -> Felt {
-        self.stack[0][clk.as_usize()]
+        self.stack[0][clk.as_usize() & self.row_mask]
     }
 
     /// Returns the value located at the specified position on the stack at the specified clock
     /// cycle.
     #[inline(always)]
     pub fn get_stack_value_at(&self, clk: RowIndex, pos: usize) -> Felt {
-        self.stack[pos][clk.as_usize()]
+        self.stack[pos][clk.as_usize() & self.row_mask]
     }
 
     /// Sets the value at the specified position on the stack at the specified cycle.
     #[inline(always)]
     pub fn set_stack_value_at(&mut self, clk: RowIndex, pos: usize, value: Felt) {
-        self.stack[pos][clk.as_usize()] = value;
+        self.stack[pos][clk.as_usize() & self.row_mask] = value;
     }
 
     /// Copies the stack values starting at the specified position at the specified clock cycle to
@@ -78,9 +108,11 @@ impl StackTrace {
         stack_depth: Felt,
         next_overflow_addr: Felt,
     ) {
+        let (row, next_row) = self.rows_at(clk);
+
         // copy over stack top columns
         for i in start_pos..MIN_STACK_DEPTH {
-            self.stack[i][clk + 1] = self.stack[i][clk];
+            self.stack[i][next_row] = self.stack[i][row];
         }
 
         // update stack helper columns
@@ -106,23 +138,21 @@ impl StackTrace {
         last_value: Felt,
         next_overflow_addr: Option<Felt>,
     ) -> (Felt, Felt) {
-        let clk = clk.as_usize();
+        let (row, next_row) = self.rows_at(clk.as_usize());
 
         // update stack top columns
         for i in start_pos..=MAX_TOP_IDX {
-            self.stack[i - 1][clk + 1] = self.stack[i][clk];
+            self.stack[i - 1][next_row] = self.stack[i][row];
         }
-        self.stack[MAX_TOP_IDX][clk + 1] = last_value;
+        self.stack[MAX_TOP_IDX][next_row] = last_value;
 
         // return stack helper columns
+        let helpers = self.helpers_at(clk.as_usize());
         if let Some(next_overflow_addr) = next_overflow_addr {
-            let next_depth = self.helpers[0][clk] - ONE;
-            (next_depth, next_overflow_addr)
+            (helpers.depth - ONE, next_overflow_addr)
         } else {
             // if next_overflow_addr was not provide, just return the values from the last row
-            let next_depth = self.helpers[0][clk];
-            let next_overflow_addr = self.helpers[1][clk];
-            (next_depth, next_overflow_addr)
+            (helpers.depth, helpers.overflow_addr)
         }
     }
 
@@ -134,18 +164,19 @@ impl StackTrace {
     /// - Increments the stack depth (b0) by one.
     /// - Sets b1 to the address of the new top row in overflow table, which is the current clock
     ///   cycle.
-    /// - Set h0 to (depth - 16). Inverses of these values will be computed in into_array() method
-    ///   after the entire trace is constructed.
+    /// - Set h0 to (depth - 16). This value, as well as its inverse, will be computed in
+    ///   into_array() method after the entire trace is constructed.
     pub fn stack_shift_right_at(&mut self, clk: RowIndex, start_pos: usize) {
         let clk = clk.as_usize();
+        let (row, next_row) = self.rows_at(clk);
 
         // update stack top columns
         for i in start_pos..MAX_TOP_IDX {
-            self.stack[i + 1][clk + 1] = self.stack[i][clk];
+            self.stack[i + 1][next_row] = self.stack[i][row];
         }
 
         // update stack helper columns
-        let next_depth = self.helpers[0][clk] + ONE;
+        let next_depth = self.helpers_at(clk).depth + ONE;
         self.set_helpers_at(clk, next_depth, Felt::from(clk as u32));
     }
 
@@ -156,11 +187,14 @@ impl StackTrace {
     ///
     /// Trace length is doubled every time it needs to be increased.
     pub fn ensure_trace_capacity(&mut self, clk: RowIndex) {
+        if self.row_mask != usize::MAX {
+            return;
+        }
         let current_capacity = get_trace_len(&self.stack);
         // current_capacity as trace_length can not be bigger than clk, so it is safe to cast to u32
         if (clk + 1) >= current_capacity {
             let new_length = current_capacity * 2;
-            for column in self.stack.iter_mut().chain(self.helpers.iter_mut()) {
+            for column in self.stack.iter_mut() {
                 column.resize(new_length, ZERO);
             }
         }
@@ -169,19 +203,36 @@ impl StackTrace {
     /// Appends stack top state (16 items) at the specified clock cycle into the provided vector.
     pub fn append_state_into(&self, result: &mut Vec<Felt>, clk: RowIndex) {
         for column in self.stack.iter() {
-            result.push(column[clk.as_usize()]);
+            result.push(column[clk.as_usize() & self.row_mask]);
         }
     }
 
     /// Combines all columns of the trace (stack + helpers) into a single array of vectors.
+    ///
+    /// The bookkeeping and helper columns are materialized from the journal of their values.
     pub fn into_array(self) -> [Vec<Felt>; STACK_TRACE_WIDTH] {
+        assert_eq!(self.row_mask, usize::MAX, "trace generation was disabled");
+        let trace_len = get_trace_len(&self.stack);
+
+        // expand each journal entry into the rows preceding the next entry
+        let mut b0 = Vec::with_capacity(trace_len);
+        let mut b1 = Vec::with_capacity(trace_len);
+        for (i, entry) in self.helpers.iter().enumerate() {
+            let end = self.helpers.get(i + 1).map_or(trace_len, |next| next.clk);
+            b0.resize(end, entry.depth);
+            b1.resize(end, entry.overflow_addr);
+        }
+
+        // compute inverses of (depth - 16) in the h0 helper column using batch inversion, which
+        // is parallelized when the `concurrent` feature is enabled; any ZERO in the vector will
+        // remain unchanged
+        let min_depth = Felt::from(MIN_STACK_DEPTH as u32);
+        let h0 = batch_inversion(&b0.iter().map(|&depth| depth - min_depth).collect::<Vec<_>>());
+
         let mut trace = Vec::with_capacity(STACK_TRACE_WIDTH);
         self.stack.into_iter().for_each(|col| trace.push(col));
-        self.helpers.into_iter().for_each(|col| trace.push(col));
-
-        // compute inverses in the h0 helper column using batch inversion; any ZERO in the vector
-        // will remain unchanged
-        trace[H0_COL_IDX] = batch_inversion(&trace[H0_COL_IDX]);
+        let helpers: [Vec<Felt>; NUM_STACK_HELPER_COLS] = [b0, b1, h0];
+        trace.extend(helpers);
 
         trace.try_into().expect("Failed to convert vector to an array")
     }
@@ -189,19 +240,53 @@ impl StackTrace {
     // HELPER METHODS
     // --------------------------------------------------------------------------------------------
 
-    /// Sets values of stack helper columns for the next clock cycle. Note that h0 column value is
-    /// set to (stack_depth - 16) rather than to 1 / (stack_depth - 16). Inverses of these values
-    /// will be computed in into_array() method (using batch inversion) after the entire trace is
-    /// constructed.
+    /// Returns the indices of the trace rows for the specified clock cycle and the one after it.
+    #[inline(always)]
+    fn rows_at(&self, clk: usize) -> (usize, usize) {
+        (clk & self.row_mask, (clk + 1) & self.row_mask)
+    }
+
+    /// Returns the journal entry holding the values of the stack helper columns at the specified
+    /// clock cycle.
+    #[inline(always)]
+    fn helpers_at(&self, clk: usize) -> &HelpersEntry {
+        // helper values are almost always requested for one of the last two entries, and thus
+        // searching from the end of the journal is faster than a binary search
+        self.helpers
+            .iter()
+            .rev()
+            .find(|entry| entry.clk <= clk)
+            .expect("journal contains an entry for the first clock cycle")
+    }
+
+    /// Sets values of stack helper columns for the next clock cycle.
+    ///
+    /// The values are appended to the journal only if they differ from the ones at the current
+    /// clock cycle. The h0 column value is not recorded as it is derived from the stack depth in
+    /// into_array() method (using batch inversion) after the entire trace is constructed.
     pub(super) fn set_helpers_at(
         &mut self,
         clk: usize,
         stack_depth: Felt,
         next_overflow_addr: Felt,
     ) {
-        self.helpers[0][clk + 1] = stack_depth;
-        self.helpers[1][clk + 1] = next_overflow_addr;
-        self.helpers[2][clk + 1] = stack_depth - Felt::from(MIN_STACK_DEPTH as u32);
+        let next_clk = clk + 1;
+        let last = self.helpers.last_mut().expect("journal is never empty");
+        if last.clk == next_clk {
+            last.depth = stack_depth;
+            last.overflow_addr = next_overflow_addr;
+        } else if last.depth != stack_depth || last.overflow_addr != next_overflow_addr {
+            // when only the last two rows are kept, only the entry for the current clock cycle
+            // needs to be kept as well
+            if self.row_mask != usize::MAX {
+                self.helpers.drain(..self.helpers.len() - 1);
+            }
+            self.helpers.push(HelpersEntry {
+                clk: next_clk,
+                depth: stack_depth,
+                overflow_addr: next_overflow_addr,
+            });
+        }
     }
 
     // TEST HELPERS
@@ -212,7 +297,7 @@ impl StackTrace {
     pub fn get_stack_state_at(&self, clk: RowIndex) -> [Felt; MIN_STACK_DEPTH] {
         let mut result = [ZERO; MIN_STACK_DEPTH];
         for (result, column) in result.iter_mut().zip(self.stack.iter()) {
-            *result = column[clk.as_usize()];
+            *result = column[clk.as_usize() & self.row_mask];
         }
         result
     }
@@ -220,14 +305,29 @@ impl StackTrace {
     /// Returns the trace state of the stack helper columns at the specified clock cycle.
     #[cfg(test)]
     pub fn get_helpers_state_at(&self, clk: RowIndex) -> [Felt; NUM_STACK_HELPER_COLS] {
-        let mut result = [ZERO; NUM_STACK_HELPER_COLS];
-        for (result, column) in result.iter_mut().zip(self.helpers.iter()) {
-            *result = column[clk.as_usize()];
-        }
-        result
+        let helpers = self.helpers_at(clk.as_usize());
+        [
+            helpers.depth,
+            helpers.overflow_addr,
+            helpers.depth - Felt::from(MIN_STACK_DEPTH as u32),
+        ]
     }
 }
 
+// HELPERS JOURNAL
+// ================================================================================================
+
+/// An entry of the journal of stack helper column values.
+///
+/// The bookkeeping columns hold the values of the entry from its clock cycle up to (but not
+/// including) the clock cycle of the next entry in the journal.
+#[derive(Debug, Clone, Copy)]
+struct HelpersEntry {
+    clk: usize,
+    depth: Felt,
+    overflow_addr: Felt,
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
@@ -247,26 +347,3 @@ fn init_stack_columns(
 
     stack.try_into().expect("Failed to convert vector to an array")
 }
-
-/// Initializes the bookkeeping & helper columns.
-fn init_helper_columns(
-    init_trace_capacity: usize,
-    init_depth: usize,
-    init_overflow_addr: Felt,
-) -> [Vec<Felt>; NUM_STACK_HELPER_COLS] {
-    // initialize b0 to the initial stack depth.
-    let mut b0 = vec![Felt::ZERO; init_trace_capacity];
-    b0[0] = Felt::new(init_depth as u64);
-
-    // initialize b1 to the address of the last row in the stack overflow table.
-    let mut b1 = vec![Felt::ZERO; init_trace_capacity];
-    b1[0] = init_overflow_addr;
-
-    // if the overflow table is not empty, set h0 to (init_depth - 16)
-    let mut h0 = vec![Felt::ZERO; init_trace_capacity];
-    // TODO: change type of `init_depth` to `u32`
-    h0[0] = Felt::try_from((init_depth - MIN_STACK_DEPTH) as u64)
-        .expect("value is greater than or equal to the field modulus");
-
-    [b0, b1, h0]
-}
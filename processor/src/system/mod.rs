@@ -52,6 +52,9 @@ pub struct System {
     fmp_trace: Vec<Felt>,
     in_syscall_trace: Vec<Felt>,
     fn_hash_trace: [Vec<Felt>; 4],
+    /// A mask applied to clock cycles to get the index of the corresponding trace row. When the
+    /// full trace is recorded, this is `usize::MAX`; otherwise only the last two rows are kept.
+    row_mask: usize,
 }
 
 impl System {
@@ -82,12 +85,24 @@ impl System {
                 vec![Felt::ZERO; init_trace_capacity],
                 vec![Felt::ZERO; init_trace_capacity],
             ],
+            row_mask: usize::MAX,
         }
     }
 
+    /// Returns a new [System] struct which does not record the execution trace, beyond the rows
+    /// for the current and the next clock cycles.
+    pub fn without_trace() -> Self {
+        Self { row_mask: 1, ..Self::new(2) }
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
+    /// Returns true if this [System] records the execution trace.
+    pub fn is_tracing(&self) -> bool {
+        self.row_mask == usize::MAX
+    }
+
     /// Returns the current clock cycle of a process.
     #[inline(always)]
     pub fn clk(&self) -> RowIndex {
@@ -128,13 +143,13 @@ impl System {
     /// Returns execution context ID at the specified clock cycle.
     #[inline(always)]
     pub fn get_ctx_at(&self, clk: RowIndex) -> ContextId {
-        (self.ctx_trace[clk.as_usize()].as_int() as u32).into()
+        (self.ctx_trace[clk.as_usize() & self.row_mask].as_int() as u32).into()
     }
 
     /// Returns free memory pointer at the specified clock cycle.
     #[inline(always)]
     pub fn get_fmp_at(&self, clk: RowIndex) -> Felt {
-        self.fmp_trace[clk.as_usize()]
+        self.fmp_trace[clk.as_usize() & self.row_mask]
     }
 
     // STATE MUTATORS
@@ -149,7 +164,7 @@ impl System {
             return Err(ExecutionError::CycleLimitExceeded(max_cycles));
         }
 
-        let clk: usize = self.clk.into();
+        let clk = self.clk.as_usize() & self.row_mask;
 
         self.clk_trace[clk] = Felt::from(self.clk);
         self.fmp_trace[clk] = self.fmp;
@@ -237,6 +252,7 @@ impl System {
     /// overwritten with random values. This parameter is unused because last rows are just
     /// duplicates of the prior rows and thus can be safely overwritten.
     pub fn into_trace(mut self, trace_len: usize, num_rand_rows: usize) -> SysTrace {
+        assert_eq!(self.row_mask, usize::MAX, "trace generation was disabled");
         let clk: usize = self.clk().into();
         // make sure that only the duplicate rows will be overwritten with random values
         assert!(clk + num_rand_rows <= trace_len, "target trace length too small");
@@ -285,6 +301,9 @@ impl System {
     ///
     /// Trace length is doubled every time it needs to be increased.
     pub fn ensure_trace_capacity(&mut self) {
+        if self.row_mask != usize::MAX {
+            return;
+        }
         let current_capacity = self.clk_trace.len();
         if self.clk + 1 >= RowIndex::from(current_capacity) {
             let new_length = current_capacity * 2;
@@ -6,19 +6,23 @@ use miden_air::trace::{
     decoder::{NUM_USER_OP_HELPERS, USER_OP_HELPERS_OFFSET},
     main_trace::MainTrace,
 };
-use vm_core::{ProgramInfo, StackInputs, StackOutputs, ZERO, stack::MIN_STACK_DEPTH};
+use vm_core::{
+    ExecutionEnvironment, ProgramInfo, StackInputs, StackOutputs, ZERO, stack::MIN_STACK_DEPTH,
+};
 use winter_prover::{EvaluationFrame, Trace, TraceInfo, crypto::RandomCoin};
 
 use super::{
-    ColMatrix, Digest, Felt, FieldElement, Process,
-    chiplets::AuxTraceBuilder as ChipletsAuxTraceBuilder, crypto::RpoRandomCoin,
+    ColMatrix, CoverageReport, Digest, EventTimeline, Felt, FieldElement, MemoryState, Process,
+    ProfileReport, chiplets::AuxTraceBuilder as ChipletsAuxTraceBuilder, crypto::RpoRandomCoin,
     decoder::AuxTraceBuilder as DecoderAuxTraceBuilder,
     range::AuxTraceBuilder as RangeCheckerAuxTraceBuilder,
     stack::AuxTraceBuilder as StackAuxTraceBuilder,
 };
 
 mod utils;
-pub use utils::{AuxColumnBuilder, ChipletsLengths, TraceFragment, TraceLenSummary};
+pub use utils::{
+    AuxColumnBuilder, ChipletsLengths, TraceComponent, TraceFragment, TraceLenSummary, TraceStats,
+};
 
 #[cfg(test)]
 mod tests;
@@ -55,7 +59,13 @@ pub struct ExecutionTrace {
     aux_trace_builders: AuxTraceBuilders,
     program_info: ProgramInfo,
     stack_outputs: StackOutputs,
-    trace_len_summary: TraceLenSummary,
+    environment: ExecutionEnvironment,
+    trace_stats: TraceStats,
+    profile: Option<ProfileReport>,
+    coverage: Option<CoverageReport>,
+    event_timeline: Option<EventTimeline>,
+    initial_memory: MemoryState,
+    memory: MemoryState,
 }
 
 impl ExecutionTrace {
@@ -68,7 +78,7 @@ impl ExecutionTrace {
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
     /// Builds an execution trace for the provided process.
-    pub fn new(process: Process, stack_outputs: StackOutputs) -> Self {
+    pub fn new(mut process: Process, stack_outputs: StackOutputs) -> Self {
         // use program hash to initialize random element generator; this generator will be used
         // to inject random values at the end of the trace; using program hash here is OK because
         // we are using random values only to stabilize constraint degrees, and not to achieve
@@ -76,10 +86,22 @@ impl ExecutionTrace {
         let program_hash = process.decoder.program_hash();
         let rng = RpoRandomCoin::new(program_hash);
 
-        // create a new program info instance with the underlying kernel
+        // create a new program info instance with the underlying kernel and the version of the
+        // instruction set targeted by the program
         let kernel = process.kernel().clone();
-        let program_info = ProgramInfo::new(program_hash.into(), kernel);
+        let program_info =
+            ProgramInfo::new(program_hash.into(), kernel).with_isa_version(process.isa_version);
+        let clk = process.system.clk().into();
+        let profile = process.profiler.take().and_then(|profiler| profiler.into_report(clk));
+        let coverage = process.coverage.take();
+        let event_timeline =
+            process.events.take().map(|events| events.into_timeline(program_hash.into()));
+        let initial_memory = process.chiplets.memory.initial_state();
+        let memory = process.memory_state();
+        let environment = process.environment;
+        let overflow_table_peak_len = process.stack.overflow_table_peak_len();
         let (main_trace, aux_trace_builders, trace_len_summary) = finalize_trace(process, rng);
+        let trace_stats = TraceStats::new(trace_len_summary, overflow_table_peak_len);
         let trace_info = TraceInfo::new_multi_segment(
             TRACE_WIDTH,
             AUX_TRACE_WIDTH,
@@ -95,7 +117,13 @@ impl ExecutionTrace {
             main_trace,
             program_info,
             stack_outputs,
-            trace_len_summary,
+            environment,
+            trace_stats,
+            profile,
+            coverage,
+            event_timeline,
+            initial_memory,
+            memory,
         }
     }
 
@@ -117,6 +145,47 @@ impl ExecutionTrace {
         &self.stack_outputs
     }
 
+    /// Returns the environment of the program execution which resulted in this execution trace,
+    /// i.e., the environment committed to in the public inputs of its proof.
+    pub fn environment(&self) -> &ExecutionEnvironment {
+        &self.environment
+    }
+
+    /// Returns the profile of the program execution which resulted in this execution trace, if
+    /// profiling was enabled in the [ExecutionOptions](crate::ExecutionOptions).
+    pub fn profile(&self) -> Option<&ProfileReport> {
+        self.profile.as_ref()
+    }
+
+    /// Returns the coverage of the program execution which resulted in this execution trace, if
+    /// coverage recording was enabled in the [ExecutionOptions](crate::ExecutionOptions).
+    pub fn coverage(&self) -> Option<&CoverageReport> {
+        self.coverage.as_ref()
+    }
+
+    /// Returns the events emitted by the program execution which resulted in this execution
+    /// trace, if the event timeline was enabled in the [ExecutionOptions](crate::ExecutionOptions).
+    pub fn event_timeline(&self) -> Option<&EventTimeline> {
+        self.event_timeline.as_ref()
+    }
+
+    /// Returns the state of memory across all execution contexts before the program execution
+    /// which resulted in this execution trace started.
+    ///
+    /// This is empty unless memory was initialized via
+    /// [Process::with_initial_memory](crate::Process::with_initial_memory).
+    pub fn initial_memory(&self) -> &MemoryState {
+        &self.initial_memory
+    }
+
+    /// Returns the final state of memory across all execution contexts of the program execution
+    /// which resulted in this execution trace.
+    ///
+    /// Words holding four ZERO elements are omitted from the returned state.
+    pub fn memory(&self) -> &MemoryState {
+        &self.memory
+    }
+
     /// Returns the initial state of the top 16 stack registers.
     pub fn init_stack_state(&self) -> StackInputs {
         let mut result = [ZERO; MIN_STACK_DEPTH];
@@ -153,7 +222,12 @@ impl ExecutionTrace {
 
     /// Returns a summary of the lengths of main, range and chiplet traces.
     pub fn trace_len_summary(&self) -> &TraceLenSummary {
-        &self.trace_len_summary
+        self.trace_stats.trace_len_summary()
+    }
+
+    /// Returns statistics about the rows used by each component of the trace.
+    pub fn trace_stats(&self) -> &TraceStats {
+        &self.trace_stats
     }
 
     /// Returns the trace meta data.
@@ -191,25 +265,23 @@ impl ExecutionTrace {
     where
         E: FieldElement<BaseField = Felt>,
     {
-        // add decoder's running product columns
-        let decoder_aux_columns = self
-            .aux_trace_builders
-            .decoder
-            .build_aux_columns(&self.main_trace, rand_elements);
-
-        // add stack's running product columns
-        let stack_aux_columns =
-            self.aux_trace_builders.stack.build_aux_columns(&self.main_trace, rand_elements);
-
-        // add the range checker's running product columns
-        let range_aux_columns =
-            self.aux_trace_builders.range.build_aux_columns(&self.main_trace, rand_elements);
-
-        // add the running product columns for the chiplets
-        let chiplets = self
-            .aux_trace_builders
-            .chiplets
-            .build_aux_columns(&self.main_trace, rand_elements);
+        // build the running product columns of the decoder, the stack, the range checker, and
+        // the chiplets, in parallel when the `concurrent` feature is enabled
+        let builders = &self.aux_trace_builders;
+        let ((decoder_aux_columns, stack_aux_columns), (range_aux_columns, chiplets)) = join(
+            || {
+                join(
+                    || builders.decoder.build_aux_columns(&self.main_trace, rand_elements),
+                    || builders.stack.build_aux_columns(&self.main_trace, rand_elements),
+                )
+            },
+            || {
+                join(
+                    || builders.range.build_aux_columns(&self.main_trace, rand_elements),
+                    || builders.chiplets.build_aux_columns(&self.main_trace, rand_elements),
+                )
+            },
+        );
 
         // combine all auxiliary columns into a single vector
         let mut aux_columns = decoder_aux_columns
@@ -301,14 +373,27 @@ fn finalize_trace(
     let trace_len_summary =
         TraceLenSummary::new(clk.into(), range_table_len, ChipletsLengths::new(&chiplets));
 
-    // Combine all trace segments into the main trace
-    let system_trace = system.into_trace(trace_len, NUM_RAND_ROWS);
-    let decoder_trace = decoder.into_trace(trace_len, NUM_RAND_ROWS);
-    let stack_trace = stack.into_trace(trace_len, NUM_RAND_ROWS);
-    let chiplets_trace = chiplets.into_trace(trace_len, NUM_RAND_ROWS);
-
-    // Combine the range trace segment using the support lookup table
-    let range_check_trace = range.into_trace_with_table(range_table_len, trace_len, NUM_RAND_ROWS);
+    // Build the trace segments of all components, in parallel when the `concurrent` feature is
+    // enabled; the range trace segment is combined using the support lookup table
+    let ((system_trace, decoder_trace), (stack_trace, (chiplets_trace, range_check_trace))) = join(
+        || {
+            join(
+                || system.into_trace(trace_len, NUM_RAND_ROWS),
+                || decoder.into_trace(trace_len, NUM_RAND_ROWS),
+            )
+        },
+        || {
+            join(
+                || stack.into_trace(trace_len, NUM_RAND_ROWS),
+                || {
+                    join(
+                        || chiplets.into_trace(trace_len, NUM_RAND_ROWS),
+                        || range.into_trace_with_table(range_table_len, trace_len, NUM_RAND_ROWS),
+                    )
+                },
+            )
+        },
+    );
 
     let mut trace = system_trace
         .into_iter()
@@ -336,3 +421,20 @@ fn finalize_trace(
 
     (main_trace, aux_trace_hints, trace_len_summary)
 }
+
+/// Executes the two provided closures and returns their results.
+///
+/// When the `concurrent` feature is enabled, the closures are executed in parallel.
+fn join<A, B, RA, RB>(a: A, b: B) -> (RA, RB)
+where
+    A: FnOnce() -> RA + Send,
+    B: FnOnce() -> RB + Send,
+    RA: Send,
+    RB: Send,
+{
+    #[cfg(feature = "concurrent")]
+    return winter_utils::rayon::join(a, b);
+
+    #[cfg(not(feature = "concurrent"))]
+    (a(), b())
+}
@@ -96,3 +96,26 @@ fn p1_trace() {
         assert_eq!(ONE, p1[i]);
     }
 }
+
+#[test]
+fn overflow_table_peak_len() {
+    // the overflow table holds at most 2 items at a time since the 3rd PAD follows a DROP
+    let ops = vec![
+        Operation::Pad,
+        Operation::Pad,
+        Operation::Drop,
+        Operation::Pad,
+        Operation::Drop,
+        Operation::Drop,
+    ];
+    let init_stack = (1..17).collect::<Vec<_>>();
+    let trace = build_trace_from_ops(ops, &init_stack);
+    let stats = trace.trace_stats();
+
+    assert_eq!(stats.overflow_table_peak_len(), 2);
+
+    // SPAN + 6 operations + END
+    assert_eq!(stats.main_trace_rows(), 8);
+    assert_eq!(stats.trace_len_summary(), trace.trace_len_summary());
+    assert_eq!(stats.padded_trace_len(), trace.get_trace_len());
+}
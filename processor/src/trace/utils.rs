@@ -4,10 +4,19 @@ use core::slice;
 use miden_air::{RowIndex, trace::main_trace::MainTrace};
 #[cfg(test)]
 use vm_core::{Operation, utils::ToElements};
+#[cfg(all(feature = "concurrent", not(any(test, feature = "bus-debugger"))))]
+use winter_utils::iterators::*;
 
 use super::{Felt, FieldElement, NUM_RAND_ROWS};
 use crate::{chiplets::Chiplets, debug::BusDebugger, utils::uninit_vector};
 
+// CONSTANTS
+// ================================================================================================
+
+/// The smallest number of rows of an auxiliary column processed by a single thread.
+#[cfg(all(feature = "concurrent", not(any(test, feature = "bus-debugger"))))]
+const MIN_AUX_CHUNK_SIZE: usize = 1024;
+
 // TRACE FRAGMENT
 // ================================================================================================
 
@@ -132,6 +141,32 @@ impl TraceLenSummary {
     pub fn padding_percentage(&self) -> usize {
         (self.padded_trace_len() - self.trace_len()) * 100 / self.padded_trace_len()
     }
+
+    /// Returns the component with the longest trace, which determines the length of the trace.
+    ///
+    /// If several components have the longest trace, the main trace takes precedence over the
+    /// chiplets, which take precedence over the range checker.
+    pub fn dominant_component(&self) -> TraceComponent {
+        let chiplets_trace_len = self.chiplets_trace_len.trace_len();
+        if self.main_trace_len >= chiplets_trace_len.max(self.range_trace_len) {
+            TraceComponent::Main
+        } else if chiplets_trace_len >= self.range_trace_len {
+            TraceComponent::Chiplets
+        } else {
+            TraceComponent::RangeChecker
+        }
+    }
+}
+
+/// A component of the execution trace whose length is tracked separately by [TraceLenSummary].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceComponent {
+    /// The system, decoder and stack traces, whose length is the number of executed cycles.
+    Main,
+    /// The range checker trace.
+    RangeChecker,
+    /// The chiplets trace (hash, bitwise, memory and kernel ROM chiplets).
+    Chiplets,
 }
 
 /// Contains trace lengths of all chilplets: hash, bitwise, memory and kernel ROM trace
@@ -200,6 +235,90 @@ impl ChipletsLengths {
     }
 }
 
+// TRACE STATISTICS
+// ================================================================================================
+
+/// Contains statistics about the resources used by an execution of a program.
+///
+/// In addition to the lengths of the trace parts reported by [TraceLenSummary], this includes
+/// the data which does not affect the trace length directly, but helps explain it - e.g., the
+/// largest number of items simultaneously stored in the stack overflow table.
+#[derive(Debug, Default, Eq, PartialEq, Clone, Copy)]
+pub struct TraceStats {
+    trace_len_summary: TraceLenSummary,
+    overflow_table_peak_len: usize,
+}
+
+impl TraceStats {
+    pub fn new(trace_len_summary: TraceLenSummary, overflow_table_peak_len: usize) -> Self {
+        TraceStats {
+            trace_len_summary,
+            overflow_table_peak_len,
+        }
+    }
+
+    /// Returns the summary of the lengths of main, range and chiplet traces.
+    pub fn trace_len_summary(&self) -> &TraceLenSummary {
+        &self.trace_len_summary
+    }
+
+    /// Returns the number of rows in the main trace (i.e., the number of executed cycles).
+    pub fn main_trace_rows(&self) -> usize {
+        self.trace_len_summary.main_trace_len()
+    }
+
+    /// Returns the number of rows in the range checker trace.
+    pub fn range_checker_rows(&self) -> usize {
+        self.trace_len_summary.range_trace_len()
+    }
+
+    /// Returns the number of rows used by the hash chiplet.
+    pub fn hasher_rows(&self) -> usize {
+        self.trace_len_summary.chiplets_trace_len().hash_chiplet_len()
+    }
+
+    /// Returns the number of rows used by the bitwise chiplet.
+    pub fn bitwise_rows(&self) -> usize {
+        self.trace_len_summary.chiplets_trace_len().bitwise_chiplet_len()
+    }
+
+    /// Returns the number of rows used by the memory chiplet.
+    pub fn memory_rows(&self) -> usize {
+        self.trace_len_summary.chiplets_trace_len().memory_chiplet_len()
+    }
+
+    /// Returns the number of rows used by the kernel ROM chiplet.
+    pub fn kernel_rom_rows(&self) -> usize {
+        self.trace_len_summary.chiplets_trace_len().kernel_rom_len()
+    }
+
+    /// Returns the number of rows used by all chiplets, including the mandatory padding row.
+    pub fn chiplets_rows(&self) -> usize {
+        self.trace_len_summary.chiplets_trace_len().trace_len()
+    }
+
+    /// Returns the largest number of items simultaneously stored in the stack overflow table
+    /// (across all execution contexts) during execution.
+    pub fn overflow_table_peak_len(&self) -> usize {
+        self.overflow_table_peak_len
+    }
+
+    /// Returns the length of the trace before padding, i.e., the length of its longest part.
+    pub fn trace_len(&self) -> usize {
+        self.trace_len_summary.trace_len()
+    }
+
+    /// Returns the length of the trace padded to the next power of two.
+    pub fn padded_trace_len(&self) -> usize {
+        self.trace_len_summary.padded_trace_len()
+    }
+
+    /// Returns the component with the longest trace, which determines the length of the trace.
+    pub fn dominant_component(&self) -> TraceComponent {
+        self.trace_len_summary.dominant_component()
+    }
+}
+
 // AUXILIARY COLUMN BUILDER
 // ================================================================================================
 
@@ -247,27 +366,59 @@ pub trait AuxColumnBuilder<E: FieldElement<BaseField = Felt>> {
     }
 
     /// Builds the chiplets bus auxiliary trace column.
-    fn build_aux_column(&self, main_trace: &MainTrace, alphas: &[E]) -> Vec<E> {
-        let mut responses_prod: Vec<E> = unsafe { uninit_vector(main_trace.num_rows()) };
-        let mut requests: Vec<E> = unsafe { uninit_vector(main_trace.num_rows()) };
+    ///
+    /// When the `concurrent` feature is enabled, the requests and responses of the rows are
+    /// computed in chunks of rows processed in separate threads. This is not done when the bus
+    /// debugger is enabled, since it needs to observe all rows of the column.
+    fn build_aux_column(&self, main_trace: &MainTrace, alphas: &[E]) -> Vec<E>
+    where
+        Self: Sync,
+    {
+        let num_rows = main_trace.num_rows();
+        let mut responses_prod: Vec<E> = unsafe { uninit_vector(num_rows) };
+        let mut requests: Vec<E> = unsafe { uninit_vector(num_rows) };
         let mut bus_debugger = BusDebugger::new("chiplets bus".to_string());
 
         responses_prod[0] = self.init_responses(main_trace, alphas, &mut bus_debugger);
         requests[0] = self.init_requests(main_trace, alphas, &mut bus_debugger);
 
-        let mut requests_running_prod = requests[0];
-        for row_idx in 0..main_trace.num_rows() - 1 {
+        // the requests and responses at row `i` are accumulated into the column at row `i + 1`
+        #[cfg(all(feature = "concurrent", not(any(test, feature = "bus-debugger"))))]
+        responses_prod[1..]
+            .par_chunks_mut(MIN_AUX_CHUNK_SIZE)
+            .zip(requests[1..].par_chunks_mut(MIN_AUX_CHUNK_SIZE))
+            .enumerate()
+            .for_each(|(chunk_idx, (responses_chunk, requests_chunk))| {
+                let mut bus_debugger = BusDebugger::new("chiplets bus".to_string());
+                let offset = chunk_idx * MIN_AUX_CHUNK_SIZE;
+                for (i, (response, request)) in
+                    responses_chunk.iter_mut().zip(requests_chunk.iter_mut()).enumerate()
+                {
+                    let row = (offset + i).into();
+                    *response = self.get_responses_at(main_trace, alphas, row, &mut bus_debugger);
+                    *request = self.get_requests_at(main_trace, alphas, row, &mut bus_debugger);
+                }
+            });
+
+        #[cfg(not(all(feature = "concurrent", not(any(test, feature = "bus-debugger")))))]
+        for row_idx in 0..num_rows - 1 {
             let row = row_idx.into();
-            responses_prod[row_idx + 1] = responses_prod[row_idx]
-                * self.get_responses_at(main_trace, alphas, row, &mut bus_debugger);
+            responses_prod[row_idx + 1] =
+                self.get_responses_at(main_trace, alphas, row, &mut bus_debugger);
             requests[row_idx + 1] =
                 self.get_requests_at(main_trace, alphas, row, &mut bus_debugger);
-            requests_running_prod *= requests[row_idx + 1];
+        }
+
+        let mut requests_running_prod = requests[0];
+        for row_idx in 1..num_rows {
+            let prev_response_prod = responses_prod[row_idx - 1];
+            responses_prod[row_idx] *= prev_response_prod;
+            requests_running_prod *= requests[row_idx];
         }
 
         let mut requests_running_divisor = requests_running_prod.inv();
         let mut result_aux_column = responses_prod;
-        for i in (0..main_trace.num_rows()).rev() {
+        for i in (0..num_rows).rev() {
             result_aux_column[i] *= requests_running_divisor;
             requests_running_divisor *= requests[i];
         }
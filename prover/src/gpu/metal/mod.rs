@@ -23,7 +23,7 @@ use winter_prover::{
 };
 
 use crate::{
-    ExecutionProver, ExecutionTrace, Felt, FieldElement, ProcessorAir, PublicInputs,
+    ExecutionProver, ExecutionTrace, Felt, FieldElement, ProcessorAir, ProvingPhase, PublicInputs,
     WinterProofOptions,
     crypto::{RandomCoin, Rpo256},
     math::fft,
@@ -97,6 +97,7 @@ where
         trace: &Self::Trace,
         aux_rand_elements: &AuxRandElements<E>,
     ) -> ColMatrix<E> {
+        self.execution_prover.report_progress(ProvingPhase::AuxTraceBuild);
         trace.build_aux_trace(aux_rand_elements.rand_elements()).unwrap()
     }
 
@@ -107,6 +108,7 @@ where
         domain: &StarkDomain<Felt>,
         _partition_options: PartitionOptions,
     ) -> (Self::TraceLde<E>, TracePolyTable<E>) {
+        self.execution_prover.report_progress(ProvingPhase::TraceLde);
         MetalTraceLde::new(trace_info, main_trace, domain, self.metal_hash_fn)
     }
 
@@ -127,12 +129,16 @@ where
         domain: &StarkDomain<Self::BaseField>,
         _partition_options: PartitionOptions,
     ) -> (Self::ConstraintCommitment<E>, CompositionPoly<E>) {
-        MetalConstraintCommitment::new(
+        let commitment = MetalConstraintCommitment::new(
             composition_poly_trace,
             num_constraint_composition_columns,
             domain,
             self.metal_hash_fn,
-        )
+        );
+
+        // the remaining work of the prover consists of the DEEP composition and FRI
+        self.execution_prover.report_progress(ProvingPhase::Fri);
+        commitment
     }
 }
 
@@ -251,12 +251,14 @@ where
             ProvingOptions::with_128_bit_security_rpx(),
             StackInputs::default(),
             StackOutputs::default(),
+            ExecutionEnvironment::default(),
         )
     } else {
         ExecutionProver::new(
             ProvingOptions::with_128_bit_security(true),
             StackInputs::default(),
             StackOutputs::default(),
+            ExecutionEnvironment::default(),
         )
     }
 }
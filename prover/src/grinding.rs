@@ -0,0 +1,414 @@
+//! Proof-of-work grinding.
+//!
+//! Before drawing the query positions, the prover searches for a nonce which, hashed together
+//! with the state of its random coin, yields a number of trailing zeros at least equal to the
+//! grinding factor. The Winterfell prover performs this search on the proving thread (or on the
+//! rayon thread pool, with the `concurrent` feature), through [RandomCoin::check_leading_zeros].
+//!
+//! [GrindingCoin] takes control of this search: when it is reseeded with the last commitment
+//! before the query seed is ground, it determines the nonce to use. It then reports the actual
+//! number of leading zeros for every nonce, except for the other valid nonces, which it reports as
+//! falling one zero short of the grinding factor. The nonce is thus determined on the proving
+//! thread before the Winterfell prover starts its search, rather than from within it. This serves
+//! two purposes:
+//!
+//! - Determinism: with the `concurrent` feature, the Winterfell prover takes the first valid nonce
+//!   found by any thread. Which nonce that is depends on thread scheduling, and since the nonce
+//!   determines the query positions, so do the bytes of the proof. [GrindingCoin] instead finds
+//!   the smallest valid nonce with an order-preserving parallel search. Proofs are thus
+//!   byte-identical regardless of the number of threads used to generate them, and identical to
+//!   those generated without the `concurrent` feature, which search for the nonce sequentially.
+//! - Offloading: the search can be delegated to a [NonceGrinder], e.g., a GPU or a remote
+//!   service. The grinder is handed a [GrindingChallenge] from which it can reconstruct the state
+//!   of the random coin, and the nonce it returns is checked against the actual random coin
+//!   before being accepted.
+//!
+//! The one exception to determinism is a grinding factor of zero, i.e. no proof-of-work: every
+//! nonce is then valid, and rejecting all but one of them is not possible through [RandomCoin].
+
+use alloc::{format, sync::Arc, vec::Vec};
+use core::ops::Range;
+use std::cell::RefCell;
+
+use air::HashFunction;
+use processor::{
+    crypto::{Blake3_192, Blake3_256, Keccak256, RpoRandomCoin, RpxRandomCoin, WinterRandomCoin},
+    math::Felt,
+    utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable},
+};
+#[cfg(feature = "concurrent")]
+use winter_prover::iterators::*;
+use winter_prover::{
+    ProofOptions,
+    crypto::{Digest, Hasher, RandomCoin, RandomCoinError},
+    math::{
+        FieldElement,
+        fields::{CubeExtension, QuadExtension},
+    },
+};
+
+/// The largest nonce accepted from a [NonceGrinder].
+///
+/// The Winterfell prover looks for the nonce by checking candidates in increasing order, so
+/// accepting a nonce takes time linear in its value: every smaller candidate is hashed before the
+/// nonce is reached.
+pub const MAX_GRINDER_NONCE: u64 = 1 << 32;
+
+// NONCE GRINDER
+// ================================================================================================
+
+/// A worker to which the proof-of-work nonce search of the prover is delegated.
+///
+/// Grinding is embarrassingly parallel, and thus well suited to GPUs or to remote services. The
+/// grinder is invoked at most once per proof, from the proving thread, and blocks proof generation
+/// until it returns.
+///
+/// The nonce returned by the grinder is verified by the prover: a nonce which is not valid for the
+/// challenge, is zero, or is greater than [MAX_GRINDER_NONCE] is rejected, and the prover then
+/// searches for the nonce itself. The same happens if the grinder returns `None`.
+///
+/// Since proofs depend on the nonce, proofs are identical to those generated without a grinder
+/// only if the grinder returns the smallest valid nonce, e.g., via [GrindingChallenge::find_nonce].
+///
+/// This trait is implemented for all closures taking a [GrindingChallenge].
+pub trait NonceGrinder: Send + Sync {
+    /// Returns a nonce satisfying the proof-of-work requirement of the specified challenge, or
+    /// `None` if no such nonce could be found.
+    fn grind(&self, challenge: &GrindingChallenge) -> Option<u64>;
+}
+
+impl<F> NonceGrinder for F
+where
+    F: Fn(&GrindingChallenge) -> Option<u64> + Send + Sync,
+{
+    fn grind(&self, challenge: &GrindingChallenge) -> Option<u64> {
+        self(challenge)
+    }
+}
+
+// GRINDING CHALLENGE
+// ================================================================================================
+
+/// An operation applied to a random coin after its instantiation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CoinOperation {
+    /// The coin was reseeded with the digest serialized into the provided bytes, as returned by
+    /// [Digest::as_bytes].
+    Reseed([u8; 32]),
+    /// A field element of the specified extension degree was drawn from the coin.
+    Draw(u8),
+}
+
+/// The proof-of-work problem solved by a [NonceGrinder].
+///
+/// A nonce is valid if [RandomCoin::check_leading_zeros] returns at least the grinding factor for
+/// it, where the random coin is the one of the specified hash function, instantiated from the seed
+/// and then updated by the operations of the transcript, in order.
+///
+/// Challenges can be serialized, e.g., to be sent to a remote service.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GrindingChallenge {
+    hash_fn: HashFunction,
+    grinding_factor: u32,
+    seed: Vec<Felt>,
+    transcript: Vec<CoinOperation>,
+}
+
+impl GrindingChallenge {
+    /// Returns the hash function of the random coin.
+    pub fn hash_fn(&self) -> HashFunction {
+        self.hash_fn
+    }
+
+    /// Returns the minimum number of trailing zeros required for a nonce to be valid.
+    pub fn grinding_factor(&self) -> u32 {
+        self.grinding_factor
+    }
+
+    /// Returns the seed from which the random coin was instantiated.
+    pub fn seed(&self) -> &[Felt] {
+        &self.seed
+    }
+
+    /// Returns the operations applied to the random coin after its instantiation.
+    pub fn transcript(&self) -> &[CoinOperation] {
+        &self.transcript
+    }
+
+    /// Returns the smallest valid nonce in the specified range, or `None` if there is no valid
+    /// nonce in the range.
+    ///
+    /// This is the reference implementation of the search, which grinders can use to search
+    /// ranges of nonces on CPUs, e.g., by splitting the range of all possible nonces across
+    /// multiple machines. The search is parallel with the `concurrent` feature.
+    pub fn find_nonce(&self, nonces: Range<u64>) -> Option<u64> {
+        match self.hash_fn {
+            HashFunction::Blake3_192 => self.search::<WinterRandomCoin<Blake3_192>>(nonces),
+            HashFunction::Blake3_256 => self.search::<WinterRandomCoin<Blake3_256>>(nonces),
+            HashFunction::Rpo256 => self.search::<RpoRandomCoin>(nonces),
+            HashFunction::Rpx256 => self.search::<RpxRandomCoin>(nonces),
+            HashFunction::Keccak256 => self.search::<WinterRandomCoin<Keccak256>>(nonces),
+        }
+    }
+
+    /// Returns true if the specified nonce satisfies the proof-of-work requirement.
+    pub fn is_valid_nonce(&self, nonce: u64) -> bool {
+        self.find_nonce(nonce..nonce.saturating_add(1)).is_some()
+    }
+
+    // HELPERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Reconstructs the random coin of the challenge, and returns the smallest valid nonce in the
+    /// specified range.
+    fn search<R>(&self, nonces: Range<u64>) -> Option<u64>
+    where
+        R: RandomCoin<BaseField = Felt> + Sync,
+    {
+        let mut coin = R::new(&self.seed);
+        for operation in self.transcript.iter() {
+            match operation {
+                CoinOperation::Reseed(bytes) => {
+                    let digest = <R::Hasher as Hasher>::Digest::read_from_bytes(bytes).ok()?;
+                    coin.reseed(digest);
+                },
+                CoinOperation::Draw(1) => {
+                    coin.draw::<Felt>().ok()?;
+                },
+                CoinOperation::Draw(2) => {
+                    coin.draw::<QuadExtension<Felt>>().ok()?;
+                },
+                CoinOperation::Draw(3) => {
+                    coin.draw::<CubeExtension<Felt>>().ok()?;
+                },
+                CoinOperation::Draw(_) => return None,
+            }
+        }
+
+        search_nonce(nonces, |nonce| coin.check_leading_zeros(nonce) >= self.grinding_factor)
+    }
+}
+
+impl Serializable for CoinOperation {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        match self {
+            Self::Reseed(bytes) => {
+                target.write_u8(0);
+                target.write_bytes(bytes);
+            },
+            Self::Draw(extension_degree) => {
+                target.write_u8(1);
+                target.write_u8(*extension_degree);
+            },
+        }
+    }
+}
+
+impl Deserializable for CoinOperation {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        match source.read_u8()? {
+            0 => Ok(Self::Reseed(source.read_array()?)),
+            1 => Ok(Self::Draw(source.read_u8()?)),
+            tag => Err(DeserializationError::InvalidValue(format!(
+                "invalid coin operation tag: {tag}"
+            ))),
+        }
+    }
+}
+
+impl Serializable for GrindingChallenge {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.hash_fn.write_into(target);
+        target.write_u32(self.grinding_factor);
+        self.seed.write_into(target);
+        self.transcript.write_into(target);
+    }
+}
+
+impl Deserializable for GrindingChallenge {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Ok(Self {
+            hash_fn: source.read()?,
+            grinding_factor: source.read_u32()?,
+            seed: source.read()?,
+            transcript: source.read()?,
+        })
+    }
+}
+
+// GRINDING CONFIG
+// ================================================================================================
+
+/// How the proof-of-work nonce of the proof being generated on a thread is found.
+#[derive(Clone)]
+pub struct GrindingConfig {
+    pub hash_fn: HashFunction,
+    pub grinding_factor: u32,
+    pub grinder: Option<Arc<dyn NonceGrinder>>,
+    /// The number of times the random coin is reseeded before the query seed is ground, see
+    /// [num_reseeds].
+    pub num_reseeds: usize,
+}
+
+/// Returns the number of times the Winterfell prover reseeds its random coin before grinding the
+/// query seed, for a proof of a trace of the specified length with the specified options.
+///
+/// The coin is reseeded with the commitments to the main and auxiliary trace segments and to the
+/// constraint evaluations, with the out-of-domain trace states and constraint evaluations, and
+/// with the commitments to the FRI layers and to the FRI remainder.
+pub fn num_reseeds(trace_len: usize, options: &ProofOptions) -> usize {
+    let lde_domain_size = trace_len * options.blowup_factor();
+    5 + options.to_fri_options().num_fri_layers(lde_domain_size) + 1
+}
+
+std::thread_local! {
+    /// The grinding config of the proof being generated on this thread.
+    static GRINDING_CONFIG: RefCell<Option<GrindingConfig>> = const { RefCell::new(None) };
+}
+
+/// Sets the grinding config used by the [GrindingCoin]s created on this thread, until the
+/// returned guard is dropped.
+///
+/// The Winterfell prover instantiates its random coin through [RandomCoin::new], which leaves no
+/// way to pass it the grinding config directly; the coin is however instantiated on the thread
+/// which starts generating the proof.
+pub fn set_grinding_config(config: GrindingConfig) -> GrindingConfigGuard {
+    let prev = GRINDING_CONFIG.replace(Some(config));
+    GrindingConfigGuard { prev }
+}
+
+/// Restores the previous grinding config of the thread when dropped.
+pub struct GrindingConfigGuard {
+    prev: Option<GrindingConfig>,
+}
+
+impl Drop for GrindingConfigGuard {
+    fn drop(&mut self) {
+        GRINDING_CONFIG.set(self.prev.take());
+    }
+}
+
+// GRINDING COIN
+// ================================================================================================
+
+/// A [RandomCoin] which accepts only a single proof-of-work nonce for the state at which the query
+/// seed is ground: the one returned by the [NonceGrinder] of its config if it is valid, or the
+/// smallest valid nonce otherwise.
+pub struct GrindingCoin<R> {
+    coin: R,
+    config: GrindingConfig,
+    /// The seed and the transcript of the coin, from which grinders can reconstruct its state.
+    seed: Vec<Felt>,
+    transcript: Vec<CoinOperation>,
+    /// The number of times the coin was reseeded.
+    num_reseeds: usize,
+    /// The nonce accepted for the current state, if it is the state at which the query seed is
+    /// ground.
+    nonce: Option<u64>,
+}
+
+impl<R: RandomCoin<BaseField = Felt> + Sync> GrindingCoin<R> {
+    /// Returns the nonce to accept for the current state of the coin.
+    fn determine_nonce(&self) -> u64 {
+        self.delegate_grinding().unwrap_or_else(|| {
+            search_nonce(1..u64::MAX, |nonce| {
+                self.coin.check_leading_zeros(nonce) >= self.config.grinding_factor
+            })
+            .expect("nonce not found")
+        })
+    }
+
+    /// Returns the nonce returned by the grinder, if there is a grinder and the nonce is valid.
+    fn delegate_grinding(&self) -> Option<u64> {
+        let grinder = self.config.grinder.as_ref()?;
+        let challenge = GrindingChallenge {
+            hash_fn: self.config.hash_fn,
+            grinding_factor: self.config.grinding_factor,
+            seed: self.seed.clone(),
+            transcript: self.transcript.clone(),
+        };
+
+        let nonce = grinder.grind(&challenge)?;
+        if (1..=MAX_GRINDER_NONCE).contains(&nonce)
+            && self.coin.check_leading_zeros(nonce) >= self.config.grinding_factor
+        {
+            Some(nonce)
+        } else {
+            tracing::warn!("rejected invalid proof-of-work nonce {nonce} returned by the grinder");
+            None
+        }
+    }
+}
+
+impl<R: RandomCoin<BaseField = Felt> + Sync> RandomCoin for GrindingCoin<R> {
+    type BaseField = Felt;
+    type Hasher = R::Hasher;
+
+    fn new(seed: &[Self::BaseField]) -> Self {
+        let config = GRINDING_CONFIG
+            .with_borrow(Clone::clone)
+            .expect("the grinding config must be set before generating a proof");
+        Self {
+            coin: R::new(seed),
+            config,
+            seed: seed.to_vec(),
+            transcript: Vec::new(),
+            num_reseeds: 0,
+            nonce: None,
+        }
+    }
+
+    fn reseed(&mut self, data: <Self::Hasher as Hasher>::Digest) {
+        self.coin.reseed(data);
+        self.transcript.push(CoinOperation::Reseed(data.as_bytes()));
+        self.num_reseeds += 1;
+
+        // the nonce is determined here, on the proving thread, rather than on the first nonce
+        // check, as the Winterfell prover checks nonces from within a parallel iterator
+        self.nonce = (self.num_reseeds == self.config.num_reseeds).then(|| self.determine_nonce());
+    }
+
+    fn check_leading_zeros(&self, value: u64) -> u32 {
+        let leading_zeros = self.coin.check_leading_zeros(value);
+        match self.nonce {
+            // other valid nonces are reported one zero short of the grinding factor, so that the
+            // Winterfell prover accepts the determined nonce, whichever thread finds it
+            Some(nonce)
+                if value != nonce
+                    && self.config.grinding_factor > 0
+                    && leading_zeros >= self.config.grinding_factor =>
+            {
+                self.config.grinding_factor - 1
+            },
+            _ => leading_zeros,
+        }
+    }
+
+    fn draw<E: FieldElement<BaseField = Self::BaseField>>(&mut self) -> Result<E, RandomCoinError> {
+        self.transcript.push(CoinOperation::Draw(E::EXTENSION_DEGREE as u8));
+        self.nonce = None;
+        self.coin.draw()
+    }
+
+    fn draw_integers(
+        &mut self,
+        num_values: usize,
+        domain_size: usize,
+        nonce: u64,
+    ) -> Result<Vec<usize>, RandomCoinError> {
+        self.coin.draw_integers(num_values, domain_size, nonce)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the smallest nonce in the specified range which satisfies the predicate, searching in
+/// parallel with the `concurrent` feature.
+fn search_nonce(nonces: Range<u64>, is_valid: impl Fn(u64) -> bool + Sync) -> Option<u64> {
+    #[cfg(feature = "concurrent")]
+    return nonces.into_par_iter().find_first(|&nonce| is_valid(nonce));
+
+    #[cfg(not(feature = "concurrent"))]
+    nonces.into_iter().find(|&nonce| is_valid(nonce))
+}
@@ -6,6 +6,7 @@ extern crate alloc;
 #[cfg(feature = "std")]
 extern crate std;
 
+use alloc::boxed::Box;
 use core::marker::PhantomData;
 
 use air::{AuxRandElements, PartitionOptions, ProcessorAir, PublicInputs};
@@ -14,31 +15,55 @@ use miden_gpu::HashFn;
 use processor::{
     ExecutionTrace, Program,
     crypto::{
-        Blake3_192, Blake3_256, ElementHasher, RandomCoin, Rpo256, RpoRandomCoin, Rpx256,
-        RpxRandomCoin, WinterRandomCoin,
+        Blake3_192, Blake3_256, ElementHasher, Keccak256, RandomCoin, Rpo256, RpoRandomCoin,
+        Rpx256, RpxRandomCoin, WinterRandomCoin,
     },
     math::{Felt, FieldElement},
 };
 use tracing::instrument;
 use winter_maybe_async::{maybe_async, maybe_await};
+#[cfg(not(feature = "std"))]
+use winter_prover::DefaultTraceLde;
 use winter_prover::{
     CompositionPoly, CompositionPolyTrace, ConstraintCompositionCoefficients,
-    DefaultConstraintCommitment, DefaultConstraintEvaluator, DefaultTraceLde,
-    ProofOptions as WinterProofOptions, Prover, StarkDomain, TraceInfo, TracePolyTable,
-    matrix::ColMatrix,
+    DefaultConstraintCommitment, DefaultConstraintEvaluator, ProofOptions as WinterProofOptions,
+    Prover, StarkDomain, TraceInfo, TracePolyTable, matrix::ColMatrix,
 };
+use winter_verifier::{AcceptableOptions, verify as verify_proof};
 #[cfg(feature = "std")]
-use {std::time::Instant, winter_prover::Trace};
+use {
+    alloc::{sync::Arc, vec::Vec},
+    std::time::Instant,
+    winter_prover::Trace,
+};
 mod gpu;
+#[cfg(feature = "std")]
+mod grinding;
+#[cfg(feature = "std")]
+mod progress;
+#[cfg(feature = "prove-async")]
+mod task;
+#[cfg(feature = "std")]
+mod trace_lde;
 
 // EXPORTS
 // ================================================================================================
 
-pub use air::{DeserializationError, ExecutionProof, FieldExtension, HashFunction, ProvingOptions};
+pub use air::{
+    ComponentConstraints, ConstraintReport, DelegatedClaim, DeserializationError, ExecutionProof,
+    FieldExtension, HashFunction, ProofEnvelope, ProvingOptions, SecurityEstimate, SecurityPreset,
+    TraceRandomizer, claims_commitment,
+};
+#[cfg(feature = "std")]
+pub use grinding::{CoinOperation, GrindingChallenge, MAX_GRINDER_NONCE, NonceGrinder};
 pub use processor::{
-    AdviceInputs, Digest, ExecutionError, Host, InputError, MemAdviceProvider, StackInputs,
-    StackOutputs, Word, crypto, math, utils,
+    AdviceInputs, Digest, ExecutionEnvironment, ExecutionError, Host, InputError,
+    MemAdviceProvider, StackInputs, StackOutputs, Word, crypto, math, utils,
 };
+#[cfg(feature = "std")]
+pub use progress::{ProgressListener, ProvingPhase};
+#[cfg(feature = "prove-async")]
+pub use task::{ProvingResult, ProvingTask};
 pub use winter_prover::{Proof, crypto::MerkleTree as MerkleTreeVC};
 
 // PROVER
@@ -54,19 +79,297 @@ pub use winter_prover::{Proof, crypto::MerkleTree as MerkleTreeVC};
 ///
 /// # Errors
 /// Returns an error if program execution or STARK proof generation fails for any reason.
-#[instrument("prove_program", skip_all)]
 #[maybe_async]
 pub fn prove(
     program: &Program,
     stack_inputs: StackInputs,
     host: &mut impl Host,
     options: ProvingOptions,
+) -> Result<(StackOutputs, ExecutionProof), ExecutionError> {
+    #[cfg(feature = "std")]
+    return maybe_await!(prove_program(program, stack_inputs, host, options, None, None, None));
+
+    #[cfg(not(feature = "std"))]
+    maybe_await!(prove_program(program, stack_inputs, host, options))
+}
+
+/// Executes and proves the specified `program` like [prove()], but delegates the proof-of-work
+/// nonce search (grinding) to the specified `grinder`.
+///
+/// The nonce returned by the grinder is verified before being included in the proof; if it is not
+/// valid, the prover searches for the nonce itself. See [NonceGrinder] for details.
+///
+/// # Errors
+/// Returns an error if program execution or STARK proof generation fails for any reason.
+#[cfg(feature = "std")]
+#[maybe_async]
+pub fn prove_with_grinder(
+    program: &Program,
+    stack_inputs: StackInputs,
+    host: &mut impl Host,
+    options: ProvingOptions,
+    grinder: Arc<dyn NonceGrinder>,
+) -> Result<(StackOutputs, ExecutionProof), ExecutionError> {
+    maybe_await!(prove_program(program, stack_inputs, host, options, Some(grinder), None, None))
+}
+
+/// Executes and proves the specified `program` like [prove()], notifying the specified `listener`
+/// at the start of every [ProvingPhase].
+///
+/// # Errors
+/// Returns an error if program execution or STARK proof generation fails for any reason.
+#[cfg(feature = "std")]
+#[maybe_async]
+pub fn prove_with_progress(
+    program: &Program,
+    stack_inputs: StackInputs,
+    host: &mut impl Host,
+    options: ProvingOptions,
+    listener: Arc<dyn ProgressListener>,
+) -> Result<(StackOutputs, ExecutionProof), ExecutionError> {
+    maybe_await!(prove_program(program, stack_inputs, host, options, None, Some(listener), None))
+}
+
+/// Executes and proves the specified `program` like [prove()], deriving the random elements used to
+/// build the auxiliary trace with the specified `randomizer`.
+///
+/// The proof must be verified with the same randomizer, e.g., via
+/// `miden_verifier::verify_with_randomizer`. See [TraceRandomizer] for details.
+///
+/// # Errors
+/// Returns an error if program execution or STARK proof generation fails for any reason.
+#[cfg(feature = "std")]
+#[maybe_async]
+pub fn prove_with_randomizer(
+    program: &Program,
+    stack_inputs: StackInputs,
+    host: &mut impl Host,
+    options: ProvingOptions,
+    randomizer: Arc<dyn TraceRandomizer>,
+) -> Result<(StackOutputs, ExecutionProof), ExecutionError> {
+    maybe_await!(prove_program(
+        program,
+        stack_inputs,
+        host,
+        options,
+        None,
+        None,
+        Some(randomizer)
+    ))
+}
+
+/// Proves the execution recorded in the specified `trace`, and returns the outputs of the execution
+/// together with a STARK-based proof of it.
+///
+/// This is the same as [prove()] for a program which has already been executed, e.g., via
+/// `miden_processor::execute()`, so that its execution is not repeated. `stack_inputs` must be the
+/// inputs against which the program was executed, and the execution options of `options` are not
+/// used.
+///
+/// # Errors
+/// Returns an error if STARK proof generation fails for any reason.
+#[maybe_async]
+pub fn prove_trace(
+    trace: ExecutionTrace,
+    stack_inputs: StackInputs,
+    options: ProvingOptions,
+) -> Result<(StackOutputs, ExecutionProof), ExecutionError> {
+    #[cfg(feature = "std")]
+    return maybe_await!(prove_execution_trace(trace, stack_inputs, options, None, None, None));
+
+    #[cfg(not(feature = "std"))]
+    maybe_await!(prove_execution_trace(trace, stack_inputs, options))
+}
+
+/// A program to be proven by [prove_batch()], together with its inputs and the host it is
+/// executed against.
+#[cfg(feature = "std")]
+pub struct ProvingJob<'a, H: Host> {
+    pub program: &'a Program,
+    pub stack_inputs: StackInputs,
+    pub host: H,
+}
+
+#[cfg(feature = "std")]
+impl<'a, H: Host> ProvingJob<'a, H> {
+    /// Returns a new job proving the execution of `program` with the specified inputs and host.
+    pub fn new(program: &'a Program, stack_inputs: StackInputs, host: H) -> Self {
+        Self { program, stack_inputs, host }
+    }
+}
+
+/// Executes and proves each of the specified `jobs` like [prove()], with the same `options`, and
+/// returns the results in the order of the jobs.
+///
+/// With the `concurrent` feature (and without the `async` feature), the batch is processed in two
+/// phases which share the rayon thread pool:
+/// - The programs of all jobs are executed in parallel. The execution of a program runs on a
+///   single thread, so executing the programs one after the other would leave the other threads
+///   idle.
+/// - The execution traces are then proven in parallel, starting with the longest traces. Proving a
+///   small program keeps few threads busy, and the proofs of the shorter traces fill the threads
+///   left idle by the longer ones, rather than the batch ending with a single long proof running
+///   on a mostly idle pool.
+///
+/// Hence, the execution traces of all jobs are held in memory until they are proven. The proofs
+/// are identical to those generated by [prove()]: every proof commits to its own trace with its
+/// own transcript, so no commitment can be shared between the proofs of a batch.
+///
+/// A job failing does not prevent the other jobs from being proven.
+#[cfg(feature = "std")]
+#[maybe_async]
+pub fn prove_batch<H>(
+    jobs: Vec<ProvingJob<'_, H>>,
+    options: ProvingOptions,
+) -> Vec<Result<(StackOutputs, ExecutionProof), ExecutionError>>
+where
+    H: Host + Send,
+{
+    #[cfg(all(feature = "concurrent", not(feature = "async")))]
+    {
+        use core::cmp::Reverse;
+
+        use winter_prover::iterators::*;
+
+        let num_jobs = jobs.len();
+        let executions = jobs
+            .into_par_iter()
+            .map(|mut job| {
+                let trace = execute_program(
+                    job.program,
+                    job.stack_inputs.clone(),
+                    &mut job.host,
+                    &options,
+                )?;
+                Ok((job.stack_inputs, trace))
+            })
+            .collect::<Vec<Result<_, ExecutionError>>>();
+
+        let mut results = Vec::with_capacity(num_jobs);
+        let mut traces = Vec::with_capacity(num_jobs);
+        for (index, execution) in executions.into_iter().enumerate() {
+            match execution {
+                Ok((stack_inputs, trace)) => {
+                    results.push(None);
+                    traces.push((index, stack_inputs, trace));
+                },
+                Err(err) => results.push(Some(Err(err))),
+            }
+        }
+
+        // the bridged iterator hands out the traces to the threads in order as they become idle
+        traces.sort_by_key(|(_, _, trace)| Reverse(trace.trace_len_summary().padded_trace_len()));
+        let proofs = traces
+            .into_iter()
+            .par_bridge()
+            .map(|(index, stack_inputs, trace)| {
+                (
+                    index,
+                    prove_execution_trace(trace, stack_inputs, options.clone(), None, None, None),
+                )
+            })
+            .collect::<Vec<_>>();
+        for (index, proof) in proofs {
+            results[index] = Some(proof);
+        }
+
+        results
+            .into_iter()
+            .map(|result| result.expect("every job must have been executed or proven"))
+            .collect()
+    }
+
+    #[cfg(not(all(feature = "concurrent", not(feature = "async"))))]
+    {
+        let mut results = Vec::with_capacity(jobs.len());
+        for mut job in jobs {
+            results.push(maybe_await!(prove_program(
+                job.program,
+                job.stack_inputs,
+                &mut job.host,
+                options.clone(),
+                None,
+                None,
+                None,
+            )));
+        }
+        results
+    }
+}
+
+/// Executes and proves the specified `program` on a background thread, and returns a
+/// [ProvingTask] which resolves to the result of [prove()] once proving completes.
+///
+/// The program, its inputs and the host are moved to the proving thread. The specified `listener`
+/// is notified from that thread at the start of every [ProvingPhase]; see [ProgressListener] for
+/// how to forward the phases to another thread.
+#[cfg(feature = "prove-async")]
+pub fn prove_async<H>(
+    program: Program,
+    stack_inputs: StackInputs,
+    mut host: H,
+    options: ProvingOptions,
+    listener: impl ProgressListener + 'static,
+) -> ProvingTask
+where
+    H: Host + Send + 'static,
+{
+    let listener: Arc<dyn ProgressListener> = Arc::new(listener);
+    ProvingTask::spawn(move || {
+        let result =
+            prove_program(&program, stack_inputs, &mut host, options, None, Some(listener), None);
+
+        #[cfg(feature = "async")]
+        let result = task::block_on(result);
+
+        result
+    })
+}
+
+#[instrument("prove_program", skip_all)]
+#[maybe_async]
+fn prove_program(
+    program: &Program,
+    stack_inputs: StackInputs,
+    host: &mut impl Host,
+    options: ProvingOptions,
+    #[cfg(feature = "std")] grinder: Option<Arc<dyn NonceGrinder>>,
+    #[cfg(feature = "std")] listener: Option<Arc<dyn ProgressListener>>,
+    #[cfg(feature = "std")] trace_randomizer: Option<Arc<dyn TraceRandomizer>>,
 ) -> Result<(StackOutputs, ExecutionProof), ExecutionError> {
     // execute the program to create an execution trace
+    #[cfg(feature = "std")]
+    if let Some(listener) = &listener {
+        listener.on_phase(ProvingPhase::TraceBuild);
+    }
+    let trace = execute_program(program, stack_inputs.clone(), host, &options)?;
+
+    #[cfg(feature = "std")]
+    return maybe_await!(prove_execution_trace(
+        trace,
+        stack_inputs,
+        options,
+        grinder,
+        listener,
+        trace_randomizer
+    ));
+
+    #[cfg(not(feature = "std"))]
+    maybe_await!(prove_execution_trace(trace, stack_inputs, options))
+}
+
+/// Executes the specified `program` with the execution options of `options`, and returns its
+/// execution trace.
+fn execute_program(
+    program: &Program,
+    stack_inputs: StackInputs,
+    host: &mut impl Host,
+    options: &ProvingOptions,
+) -> Result<ExecutionTrace, ExecutionError> {
     #[cfg(feature = "std")]
     let now = Instant::now();
     let trace =
-        processor::execute(program, stack_inputs.clone(), host, *options.execution_options())?;
+        processor::execute(program, stack_inputs, host, options.execution_options().clone())?;
     #[cfg(feature = "std")]
     tracing::event!(
         tracing::Level::INFO,
@@ -77,57 +380,200 @@ pub fn prove(
         now.elapsed().as_millis()
     );
 
+    Ok(trace)
+}
+
+/// Generates a STARK proof of the execution recorded in the specified `trace`, which must have
+/// been generated against the specified `stack_inputs`.
+#[maybe_async]
+fn prove_execution_trace(
+    trace: ExecutionTrace,
+    stack_inputs: StackInputs,
+    options: ProvingOptions,
+    #[cfg(feature = "std")] grinder: Option<Arc<dyn NonceGrinder>>,
+    #[cfg(feature = "std")] listener: Option<Arc<dyn ProgressListener>>,
+    #[cfg(feature = "std")] trace_randomizer: Option<Arc<dyn TraceRandomizer>>,
+) -> Result<(StackOutputs, ExecutionProof), ExecutionError> {
     let stack_outputs = trace.stack_outputs().clone();
+    let environment = *trace.environment();
+    let initial_memory = trace.initial_memory().commitment();
     let hash_fn = options.hash_fn();
 
+    // make the proof-of-work nonce, and hence the proof, independent of the number of threads,
+    // and delegate its search to the grinder if there is one
+    #[cfg(feature = "std")]
+    let _grinding_config = {
+        let proof_options = WinterProofOptions::from(options.clone());
+        grinding::set_grinding_config(grinding::GrindingConfig {
+            hash_fn,
+            grinding_factor: proof_options.grinding_factor(),
+            grinder,
+            num_reseeds: grinding::num_reseeds(trace.get_trace_len(), &proof_options),
+        })
+    };
+
+    // the public inputs of the execution are only needed to verify the proof once it is generated
+    let self_check = options.self_check().then(|| {
+        let pub_inputs = PublicInputs::new(
+            trace.program_info().clone(),
+            stack_inputs.clone(),
+            stack_outputs.clone(),
+        )
+        .with_environment(environment)
+        .with_initial_memory(initial_memory);
+        #[cfg(feature = "std")]
+        let pub_inputs = match &trace_randomizer {
+            Some(randomizer) => pub_inputs.with_trace_randomizer(randomizer.clone()),
+            None => pub_inputs,
+        };
+        (pub_inputs, WinterProofOptions::from(options.clone()))
+    });
+
     // generate STARK proof
     let proof = match hash_fn {
         HashFunction::Blake3_192 => {
-            let prover = ExecutionProver::<Blake3_192, WinterRandomCoin<_>>::new(
+            let prover = ExecutionProver::<Blake3_192, Coin<WinterRandomCoin<Blake3_192>>>::new(
                 options,
                 stack_inputs,
                 stack_outputs.clone(),
+                environment,
             );
+            #[cfg(feature = "std")]
+            let prover = prover.with_progress(listener).with_trace_randomizer(trace_randomizer);
             maybe_await!(prover.prove(trace))
         },
         HashFunction::Blake3_256 => {
-            let prover = ExecutionProver::<Blake3_256, WinterRandomCoin<_>>::new(
+            let prover = ExecutionProver::<Blake3_256, Coin<WinterRandomCoin<Blake3_256>>>::new(
                 options,
                 stack_inputs,
                 stack_outputs.clone(),
+                environment,
             );
+            #[cfg(feature = "std")]
+            let prover = prover.with_progress(listener).with_trace_randomizer(trace_randomizer);
             maybe_await!(prover.prove(trace))
         },
         HashFunction::Rpo256 => {
-            let prover = ExecutionProver::<Rpo256, RpoRandomCoin>::new(
+            let prover = ExecutionProver::<Rpo256, Coin<RpoRandomCoin>>::new(
                 options,
                 stack_inputs,
                 stack_outputs.clone(),
+                environment,
             );
+            #[cfg(feature = "std")]
+            let prover = prover.with_progress(listener).with_trace_randomizer(trace_randomizer);
             #[cfg(all(feature = "metal", target_arch = "aarch64", target_os = "macos"))]
             let prover = gpu::metal::MetalExecutionProver::new(prover, HashFn::Rpo256);
             maybe_await!(prover.prove(trace))
         },
         HashFunction::Rpx256 => {
-            let prover = ExecutionProver::<Rpx256, RpxRandomCoin>::new(
+            let prover = ExecutionProver::<Rpx256, Coin<RpxRandomCoin>>::new(
                 options,
                 stack_inputs,
                 stack_outputs.clone(),
+                environment,
             );
+            #[cfg(feature = "std")]
+            let prover = prover.with_progress(listener).with_trace_randomizer(trace_randomizer);
             #[cfg(all(feature = "metal", target_arch = "aarch64", target_os = "macos"))]
             let prover = gpu::metal::MetalExecutionProver::new(prover, HashFn::Rpx256);
             maybe_await!(prover.prove(trace))
         },
+        HashFunction::Keccak256 => {
+            let prover = ExecutionProver::<Keccak256, Coin<WinterRandomCoin<Keccak256>>>::new(
+                options,
+                stack_inputs,
+                stack_outputs.clone(),
+                environment,
+            );
+            #[cfg(feature = "std")]
+            let prover = prover.with_progress(listener).with_trace_randomizer(trace_randomizer);
+            maybe_await!(prover.prove(trace))
+        },
     }
     .map_err(ExecutionError::ProverError)?;
     let proof = ExecutionProof::new(proof, hash_fn);
 
+    if let Some((pub_inputs, proof_options)) = self_check {
+        #[cfg(feature = "std")]
+        let now = Instant::now();
+        check_proof(&proof, pub_inputs, proof_options)?;
+        #[cfg(feature = "std")]
+        tracing::event!(
+            tracing::Level::INFO,
+            "Verified the generated proof in {} ms",
+            now.elapsed().as_millis()
+        );
+    }
+
     Ok((stack_outputs, proof))
 }
 
+/// Verifies the specified proof against the public inputs of the proven execution, accepting only
+/// the proof options with which the proof was meant to be generated.
+///
+/// This is the check performed when [ProvingOptions::with_self_check] is enabled. Unlike the
+/// verifier, this accepts proofs generated with any options, so that proofs generated with custom
+/// options can be checked as well.
+fn check_proof(
+    proof: &ExecutionProof,
+    pub_inputs: PublicInputs,
+    proof_options: WinterProofOptions,
+) -> Result<(), ExecutionError> {
+    let program = *pub_inputs.program_info().program_hash();
+    let opts = AcceptableOptions::OptionSet(alloc::vec![proof_options]);
+    let stark_proof = proof.stark_proof().clone();
+
+    match proof.hash_fn() {
+        HashFunction::Blake3_192 => verify_proof::<
+            ProcessorAir,
+            Blake3_192,
+            WinterRandomCoin<_>,
+            MerkleTreeVC<_>,
+        >(stark_proof, pub_inputs, &opts),
+        HashFunction::Blake3_256 => verify_proof::<
+            ProcessorAir,
+            Blake3_256,
+            WinterRandomCoin<_>,
+            MerkleTreeVC<_>,
+        >(stark_proof, pub_inputs, &opts),
+        HashFunction::Rpo256 => {
+            verify_proof::<ProcessorAir, Rpo256, RpoRandomCoin, MerkleTreeVC<_>>(
+                stark_proof,
+                pub_inputs,
+                &opts,
+            )
+        },
+        HashFunction::Rpx256 => {
+            verify_proof::<ProcessorAir, Rpx256, RpxRandomCoin, MerkleTreeVC<_>>(
+                stark_proof,
+                pub_inputs,
+                &opts,
+            )
+        },
+        HashFunction::Keccak256 => verify_proof::<
+            ProcessorAir,
+            Keccak256,
+            WinterRandomCoin<_>,
+            MerkleTreeVC<_>,
+        >(stark_proof, pub_inputs, &opts),
+    }
+    .map_err(|source| ExecutionError::ProofSelfCheckFailed { program, source: Box::new(source) })
+}
+
 // PROVER
 // ================================================================================================
 
+/// The random coin used by the prover, given the random coin `R` of its hash function.
+///
+/// With the `std` feature, the coin is wrapped so that the proof-of-work nonce does not depend on
+/// the order in which threads search for it, and so that its search can be delegated to a
+/// [NonceGrinder]; see [grinding::GrindingCoin].
+#[cfg(feature = "std")]
+type Coin<R> = grinding::GrindingCoin<R>;
+#[cfg(not(feature = "std"))]
+type Coin<R> = R;
+
 struct ExecutionProver<H, R>
 where
     H: ElementHasher<BaseField = Felt>,
@@ -137,6 +583,13 @@ where
     options: WinterProofOptions,
     stack_inputs: StackInputs,
     stack_outputs: StackOutputs,
+    environment: ExecutionEnvironment,
+    #[cfg(feature = "std")]
+    memory_limit: Option<usize>,
+    #[cfg(feature = "std")]
+    listener: Option<Arc<dyn ProgressListener>>,
+    #[cfg(feature = "std")]
+    trace_randomizer: Option<Arc<dyn TraceRandomizer>>,
 }
 
 impl<H, R> ExecutionProver<H, R>
@@ -148,18 +601,48 @@ where
         options: ProvingOptions,
         stack_inputs: StackInputs,
         stack_outputs: StackOutputs,
+        environment: ExecutionEnvironment,
     ) -> Self {
         Self {
             random_coin: PhantomData,
+            #[cfg(feature = "std")]
+            memory_limit: options.memory_limit(),
             options: options.into(),
             stack_inputs,
             stack_outputs,
+            environment,
+            #[cfg(feature = "std")]
+            listener: None,
+            #[cfg(feature = "std")]
+            trace_randomizer: None,
         }
     }
 
+    /// Sets the listener notified at the start of every [ProvingPhase].
+    #[cfg(feature = "std")]
+    pub fn with_progress(mut self, listener: Option<Arc<dyn ProgressListener>>) -> Self {
+        self.listener = listener;
+        self
+    }
+
+    /// Sets the randomizer deriving the random elements used to build the auxiliary trace.
+    #[cfg(feature = "std")]
+    pub fn with_trace_randomizer(mut self, randomizer: Option<Arc<dyn TraceRandomizer>>) -> Self {
+        self.trace_randomizer = randomizer;
+        self
+    }
+
     // HELPER FUNCTIONS
     // --------------------------------------------------------------------------------------------
 
+    /// Notifies the progress listener, if there is one, that the specified phase has started.
+    #[cfg(feature = "std")]
+    pub fn report_progress(&self, phase: ProvingPhase) {
+        if let Some(listener) = &self.listener {
+            listener.on_phase(phase);
+        }
+    }
+
     /// Validates the stack inputs against the provided execution trace and returns true if valid.
     fn are_inputs_valid(&self, trace: &ExecutionTrace) -> bool {
         self.stack_inputs
@@ -188,6 +671,9 @@ where
     type HashFn = H;
     type VC = MerkleTreeVC<Self::HashFn>;
     type RandomCoin = R;
+    #[cfg(feature = "std")]
+    type TraceLde<E: FieldElement<BaseField = Felt>> = trace_lde::ExecutionTraceLde<E, H, Self::VC>;
+    #[cfg(not(feature = "std"))]
     type TraceLde<E: FieldElement<BaseField = Felt>> = DefaultTraceLde<E, H, Self::VC>;
     type ConstraintEvaluator<'a, E: FieldElement<BaseField = Felt>> =
         DefaultConstraintEvaluator<'a, ProcessorAir, E>;
@@ -210,7 +696,17 @@ where
         );
 
         let program_info = trace.program_info().clone();
-        PublicInputs::new(program_info, self.stack_inputs.clone(), self.stack_outputs.clone())
+        let pub_inputs =
+            PublicInputs::new(program_info, self.stack_inputs.clone(), self.stack_outputs.clone())
+                .with_environment(self.environment)
+                .with_initial_memory(trace.initial_memory().commitment());
+
+        #[cfg(feature = "std")]
+        if let Some(randomizer) = &self.trace_randomizer {
+            return pub_inputs.with_trace_randomizer(randomizer.clone());
+        }
+
+        pub_inputs
     }
 
     #[maybe_async]
@@ -221,6 +717,19 @@ where
         domain: &StarkDomain<Felt>,
         partition_options: PartitionOptions,
     ) -> (Self::TraceLde<E>, TracePolyTable<E>) {
+        #[cfg(feature = "std")]
+        self.report_progress(ProvingPhase::TraceLde);
+
+        #[cfg(feature = "std")]
+        return trace_lde::ExecutionTraceLde::new(
+            trace_info,
+            main_trace,
+            domain,
+            partition_options,
+            self.memory_limit,
+        );
+
+        #[cfg(not(feature = "std"))]
         DefaultTraceLde::new(trace_info, main_trace, domain, partition_options)
     }
 
@@ -231,6 +740,8 @@ where
         aux_rand_elements: Option<AuxRandElements<E>>,
         composition_coefficients: ConstraintCompositionCoefficients<E>,
     ) -> Self::ConstraintEvaluator<'a, E> {
+        #[cfg(feature = "std")]
+        self.report_progress(ProvingPhase::ConstraintCommitment);
         DefaultConstraintEvaluator::new(air, aux_rand_elements, composition_coefficients)
     }
 
@@ -241,6 +752,8 @@ where
         trace: &Self::Trace,
         aux_rand_elements: &AuxRandElements<E>,
     ) -> ColMatrix<E> {
+        #[cfg(feature = "std")]
+        self.report_progress(ProvingPhase::AuxTraceBuild);
         trace.build_aux_trace(aux_rand_elements.rand_elements()).unwrap()
     }
 
@@ -252,11 +765,16 @@ where
         domain: &StarkDomain<Self::BaseField>,
         partition_options: PartitionOptions,
     ) -> (Self::ConstraintCommitment<E>, CompositionPoly<E>) {
-        DefaultConstraintCommitment::new(
+        let commitment = DefaultConstraintCommitment::new(
             composition_poly_trace,
             num_constraint_composition_columns,
             domain,
             partition_options,
-        )
+        );
+
+        // the remaining work of the prover consists of the DEEP composition and FRI
+        #[cfg(feature = "std")]
+        self.report_progress(ProvingPhase::Fri);
+        commitment
     }
 }
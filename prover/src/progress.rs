@@ -0,0 +1,96 @@
+//! Progress reporting.
+//!
+//! Generating a proof goes through a fixed sequence of [ProvingPhase]s. A [ProgressListener] is
+//! notified at the start of each phase, which allows reporting more than "still running" for
+//! proofs which take minutes to generate.
+
+use std::sync::mpsc::{Sender, SyncSender};
+
+// PROVING PHASE
+// ================================================================================================
+
+/// A phase of proof generation.
+///
+/// Phases are entered in the order in which they are declared, and each phase is entered exactly
+/// once per proof.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ProvingPhase {
+    /// The program is executed to build its execution trace.
+    TraceBuild,
+    /// The low-degree extension of the main segment of the execution trace is computed and
+    /// committed to.
+    TraceLde,
+    /// The auxiliary segment of the execution trace is built, extended and committed to.
+    AuxTraceBuild,
+    /// The constraints are evaluated over the low-degree extension domain and the resulting
+    /// composition polynomial is committed to.
+    ConstraintCommitment,
+    /// The DEEP composition polynomial is built, the FRI layers are computed, and the queries are
+    /// answered.
+    Fri,
+}
+
+impl ProvingPhase {
+    /// All phases, in the order in which they are entered.
+    pub const ALL: [Self; 5] = [
+        Self::TraceBuild,
+        Self::TraceLde,
+        Self::AuxTraceBuild,
+        Self::ConstraintCommitment,
+        Self::Fri,
+    ];
+
+    /// Returns a short human-readable name of this phase.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::TraceBuild => "trace build",
+            Self::TraceLde => "trace LDE",
+            Self::AuxTraceBuild => "auxiliary trace build",
+            Self::ConstraintCommitment => "constraint commitment",
+            Self::Fri => "FRI",
+        }
+    }
+}
+
+impl core::fmt::Display for ProvingPhase {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+// PROGRESS LISTENER
+// ================================================================================================
+
+/// A listener notified of the progress of proof generation.
+///
+/// The listener is invoked from the proving thread at the start of every [ProvingPhase], and
+/// blocks proof generation until it returns; it should thus return quickly.
+///
+/// This trait is implemented for all closures taking a [ProvingPhase], and for the sending halves
+/// of [std::sync::mpsc] channels, which allows receiving the phases on another thread. Sending
+/// errors, i.e., a dropped receiver, are ignored.
+pub trait ProgressListener: Send + Sync {
+    /// Notifies the listener that the specified phase has started.
+    fn on_phase(&self, phase: ProvingPhase);
+}
+
+impl<F> ProgressListener for F
+where
+    F: Fn(ProvingPhase) + Send + Sync,
+{
+    fn on_phase(&self, phase: ProvingPhase) {
+        self(phase)
+    }
+}
+
+impl ProgressListener for Sender<ProvingPhase> {
+    fn on_phase(&self, phase: ProvingPhase) {
+        let _ = self.send(phase);
+    }
+}
+
+impl ProgressListener for SyncSender<ProvingPhase> {
+    fn on_phase(&self, phase: ProvingPhase) {
+        let _ = self.send(phase);
+    }
+}
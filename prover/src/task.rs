@@ -0,0 +1,144 @@
+//! Proving on a background thread.
+//!
+//! [crate::prove_async()] moves the program, its inputs and the host to a new thread which
+//! executes and proves the program, and returns a [ProvingTask] through which the result is
+//! retrieved. The task is a [Future], so it can be awaited from any async runtime without the
+//! proving work blocking the runtime's threads; it can also be waited for synchronously.
+//!
+//! The proving thread only drives proof generation: with the `concurrent` feature, the bulk of the
+//! work is performed on the rayon thread pool.
+
+use alloc::sync::Arc;
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll, Waker},
+};
+use std::{
+    panic,
+    sync::Mutex,
+    thread::{self, JoinHandle},
+};
+
+use processor::ExecutionError;
+
+use crate::{ExecutionProof, StackOutputs};
+
+/// The result of a [ProvingTask].
+pub type ProvingResult = Result<(StackOutputs, ExecutionProof), ExecutionError>;
+
+// PROVING TASK
+// ================================================================================================
+
+/// A handle to a proof being generated on a background thread, returned by
+/// [crate::prove_async()].
+///
+/// The result is retrieved by awaiting the task, or via [ProvingTask::wait]. Dropping the task
+/// does not stop proof generation, but discards its result.
+///
+/// If proof generation panics, the panic is propagated to the thread retrieving the result.
+#[derive(Debug)]
+pub struct ProvingTask {
+    handle: Option<JoinHandle<ProvingResult>>,
+    state: Arc<Mutex<TaskState>>,
+}
+
+impl ProvingTask {
+    /// Spawns a new thread running `prove` and returns a handle to it.
+    pub(crate) fn spawn<F>(prove: F) -> Self
+    where
+        F: FnOnce() -> ProvingResult + Send + 'static,
+    {
+        let state = Arc::new(Mutex::new(TaskState::default()));
+        let guard = CompletionGuard(state.clone());
+        let handle = thread::Builder::new()
+            .name("miden-prover".into())
+            .spawn(move || {
+                // the guard marks the task as completed even if `prove` panics
+                let _guard = guard;
+                prove()
+            })
+            .expect("failed to spawn the proving thread");
+
+        Self { handle: Some(handle), state }
+    }
+
+    /// Returns true if proof generation has completed, i.e., if retrieving the result of this task
+    /// will not block.
+    pub fn is_finished(&self) -> bool {
+        self.state.lock().expect("task state poisoned").completed
+    }
+
+    /// Blocks the current thread until proof generation completes, and returns its result.
+    pub fn wait(mut self) -> ProvingResult {
+        self.join()
+    }
+
+    /// Joins the proving thread, propagating its panic if there is one.
+    fn join(&mut self) -> ProvingResult {
+        let handle = self.handle.take().expect("result of the proving task already retrieved");
+        match handle.join() {
+            Ok(result) => result,
+            Err(payload) => panic::resume_unwind(payload),
+        }
+    }
+}
+
+impl Future for ProvingTask {
+    type Output = ProvingResult;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        {
+            let mut state = this.state.lock().expect("task state poisoned");
+            if !state.completed {
+                state.waker = Some(cx.waker().clone());
+                return Poll::Pending;
+            }
+        }
+
+        // the thread is about to exit once the task is completed, so joining it does not block
+        Poll::Ready(this.join())
+    }
+}
+
+// HELPERS
+// ================================================================================================
+
+/// The state shared between a [ProvingTask] and its thread.
+#[derive(Debug, Default)]
+struct TaskState {
+    completed: bool,
+    waker: Option<Waker>,
+}
+
+/// Marks a task as completed and wakes the last waker which polled it when dropped.
+struct CompletionGuard(Arc<Mutex<TaskState>>);
+
+impl Drop for CompletionGuard {
+    fn drop(&mut self) {
+        let waker = {
+            let mut state = self.0.lock().unwrap_or_else(|err| err.into_inner());
+            state.completed = true;
+            state.waker.take()
+        };
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+/// Runs the specified future to completion on the current thread.
+///
+/// The futures of the prover do not wait on external events, so they complete when first polled.
+#[cfg(feature = "async")]
+pub(crate) fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = core::pin::pin!(future);
+    let mut cx = Context::from_waker(Waker::noop());
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+        thread::yield_now();
+    }
+}
@@ -0,0 +1,659 @@
+use std::{
+    cell::RefCell,
+    format,
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    marker::PhantomData,
+    mem::size_of,
+    path::PathBuf,
+    sync::atomic::{AtomicUsize, Ordering},
+    vec,
+    vec::Vec,
+};
+
+use air::PartitionOptions;
+use processor::{
+    crypto::ElementHasher,
+    math::{Felt, FieldElement, StarkField, fft},
+};
+use tracing::{Level, event, info_span};
+use winter_prover::{
+    DefaultTraceLde, EvaluationFrame, StarkDomain, TraceInfo, TraceLde, TracePolyTable,
+    crypto::VectorCommitment, matrix::ColMatrix, proof::Queries,
+};
+
+#[cfg(test)]
+mod tests;
+
+// EXECUTION TRACE LOW DEGREE EXTENSION
+// ================================================================================================
+
+/// Low-degree extension of the execution trace used by the prover.
+///
+/// The extended trace is kept in memory unless it does not fit in the memory limit specified by
+/// [crate::ProvingOptions::with_memory_limit], in which case it is spilled to disk (see
+/// [SpilledTraceLde]).
+pub enum ExecutionTraceLde<E, H, V>
+where
+    E: FieldElement<BaseField = Felt>,
+    H: ElementHasher<BaseField = Felt>,
+    V: VectorCommitment<H>,
+{
+    InMemory(DefaultTraceLde<E, H, V>),
+    Spilled(SpilledTraceLde<E, H, V>),
+}
+
+impl<E, H, V> ExecutionTraceLde<E, H, V>
+where
+    E: FieldElement<BaseField = Felt>,
+    H: ElementHasher<BaseField = Felt>,
+    V: VectorCommitment<H>,
+{
+    /// Extends the main trace segment and commits to it, spilling the extension to disk if its
+    /// estimated size, together with the extension of the auxiliary trace segment, exceeds
+    /// `memory_limit` bytes.
+    ///
+    /// Returns a tuple containing a [TracePolyTable] with the trace polynomials for the main trace
+    /// segment and the new [ExecutionTraceLde].
+    pub fn new(
+        trace_info: &TraceInfo,
+        main_trace: &ColMatrix<Felt>,
+        domain: &StarkDomain<Felt>,
+        partition_options: PartitionOptions,
+        memory_limit: Option<usize>,
+    ) -> (Self, TracePolyTable<E>) {
+        let lde_size = trace_lde_size::<E>(trace_info, domain);
+        match memory_limit {
+            Some(memory_limit) if SPILLING_SUPPORTED && lde_size > memory_limit => {
+                event!(
+                    Level::INFO,
+                    "Spilling trace LDE of {} MiB to disk to stay within the memory limit of {} MiB",
+                    lde_size >> 20,
+                    memory_limit >> 20
+                );
+                let (trace_lde, trace_polys) =
+                    SpilledTraceLde::new(trace_info, main_trace, domain, partition_options);
+                (Self::Spilled(trace_lde), trace_polys)
+            },
+            _ => {
+                let (trace_lde, trace_polys) =
+                    DefaultTraceLde::new(trace_info, main_trace, domain, partition_options);
+                (Self::InMemory(trace_lde), trace_polys)
+            },
+        }
+    }
+}
+
+impl<E, H, V> TraceLde<E> for ExecutionTraceLde<E, H, V>
+where
+    E: FieldElement<BaseField = Felt>,
+    H: ElementHasher<BaseField = Felt> + Sync,
+    V: VectorCommitment<H> + Sync,
+{
+    type HashFn = H;
+    type VC = V;
+
+    fn get_main_trace_commitment(&self) -> H::Digest {
+        match self {
+            Self::InMemory(trace_lde) => trace_lde.get_main_trace_commitment(),
+            Self::Spilled(trace_lde) => trace_lde.get_main_trace_commitment(),
+        }
+    }
+
+    fn set_aux_trace(
+        &mut self,
+        aux_trace: &ColMatrix<E>,
+        domain: &StarkDomain<Felt>,
+    ) -> (ColMatrix<E>, H::Digest) {
+        match self {
+            Self::InMemory(trace_lde) => trace_lde.set_aux_trace(aux_trace, domain),
+            Self::Spilled(trace_lde) => trace_lde.set_aux_trace(aux_trace, domain),
+        }
+    }
+
+    fn read_main_trace_frame_into(&self, lde_step: usize, frame: &mut EvaluationFrame<Felt>) {
+        match self {
+            Self::InMemory(trace_lde) => trace_lde.read_main_trace_frame_into(lde_step, frame),
+            Self::Spilled(trace_lde) => trace_lde.read_main_trace_frame_into(lde_step, frame),
+        }
+    }
+
+    fn read_aux_trace_frame_into(&self, lde_step: usize, frame: &mut EvaluationFrame<E>) {
+        match self {
+            Self::InMemory(trace_lde) => trace_lde.read_aux_trace_frame_into(lde_step, frame),
+            Self::Spilled(trace_lde) => trace_lde.read_aux_trace_frame_into(lde_step, frame),
+        }
+    }
+
+    fn query(&self, positions: &[usize]) -> Vec<Queries> {
+        match self {
+            Self::InMemory(trace_lde) => trace_lde.query(positions),
+            Self::Spilled(trace_lde) => trace_lde.query(positions),
+        }
+    }
+
+    fn trace_len(&self) -> usize {
+        match self {
+            Self::InMemory(trace_lde) => trace_lde.trace_len(),
+            Self::Spilled(trace_lde) => trace_lde.trace_len(),
+        }
+    }
+
+    fn blowup(&self) -> usize {
+        match self {
+            Self::InMemory(trace_lde) => trace_lde.blowup(),
+            Self::Spilled(trace_lde) => trace_lde.blowup(),
+        }
+    }
+
+    fn trace_info(&self) -> &TraceInfo {
+        match self {
+            Self::InMemory(trace_lde) => trace_lde.trace_info(),
+            Self::Spilled(trace_lde) => trace_lde.trace_info(),
+        }
+    }
+}
+
+// SPILLED TRACE LOW DEGREE EXTENSION
+// ================================================================================================
+
+/// Low-degree extension of the execution trace which is stored in temporary files rather than in
+/// memory.
+///
+/// The LDE domain is the union of `blowup` cosets of the trace domain, and the extension is built
+/// and committed to one coset at a time: the trace polynomials are evaluated over the coset, the
+/// resulting rows are hashed into the leaves of the commitment, and then appended to the file of
+/// the segment. Thus, the memory used to build the extension is about the size of the execution
+/// trace, rather than `blowup` times this size, and only the row hashes are kept in memory.
+///
+/// Rows are read back from the files whenever the prover evaluates constraints, in blocks of
+/// consecutive rows of a coset, or opens the commitments at the query positions.
+pub struct SpilledTraceLde<E, H, V>
+where
+    E: FieldElement<BaseField = Felt>,
+    H: ElementHasher<BaseField = Felt>,
+    V: VectorCommitment<H>,
+{
+    // low-degree extension of the main segment of the trace
+    main_segment_lde: SpilledSegment<Felt>,
+    // commitment to the main segment of the trace
+    main_segment_oracles: V,
+    // low-degree extension of the auxiliary segment of the trace
+    aux_segment_lde: Option<SpilledSegment<E>>,
+    // commitment to the auxiliary segment of the trace
+    aux_segment_oracles: Option<V>,
+    blowup: usize,
+    trace_info: TraceInfo,
+    partition_options: PartitionOptions,
+    _h: PhantomData<H>,
+}
+
+impl<E, H, V> SpilledTraceLde<E, H, V>
+where
+    E: FieldElement<BaseField = Felt>,
+    H: ElementHasher<BaseField = Felt>,
+    V: VectorCommitment<H>,
+{
+    /// Takes the main trace segment columns as input, interpolates them into polynomials in
+    /// coefficient form, evaluates the polynomials over the LDE domain one coset at a time,
+    /// spilling the evaluations to disk, and commits to them.
+    ///
+    /// Returns a tuple containing a [TracePolyTable] with the trace polynomials for the main trace
+    /// segment and the new [SpilledTraceLde].
+    ///
+    /// # Panics
+    /// Panics if the extended trace cannot be written to a temporary file.
+    pub fn new(
+        trace_info: &TraceInfo,
+        main_trace: &ColMatrix<Felt>,
+        domain: &StarkDomain<Felt>,
+        partition_options: PartitionOptions,
+    ) -> (Self, TracePolyTable<E>) {
+        let (main_segment_lde, main_segment_oracles, main_segment_polys) =
+            SpilledSegment::build::<H, V>(main_trace, domain, partition_options);
+
+        let trace_lde = SpilledTraceLde {
+            main_segment_lde,
+            main_segment_oracles,
+            aux_segment_lde: None,
+            aux_segment_oracles: None,
+            blowup: domain.trace_to_lde_blowup(),
+            trace_info: trace_info.clone(),
+            partition_options,
+            _h: PhantomData,
+        };
+
+        (trace_lde, TracePolyTable::new(main_segment_polys))
+    }
+}
+
+impl<E, H, V> TraceLde<E> for SpilledTraceLde<E, H, V>
+where
+    E: FieldElement<BaseField = Felt>,
+    H: ElementHasher<BaseField = Felt> + Sync,
+    V: VectorCommitment<H> + Sync,
+{
+    type HashFn = H;
+    type VC = V;
+
+    /// Returns the commitment to the low-degree extension of the main trace segment.
+    fn get_main_trace_commitment(&self) -> H::Digest {
+        self.main_segment_oracles.commitment()
+    }
+
+    /// Takes auxiliary trace segment columns as input, interpolates them into polynomials in
+    /// coefficient form, evaluates the polynomials over the LDE domain one coset at a time,
+    /// spilling the evaluations to disk, and commits to them.
+    ///
+    /// # Panics
+    /// Panics if the auxiliary trace has been set already, if its number of rows does not match
+    /// the main trace, or if its extension cannot be written to a temporary file.
+    fn set_aux_trace(
+        &mut self,
+        aux_trace: &ColMatrix<E>,
+        domain: &StarkDomain<Felt>,
+    ) -> (ColMatrix<E>, H::Digest) {
+        assert!(
+            usize::from(self.aux_segment_lde.is_some()) < self.trace_info.num_aux_segments(),
+            "the auxiliary trace has already been added"
+        );
+        assert_eq!(
+            self.main_segment_lde.num_rows(),
+            aux_trace.num_rows() * domain.trace_to_lde_blowup(),
+            "the number of rows in the auxiliary segment must be the same as in the main segment"
+        );
+
+        let (aux_segment_lde, aux_segment_oracles, aux_segment_polys) =
+            SpilledSegment::build::<H, V>(aux_trace, domain, self.partition_options);
+
+        let commitment = aux_segment_oracles.commitment();
+        self.aux_segment_lde = Some(aux_segment_lde);
+        self.aux_segment_oracles = Some(aux_segment_oracles);
+
+        (aux_segment_polys, commitment)
+    }
+
+    /// Reads current and next rows from the main trace segment into the specified frame.
+    fn read_main_trace_frame_into(&self, lde_step: usize, frame: &mut EvaluationFrame<Felt>) {
+        // at the end of the trace, next state wraps around and we read the first step again
+        let next_lde_step = (lde_step + self.blowup()) % self.trace_len();
+
+        self.main_segment_lde.read_row_into(lde_step, frame.current_mut());
+        self.main_segment_lde.read_row_into(next_lde_step, frame.next_mut());
+    }
+
+    /// Reads current and next rows from the auxiliary trace segment into the specified frame.
+    fn read_aux_trace_frame_into(&self, lde_step: usize, frame: &mut EvaluationFrame<E>) {
+        // at the end of the trace, next state wraps around and we read the first step again
+        let next_lde_step = (lde_step + self.blowup()) % self.trace_len();
+
+        let segment = self.aux_segment_lde.as_ref().expect("expected aux segment to be present");
+        segment.read_row_into(lde_step, frame.current_mut());
+        segment.read_row_into(next_lde_step, frame.next_mut());
+    }
+
+    /// Returns trace table rows at the specified positions along with an opening proof to these
+    /// rows against the already computed commitment.
+    fn query(&self, positions: &[usize]) -> Vec<Queries> {
+        let mut result = vec![build_segment_queries::<Felt, H, V>(
+            &self.main_segment_lde,
+            &self.main_segment_oracles,
+            positions,
+        )];
+
+        if let Some(ref segment_oracles) = self.aux_segment_oracles {
+            let segment_lde =
+                self.aux_segment_lde.as_ref().expect("expected aux segment to be present");
+            result.push(build_segment_queries::<E, H, V>(segment_lde, segment_oracles, positions));
+        }
+
+        result
+    }
+
+    /// Returns the number of rows in the extended execution trace.
+    fn trace_len(&self) -> usize {
+        self.main_segment_lde.num_rows()
+    }
+
+    /// Returns blowup factor which was used to extend original execution trace into trace LDE.
+    fn blowup(&self) -> usize {
+        self.blowup
+    }
+
+    /// Returns the trace info of the execution trace.
+    fn trace_info(&self) -> &TraceInfo {
+        &self.trace_info
+    }
+}
+
+// SPILLED SEGMENT
+// ================================================================================================
+
+/// Whether the rows of a [SpilledSegment] can be read concurrently on the target platform.
+const SPILLING_SUPPORTED: bool = cfg!(any(unix, windows));
+
+/// Counter used to give unique ids, and thus unique file names, to spilled segments.
+static NEXT_SPILL_FILE_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Number of consecutive rows of a coset read from the file of a segment at once when reading
+/// evaluation frames.
+const ROW_BLOCK_LEN: usize = 32;
+
+/// Maximum number of blocks of rows kept by each thread.
+///
+/// Evaluating constraints over consecutive steps of the LDE domain reads from every coset of both
+/// segments in turn, so a thread needs one block per coset and segment to read each block once.
+const MAX_ROW_BLOCKS: usize = 32;
+
+std::thread_local! {
+    /// The blocks of rows most recently read by this thread from spilled segments, with the most
+    /// recently used block last.
+    static ROW_BLOCKS: RefCell<Vec<RowBlock>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Consecutive rows of a coset of a [SpilledSegment], as read from the file of the segment.
+struct RowBlock {
+    segment_id: usize,
+    coset_idx: usize,
+    first_row: usize,
+    bytes: Vec<u8>,
+}
+
+/// Low-degree extension of a single trace segment, stored in a temporary file.
+///
+/// The file contains the rows of the extension grouped by coset of the trace domain: row `i` of
+/// the extension is the `i / blowup`-th row of coset `i % blowup`. Each element is stored as the
+/// little-endian encoding of its base field elements.
+///
+/// The file is deleted when the segment is dropped.
+struct SpilledSegment<E: FieldElement<BaseField = Felt>> {
+    id: usize,
+    file: File,
+    path: PathBuf,
+    num_cols: usize,
+    coset_len: usize,
+    num_cosets: usize,
+    _e: PhantomData<E>,
+}
+
+impl<E: FieldElement<BaseField = Felt>> SpilledSegment<E> {
+    /// Interpolates the columns of `trace`, evaluates them over the LDE domain one coset at a time,
+    /// and writes the evaluations to a new temporary file.
+    ///
+    /// Returns the spilled segment, the commitment to its rows, and the trace polynomials.
+    fn build<H, V>(
+        trace: &ColMatrix<E>,
+        domain: &StarkDomain<Felt>,
+        partition_options: PartitionOptions,
+    ) -> (Self, V, ColMatrix<E>)
+    where
+        H: ElementHasher<BaseField = Felt>,
+        V: VectorCommitment<H>,
+    {
+        let span = info_span!(
+            "extend_execution_trace",
+            num_cols = trace.num_cols(),
+            blowup = domain.trace_to_lde_blowup()
+        )
+        .entered();
+        let trace_polys = trace.interpolate_columns();
+        let (segment, row_hashes) = Self::spill::<H>(&trace_polys, domain, partition_options)
+            .unwrap_or_else(|err| panic!("failed to spill the trace LDE to disk: {err}"));
+        drop(span);
+
+        let commitment_domain_size = row_hashes.len();
+        let trace_vector_com =
+            info_span!("compute_execution_trace_commitment", commitment_domain_size).in_scope(
+                || V::new(row_hashes).expect("failed to construct trace vector commitment"),
+            );
+
+        (segment, trace_vector_com, trace_polys)
+    }
+
+    /// Evaluates `trace_polys` over each coset of the trace domain in the LDE domain, writes the
+    /// resulting rows to a new temporary file, and returns the segment together with the hashes
+    /// of its rows.
+    fn spill<H: ElementHasher<BaseField = Felt>>(
+        trace_polys: &ColMatrix<E>,
+        domain: &StarkDomain<Felt>,
+        partition_options: PartitionOptions,
+    ) -> io::Result<(Self, Vec<H::Digest>)> {
+        let num_cols = trace_polys.num_cols();
+        let coset_len = domain.trace_length();
+        let num_cosets = domain.trace_to_lde_blowup();
+        let partition_size = partition_options.partition_size::<E>(num_cols);
+
+        let id = NEXT_SPILL_FILE_ID.fetch_add(1, Ordering::Relaxed);
+        let path =
+            std::env::temp_dir().join(format!("miden-trace-lde-{}-{id}.bin", std::process::id()));
+        let file = File::options().read(true).write(true).create_new(true).open(&path)?;
+        let segment = Self {
+            id,
+            file,
+            path,
+            num_cols,
+            coset_len,
+            num_cosets,
+            _e: PhantomData,
+        };
+
+        // the i-th coset is the trace domain shifted by offset * g^i, where g generates the LDE
+        // domain; thus, the k-th row of the i-th coset is the row (i + k * blowup) of the LDE
+        let g = Felt::get_root_of_unity(domain.lde_domain_size().ilog2());
+        let mut row_hashes = vec![H::Digest::default(); domain.lde_domain_size()];
+        let mut row = vec![E::ZERO; num_cols];
+        let mut writer = BufWriter::new(&segment.file);
+        for coset_idx in 0..num_cosets {
+            let coset_offset = domain.offset() * g.exp_vartime(coset_idx as u64);
+            let columns = trace_polys
+                .columns()
+                .map(|poly| {
+                    fft::evaluate_poly_with_offset(poly, domain.trace_twiddles(), coset_offset, 1)
+                })
+                .collect::<Vec<_>>();
+
+            for row_idx in 0..coset_len {
+                for (value, column) in row.iter_mut().zip(columns.iter()) {
+                    *value = column[row_idx];
+                }
+                row_hashes[coset_idx + row_idx * num_cosets] =
+                    hash_row::<E, H>(&row, partition_size);
+                for value in E::slice_as_base_elements(&row) {
+                    writer.write_all(&value.as_int().to_le_bytes())?;
+                }
+            }
+        }
+        writer.flush()?;
+        drop(writer);
+
+        Ok((segment, row_hashes))
+    }
+
+    /// Returns the number of rows in the extension of this segment.
+    fn num_rows(&self) -> usize {
+        self.coset_len * self.num_cosets
+    }
+
+    /// Returns the number of bytes used to store a row of this segment.
+    fn row_size(&self) -> usize {
+        self.num_cols * E::EXTENSION_DEGREE * size_of::<u64>()
+    }
+
+    /// Reads the row at the specified step of the LDE domain into `row`.
+    ///
+    /// The row is read from the block of rows of its coset cached by the current thread, if there
+    /// is one. Otherwise, the rows of the coset starting at this row are read as a new block, so
+    /// that reading the rows at consecutive steps of the LDE domain (as done when evaluating
+    /// constraints) takes a single read per block and coset rather than per row.
+    ///
+    /// # Panics
+    /// Panics if the row cannot be read from the file of this segment.
+    fn read_row_into(&self, lde_step: usize, row: &mut [E]) {
+        debug_assert_eq!(row.len(), self.num_cols);
+        let coset_idx = lde_step % self.num_cosets;
+        let row_idx = lde_step / self.num_cosets;
+        let row_size = self.row_size();
+
+        ROW_BLOCKS.with_borrow_mut(|blocks| {
+            let cached = blocks.iter().position(|block| {
+                block.segment_id == self.id
+                    && block.coset_idx == coset_idx
+                    && (block.first_row..block.first_row + block.bytes.len() / row_size)
+                        .contains(&row_idx)
+            });
+            let block = match cached {
+                Some(position) => blocks.remove(position),
+                None => {
+                    if blocks.len() == MAX_ROW_BLOCKS {
+                        blocks.remove(0);
+                    }
+                    self.read_block(coset_idx, row_idx)
+                },
+            };
+
+            let start = (row_idx - block.first_row) * row_size;
+            decode_row(&block.bytes[start..start + row_size], row);
+            blocks.push(block);
+        });
+    }
+
+    /// Reads the block of at most [ROW_BLOCK_LEN] rows of the specified coset starting at the
+    /// specified row.
+    ///
+    /// # Panics
+    /// Panics if the rows cannot be read from the file of this segment.
+    fn read_block(&self, coset_idx: usize, first_row: usize) -> RowBlock {
+        let num_rows = ROW_BLOCK_LEN.min(self.coset_len - first_row);
+        let mut bytes = vec![0u8; num_rows * self.row_size()];
+        self.read_rows(coset_idx, first_row, &mut bytes);
+        RowBlock {
+            segment_id: self.id,
+            coset_idx,
+            first_row,
+            bytes,
+        }
+    }
+
+    /// Returns the row at the specified step of the LDE domain.
+    ///
+    /// Unlike [Self::read_row_into], this reads the row only, as the rows of the extension are
+    /// opened at random positions.
+    fn row(&self, lde_step: usize) -> Vec<E> {
+        let mut bytes = vec![0u8; self.row_size()];
+        self.read_rows(lde_step % self.num_cosets, lde_step / self.num_cosets, &mut bytes);
+
+        let mut row = vec![E::ZERO; self.num_cols];
+        decode_row(&bytes, &mut row);
+        row
+    }
+
+    /// Fills `bytes` with the rows of the specified coset starting at the specified row.
+    ///
+    /// # Panics
+    /// Panics if the rows cannot be read from the file of this segment.
+    fn read_rows(&self, coset_idx: usize, first_row: usize, bytes: &mut [u8]) {
+        let offset = (coset_idx * self.coset_len + first_row) * self.row_size();
+        read_exact_at(&self.file, bytes, offset as u64)
+            .unwrap_or_else(|err| panic!("failed to read the spilled trace LDE: {err}"));
+    }
+}
+
+impl<E: FieldElement<BaseField = Felt>> Drop for SpilledSegment<E> {
+    fn drop(&mut self) {
+        // the blocks cached by other threads are evicted as these threads read other blocks
+        ROW_BLOCKS.with_borrow_mut(|blocks| blocks.retain(|block| block.segment_id != self.id));
+
+        // the file is only a cache of the extension, so failing to remove it is not an error
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the estimated number of bytes needed to hold the low-degree extensions of the main and
+/// auxiliary segments of the trace in memory.
+fn trace_lde_size<E: FieldElement<BaseField = Felt>>(
+    trace_info: &TraceInfo,
+    domain: &StarkDomain<Felt>,
+) -> usize {
+    let aux_width = trace_info.aux_segment_width() * <E as FieldElement>::EXTENSION_DEGREE;
+    let row_width = trace_info.main_trace_width() + aux_width;
+    domain.lde_domain_size() * row_width * size_of::<Felt>()
+}
+
+/// Hashes a row of the extended trace in the same way as
+/// [winter_prover::matrix::RowMatrix::commit_to_rows], so that the commitment to a spilled segment
+/// is identical to the commitment to the same segment kept in memory.
+fn hash_row<E, H>(row: &[E], partition_size: usize) -> H::Digest
+where
+    E: FieldElement<BaseField = Felt>,
+    H: ElementHasher<BaseField = Felt>,
+{
+    if partition_size == row.len() {
+        H::hash_elements(row)
+    } else {
+        let partition_hashes = row.chunks(partition_size).map(H::hash_elements).collect::<Vec<_>>();
+        H::merge_many(&partition_hashes)
+    }
+}
+
+/// Decodes a row of a spilled segment from the little-endian encoding of its base field elements.
+fn decode_row<E: FieldElement<BaseField = Felt>>(bytes: &[u8], row: &mut [E]) {
+    let elements = bytes
+        .chunks_exact(size_of::<u64>())
+        .map(|chunk| Felt::new(u64::from_le_bytes(chunk.try_into().unwrap())))
+        .collect::<Vec<_>>();
+    row.copy_from_slice(E::slice_from_base_elements(&elements));
+}
+
+/// Reads the rows of `segment_lde` at the specified positions, and opens `segment_vector_com` at
+/// these positions.
+fn build_segment_queries<E, H, V>(
+    segment_lde: &SpilledSegment<E>,
+    segment_vector_com: &V,
+    positions: &[usize],
+) -> Queries
+where
+    E: FieldElement<BaseField = Felt>,
+    H: ElementHasher<BaseField = Felt>,
+    V: VectorCommitment<H>,
+{
+    let trace_states = positions.iter().map(|&pos| segment_lde.row(pos)).collect::<Vec<_>>();
+
+    let trace_proof = segment_vector_com
+        .open_many(positions)
+        .expect("failed to generate a batch opening proof for trace queries");
+
+    Queries::new::<H, E, V>(trace_proof.1, trace_states)
+}
+
+/// Reads exactly `buf.len()` bytes of `file` starting at `offset`, without moving the cursor of
+/// the file, so that several threads can read the file at the same time.
+#[cfg(unix)]
+fn read_exact_at(file: &File, buf: &mut [u8], offset: u64) -> io::Result<()> {
+    std::os::unix::fs::FileExt::read_exact_at(file, buf, offset)
+}
+
+/// Reads exactly `buf.len()` bytes of `file` starting at `offset`.
+#[cfg(windows)]
+fn read_exact_at(file: &File, mut buf: &mut [u8], mut offset: u64) -> io::Result<()> {
+    use std::os::windows::fs::FileExt;
+
+    while !buf.is_empty() {
+        match file.seek_read(buf, offset)? {
+            0 => return Err(io::ErrorKind::UnexpectedEof.into()),
+            num_read => {
+                buf = &mut buf[num_read..];
+                offset += num_read as u64;
+            },
+        }
+    }
+    Ok(())
+}
+
+/// Positional reads are not available on this platform, so segments are never spilled.
+#[cfg(not(any(unix, windows)))]
+fn read_exact_at(_file: &File, _buf: &mut [u8], _offset: u64) -> io::Result<()> {
+    Err(io::ErrorKind::Unsupported.into())
+}
@@ -0,0 +1,199 @@
+use alloc::vec::Vec;
+
+use air::PartitionOptions;
+use processor::{
+    crypto::{Blake3_192, Rpo256},
+    math::{Felt, StarkField, fft},
+};
+use winter_prover::{
+    DefaultTraceLde, EvaluationFrame, StarkDomain, TraceInfo, TraceLde,
+    crypto::{ElementHasher, MerkleTree},
+    math::fields::QuadExtension,
+    matrix::ColMatrix,
+};
+
+use super::{ExecutionTraceLde, ROW_BLOCK_LEN, ROW_BLOCKS, SpilledTraceLde};
+
+type QuadFelt = QuadExtension<Felt>;
+
+const NUM_ROWS: usize = 1 << 6;
+const MAIN_WIDTH: usize = 13;
+const AUX_WIDTH: usize = 3;
+const BLOWUP: usize = 8;
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn rpo_spilled_trace_lde_matches_in_memory() {
+    spilled_trace_lde_matches_in_memory::<Rpo256>(PartitionOptions::default());
+    spilled_trace_lde_matches_in_memory::<Rpo256>(PartitionOptions::new(4, 8));
+}
+
+#[test]
+fn blake3_spilled_trace_lde_matches_in_memory() {
+    spilled_trace_lde_matches_in_memory::<Blake3_192>(PartitionOptions::default());
+}
+
+#[test]
+fn trace_lde_is_spilled_above_memory_limit() {
+    let trace_info = get_trace_info();
+    let main_trace = gen_main_trace();
+    let domain = get_domain();
+    let new_trace_lde = |memory_limit| {
+        ExecutionTraceLde::<QuadFelt, Rpo256, MerkleTree<Rpo256>>::new(
+            &trace_info,
+            &main_trace,
+            &domain,
+            PartitionOptions::default(),
+            memory_limit,
+        )
+        .0
+    };
+
+    // the main and auxiliary segments take 13 + 3 * 2 elements per row of the LDE
+    let lde_size = NUM_ROWS * BLOWUP * (MAIN_WIDTH + AUX_WIDTH * 2) * 8;
+
+    assert!(matches!(new_trace_lde(None), ExecutionTraceLde::InMemory(_)));
+    assert!(matches!(new_trace_lde(Some(lde_size)), ExecutionTraceLde::InMemory(_)));
+    assert!(matches!(new_trace_lde(Some(lde_size - 1)), ExecutionTraceLde::Spilled(_)));
+}
+
+#[test]
+fn spilled_trace_lde_file_is_removed_on_drop() {
+    let (trace_lde, _) = SpilledTraceLde::<QuadFelt, Rpo256, MerkleTree<Rpo256>>::new(
+        &get_trace_info(),
+        &gen_main_trace(),
+        &get_domain(),
+        PartitionOptions::default(),
+    );
+    let path = trace_lde.main_segment_lde.path.clone();
+    assert!(path.exists());
+
+    drop(trace_lde);
+    assert!(!path.exists());
+}
+
+#[test]
+fn spilled_trace_lde_frames_are_read_in_blocks() {
+    let (trace_lde, _) = SpilledTraceLde::<QuadFelt, Rpo256, MerkleTree<Rpo256>>::new(
+        &get_trace_info(),
+        &gen_main_trace(),
+        &get_domain(),
+        PartitionOptions::default(),
+    );
+    let segment_id = trace_lde.main_segment_lde.id;
+    let num_blocks = || {
+        ROW_BLOCKS.with_borrow(|blocks| {
+            blocks.iter().filter(|block| block.segment_id == segment_id).count()
+        })
+    };
+
+    // the frames at the first steps of the LDE domain read the first block of every coset
+    let mut frame = EvaluationFrame::<Felt>::new(MAIN_WIDTH);
+    for lde_step in 0..BLOWUP {
+        TraceLde::<QuadFelt>::read_main_trace_frame_into(&trace_lde, lde_step, &mut frame);
+    }
+    assert_eq!(num_blocks(), BLOWUP);
+
+    // which contain the rows of the following steps as well
+    for lde_step in BLOWUP..ROW_BLOCK_LEN * BLOWUP - BLOWUP {
+        TraceLde::<QuadFelt>::read_main_trace_frame_into(&trace_lde, lde_step, &mut frame);
+    }
+    assert_eq!(num_blocks(), BLOWUP);
+
+    drop(trace_lde);
+    assert_eq!(num_blocks(), 0);
+}
+
+// TEST FUNCTIONS
+// ================================================================================================
+
+fn spilled_trace_lde_matches_in_memory<H>(partition_options: PartitionOptions)
+where
+    H: ElementHasher<BaseField = Felt> + Sync,
+{
+    let trace_info = get_trace_info();
+    let main_trace = gen_main_trace();
+    let aux_trace = gen_aux_trace();
+    let domain = get_domain();
+
+    let (mut expected, expected_polys) = DefaultTraceLde::<QuadFelt, H, MerkleTree<H>>::new(
+        &trace_info,
+        &main_trace,
+        &domain,
+        partition_options,
+    );
+    let (mut spilled, spilled_polys) = SpilledTraceLde::<QuadFelt, H, MerkleTree<H>>::new(
+        &trace_info,
+        &main_trace,
+        &domain,
+        partition_options,
+    );
+
+    assert_eq!(expected.get_main_trace_commitment(), spilled.get_main_trace_commitment());
+    assert_eq!(
+        expected_polys.main_trace_polys().collect::<Vec<_>>(),
+        spilled_polys.main_trace_polys().collect::<Vec<_>>()
+    );
+    assert_eq!(expected.trace_len(), spilled.trace_len());
+
+    let (expected_aux_polys, expected_aux_commitment) = expected.set_aux_trace(&aux_trace, &domain);
+    let (spilled_aux_polys, spilled_aux_commitment) = spilled.set_aux_trace(&aux_trace, &domain);
+    assert_eq!(expected_aux_commitment, spilled_aux_commitment);
+    assert_eq!(
+        expected_aux_polys.columns().collect::<Vec<_>>(),
+        spilled_aux_polys.columns().collect::<Vec<_>>()
+    );
+
+    // frames are read identically at every step, including the ones wrapping around the domain
+    let mut expected_frame = EvaluationFrame::<Felt>::new(MAIN_WIDTH);
+    let mut spilled_frame = EvaluationFrame::<Felt>::new(MAIN_WIDTH);
+    let mut expected_aux_frame = EvaluationFrame::<QuadFelt>::new(AUX_WIDTH);
+    let mut spilled_aux_frame = EvaluationFrame::<QuadFelt>::new(AUX_WIDTH);
+    for lde_step in 0..expected.trace_len() {
+        expected.read_main_trace_frame_into(lde_step, &mut expected_frame);
+        spilled.read_main_trace_frame_into(lde_step, &mut spilled_frame);
+        assert_eq!(expected_frame.current(), spilled_frame.current());
+        assert_eq!(expected_frame.next(), spilled_frame.next());
+
+        expected.read_aux_trace_frame_into(lde_step, &mut expected_aux_frame);
+        spilled.read_aux_trace_frame_into(lde_step, &mut spilled_aux_frame);
+        assert_eq!(expected_aux_frame.current(), spilled_aux_frame.current());
+        assert_eq!(expected_aux_frame.next(), spilled_aux_frame.next());
+    }
+
+    let positions = [0, 3, 17, 64, 200, NUM_ROWS * BLOWUP - 1];
+    assert_eq!(expected.query(&positions), spilled.query(&positions));
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn get_trace_info() -> TraceInfo {
+    TraceInfo::new_multi_segment(MAIN_WIDTH, AUX_WIDTH, 2, NUM_ROWS, Vec::new())
+}
+
+fn get_domain() -> StarkDomain<Felt> {
+    StarkDomain::from_twiddles(fft::get_twiddles(NUM_ROWS), BLOWUP, Felt::GENERATOR)
+}
+
+fn gen_main_trace() -> ColMatrix<Felt> {
+    ColMatrix::new(
+        (0..MAIN_WIDTH as u64)
+            .map(|col| (0..NUM_ROWS as u64).map(|row| Felt::new(col * row * row + 7)).collect())
+            .collect(),
+    )
+}
+
+fn gen_aux_trace() -> ColMatrix<QuadFelt> {
+    ColMatrix::new(
+        (0..AUX_WIDTH as u32)
+            .map(|col| {
+                (0..NUM_ROWS as u32)
+                    .map(|row| QuadFelt::new(Felt::from(col + row), Felt::from(row * 3)))
+                    .collect()
+            })
+            .collect(),
+    )
+}
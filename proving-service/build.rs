@@ -0,0 +1,13 @@
+/// Generates the messages, server and client of the proving service from
+/// `proto/proving_service.proto` into `[OUT_DIR]`.
+///
+/// The proto file is compiled via `protox`, so that building the service does not require
+/// `protoc`.
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=proto/proving_service.proto");
+
+    let file_descriptors = protox::compile(["proving_service.proto"], ["proto"])?;
+    tonic_build::configure().compile_fds(file_descriptors)?;
+    Ok(())
+}
@@ -0,0 +1,306 @@
+use std::{
+    collections::{BTreeMap, VecDeque},
+    num::NonZeroUsize,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use miden::{
+    AdviceInputs, DefaultHost, ExecutionOptions, MemAdviceProvider, Program, ProvingOptions,
+    StackInputs, utils::Deserializable,
+};
+use stdlib::StdLibrary;
+use tokio::sync::{Semaphore, watch};
+use tonic::Status;
+
+use crate::proto::{self, JobState};
+
+// SERVICE CONFIG
+// ================================================================================================
+
+/// The limits of the job queue of the service.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ServiceConfig {
+    /// The maximum number of jobs which are proven concurrently.
+    ///
+    /// Each job uses all available cores when the service is built with the `concurrent` feature,
+    /// and the memory required to prove a program grows with the length of its execution, so this
+    /// is 1 by default.
+    pub max_concurrent_jobs: NonZeroUsize,
+    /// The maximum number of jobs which can wait for a free proving slot; further jobs are
+    /// rejected with `RESOURCE_EXHAUSTED` until a slot frees up.
+    pub max_queued_jobs: usize,
+    /// The maximum number of finished jobs whose outcome is kept for `GetJob`; the outcomes of the
+    /// oldest jobs are dropped first.
+    pub max_retained_jobs: usize,
+    /// The maximum number of cycles a job may execute; requests allowing more cycles are lowered
+    /// to this limit.
+    pub max_cycles: u32,
+    /// The maximum number of rows of the memory chiplet trace of a job; requests allowing more
+    /// rows are lowered to this limit.
+    pub max_memory_rows: u32,
+    /// The maximum amount of memory, in bytes, the extended execution trace of a job may occupy
+    /// before it is spilled to disk; requests without a limit, or with a higher one, are lowered
+    /// to this limit.
+    pub memory_limit: usize,
+}
+
+impl ServiceConfig {
+    /// Returns the specified proving options with their limits lowered to the limits of the
+    /// service, so that a client cannot make a job use more resources than the service allows.
+    ///
+    /// # Errors
+    /// Returns an error if the lowered execution limits are inconsistent, e.g., if `max_cycles`
+    /// is below the minimal length of an execution trace.
+    fn clamp(&self, options: ProvingOptions) -> Result<ProvingOptions, JobError> {
+        let requested = options.execution_options();
+        let max_cycles = requested.max_cycles().min(self.max_cycles);
+        let expected_cycles = requested.expected_cycles().min(max_cycles);
+        let mut exec_options =
+            ExecutionOptions::new(Some(max_cycles), expected_cycles, false, false)
+                .map_err(|err| JobError::InvalidRequest(format!("invalid proving options: {err}")))?
+                .with_max_memory_rows(requested.max_memory_rows().min(self.max_memory_rows))
+                .with_max_nesting_depth(requested.max_nesting_depth());
+        if !requested.enable_trace_generation() {
+            exec_options = exec_options.without_trace_generation();
+        }
+
+        let memory_limit = options
+            .memory_limit()
+            .map_or(self.memory_limit, |limit| limit.min(self.memory_limit));
+        Ok(options.with_execution_options(exec_options).with_memory_limit(memory_limit))
+    }
+}
+
+impl Default for ServiceConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrent_jobs: NonZeroUsize::MIN,
+            max_queued_jobs: 64,
+            max_retained_jobs: 1024,
+            max_cycles: 1 << 24,
+            max_memory_rows: 1 << 24,
+            memory_limit: 2 << 30,
+        }
+    }
+}
+
+// PROVING REQUEST
+// ================================================================================================
+
+/// A validated request to prove the execution of a program.
+pub(crate) struct ProvingRequest {
+    program: Program,
+    stack_inputs: StackInputs,
+    advice_inputs: AdviceInputs,
+    options: ProvingOptions,
+}
+
+impl ProvingRequest {
+    /// Returns the program, inputs and options of the specified request, with the limits of the
+    /// options lowered to the limits of the service.
+    ///
+    /// # Errors
+    /// Returns an error if any of them is malformed.
+    pub fn parse(request: proto::ProveRequest, config: &ServiceConfig) -> Result<Self, JobError> {
+        let program = Program::read_from_bytes(&request.program)
+            .map_err(|err| JobError::InvalidRequest(format!("invalid program: {err}")))?;
+        let stack_inputs = StackInputs::try_from_ints(request.stack_inputs)
+            .map_err(|err| JobError::InvalidRequest(format!("invalid stack inputs: {err}")))?;
+        let advice_inputs = AdviceInputs::default()
+            .with_stack_values(request.advice_stack)
+            .map_err(|err| JobError::InvalidRequest(format!("invalid advice stack: {err}")))?;
+        let options = if request.proving_options.is_empty() {
+            ProvingOptions::default()
+        } else {
+            ProvingOptions::read_from_bytes(&request.proving_options).map_err(|err| {
+                JobError::InvalidRequest(format!("invalid proving options: {err}"))
+            })?
+        };
+        let options = config.clamp(options)?;
+
+        Ok(Self {
+            program,
+            stack_inputs,
+            advice_inputs,
+            options,
+        })
+    }
+
+    /// Executes and proves the program, linking it against the standard library.
+    fn prove(self) -> Result<proto::ProofResult, String> {
+        let mut host = DefaultHost::new(MemAdviceProvider::from(self.advice_inputs));
+        host.load_mast_forest(StdLibrary::default().mast_forest().clone())
+            .map_err(|err| err.to_string())?;
+        let (stack_outputs, proof) =
+            miden::prove(&self.program, self.stack_inputs, &mut host, self.options)
+                .map_err(|err| err.to_string())?;
+
+        Ok(proto::ProofResult {
+            program_hash: self.program.hash().as_bytes().to_vec(),
+            stack_outputs: stack_outputs.as_int_vec(),
+            security_level: proof.security_level(),
+            proof: proof.to_bytes(),
+        })
+    }
+}
+
+// JOB QUEUE
+// ================================================================================================
+
+/// Queues proving jobs, proves at most [ServiceConfig::max_concurrent_jobs] of them at a time,
+/// and keeps track of their state.
+pub(crate) struct JobQueue {
+    config: ServiceConfig,
+    slots: Arc<Semaphore>,
+    state: Mutex<QueueState>,
+}
+
+#[derive(Default)]
+struct QueueState {
+    next_job_id: u64,
+    jobs: BTreeMap<u64, watch::Sender<proto::Job>>,
+    finished: VecDeque<u64>,
+    queued: usize,
+    running: usize,
+    succeeded: u64,
+    failed: u64,
+}
+
+impl JobQueue {
+    pub fn new(config: ServiceConfig) -> Self {
+        Self {
+            config,
+            slots: Arc::new(Semaphore::new(config.max_concurrent_jobs.get())),
+            state: Mutex::default(),
+        }
+    }
+
+    /// Returns the limits of this queue.
+    pub fn config(&self) -> &ServiceConfig {
+        &self.config
+    }
+
+    /// Queues the specified request, and returns a receiver of the state of the job.
+    ///
+    /// # Errors
+    /// Returns an error if the queue is full.
+    pub fn submit(
+        self: &Arc<Self>,
+        request: ProvingRequest,
+    ) -> Result<watch::Receiver<proto::Job>, JobError> {
+        let (job_id, receiver) = {
+            let mut state = self.lock();
+            if state.queued >= self.config.max_queued_jobs {
+                return Err(JobError::QueueFull(state.queued));
+            }
+
+            let job_id = state.next_job_id;
+            state.next_job_id += 1;
+            state.queued += 1;
+            let (sender, receiver) = watch::channel(proto::Job {
+                job_id,
+                state: JobState::Queued.into(),
+                ..Default::default()
+            });
+            state.jobs.insert(job_id, sender);
+            (job_id, receiver)
+        };
+
+        let queue = Arc::clone(self);
+        tokio::spawn(async move {
+            let _slot = queue.slots.acquire().await.expect("the semaphore is never closed");
+            queue.start(job_id);
+            let outcome = tokio::task::spawn_blocking(move || request.prove())
+                .await
+                .unwrap_or_else(|err| Err(format!("the proving task failed: {err}")));
+            queue.finish(job_id, outcome);
+        });
+
+        Ok(receiver)
+    }
+
+    /// Returns the current state of the job with the specified ID, if it is known.
+    pub fn get(&self, job_id: u64) -> Option<proto::Job> {
+        self.lock().jobs.get(&job_id).map(|job| job.borrow().clone())
+    }
+
+    /// Returns the state of the queue.
+    pub fn status(&self) -> proto::ServiceStatus {
+        let state = self.lock();
+        proto::ServiceStatus {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            queued_jobs: state.queued as u32,
+            running_jobs: state.running as u32,
+            succeeded_jobs: state.succeeded,
+            failed_jobs: state.failed,
+            max_concurrent_jobs: self.config.max_concurrent_jobs.get() as u32,
+            max_queued_jobs: self.config.max_queued_jobs as u32,
+        }
+    }
+
+    // HELPERS
+    // --------------------------------------------------------------------------------------------
+
+    fn start(&self, job_id: u64) {
+        let mut state = self.lock();
+        state.queued -= 1;
+        state.running += 1;
+        state.jobs[&job_id].send_modify(|job| job.state = JobState::Running.into());
+    }
+
+    fn finish(&self, job_id: u64, outcome: Result<proto::ProofResult, String>) {
+        let mut state = self.lock();
+        state.running -= 1;
+        match &outcome {
+            Ok(_) => state.succeeded += 1,
+            Err(_) => state.failed += 1,
+        }
+
+        state.jobs[&job_id].send_modify(|job| match outcome {
+            Ok(result) => {
+                job.state = JobState::Succeeded.into();
+                job.result = Some(result);
+            },
+            Err(error) => {
+                job.state = JobState::Failed.into();
+                job.error = error;
+            },
+        });
+
+        state.finished.push_back(job_id);
+        while state.finished.len() > self.config.max_retained_jobs {
+            let job_id = state.finished.pop_front().expect("finished jobs are not empty");
+            state.jobs.remove(&job_id);
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, QueueState> {
+        self.state.lock().expect("the queue state is never poisoned")
+    }
+}
+
+/// Returns true if the specified job finished, successfully or not.
+pub(crate) fn is_finished(job: &proto::Job) -> bool {
+    matches!(job.state(), JobState::Succeeded | JobState::Failed)
+}
+
+// ERRORS
+// ================================================================================================
+
+/// An error returned when a proving job cannot be queued.
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum JobError {
+    #[error("{0}")]
+    InvalidRequest(String),
+    #[error("the job queue is full ({0} jobs are waiting)")]
+    QueueFull(usize),
+}
+
+impl From<JobError> for Status {
+    fn from(err: JobError) -> Self {
+        match err {
+            JobError::InvalidRequest(_) => Status::invalid_argument(err.to_string()),
+            JobError::QueueFull(_) => Status::resource_exhausted(err.to_string()),
+        }
+    }
+}
@@ -0,0 +1,8 @@
+#![doc = include_str!("../README.md")]
+
+mod jobs;
+pub mod proto;
+mod service;
+
+pub use jobs::ServiceConfig;
+pub use service::ProvingService;
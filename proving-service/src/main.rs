@@ -0,0 +1,65 @@
+use std::{net::SocketAddr, num::NonZeroUsize};
+
+use clap::Parser;
+use miden_proving_service::{ProvingService, ServiceConfig};
+use tonic::transport::Server;
+use tracing::info;
+use tracing_subscriber::EnvFilter;
+
+/// gRPC service for proving and verifying the execution of Miden VM programs
+#[derive(Debug, Parser)]
+#[command(name = "miden-proving-service", version, about, long_about = None)]
+struct Cli {
+    /// Address to listen on
+    #[arg(long, default_value = "127.0.0.1:50051")]
+    listen: SocketAddr,
+    /// Maximum number of jobs proven concurrently
+    #[arg(long, default_value_t = ServiceConfig::default().max_concurrent_jobs)]
+    max_concurrent_jobs: NonZeroUsize,
+    /// Maximum number of jobs waiting to be proven; a service without room for a single job would
+    /// reject every request, so this must be at least 1
+    #[arg(long, default_value_t = NonZeroUsize::new(ServiceConfig::default().max_queued_jobs).unwrap())]
+    max_queued_jobs: NonZeroUsize,
+    /// Maximum number of finished jobs whose outcome is kept
+    #[arg(long, default_value_t = ServiceConfig::default().max_retained_jobs)]
+    max_retained_jobs: usize,
+    /// Maximum number of cycles a job may execute, regardless of its proving options
+    #[arg(long, default_value_t = ServiceConfig::default().max_cycles)]
+    max_cycles: u32,
+    /// Maximum number of rows of the memory chiplet trace of a job, regardless of its proving
+    /// options
+    #[arg(long, default_value_t = ServiceConfig::default().max_memory_rows)]
+    max_memory_rows: u32,
+    /// Maximum amount of memory, in MiB, the extended execution trace of a job may occupy before
+    /// it is spilled to disk, regardless of its proving options
+    #[arg(long, default_value_t = ServiceConfig::default().memory_limit >> 20)]
+    memory_limit: usize,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::try_from_default_env().unwrap_or_else(|_| "info".into()))
+        .init();
+
+    let cli = Cli::parse();
+    let config = ServiceConfig {
+        max_concurrent_jobs: cli.max_concurrent_jobs,
+        max_queued_jobs: cli.max_queued_jobs.get(),
+        max_retained_jobs: cli.max_retained_jobs,
+        max_cycles: cli.max_cycles,
+        max_memory_rows: cli.max_memory_rows,
+        memory_limit: cli.memory_limit.saturating_mul(1 << 20),
+    };
+
+    info!(address = %cli.listen, ?config, "starting the proving service");
+    Server::builder()
+        .add_service(ProvingService::new(config).into_server())
+        .serve_with_shutdown(cli.listen, async {
+            tokio::signal::ctrl_c().await.ok();
+            info!("shutting down the proving service");
+        })
+        .await?;
+
+    Ok(())
+}
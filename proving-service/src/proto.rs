@@ -0,0 +1,6 @@
+//! The messages, server and client of the gRPC API of the service.
+//!
+//! They are generated from `proto/proving_service.proto`, which is also the reference for clients
+//! written in other languages.
+
+include!(concat!(env!("OUT_DIR"), "/miden.proving.v1.rs"));
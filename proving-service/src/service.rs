@@ -0,0 +1,231 @@
+use std::sync::Arc;
+
+use miden::{Digest, ExecutionProof, Kernel, ProgramInfo, StackInputs, StackOutputs};
+use tonic::{Request, Response, Status};
+
+use crate::{
+    ServiceConfig,
+    jobs::{JobQueue, ProvingRequest, is_finished},
+    proto::{
+        self, GetJobRequest, GetStatusRequest, Job, ProveRequest, ServiceStatus, VerifyRequest,
+        VerifyResponse, proving_service_server::ProvingServiceServer,
+    },
+};
+
+// PROVING SERVICE
+// ================================================================================================
+
+/// The implementation of the gRPC proving service.
+///
+/// Proving jobs are queued, and proven on blocking threads by at most
+/// [ServiceConfig::max_concurrent_jobs] at a time; verification requests bypass the queue.
+#[derive(Clone)]
+pub struct ProvingService {
+    queue: Arc<JobQueue>,
+}
+
+impl ProvingService {
+    /// Returns a new service with an empty job queue with the specified limits.
+    pub fn new(config: ServiceConfig) -> Self {
+        Self { queue: Arc::new(JobQueue::new(config)) }
+    }
+
+    /// Returns a gRPC server for this service, to be added to a `tonic` router.
+    pub fn into_server(self) -> ProvingServiceServer<Self> {
+        ProvingServiceServer::new(self)
+    }
+}
+
+#[tonic::async_trait]
+impl proto::proving_service_server::ProvingService for ProvingService {
+    /// Queues a proving job, and waits for it to finish.
+    ///
+    /// The job keeps running if the client disconnects, and its outcome can then be retrieved via
+    /// `GetJob`.
+    async fn prove(&self, request: Request<ProveRequest>) -> Result<Response<Job>, Status> {
+        let request = ProvingRequest::parse(request.into_inner(), self.queue.config())?;
+        let mut job = self.queue.submit(request)?;
+        let job = job
+            .wait_for(is_finished)
+            .await
+            .map_err(|_| Status::internal("the proving job was dropped"))?
+            .clone();
+        Ok(Response::new(job))
+    }
+
+    async fn submit_job(&self, request: Request<ProveRequest>) -> Result<Response<Job>, Status> {
+        let request = ProvingRequest::parse(request.into_inner(), self.queue.config())?;
+        let job = self.queue.submit(request)?.borrow().clone();
+        Ok(Response::new(job))
+    }
+
+    async fn get_job(&self, request: Request<GetJobRequest>) -> Result<Response<Job>, Status> {
+        let job_id = request.into_inner().job_id;
+        self.queue
+            .get(job_id)
+            .map(Response::new)
+            .ok_or_else(|| Status::not_found(format!("job {job_id} is unknown or was dropped")))
+    }
+
+    async fn verify(
+        &self,
+        request: Request<VerifyRequest>,
+    ) -> Result<Response<VerifyResponse>, Status> {
+        let request = request.into_inner();
+        let program_hash = <[u8; 32]>::try_from(request.program_hash.as_slice())
+            .map_err(|_| "expected 32 bytes".to_string())
+            .and_then(|bytes| Digest::try_from(bytes).map_err(|err| err.to_string()))
+            .map_err(|err| Status::invalid_argument(format!("invalid program hash: {err}")))?;
+        let stack_inputs = StackInputs::try_from_ints(request.stack_inputs)
+            .map_err(|err| Status::invalid_argument(format!("invalid stack inputs: {err}")))?;
+        let stack_outputs = StackOutputs::try_from_ints(request.stack_outputs)
+            .map_err(|err| Status::invalid_argument(format!("invalid stack outputs: {err}")))?;
+        let proof = ExecutionProof::from_bytes(&request.proof)
+            .map_err(|err| Status::invalid_argument(format!("invalid proof: {err}")))?;
+
+        let program_info = ProgramInfo::new(program_hash, Kernel::default());
+        let outcome = tokio::task::spawn_blocking(move || {
+            miden::verify(program_info, stack_inputs, stack_outputs, proof)
+        })
+        .await
+        .map_err(|err| Status::internal(format!("the verification task failed: {err}")))?;
+
+        let response = match outcome {
+            Ok(security_level) => VerifyResponse {
+                valid: true,
+                security_level,
+                error: String::new(),
+            },
+            Err(err) => VerifyResponse {
+                valid: false,
+                security_level: 0,
+                error: err.to_string(),
+            },
+        };
+        Ok(Response::new(response))
+    }
+
+    async fn get_status(
+        &self,
+        _request: Request<GetStatusRequest>,
+    ) -> Result<Response<ServiceStatus>, Status> {
+        Ok(Response::new(self.queue.status()))
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use miden::{Assembler, ProvingOptions, utils::Serializable};
+    use tokio::net::TcpListener;
+    use tokio_stream::wrappers::TcpListenerStream;
+    use tonic::{Code, transport::Channel};
+
+    use super::*;
+    use crate::proto::{JobState, proving_service_client::ProvingServiceClient};
+
+    async fn start_service(config: ServiceConfig) -> ProvingServiceClient<Channel> {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(
+            tonic::transport::Server::builder()
+                .add_service(ProvingService::new(config).into_server())
+                .serve_with_incoming(TcpListenerStream::new(listener)),
+        );
+        ProvingServiceClient::connect(format!("http://{address}")).await.unwrap()
+    }
+
+    fn prove_request(source: &str) -> ProveRequest {
+        let program = Assembler::default().assemble_program(source).unwrap();
+        ProveRequest {
+            program: program.to_bytes(),
+            stack_inputs: vec![5],
+            advice_stack: vec![],
+            proving_options: ProvingOptions::default().to_bytes(),
+        }
+    }
+
+    #[tokio::test]
+    async fn prove_and_verify_over_grpc() {
+        let mut client = start_service(ServiceConfig::default()).await;
+
+        let job = client.prove(prove_request("begin push.3 add end")).await.unwrap().into_inner();
+        assert_eq!(job.state(), JobState::Succeeded);
+        let result = job.result.clone().unwrap();
+        assert_eq!(result.stack_outputs[0], 8);
+
+        // the outcome of the job is retained
+        let retained = client.get_job(GetJobRequest { job_id: job.job_id }).await.unwrap();
+        assert_eq!(retained.into_inner(), job);
+
+        let mut request = VerifyRequest {
+            program_hash: result.program_hash,
+            stack_inputs: vec![5],
+            stack_outputs: result.stack_outputs,
+            proof: result.proof,
+        };
+        let response = client.verify(request.clone()).await.unwrap().into_inner();
+        assert!(response.valid);
+        assert_eq!(response.security_level, result.security_level);
+
+        request.stack_inputs = vec![6];
+        let response = client.verify(request).await.unwrap().into_inner();
+        assert!(!response.valid);
+        assert!(!response.error.is_empty());
+
+        // failed executions are reported via the state of the job
+        let job = client.prove(prove_request("begin push.0 assert end")).await.unwrap();
+        assert_eq!(job.into_inner().state(), JobState::Failed);
+
+        let status = client.get_status(GetStatusRequest {}).await.unwrap().into_inner();
+        assert_eq!((status.succeeded_jobs, status.failed_jobs), (1, 1));
+        assert_eq!((status.queued_jobs, status.running_jobs), (0, 0));
+    }
+
+    #[tokio::test]
+    async fn invalid_requests_are_rejected() {
+        let mut client = start_service(ServiceConfig {
+            max_concurrent_jobs: NonZeroUsize::MIN,
+            max_queued_jobs: 0,
+            max_retained_jobs: 0,
+            ..ServiceConfig::default()
+        })
+        .await;
+
+        let mut request = prove_request("begin push.3 add end");
+        let err = client.submit_job(request.clone()).await.unwrap_err();
+        assert_eq!(err.code(), Code::ResourceExhausted);
+
+        request.program.truncate(8);
+        let err = client.submit_job(request).await.unwrap_err();
+        assert_eq!(err.code(), Code::InvalidArgument);
+
+        let err = client.get_job(GetJobRequest { job_id: 0 }).await.unwrap_err();
+        assert_eq!(err.code(), Code::NotFound);
+    }
+
+    #[tokio::test]
+    async fn requested_limits_are_lowered_to_the_limits_of_the_service() {
+        let mut client = start_service(ServiceConfig {
+            max_cycles: 1 << 10,
+            ..ServiceConfig::default()
+        })
+        .await;
+
+        // the request allows any number of cycles, but the service stops the job after 2^10 cycles
+        let job = client
+            .prove(prove_request("begin repeat.2000 push.1 drop end end"))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(job.state(), JobState::Failed);
+        assert!(job.error.contains("1024"), "{}", job.error);
+
+        let job = client.prove(prove_request("begin push.3 add end")).await.unwrap().into_inner();
+        assert_eq!(job.state(), JobState::Succeeded);
+    }
+}
@@ -0,0 +1,38 @@
+"""Python bindings for assembling, executing, proving and verifying Miden VM programs."""
+
+class MidenError(Exception): ...
+class AssemblyError(MidenError): ...
+class InputError(MidenError): ...
+class ExecutionError(MidenError): ...
+class ProvingError(MidenError): ...
+class ProofDeserializationError(MidenError): ...
+class VerificationError(MidenError): ...
+
+class Assembler:
+    def __init__(self, debug_mode: bool = False) -> None: ...
+    def add_module(self, path: str, source: str) -> None: ...
+    def assemble(self, source: str) -> Program: ...
+
+class Program:
+    @property
+    def hash(self) -> str: ...
+
+class Proof:
+    @property
+    def security_level(self) -> int: ...
+    def to_bytes(self) -> bytes: ...
+    @staticmethod
+    def from_bytes(data: bytes) -> Proof: ...
+
+def execute(
+    program: Program, stack_inputs: list[int] = ..., advice_stack: list[int] = ...
+) -> list[int]: ...
+def prove(
+    program: Program,
+    stack_inputs: list[int] = ...,
+    advice_stack: list[int] = ...,
+    security: int = 96,
+) -> tuple[list[int], Proof]: ...
+def verify(
+    program_hash: str, stack_inputs: list[int], stack_outputs: list[int], proof: Proof
+) -> int: ...
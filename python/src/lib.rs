@@ -0,0 +1,370 @@
+use miden::{
+    AdviceInputs, Assembler as VmAssembler, DefaultHost, Digest, ExecutionProof, Kernel,
+    MemAdviceProvider, Program as VmProgram, ProgramInfo, ProvingOptions, StackInputs,
+    StackOutputs,
+    assembly::CompileOptions,
+    diagnostics::{Report, reporting::PrintDiagnostic},
+    execute_without_trace,
+};
+use pyo3::{create_exception, exceptions::PyException, prelude::*, types::PyBytes};
+use stdlib::StdLibrary;
+
+// EXCEPTIONS
+// ================================================================================================
+
+create_exception!(miden, MidenError, PyException, "Base class of the errors raised by Miden VM.");
+create_exception!(
+    miden,
+    AssemblyError,
+    MidenError,
+    "Raised when a program or module fails to assemble; the message contains the diagnostics of \
+     the assembler."
+);
+create_exception!(
+    miden,
+    InputError,
+    MidenError,
+    "Raised when stack inputs, stack outputs, advice inputs or a program hash are malformed."
+);
+create_exception!(miden, ExecutionError, MidenError, "Raised when a program fails to execute.");
+create_exception!(
+    miden,
+    ProvingError,
+    MidenError,
+    "Raised when a program executed, but the proof of its execution could not be generated."
+);
+create_exception!(
+    miden,
+    ProofDeserializationError,
+    MidenError,
+    "Raised when a proof cannot be deserialized."
+);
+create_exception!(
+    miden,
+    VerificationError,
+    MidenError,
+    "Raised when a proof is not a valid proof of the execution of a program against the specified \
+     inputs and outputs."
+);
+
+// ASSEMBLER
+// ================================================================================================
+
+/// Assembles Miden assembly source code into programs, linking them against the standard library
+/// and the modules added to the assembler.
+///
+/// In debug mode, programs carry the source instructions of their operations, which are included in
+/// the messages of execution errors. Debug information does not affect the hash of a program.
+#[pyclass(module = "miden")]
+pub struct Assembler {
+    assembler: VmAssembler,
+}
+
+#[pymethods]
+impl Assembler {
+    #[new]
+    #[pyo3(signature = (debug_mode = false))]
+    fn new(debug_mode: bool) -> PyResult<Self> {
+        let mut assembler = VmAssembler::default().with_debug_mode(debug_mode);
+        assembler.add_library(StdLibrary::default()).map_err(assembly_error)?;
+        Ok(Self { assembler })
+    }
+
+    /// Adds a library module with the specified path (e.g., `"my_lib::math"`) and source code,
+    /// whose procedures can then be imported by programs and other modules.
+    fn add_module(&mut self, path: &str, source: String) -> PyResult<()> {
+        let options = CompileOptions::new(miden::ModuleKind::Library, path)
+            .map_err(|err| InputError::new_err(format!("invalid module path: {err}")))?;
+        self.assembler
+            .add_module_with_options(source, options)
+            .map_err(assembly_error)?;
+        Ok(())
+    }
+
+    /// Assembles the program with the specified source code.
+    fn assemble(&self, source: String) -> PyResult<Program> {
+        let program = self.assembler.clone().assemble_program(source).map_err(assembly_error)?;
+        Ok(Program { program })
+    }
+}
+
+/// A program assembled by an `Assembler`.
+#[pyclass(module = "miden")]
+#[derive(Debug)]
+pub struct Program {
+    program: VmProgram,
+}
+
+#[pymethods]
+impl Program {
+    /// The hash of this program, hex-encoded with the `0x` prefix.
+    #[getter]
+    fn hash(&self) -> String {
+        self.program.hash().to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Program(hash='{}')", self.hash())
+    }
+}
+
+// PROCESSOR
+// ================================================================================================
+
+/// Executes the specified program, and returns the values remaining on the stack, starting with
+/// the value at the top of the stack.
+///
+/// The stack inputs are expected to be ordered as if they would be pushed onto the stack one by
+/// one, and the values of the advice stack as if they would be popped off the advice stack one by
+/// one.
+#[pyfunction]
+#[pyo3(signature = (program, stack_inputs = Vec::new(), advice_stack = Vec::new()))]
+fn execute(
+    py: Python<'_>,
+    program: &Program,
+    stack_inputs: Vec<u64>,
+    advice_stack: Vec<u64>,
+) -> PyResult<Vec<u64>> {
+    let program = program.program.clone();
+    let (stack_inputs, mut host) = prepare_execution(stack_inputs, advice_stack)?;
+    let stack_outputs = py
+        .allow_threads(|| {
+            execute_without_trace(&program, stack_inputs, &mut host, Default::default())
+        })
+        .map_err(execution_error)?;
+    Ok(stack_outputs.as_int_vec())
+}
+
+// PROVER
+// ================================================================================================
+
+/// A proof of correct execution of a program.
+#[pyclass(module = "miden")]
+#[derive(Debug)]
+pub struct Proof {
+    proof: ExecutionProof,
+}
+
+#[pymethods]
+impl Proof {
+    /// The number of bits of security of this proof.
+    #[getter]
+    fn security_level(&self) -> u32 {
+        self.proof.security_level()
+    }
+
+    /// Serializes this proof into bytes, in the format of the `.proof` files written by
+    /// `miden prove`.
+    fn to_bytes<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        PyBytes::new(py, &self.proof.to_bytes())
+    }
+
+    /// Deserializes a proof serialized via `to_bytes()`.
+    #[staticmethod]
+    fn from_bytes(data: &[u8]) -> PyResult<Self> {
+        let proof = ExecutionProof::from_bytes(data)
+            .map_err(|err| ProofDeserializationError::new_err(err.to_string()))?;
+        Ok(Self { proof })
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Proof(security_level={})", self.security_level())
+    }
+}
+
+/// Executes the specified program and proves its execution, targeting the specified number of
+/// bits of security, which must be either 96 or 128.
+///
+/// The inputs are interpreted as by `execute()`. Returns the values remaining on the stack, as
+/// returned by `execute()`, and the proof.
+#[pyfunction]
+#[pyo3(signature = (program, stack_inputs = Vec::new(), advice_stack = Vec::new(), security = 96))]
+fn prove(
+    py: Python<'_>,
+    program: &Program,
+    stack_inputs: Vec<u64>,
+    advice_stack: Vec<u64>,
+    security: u32,
+) -> PyResult<(Vec<u64>, Proof)> {
+    let options = match security {
+        96 => ProvingOptions::with_96_bit_security(false),
+        128 => ProvingOptions::with_128_bit_security(false),
+        _ => {
+            return Err(InputError::new_err(format!(
+                "security must be 96 or 128 bits, but was {security}"
+            )));
+        },
+    };
+    let program = program.program.clone();
+    let (stack_inputs, mut host) = prepare_execution(stack_inputs, advice_stack)?;
+    let (stack_outputs, proof) = py
+        .allow_threads(|| miden::prove(&program, stack_inputs, &mut host, options))
+        .map_err(execution_error)?;
+    Ok((stack_outputs.as_int_vec(), Proof { proof }))
+}
+
+// VERIFIER
+// ================================================================================================
+
+/// Verifies that the program with the specified hash was executed correctly against the specified
+/// inputs and outputs, and returns the security level of the proof.
+///
+/// The program hash is hex-encoded, with or without the `0x` prefix. The stack inputs are ordered
+/// as for `execute()`, and the stack outputs as returned by `execute()`. The program is expected
+/// to have been executed without a kernel.
+#[pyfunction]
+fn verify(
+    py: Python<'_>,
+    program_hash: &str,
+    stack_inputs: Vec<u64>,
+    stack_outputs: Vec<u64>,
+    proof: &Proof,
+) -> PyResult<u32> {
+    let program_hash = if program_hash.starts_with("0x") {
+        Digest::try_from(program_hash)
+    } else {
+        Digest::try_from(format!("0x{program_hash}").as_str())
+    }
+    .map_err(|err| InputError::new_err(format!("invalid program hash: {err}")))?;
+    let stack_inputs = StackInputs::try_from_ints(stack_inputs)
+        .map_err(|err| InputError::new_err(format!("invalid stack inputs: {err}")))?;
+    let stack_outputs = StackOutputs::try_from_ints(stack_outputs)
+        .map_err(|err| InputError::new_err(format!("invalid stack outputs: {err}")))?;
+
+    let program_info = ProgramInfo::new(program_hash, Kernel::default());
+    let proof = proof.proof.clone();
+    py.allow_threads(|| miden::verify(program_info, stack_inputs, stack_outputs, proof))
+        .map_err(|err| VerificationError::new_err(err.to_string()))
+}
+
+// MODULE
+// ================================================================================================
+
+/// Python bindings for assembling, executing, proving and verifying Miden VM programs.
+#[pymodule]
+#[pyo3(name = "miden")]
+pub fn miden_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<Assembler>()?;
+    m.add_class::<Program>()?;
+    m.add_class::<Proof>()?;
+    m.add_function(wrap_pyfunction!(execute, m)?)?;
+    m.add_function(wrap_pyfunction!(prove, m)?)?;
+    m.add_function(wrap_pyfunction!(verify, m)?)?;
+
+    let py = m.py();
+    m.add("MidenError", py.get_type::<MidenError>())?;
+    m.add("AssemblyError", py.get_type::<AssemblyError>())?;
+    m.add("InputError", py.get_type::<InputError>())?;
+    m.add("ExecutionError", py.get_type::<ExecutionError>())?;
+    m.add("ProvingError", py.get_type::<ProvingError>())?;
+    m.add("ProofDeserializationError", py.get_type::<ProofDeserializationError>())?;
+    m.add("VerificationError", py.get_type::<VerificationError>())?;
+    Ok(())
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the stack inputs and the host to execute a program with, given the inputs passed to
+/// [execute] or [prove].
+fn prepare_execution(
+    stack_inputs: Vec<u64>,
+    advice_stack: Vec<u64>,
+) -> PyResult<(StackInputs, DefaultHost<MemAdviceProvider>)> {
+    let stack_inputs = StackInputs::try_from_ints(stack_inputs)
+        .map_err(|err| InputError::new_err(format!("invalid stack inputs: {err}")))?;
+    let advice_inputs = AdviceInputs::default()
+        .with_stack_values(advice_stack)
+        .map_err(|err| InputError::new_err(format!("invalid advice stack: {err}")))?;
+
+    let mut host = DefaultHost::new(MemAdviceProvider::from(advice_inputs));
+    host.load_mast_forest(StdLibrary::default().mast_forest().clone())
+        .map_err(execution_error)?;
+    Ok((stack_inputs, host))
+}
+
+/// Converts the diagnostics of the assembler into an [AssemblyError].
+fn assembly_error(report: Report) -> PyErr {
+    AssemblyError::new_err(PrintDiagnostic::narrated(report).to_string())
+}
+
+/// Converts an error of the processor or the prover into an [ExecutionError] or a [ProvingError].
+fn execution_error(err: miden::ExecutionError) -> PyErr {
+    match err {
+        miden::ExecutionError::ProverError(_)
+        | miden::ExecutionError::ProofSelfCheckFailed { .. } => {
+            ProvingError::new_err(err.to_string())
+        },
+        err => ExecutionError::new_err(err.to_string()),
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use pyo3::{ffi::c_str, types::PyDict};
+
+    use super::*;
+
+    #[test]
+    fn prove_and_verify_from_python() {
+        Python::with_gil(|py| {
+            let module = PyModule::new(py, "miden").unwrap();
+            miden_module(&module).unwrap();
+            let locals = PyDict::new(py);
+            locals.set_item("miden", module).unwrap();
+
+            py.run(
+                c_str!(
+                    r#"
+assembler = miden.Assembler()
+assembler.add_module("test::math", "export.add_three push.3 add end")
+program = assembler.assemble("use.test::math begin adv_push.1 add exec.math::add_three end")
+assert program.hash.startswith("0x")
+
+outputs = miden.execute(program, [5], advice_stack=[2])
+assert outputs[:2] == [10, 0] and len(outputs) == 16
+
+proven_outputs, proof = miden.prove(program, [5], [2])
+assert proven_outputs == outputs
+proof = miden.Proof.from_bytes(proof.to_bytes())
+assert miden.verify(program.hash, [5], outputs, proof) == proof.security_level
+assert miden.verify(program.hash[2:], [5], outputs, proof) == proof.security_level
+
+try:
+    miden.verify(program.hash, [6], outputs, proof)
+    raise AssertionError("a proof must not verify against different inputs")
+except miden.VerificationError:
+    pass
+"#
+                ),
+                None,
+                Some(&locals),
+            )
+            .unwrap();
+        });
+    }
+
+    #[test]
+    fn errors_are_raised_as_exceptions() {
+        Python::with_gil(|py| {
+            let assembler = Assembler::new(false).unwrap();
+            let err = assembler.assemble("begin push.1 ad end".into()).unwrap_err();
+            assert!(err.is_instance_of::<AssemblyError>(py));
+            assert!(err.is_instance_of::<MidenError>(py));
+
+            let program = assembler.assemble("begin adv_push.1 add end".into()).unwrap();
+            let err = execute(py, &program, vec![], vec![]).unwrap_err();
+            assert!(err.is_instance_of::<ExecutionError>(py));
+            let err = execute(py, &program, vec![u64::MAX], vec![]).unwrap_err();
+            assert!(err.is_instance_of::<InputError>(py));
+            let err = prove(py, &program, vec![], vec![1], 100).unwrap_err();
+            assert!(err.is_instance_of::<InputError>(py));
+
+            let err = Proof::from_bytes(&[1, 2, 3]).unwrap_err();
+            assert!(err.is_instance_of::<ProofDeserializationError>(py));
+        });
+    }
+}
@@ -1,7 +1,7 @@
 use std::{env, path::Path};
 
 use assembly::{
-    Assembler, Library, LibraryNamespace,
+    Assembler, IsaVersion, Library, LibraryNamespace,
     diagnostics::{IntoDiagnostic, Result},
 };
 
@@ -25,7 +25,12 @@ fn main() -> Result<()> {
     let manifest_dir = env!("CARGO_MANIFEST_DIR");
     let asm_dir = Path::new(manifest_dir).join(ASM_DIR_PATH);
 
-    let assembler = Assembler::default().with_debug_mode(cfg!(feature = "with-debug-info"));
+    // the standard library targets the initial version of the instruction set, so that it can be
+    // used by programs targeting any version; the assembler lowers invocations of its intrinsic
+    // procedures to newer operations when a program targets a version which supports them
+    let assembler = Assembler::default()
+        .with_debug_mode(cfg!(feature = "with-debug-info"))
+        .with_isa_version(IsaVersion::V1_0);
     let namespace = "std".parse::<LibraryNamespace>().expect("invalid base namespace");
     let stdlib = Library::from_dir(asm_dir, namespace, assembler)?;
 
@@ -0,0 +1,69 @@
+use processor::MerkleMultiProof;
+use test_utils::crypto::{MerkleTree, init_merkle_leaves};
+
+use super::*;
+
+#[test]
+fn test_verify_leaves() {
+    let leaves = init_merkle_leaves(&[10, 11, 12, 13, 14, 15, 16, 17]);
+    let tree = MerkleTree::new(leaves.clone()).unwrap();
+    let root = tree.root();
+
+    // the paths of the leaves share most of their nodes, so the multi-proof contains only 3 nodes
+    // instead of the 12 nodes of the 4 Merkle paths
+    let indexes = [1, 2, 3, 6];
+    let proof = MerkleMultiProof::new(&tree, indexes).unwrap();
+    let expected_nodes = [
+        leaves[0].into(),
+        leaves[7].into(),
+        tree.get_node(NodeIndex::new(2, 2).unwrap()).unwrap(),
+    ];
+    assert_eq!(proof.nodes(), expected_nodes);
+    assert_eq!(proof.root(), root);
+
+    let mut store = MerkleStore::default();
+    store.extend(proof.inner_nodes());
+
+    // store the leaves in memory as pairs of words [index, 0, 0, 0], V
+    let write_leaves = |values: &[Word]| {
+        indexes
+            .iter()
+            .zip(values)
+            .enumerate()
+            .map(|(i, (index, leaf))| {
+                let leaf = leaf.map(|element| element.to_string()).join(".");
+                format!(
+                    "push.{index} push.{} mem_store push.{leaf} push.{} mem_storew dropw",
+                    8 * i,
+                    8 * i + 4
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    let source = |values: &[Word]| {
+        format!(
+            "
+            use.std::collections::merkle
+
+            begin
+                {}
+                exec.merkle::verify_leaves
+            end",
+            write_leaves(values)
+        )
+    };
+
+    // [R, depth, start_ptr, end_ptr, ...]
+    let mut stack = vec![32, 0, 3];
+    stack.extend(root.iter().map(|element| element.as_int()));
+    let expected: Vec<u64> = root.iter().rev().map(|element| element.as_int()).collect();
+
+    let values: Vec<Word> = indexes.iter().map(|&index| leaves[index as usize]).collect();
+    build_test!(source(&values), &stack, &[], store.clone()).expect_stack(&expected);
+
+    // a leaf which is not in the tree is rejected
+    let mut values = values;
+    values[2] = init_merkle_leaves(&[42])[0];
+    assert!(build_test!(source(&values), &stack, &[], store).execute().is_err());
+}
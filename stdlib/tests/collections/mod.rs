@@ -1,7 +1,8 @@
 use test_utils::{
     EMPTY_WORD, Felt, Word,
-    crypto::{MerkleStore, RpoDigest, Smt},
+    crypto::{MerkleStore, NodeIndex, RpoDigest, Smt},
 };
 
+mod merkle;
 mod mmr;
 mod smt;
@@ -1,3 +1,4 @@
+use processor::ContextId;
 use sha3::{Digest, Keccak256};
 use test_utils::{
     Felt, IntoBytes, MIN_STACK_DEPTH,
@@ -70,6 +71,58 @@ fn keccak256_2_to_1_hash() {
     test.expect_stack(&expected_stack);
 }
 
+#[test]
+fn keccak_p_permutation() {
+    let lanes: [u64; 25] = core::array::from_fn(|_| rand_value::<u64>());
+
+    // the state is kept in memory, such that the word at address 4i holds lanes 2i and 2i + 1 in
+    // bit interleaved form as [odd(2i + 1), even(2i + 1), odd(2i), even(2i)]
+    let to_memory = |lanes: &[u64; 25]| {
+        let mut memory = [0u64; 52];
+        for (i, &lane) in lanes.iter().enumerate() {
+            let (even, odd) = to_bit_interleaved(lane);
+            let base = (i >> 1) * 4 + if i % 2 == 0 { 2 } else { 0 };
+            memory[base] = odd as u64;
+            memory[base + 1] = even as u64;
+        }
+        memory
+    };
+
+    let mut source = String::from("use.std::crypto::hashes::keccak256\nbegin\n");
+    for (addr, word) in to_memory(&lanes).chunks(4).enumerate() {
+        source.push_str(&format!(
+            "push.{}.{}.{}.{} mem_storew.{} dropw\n",
+            word[0],
+            word[1],
+            word[2],
+            word[3],
+            addr * 4
+        ));
+    }
+    source.push_str("push.0 exec.keccak256::keccak_p\nend");
+
+    let mut expected = lanes;
+    keccak::f1600(&mut expected);
+    let expected = to_memory(&expected);
+
+    let test = build_test!(source, &[]);
+    let trace = test.execute().unwrap();
+    for (addr, &value) in expected.iter().enumerate() {
+        let actual = trace.memory().get_value(ContextId::root(), addr as u32).unwrap_or_default();
+        assert_eq!(actual, Felt::new(value), "mismatch at address {addr}");
+    }
+}
+
+/// Splits a 64 -bit lane into the even and odd positioned bits of its standard representation.
+fn to_bit_interleaved(lane: u64) -> (u32, u32) {
+    let (mut even, mut odd) = (0u32, 0u32);
+    for i in 0..32 {
+        even |= (((lane >> (2 * i)) & 1) as u32) << i;
+        odd |= (((lane >> (2 * i + 1)) & 1) as u32) << i;
+    }
+    (even, odd)
+}
+
 /// Given N -many bytes ( such that N % 8 == 0 ), this function considers
 /// each block of contiguous 8 -bytes as little endian 64 -bit unsigned
 /// integer word and converts each u64 into two u32s such that first one holds
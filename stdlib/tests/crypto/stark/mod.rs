@@ -1,11 +1,13 @@
-mod verifier_recursive;
-use assembly::Assembler;
-use miden_air::{FieldExtension, HashFunction, PublicInputs};
+use std::sync::Arc;
+
+use assembly::{Assembler, DefaultSourceManager};
+use miden_air::{FieldExtension, HashFunction};
 use processor::{DefaultHost, Program, ProgramInfo};
 use test_utils::{
-    AdviceInputs, MemAdviceProvider, ProvingOptions, StackInputs, VerifierError, prove,
+    AdviceInputs, MemAdviceProvider, ProvingOptions, StackInputs, VerifierError,
+    crypto::MerkleStore, prove,
 };
-use verifier_recursive::{VerifierData, generate_advice_inputs};
+use verifier::recursive::{VerifierData, generate_verifier_data};
 
 // Note: Changes to Miden VM may cause this test to fail when some of the assumptions documented
 // in `stdlib/asm/crypto/stark/verifier.masm` are violated.
@@ -26,7 +28,7 @@ fn stark_verifier_e2f4() {
         advice_stack: tape,
         store,
         advice_map,
-    } = generate_recursive_verifier_data(example_source, stack_inputs).unwrap();
+    } = generate_recursive_verifier_data(example_source, None, stack_inputs).unwrap();
 
     // Verify inside Miden VM
     let source = "
@@ -41,16 +43,198 @@ fn stark_verifier_e2f4() {
     test.expect_stack(&[]);
 }
 
+#[test]
+fn stark_verifier_e2f4_with_fri_layers() {
+    // the traces of the following programs are long enough for FRI to fold the LDE domain, and
+    // the LDE domain sizes cover both remainder sizes
+    for num_iterations in [64, 100] {
+        let example_source = format!(
+            "begin
+                repeat.{num_iterations}
+                    swap dup.1 add
+                end
+            end"
+        );
+
+        let VerifierData {
+            initial_stack,
+            advice_stack: tape,
+            store,
+            advice_map,
+        } = generate_recursive_verifier_data(&example_source, None, vec![1, 0]).unwrap();
+
+        let source = "
+            use.std::crypto::stark::verifier
+            begin
+                exec.verifier::verify
+            end
+            ";
+
+        let test = build_test!(source, &initial_stack, &tape, store, advice_map);
+        test.expect_stack(&[]);
+    }
+}
+
+#[test]
+fn stark_verifier_e2f4_with_kernel() {
+    // kernels with an odd and an even number of procedures are absorbed differently in the
+    // Fiat-Shamir transcript, and thus both cases are tested
+    let kernels = [
+        "export.foo push.1 add end",
+        "export.foo push.1 add end export.bar push.2 add end",
+    ];
+    let example_source = "begin
+            repeat.32
+                swap dup.1 add
+            end
+            syscall.foo
+        end";
+
+    for kernel_source in kernels {
+        let VerifierData {
+            initial_stack,
+            advice_stack: tape,
+            store,
+            advice_map,
+        } = generate_recursive_verifier_data(example_source, Some(kernel_source), vec![1, 0])
+            .unwrap();
+
+        // the public inputs are available in memory once the proof is verified, starting with
+        // the program digest
+        let source = "
+            use.std::crypto::stark::constants
+            use.std::crypto::stark::verifier
+            begin
+                exec.verifier::verify
+                padw exec.constants::public_inputs_ptr mem_loadw
+                swapw dropw
+            end
+            ";
+        let program_digest: Vec<u64> = tape[1..5].iter().rev().copied().collect();
+
+        let test = build_test!(source, &initial_stack, &tape, store, advice_map);
+        test.expect_stack(&program_digest);
+    }
+}
+
+#[test]
+fn stark_verifier_e2f4_aggregation() {
+    // the proofs of two programs are verified by a single program, the inputs of each proof being
+    // provided via the advice stack
+    let mut advice_stack = Vec::new();
+    let mut store = MerkleStore::new();
+    let mut advice_map = Vec::new();
+    let mut proof_ptrs = Vec::new();
+    for num_iterations in [32, 64] {
+        let example_source = format!(
+            "begin
+                repeat.{num_iterations}
+                    swap dup.1 add
+                end
+            end"
+        );
+        let VerifierData {
+            initial_stack,
+            advice_stack: tape,
+            store: proof_store,
+            advice_map: proof_advice_map,
+        } = generate_recursive_verifier_data(&example_source, None, vec![1, 0]).unwrap();
+
+        advice_stack.extend(initial_stack);
+        proof_ptrs.push(advice_stack.len());
+        advice_stack.extend(tape);
+        store.extend(proof_store.inner_nodes());
+        advice_map.extend(proof_advice_map);
+    }
+
+    let source = "
+        use.std::crypto::stark
+        begin
+            repeat.2
+                adv_push.4
+                exec.stark::verify
+            end
+        end
+        ";
+
+    let test = build_test!(source, &[], &advice_stack, store.clone(), advice_map.clone());
+    test.expect_stack(&[]);
+
+    // tampering with the stack outputs of either proof, which follow the number of kernel
+    // procedures, the program digest and the stack inputs, makes the aggregation fail
+    for proof_ptr in proof_ptrs {
+        let mut tampered_stack = advice_stack.clone();
+        tampered_stack[proof_ptr + 1 + 4 + 16] += 1;
+
+        let test = build_test!(source, &[], &tampered_stack, store.clone(), advice_map.clone());
+        assert!(test.execute_without_trace().is_err());
+    }
+}
+
+#[test]
+fn stark_verifier_e2f4_corrupted_ood_frame() {
+    let example_source = "begin
+            repeat.32
+                swap dup.1 add
+            end
+        end";
+    let VerifierData {
+        initial_stack,
+        advice_stack: tape,
+        store,
+        advice_map,
+    } = generate_recursive_verifier_data(example_source, None, vec![1, 0]).unwrap();
+
+    // the OOD frame follows the number of kernel procedures, the public inputs, and the main trace,
+    // auxiliary trace and constraint commitments; it is composed of the current and next rows of
+    // the main (71 columns) and auxiliary (7 columns) traces, followed by the OOD evaluations of
+    // the constraint composition polynomial columns, each value being an extension field element
+    let num_pub_inputs = 4 * (12 + tape[0] as usize);
+    let ood_frame_ptr = 1 + num_pub_inputs + 3 * 4;
+    let main_current = ood_frame_ptr;
+    let aux_next = ood_frame_ptr + 2 * (71 + 7 + 71);
+    let constraint_evaluations = ood_frame_ptr + 2 * 2 * (71 + 7);
+
+    let source = "
+        use.std::crypto::stark::verifier
+        begin
+            exec.verifier::verify
+        end
+        ";
+
+    for (position, name) in [
+        (main_current, "main trace"),
+        (aux_next, "auxiliary trace"),
+        (constraint_evaluations, "constraint evaluation"),
+    ] {
+        let mut corrupted_tape = tape.clone();
+        corrupted_tape[position] += 1;
+
+        let test =
+            build_test!(source, &initial_stack, &corrupted_tape, store.clone(), advice_map.clone());
+        assert!(test.execute_without_trace().is_err(), "corrupted {name} OOD value was accepted");
+    }
+}
+
 // Helper function for recursive verification
 pub fn generate_recursive_verifier_data(
     source: &str,
+    kernel_source: Option<&str>,
     stack_inputs: Vec<u64>,
 ) -> Result<VerifierData, VerifierError> {
-    let program: Program = Assembler::default().assemble_program(source).unwrap();
-    let stack_inputs = StackInputs::try_from_ints(stack_inputs).unwrap();
-    let advice_inputs = AdviceInputs::default();
-    let advice_provider = MemAdviceProvider::from(advice_inputs);
+    let advice_provider = MemAdviceProvider::from(AdviceInputs::default());
     let mut host = DefaultHost::new(advice_provider);
+    let program: Program = match kernel_source {
+        Some(kernel_source) => {
+            let kernel_lib = Assembler::default().assemble_kernel(kernel_source).unwrap();
+            host.load_mast_forest(kernel_lib.mast_forest().clone()).unwrap();
+            Assembler::with_kernel(Arc::new(DefaultSourceManager::default()), kernel_lib)
+                .assemble_program(source)
+                .unwrap()
+        },
+        None => Assembler::default().assemble_program(source).unwrap(),
+    };
+    let stack_inputs = StackInputs::try_from_ints(stack_inputs).unwrap();
 
     let options =
         ProvingOptions::new(27, 8, 16, FieldExtension::Quadratic, 4, 127, HashFunction::Rpo256);
@@ -59,8 +243,6 @@ pub fn generate_recursive_verifier_data(
 
     let program_info = ProgramInfo::from(program);
 
-    // build public inputs and generate the advice data needed for recursive proof verification
-    let pub_inputs = PublicInputs::new(program_info, stack_inputs, stack_outputs);
-    let (_, proof) = proof.into_parts();
-    Ok(generate_advice_inputs(proof, pub_inputs).unwrap())
+    // generate the advice data needed for recursive proof verification
+    generate_verifier_data(proof, program_info, stack_inputs, stack_outputs)
 }
@@ -15,4 +15,6 @@ mod crypto;
 mod mast_forest_merge;
 mod math;
 mod mem;
+mod random;
+mod storage;
 mod sys;
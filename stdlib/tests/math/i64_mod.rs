@@ -0,0 +1,213 @@
+use test_utils::proptest::prelude::*;
+
+/// Values exercising the edge cases of signed 64-bit arithmetic.
+const EDGE_VALUES: [i64; 6] = [0, -1, 7, -3, i64::MAX, i64::MIN];
+
+// ARITHMETIC OPERATIONS
+// ------------------------------------------------------------------------------------------------
+
+#[test]
+fn overflowing_add() {
+    expect_binary_op("overflowing_add", |a, b| {
+        let (c, overflow) = a.overflowing_add(b);
+        [&[overflow as u64][..], &split_i64(c)].concat()
+    });
+}
+
+#[test]
+fn overflowing_sub() {
+    expect_binary_op("overflowing_sub", |a, b| {
+        let (c, overflow) = a.overflowing_sub(b);
+        [&[overflow as u64][..], &split_i64(c)].concat()
+    });
+}
+
+#[test]
+fn wrapping_mul() {
+    expect_binary_op("wrapping_mul", |a, b| split_i64(a.wrapping_mul(b)).to_vec());
+}
+
+#[test]
+fn neg_and_abs() {
+    for a in EDGE_VALUES {
+        expect_unary_op("neg", a, &split_i64(a.wrapping_neg()));
+        expect_unary_op("abs", a, &split_i64(a.wrapping_abs()));
+        expect_unary_op("is_neg", a, &[(a < 0) as u64]);
+    }
+}
+
+#[test]
+fn from_i32() {
+    let source = "
+        use.std::math::i64
+        use.std::sys
+
+        begin
+            exec.i64::from_i32
+            exec.sys::truncate_stack
+        end";
+
+    for a in [0, 1, -1, i32::MAX, i32::MIN] {
+        build_test!(source, &[a as u32 as u64]).expect_stack(&split_i64(a as i64));
+    }
+}
+
+// DIVISION
+// ------------------------------------------------------------------------------------------------
+
+#[test]
+fn div_mod() {
+    // the quotient is rounded towards zero, and the division of i64::MIN by -1 wraps around
+    expect_binary_op_nonzero("div", |a, b| split_i64(a.wrapping_div(b)).to_vec());
+    expect_binary_op_nonzero("mod", |a, b| split_i64(a.wrapping_rem(b)).to_vec());
+    expect_binary_op_nonzero("divmod", |a, b| {
+        [split_i64(a.wrapping_rem(b)), split_i64(a.wrapping_div(b))].concat()
+    });
+}
+
+#[test]
+fn div_by_zero() {
+    let source = "
+        use.std::math::i64
+        begin
+            exec.i64::div
+        end";
+
+    let (a1, a0) = split_i64_pair(-5);
+    assert!(build_test!(source, &[a0, a1, 0, 0]).execute().is_err());
+}
+
+// COMPARISONS
+// ------------------------------------------------------------------------------------------------
+
+#[test]
+fn comparisons() {
+    expect_binary_op("lt", |a, b| vec![(a < b) as u64]);
+    expect_binary_op("gt", |a, b| vec![(a > b) as u64]);
+    expect_binary_op("lte", |a, b| vec![(a <= b) as u64]);
+    expect_binary_op("gte", |a, b| vec![(a >= b) as u64]);
+    expect_binary_op("eq", |a, b| vec![(a == b) as u64]);
+    expect_binary_op("min", |a, b| split_i64(a.min(b)).to_vec());
+    expect_binary_op("max", |a, b| split_i64(a.max(b)).to_vec());
+}
+
+// BITWISE OPERATIONS
+// ------------------------------------------------------------------------------------------------
+
+#[test]
+fn shr() {
+    let source = "
+        use.std::math::i64
+        begin
+            exec.i64::shr
+        end";
+
+    for a in EDGE_VALUES {
+        for b in [0, 1, 31, 32, 63] {
+            let (a1, a0) = split_i64_pair(a);
+            build_test!(source, &[a0, a1, b as u64]).expect_stack(&split_i64(a >> b));
+        }
+    }
+}
+
+proptest! {
+    #[test]
+    fn div_mod_proptest(a in any::<i64>(), b in any::<i64>().prop_filter("non-zero", |b| *b != 0)) {
+        let source = "
+            use.std::math::i64
+            begin
+                exec.i64::divmod
+            end";
+
+        let (a1, a0) = split_i64_pair(a);
+        let (b1, b0) = split_i64_pair(b);
+        let expected = [split_i64(a.wrapping_rem(b)), split_i64(a.wrapping_div(b))].concat();
+        build_test!(source, &[a0, a1, b0, b1]).prop_expect_stack(&expected)?;
+    }
+
+    #[test]
+    fn lt_proptest(a in any::<i64>(), b in any::<i64>()) {
+        let source = "
+            use.std::math::i64
+            begin
+                exec.i64::lt
+            end";
+
+        let (a1, a0) = split_i64_pair(a);
+        let (b1, b0) = split_i64_pair(b);
+        build_test!(source, &[a0, a1, b0, b1]).prop_expect_stack(&[(a < b) as u64])?;
+    }
+
+    #[test]
+    fn shr_proptest(a in any::<i64>(), b in 0_u32..64) {
+        let source = "
+            use.std::math::i64
+            begin
+                exec.i64::shr
+            end";
+
+        let (a1, a0) = split_i64_pair(a);
+        build_test!(source, &[a0, a1, b as u64]).prop_expect_stack(&split_i64(a >> b))?;
+    }
+}
+
+// HELPER FUNCTIONS
+// ------------------------------------------------------------------------------------------------
+
+/// Checks the specified procedure of `std::math::i64` against `expected` for all pairs of edge
+/// values.
+fn expect_binary_op(procedure: &str, expected: impl Fn(i64, i64) -> Vec<u64>) {
+    expect_binary_op_filtered(procedure, expected, |_| true);
+}
+
+/// Same as [expect_binary_op], but skips a divisor of zero.
+fn expect_binary_op_nonzero(procedure: &str, expected: impl Fn(i64, i64) -> Vec<u64>) {
+    expect_binary_op_filtered(procedure, expected, |b| b != 0);
+}
+
+fn expect_binary_op_filtered(
+    procedure: &str,
+    expected: impl Fn(i64, i64) -> Vec<u64>,
+    filter: impl Fn(i64) -> bool,
+) {
+    let source = format!(
+        "
+        use.std::math::i64
+        begin
+            exec.i64::{procedure}
+        end"
+    );
+
+    for a in EDGE_VALUES {
+        for b in EDGE_VALUES.into_iter().filter(|&b| filter(b)) {
+            let (a1, a0) = split_i64_pair(a);
+            let (b1, b0) = split_i64_pair(b);
+            build_test!(&source, &[a0, a1, b0, b1]).expect_stack(&expected(a, b));
+        }
+    }
+}
+
+fn expect_unary_op(procedure: &str, a: i64, expected: &[u64]) {
+    let source = format!(
+        "
+        use.std::math::i64
+        begin
+            exec.i64::{procedure}
+        end"
+    );
+
+    let (a1, a0) = split_i64_pair(a);
+    build_test!(&source, &[a0, a1]).expect_stack(expected);
+}
+
+/// Returns the high and low limbs of the two's complement representation of the value.
+fn split_i64_pair(value: i64) -> (u64, u64) {
+    let value = value as u64;
+    (value >> 32, value as u32 as u64)
+}
+
+/// Returns the limbs of the value in the order in which they are on the stack.
+fn split_i64(value: i64) -> [u64; 2] {
+    let (hi, lo) = split_i64_pair(value);
+    [hi, lo]
+}
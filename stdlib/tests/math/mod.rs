@@ -1,4 +1,6 @@
 pub mod ecgfp5;
+mod i64_mod;
+mod ntt;
 mod secp256k1;
 mod u256_mod;
 mod u64_mod;
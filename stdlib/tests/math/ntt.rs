@@ -0,0 +1,110 @@
+use processor::ExecutionError;
+use test_utils::{Felt, expect_exec_error_matches, math::fft, rand::rand_vector};
+
+const COEFFS_PTR: u32 = 1000;
+const EVALS_PTR: u32 = 2000;
+
+#[test]
+fn forward() {
+    for n in [4, 8, 64] {
+        let coeffs: Vec<u64> = rand_vector::<Felt>(n).iter().map(Felt::as_int).collect();
+        let source = format!(
+            "
+        use.std::math::ntt
+        begin
+            {STORE_COEFFS}
+            push.{EVALS_PTR}.{COEFFS_PTR}.{n}
+            exec.ntt::forward
+        end",
+            STORE_COEFFS = store_values(&coeffs, COEFFS_PTR),
+        );
+
+        let test = build_test!(source, &[]);
+        test.expect_stack_and_memory(&[], EVALS_PTR, &evaluate(&coeffs));
+    }
+}
+
+#[test]
+fn inverse() {
+    for n in [4, 8, 64] {
+        let coeffs: Vec<u64> = rand_vector::<Felt>(n).iter().map(Felt::as_int).collect();
+        let source = format!(
+            "
+        use.std::math::ntt
+        begin
+            {STORE_EVALS}
+            push.{COEFFS_PTR}.{EVALS_PTR}.{n}
+            exec.ntt::inverse
+        end",
+            STORE_EVALS = store_values(&evaluate(&coeffs), EVALS_PTR),
+        );
+
+        let test = build_test!(source, &[]);
+        test.expect_stack_and_memory(&[], COEFFS_PTR, &coeffs);
+    }
+}
+
+#[test]
+fn verify() {
+    let coeffs: Vec<u64> = rand_vector::<Felt>(16).iter().map(Felt::as_int).collect();
+    let mut evals = evaluate(&coeffs);
+    let source = |evals: &[u64]| {
+        format!(
+            "
+        use.std::math::ntt
+        begin
+            {STORE_COEFFS}
+            {STORE_EVALS}
+            push.{EVALS_PTR}.{COEFFS_PTR}.16
+            exec.ntt::verify
+        end",
+            STORE_COEFFS = store_values(&coeffs, COEFFS_PTR),
+            STORE_EVALS = store_values(evals, EVALS_PTR),
+        )
+    };
+
+    let test = build_test!(source(&evals), &[]);
+    test.expect_stack(&[]);
+
+    // changing a single evaluation is detected
+    evals[5] = (Felt::new(evals[5]) + Felt::new(1)).as_int();
+    let test = build_test!(source(&evals), &[]);
+    expect_exec_error_matches!(test, ExecutionError::FailedAssertion { .. });
+}
+
+#[test]
+fn invalid_domain_size() {
+    let source = format!(
+        "
+    use.std::math::ntt
+    begin
+        push.{EVALS_PTR}.{COEFFS_PTR}.6
+        exec.ntt::forward
+    end"
+    );
+
+    let test = build_test!(source, &[]);
+    expect_exec_error_matches!(test, ExecutionError::FailedAssertion { .. });
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the evaluations of the polynomial with the provided coefficients over the domain of
+/// the same size.
+fn evaluate(coeffs: &[u64]) -> Vec<u64> {
+    let mut poly: Vec<Felt> = coeffs.iter().copied().map(Felt::new).collect();
+    let twiddles = fft::get_twiddles::<Felt>(poly.len());
+    fft::evaluate_poly(&mut poly, &twiddles);
+    poly.iter().map(Felt::as_int).collect()
+}
+
+/// Returns the MASM instructions which store the provided values in memory starting at `ptr`.
+fn store_values(values: &[u64], ptr: u32) -> String {
+    values
+        .iter()
+        .enumerate()
+        .map(|(i, value)| format!("push.{value} mem_store.{}", ptr + i as u32))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
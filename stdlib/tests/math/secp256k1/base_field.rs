@@ -496,3 +496,37 @@ fn test_secp256k1_base_field_inv() {
     let test = build_test!(source, &stack);
     test.expect_stack(&elm1.limbs.map(|v| v as u64));
 }
+
+#[test]
+fn test_secp256k1_base_field_inv_zero() {
+    let source = "
+    use.std::math::secp256k1::base_field
+
+    begin
+        exec.base_field::inv
+    end";
+
+    // zero has no inverse, and so the result is zero too
+    let test = build_test!(source, &[0; 8]);
+    test.expect_stack(&[0; 8]);
+}
+
+#[test]
+fn test_secp256k1_base_field_inv_modulus() {
+    let source = "
+    use.std::math::secp256k1::base_field
+
+    begin
+        exec.base_field::inv
+    end";
+
+    // the base field prime is zero modulo itself, and so the result is zero
+    let mut stack = [
+        4294966319, 4294967294, 4294967295, 4294967295, 4294967295, 4294967295, 4294967295,
+        4294967295,
+    ];
+    stack.reverse();
+
+    let test = build_test!(source, &stack);
+    test.expect_stack(&[0; 8]);
+}
@@ -302,3 +302,37 @@ fn test_secp256k1_scalar_field_inv() {
     let test = build_test!(source, &stack);
     test.expect_stack(&elm2.limbs.map(|v| v as u64));
 }
+
+#[test]
+fn test_secp256k1_scalar_field_inv_zero() {
+    let source = "
+    use.std::math::secp256k1::scalar_field
+
+    begin
+        exec.scalar_field::inv
+    end";
+
+    // zero has no inverse, and so the result is zero too
+    let test = build_test!(source, &[0; 8]);
+    test.expect_stack(&[0; 8]);
+}
+
+#[test]
+fn test_secp256k1_scalar_field_inv_modulus() {
+    let source = "
+    use.std::math::secp256k1::scalar_field
+
+    begin
+        exec.scalar_field::inv
+    end";
+
+    // the order of the group is zero modulo itself, and so the result is zero
+    let mut stack = [
+        3493216577, 3218235020, 2940772411, 3132021990, 4294967294, 4294967295, 4294967295,
+        4294967295,
+    ];
+    stack.reverse();
+
+    let test = build_test!(source, &stack);
+    test.expect_stack(&[0; 8]);
+}
@@ -1,8 +1,11 @@
 use core::cmp;
 
-use processor::ExecutionError;
+use assembly::{Assembler, IsaVersion};
+use miden_stdlib::StdLibrary;
+use processor::{DefaultHost, ExecutionError, ExecutionOptions};
 use test_utils::{
-    Felt, U32_BOUND, ZERO, expect_exec_error_matches, proptest::prelude::*, rand::rand_value,
+    Felt, StackInputs, U32_BOUND, ZERO, expect_exec_error_matches, proptest::prelude::*,
+    rand::rand_value,
 };
 use vm_core::assert_matches;
 
@@ -744,6 +747,13 @@ fn unchecked_shr() {
 
     build_test!(source, &[5, a0, a1, b as u64]).expect_stack(&[c1, c0, 5]);
 
+    // shift by 32 with all bits of the lower limb set
+    let a = u32::MAX as u64;
+    let (a1, a0) = split_u64(a);
+    let b: u32 = 32;
+
+    build_test!(source, &[5, a0, a1, b as u64]).expect_stack(&[0, 0, 5]);
+
     // shift 4294967296 by 2
     let a = 4294967296;
     let (a1, a0) = split_u64(a);
@@ -914,6 +924,48 @@ fn cto() {
     build_test!(source, &[255, 255]).expect_stack(&[8]);
 }
 
+// INTRINSICS
+// ------------------------------------------------------------------------------------------------
+
+/// Checks that the MASM implementations of the intrinsic procedures, which are invoked by programs
+/// targeting the initial version of the instruction set, compute the same results as the
+/// operations the invocations are lowered to when targeting the current version.
+#[test]
+fn intrinsics_fallback() {
+    let (a1, a0) = split_u64(rand_value());
+    let (b1, b0) = split_u64(rand_value());
+    let stack_inputs = StackInputs::try_from_ints([a0, a1, b0, b1]).unwrap();
+    let stdlib = StdLibrary::default();
+
+    for procedure in
+        ["overflowing_add", "wrapping_add", "overflowing_sub", "wrapping_sub", "lt", "gt"]
+    {
+        let source = format!("use.std::math::u64 begin exec.u64::{procedure} end");
+        let [fallback, lowered] = [IsaVersion::V1_0, IsaVersion::CURRENT].map(|isa_version| {
+            let program = Assembler::default()
+                .with_isa_version(isa_version)
+                .with_library(&stdlib)
+                .unwrap()
+                .assemble_program(source.as_str())
+                .unwrap();
+
+            let mut host = DefaultHost::default();
+            host.load_mast_forest(stdlib.mast_forest().clone()).unwrap();
+            let trace = processor::execute(
+                &program,
+                stack_inputs.clone(),
+                &mut host,
+                ExecutionOptions::default(),
+            )
+            .unwrap();
+            (trace.stack_outputs().clone(), trace.trace_len_summary().main_trace_len())
+        });
+
+        assert_eq!(fallback.0, lowered.0, "results of {procedure} differ");
+        assert!(lowered.1 < fallback.1, "{procedure} was not lowered");
+    }
+}
+
 // RANDOMIZED TESTS
 // ================================================================================================
 
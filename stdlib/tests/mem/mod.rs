@@ -1,7 +1,7 @@
-use processor::{ContextId, DefaultHost, Program};
+use processor::{ContextId, DefaultHost, ExecutionError, Program};
 use test_utils::{
     ExecutionOptions, ONE, Process, StackInputs, ZERO, build_expected_hash, build_expected_perm,
-    felt_slice_to_ints,
+    expect_exec_error_matches, felt_slice_to_ints,
 };
 
 #[test]
@@ -234,3 +234,34 @@ fn test_pipe_preimage_to_memory_invalid_preimage() {
     let res = build_test!(three_words, operand_stack, &advice_stack).execute();
     assert!(res.is_err());
 }
+
+#[test]
+fn test_protect() {
+    // writes outside of the protected range succeed
+    let source = "
+    use.std::mem
+
+    begin
+        push.1.1000 mem_store
+        push.1008.1004 exec.mem::protect
+        push.2.1003 mem_store
+        push.3.1008 mem_store
+        push.1000 mem_load swap drop
+    end
+    ";
+    build_test!(source, &[]).expect_stack(&[1]);
+
+    // writes inside of the protected range fail
+    let source = "
+    use.std::mem
+
+    begin
+        push.1008.1004 exec.mem::protect
+        push.0.0.0.0.1004 mem_storew
+    end
+    ";
+    expect_exec_error_matches!(
+        build_test!(source, &[]),
+        ExecutionError::ReadOnlyMemoryWrite { addr: 1004, .. }
+    );
+}
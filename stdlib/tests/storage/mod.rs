@@ -0,0 +1,155 @@
+use processor::{
+    DefaultHost, EVENT_RANDOM_WORD, EVENT_STORAGE_SET, ExecutionError, ExecutionOptions,
+    ExecutionTrace, MemAdviceProvider, StorageHost, SystemEvent, storage_map_key, storage_slot_key,
+};
+use test_utils::{Felt, Word, crypto::Smt};
+
+const SLOT_VALUE: Word = [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)];
+const MAP_KEY: Word = [Felt::new(5), Felt::new(6), Felt::new(7), Felt::new(8)];
+const MAP_VALUE: Word = [Felt::new(9), Felt::new(10), Felt::new(11), Felt::new(12)];
+
+#[test]
+fn slot_key() {
+    let source =
+        "use.std::storage begin exec.storage::slot_key movup.4 drop movup.4 drop movup.4 drop end";
+    let test = build_test!(source, &[7]);
+    test.expect_stack(&stack_word(storage_slot_key(7).into()));
+}
+
+#[test]
+fn map_key() {
+    let mut stack_inputs = word_to_ints(MAP_KEY);
+    stack_inputs.push(7);
+
+    let source = "use.std::storage begin exec.storage::map_key end";
+    let test = build_test!(source, &stack_inputs);
+    test.expect_stack(&stack_word(storage_map_key(7, MAP_KEY).into()));
+}
+
+#[test]
+fn storage_host() {
+    let mut host = StorageHost::new(DefaultHost::default());
+    host.set_item(0, SLOT_VALUE).unwrap();
+
+    // copy slot 0 to slot 1, and set an entry of map 2
+    let source = format!(
+        "
+    use.std::storage
+    begin
+        push.0 exec.storage::get_item
+        # => [VALUE, ROOT, ...]
+
+        push.1 exec.storage::set_item dropw
+        # => [ROOT, ...]
+
+        push.{MAP_VALUE} push.{MAP_KEY} push.2 exec.storage::set_map_item dropw
+        # => [ROOT, ...]
+    end",
+        MAP_VALUE = word_to_masm(MAP_VALUE),
+        MAP_KEY = word_to_masm(MAP_KEY),
+    );
+    let root = execute_with_storage(&source, &mut host);
+
+    assert_eq!(root, Word::from(host.root()));
+    assert_eq!(host.get_item(1), SLOT_VALUE);
+    assert_eq!(host.get_map_item(2, MAP_KEY), MAP_VALUE);
+
+    let expected_storage = Smt::with_entries([
+        (storage_slot_key(0), SLOT_VALUE),
+        (storage_slot_key(1), SLOT_VALUE),
+        (storage_map_key(2, MAP_KEY), MAP_VALUE),
+    ])
+    .unwrap();
+    assert_eq!(host.root(), expected_storage.root());
+
+    // the updates are visible to subsequent executions, including the ones made by the host
+    host.set_item(0, Word::default()).unwrap();
+    let source = format!(
+        "
+    use.std::storage
+    begin
+        push.{MAP_KEY} push.2 exec.storage::get_map_item
+        push.{MAP_VALUE} assert_eqw
+        # => [ROOT, ...]
+
+        push.0 exec.storage::get_item padw assert_eqw
+        # => [ROOT, ...]
+    end",
+        MAP_VALUE = word_to_masm(MAP_VALUE),
+        MAP_KEY = word_to_masm(MAP_KEY),
+    );
+    assert_eq!(execute_with_storage(&source, &mut host), Word::from(host.root()));
+}
+
+#[test]
+fn storage_host_root_mismatch() {
+    let mut host = StorageHost::new(DefaultHost::default());
+    host.set_item(0, SLOT_VALUE).unwrap();
+    let root = host.root();
+
+    // updating the storage against a root other than the root of the storage of the host fails,
+    // and leaves the storage of the host unchanged
+    let source = format!(
+        "
+    use.std::storage
+    begin
+        push.{SLOT_VALUE} push.1 exec.storage::set_item dropw
+    end",
+        SLOT_VALUE = word_to_masm(SLOT_VALUE),
+    );
+    let empty_root = word_to_ints(Smt::new().root().into());
+    let result = execute(&source, &empty_root, &mut host);
+    assert!(matches!(result, Err(ExecutionError::StorageRootMismatch { .. })));
+    assert_eq!(host.root(), root);
+}
+
+#[test]
+fn storage_event_id_is_unique() {
+    assert!(SystemEvent::from_event_id(EVENT_STORAGE_SET).is_none());
+    assert_ne!(EVENT_STORAGE_SET, miden_stdlib::EVENT_FALCON_SIG_TO_STACK);
+    assert_ne!(EVENT_STORAGE_SET, EVENT_RANDOM_WORD);
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Executes the provided program against the storage of the host, and returns the root of the
+/// storage output by the program.
+fn execute_with_storage(source: &str, host: &mut StorageHost<MemAdviceProvider>) -> Word {
+    let trace = execute(source, &word_to_ints(host.root().into()), host).unwrap();
+    let outputs = trace.stack_outputs();
+    [3, 2, 1, 0].map(|i| outputs.get_stack_item(i).unwrap())
+}
+
+/// Executes the provided program with the standard library loaded into the host.
+fn execute(
+    source: &str,
+    stack_inputs: &[u64],
+    host: &mut StorageHost<MemAdviceProvider>,
+) -> Result<ExecutionTrace, ExecutionError> {
+    let test = build_test!(source, stack_inputs);
+    let (program, _) = test.compile().unwrap();
+    host.host_mut()
+        .load_mast_forest(miden_stdlib::StdLibrary::default().mast_forest().clone())
+        .unwrap();
+
+    processor::execute(&program, test.stack_inputs.clone(), host, ExecutionOptions::default())
+}
+
+/// Returns the elements of a word in the order in which they are provided as stack inputs.
+fn word_to_ints(word: Word) -> Vec<u64> {
+    word.iter().map(|element| element.as_int()).collect()
+}
+
+/// Returns the elements of a word in the order in which they are pushed via `push.WORD`.
+fn word_to_masm(word: Word) -> String {
+    word.iter()
+        .map(|element| element.as_int().to_string())
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// Returns the elements of a word in the order in which they appear on the stack.
+fn stack_word(word: Word) -> Vec<u64> {
+    word.iter().rev().map(|element| element.as_int()).collect()
+}
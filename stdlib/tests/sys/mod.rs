@@ -1,4 +1,12 @@
-use test_utils::{MIN_STACK_DEPTH, proptest::prelude::*, rand::rand_vector};
+use miden_air::{DelegatedClaim, claims_commitment};
+use processor::{DefaultHost, ExecutionEnvironment, ExecutionOptions};
+use test_utils::{
+    Felt, MIN_STACK_DEPTH, StackInputs, StackOutputs, Word,
+    crypto::{Rpo256, RpoDigest},
+    proptest::prelude::*,
+    rand::rand_vector,
+};
+use vm_core::{Kernel, ProgramInfo};
 
 #[test]
 fn truncate_stack() {
@@ -7,6 +15,91 @@ fn truncate_stack() {
     test.expect_stack(&[0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 2, 3, 4]);
 }
 
+#[test]
+fn overflow_size() {
+    let source = "
+    use.std::sys
+    begin
+        exec.sys::get_overflow_size
+        push.7 exec.sys::get_overflow_size
+        movup.3 drop movup.3 drop movup.3 drop
+    end";
+    let test = build_test!(source, &[]);
+    test.expect_stack(&[2, 7, 0]);
+
+    let source = "use.std::sys begin exec.sys::assert_overflow_empty push.1 swap drop end";
+    build_test!(source, &[]).expect_stack(&[1]);
+
+    let source = "use.std::sys begin push.1 exec.sys::assert_overflow_empty end";
+    assert!(build_test!(source, &[]).execute().is_err());
+}
+
+#[test]
+fn claim_digest() {
+    let claim = build_claim(1);
+    let inputs = Rpo256::hash_elements(claim.stack_inputs().as_slice());
+    let outputs = Rpo256::hash_elements(claim.stack_outputs().as_slice());
+
+    // words are pushed onto the stack in reverse order, so that PROGRAM ends up at the top
+    let mut stack_inputs = Vec::new();
+    for word in [outputs, inputs, claim.program_commitment()] {
+        stack_inputs.extend(word_to_ints(word.into()));
+    }
+
+    let source = "use.std::sys begin exec.sys::claim_digest end";
+    let test = build_test!(source, &stack_inputs);
+    test.expect_stack(&stack_word(claim.digest().into()));
+}
+
+#[test]
+fn consume_claims() {
+    let claims = [build_claim(1), build_claim(2)];
+
+    let mut stack_inputs = Vec::new();
+    for claim in claims.iter().rev() {
+        stack_inputs.extend(word_to_ints(claim.digest().into()));
+    }
+
+    // start with the empty commitment, and consume both claims
+    let source = "
+    use.std::sys
+    begin
+        padw swapw exec.sys::consume_claim
+        swapw exec.sys::consume_claim
+    end";
+    let test = build_test!(source, &stack_inputs);
+    test.expect_stack(&stack_word(claims_commitment(&claims)));
+}
+
+#[test]
+fn execution_environment() {
+    let seed = [1, 2, 3, 4].map(Felt::new);
+    let caller_id = [5, 6, 7, 8].map(Felt::new);
+    let environment = ExecutionEnvironment::new(42, 1_700_000_000, seed, caller_id);
+
+    let mut expected = vec![42, 1_700_000_000];
+    expected.extend(stack_word(seed));
+    expected.extend(stack_word(caller_id));
+
+    let source = "use.std::sys begin exec.sys::get_env exec.sys::truncate_stack end";
+    assert_eq!(&execute_in(source, environment)[..10], &expected[..]);
+
+    // each value of the environment can also be read on its own
+    let source = "
+    use.std::sys
+    begin
+        exec.sys::get_caller_id
+        exec.sys::get_seed
+        exec.sys::get_timestamp
+        exec.sys::get_block_num
+        exec.sys::truncate_stack
+    end";
+    assert_eq!(&execute_in(source, environment)[..10], &expected[..]);
+
+    // the default environment has all values set to zero
+    assert_eq!(execute_in(source, ExecutionEnvironment::default()), vec![0; 16]);
+}
+
 proptest! {
     #[test]
     fn truncate_stack_proptest(test_values in prop::collection::vec(any::<u64>(), MIN_STACK_DEPTH), n in 1_usize..100) {
@@ -26,3 +119,45 @@ proptest! {
         build_test!(&source, &test_values).prop_expect_stack(&expected_values)?;
     }
 }
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Executes the provided program with the host providing the specified environment, and returns
+/// the stack outputs.
+fn execute_in(source: &str, environment: ExecutionEnvironment) -> Vec<u64> {
+    let test = build_test!(source);
+    let (program, _) = test.compile().unwrap();
+    let mut host = DefaultHost::default().with_environment(environment);
+    host.load_mast_forest(miden_stdlib::StdLibrary::default().mast_forest().clone())
+        .unwrap();
+
+    let trace = processor::execute(
+        &program,
+        StackInputs::default(),
+        &mut host,
+        ExecutionOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(trace.environment(), &environment);
+    trace.stack_outputs().iter().map(|element| element.as_int()).collect()
+}
+
+/// Builds a claim about an arbitrary program, whose inputs and outputs are derived from `seed`.
+fn build_claim(seed: u64) -> DelegatedClaim {
+    let program_hash = RpoDigest::new([seed, seed + 1, seed + 2, seed + 3].map(Felt::new));
+    let program_info = ProgramInfo::new(program_hash, Kernel::default());
+    let stack_inputs = StackInputs::try_from_ints((0..16).map(|i| seed * 100 + i)).unwrap();
+    let stack_outputs = StackOutputs::try_from_ints((0..16).map(|i| seed * 1000 + i)).unwrap();
+    DelegatedClaim::new(program_info, stack_inputs, stack_outputs)
+}
+
+/// Returns the elements of a word in the order in which they must be pushed onto the stack.
+fn word_to_ints(word: Word) -> Vec<u64> {
+    word.iter().map(|element| element.as_int()).collect()
+}
+
+/// Returns the elements of a word in the order in which they appear on the stack.
+fn stack_word(word: Word) -> Vec<u64> {
+    word.iter().rev().map(|element| element.as_int()).collect()
+}
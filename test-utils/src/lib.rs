@@ -19,7 +19,7 @@ pub use pretty_assertions::{assert_eq, assert_ne, assert_str_eq};
 use processor::Program;
 pub use processor::{
     AdviceInputs, AdviceProvider, ContextId, ExecutionError, ExecutionOptions, ExecutionTrace,
-    Process, ProcessState, VmStateIterator,
+    MemoryState, Process, ProcessState, VmStateIterator,
 };
 #[cfg(not(target_family = "wasm"))]
 use proptest::prelude::{Arbitrary, Strategy};
@@ -28,8 +28,8 @@ pub use prover::{MemAdviceProvider, MerkleTreeVC, ProvingOptions, prove};
 pub use test_case::test_case;
 pub use verifier::{AcceptableOptions, VerifierError, verify};
 pub use vm_core::{
-    EMPTY_WORD, Felt, FieldElement, ONE, StackInputs, StackOutputs, StarkField, WORD_SIZE, Word,
-    ZERO,
+    EMPTY_WORD, Felt, FieldElement, ONE, Operation, StackInputs, StackOutputs, StarkField,
+    WORD_SIZE, Word, ZERO,
     chiplets::hasher::{STATE_WIDTH, hash_elements},
     stack::MIN_STACK_DEPTH,
     utils::{IntoBytes, ToElements, collections, group_slice_elements},
@@ -56,6 +56,9 @@ use host::TestHost;
 #[cfg(not(target_family = "wasm"))]
 pub mod rand;
 
+mod operation;
+pub use operation::OperationTest;
+
 mod test_builders;
 
 #[cfg(not(target_family = "wasm"))]
@@ -171,7 +174,7 @@ macro_rules! assert_assembler_diagnostic {
 /// - Execution error test: check that running a program compiled from the given source causes an
 ///   ExecutionError which contains the specified substring.
 pub struct Test {
-    pub source_manager: Arc<dyn SourceManager>,
+    pub source_manager: Arc<dyn SourceManager + Send + Sync>,
     pub source: Arc<SourceFile>,
     pub kernel_source: Option<Arc<SourceFile>>,
     pub stack_inputs: StackInputs,
@@ -269,6 +272,19 @@ impl Test {
         self.expect_stack(final_stack);
     }
 
+    /// Executes the test and validates that the final memory state across all execution contexts
+    /// is equal to `expected_memory`, reporting the words which differ otherwise.
+    #[track_caller]
+    pub fn expect_memory(&self, expected_memory: &MemoryState) {
+        let trace = self.execute().expect("Failed to execute test.");
+        let diff = expected_memory.diff(trace.memory());
+        assert!(
+            diff.is_empty(),
+            "Unexpected final memory state (expected -> found):\n{}",
+            diff.iter().map(ToString::to_string).collect::<Vec<_>>().join("\n")
+        );
+    }
+
     /// Asserts that executing the test inside a proptest results in the expected final stack state.
     /// The proptest will return a test failure instead of panicking if the assertion condition
     /// fails.
@@ -345,6 +361,29 @@ impl Test {
         )
     }
 
+    /// Compiles the test's source to a Program and executes it with the tests inputs, without
+    /// generating the execution trace. Returns the resulting stack outputs or error.
+    ///
+    /// This is intended for tests of long-running programs, whose execution trace would not fit
+    /// into memory.
+    #[track_caller]
+    pub fn execute_without_trace(&self) -> Result<StackOutputs, ExecutionError> {
+        let (program, kernel) = self.compile().expect("Failed to compile test source.");
+        let mut host = TestHost::new(MemAdviceProvider::from(self.advice_inputs.clone()));
+        if let Some(kernel) = kernel {
+            host.load_mast_forest(kernel.mast_forest().clone()).unwrap();
+        }
+        for library in &self.libraries {
+            host.load_mast_forest(library.mast_forest().clone()).unwrap();
+        }
+        processor::execute_without_trace(
+            &program,
+            self.stack_inputs.clone(),
+            &mut host,
+            ExecutionOptions::default(),
+        )
+    }
+
     /// Compiles the test's source to a Program and executes it with the tests inputs. Returns the
     /// process once execution is finished.
     pub fn execute_process(&self) -> Result<(Process, TestHost), ExecutionError> {
@@ -0,0 +1,158 @@
+use alloc::vec::Vec;
+
+use processor::Program;
+use vm_core::{Kernel, Operation, ProgramInfo, mast::MastForest};
+
+use super::{
+    AdviceInputs, ContextId, ExecutionError, ExecutionOptions, ExecutionTrace, MIN_STACK_DEPTH,
+    MemAdviceProvider, Process, ProcessState, ProvingOptions, StackInputs, ZERO, assert_eq,
+    host::TestHost,
+};
+
+// OPERATION TEST
+// ================================================================================================
+
+/// A container for the data required to test a sequence of VM operations, without going through
+/// the assembler.
+///
+/// The operations are executed as a single basic block, which makes it possible to test the
+/// semantics of each [Operation] variant in isolation, including operations which have no direct
+/// assembly counterpart. Control flow operations (e.g., `Join`, `Split`, `Call`) cannot be part
+/// of a basic block, and are thus not supported.
+///
+/// Tests are usually built with the `build_operation_test` macro rather than directly.
+pub struct OperationTest {
+    pub operations: Vec<Operation>,
+    pub stack_inputs: StackInputs,
+    pub advice_inputs: AdviceInputs,
+}
+
+impl OperationTest {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Creates a new test executing the provided operations against an empty stack and empty
+    /// advice inputs.
+    pub fn new(operations: Vec<Operation>) -> Self {
+        Self {
+            operations,
+            stack_inputs: StackInputs::default(),
+            advice_inputs: AdviceInputs::default(),
+        }
+    }
+
+    // TEST METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Builds a final stack from the provided stack-ordered array and asserts that executing the
+    /// operations will result in the expected final stack state.
+    ///
+    /// Unlike [crate::Test::expect_stack], the final stack is not required to have a depth of 16,
+    /// and items in the overflow table are compared as well. The expected stack is padded with
+    /// ZEROs to the depth of the final stack.
+    #[track_caller]
+    pub fn expect_stack(&self, final_stack: &[u64]) {
+        let (process, _) = self.execute_process().unwrap();
+        let result: Vec<u64> = ProcessState::from(&process)
+            .get_stack_state()
+            .into_iter()
+            .map(|value| value.as_int())
+            .collect();
+        let mut expected = final_stack.to_vec();
+        expected.resize(result.len().max(final_stack.len()).max(MIN_STACK_DEPTH), 0);
+        assert_eq!(expected, result, "Expected stack to be {:?}, found {:?}", expected, result);
+    }
+
+    /// Executes the operations and validates that the root context memory has the elements of
+    /// `expected_mem` at address `mem_start_addr`, and that the final stack matches
+    /// `final_stack`.
+    #[track_caller]
+    pub fn expect_stack_and_memory(
+        &self,
+        final_stack: &[u64],
+        mem_start_addr: u32,
+        expected_mem: &[u64],
+    ) {
+        let (process, _) = self.execute_process().unwrap();
+        let state = ProcessState::from(&process);
+
+        for (addr, mem_value) in (mem_start_addr..).zip(expected_mem.iter()) {
+            let mem_state = state.get_mem_value(ContextId::root(), addr).unwrap_or(ZERO);
+            assert_eq!(
+                *mem_value,
+                mem_state.as_int(),
+                "Expected memory [{}] => {:?}, found {:?}",
+                addr,
+                mem_value,
+                mem_state
+            );
+        }
+
+        self.expect_stack(final_stack);
+    }
+
+    /// Generates and verifies a proof of executing the operations, which checks that the trace
+    /// generated for them satisfies the constraints of the VM.
+    ///
+    /// The final stack must have a depth of 16 for the execution to succeed.
+    #[track_caller]
+    pub fn prove_and_verify(&self) {
+        let program = self.build_program();
+        let mut host = TestHost::new(MemAdviceProvider::from(self.advice_inputs.clone()));
+        let (stack_outputs, proof) = prover::prove(
+            &program,
+            self.stack_inputs.clone(),
+            &mut host,
+            ProvingOptions::default(),
+        )
+        .unwrap();
+
+        let program_info = ProgramInfo::from(program);
+        let result =
+            verifier::verify(program_info, self.stack_inputs.clone(), stack_outputs, proof);
+        assert!(result.is_ok(), "error: {result:?}");
+    }
+
+    // UTILITY METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Builds a program consisting of a single basic block with the test's operations.
+    pub fn build_program(&self) -> Program {
+        let mut mast_forest = MastForest::new();
+        let basic_block_id = mast_forest
+            .add_block(self.operations.clone(), None)
+            .expect("failed to build a basic block from the test operations");
+        mast_forest.make_root(basic_block_id);
+
+        Program::new(mast_forest.into(), basic_block_id)
+    }
+
+    /// Executes the operations with the test's inputs. Returns the resulting execution trace or
+    /// error.
+    ///
+    /// As for programs, execution fails if the final stack has a depth greater than 16.
+    pub fn execute(&self) -> Result<ExecutionTrace, ExecutionError> {
+        let mut host = TestHost::new(MemAdviceProvider::from(self.advice_inputs.clone()));
+        processor::execute(
+            &self.build_program(),
+            self.stack_inputs.clone(),
+            &mut host,
+            ExecutionOptions::default(),
+        )
+    }
+
+    /// Executes the operations with the test's inputs. Returns the process once execution is
+    /// finished.
+    ///
+    /// Unlike [Self::execute], this succeeds if the final stack has a depth greater than 16, so
+    /// that the effects of operations which grow the stack can be inspected.
+    pub fn execute_process(&self) -> Result<(Process, TestHost), ExecutionError> {
+        let mut host = TestHost::new(MemAdviceProvider::from(self.advice_inputs.clone()));
+        let mut process =
+            Process::new(Kernel::default(), self.stack_inputs.clone(), ExecutionOptions::default());
+        match process.execute(&self.build_program(), &mut host) {
+            Ok(_) | Err(ExecutionError::OutputStackOverflow(_)) => Ok((process, host)),
+            Err(err) => Err(err),
+        }
+    }
+}
@@ -219,3 +219,40 @@ macro_rules! build_test_by_mode {
         }
     }};
 }
+
+/// Returns an OperationTest struct from a list of VM operations and any specified stack and
+/// advice inputs.
+///
+/// Parameters are expected in the following order:
+/// `operations`, `stack_inputs` (optional), `advice_stack` (optional)
+///
+/// * `operations`: a bracketed list of [Operation](crate::Operation) variants, e.g.
+///   `[Push(ONE), Add]`. The variants of [Operation](crate::Operation) are in scope within the
+///   list, so they don't need to be qualified.
+/// * `stack_inputs` (optional): the initial inputs which must be at the top of the stack before
+///   executing the `operations`. Stack inputs can be provided independently without any advice
+///   inputs.
+/// * `advice_stack` (optional): the initial advice stack values. When provided, `stack_inputs` are
+///   also expected.
+#[macro_export]
+macro_rules! build_operation_test {
+    ([$($op:expr),* $(,)?] $(, $stack_inputs:expr $(, $advice_stack:expr)?)? $(,)?) => {{
+        let operations: Vec<$crate::Operation> = {
+            #[allow(unused_imports)]
+            use $crate::Operation::*;
+            vec![$($op),*]
+        };
+        #[allow(unused_mut)]
+        let mut test = $crate::OperationTest::new(operations);
+        $(
+            let stack_inputs: Vec<u64> = $stack_inputs.to_vec();
+            test.stack_inputs = $crate::StackInputs::try_from_ints(stack_inputs).unwrap();
+            $(
+                let stack_values: Vec<u64> = $advice_stack.to_vec();
+                test.advice_inputs =
+                    $crate::AdviceInputs::default().with_stack_values(stack_values).unwrap();
+            )?
+        )?
+        test
+    }};
+}
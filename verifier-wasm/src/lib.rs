@@ -0,0 +1,137 @@
+use verifier::{
+    Digest, ExecutionProof, Kernel, ProgramInfo, StackInputs, StackOutputs, VerificationError,
+};
+use wasm_bindgen::prelude::*;
+
+// JS API
+// ================================================================================================
+
+/// Returns the security level of the proof if the program with the specified hash was executed
+/// correctly against the specified inputs and outputs.
+///
+/// This is exported to JavaScript as `verify(programHashHex, stackInputs, stackOutputs,
+/// proofBytes)`, where the stack inputs and outputs are `BigUint64Array`s, and the proof is a
+/// `Uint8Array`. See [verify_proof] for the expected format of the arguments.
+///
+/// # Errors
+/// Throws a JavaScript `Error` with the message of the [VerifyError] returned by [verify_proof].
+#[wasm_bindgen(js_name = verify)]
+pub fn verify_js(
+    program_hash_hex: &str,
+    stack_inputs: Vec<u64>,
+    stack_outputs: Vec<u64>,
+    proof_bytes: &[u8],
+) -> Result<u32, JsError> {
+    verify_proof(program_hash_hex, &stack_inputs, &stack_outputs, proof_bytes)
+        .map_err(|err| JsError::new(&err.to_string()))
+}
+
+// VERIFIER
+// ================================================================================================
+
+/// Returns the security level of the proof if the program with the specified hash was executed
+/// correctly against the specified inputs and outputs.
+///
+/// - `program_hash_hex` is the hex encoding of the hash of the program, with or without the `0x`
+///   prefix, as printed by `miden prove`.
+/// - `stack_inputs` and `stack_outputs` are ordered as described in [verifier::verify()].
+/// - `proof_bytes` is the proof serialized via `ExecutionProof::to_bytes`, i.e., the contents of
+///   the `.proof` files written by `miden prove`.
+///
+/// The program is expected to have been executed without a kernel, in the default execution
+/// environment.
+///
+/// # Errors
+/// Returns an error if any of the arguments is malformed, or if the proof fails to verify as
+/// described in [verifier::verify()].
+pub fn verify_proof(
+    program_hash_hex: &str,
+    stack_inputs: &[u64],
+    stack_outputs: &[u64],
+    proof_bytes: &[u8],
+) -> Result<u32, VerifyError> {
+    let program_hash = if program_hash_hex.starts_with("0x") {
+        Digest::try_from(program_hash_hex)
+    } else {
+        Digest::try_from(format!("0x{program_hash_hex}").as_str())
+    }
+    .map_err(|err| VerifyError::InvalidProgramHash(err.to_string()))?;
+    let stack_inputs = StackInputs::try_from_ints(stack_inputs.iter().copied())
+        .map_err(|err| VerifyError::InvalidStackInputs(err.to_string()))?;
+    let stack_outputs = StackOutputs::try_from_ints(stack_outputs.iter().copied())
+        .map_err(|err| VerifyError::InvalidStackOutputs(err.to_string()))?;
+    let proof = ExecutionProof::from_bytes(proof_bytes)
+        .map_err(|err| VerifyError::InvalidProof(err.to_string()))?;
+
+    let program_info = ProgramInfo::new(program_hash, Kernel::default());
+    Ok(verifier::verify(program_info, stack_inputs, stack_outputs, proof)?)
+}
+
+// ERRORS
+// ================================================================================================
+
+/// An error returned by [verify_proof].
+///
+/// As these errors are passed to JavaScript as messages, the errors of malformed arguments are
+/// kept as the messages of the errors they were parsed with.
+#[derive(Debug, thiserror::Error)]
+pub enum VerifyError {
+    #[error("invalid program hash: {0}")]
+    InvalidProgramHash(String),
+    #[error("invalid stack inputs: {0}")]
+    InvalidStackInputs(String),
+    #[error("invalid stack outputs: {0}")]
+    InvalidStackOutputs(String),
+    #[error("failed to deserialize the proof: {0}")]
+    InvalidProof(String),
+    #[error(transparent)]
+    Verification(#[from] VerificationError),
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use assembly::Assembler;
+    use processor::DefaultHost;
+    use prover::{ProvingOptions, prove};
+
+    use super::*;
+
+    #[test]
+    fn verify_serialized_proof() {
+        let program = Assembler::default().assemble_program("begin push.3 add end").unwrap();
+        let (stack_outputs, proof) = prove(
+            &program,
+            StackInputs::try_from_ints([5]).unwrap(),
+            &mut DefaultHost::default(),
+            ProvingOptions::default(),
+        )
+        .unwrap();
+        let stack_outputs = stack_outputs.iter().map(|felt| felt.as_int()).collect::<Vec<_>>();
+        let proof_bytes = proof.to_bytes();
+
+        let program_hash = program.hash().to_string();
+        let security_level = verify_proof(&program_hash, &[5], &stack_outputs, &proof_bytes)
+            .expect("proof must be valid");
+        assert_eq!(security_level, proof.security_level());
+
+        // the prefix of the program hash is optional
+        let unprefixed = program_hash.strip_prefix("0x").unwrap();
+        assert!(verify_proof(unprefixed, &[5], &stack_outputs, &proof_bytes).is_ok());
+
+        assert!(matches!(
+            verify_proof(&program_hash, &[6], &stack_outputs, &proof_bytes),
+            Err(VerifyError::Verification(_))
+        ));
+        assert!(matches!(
+            verify_proof("0x1234", &[5], &stack_outputs, &proof_bytes),
+            Err(VerifyError::InvalidProgramHash(_))
+        ));
+        assert!(matches!(
+            verify_proof(&program_hash, &[5], &stack_outputs, &proof_bytes[1..]),
+            Err(VerifyError::InvalidProof(_))
+        ));
+    }
+}
@@ -0,0 +1,278 @@
+use alloc::{collections::BTreeSet, vec::Vec};
+
+use air::PublicInputs;
+use vm_core::{ExecutionEnvironment, ProgramInfo, StackInputs, StackOutputs};
+
+use crate::{Digest, ExecutionProof, VerificationError, verify_batch, verify_with_environment};
+
+// VERIFIER
+// ================================================================================================
+
+/// A verifier which accepts only proofs of a known set of programs.
+///
+/// Services verifying proofs usually need to confirm, in addition to the validity of a proof, that
+/// the proven program is one of the programs they expect. A [Verifier] checks this before
+/// verifying a proof, against the following policy:
+/// - The hash of the program must be one of the allowed program hashes, if any were specified.
+/// - Every kernel procedure of the program must be one of the allowed kernel procedure roots, if
+///   any were specified. Programs without a kernel are not restricted by this rule.
+/// - The security level of the proof must be at least the minimum security level, if one was
+///   specified.
+///
+/// These checks are cheap relative to verifying a proof, so [Verifier::verify_batch] performs them
+/// for all proofs of a batch before verifying any of them.
+///
+/// A [Verifier] without any restrictions accepts the same proofs as [crate::verify()].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Verifier {
+    allowed_programs: Option<BTreeSet<Digest>>,
+    allowed_kernel_procedures: Option<BTreeSet<Digest>>,
+    min_security_level: u32,
+}
+
+impl Verifier {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new verifier which accepts any valid proof.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Restricts the accepted programs to the programs with the specified hashes.
+    ///
+    /// If this is called multiple times, the hashes of all calls are allowed.
+    pub fn with_allowed_programs(
+        mut self,
+        program_hashes: impl IntoIterator<Item = Digest>,
+    ) -> Self {
+        self.allowed_programs.get_or_insert_default().extend(program_hashes);
+        self
+    }
+
+    /// Restricts the accepted programs to the programs whose kernel procedures all have one of the
+    /// specified MAST roots.
+    ///
+    /// If this is called multiple times, the roots of all calls are allowed.
+    pub fn with_allowed_kernel_procedures(
+        mut self,
+        proc_hashes: impl IntoIterator<Item = Digest>,
+    ) -> Self {
+        self.allowed_kernel_procedures.get_or_insert_default().extend(proc_hashes);
+        self
+    }
+
+    /// Rejects the proofs whose security level is lower than the specified one.
+    pub fn with_min_security_level(mut self, min_security_level: u32) -> Self {
+        self.min_security_level = min_security_level;
+        self
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns true if this verifier accepts proofs of the program with the specified hash.
+    pub fn is_program_allowed(&self, program_hash: &Digest) -> bool {
+        self.allowed_programs
+            .as_ref()
+            .is_none_or(|allowed| allowed.contains(program_hash))
+    }
+
+    /// Returns true if this verifier accepts proofs of programs with a kernel procedure with the
+    /// specified MAST root.
+    pub fn is_kernel_procedure_allowed(&self, proc_hash: &Digest) -> bool {
+        self.allowed_kernel_procedures
+            .as_ref()
+            .is_none_or(|allowed| allowed.contains(proc_hash))
+    }
+
+    /// Returns the minimum security level of the accepted proofs.
+    pub fn min_security_level(&self) -> u32 {
+        self.min_security_level
+    }
+
+    // VERIFICATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Checks that a proof of the specified program, with the specified security level, is
+    /// accepted by this verifier, without verifying the proof itself.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The hash of the program is not allowed.
+    /// - A kernel procedure of the program is not allowed.
+    /// - The security level is lower than the minimum security level.
+    pub fn check(
+        &self,
+        program_info: &ProgramInfo,
+        security_level: u32,
+    ) -> Result<(), VerificationError> {
+        let program_hash = *program_info.program_hash();
+        if !self.is_program_allowed(&program_hash) {
+            return Err(VerificationError::ProgramNotAllowed(program_hash));
+        }
+
+        if let Some(&proc_hash) = program_info
+            .kernel_procedures()
+            .iter()
+            .find(|proc_hash| !self.is_kernel_procedure_allowed(proc_hash))
+        {
+            return Err(VerificationError::KernelProcedureNotAllowed {
+                program: program_hash,
+                procedure: proc_hash,
+            });
+        }
+
+        if security_level < self.min_security_level {
+            return Err(VerificationError::InsufficientSecurityLevel {
+                program: program_hash,
+                security_level,
+                min_security_level: self.min_security_level,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Returns the security level of the proof if the proven program is accepted by this
+    /// verifier, and if it was executed correctly against the specified inputs and outputs.
+    ///
+    /// See [crate::verify()] for the expected order of the inputs and outputs.
+    ///
+    /// # Errors
+    /// Returns an error if the program is not accepted by this verifier (see [Verifier::check]),
+    /// or if the proof fails to verify as described in [crate::verify()].
+    pub fn verify(
+        &self,
+        program_info: ProgramInfo,
+        stack_inputs: StackInputs,
+        stack_outputs: StackOutputs,
+        proof: ExecutionProof,
+    ) -> Result<u32, VerificationError> {
+        self.verify_with_environment(
+            program_info,
+            stack_inputs,
+            stack_outputs,
+            ExecutionEnvironment::default(),
+            proof,
+        )
+    }
+
+    /// Returns the security level of the proof if the proven program is accepted by this
+    /// verifier, and if it was executed correctly against the specified inputs and outputs in the
+    /// specified environment.
+    ///
+    /// # Errors
+    /// Returns an error if the program is not accepted by this verifier (see [Verifier::check]),
+    /// or if the proof fails to verify as described in [crate::verify_with_environment()].
+    pub fn verify_with_environment(
+        &self,
+        program_info: ProgramInfo,
+        stack_inputs: StackInputs,
+        stack_outputs: StackOutputs,
+        environment: ExecutionEnvironment,
+        proof: ExecutionProof,
+    ) -> Result<u32, VerificationError> {
+        self.check(&program_info, proof.security_level())?;
+        verify_with_environment(program_info, stack_inputs, stack_outputs, environment, proof)
+    }
+
+    /// Verifies the specified batch of proofs, and returns the result of the verification of each
+    /// of them, in order.
+    ///
+    /// All proofs are checked against the policy of this verifier (see [Verifier::check]) before
+    /// any of them is verified, so that the proofs of programs which are not accepted are rejected
+    /// without incurring the cost of verification.
+    pub fn verify_batch(
+        &self,
+        requests: impl IntoIterator<Item = VerificationRequest>,
+    ) -> Vec<Result<u32, VerificationError>> {
+        // results of the requests rejected by the policy; the accepted ones are verified together
+        let mut rejected = Vec::new();
+        let mut accepted = Vec::new();
+        for request in requests {
+            match self.check(&request.program_info, request.proof.security_level()) {
+                Ok(()) => {
+                    accepted.push(request);
+                    rejected.push(None);
+                },
+                Err(err) => rejected.push(Some(Err(err))),
+            }
+        }
+
+        let mut verified = verify_batch(accepted).into_iter();
+        rejected
+            .into_iter()
+            .map(|result| {
+                result.unwrap_or_else(|| verified.next().expect("missing verification result"))
+            })
+            .collect()
+    }
+}
+
+// VERIFICATION REQUEST
+// ================================================================================================
+
+/// A proof of the execution of a program, together with the public inputs it is verified against.
+#[derive(Debug, Clone)]
+pub struct VerificationRequest {
+    program_info: ProgramInfo,
+    stack_inputs: StackInputs,
+    stack_outputs: StackOutputs,
+    environment: ExecutionEnvironment,
+    initial_memory: Digest,
+    proof: ExecutionProof,
+}
+
+impl VerificationRequest {
+    /// Returns a new request to verify the specified proof of the execution of a program against
+    /// the specified inputs and outputs, in the default execution environment and with memory
+    /// initialized to zero.
+    pub fn new(
+        program_info: ProgramInfo,
+        stack_inputs: StackInputs,
+        stack_outputs: StackOutputs,
+        proof: ExecutionProof,
+    ) -> Self {
+        Self {
+            program_info,
+            stack_inputs,
+            stack_outputs,
+            environment: ExecutionEnvironment::default(),
+            initial_memory: Digest::default(),
+            proof,
+        }
+    }
+
+    /// Sets the execution environment the proof is verified against.
+    pub fn with_environment(mut self, environment: ExecutionEnvironment) -> Self {
+        self.environment = environment;
+        self
+    }
+
+    /// Sets the commitment to the memory the execution started from which the proof is verified
+    /// against, e.g., as returned by `MemoryState::commitment` of the processor.
+    pub fn with_initial_memory(mut self, commitment: Digest) -> Self {
+        self.initial_memory = commitment;
+        self
+    }
+
+    /// Returns information about the program whose execution is proven.
+    pub fn program_info(&self) -> &ProgramInfo {
+        &self.program_info
+    }
+
+    /// Returns the proof to verify.
+    pub fn proof(&self) -> &ExecutionProof {
+        &self.proof
+    }
+
+    /// Returns the public inputs the proof is verified against, and the proof itself.
+    pub(crate) fn into_parts(self) -> (PublicInputs, ExecutionProof) {
+        let pub_inputs =
+            PublicInputs::new(self.program_info, self.stack_inputs, self.stack_outputs)
+                .with_environment(self.environment)
+                .with_initial_memory(self.initial_memory);
+        (pub_inputs, self.proof)
+    }
+}
@@ -0,0 +1,102 @@
+use alloc::string::{String, ToString};
+
+use winter_fri::VerifierError as FriVerifierError;
+use winter_verifier::VerifierError;
+
+// VERIFICATION FAILURE
+// ================================================================================================
+
+/// The check of the STARK verification protocol which a proof failed.
+///
+/// This is derived from the error reported by the underlying STARK verifier, and is meant to help
+/// diagnose why a proof does not verify. Indices are included where the STARK verifier reports
+/// them.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum VerificationFailure {
+    /// The proof could not be deserialized, or its structure is inconsistent with its parameters
+    /// (e.g., it uses an unsupported field extension or FRI folding factor, or contains a number
+    /// of query values which does not match the number of queries).
+    #[error("the proof is malformed: {0}")]
+    ProofDeserialization(String),
+    /// The parameters of the proof are not in the set of parameters accepted by the verifier.
+    #[error("the proof parameters are not accepted by the verifier")]
+    UnacceptableProofOptions,
+    /// The constraints evaluated over the out-of-domain trace frame do not match the evaluation
+    /// of the constraint composition polynomial at the out-of-domain point.
+    ///
+    /// As the public inputs are bound to the proof via the Fiat-Shamir transcript, this is also
+    /// how a proof which was generated for different public inputs (i.e., a different program,
+    /// stack inputs, stack outputs or execution environment) fails to verify.
+    #[error(
+        "the out-of-domain constraint evaluations are inconsistent; this is also the case when the proof was generated for different public inputs"
+    )]
+    OodConstraintEvaluation,
+    /// The Merkle paths of the trace queries do not open to the trace commitment of the proof.
+    #[error("the Merkle paths of the trace queries do not match the trace commitment")]
+    TraceQueryMerklePath,
+    /// The Merkle paths of the constraint evaluation queries do not open to the constraint
+    /// commitment of the proof.
+    #[error("the Merkle paths of the constraint queries do not match the constraint commitment")]
+    ConstraintQueryMerklePath,
+    /// The proof-of-work nonce does not meet the grinding factor of the proof.
+    #[error("the proof-of-work nonce does not meet the grinding factor of the proof")]
+    ProofOfWork,
+    /// The values of the FRI queries do not open to the commitment to one of the FRI layers.
+    #[error("the FRI queries do not match the FRI layer commitments")]
+    FriLayerCommitment,
+    /// The values of the FRI queries were not folded correctly into the FRI layer with the
+    /// specified index.
+    #[error("the FRI queries are not folded consistently at FRI layer {layer}")]
+    FriLayerFolding { layer: usize },
+    /// The degree of the polynomial at the FRI layer with the specified index is not divisible by
+    /// the FRI folding factor.
+    #[error("the polynomial degree at FRI layer {layer} is not divisible by the folding factor")]
+    FriDegreeTruncation { layer: usize },
+    /// The FRI remainder does not match its commitment, is not consistent with the last FRI layer,
+    /// or is not a polynomial of the expected degree.
+    #[error("the FRI remainder is invalid: {0}")]
+    FriRemainder(String),
+    /// The verifier failed to draw a random value from the public coin.
+    #[error("failed to draw a random value from the public coin")]
+    RandomCoin,
+}
+
+impl From<&VerifierError> for VerificationFailure {
+    fn from(error: &VerifierError) -> Self {
+        match error {
+            VerifierError::InconsistentBaseField
+            | VerifierError::UnsupportedFieldExtension(_)
+            | VerifierError::ProofDeserializationError(_) => {
+                Self::ProofDeserialization(error.to_string())
+            },
+            VerifierError::InsufficientConjecturedSecurity(..)
+            | VerifierError::InsufficientProvenSecurity(..)
+            | VerifierError::UnacceptableProofOptions => Self::UnacceptableProofOptions,
+            VerifierError::InconsistentOodConstraintEvaluations => Self::OodConstraintEvaluation,
+            VerifierError::TraceQueryDoesNotMatchCommitment => Self::TraceQueryMerklePath,
+            VerifierError::ConstraintQueryDoesNotMatchCommitment => Self::ConstraintQueryMerklePath,
+            VerifierError::QuerySeedProofOfWorkVerificationFailed => Self::ProofOfWork,
+            VerifierError::RandomCoinError => Self::RandomCoin,
+            VerifierError::FriVerificationFailed(error) => match error {
+                FriVerifierError::LayerCommitmentMismatch => Self::FriLayerCommitment,
+                FriVerifierError::InvalidLayerFolding(layer) => {
+                    Self::FriLayerFolding { layer: *layer }
+                },
+                FriVerifierError::DegreeTruncation(_, _, layer) => {
+                    Self::FriDegreeTruncation { layer: *layer }
+                },
+                FriVerifierError::RemainderCommitmentMismatch
+                | FriVerifierError::InvalidRemainderFolding
+                | FriVerifierError::RemainderDegreeNotValid
+                | FriVerifierError::RemainderDegreeMismatch(_) => {
+                    Self::FriRemainder(error.to_string())
+                },
+                FriVerifierError::UnsupportedFoldingFactor(_)
+                | FriVerifierError::NumPositionEvaluationMismatch(..) => {
+                    Self::ProofDeserialization(error.to_string())
+                },
+                FriVerifierError::RandomCoinError(_) => Self::RandomCoin,
+            },
+        }
+    }
+}
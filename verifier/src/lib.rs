@@ -5,22 +5,35 @@ extern crate alloc;
 #[cfg(feature = "std")]
 extern crate std;
 
-use alloc::vec;
+use alloc::{boxed::Box, sync::Arc, vec, vec::Vec};
 
 use air::{HashFunction, ProcessorAir, ProvingOptions, PublicInputs};
-use vm_core::crypto::{
-    hash::{Blake3_192, Blake3_256, Rpo256, Rpx256},
-    random::{RpoRandomCoin, RpxRandomCoin, WinterRandomCoin},
+use vm_core::{
+    crypto::{
+        hash::{Blake3_192, Blake3_256, Keccak256, Rpo256, Rpx256},
+        random::{RpoRandomCoin, RpxRandomCoin, WinterRandomCoin},
+    },
+    utils::UnsupportedVersion,
 };
+
+mod allowlist;
+mod failure;
+pub mod recursive;
+
 // EXPORTS
 // ================================================================================================
-pub use vm_core::{Kernel, ProgramInfo, StackInputs, StackOutputs, Word, chiplets::hasher::Digest};
+pub use vm_core::{
+    ExecutionEnvironment, IsaVersion, Kernel, ProgramInfo, StackInputs, StackOutputs, Word,
+    chiplets::hasher::Digest,
+};
 pub use winter_verifier::{AcceptableOptions, VerifierError};
 use winter_verifier::{crypto::MerkleTree, verify as verify_proof};
 pub mod math {
     pub use vm_core::{Felt, FieldElement, StarkField};
 }
-pub use air::ExecutionProof;
+pub use air::{DelegatedClaim, ExecutionProof, ProofEnvelope, TraceRandomizer, claims_commitment};
+pub use allowlist::{VerificationRequest, Verifier};
+pub use failure::VerificationFailure;
 
 // VERIFIER
 // ================================================================================================
@@ -41,64 +54,291 @@ pub use air::ExecutionProof;
 /// order on the stack. This is the reverse of the order of the `stack_inputs` slice.
 ///
 /// The verifier accepts proofs generated using a parameter set defined in [ProvingOptions].
-/// Specifically, parameter sets targeting the following are accepted:
-/// - 96-bit security level, non-recursive context (BLAKE3 hash function).
-/// - 96-bit security level, recursive context (BLAKE3 hash function).
-/// - 128-bit security level, non-recursive context (RPO hash function).
-/// - 128-bit security level, recursive context (RPO hash function).
+/// Specifically, the parameter sets of all [SecurityPreset](air::SecurityPreset)s are accepted:
+/// - 96-bit, 100-bit, and 128-bit conjectured security level, in non-recursive context (BLAKE3
+///   hash function) and recursive context (RPO hash function).
+/// - 96-bit, 100-bit, and 128-bit proven security level, with either of these hash functions.
+/// - 96-bit and 128-bit conjectured security level, in recursive context with the RPX hash
+///   function.
 ///
 /// # Errors
 /// Returns an error if:
 /// - The provided proof does not prove a correct execution of the program.
 /// - The protocol parameters used to generate the proof are not in the set of acceptable
 ///   parameters.
+/// - The program targets a version of the instruction set which is not supported.
+/// - The proof was generated by a previous release of Miden VM, against other constraints and
+///   public inputs (see [ExecutionProof::check_version]).
 #[tracing::instrument("verify_program", skip_all)]
 pub fn verify(
     program_info: ProgramInfo,
     stack_inputs: StackInputs,
     stack_outputs: StackOutputs,
     proof: ExecutionProof,
+) -> Result<u32, VerificationError> {
+    verify_with_environment(
+        program_info,
+        stack_inputs,
+        stack_outputs,
+        ExecutionEnvironment::default(),
+        proof,
+    )
+}
+
+/// Returns the security level of the proof if the specified program was executed correctly against
+/// the specified inputs and outputs in the specified environment.
+///
+/// This is the same as [verify], except that the proof is checked against the specified execution
+/// environment (i.e., the environment the host provided to the program), rather than against the
+/// default one.
+///
+/// # Errors
+/// Returns an error in the same cases as [verify], which includes the case of the proof having
+/// been generated in a different environment.
+#[tracing::instrument("verify_program", skip_all)]
+pub fn verify_with_environment(
+    program_info: ProgramInfo,
+    stack_inputs: StackInputs,
+    stack_outputs: StackOutputs,
+    environment: ExecutionEnvironment,
+    proof: ExecutionProof,
+) -> Result<u32, VerificationError> {
+    let pub_inputs =
+        PublicInputs::new(program_info, stack_inputs, stack_outputs).with_environment(environment);
+    verify_public_inputs(pub_inputs, proof)
+}
+
+/// Returns the security level of the proof if the specified program was executed correctly against
+/// the specified inputs and outputs in the specified environment, with the random elements used to
+/// build the auxiliary trace derived by the specified `randomizer`.
+///
+/// This is the same as [verify_with_environment], except that the proof must have been generated
+/// with the same randomizer (e.g., via `miden_prover::prove_with_randomizer`). See
+/// [TraceRandomizer] for details.
+///
+/// # Errors
+/// Returns an error in the same cases as [verify_with_environment], which includes the case of the
+/// proof having been generated with a different randomizer.
+#[tracing::instrument("verify_program", skip_all)]
+pub fn verify_with_randomizer(
+    program_info: ProgramInfo,
+    stack_inputs: StackInputs,
+    stack_outputs: StackOutputs,
+    environment: ExecutionEnvironment,
+    proof: ExecutionProof,
+    randomizer: Arc<dyn TraceRandomizer>,
+) -> Result<u32, VerificationError> {
+    let pub_inputs = PublicInputs::new(program_info, stack_inputs, stack_outputs)
+        .with_environment(environment)
+        .with_trace_randomizer(randomizer);
+    verify_public_inputs(pub_inputs, proof)
+}
+
+/// Returns the security level of each of the specified proofs, or the error the proof failed to
+/// verify with, in the order of the requests.
+///
+/// Each proof is verified as by [verify_with_environment], against the inputs, outputs and
+/// environment of its [VerificationRequest]. A proof failing to verify does not prevent the other
+/// proofs from being verified, and the error of each failed proof identifies the proven program.
+///
+/// The sets of acceptable proof parameters are built once for the whole batch rather than for
+/// every proof. With the `concurrent` feature, the proofs are verified in parallel on the rayon
+/// thread pool, which is much faster than calling [verify] for each proof when the batch is large.
+///
+/// To also check the proven programs against an allowlist, use [Verifier::verify_batch].
+#[tracing::instrument("verify_batch", skip_all)]
+pub fn verify_batch(
+    requests: impl IntoIterator<Item = VerificationRequest>,
+) -> Vec<Result<u32, VerificationError>> {
+    let options = AcceptableOptionSets::new();
+    let requests = requests.into_iter().collect::<Vec<_>>();
+
+    #[cfg(feature = "concurrent")]
+    {
+        use winter_utils::iterators::*;
+
+        requests
+            .into_par_iter()
+            .map(|request| {
+                let (pub_inputs, proof) = request.into_parts();
+                verify_public_inputs_with(pub_inputs, proof, &options)
+            })
+            .collect()
+    }
+
+    #[cfg(not(feature = "concurrent"))]
+    requests
+        .into_iter()
+        .map(|request| {
+            let (pub_inputs, proof) = request.into_parts();
+            verify_public_inputs_with(pub_inputs, proof, &options)
+        })
+        .collect()
+}
+
+/// Verifies the proof against the specified public inputs, and returns its security level.
+fn verify_public_inputs(
+    pub_inputs: PublicInputs,
+    proof: ExecutionProof,
+) -> Result<u32, VerificationError> {
+    verify_public_inputs_with(pub_inputs, proof, &AcceptableOptionSets::new())
+}
+
+/// Verifies the proof against the specified public inputs, accepting the proof parameters in the
+/// specified sets, and returns its security level.
+fn verify_public_inputs_with(
+    pub_inputs: PublicInputs,
+    proof: ExecutionProof,
+    options: &AcceptableOptionSets,
 ) -> Result<u32, VerificationError> {
     // get security level of the proof
     let security_level = proof.security_level();
+    let program_info = pub_inputs.program_info();
     let program_hash = *program_info.program_hash();
 
-    // build public inputs and try to verify the proof
-    let pub_inputs = PublicInputs::new(program_info, stack_inputs, stack_outputs);
+    // make sure the proof was generated against the constraints and public inputs of this release
+    proof
+        .check_version()
+        .map_err(|source| VerificationError::UnsupportedProofVersion {
+            program: program_hash,
+            source,
+        })?;
+
+    // make sure the program targets a supported version of the instruction set
+    if !IsaVersion::CURRENT.supports(program_info.isa_version()) {
+        return Err(VerificationError::UnsupportedIsaVersion {
+            program: program_info.isa_version(),
+            supported: IsaVersion::CURRENT,
+        });
+    }
+
+    // try to verify the proof
     let (hash_fn, proof) = proof.into_parts();
     match hash_fn {
-        HashFunction::Blake3_192 => {
-            let opts = AcceptableOptions::OptionSet(vec![ProvingOptions::REGULAR_96_BITS]);
-            verify_proof::<ProcessorAir, Blake3_192, WinterRandomCoin<_>, MerkleTree<_>>(
-                proof, pub_inputs, &opts,
-            )
-        },
-        HashFunction::Blake3_256 => {
-            let opts = AcceptableOptions::OptionSet(vec![ProvingOptions::REGULAR_128_BITS]);
-            verify_proof::<ProcessorAir, Blake3_256, WinterRandomCoin<_>, MerkleTree<_>>(
-                proof, pub_inputs, &opts,
-            )
-        },
-        HashFunction::Rpo256 => {
-            let opts = AcceptableOptions::OptionSet(vec![
-                ProvingOptions::RECURSIVE_96_BITS,
-                ProvingOptions::RECURSIVE_128_BITS,
-            ]);
-            verify_proof::<ProcessorAir, Rpo256, RpoRandomCoin, MerkleTree<_>>(
-                proof, pub_inputs, &opts,
-            )
-        },
-        HashFunction::Rpx256 => {
-            let opts = AcceptableOptions::OptionSet(vec![
-                ProvingOptions::RECURSIVE_96_BITS,
-                ProvingOptions::RECURSIVE_128_BITS,
-            ]);
-            verify_proof::<ProcessorAir, Rpx256, RpxRandomCoin, MerkleTree<_>>(
-                proof, pub_inputs, &opts,
-            )
-        },
+        HashFunction::Blake3_192 => verify_proof::<
+            ProcessorAir,
+            Blake3_192,
+            WinterRandomCoin<_>,
+            MerkleTree<_>,
+        >(proof, pub_inputs, &options.blake3_192),
+        HashFunction::Blake3_256 => verify_proof::<
+            ProcessorAir,
+            Blake3_256,
+            WinterRandomCoin<_>,
+            MerkleTree<_>,
+        >(proof, pub_inputs, &options.blake3_256),
+        HashFunction::Rpo256 => verify_proof::<ProcessorAir, Rpo256, RpoRandomCoin, MerkleTree<_>>(
+            proof,
+            pub_inputs,
+            &options.rpo256,
+        ),
+        HashFunction::Rpx256 => verify_proof::<ProcessorAir, Rpx256, RpxRandomCoin, MerkleTree<_>>(
+            proof,
+            pub_inputs,
+            &options.rpx256,
+        ),
+        HashFunction::Keccak256 => verify_proof::<
+            ProcessorAir,
+            Keccak256,
+            WinterRandomCoin<_>,
+            MerkleTree<_>,
+        >(proof, pub_inputs, &options.keccak256),
+    }
+    .map_err(|source| VerificationError::ProgramVerificationError {
+        program: program_hash,
+        failure: VerificationFailure::from(&source),
+        source,
+    })?;
+
+    Ok(security_level)
+}
+
+/// The sets of proof parameters accepted by the verifier, for each hash function.
+struct AcceptableOptionSets {
+    blake3_192: AcceptableOptions,
+    blake3_256: AcceptableOptions,
+    rpo256: AcceptableOptions,
+    rpx256: AcceptableOptions,
+    keccak256: AcceptableOptions,
+}
+
+impl AcceptableOptionSets {
+    fn new() -> Self {
+        Self {
+            blake3_192: Self::all_presets(),
+            blake3_256: Self::all_presets(),
+            rpo256: Self::all_presets(),
+            rpx256: Self::all_presets(),
+            keccak256: Self::all_presets(),
+        }
+    }
+
+    /// Returns the parameters of all named presets of [ProvingOptions].
+    ///
+    /// Any preset can be combined with any hash function via [ProvingOptions::with_hash_fn], and
+    /// the security level returned by the verifier is already capped by the collision resistance
+    /// of the hash function the proof was generated with, so there is no need to tie the presets
+    /// to particular hash functions.
+    fn all_presets() -> AcceptableOptions {
+        AcceptableOptions::OptionSet(vec![
+            ProvingOptions::REGULAR_96_BITS,
+            ProvingOptions::REGULAR_100_BITS,
+            ProvingOptions::REGULAR_128_BITS,
+            ProvingOptions::RECURSIVE_96_BITS,
+            ProvingOptions::RECURSIVE_100_BITS,
+            ProvingOptions::RECURSIVE_128_BITS,
+            ProvingOptions::PROVEN_96_BITS,
+            ProvingOptions::PROVEN_100_BITS,
+            ProvingOptions::PROVEN_128_BITS,
+        ])
+    }
+}
+
+/// Returns the security level of the proofs in the envelope if the specified program was executed
+/// correctly against the specified inputs and outputs, and all claims consumed by the program
+/// hold.
+///
+/// Specifically, in addition to verifying the proof of the program as in [verify], this verifies:
+/// - The word at the top of `stack_outputs` is the commitment to the claims in the envelope (see
+///   [claims_commitment]), i.e., the program consumed exactly these claims, in this order.
+/// - Each claim in the envelope is proven by its accompanying proof.
+///
+/// The returned security level is the minimum of the security levels of all proofs in the
+/// envelope.
+///
+/// # Errors
+/// Returns an error if:
+/// - The commitment to the claims doesn't match the top word of `stack_outputs`.
+/// - Any of the proofs in the envelope fails to verify, as described in [verify].
+#[tracing::instrument("verify_envelope", skip_all)]
+pub fn verify_envelope(
+    program_info: ProgramInfo,
+    stack_inputs: StackInputs,
+    stack_outputs: StackOutputs,
+    envelope: ProofEnvelope,
+) -> Result<u32, VerificationError> {
+    // make sure the program committed to the claims in the envelope
+    let expected = envelope.claims_commitment();
+    let actual = stack_outputs.get_stack_word(0).expect("stack outputs contain 16 elements");
+    if expected != actual {
+        return Err(VerificationError::ClaimsCommitmentMismatch {
+            expected: expected.into(),
+            actual: actual.into(),
+        });
+    }
+
+    let (proof, delegated_proofs) = envelope.into_parts();
+    let mut security_level = verify(program_info, stack_inputs, stack_outputs, proof)?;
+    for (index, (claim, proof)) in delegated_proofs.into_iter().enumerate() {
+        let claim_security_level = verify(
+            claim.program_info().clone(),
+            claim.stack_inputs().clone(),
+            claim.stack_outputs().clone(),
+            proof,
+        )
+        .map_err(|source| VerificationError::DelegatedClaimNotProven(index, Box::new(source)))?;
+        security_level = security_level.min(claim_security_level);
     }
-    .map_err(|source| VerificationError::ProgramVerificationError(program_hash, source))?;
 
     Ok(security_level)
 }
@@ -109,10 +349,50 @@ pub fn verify(
 /// TODO: add docs
 #[derive(Debug, thiserror::Error)]
 pub enum VerificationError {
-    #[error("failed to verify proof for program with hash {0}")]
-    ProgramVerificationError(Digest, #[source] VerifierError),
+    #[error("failed to verify proof for program with hash {program}: {failure}")]
+    ProgramVerificationError {
+        program: Digest,
+        failure: VerificationFailure,
+        #[source]
+        source: VerifierError,
+    },
     #[error("the input {0} is not a valid field element")]
     InputNotFieldElement(u64),
+    #[error(
+        "the program committed to claims {actual}, but the claims in the proof envelope have commitment {expected}"
+    )]
+    ClaimsCommitmentMismatch { expected: Digest, actual: Digest },
+    #[error("failed to verify delegated claim {0}")]
+    DelegatedClaimNotProven(usize, #[source] Box<VerificationError>),
     #[error("the output {0} is not a valid field element")]
     OutputNotFieldElement(u64),
+    #[error(
+        "program targets version {program} of the instruction set, but the verifier supports version {supported}"
+    )]
+    UnsupportedIsaVersion {
+        program: IsaVersion,
+        supported: IsaVersion,
+    },
+    #[error(
+        "proof for program with hash {program} was generated by a previous release of Miden VM, against other constraints and public inputs, and cannot be verified"
+    )]
+    UnsupportedProofVersion {
+        program: Digest,
+        #[source]
+        source: UnsupportedVersion,
+    },
+    #[error("program with hash {0} is not in the set of allowed programs")]
+    ProgramNotAllowed(Digest),
+    #[error(
+        "program with hash {program} has kernel procedure {procedure}, which is not in the set of allowed kernel procedures"
+    )]
+    KernelProcedureNotAllowed { program: Digest, procedure: Digest },
+    #[error(
+        "proof for program with hash {program} has security level {security_level}, but at least {min_security_level} is required"
+    )]
+    InsufficientSecurityLevel {
+        program: Digest,
+        security_level: u32,
+        min_security_level: u32,
+    },
 }
@@ -1,17 +1,23 @@
-use alloc::vec::Vec;
-
-use miden_air::ProcessorAir;
-use test_utils::{
-    Felt, MerkleTreeVC, VerifierError,
-    crypto::{BatchMerkleProof, PartialMerkleTree, Rpo256, RpoDigest},
-    group_slice_elements,
-    math::{FieldElement, QuadExtension, StarkField},
+use alloc::{borrow::ToOwned, format, string::ToString, vec, vec::Vec};
+
+use air::ProcessorAir;
+use vm_core::{
+    Felt, FieldElement, QuadExtension, StarkField,
+    crypto::{
+        hash::{Rpo256, RpoDigest},
+        merkle::PartialMerkleTree,
+    },
+    utils::group_slice_elements,
 };
 use winter_air::{
     Air,
     proof::{Proof, Queries, Table, TraceOodFrame},
 };
 use winter_fri::{VerifierChannel as FriVerifierChannel, folding::fold_positions};
+use winter_verifier::{
+    VerifierError,
+    crypto::{BatchMerkleProof, MerkleTree as MerkleTreeVC},
+};
 
 pub type QuadExt = QuadExtension<Felt>;
 
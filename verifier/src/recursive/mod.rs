@@ -1,21 +1,44 @@
-use alloc::vec::Vec;
-
-use miden_air::ProcessorAir;
-use processor::crypto::RpoRandomCoin;
-use test_utils::{
-    VerifierError,
-    crypto::{MerkleStore, RandomCoin, Rpo256, RpoDigest},
-    math::{FieldElement, QuadExtension, ToElements},
+//! Generation of the inputs of the recursive STARK verifier of the standard library, i.e., of the
+//! `std::crypto::stark::verifier::verify` procedure, which verifies a proof of execution of a
+//! program inside Miden VM.
+//!
+//! The proof is laid out as a [VerifierData]: the parameters of the proof are passed via the
+//! operand stack, while the proof itself is passed via the advice provider. The verifier makes
+//! assumptions on the proof which are documented in `stdlib/asm/crypto/stark/verifier.masm`; in
+//! particular, the proof must have been generated using RPO-256 as the hash function, a quadratic
+//! extension field, a blowup factor of 8, and a folding factor of 4.
+
+use alloc::{borrow::ToOwned, vec, vec::Vec};
+
+use air::{ExecutionProof, ProcessorAir, PublicInputs};
+use vm_core::{
+    Felt, FieldElement, ProgramInfo, QuadExtension, StackInputs, StackOutputs, ToElements,
+    crypto::{
+        hash::{Rpo256, RpoDigest},
+        merkle::MerkleStore,
+        random::{RandomCoin, RpoRandomCoin},
+    },
 };
-use vm_core::Felt;
 use winter_air::{Air, proof::Proof};
 use winter_fri::VerifierChannel as FriVerifierChannel;
+use winter_verifier::VerifierError;
 
 mod channel;
 use channel::VerifierChannel;
 
 pub type QuadExt = QuadExtension<Felt>;
 
+/// The data needed to verify a proof inside Miden VM.
+///
+/// The initial stack holds the inputs of the `verify` procedure in the order expected by
+/// `StackInputs`, and the advice stack is meant to be consumed starting from its first element.
+/// The Merkle store and the advice map hold the authentication paths and the queried values of the
+/// trace, constraint and FRI commitments, and must be added to the advice provider as well.
+///
+/// The data of several proofs can be combined to verify them in a single program: their advice
+/// stacks are concatenated in the order in which the proofs are verified, while their Merkle stores
+/// and advice maps are merged. As the verifier does not preserve the operand stack, the initial
+/// stack of each proof can then be provided via the advice stack, ahead of its advice stack.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct VerifierData {
     pub initial_stack: Vec<u64>,
@@ -24,6 +47,24 @@ pub struct VerifierData {
     pub advice_map: Vec<(RpoDigest, Vec<Felt>)>,
 }
 
+/// Returns the data needed to verify the specified proof of execution of a program inside
+/// Miden VM.
+///
+/// The proof must have been generated using RPO-256 as the hash function, and with the proving
+/// options expected by the recursive verifier.
+pub fn generate_verifier_data(
+    proof: ExecutionProof,
+    program_info: ProgramInfo,
+    stack_inputs: StackInputs,
+    stack_outputs: StackOutputs,
+) -> Result<VerifierData, VerifierError> {
+    let pub_inputs = PublicInputs::new(program_info, stack_inputs, stack_outputs);
+    let (_, proof) = proof.into_parts();
+    generate_advice_inputs(proof, pub_inputs)
+}
+
+/// Returns the data needed to verify the specified proof against the specified public inputs
+/// inside Miden VM.
 pub fn generate_advice_inputs(
     proof: Proof,
     pub_inputs: <ProcessorAir as Air>::PublicInputs,
@@ -43,8 +84,11 @@ pub fn generate_advice_inputs(
     let mut public_coin_seed = proof.context.to_elements();
     public_coin_seed.append(&mut pub_inputs.to_elements());
 
-    // add the public inputs, which is nothing but the input and output stacks to the VM, to the
+    // add the number of kernel procedures, followed by the public inputs, which are the program
+    // and kernel procedure digests, the input and output stacks of the VM, the commitment to the
+    // execution environment, the ISA version word and the commitment to the initial memory, to the
     // advice tape
+    advice_stack.push(pub_inputs.program_info().kernel_procedures().len() as u64);
     let pub_inputs_int: Vec<u64> = pub_inputs.to_elements().iter().map(|a| a.as_int()).collect();
     advice_stack.extend_from_slice(&pub_inputs_int[..]);
 
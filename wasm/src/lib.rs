@@ -0,0 +1,340 @@
+#![no_std]
+
+extern crate alloc;
+
+use alloc::{
+    string::{String, ToString},
+    sync::Arc,
+    vec::Vec,
+};
+
+use assembly::{
+    Assembler, DefaultSourceManager,
+    diagnostics::{Report, reporting::PrintDiagnostic},
+};
+use processor::{
+    AdviceInputs, Breakpoint, DefaultHost, ExecutionError, ExecutionOptions, InputError,
+    MemAdviceProvider, Program as VmProgram, StackInputs, VmState, VmStateIterator,
+    execute_without_trace,
+};
+use stdlib::StdLibrary;
+use wasm_bindgen::prelude::*;
+
+// ASSEMBLER
+// ================================================================================================
+
+/// A program assembled by [assemble].
+///
+/// Programs are assembled in debug mode, so that they can be stepped through by a [Debugger]
+/// with the source instructions of each operation. Debug information does not affect the hash of
+/// a program.
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+pub struct Program {
+    program: VmProgram,
+}
+
+#[wasm_bindgen]
+impl Program {
+    /// Returns the hash of this program, hex-encoded with the `0x` prefix.
+    pub fn hash(&self) -> String {
+        self.program.hash().to_string()
+    }
+}
+
+/// Assembles the program with the specified source, linking it against the standard library.
+///
+/// # Errors
+/// Returns an error with the rendered diagnostics of the assembler if the program fails to
+/// assemble.
+#[wasm_bindgen]
+pub fn assemble(source: &str) -> Result<Program, Error> {
+    let mut assembler =
+        Assembler::new(Arc::new(DefaultSourceManager::default())).with_debug_mode(true);
+    assembler.add_library(StdLibrary::default()).map_err(Error::assembly)?;
+    let program = assembler.assemble_program(source).map_err(Error::assembly)?;
+    Ok(Program { program })
+}
+
+// PROCESSOR
+// ================================================================================================
+
+/// Executes the specified program, and returns the values remaining on the stack, starting with
+/// the value at the top of the stack.
+///
+/// The stack inputs are expected to be ordered as if they would be pushed onto the stack one by
+/// one, and the values of the advice stack as if they would be popped off the advice stack one by
+/// one.
+///
+/// # Errors
+/// Returns an error if the inputs are not valid field elements, or if the program fails to
+/// execute.
+#[wasm_bindgen]
+pub fn run(
+    program: &Program,
+    stack_inputs: Vec<u64>,
+    advice_stack: Option<Vec<u64>>,
+) -> Result<Vec<u64>, Error> {
+    let (stack_inputs, mut host) = prepare_execution(stack_inputs, advice_stack)?;
+    let stack_outputs = execute_without_trace(
+        &program.program,
+        stack_inputs,
+        &mut host,
+        ExecutionOptions::default(),
+    )?;
+    Ok(stack_outputs.iter().map(|value| value.as_int()).collect())
+}
+
+// DEBUGGER
+// ================================================================================================
+
+/// Steps through the execution of a program, one clock cycle at a time.
+///
+/// The program is executed in full when the debugger is created, and the debugger then replays the
+/// state of the VM at each clock cycle, in either direction. [Debugger::resume] pauses at the
+/// clock cycles registered via [Debugger::add_cycle_breakpoint].
+#[wasm_bindgen]
+pub struct Debugger {
+    states: VmStateIterator,
+    state: Option<VmState>,
+    error: Option<String>,
+}
+
+#[wasm_bindgen]
+impl Debugger {
+    /// Returns a new debugger stepping through the execution of the specified program against the
+    /// specified inputs, which are interpreted as by [run].
+    ///
+    /// The debugger is positioned before the first clock cycle of the execution.
+    ///
+    /// # Errors
+    /// Returns an error if the inputs are not valid field elements.
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        program: &Program,
+        stack_inputs: Vec<u64>,
+        advice_stack: Option<Vec<u64>>,
+    ) -> Result<Debugger, Error> {
+        let (stack_inputs, mut host) = prepare_execution(stack_inputs, advice_stack)?;
+        let states = processor::execute_iter(&program.program, stack_inputs, &mut host);
+        Ok(Self { states, state: None, error: None })
+    }
+
+    /// Registers a breakpoint at the specified clock cycle.
+    #[wasm_bindgen(js_name = addCycleBreakpoint)]
+    pub fn add_cycle_breakpoint(&mut self, clk: u32) {
+        self.states.add_breakpoint(Breakpoint::Cycle(clk.into()));
+    }
+
+    /// Advances the execution by one clock cycle, and returns false if the execution has already
+    /// ended.
+    ///
+    /// # Errors
+    /// Returns an error if the program failed at this clock cycle.
+    pub fn step(&mut self) -> Result<bool, Error> {
+        // when the iterator changes direction, it first returns the current state again
+        let current = self.state.as_ref().map(|state| state.clk.as_u32());
+        loop {
+            match self.states.next() {
+                Some(Ok(state)) if current.is_some_and(|clk| state.clk.as_u32() <= clk) => {},
+                Some(Ok(state)) => {
+                    self.state = Some(state);
+                    return Ok(true);
+                },
+                Some(Err(err)) => return Err(self.fail(err)),
+                None => return Ok(false),
+            }
+        }
+    }
+
+    /// Moves the execution back by one clock cycle, and returns false if the debugger is already
+    /// at the first clock cycle.
+    pub fn back(&mut self) -> bool {
+        // as for stepping forward, the current state may be returned again first
+        let clk = self.clk();
+        while let Some(state) = self.states.back() {
+            if state.clk.as_u32() < clk {
+                self.state = Some(state);
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Advances the execution until a breakpoint is hit, and returns true if the execution paused
+    /// at a breakpoint, or false if it ran to the end.
+    ///
+    /// # Errors
+    /// Returns an error if the program failed before a breakpoint was hit.
+    pub fn resume(&mut self) -> Result<bool, Error> {
+        match self.states.resume() {
+            Some(Ok((state, breakpoint))) => {
+                self.state = Some(state);
+                Ok(breakpoint.is_some())
+            },
+            Some(Err(err)) => Err(self.fail(err)),
+            None => Ok(false),
+        }
+    }
+
+    /// Returns the current clock cycle.
+    pub fn clk(&self) -> u32 {
+        self.state.as_ref().map_or(0, |state| state.clk.as_u32())
+    }
+
+    /// Returns the values on the stack at the current clock cycle, starting with the value at the
+    /// top of the stack.
+    pub fn stack(&self) -> Vec<u64> {
+        self.state
+            .as_ref()
+            .map(|state| state.stack.iter().map(|value| value.as_int()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Returns the memory of the current context at the current clock cycle, as a flat array of
+    /// address and value pairs.
+    pub fn memory(&self) -> Vec<u64> {
+        self.state
+            .as_ref()
+            .map(|state| {
+                state.memory.iter().flat_map(|&(addr, value)| [addr, value.as_int()]).collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns the VM operation executed at the current clock cycle, if any.
+    pub fn operation(&self) -> Option<String> {
+        self.state.as_ref()?.op.map(|op| op.to_string())
+    }
+
+    /// Returns the assembly instruction the current VM operation was assembled from, if any.
+    pub fn instruction(&self) -> Option<String> {
+        Some(self.state.as_ref()?.asmop.as_ref()?.op().to_string())
+    }
+
+    /// Returns the error the program failed with, if execution reached the failing clock cycle.
+    pub fn error(&self) -> Option<String> {
+        self.error.clone()
+    }
+}
+
+impl Debugger {
+    /// Records the error the execution failed with, and returns it.
+    fn fail(&mut self, err: ExecutionError) -> Error {
+        let err = Error::from(err);
+        self.error = Some(err.to_string());
+        err
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the stack inputs and the host to execute a program with, given the inputs passed to
+/// [run] or [Debugger::new].
+fn prepare_execution(
+    stack_inputs: Vec<u64>,
+    advice_stack: Option<Vec<u64>>,
+) -> Result<(StackInputs, DefaultHost<MemAdviceProvider>), Error> {
+    let stack_inputs = StackInputs::try_from_ints(stack_inputs)?;
+    let advice_inputs =
+        AdviceInputs::default().with_stack_values(advice_stack.unwrap_or_default())?;
+
+    let mut host = DefaultHost::new(MemAdviceProvider::from(advice_inputs));
+    host.load_mast_forest(StdLibrary::default().mast_forest().clone())?;
+    Ok((stack_inputs, host))
+}
+
+// ERRORS
+// ================================================================================================
+
+/// An error returned to JavaScript by the bindings, where it is thrown as an `Error` with the
+/// message of this error.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// The program failed to assemble; this contains the diagnostics of the assembler, rendered as
+    /// text.
+    #[error("{0}")]
+    Assembly(String),
+    #[error("invalid inputs: {0}")]
+    InvalidInputs(#[from] InputError),
+    #[error("{0}")]
+    Execution(#[from] ExecutionError),
+}
+
+impl Error {
+    fn assembly(report: Report) -> Self {
+        Self::Assembly(PrintDiagnostic::narrated(report).to_string())
+    }
+}
+
+impl From<Error> for JsValue {
+    fn from(err: Error) -> Self {
+        JsError::new(&err.to_string()).into()
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use super::*;
+
+    const SOURCE: &str = "
+        use.std::math::u64
+
+        begin
+            push.1 add
+            push.0.3
+            exec.u64::wrapping_add
+        end";
+
+    #[test]
+    fn assemble_and_run() {
+        let program = assemble(SOURCE).unwrap();
+        assert!(program.hash().starts_with("0x"));
+
+        let outputs = run(&program, vec![0, 5], None).unwrap();
+        assert_eq!(outputs[..2], [9, 0]);
+
+        // inputs are validated, and execution errors are reported
+        assert!(matches!(run(&program, vec![u64::MAX], None), Err(Error::InvalidInputs(_))));
+        let failing = assemble("begin adv_push.1 add end").unwrap();
+        assert!(matches!(run(&failing, vec![], None), Err(Error::Execution(_))));
+        assert_eq!(run(&failing, vec![], Some(vec![9])).unwrap()[0], 9);
+
+        let err = assemble("begin push.1 ad end").unwrap_err();
+        assert!(matches!(err, Error::Assembly(_)));
+    }
+
+    #[test]
+    fn step_through_execution() {
+        let program = assemble(SOURCE).unwrap();
+        let mut debugger = Debugger::new(&program, vec![], None).unwrap();
+
+        // execution pauses at the breakpoint
+        debugger.add_cycle_breakpoint(3);
+        assert!(debugger.resume().unwrap());
+        let clk = debugger.clk();
+        assert_eq!(clk, 3);
+        assert_eq!(debugger.operation().as_deref(), Some("incr"));
+        assert_eq!(debugger.stack()[0], 1);
+
+        assert!(debugger.step().unwrap());
+        assert_eq!(debugger.clk(), clk + 1);
+        assert!(debugger.back());
+        assert_eq!(debugger.clk(), clk);
+        assert_eq!(debugger.stack()[0], 1);
+        assert!(debugger.step().unwrap());
+        assert_eq!(debugger.clk(), clk + 1);
+
+        // without further breakpoints, execution runs to the end
+        assert!(!debugger.resume().unwrap());
+        assert_eq!(debugger.stack()[..2], [4, 0]);
+        assert!(!debugger.step().unwrap());
+        assert!(debugger.error().is_none());
+    }
+}